@@ -69,6 +69,42 @@ pub fn get_open_api_versions_mapping() -> HashMap<OpenAPIFileName, semver::Versi
     )
 }
 
+/// Generate the `get_open_api_specs_mapping` function embedding the Open API files
+/// in the given folders.
+pub fn generate_open_api_specs_mapping(paths: &[&Path]) -> String {
+    let open_api_spec_files = list_all_open_api_spec_files(paths);
+    // Use a BTreeMap to guarantee the deterministic code generation below
+    let open_api_specs: BTreeMap<OpenAPIFileName, PathBuf> = open_api_spec_files
+        .into_iter()
+        .map(|path| {
+            (
+                path.file_name().unwrap().to_string_lossy().to_string(),
+                fs::canonicalize(&path).unwrap(),
+            )
+        })
+        .collect();
+
+    let mut open_api_specs_hashmap = String::new();
+    for (filename, path) in open_api_specs {
+        open_api_specs_hashmap.push_str(&format!(
+            r#"("{filename}".to_string(), include_str!({:?})), "#,
+            path.to_string_lossy()
+        ));
+    }
+
+    format!(
+        r#"
+/// Build Open API specifications mapping, the specifications are embedded in yaml format
+pub fn get_open_api_specs_mapping() -> HashMap<OpenAPIFileName, &'static str> {{
+    HashMap::from([
+        {}
+    ])
+}}
+        "#,
+        open_api_specs_hashmap
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,4 +202,21 @@ info:
 
         assert_open_api_content_contains(expected, &generated_code);
     }
+
+    #[test]
+    fn generate_code_embedding_a_simple_open_api_file() {
+        let dir = get_temp_dir("generate_code_embedding_a_simple_open_api_file");
+        write_minimal_open_api_file("1.0.0", &dir.join("openapi.yaml"));
+
+        let expected = format!(
+            r#"("openapi.yaml".to_string(), include_str!({:?}))"#,
+            fs::canonicalize(dir.join("openapi.yaml"))
+                .unwrap()
+                .to_string_lossy()
+        );
+        let generated_code = generate_open_api_specs_mapping(&[&dir]);
+
+        assert_open_api_content_contains(&expected, &generated_code);
+        assert!(generated_code.contains("-> HashMap<OpenAPIFileName, &'static str>"));
+    }
 }
@@ -124,17 +124,8 @@ impl<'conn> DatabaseVersionProvider<'conn> {
         application_type: &ApplicationNodeType,
     ) -> StdResult<()> {
         let connection = self.get_connection();
-        let sql = "select exists(select name from sqlite_master where type='table' and name='db_version') as table_exists";
-        let table_exists = connection
-            .prepare(sql)?
-            .iter()
-            .next()
-            .unwrap()
-            .unwrap()
-            .read::<i64, _>(0)
-            == 1;
 
-        if !table_exists {
+        if !self.table_exists()? {
             let sql = format!("
 create table db_version (application_type text not null primary key, version integer not null, updated_at text not null);
 insert into db_version (application_type, version, updated_at) values ('{application_type}', 0, '{}');
@@ -145,6 +136,22 @@ insert into db_version (application_type, version, updated_at) values ('{applica
         Ok(())
     }
 
+    /// Check if the `db_version` table exists, it's not the case before the first migration.
+    pub fn table_exists(&self) -> StdResult<bool> {
+        let sql = "select exists(select name from sqlite_master where type='table' and name='db_version') as table_exists";
+        let table_exists = self
+            .get_connection()
+            .prepare(sql)?
+            .iter()
+            .next()
+            .unwrap()
+            .unwrap()
+            .read::<i64, _>(0)
+            == 1;
+
+        Ok(table_exists)
+    }
+
     /// Read the application version from the database.
     pub fn get_application_version(
         &self,
@@ -235,7 +242,7 @@ returning {projection}
 
 #[cfg(test)]
 mod tests {
-    use sqlite::Connection;
+    use crate::sqlite::ConnectionBuilder;
 
     use super::*;
 
@@ -253,7 +260,7 @@ mod tests {
 
     #[test]
     fn test_definition() {
-        let connection = Connection::open_thread_safe(":memory:").unwrap();
+        let connection = ConnectionBuilder::open_memory().build().unwrap();
         let provider = DatabaseVersionProvider::new(&connection);
 
         assert_eq!(
@@ -268,7 +275,7 @@ where true
 
     #[test]
     fn test_updated_entity() {
-        let connection = Connection::open_thread_safe(":memory:").unwrap();
+        let connection = ConnectionBuilder::open_memory().build().unwrap();
         let provider = DatabaseVersionUpdater::new(&connection);
 
         assert_eq!(
@@ -10,4 +10,4 @@ pub type DbVersion = i64;
 
 pub use db_version::*;
 pub use signed_entity_hydrator::SignedEntityTypeHydrator;
-pub use version_checker::{DatabaseVersionChecker, SqlMigration};
+pub use version_checker::{DatabaseVersionChecker, SqlMigration, SqlMigrationStatus};
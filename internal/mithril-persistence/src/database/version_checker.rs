@@ -9,7 +9,7 @@ use super::{
     DbVersion,
 };
 
-use crate::sqlite::SqliteConnection;
+use crate::sqlite::{SqliteConnection, SqliteTransaction};
 
 /// Struct to perform application version check in the database.
 pub struct DatabaseVersionChecker<'conn> {
@@ -99,6 +99,132 @@ impl<'conn> DatabaseVersionChecker<'conn> {
         Ok(())
     }
 
+    /// Read the current database version without altering the database.
+    ///
+    /// Return `0` if no migration was ever applied.
+    pub fn get_current_version(&self) -> StdResult<DbVersion> {
+        let provider = DatabaseVersionProvider::new(self.connection);
+        if !provider.table_exists()? {
+            return Ok(0);
+        }
+
+        let version = provider
+            .get_application_version(&self.application_type)?
+            .map(|db_version| db_version.version)
+            .unwrap_or(0);
+
+        Ok(version)
+    }
+
+    /// List the registered migrations with their status in the database.
+    pub fn status(&self) -> StdResult<Vec<SqlMigrationStatus>> {
+        let current_version = self.get_current_version()?;
+        let status = self
+            .migrations
+            .iter()
+            .map(|migration| SqlMigrationStatus {
+                version: migration.version,
+                applied: migration.version <= current_version,
+                reversible: migration.is_reversible(),
+            })
+            .collect();
+
+        Ok(status)
+    }
+
+    /// List the migrations that [apply][Self::apply] would play, without altering the database.
+    pub fn list_pending_migrations(&self) -> StdResult<Vec<&SqlMigration>> {
+        let current_version = self.get_current_version()?;
+
+        Ok(self
+            .migrations
+            .iter()
+            .filter(|m| m.version > current_version)
+            .collect())
+    }
+
+    /// List, in the order they would be played, the migrations that [revert_to][Self::revert_to]
+    /// would revert, without altering the database.
+    pub fn list_migrations_to_revert(
+        &self,
+        target_version: DbVersion,
+    ) -> StdResult<Vec<&SqlMigration>> {
+        let current_version = self.get_current_version()?;
+        if target_version > current_version {
+            return Err(anyhow!(
+                "Can not revert database to version '{target_version}' as it is higher than its current version '{current_version}'."
+            ));
+        }
+
+        let migrations: Vec<&SqlMigration> = self
+            .migrations
+            .iter()
+            .rev()
+            .filter(|m| m.version > target_version && m.version <= current_version)
+            .collect();
+
+        if let Some(migration) = migrations.iter().find(|m| !m.is_reversible()) {
+            return Err(anyhow!(
+                "Can not revert database to version '{target_version}': migration '{}' has no down migration.",
+                migration.version
+            ));
+        }
+
+        Ok(migrations)
+    }
+
+    /// Revert the migrations applied after the given version using their down migrations.
+    ///
+    /// Nothing is reverted if one of the migrations to revert has no down migration, the
+    /// migrations are reverted in a single transaction so a failing down migration leaves the
+    /// database untouched.
+    pub fn revert_to(&self, target_version: DbVersion) -> StdResult<()> {
+        let migrations = self.list_migrations_to_revert(target_version)?;
+        let updater = DatabaseVersionUpdater::new(self.connection);
+        let transaction = SqliteTransaction::begin(self.connection)?;
+
+        for migration in migrations {
+            debug!(
+                &self.logger,
+                "Reverting database migration '{}'", migration.version
+            );
+            // Checked by `list_migrations_to_revert`
+            let down_alterations = migration.down_alterations.as_ref().unwrap();
+            self.connection.execute(down_alterations).with_context(|| {
+                format!(
+                    "Can not revert migration '{}', the database is left untouched",
+                    migration.version
+                )
+            })?;
+            let previous_version = self
+                .migrations
+                .iter()
+                .map(|m| m.version)
+                .filter(|version| *version < migration.version)
+                .max()
+                .unwrap_or(0);
+            let db_version = DatabaseVersion {
+                version: previous_version,
+                application_type: self.application_type.clone(),
+                updated_at: Utc::now(),
+            };
+            let _ = updater.save(db_version).with_context(|| {
+                format!(
+                    "Can not save database version when reverting migration: '{}'",
+                    migration.version
+                )
+            })?;
+        }
+        transaction.commit()?;
+        info!(
+            &self.logger,
+            "database reverted to version '{}'",
+            self.get_current_version()?
+        );
+
+        Ok(())
+    }
+
     fn apply_migrations(
         &self,
         starting_version: &DatabaseVersion,
@@ -129,6 +255,19 @@ impl<'conn> DatabaseVersionChecker<'conn> {
     }
 }
 
+/// Status of a registered [SqlMigration] in the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlMigrationStatus {
+    /// Version of the migration.
+    pub version: DbVersion,
+
+    /// Is the migration applied to the database.
+    pub applied: bool,
+
+    /// Does the migration have a down migration.
+    pub reversible: bool,
+}
+
 /// Represent a file containing SQL structure or data alterations.
 #[derive(Debug)]
 pub struct SqlMigration {
@@ -137,6 +276,9 @@ pub struct SqlMigration {
 
     /// SQL statements to alter the database.
     pub alterations: String,
+
+    /// SQL statements to revert the alterations, if the migration is reversible.
+    pub down_alterations: Option<String>,
 }
 
 impl SqlMigration {
@@ -145,8 +287,23 @@ impl SqlMigration {
         Self {
             version,
             alterations: alteration.to_string(),
+            down_alterations: None,
+        }
+    }
+
+    /// Create a new SQL migration instance that can be reverted using the given down alterations.
+    pub fn new_reversible(version: DbVersion, alteration: &str, down_alteration: &str) -> Self {
+        Self {
+            version,
+            alterations: alteration.to_string(),
+            down_alterations: Some(down_alteration.to_string()),
         }
     }
+
+    /// Check if the migration can be reverted.
+    pub fn is_reversible(&self) -> bool {
+        self.down_alterations.is_some()
+    }
 }
 
 impl PartialOrd for SqlMigration {
@@ -174,9 +331,10 @@ mod tests {
     use anyhow::Context;
     use mithril_common::test_utils::TempDir;
     use mithril_common::StdResult;
-    use sqlite::Connection;
     use std::path::PathBuf;
 
+    use crate::sqlite::ConnectionBuilder;
+
     use super::*;
 
     fn check_database_version(connection: &SqliteConnection, db_version: DbVersion) {
@@ -193,7 +351,8 @@ mod tests {
         let dirpath = TempDir::create("mithril_test_database", test_name);
         let filepath = dirpath.join("db.sqlite3");
 
-        let connection = Connection::open_thread_safe(&filepath)
+        let connection = ConnectionBuilder::open_file(&filepath)
+            .build()
             .with_context(|| "connection to sqlite file failure")?;
 
         Ok((filepath, connection))
@@ -230,10 +389,7 @@ mod tests {
         assert_eq!(0, get_table_whatever_column_count(&connection));
 
         let alterations = "create table whatever (thing_id integer); insert into whatever (thing_id) values (1), (2), (3), (4);";
-        let migration = SqlMigration {
-            version: 1,
-            alterations: alterations.to_string(),
-        };
+        let migration = SqlMigration::new(1, alterations);
         db_checker.add_migration(migration);
         db_checker.apply().unwrap();
         assert_eq!(1, get_table_whatever_column_count(&connection));
@@ -244,10 +400,7 @@ mod tests {
         check_database_version(&connection, 1);
 
         let alterations = "alter table whatever add column thing_content text; update whatever set thing_content = 'some content'";
-        let migration = SqlMigration {
-            version: 2,
-            alterations: alterations.to_string(),
-        };
+        let migration = SqlMigration::new(2, alterations);
         db_checker.add_migration(migration);
         db_checker.apply().unwrap();
         assert_eq!(2, get_table_whatever_column_count(&connection));
@@ -257,16 +410,10 @@ mod tests {
         // ensure they are played in the right order. The last one depends on
         // the 3rd.
         let alterations = "alter table whatever add column one_last_thing text; update whatever set one_last_thing = more_thing";
-        let migration = SqlMigration {
-            version: 4,
-            alterations: alterations.to_string(),
-        };
+        let migration = SqlMigration::new(4, alterations);
         db_checker.add_migration(migration);
         let alterations = "alter table whatever add column more_thing text; update whatever set more_thing = 'more thing'";
-        let migration = SqlMigration {
-            version: 3,
-            alterations: alterations.to_string(),
-        };
+        let migration = SqlMigration::new(3, alterations);
         db_checker.add_migration(migration);
         db_checker.apply().unwrap();
         assert_eq!(4, get_table_whatever_column_count(&connection));
@@ -283,10 +430,7 @@ mod tests {
         );
 
         let alterations = "create table whatever (thing_id integer); insert into whatever (thing_id) values (1), (2), (3), (4);";
-        let migration = SqlMigration {
-            version: 1,
-            alterations: alterations.to_string(),
-        };
+        let migration = SqlMigration::new(1, alterations);
         db_checker.add_migration(migration);
         db_checker.apply().unwrap();
         assert_eq!(1, get_table_whatever_column_count(&connection));
@@ -306,27 +450,173 @@ mod tests {
         );
         // Table whatever does not exist, this should fail with error.
         let alterations = "create table whatever (thing_id integer); insert into whatever (thing_id) values (1), (2), (3), (4);";
-        let migration = SqlMigration {
-            version: 1,
-            alterations: alterations.to_string(),
-        };
+        let migration = SqlMigration::new(1, alterations);
         db_checker.add_migration(migration);
         let alterations = "alter table wrong add column thing_content text; update whatever set thing_content = 'some content'";
-        let migration = SqlMigration {
-            version: 2,
-            alterations: alterations.to_string(),
-        };
+        let migration = SqlMigration::new(2, alterations);
         db_checker.add_migration(migration);
         let alterations = "alter table whatever add column thing_content text; update whatever set thing_content = 'some content'";
-        let migration = SqlMigration {
-            version: 3,
-            alterations: alterations.to_string(),
-        };
+        let migration = SqlMigration::new(3, alterations);
         db_checker.add_migration(migration);
         db_checker.apply().unwrap_err();
         check_database_version(&connection, 1);
     }
 
+    fn create_reversible_migrations() -> Vec<SqlMigration> {
+        vec![
+            SqlMigration::new_reversible(
+                1,
+                "create table whatever (thing_id integer);",
+                "drop table whatever;",
+            ),
+            SqlMigration::new_reversible(
+                2,
+                "alter table whatever add column thing_content text;",
+                "alter table whatever drop column thing_content;",
+            ),
+            SqlMigration::new_reversible(
+                3,
+                "alter table whatever add column more_thing text;",
+                "alter table whatever drop column more_thing;",
+            ),
+        ]
+    }
+
+    fn build_checker_with_migrations(
+        connection: &SqliteConnection,
+        migrations: Vec<SqlMigration>,
+    ) -> DatabaseVersionChecker {
+        let mut db_checker = DatabaseVersionChecker::new(
+            slog_scope::logger(),
+            ApplicationNodeType::Aggregator,
+            connection,
+        );
+        for migration in migrations {
+            db_checker.add_migration(migration);
+        }
+
+        db_checker
+    }
+
+    #[test]
+    fn status_and_pending_migrations_do_not_alter_database() {
+        let (_filepath, connection) =
+            create_sqlite_file("status_and_pending_migrations_do_not_alter_database").unwrap();
+        let db_checker = build_checker_with_migrations(&connection, create_reversible_migrations());
+
+        assert_eq!(0, db_checker.get_current_version().unwrap());
+        assert_eq!(
+            vec![1, 2, 3],
+            db_checker
+                .list_pending_migrations()
+                .unwrap()
+                .iter()
+                .map(|m| m.version)
+                .collect::<Vec<_>>()
+        );
+        assert!(!DatabaseVersionProvider::new(&connection)
+            .table_exists()
+            .unwrap());
+
+        db_checker.apply().unwrap();
+        assert_eq!(
+            vec![
+                SqlMigrationStatus {
+                    version: 1,
+                    applied: true,
+                    reversible: true
+                },
+                SqlMigrationStatus {
+                    version: 2,
+                    applied: true,
+                    reversible: true
+                },
+                SqlMigrationStatus {
+                    version: 3,
+                    applied: true,
+                    reversible: true
+                },
+            ],
+            db_checker.status().unwrap()
+        );
+        assert!(db_checker.list_pending_migrations().unwrap().is_empty());
+    }
+
+    #[test]
+    fn revert_to_a_previous_version() {
+        let (_filepath, connection) = create_sqlite_file("revert_to_a_previous_version").unwrap();
+        let db_checker = build_checker_with_migrations(&connection, create_reversible_migrations());
+        db_checker.apply().unwrap();
+        assert_eq!(3, get_table_whatever_column_count(&connection));
+
+        assert_eq!(
+            vec![3, 2],
+            db_checker
+                .list_migrations_to_revert(1)
+                .unwrap()
+                .iter()
+                .map(|m| m.version)
+                .collect::<Vec<_>>()
+        );
+        db_checker.revert_to(1).unwrap();
+
+        assert_eq!(1, get_table_whatever_column_count(&connection));
+        check_database_version(&connection, 1);
+    }
+
+    #[test]
+    fn revert_fails_without_altering_database_if_a_migration_is_not_reversible() {
+        let (_filepath, connection) = create_sqlite_file(
+            "revert_fails_without_altering_database_if_a_migration_is_not_reversible",
+        )
+        .unwrap();
+        let mut migrations = create_reversible_migrations();
+        migrations[1] = SqlMigration::new(2, "alter table whatever add column thing_content text;");
+        let db_checker = build_checker_with_migrations(&connection, migrations);
+        db_checker.apply().unwrap();
+
+        db_checker
+            .revert_to(0)
+            .expect_err("Reverting a non reversible migration should fail");
+
+        assert_eq!(3, get_table_whatever_column_count(&connection));
+        check_database_version(&connection, 3);
+    }
+
+    #[test]
+    fn revert_fails_without_altering_database_if_a_down_migration_fails() {
+        let (_filepath, connection) =
+            create_sqlite_file("revert_fails_without_altering_database_if_a_down_migration_fails")
+                .unwrap();
+        let mut migrations = create_reversible_migrations();
+        migrations[1] = SqlMigration::new_reversible(
+            2,
+            "alter table whatever add column thing_content text;",
+            "alter table wrong drop column thing_content;",
+        );
+        let db_checker = build_checker_with_migrations(&connection, migrations);
+        db_checker.apply().unwrap();
+
+        db_checker
+            .revert_to(0)
+            .expect_err("Reverting with a failing down migration should fail");
+
+        assert_eq!(3, get_table_whatever_column_count(&connection));
+        check_database_version(&connection, 3);
+    }
+
+    #[test]
+    fn revert_fails_if_target_version_is_higher_than_current_version() {
+        let (_filepath, connection) =
+            create_sqlite_file("revert_fails_if_target_version_is_higher_than_current_version")
+                .unwrap();
+        let db_checker = build_checker_with_migrations(&connection, create_reversible_migrations());
+
+        db_checker
+            .revert_to(2)
+            .expect_err("Reverting to a version higher than the current one should fail");
+    }
+
     #[test]
     fn test_fail_downgrading() {
         let (_filepath, connection) = create_sqlite_file("test_fail_downgrading").unwrap();
@@ -336,10 +626,7 @@ mod tests {
             &connection,
         );
         let alterations = "create table whatever (thing_id integer); insert into whatever (thing_id) values (1), (2), (3), (4);";
-        let migration = SqlMigration {
-            version: 1,
-            alterations: alterations.to_string(),
-        };
+        let migration = SqlMigration::new(1, alterations);
         db_checker.add_migration(migration);
         db_checker.apply().unwrap();
         check_database_version(&connection, 1);
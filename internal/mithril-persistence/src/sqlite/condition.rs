@@ -97,6 +97,31 @@ impl WhereCondition {
         }
     }
 
+    /// Instanciate a condition holding the given columns and rows of values of an `insert`
+    /// statement, ie `(column_a, column_b) values (?1, ?2), (?3, ?4)`.
+    pub fn insert_values(columns: &[&str], rows: Vec<Vec<Value>>) -> Self {
+        let row_placeholders = format!(
+            "({})",
+            repeat("?*")
+                .take(columns.len())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let expression = format!(
+            "({}) values {}",
+            columns.join(", "),
+            repeat(row_placeholders.as_str())
+                .take(rows.len())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        Self {
+            condition: BooleanCondition::Expression(expression),
+            parameters: rows.into_iter().flatten().collect(),
+        }
+    }
+
     /// Add a new parameter using a AND operator
     pub fn and_where(mut self, mut condition: WhereCondition) -> Self {
         if condition.condition.is_none() {
@@ -358,6 +383,29 @@ mod tests {
         assert_eq!(1, params.len());
     }
 
+    #[test]
+    fn expression_insert_values() {
+        let expression = WhereCondition::insert_values(
+            &["a", "b"],
+            vec![
+                vec![Value::Integer(1), Value::String("one".into())],
+                vec![Value::Integer(2), Value::String("two".into())],
+            ],
+        );
+        let (sql, params) = expression.expand();
+
+        assert_eq!("(a, b) values (?1, ?2), (?3, ?4)", &sql);
+        assert_eq!(
+            vec![
+                Value::Integer(1),
+                Value::String("one".into()),
+                Value::Integer(2),
+                Value::String("two".into())
+            ],
+            params
+        );
+    }
+
     #[test]
     fn expression_get_all_default() {
         impl GetAllCondition for String {}
@@ -0,0 +1,125 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use sqlite::ConnectionThreadSafe;
+
+use super::{QueryInstrumentation, QueryTracker};
+
+/// Type of the connection used in Mithril
+///
+/// A thread safe SQLite connection that records its queries with the [QueryInstrumentation]
+/// it has been built with, if any.
+///
+/// It dereferences to the underlying [sqlite::Connection]: the statements prepared directly on
+/// it are not instrumented, only the [Provider][super::Provider] queries and the
+/// [execute][SqliteConnection::execute] calls are.
+pub struct SqliteConnection {
+    connection: ConnectionThreadSafe,
+    query_instrumentation: Option<Arc<QueryInstrumentation>>,
+}
+
+impl SqliteConnection {
+    /// Wrap the given connection, without instrumentation.
+    pub fn new(connection: ConnectionThreadSafe) -> Self {
+        Self {
+            connection,
+            query_instrumentation: None,
+        }
+    }
+
+    /// Record the queries performed on this connection with the given instrumentation.
+    pub fn with_query_instrumentation(
+        mut self,
+        query_instrumentation: Arc<QueryInstrumentation>,
+    ) -> Self {
+        self.query_instrumentation = Some(query_instrumentation);
+        self
+    }
+
+    /// Execute a statement without processing the resulting rows if any.
+    ///
+    /// The statement is instrumented with `execute` as its query type.
+    pub fn execute<T: AsRef<str>>(&self, statement: T) -> sqlite::Result<()> {
+        match self.track_query("execute") {
+            Some(mut tracker) => tracker.measure(|| self.connection.execute(statement)),
+            None => self.connection.execute(statement),
+        }
+    }
+
+    /// Start tracking a query of the given type if this connection is instrumented.
+    pub(crate) fn track_query(&self, query_type: &'static str) -> Option<QueryTracker> {
+        self.query_instrumentation
+            .as_ref()
+            .map(|instrumentation| QueryTracker::new(instrumentation.clone(), query_type))
+    }
+}
+
+impl From<ConnectionThreadSafe> for SqliteConnection {
+    fn from(connection: ConnectionThreadSafe) -> Self {
+        Self::new(connection)
+    }
+}
+
+impl Deref for SqliteConnection {
+    type Target = ConnectionThreadSafe;
+
+    fn deref(&self) -> &Self::Target {
+        &self.connection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use slog::Logger;
+
+    use crate::sqlite::{ConnectionBuilder, QueryObserver, QueryStatistics};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct TestQueryObserver {
+        query_types: Mutex<Vec<String>>,
+    }
+
+    impl QueryObserver for TestQueryObserver {
+        fn on_query_done(&self, statistics: &QueryStatistics) {
+            self.query_types
+                .lock()
+                .unwrap()
+                .push(statistics.query_type.clone());
+        }
+    }
+
+    fn instrumentation_with_observer(
+        observer: Arc<TestQueryObserver>,
+    ) -> Arc<QueryInstrumentation> {
+        Arc::new(
+            QueryInstrumentation::new(None, Logger::root(slog::Discard, slog::o!()))
+                .with_observer(observer),
+        )
+    }
+
+    #[test]
+    fn only_the_queries_of_the_instrumented_connection_are_recorded() {
+        let observer = Arc::new(TestQueryObserver::default());
+        let connection = ConnectionBuilder::open_memory()
+            .with_query_instrumentation(instrumentation_with_observer(observer.clone()))
+            .build()
+            .unwrap();
+        let other_connection = ConnectionBuilder::open_memory().build().unwrap();
+
+        connection
+            .execute("create table whatever (id integer);")
+            .unwrap();
+        other_connection
+            .execute("create table whatever (id integer);")
+            .unwrap();
+
+        assert_eq!(
+            vec!["execute".to_string()],
+            observer.query_types.lock().unwrap().clone()
+        );
+    }
+}
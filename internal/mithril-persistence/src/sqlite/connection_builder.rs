@@ -1,13 +1,16 @@
 use std::ops::Not;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Context;
 use slog::Logger;
-use sqlite::{Connection, ConnectionThreadSafe};
+use sqlite::{Connection, OpenFlags};
 
 use mithril_common::StdResult;
 
 use crate::database::{ApplicationNodeType, DatabaseVersionChecker, SqlMigration};
+use crate::sqlite::{QueryInstrumentation, SqliteConnection};
 
 /// Builder of SQLite connection
 pub struct ConnectionBuilder {
@@ -15,6 +18,7 @@ pub struct ConnectionBuilder {
     sql_migrations: Vec<SqlMigration>,
     options: Vec<ConnectionOptions>,
     node_type: ApplicationNodeType,
+    query_instrumentation: Option<Arc<QueryInstrumentation>>,
     logger: Logger,
 }
 
@@ -31,6 +35,16 @@ pub enum ConnectionOptions {
     ///
     /// This option take priority over [ConnectionOptions::EnableForeignKeys] if both are enabled.
     ForceDisableForeignKeys,
+
+    /// Open the connection in read only mode
+    ///
+    /// The database must already exist: the migrations and the Write Ahead Log journal mode
+    /// can not be applied by a read only connection, they are left to a writable connection.
+    ReadOnly,
+
+    /// Wait up to the given duration for the locks held by the other connections to the
+    /// database to be released instead of failing right away
+    BusyTimeout(Duration),
 }
 
 impl ConnectionBuilder {
@@ -41,6 +55,7 @@ impl ConnectionBuilder {
             sql_migrations: vec![],
             options: vec![],
             node_type: ApplicationNodeType::Signer,
+            query_instrumentation: None,
             logger: Logger::root(slog::Discard, slog::o!()),
         }
     }
@@ -76,32 +91,60 @@ impl ConnectionBuilder {
         self
     }
 
-    /// Build a connection based on the builder configuration
-    pub fn build(self) -> StdResult<ConnectionThreadSafe> {
-        let connection =
-            Connection::open_thread_safe(&self.connection_path).with_context(|| {
-                format!(
-                    "SQLite initialization: could not open connection with string '{}'.",
-                    self.connection_path.display()
-                )
-            })?;
+    /// Set the [QueryInstrumentation] recording the queries performed on the built connection,
+    /// the queries made at build time (i.e. the migrations) are not recorded.
+    pub fn with_query_instrumentation(
+        mut self,
+        query_instrumentation: Arc<QueryInstrumentation>,
+    ) -> Self {
+        self.query_instrumentation = Some(query_instrumentation);
+        self
+    }
 
-        if self
-            .options
-            .contains(&ConnectionOptions::EnableWriteAheadLog)
+    /// Build a connection based on the builder configuration
+    pub fn build(self) -> StdResult<SqliteConnection> {
+        let is_read_only = self.options.contains(&ConnectionOptions::ReadOnly);
+        let connection = if is_read_only {
+            Connection::open_thread_safe_with_flags(
+                &self.connection_path,
+                OpenFlags::new().with_read_only().with_full_mutex(),
+            )
+        } else {
+            Connection::open_thread_safe(&self.connection_path)
+        }
+        .map(SqliteConnection::new)
+        .with_context(|| {
+            format!(
+                "SQLite initialization: could not open connection with string '{}'.",
+                self.connection_path.display()
+            )
+        })?;
+
+        if !is_read_only
+            && self
+                .options
+                .contains(&ConnectionOptions::EnableWriteAheadLog)
         {
             connection
                 .execute("pragma journal_mode = wal; pragma synchronous = normal;")
                 .with_context(|| "SQLite initialization: could not enable WAL.")?;
         }
 
+        for option in &self.options {
+            if let ConnectionOptions::BusyTimeout(timeout) = option {
+                connection
+                    .execute(format!("pragma busy_timeout = {};", timeout.as_millis()))
+                    .with_context(|| "SQLite initialization: could not set the busy timeout.")?;
+            }
+        }
+
         if self.options.contains(&ConnectionOptions::EnableForeignKeys) {
             connection
                 .execute("pragma foreign_keys=true")
                 .with_context(|| "SQLite initialization: could not enable FOREIGN KEY support.")?;
         }
 
-        if self.sql_migrations.is_empty().not() {
+        if !is_read_only && self.sql_migrations.is_empty().not() {
             // Check database migrations
             let mut db_checker =
                 DatabaseVersionChecker::new(self.logger, self.node_type, &connection);
@@ -124,7 +167,12 @@ impl ConnectionBuilder {
                 .with_context(|| "SQLite initialization: could not disable FOREIGN KEY support.")?;
         }
 
-        Ok(connection)
+        match self.query_instrumentation {
+            Some(query_instrumentation) => {
+                Ok(connection.with_query_instrumentation(query_instrumentation))
+            }
+            None => Ok(connection),
+        }
     }
 }
 
@@ -171,6 +219,18 @@ mod tests {
         assert_eq!(Value::Integer(true.into()), foreign_keys);
     }
 
+    #[test]
+    fn test_open_with_busy_timeout() {
+        let connection = ConnectionBuilder::open_memory()
+            .with_options(&[ConnectionOptions::BusyTimeout(Duration::from_millis(1500))])
+            .build()
+            .unwrap();
+
+        let busy_timeout = execute_single_cell_query(&connection, "pragma busy_timeout;");
+
+        assert_eq!(Value::Integer(1500), busy_timeout);
+    }
+
     #[test]
     fn test_open_file_without_wal_and_foreign_keys() {
         let dirpath = TempDir::create(
@@ -218,6 +278,54 @@ mod tests {
         assert_eq!(Value::Integer(true.into()), foreign_keys);
     }
 
+    #[test]
+    fn read_only_connection_reads_the_writes_of_a_writable_connection() {
+        let dirpath = TempDir::create(
+            "mithril_test_database",
+            "read_only_connection_reads_the_writes_of_a_writable_connection",
+        );
+        let filepath = dirpath.join("db.sqlite3");
+        let write_connection = ConnectionBuilder::open_file(&filepath)
+            .with_options(&[ConnectionOptions::EnableWriteAheadLog])
+            .build()
+            .unwrap();
+        write_connection
+            .execute("create table value (value integer not null);")
+            .unwrap();
+
+        let read_connection = ConnectionBuilder::open_file(&filepath)
+            .with_options(&[
+                ConnectionOptions::ReadOnly,
+                ConnectionOptions::EnableWriteAheadLog,
+            ])
+            .build()
+            .unwrap();
+        write_connection
+            .execute("insert into value values (1);")
+            .unwrap();
+
+        assert_eq!(
+            Value::Integer(1),
+            execute_single_cell_query(&read_connection, "select count(*) from value;")
+        );
+        read_connection
+            .execute("insert into value values (2);")
+            .expect_err("Writing with a read only connection should fail");
+    }
+
+    #[test]
+    fn read_only_connection_fails_if_the_database_does_not_exist() {
+        let dirpath = TempDir::create(
+            "mithril_test_database",
+            "read_only_connection_fails_if_the_database_does_not_exist",
+        );
+
+        ConnectionBuilder::open_file(&dirpath.join("db.sqlite3"))
+            .with_options(&[ConnectionOptions::ReadOnly])
+            .build()
+            .expect_err("Opening a missing database in read only mode should fail");
+    }
+
     #[test]
     fn enabling_wal_option_also_set_synchronous_flag_to_normal() {
         let dirpath = TempDir::create(
@@ -1,12 +1,13 @@
 use sqlite::CursorWithOwnership;
 use std::marker::PhantomData;
 
-use super::SqLiteEntity;
+use super::{QueryTracker, SqLiteEntity};
 
 /// Database query result Iterator wrapper. This wrapper allows to call entity
 /// hydration for each extracted result.
 pub struct EntityCursor<'a, T> {
     cursor: CursorWithOwnership<'a>,
+    tracker: Option<QueryTracker>,
     phantom: PhantomData<T>,
 }
 
@@ -15,9 +16,16 @@ impl<'a, T> EntityCursor<'a, T> {
     pub fn new(cursor: CursorWithOwnership<'a>) -> Self {
         Self {
             cursor,
+            tracker: None,
             phantom: PhantomData,
         }
     }
+
+    /// Record the time spent fetching the rows and their count with the given tracker.
+    pub(crate) fn with_tracker(mut self, tracker: Option<QueryTracker>) -> Self {
+        self.tracker = tracker;
+        self
+    }
 }
 
 impl<'a, T> Iterator for EntityCursor<'a, T>
@@ -30,8 +38,17 @@ where
     /// This iterator will crash the application if an error occures during this process.
     /// This is intended because it prevents inconsistent data to spread accross the application.
     fn next(&mut self) -> Option<T> {
-        self.cursor
-            .next()
-            .map(|res| T::hydrate(res.map_err(|e| panic!("{e}")).unwrap()).unwrap())
+        let row = match &mut self.tracker {
+            Some(tracker) => {
+                let row = tracker.measure(|| self.cursor.next());
+                if row.is_some() {
+                    tracker.add_row();
+                }
+                row
+            }
+            None => self.cursor.next(),
+        };
+
+        row.map(|res| T::hydrate(res.map_err(|e| panic!("{e}")).unwrap()).unwrap())
     }
 }
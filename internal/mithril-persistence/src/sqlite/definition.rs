@@ -0,0 +1,121 @@
+//! Helpers to build the definition of the [Provider][crate::sqlite::Provider]s that write in a
+//! table and return the written rows.
+//!
+//! The returned projection is expanded with the table name as the alias of the `{:<table>:}`
+//! source since a table cannot be aliased in a `RETURNING` statement in SQLite.
+
+use super::{SourceAlias, SqLiteEntity};
+
+fn returning_projection<E: SqLiteEntity>(table: &str) -> String {
+    E::get_projection().expand(SourceAlias::new(&[(&format!("{{:{table}:}}"), table)]))
+}
+
+/// Definition of an `insert` statement in the given table, the condition must hold the
+/// inserted columns and values (see [WhereCondition::insert_values][crate::sqlite::WhereCondition::insert_values]).
+pub fn insert_definition<E: SqLiteEntity>(table: &str, condition: &str) -> String {
+    let projection = returning_projection::<E>(table);
+
+    format!("insert into {table} {condition} returning {projection}")
+}
+
+/// Definition of an upsert statement in the given table, the condition must hold the inserted
+/// columns and values (see [WhereCondition::insert_values][crate::sqlite::WhereCondition::insert_values]).
+///
+/// The rows conflicting on the `conflict_columns` have their `update_columns` updated with the
+/// inserted values, or are ignored if there are no `update_columns`.
+///
+/// Contrary to an `insert or replace` statement, a conflicting row is not deleted before the
+/// insertion, so the rows that reference it with a cascading foreign key are kept.
+pub fn upsert_definition<E: SqLiteEntity>(
+    table: &str,
+    conflict_columns: &[&str],
+    update_columns: &[&str],
+    condition: &str,
+) -> String {
+    let projection = returning_projection::<E>(table);
+    let conflict_target = match conflict_columns {
+        [] => String::new(),
+        columns => format!(" ({})", columns.join(", ")),
+    };
+    let action = match update_columns {
+        [] => "nothing".to_string(),
+        columns => format!(
+            "update set {}",
+            columns
+                .iter()
+                .map(|column| format!("{column} = excluded.{column}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+
+    format!(
+        "insert into {table} {condition} on conflict{conflict_target} do {action} returning {projection}"
+    )
+}
+
+/// Definition of a `delete` statement in the given table, returning the deleted rows.
+pub fn delete_definition<E: SqLiteEntity>(table: &str, condition: &str) -> String {
+    let projection = returning_projection::<E>(table);
+
+    format!("delete from {table} where {condition} returning {projection}")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sqlite::{HydrationError, Projection};
+
+    use super::*;
+
+    struct TestEntity;
+
+    impl SqLiteEntity for TestEntity {
+        fn hydrate(_row: sqlite::Row) -> Result<Self, HydrationError> {
+            Ok(TestEntity)
+        }
+
+        fn get_projection() -> Projection {
+            Projection::from(&[
+                ("id", "{:test:}.id", "integer"),
+                ("value", "{:test:}.value", "text"),
+            ])
+        }
+    }
+
+    #[test]
+    fn insert_definition_returns_the_inserted_rows() {
+        assert_eq!(
+            "insert into test (id, value) values (?1, ?2) returning test.id as id, test.value as value",
+            insert_definition::<TestEntity>("test", "(id, value) values (?1, ?2)")
+        );
+    }
+
+    #[test]
+    fn upsert_definition_updates_the_conflicting_rows() {
+        assert_eq!(
+            "insert into test (id, value) values (?1, ?2) on conflict (id) do update set value = excluded.value returning test.id as id, test.value as value",
+            upsert_definition::<TestEntity>(
+                "test",
+                &["id"],
+                &["value"],
+                "(id, value) values (?1, ?2)"
+            )
+        );
+    }
+
+    #[test]
+    fn upsert_definition_without_update_columns_ignores_the_conflicting_rows() {
+        assert_eq!(
+            "insert into test (id, value) values (?1, ?2) on conflict do nothing returning test.id as id, test.value as value",
+            upsert_definition::<TestEntity>("test", &[], &[], "(id, value) values (?1, ?2)")
+        );
+    }
+
+    #[test]
+    fn delete_definition_returns_the_deleted_rows() {
+        assert_eq!(
+            "delete from test where id = ?1 returning test.id as id, test.value as value",
+            delete_definition::<TestEntity>("test", "id = ?1")
+        );
+    }
+}
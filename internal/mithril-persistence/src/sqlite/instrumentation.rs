@@ -0,0 +1,176 @@
+use slog::{warn, Logger};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Statistics of a query performed on an instrumented [SqliteConnection][super::SqliteConnection]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryStatistics {
+    /// Type of the query, i.e. the name of the provider that performed it or `execute`
+    pub query_type: String,
+
+    /// Time spent by SQLite to prepare the query and to fetch its rows
+    pub duration: Duration,
+
+    /// Number of rows fetched from the query result
+    pub rows: u64,
+}
+
+/// Observer of the queries performed on the instrumented connections, i.e. to export their
+/// statistics as metrics.
+pub trait QueryObserver: Sync + Send {
+    /// Called once the result of a query is dropped.
+    fn on_query_done(&self, statistics: &QueryStatistics);
+}
+
+/// Record the statistics of the queries performed on the connections it is given to (see
+/// [ConnectionBuilder::with_query_instrumentation][super::ConnectionBuilder::with_query_instrumentation]),
+/// logging the queries slower than a threshold and notifying its [QueryObservers][QueryObserver].
+pub struct QueryInstrumentation {
+    slow_query_threshold: Option<Duration>,
+    observers: Vec<Arc<dyn QueryObserver>>,
+    logger: Logger,
+}
+
+impl QueryInstrumentation {
+    /// [QueryInstrumentation] factory, the slow queries are not logged if no threshold is given.
+    pub fn new(slow_query_threshold: Option<Duration>, logger: Logger) -> Self {
+        Self {
+            slow_query_threshold,
+            observers: vec![],
+            logger,
+        }
+    }
+
+    /// Add an observer notified of the statistics of each query.
+    pub fn with_observer(mut self, observer: Arc<dyn QueryObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Record the statistics of a query.
+    pub fn record(&self, statistics: &QueryStatistics) {
+        if self
+            .slow_query_threshold
+            .is_some_and(|threshold| statistics.duration >= threshold)
+        {
+            warn!(
+                self.logger, "Slow SQLite query";
+                "query_type" => &statistics.query_type,
+                "duration_ms" => statistics.duration.as_millis(),
+                "rows" => statistics.rows,
+            );
+        }
+
+        for observer in &self.observers {
+            observer.on_query_done(statistics);
+        }
+    }
+}
+
+/// Measure the duration and the fetched rows of a query, recorded by its [QueryInstrumentation]
+/// once dropped.
+pub(crate) struct QueryTracker {
+    instrumentation: Arc<QueryInstrumentation>,
+    query_type: &'static str,
+    duration: Duration,
+    rows: u64,
+}
+
+impl QueryTracker {
+    /// Start tracking a query of the given type.
+    pub(crate) fn new(
+        instrumentation: Arc<QueryInstrumentation>,
+        query_type: &'static str,
+    ) -> Self {
+        Self {
+            instrumentation,
+            query_type,
+            duration: Duration::ZERO,
+            rows: 0,
+        }
+    }
+
+    /// Measure the time spent by SQLite in the given closure.
+    pub(crate) fn measure<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        let started_at = Instant::now();
+        let result = f();
+        self.duration += started_at.elapsed();
+
+        result
+    }
+
+    /// Count a row fetched from the query result.
+    pub(crate) fn add_row(&mut self) {
+        self.rows += 1;
+    }
+}
+
+impl Drop for QueryTracker {
+    fn drop(&mut self) {
+        self.instrumentation.record(&QueryStatistics {
+            query_type: short_type_name(self.query_type).to_string(),
+            duration: self.duration,
+            rows: self.rows,
+        });
+    }
+}
+
+/// Strip the module path and the generic parameters of a type name.
+fn short_type_name(type_name: &str) -> &str {
+    let type_name = type_name.split('<').next().unwrap_or(type_name);
+
+    type_name.rsplit("::").next().unwrap_or(type_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct TestQueryObserver {
+        statistics: Mutex<Vec<QueryStatistics>>,
+    }
+
+    impl QueryObserver for TestQueryObserver {
+        fn on_query_done(&self, statistics: &QueryStatistics) {
+            self.statistics.lock().unwrap().push(statistics.clone());
+        }
+    }
+
+    #[test]
+    fn short_type_name_strip_module_path_and_generics() {
+        assert_eq!(
+            "GetCertificateRecordProvider",
+            short_type_name("mithril_aggregator::database::query::GetCertificateRecordProvider")
+        );
+        assert_eq!(
+            "TestProvider",
+            short_type_name("crate::TestProvider<mithril_common::entities::Epoch>")
+        );
+        assert_eq!("Provider", short_type_name("Provider"));
+    }
+
+    #[test]
+    fn tracker_record_its_statistics_once_dropped() {
+        let observer = Arc::new(TestQueryObserver::default());
+        let instrumentation = Arc::new(
+            QueryInstrumentation::new(None, Logger::root(slog::Discard, slog::o!()))
+                .with_observer(observer.clone()),
+        );
+
+        let mut tracker = QueryTracker::new(instrumentation, "my_crate::MyProvider<'_>");
+        tracker.measure(|| std::thread::sleep(Duration::from_millis(2)));
+        tracker.add_row();
+        tracker.add_row();
+        assert!(observer.statistics.lock().unwrap().is_empty());
+        drop(tracker);
+
+        let statistics = observer.statistics.lock().unwrap().clone();
+        assert_eq!(1, statistics.len());
+        assert_eq!("MyProvider", statistics[0].query_type);
+        assert_eq!(2, statistics[0].rows);
+        assert!(statistics[0].duration >= Duration::from_millis(2));
+    }
+}
@@ -3,26 +3,32 @@
 //! with ability to perform any SQL query possible and hydrate results in Rust
 //! structs.
 mod condition;
+mod connection;
 mod connection_builder;
 mod cursor;
+mod definition;
 mod entity;
+mod instrumentation;
 mod projection;
 mod provider;
 mod source_alias;
+mod transaction;
 
 pub use condition::{GetAllCondition, WhereCondition};
+pub use connection::SqliteConnection;
 pub use connection_builder::{ConnectionBuilder, ConnectionOptions};
 pub use cursor::EntityCursor;
+pub use definition::{delete_definition, insert_definition, upsert_definition};
 pub use entity::{HydrationError, SqLiteEntity};
+pub use instrumentation::{QueryInstrumentation, QueryObserver, QueryStatistics};
 pub use projection::{Projection, ProjectionField};
-pub use provider::{GetAllProvider, Provider};
+pub use provider::{BulkInsertProvider, GetAllProvider, Provider, SQLITE_MAX_VARIABLE_NUMBER};
 pub use source_alias::SourceAlias;
+pub use transaction::SqliteTransaction;
 
-use mithril_common::StdResult;
-use sqlite::ConnectionThreadSafe;
+pub(crate) use instrumentation::QueryTracker;
 
-/// Type of the connection used in Mithril
-pub type SqliteConnection = ConnectionThreadSafe;
+use mithril_common::StdResult;
 
 /// Do a [vacuum](https://www.sqlite.org/lang_vacuum.html) on the given connection, this will
 /// reconstruct the database file, repacking it into a minimal amount of disk space.
@@ -34,12 +40,12 @@ pub async fn vacuum_database(connection: &SqliteConnection) -> StdResult<()> {
 
 #[cfg(test)]
 mod test {
-    use crate::sqlite::vacuum_database;
+    use crate::sqlite::{vacuum_database, ConnectionBuilder};
     use sqlite::Connection;
 
     #[tokio::test]
     async fn calling_vacuum_on_an_empty_in_memory_db_should_not_fail() {
-        let connection = Connection::open_thread_safe(":memory:").unwrap();
+        let connection = ConnectionBuilder::open_memory().build().unwrap();
 
         vacuum_database(&connection)
             .await
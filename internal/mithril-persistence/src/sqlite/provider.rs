@@ -1,6 +1,7 @@
 use crate::sqlite::condition::GetAllCondition;
 use anyhow::Context;
 use mithril_common::StdResult;
+use sqlite::Value;
 
 use super::{EntityCursor, SqLiteEntity, SqliteConnection, WhereCondition};
 
@@ -14,22 +15,31 @@ pub trait Provider<'conn> {
     fn get_connection(&'conn self) -> &'conn SqliteConnection;
 
     /// Perform the parametrized definition query.
+    ///
+    /// The query is instrumented if the connection has a
+    /// [QueryInstrumentation][super::QueryInstrumentation], with the type of the provider as its
+    /// query type.
     fn find(&'conn self, filters: WhereCondition) -> StdResult<EntityCursor<'conn, Self::Entity>> {
         let (condition, params) = filters.expand();
         let sql = self.get_definition(&condition);
-        let cursor = self
+        let mut tracker = self
             .get_connection()
-            .prepare(&sql)
-            .with_context(|| {
+            .track_query(std::any::type_name::<Self>());
+        let prepare = || {
+            self.get_connection().prepare(&sql).with_context(|| {
                 format!(
                     "Prepare query error: SQL=`{}`",
                     &sql.replace('\n', " ").trim()
                 )
-            })?
-            .into_iter()
-            .bind(&params[..])?;
+            })
+        };
+        let statement = match &mut tracker {
+            Some(tracker) => tracker.measure(prepare)?,
+            None => prepare()?,
+        };
+        let cursor = statement.into_iter().bind(&params[..])?;
 
-        let iterator = EntityCursor::new(cursor);
+        let iterator = EntityCursor::new(cursor).with_tracker(tracker);
 
         Ok(iterator)
     }
@@ -55,11 +65,44 @@ where
     }
 }
 
+/// Maximum number of parameters that can be bound to a single SQLite statement (default
+/// `SQLITE_MAX_VARIABLE_NUMBER` since SQLite 3.32).
+pub const SQLITE_MAX_VARIABLE_NUMBER: usize = 32766;
+
+/// An extension of the [Provider] that inserts many rows at once, splitting them in as many
+/// statements as needed to respect the [SQLITE_MAX_VARIABLE_NUMBER] limit.
+///
+/// The definition of the provider must be an insert statement taking its values from the
+/// condition (see [WhereCondition::insert_values]).
+pub trait BulkInsertProvider<'conn, E: SqLiteEntity> {
+    /// Insert the given rows of values of the given columns, returning the written entities.
+    fn insert_many(&'conn self, columns: &[&str], rows: Vec<Vec<Value>>) -> StdResult<Vec<E>>;
+}
+
+impl<'conn, P, E> BulkInsertProvider<'conn, E> for P
+where
+    P: Provider<'conn, Entity = E>,
+    E: SqLiteEntity,
+{
+    fn insert_many(&'conn self, columns: &[&str], rows: Vec<Vec<Value>>) -> StdResult<Vec<E>> {
+        let rows_per_statement = (SQLITE_MAX_VARIABLE_NUMBER / columns.len().max(1)).max(1);
+        let mut entities = Vec::with_capacity(rows.len());
+        let mut rows = rows.into_iter().peekable();
+
+        while rows.peek().is_some() {
+            let chunk: Vec<Vec<Value>> = rows.by_ref().take(rows_per_statement).collect();
+            entities.extend(self.find(WhereCondition::insert_values(columns, chunk))?);
+        }
+
+        Ok(entities)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use sqlite::{Connection, Value};
+    use sqlite::Value;
 
-    use crate::sqlite::{Projection, SourceAlias};
+    use crate::sqlite::{upsert_definition, ConnectionBuilder, Projection, SourceAlias};
 
     use super::super::{entity::HydrationError, SqLiteEntity};
     use super::*;
@@ -153,10 +196,37 @@ returning {projection}
         }
     }
 
+    struct TestEntityInsertProvider<'conn> {
+        connection: &'conn SqliteConnection,
+    }
+
+    impl<'conn> TestEntityInsertProvider<'conn> {
+        pub fn new(connection: &'conn SqliteConnection) -> Self {
+            Self { connection }
+        }
+    }
+
+    impl<'conn> Provider<'conn> for TestEntityInsertProvider<'conn> {
+        type Entity = TestEntity;
+
+        fn get_connection(&'conn self) -> &'conn SqliteConnection {
+            self.connection
+        }
+
+        fn get_definition(&self, condition: &str) -> String {
+            upsert_definition::<Self::Entity>(
+                "provider_test",
+                &["text_data"],
+                &["real_data", "integer_data", "maybe_null"],
+                condition,
+            )
+        }
+    }
+
     impl GetAllCondition for TestEntityProvider<'_> {}
 
     fn init_database() -> SqliteConnection {
-        let connection = Connection::open_thread_safe(":memory:").unwrap();
+        let connection = ConnectionBuilder::open_memory().build().unwrap();
         connection
             .execute(
                 "
@@ -304,4 +374,91 @@ returning {projection}
             entities
         );
     }
+
+    fn test_entity_values(text_data: &str, integer_data: i64) -> Vec<Value> {
+        vec![
+            Value::String(text_data.to_string()),
+            Value::Float(1.0),
+            Value::Integer(integer_data),
+            Value::Null,
+        ]
+    }
+
+    #[test]
+    fn test_blanket_insert_many() {
+        let connection = init_database();
+        let provider = TestEntityInsertProvider::new(&connection);
+        let entities = provider
+            .insert_many(
+                &["text_data", "real_data", "integer_data", "maybe_null"],
+                vec![
+                    test_entity_values("row 2", 2),
+                    test_entity_values("row 3", 3),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                TestEntity {
+                    text_data: "row 2".to_string(),
+                    real_data: 1.0,
+                    integer_data: 2,
+                    maybe_null: None
+                },
+                TestEntity {
+                    text_data: "row 3".to_string(),
+                    real_data: 1.0,
+                    integer_data: 3,
+                    maybe_null: None
+                }
+            ],
+            entities
+        );
+        assert_eq!(
+            3,
+            TestEntityProvider::new(&connection)
+                .get_all()
+                .unwrap()
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_blanket_insert_many_splits_the_rows_exceeding_the_variables_limit() {
+        let connection = init_database();
+        let provider = TestEntityInsertProvider::new(&connection);
+        let rows_count = SQLITE_MAX_VARIABLE_NUMBER / 4 + 10;
+        let entities = provider
+            .insert_many(
+                &["text_data", "real_data", "integer_data", "maybe_null"],
+                (0..rows_count)
+                    .map(|i| test_entity_values(&format!("bulk {i}"), i as i64))
+                    .collect(),
+            )
+            .unwrap();
+
+        assert_eq!(rows_count, entities.len());
+        assert_eq!(
+            rows_count + 2,
+            TestEntityProvider::new(&connection)
+                .get_all()
+                .unwrap()
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_blanket_insert_many_without_rows() {
+        let connection = init_database();
+        let provider = TestEntityInsertProvider::new(&connection);
+        let entities = provider
+            .insert_many(
+                &["text_data", "real_data", "integer_data", "maybe_null"],
+                vec![],
+            )
+            .unwrap();
+
+        assert!(entities.is_empty());
+    }
 }
@@ -0,0 +1,170 @@
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use mithril_common::StdResult;
+
+use crate::sqlite::SqliteConnection;
+
+/// Counter used to give an unique name to the savepoints of the transactions.
+static SAVEPOINT_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// ## SqliteTransaction
+///
+/// Unit of work over a [SqliteConnection]: the writes made on the connection between the
+/// beginning of the transaction and its commit are either all applied or all discarded.
+///
+/// The transaction is rolled back if it is dropped without being committed, so an early return
+/// on error discards the writes already made.
+///
+/// The transaction relies on a SQLite savepoint, transactions can be nested. All the writes made
+/// on the connection while the transaction is pending are part of it, whoever makes them: the
+/// transaction must be used on a connection dedicated to it and must not be held across an
+/// `.await`. To enforce the latter the transaction is not `Send`, so it can not live in a future
+/// spawned on a multithreaded runtime.
+pub struct SqliteTransaction<'conn> {
+    connection: &'conn SqliteConnection,
+    savepoint_name: String,
+    is_finished: bool,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl<'conn> SqliteTransaction<'conn> {
+    /// Begin a new transaction on the given connection.
+    pub fn begin(connection: &'conn SqliteConnection) -> StdResult<Self> {
+        let savepoint_name = format!(
+            "mithril_transaction_{}",
+            SAVEPOINT_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        connection.execute(format!("savepoint {savepoint_name};"))?;
+
+        Ok(Self {
+            connection,
+            savepoint_name,
+            is_finished: false,
+            _not_send: PhantomData,
+        })
+    }
+
+    /// The connection on which the writes of the transaction are made.
+    pub fn connection(&self) -> &'conn SqliteConnection {
+        self.connection
+    }
+
+    /// Apply the writes made during the transaction.
+    pub fn commit(mut self) -> StdResult<()> {
+        self.is_finished = true;
+        self.connection
+            .execute(format!("release {};", self.savepoint_name))?;
+
+        Ok(())
+    }
+
+    /// Discard the writes made during the transaction.
+    pub fn rollback(mut self) -> StdResult<()> {
+        self.is_finished = true;
+        self.rollback_savepoint()
+    }
+
+    fn rollback_savepoint(&self) -> StdResult<()> {
+        self.connection.execute(format!(
+            "rollback to {name}; release {name};",
+            name = self.savepoint_name
+        ))?;
+
+        Ok(())
+    }
+}
+
+impl Drop for SqliteTransaction<'_> {
+    fn drop(&mut self) {
+        if !self.is_finished {
+            // An error can not be propagated from a drop, the savepoint is released with the
+            // connection in the worst case.
+            let _ = self.rollback_savepoint();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sqlite::ConnectionBuilder;
+    use anyhow::anyhow;
+
+    use super::*;
+
+    fn create_connection() -> SqliteConnection {
+        let connection = ConnectionBuilder::open_memory().build().unwrap();
+        connection
+            .execute("create table value (value integer not null);")
+            .unwrap();
+
+        connection
+    }
+
+    fn insert_value(connection: &SqliteConnection, value: i64) {
+        connection
+            .execute(format!("insert into value values ({value});"))
+            .unwrap();
+    }
+
+    fn get_values(connection: &SqliteConnection) -> Vec<i64> {
+        connection
+            .prepare("select value from value order by value")
+            .unwrap()
+            .iter()
+            .map(|row| row.unwrap().read::<i64, _>(0))
+            .collect()
+    }
+
+    #[test]
+    fn commit_applies_the_writes_of_the_transaction() {
+        let connection = create_connection();
+
+        let transaction = SqliteTransaction::begin(&connection).unwrap();
+        insert_value(&connection, 1);
+        insert_value(&connection, 2);
+        transaction.commit().unwrap();
+
+        assert_eq!(vec![1, 2], get_values(&connection));
+    }
+
+    #[test]
+    fn rollback_discards_the_writes_of_the_transaction() {
+        let connection = create_connection();
+        insert_value(&connection, 1);
+
+        let transaction = SqliteTransaction::begin(&connection).unwrap();
+        insert_value(&connection, 2);
+        transaction.rollback().unwrap();
+
+        assert_eq!(vec![1], get_values(&connection));
+    }
+
+    #[test]
+    fn dropping_an_uncommitted_transaction_discards_its_writes() {
+        let connection = create_connection();
+
+        let write_then_fail = || -> StdResult<()> {
+            let _transaction = SqliteTransaction::begin(&connection)?;
+            insert_value(&connection, 1);
+            Err(anyhow!("failure before the commit"))
+        };
+        write_then_fail().unwrap_err();
+
+        assert!(get_values(&connection).is_empty());
+    }
+
+    #[test]
+    fn nested_transactions_can_be_rolled_back_independently() {
+        let connection = create_connection();
+
+        let transaction = SqliteTransaction::begin(&connection).unwrap();
+        insert_value(&connection, 1);
+        let nested_transaction = SqliteTransaction::begin(&connection).unwrap();
+        insert_value(&connection, 2);
+        nested_transaction.rollback().unwrap();
+        transaction.commit().unwrap();
+
+        assert_eq!(vec![1], get_values(&connection));
+    }
+}
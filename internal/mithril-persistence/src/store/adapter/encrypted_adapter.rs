@@ -0,0 +1,278 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{marker::PhantomData, sync::Arc};
+
+use super::{AdapterError, StoreAdapter};
+use crate::store::StoreCipher;
+
+type Result<T> = std::result::Result<T, AdapterError>;
+
+type InnerAdapter<K> = Box<dyn StoreAdapter<Key = K, Record = Value>>;
+
+/// A [StoreAdapter] that encrypts the records with a [StoreCipher] before handing them to
+/// another adapter, only the keys are stored in clear.
+///
+/// Each record is bound to the name of the store and to its key, so a record moved to another
+/// key or to another store can not be read. The records that are not encrypted, i.e. stored by
+/// the inner adapter before the encryption was enabled, can not be read either.
+pub struct EncryptedStoreAdapter<K, V> {
+    adapter: InnerAdapter<K>,
+    cipher: Arc<StoreCipher>,
+    store_name: String,
+    value: PhantomData<V>,
+}
+
+impl<K, V> EncryptedStoreAdapter<K, V>
+where
+    K: Serialize,
+    V: Serialize + DeserializeOwned,
+{
+    /// EncryptedStoreAdapter factory, the store name must be unique among the stores encrypted
+    /// with the same cipher (e.g. the name of its table).
+    pub fn new(adapter: InnerAdapter<K>, cipher: Arc<StoreCipher>, store_name: &str) -> Self {
+        Self {
+            adapter,
+            cipher,
+            store_name: store_name.to_string(),
+            value: PhantomData,
+        }
+    }
+
+    /// Associated data binding an encrypted record to the store and to the key it's stored with.
+    fn associated_data(&self, key: &K) -> Result<String> {
+        let key = serde_json::to_string(key).map_err(|e| {
+            AdapterError::GeneralError(
+                anyhow!(e).context("Encrypted adapter: could not serialize key"),
+            )
+        })?;
+
+        Ok(format!("{}:{key}", self.store_name))
+    }
+
+    fn encrypt_record(&self, key: &K, record: &V) -> Result<Value> {
+        let json = serde_json::to_string(record).map_err(|e| {
+            AdapterError::GeneralError(
+                anyhow!(e).context("Encrypted adapter: could not serialize record"),
+            )
+        })?;
+        let encrypted = self
+            .cipher
+            .encrypt(&json, &self.associated_data(key)?)
+            .map_err(AdapterError::GeneralError)?;
+
+        Ok(Value::String(encrypted))
+    }
+
+    fn decrypt_record(&self, key: &K, value: Value) -> Result<V> {
+        let Value::String(encrypted) = value else {
+            return Err(AdapterError::ParsingDataError(anyhow!(
+                "Encrypted adapter: the record is not encrypted"
+            )));
+        };
+        let json = self
+            .cipher
+            .decrypt(&encrypted, &self.associated_data(key)?)
+            .map_err(AdapterError::ParsingDataError)?;
+
+        serde_json::from_str(&json).map_err(|e| {
+            AdapterError::ParsingDataError(
+                anyhow!(e).context("Encrypted adapter: could not deserialize record"),
+            )
+        })
+    }
+
+    fn decrypt_maybe_record(&self, key: &K, value: Option<Value>) -> Result<Option<V>> {
+        value
+            .map(|value| self.decrypt_record(key, value))
+            .transpose()
+    }
+}
+
+#[async_trait]
+impl<K, V> StoreAdapter for EncryptedStoreAdapter<K, V>
+where
+    K: Send + Sync + Serialize,
+    V: Send + Sync + Serialize + DeserializeOwned,
+{
+    type Key = K;
+    type Record = V;
+
+    async fn store_record(&mut self, key: &Self::Key, record: &Self::Record) -> Result<()> {
+        let encrypted_record = self.encrypt_record(key, record)?;
+
+        self.adapter.store_record(key, &encrypted_record).await
+    }
+
+    async fn get_record(&self, key: &Self::Key) -> Result<Option<Self::Record>> {
+        let value = self.adapter.get_record(key).await?;
+
+        self.decrypt_maybe_record(key, value)
+    }
+
+    async fn record_exists(&self, key: &Self::Key) -> Result<bool> {
+        self.adapter.record_exists(key).await
+    }
+
+    async fn get_last_n_records(&self, how_many: usize) -> Result<Vec<(Self::Key, Self::Record)>> {
+        self.adapter
+            .get_last_n_records(how_many)
+            .await?
+            .into_iter()
+            .map(|(key, value)| {
+                let record = self.decrypt_record(&key, value)?;
+                Ok((key, record))
+            })
+            .collect()
+    }
+
+    async fn remove(&mut self, key: &Self::Key) -> Result<Option<Self::Record>> {
+        let value = self.adapter.remove(key).await?;
+
+        self.decrypt_maybe_record(key, value)
+    }
+
+    async fn get_iter(&self) -> Result<Box<dyn Iterator<Item = Self::Record> + '_>> {
+        // The keys of the records are needed to decrypt them, so the records are listed with
+        // their keys, from the most recent
+        let records = self
+            .get_last_n_records(usize::MAX)
+            .await?
+            .into_iter()
+            .map(|(_key, record)| record)
+            .collect::<Vec<_>>();
+
+        Ok(Box::new(records.into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::store::adapter::MemoryAdapter;
+
+    use super::*;
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    fn cipher() -> Arc<StoreCipher> {
+        Arc::new(StoreCipher::from_hex_key(KEY).unwrap())
+    }
+
+    fn build_adapter(data: Option<Vec<(u64, Value)>>) -> EncryptedStoreAdapter<u64, String> {
+        EncryptedStoreAdapter::new(
+            Box::new(MemoryAdapter::new(data).unwrap()),
+            cipher(),
+            "store",
+        )
+    }
+
+    #[tokio::test]
+    async fn records_are_encrypted_in_the_inner_adapter() {
+        let mut adapter = build_adapter(None);
+
+        adapter
+            .store_record(&1, &"secret".to_string())
+            .await
+            .unwrap();
+
+        let stored_value = adapter.adapter.get_record(&1).await.unwrap().unwrap();
+        assert!(StoreCipher::is_encrypted(stored_value.as_str().unwrap()));
+        assert!(!stored_value.to_string().contains("secret"));
+        assert_eq!(
+            Some("secret".to_string()),
+            adapter.get_record(&1).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn store_and_read_records() {
+        let mut adapter = build_adapter(None);
+        adapter.store_record(&1, &"one".to_string()).await.unwrap();
+        adapter.store_record(&2, &"two".to_string()).await.unwrap();
+
+        assert!(adapter.record_exists(&1).await.unwrap());
+        assert!(!adapter.record_exists(&3).await.unwrap());
+        assert_eq!(
+            Some("two".to_string()),
+            adapter.get_record(&2).await.unwrap()
+        );
+        assert_eq!(
+            vec![(2, "two".to_string()), (1, "one".to_string())],
+            adapter.get_last_n_records(2).await.unwrap()
+        );
+        assert_eq!(
+            vec!["two".to_string(), "one".to_string()],
+            adapter.get_iter().await.unwrap().collect::<Vec<_>>()
+        );
+        assert_eq!(Some("one".to_string()), adapter.remove(&1).await.unwrap());
+        assert_eq!(None, adapter.get_record(&1).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn records_stored_in_clear_are_not_readable() {
+        let adapter = build_adapter(Some(vec![(1, json!("stored in clear"))]));
+
+        adapter
+            .get_record(&1)
+            .await
+            .expect_err("Reading a record stored in clear should fail");
+    }
+
+    #[tokio::test]
+    async fn records_moved_to_another_key_are_not_readable() {
+        let mut adapter = build_adapter(None);
+        adapter
+            .store_record(&1, &"secret".to_string())
+            .await
+            .unwrap();
+        let encrypted_value = adapter.adapter.get_record(&1).await.unwrap().unwrap();
+        adapter
+            .adapter
+            .store_record(&2, &encrypted_value)
+            .await
+            .unwrap();
+
+        adapter
+            .get_record(&2)
+            .await
+            .expect_err("Reading a record moved to another key should fail");
+    }
+
+    #[tokio::test]
+    async fn records_moved_to_another_store_are_not_readable() {
+        let mut adapter = build_adapter(None);
+        adapter
+            .store_record(&1, &"secret".to_string())
+            .await
+            .unwrap();
+        let encrypted_value = adapter.adapter.get_record(&1).await.unwrap().unwrap();
+        let other_store_adapter: EncryptedStoreAdapter<u64, String> = EncryptedStoreAdapter::new(
+            Box::new(MemoryAdapter::new(Some(vec![(1, encrypted_value)])).unwrap()),
+            cipher(),
+            "other_store",
+        );
+
+        other_store_adapter
+            .get_record(&1)
+            .await
+            .expect_err("Reading a record moved to another store should fail");
+    }
+
+    #[tokio::test]
+    async fn reading_records_encrypted_with_another_key_fails() {
+        let other_cipher = StoreCipher::from_hex_key(
+            "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100",
+        )
+        .unwrap();
+        let encrypted_value = other_cipher.encrypt("\"secret\"", "store:1").unwrap();
+        let adapter = build_adapter(Some(vec![(1, Value::String(encrypted_value))]));
+
+        adapter
+            .get_record(&1)
+            .await
+            .expect_err("Reading a record encrypted with another key should fail");
+    }
+}
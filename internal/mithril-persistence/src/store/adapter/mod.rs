@@ -1,10 +1,15 @@
 //! Define a generic way to store data with the [Store Adapter][store_adapter::StoreAdapter], with
 //! an adapter [in memory][MemoryAdapter] and another [sqlite][SQLiteAdapter].
+//!
+//! The records of any adapter can be encrypted at rest by wrapping it in an
+//! [EncryptedStoreAdapter].
 
+mod encrypted_adapter;
 mod memory_adapter;
 mod sqlite_adapter;
 mod store_adapter;
 
+pub use encrypted_adapter::EncryptedStoreAdapter;
 pub use memory_adapter::MemoryAdapter;
 pub use sqlite_adapter::{SQLiteAdapter, SQLiteResultIterator};
 pub use store_adapter::*;
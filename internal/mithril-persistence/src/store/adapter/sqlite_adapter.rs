@@ -297,6 +297,8 @@ mod tests {
     use sqlite::Value;
     use std::path::{Path, PathBuf};
 
+    use crate::sqlite::ConnectionBuilder;
+
     use super::*;
 
     const TABLE_NAME: &str = "key_value_store";
@@ -307,7 +309,7 @@ mod tests {
 
     fn init_db(db_path: &Path, tablename: Option<&str>) -> SQLiteAdapter<u64, String> {
         let tablename = tablename.unwrap_or(TABLE_NAME);
-        let connection = Connection::open_thread_safe(db_path).unwrap();
+        let connection = ConnectionBuilder::open_file(db_path).build().unwrap();
 
         SQLiteAdapter::new(tablename, Arc::new(connection)).unwrap()
     }
@@ -0,0 +1,230 @@
+use anyhow::{anyhow, Context};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand_core::{OsRng, RngCore};
+
+use mithril_common::StdResult;
+
+/// Prefix of the values encrypted by a [StoreCipher]
+const ENCRYPTED_VALUE_PREFIX: &str = "mithril-encrypted:v1:";
+
+/// Size in bytes of the encryption key
+pub const STORE_CIPHER_KEY_SIZE: usize = 32;
+
+/// Size in bytes of the nonce prepended to the encrypted values
+const NONCE_SIZE: usize = 12;
+
+/// Encrypts the sensitive values persisted by the stores (ChaCha20-Poly1305 with a random nonce
+/// per value).
+///
+/// An encrypted value is a text of the form `mithril-encrypted:v1:<hex(nonce || ciphertext)>`,
+/// so that it can be stored in any text or json column. Values that are not encrypted are
+/// rejected when decrypted, so that they can not be substituted to the encrypted ones.
+///
+/// Each value is encrypted with associated data that identifies where it's stored (e.g. the
+/// table, the column and the primary key of its row): a value can only be decrypted with the
+/// same associated data, so it can not be moved to another row.
+pub struct StoreCipher {
+    cipher: ChaCha20Poly1305,
+}
+
+impl StoreCipher {
+    /// Create a cipher from a hex encoded 32 bytes key (e.g. generated with `openssl rand -hex 32`).
+    pub fn from_hex_key(hex_key: &str) -> StdResult<Self> {
+        let key = hex::decode(hex_key.trim())
+            .with_context(|| "Store encryption key must be hex encoded")?;
+        let key: [u8; STORE_CIPHER_KEY_SIZE] = key.try_into().map_err(|key: Vec<u8>| {
+            anyhow!(
+                "Store encryption key must be {STORE_CIPHER_KEY_SIZE} bytes long, got {} bytes",
+                key.len()
+            )
+        })?;
+
+        Ok(Self::from_key(&key))
+    }
+
+    /// Create a cipher from a 32 bytes key, i.e. derived from a passphrase.
+    pub fn from_key(key: &[u8; STORE_CIPHER_KEY_SIZE]) -> Self {
+        Self {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(key)),
+        }
+    }
+
+    /// Check if the given value has been encrypted by a [StoreCipher].
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(ENCRYPTED_VALUE_PREFIX)
+    }
+
+    /// Encrypt the given value, bound to the given associated data.
+    pub fn encrypt(&self, value: &str, associated_data: &str) -> StdResult<String> {
+        let mut nonce = [0u8; NONCE_SIZE];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: value.as_bytes(),
+                    aad: associated_data.as_bytes(),
+                },
+            )
+            .map_err(|e| anyhow!("Store value encryption failed: {e}"))?;
+
+        Ok(format!(
+            "{ENCRYPTED_VALUE_PREFIX}{}{}",
+            hex::encode(nonce),
+            hex::encode(ciphertext)
+        ))
+    }
+
+    /// Decrypt the given value with the associated data it was encrypted with.
+    ///
+    /// Fails if the value is not encrypted, if it was tampered or if it was encrypted with
+    /// another key or other associated data.
+    pub fn decrypt(&self, value: &str, associated_data: &str) -> StdResult<String> {
+        let Some(encoded) = value.strip_prefix(ENCRYPTED_VALUE_PREFIX) else {
+            return Err(anyhow!("Store value is not encrypted"));
+        };
+        let bytes =
+            hex::decode(encoded).with_context(|| "Encrypted store value must be hex encoded")?;
+        if bytes.len() < NONCE_SIZE {
+            return Err(anyhow!("Encrypted store value is too short"));
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_SIZE);
+        let plaintext = self
+            .cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: associated_data.as_bytes(),
+                },
+            )
+            .map_err(|e| {
+                anyhow!("Store value decryption failed, is the encryption key the right one and was the value stored at this location? {e}")
+            })?;
+
+        String::from_utf8(plaintext).with_context(|| "Decrypted store value is not valid UTF-8")
+    }
+
+    /// Encrypt the given optional value, bound to the given associated data.
+    pub fn encrypt_option(
+        &self,
+        value: Option<&str>,
+        associated_data: &str,
+    ) -> StdResult<Option<String>> {
+        value
+            .map(|value| self.encrypt(value, associated_data))
+            .transpose()
+    }
+
+    /// Decrypt the given optional value with the associated data it was encrypted with.
+    pub fn decrypt_option(
+        &self,
+        value: Option<&str>,
+        associated_data: &str,
+    ) -> StdResult<Option<String>> {
+        value
+            .map(|value| self.decrypt(value, associated_data))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+    const OTHER_KEY: &str = "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100";
+
+    #[test]
+    fn key_must_be_a_32_bytes_hex_string() {
+        StoreCipher::from_hex_key(KEY).expect("A 32 bytes hex key should be valid");
+        StoreCipher::from_hex_key("not-hex").expect_err("A non hex key should be rejected");
+        StoreCipher::from_hex_key("00010203")
+            .expect_err("A key shorter than 32 bytes should be rejected");
+    }
+
+    #[test]
+    fn encrypted_value_can_be_decrypted() {
+        let cipher = StoreCipher::from_hex_key(KEY).unwrap();
+
+        let encrypted = cipher.encrypt("sensitive value", "row-1").unwrap();
+
+        assert!(StoreCipher::is_encrypted(&encrypted));
+        assert!(!encrypted.contains("sensitive value"));
+        assert_eq!(
+            "sensitive value",
+            cipher.decrypt(&encrypted, "row-1").unwrap()
+        );
+    }
+
+    #[test]
+    fn encrypting_twice_the_same_value_gives_different_results() {
+        let cipher = StoreCipher::from_hex_key(KEY).unwrap();
+
+        assert_ne!(
+            cipher.encrypt("sensitive value", "row-1").unwrap(),
+            cipher.encrypt("sensitive value", "row-1").unwrap()
+        );
+    }
+
+    #[test]
+    fn decrypting_a_not_encrypted_value_fails() {
+        let cipher = StoreCipher::from_hex_key(KEY).unwrap();
+
+        assert!(!StoreCipher::is_encrypted("plain value"));
+        cipher
+            .decrypt("plain value", "row-1")
+            .expect_err("Decrypting a value that is not encrypted should fail");
+    }
+
+    #[test]
+    fn decrypting_with_another_key_fails() {
+        let encrypted = StoreCipher::from_hex_key(KEY)
+            .unwrap()
+            .encrypt("sensitive value", "row-1")
+            .unwrap();
+
+        StoreCipher::from_hex_key(OTHER_KEY)
+            .unwrap()
+            .decrypt(&encrypted, "row-1")
+            .expect_err("Decrypting with another key should fail");
+    }
+
+    #[test]
+    fn decrypting_with_other_associated_data_fails() {
+        let cipher = StoreCipher::from_hex_key(KEY).unwrap();
+        let encrypted = cipher.encrypt("sensitive value", "row-1").unwrap();
+
+        cipher
+            .decrypt(&encrypted, "row-2")
+            .expect_err("Decrypting a value moved to another row should fail");
+    }
+
+    #[test]
+    fn decrypting_a_tampered_value_fails() {
+        let cipher = StoreCipher::from_hex_key(KEY).unwrap();
+        let mut encrypted = cipher.encrypt("sensitive value", "row-1").unwrap();
+        let last_char = if encrypted.ends_with('0') { "1" } else { "0" };
+        encrypted.replace_range(encrypted.len() - 1.., last_char);
+
+        cipher
+            .decrypt(&encrypted, "row-1")
+            .expect_err("Decrypting a tampered value should fail");
+    }
+
+    #[test]
+    fn encrypt_and_decrypt_optional_values() {
+        let cipher = StoreCipher::from_hex_key(KEY).unwrap();
+
+        assert_eq!(None, cipher.encrypt_option(None, "row-1").unwrap());
+        let encrypted = cipher.encrypt_option(Some("value"), "row-1").unwrap();
+        assert_eq!(
+            Some("value".to_string()),
+            cipher
+                .decrypt_option(encrypted.as_deref(), "row-1")
+                .unwrap()
+        );
+    }
+}
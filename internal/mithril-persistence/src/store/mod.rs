@@ -1,9 +1,13 @@
 //! Define a generic way to store data with the [Store Adapters][adapter], and the [StakeStorer]
 //! to store stakes.
+//!
+//! The sensitive values can be encrypted at rest with a [StoreCipher].
 
 pub mod adapter;
+mod cipher;
 mod stake_store;
 mod store_pruner;
 
+pub use cipher::{StoreCipher, STORE_CIPHER_KEY_SIZE};
 pub use stake_store::{StakeStore, StakeStorer};
 pub use store_pruner::StorePruner;
@@ -44,8 +44,7 @@ pub trait StorePruner {
 mod tests {
     use std::{cmp::min, sync::Arc};
 
-    use sqlite::Connection;
-
+    use crate::sqlite::ConnectionBuilder;
     use crate::store::adapter::SQLiteAdapter;
 
     use super::*;
@@ -83,7 +82,7 @@ mod tests {
     }
 
     async fn get_adapter(data_len: u64) -> SQLiteAdapter<u64, String> {
-        let connection = Connection::open_thread_safe(":memory:").unwrap();
+        let connection = ConnectionBuilder::open_memory().build().unwrap();
         let mut adapter: SQLiteAdapter<u64, String> =
             SQLiteAdapter::new("whatever", Arc::new(connection)).unwrap();
 
@@ -0,0 +1,356 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use clap::{builder::Resettable, Command, Id, Parser};
+
+use mithril_common::StdResult;
+
+use crate::SecretResolver;
+
+/// Suffix of the environment variables holding the path of a file containing a secret
+const FILE_INDIRECTION_SUFFIX: &str = "_FILE";
+
+/// Resolve the secrets given to a node through its environment variables.
+#[derive(Default)]
+pub struct SecretsEnvironmentResolver {
+    resolvers: Vec<Arc<dyn SecretResolver>>,
+}
+
+impl SecretsEnvironmentResolver {
+    /// Constructs a new `SecretsEnvironmentResolver` that only resolves the `*_FILE` indirections.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a resolver of the secret references with the given scheme.
+    pub fn with_resolver(mut self, resolver: Arc<dyn SecretResolver>) -> Self {
+        self.resolvers.push(resolver);
+        self
+    }
+
+    /// Add the resolvers of the secret managers enabled by the crate features.
+    #[allow(unused_mut)]
+    pub fn with_enabled_resolvers(mut self) -> Self {
+        #[cfg(feature = "vault")]
+        {
+            self = self.with_resolver(Arc::new(crate::VaultResolver::default()));
+        }
+        #[cfg(feature = "gcp_secret_manager")]
+        {
+            self = self.with_resolver(Arc::new(crate::GcpSecretManagerResolver::default()));
+        }
+
+        self
+    }
+
+    /// Resolve the secrets of the environment variables of the current process.
+    ///
+    /// The environment of the process is left untouched (mutating it is unsound once other
+    /// threads may read it): the resolved values must be read from the returned
+    /// [ResolvedEnvironment] when loading the configuration of the node.
+    pub async fn resolve_environment(&self) -> StdResult<ResolvedEnvironment> {
+        let mut variables = Self::environment_variables();
+        let mut secrets = self.resolve_file_indirections(&variables)?;
+        variables.extend(secrets.clone());
+
+        // The references are resolved after the files so the credentials of the secret managers
+        // can themselves be given through files (e.g. `VAULT_TOKEN_FILE`).
+        let referenced_secrets = self.resolve_references(&variables).await?;
+        variables.extend(referenced_secrets.clone());
+        secrets.extend(referenced_secrets);
+
+        Ok(ResolvedEnvironment { variables, secrets })
+    }
+
+    /// Return the variables to add to resolve the `*_FILE` indirections of the given variables.
+    pub fn resolve_file_indirections(
+        &self,
+        variables: &BTreeMap<String, String>,
+    ) -> StdResult<BTreeMap<String, String>> {
+        let mut resolved = BTreeMap::new();
+        for (name, path) in variables {
+            let Some(target) = name.strip_suffix(FILE_INDIRECTION_SUFFIX) else {
+                continue;
+            };
+            if target.is_empty() {
+                continue;
+            }
+            if variables.contains_key(target) {
+                return Err(anyhow!(
+                    "Both '{target}' and '{name}' environment variables are set, only one of them must be given"
+                ));
+            }
+
+            let content = std::fs::read_to_string(Path::new(path)).with_context(|| {
+                format!("Could not read the secret file given by the '{name}' environment variable: '{path}'")
+            })?;
+            resolved.insert(
+                target.to_string(),
+                content.trim_end_matches(['\n', '\r']).to_string(),
+            );
+        }
+
+        Ok(resolved)
+    }
+
+    /// Return the variables to update to resolve the secret references of the given variables.
+    ///
+    /// Values with an unknown scheme (e.g. `https://`) are left untouched.
+    pub async fn resolve_references(
+        &self,
+        variables: &BTreeMap<String, String>,
+    ) -> StdResult<BTreeMap<String, String>> {
+        let mut resolved = BTreeMap::new();
+        for (name, value) in variables {
+            let Some((scheme, reference)) = value.split_once("://") else {
+                continue;
+            };
+            let Some(resolver) = self.resolvers.iter().find(|r| r.scheme() == scheme) else {
+                continue;
+            };
+
+            let secret = resolver
+                .resolve(reference, variables)
+                .await
+                .with_context(|| {
+                    format!(
+                    "Could not resolve the secret referenced by the '{name}' environment variable"
+                )
+                })?;
+            resolved.insert(name.clone(), secret);
+        }
+
+        Ok(resolved)
+    }
+
+    fn environment_variables() -> BTreeMap<String, String> {
+        // Variables with a non unicode name or value can't hold a secret reference and are skipped
+        std::env::vars_os()
+            .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+            .collect()
+    }
+}
+
+/// Environment variables of a node with their secrets resolved.
+///
+/// It does not implement `Debug` so the secrets can't be logged by mistake.
+#[derive(Default, Clone)]
+pub struct ResolvedEnvironment {
+    variables: BTreeMap<String, String>,
+    secrets: BTreeMap<String, String>,
+}
+
+impl ResolvedEnvironment {
+    /// Get the value of the given variable.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(String::as_str)
+    }
+
+    /// Collect all the variables, e.g. to give them as the source of a `config::Environment`.
+    pub fn to_map<M: FromIterator<(String, String)>>(&self) -> M {
+        self.variables.clone().into_iter().collect()
+    }
+
+    /// Make the arguments of the given command, and of its subcommands, read from an environment
+    /// variable holding a secret use the resolved secret instead of the raw variable.
+    pub fn apply_to_command(&self, command: Command) -> Command {
+        let resolved_arguments: Vec<(Id, String)> = command
+            .get_arguments()
+            .filter_map(|argument| {
+                let name = argument.get_env()?.to_str()?;
+                let secret = self.secrets.get(name)?;
+                Some((argument.get_id().clone(), secret.clone()))
+            })
+            .collect();
+        let mut command = command;
+        for (id, secret) in resolved_arguments {
+            command = command.mut_arg(id, |argument| {
+                argument
+                    .env(Resettable::Reset)
+                    .default_value(secret)
+                    .hide_default_value(true)
+            });
+        }
+
+        let subcommands: Vec<String> = command
+            .get_subcommands()
+            .map(|subcommand| subcommand.get_name().to_string())
+            .collect();
+        for name in subcommands {
+            command = command.mut_subcommand(name, |subcommand| self.apply_to_command(subcommand));
+        }
+
+        command
+    }
+
+    /// Parse the command line arguments of the process, the arguments read from an environment
+    /// variable holding a secret use the resolved secret.
+    ///
+    /// Exit the process on error, like [Parser::parse].
+    pub fn parse_arguments<P: Parser>(&self) -> P {
+        let matches = self.apply_to_command(P::command()).get_matches();
+
+        P::from_arg_matches(&matches).unwrap_or_else(|error| error.format(&mut P::command()).exit())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use mithril_common::test_utils::TempDir;
+
+    use super::*;
+
+    struct DummySecretResolver;
+
+    #[async_trait]
+    impl SecretResolver for DummySecretResolver {
+        fn scheme(&self) -> &str {
+            "dummy"
+        }
+
+        async fn resolve(
+            &self,
+            reference: &str,
+            _environment: &BTreeMap<String, String>,
+        ) -> StdResult<String> {
+            match reference {
+                "unknown" => Err(anyhow!("unknown secret")),
+                _ => Ok(format!("secret-{reference}")),
+            }
+        }
+    }
+
+    fn variables(variables: &[(&str, &str)]) -> BTreeMap<String, String> {
+        variables
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn resolve_file_indirections_read_the_secret_files() {
+        let directory = TempDir::create("mithril-secrets", "resolve_file_indirections");
+        let secret_path = directory.join("secret");
+        std::fs::write(&secret_path, "my-secret\n").unwrap();
+
+        let resolved = SecretsEnvironmentResolver::new()
+            .resolve_file_indirections(&variables(&[
+                ("GENESIS_SECRET_KEY_FILE", secret_path.to_str().unwrap()),
+                ("NETWORK", "devnet"),
+            ]))
+            .unwrap();
+
+        assert_eq!(variables(&[("GENESIS_SECRET_KEY", "my-secret")]), resolved);
+    }
+
+    #[test]
+    fn resolve_file_indirections_fails_if_the_target_variable_is_also_set() {
+        SecretsEnvironmentResolver::new()
+            .resolve_file_indirections(&variables(&[
+                ("GENESIS_SECRET_KEY", "my-secret"),
+                ("GENESIS_SECRET_KEY_FILE", "/whatever"),
+            ]))
+            .expect_err("Giving both a variable and its file indirection should fail");
+    }
+
+    #[test]
+    fn resolve_file_indirections_fails_if_the_file_does_not_exist() {
+        SecretsEnvironmentResolver::new()
+            .resolve_file_indirections(&variables(&[(
+                "GENESIS_SECRET_KEY_FILE",
+                "/this/file/does/not/exist",
+            )]))
+            .expect_err("A missing secret file should fail the resolution");
+    }
+
+    #[tokio::test]
+    async fn resolve_references_with_registered_schemes_only() {
+        let resolved = SecretsEnvironmentResolver::new()
+            .with_resolver(Arc::new(DummySecretResolver))
+            .resolve_references(&variables(&[
+                ("GENESIS_SECRET_KEY", "dummy://genesis"),
+                ("AGGREGATOR_ENDPOINT", "https://aggregator.mithril.network"),
+                ("NETWORK", "devnet"),
+            ]))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            variables(&[("GENESIS_SECRET_KEY", "secret-genesis")]),
+            resolved
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_references_fails_if_a_secret_can_not_be_resolved() {
+        SecretsEnvironmentResolver::new()
+            .with_resolver(Arc::new(DummySecretResolver))
+            .resolve_references(&variables(&[("GENESIS_SECRET_KEY", "dummy://unknown")]))
+            .await
+            .expect_err("An unresolvable secret should fail the resolution");
+    }
+
+    fn resolved_environment(
+        variables: &[(&str, &str)],
+        secrets: &[(&str, &str)],
+    ) -> ResolvedEnvironment {
+        ResolvedEnvironment {
+            variables: variables(variables),
+            secrets: variables(secrets),
+        }
+    }
+
+    #[test]
+    fn apply_to_command_use_the_resolved_secrets_in_the_arguments_read_from_the_environment() {
+        let environment = resolved_environment(
+            &[("GENESIS_SECRET_KEY", "my-secret")],
+            &[("GENESIS_SECRET_KEY", "my-secret")],
+        );
+        let command = Command::new("node").subcommand(
+            Command::new("genesis")
+                .arg(
+                    clap::Arg::new("secret")
+                        .long("secret")
+                        .env("GENESIS_SECRET_KEY"),
+                )
+                .arg(clap::Arg::new("network").long("network").env("NETWORK")),
+        );
+
+        let matches = environment
+            .apply_to_command(command)
+            .try_get_matches_from(["node", "genesis", "--network", "devnet"])
+            .unwrap();
+        let (_, genesis_matches) = matches.subcommand().unwrap();
+
+        assert_eq!(
+            Some(&"my-secret".to_string()),
+            genesis_matches.get_one::<String>("secret")
+        );
+    }
+
+    #[test]
+    fn apply_to_command_keep_the_arguments_given_on_the_command_line() {
+        let environment = resolved_environment(
+            &[("GENESIS_SECRET_KEY", "my-secret")],
+            &[("GENESIS_SECRET_KEY", "my-secret")],
+        );
+        let command = Command::new("node").arg(
+            clap::Arg::new("secret")
+                .long("secret")
+                .env("GENESIS_SECRET_KEY"),
+        );
+
+        let matches = environment
+            .apply_to_command(command)
+            .try_get_matches_from(["node", "--secret", "given-secret"])
+            .unwrap();
+
+        assert_eq!(
+            Some(&"given-secret".to_string()),
+            matches.get_one::<String>("secret")
+        );
+    }
+}
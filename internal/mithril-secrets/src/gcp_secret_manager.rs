@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::Value;
+
+use mithril_common::StdResult;
+
+use crate::SecretResolver;
+
+/// Environment variable holding an OAuth 2.0 access token used to authenticate to GCP
+const ACCESS_TOKEN_VARIABLE: &str = "GOOGLE_OAUTH_ACCESS_TOKEN";
+
+/// Endpoint of the metadata server providing the access token of the instance service account
+const METADATA_TOKEN_ENDPOINT: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Endpoint of the GCP Secret Manager API
+const SECRET_MANAGER_ENDPOINT: &str = "https://secretmanager.googleapis.com/v1";
+
+/// A [SecretResolver] that reads the secrets from
+/// [GCP Secret Manager](https://cloud.google.com/secret-manager).
+///
+/// The references are of the form
+/// `gcp-secret-manager://projects/<project>/secrets/<secret>/versions/<version>`.
+///
+/// The access token is read from the `GOOGLE_OAUTH_ACCESS_TOKEN` environment variable, or from
+/// the metadata server of the instance if it's not set.
+#[derive(Default)]
+pub struct GcpSecretManagerResolver {
+    client: reqwest::Client,
+}
+
+impl GcpSecretManagerResolver {
+    async fn access_token(&self, environment: &BTreeMap<String, String>) -> StdResult<String> {
+        if let Some(token) = environment.get(ACCESS_TOKEN_VARIABLE) {
+            return Ok(token.clone());
+        }
+
+        let response = self
+            .client
+            .get(METADATA_TOKEN_ENDPOINT)
+            .header("Metadata-Flavor", "Google")
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| {
+                format!("Could not get a GCP access token: '{ACCESS_TOKEN_VARIABLE}' is not set and the metadata server is not reachable")
+            })?
+            .json::<Value>()
+            .await
+            .with_context(|| "Could not parse the GCP access token from the metadata server")?;
+
+        response["access_token"]
+            .as_str()
+            .map(|token| token.to_string())
+            .ok_or_else(|| anyhow!("No access token in the metadata server response"))
+    }
+
+    fn decode_payload(response: &Value) -> StdResult<String> {
+        let data = response["payload"]["data"]
+            .as_str()
+            .ok_or_else(|| anyhow!("No payload in the GCP Secret Manager response"))?;
+        let payload = STANDARD
+            .decode(data)
+            .with_context(|| "Could not decode the GCP secret payload")?;
+
+        String::from_utf8(payload).with_context(|| "The GCP secret payload is not valid UTF-8")
+    }
+}
+
+#[async_trait]
+impl SecretResolver for GcpSecretManagerResolver {
+    fn scheme(&self) -> &str {
+        "gcp-secret-manager"
+    }
+
+    async fn resolve(
+        &self,
+        reference: &str,
+        environment: &BTreeMap<String, String>,
+    ) -> StdResult<String> {
+        if !reference.starts_with("projects/") || !reference.contains("/secrets/") {
+            return Err(anyhow!(
+                "Invalid GCP secret reference, expected 'projects/<project>/secrets/<secret>/versions/<version>': '{reference}'"
+            ));
+        }
+        let token = self.access_token(environment).await?;
+
+        let response = self
+            .client
+            .get(format!("{SECRET_MANAGER_ENDPOINT}/{reference}:access"))
+            .bearer_auth(token)
+            .send()
+            .await
+            .with_context(|| "Could not reach GCP Secret Manager")?
+            .error_for_status()
+            .with_context(|| format!("Could not access the GCP secret: '{reference}'"))?
+            .json::<Value>()
+            .await
+            .with_context(|| format!("Could not parse the GCP secret: '{reference}'"))?;
+
+        Self::decode_payload(&response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn decode_payload_of_secret_manager_response() {
+        let response = json!({
+            "name": "projects/123/secrets/genesis/versions/1",
+            "payload": { "data": STANDARD.encode("my-secret") }
+        });
+
+        assert_eq!(
+            "my-secret",
+            GcpSecretManagerResolver::decode_payload(&response).unwrap()
+        );
+        GcpSecretManagerResolver::decode_payload(&json!({}))
+            .expect_err("A response without payload should be rejected");
+    }
+
+    #[tokio::test]
+    async fn resolve_rejects_malformed_references() {
+        GcpSecretManagerResolver::default()
+            .resolve("my-secret", &BTreeMap::new())
+            .await
+            .expect_err("A reference that is not a secret version name should be rejected");
+    }
+}
@@ -0,0 +1,30 @@
+#![warn(missing_docs)]
+
+//! Resolution of the secrets given to the configuration of the Mithril nodes.
+//!
+//! The secrets are resolved from the environment variables of the node, before its configuration
+//! is loaded, into a [ResolvedEnvironment] that the node reads its settings from (environment
+//! variables and command line arguments read from the environment); the environment of the
+//! process itself is never updated:
+//! * `*_FILE` indirection: if `FOO_FILE` is set and `FOO` is not, `FOO` is resolved with the
+//!   content of the file at the path given by `FOO_FILE`.
+//! * secret references: if the value of an environment variable is `<scheme>://<reference>` and a
+//!   [SecretResolver] is registered for `<scheme>`, it's replaced with the resolved secret.
+
+mod environment;
+mod resolver;
+mod secret;
+
+#[cfg(feature = "gcp_secret_manager")]
+mod gcp_secret_manager;
+#[cfg(feature = "vault")]
+mod vault;
+
+pub use environment::{ResolvedEnvironment, SecretsEnvironmentResolver};
+pub use resolver::SecretResolver;
+pub use secret::SecretString;
+
+#[cfg(feature = "gcp_secret_manager")]
+pub use gcp_secret_manager::GcpSecretManagerResolver;
+#[cfg(feature = "vault")]
+pub use vault::VaultResolver;
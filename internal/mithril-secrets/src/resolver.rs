@@ -0,0 +1,23 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+
+use mithril_common::StdResult;
+
+/// A resolver of the secrets stored in an external secret manager.
+#[async_trait]
+pub trait SecretResolver: Send + Sync {
+    /// Scheme of the secret references handled by this resolver, ie: `vault` for
+    /// `vault://secret/data/mithril#genesis_secret_key`.
+    fn scheme(&self) -> &str;
+
+    /// Resolve the secret targeted by the given reference (stripped of its `<scheme>://` prefix).
+    ///
+    /// The settings of the resolver (i.e. its credentials) are read from the given environment
+    /// variables, in which the `*_FILE` indirections are already resolved.
+    async fn resolve(
+        &self,
+        reference: &str,
+        environment: &BTreeMap<String, String>,
+    ) -> StdResult<String>;
+}
@@ -0,0 +1,82 @@
+use std::fmt::{Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+
+/// A secret setting of a node (e.g. a token or a key).
+///
+/// Its value is redacted when it's debug formatted, so logging the configuration that holds it
+/// does not leak it.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Constructs a new `SecretString`.
+    pub fn new<T: Into<String>>(value: T) -> Self {
+        Self(value.into())
+    }
+
+    /// Value of the secret, it must not be logged.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Check if the given value is the secret, in a time that does not depend on how much of
+    /// it matches the secret (e.g. to check a token received from a request).
+    pub fn matches(&self, value: &str) -> bool {
+        self.0.as_bytes().ct_eq(value.as_bytes()).into()
+    }
+}
+
+impl Debug for SecretString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretString(<redacted>)")
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_format_redacts_the_secret() {
+        let secret = SecretString::new("my-secret");
+
+        assert_eq!("SecretString(<redacted>)", format!("{secret:?}"));
+        assert_eq!(
+            "Some(SecretString(<redacted>))",
+            format!("{:?}", Some(secret))
+        );
+    }
+
+    #[test]
+    fn matches_only_the_exact_secret() {
+        let secret = SecretString::new("my-secret");
+
+        assert!(secret.matches("my-secret"));
+        assert!(!secret.matches("my-secreT"));
+        assert!(!secret.matches("my-secret-longer"));
+        assert!(!secret.matches(""));
+    }
+
+    #[test]
+    fn serialize_and_deserialize_as_a_string() {
+        let secret: SecretString = serde_json::from_str(r#""my-secret""#).unwrap();
+
+        assert_eq!("my-secret", secret.expose());
+        assert_eq!(r#""my-secret""#, serde_json::to_string(&secret).unwrap());
+    }
+}
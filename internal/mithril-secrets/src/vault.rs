@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use serde_json::Value;
+
+use mithril_common::StdResult;
+
+use crate::SecretResolver;
+
+/// Environment variable holding the address of the Vault server
+const VAULT_ADDRESS_VARIABLE: &str = "VAULT_ADDR";
+
+/// Environment variable holding the token used to authenticate to the Vault server
+const VAULT_TOKEN_VARIABLE: &str = "VAULT_TOKEN";
+
+/// A [SecretResolver] that reads the secrets from the key value secrets engine of a
+/// [Vault](https://developer.hashicorp.com/vault) server.
+///
+/// The references are of the form `vault://<path>#<field>`, e.g.
+/// `vault://secret/data/mithril/aggregator#genesis_secret_key`.
+///
+/// The address of the server and the authentication token are read from the `VAULT_ADDR` and
+/// `VAULT_TOKEN` environment variables (or their `*_FILE` indirections) when a secret is resolved.
+#[derive(Default)]
+pub struct VaultResolver {
+    client: reqwest::Client,
+}
+
+impl VaultResolver {
+    fn read_environment_variable<'a>(
+        environment: &'a BTreeMap<String, String>,
+        name: &str,
+    ) -> StdResult<&'a str> {
+        environment.get(name).map(String::as_str).with_context(|| {
+            format!("The '{name}' environment variable is required to resolve Vault secrets")
+        })
+    }
+
+    fn parse_reference(reference: &str) -> StdResult<(&str, &str)> {
+        match reference.split_once('#') {
+            Some((path, field)) if !path.is_empty() && !field.is_empty() => Ok((path, field)),
+            _ => Err(anyhow!(
+                "Invalid Vault secret reference, expected '<path>#<field>': '{reference}'"
+            )),
+        }
+    }
+
+    fn extract_field(response: &Value, field: &str) -> Option<String> {
+        // KV version 2 nests the secret in a `data` object, version 1 does not
+        let data = &response["data"];
+        let value = data["data"].get(field).or_else(|| data.get(field))?;
+
+        match value {
+            Value::String(value) => Some(value.clone()),
+            Value::Null => None,
+            value => Some(value.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl SecretResolver for VaultResolver {
+    fn scheme(&self) -> &str {
+        "vault"
+    }
+
+    async fn resolve(
+        &self,
+        reference: &str,
+        environment: &BTreeMap<String, String>,
+    ) -> StdResult<String> {
+        let (path, field) = Self::parse_reference(reference)?;
+        let address = Self::read_environment_variable(environment, VAULT_ADDRESS_VARIABLE)?;
+        let token = Self::read_environment_variable(environment, VAULT_TOKEN_VARIABLE)?;
+
+        let response = self
+            .client
+            .get(format!(
+                "{}/v1/{}",
+                address.trim_end_matches('/'),
+                path.trim_start_matches('/')
+            ))
+            .header("X-Vault-Token", token)
+            .send()
+            .await
+            .with_context(|| format!("Could not reach the Vault server: '{address}'"))?
+            .error_for_status()
+            .with_context(|| format!("Could not read the Vault secret: '{path}'"))?
+            .json::<Value>()
+            .await
+            .with_context(|| format!("Could not parse the Vault secret: '{path}'"))?;
+
+        Self::extract_field(&response, field)
+            .ok_or_else(|| anyhow!("Field '{field}' not found in the Vault secret: '{path}'"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_reference_split_path_and_field() {
+        assert_eq!(
+            ("secret/data/mithril", "genesis_secret_key"),
+            VaultResolver::parse_reference("secret/data/mithril#genesis_secret_key").unwrap()
+        );
+
+        VaultResolver::parse_reference("secret/data/mithril")
+            .expect_err("A reference without field should be rejected");
+        VaultResolver::parse_reference("secret/data/mithril#")
+            .expect_err("A reference with an empty field should be rejected");
+    }
+
+    #[test]
+    fn extract_field_from_kv_v1_and_v2_responses() {
+        let kv_v1 = json!({ "data": { "genesis_secret_key": "secret-v1" } });
+        let kv_v2 = json!({ "data": { "data": { "genesis_secret_key": "secret-v2" } } });
+
+        assert_eq!(
+            Some("secret-v1".to_string()),
+            VaultResolver::extract_field(&kv_v1, "genesis_secret_key")
+        );
+        assert_eq!(
+            Some("secret-v2".to_string()),
+            VaultResolver::extract_field(&kv_v2, "genesis_secret_key")
+        );
+        assert_eq!(None, VaultResolver::extract_field(&kv_v2, "unknown"));
+    }
+}
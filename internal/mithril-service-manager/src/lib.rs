@@ -0,0 +1,35 @@
+#![warn(missing_docs)]
+
+//! Integration of the Mithril nodes with the service managers that run them.
+//!
+//! A node reports its state through a [ServiceNotifier], so that the service manager knows when
+//! it's really ready (its dependencies are initialized and its database migrations are done)
+//! rather than when its process is started:
+//! * [SystemdNotifier]: `sd_notify` protocol, used when the node is started by systemd with a
+//!   notification socket (`Type=notify` units), including the watchdog keep-alive pings.
+//! * `WindowsServiceNotifier`: status reports to the Windows Service Control Manager, used when
+//!   the node is started as a Windows service (`windows_service` feature).
+//! * [NoServiceNotifier]: used when the node is not run by a service manager.
+
+mod notifier;
+#[cfg(unix)]
+mod systemd;
+#[cfg(all(windows, feature = "windows_service"))]
+pub mod windows;
+
+pub use notifier::{run_watchdog, NoServiceNotifier, ServiceNotifier};
+#[cfg(unix)]
+pub use systemd::SystemdNotifier;
+
+use std::sync::Arc;
+
+/// Build the [ServiceNotifier] of the service manager that started the node, if any, using its
+/// environment variables.
+pub fn notifier_from_environment() -> Arc<dyn ServiceNotifier> {
+    #[cfg(unix)]
+    if let Some(notifier) = SystemdNotifier::from_environment() {
+        return Arc::new(notifier);
+    }
+
+    Arc::new(NoServiceNotifier)
+}
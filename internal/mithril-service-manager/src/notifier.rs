@@ -0,0 +1,150 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+use mithril_common::StdResult;
+
+/// Report the state of a node to the service manager that runs it
+#[async_trait]
+pub trait ServiceNotifier: Send + Sync {
+    /// Notify that the node is ready: its dependencies are initialized and it serves requests.
+    fn notify_ready(&self) -> StdResult<()>;
+
+    /// Notify a free form status of the node.
+    fn notify_status(&self, status: &str) -> StdResult<()>;
+
+    /// Notify that the node is stopping.
+    fn notify_stopping(&self) -> StdResult<()>;
+
+    /// Keep-alive ping of the watchdog of the service manager.
+    fn notify_watchdog(&self) -> StdResult<()>;
+
+    /// Interval after which the service manager considers that the node is stuck if it has not
+    /// received a keep-alive ping, if the watchdog is enabled.
+    fn watchdog_interval(&self) -> Option<Duration>;
+
+    /// Wait until the service manager requests the node to stop.
+    ///
+    /// Never returns if the service manager stops the node with a signal.
+    async fn wait_for_stop_request(&self);
+}
+
+/// [ServiceNotifier] used when the node is not run by a service manager
+pub struct NoServiceNotifier;
+
+#[async_trait]
+impl ServiceNotifier for NoServiceNotifier {
+    fn notify_ready(&self) -> StdResult<()> {
+        Ok(())
+    }
+
+    fn notify_status(&self, _status: &str) -> StdResult<()> {
+        Ok(())
+    }
+
+    fn notify_stopping(&self) -> StdResult<()> {
+        Ok(())
+    }
+
+    fn notify_watchdog(&self) -> StdResult<()> {
+        Ok(())
+    }
+
+    fn watchdog_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    async fn wait_for_stop_request(&self) {
+        std::future::pending().await
+    }
+}
+
+/// Send keep-alive pings to the watchdog of the service manager at half its interval.
+///
+/// Never returns unless a ping fails, and never returns either if the watchdog is disabled.
+pub async fn run_watchdog(notifier: Arc<dyn ServiceNotifier>) -> StdResult<()> {
+    let Some(watchdog_interval) = notifier.watchdog_interval() else {
+        return std::future::pending().await;
+    };
+    let mut interval = tokio::time::interval(watchdog_interval / 2);
+
+    loop {
+        interval.tick().await;
+        notifier.notify_watchdog()?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    struct CountingNotifier {
+        watchdog_interval: Option<Duration>,
+        watchdog_pings: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ServiceNotifier for CountingNotifier {
+        fn notify_ready(&self) -> StdResult<()> {
+            Ok(())
+        }
+
+        fn notify_status(&self, _status: &str) -> StdResult<()> {
+            Ok(())
+        }
+
+        fn notify_stopping(&self) -> StdResult<()> {
+            Ok(())
+        }
+
+        fn notify_watchdog(&self) -> StdResult<()> {
+            self.watchdog_pings.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn watchdog_interval(&self) -> Option<Duration> {
+            self.watchdog_interval
+        }
+
+        async fn wait_for_stop_request(&self) {
+            std::future::pending().await
+        }
+    }
+
+    fn counting_notifier(watchdog_interval: Option<Duration>) -> Arc<CountingNotifier> {
+        Arc::new(CountingNotifier {
+            watchdog_interval,
+            watchdog_pings: AtomicUsize::new(0),
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn watchdog_is_pinged_at_half_its_interval() {
+        let notifier = counting_notifier(Some(Duration::from_secs(10)));
+
+        let _ = tokio::time::timeout(
+            Duration::from_secs(22),
+            run_watchdog(notifier.clone() as Arc<dyn ServiceNotifier>),
+        )
+        .await;
+
+        // Pings at 0s, 5s, 10s, 15s and 20s
+        assert_eq!(5, notifier.watchdog_pings.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn watchdog_is_never_pinged_when_disabled() {
+        let notifier = counting_notifier(None);
+
+        tokio::time::timeout(
+            Duration::from_secs(60),
+            run_watchdog(notifier.clone() as Arc<dyn ServiceNotifier>),
+        )
+        .await
+        .expect_err("a disabled watchdog should never return");
+
+        assert_eq!(0, notifier.watchdog_pings.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,229 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use std::os::unix::net::UnixDatagram;
+use std::time::Duration;
+use tokio::signal::unix::{signal, SignalKind};
+
+use mithril_common::StdResult;
+
+use crate::ServiceNotifier;
+
+/// [ServiceNotifier] that implements the systemd `sd_notify` protocol
+///
+/// The notifications are datagrams sent to the unix socket given by systemd in the
+/// `NOTIFY_SOCKET` environment variable, if its name starts with `@` the socket is in the
+/// abstract namespace (Linux only).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemdNotifier {
+    notify_socket: String,
+    watchdog_interval: Option<Duration>,
+}
+
+impl SystemdNotifier {
+    /// `SystemdNotifier` factory
+    pub fn new(notify_socket: String, watchdog_interval: Option<Duration>) -> Self {
+        Self {
+            notify_socket,
+            watchdog_interval,
+        }
+    }
+
+    /// Build a notifier from the environment variables set by systemd, returns `None` if the
+    /// node is not run by systemd with a notification socket.
+    pub fn from_environment() -> Option<Self> {
+        Self::from_environment_values(
+            std::env::var("NOTIFY_SOCKET").ok(),
+            std::env::var("WATCHDOG_USEC").ok(),
+            std::env::var("WATCHDOG_PID").ok(),
+            std::process::id(),
+        )
+    }
+
+    fn from_environment_values(
+        notify_socket: Option<String>,
+        watchdog_usec: Option<String>,
+        watchdog_pid: Option<String>,
+        pid: u32,
+    ) -> Option<Self> {
+        let notify_socket = notify_socket.filter(|socket| !socket.is_empty())?;
+        // The watchdog is enabled for the process only if it's the one given in `WATCHDOG_PID`,
+        // if set, since the environment variables may have been inherited from a parent process.
+        let is_watchdog_process = watchdog_pid.map_or(true, |watchdog_pid| {
+            watchdog_pid.trim().parse::<u32>().ok() == Some(pid)
+        });
+        let watchdog_interval = watchdog_usec
+            .and_then(|usec| usec.trim().parse::<u64>().ok())
+            .filter(|usec| *usec > 0 && is_watchdog_process)
+            .map(Duration::from_micros);
+
+        Some(Self::new(notify_socket, watchdog_interval))
+    }
+
+    fn notify(&self, state: &str) -> StdResult<()> {
+        let socket = UnixDatagram::unbound()
+            .with_context(|| "systemd notifier can not create a unix datagram socket")?;
+
+        match self.notify_socket.strip_prefix('@') {
+            Some(abstract_name) => send_to_abstract_socket(&socket, abstract_name, state),
+            None => socket
+                .send_to(state.as_bytes(), &self.notify_socket)
+                .map(|_| ()),
+        }
+        .with_context(|| {
+            format!(
+                "systemd notifier can not send '{state}' to socket '{}'",
+                self.notify_socket
+            )
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn send_to_abstract_socket(
+    socket: &UnixDatagram,
+    abstract_name: &str,
+    state: &str,
+) -> std::io::Result<()> {
+    use std::os::linux::net::SocketAddrExt;
+    use std::os::unix::net::SocketAddr;
+
+    let address = SocketAddr::from_abstract_name(abstract_name)?;
+    socket.send_to_addr(state.as_bytes(), &address).map(|_| ())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn send_to_abstract_socket(
+    _socket: &UnixDatagram,
+    abstract_name: &str,
+    _state: &str,
+) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("abstract unix socket '@{abstract_name}' is only supported on Linux"),
+    ))
+}
+
+#[async_trait]
+impl ServiceNotifier for SystemdNotifier {
+    fn notify_ready(&self) -> StdResult<()> {
+        self.notify("READY=1")
+    }
+
+    fn notify_status(&self, status: &str) -> StdResult<()> {
+        // A state is a single line
+        self.notify(&format!("STATUS={}", status.replace('\n', " ")))
+    }
+
+    fn notify_stopping(&self) -> StdResult<()> {
+        self.notify("STOPPING=1")
+    }
+
+    fn notify_watchdog(&self) -> StdResult<()> {
+        self.notify("WATCHDOG=1")
+    }
+
+    fn watchdog_interval(&self) -> Option<Duration> {
+        self.watchdog_interval
+    }
+
+    async fn wait_for_stop_request(&self) {
+        // systemd stops a service with SIGTERM, if it can't be listened to the node is stopped
+        // by the default handler of the signal.
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(_) => std::future::pending().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::TempDir;
+
+    use super::*;
+
+    fn notifier_from_environment_values(
+        notify_socket: Option<&str>,
+        watchdog_usec: Option<&str>,
+        watchdog_pid: Option<&str>,
+    ) -> Option<SystemdNotifier> {
+        SystemdNotifier::from_environment_values(
+            notify_socket.map(str::to_string),
+            watchdog_usec.map(str::to_string),
+            watchdog_pid.map(str::to_string),
+            42,
+        )
+    }
+
+    #[test]
+    fn notifier_is_built_only_if_a_notification_socket_is_given() {
+        assert_eq!(None, notifier_from_environment_values(None, None, None));
+        assert_eq!(None, notifier_from_environment_values(Some(""), None, None));
+        assert_eq!(
+            Some(SystemdNotifier::new("/run/notify".to_string(), None)),
+            notifier_from_environment_values(Some("/run/notify"), None, None)
+        );
+    }
+
+    #[test]
+    fn watchdog_is_enabled_only_for_the_watchdog_process() {
+        let watchdog_interval = |watchdog_usec, watchdog_pid| {
+            notifier_from_environment_values(Some("/run/notify"), watchdog_usec, watchdog_pid)
+                .unwrap()
+                .watchdog_interval
+        };
+
+        assert_eq!(
+            Some(Duration::from_secs(30)),
+            watchdog_interval(Some("30000000"), None)
+        );
+        assert_eq!(
+            Some(Duration::from_secs(30)),
+            watchdog_interval(Some("30000000"), Some("42"))
+        );
+        assert_eq!(None, watchdog_interval(Some("30000000"), Some("1234")));
+        assert_eq!(None, watchdog_interval(Some("0"), None));
+        assert_eq!(None, watchdog_interval(Some("not a number"), None));
+        assert_eq!(None, watchdog_interval(None, None));
+    }
+
+    #[test]
+    fn notifications_are_sent_to_the_notification_socket() {
+        let socket_path = TempDir::create_with_short_path("systemd", "notifications_are_sent")
+            .join("notify.sock");
+        let listener = UnixDatagram::bind(&socket_path).unwrap();
+        let notifier = SystemdNotifier::new(socket_path.to_string_lossy().to_string(), None);
+
+        notifier.notify_ready().unwrap();
+        notifier.notify_status("Importing\ntransactions").unwrap();
+        notifier.notify_watchdog().unwrap();
+        notifier.notify_stopping().unwrap();
+
+        let mut received = vec![];
+        let mut buffer = [0; 256];
+        for _ in 0..4 {
+            let size = listener.recv(&mut buffer).unwrap();
+            received.push(String::from_utf8_lossy(&buffer[..size]).to_string());
+        }
+        assert_eq!(
+            vec![
+                "READY=1",
+                "STATUS=Importing transactions",
+                "WATCHDOG=1",
+                "STOPPING=1"
+            ],
+            received
+        );
+    }
+
+    #[test]
+    fn notification_fails_if_the_socket_does_not_exist() {
+        let notifier = SystemdNotifier::new("/not/existing/notify.sock".to_string(), None);
+
+        notifier
+            .notify_ready()
+            .expect_err("sending to a missing socket should fail");
+    }
+}
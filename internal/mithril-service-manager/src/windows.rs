@@ -0,0 +1,166 @@
+//! Windows service wrapper of the Mithril nodes
+//!
+//! When a node is started by the Service Control Manager, [run_if_started_as_service] connects
+//! it to the dispatcher of the SCM and runs the node in the service main function, with a
+//! [WindowsServiceNotifier] that reports its status to the SCM and is notified of the stop
+//! requests.
+
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use std::ffi::OsString;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::Notify;
+use windows_service::{
+    define_windows_service,
+    service::{
+        ServiceControl, ServiceControlAccept, ServiceExitCode, ServiceState, ServiceStatus,
+        ServiceType,
+    },
+    service_control_handler::{self, ServiceControlHandlerResult, ServiceStatusHandle},
+    service_dispatcher,
+};
+
+use mithril_common::StdResult;
+
+use crate::ServiceNotifier;
+
+/// Windows error returned when the process was not started by the Service Control Manager
+const ERROR_FAILED_SERVICE_CONTROLLER_CONNECT: i32 = 1063;
+
+/// Exit code reported to the SCM when the node stops with an error
+const SERVICE_FAILURE_EXIT_CODE: u32 = 1;
+
+type ServiceRunner = Box<dyn Fn(Arc<dyn ServiceNotifier>) -> StdResult<()> + Send + Sync>;
+
+struct ServiceDefinition {
+    name: String,
+    run: ServiceRunner,
+}
+
+static SERVICE_DEFINITION: OnceLock<ServiceDefinition> = OnceLock::new();
+
+define_windows_service!(ffi_service_main, service_main);
+
+/// Run the node as a Windows service if the process was started by the Service Control Manager.
+///
+/// Returns `false`, without running the node, if the process was not started by the SCM, in
+/// which case the node must be run as a regular process.
+/// Else blocks until the service is stopped and returns `true`.
+pub fn run_if_started_as_service<F>(name: &str, run: F) -> StdResult<bool>
+where
+    F: Fn(Arc<dyn ServiceNotifier>) -> StdResult<()> + Send + Sync + 'static,
+{
+    SERVICE_DEFINITION
+        .set(ServiceDefinition {
+            name: name.to_string(),
+            run: Box::new(run),
+        })
+        .map_err(|_| anyhow!("The Windows service '{name}' is already defined"))?;
+
+    match service_dispatcher::start(name, ffi_service_main) {
+        Ok(()) => Ok(true),
+        Err(windows_service::Error::Winapi(error))
+            if error.raw_os_error() == Some(ERROR_FAILED_SERVICE_CONTROLLER_CONNECT) =>
+        {
+            Ok(false)
+        }
+        Err(error) => Err(anyhow!(error))
+            .with_context(|| format!("Windows service '{name}' dispatcher error")),
+    }
+}
+
+fn service_main(_arguments: Vec<OsString>) {
+    let Some(definition) = SERVICE_DEFINITION.get() else {
+        return;
+    };
+    let Ok(notifier) = WindowsServiceNotifier::register(&definition.name) else {
+        return;
+    };
+    let notifier = Arc::new(notifier);
+
+    let exit_code = match (definition.run)(notifier.clone()) {
+        Ok(()) => ServiceExitCode::Win32(0),
+        Err(error) => {
+            eprintln!("Windows service '{}' error: {error:?}", definition.name);
+            ServiceExitCode::ServiceSpecific(SERVICE_FAILURE_EXIT_CODE)
+        }
+    };
+    let _ = notifier.set_status(ServiceState::Stopped, exit_code);
+}
+
+/// [ServiceNotifier] that reports the status of the node to the Windows Service Control Manager
+pub struct WindowsServiceNotifier {
+    status_handle: ServiceStatusHandle,
+    stop_request: Arc<Notify>,
+}
+
+impl WindowsServiceNotifier {
+    fn register(name: &str) -> StdResult<Self> {
+        let stop_request = Arc::new(Notify::new());
+        let control_stop_request = stop_request.clone();
+        let status_handle = service_control_handler::register(name, move |control| match control {
+            ServiceControl::Stop | ServiceControl::Shutdown => {
+                control_stop_request.notify_one();
+                ServiceControlHandlerResult::NoError
+            }
+            ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+            _ => ServiceControlHandlerResult::NotImplemented,
+        })
+        .with_context(|| format!("Can not register the Windows service '{name}' handler"))?;
+
+        let notifier = Self {
+            status_handle,
+            stop_request,
+        };
+        notifier.set_status(ServiceState::StartPending, ServiceExitCode::Win32(0))?;
+
+        Ok(notifier)
+    }
+
+    fn set_status(&self, state: ServiceState, exit_code: ServiceExitCode) -> StdResult<()> {
+        let controls_accepted = match state {
+            ServiceState::Running => ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
+            _ => ServiceControlAccept::empty(),
+        };
+        self.status_handle
+            .set_service_status(ServiceStatus {
+                service_type: ServiceType::OWN_PROCESS,
+                current_state: state,
+                controls_accepted,
+                exit_code,
+                checkpoint: 0,
+                wait_hint: Duration::default(),
+                process_id: None,
+            })
+            .with_context(|| format!("Can not set the Windows service status to {state:?}"))
+    }
+}
+
+#[async_trait]
+impl ServiceNotifier for WindowsServiceNotifier {
+    fn notify_ready(&self) -> StdResult<()> {
+        self.set_status(ServiceState::Running, ServiceExitCode::Win32(0))
+    }
+
+    fn notify_status(&self, _status: &str) -> StdResult<()> {
+        // The SCM has no free form status
+        Ok(())
+    }
+
+    fn notify_stopping(&self) -> StdResult<()> {
+        self.set_status(ServiceState::StopPending, ServiceExitCode::Win32(0))
+    }
+
+    fn notify_watchdog(&self) -> StdResult<()> {
+        Ok(())
+    }
+
+    fn watchdog_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    async fn wait_for_stop_request(&self) {
+        self.stop_request.notified().await
+    }
+}
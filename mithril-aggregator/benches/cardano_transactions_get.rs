@@ -1,15 +1,14 @@
 use std::sync::Arc;
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
-use sqlite::ConnectionThreadSafe;
 
 use mithril_aggregator::{
     database::repository::CardanoTransactionRepository, services::TransactionStore,
 };
 use mithril_common::{entities::CardanoTransaction, test_utils::TempDir};
-use mithril_persistence::sqlite::ConnectionBuilder;
+use mithril_persistence::sqlite::{ConnectionBuilder, SqliteConnection};
 
-fn cardano_tx_db_connection(db_file_name: &str) -> ConnectionThreadSafe {
+fn cardano_tx_db_connection(db_file_name: &str) -> SqliteConnection {
     let db_path =
         TempDir::create("aggregator_benches", "bench_get_transactions").join(db_file_name);
 
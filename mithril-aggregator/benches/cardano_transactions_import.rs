@@ -1,14 +1,13 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use sqlite::ConnectionThreadSafe;
 use std::sync::Arc;
 
 use mithril_aggregator::{
     database::repository::CardanoTransactionRepository, services::TransactionStore,
 };
 use mithril_common::{entities::CardanoTransaction, test_utils::TempDir};
-use mithril_persistence::sqlite::ConnectionBuilder;
+use mithril_persistence::sqlite::{ConnectionBuilder, SqliteConnection};
 
-fn cardano_tx_db_connection() -> ConnectionThreadSafe {
+fn cardano_tx_db_connection() -> SqliteConnection {
     let db_path =
         TempDir::create("aggregator_benches", "bench_store_transactions").join("cardano_tx.db");
 
@@ -6,15 +6,14 @@ use std::sync::Arc;
 use thiserror::Error;
 
 use crate::{
-    snapshot_uploaders::SnapshotLocation, snapshotter::OngoingSnapshot, SnapshotUploader,
-    Snapshotter,
+    snapshot_uploaders::{SnapshotArchiveType, SnapshotLocation},
+    snapshotter::OngoingSnapshot,
+    SnapshotUploader, Snapshotter,
 };
 
 use super::ArtifactBuilder;
 use mithril_common::{
-    entities::{
-        CardanoDbBeacon, Certificate, CompressionAlgorithm, ProtocolMessagePartKey, Snapshot,
-    },
+    entities::{CardanoDbBeacon, Certificate, CompressionAlgorithm, Snapshot},
     StdResult,
 };
 
@@ -79,14 +78,43 @@ impl CardanoImmutableFilesFullArtifactBuilder {
         Ok(ongoing_snapshot)
     }
 
+    async fn create_ancillary_archive(
+        &self,
+        beacon: &CardanoDbBeacon,
+        snapshot_digest: &str,
+    ) -> StdResult<Option<OngoingSnapshot>> {
+        debug!("CardanoImmutableFilesFullArtifactBuilder: create ancillary archive");
+
+        let snapshotter = self.snapshotter.clone();
+        let archive_name = format!(
+            "{}-e{}-i{}.{}.ancillary.{}",
+            beacon.network,
+            *beacon.epoch,
+            beacon.immutable_file_number,
+            snapshot_digest,
+            self.compression_algorithm.tar_file_extension()
+        );
+        // spawn a separate thread to prevent blocking
+        let ongoing_snapshot =
+            tokio::task::spawn_blocking(move || -> StdResult<Option<OngoingSnapshot>> {
+                snapshotter.snapshot_ancillary(&archive_name)
+            })
+            .await??;
+
+        debug!(" > ancillary archive created: '{:?}'", ongoing_snapshot);
+
+        Ok(ongoing_snapshot)
+    }
+
     async fn upload_snapshot_archive(
         &self,
         ongoing_snapshot: &OngoingSnapshot,
+        archive_type: SnapshotArchiveType,
     ) -> StdResult<Vec<SnapshotLocation>> {
-        debug!("CardanoImmutableFilesFullArtifactBuilder: upload snapshot archive");
+        debug!("CardanoImmutableFilesFullArtifactBuilder: upload snapshot archive"; "archive_type" => ?archive_type);
         let location = self
             .snapshot_uploader
-            .upload_snapshot(ongoing_snapshot.get_file_path())
+            .upload_snapshot(ongoing_snapshot.get_file_path(), archive_type)
             .await;
 
         if let Err(error) = tokio::fs::remove_file(ongoing_snapshot.get_file_path()).await {
@@ -105,6 +133,7 @@ impl CardanoImmutableFilesFullArtifactBuilder {
         ongoing_snapshot: &OngoingSnapshot,
         snapshot_digest: String,
         remote_locations: Vec<String>,
+        ancillary_archive: Option<(&OngoingSnapshot, Vec<String>)>,
     ) -> StdResult<Snapshot> {
         debug!("CardanoImmutableFilesFullArtifactBuilder: create snapshot");
 
@@ -116,6 +145,18 @@ impl CardanoImmutableFilesFullArtifactBuilder {
             self.compression_algorithm,
             &self.cardano_node_version,
         );
+        let snapshot = match ongoing_snapshot.get_file_hash() {
+            Some(archive_hash) => snapshot.with_archive_hash(archive_hash.to_owned()),
+            None => snapshot,
+        };
+        let snapshot = match ancillary_archive {
+            Some((ongoing_ancillary_snapshot, ancillary_locations)) => snapshot
+                .with_ancillary_archive(
+                    *ongoing_ancillary_snapshot.get_file_size(),
+                    ancillary_locations,
+                ),
+            None => snapshot,
+        };
 
         Ok(snapshot)
     }
@@ -130,7 +171,7 @@ impl ArtifactBuilder<CardanoDbBeacon, Snapshot> for CardanoImmutableFilesFullArt
     ) -> StdResult<Snapshot> {
         let snapshot_digest = certificate
             .protocol_message
-            .get_message_part(&ProtocolMessagePartKey::SnapshotDigest)
+            .snapshot_digest()
             .ok_or_else(|| {
                 CardanoImmutableFilesFullArtifactError::MissingProtocolMessage(beacon.clone())
             })?
@@ -143,14 +184,40 @@ impl ArtifactBuilder<CardanoDbBeacon, Snapshot> for CardanoImmutableFilesFullArt
                 "Cardano Immutable Files Full Artifact Builder can not create snapshot archive"
             })?;
         let locations = self
-            .upload_snapshot_archive(&ongoing_snapshot)
+            .upload_snapshot_archive(&ongoing_snapshot, SnapshotArchiveType::Snapshot)
             .await
             .with_context(|| {
                 format!("Cardano Immutable Files Full Artifact Builder can not upload snapshot archive to path: '{:?}'", ongoing_snapshot.get_file_path())
             })?;
 
+        let ongoing_ancillary_snapshot = self
+            .create_ancillary_archive(&beacon, &snapshot_digest)
+            .await
+            .with_context(|| {
+                "Cardano Immutable Files Full Artifact Builder can not create ancillary archive"
+            })?;
+        let ancillary_locations = match &ongoing_ancillary_snapshot {
+            Some(ongoing_ancillary_snapshot) => Some(
+                self.upload_snapshot_archive(
+                    ongoing_ancillary_snapshot,
+                    SnapshotArchiveType::Ancillary,
+                )
+                    .await
+                    .with_context(|| {
+                        format!("Cardano Immutable Files Full Artifact Builder can not upload ancillary archive to path: '{:?}'", ongoing_ancillary_snapshot.get_file_path())
+                    })?,
+            ),
+            None => None,
+        };
+
         let snapshot = self
-            .create_snapshot(beacon, &ongoing_snapshot, snapshot_digest, locations)
+            .create_snapshot(
+                beacon,
+                &ongoing_snapshot,
+                snapshot_digest,
+                locations,
+                ongoing_ancillary_snapshot.as_ref().zip(ancillary_locations),
+            )
             .await?;
 
         Ok(snapshot)
@@ -173,10 +240,7 @@ mod tests {
     async fn should_compute_valid_artifact() {
         let beacon = fake_data::beacon();
         let certificate = fake_data::certificate("certificate-123".to_string());
-        let snapshot_digest = certificate
-            .protocol_message
-            .get_message_part(&ProtocolMessagePartKey::SnapshotDigest)
-            .unwrap();
+        let snapshot_digest = certificate.protocol_message.snapshot_digest().unwrap();
 
         let dumb_snapshotter = Arc::new(DumbSnapshotter::new());
         let dumb_snapshot_uploader = Arc::new(DumbSnapshotUploader::new());
@@ -212,6 +276,68 @@ mod tests {
         assert_eq!(artifact_expected, artifact);
     }
 
+    #[tokio::test]
+    async fn should_compute_artifact_with_ancillary_archive_if_the_snapshotter_creates_one() {
+        let beacon = CardanoDbBeacon::new("network".to_string(), 20, 145);
+        let certificate = fake_data::certificate("certificate-123".to_string());
+        let snapshot_digest = certificate.protocol_message.snapshot_digest().unwrap();
+
+        let cardano_immutable_files_full_artifact_builder =
+            CardanoImmutableFilesFullArtifactBuilder::new(
+                &Version::parse("1.0.0").unwrap(),
+                Arc::new(DumbSnapshotter::new().with_separate_ancillary_archive()),
+                Arc::new(DumbSnapshotUploader::new()),
+                CompressionAlgorithm::Gzip,
+            );
+        let artifact = cardano_immutable_files_full_artifact_builder
+            .compute_artifact(beacon.clone(), &certificate)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![format!(
+                "{}-e{}-i{}.{snapshot_digest}.tar.gz",
+                beacon.network, *beacon.epoch, beacon.immutable_file_number,
+            )],
+            artifact.locations
+        );
+        assert_eq!(Some(0), artifact.ancillary_size);
+        assert_eq!(
+            Some(vec![format!(
+                "{}-e{}-i{}.{snapshot_digest}.ancillary.tar.gz",
+                beacon.network, *beacon.epoch, beacon.immutable_file_number,
+            )]),
+            artifact.ancillary_locations
+        );
+    }
+
+    #[tokio::test]
+    async fn should_compute_artifact_with_the_hash_of_the_snapshot_archive() {
+        let cardano_immutable_files_full_artifact_builder =
+            CardanoImmutableFilesFullArtifactBuilder::new(
+                &Version::parse("1.0.0").unwrap(),
+                Arc::new(DumbSnapshotter::new()),
+                Arc::new(DumbSnapshotUploader::new()),
+                CompressionAlgorithm::Gzip,
+            );
+        let ongoing_snapshot =
+            OngoingSnapshot::new(Path::new("archive.tar.gz").to_path_buf(), 7331)
+                .with_file_hash("archive_hash".to_string());
+
+        let artifact = cardano_immutable_files_full_artifact_builder
+            .create_snapshot(
+                fake_data::beacon(),
+                &ongoing_snapshot,
+                "snapshot_digest".to_string(),
+                vec!["archive_location".to_string()],
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(Some("archive_hash".to_string()), artifact.archive_hash);
+    }
+
     #[tokio::test]
     async fn remove_snapshot_archive_after_upload() {
         let file = NamedTempFile::new().unwrap();
@@ -227,7 +353,7 @@ mod tests {
             );
 
         cardano_immutable_files_full_artifact_builder
-            .upload_snapshot_archive(&snapshot)
+            .upload_snapshot_archive(&snapshot, SnapshotArchiveType::Snapshot)
             .await
             .expect("Snapshot upload should not fail");
 
@@ -307,7 +433,7 @@ mod tests {
         let mut snapshot_uploader = MockSnapshotUploader::new();
         snapshot_uploader
             .expect_upload_snapshot()
-            .return_once(|_| Err(anyhow!("an error")))
+            .return_once(|_, _| Err(anyhow!("an error")))
             .once();
 
         let cardano_immutable_files_full_artifact_builder =
@@ -319,7 +445,7 @@ mod tests {
             );
 
         cardano_immutable_files_full_artifact_builder
-            .upload_snapshot_archive(&snapshot)
+            .upload_snapshot_archive(&snapshot, SnapshotArchiveType::Snapshot)
             .await
             .expect_err("Snapshot upload should have failed");
 
@@ -3,10 +3,7 @@ use async_trait::async_trait;
 use super::ArtifactBuilder;
 use anyhow::{anyhow, Context};
 use mithril_common::{
-    entities::{
-        CardanoDbBeacon, CardanoTransactionsSnapshot, Certificate, ProtocolMessagePartKey,
-        SignedEntityType,
-    },
+    entities::{CardanoDbBeacon, CardanoTransactionsSnapshot, Certificate, SignedEntityType},
     StdResult,
 };
 
@@ -31,7 +28,7 @@ impl ArtifactBuilder<CardanoDbBeacon, CardanoTransactionsSnapshot>
     ) -> StdResult<CardanoTransactionsSnapshot> {
         let merkle_root = certificate
             .protocol_message
-            .get_message_part(&ProtocolMessagePartKey::CardanoTransactionsMerkleRoot)
+            .cardano_transactions_merkle_root()
             .ok_or(anyhow!(
                 "Can not find CardanoTransactionsMerkleRoot protocol message part in certificate"
             ))
@@ -52,7 +49,7 @@ impl ArtifactBuilder<CardanoDbBeacon, CardanoTransactionsSnapshot>
 #[cfg(test)]
 mod tests {
     use mithril_common::{
-        entities::ProtocolMessage,
+        entities::{ProtocolMessage, ProtocolMessagePartKey},
         test_utils::fake_data::{self},
     };
 
@@ -0,0 +1,132 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use mithril_common::StdResult;
+use slog_scope::debug;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::artifact_storage::{ArtifactLocation, ArtifactStorage};
+use crate::tools::RemoteFileUploader;
+
+/// GcpArtifactStorage stores the artifacts files in a Google Cloud Storage bucket
+pub struct GcpArtifactStorage {
+    bucket: String,
+    file_uploader: Box<dyn RemoteFileUploader>,
+    use_cdn_domain: bool,
+}
+
+impl GcpArtifactStorage {
+    /// GcpArtifactStorage factory
+    pub fn new(
+        file_uploader: Box<dyn RemoteFileUploader>,
+        bucket: String,
+        use_cdn_domain: bool,
+    ) -> Self {
+        debug!("New GcpArtifactStorage created"; "bucket" => &bucket);
+        Self {
+            bucket,
+            file_uploader,
+            use_cdn_domain,
+        }
+    }
+}
+
+#[async_trait]
+impl ArtifactStorage for GcpArtifactStorage {
+    async fn store(&self, filepath: &Path) -> StdResult<ArtifactLocation> {
+        let file_name = filepath
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| anyhow!("Invalid file name: '{}'", filepath.display()))?;
+        self.file_uploader.upload_file(filepath).await?;
+
+        Ok(self.location(file_name))
+    }
+
+    fn location(&self, file_name: &str) -> ArtifactLocation {
+        if self.use_cdn_domain {
+            format!("https://{}/{}", self.bucket, file_name)
+        } else {
+            format!(
+                "https://storage.googleapis.com/{}/{}",
+                self.bucket, file_name
+            )
+        }
+    }
+
+    async fn signed_location(
+        &self,
+        _file_name: &str,
+        _expires_in: Duration,
+    ) -> StdResult<Option<ArtifactLocation>> {
+        // Signing GCS URLs requires the private key of a service account, the buckets accessed
+        // with HMAC keys can use the `s3` storage instead
+        Ok(None)
+    }
+
+    fn local_directory(&self) -> Option<PathBuf> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::MockRemoteFileUploader;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_store_not_using_cdn_domain_ok() {
+        let mut file_uploader = MockRemoteFileUploader::new();
+        file_uploader.expect_upload_file().returning(|_| Ok(()));
+        let storage = GcpArtifactStorage::new(
+            Box::new(file_uploader),
+            "cardano-testnet".to_string(),
+            false,
+        );
+
+        let location = storage
+            .store(Path::new("test/snapshot.xxx.tar.gz"))
+            .await
+            .expect("remote upload should not fail");
+
+        assert_eq!(
+            "https://storage.googleapis.com/cardano-testnet/snapshot.xxx.tar.gz",
+            location
+        );
+    }
+
+    #[tokio::test]
+    async fn test_store_using_cdn_domain_ok() {
+        let mut file_uploader = MockRemoteFileUploader::new();
+        file_uploader.expect_upload_file().returning(|_| Ok(()));
+        let storage = GcpArtifactStorage::new(
+            Box::new(file_uploader),
+            "cdn.mithril.network".to_string(),
+            true,
+        );
+
+        let location = storage
+            .store(Path::new("test/snapshot.xxx.tar.gz"))
+            .await
+            .expect("remote upload should not fail");
+
+        assert_eq!("https://cdn.mithril.network/snapshot.xxx.tar.gz", location);
+    }
+
+    #[tokio::test]
+    async fn test_store_ko() {
+        let mut file_uploader = MockRemoteFileUploader::new();
+        file_uploader
+            .expect_upload_file()
+            .returning(|_| Err(anyhow!("unexpected error")));
+        let storage = GcpArtifactStorage::new(Box::new(file_uploader), "".to_string(), false);
+
+        let result = storage
+            .store(Path::new("test/snapshot.xxx.tar.gz"))
+            .await
+            .expect_err("remote upload should fail");
+
+        assert_eq!("unexpected error".to_string(), result.to_string());
+    }
+}
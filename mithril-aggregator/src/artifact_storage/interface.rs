@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use mithril_common::StdResult;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[cfg(test)]
+use mockall::automock;
+
+/// Location where a stored artifact file can be downloaded
+pub type ArtifactLocation = String;
+
+/// ArtifactStorage stores the artifacts files, each file is stored under its file name.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ArtifactStorage: Sync + Send {
+    /// Store a file, returns the location where it can be downloaded
+    async fn store(&self, filepath: &Path) -> StdResult<ArtifactLocation>;
+
+    /// Location where the file stored under the given name can be downloaded
+    fn location(&self, file_name: &str) -> ArtifactLocation;
+
+    /// Time-limited signed URL where the file stored under the given name can be downloaded, if
+    /// the storage supports them.
+    async fn signed_location(
+        &self,
+        file_name: &str,
+        expires_in: Duration,
+    ) -> StdResult<Option<ArtifactLocation>>;
+
+    /// Directory of the stored files if they are stored on the filesystem of the aggregator, in
+    /// which case they are served by the aggregator HTTP server.
+    fn local_directory(&self) -> Option<PathBuf>;
+}
@@ -0,0 +1,191 @@
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use mithril_common::StdResult;
+use slog_scope::debug;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::artifact_storage::{ArtifactLocation, ArtifactStorage};
+use crate::tools::{BandwidthLimiter, FileDigestCache, ThrottledReader};
+
+/// LocalArtifactStorage stores the artifacts files in a directory of the aggregator, they are
+/// served by its HTTP server.
+pub struct LocalArtifactStorage {
+    /// Target folder where to store the files
+    target_location: PathBuf,
+
+    /// URL of the route of the aggregator HTTP server that serves the target folder
+    download_url: String,
+
+    bandwidth_limiter: Option<Arc<BandwidthLimiter>>,
+
+    file_digest_cache: Option<Arc<FileDigestCache>>,
+}
+
+impl LocalArtifactStorage {
+    /// LocalArtifactStorage factory
+    pub fn new(target_location: &Path, download_url: String) -> Self {
+        debug!("New LocalArtifactStorage created"; "download_url" => &download_url);
+        Self {
+            target_location: target_location.to_path_buf(),
+            download_url,
+            bandwidth_limiter: None,
+            file_digest_cache: None,
+        }
+    }
+
+    /// Limit the bandwidth used by the copies with the given [BandwidthLimiter]
+    pub fn with_bandwidth_limiter(mut self, bandwidth_limiter: Arc<BandwidthLimiter>) -> Self {
+        self.bandwidth_limiter = Some(bandwidth_limiter);
+        self
+    }
+
+    /// Compute the digest of the stored files in background with the given [FileDigestCache]
+    pub fn with_file_digest_cache(mut self, file_digest_cache: Arc<FileDigestCache>) -> Self {
+        self.file_digest_cache = Some(file_digest_cache);
+        self
+    }
+
+    async fn copy(&self, source: &Path, target: &Path) -> StdResult<()> {
+        match &self.bandwidth_limiter {
+            None => {
+                tokio::fs::copy(source, target).await?;
+            }
+            Some(bandwidth_limiter) => {
+                let source_file = tokio::fs::File::open(source).await?;
+                let mut target_file = tokio::fs::File::create(target).await?;
+                tokio::io::copy(
+                    &mut ThrottledReader::new(source_file, bandwidth_limiter.clone()),
+                    &mut target_file,
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ArtifactStorage for LocalArtifactStorage {
+    async fn store(&self, filepath: &Path) -> StdResult<ArtifactLocation> {
+        let file_name = filepath
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| anyhow!("Invalid file name: '{}'", filepath.display()))?;
+        let target_path = self.target_location.join(file_name);
+        self.copy(filepath, &target_path).await.with_context(|| {
+            format!(
+                "Could not copy '{}' to '{}'",
+                filepath.display(),
+                target_path.display()
+            )
+        })?;
+        if let Some(file_digest_cache) = &self.file_digest_cache {
+            file_digest_cache.compute_in_background(&target_path);
+        }
+
+        Ok(self.location(file_name))
+    }
+
+    fn location(&self, file_name: &str) -> ArtifactLocation {
+        format!("{}/{}", self.download_url.trim_end_matches('/'), file_name)
+    }
+
+    async fn signed_location(
+        &self,
+        _file_name: &str,
+        _expires_in: Duration,
+    ) -> StdResult<Option<ArtifactLocation>> {
+        // The files are served by the aggregator, there is nothing to sign
+        Ok(None)
+    }
+
+    fn local_directory(&self) -> Option<PathBuf> {
+        Some(self.target_location.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn store_copy_the_file_to_the_target_location() {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let filepath = source_dir.path().join("archive.tar.gz");
+        fs::write(&filepath, "archive content").unwrap();
+        let storage = LocalArtifactStorage::new(
+            target_dir.path(),
+            "http://test.com:8080/aggregator/snapshot_download".to_string(),
+        );
+
+        let location = storage.store(&filepath).await.unwrap();
+
+        assert_eq!(
+            "http://test.com:8080/aggregator/snapshot_download/archive.tar.gz",
+            location
+        );
+        assert_eq!(
+            "archive content",
+            fs::read_to_string(target_dir.path().join("archive.tar.gz")).unwrap()
+        );
+        assert_eq!(
+            Some(target_dir.path().to_path_buf()),
+            storage.local_directory()
+        );
+    }
+
+    #[tokio::test]
+    async fn store_fails_if_the_file_does_not_exist() {
+        let target_dir = tempdir().unwrap();
+        let storage = LocalArtifactStorage::new(target_dir.path(), "http://test.com".to_string());
+
+        storage
+            .store(Path::new("not_existing.tar.gz"))
+            .await
+            .expect_err("storing a missing file should fail");
+    }
+
+    #[tokio::test]
+    async fn store_with_a_bandwidth_limiter_copy_the_whole_file() {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let filepath = source_dir.path().join("archive.tar.gz");
+        fs::write(&filepath, vec![1u8; 2048]).unwrap();
+        let storage = LocalArtifactStorage::new(target_dir.path(), "http://test.com".to_string())
+            .with_bandwidth_limiter(Arc::new(BandwidthLimiter::new(1024 * 1024)));
+
+        storage.store(&filepath).await.unwrap();
+
+        assert_eq!(
+            vec![1u8; 2048],
+            fs::read(target_dir.path().join("archive.tar.gz")).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn store_with_a_file_digest_cache_start_the_computation_of_the_digest() {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let filepath = source_dir.path().join("archive.tar.gz");
+        fs::write(&filepath, "archive content").unwrap();
+        let file_digest_cache = Arc::new(FileDigestCache::new());
+        let storage = LocalArtifactStorage::new(target_dir.path(), "http://test.com".to_string())
+            .with_file_digest_cache(file_digest_cache.clone());
+
+        storage.store(&filepath).await.unwrap();
+
+        assert!(
+            file_digest_cache
+                .compute_in_background(&target_dir.path().join("archive.tar.gz"))
+                .is_none(),
+            "The digest should be computed or in progress"
+        );
+    }
+}
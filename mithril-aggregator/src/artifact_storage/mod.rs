@@ -0,0 +1,12 @@
+//! The module used to store the artifacts files (ie: the snapshot archives) and to build the
+//! locations where they are downloaded.
+
+mod gcp_artifact_storage;
+mod interface;
+mod local_artifact_storage;
+mod s3_artifact_storage;
+
+pub use gcp_artifact_storage::GcpArtifactStorage;
+pub use interface::*;
+pub use local_artifact_storage::LocalArtifactStorage;
+pub use s3_artifact_storage::{S3ArtifactStorage, S3Credentials};
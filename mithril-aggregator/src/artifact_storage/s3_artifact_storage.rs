@@ -0,0 +1,573 @@
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use aws_sdk_s3::config::{
+    BehaviorVersion, Credentials, Region, RequestChecksumCalculation, ResponseChecksumValidation,
+};
+use aws_sdk_s3::presigning::PresigningConfig;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+use aws_sdk_s3::Client;
+use mithril_common::StdResult;
+use reqwest::Url;
+use slog_scope::{debug, info, warn};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+
+use crate::artifact_storage::{ArtifactLocation, ArtifactStorage};
+use crate::tools::BandwidthLimiter;
+
+/// Size of the parts of the multipart uploads when the bandwidth is not limited.
+const MULTIPART_UPLOAD_PART_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Minimum size of the parts of the multipart uploads required by S3 (except for the last one).
+const MULTIPART_UPLOAD_MIN_PART_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Maximum number of parts of a multipart upload allowed by S3.
+const MULTIPART_UPLOAD_MAX_PARTS: u64 = 10_000;
+
+/// Maximum validity of a presigned URL allowed by AWS Signature Version 4 (7 days).
+const MAX_PRESIGNED_URL_EXPIRATION: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Credentials of an S3 compatible storage
+#[derive(Clone)]
+pub struct S3Credentials {
+    access_key_id: String,
+    secret_access_key: String,
+}
+
+impl S3Credentials {
+    /// S3Credentials factory
+    pub fn new(access_key_id: String, secret_access_key: String) -> Self {
+        Self {
+            access_key_id,
+            secret_access_key,
+        }
+    }
+
+    /// Read the credentials from the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`
+    /// environment variables
+    pub fn from_environment() -> StdResult<Self> {
+        let read_variable = |name: &str| {
+            std::env::var(name).with_context(|| format!("Missing {name} environment variable"))
+        };
+
+        Ok(Self::new(
+            read_variable("AWS_ACCESS_KEY_ID")?,
+            read_variable("AWS_SECRET_ACCESS_KEY")?,
+        ))
+    }
+}
+
+impl From<S3Credentials> for Credentials {
+    fn from(credentials: S3Credentials) -> Self {
+        Credentials::new(
+            credentials.access_key_id,
+            credentials.secret_access_key,
+            None,
+            None,
+            "mithril-aggregator",
+        )
+    }
+}
+
+/// S3ArtifactStorage stores the artifacts files in a bucket of an S3 compatible storage (ie: AWS
+/// S3 or MinIO), the files are uploaded with multipart uploads through the AWS SDK.
+///
+/// The bucket is addressed with path style URLs (`{endpoint}/{bucket}/{file_name}`) which are
+/// supported by all the S3 compatible storages.
+/// The files are not made public by the storage, the bucket policy must allow their download.
+pub struct S3ArtifactStorage {
+    bucket: String,
+    public_url: String,
+    client: Client,
+    bandwidth_limiter: Option<Arc<BandwidthLimiter>>,
+}
+
+impl S3ArtifactStorage {
+    /// S3ArtifactStorage factory, the files are downloaded from the public URL if given, or else
+    /// directly from the bucket.
+    pub fn new(
+        endpoint: &str,
+        bucket: String,
+        region: String,
+        public_url: Option<String>,
+        credentials: S3Credentials,
+    ) -> StdResult<Self> {
+        let endpoint = Url::parse(endpoint)
+            .with_context(|| format!("Invalid S3 endpoint URL: '{endpoint}'"))?;
+        endpoint
+            .host_str()
+            .ok_or_else(|| anyhow!("S3 endpoint URL has no host: '{endpoint}'"))?;
+        let endpoint = endpoint.as_str().trim_end_matches('/').to_string();
+        let public_url = public_url.unwrap_or_else(|| format!("{endpoint}/{bucket}"));
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .endpoint_url(&endpoint)
+            .region(Region::new(region))
+            .credentials_provider(Credentials::from(credentials))
+            .force_path_style(true)
+            // The S3 compatible storages do not all support the checksums added by default
+            .request_checksum_calculation(RequestChecksumCalculation::WhenRequired)
+            .response_checksum_validation(ResponseChecksumValidation::WhenRequired)
+            .build();
+        debug!("New S3ArtifactStorage created"; "endpoint" => &endpoint, "bucket" => &bucket);
+
+        Ok(Self {
+            bucket,
+            public_url,
+            client: Client::from_conf(config),
+            bandwidth_limiter: None,
+        })
+    }
+
+    /// Limit the bandwidth used by the uploads with the given [BandwidthLimiter]
+    pub fn with_bandwidth_limiter(mut self, bandwidth_limiter: Arc<BandwidthLimiter>) -> Self {
+        self.bandwidth_limiter = Some(bandwidth_limiter);
+        self
+    }
+
+    /// URL of a `GET` request on the object with the given name, presigned with AWS Signature
+    /// Version 4 query parameters.
+    ///
+    /// The URL targets the endpoint of the storage, not the public URL, since the signature is
+    /// checked by the storage.
+    async fn presigned_url(
+        &self,
+        file_name: &str,
+        expires_in: Duration,
+    ) -> StdResult<ArtifactLocation> {
+        if expires_in.is_zero() || expires_in > MAX_PRESIGNED_URL_EXPIRATION {
+            return Err(anyhow!(
+                "Invalid S3 presigned URL expiration: {}s, it must be between 1s and {}s",
+                expires_in.as_secs(),
+                MAX_PRESIGNED_URL_EXPIRATION.as_secs()
+            ));
+        }
+
+        let request = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(file_name)
+            .presigned(PresigningConfig::expires_in(expires_in)?)
+            .await
+            .with_context(|| format!("S3 presigning failure: '{file_name}'"))?;
+
+        Ok(request.uri().to_string())
+    }
+
+    async fn create_multipart_upload(&self, file_name: &str) -> StdResult<String> {
+        let output = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(file_name)
+            .send()
+            .await
+            .with_context(|| format!("S3 multipart upload creation failure: '{file_name}'"))?;
+
+        output
+            .upload_id
+            .ok_or_else(|| anyhow!("S3 multipart upload response has no 'UploadId'"))
+    }
+
+    /// Upload the file by parts, returns the uploaded parts
+    async fn upload_parts(
+        &self,
+        filepath: &Path,
+        file_name: &str,
+        upload_id: &str,
+    ) -> StdResult<Vec<CompletedPart>> {
+        let mut file = tokio::fs::File::open(filepath)
+            .await
+            .with_context(|| format!("Could not open file: '{}'", filepath.display()))?;
+        let file_size = file
+            .metadata()
+            .await
+            .with_context(|| format!("Could not read file metadata: '{}'", filepath.display()))?
+            .len();
+        let part_size_limit =
+            multipart_upload_part_size(file_size, self.bandwidth_limiter.is_some());
+        let mut parts = vec![];
+
+        loop {
+            let mut part = vec![];
+            (&mut file)
+                .take(part_size_limit)
+                .read_to_end(&mut part)
+                .await
+                .with_context(|| format!("Could not read file: '{}'", filepath.display()))?;
+            // A file is uploaded with at least one part, even if it's empty
+            if part.is_empty() && !parts.is_empty() {
+                break;
+            }
+            let is_last_part = (part.len() as u64) < part_size_limit;
+
+            let part_number = parts.len() as i32 + 1;
+            if let Some(bandwidth_limiter) = &self.bandwidth_limiter {
+                bandwidth_limiter.throttle(part.len() as u64).await;
+            }
+            let output = self
+                .client
+                .upload_part()
+                .bucket(&self.bucket)
+                .key(file_name)
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .body(ByteStream::from(part))
+                .send()
+                .await
+                .with_context(|| {
+                    format!("S3 upload failure of the part {part_number} of '{file_name}'")
+                })?;
+            let etag = output
+                .e_tag
+                .ok_or_else(|| anyhow!("S3 upload part response has no 'ETag'"))?;
+            parts.push(
+                CompletedPart::builder()
+                    .part_number(part_number)
+                    .e_tag(etag)
+                    .build(),
+            );
+
+            if is_last_part {
+                break;
+            }
+        }
+
+        Ok(parts)
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        file_name: &str,
+        upload_id: &str,
+        parts: Vec<CompletedPart>,
+    ) -> StdResult<()> {
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(file_name)
+            .upload_id(upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build(),
+            )
+            .send()
+            .await
+            .with_context(|| format!("S3 multipart upload completion failure: '{file_name}'"))?;
+
+        Ok(())
+    }
+
+    async fn abort_multipart_upload(&self, file_name: &str, upload_id: &str) -> StdResult<()> {
+        self.client
+            .abort_multipart_upload()
+            .bucket(&self.bucket)
+            .key(file_name)
+            .upload_id(upload_id)
+            .send()
+            .await
+            .with_context(|| format!("S3 multipart upload abort failure: '{file_name}'"))?;
+
+        Ok(())
+    }
+
+    async fn upload_and_complete(
+        &self,
+        filepath: &Path,
+        file_name: &str,
+        upload_id: &str,
+    ) -> StdResult<()> {
+        let parts = self.upload_parts(filepath, file_name, upload_id).await?;
+        self.complete_multipart_upload(file_name, upload_id, parts)
+            .await
+    }
+}
+
+#[async_trait]
+impl ArtifactStorage for S3ArtifactStorage {
+    async fn store(&self, filepath: &Path) -> StdResult<ArtifactLocation> {
+        let file_name = filepath
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| anyhow!("Invalid file name: '{}'", filepath.display()))?;
+
+        info!("uploading {file_name} to S3 bucket {}", self.bucket);
+        let upload_id = self.create_multipart_upload(file_name).await?;
+        if let Err(error) = self
+            .upload_and_complete(filepath, file_name, &upload_id)
+            .await
+        {
+            // The parts already uploaded are stored (and billed) until the upload is aborted
+            if let Err(abort_error) = self.abort_multipart_upload(file_name, &upload_id).await {
+                warn!("S3 multipart upload abort failure"; "error" => ?abort_error);
+            }
+            return Err(error.context("S3 uploading failure"));
+        }
+        info!("uploaded {file_name} to S3 bucket {}", self.bucket);
+
+        Ok(self.location(file_name))
+    }
+
+    fn location(&self, file_name: &str) -> ArtifactLocation {
+        format!(
+            "{}/{}",
+            self.public_url.trim_end_matches('/'),
+            uri_encode(file_name)
+        )
+    }
+
+    async fn signed_location(
+        &self,
+        file_name: &str,
+        expires_in: Duration,
+    ) -> StdResult<Option<ArtifactLocation>> {
+        self.presigned_url(file_name, expires_in).await.map(Some)
+    }
+
+    fn local_directory(&self) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// Size of the parts of the multipart upload of a file of the given size.
+///
+/// When the bandwidth is limited the parts are as small as allowed, so the upload is paced in
+/// small bursts, but there are never more parts than allowed by S3.
+fn multipart_upload_part_size(file_size: u64, is_bandwidth_limited: bool) -> u64 {
+    let part_size = if is_bandwidth_limited {
+        MULTIPART_UPLOAD_MIN_PART_SIZE
+    } else {
+        MULTIPART_UPLOAD_PART_SIZE
+    };
+
+    part_size.max(file_size.div_ceil(MULTIPART_UPLOAD_MAX_PARTS))
+}
+
+/// URI encoding of a path: every byte but the unreserved characters and `/` is percent encoded
+fn uri_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::MockServer;
+    use std::fs;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn build_storage(endpoint: &str, public_url: Option<String>) -> S3ArtifactStorage {
+        S3ArtifactStorage::new(
+            endpoint,
+            "snapshots".to_string(),
+            "us-east-1".to_string(),
+            public_url,
+            S3Credentials::new("access_key".to_string(), "secret_key".to_string()),
+        )
+        .unwrap()
+    }
+
+    fn write_archive() -> (tempfile::TempDir, PathBuf) {
+        let source_dir = tempdir().unwrap();
+        let filepath = source_dir.path().join("archive.tar.zst");
+        fs::write(&filepath, "archive content").unwrap();
+
+        (source_dir, filepath)
+    }
+
+    fn mock_create_multipart_upload(server: &MockServer) -> httpmock::Mock {
+        server.mock(|when, then| {
+            when.method(httpmock::Method::POST)
+                .path("/snapshots/archive.tar.zst")
+                .query_param_exists("uploads")
+                .header_exists("authorization");
+            then.status(200).body(
+                "<InitiateMultipartUploadResult><Bucket>snapshots</Bucket><Key>archive.tar.zst</Key><UploadId>upload-1</UploadId></InitiateMultipartUploadResult>",
+            );
+        })
+    }
+
+    fn mock_abort_multipart_upload(server: &MockServer) -> httpmock::Mock {
+        server.mock(|when, then| {
+            when.method(httpmock::Method::DELETE)
+                .path("/snapshots/archive.tar.zst")
+                .query_param("uploadId", "upload-1");
+            then.status(204);
+        })
+    }
+
+    #[tokio::test]
+    async fn presigned_url_target_the_object_in_the_bucket() {
+        let storage = build_storage("http://localhost:9000", None);
+
+        let url = storage
+            .presigned_url("archive.tar.zst", Duration::from_secs(3600))
+            .await
+            .unwrap();
+
+        assert!(
+            url.starts_with("http://localhost:9000/snapshots/archive.tar.zst?"),
+            "unexpected presigned url: {url}"
+        );
+        assert!(url.contains("X-Amz-Expires=3600"), "{url}");
+        assert!(url.contains("X-Amz-Signature="), "{url}");
+    }
+
+    #[tokio::test]
+    async fn presigned_url_expiration_is_limited_to_seven_days() {
+        let storage = build_storage("http://localhost:9000", None);
+
+        storage
+            .presigned_url("archive.tar.zst", Duration::ZERO)
+            .await
+            .expect_err("a presigned url can not expire immediately");
+        storage
+            .presigned_url(
+                "archive.tar.zst",
+                MAX_PRESIGNED_URL_EXPIRATION + Duration::from_secs(1),
+            )
+            .await
+            .expect_err("a presigned url can not be valid for more than seven days");
+        storage
+            .presigned_url("archive.tar.zst", MAX_PRESIGNED_URL_EXPIRATION)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn uri_encode_only_keeps_unreserved_characters_and_slashes() {
+        assert_eq!("abc-_.~123", uri_encode("abc-_.~123"));
+        assert_eq!("a%20b%2Bc/d", uri_encode("a b+c/d"));
+    }
+
+    #[test]
+    fn location_use_public_url_if_given() {
+        assert_eq!(
+            "http://localhost:9000/snapshots/archive.tar.zst",
+            build_storage("http://localhost:9000/", None).location("archive.tar.zst")
+        );
+        assert_eq!(
+            "https://cdn.mithril.network/archive.tar.zst",
+            build_storage(
+                "http://localhost:9000",
+                Some("https://cdn.mithril.network/".to_string())
+            )
+            .location("archive.tar.zst")
+        );
+    }
+
+    #[tokio::test]
+    async fn store_upload_the_file_with_a_multipart_upload() {
+        let server = MockServer::start();
+        let create_mock = mock_create_multipart_upload(&server);
+        let part_mock = server.mock(|when, then| {
+            when.method(httpmock::Method::PUT)
+                .path("/snapshots/archive.tar.zst")
+                .query_param("partNumber", "1")
+                .query_param("uploadId", "upload-1")
+                .body("archive content");
+            then.status(200).header("ETag", "\"etag-1\"");
+        });
+        let complete_mock = server.mock(|when, then| {
+            when.method(httpmock::Method::POST)
+                .path("/snapshots/archive.tar.zst")
+                .query_param("uploadId", "upload-1")
+                .body_contains("<PartNumber>1</PartNumber>")
+                .body_contains("etag-1");
+            then.status(200).body(
+                "<CompleteMultipartUploadResult><Bucket>snapshots</Bucket><Key>archive.tar.zst</Key><ETag>\"etag\"</ETag></CompleteMultipartUploadResult>",
+            );
+        });
+        let (_source_dir, filepath) = write_archive();
+        let storage = build_storage(&server.base_url(), None);
+
+        let location = storage.store(&filepath).await.unwrap();
+
+        create_mock.assert();
+        part_mock.assert();
+        complete_mock.assert();
+        assert_eq!(
+            format!("{}/snapshots/archive.tar.zst", server.base_url()),
+            location
+        );
+    }
+
+    #[tokio::test]
+    async fn store_abort_the_multipart_upload_if_a_part_upload_fails() {
+        let server = MockServer::start();
+        mock_create_multipart_upload(&server);
+        server.mock(|when, then| {
+            when.method(httpmock::Method::PUT);
+            then.status(403)
+                .body("<Error><Code>AccessDenied</Code></Error>");
+        });
+        let abort_mock = mock_abort_multipart_upload(&server);
+        let (_source_dir, filepath) = write_archive();
+        let storage = build_storage(&server.base_url(), None);
+
+        storage
+            .store(&filepath)
+            .await
+            .expect_err("a failed part upload should fail the upload");
+
+        abort_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn store_abort_the_multipart_upload_if_the_completion_fails() {
+        let server = MockServer::start();
+        mock_create_multipart_upload(&server);
+        server.mock(|when, then| {
+            when.method(httpmock::Method::PUT);
+            then.status(200).header("ETag", "\"etag-1\"");
+        });
+        server.mock(|when, then| {
+            when.method(httpmock::Method::POST)
+                .query_param("uploadId", "upload-1");
+            then.status(400)
+                .body("<Error><Code>InvalidPart</Code></Error>");
+        });
+        let abort_mock = mock_abort_multipart_upload(&server);
+        let (_source_dir, filepath) = write_archive();
+        let storage = build_storage(&server.base_url(), None);
+
+        storage
+            .store(&filepath)
+            .await
+            .expect_err("a failed completion should fail the upload");
+
+        abort_mock.assert();
+    }
+
+    #[test]
+    fn multipart_upload_part_size_is_smaller_when_the_bandwidth_is_limited() {
+        let mib = 1024 * 1024;
+
+        assert_eq!(100 * mib, multipart_upload_part_size(1000 * mib, false));
+        assert_eq!(5 * mib, multipart_upload_part_size(1000 * mib, true));
+    }
+
+    #[test]
+    fn multipart_upload_part_size_never_exceeds_the_maximum_number_of_parts() {
+        let file_size = 2 * 1024 * 1024 * 1024 * 1024;
+
+        for is_bandwidth_limited in [false, true] {
+            let part_size = multipart_upload_part_size(file_size, is_bandwidth_limited);
+
+            assert!(file_size.div_ceil(part_size) <= MULTIPART_UPLOAD_MAX_PARTS);
+        }
+    }
+}
@@ -1,8 +1,8 @@
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use clap::{Parser, Subcommand};
 use config::{builder::DefaultState, ConfigBuilder};
 use mithril_common::{
-    crypto_helper::{ProtocolGenesisSecretKey, ProtocolGenesisSigner},
+    crypto_helper::{ProtocolGenesisSecretKey, ProtocolGenesisSigner, SigningProvider},
     entities::HexEncodedGenesisSecretKey,
     StdResult,
 };
@@ -39,6 +39,9 @@ pub enum GenesisSubCommand {
 
     /// Genesis certificate bootstrap command.
     Bootstrap(BootstrapGenesisSubCommand),
+
+    /// Genesis key rotation command.
+    Rotate(RotateGenesisSubCommand),
 }
 
 impl GenesisSubCommand {
@@ -48,6 +51,7 @@ impl GenesisSubCommand {
             Self::Export(cmd) => cmd.execute(config_builder).await,
             Self::Import(cmd) => cmd.execute(config_builder).await,
             Self::Sign(cmd) => cmd.execute(config_builder).await,
+            Self::Rotate(cmd) => cmd.execute(config_builder).await,
         }
     }
 }
@@ -139,8 +143,13 @@ pub struct SignGenesisSubCommand {
     target_signed_payload_path: PathBuf,
 
     /// Genesis Secret Key Path
-    #[clap(long)]
-    genesis_secret_key_path: PathBuf,
+    #[clap(long, required_unless_present = "genesis_kms_key_name")]
+    genesis_secret_key_path: Option<PathBuf>,
+
+    /// Name of a GCP KMS key version used to sign instead of a genesis secret key
+    /// (`projects/<project>/locations/<location>/keyRings/<key_ring>/cryptoKeys/<key>/cryptoKeyVersions/<version>`)
+    #[clap(long, conflicts_with = "genesis_secret_key_path")]
+    genesis_kms_key_name: Option<String>,
 }
 
 impl SignGenesisSubCommand {
@@ -152,16 +161,36 @@ impl SignGenesisSubCommand {
             self.target_signed_payload_path.to_string_lossy()
         );
 
+        let signing_provider = self.build_signing_provider()?;
         GenesisTools::sign_genesis_certificate(
             &self.to_sign_payload_path,
             &self.target_signed_payload_path,
-            &self.genesis_secret_key_path,
+            signing_provider.as_ref(),
         )
         .await
         .with_context(|| "genesis-tools: sign error")?;
 
         Ok(())
     }
+
+    fn build_signing_provider(&self) -> StdResult<Box<dyn SigningProvider>> {
+        match (&self.genesis_secret_key_path, &self.genesis_kms_key_name) {
+            (Some(genesis_secret_key_path), None) => Ok(Box::new(
+                GenesisTools::read_genesis_signer(genesis_secret_key_path)?,
+            )),
+            #[cfg(feature = "gcp_kms")]
+            (None, Some(genesis_kms_key_name)) => Ok(Box::new(
+                mithril_common::crypto_helper::GcpKmsSigningProvider::new(genesis_kms_key_name)?,
+            )),
+            #[cfg(not(feature = "gcp_kms"))]
+            (None, Some(_)) => Err(anyhow!(
+                "Signing with a GCP KMS key requires an aggregator built with the 'gcp_kms' feature"
+            )),
+            _ => Err(anyhow!(
+                "Exactly one of a genesis secret key path or a GCP KMS key name must be given"
+            )),
+        }
+    }
 }
 #[derive(Parser, Debug, Clone)]
 pub struct BootstrapGenesisSubCommand {
@@ -200,3 +229,61 @@ impl BootstrapGenesisSubCommand {
         Ok(())
     }
 }
+
+#[derive(Parser, Debug, Clone)]
+pub struct RotateGenesisSubCommand {
+    /// Previous Genesis Secret Key Path, the key of the configured genesis verification key
+    #[clap(long)]
+    previous_genesis_secret_key_path: PathBuf,
+
+    /// Next Genesis Secret Key Path
+    #[clap(long)]
+    next_genesis_secret_key_path: PathBuf,
+}
+
+impl RotateGenesisSubCommand {
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> StdResult<()> {
+        let config: Configuration = config_builder
+            .build()
+            .with_context(|| "configuration build error")?
+            .try_deserialize()
+            .with_context(|| "configuration deserialize error")?;
+        debug!("ROTATE GENESIS command");
+        println!(
+            "Genesis key rotation from {} to {}",
+            self.previous_genesis_secret_key_path.display(),
+            self.next_genesis_secret_key_path.display()
+        );
+        let mut dependencies_builder = DependenciesBuilder::new(config.clone());
+        let dependencies = dependencies_builder
+            .create_genesis_container()
+            .await
+            .with_context(|| {
+                "Dependencies Builder can not create genesis command dependencies container"
+            })?;
+
+        let genesis_tools = GenesisTools::from_dependencies(dependencies)
+            .await
+            .with_context(|| "genesis-tools: initialization error")?;
+        let previous_genesis_signer =
+            GenesisTools::read_genesis_signer(&self.previous_genesis_secret_key_path)?;
+        let next_genesis_signer =
+            GenesisTools::read_genesis_signer(&self.next_genesis_secret_key_path)?;
+        let rotation_certificate = genesis_tools
+            .rotate_genesis_key(&previous_genesis_signer, &next_genesis_signer)
+            .await
+            .with_context(|| "genesis-tools: rotate error")?;
+        println!(
+            "Genesis key rotation certificate created: {}",
+            rotation_certificate.hash
+        );
+        println!(
+            "The aggregator genesis verification key must now be updated to: {}",
+            next_genesis_signer
+                .create_genesis_verifier()
+                .to_verification_key()
+                .to_json_hex()?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use config::{builder::DefaultState, ConfigBuilder};
+use mithril_common::StdResult;
+use mithril_persistence::database::{
+    ApplicationNodeType, DatabaseVersionChecker, DbVersion, SqlMigration,
+};
+use mithril_persistence::sqlite::{ConnectionBuilder, SqliteConnection};
+use slog_scope::debug;
+use std::path::Path;
+
+use crate::{
+    database,
+    dependency_injection::{SQLITE_FILE, SQLITE_FILE_CARDANO_TRANSACTION, SQLITE_MONITORING_FILE},
+    event_store, Configuration,
+};
+
+/// Inspect and manage the migrations of the aggregator databases
+#[derive(Parser, Debug, Clone)]
+pub struct MigrationsCommand {
+    /// commands
+    #[clap(subcommand)]
+    pub migrations_subcommand: MigrationsSubCommand,
+}
+
+impl MigrationsCommand {
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> StdResult<()> {
+        let config: Configuration = config_builder
+            .build()
+            .with_context(|| "configuration build error")?
+            .try_deserialize()
+            .with_context(|| "configuration deserialize error")?;
+        debug!("MIGRATIONS command"; "config" => format!("{config:?}"));
+
+        for (database_file, migrations) in [
+            (SQLITE_FILE, database::migration::get_migrations()),
+            (
+                SQLITE_FILE_CARDANO_TRANSACTION,
+                database::cardano_transaction_migration::get_migrations(),
+            ),
+            (
+                SQLITE_MONITORING_FILE,
+                event_store::migration::get_migrations(),
+            ),
+        ] {
+            let database_path = config.get_sqlite_dir().join(database_file);
+            println!("Database '{}':", database_path.display());
+            let connection = open_connection(&database_path)?;
+            let checker = build_checker(&connection, migrations);
+            self.migrations_subcommand.execute(&checker)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Migrations subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum MigrationsSubCommand {
+    /// List the applied and pending migrations of each database.
+    Status,
+
+    /// Apply the pending migrations of each database.
+    Apply(ApplyMigrationsCommand),
+
+    /// Revert the migrations applied after the given version using their down migrations.
+    ///
+    /// Since it will modify the aggregator sqlite databases it's strongly recommended to backup
+    /// them before running this command.
+    Revert(RevertMigrationsCommand),
+}
+
+impl MigrationsSubCommand {
+    fn execute(&self, checker: &DatabaseVersionChecker) -> StdResult<()> {
+        match self {
+            Self::Status => print_status(checker),
+            Self::Apply(cmd) => cmd.execute(checker),
+            Self::Revert(cmd) => cmd.execute(checker),
+        }
+    }
+}
+
+/// Apply migrations command.
+#[derive(Parser, Debug, Clone)]
+pub struct ApplyMigrationsCommand {
+    /// Print the SQL of the migrations that would be applied without applying them.
+    #[clap(long)]
+    dry_run: bool,
+}
+
+impl ApplyMigrationsCommand {
+    fn execute(&self, checker: &DatabaseVersionChecker) -> StdResult<()> {
+        if self.dry_run {
+            for migration in checker.list_pending_migrations()? {
+                println!("-- Migration {}", migration.version);
+                println!("{}", migration.alterations.trim());
+            }
+            return Ok(());
+        }
+
+        checker.apply()?;
+        println!("  version: {}", checker.get_current_version()?);
+
+        Ok(())
+    }
+}
+
+/// Revert migrations command.
+#[derive(Parser, Debug, Clone)]
+pub struct RevertMigrationsCommand {
+    /// Database version to revert to.
+    #[clap(long)]
+    target_version: DbVersion,
+
+    /// Print the SQL of the down migrations that would be played without playing them.
+    #[clap(long)]
+    dry_run: bool,
+}
+
+impl RevertMigrationsCommand {
+    fn execute(&self, checker: &DatabaseVersionChecker) -> StdResult<()> {
+        if self.dry_run {
+            for migration in checker.list_migrations_to_revert(self.target_version)? {
+                println!("-- Revert migration {}", migration.version);
+                println!(
+                    "{}",
+                    migration
+                        .down_alterations
+                        .as_deref()
+                        .unwrap_or_default()
+                        .trim()
+                );
+            }
+            return Ok(());
+        }
+
+        checker.revert_to(self.target_version)?;
+        println!("  version: {}", checker.get_current_version()?);
+
+        Ok(())
+    }
+}
+
+fn open_connection(database_path: &Path) -> StdResult<SqliteConnection> {
+    ConnectionBuilder::open_file(database_path)
+        .with_node_type(ApplicationNodeType::Aggregator)
+        .build()
+        .with_context(|| {
+            format!(
+                "Could not open database file: '{}'",
+                database_path.display()
+            )
+        })
+}
+
+fn build_checker(
+    connection: &SqliteConnection,
+    migrations: Vec<SqlMigration>,
+) -> DatabaseVersionChecker {
+    let mut checker = DatabaseVersionChecker::new(
+        slog_scope::logger(),
+        ApplicationNodeType::Aggregator,
+        connection,
+    );
+    for migration in migrations {
+        checker.add_migration(migration);
+    }
+
+    checker
+}
+
+fn print_status(checker: &DatabaseVersionChecker) -> StdResult<()> {
+    println!("  version: {}", checker.get_current_version()?);
+    for status in checker.status()? {
+        println!(
+            "  {:>4} {:<8} {}",
+            status.version,
+            if status.applied { "applied" } else { "pending" },
+            if status.reversible {
+                "reversible"
+            } else {
+                "irreversible"
+            }
+        );
+    }
+
+    Ok(())
+}
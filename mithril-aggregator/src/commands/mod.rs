@@ -1,16 +1,21 @@
 mod era_command;
 mod genesis_command;
+mod migrations_command;
+mod registrations_command;
 mod serve_command;
 mod tools_command;
 
 use anyhow::anyhow;
 use clap::{CommandFactory, Parser, Subcommand};
 use config::{builder::DefaultState, ConfigBuilder, Map, Source, Value, ValueKind};
-use mithril_common::StdResult;
+use mithril_common::{logging::ComponentLogLevels, StdResult};
 use mithril_doc::{Documenter, DocumenterDefault, StructDoc};
+use mithril_secrets::ResolvedEnvironment;
+use mithril_service_manager::ServiceNotifier;
 use slog::Level;
 use slog_scope::debug;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use crate::{Configuration, DefaultConfiguration};
 use mithril_doc::GenerateDocCommands;
@@ -21,6 +26,8 @@ pub enum MainCommand {
     Genesis(genesis_command::GenesisCommand),
     Era(era_command::EraCommand),
     Serve(serve_command::ServeCommand),
+    Migrations(migrations_command::MigrationsCommand),
+    Registrations(registrations_command::RegistrationsCommand),
     Tools(tools_command::ToolsCommand),
     #[clap(alias("doc"), hide(true))]
     GenerateDoc(GenerateDocCommands),
@@ -35,11 +42,22 @@ pub enum CommandType {
 }
 
 impl MainCommand {
-    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> StdResult<()> {
+    pub async fn execute(
+        &self,
+        config_builder: ConfigBuilder<DefaultState>,
+        environment: &ResolvedEnvironment,
+        log_levels: ComponentLogLevels,
+        service_notifier: Arc<dyn ServiceNotifier>,
+    ) -> StdResult<()> {
         match self {
             Self::Genesis(cmd) => cmd.execute(config_builder).await,
             Self::Era(cmd) => cmd.execute(config_builder).await,
-            Self::Serve(cmd) => cmd.execute(config_builder).await,
+            Self::Serve(cmd) => {
+                cmd.execute(config_builder, environment, log_levels, service_notifier)
+                    .await
+            }
+            Self::Migrations(cmd) => cmd.execute(config_builder).await,
+            Self::Registrations(cmd) => cmd.execute(config_builder).await,
             Self::Tools(cmd) => cmd.execute(config_builder).await,
             Self::GenerateDoc(cmd) => {
                 let config_infos = vec![Configuration::extract(), DefaultConfiguration::extract()];
@@ -54,6 +72,8 @@ impl MainCommand {
             MainCommand::Serve(_) => CommandType::Server,
             MainCommand::Genesis(_) => CommandType::CommandLine,
             MainCommand::Era(_) => CommandType::CommandLine,
+            MainCommand::Migrations(_) => CommandType::CommandLine,
+            MainCommand::Registrations(_) => CommandType::CommandLine,
             MainCommand::Tools(_) => CommandType::CommandLine,
             MainCommand::GenerateDoc(_) => CommandType::CommandLine,
         }
@@ -110,8 +130,15 @@ impl Source for MainOpts {
 }
 
 impl MainOpts {
-    /// execute command
-    pub async fn execute(&self) -> StdResult<()> {
+    /// execute command, the settings are read from the given environment (with its secrets
+    /// resolved), the log levels are the ones used by the logger of the aggregator and the
+    /// state of the server is reported to the service manager through the service notifier
+    pub async fn execute(
+        &self,
+        environment: &ResolvedEnvironment,
+        log_levels: ComponentLogLevels,
+        service_notifier: Arc<dyn ServiceNotifier>,
+    ) -> StdResult<()> {
         let config_file_path = self
             .config_directory
             .join(format!("{}.json", self.run_mode));
@@ -120,11 +147,17 @@ impl MainOpts {
             .add_source(
                 config::File::with_name(&config_file_path.to_string_lossy()).required(false),
             )
-            .add_source(config::Environment::default().separator("__"))
+            .add_source(
+                config::Environment::default()
+                    .separator("__")
+                    .source(Some(environment.to_map())),
+            )
             .add_source(self.clone());
         debug!("Started"; "run_mode" => &self.run_mode, "node_version" => env!("CARGO_PKG_VERSION"));
 
-        self.command.execute(config_builder).await
+        self.command
+            .execute(config_builder, environment, log_levels, service_notifier)
+            .await
     }
 
     /// get log level from parameters
@@ -0,0 +1,176 @@
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use config::{builder::DefaultState, ConfigBuilder};
+use mithril_common::{
+    crypto_helper::{
+        EraMarkersSigner, EraMarkersVerifier, EraMarkersVerifierSecretKey,
+        EraMarkersVerifierVerificationKey,
+    },
+    entities::{HexEncodedKey, HexEncodedVerificationKey},
+    StdResult,
+};
+use slog_scope::debug;
+use std::{fs::File, path::PathBuf};
+
+use crate::{
+    dependency_injection::DependenciesBuilder,
+    tools::{SignerRegistrationsExport, SignerRegistrationsTools},
+    Configuration,
+};
+
+/// Signer registrations tools, used to migrate the registrations to another aggregator
+#[derive(Parser, Debug, Clone)]
+pub struct RegistrationsCommand {
+    /// commands
+    #[clap(subcommand)]
+    pub registrations_subcommand: RegistrationsSubCommand,
+}
+
+impl RegistrationsCommand {
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> StdResult<()> {
+        self.registrations_subcommand.execute(config_builder).await
+    }
+}
+
+/// Signer registrations tools commands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum RegistrationsSubCommand {
+    /// Export and sign the signer registrations of the current, next and upcoming epochs.
+    Export(ExportRegistrationsSubCommand),
+
+    /// Verify and import signer registrations exported by another aggregator.
+    ///
+    /// The imported registrations replace the existing ones of the same signers and epochs.
+    Import(ImportRegistrationsSubCommand),
+}
+
+impl RegistrationsSubCommand {
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> StdResult<()> {
+        match self {
+            Self::Export(cmd) => cmd.execute(config_builder).await,
+            Self::Import(cmd) => cmd.execute(config_builder).await,
+        }
+    }
+}
+
+/// Signer registrations export command
+#[derive(Parser, Debug, Clone)]
+pub struct ExportRegistrationsSubCommand {
+    /// Registrations Export Secret Key
+    #[clap(long, env = "REGISTRATIONS_EXPORT_SECRET_KEY")]
+    registrations_export_secret_key: HexEncodedKey,
+
+    /// Target Path
+    #[clap(long)]
+    target_path: PathBuf,
+}
+
+impl ExportRegistrationsSubCommand {
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> StdResult<()> {
+        let config: Configuration = config_builder
+            .build()
+            .with_context(|| "configuration build error")?
+            .try_deserialize()
+            .with_context(|| "configuration deserialize error")?;
+        debug!("EXPORT REGISTRATIONS command"; "config" => format!("{config:?}"));
+        println!(
+            "Signer registrations export to {}",
+            self.target_path.display()
+        );
+        let secret_key =
+            EraMarkersVerifierSecretKey::from_json_hex(&self.registrations_export_secret_key)
+                .with_context(|| "json hex decode of registrations export secret key failure")?;
+        let mut dependencies_builder = DependenciesBuilder::new(config.clone());
+        let current_epoch = dependencies_builder
+            .get_ticker_service()
+            .await?
+            .get_current_epoch()
+            .await
+            .with_context(|| "could not get the current epoch")?;
+        let registrations_tools =
+            SignerRegistrationsTools::new(dependencies_builder.get_verification_key_store().await?);
+
+        let export = registrations_tools
+            .export(
+                current_epoch,
+                &EraMarkersSigner::from_secret_key(secret_key),
+            )
+            .await
+            .with_context(|| "registrations-tools: export error")?;
+        serde_json::to_writer(File::create(&self.target_path)?, &export)?;
+
+        for epoch_registrations in &export.registrations {
+            println!(
+                "Exported {} signer registrations for epoch {}",
+                epoch_registrations.signers.len(),
+                epoch_registrations.epoch
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Signer registrations import command
+#[derive(Parser, Debug, Clone)]
+pub struct ImportRegistrationsSubCommand {
+    /// Registrations Export Verification Key
+    #[clap(long, env = "REGISTRATIONS_EXPORT_VERIFICATION_KEY")]
+    registrations_export_verification_key: HexEncodedVerificationKey,
+
+    /// Source Path
+    #[clap(long)]
+    source_path: PathBuf,
+}
+
+impl ImportRegistrationsSubCommand {
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> StdResult<()> {
+        let config: Configuration = config_builder
+            .build()
+            .with_context(|| "configuration build error")?
+            .try_deserialize()
+            .with_context(|| "configuration deserialize error")?;
+        debug!("IMPORT REGISTRATIONS command"; "config" => format!("{config:?}"));
+        println!(
+            "Signer registrations import from {}",
+            self.source_path.display()
+        );
+        let verification_key = EraMarkersVerifierVerificationKey::from_json_hex(
+            &self.registrations_export_verification_key,
+        )
+        .with_context(|| "json hex decode of registrations export verification key failure")?;
+        let export: SignerRegistrationsExport =
+            serde_json::from_reader(File::open(&self.source_path)?).with_context(|| {
+                format!(
+                    "could not read registrations export: '{}'",
+                    self.source_path.display()
+                )
+            })?;
+        let mut dependencies_builder = DependenciesBuilder::new(config.clone());
+        let current_epoch = dependencies_builder
+            .get_ticker_service()
+            .await?
+            .get_current_epoch()
+            .await
+            .with_context(|| "could not get the current epoch")?;
+        if current_epoch != export.exported_at_epoch {
+            println!(
+                "Warning: the registrations were exported at epoch {} but the current epoch is {current_epoch}",
+                export.exported_at_epoch
+            );
+        }
+        let registrations_tools =
+            SignerRegistrationsTools::new(dependencies_builder.get_verification_key_store().await?);
+
+        let imported_registrations = registrations_tools
+            .import(
+                export,
+                &EraMarkersVerifier::from_verification_key(verification_key),
+            )
+            .await
+            .with_context(|| "registrations-tools: import error")?;
+        println!("Imported {imported_registrations} signer registrations");
+
+        Ok(())
+    }
+}
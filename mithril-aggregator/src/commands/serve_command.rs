@@ -1,15 +1,22 @@
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use clap::Parser;
 use config::{builder::DefaultState, ConfigBuilder, Map, Source, Value, ValueKind};
-use mithril_common::StdResult;
+use mithril_common::{logging::ComponentLogLevels, StdResult};
+use mithril_secrets::ResolvedEnvironment;
+use mithril_service_manager::{run_watchdog, ServiceNotifier};
 use slog_scope::{crit, debug, info, warn};
+use std::sync::Arc;
 use std::time::Duration;
 use std::{net::IpAddr, path::PathBuf};
 use tokio::{sync::oneshot, task::JoinSet};
 
-use crate::{dependency_injection::DependenciesBuilder, Configuration};
+use crate::{
+    dependency_injection::DependenciesBuilder, http_server::routes::router, Configuration,
+    MetricsServer,
+};
 
-const SQLITE_MONITORING_FILE: &str = "monitoring.sqlite3";
+/// Maximum duration to wait for the artifacts built in the background when the aggregator stops
+const ARTIFACT_BUILDS_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Server runtime mode
 #[derive(Parser, Debug, Clone)]
@@ -22,6 +29,14 @@ pub struct ServeCommand {
     #[clap(long)]
     pub server_port: Option<u16>,
 
+    /// Path of the PEM encoded certificate used to serve the HTTP API over TLS
+    #[clap(long, requires = "server_tls_key_path")]
+    pub server_tls_cert_path: Option<PathBuf>,
+
+    /// Path of the PEM encoded private key used to serve the HTTP API over TLS
+    #[clap(long, requires = "server_tls_cert_path")]
+    pub server_tls_key_path: Option<PathBuf>,
+
     /// Directory to store snapshot
     /// Defaults to work folder
     #[clap(long)]
@@ -65,6 +80,24 @@ impl Source for ServeCommand {
                 Value::new(Some(&namespace), ValueKind::from(server_port)),
             );
         }
+        if let Some(server_tls_cert_path) = self.server_tls_cert_path.clone() {
+            result.insert(
+                "server_tls_cert_path".to_string(),
+                Value::new(
+                    Some(&namespace),
+                    ValueKind::from(format!("{}", server_tls_cert_path.to_string_lossy())),
+                ),
+            );
+        }
+        if let Some(server_tls_key_path) = self.server_tls_key_path.clone() {
+            result.insert(
+                "server_tls_key_path".to_string(),
+                Value::new(
+                    Some(&namespace),
+                    ValueKind::from(format!("{}", server_tls_key_path.to_string_lossy())),
+                ),
+            );
+        }
         if let Some(snapshot_directory) = self.snapshot_directory.clone() {
             result.insert(
                 "snapshot_directory".to_string(),
@@ -79,73 +112,305 @@ impl Source for ServeCommand {
     }
 }
 
+/// Configuration source of a network hosted by the aggregator in addition to its main network.
+///
+/// Its stores are located in a subdirectory, named after the network, of the ones of the main
+/// network.
+#[derive(Debug, Clone)]
+struct HostedNetworkSource {
+    network: String,
+    snapshot_directory: PathBuf,
+    data_stores_directory: PathBuf,
+}
+
+impl HostedNetworkSource {
+    fn new(network: &str, main_network_config: &Configuration) -> Self {
+        Self {
+            network: network.to_string(),
+            snapshot_directory: main_network_config.snapshot_directory.join(network),
+            data_stores_directory: main_network_config.data_stores_directory.join(network),
+        }
+    }
+}
+
+impl Source for HostedNetworkSource {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, config::ConfigError> {
+        let mut result = Map::new();
+        let namespace = format!("hosted network {}", self.network);
+
+        result.insert(
+            "network".to_string(),
+            Value::new(Some(&namespace), ValueKind::from(self.network.clone())),
+        );
+        result.insert(
+            "server_path_prefix".to_string(),
+            Value::new(Some(&namespace), ValueKind::from(self.network.clone())),
+        );
+        result.insert(
+            "snapshot_directory".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(format!("{}", self.snapshot_directory.to_string_lossy())),
+            ),
+        );
+        result.insert(
+            "data_stores_directory".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(format!("{}", self.data_stores_directory.to_string_lossy())),
+            ),
+        );
+
+        Ok(result)
+    }
+}
+
 impl ServeCommand {
-    pub async fn execute(&self, mut config_builder: ConfigBuilder<DefaultState>) -> StdResult<()> {
+    /// Build the configuration of a network hosted in addition to the main network: the
+    /// configuration of the main network overridden by the environment variables prefixed with
+    /// the uppercased network name.
+    fn build_hosted_network_configuration(
+        config_builder: ConfigBuilder<DefaultState>,
+        main_network_config: &Configuration,
+        network: &str,
+        environment: &ResolvedEnvironment,
+    ) -> StdResult<Configuration> {
+        let config: Configuration = config_builder
+            .add_source(HostedNetworkSource::new(network, main_network_config))
+            .add_source(
+                config::Environment::with_prefix(&network.to_uppercase())
+                    .prefix_separator("__")
+                    .separator("__")
+                    .source(Some(environment.to_map())),
+            )
+            .build()
+            .with_context(|| format!("configuration build error for hosted network '{network}'"))?
+            .try_deserialize()
+            .with_context(|| {
+                format!("configuration deserialize error for hosted network '{network}'")
+            })?;
+        config.list_cors_allowed_methods()?;
+        config.list_cors_allowed_origins()?;
+
+        if config.data_stores_directory == main_network_config.data_stores_directory {
+            return Err(anyhow!(
+                "hosted network '{network}' must not share the data stores directory of the main network"
+            ));
+        }
+        Self::check_hosted_network_specific_settings(&config, main_network_config, network)?;
+
+        Ok(config)
+    }
+
+    /// Check that the settings specific to a Cardano network are set for the hosted network,
+    /// instead of being inherited from the main network.
+    fn check_hosted_network_specific_settings(
+        config: &Configuration,
+        main_network_config: &Configuration,
+        network: &str,
+    ) -> StdResult<()> {
+        let inherited_settings: Vec<&str> = [
+            (
+                "network_magic",
+                config.network_magic.is_some()
+                    && config.network_magic == main_network_config.network_magic,
+            ),
+            (
+                "cardano_node_socket_path",
+                config.cardano_node_socket_path == main_network_config.cardano_node_socket_path,
+            ),
+            (
+                "db_directory",
+                config.db_directory == main_network_config.db_directory,
+            ),
+            (
+                "genesis_verification_key",
+                config.genesis_verification_key == main_network_config.genesis_verification_key,
+            ),
+            (
+                "cexplorer_pools_url",
+                config.cexplorer_pools_url.is_some()
+                    && config.cexplorer_pools_url == main_network_config.cexplorer_pools_url,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(setting, is_inherited)| is_inherited.then_some(setting))
+        .collect();
+
+        if !inherited_settings.is_empty() {
+            let prefix = network.to_uppercase();
+            return Err(anyhow!(
+                "hosted network '{network}' must set its own {}",
+                inherited_settings
+                    .iter()
+                    .map(|setting| format!("'{setting}' ({prefix}__{})", setting.to_uppercase()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub async fn execute(
+        &self,
+        mut config_builder: ConfigBuilder<DefaultState>,
+        environment: &ResolvedEnvironment,
+        log_levels: ComponentLogLevels,
+        service_notifier: Arc<dyn ServiceNotifier>,
+    ) -> StdResult<()> {
         config_builder = config_builder.add_source(self.clone());
         let config: Configuration = config_builder
+            .clone()
             .build()
             .with_context(|| "configuration build error")?
             .try_deserialize()
             .with_context(|| "configuration deserialize error")?;
         debug!("SERVE command"; "config" => format!("{config:?}"));
+        config.check_server_tls_configuration()?;
+        config.list_cors_allowed_methods()?;
+        config.list_cors_allowed_origins()?;
+        let server_address = (
+            config
+                .server_ip
+                .parse::<IpAddr>()
+                .with_context(|| format!("Invalid server IP: '{}'", config.server_ip))?,
+            config.server_port,
+        );
+        log_levels.set_component_levels(&config.list_component_log_levels()?);
         let mut dependencies_builder = DependenciesBuilder::new(config.clone());
+        dependencies_builder.log_levels = Some(log_levels.clone());
+
+        // each hosted network has its own dependencies, isolated from the main network ones
+        let mut hosted_networks_builders = vec![];
+        for network in config.list_hosted_networks()? {
+            let hosted_network_config = Self::build_hosted_network_configuration(
+                config_builder.clone(),
+                &config,
+                &network,
+                environment,
+            )?;
+            debug!("SERVE command: hosted network"; "network" => &network, "config" => format!("{hosted_network_config:?}"));
+            let mut hosted_network_builder = DependenciesBuilder::new(hosted_network_config);
+            hosted_network_builder.log_levels = Some(log_levels.clone());
+            hosted_networks_builders.push(hosted_network_builder);
+        }
 
         // start servers
         println!("Starting server...");
         println!("Press Ctrl+C to stop");
 
-        // start the monitoring thread
-        let mut event_store = dependencies_builder
+        // start the monitoring threads
+        let mut event_stores = vec![dependencies_builder
             .create_event_store()
             .await
-            .with_context(|| "Dependencies Builder can not create event store")?;
-        let event_store_config = config.clone();
-        let event_store_thread = tokio::spawn(async move {
-            event_store
-                .run(Some(
-                    event_store_config
-                        .get_sqlite_dir()
-                        .join(SQLITE_MONITORING_FILE),
-                ))
-                .await
-                .unwrap()
-        });
+            .with_context(|| "Dependencies Builder can not create event store")?];
+        for hosted_network_builder in hosted_networks_builders.iter_mut() {
+            event_stores.push(
+                hosted_network_builder
+                    .create_event_store()
+                    .await
+                    .with_context(|| "Dependencies Builder can not create event store")?,
+            );
+        }
+        let event_store_threads: Vec<_> = event_stores
+            .into_iter()
+            .map(|mut event_store| tokio::spawn(async move { event_store.run().await.unwrap() }))
+            .collect();
 
-        // start the aggregator runtime
+        // start the aggregator runtimes
+        let mut join_set = JoinSet::new();
         let mut runtime = dependencies_builder
             .create_aggregator_runner()
             .await
             .with_context(|| "Dependencies Builder can not create aggregator runner")?;
-        let mut join_set = JoinSet::new();
+        let mut artifact_build_pools = vec![runtime.artifact_build_pool()];
         join_set.spawn(async move { runtime.run().await.map_err(|e| e.to_string()) });
+        for hosted_network_builder in hosted_networks_builders.iter_mut() {
+            let mut runtime = hosted_network_builder
+                .create_aggregator_runner()
+                .await
+                .with_context(|| "Dependencies Builder can not create aggregator runner")?;
+            artifact_build_pools.push(runtime.artifact_build_pool());
+            join_set.spawn(async move { runtime.run().await.map_err(|e| e.to_string()) });
+        }
 
         // start the HTTP server
         let (shutdown_tx, shutdown_rx) = oneshot::channel();
-        let routes = dependencies_builder
+        let main_network_routes = dependencies_builder
             .create_http_routes()
             .await
             .with_context(|| "Dependencies Builder can not create http routes")?;
-        join_set.spawn(async move {
-            let (_, server) = warp::serve(routes).bind_with_graceful_shutdown(
-                (
-                    config.server_ip.clone().parse::<IpAddr>().unwrap(),
-                    config.server_port,
-                ),
-                async {
-                    shutdown_rx.await.ok();
-                },
+        let mut hosted_networks_routes = vec![];
+        for hosted_network_builder in hosted_networks_builders.iter_mut() {
+            hosted_networks_routes.push(
+                hosted_network_builder
+                    .create_http_routes()
+                    .await
+                    .with_context(|| "Dependencies Builder can not create http routes")?,
             );
-            server.await;
+        }
+        let routes =
+            router::with_hosted_networks_routes(main_network_routes, hosted_networks_routes);
+        let server_tls_paths = config.get_server_tls_paths();
+        join_set.spawn(async move {
+            let shutdown_signal = async {
+                shutdown_rx.await.ok();
+            };
+            match server_tls_paths {
+                Some((cert_path, key_path)) => {
+                    info!("HTTP server uses TLS"; "cert_path" => ?cert_path, "key_path" => ?key_path);
+                    let (_, server) = warp::serve(routes)
+                        .tls()
+                        .cert_path(cert_path)
+                        .key_path(key_path)
+                        .bind_with_graceful_shutdown(server_address, shutdown_signal);
+                    server.await;
+                }
+                None => {
+                    let (_, server) = warp::serve(routes)
+                        .bind_with_graceful_shutdown(server_address, shutdown_signal);
+                    server.await;
+                }
+            }
 
             Ok(())
         });
 
-        // Create a SignersImporter only if the `cexplorer_pools_url` is provided in the config.
-        if let Some(cexplorer_pools_url) = config.cexplorer_pools_url {
-            match dependencies_builder
-                .create_signer_importer(&cexplorer_pools_url)
+        // start the metrics server
+        let (metrics_server_shutdown_tx, metrics_server_shutdown_rx) = oneshot::channel();
+        if config.enable_metrics_server {
+            let metrics_service = dependencies_builder
+                .get_metrics_service()
                 .await
-            {
+                .with_context(|| "Dependencies Builder can not create metrics service")?;
+            let metrics_server = MetricsServer::new(
+                &config.metrics_server_ip,
+                config.metrics_server_port,
+                metrics_service,
+            );
+            join_set.spawn(async move {
+                metrics_server
+                    .start(metrics_server_shutdown_rx)
+                    .await
+                    .map_err(|e| e.to_string())
+            });
+        }
+
+        // Create a SignersImporter only if the `cexplorer_pools_url` is provided in the config.
+        for builder in
+            std::iter::once(&mut dependencies_builder).chain(hosted_networks_builders.iter_mut())
+        {
+            let Some(cexplorer_pools_url) = builder.configuration.cexplorer_pools_url.clone()
+            else {
+                continue;
+            };
+            let signer_importer_run_interval = builder.configuration.signer_importer_run_interval;
+            match builder.create_signer_importer(&cexplorer_pools_url).await {
                 Ok(service) => {
                     join_set.spawn(async move {
                         // Wait 5s to let the other services the time to start before running
@@ -154,7 +419,7 @@ impl ServeCommand {
                         service
                             .run_forever(Duration::from_secs(
                                 // Import interval are in minutes
-                                config.signer_importer_run_interval * 60,
+                                signer_importer_run_interval * 60,
                             ))
                             .await;
                         Ok(())
@@ -169,21 +434,165 @@ impl ServeCommand {
             }
         }
 
+        // reload the settings that can be changed without a restart
+        let reload_interval = config
+            .configuration_reload_interval
+            .map(Duration::from_secs);
+        let configuration_reloader = dependencies_builder
+            .get_configuration_reloader()
+            .await
+            .with_context(|| "Dependencies Builder can not create configuration reloader")?;
+        let main_network_config_builder = config_builder.clone();
+        join_set.spawn(async move {
+            configuration_reloader
+                .watch(
+                    || {
+                        main_network_config_builder
+                            .clone()
+                            .build()
+                            .with_context(|| "configuration build error")?
+                            .try_deserialize()
+                            .with_context(|| "configuration deserialize error")
+                    },
+                    reload_interval,
+                )
+                .await;
+            Ok(())
+        });
+        for hosted_network_builder in hosted_networks_builders.iter_mut() {
+            let configuration_reloader = hosted_network_builder
+                .get_configuration_reloader()
+                .await
+                .with_context(|| "Dependencies Builder can not create configuration reloader")?;
+            let network = hosted_network_builder.configuration.network.clone();
+            let hosted_network_config_builder = config_builder.clone();
+            let main_network_config = config.clone();
+            let hosted_network_environment = environment.clone();
+            join_set.spawn(async move {
+                configuration_reloader
+                    .watch(
+                        || {
+                            Self::build_hosted_network_configuration(
+                                hosted_network_config_builder.clone(),
+                                &main_network_config,
+                                &network,
+                                &hosted_network_environment,
+                            )
+                        },
+                        reload_interval,
+                    )
+                    .await;
+                Ok(())
+            });
+        }
+
         join_set.spawn(async { tokio::signal::ctrl_c().await.map_err(|e| e.to_string()) });
         dependencies_builder.vanish().await;
+        for hosted_network_builder in hosted_networks_builders {
+            hosted_network_builder.vanish().await;
+        }
+
+        // The dependencies are initialized and the database migrations are done: the aggregator
+        // is ready, this is reported to the service manager that runs it, if any.
+        if let Err(error) = service_notifier.notify_ready() {
+            warn!("Failed to notify the service manager that the aggregator is ready"; "error" => ?error);
+        }
+        let watchdog_notifier = service_notifier.clone();
+        join_set.spawn(async move {
+            run_watchdog(watchdog_notifier)
+                .await
+                .map_err(|e| format!("Service manager watchdog error: {e:?}"))
+        });
+        let stop_request_notifier = service_notifier.clone();
+        join_set.spawn(async move {
+            stop_request_notifier.wait_for_stop_request().await;
+            info!("Stop requested by the service manager");
+            Ok(())
+        });
 
         if let Err(e) = join_set.join_next().await.unwrap()? {
             crit!("A critical error occurred: {e}");
         }
 
+        if let Err(error) = service_notifier.notify_stopping() {
+            warn!("Failed to notify the service manager that the aggregator is stopping"; "error" => ?error);
+        }
+
         // stop servers
         join_set.shutdown().await;
         let _ = shutdown_tx.send(());
+        let _ = metrics_server_shutdown_tx.send(());
+
+        // the runtimes are stopped, the artifacts of the certificates they already created are
+        // still built
+        for artifact_build_pool in artifact_build_pools {
+            artifact_build_pool
+                .drain(ARTIFACT_BUILDS_SHUTDOWN_TIMEOUT)
+                .await;
+        }
 
         info!("Event store is finishing...");
-        event_store_thread.await.unwrap();
+        for event_store_thread in event_store_threads {
+            event_store_thread.await.unwrap();
+        }
         println!("Services stopped, exiting.");
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hosted_network_configuration(main_network_config: &Configuration) -> Configuration {
+        Configuration {
+            network: "preprod".to_string(),
+            network_magic: Some(1),
+            cardano_node_socket_path: PathBuf::from("/preprod/node.socket"),
+            db_directory: PathBuf::from("/preprod/db"),
+            genesis_verification_key: "preprod-genesis-verification-key".to_string(),
+            cexplorer_pools_url: Some("https://preprod.cexplorer.io/pools".to_string()),
+            ..main_network_config.clone()
+        }
+    }
+
+    #[test]
+    fn hosted_network_with_its_own_specific_settings_is_valid() {
+        let main_network_config = Configuration {
+            cexplorer_pools_url: Some("https://cexplorer.io/pools".to_string()),
+            ..Configuration::new_sample()
+        };
+
+        ServeCommand::check_hosted_network_specific_settings(
+            &hosted_network_configuration(&main_network_config),
+            &main_network_config,
+            "preprod",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn hosted_network_inheriting_specific_settings_of_the_main_network_is_invalid() {
+        let main_network_config = Configuration::new_sample();
+        let config = Configuration {
+            network_magic: main_network_config.network_magic,
+            db_directory: main_network_config.db_directory.clone(),
+            ..hosted_network_configuration(&main_network_config)
+        };
+
+        let error = ServeCommand::check_hosted_network_specific_settings(
+            &config,
+            &main_network_config,
+            "preprod",
+        )
+        .expect_err("Inheriting network specific settings should fail");
+
+        assert!(
+            error.to_string().contains(
+                "'network_magic' (PREPROD__NETWORK_MAGIC), 'db_directory' (PREPROD__DB_DIRECTORY)"
+            ),
+            "unexpected error: {error}"
+        );
+    }
+}
@@ -1,15 +1,23 @@
 use anyhow::Context;
 use clap::{Parser, Subcommand};
 use config::{builder::DefaultState, ConfigBuilder};
-use mithril_common::StdResult;
+use mithril_common::{
+    entities::{Epoch, ProtocolParameters, StakeDistribution},
+    StdResult,
+};
 use mithril_persistence::sqlite::vacuum_database;
 use slog_scope::debug;
-use std::sync::Arc;
+use std::{fs::File, path::PathBuf, sync::Arc, time::Duration};
 
 use crate::{
     database::repository::{CertificateRepository, SignedEntityStore},
     dependency_injection::DependenciesBuilder,
-    tools::CertificatesHashMigrator,
+    tools::{
+        AggregatorCertificatesRetriever, CertificatesHashMigrator, CertificatesImporter,
+        ChainObserverStakeDistributionRetriever, CommitteeSimulationParameters, CommitteeSimulator,
+        DbSyncStakeDistributionRetriever, JsonFileStakeDistributionRetriever,
+        StakeDistributionBackfillRetriever, StakeDistributionBackfiller,
+    },
     Configuration,
 };
 
@@ -36,12 +44,36 @@ pub enum ToolsSubCommand {
     /// Since it will modify the aggregator sqlite database it's strongly recommended to backup it
     /// before running this command.
     RecomputeCertificatesHash(RecomputeCertificatesHashCommand),
+
+    /// Simulate signing rounds for a stake distribution to estimate the quorum failure
+    /// probability and the certificate latency of a set of protocol parameters.
+    ///
+    /// Useful to assess the impact of a protocol parameters change before proposing it.
+    SimulateCommittee(SimulateCommitteeCommand),
+
+    /// Import the certificate chain of another aggregator, validated down to its genesis
+    /// certificate, and the signed entities of its certificates.
+    ///
+    /// Useful to bootstrap a new aggregator for an existing network. Only the missing
+    /// certificates and signed entities are imported, so the command can be run several times.
+    ImportCertificates(ImportCertificatesCommand),
+
+    /// Detect the epochs without a stake distribution in the stake store and backfill them.
+    ///
+    /// Useful when the aggregator was down across an epoch boundary. The stake distributions are
+    /// read from a db-sync database or from a directory of JSON files if given, else from the
+    /// Cardano node which only keeps the stake distributions of the current epoch and of the two
+    /// previous epochs.
+    BackfillStakeDistributions(BackfillStakeDistributionsCommand),
 }
 
 impl ToolsSubCommand {
     pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> StdResult<()> {
         match self {
             Self::RecomputeCertificatesHash(cmd) => cmd.execute(config_builder).await,
+            Self::SimulateCommittee(cmd) => cmd.execute(config_builder).await,
+            Self::ImportCertificates(cmd) => cmd.execute(config_builder).await,
+            Self::BackfillStakeDistributions(cmd) => cmd.execute(config_builder).await,
         }
     }
 }
@@ -81,3 +113,245 @@ impl RecomputeCertificatesHashCommand {
         Ok(())
     }
 }
+
+/// Simulate committee command.
+#[derive(Parser, Debug, Clone)]
+pub struct SimulateCommitteeCommand {
+    /// Path of a JSON file containing the stake distribution to simulate, as a map of party
+    /// ids to their stake.
+    #[clap(long)]
+    stake_distribution_file: PathBuf,
+
+    /// Quorum parameter
+    #[clap(short, long)]
+    k: u64,
+
+    /// Security parameter (number of lotteries)
+    #[clap(short, long)]
+    m: u64,
+
+    /// f in phi(w) = 1 - (1 - f)^w, where w is the stake of a participant
+    #[clap(long)]
+    phi_f: f64,
+
+    /// Number of signing rounds to simulate
+    #[clap(long, default_value_t = 1000)]
+    rounds: u64,
+
+    /// Probability for each signer to send its signature during a round
+    #[clap(long, default_value_t = 1.0)]
+    participation_rate: f64,
+
+    /// Mean delay before a signature is received by the aggregator (in milliseconds)
+    #[clap(long, default_value_t = 1000.0)]
+    mean_signature_delay_ms: f64,
+
+    /// Seed of the random generator
+    #[clap(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Enable JSON output.
+    #[clap(long)]
+    json: bool,
+}
+
+impl SimulateCommitteeCommand {
+    pub async fn execute(&self, _config_builder: ConfigBuilder<DefaultState>) -> StdResult<()> {
+        debug!("SIMULATE COMMITTEE command"; "command" => format!("{self:?}"));
+        let stake_distribution: StakeDistribution = serde_json::from_reader(
+            File::open(&self.stake_distribution_file).with_context(|| {
+                format!(
+                    "simulate-committee: can not open stake distribution file '{}'",
+                    self.stake_distribution_file.display()
+                )
+            })?,
+        )
+        .with_context(|| "simulate-committee: can not parse stake distribution file")?;
+
+        let simulator = CommitteeSimulator::new(&stake_distribution)?;
+        let report = simulator.simulate(&CommitteeSimulationParameters {
+            protocol_parameters: ProtocolParameters::new(self.k, self.m, self.phi_f),
+            number_of_rounds: self.rounds,
+            participation_rate: self.participation_rate,
+            mean_signature_delay_ms: self.mean_signature_delay_ms,
+            seed: self.seed,
+        })?;
+
+        if self.json {
+            println!("{}", serde_json::to_string(&report)?);
+        } else {
+            println!(
+                "Simulated {} rounds with {} signers:",
+                report.number_of_rounds,
+                stake_distribution.len()
+            );
+            println!("{report:#?}");
+        }
+
+        Ok(())
+    }
+}
+
+/// Import certificates command.
+#[derive(Parser, Debug, Clone)]
+pub struct ImportCertificatesCommand {
+    /// Endpoint of the aggregator to import the certificates from.
+    #[clap(long)]
+    aggregator_endpoint: String,
+
+    /// Timeout of the requests to the aggregator (in seconds)
+    #[clap(long, default_value_t = 30)]
+    timeout: u64,
+
+    /// Enable JSON output.
+    #[clap(long)]
+    json: bool,
+}
+
+impl ImportCertificatesCommand {
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> StdResult<()> {
+        let config: Configuration = config_builder
+            .build()
+            .with_context(|| "configuration build error")?
+            .try_deserialize()
+            .with_context(|| "configuration deserialize error")?;
+        debug!("IMPORT CERTIFICATES command"; "config" => format!("{config:?}"), "command" => format!("{self:?}"));
+        let mut dependencies_builder = DependenciesBuilder::new(config.clone());
+        let connection = dependencies_builder
+            .get_sqlite_connection()
+            .await
+            .with_context(|| "Dependencies Builder can not get sqlite connection")?;
+        let importer = CertificatesImporter::new(
+            Arc::new(AggregatorCertificatesRetriever::new(
+                &self.aggregator_endpoint,
+                Some(Duration::from_secs(self.timeout)),
+            )?),
+            Arc::new(CertificateRepository::new(connection)),
+            dependencies_builder
+                .get_signed_entity_storer()
+                .await
+                .with_context(|| "Dependencies Builder can not get signed entity storer")?,
+            dependencies_builder
+                .get_genesis_verifier()
+                .await
+                .with_context(|| "Dependencies Builder can not get genesis verifier")?
+                .to_verification_key(),
+        );
+
+        let report = importer
+            .run()
+            .await
+            .with_context(|| "import-certificates: import error")?;
+
+        if self.json {
+            println!("{}", serde_json::to_string(&report)?);
+        } else {
+            println!(
+                "Imported {} certificates and {} signed entities from '{}'",
+                report.imported_certificates,
+                report.imported_signed_entities,
+                self.aggregator_endpoint
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Backfill stake distributions command.
+#[derive(Parser, Debug, Clone)]
+pub struct BackfillStakeDistributionsCommand {
+    /// First epoch to check (included).
+    #[clap(long)]
+    from_epoch: u64,
+
+    /// Last epoch to check (included), the current epoch of the Cardano node if not set.
+    #[clap(long)]
+    to_epoch: Option<u64>,
+
+    /// Directory of the `stake-distribution-<epoch>.json` files to read the stake distributions
+    /// from, each file contains a map of party ids to their stake.
+    #[clap(long, conflicts_with = "db_sync_connection_string")]
+    stake_distributions_directory: Option<PathBuf>,
+
+    /// Connection string of a db-sync database to read the stake distributions from, using `psql`.
+    ///
+    /// The password should be provided with the `PGPASSWORD` environment variable or a
+    /// `.pgpass` file rather than in the connection string.
+    #[clap(long, env = "DB_SYNC_CONNECTION_STRING")]
+    db_sync_connection_string: Option<String>,
+
+    /// Only report the missing epochs, without saving anything.
+    #[clap(long)]
+    dry_run: bool,
+
+    /// Enable JSON output.
+    #[clap(long)]
+    json: bool,
+}
+
+impl BackfillStakeDistributionsCommand {
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> StdResult<()> {
+        let config: Configuration = config_builder
+            .build()
+            .with_context(|| "configuration build error")?
+            .try_deserialize()
+            .with_context(|| "configuration deserialize error")?;
+        debug!("BACKFILL STAKE DISTRIBUTIONS command"; "config" => format!("{config:?}"), "command" => format!("{self:?}"));
+        let mut dependencies_builder = DependenciesBuilder::new(config.clone());
+        let chain_observer = dependencies_builder
+            .get_chain_observer()
+            .await
+            .with_context(|| "Dependencies Builder can not get chain observer")?;
+        let to_epoch = match self.to_epoch {
+            Some(epoch) => Epoch(epoch),
+            None => chain_observer.get_current_epoch().await?.with_context(|| {
+                "backfill-stake-distributions: no current epoch returned by the chain observer"
+            })?,
+        };
+        let retriever: Arc<dyn StakeDistributionBackfillRetriever> = match (
+            &self.stake_distributions_directory,
+            &self.db_sync_connection_string,
+        ) {
+            (Some(directory), _) => Arc::new(JsonFileStakeDistributionRetriever::new(directory)),
+            (None, Some(connection_string)) => {
+                Arc::new(DbSyncStakeDistributionRetriever::new(connection_string))
+            }
+            (None, None) => Arc::new(ChainObserverStakeDistributionRetriever::new(chain_observer)),
+        };
+        let backfiller = StakeDistributionBackfiller::new(
+            dependencies_builder
+                .get_stake_store()
+                .await
+                .with_context(|| "Dependencies Builder can not get stake store")?,
+            retriever,
+        );
+
+        let report = backfiller
+            .run(Epoch(self.from_epoch), to_epoch, self.dry_run)
+            .await
+            .with_context(|| "backfill-stake-distributions: backfill error")?;
+
+        if self.json {
+            println!("{}", serde_json::to_string(&report)?);
+        } else {
+            println!(
+                "{} missing epochs between epoch {} and epoch {to_epoch}: {} {}, {} unavailable",
+                report.missing_epochs.len(),
+                self.from_epoch,
+                report.backfilled_epochs.len(),
+                if self.dry_run {
+                    "can be backfilled"
+                } else {
+                    "backfilled"
+                },
+                report.unavailable_epochs.len(),
+            );
+            for epoch in &report.unavailable_epochs {
+                println!("No stake distribution available for epoch {epoch}");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -723,6 +723,193 @@ create index signed_entity_certificate_id_index on signed_entity(certificate_id)
 create index single_signature_open_message_id_index on single_signature(open_message_id);
 create index single_signature_signer_id_index on single_signature(signer_id);
 create index single_signature_registration_epoch_setting_id_index on single_signature(registration_epoch_setting_id);
+"#,
+        ),
+        // Migration 25
+        // Add the `runtime_decision` table to audit the decisions taken by the runtime.
+        SqlMigration::new(
+            25,
+            r#"
+create table runtime_decision (
+    runtime_decision_id     integer     not null    primary key autoincrement,
+    signed_entity_type_id   integer     not null,
+    beacon                  text        not null,
+    decision                text        not null,
+    reason                  text,
+    created_at              text        not null,
+    last_seen_at            text        not null,
+    foreign key (signed_entity_type_id) references signed_entity_type(signed_entity_type_id)
+);
+create index runtime_decision_signed_entity_type_id_index on runtime_decision(signed_entity_type_id);
+create index runtime_decision_last_seen_at_index on runtime_decision(last_seen_at);
+"#,
+        ),
+        // Migration 26
+        // Add the `signer_metadata` table to record the metadata sent by the signers when they
+        // register (node version, supported eras).
+        SqlMigration::new_reversible(
+            26,
+            r#"
+create table signer_metadata (
+    signer_id               text        not null    primary key,
+    registration_epoch      integer     not null,
+    node_version            text,
+    supported_eras          text        not null,
+    ip_address              text,
+    updated_at              text        not null
+);
+create index signer_metadata_registration_epoch_index on signer_metadata(registration_epoch);
+"#,
+            r#"
+drop table signer_metadata;
+"#,
+        ),
+        // Migration 27
+        // Alter `certificate` table to add the `era` under which the certificate was produced.
+        SqlMigration::new_reversible(
+            27,
+            r#"
+alter table certificate add column era text;
+"#,
+            r#"
+alter table certificate drop column era;
+"#,
+        ),
+        // Migration 28
+        // Add the `signer_participation` table to track the registrations and the signatures
+        // issued by each signer across epochs.
+        SqlMigration::new_reversible(
+            28,
+            r#"
+create table signer_participation (
+    signer_id               text        not null,
+    epoch                   integer     not null,
+    registered              integer     not null    default 0,
+    signatures_issued       integer     not null    default 0,
+    updated_at              text        not null,
+    primary key (signer_id, epoch)
+);
+"#,
+            r#"
+drop table signer_participation;
+"#,
+        ),
+        // Migration 29
+        // Add an index on the `signed_entity_type_id` of the `certificate` table to filter the
+        // certificates list by signed entity type.
+        SqlMigration::new_reversible(
+            29,
+            r#"
+create index certificate_signed_entity_type_id_index on certificate(signed_entity_type_id);
+"#,
+            r#"
+drop index certificate_signed_entity_type_id_index;
+"#,
+        ),
+        // Migration 30
+        // Alter `open_message` table to add the number of times the open message was re-opened
+        // after its expiration.
+        SqlMigration::new_reversible(
+            30,
+            r#"
+alter table open_message add column reopening_count integer not null default 0;
+"#,
+            r#"
+alter table open_message drop column reopening_count;
+"#,
+        ),
+        // Migration 31
+        // Add the `signed_entity_lease` table to lease the creation of the artifact of a signed
+        // entity type to a single aggregator process.
+        SqlMigration::new_reversible(
+            31,
+            r#"
+create table signed_entity_lease (
+    signed_entity_type_id   integer     not null,
+    beacon                  text        not null,
+    holder                  text        not null,
+    expires_at              text        not null,
+    primary key (signed_entity_type_id, beacon)
+);
+"#,
+            r#"
+drop table signed_entity_lease;
+"#,
+        ),
+        // Migration 32
+        // Add the `certificate_chain_checkpoint` table to record the signed checkpoints of the
+        // certificates whose chain was verified, to bound the traversal of the next verifications
+        // of the chain.
+        SqlMigration::new_reversible(
+            32,
+            r#"
+create table certificate_chain_checkpoint (
+    epoch                   integer     not null,
+    certificate_hash        text        not null,
+    signature               text        not null,
+    created_at              text        not null,
+    primary key (epoch)
+);
+"#,
+            r#"
+drop table certificate_chain_checkpoint;
+"#,
+        ),
+        // Migration 33
+        // Add the `buffered_single_signature` table to keep the single signatures received
+        // before the open message they sign is created.
+        SqlMigration::new_reversible(
+            33,
+            r#"
+create table buffered_single_signature (
+    signed_entity_type_id   integer     not null,
+    party_id                text        not null,
+    lottery_indexes         json        not null,
+    signature               text        not null,
+    created_at              text        not null,
+    primary key (signed_entity_type_id, party_id)
+);
+"#,
+            r#"
+drop table buffered_single_signature;
+"#,
+        ),
+        // Migration 34
+        // Add the `signer_unregistration` table to keep the signers unregistered from an epoch,
+        // so that they can not be registered again at this epoch by replaying their registration.
+        SqlMigration::new_reversible(
+            34,
+            r#"
+create table signer_unregistration (
+    signer_id               text        not null,
+    epoch                   integer     not null,
+    created_at              text        not null,
+    primary key (epoch, signer_id)
+);
+"#,
+            r#"
+drop table signer_unregistration;
+"#,
+        ),
+        // Migration 35
+        // Add the `signer_registration_next_era` table to keep the verification keys created
+        // by the signers with the signature scheme of the next era, registered ahead of the era
+        // switch along with the verification keys of the current era.
+        SqlMigration::new_reversible(
+            35,
+            r#"
+create table signer_registration_next_era (
+    signer_id                   text        not null,
+    epoch_setting_id            integer     not null,
+    era                         text        not null,
+    verification_key            text        not null,
+    verification_key_signature  text,
+    created_at                  text        not null,
+    primary key (epoch_setting_id, signer_id, era)
+);
+"#,
+            r#"
+drop table signer_registration_next_era;
 "#,
         ),
     ]
@@ -0,0 +1,52 @@
+use sqlite::Value;
+
+use mithril_common::entities::{PartyId, SignedEntityTypeDiscriminants};
+use mithril_persistence::sqlite::{delete_definition, Provider, SqliteConnection, WhereCondition};
+
+use crate::database::record::BufferedSingleSignatureRecord;
+
+/// Query to delete [BufferedSingleSignatureRecord] from the sqlite database
+pub struct DeleteBufferedSingleSignatureProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> DeleteBufferedSingleSignatureProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Create the SQL condition to select the signatures buffered by the given parties for the
+    /// given signed entity type.
+    pub fn get_party_ids_condition(
+        &self,
+        signed_entity_type_discriminant: SignedEntityTypeDiscriminants,
+        party_ids: &[PartyId],
+    ) -> WhereCondition {
+        WhereCondition::new(
+            "signed_entity_type_id = ?*",
+            vec![Value::Integer(
+                signed_entity_type_discriminant.index() as i64
+            )],
+        )
+        .and_where(WhereCondition::where_in(
+            "party_id",
+            party_ids
+                .iter()
+                .map(|party_id| Value::String(party_id.to_owned()))
+                .collect(),
+        ))
+    }
+}
+
+impl<'client> Provider<'client> for DeleteBufferedSingleSignatureProvider<'client> {
+    type Entity = BufferedSingleSignatureRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        delete_definition::<Self::Entity>("buffered_single_signature", condition)
+    }
+}
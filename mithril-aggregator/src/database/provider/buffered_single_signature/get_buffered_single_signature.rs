@@ -0,0 +1,50 @@
+use sqlite::Value;
+
+use mithril_common::entities::SignedEntityTypeDiscriminants;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::BufferedSingleSignatureRecord;
+
+/// Simple queries to retrieve [BufferedSingleSignatureRecord] from the sqlite database.
+pub struct GetBufferedSingleSignatureProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> GetBufferedSingleSignatureProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_signed_entity_type_discriminant_condition(
+        &self,
+        signed_entity_type_discriminant: SignedEntityTypeDiscriminants,
+    ) -> WhereCondition {
+        WhereCondition::new(
+            "signed_entity_type_id = ?*",
+            vec![Value::Integer(
+                signed_entity_type_discriminant.index() as i64
+            )],
+        )
+    }
+}
+
+impl<'client> Provider<'client> for GetBufferedSingleSignatureProvider<'client> {
+    type Entity = BufferedSingleSignatureRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases =
+            SourceAlias::new(&[("{:buffered_single_signature:}", "buffered_single_signature")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!(
+            "select {projection} from buffered_single_signature where {condition} order by party_id"
+        )
+    }
+}
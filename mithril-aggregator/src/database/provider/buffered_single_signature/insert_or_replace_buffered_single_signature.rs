@@ -0,0 +1,72 @@
+use sqlite::Value;
+
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{upsert_definition, Provider, SqliteConnection, WhereCondition};
+
+use crate::database::record::BufferedSingleSignatureRecord;
+
+/// Query to buffer a [BufferedSingleSignatureRecord] in the sqlite database, it replaces the
+/// signature previously buffered by the same party for the same signed entity type.
+pub struct InsertOrReplaceBufferedSingleSignatureProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> InsertOrReplaceBufferedSingleSignatureProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_insert_or_replace_condition(
+        &self,
+        record: BufferedSingleSignatureRecord,
+    ) -> StdResult<WhereCondition> {
+        Ok(WhereCondition::insert_values(
+            &[
+                "signed_entity_type_id",
+                "party_id",
+                "lottery_indexes",
+                "signature",
+                "created_at",
+            ],
+            vec![vec![
+                Value::Integer(record.signed_entity_type_id.index() as i64),
+                Value::String(record.party_id),
+                Value::String(serde_json::to_string(&record.lottery_indexes)?),
+                Value::String(record.signature),
+                Value::String(record.created_at.to_rfc3339()),
+            ]],
+        ))
+    }
+
+    pub fn persist(
+        &self,
+        record: BufferedSingleSignatureRecord,
+    ) -> StdResult<BufferedSingleSignatureRecord> {
+        let party_id = record.party_id.clone();
+        let filters = self.get_insert_or_replace_condition(record)?;
+
+        let entity = self.find(filters)?.next().unwrap_or_else(|| {
+            panic!("No entity returned by the persister, party_id = {party_id:?}")
+        });
+
+        Ok(entity)
+    }
+}
+
+impl<'client> Provider<'client> for InsertOrReplaceBufferedSingleSignatureProvider<'client> {
+    type Entity = BufferedSingleSignatureRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        upsert_definition::<Self::Entity>(
+            "buffered_single_signature",
+            &["signed_entity_type_id", "party_id"],
+            &["lottery_indexes", "signature", "created_at"],
+            condition,
+        )
+    }
+}
@@ -0,0 +1,7 @@
+mod delete_buffered_single_signature;
+mod get_buffered_single_signature;
+mod insert_or_replace_buffered_single_signature;
+
+pub use delete_buffered_single_signature::*;
+pub use get_buffered_single_signature::*;
+pub use insert_or_replace_buffered_single_signature::*;
@@ -1,10 +1,8 @@
-use std::iter::repeat;
-
 use sqlite::Value;
 
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::{
-    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+    upsert_definition, BulkInsertProvider, Provider, SqliteConnection, WhereCondition,
 };
 
 use crate::database::record::CardanoTransactionRecord;
@@ -15,6 +13,14 @@ pub struct InsertCardanoTransactionProvider<'client> {
 }
 
 impl<'client> InsertCardanoTransactionProvider<'client> {
+    const COLUMNS: [&'static str; 5] = [
+        "transaction_hash",
+        "block_number",
+        "slot_number",
+        "block_hash",
+        "immutable_file_number",
+    ];
+
     /// Create a new instance
     pub fn new(connection: &'client SqliteConnection) -> Self {
         Self { connection }
@@ -33,30 +39,36 @@ impl<'client> InsertCardanoTransactionProvider<'client> {
         &self,
         transactions_records: Vec<CardanoTransactionRecord>,
     ) -> StdResult<WhereCondition> {
-        let columns =
-            "(transaction_hash, block_number, slot_number, block_hash, immutable_file_number)";
-        let values_columns: Vec<&str> = repeat("(?*, ?*, ?*, ?*, ?*)")
-            .take(transactions_records.len())
-            .collect();
+        Ok(WhereCondition::insert_values(
+            &Self::COLUMNS,
+            Self::to_values(transactions_records)?,
+        ))
+    }
 
-        let values: StdResult<Vec<Value>> =
-            transactions_records
-                .into_iter()
-                .try_fold(vec![], |mut vec, record| {
-                    vec.append(&mut vec![
-                        Value::String(record.transaction_hash),
-                        Value::Integer(record.block_number.try_into()?),
-                        Value::Integer(record.slot_number.try_into()?),
-                        Value::String(record.block_hash.clone()),
-                        Value::Integer(record.immutable_file_number.try_into()?),
-                    ]);
-                    Ok(vec)
-                });
+    /// Insert multiples records, splitting them in as many statements as needed to respect the
+    /// SQLite bound parameters limit.
+    pub fn persist_many(
+        &self,
+        transactions_records: Vec<CardanoTransactionRecord>,
+    ) -> StdResult<Vec<CardanoTransactionRecord>> {
+        self.insert_many(&Self::COLUMNS, Self::to_values(transactions_records)?)
+    }
 
-        Ok(WhereCondition::new(
-            format!("{columns} values {}", values_columns.join(", ")).as_str(),
-            values?,
-        ))
+    fn to_values(
+        transactions_records: Vec<CardanoTransactionRecord>,
+    ) -> StdResult<Vec<Vec<Value>>> {
+        transactions_records
+            .into_iter()
+            .map(|record| {
+                Ok(vec![
+                    Value::String(record.transaction_hash),
+                    Value::Integer(record.block_number.try_into()?),
+                    Value::Integer(record.slot_number.try_into()?),
+                    Value::String(record.block_hash),
+                    Value::Integer(record.immutable_file_number.try_into()?),
+                ])
+            })
+            .collect()
     }
 }
 
@@ -68,9 +80,7 @@ impl<'client> Provider<'client> for InsertCardanoTransactionProvider<'client> {
     }
 
     fn get_definition(&self, condition: &str) -> String {
-        let aliases = SourceAlias::new(&[("{:cardano_tx:}", "cardano_tx")]);
-        let projection = Self::Entity::get_projection().expand(aliases);
-
-        format!("insert or ignore into cardano_tx {condition} returning {projection}")
+        // the already stored transactions are ignored
+        upsert_definition::<Self::Entity>("cardano_tx", &[], &[], condition)
     }
 }
@@ -1,37 +1,32 @@
-use sqlite::{ConnectionThreadSafe, Value};
+use sqlite::Value;
 
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::{
-    EntityCursor, Provider, SourceAlias, SqLiteEntity, WhereCondition,
+    delete_definition, EntityCursor, Provider, SqliteConnection, WhereCondition,
 };
 
 use crate::database::record::CertificateRecord;
 
 /// Query to delete old [CertificateRecord] from the sqlite database
 pub struct DeleteCertificateProvider<'conn> {
-    connection: &'conn ConnectionThreadSafe,
+    connection: &'conn SqliteConnection,
 }
 
 impl<'conn> Provider<'conn> for DeleteCertificateProvider<'conn> {
     type Entity = CertificateRecord;
 
-    fn get_connection(&'conn self) -> &'conn ConnectionThreadSafe {
+    fn get_connection(&'conn self) -> &'conn SqliteConnection {
         self.connection
     }
 
     fn get_definition(&self, condition: &str) -> String {
-        // it is important to alias the fields with the same name as the table
-        // since the table cannot be aliased in a RETURNING statement in SQLite.
-        let projection = Self::Entity::get_projection()
-            .expand(SourceAlias::new(&[("{:certificate:}", "certificate")]));
-
-        format!("delete from certificate where {condition} returning {projection}")
+        delete_definition::<Self::Entity>("certificate", condition)
     }
 }
 
 impl<'conn> DeleteCertificateProvider<'conn> {
     /// Create a new instance
-    pub fn new(connection: &'conn ConnectionThreadSafe) -> Self {
+    pub fn new(connection: &'conn SqliteConnection) -> Self {
         Self { connection }
     }
 
@@ -1,22 +1,22 @@
-use sqlite::{ConnectionThreadSafe, Value};
+use sqlite::Value;
 
-#[cfg(test)]
-use mithril_common::entities::Epoch;
+use mithril_common::entities::{Epoch, SignedEntityTypeDiscriminants};
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::{
-    EntityCursor, GetAllCondition, Provider, SourceAlias, SqLiteEntity, WhereCondition,
+    EntityCursor, GetAllCondition, Provider, SourceAlias, SqLiteEntity, SqliteConnection,
+    WhereCondition,
 };
 
 use crate::database::record::CertificateRecord;
 
 /// Simple queries to retrieve [CertificateRecord] from the sqlite database.
 pub struct GetCertificateRecordProvider<'client> {
-    client: &'client ConnectionThreadSafe,
+    client: &'client SqliteConnection,
 }
 
 impl<'client> GetCertificateRecordProvider<'client> {
     /// Create a new provider
-    pub fn new(client: &'client ConnectionThreadSafe) -> Self {
+    pub fn new(client: &'client SqliteConnection) -> Self {
         Self { client }
     }
 
@@ -35,6 +35,44 @@ impl<'client> GetCertificateRecordProvider<'client> {
         ))
     }
 
+    /// Condition to get the certificates of the given epoch or of a later one.
+    pub fn get_epoch_from_condition(&self, epoch: Epoch) -> StdResult<WhereCondition> {
+        Ok(WhereCondition::new(
+            "epoch >= ?*",
+            vec![Value::Integer(epoch.try_into()?)],
+        ))
+    }
+
+    /// Condition to get the certificates of the given epoch or of an earlier one.
+    pub fn get_epoch_to_condition(&self, epoch: Epoch) -> StdResult<WhereCondition> {
+        Ok(WhereCondition::new(
+            "epoch <= ?*",
+            vec![Value::Integer(epoch.try_into()?)],
+        ))
+    }
+
+    /// Condition to get the certificates of the given signed entity type.
+    pub fn get_signed_entity_type_discriminant_condition(
+        &self,
+        signed_entity_type_discriminant: &SignedEntityTypeDiscriminants,
+    ) -> WhereCondition {
+        WhereCondition::new(
+            "signed_entity_type_id = ?*",
+            vec![Value::Integer(
+                signed_entity_type_discriminant.index() as i64
+            )],
+        )
+    }
+
+    /// Condition to get the genesis key rotation certificates, i.e. the certificates whose
+    /// protocol message certifies a next genesis verification key.
+    pub fn get_genesis_key_rotation_condition(&self) -> WhereCondition {
+        WhereCondition::new(
+            "json_extract(protocol_message, '$.message_parts.next_genesis_verification_key') is not null",
+            vec![],
+        )
+    }
+
     /// Get CertificateRecords for a given certificate id.
     pub fn get_by_certificate_id(
         &self,
@@ -61,7 +99,7 @@ impl GetAllCondition for GetCertificateRecordProvider<'_> {}
 impl<'client> Provider<'client> for GetCertificateRecordProvider<'client> {
     type Entity = CertificateRecord;
 
-    fn get_connection(&'client self) -> &'client ConnectionThreadSafe {
+    fn get_connection(&'client self) -> &'client SqliteConnection {
         self.client
     }
 
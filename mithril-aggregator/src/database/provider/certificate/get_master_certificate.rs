@@ -1,18 +1,20 @@
-use sqlite::{ConnectionThreadSafe, Value};
+use sqlite::Value;
 
 use mithril_common::entities::Epoch;
-use mithril_persistence::sqlite::{Provider, SourceAlias, SqLiteEntity, WhereCondition};
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
 
 use crate::database::record::CertificateRecord;
 
 /// Query to obtains the master [CertificateRecord] of an epoch
 pub struct MasterCertificateProvider<'conn> {
-    connection: &'conn ConnectionThreadSafe,
+    connection: &'conn SqliteConnection,
 }
 
 impl<'conn> MasterCertificateProvider<'conn> {
     /// Create a new instance
-    pub fn new(connection: &'conn ConnectionThreadSafe) -> Self {
+    pub fn new(connection: &'conn SqliteConnection) -> Self {
         Self { connection }
     }
 
@@ -23,9 +25,16 @@ impl<'conn> MasterCertificateProvider<'conn> {
             vec![Value::Integer(epoch_i64 - 1), Value::Integer(epoch_i64)],
         )
         .and_where(
-            WhereCondition::new("certificate.parent_certificate_id is null", vec![]).or_where(
-                WhereCondition::new("certificate.epoch != parent_certificate.epoch", vec![]),
-            ),
+            WhereCondition::new("certificate.parent_certificate_id is null", vec![])
+                .or_where(WhereCondition::new(
+                    "certificate.epoch != parent_certificate.epoch",
+                    vec![],
+                ))
+                // genesis key rotations are chained to a parent certificate of the same epoch
+                .or_where(WhereCondition::new(
+                    "json_extract(certificate.protocol_message, '$.message_parts.next_genesis_verification_key') is not null",
+                    vec![],
+                )),
         )
     }
 }
@@ -33,7 +42,7 @@ impl<'conn> MasterCertificateProvider<'conn> {
 impl<'conn> Provider<'conn> for MasterCertificateProvider<'conn> {
     type Entity = CertificateRecord;
 
-    fn get_connection(&'conn self) -> &'conn ConnectionThreadSafe {
+    fn get_connection(&'conn self) -> &'conn SqliteConnection {
         self.connection
     }
 
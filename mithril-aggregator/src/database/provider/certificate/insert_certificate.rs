@@ -1,20 +1,22 @@
 use std::iter::repeat;
 
-use sqlite::{ConnectionThreadSafe, Value};
+use sqlite::Value;
 
 use mithril_common::StdResult;
-use mithril_persistence::sqlite::{Provider, SourceAlias, SqLiteEntity, WhereCondition};
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
 
 use crate::database::record::CertificateRecord;
 
 /// Query to insert [CertificateRecord] in the sqlite database
 pub struct InsertCertificateRecordProvider<'conn> {
-    connection: &'conn ConnectionThreadSafe,
+    connection: &'conn SqliteConnection,
 }
 
 impl<'conn> InsertCertificateRecordProvider<'conn> {
     /// Create a new instance
-    pub fn new(connection: &'conn ConnectionThreadSafe) -> Self {
+    pub fn new(connection: &'conn SqliteConnection) -> Self {
         Self { connection }
     }
 
@@ -42,9 +44,10 @@ impl<'conn> InsertCertificateRecordProvider<'conn> {
         protocol_message, \
         signers, \
         initiated_at, \
-        sealed_at)";
+        sealed_at, \
+        era)";
         let values_columns: Vec<&str> =
-            repeat("(?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*)")
+            repeat("(?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*, ?*)")
                 .take(certificates_records.len())
                 .collect();
 
@@ -80,6 +83,10 @@ impl<'conn> InsertCertificateRecordProvider<'conn> {
                     Value::String(serde_json::to_string(&certificate_record.signers).unwrap()),
                     Value::String(certificate_record.initiated_at.to_rfc3339()),
                     Value::String(certificate_record.sealed_at.to_rfc3339()),
+                    match certificate_record.era {
+                        Some(era) => Value::String(era.to_string()),
+                        None => Value::Null,
+                    },
                 ]
             })
             .collect();
@@ -118,7 +125,7 @@ impl<'conn> InsertCertificateRecordProvider<'conn> {
 impl<'conn> Provider<'conn> for InsertCertificateRecordProvider<'conn> {
     type Entity = CertificateRecord;
 
-    fn get_connection(&'conn self) -> &'conn ConnectionThreadSafe {
+    fn get_connection(&'conn self) -> &'conn SqliteConnection {
         self.connection
     }
 
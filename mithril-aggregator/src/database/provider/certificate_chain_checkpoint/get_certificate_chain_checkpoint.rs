@@ -0,0 +1,46 @@
+use sqlite::Value;
+
+use mithril_common::entities::Epoch;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::CertificateChainCheckpointRecord;
+
+/// Simple queries to retrieve [CertificateChainCheckpointRecord] from the sqlite database.
+pub struct GetCertificateChainCheckpointProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> GetCertificateChainCheckpointProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_epoch_condition(&self, epoch: Epoch) -> StdResult<WhereCondition> {
+        Ok(WhereCondition::new(
+            "epoch = ?*",
+            vec![Value::Integer(epoch.try_into()?)],
+        ))
+    }
+}
+
+impl<'client> Provider<'client> for GetCertificateChainCheckpointProvider<'client> {
+    type Entity = CertificateChainCheckpointRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[(
+            "{:certificate_chain_checkpoint:}",
+            "certificate_chain_checkpoint",
+        )]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!("select {projection} from certificate_chain_checkpoint where {condition} order by epoch desc")
+    }
+}
@@ -0,0 +1,56 @@
+use sqlite::Value;
+
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::CertificateChainCheckpointRecord;
+
+/// Query to insert [CertificateChainCheckpointRecord] in the sqlite database, the checkpoint
+/// already recorded for an epoch is kept.
+pub struct InsertCertificateChainCheckpointProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> InsertCertificateChainCheckpointProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_insert_condition(
+        &self,
+        record: &CertificateChainCheckpointRecord,
+    ) -> StdResult<WhereCondition> {
+        let expression = "(epoch, certificate_hash, signature, created_at) values (?*, ?*, ?*, ?*)";
+        let parameters = vec![
+            Value::Integer(record.epoch.try_into()?),
+            Value::String(record.certificate_hash.clone()),
+            Value::String(record.signature.clone()),
+            Value::String(record.created_at.to_rfc3339()),
+        ];
+
+        Ok(WhereCondition::new(expression, parameters))
+    }
+}
+
+impl<'client> Provider<'client> for InsertCertificateChainCheckpointProvider<'client> {
+    type Entity = CertificateChainCheckpointRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[(
+            "{:certificate_chain_checkpoint:}",
+            "certificate_chain_checkpoint",
+        )]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!(
+            "insert or ignore into certificate_chain_checkpoint {condition} returning {projection}"
+        )
+    }
+}
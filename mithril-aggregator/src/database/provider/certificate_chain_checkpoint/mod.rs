@@ -0,0 +1,5 @@
+mod get_certificate_chain_checkpoint;
+mod insert_certificate_chain_checkpoint;
+
+pub use get_certificate_chain_checkpoint::*;
+pub use insert_certificate_chain_checkpoint::*;
@@ -2,9 +2,7 @@ use sqlite::Value;
 
 use mithril_common::entities::{Epoch, ProtocolParameters};
 use mithril_common::StdResult;
-use mithril_persistence::sqlite::{
-    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
-};
+use mithril_persistence::sqlite::{upsert_definition, Provider, SqliteConnection, WhereCondition};
 
 use crate::database::record::EpochSettingRecord;
 
@@ -27,12 +25,12 @@ impl<'conn> UpdateEpochSettingProvider<'conn> {
     ) -> WhereCondition {
         let epoch_setting_id: i64 = epoch.try_into().unwrap();
 
-        WhereCondition::new(
-            "(epoch_setting_id, protocol_parameters) values (?1, ?2)",
-            vec![
+        WhereCondition::insert_values(
+            &["epoch_setting_id", "protocol_parameters"],
+            vec![vec![
                 Value::Integer(epoch_setting_id),
                 Value::String(serde_json::to_string(&protocol_parameters).unwrap()),
-            ],
+            ]],
         )
     }
 
@@ -60,21 +58,24 @@ impl<'conn> Provider<'conn> for UpdateEpochSettingProvider<'conn> {
     }
 
     fn get_definition(&self, condition: &str) -> String {
-        // it is important to alias the fields with the same name as the table
-        // since the table cannot be aliased in a RETURNING statement in SQLite.
-        let projection = Self::Entity::get_projection()
-            .expand(SourceAlias::new(&[("{:epoch_setting:}", "epoch_setting")]));
-
-        format!("insert or replace into epoch_setting {condition} returning {projection}")
+        upsert_definition::<Self::Entity>(
+            "epoch_setting",
+            &["epoch_setting_id"],
+            &["protocol_parameters"],
+            condition,
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use mithril_common::test_utils::fake_data;
+    use mithril_persistence::sqlite::{ConnectionBuilder, ConnectionOptions};
 
     use crate::database::provider::GetEpochSettingProvider;
-    use crate::database::test_helper::{insert_epoch_settings, main_db_connection};
+    use crate::database::test_helper::{
+        insert_epoch_settings, insert_signer_registrations, main_db_connection,
+    };
 
     use super::*;
 
@@ -107,4 +108,30 @@ mod tests {
         );
         assert_eq!(0, cursor.count());
     }
+
+    #[test]
+    fn test_update_epoch_setting_keeps_the_records_referencing_it() {
+        let connection = ConnectionBuilder::open_memory()
+            .with_options(&[ConnectionOptions::EnableForeignKeys])
+            .with_migrations(crate::database::migration::get_migrations())
+            .build()
+            .unwrap();
+        insert_epoch_settings(&connection, &[3]).unwrap();
+        insert_signer_registrations(
+            &connection,
+            vec![(Epoch(3), fake_data::signers_with_stakes(2))],
+        )
+        .unwrap();
+
+        let provider = UpdateEpochSettingProvider::new(&connection);
+        provider
+            .persist(Epoch(3), fake_data::protocol_parameters())
+            .unwrap();
+
+        let mut statement = connection
+            .prepare("select count(*) from signer_registration where epoch_setting_id = 3")
+            .unwrap();
+        let registrations_count = statement.iter().next().unwrap().unwrap().read::<i64, _>(0);
+        assert_eq!(2, registrations_count);
+    }
 }
@@ -1,22 +1,38 @@
 //! Aggregator related database providers
 mod block_range_root;
+mod buffered_single_signature;
 mod cardano_transaction;
 mod certificate;
+mod certificate_chain_checkpoint;
 mod epoch_setting;
 mod open_message;
+mod runtime_decision;
 mod signed_entity;
+mod signed_entity_lease;
 mod signer;
+mod signer_metadata;
+mod signer_participation;
 mod signer_registration;
+mod signer_registration_next_era;
+mod signer_unregistration;
 mod single_signature;
 mod stake_pool;
 
 pub use block_range_root::*;
+pub use buffered_single_signature::*;
 pub use cardano_transaction::*;
 pub use certificate::*;
+pub use certificate_chain_checkpoint::*;
 pub use epoch_setting::*;
 pub use open_message::*;
+pub use runtime_decision::*;
 pub use signed_entity::*;
+pub use signed_entity_lease::*;
 pub use signer::*;
+pub use signer_metadata::*;
+pub use signer_participation::*;
 pub use signer_registration::*;
+pub use signer_registration_next_era::*;
+pub use signer_unregistration::*;
 pub use single_signature::*;
 pub use stake_pool::*;
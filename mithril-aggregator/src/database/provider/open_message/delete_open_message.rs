@@ -1,9 +1,8 @@
 use sqlite::Value;
 
-use mithril_common::entities::Epoch;
-use mithril_persistence::sqlite::{
-    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
-};
+use mithril_common::entities::{Epoch, SignedEntityType};
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{delete_definition, Provider, SqliteConnection, WhereCondition};
 
 use crate::database::record::OpenMessageRecord;
 
@@ -21,6 +20,20 @@ impl<'client> DeleteOpenMessageProvider<'client> {
     pub fn get_epoch_condition(&self, epoch: Epoch) -> WhereCondition {
         WhereCondition::new("epoch_setting_id < ?*", vec![Value::Integer(*epoch as i64)])
     }
+
+    pub fn get_signed_entity_type_condition(
+        &self,
+        signed_entity_type: &SignedEntityType,
+    ) -> StdResult<WhereCondition> {
+        Ok(WhereCondition::new(
+            "epoch_setting_id = ?* and signed_entity_type_id = ?* and beacon = ?*",
+            vec![
+                Value::Integer(*signed_entity_type.get_epoch() as i64),
+                Value::Integer(signed_entity_type.index() as i64),
+                Value::String(signed_entity_type.get_json_beacon()?),
+            ],
+        ))
+    }
 }
 
 impl<'client> Provider<'client> for DeleteOpenMessageProvider<'client> {
@@ -31,9 +44,6 @@ impl<'client> Provider<'client> for DeleteOpenMessageProvider<'client> {
     }
 
     fn get_definition(&self, condition: &str) -> String {
-        let aliases = SourceAlias::new(&[("{:open_message:}", "open_message")]);
-        let projection = Self::Entity::get_projection().expand(aliases);
-
-        format!("delete from open_message where {condition} returning {projection}")
+        delete_definition::<Self::Entity>("open_message", condition)
     }
 }
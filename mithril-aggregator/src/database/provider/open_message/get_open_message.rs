@@ -1,7 +1,7 @@
 use sqlite::Value;
 
 use mithril_common::{
-    entities::{Epoch, SignedEntityType},
+    entities::{Epoch, SignedEntityType, SignedEntityTypeDiscriminants},
     StdResult,
 };
 use mithril_persistence::sqlite::{
@@ -38,6 +38,18 @@ impl<'client> GetOpenMessageProvider<'client> {
         ))
     }
 
+    pub fn get_signed_entity_type_discriminant_condition(
+        &self,
+        signed_entity_type_discriminant: &SignedEntityTypeDiscriminants,
+    ) -> WhereCondition {
+        WhereCondition::new(
+            "signed_entity_type_id = ?*",
+            vec![Value::Integer(
+                signed_entity_type_discriminant.index() as i64
+            )],
+        )
+    }
+
     pub fn get_expired_entity_type_condition(&self, now: &str) -> WhereCondition {
         WhereCondition::new("expires_at < ?*", vec![Value::String(now.to_string())])
     }
@@ -0,0 +1,59 @@
+use sqlite::Value;
+use uuid::Uuid;
+
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::OpenMessageQuorumStatusRecord;
+
+/// Aggregation query of the single signatures registered for an open message, retrieving
+/// [OpenMessageQuorumStatusRecord] from the sqlite database.
+pub struct GetOpenMessageQuorumStatusProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> GetOpenMessageQuorumStatusProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_open_message_id_condition(&self, open_message_id: &Uuid) -> WhereCondition {
+        WhereCondition::new(
+            "open_message.open_message_id = ?*",
+            vec![Value::String(open_message_id.to_string())],
+        )
+    }
+}
+
+impl<'client> Provider<'client> for GetOpenMessageQuorumStatusProvider<'client> {
+    type Entity = OpenMessageQuorumStatusRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[
+            ("{:open_message:}", "open_message"),
+            ("{:single_signature:}", "single_signature"),
+            ("{:signer_registration:}", "signer_registration"),
+        ]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!(
+            r#"
+select {projection}
+from open_message
+    left outer join single_signature
+        on open_message.open_message_id = single_signature.open_message_id
+    left outer join signer_registration
+        on single_signature.signer_id = signer_registration.signer_id
+        and single_signature.registration_epoch_setting_id = signer_registration.epoch_setting_id
+where {condition}
+group by open_message.open_message_id
+"#
+        )
+    }
+}
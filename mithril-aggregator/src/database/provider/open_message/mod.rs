@@ -1,11 +1,13 @@
 mod delete_open_message;
 mod get_open_message;
+mod get_open_message_quorum_status;
 mod get_open_message_with_single_signatures;
 mod insert_open_message;
 mod update_open_message;
 
 pub use delete_open_message::*;
 pub use get_open_message::*;
+pub use get_open_message_quorum_status::*;
 pub use get_open_message_with_single_signatures::*;
 pub use insert_open_message::*;
 pub use update_open_message::*;
@@ -25,7 +25,7 @@ impl<'client> UpdateOpenMessageProvider<'client> {
     ) -> StdResult<WhereCondition> {
         let expression = "epoch_setting_id = ?*, beacon = ?*, \
 signed_entity_type_id = ?*, protocol_message = ?*, is_certified = ?*, \
-is_expired = ?*, expires_at = ?* where open_message_id = ?*";
+is_expired = ?*, expires_at = ?*, reopening_count = ?* where open_message_id = ?*";
         let beacon_str = open_message.signed_entity_type.get_json_beacon()?;
         let parameters = vec![
             Value::Integer(
@@ -43,6 +43,7 @@ is_expired = ?*, expires_at = ?* where open_message_id = ?*";
                 .expires_at
                 .map(|d| Value::String(d.to_rfc3339()))
                 .unwrap_or(Value::Null),
+            Value::Integer(open_message.reopening_count as i64),
             Value::String(open_message.open_message_id.to_string()),
         ];
 
@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use sqlite::Value;
+
+use mithril_common::entities::SignedEntityTypeDiscriminants;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::RuntimeDecisionRecord;
+
+/// Simple queries to retrieve [RuntimeDecisionRecord] from the sqlite database.
+pub struct GetRuntimeDecisionProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> GetRuntimeDecisionProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_signed_entity_type_condition(
+        &self,
+        signed_entity_type: &SignedEntityTypeDiscriminants,
+    ) -> WhereCondition {
+        WhereCondition::new(
+            "signed_entity_type_id = ?*",
+            vec![Value::Integer(signed_entity_type.index() as i64)],
+        )
+    }
+
+    pub fn get_last_seen_since_condition(&self, since: &DateTime<Utc>) -> WhereCondition {
+        WhereCondition::new(
+            "last_seen_at >= ?*",
+            vec![Value::String(since.to_rfc3339())],
+        )
+    }
+}
+
+impl<'client> Provider<'client> for GetRuntimeDecisionProvider<'client> {
+    type Entity = RuntimeDecisionRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:runtime_decision:}", "runtime_decision")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!("select {projection} from runtime_decision where {condition} order by last_seen_at desc, runtime_decision_id desc")
+    }
+}
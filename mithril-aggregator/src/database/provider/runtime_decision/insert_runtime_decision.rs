@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use sqlite::Value;
+
+use mithril_common::entities::SignedEntityType;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::{RuntimeDecision, RuntimeDecisionRecord};
+
+/// Query to insert [RuntimeDecisionRecord] in the sqlite database
+pub struct InsertRuntimeDecisionProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> InsertRuntimeDecisionProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_insert_condition(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        decision: RuntimeDecision,
+        reason: Option<String>,
+        decided_at: DateTime<Utc>,
+    ) -> StdResult<WhereCondition> {
+        let expression = "(signed_entity_type_id, beacon, decision, reason, created_at, last_seen_at) values (?*, ?*, ?*, ?*, ?*, ?*)";
+        let parameters = vec![
+            Value::Integer(signed_entity_type.index() as i64),
+            Value::String(signed_entity_type.get_json_beacon()?),
+            Value::String(decision.to_string()),
+            reason.map(Value::String).unwrap_or(Value::Null),
+            Value::String(decided_at.to_rfc3339()),
+            Value::String(decided_at.to_rfc3339()),
+        ];
+
+        Ok(WhereCondition::new(expression, parameters))
+    }
+}
+
+impl<'client> Provider<'client> for InsertRuntimeDecisionProvider<'client> {
+    type Entity = RuntimeDecisionRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:runtime_decision:}", "runtime_decision")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!("insert into runtime_decision {condition} returning {projection}")
+    }
+}
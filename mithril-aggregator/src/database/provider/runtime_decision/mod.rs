@@ -0,0 +1,7 @@
+mod get_runtime_decision;
+mod insert_runtime_decision;
+mod update_runtime_decision;
+
+pub use get_runtime_decision::*;
+pub use insert_runtime_decision::*;
+pub use update_runtime_decision::*;
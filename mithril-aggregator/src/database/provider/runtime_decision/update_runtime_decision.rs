@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use sqlite::Value;
+
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::RuntimeDecisionRecord;
+
+/// Query to update the `last_seen_at` field of a [RuntimeDecisionRecord] in the sqlite database
+pub struct UpdateRuntimeDecisionLastSeenProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> UpdateRuntimeDecisionLastSeenProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_update_condition(
+        &self,
+        runtime_decision_id: i64,
+        last_seen_at: DateTime<Utc>,
+    ) -> WhereCondition {
+        WhereCondition::new(
+            "last_seen_at = ?* where runtime_decision_id = ?*",
+            vec![
+                Value::String(last_seen_at.to_rfc3339()),
+                Value::Integer(runtime_decision_id),
+            ],
+        )
+    }
+}
+
+impl<'client> Provider<'client> for UpdateRuntimeDecisionLastSeenProvider<'client> {
+    type Entity = RuntimeDecisionRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:runtime_decision:}", "runtime_decision")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!("update runtime_decision set {condition} returning {projection}")
+    }
+}
@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use sqlite::Value;
+
+use mithril_common::entities::SignedEntityType;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SignedEntityLeaseRecord;
+
+/// Query to acquire a [SignedEntityLeaseRecord] in the sqlite database.
+///
+/// The lease is returned only if it was acquired: it did not exist, it was stale or it was
+/// already held by the same holder (in which case it is extended).
+pub struct AcquireSignedEntityLeaseProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> AcquireSignedEntityLeaseProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_acquire_condition(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        holder: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> StdResult<WhereCondition> {
+        let expression = "(signed_entity_type_id, beacon, holder, expires_at) values (?*, ?*, ?*, ?*) \
+            on conflict (signed_entity_type_id, beacon) do update set holder = excluded.holder, expires_at = excluded.expires_at \
+            where signed_entity_lease.holder = excluded.holder or signed_entity_lease.expires_at <= ?*";
+        let parameters = vec![
+            Value::Integer(signed_entity_type.index() as i64),
+            Value::String(signed_entity_type.get_json_beacon()?),
+            Value::String(holder.to_owned()),
+            Value::String(expires_at.to_rfc3339()),
+            Value::String(now.to_rfc3339()),
+        ];
+
+        Ok(WhereCondition::new(expression, parameters))
+    }
+}
+
+impl<'client> Provider<'client> for AcquireSignedEntityLeaseProvider<'client> {
+    type Entity = SignedEntityLeaseRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:signed_entity_lease:}", "signed_entity_lease")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!("insert into signed_entity_lease {condition} returning {projection}")
+    }
+}
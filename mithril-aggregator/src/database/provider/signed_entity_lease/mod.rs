@@ -0,0 +1,5 @@
+mod acquire_signed_entity_lease;
+mod release_signed_entity_lease;
+
+pub use acquire_signed_entity_lease::*;
+pub use release_signed_entity_lease::*;
@@ -0,0 +1,51 @@
+use sqlite::Value;
+
+use mithril_common::entities::SignedEntityType;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SignedEntityLeaseRecord;
+
+/// Query to release a [SignedEntityLeaseRecord] held by a holder in the sqlite database
+pub struct ReleaseSignedEntityLeaseProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> ReleaseSignedEntityLeaseProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_release_condition(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        holder: &str,
+    ) -> StdResult<WhereCondition> {
+        Ok(WhereCondition::new(
+            "signed_entity_type_id = ?* and beacon = ?* and holder = ?*",
+            vec![
+                Value::Integer(signed_entity_type.index() as i64),
+                Value::String(signed_entity_type.get_json_beacon()?),
+                Value::String(holder.to_owned()),
+            ],
+        ))
+    }
+}
+
+impl<'client> Provider<'client> for ReleaseSignedEntityLeaseProvider<'client> {
+    type Entity = SignedEntityLeaseRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:signed_entity_lease:}", "signed_entity_lease")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!("delete from signed_entity_lease where {condition} returning {projection}")
+    }
+}
@@ -0,0 +1,41 @@
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SignerMetadataRecord;
+
+/// Simple queries to retrieve [SignerMetadataRecord] from the sqlite database.
+pub struct GetSignerMetadataProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> GetSignerMetadataProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Condition to get the metadata of the signers that registered at the latest known
+    /// registration epoch.
+    pub fn get_latest_registration_epoch_condition(&self) -> WhereCondition {
+        WhereCondition::new(
+            "registration_epoch = (select max(registration_epoch) from signer_metadata)",
+            vec![],
+        )
+    }
+}
+
+impl<'client> Provider<'client> for GetSignerMetadataProvider<'client> {
+    type Entity = SignerMetadataRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:signer_metadata:}", "signer_metadata")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!("select {projection} from signer_metadata where {condition} order by signer_id")
+    }
+}
@@ -0,0 +1,60 @@
+use sqlite::Value;
+
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SignerMetadataRecord;
+
+/// Query to insert or replace a [SignerMetadataRecord] in the sqlite database
+pub struct InsertOrReplaceSignerMetadataProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> InsertOrReplaceSignerMetadataProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_insert_or_replace_condition(
+        &self,
+        signer_metadata: &SignerMetadataRecord,
+    ) -> StdResult<WhereCondition> {
+        let expression = "(signer_id, registration_epoch, node_version, supported_eras, ip_address, updated_at) values (?*, ?*, ?*, ?*, ?*, ?*)";
+        let parameters = vec![
+            Value::String(signer_metadata.signer_id.clone()),
+            Value::Integer(signer_metadata.registration_epoch.try_into()?),
+            signer_metadata
+                .node_version
+                .clone()
+                .map(Value::String)
+                .unwrap_or(Value::Null),
+            Value::String(serde_json::to_string(&signer_metadata.supported_eras)?),
+            signer_metadata
+                .ip_address
+                .clone()
+                .map(Value::String)
+                .unwrap_or(Value::Null),
+            Value::String(signer_metadata.updated_at.to_rfc3339()),
+        ];
+
+        Ok(WhereCondition::new(expression, parameters))
+    }
+}
+
+impl<'client> Provider<'client> for InsertOrReplaceSignerMetadataProvider<'client> {
+    type Entity = SignerMetadataRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:signer_metadata:}", "signer_metadata")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!("insert or replace into signer_metadata {condition} returning {projection}")
+    }
+}
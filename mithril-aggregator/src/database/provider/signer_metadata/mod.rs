@@ -0,0 +1,5 @@
+mod get_signer_metadata;
+mod insert_or_replace_signer_metadata;
+
+pub use get_signer_metadata::*;
+pub use insert_or_replace_signer_metadata::*;
@@ -0,0 +1,41 @@
+use sqlite::Value;
+
+use mithril_common::entities::PartyId;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SignerParticipationRecord;
+
+/// Simple queries to retrieve [SignerParticipationRecord] from the sqlite database.
+pub struct GetSignerParticipationProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> GetSignerParticipationProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_signer_id_condition(&self, signer_id: &PartyId) -> WhereCondition {
+        WhereCondition::new("signer_id = ?*", vec![Value::String(signer_id.to_owned())])
+    }
+}
+
+impl<'client> Provider<'client> for GetSignerParticipationProvider<'client> {
+    type Entity = SignerParticipationRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:signer_participation:}", "signer_participation")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!(
+            "select {projection} from signer_participation where {condition} order by epoch desc"
+        )
+    }
+}
@@ -0,0 +1,5 @@
+mod get_signer_participation;
+mod upsert_signer_participation;
+
+pub use get_signer_participation::*;
+pub use upsert_signer_participation::*;
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use sqlite::Value;
+
+use mithril_common::entities::{Epoch, PartyId};
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SignerParticipationRecord;
+
+/// Query to create or update a [SignerParticipationRecord] in the sqlite database
+pub struct UpsertSignerParticipationProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> UpsertSignerParticipationProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Condition to mark the signer as registered at the given epoch.
+    pub fn get_registration_condition(
+        &self,
+        signer_id: &PartyId,
+        epoch: Epoch,
+        updated_at: DateTime<Utc>,
+    ) -> StdResult<WhereCondition> {
+        let expression = "(signer_id, epoch, registered, signatures_issued, updated_at) values (?*, ?*, 1, 0, ?*) \
+            on conflict (signer_id, epoch) do update set registered = 1, updated_at = excluded.updated_at";
+        let parameters = vec![
+            Value::String(signer_id.to_owned()),
+            Value::Integer(epoch.try_into()?),
+            Value::String(updated_at.to_rfc3339()),
+        ];
+
+        Ok(WhereCondition::new(expression, parameters))
+    }
+
+    /// Condition to count one more single signature issued by the signer at the given epoch.
+    pub fn get_signature_condition(
+        &self,
+        signer_id: &PartyId,
+        epoch: Epoch,
+        updated_at: DateTime<Utc>,
+    ) -> StdResult<WhereCondition> {
+        let expression = "(signer_id, epoch, registered, signatures_issued, updated_at) values (?*, ?*, 0, 1, ?*) \
+            on conflict (signer_id, epoch) do update set signatures_issued = signatures_issued + 1, updated_at = excluded.updated_at";
+        let parameters = vec![
+            Value::String(signer_id.to_owned()),
+            Value::Integer(epoch.try_into()?),
+            Value::String(updated_at.to_rfc3339()),
+        ];
+
+        Ok(WhereCondition::new(expression, parameters))
+    }
+}
+
+impl<'client> Provider<'client> for UpsertSignerParticipationProvider<'client> {
+    type Entity = SignerParticipationRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:signer_participation:}", "signer_participation")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!("insert into signer_participation {condition} returning {projection}")
+    }
+}
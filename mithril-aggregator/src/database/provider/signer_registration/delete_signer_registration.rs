@@ -3,12 +3,12 @@ use sqlite::Value;
 use mithril_common::entities::Epoch;
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::{
-    EntityCursor, Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+    delete_definition, EntityCursor, Provider, SqliteConnection, WhereCondition,
 };
 
 use crate::database::record::SignerRegistrationRecord;
 
-/// Query to delete old [SignerRegistrationRecord] from the sqlite database
+/// Query to delete [SignerRegistrationRecord] from the sqlite database
 pub struct DeleteSignerRegistrationRecordProvider<'conn> {
     connection: &'conn SqliteConnection,
 }
@@ -21,14 +21,7 @@ impl<'conn> Provider<'conn> for DeleteSignerRegistrationRecordProvider<'conn> {
     }
 
     fn get_definition(&self, condition: &str) -> String {
-        // it is important to alias the fields with the same name as the table
-        // since the table cannot be aliased in a RETURNING statement in SQLite.
-        let projection = Self::Entity::get_projection().expand(SourceAlias::new(&[(
-            "{:signer_registration:}",
-            "signer_registration",
-        )]));
-
-        format!("delete from signer_registration where {condition} returning {projection}")
+        delete_definition::<Self::Entity>("signer_registration", condition)
     }
 }
 
@@ -54,4 +47,17 @@ impl<'conn> DeleteSignerRegistrationRecordProvider<'conn> {
 
         self.find(filters)
     }
+
+    /// Delete the registration of the given signer at the given epoch.
+    pub fn delete_by_signer_id_and_epoch(
+        &self,
+        signer_id: String,
+        epoch: Epoch,
+    ) -> StdResult<EntityCursor<SignerRegistrationRecord>> {
+        let epoch = Value::Integer(epoch.try_into()?);
+        let filters = WhereCondition::new("signer_id = ?*", vec![Value::String(signer_id)])
+            .and_where(WhereCondition::new("epoch_setting_id = ?*", vec![epoch]));
+
+        self.find(filters)
+    }
 }
@@ -0,0 +1,59 @@
+use sqlite::Value;
+
+use mithril_common::entities::Epoch;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    delete_definition, EntityCursor, Provider, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SignerRegistrationNextEraRecord;
+
+/// Query to delete [SignerRegistrationNextEraRecord] from the sqlite database
+pub struct DeleteSignerRegistrationNextEraRecordProvider<'conn> {
+    connection: &'conn SqliteConnection,
+}
+
+impl<'conn> Provider<'conn> for DeleteSignerRegistrationNextEraRecordProvider<'conn> {
+    type Entity = SignerRegistrationNextEraRecord;
+
+    fn get_connection(&'conn self) -> &'conn SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        delete_definition::<Self::Entity>("signer_registration_next_era", condition)
+    }
+}
+
+impl<'conn> DeleteSignerRegistrationNextEraRecordProvider<'conn> {
+    /// Create a new instance
+    pub fn new(connection: &'conn SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Prune the next era registrations older than the given epoch.
+    pub fn prune(
+        &self,
+        epoch_threshold: Epoch,
+    ) -> StdResult<EntityCursor<SignerRegistrationNextEraRecord>> {
+        let epoch_threshold = Value::Integer(epoch_threshold.try_into()?);
+
+        self.find(WhereCondition::new(
+            "epoch_setting_id < ?*",
+            vec![epoch_threshold],
+        ))
+    }
+
+    /// Delete the next era registrations of the given signer at the given epoch.
+    pub fn delete_by_signer_id_and_epoch(
+        &self,
+        signer_id: String,
+        epoch: Epoch,
+    ) -> StdResult<EntityCursor<SignerRegistrationNextEraRecord>> {
+        let epoch = Value::Integer(epoch.try_into()?);
+        let filters = WhereCondition::new("signer_id = ?*", vec![Value::String(signer_id)])
+            .and_where(WhereCondition::new("epoch_setting_id = ?*", vec![epoch]));
+
+        self.find(filters)
+    }
+}
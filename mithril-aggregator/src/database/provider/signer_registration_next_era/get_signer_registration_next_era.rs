@@ -0,0 +1,54 @@
+use sqlite::Value;
+
+use mithril_common::entities::Epoch;
+use mithril_common::era::SupportedEra;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    EntityCursor, Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SignerRegistrationNextEraRecord;
+
+/// Simple queries to retrieve [SignerRegistrationNextEraRecord] from the sqlite database.
+pub struct GetSignerRegistrationNextEraRecordProvider<'client> {
+    client: &'client SqliteConnection,
+}
+
+impl<'client> GetSignerRegistrationNextEraRecordProvider<'client> {
+    /// Create a new provider
+    pub fn new(client: &'client SqliteConnection) -> Self {
+        Self { client }
+    }
+
+    /// Get the SignerRegistrationNextEraRecords of the given era for the given epoch.
+    pub fn get_by_epoch_and_era(
+        &self,
+        epoch: Epoch,
+        era: SupportedEra,
+    ) -> StdResult<EntityCursor<SignerRegistrationNextEraRecord>> {
+        let filters = WhereCondition::new(
+            "epoch_setting_id = ?*",
+            vec![Value::Integer(epoch.try_into()?)],
+        )
+        .and_where(WhereCondition::new(
+            "era = ?*",
+            vec![Value::String(era.to_string())],
+        ));
+
+        self.find(filters)
+    }
+}
+
+impl<'client> Provider<'client> for GetSignerRegistrationNextEraRecordProvider<'client> {
+    type Entity = SignerRegistrationNextEraRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.client
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:signer_registration_next_era:}", "srne")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+        format!("select {projection} from signer_registration_next_era as srne where {condition} order by ROWID desc")
+    }
+}
@@ -0,0 +1,124 @@
+use sqlite::Value;
+
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SignerRegistrationNextEraRecord;
+
+/// Query to insert or replace [SignerRegistrationNextEraRecord] in the sqlite database
+pub struct InsertOrReplaceSignerRegistrationNextEraRecordProvider<'conn> {
+    connection: &'conn SqliteConnection,
+}
+
+impl<'conn> InsertOrReplaceSignerRegistrationNextEraRecordProvider<'conn> {
+    /// Create a new instance
+    pub fn new(connection: &'conn SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_insert_or_replace_condition(
+        &self,
+        record: SignerRegistrationNextEraRecord,
+    ) -> StdResult<WhereCondition> {
+        let expression = "(signer_id, epoch_setting_id, era, verification_key, verification_key_signature, created_at) values (?*, ?*, ?*, ?*, ?*, ?*)";
+        let parameters = vec![
+            Value::String(record.signer_id),
+            Value::Integer(record.epoch_setting_id.try_into()?),
+            Value::String(record.era),
+            Value::String(record.verification_key),
+            record
+                .verification_key_signature
+                .map(Value::String)
+                .unwrap_or(Value::Null),
+            Value::String(record.created_at.to_rfc3339()),
+        ];
+
+        Ok(WhereCondition::new(expression, parameters))
+    }
+
+    pub fn persist(
+        &self,
+        record: SignerRegistrationNextEraRecord,
+    ) -> StdResult<SignerRegistrationNextEraRecord> {
+        let filters = self.get_insert_or_replace_condition(record.clone())?;
+
+        let entity = self.find(filters)?.next().unwrap_or_else(|| {
+            panic!("No entity returned by the persister, signer_registration_next_era_record = {record:?}")
+        });
+
+        Ok(entity)
+    }
+}
+
+impl<'conn> Provider<'conn> for InsertOrReplaceSignerRegistrationNextEraRecordProvider<'conn> {
+    type Entity = SignerRegistrationNextEraRecord;
+
+    fn get_connection(&'conn self) -> &'conn SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        // it is important to alias the fields with the same name as the table
+        // since the table cannot be aliased in a RETURNING statement in SQLite.
+        let projection = Self::Entity::get_projection().expand(SourceAlias::new(&[(
+            "{:signer_registration_next_era:}",
+            "signer_registration_next_era",
+        )]));
+
+        format!(
+            "insert or replace into signer_registration_next_era {condition} returning {projection}"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::{Epoch, Signer};
+    use mithril_common::era::SupportedEra;
+    use mithril_common::test_utils::fake_data;
+
+    use crate::database::provider::GetSignerRegistrationNextEraRecordProvider;
+    use crate::database::test_helper::main_db_connection;
+
+    use super::*;
+
+    #[test]
+    fn persist_and_get_the_next_era_registrations_of_an_era() {
+        let connection = main_db_connection().unwrap();
+        let provider = InsertOrReplaceSignerRegistrationNextEraRecordProvider::new(&connection);
+        let signers = fake_data::signers(2);
+        let era = SupportedEra::dummy();
+
+        for signer in signers.clone() {
+            let record = SignerRegistrationNextEraRecord::from_signer(signer, Epoch(1), era);
+            let saved_record = provider.persist(record.clone()).unwrap();
+            assert_eq!(record, saved_record);
+        }
+        let replaced_record = SignerRegistrationNextEraRecord::from_signer(
+            Signer {
+                verification_key: signers[1].verification_key.clone(),
+                ..signers[0].clone()
+            },
+            Epoch(1),
+            era,
+        );
+        provider.persist(replaced_record.clone()).unwrap();
+
+        let records: Vec<SignerRegistrationNextEraRecord> =
+            GetSignerRegistrationNextEraRecordProvider::new(&connection)
+                .get_by_epoch_and_era(Epoch(1), era)
+                .unwrap()
+                .collect();
+        assert_eq!(2, records.len());
+        assert!(records.contains(&replaced_record));
+        assert_eq!(
+            0,
+            GetSignerRegistrationNextEraRecordProvider::new(&connection)
+                .get_by_epoch_and_era(Epoch(2), era)
+                .unwrap()
+                .count()
+        );
+    }
+}
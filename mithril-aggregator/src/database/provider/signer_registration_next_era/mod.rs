@@ -0,0 +1,7 @@
+mod delete_signer_registration_next_era;
+mod get_signer_registration_next_era;
+mod insert_or_replace_signer_registration_next_era;
+
+pub use delete_signer_registration_next_era::*;
+pub use get_signer_registration_next_era::*;
+pub use insert_or_replace_signer_registration_next_era::*;
@@ -0,0 +1,44 @@
+use sqlite::Value;
+
+use mithril_common::entities::Epoch;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SignerUnregistrationRecord;
+
+/// Query to delete [SignerUnregistrationRecord] from the sqlite database
+pub struct DeleteSignerUnregistrationProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> DeleteSignerUnregistrationProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Create the SQL condition to prune the unregistrations at or below the given epoch.
+    pub fn get_prune_condition(&self, max_epoch_to_prune: Epoch) -> StdResult<WhereCondition> {
+        Ok(WhereCondition::new(
+            "epoch <= ?*",
+            vec![Value::Integer(max_epoch_to_prune.try_into()?)],
+        ))
+    }
+}
+
+impl<'client> Provider<'client> for DeleteSignerUnregistrationProvider<'client> {
+    type Entity = SignerUnregistrationRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:signer_unregistration:}", "signer_unregistration")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!("delete from signer_unregistration where {condition} returning {projection}")
+    }
+}
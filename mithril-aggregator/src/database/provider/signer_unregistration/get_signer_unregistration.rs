@@ -0,0 +1,50 @@
+use sqlite::Value;
+
+use mithril_common::entities::{Epoch, PartyId};
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SignerUnregistrationRecord;
+
+/// Simple queries to retrieve [SignerUnregistrationRecord] from the sqlite database.
+pub struct GetSignerUnregistrationProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> GetSignerUnregistrationProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_signer_id_and_epoch_condition(
+        &self,
+        signer_id: &PartyId,
+        epoch: Epoch,
+    ) -> StdResult<WhereCondition> {
+        Ok(WhereCondition::new(
+            "signer_id = ?* and epoch = ?*",
+            vec![
+                Value::String(signer_id.to_owned()),
+                Value::Integer(epoch.try_into()?),
+            ],
+        ))
+    }
+}
+
+impl<'client> Provider<'client> for GetSignerUnregistrationProvider<'client> {
+    type Entity = SignerUnregistrationRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:signer_unregistration:}", "signer_unregistration")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!("select {projection} from signer_unregistration where {condition}")
+    }
+}
@@ -0,0 +1,50 @@
+use sqlite::Value;
+
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SignerUnregistrationRecord;
+
+/// Query to insert [SignerUnregistrationRecord] in the sqlite database, the unregistration
+/// already recorded for a signer at an epoch is kept.
+pub struct InsertSignerUnregistrationProvider<'client> {
+    connection: &'client SqliteConnection,
+}
+
+impl<'client> InsertSignerUnregistrationProvider<'client> {
+    /// Create a new instance
+    pub fn new(connection: &'client SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    pub fn get_insert_condition(
+        &self,
+        record: &SignerUnregistrationRecord,
+    ) -> StdResult<WhereCondition> {
+        let expression = "(signer_id, epoch, created_at) values (?*, ?*, ?*)";
+        let parameters = vec![
+            Value::String(record.signer_id.clone()),
+            Value::Integer(record.epoch.try_into()?),
+            Value::String(record.created_at.to_rfc3339()),
+        ];
+
+        Ok(WhereCondition::new(expression, parameters))
+    }
+}
+
+impl<'client> Provider<'client> for InsertSignerUnregistrationProvider<'client> {
+    type Entity = SignerUnregistrationRecord;
+
+    fn get_connection(&'client self) -> &'client SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:signer_unregistration:}", "signer_unregistration")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!("insert or ignore into signer_unregistration {condition} returning {projection}")
+    }
+}
@@ -0,0 +1,7 @@
+mod delete_signer_unregistration;
+mod get_signer_unregistration;
+mod insert_signer_unregistration;
+
+pub use delete_signer_unregistration::*;
+pub use get_signer_unregistration::*;
+pub use insert_signer_unregistration::*;
@@ -0,0 +1,109 @@
+use sqlite::Value;
+
+use mithril_common::entities::Epoch;
+use mithril_persistence::sqlite::{delete_definition, Provider, SqliteConnection, WhereCondition};
+
+use crate::database::record::SingleSignatureRecord;
+
+/// Query to delete old [SingleSignatureRecord] from the sqlite database
+pub struct DeleteSingleSignatureProvider<'conn> {
+    connection: &'conn SqliteConnection,
+}
+
+impl<'conn> DeleteSingleSignatureProvider<'conn> {
+    /// Create a new instance
+    pub fn new(connection: &'conn SqliteConnection) -> Self {
+        Self { connection }
+    }
+
+    /// Create the SQL condition to select the single signatures of the open messages of the
+    /// strictly previous epochs of the given epoch.
+    pub fn get_open_message_epoch_condition(&self, epoch: Epoch) -> WhereCondition {
+        WhereCondition::new(
+            "open_message_id in (select open_message_id from open_message where epoch_setting_id < ?*)",
+            vec![Value::Integer(*epoch as i64)],
+        )
+    }
+}
+
+impl<'conn> Provider<'conn> for DeleteSingleSignatureProvider<'conn> {
+    type Entity = SingleSignatureRecord;
+
+    fn get_connection(&'conn self) -> &'conn SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        delete_definition::<Self::Entity>("single_signature", condition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::{ProtocolMessage, SignedEntityType};
+
+    use crate::database::provider::InsertOpenMessageProvider;
+    use crate::database::record::{OpenMessageRecord, SingleSignatureRecord};
+    use crate::database::test_helper::{
+        insert_single_signatures_in_db, main_db_connection, setup_single_signature_records,
+    };
+
+    use super::*;
+
+    fn insert_open_message_with_signatures(
+        connection: &SqliteConnection,
+        epoch: Epoch,
+        total_signer: u64,
+    ) -> OpenMessageRecord {
+        let provider = InsertOpenMessageProvider::new(connection);
+        let filters = provider
+            .get_insert_condition(
+                epoch,
+                &SignedEntityType::MithrilStakeDistribution(epoch),
+                &ProtocolMessage::new(),
+            )
+            .unwrap();
+        let open_message = provider.find(filters).unwrap().next().unwrap();
+        let single_signature_records = setup_single_signature_records(1, 1, total_signer)
+            .into_iter()
+            .map(|record| SingleSignatureRecord {
+                open_message_id: open_message.open_message_id,
+                ..record
+            })
+            .collect();
+        insert_single_signatures_in_db(connection, single_signature_records).unwrap();
+
+        open_message
+    }
+
+    #[test]
+    fn test_prune_single_signatures_of_previous_epochs_open_messages() {
+        let connection = main_db_connection().unwrap();
+        let open_message_epoch_1 = insert_open_message_with_signatures(&connection, Epoch(1), 2);
+        let open_message_epoch_2 = insert_open_message_with_signatures(&connection, Epoch(2), 3);
+        insert_open_message_with_signatures(&connection, Epoch(3), 4);
+
+        let provider = DeleteSingleSignatureProvider::new(&connection);
+        let deleted_records: Vec<SingleSignatureRecord> = provider
+            .find(provider.get_open_message_epoch_condition(Epoch(3)))
+            .unwrap()
+            .collect();
+
+        assert_eq!(5, deleted_records.len());
+        assert!(deleted_records.iter().all(|record| [
+            open_message_epoch_1.open_message_id,
+            open_message_epoch_2.open_message_id
+        ]
+        .contains(&record.open_message_id)));
+
+        let remaining_count = connection
+            .prepare("select count(*) from single_signature")
+            .unwrap()
+            .iter()
+            .next()
+            .unwrap()
+            .unwrap()
+            .read::<i64, _>(0);
+        assert_eq!(4, remaining_count);
+    }
+}
@@ -0,0 +1,219 @@
+use sqlite::Value;
+use uuid::Uuid;
+
+use mithril_common::entities::Epoch;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{
+    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+};
+
+use crate::database::record::SingleSignatureRecord;
+
+/// Query to retrieve pages of [SingleSignatureRecord] from the sqlite database, ordered by
+/// registration epoch and signer id.
+pub struct GetSingleSignatureProvider<'conn> {
+    connection: &'conn SqliteConnection,
+    page_size: usize,
+}
+
+impl<'conn> GetSingleSignatureProvider<'conn> {
+    /// Create a new instance returning at most `page_size` records per query
+    pub fn new(connection: &'conn SqliteConnection, page_size: usize) -> Self {
+        Self {
+            connection,
+            page_size,
+        }
+    }
+
+    /// Condition to get the single signatures of an open message, starting after the given
+    /// registration epoch and signer id (keyset pagination on the primary key index).
+    ///
+    /// A signer can sign an open message with registrations of different epochs, so the signer
+    /// id alone does not identify the last returned signature.
+    pub fn get_open_message_page_condition(
+        &self,
+        open_message_id: &Uuid,
+        after: Option<(Epoch, &str)>,
+    ) -> StdResult<WhereCondition> {
+        let condition = WhereCondition::new(
+            "open_message_id = ?*",
+            vec![Value::String(open_message_id.to_string())],
+        );
+
+        match after {
+            Some((registration_epoch, signer_id)) => {
+                let registration_epoch: i64 = registration_epoch.try_into()?;
+
+                Ok(condition.and_where(WhereCondition::new(
+                    "(registration_epoch_setting_id > ?* or (registration_epoch_setting_id = ?* and signer_id > ?*))",
+                    vec![
+                        Value::Integer(registration_epoch),
+                        Value::Integer(registration_epoch),
+                        Value::String(signer_id.to_string()),
+                    ],
+                )))
+            }
+            None => Ok(condition),
+        }
+    }
+}
+
+impl<'conn> Provider<'conn> for GetSingleSignatureProvider<'conn> {
+    type Entity = SingleSignatureRecord;
+
+    fn get_connection(&'conn self) -> &'conn SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let aliases = SourceAlias::new(&[("{:single_signature:}", "ss")]);
+        let projection = Self::Entity::get_projection().expand(aliases);
+
+        format!(
+            "select {projection} from single_signature as ss where {condition} order by ss.registration_epoch_setting_id asc, ss.signer_id asc limit {}",
+            self.page_size
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::{Epoch, ProtocolMessage, SignedEntityType};
+
+    use crate::database::provider::InsertOpenMessageProvider;
+    use crate::database::record::OpenMessageRecord;
+    use crate::database::test_helper::{
+        insert_single_signatures_in_db, main_db_connection, setup_single_signature_records,
+    };
+
+    use super::*;
+
+    fn insert_open_message_with_signatures(
+        connection: &SqliteConnection,
+        epoch: Epoch,
+        total_signer: u64,
+    ) -> OpenMessageRecord {
+        let provider = InsertOpenMessageProvider::new(connection);
+        let filters = provider
+            .get_insert_condition(
+                epoch,
+                &SignedEntityType::MithrilStakeDistribution(epoch),
+                &ProtocolMessage::new(),
+            )
+            .unwrap();
+        let open_message = provider.find(filters).unwrap().next().unwrap();
+        let single_signature_records = setup_single_signature_records(1, 1, total_signer)
+            .into_iter()
+            .map(|record| SingleSignatureRecord {
+                open_message_id: open_message.open_message_id,
+                ..record
+            })
+            .collect();
+        insert_single_signatures_in_db(connection, single_signature_records).unwrap();
+
+        open_message
+    }
+
+    fn signer_ids(records: &[SingleSignatureRecord]) -> Vec<&str> {
+        records
+            .iter()
+            .map(|record| record.signer_id.as_str())
+            .collect()
+    }
+
+    fn page_key(records: &[SingleSignatureRecord]) -> (Epoch, &str) {
+        let last_record = records.last().unwrap();
+
+        (
+            last_record.registration_epoch_setting_id,
+            last_record.signer_id.as_str(),
+        )
+    }
+
+    #[test]
+    fn get_single_signatures_of_an_open_message_by_pages() {
+        let connection = main_db_connection().unwrap();
+        let open_message = insert_open_message_with_signatures(&connection, Epoch(1), 3);
+        insert_open_message_with_signatures(&connection, Epoch(2), 4);
+
+        let provider = GetSingleSignatureProvider::new(&connection, 2);
+        let first_page: Vec<SingleSignatureRecord> = provider
+            .find(
+                provider
+                    .get_open_message_page_condition(&open_message.open_message_id, None)
+                    .unwrap(),
+            )
+            .unwrap()
+            .collect();
+        let second_page: Vec<SingleSignatureRecord> = provider
+            .find(
+                provider
+                    .get_open_message_page_condition(
+                        &open_message.open_message_id,
+                        Some(page_key(&first_page)),
+                    )
+                    .unwrap(),
+            )
+            .unwrap()
+            .collect();
+
+        assert_eq!(vec!["signer-1", "signer-2"], signer_ids(&first_page));
+        assert_eq!(vec!["signer-3"], signer_ids(&second_page));
+        assert!(first_page
+            .iter()
+            .chain(second_page.iter())
+            .all(|record| record.open_message_id == open_message.open_message_id));
+    }
+
+    #[test]
+    fn get_single_signatures_of_an_open_message_by_pages_across_registration_epochs() {
+        let connection = main_db_connection().unwrap();
+        let open_message = insert_open_message_with_signatures(&connection, Epoch(1), 0);
+        let single_signature_records = setup_single_signature_records(2, 1, 3)
+            .into_iter()
+            .map(|record| SingleSignatureRecord {
+                open_message_id: open_message.open_message_id,
+                ..record
+            })
+            .collect();
+        insert_single_signatures_in_db(&connection, single_signature_records).unwrap();
+
+        let provider = GetSingleSignatureProvider::new(&connection, 2);
+        let mut pages: Vec<Vec<SingleSignatureRecord>> = vec![];
+        loop {
+            let condition = provider
+                .get_open_message_page_condition(
+                    &open_message.open_message_id,
+                    pages.last().map(|page| page_key(page)),
+                )
+                .unwrap();
+            let page: Vec<SingleSignatureRecord> = provider.find(condition).unwrap().collect();
+            if page.is_empty() {
+                break;
+            }
+            pages.push(page);
+        }
+
+        let keys: Vec<(Epoch, &str)> = pages
+            .iter()
+            .flatten()
+            .map(|record| {
+                (
+                    record.registration_epoch_setting_id,
+                    record.signer_id.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                (Epoch(1), "signer-1"),
+                (Epoch(1), "signer-2"),
+                (Epoch(1), "signer-3"),
+                (Epoch(2), "signer-1"),
+                (Epoch(2), "signer-2"),
+                (Epoch(2), "signer-3"),
+            ],
+            keys
+        );
+    }
+}
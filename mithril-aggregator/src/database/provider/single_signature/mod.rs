@@ -1,3 +1,7 @@
+mod delete_single_signature;
+mod get_single_signature;
 mod update_single_signature;
 
+pub use delete_single_signature::*;
+pub use get_single_signature::*;
 pub use update_single_signature::*;
@@ -3,7 +3,7 @@ use sqlite::Value;
 use mithril_common::entities::Epoch;
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::{
-    EntityCursor, Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+    delete_definition, EntityCursor, Provider, SqliteConnection, WhereCondition,
 };
 
 use crate::database::record::StakePool;
@@ -21,12 +21,7 @@ impl<'conn> Provider<'conn> for DeleteStakePoolProvider<'conn> {
     }
 
     fn get_definition(&self, condition: &str) -> String {
-        // it is important to alias the fields with the same name as the table
-        // since the table cannot be aliased in a RETURNING statement in SQLite.
-        let projection = Self::Entity::get_projection()
-            .expand(SourceAlias::new(&[("{:stake_pool:}", "stake_pool")]));
-
-        format!("delete from stake_pool where {condition} returning {projection}")
+        delete_definition::<Self::Entity>("stake_pool", condition)
     }
 }
 
@@ -1,12 +1,10 @@
-use std::iter::repeat;
-
 use chrono::Utc;
 use sqlite::Value;
 
 use mithril_common::entities::{Epoch, PartyId, Stake};
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::{
-    Provider, SourceAlias, SqLiteEntity, SqliteConnection, WhereCondition,
+    upsert_definition, BulkInsertProvider, Provider, SqliteConnection, WhereCondition,
 };
 
 use crate::database::record::StakePool;
@@ -17,20 +15,17 @@ pub struct InsertOrReplaceStakePoolProvider<'conn> {
 }
 
 impl<'conn> InsertOrReplaceStakePoolProvider<'conn> {
+    const COLUMNS: [&'static str; 4] = ["stake_pool_id", "epoch", "stake", "created_at"];
+
     /// Create a new instance
     pub fn new(connection: &'conn SqliteConnection) -> Self {
         Self { connection }
     }
 
-    pub fn get_insert_or_replace_condition(
-        &self,
-        records: Vec<(PartyId, Epoch, Stake)>,
-    ) -> WhereCondition {
-        let columns = "(stake_pool_id, epoch, stake, created_at)";
-        let values_columns: Vec<&str> = repeat("(?*, ?*, ?*, ?*)").take(records.len()).collect();
-        let values = records
+    fn to_values(records: Vec<(PartyId, Epoch, Stake)>) -> Vec<Vec<Value>> {
+        records
             .into_iter()
-            .flat_map(|(stake_pool_id, epoch, stake)| {
+            .map(|(stake_pool_id, epoch, stake)| {
                 vec![
                     Value::String(stake_pool_id),
                     Value::Integer(epoch.try_into().unwrap()),
@@ -38,18 +33,18 @@ impl<'conn> InsertOrReplaceStakePoolProvider<'conn> {
                     Value::String(Utc::now().to_rfc3339()),
                 ]
             })
-            .collect();
+            .collect()
+    }
 
-        WhereCondition::new(
-            format!("{columns} values {}", values_columns.join(", ")).as_str(),
-            values,
-        )
+    pub fn get_insert_or_replace_condition(
+        &self,
+        records: Vec<(PartyId, Epoch, Stake)>,
+    ) -> WhereCondition {
+        WhereCondition::insert_values(&Self::COLUMNS, Self::to_values(records))
     }
 
     pub fn persist_many(&self, records: Vec<(PartyId, Epoch, Stake)>) -> StdResult<Vec<StakePool>> {
-        let filters = self.get_insert_or_replace_condition(records);
-
-        Ok(self.find(filters)?.collect())
+        self.insert_many(&Self::COLUMNS, Self::to_values(records))
     }
 }
 
@@ -61,12 +56,12 @@ impl<'conn> Provider<'conn> for InsertOrReplaceStakePoolProvider<'conn> {
     }
 
     fn get_definition(&self, condition: &str) -> String {
-        // it is important to alias the fields with the same name as the table
-        // since the table cannot be aliased in a RETURNING statement in SQLite.
-        let projection = Self::Entity::get_projection()
-            .expand(SourceAlias::new(&[("{:stake_pool:}", "stake_pool")]));
-
-        format!("insert or replace into stake_pool {condition} returning {projection}")
+        upsert_definition::<Self::Entity>(
+            "stake_pool",
+            &["epoch", "stake_pool_id"],
+            &["stake", "created_at"],
+            condition,
+        )
     }
 }
 
@@ -0,0 +1,149 @@
+use chrono::{DateTime, Utc};
+use sqlite::Row;
+
+use mithril_common::entities::{
+    HexEncodedSingleSignature, LotteryIndex, PartyId, SignedEntityTypeDiscriminants,
+    SingleSignatures,
+};
+use mithril_common::{StdError, StdResult};
+use mithril_persistence::sqlite::{HydrationError, Projection, SqLiteEntity};
+
+/// ## BufferedSingleSignature
+///
+/// Single signature received for a signed entity type before its open message was created, it
+/// is registered once the open message is created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BufferedSingleSignatureRecord {
+    /// Signed entity type discriminant of the signed message
+    pub signed_entity_type_id: SignedEntityTypeDiscriminants,
+
+    /// Party id of the signer
+    pub party_id: PartyId,
+
+    /// Lottery indexes
+    pub lottery_indexes: Vec<LotteryIndex>,
+
+    /// The STM single signature of the message
+    pub signature: HexEncodedSingleSignature,
+
+    /// Date and time when the single signature was buffered
+    pub created_at: DateTime<Utc>,
+}
+
+impl BufferedSingleSignatureRecord {
+    pub(crate) fn try_from_single_signatures(
+        other: &SingleSignatures,
+        signed_entity_type_id: SignedEntityTypeDiscriminants,
+    ) -> StdResult<Self> {
+        let record = BufferedSingleSignatureRecord {
+            signed_entity_type_id,
+            party_id: other.party_id.to_owned(),
+            lottery_indexes: other.won_indexes.to_owned(),
+            signature: other.signature.to_json_hex()?,
+            created_at: Utc::now(),
+        };
+
+        Ok(record)
+    }
+}
+
+impl TryFrom<BufferedSingleSignatureRecord> for SingleSignatures {
+    type Error = StdError;
+
+    fn try_from(value: BufferedSingleSignatureRecord) -> Result<Self, Self::Error> {
+        let signatures = SingleSignatures {
+            party_id: value.party_id,
+            won_indexes: value.lottery_indexes,
+            signature: value.signature.try_into()?,
+            era: None,
+            nonce_signature: None,
+        };
+
+        Ok(signatures)
+    }
+}
+
+impl SqLiteEntity for BufferedSingleSignatureRecord {
+    fn hydrate(row: Row) -> Result<Self, HydrationError>
+    where
+        Self: Sized,
+    {
+        let signed_entity_type_id = usize::try_from(row.read::<i64, _>(0)).map_err(|e| {
+            HydrationError::InvalidData(format!(
+                "Integer field buffered_single_signature.signed_entity_type_id cannot be turned into usize: {e}"
+            ))
+        })?;
+        let party_id = row.read::<&str, _>(1).to_string();
+        let lottery_indexes_str = row.read::<&str, _>(2);
+        let signature = row.read::<&str, _>(3).to_string();
+        let created_at = row.read::<&str, _>(4);
+
+        let buffered_single_signature = Self {
+            signed_entity_type_id: SignedEntityTypeDiscriminants::from_id(signed_entity_type_id)
+                .map_err(|e| HydrationError::InvalidData(e.to_string()))?,
+            party_id,
+            lottery_indexes: serde_json::from_str(lottery_indexes_str).map_err(|e| {
+                HydrationError::InvalidData(format!(
+                    "Could not turn string '{lottery_indexes_str}' to Vec<LotteryIndex>. Error: {e}"
+                ))
+            })?,
+            signature,
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .map_err(|e| {
+                    HydrationError::InvalidData(format!(
+                        "Could not turn buffered_single_signature.created_at field value '{created_at}' to rfc3339 Datetime. Error: {e}"
+                    ))
+                })?
+                .with_timezone(&Utc),
+        };
+
+        Ok(buffered_single_signature)
+    }
+
+    fn get_projection() -> Projection {
+        let mut projection = Projection::default();
+        projection.add_field(
+            "signed_entity_type_id",
+            "{:buffered_single_signature:}.signed_entity_type_id",
+            "integer",
+        );
+        projection.add_field("party_id", "{:buffered_single_signature:}.party_id", "text");
+        projection.add_field(
+            "lottery_indexes",
+            "{:buffered_single_signature:}.lottery_indexes",
+            "text",
+        );
+        projection.add_field(
+            "signature",
+            "{:buffered_single_signature:}.signature",
+            "text",
+        );
+        projection.add_field(
+            "created_at",
+            "{:buffered_single_signature:}.created_at",
+            "text",
+        );
+
+        projection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::fake_data;
+
+    use super::*;
+
+    #[test]
+    fn test_convert_single_signatures() {
+        let single_signature = fake_data::single_signatures(vec![1, 3, 4, 6, 7, 9]);
+        let record = BufferedSingleSignatureRecord::try_from_single_signatures(
+            &single_signature,
+            SignedEntityTypeDiscriminants::CardanoTransactions,
+        )
+        .unwrap();
+        let single_signature_returned: SingleSignatures = record.try_into().unwrap();
+
+        assert_eq!(single_signature, single_signature_returned);
+    }
+}
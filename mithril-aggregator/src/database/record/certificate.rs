@@ -1,10 +1,12 @@
 use chrono::{DateTime, Utc};
+use std::str::FromStr;
 
 use mithril_common::entities::{
     CardanoDbBeacon, Certificate, CertificateMetadata, CertificateSignature, Epoch,
     HexEncodedAgregateVerificationKey, HexEncodedKey, ImmutableFileNumber, ProtocolMessage,
     ProtocolParameters, ProtocolVersion, SignedEntityType, StakeDistributionParty,
 };
+use mithril_common::era::SupportedEra;
 use mithril_common::era_deprecate;
 use mithril_common::messages::{
     CertificateListItemMessage, CertificateListItemMessageMetadata, CertificateMessage,
@@ -33,7 +35,8 @@ pub struct CertificateRecord {
     pub message: String,
 
     /// Signature of the certificate.
-    /// Note: multi-signature if parent certificate id is set, genesis signature otherwise.
+    /// Note: multi-signature if parent certificate id is set, genesis signature otherwise (or
+    /// if the certificate is a genesis key rotation).
     pub signature: HexEncodedKey,
 
     /// Aggregate verification key
@@ -69,6 +72,9 @@ pub struct CertificateRecord {
 
     /// Date and time when the certificate was sealed
     pub sealed_at: DateTime<Utc>,
+
+    /// Era under which the certificate was produced
+    pub era: Option<SupportedEra>,
 }
 
 #[cfg(test)]
@@ -152,6 +158,7 @@ impl CertificateRecord {
             sealed_at: DateTime::parse_from_rfc3339("2024-02-12T13:12:57Z")
                 .unwrap()
                 .with_timezone(&Utc),
+            era: None,
         }
     }
 }
@@ -168,13 +175,28 @@ impl CertificateRecord {
             self.immutable_file_number,
         )
     }
+
+    /// Tell if the signature of this record is a genesis signature
+    ///
+    /// A genesis key rotation certificate is genesis signed while having a parent certificate.
+    fn is_genesis_signed(&self) -> bool {
+        self.parent_certificate_id.is_none()
+            || self
+                .protocol_message
+                .next_genesis_verification_key()
+                .is_some()
+    }
 }
 
 impl From<Certificate> for CertificateRecord {
     fn from(other: Certificate) -> Self {
         let signed_entity_type = other.signed_entity_type();
+        let is_genesis_key_rotation = other.is_genesis_key_rotation();
         let (signature, parent_certificate_id) = match other.signature {
-            CertificateSignature::GenesisSignature(signature) => (signature.to_bytes_hex(), None),
+            CertificateSignature::GenesisSignature(signature) => (
+                signature.to_bytes_hex(),
+                is_genesis_key_rotation.then_some(other.previous_hash),
+            ),
             CertificateSignature::MultiSignature(_, signature) => {
                 (signature.to_json_hex().unwrap(), Some(other.previous_hash))
             }
@@ -197,33 +219,34 @@ impl From<Certificate> for CertificateRecord {
             signers: other.metadata.signers,
             initiated_at: other.metadata.initiated_at,
             sealed_at: other.metadata.sealed_at,
+            era: other.metadata.era,
         }
     }
 }
 
 impl From<CertificateRecord> for Certificate {
     fn from(other: CertificateRecord) -> Self {
-        let certificate_metadata = CertificateMetadata::new(
-            other.network,
-            other.immutable_file_number,
-            other.protocol_version,
-            other.protocol_parameters,
-            other.initiated_at,
-            other.sealed_at,
-            other.signers,
-        );
-        let (previous_hash, signature) = match other.parent_certificate_id {
-            None => (
-                String::new(),
-                CertificateSignature::GenesisSignature(other.signature.try_into().unwrap()),
-            ),
-            Some(parent_certificate_id) => (
-                parent_certificate_id,
-                CertificateSignature::MultiSignature(
-                    other.signed_entity_type,
-                    other.signature.try_into().unwrap(),
-                ),
-            ),
+        let is_genesis_signed = other.is_genesis_signed();
+        let certificate_metadata = CertificateMetadata {
+            era: other.era,
+            ..CertificateMetadata::new(
+                other.network,
+                other.immutable_file_number,
+                other.protocol_version,
+                other.protocol_parameters,
+                other.initiated_at,
+                other.sealed_at,
+                other.signers,
+            )
+        };
+        let previous_hash = other.parent_certificate_id.unwrap_or_default();
+        let signature = if is_genesis_signed {
+            CertificateSignature::GenesisSignature(other.signature.try_into().unwrap())
+        } else {
+            CertificateSignature::MultiSignature(
+                other.signed_entity_type,
+                other.signature.try_into().unwrap(),
+            )
         };
 
         Certificate {
@@ -241,6 +264,7 @@ impl From<CertificateRecord> for Certificate {
 
 impl From<CertificateRecord> for CertificateMessage {
     fn from(value: CertificateRecord) -> Self {
+        let is_genesis_signed = value.is_genesis_signed();
         let beacon = value.as_cardano_db_beacon();
         let metadata = CertificateMetadataMessagePart {
             network: value.network,
@@ -249,8 +273,9 @@ impl From<CertificateRecord> for CertificateMessage {
             initiated_at: value.initiated_at,
             sealed_at: value.sealed_at,
             signers: value.signers,
+            era: value.era,
         };
-        let (multi_signature, genesis_signature) = if value.parent_certificate_id.is_none() {
+        let (multi_signature, genesis_signature) = if is_genesis_signed {
             (String::new(), value.signature)
         } else {
             (value.signature, String::new())
@@ -321,6 +346,7 @@ impl SqLiteEntity for CertificateRecord {
         let signers_string = row.read::<&str, _>(13);
         let initiated_at = row.read::<&str, _>(14);
         let sealed_at = row.read::<&str, _>(15);
+        let era = row.read::<Option<&str>, _>(16);
 
         let certificate_record = Self {
             certificate_id,
@@ -383,6 +409,15 @@ impl SqLiteEntity for CertificateRecord {
                     ))
                 },
             )?.with_timezone(&Utc),
+            era: era
+                .map(|era| {
+                    SupportedEra::from_str(era).map_err(|e| {
+                        HydrationError::InvalidData(format!(
+                            "Could not turn string '{era}' to SupportedEra. Error: {e}"
+                        ))
+                    })
+                })
+                .transpose()?,
         };
 
         Ok(certificate_record)
@@ -438,6 +473,7 @@ impl SqLiteEntity for CertificateRecord {
         projection.add_field("signers", "{:certificate:}.signers", "text");
         projection.add_field("initiated_at", "{:certificate:}.initiated_at", "text");
         projection.add_field("sealed_at", "{:certificate:}.sealed_at", "text");
+        projection.add_field("era", "{:certificate:}.era", "text");
 
         projection
     }
@@ -445,7 +481,13 @@ impl SqLiteEntity for CertificateRecord {
 
 #[cfg(test)]
 mod tests {
-    use mithril_common::crypto_helper::tests_setup::setup_certificate_chain;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    use mithril_common::certificate_chain::CertificateGenesisProducer;
+    use mithril_common::crypto_helper::{
+        tests_setup::setup_certificate_chain, ProtocolGenesisSigner,
+    };
 
     use super::*;
 
@@ -463,6 +505,33 @@ mod tests {
         assert_eq!(certificates, certificates_new);
     }
 
+    #[test]
+    fn test_convert_genesis_key_rotation_certificate() {
+        let (certificates, _) = setup_certificate_chain(5, 2);
+        let rotation_certificate =
+            CertificateGenesisProducer::create_genesis_key_rotation_certificate(
+                &certificates[0],
+                &ProtocolGenesisSigner::create_deterministic_genesis_signer(),
+                &ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed(
+                    [1u8; 32],
+                )),
+            )
+            .unwrap();
+
+        let record: CertificateRecord = rotation_certificate.clone().into();
+        assert_eq!(
+            Some(certificates[0].hash.clone()),
+            record.parent_certificate_id
+        );
+
+        let message: CertificateMessage = record.clone().into();
+        assert!(message.multi_signature.is_empty());
+        assert!(!message.genesis_signature.is_empty());
+
+        let certificate: Certificate = record.into();
+        assert_eq!(rotation_certificate, certificate);
+    }
+
     #[test]
     fn converting_certificate_record_to_certificate_should_not_recompute_hash() {
         let expected_hash = "my_hash";
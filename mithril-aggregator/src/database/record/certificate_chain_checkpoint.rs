@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use sqlite::Row;
+
+use mithril_common::entities::{Epoch, HexEncodedGenesisSignature};
+use mithril_common::messages::CertificateChainCheckpointListItemMessage;
+use mithril_persistence::sqlite::{HydrationError, Projection, SqLiteEntity};
+
+use crate::database::record::hydrator;
+
+/// ## CertificateChainCheckpoint
+///
+/// Checkpoint of the certificate chain: the chain of the checkpointed certificate was verified
+/// down to the genesis certificate (or to a previous checkpoint).
+///
+/// The checkpoint is signed so that it can be trusted by the verifiers of the chain that know
+/// the checkpoint verification key, without trusting the database it is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateChainCheckpointRecord {
+    /// Epoch of the checkpoint
+    pub epoch: Epoch,
+
+    /// Hash of the checkpointed certificate
+    pub certificate_hash: String,
+
+    /// Signature of the checkpoint computed from its epoch and its certificate hash
+    pub signature: HexEncodedGenesisSignature,
+
+    /// Date and time when the checkpoint was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<CertificateChainCheckpointRecord> for CertificateChainCheckpointListItemMessage {
+    fn from(record: CertificateChainCheckpointRecord) -> Self {
+        Self {
+            epoch: record.epoch,
+            certificate_hash: record.certificate_hash,
+            signature: record.signature,
+            created_at: record.created_at,
+        }
+    }
+}
+
+impl SqLiteEntity for CertificateChainCheckpointRecord {
+    fn hydrate(row: Row) -> Result<Self, HydrationError>
+    where
+        Self: Sized,
+    {
+        let epoch = hydrator::try_to_u64("certificate_chain_checkpoint.epoch", row.read(0))?;
+        let certificate_hash = row.read::<&str, _>(1).to_string();
+        let signature = row.read::<&str, _>(2).to_string();
+        let created_at = row.read::<&str, _>(3);
+
+        let checkpoint = Self {
+            epoch: Epoch(epoch),
+            certificate_hash,
+            signature,
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .map_err(|e| {
+                    HydrationError::InvalidData(format!(
+                        "Could not turn certificate_chain_checkpoint.created_at field value '{created_at}' to rfc3339 Datetime. Error: {e}"
+                    ))
+                })?
+                .with_timezone(&Utc),
+        };
+
+        Ok(checkpoint)
+    }
+
+    fn get_projection() -> Projection {
+        let mut projection = Projection::default();
+        projection.add_field("epoch", "{:certificate_chain_checkpoint:}.epoch", "int");
+        projection.add_field(
+            "certificate_hash",
+            "{:certificate_chain_checkpoint:}.certificate_hash",
+            "text",
+        );
+        projection.add_field(
+            "signature",
+            "{:certificate_chain_checkpoint:}.signature",
+            "text",
+        );
+        projection.add_field(
+            "created_at",
+            "{:certificate_chain_checkpoint:}.created_at",
+            "text",
+        );
+
+        projection
+    }
+}
@@ -1,28 +1,46 @@
 //! Aggregator related database records
 
 mod block_range_root;
+mod buffered_single_signature;
 mod cardano_transaction;
 mod certificate;
+mod certificate_chain_checkpoint;
 mod epoch_setting;
 mod interval_without_block_range_root;
 mod open_message;
+mod open_message_quorum_status;
 mod open_message_with_single_signatures;
+mod runtime_decision;
 mod signed_entity;
+mod signed_entity_lease;
 mod signer;
+mod signer_metadata;
+mod signer_participation;
 mod signer_registration;
+mod signer_registration_next_era;
+mod signer_unregistration;
 mod single_signature;
 mod stake_pool;
 
 pub use block_range_root::*;
+pub use buffered_single_signature::*;
 pub use cardano_transaction::*;
 pub use certificate::*;
+pub use certificate_chain_checkpoint::*;
 pub use epoch_setting::*;
 pub use interval_without_block_range_root::*;
 pub use open_message::*;
+pub use open_message_quorum_status::*;
 pub use open_message_with_single_signatures::*;
+pub use runtime_decision::*;
 pub use signed_entity::*;
+pub use signed_entity_lease::*;
 pub use signer::*;
+pub use signer_metadata::*;
+pub use signer_participation::*;
 pub use signer_registration::*;
+pub use signer_registration_next_era::*;
+pub use signer_unregistration::*;
 pub use single_signature::*;
 pub use stake_pool::*;
 
@@ -38,6 +38,9 @@ pub struct OpenMessageRecord {
 
     /// Message expiration datetime, if it exists.
     pub expires_at: Option<DateTime<Utc>>,
+
+    /// Number of times the message was re-opened after its expiration
+    pub reopening_count: u32,
 }
 
 impl OpenMessageRecord {
@@ -57,6 +60,7 @@ impl OpenMessageRecord {
             is_expired: false,
             created_at: Utc::now(),
             expires_at: None,
+            reopening_count: 0,
         }
     }
 }
@@ -104,6 +108,12 @@ impl SqLiteEntity for OpenMessageRecord {
                 "Could not turn open_message.expires_at field value '{datetime}' to rfc3339 Datetime. Error: {e}"
             ))
         })).transpose()?.map(|datetime| datetime.with_timezone(&Utc));
+        let reopening_count = row.read::<i64, _>(9);
+        let reopening_count = u32::try_from(reopening_count).map_err(|e| {
+            HydrationError::InvalidData(format!(
+                "Integer field open_message.reopening_count (value={reopening_count}) is incompatible with u32 representation. Error = {e}"
+            ))
+        })?;
         let open_message = Self {
             open_message_id,
             epoch: Epoch(epoch_val),
@@ -113,6 +123,7 @@ impl SqLiteEntity for OpenMessageRecord {
             is_expired,
             created_at,
             expires_at,
+            reopening_count,
         };
 
         Ok(open_message)
@@ -145,6 +156,7 @@ impl SqLiteEntity for OpenMessageRecord {
             ("is_expired", "{:open_message:}.is_expired", "bool"),
             ("created_at", "{:open_message:}.created_at", "text"),
             ("expires_at", "{:open_message:}.expires_at", "text"),
+            ("reopening_count", "{:open_message:}.reopening_count", "int"),
         ])
     }
 }
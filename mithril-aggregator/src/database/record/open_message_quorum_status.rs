@@ -0,0 +1,80 @@
+use sqlite::Row;
+use uuid::Uuid;
+
+use mithril_persistence::sqlite::{HydrationError, Projection, SqLiteEntity};
+
+use crate::database::record::hydrator::try_to_u64;
+
+/// Aggregation of the single signatures registered for an open message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenMessageQuorumStatusRecord {
+    /// OpenMessage unique identifier
+    pub open_message_id: Uuid,
+
+    /// Number of registered single signatures
+    pub signatures_count: u64,
+
+    /// Number of lottery indexes won by the registered single signatures
+    pub won_lottery_indexes: u64,
+
+    /// Cumulative stake of the signers that registered a single signature
+    pub signed_stake: u64,
+
+    /// Cumulative stake of the signers that can sign the open message
+    pub total_stake: u64,
+}
+
+impl SqLiteEntity for OpenMessageQuorumStatusRecord {
+    fn hydrate(row: Row) -> Result<Self, HydrationError>
+    where
+        Self: Sized,
+    {
+        let open_message_id = row.read::<&str, _>(0);
+        let open_message_id = Uuid::parse_str(open_message_id).map_err(|e| {
+            HydrationError::InvalidData(format!(
+                "Invalid UUID in open_message.open_message_id: '{open_message_id}'. Error: {e}"
+            ))
+        })?;
+
+        Ok(Self {
+            open_message_id,
+            signatures_count: try_to_u64("signatures_count", row.read::<i64, _>(1))?,
+            won_lottery_indexes: try_to_u64("won_lottery_indexes", row.read::<i64, _>(2))?,
+            signed_stake: try_to_u64("signed_stake", row.read::<i64, _>(3))?,
+            total_stake: try_to_u64("total_stake", row.read::<i64, _>(4))?,
+        })
+    }
+
+    fn get_projection() -> Projection {
+        Projection::from(&[
+            (
+                "open_message_id",
+                "{:open_message:}.open_message_id",
+                "text",
+            ),
+            (
+                "signatures_count",
+                "count({:single_signature:}.signer_id)",
+                "int",
+            ),
+            (
+                "won_lottery_indexes",
+                "coalesce(sum(json_array_length({:single_signature:}.lottery_indexes)), 0)",
+                "int",
+            ),
+            (
+                "signed_stake",
+                "coalesce(sum({:signer_registration:}.stake), 0)",
+                "int",
+            ),
+            (
+                "total_stake",
+                // The signers of an open message are the ones registered at its signer retrieval
+                // epoch, i.e. the epoch preceding the open message epoch.
+                "(select coalesce(sum(registration.stake), 0) from signer_registration as registration \
+                where registration.epoch_setting_id = {:open_message:}.epoch_setting_id - 1)",
+                "int",
+            ),
+        ])
+    }
+}
@@ -36,6 +36,9 @@ pub struct OpenMessageWithSingleSignaturesRecord {
 
     /// Message expiration datetime, if it exists.
     pub expires_at: Option<DateTime<Utc>>,
+
+    /// Number of times the message was re-opened after its expiration
+    pub reopening_count: u32,
 }
 
 impl From<OpenMessageWithSingleSignaturesRecord> for OpenMessageRecord {
@@ -49,6 +52,7 @@ impl From<OpenMessageWithSingleSignaturesRecord> for OpenMessageRecord {
             is_expired: value.is_expired,
             created_at: value.created_at,
             expires_at: value.expires_at,
+            reopening_count: value.reopening_count,
         }
     }
 }
@@ -58,7 +62,7 @@ impl SqLiteEntity for OpenMessageWithSingleSignaturesRecord {
     where
         Self: Sized,
     {
-        let single_signatures = &row.read::<&str, _>(9);
+        let single_signatures = &row.read::<&str, _>(10);
         let single_signatures: Vec<SingleSignatures> = serde_json::from_str(single_signatures)
             .map_err(|e| {
                 HydrationError::InvalidData(format!(
@@ -78,6 +82,7 @@ impl SqLiteEntity for OpenMessageWithSingleSignaturesRecord {
             single_signatures,
             created_at: open_message.created_at,
             expires_at: open_message.expires_at,
+            reopening_count: open_message.reopening_count,
         };
 
         Ok(open_message)
@@ -110,6 +115,7 @@ impl SqLiteEntity for OpenMessageWithSingleSignaturesRecord {
             ("is_expired", "{:open_message:}.is_expired", "bool"),
             ("created_at", "{:open_message:}.created_at", "text"),
             ("expires_at", "{:open_message:}.expires_at", "text"),
+            ("reopening_count", "{:open_message:}.reopening_count", "int"),
             (
                 "single_signatures",
                 "case when {:single_signature:}.signer_id is null then json('[]') \
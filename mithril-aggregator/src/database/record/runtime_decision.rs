@@ -0,0 +1,303 @@
+use std::fmt::{Display, Formatter};
+
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Utc};
+use sqlite::Row;
+
+use mithril_common::entities::SignedEntityType;
+use mithril_common::StdResult;
+use mithril_persistence::database::SignedEntityTypeHydrator;
+use mithril_persistence::sqlite::{HydrationError, Projection, SqLiteEntity};
+
+use crate::database::record::hydrator;
+
+/// Decision taken by the runtime for a signed entity type during a tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeDecision {
+    /// The signed entity type is not in the list of the allowed signed entity types
+    SignedEntityTypeDisabled,
+
+    /// A new open message has been created for the beacon
+    OpenMessageCreated,
+
+    /// An open message already exists for the beacon and is still collecting signatures
+    OpenMessageInProgress,
+
+    /// The beacon has not changed since it was certified
+    BeaconAlreadyCertified,
+
+    /// The open message of the beacon has expired before being certified
+    OpenMessageExpired,
+
+    /// The protocol message of the beacon could not be computed (ie: missing stake distribution)
+    ProtocolMessageUnavailable,
+
+    /// The beacon has been batched in the open message of another signed entity type, given as
+    /// the reason of the decision in its canonical textual representation
+    BatchedInOpenMessage,
+
+    /// The snapshot is certified outside of the configured snapshot upload windows, its upload
+    /// is deferred to the next window
+    OutsideSnapshotUploadWindow,
+}
+
+impl RuntimeDecision {
+    /// Textual representation of the decision, as stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SignedEntityTypeDisabled => "signed_entity_type_disabled",
+            Self::OpenMessageCreated => "open_message_created",
+            Self::OpenMessageInProgress => "open_message_in_progress",
+            Self::BeaconAlreadyCertified => "beacon_already_certified",
+            Self::OpenMessageExpired => "open_message_expired",
+            Self::ProtocolMessageUnavailable => "protocol_message_unavailable",
+            Self::BatchedInOpenMessage => "batched_in_open_message",
+            Self::OutsideSnapshotUploadWindow => "outside_snapshot_upload_window",
+        }
+    }
+}
+
+impl Display for RuntimeDecision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl TryFrom<&str> for RuntimeDecision {
+    type Error = HydrationError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "signed_entity_type_disabled" => Ok(Self::SignedEntityTypeDisabled),
+            "open_message_created" => Ok(Self::OpenMessageCreated),
+            "open_message_in_progress" => Ok(Self::OpenMessageInProgress),
+            "beacon_already_certified" => Ok(Self::BeaconAlreadyCertified),
+            "open_message_expired" => Ok(Self::OpenMessageExpired),
+            "protocol_message_unavailable" => Ok(Self::ProtocolMessageUnavailable),
+            "batched_in_open_message" => Ok(Self::BatchedInOpenMessage),
+            "outside_snapshot_upload_window" => Ok(Self::OutsideSnapshotUploadWindow),
+            _ => Err(HydrationError::InvalidData(format!(
+                "Invalid runtime decision: '{value}'"
+            ))),
+        }
+    }
+}
+
+/// ## RuntimeDecision
+///
+/// Record of a decision taken by the runtime for a signed entity type.
+///
+/// Consecutive identical decisions are stored once, their `last_seen_at` field being updated
+/// at each tick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeDecisionRecord {
+    /// Record unique identifier, it is set by the database.
+    pub runtime_decision_id: i64,
+
+    /// Signed entity type (and its beacon) the decision was taken for
+    pub signed_entity_type: SignedEntityType,
+
+    /// Decision taken by the runtime
+    pub decision: RuntimeDecision,
+
+    /// Detailed reason of the decision, if any
+    ///
+    /// For a [RuntimeDecision::BatchedInOpenMessage] decision, it is the canonical textual
+    /// representation of the signed entity type of the open message (see [Self::batched_in]).
+    pub reason: Option<String>,
+
+    /// Date and time when the decision was taken for the first time
+    pub created_at: DateTime<Utc>,
+
+    /// Date and time when the decision was taken for the last time
+    pub last_seen_at: DateTime<Utc>,
+}
+
+impl RuntimeDecisionRecord {
+    /// Check if this record holds the same decision than the given one
+    pub fn is_same_decision(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        decision: RuntimeDecision,
+        reason: &Option<String>,
+    ) -> StdResult<bool> {
+        Ok(
+            self.signed_entity_type.index() == signed_entity_type.index()
+                && self.signed_entity_type.get_json_beacon()?
+                    == signed_entity_type.get_json_beacon()?
+                && self.decision == decision
+                && &self.reason == reason,
+        )
+    }
+
+    /// Signed entity type of the open message the beacon was batched in, parsed from the
+    /// reason of a [RuntimeDecision::BatchedInOpenMessage] decision.
+    pub fn batched_in(&self) -> StdResult<Option<SignedEntityType>> {
+        match (self.decision, &self.reason) {
+            (RuntimeDecision::BatchedInOpenMessage, Some(reason)) => {
+                let signed_entity_type = reason.parse::<SignedEntityType>().with_context(|| {
+                    format!("Invalid batched in signed entity type: '{reason}'")
+                })?;
+                Ok(Some(signed_entity_type))
+            }
+            (RuntimeDecision::BatchedInOpenMessage, None) => Err(anyhow!(
+                "Missing batched in signed entity type of a '{}' decision",
+                self.decision
+            )),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl SqLiteEntity for RuntimeDecisionRecord {
+    fn hydrate(row: Row) -> Result<Self, HydrationError>
+    where
+        Self: Sized,
+    {
+        let runtime_decision_id = row.read::<i64, _>(0);
+        let signed_entity_type_id = usize::try_from(row.read::<i64, _>(1)).map_err(|e| {
+            panic!(
+                "Integer field runtime_decision.signed_entity_type_id cannot be turned into usize: {e}"
+            )
+        })?;
+        let beacon_str = hydrator::read_signed_entity_beacon_column(&row, 2);
+        let signed_entity_type =
+            SignedEntityTypeHydrator::hydrate(signed_entity_type_id, &beacon_str)?;
+        let decision = RuntimeDecision::try_from(row.read::<&str, _>(3))?;
+        let reason = row.read::<Option<&str>, _>(4).map(|s| s.to_owned());
+        let created_at = row.read::<&str, _>(5);
+        let last_seen_at = row.read::<&str, _>(6);
+
+        let runtime_decision = Self {
+            runtime_decision_id,
+            signed_entity_type,
+            decision,
+            reason,
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .map_err(|e| {
+                    HydrationError::InvalidData(format!(
+                        "Could not turn runtime_decision.created_at field value '{created_at}' to rfc3339 Datetime. Error: {e}"
+                    ))
+                })?
+                .with_timezone(&Utc),
+            last_seen_at: DateTime::parse_from_rfc3339(last_seen_at)
+                .map_err(|e| {
+                    HydrationError::InvalidData(format!(
+                        "Could not turn runtime_decision.last_seen_at field value '{last_seen_at}' to rfc3339 Datetime. Error: {e}"
+                    ))
+                })?
+                .with_timezone(&Utc),
+        };
+        runtime_decision
+            .batched_in()
+            .map_err(|e| HydrationError::InvalidData(format!("{e:?}")))?;
+
+        Ok(runtime_decision)
+    }
+
+    fn get_projection() -> Projection {
+        let mut projection = Projection::default();
+        projection.add_field(
+            "runtime_decision_id",
+            "{:runtime_decision:}.runtime_decision_id",
+            "int",
+        );
+        projection.add_field(
+            "signed_entity_type_id",
+            "{:runtime_decision:}.signed_entity_type_id",
+            "int",
+        );
+        projection.add_field("beacon", "{:runtime_decision:}.beacon", "text");
+        projection.add_field("decision", "{:runtime_decision:}.decision", "text");
+        projection.add_field("reason", "{:runtime_decision:}.reason", "text");
+        projection.add_field("created_at", "{:runtime_decision:}.created_at", "text");
+        projection.add_field("last_seen_at", "{:runtime_decision:}.last_seen_at", "text");
+
+        projection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::CardanoDbBeacon;
+
+    use super::*;
+
+    #[test]
+    fn runtime_decision_textual_representation_round_trip() {
+        for decision in [
+            RuntimeDecision::SignedEntityTypeDisabled,
+            RuntimeDecision::OpenMessageCreated,
+            RuntimeDecision::OpenMessageInProgress,
+            RuntimeDecision::BeaconAlreadyCertified,
+            RuntimeDecision::OpenMessageExpired,
+            RuntimeDecision::ProtocolMessageUnavailable,
+            RuntimeDecision::BatchedInOpenMessage,
+            RuntimeDecision::OutsideSnapshotUploadWindow,
+        ] {
+            assert_eq!(
+                decision,
+                RuntimeDecision::try_from(decision.as_str()).unwrap()
+            );
+        }
+    }
+
+    fn record(decision: RuntimeDecision, reason: Option<&str>) -> RuntimeDecisionRecord {
+        RuntimeDecisionRecord {
+            runtime_decision_id: 1,
+            signed_entity_type: SignedEntityType::dummy(),
+            decision,
+            reason: reason.map(|r| r.to_string()),
+            created_at: Utc::now(),
+            last_seen_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn batched_in_is_parsed_from_the_reason_of_a_batched_in_open_message_decision() {
+        let signed_entity_type =
+            SignedEntityType::CardanoTransactions(CardanoDbBeacon::new("devnet", 5, 123));
+
+        assert_eq!(
+            Some(signed_entity_type.clone()),
+            record(
+                RuntimeDecision::BatchedInOpenMessage,
+                Some(&signed_entity_type.to_string())
+            )
+            .batched_in()
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn batched_in_is_none_for_the_other_decisions() {
+        assert_eq!(
+            None,
+            record(
+                RuntimeDecision::ProtocolMessageUnavailable,
+                Some("Missing stake distribution")
+            )
+            .batched_in()
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn batched_in_fails_with_an_invalid_or_missing_reason() {
+        record(
+            RuntimeDecision::BatchedInOpenMessage,
+            Some("CardanoTransactions(devnet, 5, 123)"),
+        )
+        .batched_in()
+        .expect_err("an invalid signed entity type should not be parsed");
+        record(RuntimeDecision::BatchedInOpenMessage, None)
+            .batched_in()
+            .expect_err("a missing signed entity type should not be parsed");
+    }
+
+    #[test]
+    fn invalid_runtime_decision_textual_representation() {
+        RuntimeDecision::try_from("not_a_decision")
+            .expect_err("Parsing an unknown decision should fail");
+    }
+}
@@ -115,6 +115,10 @@ impl TryFrom<SignedEntityRecord> for SnapshotMessage {
             locations: artifact.locations,
             compression_algorithm: Some(artifact.compression_algorithm),
             cardano_node_version: Some(artifact.cardano_node_version),
+            ancillary_size: artifact.ancillary_size,
+            ancillary_locations: artifact.ancillary_locations,
+            signed_locations: None,
+            archive_hash: artifact.archive_hash,
         };
 
         Ok(snapshot_message)
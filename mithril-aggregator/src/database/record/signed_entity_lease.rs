@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use sqlite::Row;
+
+use mithril_common::entities::SignedEntityType;
+use mithril_persistence::database::SignedEntityTypeHydrator;
+use mithril_persistence::sqlite::{HydrationError, Projection, SqLiteEntity};
+
+use crate::database::record::hydrator;
+
+/// ## SignedEntityLease
+///
+/// Lease on the creation of the artifact of a signed entity type (and its beacon) held by an
+/// aggregator process.
+///
+/// A lease whose expiration date is passed is stale: its holder is considered gone and the lease
+/// can be taken over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedEntityLeaseRecord {
+    /// Signed entity type (and its beacon) whose artifact is being created
+    pub signed_entity_type: SignedEntityType,
+
+    /// Identifier of the aggregator process holding the lease
+    pub holder: String,
+
+    /// Date and time after which the lease is stale
+    pub expires_at: DateTime<Utc>,
+}
+
+impl SqLiteEntity for SignedEntityLeaseRecord {
+    fn hydrate(row: Row) -> Result<Self, HydrationError>
+    where
+        Self: Sized,
+    {
+        let signed_entity_type_id = usize::try_from(row.read::<i64, _>(0)).map_err(|e| {
+            HydrationError::InvalidData(format!(
+                "Integer field signed_entity_lease.signed_entity_type_id cannot be turned into usize: {e}"
+            ))
+        })?;
+        let beacon_str = hydrator::read_signed_entity_beacon_column(&row, 1);
+        let holder = row.read::<&str, _>(2).to_string();
+        let expires_at = row.read::<&str, _>(3);
+
+        let signed_entity_lease = Self {
+            signed_entity_type: SignedEntityTypeHydrator::hydrate(
+                signed_entity_type_id,
+                &beacon_str,
+            )?,
+            holder,
+            expires_at: DateTime::parse_from_rfc3339(expires_at)
+                .map_err(|e| {
+                    HydrationError::InvalidData(format!(
+                        "Could not turn signed_entity_lease.expires_at field value '{expires_at}' to rfc3339 Datetime. Error: {e}"
+                    ))
+                })?
+                .with_timezone(&Utc),
+        };
+
+        Ok(signed_entity_lease)
+    }
+
+    fn get_projection() -> Projection {
+        let mut projection = Projection::default();
+        projection.add_field(
+            "signed_entity_type_id",
+            "{:signed_entity_lease:}.signed_entity_type_id",
+            "int",
+        );
+        projection.add_field("beacon", "{:signed_entity_lease:}.beacon", "text");
+        projection.add_field("holder", "{:signed_entity_lease:}.holder", "text");
+        projection.add_field("expires_at", "{:signed_entity_lease:}.expires_at", "text");
+
+        projection
+    }
+}
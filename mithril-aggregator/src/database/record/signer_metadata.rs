@@ -0,0 +1,88 @@
+use chrono::{DateTime, Utc};
+use sqlite::Row;
+
+use mithril_common::entities::{Epoch, PartyId};
+use mithril_persistence::sqlite::{HydrationError, Projection, SqLiteEntity};
+
+/// ## SignerMetadata
+///
+/// Record of the metadata sent by a signer along its last registration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignerMetadataRecord {
+    /// Signer id.
+    pub signer_id: PartyId,
+
+    /// Epoch of the last registration of the signer.
+    pub registration_epoch: Epoch,
+
+    /// Version of the signer node software, if known.
+    pub node_version: Option<String>,
+
+    /// Eras supported by the signer node.
+    pub supported_eras: Vec<String>,
+
+    /// IP address the signer registered from, if known.
+    pub ip_address: Option<String>,
+
+    /// Date and time when the metadata were updated.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SqLiteEntity for SignerMetadataRecord {
+    fn hydrate(row: Row) -> Result<Self, HydrationError>
+    where
+        Self: Sized,
+    {
+        let signer_id = row.read::<&str, _>(0).to_string();
+        let registration_epoch_int = row.read::<i64, _>(1);
+        let node_version = row.read::<Option<&str>, _>(2).map(|s| s.to_owned());
+        let supported_eras = row.read::<&str, _>(3);
+        let ip_address = row.read::<Option<&str>, _>(4).map(|s| s.to_owned());
+        let updated_at = row.read::<&str, _>(5);
+
+        let signer_metadata = Self {
+            signer_id,
+            registration_epoch: Epoch(registration_epoch_int.try_into().map_err(|e| {
+                HydrationError::InvalidData(format!(
+                    "Could not cast i64 ({registration_epoch_int}) to u64. Error: '{e}'"
+                ))
+            })?),
+            node_version,
+            supported_eras: serde_json::from_str(supported_eras).map_err(|e| {
+                HydrationError::InvalidData(format!(
+                    "Could not turn signer_metadata.supported_eras field value '{supported_eras}' to a list of eras. Error: {e}"
+                ))
+            })?,
+            ip_address,
+            updated_at: DateTime::parse_from_rfc3339(updated_at)
+                .map_err(|e| {
+                    HydrationError::InvalidData(format!(
+                        "Could not turn signer_metadata.updated_at field value '{updated_at}' to rfc3339 Datetime. Error: {e}"
+                    ))
+                })?
+                .with_timezone(&Utc),
+        };
+
+        Ok(signer_metadata)
+    }
+
+    fn get_projection() -> Projection {
+        let mut projection = Projection::default();
+        projection.add_field("signer_id", "{:signer_metadata:}.signer_id", "text");
+        projection.add_field(
+            "registration_epoch",
+            "{:signer_metadata:}.registration_epoch",
+            "integer",
+        );
+        projection.add_field("node_version", "{:signer_metadata:}.node_version", "text");
+        projection.add_field(
+            "supported_eras",
+            "{:signer_metadata:}.supported_eras",
+            "text",
+        );
+        projection.add_field("ip_address", "{:signer_metadata:}.ip_address", "text");
+        projection.add_field("updated_at", "{:signer_metadata:}.updated_at", "text");
+
+        projection
+    }
+}
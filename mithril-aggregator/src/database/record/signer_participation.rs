@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use sqlite::Row;
+
+use mithril_common::entities::{Epoch, PartyId};
+use mithril_persistence::sqlite::{HydrationError, Projection, SqLiteEntity};
+
+use crate::database::record::hydrator::try_to_u64;
+
+/// ## SignerParticipation
+///
+/// Record of the participation of a signer at an epoch: whether it was registered to sign
+/// at this epoch and how many single signatures it issued for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignerParticipationRecord {
+    /// Signer id.
+    pub signer_id: PartyId,
+
+    /// Epoch of the participation.
+    pub epoch: Epoch,
+
+    /// Whether the signer was registered to sign at this epoch.
+    pub registered: bool,
+
+    /// Number of single signatures issued by the signer at this epoch.
+    pub signatures_issued: u64,
+
+    /// Date and time when the participation was updated.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SqLiteEntity for SignerParticipationRecord {
+    fn hydrate(row: Row) -> Result<Self, HydrationError>
+    where
+        Self: Sized,
+    {
+        let signer_id = row.read::<&str, _>(0).to_string();
+        let epoch_int = row.read::<i64, _>(1);
+        let registered = row.read::<i64, _>(2) != 0;
+        let signatures_issued = row.read::<i64, _>(3);
+        let updated_at = row.read::<&str, _>(4);
+
+        let signer_participation = Self {
+            signer_id,
+            epoch: Epoch(try_to_u64("signer_participation.epoch", epoch_int)?),
+            registered,
+            signatures_issued: try_to_u64(
+                "signer_participation.signatures_issued",
+                signatures_issued,
+            )?,
+            updated_at: DateTime::parse_from_rfc3339(updated_at)
+                .map_err(|e| {
+                    HydrationError::InvalidData(format!(
+                        "Could not turn signer_participation.updated_at field value '{updated_at}' to rfc3339 Datetime. Error: {e}"
+                    ))
+                })?
+                .with_timezone(&Utc),
+        };
+
+        Ok(signer_participation)
+    }
+
+    fn get_projection() -> Projection {
+        let mut projection = Projection::default();
+        projection.add_field("signer_id", "{:signer_participation:}.signer_id", "text");
+        projection.add_field("epoch", "{:signer_participation:}.epoch", "integer");
+        projection.add_field(
+            "registered",
+            "{:signer_participation:}.registered",
+            "integer",
+        );
+        projection.add_field(
+            "signatures_issued",
+            "{:signer_participation:}.signatures_issued",
+            "integer",
+        );
+        projection.add_field("updated_at", "{:signer_participation:}.updated_at", "text");
+
+        projection
+    }
+}
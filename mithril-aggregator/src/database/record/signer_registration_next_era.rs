@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use sqlite::Row;
+
+use mithril_common::entities::{
+    Epoch, HexEncodedVerificationKey, HexEncodedVerificationKeySignature, PartyId, Signer,
+};
+use mithril_common::era::SupportedEra;
+use mithril_persistence::sqlite::{HydrationError, Projection, SqLiteEntity};
+
+use crate::database::record::hydrator;
+
+/// ## SignerRegistrationNextEra
+///
+/// Verification key created by a signer with the signature scheme of the next era, registered
+/// ahead of the era switch along with the verification key of the current era.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignerRegistrationNextEraRecord {
+    /// Signer id.
+    pub signer_id: PartyId,
+
+    /// Epoch of creation of the signer registration.
+    pub epoch_setting_id: Epoch,
+
+    /// Era of the signature scheme the verification key was created with.
+    pub era: String,
+
+    /// Verification key of the signer for the era
+    pub verification_key: HexEncodedVerificationKey,
+
+    /// Signature of the verification key of the signer for the era
+    pub verification_key_signature: Option<HexEncodedVerificationKeySignature>,
+
+    /// Date and time when the signer registration was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl SignerRegistrationNextEraRecord {
+    pub(crate) fn from_signer(other: Signer, epoch: Epoch, era: SupportedEra) -> Self {
+        SignerRegistrationNextEraRecord {
+            signer_id: other.party_id,
+            epoch_setting_id: epoch,
+            era: era.to_string(),
+            verification_key: other.verification_key.to_json_hex().unwrap(),
+            verification_key_signature: other
+                .verification_key_signature
+                .map(|k| k.to_json_hex().unwrap()),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+impl SqLiteEntity for SignerRegistrationNextEraRecord {
+    fn hydrate(row: Row) -> Result<Self, HydrationError>
+    where
+        Self: Sized,
+    {
+        let signer_id = row.read::<&str, _>(0).to_string();
+        let epoch_setting_id =
+            hydrator::try_to_u64("signer_registration_next_era.epoch_setting_id", row.read(1))?;
+        let era = row.read::<&str, _>(2).to_string();
+        let verification_key = row.read::<&str, _>(3).to_string();
+        let verification_key_signature = row.read::<Option<&str>, _>(4).map(|s| s.to_owned());
+        let created_at = row.read::<&str, _>(5);
+
+        let signer_registration_next_era = Self {
+            signer_id,
+            epoch_setting_id: Epoch(epoch_setting_id),
+            era,
+            verification_key,
+            verification_key_signature,
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .map_err(|e| {
+                    HydrationError::InvalidData(format!(
+                        "Could not turn signer_registration_next_era.created_at field value '{created_at}' to rfc3339 Datetime. Error: {e}"
+                    ))
+                })?
+                .with_timezone(&Utc),
+        };
+
+        Ok(signer_registration_next_era)
+    }
+
+    fn get_projection() -> Projection {
+        let mut projection = Projection::default();
+        projection.add_field(
+            "signer_id",
+            "{:signer_registration_next_era:}.signer_id",
+            "text",
+        );
+        projection.add_field(
+            "epoch_setting_id",
+            "{:signer_registration_next_era:}.epoch_setting_id",
+            "integer",
+        );
+        projection.add_field("era", "{:signer_registration_next_era:}.era", "text");
+        projection.add_field(
+            "verification_key",
+            "{:signer_registration_next_era:}.verification_key",
+            "text",
+        );
+        projection.add_field(
+            "verification_key_signature",
+            "{:signer_registration_next_era:}.verification_key_signature",
+            "text",
+        );
+        projection.add_field(
+            "created_at",
+            "{:signer_registration_next_era:}.created_at",
+            "text",
+        );
+
+        projection
+    }
+}
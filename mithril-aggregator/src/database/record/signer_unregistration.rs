@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use sqlite::Row;
+
+use mithril_common::entities::{Epoch, PartyId};
+use mithril_persistence::sqlite::{HydrationError, Projection, SqLiteEntity};
+
+use crate::database::record::hydrator;
+
+/// ## SignerUnregistration
+///
+/// Tombstone of a signer unregistered from an epoch: the signer can not be registered again at
+/// this epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignerUnregistrationRecord {
+    /// Party id of the unregistered signer
+    pub signer_id: PartyId,
+
+    /// Epoch from which the signer is unregistered
+    pub epoch: Epoch,
+
+    /// Date and time when the signer was unregistered
+    pub created_at: DateTime<Utc>,
+}
+
+impl SqLiteEntity for SignerUnregistrationRecord {
+    fn hydrate(row: Row) -> Result<Self, HydrationError>
+    where
+        Self: Sized,
+    {
+        let signer_id = row.read::<&str, _>(0).to_string();
+        let epoch = hydrator::try_to_u64("signer_unregistration.epoch", row.read(1))?;
+        let created_at = row.read::<&str, _>(2);
+
+        let signer_unregistration = Self {
+            signer_id,
+            epoch: Epoch(epoch),
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .map_err(|e| {
+                    HydrationError::InvalidData(format!(
+                        "Could not turn signer_unregistration.created_at field value '{created_at}' to rfc3339 Datetime. Error: {e}"
+                    ))
+                })?
+                .with_timezone(&Utc),
+        };
+
+        Ok(signer_unregistration)
+    }
+
+    fn get_projection() -> Projection {
+        let mut projection = Projection::default();
+        projection.add_field("signer_id", "{:signer_unregistration:}.signer_id", "text");
+        projection.add_field("epoch", "{:signer_unregistration:}.epoch", "int");
+        projection.add_field("created_at", "{:signer_unregistration:}.created_at", "text");
+
+        projection
+    }
+}
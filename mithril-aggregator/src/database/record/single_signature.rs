@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use anyhow::Context;
 use mithril_common::entities::{Epoch, HexEncodedSingleSignature, LotteryIndex, SingleSignatures};
 use mithril_common::{StdError, StdResult};
 use mithril_persistence::sqlite::{HydrationError, Projection, SqLiteEntity};
@@ -44,6 +45,21 @@ impl SingleSignatureRecord {
 
         Ok(record)
     }
+
+    /// Convert a batch of records into the [SingleSignatures] given to the protocol aggregation.
+    pub(crate) fn try_into_single_signatures(
+        records: Vec<SingleSignatureRecord>,
+    ) -> StdResult<Vec<SingleSignatures>> {
+        records
+            .into_iter()
+            .map(|record| {
+                let signer_id = record.signer_id.clone();
+                SingleSignatures::try_from(record).with_context(|| {
+                    format!("Could not convert the single signature record of signer '{signer_id}'")
+                })
+            })
+            .collect()
+    }
 }
 
 impl TryFrom<SingleSignatureRecord> for SingleSignatures {
@@ -54,6 +70,8 @@ impl TryFrom<SingleSignatureRecord> for SingleSignatures {
             party_id: value.signer_id,
             won_indexes: value.lottery_indexes,
             signature: value.signature.try_into()?,
+            era: None,
+            nonce_signature: None,
         };
 
         Ok(signatures)
@@ -150,4 +168,43 @@ mod tests {
 
         assert_eq!(single_signature, single_signature_returned);
     }
+
+    #[test]
+    fn test_convert_batch_of_records_into_single_signatures() {
+        let single_signatures = vec![
+            fake_data::single_signatures(vec![1, 3]),
+            fake_data::single_signatures(vec![2, 4]),
+        ];
+        let open_message_id = Uuid::parse_str("193d1442-e89b-43cf-9519-04d8db9a12ff").unwrap();
+        let records = single_signatures
+            .iter()
+            .map(|single_signature| {
+                SingleSignatureRecord::try_from_single_signatures(
+                    single_signature,
+                    &open_message_id,
+                    Epoch(1),
+                )
+                .unwrap()
+            })
+            .collect();
+
+        let converted = SingleSignatureRecord::try_into_single_signatures(records).unwrap();
+
+        assert_eq!(single_signatures, converted);
+    }
+
+    #[test]
+    fn test_convert_batch_of_records_fails_with_an_invalid_signature() {
+        let open_message_id = Uuid::parse_str("193d1442-e89b-43cf-9519-04d8db9a12ff").unwrap();
+        let mut record = SingleSignatureRecord::try_from_single_signatures(
+            &fake_data::single_signatures(vec![1, 3]),
+            &open_message_id,
+            Epoch(1),
+        )
+        .unwrap();
+        record.signature = "invalid".to_string();
+
+        SingleSignatureRecord::try_into_single_signatures(vec![record])
+            .expect_err("Converting an invalid signature should fail");
+    }
 }
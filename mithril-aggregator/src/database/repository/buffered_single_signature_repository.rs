@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use mithril_common::entities::{SignedEntityTypeDiscriminants, SingleSignatures};
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{Provider, SqliteConnection};
+
+use crate::database::provider::{
+    GetBufferedSingleSignatureProvider, InsertOrReplaceBufferedSingleSignatureProvider,
+};
+use crate::database::record::BufferedSingleSignatureRecord;
+
+/// ## Buffered single signature repository
+///
+/// Keep the single signatures received before the open message they sign is created.
+pub struct BufferedSingleSignatureRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl BufferedSingleSignatureRepository {
+    /// Create a new instance
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+
+    /// Buffer a single signature for the given signed entity type, it replaces the signature
+    /// previously buffered by the same party for this signed entity type.
+    pub async fn buffer_single_signature(
+        &self,
+        signed_entity_type_discriminant: SignedEntityTypeDiscriminants,
+        single_signature: &SingleSignatures,
+    ) -> StdResult<BufferedSingleSignatureRecord> {
+        let record = BufferedSingleSignatureRecord::try_from_single_signatures(
+            single_signature,
+            signed_entity_type_discriminant,
+        )?;
+        let provider = InsertOrReplaceBufferedSingleSignatureProvider::new(&self.connection);
+
+        provider.persist(record)
+    }
+
+    /// Return the single signatures buffered for the given signed entity type.
+    pub async fn get_buffered_single_signatures(
+        &self,
+        signed_entity_type_discriminant: SignedEntityTypeDiscriminants,
+    ) -> StdResult<Vec<SingleSignatures>> {
+        let provider = GetBufferedSingleSignatureProvider::new(&self.connection);
+        let filters =
+            provider.get_signed_entity_type_discriminant_condition(signed_entity_type_discriminant);
+
+        provider
+            .find(filters)?
+            .map(SingleSignatures::try_from)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::fake_data;
+
+    use crate::database::test_helper::main_db_connection;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn buffered_signature_replaces_the_one_previously_buffered_by_the_same_party() {
+        let repository =
+            BufferedSingleSignatureRepository::new(Arc::new(main_db_connection().unwrap()));
+        let first_signature = SingleSignatures {
+            party_id: "party_1".to_string(),
+            ..fake_data::single_signatures(vec![1, 2])
+        };
+        let second_signature = SingleSignatures {
+            party_id: "party_1".to_string(),
+            ..fake_data::single_signatures(vec![3])
+        };
+        let other_party_signature = SingleSignatures {
+            party_id: "party_2".to_string(),
+            ..fake_data::single_signatures(vec![4])
+        };
+
+        for signature in [&first_signature, &second_signature, &other_party_signature] {
+            repository
+                .buffer_single_signature(
+                    SignedEntityTypeDiscriminants::CardanoTransactions,
+                    signature,
+                )
+                .await
+                .unwrap();
+        }
+        repository
+            .buffer_single_signature(
+                SignedEntityTypeDiscriminants::MithrilStakeDistribution,
+                &first_signature,
+            )
+            .await
+            .unwrap();
+
+        let buffered_signatures = repository
+            .get_buffered_single_signatures(SignedEntityTypeDiscriminants::CardanoTransactions)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![second_signature, other_party_signature],
+            buffered_signatures
+        );
+    }
+}
@@ -120,10 +120,8 @@ impl CardanoTransactionRepository {
             transactions.into_iter().map(|tx| tx.into()).collect();
 
         let provider = InsertCardanoTransactionProvider::new(&self.connection);
-        let filters = provider.get_insert_many_condition(records)?;
-        let cursor = provider.find(filters)?;
 
-        Ok(cursor.collect())
+        provider.persist_many(records)
     }
 
     /// Create new [BlockRangeRootRecord]s in the database.
@@ -251,12 +249,9 @@ impl TransactionStore for CardanoTransactionRepository {
         for transactions_in_db_transaction_chunk in transactions.chunks(DB_TRANSACTION_SIZE) {
             self.connection.execute("BEGIN TRANSACTION;")?;
 
-            // Chunk transactions to avoid an error when we exceed sqlite binding limitations
-            for transactions_in_chunk in transactions_in_db_transaction_chunk.chunks(100) {
-                self.create_transactions(transactions_in_chunk.to_vec())
-                    .await
-                    .with_context(|| "CardanoTransactionRepository can not store transactions")?;
-            }
+            self.create_transactions(transactions_in_db_transaction_chunk.to_vec())
+                .await
+                .with_context(|| "CardanoTransactionRepository can not store transactions")?;
 
             self.connection.execute("END TRANSACTION;")?;
         }
@@ -0,0 +1,152 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+#[cfg(test)]
+use mockall::automock;
+
+use mithril_common::entities::{Epoch, HexEncodedGenesisSignature};
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{Provider, SqliteConnection, WhereCondition};
+
+use crate::database::provider::{
+    GetCertificateChainCheckpointProvider, InsertCertificateChainCheckpointProvider,
+};
+use crate::database::record::CertificateChainCheckpointRecord;
+
+/// Service to record and retrieve the checkpoints of the certificate chain.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait CertificateChainCheckpointStorer: Sync + Send {
+    /// Record a signed checkpoint of the certificate chain at the given epoch, the checkpoint
+    /// already recorded for this epoch, if any, is kept.
+    async fn record_checkpoint(
+        &self,
+        epoch: Epoch,
+        certificate_hash: &str,
+        signature: HexEncodedGenesisSignature,
+    ) -> StdResult<()>;
+
+    /// Get the checkpoint recorded at the given epoch.
+    async fn get_checkpoint(
+        &self,
+        epoch: Epoch,
+    ) -> StdResult<Option<CertificateChainCheckpointRecord>>;
+
+    /// Get all the recorded checkpoints, most recent first.
+    async fn get_checkpoints(&self) -> StdResult<Vec<CertificateChainCheckpointRecord>>;
+}
+
+/// ## Certificate chain checkpoint repository
+///
+/// This is a business oriented layer to perform actions on the database through
+/// providers.
+pub struct CertificateChainCheckpointRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl CertificateChainCheckpointRepository {
+    /// Instanciate service
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl CertificateChainCheckpointStorer for CertificateChainCheckpointRepository {
+    async fn record_checkpoint(
+        &self,
+        epoch: Epoch,
+        certificate_hash: &str,
+        signature: HexEncodedGenesisSignature,
+    ) -> StdResult<()> {
+        let provider = InsertCertificateChainCheckpointProvider::new(&self.connection);
+        let filters = provider.get_insert_condition(&CertificateChainCheckpointRecord {
+            epoch,
+            certificate_hash: certificate_hash.to_string(),
+            signature,
+            created_at: Utc::now(),
+        })?;
+        provider.find(filters)?.next();
+
+        Ok(())
+    }
+
+    async fn get_checkpoint(
+        &self,
+        epoch: Epoch,
+    ) -> StdResult<Option<CertificateChainCheckpointRecord>> {
+        let provider = GetCertificateChainCheckpointProvider::new(&self.connection);
+        let filters = provider.get_epoch_condition(epoch)?;
+        let mut records = provider.find(filters)?;
+
+        Ok(records.next())
+    }
+
+    async fn get_checkpoints(&self) -> StdResult<Vec<CertificateChainCheckpointRecord>> {
+        let provider = GetCertificateChainCheckpointProvider::new(&self.connection);
+        let records = provider.find(WhereCondition::default())?;
+
+        Ok(records.collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::test_helper::main_db_connection;
+
+    use super::*;
+
+    fn repository() -> CertificateChainCheckpointRepository {
+        CertificateChainCheckpointRepository::new(Arc::new(main_db_connection().unwrap()))
+    }
+
+    #[tokio::test]
+    async fn record_then_get_checkpoints_most_recent_first() {
+        let repository = repository();
+
+        repository
+            .record_checkpoint(Epoch(10), "certificate-10", "signature-10".to_string())
+            .await
+            .unwrap();
+        repository
+            .record_checkpoint(Epoch(20), "certificate-20", "signature-20".to_string())
+            .await
+            .unwrap();
+
+        let checkpoints = repository.get_checkpoints().await.unwrap();
+        assert_eq!(
+            vec![(Epoch(20), "certificate-20"), (Epoch(10), "certificate-10")],
+            checkpoints
+                .iter()
+                .map(|checkpoint| (checkpoint.epoch, checkpoint.certificate_hash.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(("certificate-10".to_string(), "signature-10".to_string())),
+            repository
+                .get_checkpoint(Epoch(10))
+                .await
+                .unwrap()
+                .map(|checkpoint| (checkpoint.certificate_hash, checkpoint.signature))
+        );
+        assert_eq!(None, repository.get_checkpoint(Epoch(15)).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn checkpoint_already_recorded_for_an_epoch_is_kept() {
+        let repository = repository();
+
+        repository
+            .record_checkpoint(Epoch(10), "certificate-10", "signature".to_string())
+            .await
+            .unwrap();
+        repository
+            .record_checkpoint(Epoch(10), "another-certificate-10", "signature".to_string())
+            .await
+            .unwrap();
+
+        let checkpoint = repository.get_checkpoint(Epoch(10)).await.unwrap().unwrap();
+        assert_eq!("certificate-10", checkpoint.certificate_hash);
+    }
+}
@@ -2,12 +2,11 @@ use std::sync::Arc;
 
 use anyhow::anyhow;
 use async_trait::async_trait;
-use sqlite::ConnectionThreadSafe;
 
 use mithril_common::certificate_chain::{CertificateRetriever, CertificateRetrieverError};
-use mithril_common::entities::{Certificate, Epoch};
+use mithril_common::entities::{Certificate, Epoch, SignedEntityTypeDiscriminants};
 use mithril_common::StdResult;
-use mithril_persistence::sqlite::{GetAllProvider, Provider};
+use mithril_persistence::sqlite::{GetAllProvider, Provider, SqliteConnection, WhereCondition};
 
 use crate::database::provider::{
     DeleteCertificateProvider, GetCertificateRecordProvider, InsertCertificateRecordProvider,
@@ -15,14 +14,28 @@ use crate::database::provider::{
 };
 use crate::database::record::CertificateRecord;
 
+/// Filters applied when listing the certificates, the certificates matching all the given
+/// filters are returned.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CertificateListFilters {
+    /// Only return the certificates of this epoch or of a later one.
+    pub epoch_from: Option<Epoch>,
+
+    /// Only return the certificates of this epoch or of an earlier one.
+    pub epoch_to: Option<Epoch>,
+
+    /// Only return the certificates of this signed entity type.
+    pub signed_entity_type: Option<SignedEntityTypeDiscriminants>,
+}
+
 /// Database frontend API for Certificate queries.
 pub struct CertificateRepository {
-    connection: Arc<ConnectionThreadSafe>,
+    connection: Arc<SqliteConnection>,
 }
 
 impl CertificateRepository {
     /// Instantiate a new repository
-    pub fn new(connection: Arc<ConnectionThreadSafe>) -> Self {
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
         Self { connection }
     }
 
@@ -48,6 +61,47 @@ impl CertificateRepository {
         Ok(cursor.take(last_n).map(|v| v.into()).collect())
     }
 
+    /// Return the latest certificates matching the given filters.
+    pub async fn get_latest_certificates_with_filters<T>(
+        &self,
+        filters: &CertificateListFilters,
+        last_n: usize,
+    ) -> StdResult<Vec<T>>
+    where
+        T: From<CertificateRecord>,
+    {
+        let provider = GetCertificateRecordProvider::new(&self.connection);
+        let mut condition = WhereCondition::default();
+        if let Some(epoch_from) = filters.epoch_from {
+            condition = condition.and_where(provider.get_epoch_from_condition(epoch_from)?);
+        }
+        if let Some(epoch_to) = filters.epoch_to {
+            condition = condition.and_where(provider.get_epoch_to_condition(epoch_to)?);
+        }
+        if let Some(signed_entity_type) = &filters.signed_entity_type {
+            condition = condition.and_where(
+                provider.get_signed_entity_type_discriminant_condition(signed_entity_type),
+            );
+        }
+        let cursor = provider.find(condition)?;
+
+        Ok(cursor.take(last_n).map(|v| v.into()).collect())
+    }
+
+    /// Return the latest genesis key rotation certificates.
+    pub async fn get_latest_genesis_key_rotation_certificates<T>(
+        &self,
+        last_n: usize,
+    ) -> StdResult<Vec<T>>
+    where
+        T: From<CertificateRecord>,
+    {
+        let provider = GetCertificateRecordProvider::new(&self.connection);
+        let cursor = provider.find(provider.get_genesis_key_rotation_condition())?;
+
+        Ok(cursor.take(last_n).map(|v| v.into()).collect())
+    }
+
     /// Return the first certificate signed per epoch as the reference
     /// certificate for this Epoch. This will be the parent certificate for all
     /// other certificates issued within this Epoch.
@@ -116,7 +170,13 @@ impl CertificateRetriever for CertificateRepository {
 
 #[cfg(test)]
 mod tests {
-    use mithril_common::crypto_helper::tests_setup::setup_certificate_chain;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    use mithril_common::certificate_chain::CertificateGenesisProducer;
+    use mithril_common::crypto_helper::{
+        tests_setup::setup_certificate_chain, ProtocolGenesisSigner,
+    };
 
     use crate::database::test_helper::{insert_certificate_records, main_db_connection};
     use crate::dependency_injection::DependenciesBuilder;
@@ -124,7 +184,7 @@ mod tests {
 
     use super::*;
 
-    fn insert_golden_certificate(connection: &ConnectionThreadSafe) {
+    fn insert_golden_certificate(connection: &SqliteConnection) {
         connection
             .execute(r#"
             -- genesis certificate
@@ -154,7 +214,8 @@ mod tests {
                     "stake":1009497432569
                 }]',
                 '2023-06-23T08:37:49.066Z',
-                '2023-06-23T08:37:49.066Z'
+                '2023-06-23T08:37:49.066Z',
+                null
             );
             
             -- multi-signature certificate
@@ -185,7 +246,8 @@ mod tests {
                     "stake":1009497432569
                 }]',
                 '2023-03-16T01:51:00.880Z',
-                '2023-03-16T02:07:22.145Z'
+                '2023-03-16T02:07:22.145Z',
+                null
             );
             "#,
             )
@@ -265,6 +327,66 @@ mod tests {
         assert_eq!(expected, latest_certificates);
     }
 
+    async fn get_hashes(
+        repository: &CertificateRepository,
+        filters: CertificateListFilters,
+    ) -> Vec<String> {
+        repository
+            .get_latest_certificates_with_filters::<Certificate>(&filters, usize::MAX)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.hash)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn repository_get_latest_certificates_with_filters() {
+        let (certificates, _) = setup_certificate_chain(10, 2);
+        let mut deps = DependenciesBuilder::new(Configuration::new_sample());
+        let connection = deps.get_sqlite_connection().await.unwrap();
+        insert_certificate_records(&connection, certificates.clone());
+        let repository = CertificateRepository::new(connection);
+        let expected_hashes = |filter: &dyn Fn(&Certificate) -> bool| -> Vec<String> {
+            certificates
+                .iter()
+                .rev()
+                .filter(|c| filter(c))
+                .map(|c| c.hash.clone())
+                .collect()
+        };
+
+        assert_eq!(
+            expected_hashes(&|_| true),
+            get_hashes(&repository, CertificateListFilters::default()).await
+        );
+        assert_eq!(
+            expected_hashes(&|c| c.epoch >= Epoch(3) && c.epoch <= Epoch(4)),
+            get_hashes(
+                &repository,
+                CertificateListFilters {
+                    epoch_from: Some(Epoch(3)),
+                    epoch_to: Some(Epoch(4)),
+                    ..CertificateListFilters::default()
+                }
+            )
+            .await
+        );
+        assert_eq!(
+            expected_hashes(&|c| c.is_genesis()),
+            get_hashes(
+                &repository,
+                CertificateListFilters {
+                    signed_entity_type: Some(
+                        SignedEntityTypeDiscriminants::MithrilStakeDistribution
+                    ),
+                    ..CertificateListFilters::default()
+                }
+            )
+            .await
+        );
+    }
+
     #[tokio::test]
     async fn get_master_certificate_no_certificate_recorded_returns_none() {
         let mut deps = DependenciesBuilder::new(Configuration::new_sample());
@@ -506,6 +628,65 @@ mod tests {
         assert_eq!(expected_certificate_id.to_string(), certificate.hash);
     }
 
+    #[tokio::test]
+    async fn get_master_certificate_genesis_key_rotation_after_multiple_cert_in_current_epoch_returns_the_rotation(
+    ) {
+        let (certificates, _) = setup_certificate_chain(5, 2);
+        let mut deps = DependenciesBuilder::new(Configuration::new_sample());
+        let connection = deps.get_sqlite_connection().await.unwrap();
+        insert_certificate_records(&connection, certificates.iter().rev().cloned().collect());
+        let repository = CertificateRepository::new(connection);
+        let rotation_certificate =
+            CertificateGenesisProducer::create_genesis_key_rotation_certificate(
+                &certificates[0],
+                &ProtocolGenesisSigner::create_deterministic_genesis_signer(),
+                &ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed(
+                    [1u8; 32],
+                )),
+            )
+            .unwrap();
+        repository
+            .create_certificate(rotation_certificate.clone())
+            .await
+            .unwrap();
+
+        let certificate = repository
+            .get_master_certificate_for_epoch::<Certificate>(rotation_certificate.epoch)
+            .await
+            .unwrap();
+
+        assert_eq!(Some(rotation_certificate), certificate);
+    }
+
+    #[tokio::test]
+    async fn repository_get_latest_genesis_key_rotation_certificates() {
+        let (certificates, _) = setup_certificate_chain(5, 2);
+        let mut deps = DependenciesBuilder::new(Configuration::new_sample());
+        let connection = deps.get_sqlite_connection().await.unwrap();
+        insert_certificate_records(&connection, certificates.iter().rev().cloned().collect());
+        let repository = CertificateRepository::new(connection);
+        let rotation_certificate =
+            CertificateGenesisProducer::create_genesis_key_rotation_certificate(
+                &certificates[0],
+                &ProtocolGenesisSigner::create_deterministic_genesis_signer(),
+                &ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed(
+                    [1u8; 32],
+                )),
+            )
+            .unwrap();
+        repository
+            .create_certificate(rotation_certificate.clone())
+            .await
+            .unwrap();
+
+        let rotation_certificates: Vec<Certificate> = repository
+            .get_latest_genesis_key_rotation_certificates(10)
+            .await
+            .unwrap();
+
+        assert_eq!(vec![rotation_certificate], rotation_certificates);
+    }
+
     #[tokio::test]
     async fn save_certificate() {
         let (certificates, _) = setup_certificate_chain(5, 3);
@@ -0,0 +1,257 @@
+use anyhow::Context;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use mithril_common::entities::{
+    Certificate, Epoch, PartyId, ProtocolMessage, SignedEntityType, SingleSignatures,
+};
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{Provider, SqliteConnection, SqliteTransaction};
+
+use crate::database::provider::{
+    DeleteBufferedSingleSignatureProvider, InsertCertificateRecordProvider,
+    InsertOpenMessageProvider, UpdateOpenMessageProvider, UpdateSingleSignatureRecordProvider,
+};
+use crate::database::record::{OpenMessageRecord, SingleSignatureRecord};
+
+/// ## Certification unit of work
+///
+/// Writes of the certifier spanning several tables that are either all applied or all
+/// discarded.
+///
+/// Each unit of work runs in a [SqliteTransaction] on a connection dedicated to the certifier,
+/// one at a time, and does not await while its transaction is pending: the writes made at the
+/// same time on the other connections to the database are never part of it.
+pub struct CertificationUnitOfWork {
+    connection: Mutex<Arc<SqliteConnection>>,
+}
+
+impl CertificationUnitOfWork {
+    /// Create a new instance, the given connection must not be used by the other services.
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self {
+            connection: Mutex::new(connection),
+        }
+    }
+
+    fn run<T>(&self, work: impl FnOnce(&SqliteConnection) -> StdResult<T>) -> StdResult<T> {
+        let connection = self
+            .connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let transaction = SqliteTransaction::begin(&connection)?;
+        let result = work(transaction.connection())?;
+        transaction.commit()?;
+
+        Ok(result)
+    }
+
+    /// Create the open message of the given signed entity type with the given single signatures
+    /// registered, and remove from the buffer the single signatures of the given parties.
+    pub fn create_open_message_with_buffered_signatures(
+        &self,
+        epoch: Epoch,
+        signed_entity_type: &SignedEntityType,
+        protocol_message: &ProtocolMessage,
+        single_signatures: &[SingleSignatures],
+        buffered_party_ids: &[PartyId],
+    ) -> StdResult<OpenMessageRecord> {
+        self.run(|connection| {
+            let open_message =
+                insert_open_message(connection, epoch, signed_entity_type, protocol_message)?;
+
+            let provider = UpdateSingleSignatureRecordProvider::new(connection);
+            for single_signature in single_signatures {
+                provider.persist(SingleSignatureRecord::try_from_single_signatures(
+                    single_signature,
+                    &open_message.open_message_id,
+                    epoch.offset_to_signer_retrieval_epoch()?,
+                )?)?;
+            }
+
+            if !buffered_party_ids.is_empty() {
+                let provider = DeleteBufferedSingleSignatureProvider::new(connection);
+                let filters =
+                    provider.get_party_ids_condition(signed_entity_type.into(), buffered_party_ids);
+                provider.find(filters)?.for_each(drop);
+            }
+
+            Ok(open_message)
+        })
+    }
+
+    /// Store the given certificate, mark its open message as certified and create the open
+    /// messages of the signed entity types batched in its protocol message already certified.
+    pub fn store_certificate(
+        &self,
+        certificate: Certificate,
+        open_message: &OpenMessageRecord,
+        batched_signed_entity_types: &[SignedEntityType],
+    ) -> StdResult<Certificate> {
+        self.run(|connection| {
+            let certificate = InsertCertificateRecordProvider::new(connection)
+                .persist(certificate.into())
+                .with_context(|| "Can not store the certificate")?;
+
+            let open_message_certified = OpenMessageRecord {
+                is_certified: true,
+                ..open_message.clone()
+            };
+            update_open_message(connection, &open_message_certified)?;
+
+            for batched_signed_entity_type in batched_signed_entity_types {
+                let batched_open_message = insert_open_message(
+                    connection,
+                    batched_signed_entity_type.get_epoch(),
+                    batched_signed_entity_type,
+                    &open_message.protocol_message,
+                )?;
+                update_open_message(
+                    connection,
+                    &OpenMessageRecord {
+                        is_certified: true,
+                        ..batched_open_message
+                    },
+                )?;
+            }
+
+            Ok(certificate.into())
+        })
+    }
+}
+
+fn insert_open_message(
+    connection: &SqliteConnection,
+    epoch: Epoch,
+    signed_entity_type: &SignedEntityType,
+    protocol_message: &ProtocolMessage,
+) -> StdResult<OpenMessageRecord> {
+    let provider = InsertOpenMessageProvider::new(connection);
+    let filters = provider.get_insert_condition(epoch, signed_entity_type, protocol_message)?;
+
+    provider.find(filters)?.next().with_context(|| {
+        format!("Inserting the open message of '{signed_entity_type}' returned nothing")
+    })
+}
+
+fn update_open_message(
+    connection: &SqliteConnection,
+    open_message: &OpenMessageRecord,
+) -> StdResult<OpenMessageRecord> {
+    let provider = UpdateOpenMessageProvider::new(connection);
+    let filters = provider.get_update_condition(open_message)?;
+
+    provider.find(filters)?.next().with_context(|| {
+        format!(
+            "Updating the open message '{}' returned nothing",
+            open_message.open_message_id
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::fake_data;
+
+    use crate::database::repository::{
+        BufferedSingleSignatureRepository, OpenMessageRepository, SingleSignatureRepository,
+    };
+    use crate::database::test_helper::main_db_connection;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn create_open_message_registers_the_buffered_signatures_and_removes_them() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let buffered_repository = BufferedSingleSignatureRepository::new(connection.clone());
+        let signed_entity_type = SignedEntityType::MithrilStakeDistribution(Epoch(3));
+        let registered_signature = SingleSignatures {
+            party_id: "1".to_string(),
+            ..fake_data::single_signatures(vec![1, 2])
+        };
+        let discarded_signature = SingleSignatures {
+            party_id: "2".to_string(),
+            ..fake_data::single_signatures(vec![3])
+        };
+        for signature in [&registered_signature, &discarded_signature] {
+            buffered_repository
+                .buffer_single_signature((&signed_entity_type).into(), signature)
+                .await
+                .unwrap();
+        }
+
+        let open_message = CertificationUnitOfWork::new(connection.clone())
+            .create_open_message_with_buffered_signatures(
+                Epoch(3),
+                &signed_entity_type,
+                &ProtocolMessage::new(),
+                &[registered_signature.clone()],
+                &["1".to_string(), "2".to_string()],
+            )
+            .unwrap();
+
+        let single_signatures = SingleSignatureRepository::new(connection.clone())
+            .get_all_single_signatures_for_open_message(&open_message.open_message_id)
+            .await
+            .unwrap();
+        assert_eq!(vec![registered_signature], single_signatures);
+        assert!(buffered_repository
+            .get_buffered_single_signatures((&signed_entity_type).into())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn create_open_message_writes_nothing_if_a_signature_can_not_be_registered() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        // There is no signer registration epoch for the signatures of the epoch 0.
+        let signed_entity_type = SignedEntityType::MithrilStakeDistribution(Epoch(0));
+
+        CertificationUnitOfWork::new(connection.clone())
+            .create_open_message_with_buffered_signatures(
+                Epoch(0),
+                &signed_entity_type,
+                &ProtocolMessage::new(),
+                &[fake_data::single_signatures(vec![1, 2])],
+                &[],
+            )
+            .expect_err("Registering a signature without registration epoch should fail");
+
+        let open_message = OpenMessageRepository::new(connection)
+            .get_open_message(&signed_entity_type)
+            .await
+            .unwrap();
+        assert_eq!(None, open_message);
+    }
+
+    #[tokio::test]
+    async fn store_certificate_certifies_the_open_message_and_the_batched_ones() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let open_message_repository = OpenMessageRepository::new(connection.clone());
+        let signed_entity_type = SignedEntityType::MithrilStakeDistribution(Epoch(3));
+        let batched_signed_entity_type = SignedEntityType::CardanoStakeDistribution(Epoch(2));
+        let open_message = open_message_repository
+            .create_open_message(Epoch(3), &signed_entity_type, &ProtocolMessage::new())
+            .await
+            .unwrap();
+        let certificate = fake_data::certificate("certificate-hash".to_string());
+
+        let stored_certificate = CertificationUnitOfWork::new(connection.clone())
+            .store_certificate(
+                certificate.clone(),
+                &open_message,
+                &[batched_signed_entity_type.clone()],
+            )
+            .unwrap();
+
+        assert_eq!(certificate.hash, stored_certificate.hash);
+        for signed_entity_type in [signed_entity_type, batched_signed_entity_type] {
+            let open_message = open_message_repository
+                .get_open_message(&signed_entity_type)
+                .await
+                .unwrap()
+                .expect("The open message should exist");
+            assert!(open_message.is_certified, "{signed_entity_type:?}");
+        }
+    }
+}
@@ -1,20 +1,36 @@
 //! Aggregator related database repositories
+mod buffered_single_signature_repository;
 mod cardano_transaction_repository;
+mod certificate_chain_checkpoint_repository;
 mod certificate_repository;
+mod certification_unit_of_work;
 mod epoch_setting_store;
 mod open_message_repository;
+mod runtime_decision_repository;
+mod signed_entity_lease_repository;
 mod signed_entity_store;
+mod signer_metadata_repository;
+mod signer_participation_repository;
 mod signer_registration_store;
 mod signer_store;
+mod signer_unregistration_repository;
 mod single_signature_repository;
 mod stake_pool_store;
 
+pub use buffered_single_signature_repository::*;
 pub use cardano_transaction_repository::*;
+pub use certificate_chain_checkpoint_repository::*;
 pub use certificate_repository::*;
+pub use certification_unit_of_work::*;
 pub use epoch_setting_store::*;
 pub use open_message_repository::*;
+pub use runtime_decision_repository::*;
+pub use signed_entity_lease_repository::*;
 pub use signed_entity_store::*;
+pub use signer_metadata_repository::*;
+pub use signer_participation_repository::*;
 pub use signer_registration_store::*;
 pub use signer_store::*;
+pub use signer_unregistration_repository::*;
 pub use single_signature_repository::*;
 pub use stake_pool_store::*;
@@ -1,16 +1,24 @@
+use anyhow::Context;
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::Utc;
+use uuid::Uuid;
 
-use mithril_common::entities::{Epoch, ProtocolMessage, SignedEntityType};
+use mithril_common::entities::{
+    Epoch, ProtocolMessage, SignedEntityType, SignedEntityTypeDiscriminants,
+};
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::{Provider, SqliteConnection};
 
 use crate::database::provider::{
-    DeleteOpenMessageProvider, GetOpenMessageProvider, GetOpenMessageWithSingleSignaturesProvider,
-    InsertOpenMessageProvider, UpdateOpenMessageProvider,
+    DeleteOpenMessageProvider, GetOpenMessageProvider, GetOpenMessageQuorumStatusProvider,
+    GetOpenMessageWithSingleSignaturesProvider, InsertOpenMessageProvider,
+    UpdateOpenMessageProvider,
+};
+use crate::database::record::{
+    OpenMessageQuorumStatusRecord, OpenMessageRecord, OpenMessageWithSingleSignaturesRecord,
 };
-use crate::database::record::{OpenMessageRecord, OpenMessageWithSingleSignaturesRecord};
 
 /// ## Open message repository
 ///
@@ -40,6 +48,31 @@ impl OpenMessageRepository {
         Ok(messages.next())
     }
 
+    /// Return the most recent [OpenMessageRecord] of the given [SignedEntityTypeDiscriminants].
+    pub async fn get_latest_open_message_by_discriminant(
+        &self,
+        signed_entity_type_discriminant: &SignedEntityTypeDiscriminants,
+    ) -> StdResult<Option<OpenMessageRecord>> {
+        let provider = GetOpenMessageProvider::new(&self.connection);
+        let filters =
+            provider.get_signed_entity_type_discriminant_condition(signed_entity_type_discriminant);
+        let mut messages = provider.find(filters)?;
+
+        Ok(messages.next())
+    }
+
+    /// Return the aggregation of the single signatures registered for the given open message.
+    pub async fn get_open_message_quorum_status(
+        &self,
+        open_message_id: &Uuid,
+    ) -> StdResult<Option<OpenMessageQuorumStatusRecord>> {
+        let provider = GetOpenMessageQuorumStatusProvider::new(&self.connection);
+        let filters = provider.get_open_message_id_condition(open_message_id);
+        let mut records = provider.find(filters)?;
+
+        Ok(records.next())
+    }
+
     /// Return an open message with its associated single signatures for the given Epoch and [SignedEntityType].
     pub async fn get_open_message_with_single_signatures(
         &self,
@@ -55,14 +88,21 @@ impl OpenMessageRepository {
     }
 
     /// Return the expired [OpenMessageRecord] for the given Epoch and [SignedEntityType] if it exists
+    ///
+    /// An open message is expired once its expiration date is older than the given clock skew
+    /// tolerance.
     pub async fn get_expired_open_message(
         &self,
         signed_entity_type: &SignedEntityType,
+        clock_skew_tolerance: Duration,
     ) -> StdResult<Option<OpenMessageRecord>> {
         let provider = GetOpenMessageProvider::new(&self.connection);
-        let now = Utc::now().to_rfc3339();
+        let expired_before = (Utc::now()
+            - chrono::Duration::from_std(clock_skew_tolerance)
+                .with_context(|| "Invalid clock skew tolerance")?)
+        .to_rfc3339();
         let filters = provider
-            .get_expired_entity_type_condition(&now)
+            .get_expired_entity_type_condition(&expired_before)
             .and_where(provider.get_signed_entity_type_condition(signed_entity_type)?);
         let mut messages = provider.find(filters)?;
 
@@ -99,6 +139,19 @@ impl OpenMessageRepository {
             .ok_or_else(|| panic!("Updating an open_message should not return nothing."))
     }
 
+    /// Remove the [OpenMessageRecord] of the given [SignedEntityType], along with its single
+    /// signatures, from the database. It returns the removed message if it existed.
+    pub async fn delete_open_message(
+        &self,
+        signed_entity_type: &SignedEntityType,
+    ) -> StdResult<Option<OpenMessageRecord>> {
+        let provider = DeleteOpenMessageProvider::new(&self.connection);
+        let filters = provider.get_signed_entity_type_condition(signed_entity_type)?;
+        let mut cursor = provider.find(filters)?;
+
+        Ok(cursor.next())
+    }
+
     /// Remove all the [OpenMessageRecord] for the strictly previous epochs of the given epoch in the database.
     /// It returns the number of messages removed.
     pub async fn clean_epoch(&self, epoch: Epoch) -> StdResult<usize> {
@@ -115,12 +168,13 @@ mod tests {
     use sqlite::Value;
 
     use mithril_common::entities::CardanoDbBeacon;
+    use mithril_common::test_utils::fake_data;
     use mithril_persistence::sqlite::WhereCondition;
 
     use crate::database::record::SingleSignatureRecord;
     use crate::database::test_helper::{
-        insert_epoch_settings, insert_single_signatures_in_db, main_db_connection,
-        setup_single_signature_records,
+        insert_epoch_settings, insert_signer_registrations, insert_single_signatures_in_db,
+        main_db_connection, setup_single_signature_records,
     };
 
     use super::*;
@@ -146,7 +200,8 @@ mod tests {
                     }}',
                     1,
                     0,
-                    '2021-07-27T01:02:44.505640275+00:00'
+                    '2021-07-27T01:02:44.505640275+00:00',
+                    0
                 );
 
                 insert into single_signature values(
@@ -222,7 +277,7 @@ mod tests {
             .unwrap();
 
         let open_message_result = repository
-            .get_expired_open_message(&signed_entity_type)
+            .get_expired_open_message(&signed_entity_type, Duration::ZERO)
             .await
             .unwrap();
         assert!(open_message_result.is_none());
@@ -231,10 +286,16 @@ mod tests {
         repository.update_open_message(&open_message).await.unwrap();
 
         let open_message_result = repository
-            .get_expired_open_message(&signed_entity_type)
+            .get_expired_open_message(&signed_entity_type, Duration::ZERO)
             .await
             .unwrap();
         assert!(open_message_result.is_some());
+
+        let open_message_result = repository
+            .get_expired_open_message(&signed_entity_type, Duration::from_secs(200 * 24 * 3600))
+            .await
+            .unwrap();
+        assert!(open_message_result.is_none());
     }
 
     #[tokio::test]
@@ -334,6 +395,49 @@ mod tests {
         assert_eq!(2, count);
     }
 
+    #[tokio::test]
+    async fn repository_delete_open_message() {
+        let connection = get_connection().await;
+        let repository = OpenMessageRepository::new(connection.clone());
+        let signed_entity_type = SignedEntityType::MithrilStakeDistribution(Epoch(1));
+        let open_message = repository
+            .create_open_message(Epoch(1), &signed_entity_type, &ProtocolMessage::new())
+            .await
+            .unwrap();
+        repository
+            .create_open_message(
+                Epoch(2),
+                &SignedEntityType::MithrilStakeDistribution(Epoch(2)),
+                &ProtocolMessage::new(),
+            )
+            .await
+            .unwrap();
+
+        let deleted_open_message = repository
+            .delete_open_message(&signed_entity_type)
+            .await
+            .unwrap();
+
+        assert_eq!(Some(open_message), deleted_open_message);
+        assert!(repository
+            .get_open_message(&signed_entity_type)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repository
+            .get_open_message(&SignedEntityType::MithrilStakeDistribution(Epoch(2)))
+            .await
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            None,
+            repository
+                .delete_open_message(&signed_entity_type)
+                .await
+                .unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn repository_get_open_message_with_single_signatures_when_signatures_exist() {
         let connection = Arc::new(main_db_connection().unwrap());
@@ -394,4 +498,116 @@ mod tests {
             .single_signatures
             .is_empty())
     }
+
+    #[tokio::test]
+    async fn repository_get_latest_open_message_by_discriminant() {
+        let connection = get_connection().await;
+        let repository = OpenMessageRepository::new(connection.clone());
+        for epoch in [Epoch(1), Epoch(2)] {
+            repository
+                .create_open_message(
+                    epoch,
+                    &SignedEntityType::MithrilStakeDistribution(epoch),
+                    &ProtocolMessage::new(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let open_message = repository
+            .get_latest_open_message_by_discriminant(
+                &SignedEntityTypeDiscriminants::MithrilStakeDistribution,
+            )
+            .await
+            .unwrap()
+            .expect("an open message should be found");
+        assert_eq!(
+            SignedEntityType::MithrilStakeDistribution(Epoch(2)),
+            open_message.signed_entity_type
+        );
+
+        let open_message = repository
+            .get_latest_open_message_by_discriminant(
+                &SignedEntityTypeDiscriminants::CardanoTransactions,
+            )
+            .await
+            .unwrap();
+        assert_eq!(None, open_message);
+    }
+
+    #[tokio::test]
+    async fn repository_get_open_message_quorum_status() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let repository = OpenMessageRepository::new(connection.clone());
+        let signers = fake_data::signers_with_stakes(3);
+        insert_signer_registrations(&connection, vec![(Epoch(1), signers.clone())]).unwrap();
+        let open_message = repository
+            .create_open_message(
+                Epoch(2),
+                &SignedEntityType::MithrilStakeDistribution(Epoch(2)),
+                &ProtocolMessage::new(),
+            )
+            .await
+            .unwrap();
+        let single_signature_records: Vec<SingleSignatureRecord> = signers[0..2]
+            .iter()
+            .enumerate()
+            .map(|(i, signer)| SingleSignatureRecord {
+                open_message_id: open_message.open_message_id,
+                signer_id: signer.party_id.clone(),
+                registration_epoch_setting_id: Epoch(1),
+                lottery_indexes: (0..=i as u64).collect(),
+                ..setup_single_signature_records(1, 1, 1)[0].clone()
+            })
+            .collect();
+        insert_single_signatures_in_db(&connection, single_signature_records).unwrap();
+
+        let quorum_status = repository
+            .get_open_message_quorum_status(&open_message.open_message_id)
+            .await
+            .unwrap()
+            .expect("a quorum status should be found");
+
+        assert_eq!(
+            OpenMessageQuorumStatusRecord {
+                open_message_id: open_message.open_message_id,
+                signatures_count: 2,
+                won_lottery_indexes: 3,
+                signed_stake: signers[0].stake + signers[1].stake,
+                total_stake: signers.iter().map(|s| s.stake).sum(),
+            },
+            quorum_status
+        );
+    }
+
+    #[tokio::test]
+    async fn repository_get_open_message_quorum_status_without_signatures() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let repository = OpenMessageRepository::new(connection.clone());
+        let open_message = repository
+            .create_open_message(
+                Epoch(2),
+                &SignedEntityType::MithrilStakeDistribution(Epoch(2)),
+                &ProtocolMessage::new(),
+            )
+            .await
+            .unwrap();
+
+        let quorum_status = repository
+            .get_open_message_quorum_status(&open_message.open_message_id)
+            .await
+            .unwrap()
+            .expect("a quorum status should be found");
+
+        assert_eq!(
+            OpenMessageQuorumStatusRecord {
+                open_message_id: open_message.open_message_id,
+                signatures_count: 0,
+                won_lottery_indexes: 0,
+                signed_stake: 0,
+                total_stake: 0,
+            },
+            quorum_status
+        );
+    }
 }
@@ -0,0 +1,238 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+
+use mithril_common::entities::{SignedEntityType, SignedEntityTypeDiscriminants};
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{Provider, SqliteConnection};
+
+use crate::database::provider::{
+    GetRuntimeDecisionProvider, InsertRuntimeDecisionProvider,
+    UpdateRuntimeDecisionLastSeenProvider,
+};
+use crate::database::record::{RuntimeDecision, RuntimeDecisionRecord};
+
+/// Service to record and retrieve the decisions taken by the runtime.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait RuntimeDecisionStorer: Sync + Send {
+    /// Record a decision taken by the runtime for the given signed entity type.
+    ///
+    /// If the previous decision recorded for the signed entity type is identical only its
+    /// `last_seen_at` date is updated.
+    async fn record_decision(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        decision: RuntimeDecision,
+        reason: Option<String>,
+    ) -> StdResult<()>;
+
+    /// Return the decisions taken at least once since the given date, most recent first.
+    async fn get_decisions_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> StdResult<Vec<RuntimeDecisionRecord>>;
+}
+
+/// ## Runtime decision repository
+///
+/// This is a business oriented layer to perform actions on the database through
+/// providers.
+pub struct RuntimeDecisionRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl RuntimeDecisionRepository {
+    /// Instanciate service
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+
+    fn get_last_decision(
+        &self,
+        signed_entity_type: &SignedEntityTypeDiscriminants,
+    ) -> StdResult<Option<RuntimeDecisionRecord>> {
+        let provider = GetRuntimeDecisionProvider::new(&self.connection);
+        let filters = provider.get_signed_entity_type_condition(signed_entity_type);
+        let mut records = provider.find(filters)?;
+
+        Ok(records.next())
+    }
+}
+
+#[async_trait]
+impl RuntimeDecisionStorer for RuntimeDecisionRepository {
+    async fn record_decision(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        decision: RuntimeDecision,
+        reason: Option<String>,
+    ) -> StdResult<()> {
+        let now = Utc::now();
+        let last_decision = self.get_last_decision(&signed_entity_type.into())?;
+
+        match last_decision {
+            Some(record) if record.is_same_decision(signed_entity_type, decision, &reason)? => {
+                let provider = UpdateRuntimeDecisionLastSeenProvider::new(&self.connection);
+                let filters = provider.get_update_condition(record.runtime_decision_id, now);
+                provider.find(filters)?.next();
+            }
+            _ => {
+                let provider = InsertRuntimeDecisionProvider::new(&self.connection);
+                let filters =
+                    provider.get_insert_condition(signed_entity_type, decision, reason, now)?;
+                provider.find(filters)?.next();
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_decisions_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> StdResult<Vec<RuntimeDecisionRecord>> {
+        let provider = GetRuntimeDecisionProvider::new(&self.connection);
+        let filters = provider.get_last_seen_since_condition(&since);
+        let records = provider.find(filters)?;
+
+        Ok(records.collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use mithril_common::entities::{CardanoDbBeacon, Epoch};
+
+    use crate::database::test_helper::main_db_connection;
+
+    use super::*;
+
+    fn repository() -> RuntimeDecisionRepository {
+        RuntimeDecisionRepository::new(Arc::new(main_db_connection().unwrap()))
+    }
+
+    fn since_a_minute_ago() -> DateTime<Utc> {
+        Utc::now() - Duration::try_minutes(1).unwrap()
+    }
+
+    #[tokio::test]
+    async fn record_then_get_decisions() {
+        let repository = repository();
+        let signed_entity_type = SignedEntityType::MithrilStakeDistribution(Epoch(5));
+
+        repository
+            .record_decision(
+                &signed_entity_type,
+                RuntimeDecision::ProtocolMessageUnavailable,
+                Some("Missing stake distribution".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let decisions = repository
+            .get_decisions_since(since_a_minute_ago())
+            .await
+            .unwrap();
+        assert_eq!(1, decisions.len());
+        assert_eq!(signed_entity_type, decisions[0].signed_entity_type);
+        assert_eq!(
+            RuntimeDecision::ProtocolMessageUnavailable,
+            decisions[0].decision
+        );
+        assert_eq!(
+            Some("Missing stake distribution".to_string()),
+            decisions[0].reason
+        );
+    }
+
+    #[tokio::test]
+    async fn identical_consecutive_decisions_are_stored_once() {
+        let repository = repository();
+        let signed_entity_type =
+            SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::new("devnet", 5, 10));
+
+        for _ in 0..3 {
+            repository
+                .record_decision(
+                    &signed_entity_type,
+                    RuntimeDecision::BeaconAlreadyCertified,
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+
+        let decisions = repository
+            .get_decisions_since(since_a_minute_ago())
+            .await
+            .unwrap();
+        assert_eq!(1, decisions.len());
+        assert!(decisions[0].last_seen_at >= decisions[0].created_at);
+    }
+
+    #[tokio::test]
+    async fn a_new_decision_is_stored_when_beacon_or_decision_changes() {
+        let repository = repository();
+        let first_beacon =
+            SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::new("devnet", 5, 10));
+        let second_beacon =
+            SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::new("devnet", 5, 11));
+
+        repository
+            .record_decision(&first_beacon, RuntimeDecision::OpenMessageCreated, None)
+            .await
+            .unwrap();
+        repository
+            .record_decision(&first_beacon, RuntimeDecision::BeaconAlreadyCertified, None)
+            .await
+            .unwrap();
+        repository
+            .record_decision(&second_beacon, RuntimeDecision::OpenMessageCreated, None)
+            .await
+            .unwrap();
+
+        let decisions = repository
+            .get_decisions_since(since_a_minute_ago())
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![
+                (second_beacon, RuntimeDecision::OpenMessageCreated),
+                (
+                    first_beacon.clone(),
+                    RuntimeDecision::BeaconAlreadyCertified
+                ),
+                (first_beacon, RuntimeDecision::OpenMessageCreated),
+            ],
+            decisions
+                .into_iter()
+                .map(|d| (d.signed_entity_type, d.decision))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn get_decisions_since_exclude_older_decisions() {
+        let repository = repository();
+        repository
+            .record_decision(
+                &SignedEntityType::MithrilStakeDistribution(Epoch(5)),
+                RuntimeDecision::SignedEntityTypeDisabled,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let decisions = repository
+            .get_decisions_since(Utc::now() + Duration::try_minutes(1).unwrap())
+            .await
+            .unwrap();
+        assert!(decisions.is_empty());
+    }
+}
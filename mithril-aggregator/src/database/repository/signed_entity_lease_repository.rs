@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+
+use mithril_common::entities::SignedEntityType;
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{Provider, SqliteConnection};
+
+use crate::database::provider::{
+    AcquireSignedEntityLeaseProvider, ReleaseSignedEntityLeaseProvider,
+};
+
+/// Service to lease the creation of the artifacts of the signed entity types.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait SignedEntityLeaseStorer: Sync + Send {
+    /// Try to acquire the lease of the given signed entity type until the given date.
+    ///
+    /// Returns `true` if the lease was acquired: it was not held, it was stale or it was already
+    /// held by the same holder.
+    async fn try_acquire_lease(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        holder: &str,
+        expires_at: DateTime<Utc>,
+    ) -> StdResult<bool>;
+
+    /// Release the lease of the given signed entity type if it is held by the given holder.
+    async fn release_lease(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        holder: &str,
+    ) -> StdResult<()>;
+}
+
+/// ## Signed entity lease repository
+///
+/// This is a business oriented layer to perform actions on the database through
+/// providers.
+pub struct SignedEntityLeaseRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl SignedEntityLeaseRepository {
+    /// Instanciate service
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl SignedEntityLeaseStorer for SignedEntityLeaseRepository {
+    async fn try_acquire_lease(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        holder: &str,
+        expires_at: DateTime<Utc>,
+    ) -> StdResult<bool> {
+        let provider = AcquireSignedEntityLeaseProvider::new(&self.connection);
+        let filters =
+            provider.get_acquire_condition(signed_entity_type, holder, Utc::now(), expires_at)?;
+        let acquired_lease = provider.find(filters)?.next();
+
+        Ok(acquired_lease.is_some())
+    }
+
+    async fn release_lease(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        holder: &str,
+    ) -> StdResult<()> {
+        let provider = ReleaseSignedEntityLeaseProvider::new(&self.connection);
+        let filters = provider.get_release_condition(signed_entity_type, holder)?;
+        provider.find(filters)?.next();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use mithril_common::entities::{CardanoDbBeacon, Epoch};
+
+    use crate::database::test_helper::main_db_connection;
+
+    use super::*;
+
+    fn repository() -> SignedEntityLeaseRepository {
+        SignedEntityLeaseRepository::new(Arc::new(main_db_connection().unwrap()))
+    }
+
+    fn in_an_hour() -> DateTime<Utc> {
+        Utc::now() + Duration::try_hours(1).unwrap()
+    }
+
+    #[tokio::test]
+    async fn lease_held_by_another_holder_can_not_be_acquired_until_released() {
+        let repository = repository();
+        let signed_entity_type =
+            SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::new("devnet", 5, 10));
+
+        assert!(repository
+            .try_acquire_lease(&signed_entity_type, "holder-1", in_an_hour())
+            .await
+            .unwrap());
+        assert!(!repository
+            .try_acquire_lease(&signed_entity_type, "holder-2", in_an_hour())
+            .await
+            .unwrap());
+        assert!(repository
+            .try_acquire_lease(&signed_entity_type, "holder-1", in_an_hour())
+            .await
+            .unwrap());
+
+        repository
+            .release_lease(&signed_entity_type, "holder-2")
+            .await
+            .unwrap();
+        assert!(!repository
+            .try_acquire_lease(&signed_entity_type, "holder-2", in_an_hour())
+            .await
+            .unwrap());
+
+        repository
+            .release_lease(&signed_entity_type, "holder-1")
+            .await
+            .unwrap();
+        assert!(repository
+            .try_acquire_lease(&signed_entity_type, "holder-2", in_an_hour())
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn stale_lease_can_be_taken_over() {
+        let repository = repository();
+        let signed_entity_type = SignedEntityType::MithrilStakeDistribution(Epoch(5));
+
+        repository
+            .try_acquire_lease(
+                &signed_entity_type,
+                "holder-1",
+                Utc::now() - Duration::try_minutes(1).unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert!(repository
+            .try_acquire_lease(&signed_entity_type, "holder-2", in_an_hour())
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn leases_of_different_beacons_are_independent() {
+        let repository = repository();
+
+        assert!(repository
+            .try_acquire_lease(
+                &SignedEntityType::MithrilStakeDistribution(Epoch(5)),
+                "holder-1",
+                in_an_hour()
+            )
+            .await
+            .unwrap());
+        assert!(repository
+            .try_acquire_lease(
+                &SignedEntityType::MithrilStakeDistribution(Epoch(6)),
+                "holder-2",
+                in_an_hour()
+            )
+            .await
+            .unwrap());
+    }
+}
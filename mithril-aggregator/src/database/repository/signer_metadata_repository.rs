@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{Provider, SqliteConnection};
+
+use crate::database::provider::{GetSignerMetadataProvider, InsertOrReplaceSignerMetadataProvider};
+use crate::database::record::SignerMetadataRecord;
+
+/// Service to record and retrieve the metadata sent by the signers when they register.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait SignerMetadataStorer: Sync + Send {
+    /// Record the metadata of a signer, replacing the ones previously recorded for it.
+    async fn record_signer_metadata(&self, signer_metadata: SignerMetadataRecord) -> StdResult<()>;
+
+    /// Return the metadata of the signers that registered at the latest known registration epoch.
+    async fn get_latest_signers_metadata(&self) -> StdResult<Vec<SignerMetadataRecord>>;
+}
+
+/// ## Signer metadata repository
+///
+/// This is a business oriented layer to perform actions on the database through
+/// providers.
+pub struct SignerMetadataRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl SignerMetadataRepository {
+    /// Instanciate service
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl SignerMetadataStorer for SignerMetadataRepository {
+    async fn record_signer_metadata(&self, signer_metadata: SignerMetadataRecord) -> StdResult<()> {
+        let provider = InsertOrReplaceSignerMetadataProvider::new(&self.connection);
+        let filters = provider.get_insert_or_replace_condition(&signer_metadata)?;
+        provider.find(filters)?.next();
+
+        Ok(())
+    }
+
+    async fn get_latest_signers_metadata(&self) -> StdResult<Vec<SignerMetadataRecord>> {
+        let provider = GetSignerMetadataProvider::new(&self.connection);
+        let filters = provider.get_latest_registration_epoch_condition();
+        let records = provider.find(filters)?;
+
+        Ok(records.collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use mithril_common::entities::Epoch;
+
+    use crate::database::test_helper::main_db_connection;
+
+    use super::*;
+
+    fn repository() -> SignerMetadataRepository {
+        SignerMetadataRepository::new(Arc::new(main_db_connection().unwrap()))
+    }
+
+    fn signer_metadata(
+        signer_id: &str,
+        registration_epoch: u64,
+        node_version: Option<&str>,
+    ) -> SignerMetadataRecord {
+        SignerMetadataRecord {
+            signer_id: signer_id.to_string(),
+            registration_epoch: Epoch(registration_epoch),
+            node_version: node_version.map(|v| v.to_string()),
+            supported_eras: vec!["thales".to_string()],
+            ip_address: Some("127.0.0.1".to_string()),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_latest_signers_metadata_when_none_recorded() {
+        let repository = repository();
+
+        let records = repository.get_latest_signers_metadata().await.unwrap();
+
+        assert!(records.is_empty());
+    }
+
+    #[tokio::test]
+    async fn record_then_get_latest_signers_metadata() {
+        let repository = repository();
+        let record = signer_metadata("signer-1", 5, Some("0.2.150"));
+
+        repository
+            .record_signer_metadata(record.clone())
+            .await
+            .unwrap();
+
+        let records = repository.get_latest_signers_metadata().await.unwrap();
+        assert_eq!(vec![record], records);
+    }
+
+    #[tokio::test]
+    async fn recording_signer_metadata_replace_the_previous_ones() {
+        let repository = repository();
+        repository
+            .record_signer_metadata(signer_metadata("signer-1", 5, Some("0.2.150")))
+            .await
+            .unwrap();
+        let new_record = signer_metadata("signer-1", 6, None);
+
+        repository
+            .record_signer_metadata(new_record.clone())
+            .await
+            .unwrap();
+
+        let records = repository.get_latest_signers_metadata().await.unwrap();
+        assert_eq!(vec![new_record], records);
+    }
+
+    #[tokio::test]
+    async fn get_latest_signers_metadata_only_return_the_latest_registration_epoch() {
+        let repository = repository();
+        for record in [
+            signer_metadata("signer-1", 5, Some("0.2.150")),
+            signer_metadata("signer-2", 6, Some("0.2.151")),
+            signer_metadata("signer-3", 6, Some("0.2.150")),
+        ] {
+            repository.record_signer_metadata(record).await.unwrap();
+        }
+
+        let records = repository.get_latest_signers_metadata().await.unwrap();
+        assert_eq!(
+            vec!["signer-2".to_string(), "signer-3".to_string()],
+            records.into_iter().map(|r| r.signer_id).collect::<Vec<_>>()
+        );
+    }
+}
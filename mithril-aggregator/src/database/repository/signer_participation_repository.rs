@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+#[cfg(test)]
+use mockall::automock;
+
+use mithril_common::entities::{Epoch, PartyId};
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{Provider, SqliteConnection};
+
+use crate::database::provider::{
+    GetSignerParticipationProvider, UpsertSignerParticipationProvider,
+};
+use crate::database::record::SignerParticipationRecord;
+
+/// Service to track the participation of the signers across epochs.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait SignerParticipationStorer: Sync + Send {
+    /// Record that the signer is registered to sign at the given epoch.
+    async fn record_registration(&self, signer_id: &PartyId, epoch: Epoch) -> StdResult<()>;
+
+    /// Record that the signer issued a single signature at the given epoch.
+    async fn record_signature(&self, signer_id: &PartyId, epoch: Epoch) -> StdResult<()>;
+
+    /// Return the participation of the signer for all the epochs it was seen at, most recent
+    /// epoch first.
+    async fn get_signer_history(
+        &self,
+        signer_id: &PartyId,
+    ) -> StdResult<Vec<SignerParticipationRecord>>;
+}
+
+/// ## Signer participation repository
+///
+/// This is a business oriented layer to perform actions on the database through
+/// providers.
+pub struct SignerParticipationRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl SignerParticipationRepository {
+    /// Instanciate service
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl SignerParticipationStorer for SignerParticipationRepository {
+    async fn record_registration(&self, signer_id: &PartyId, epoch: Epoch) -> StdResult<()> {
+        let provider = UpsertSignerParticipationProvider::new(&self.connection);
+        let filters = provider.get_registration_condition(signer_id, epoch, Utc::now())?;
+        provider.find(filters)?.next();
+
+        Ok(())
+    }
+
+    async fn record_signature(&self, signer_id: &PartyId, epoch: Epoch) -> StdResult<()> {
+        let provider = UpsertSignerParticipationProvider::new(&self.connection);
+        let filters = provider.get_signature_condition(signer_id, epoch, Utc::now())?;
+        provider.find(filters)?.next();
+
+        Ok(())
+    }
+
+    async fn get_signer_history(
+        &self,
+        signer_id: &PartyId,
+    ) -> StdResult<Vec<SignerParticipationRecord>> {
+        let provider = GetSignerParticipationProvider::new(&self.connection);
+        let filters = provider.get_signer_id_condition(signer_id);
+        let records = provider.find(filters)?;
+
+        Ok(records.collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::test_helper::main_db_connection;
+
+    use super::*;
+
+    fn repository() -> SignerParticipationRepository {
+        SignerParticipationRepository::new(Arc::new(main_db_connection().unwrap()))
+    }
+
+    fn summarize(records: Vec<SignerParticipationRecord>) -> Vec<(Epoch, bool, u64)> {
+        records
+            .into_iter()
+            .map(|r| (r.epoch, r.registered, r.signatures_issued))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn get_signer_history_when_none_recorded() {
+        let repository = repository();
+
+        let records = repository
+            .get_signer_history(&"signer-1".to_string())
+            .await
+            .unwrap();
+
+        assert!(records.is_empty());
+    }
+
+    #[tokio::test]
+    async fn record_registrations_and_signatures_then_get_signer_history() {
+        let repository = repository();
+        let signer_id = "signer-1".to_string();
+
+        repository
+            .record_registration(&signer_id, Epoch(5))
+            .await
+            .unwrap();
+        repository
+            .record_signature(&signer_id, Epoch(5))
+            .await
+            .unwrap();
+        repository
+            .record_signature(&signer_id, Epoch(5))
+            .await
+            .unwrap();
+        repository
+            .record_registration(&signer_id, Epoch(6))
+            .await
+            .unwrap();
+        repository
+            .record_registration(&signer_id, Epoch(6))
+            .await
+            .unwrap();
+
+        let records = repository.get_signer_history(&signer_id).await.unwrap();
+        assert_eq!(
+            vec![(Epoch(6), true, 0), (Epoch(5), true, 2)],
+            summarize(records)
+        );
+    }
+
+    #[tokio::test]
+    async fn signature_recorded_before_registration_is_kept() {
+        let repository = repository();
+        let signer_id = "signer-1".to_string();
+
+        repository
+            .record_signature(&signer_id, Epoch(5))
+            .await
+            .unwrap();
+        repository
+            .record_registration(&signer_id, Epoch(5))
+            .await
+            .unwrap();
+
+        let records = repository.get_signer_history(&signer_id).await.unwrap();
+        assert_eq!(vec![(Epoch(5), true, 1)], summarize(records));
+    }
+
+    #[tokio::test]
+    async fn get_signer_history_only_return_the_given_signer() {
+        let repository = repository();
+        repository
+            .record_registration(&"signer-1".to_string(), Epoch(5))
+            .await
+            .unwrap();
+        repository
+            .record_registration(&"signer-2".to_string(), Epoch(5))
+            .await
+            .unwrap();
+
+        let records = repository
+            .get_signer_history(&"signer-2".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            vec!["signer-2".to_string()],
+            records.into_iter().map(|r| r.signer_id).collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,30 +1,200 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 
 use mithril_common::entities::{Epoch, PartyId, Signer, SignerWithStake};
+use mithril_common::era::SupportedEra;
 use mithril_common::StdResult;
 use mithril_persistence::sqlite::SqliteConnection;
 use mithril_persistence::store::adapter::AdapterError;
+use mithril_persistence::store::StoreCipher;
 
 use crate::database::provider::{
-    DeleteSignerRegistrationRecordProvider, GetSignerRegistrationRecordProvider,
+    DeleteSignerRegistrationNextEraRecordProvider, DeleteSignerRegistrationRecordProvider,
+    GetSignerRegistrationNextEraRecordProvider, GetSignerRegistrationRecordProvider,
+    InsertOrReplaceSignerRegistrationNextEraRecordProvider,
     InsertOrReplaceSignerRegistrationRecordProvider,
 };
-use crate::database::record::SignerRegistrationRecord;
+use crate::database::record::{SignerRegistrationNextEraRecord, SignerRegistrationRecord};
+use crate::store::with_era_verification_keys;
 use crate::VerificationKeyStorer;
 
 /// Service to deal with signer_registration (read & write).
 pub struct SignerRegistrationStore {
     connection: Arc<SqliteConnection>,
+    cipher: Option<Arc<StoreCipher>>,
 }
 
 impl SignerRegistrationStore {
     /// Create a new [SignerRegistrationStore] service
     pub fn new(connection: Arc<SqliteConnection>) -> Self {
-        Self { connection }
+        Self {
+            connection,
+            cipher: None,
+        }
+    }
+
+    /// Encrypt the keys of the signer registrations at rest with the given [StoreCipher].
+    pub fn with_cipher(mut self, cipher: Arc<StoreCipher>) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    /// Associated data binding an encrypted column of a signer registration to its row.
+    fn associated_data(table: &str, column: &str, signer_id: &str, row_id: &str) -> String {
+        format!("{table}:{column}:{signer_id}:{row_id}")
+    }
+
+    fn encrypt_record(
+        &self,
+        record: SignerRegistrationRecord,
+    ) -> StdResult<SignerRegistrationRecord> {
+        let Some(cipher) = &self.cipher else {
+            return Ok(record);
+        };
+        let associated_data = |column| {
+            Self::associated_data(
+                "signer_registration",
+                column,
+                &record.signer_id,
+                &record.epoch_setting_id.to_string(),
+            )
+        };
+
+        Ok(SignerRegistrationRecord {
+            verification_key: cipher.encrypt(
+                &record.verification_key,
+                &associated_data("verification_key"),
+            )?,
+            verification_key_signature: cipher.encrypt_option(
+                record.verification_key_signature.as_deref(),
+                &associated_data("verification_key_signature"),
+            )?,
+            operational_certificate: cipher.encrypt_option(
+                record.operational_certificate.as_deref(),
+                &associated_data("operational_certificate"),
+            )?,
+            ..record
+        })
+    }
+
+    fn decrypt_record(
+        &self,
+        record: SignerRegistrationRecord,
+    ) -> StdResult<SignerRegistrationRecord> {
+        let Some(cipher) = &self.cipher else {
+            if StoreCipher::is_encrypted(&record.verification_key) {
+                return Err(anyhow!(
+                    "Signer registration of '{}' is encrypted but no store encryption key is configured",
+                    record.signer_id
+                ));
+            }
+            return Ok(record);
+        };
+        let associated_data = |column| {
+            Self::associated_data(
+                "signer_registration",
+                column,
+                &record.signer_id,
+                &record.epoch_setting_id.to_string(),
+            )
+        };
+
+        Ok(SignerRegistrationRecord {
+            verification_key: cipher
+                .decrypt(
+                    &record.verification_key,
+                    &associated_data("verification_key"),
+                )
+                .with_context(|| {
+                    format!(
+                        "Could not decrypt the signer registration of '{}'",
+                        record.signer_id
+                    )
+                })?,
+            verification_key_signature: cipher.decrypt_option(
+                record.verification_key_signature.as_deref(),
+                &associated_data("verification_key_signature"),
+            )?,
+            operational_certificate: cipher.decrypt_option(
+                record.operational_certificate.as_deref(),
+                &associated_data("operational_certificate"),
+            )?,
+            ..record
+        })
+    }
+
+    fn decrypt_records(
+        &self,
+        records: impl Iterator<Item = SignerRegistrationRecord>,
+    ) -> StdResult<Vec<SignerRegistrationRecord>> {
+        records.map(|record| self.decrypt_record(record)).collect()
+    }
+
+    fn next_era_associated_data(record: &SignerRegistrationNextEraRecord, column: &str) -> String {
+        Self::associated_data(
+            "signer_registration_next_era",
+            column,
+            &record.signer_id,
+            &format!("{}:{}", record.epoch_setting_id, record.era),
+        )
+    }
+
+    fn encrypt_next_era_record(
+        &self,
+        record: SignerRegistrationNextEraRecord,
+    ) -> StdResult<SignerRegistrationNextEraRecord> {
+        let Some(cipher) = &self.cipher else {
+            return Ok(record);
+        };
+
+        Ok(SignerRegistrationNextEraRecord {
+            verification_key: cipher.encrypt(
+                &record.verification_key,
+                &Self::next_era_associated_data(&record, "verification_key"),
+            )?,
+            verification_key_signature: cipher.encrypt_option(
+                record.verification_key_signature.as_deref(),
+                &Self::next_era_associated_data(&record, "verification_key_signature"),
+            )?,
+            ..record
+        })
+    }
+
+    fn decrypt_next_era_record(
+        &self,
+        record: SignerRegistrationNextEraRecord,
+    ) -> StdResult<SignerRegistrationNextEraRecord> {
+        let Some(cipher) = &self.cipher else {
+            if StoreCipher::is_encrypted(&record.verification_key) {
+                return Err(anyhow!(
+                    "Next era signer registration of '{}' is encrypted but no store encryption key is configured",
+                    record.signer_id
+                ));
+            }
+            return Ok(record);
+        };
+
+        Ok(SignerRegistrationNextEraRecord {
+            verification_key: cipher
+                .decrypt(
+                    &record.verification_key,
+                    &Self::next_era_associated_data(&record, "verification_key"),
+                )
+                .with_context(|| {
+                    format!(
+                        "Could not decrypt the next era signer registration of '{}'",
+                        record.signer_id
+                    )
+                })?,
+            verification_key_signature: cipher.decrypt_option(
+                record.verification_key_signature.as_deref(),
+                &Self::next_era_associated_data(&record, "verification_key_signature"),
+            )?,
+            ..record
+        })
     }
 }
 
@@ -45,12 +215,16 @@ impl VerificationKeyStorer for SignerRegistrationStore {
                 )
             })
             .map_err(AdapterError::QueryError)?
-            .next();
+            .next()
+            .map(|record| self.decrypt_record(record))
+            .transpose()?;
 
         let _updated_record = provider
-            .persist(SignerRegistrationRecord::from_signer_with_stake(
-                signer, epoch,
-            ))
+            .persist(
+                self.encrypt_record(SignerRegistrationRecord::from_signer_with_stake(
+                    signer, epoch,
+                ))?,
+            )
             .with_context(|| format!("persist verification key failure, epoch: {epoch}"))
             .map_err(AdapterError::GeneralError)?;
 
@@ -70,8 +244,11 @@ impl VerificationKeyStorer for SignerRegistrationStore {
             .with_context(|| format!("get verification key failure, epoch: {epoch}"))
             .map_err(AdapterError::GeneralError)?;
 
-        let signer_with_stakes: HashMap<PartyId, Signer> =
-            HashMap::from_iter(cursor.map(|record| (record.signer_id.to_owned(), record.into())));
+        let signer_with_stakes: HashMap<PartyId, Signer> = HashMap::from_iter(
+            self.decrypt_records(cursor)?
+                .into_iter()
+                .map(|record| (record.signer_id.to_owned(), record.into())),
+        );
 
         match signer_with_stakes.is_empty() {
             true => Ok(None),
@@ -86,7 +263,11 @@ impl VerificationKeyStorer for SignerRegistrationStore {
             .with_context(|| format!("get verification key failure, epoch: {epoch}"))
             .map_err(AdapterError::GeneralError)?;
 
-        let signer_with_stakes: Vec<SignerWithStake> = cursor.map(|record| record.into()).collect();
+        let signer_with_stakes: Vec<SignerWithStake> = self
+            .decrypt_records(cursor)?
+            .into_iter()
+            .map(|record| record.into())
+            .collect();
 
         match signer_with_stakes.is_empty() {
             true => Ok(None),
@@ -94,12 +275,94 @@ impl VerificationKeyStorer for SignerRegistrationStore {
         }
     }
 
+    async fn save_next_era_verification_key(
+        &self,
+        epoch: Epoch,
+        era: SupportedEra,
+        signer: Signer,
+    ) -> StdResult<()> {
+        let _updated_record =
+            InsertOrReplaceSignerRegistrationNextEraRecordProvider::new(&self.connection)
+                .persist(self.encrypt_next_era_record(
+                    SignerRegistrationNextEraRecord::from_signer(signer, epoch, era),
+                )?)
+                .with_context(|| {
+                    format!("persist next era verification key failure, epoch: {epoch}, era: {era}")
+                })
+                .map_err(AdapterError::GeneralError)?;
+
+        Ok(())
+    }
+
+    async fn get_signers_of_era(
+        &self,
+        epoch: Epoch,
+        era: SupportedEra,
+    ) -> StdResult<Option<Vec<SignerWithStake>>> {
+        let Some(signers) = self.get_signers(epoch).await? else {
+            return Ok(None);
+        };
+        let cursor = GetSignerRegistrationNextEraRecordProvider::new(&self.connection)
+            .get_by_epoch_and_era(epoch, era)
+            .with_context(|| {
+                format!("get next era verification key failure, epoch: {epoch}, era: {era}")
+            })
+            .map_err(AdapterError::GeneralError)?;
+        let mut era_signers = HashMap::new();
+        for record in cursor {
+            let record = self.decrypt_next_era_record(record)?;
+            let signer = Signer {
+                party_id: record.signer_id.clone(),
+                verification_key: record.verification_key.try_into()?,
+                verification_key_signature: record
+                    .verification_key_signature
+                    .map(|k| k.try_into())
+                    .transpose()?,
+                operational_certificate: None,
+                kes_period: None,
+            };
+            era_signers.insert(record.signer_id, signer);
+        }
+
+        Ok(Some(with_era_verification_keys(signers, &era_signers)))
+    }
+
+    async fn remove_verification_key(
+        &self,
+        epoch: Epoch,
+        party_id: &PartyId,
+    ) -> StdResult<Option<SignerWithStake>> {
+        let removed_record = DeleteSignerRegistrationRecordProvider::new(&self.connection)
+            .delete_by_signer_id_and_epoch(party_id.to_owned(), epoch)
+            .with_context(|| {
+                format!(
+                    "Delete signer registration record failure with signer_id: '{party_id}', epoch: '{epoch}'"
+                )
+            })
+            .map_err(AdapterError::QueryError)?
+            .next()
+            .map(|record| self.decrypt_record(record))
+            .transpose()?;
+        let _removed_next_era_records =
+            DeleteSignerRegistrationNextEraRecordProvider::new(&self.connection)
+                .delete_by_signer_id_and_epoch(party_id.to_owned(), epoch)
+                .map_err(AdapterError::QueryError)?
+                .collect::<Vec<_>>();
+
+        Ok(removed_record.map(|record| record.into()))
+    }
+
     async fn prune_verification_keys(&self, max_epoch_to_prune: Epoch) -> StdResult<()> {
         let _deleted_records = DeleteSignerRegistrationRecordProvider::new(&self.connection)
             // we want to prune including the given epoch (+1)
             .prune(max_epoch_to_prune + 1)
             .map_err(AdapterError::QueryError)?
             .collect::<Vec<_>>();
+        let _deleted_next_era_records =
+            DeleteSignerRegistrationNextEraRecordProvider::new(&self.connection)
+                .prune(max_epoch_to_prune + 1)
+                .map_err(AdapterError::QueryError)?
+                .collect::<Vec<_>>();
 
         Ok(())
     }
@@ -107,6 +370,8 @@ impl VerificationKeyStorer for SignerRegistrationStore {
 
 #[cfg(test)]
 mod tests {
+    use mithril_common::test_utils::fake_data;
+
     use crate::database::test_helper::{insert_signer_registrations, main_db_connection};
     use crate::store::test_verification_key_storer;
 
@@ -158,6 +423,143 @@ mod tests {
         Arc::new(SignerRegistrationStore::new(Arc::new(connection)))
     }
 
+    const STORE_ENCRYPTION_KEY: &str =
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    fn store_cipher() -> Arc<StoreCipher> {
+        Arc::new(StoreCipher::from_hex_key(STORE_ENCRYPTION_KEY).unwrap())
+    }
+
+    #[tokio::test]
+    async fn keys_are_encrypted_at_rest_when_a_cipher_is_set() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let store = SignerRegistrationStore::new(connection.clone()).with_cipher(store_cipher());
+        let signer = fake_data::signers_with_stakes(1)[0].clone();
+
+        store
+            .save_verification_key(Epoch(1), signer.clone())
+            .await
+            .unwrap();
+
+        let record = GetSignerRegistrationRecordProvider::new(&connection)
+            .get_by_epoch(&Epoch(1))
+            .unwrap()
+            .next()
+            .expect("A signer registration should have been stored");
+        assert!(StoreCipher::is_encrypted(&record.verification_key));
+        assert_eq!(
+            Some(vec![signer]),
+            store.get_signers(Epoch(1)).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn keys_stored_in_clear_are_not_readable_when_a_cipher_is_set() {
+        let connection = main_db_connection().unwrap();
+        let signers = fake_data::signers_with_stakes(2);
+        insert_signer_registrations(&connection, vec![(Epoch(1), signers)]).unwrap();
+        let store = SignerRegistrationStore::new(Arc::new(connection)).with_cipher(store_cipher());
+
+        store
+            .get_signers(Epoch(1))
+            .await
+            .expect_err("Reading keys stored in clear when a cipher is set should fail");
+    }
+
+    #[tokio::test]
+    async fn encrypted_keys_moved_to_another_registration_are_not_readable() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let store = SignerRegistrationStore::new(connection.clone()).with_cipher(store_cipher());
+        let signers = fake_data::signers_with_stakes(2);
+        for signer in &signers {
+            store
+                .save_verification_key(Epoch(1), signer.clone())
+                .await
+                .unwrap();
+        }
+
+        connection
+            .execute(format!(
+                "update signer_registration set verification_key = \
+                    (select verification_key from signer_registration where signer_id = '{}') \
+                where signer_id = '{}'",
+                signers[0].party_id, signers[1].party_id
+            ))
+            .unwrap();
+
+        store
+            .get_signers(Epoch(1))
+            .await
+            .expect_err("Reading a key moved to another registration should fail");
+    }
+
+    #[tokio::test]
+    async fn next_era_keys_are_encrypted_at_rest_when_a_cipher_is_set() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let store = SignerRegistrationStore::new(connection.clone()).with_cipher(store_cipher());
+        let signers = fake_data::signers_with_stakes(2);
+        store
+            .save_verification_key(Epoch(1), signers[0].clone())
+            .await
+            .unwrap();
+        // The key of the next era of the signer is another key than its current one
+        let next_era_signer = Signer {
+            party_id: signers[0].party_id.clone(),
+            ..signers[1].clone().into()
+        };
+        store
+            .save_next_era_verification_key(Epoch(1), SupportedEra::dummy(), next_era_signer)
+            .await
+            .unwrap();
+
+        let era_signers = store
+            .get_signers_of_era(Epoch(1), SupportedEra::dummy())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(signers[1].verification_key, era_signers[0].verification_key);
+    }
+
+    #[tokio::test]
+    async fn next_era_keys_stored_in_clear_are_not_readable_when_a_cipher_is_set() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let signers = fake_data::signers_with_stakes(1);
+        SignerRegistrationStore::new(connection.clone())
+            .save_next_era_verification_key(
+                Epoch(1),
+                SupportedEra::dummy(),
+                signers[0].clone().into(),
+            )
+            .await
+            .unwrap();
+        let store = SignerRegistrationStore::new(connection).with_cipher(store_cipher());
+        store
+            .save_verification_key(Epoch(1), signers[0].clone())
+            .await
+            .unwrap();
+
+        store
+            .get_signers_of_era(Epoch(1), SupportedEra::dummy())
+            .await
+            .expect_err("Reading next era keys stored in clear when a cipher is set should fail");
+    }
+
+    #[tokio::test]
+    async fn reading_encrypted_keys_without_cipher_fails() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        SignerRegistrationStore::new(connection.clone())
+            .with_cipher(store_cipher())
+            .save_verification_key(Epoch(1), fake_data::signers_with_stakes(1)[0].clone())
+            .await
+            .unwrap();
+
+        SignerRegistrationStore::new(connection)
+            .get_signers(Epoch(1))
+            .await
+            .expect_err("Reading encrypted keys without cipher should fail");
+    }
+
     test_verification_key_storer!(
         test_signer_registration_store =>
         crate::database::repository::signer_registration_store::tests::init_signer_registration_store
@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+#[cfg(test)]
+use mockall::automock;
+
+use mithril_common::entities::{Epoch, PartyId};
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{Provider, SqliteConnection};
+
+use crate::database::provider::{
+    DeleteSignerUnregistrationProvider, GetSignerUnregistrationProvider,
+    InsertSignerUnregistrationProvider,
+};
+use crate::database::record::SignerUnregistrationRecord;
+
+/// Service to keep the tombstones of the signers unregistered from an epoch.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait SignerUnregistrationStorer: Sync + Send {
+    /// Record that the given signer is unregistered from the given epoch.
+    async fn record_signer_unregistration(&self, epoch: Epoch, party_id: &PartyId)
+        -> StdResult<()>;
+
+    /// Check if the given signer is unregistered from the given epoch.
+    async fn is_signer_unregistered(&self, epoch: Epoch, party_id: &PartyId) -> StdResult<bool>;
+
+    /// Prune the unregistrations that are at or below the given epoch.
+    async fn prune_signer_unregistrations(&self, max_epoch_to_prune: Epoch) -> StdResult<()>;
+}
+
+/// ## Signer unregistration repository
+///
+/// This is a business oriented layer to perform actions on the database through
+/// providers.
+pub struct SignerUnregistrationRepository {
+    connection: Arc<SqliteConnection>,
+}
+
+impl SignerUnregistrationRepository {
+    /// Instanciate service
+    pub fn new(connection: Arc<SqliteConnection>) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl SignerUnregistrationStorer for SignerUnregistrationRepository {
+    async fn record_signer_unregistration(
+        &self,
+        epoch: Epoch,
+        party_id: &PartyId,
+    ) -> StdResult<()> {
+        let provider = InsertSignerUnregistrationProvider::new(&self.connection);
+        let filters = provider.get_insert_condition(&SignerUnregistrationRecord {
+            signer_id: party_id.to_owned(),
+            epoch,
+            created_at: Utc::now(),
+        })?;
+        provider.find(filters)?.next();
+
+        Ok(())
+    }
+
+    async fn is_signer_unregistered(&self, epoch: Epoch, party_id: &PartyId) -> StdResult<bool> {
+        let provider = GetSignerUnregistrationProvider::new(&self.connection);
+        let filters = provider.get_signer_id_and_epoch_condition(party_id, epoch)?;
+        let mut records = provider.find(filters)?;
+
+        Ok(records.next().is_some())
+    }
+
+    async fn prune_signer_unregistrations(&self, max_epoch_to_prune: Epoch) -> StdResult<()> {
+        let provider = DeleteSignerUnregistrationProvider::new(&self.connection);
+        let filters = provider.get_prune_condition(max_epoch_to_prune)?;
+        let _deleted_records = provider.find(filters)?.count();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::test_helper::main_db_connection;
+
+    use super::*;
+
+    fn repository() -> SignerUnregistrationRepository {
+        SignerUnregistrationRepository::new(Arc::new(main_db_connection().unwrap()))
+    }
+
+    #[tokio::test]
+    async fn signer_is_unregistered_only_from_the_recorded_epoch() {
+        let repository = repository();
+        let party_id = "party-1".to_string();
+
+        repository
+            .record_signer_unregistration(Epoch(10), &party_id)
+            .await
+            .unwrap();
+        repository
+            .record_signer_unregistration(Epoch(10), &party_id)
+            .await
+            .unwrap();
+
+        assert!(repository
+            .is_signer_unregistered(Epoch(10), &party_id)
+            .await
+            .unwrap());
+        assert!(!repository
+            .is_signer_unregistered(Epoch(11), &party_id)
+            .await
+            .unwrap());
+        assert!(!repository
+            .is_signer_unregistered(Epoch(10), &"party-2".to_string())
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn prune_the_unregistrations_at_or_below_the_given_epoch() {
+        let repository = repository();
+        let party_id = "party-1".to_string();
+        for epoch in [Epoch(9), Epoch(10), Epoch(11)] {
+            repository
+                .record_signer_unregistration(epoch, &party_id)
+                .await
+                .unwrap();
+        }
+
+        repository
+            .prune_signer_unregistrations(Epoch(10))
+            .await
+            .unwrap();
+
+        let mut unregistered_epochs = vec![];
+        for epoch in [Epoch(9), Epoch(10), Epoch(11)] {
+            if repository
+                .is_signer_unregistered(epoch, &party_id)
+                .await
+                .unwrap()
+            {
+                unregistered_epochs.push(epoch);
+            }
+        }
+        assert_eq!(vec![Epoch(11)], unregistered_epochs);
+    }
+}
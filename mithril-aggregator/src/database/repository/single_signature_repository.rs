@@ -1,21 +1,84 @@
 use std::sync::Arc;
 
-use mithril_common::entities::SingleSignatures;
+use uuid::Uuid;
+
+use mithril_common::entities::{Epoch, SingleSignatures};
 use mithril_common::StdResult;
-use mithril_persistence::sqlite::SqliteConnection;
+use mithril_persistence::sqlite::{Provider, SqliteConnection};
 
-use crate::database::provider::UpdateSingleSignatureRecordProvider;
+use crate::database::provider::{
+    DeleteSingleSignatureProvider, GetSingleSignatureProvider, UpdateSingleSignatureRecordProvider,
+};
 use crate::database::record::{OpenMessageRecord, SingleSignatureRecord};
 
+/// Default number of single signatures fetched per query.
+const SINGLE_SIGNATURES_PAGE_SIZE: usize = 1000;
+
 /// Service to deal with single_signature (read & write).
 pub struct SingleSignatureRepository {
     connection: Arc<SqliteConnection>,
+    page_size: usize,
 }
 
 impl SingleSignatureRepository {
     /// Create a new SingleSignatureStoreAdapter service
     pub fn new(connection: Arc<SqliteConnection>) -> Self {
-        Self { connection }
+        Self {
+            connection,
+            page_size: SINGLE_SIGNATURES_PAGE_SIZE,
+        }
+    }
+
+    /// Set the number of single signatures fetched per query
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Return a page of the single signatures of an open message, ordered by registration epoch
+    /// and signer id, starting after the given registration epoch and signer id.
+    pub async fn get_single_signatures_page(
+        &self,
+        open_message_id: &Uuid,
+        after: Option<(Epoch, &str)>,
+    ) -> StdResult<Vec<SingleSignatureRecord>> {
+        let provider = GetSingleSignatureProvider::new(&self.connection, self.page_size);
+        let filters = provider.get_open_message_page_condition(open_message_id, after)?;
+
+        Ok(provider.find(filters)?.collect())
+    }
+
+    /// Return all the single signatures of an open message, fetched page by page, converted
+    /// into the [SingleSignatures] used by the protocol aggregation.
+    pub async fn get_all_single_signatures_for_open_message(
+        &self,
+        open_message_id: &Uuid,
+    ) -> StdResult<Vec<SingleSignatures>> {
+        let mut records = Vec::new();
+        loop {
+            let after = records.last().map(|record: &SingleSignatureRecord| {
+                (
+                    record.registration_epoch_setting_id,
+                    record.signer_id.clone(),
+                )
+            });
+            let page = self
+                .get_single_signatures_page(
+                    open_message_id,
+                    after
+                        .as_ref()
+                        .map(|(epoch, signer_id)| (*epoch, signer_id.as_str())),
+                )
+                .await?;
+            let is_last_page = page.len() < self.page_size;
+            records.extend(page);
+
+            if is_last_page {
+                break;
+            }
+        }
+
+        SingleSignatureRecord::try_into_single_signatures(records)
     }
 
     /// Create a new Single Signature in database
@@ -33,4 +96,72 @@ impl SingleSignatureRepository {
 
         provider.persist(single_signature)
     }
+
+    /// Remove the single signatures of the open messages of the strictly previous epochs of the
+    /// given epoch in the database.
+    /// It returns the number of single signatures removed.
+    pub async fn prune(&self, epoch: Epoch) -> StdResult<usize> {
+        let provider = DeleteSingleSignatureProvider::new(&self.connection);
+        let filters = provider.get_open_message_epoch_condition(epoch);
+        let cursor = provider.find(filters)?;
+
+        Ok(cursor.count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::{ProtocolMessage, SignedEntityType};
+    use mithril_common::test_utils::fake_data;
+
+    use crate::database::repository::OpenMessageRepository;
+    use crate::database::test_helper::main_db_connection;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn get_all_single_signatures_of_an_open_message_across_pages() {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let open_message_repository = OpenMessageRepository::new(connection.clone());
+        let repository = SingleSignatureRepository::new(connection).with_page_size(2);
+        let open_message = open_message_repository
+            .create_open_message(
+                Epoch(5),
+                &SignedEntityType::MithrilStakeDistribution(Epoch(5)),
+                &ProtocolMessage::new(),
+            )
+            .await
+            .unwrap();
+        let other_open_message = open_message_repository
+            .create_open_message(
+                Epoch(5),
+                &SignedEntityType::dummy(),
+                &ProtocolMessage::new(),
+            )
+            .await
+            .unwrap();
+        let single_signatures: Vec<SingleSignatures> = (1..=5)
+            .map(|index| SingleSignatures {
+                party_id: format!("party-{index}"),
+                ..fake_data::single_signatures(vec![index])
+            })
+            .collect();
+        for single_signature in &single_signatures {
+            repository
+                .create_single_signature(single_signature, &open_message)
+                .await
+                .unwrap();
+        }
+        repository
+            .create_single_signature(&single_signatures[0], &other_open_message)
+            .await
+            .unwrap();
+
+        let fetched_single_signatures = repository
+            .get_all_single_signatures_for_open_message(&open_message.open_message_id)
+            .await
+            .unwrap();
+
+        assert_eq!(single_signatures, fetched_single_signatures);
+    }
 }
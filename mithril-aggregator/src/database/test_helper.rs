@@ -1,5 +1,5 @@
 use chrono::Utc;
-use sqlite::{ConnectionThreadSafe, Value};
+use sqlite::Value;
 use uuid::Uuid;
 
 use mithril_common::entities::{ProtocolParameters, SignerWithStake};
@@ -18,7 +18,7 @@ use crate::database::record::{
 };
 
 /// In-memory sqlite database without foreign key support with migrations applied
-pub fn main_db_connection() -> StdResult<ConnectionThreadSafe> {
+pub fn main_db_connection() -> StdResult<SqliteConnection> {
     let connection = ConnectionBuilder::open_memory()
         .with_options(&[ConnectionOptions::ForceDisableForeignKeys])
         .with_migrations(crate::database::migration::get_migrations())
@@ -27,7 +27,7 @@ pub fn main_db_connection() -> StdResult<ConnectionThreadSafe> {
 }
 
 /// In-memory sqlite database without foreign key support with cardano db migrations applied
-pub fn cardano_tx_db_connection() -> StdResult<ConnectionThreadSafe> {
+pub fn cardano_tx_db_connection() -> StdResult<SqliteConnection> {
     let connection = ConnectionBuilder::open_memory()
         .with_options(&[ConnectionOptions::ForceDisableForeignKeys])
         .with_migrations(crate::database::cardano_transaction_migration::get_migrations())
@@ -111,7 +111,7 @@ pub fn insert_single_signatures_in_db(
 }
 
 pub fn insert_certificate_records<T: Into<CertificateRecord>>(
-    connection: &ConnectionThreadSafe,
+    connection: &SqliteConnection,
     records: Vec<T>,
 ) {
     let provider = InsertCertificateRecordProvider::new(connection);
@@ -1,7 +1,7 @@
 use anyhow::Context;
 use semver::Version;
-use slog::Logger;
-use std::sync::Arc;
+use slog::{Level, Logger};
+use std::{path::PathBuf, sync::Arc};
 use tokio::{
     sync::{
         mpsc::{UnboundedReceiver, UnboundedSender},
@@ -15,7 +15,9 @@ use mithril_common::{
     api_version::APIVersionProvider,
     cardano_block_scanner::{BlockScanner, CardanoBlockScanner},
     certificate_chain::{CertificateVerifier, MithrilCertificateVerifier},
-    chain_observer::{CardanoCliRunner, ChainObserver, ChainObserverBuilder, FakeObserver},
+    chain_observer::{
+        CachedChainObserver, CardanoCliRunner, ChainObserver, ChainObserverBuilder, FakeObserver,
+    },
     crypto_helper::{
         ProtocolGenesisSigner, ProtocolGenesisVerificationKey, ProtocolGenesisVerifier,
     },
@@ -29,6 +31,7 @@ use mithril_common::{
         adapters::{EraReaderAdapterBuilder, EraReaderDummyAdapter},
         EraChecker, EraMarker, EraReader, EraReaderAdapter, SupportedEra,
     },
+    logging::ComponentLogLevels,
     signable_builder::{
         CardanoImmutableFilesFullSignableBuilder, CardanoTransactionsSignableBuilder,
         MithrilSignableBuilderService, MithrilStakeDistributionSignableBuilder,
@@ -38,7 +41,7 @@ use mithril_common::{
 };
 use mithril_persistence::{
     database::{ApplicationNodeType, SqlMigration},
-    sqlite::{ConnectionBuilder, ConnectionOptions, SqliteConnection},
+    sqlite::{ConnectionBuilder, ConnectionOptions, QueryInstrumentation, SqliteConnection},
     store::adapter::{MemoryAdapter, SQLiteAdapter, StoreAdapter},
 };
 
@@ -47,33 +50,65 @@ use crate::{
         CardanoImmutableFilesFullArtifactBuilder, CardanoTransactionsArtifactBuilder,
         MithrilStakeDistributionArtifactBuilder,
     },
+    artifact_storage::{
+        ArtifactStorage, GcpArtifactStorage, LocalArtifactStorage, S3ArtifactStorage, S3Credentials,
+    },
     configuration::ExecutionEnvironment,
     database::repository::{
-        CardanoTransactionRepository, CertificateRepository, EpochSettingStore,
-        OpenMessageRepository, SignedEntityStore, SignedEntityStorer, SignerRegistrationStore,
-        SignerStore, SingleSignatureRepository, StakePoolStore,
+        BufferedSingleSignatureRepository, CardanoTransactionRepository,
+        CertificateChainCheckpointRepository, CertificateChainCheckpointStorer,
+        CertificateRepository, CertificationUnitOfWork, EpochSettingStore, OpenMessageRepository,
+        RuntimeDecisionRepository, RuntimeDecisionStorer, SignedEntityLeaseRepository,
+        SignedEntityStore, SignedEntityStorer, SignerMetadataRepository, SignerMetadataStorer,
+        SignerParticipationRepository, SignerParticipationStorer, SignerRegistrationStore,
+        SignerStore, SignerUnregistrationRepository, SingleSignatureRepository, StakePoolStore,
     },
-    event_store::{EventMessage, EventStore, TransmitterService},
-    http_server::routes::router,
+    event_store::{
+        EventForwarder, EventMessage, EventPersister, EventReader, EventSink, EventStore,
+        NdjsonFileEventSink, TransmitterService, WebhookEventSink,
+    },
+    http_server::{routes::router, SERVER_BASE_PATH},
     services::{
-        CardanoTransactionsImporter, CertifierService, MessageService, MithrilCertifierService,
-        MithrilEpochService, MithrilMessageService, MithrilProverService,
-        MithrilSignedEntityService, MithrilStakeDistributionService, MithrilTickerService,
-        ProverService, SignedEntityService, StakeDistributionService, TickerService,
-        TransactionStore,
+        CardanoTransactionsImporter, CertificateChainCheckpointPolicy, CertifierService,
+        ConfigurationReloader, ImmutableFileLagWatchdog, MessageService, MithrilCertifierService,
+        MithrilEpochService, MithrilImmutableFileLagWatchdog, MithrilMessageService,
+        MithrilProofBudgetService, MithrilProverService, MithrilPruningService,
+        MithrilQuorumSimulationService, MithrilSignatureAuthenticator, MithrilSignedEntityService,
+        MithrilStakeDistributionService, MithrilStaleSnapshotDetector, MithrilStatusService,
+        MithrilTickerService, ProofBudgetService, ProverCache, ProverService, PruningService,
+        QuorumSimulationService, ReloadableSettings, RetentionPolicy,
+        SharedOpenMessageReopeningPolicy, SignatureAuthenticator, SignedEntityService,
+        SignedEntityTypeLock, StakeDistributionService, StaleSnapshotDetector, StatusService,
+        TickerService, TransactionStore,
+    },
+    snapshot_uploaders::MANIFESTS_DIRECTORY,
+    tools::{
+        BandwidthLimiter, CExplorerSignerRetriever, FileDigestCache, GcpFileUploader, GenesisTools,
+        GenesisToolsDependency, SignersImporter, SnapshotManifestCache,
     },
-    tools::{CExplorerSignerRetriever, GcpFileUploader, GenesisToolsDependency, SignersImporter},
     AggregatorConfig, AggregatorRunner, AggregatorRuntime, CertificatePendingStore,
-    CompressedArchiveSnapshotter, Configuration, DependencyContainer, DumbSnapshotUploader,
-    DumbSnapshotter, LocalSnapshotUploader, MithrilSignerRegisterer, MultiSigner, MultiSignerImpl,
-    ProtocolParametersStorer, RemoteSnapshotUploader, SnapshotUploader, SnapshotUploaderType,
+    CompressedArchiveSnapshotter, Configuration, DeduplicatingSnapshotUploader,
+    DependencyContainer, DumbSnapshotUploader, DumbSnapshotter, LocalSnapshotUploader,
+    MeteredSnapshotUploader, MetricsService, MithrilSignerRegisterer, MultiSigner, MultiSignerImpl,
+    ProtocolParametersStorer, RemoteSnapshotUploader, RuntimeCycleTrigger,
+    ScheduledSnapshotUploader, SharedUploadSchedule, SnapshotUploader, SnapshotUploaderType,
     Snapshotter, SnapshotterCompressionAlgorithm, VerificationKeyStorer,
 };
 
 use super::{DependenciesBuilderError, EpochServiceWrapper, Result};
 
-const SQLITE_FILE: &str = "aggregator.sqlite3";
-const SQLITE_FILE_CARDANO_TRANSACTION: &str = "cardano-transaction.sqlite3";
+/// Name of the main SQLite database file of the aggregator
+pub const SQLITE_FILE: &str = "aggregator.sqlite3";
+
+/// Name of the SQLite database file storing the Cardano transactions
+pub const SQLITE_FILE_CARDANO_TRANSACTION: &str = "cardano-transaction.sqlite3";
+
+/// Name of the SQLite database file of the event store
+pub const SQLITE_MONITORING_FILE: &str = "monitoring.sqlite3";
+
+/// Maximum duration a connection waits for the locks held by the other connections to the same
+/// database to be released
+const SQLITE_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// ## Dependencies container builder
 ///
@@ -96,10 +131,22 @@ pub struct DependenciesBuilder {
     /// Cardano transactions SQLite database connection
     pub transaction_sqlite_connection: Option<Arc<SqliteConnection>>,
 
+    /// Event store SQLite database connection
+    pub event_store_sqlite_connection: Option<Arc<SqliteConnection>>,
+
+    /// SQLite database connection used by the HTTP read routes
+    pub read_sqlite_connection: Option<Arc<SqliteConnection>>,
+
+    /// SQLite database connection dedicated to the transactions of the certifier
+    pub certifier_sqlite_connection: Option<Arc<SqliteConnection>>,
+
     /// Stake Store used by the StakeDistributionService
     /// It shall be a private dependency.
     pub stake_store: Option<Arc<StakePoolStore>>,
 
+    /// Artifact storage service.
+    pub artifact_storage: Option<Arc<dyn ArtifactStorage>>,
+
     /// Snapshot uploader service.
     pub snapshot_uploader: Option<Arc<dyn SnapshotUploader>>,
 
@@ -115,6 +162,18 @@ pub struct DependenciesBuilder {
     /// Open message repository.
     pub open_message_repository: Option<Arc<OpenMessageRepository>>,
 
+    /// Runtime decision storer.
+    pub runtime_decision_storer: Option<Arc<dyn RuntimeDecisionStorer>>,
+
+    /// Certificate chain checkpoint storer.
+    pub certificate_chain_checkpoint_storer: Option<Arc<dyn CertificateChainCheckpointStorer>>,
+
+    /// Signer metadata storer.
+    pub signer_metadata_storer: Option<Arc<dyn SignerMetadataStorer>>,
+
+    /// Signer participation storer.
+    pub signer_participation_storer: Option<Arc<dyn SignerParticipationStorer>>,
+
     /// Verification key store.
     pub verification_key_store: Option<Arc<dyn VerificationKeyStorer>>,
 
@@ -181,6 +240,12 @@ pub struct DependenciesBuilder {
         Option<UnboundedSender<EventMessage>>,
     ),
 
+    /// Event Reader Service
+    pub event_reader: Option<Arc<dyn EventReader>>,
+
+    /// Log levels of the components of the aggregator
+    pub log_levels: Option<ComponentLogLevels>,
+
     /// API Version provider
     pub api_version_provider: Option<Arc<APIVersionProvider>>,
 
@@ -213,6 +278,57 @@ pub struct DependenciesBuilder {
 
     /// Prover service
     pub prover_service: Option<Arc<dyn ProverService>>,
+
+    /// Prover cache
+    pub prover_cache: Option<Arc<ProverCache>>,
+
+    /// Proof budget service
+    pub proof_budget_service: Option<Arc<dyn ProofBudgetService>>,
+
+    /// Quorum simulation service
+    pub quorum_simulation_service: Option<Arc<dyn QuorumSimulationService>>,
+
+    /// Status service
+    pub status_service: Option<Arc<dyn StatusService>>,
+
+    /// Metrics service
+    pub metrics_service: Option<Arc<MetricsService>>,
+
+    /// Instrumentation of the queries of the SQLite connections
+    pub query_instrumentation: Option<Arc<QueryInstrumentation>>,
+
+    /// Pruning service
+    pub pruning_service: Option<Arc<dyn PruningService>>,
+
+    /// Stale snapshot detector
+    pub stale_snapshot_detector: Option<Arc<dyn StaleSnapshotDetector>>,
+
+    /// Immutable file lag watchdog
+    pub immutable_file_lag_watchdog: Option<Arc<dyn ImmutableFileLagWatchdog>>,
+
+    /// Signature authenticator
+    pub signature_authenticator: Option<Arc<dyn SignatureAuthenticator>>,
+
+    /// Digests of the served snapshot archives
+    pub file_digest_cache: Option<Arc<FileDigestCache>>,
+
+    /// Objects of the served snapshot manifests
+    pub snapshot_manifest_cache: Option<Arc<SnapshotManifestCache>>,
+
+    /// Trigger of the cycles of the runtime
+    pub runtime_cycle_trigger: Option<Arc<RuntimeCycleTrigger>>,
+
+    /// Policy used to re-open the expired open messages
+    pub open_message_reopening_policy: Option<SharedOpenMessageReopeningPolicy>,
+
+    /// Bandwidth limiter of the snapshot uploads
+    pub snapshot_upload_bandwidth_limiter: Option<Arc<BandwidthLimiter>>,
+
+    /// Schedule of the snapshot uploads
+    pub snapshot_upload_schedule: Option<SharedUploadSchedule>,
+
+    /// Reloader of the settings that can be changed without a restart
+    pub configuration_reloader: Option<Arc<ConfigurationReloader>>,
 }
 
 impl DependenciesBuilder {
@@ -222,12 +338,20 @@ impl DependenciesBuilder {
             configuration,
             sqlite_connection: None,
             transaction_sqlite_connection: None,
+            event_store_sqlite_connection: None,
+            read_sqlite_connection: None,
+            certifier_sqlite_connection: None,
             stake_store: None,
+            artifact_storage: None,
             snapshot_uploader: None,
             multi_signer: None,
             certificate_pending_store: None,
             certificate_repository: None,
             open_message_repository: None,
+            runtime_decision_storer: None,
+            certificate_chain_checkpoint_storer: None,
+            signer_metadata_storer: None,
+            signer_participation_storer: None,
             verification_key_store: None,
             protocol_parameters_store: None,
             cardano_cli_runner: None,
@@ -249,6 +373,8 @@ impl DependenciesBuilder {
             era_reader: None,
             event_transmitter: None,
             event_transmitter_channel: (None, None),
+            event_reader: None,
+            log_levels: None,
             api_version_provider: None,
             stake_distribution_service: None,
             ticker_service: None,
@@ -260,27 +386,49 @@ impl DependenciesBuilder {
             signed_entity_storer: None,
             message_service: None,
             prover_service: None,
+            prover_cache: None,
+            proof_budget_service: None,
+            quorum_simulation_service: None,
+            status_service: None,
+            metrics_service: None,
+            query_instrumentation: None,
+            pruning_service: None,
+            stale_snapshot_detector: None,
+            immutable_file_lag_watchdog: None,
+            signature_authenticator: None,
+            file_digest_cache: None,
+            snapshot_manifest_cache: None,
+            runtime_cycle_trigger: None,
+            open_message_reopening_policy: None,
+            snapshot_upload_bandwidth_limiter: None,
+            snapshot_upload_schedule: None,
+            configuration_reloader: None,
         }
     }
 
-    async fn build_sqlite_connection(
-        &self,
-        sqlite_file_name: &str,
-        migrations: Vec<SqlMigration>,
-    ) -> Result<Arc<SqliteConnection>> {
-        let connection_builder = match self.configuration.environment {
-            ExecutionEnvironment::Production => ConnectionBuilder::open_file(
-                &self.configuration.get_sqlite_dir().join(sqlite_file_name),
-            ),
-            _ if self.configuration.data_stores_directory.to_string_lossy() == ":memory:" => {
-                ConnectionBuilder::open_memory()
+    /// Path of the given SQLite file, `None` if the databases are stored in memory.
+    fn get_sqlite_file_path(&self, sqlite_file_name: &str) -> Option<PathBuf> {
+        match self.configuration.environment {
+            ExecutionEnvironment::Production => {
+                Some(self.configuration.get_sqlite_dir().join(sqlite_file_name))
             }
-            _ => ConnectionBuilder::open_file(
-                &self
-                    .configuration
+            _ if self.configuration.data_stores_directory.to_string_lossy() == ":memory:" => None,
+            _ => Some(
+                self.configuration
                     .data_stores_directory
                     .join(sqlite_file_name),
             ),
+        }
+    }
+
+    async fn build_sqlite_connection(
+        &mut self,
+        sqlite_file_name: &str,
+        migrations: Vec<SqlMigration>,
+    ) -> Result<Arc<SqliteConnection>> {
+        let connection_builder = match self.get_sqlite_file_path(sqlite_file_name) {
+            Some(path) => ConnectionBuilder::open_file(&path),
+            None => ConnectionBuilder::open_memory(),
         };
 
         let connection = connection_builder
@@ -288,8 +436,10 @@ impl DependenciesBuilder {
             .with_options(&[
                 ConnectionOptions::EnableForeignKeys,
                 ConnectionOptions::EnableWriteAheadLog,
+                ConnectionOptions::BusyTimeout(SQLITE_BUSY_TIMEOUT),
             ])
             .with_logger(self.get_logger().await?)
+            .with_query_instrumentation(self.get_query_instrumentation().await?)
             .with_migrations(migrations)
             .build()
             .map_err(|e| DependenciesBuilderError::Initialization {
@@ -308,6 +458,10 @@ impl DependenciesBuilder {
         if let Some(connection) = &self.transaction_sqlite_connection {
             let _ = connection.execute("pragma analysis_limit=400; pragma optimize;");
         }
+
+        if let Some(connection) = &self.event_store_sqlite_connection {
+            let _ = connection.execute("pragma analysis_limit=400; pragma optimize;");
+        }
     }
 
     /// Get SQLite connection
@@ -325,6 +479,92 @@ impl DependenciesBuilder {
         Ok(self.sqlite_connection.as_ref().cloned().unwrap())
     }
 
+    async fn build_read_sqlite_connection(&mut self) -> Result<Arc<SqliteConnection>> {
+        // The aggregator database must be created and migrated before it is opened read only.
+        let write_connection = self.get_sqlite_connection().await?;
+        let database_path = match &self.configuration.database_read_replica_path {
+            Some(path) => path.clone(),
+            None if self.configuration.database_read_only_connection => {
+                match self.get_sqlite_file_path(SQLITE_FILE) {
+                    Some(path) => path,
+                    // An in memory database can not be shared between connections.
+                    None => return Ok(write_connection),
+                }
+            }
+            None => return Ok(write_connection),
+        };
+
+        let connection = ConnectionBuilder::open_file(&database_path)
+            .with_node_type(ApplicationNodeType::Aggregator)
+            .with_options(&[ConnectionOptions::ReadOnly])
+            .with_logger(self.get_logger().await?)
+            .with_query_instrumentation(self.get_query_instrumentation().await?)
+            .build()
+            .map_err(|e| DependenciesBuilderError::Initialization {
+                message: format!(
+                    "SQLite initialization: failed to build read only connection to '{}'.",
+                    database_path.display()
+                ),
+                error: Some(e),
+            })?;
+
+        Ok(Arc::new(connection))
+    }
+
+    /// Get the SQLite connection used by the HTTP read routes.
+    ///
+    /// It is the aggregator database connection unless a read only connection or a read replica
+    /// of the database is configured.
+    pub async fn get_read_sqlite_connection(&mut self) -> Result<Arc<SqliteConnection>> {
+        if self.read_sqlite_connection.is_none() {
+            self.read_sqlite_connection = Some(self.build_read_sqlite_connection().await?);
+        }
+
+        Ok(self.read_sqlite_connection.as_ref().cloned().unwrap())
+    }
+
+    async fn build_certifier_sqlite_connection(&mut self) -> Result<Arc<SqliteConnection>> {
+        // The aggregator database must be created and migrated before it is opened again.
+        let connection = self.get_sqlite_connection().await?;
+        let Some(database_path) = self.get_sqlite_file_path(SQLITE_FILE) else {
+            // An in memory database can not be shared between connections, the transactions
+            // of the certifier are made on the aggregator connection.
+            return Ok(connection);
+        };
+
+        let connection = ConnectionBuilder::open_file(&database_path)
+            .with_node_type(ApplicationNodeType::Aggregator)
+            .with_options(&[
+                ConnectionOptions::EnableForeignKeys,
+                ConnectionOptions::BusyTimeout(SQLITE_BUSY_TIMEOUT),
+            ])
+            .with_logger(self.get_logger().await?)
+            .with_query_instrumentation(self.get_query_instrumentation().await?)
+            .build()
+            .map_err(|e| DependenciesBuilderError::Initialization {
+                message: format!(
+                    "SQLite initialization: failed to build the certifier connection to '{}'.",
+                    database_path.display()
+                ),
+                error: Some(e),
+            })?;
+
+        Ok(Arc::new(connection))
+    }
+
+    /// Get the SQLite connection dedicated to the transactions of the certifier.
+    ///
+    /// A transaction includes all the writes made on its connection, so the connection can not
+    /// be shared with the other services.
+    pub async fn get_certifier_sqlite_connection(&mut self) -> Result<Arc<SqliteConnection>> {
+        if self.certifier_sqlite_connection.is_none() {
+            self.certifier_sqlite_connection =
+                Some(self.build_certifier_sqlite_connection().await?);
+        }
+
+        Ok(self.certifier_sqlite_connection.as_ref().cloned().unwrap())
+    }
+
     /// Get SQLite connection for the cardano transactions store
     pub async fn get_sqlite_connection_cardano_transaction(
         &mut self,
@@ -346,6 +586,25 @@ impl DependenciesBuilder {
             .unwrap())
     }
 
+    /// Get SQLite connection for the event store
+    pub async fn get_event_store_sqlite_connection(&mut self) -> Result<Arc<SqliteConnection>> {
+        if self.event_store_sqlite_connection.is_none() {
+            self.event_store_sqlite_connection = Some(
+                self.build_sqlite_connection(
+                    SQLITE_MONITORING_FILE,
+                    crate::event_store::migration::get_migrations(),
+                )
+                .await?,
+            );
+        }
+
+        Ok(self
+            .event_store_sqlite_connection
+            .as_ref()
+            .cloned()
+            .unwrap())
+    }
+
     async fn build_stake_store(&mut self) -> Result<Arc<StakePoolStore>> {
         let stake_pool_store = Arc::new(StakePoolStore::new(
             self.get_sqlite_connection().await?,
@@ -364,31 +623,130 @@ impl DependenciesBuilder {
         Ok(self.stake_store.as_ref().cloned().unwrap())
     }
 
+    async fn build_artifact_storage(&mut self) -> Result<Arc<dyn ArtifactStorage>> {
+        let bandwidth_limiter = self.get_snapshot_upload_bandwidth_limiter().await?;
+        let file_digest_cache = self.get_file_digest_cache().await?;
+        let local_artifact_storage = |bandwidth_limiter| {
+            let storage = LocalArtifactStorage::new(
+                &self.configuration.snapshot_directory,
+                format!(
+                    "{}{}/snapshot_download",
+                    self.configuration.get_server_url(),
+                    SERVER_BASE_PATH
+                ),
+            )
+            .with_bandwidth_limiter(bandwidth_limiter);
+
+            if self.configuration.snapshot_download_digest_header {
+                Arc::new(storage.with_file_digest_cache(file_digest_cache))
+            } else {
+                Arc::new(storage)
+            }
+        };
+        if self.configuration.environment != ExecutionEnvironment::Production {
+            return Ok(local_artifact_storage(bandwidth_limiter));
+        }
+
+        let bucket = || {
+            self.configuration
+                .snapshot_bucket_name
+                .to_owned()
+                .ok_or_else(|| {
+                    DependenciesBuilderError::MissingConfiguration(
+                        "snapshot_bucket_name".to_string(),
+                    )
+                })
+        };
+        match self.configuration.snapshot_uploader_type {
+            SnapshotUploaderType::Gcp => {
+                let bucket = bucket()?;
+                let file_uploader =
+                    GcpFileUploader::new(bucket.clone()).with_bandwidth_limiter(bandwidth_limiter);
+
+                Ok(Arc::new(GcpArtifactStorage::new(
+                    Box::new(file_uploader),
+                    bucket,
+                    self.configuration.snapshot_use_cdn_domain,
+                )))
+            }
+            SnapshotUploaderType::S3 => {
+                let endpoint = self
+                    .configuration
+                    .snapshot_s3_endpoint
+                    .to_owned()
+                    .ok_or_else(|| {
+                        DependenciesBuilderError::MissingConfiguration(
+                            "snapshot_s3_endpoint".to_string(),
+                        )
+                    })?;
+                let credentials = S3Credentials::from_environment().map_err(|e| {
+                    DependenciesBuilderError::Initialization {
+                        message: "Could not read the S3 storage credentials".to_string(),
+                        error: Some(e),
+                    }
+                })?;
+                let artifact_storage = S3ArtifactStorage::new(
+                    &endpoint,
+                    bucket()?,
+                    self.configuration.snapshot_s3_region.clone(),
+                    self.configuration.snapshot_s3_public_url.clone(),
+                    credentials,
+                )
+                .map_err(|e| DependenciesBuilderError::Initialization {
+                    message: "Could not create the S3 artifact storage".to_string(),
+                    error: Some(e),
+                })?
+                .with_bandwidth_limiter(bandwidth_limiter);
+
+                Ok(Arc::new(artifact_storage))
+            }
+            SnapshotUploaderType::Local | SnapshotUploaderType::Deduplicated => {
+                Ok(local_artifact_storage(bandwidth_limiter))
+            }
+        }
+    }
+
+    /// Get the [ArtifactStorage] where the artifacts files are stored
+    pub async fn get_artifact_storage(&mut self) -> Result<Arc<dyn ArtifactStorage>> {
+        if self.artifact_storage.is_none() {
+            self.artifact_storage = Some(self.build_artifact_storage().await?);
+        }
+
+        Ok(self.artifact_storage.as_ref().cloned().unwrap())
+    }
+
     async fn build_snapshot_uploader(&mut self) -> Result<Arc<dyn SnapshotUploader>> {
         if self.configuration.environment == ExecutionEnvironment::Production {
-            match self.configuration.snapshot_uploader_type {
-                SnapshotUploaderType::Gcp => {
-                    let bucket = self
-                        .configuration
-                        .snapshot_bucket_name
-                        .to_owned()
-                        .ok_or_else(|| {
-                            DependenciesBuilderError::MissingConfiguration(
-                                "snapshot_bucket_name".to_string(),
-                            )
-                        })?;
-
-                    Ok(Arc::new(RemoteSnapshotUploader::new(
-                        Box::new(GcpFileUploader::new(bucket.clone())),
-                        bucket,
-                        self.configuration.snapshot_use_cdn_domain,
-                    )))
-                }
-                SnapshotUploaderType::Local => Ok(Arc::new(LocalSnapshotUploader::new(
+            let snapshot_uploader: Arc<dyn SnapshotUploader> = match self
+                .configuration
+                .snapshot_uploader_type
+            {
+                SnapshotUploaderType::Gcp | SnapshotUploaderType::S3 => Arc::new(
+                    RemoteSnapshotUploader::new(self.get_artifact_storage().await?),
+                ),
+                SnapshotUploaderType::Local => Arc::new(LocalSnapshotUploader::new(
                     self.configuration.get_server_url(),
-                    &self.configuration.snapshot_directory,
-                ))),
-            }
+                    self.get_artifact_storage().await?,
+                )),
+                SnapshotUploaderType::Deduplicated => Arc::new(
+                    DeduplicatingSnapshotUploader::new(
+                        self.configuration.get_server_url(),
+                        &self.configuration.snapshot_directory,
+                        self.configuration.snapshot_compression_algorithm,
+                    )
+                    .with_bandwidth_limiter(self.get_snapshot_upload_bandwidth_limiter().await?),
+                ),
+            };
+            // The upload schedule wait is not part of the metered upload duration
+            let metered_snapshot_uploader = Arc::new(MeteredSnapshotUploader::new(
+                snapshot_uploader,
+                self.get_metrics_service().await?,
+            ));
+
+            Ok(Arc::new(ScheduledSnapshotUploader::new(
+                metered_snapshot_uploader,
+                self.get_snapshot_upload_schedule().await?,
+            )))
         } else {
             Ok(Arc::new(DumbSnapshotUploader::new()))
         }
@@ -404,7 +762,10 @@ impl DependenciesBuilder {
     }
 
     async fn build_multi_signer(&mut self) -> Result<Arc<RwLock<dyn MultiSigner>>> {
-        let multi_signer = MultiSignerImpl::new(self.get_epoch_service().await?);
+        let mut multi_signer = MultiSignerImpl::new(self.get_epoch_service().await?);
+        if let Some(number_of_threads) = self.configuration.multi_signer_threads {
+            multi_signer = multi_signer.with_thread_pool_size(number_of_threads)?;
+        }
 
         Ok(Arc::new(RwLock::new(multi_signer)))
     }
@@ -488,12 +849,89 @@ impl DependenciesBuilder {
         Ok(self.open_message_repository.as_ref().cloned().unwrap())
     }
 
-    async fn build_verification_key_store(&mut self) -> Result<Arc<dyn VerificationKeyStorer>> {
-        Ok(Arc::new(SignerRegistrationStore::new(
+    async fn build_runtime_decision_storer(&mut self) -> Result<Arc<dyn RuntimeDecisionStorer>> {
+        Ok(Arc::new(RuntimeDecisionRepository::new(
+            self.get_sqlite_connection().await?,
+        )))
+    }
+
+    /// Get a configured [RuntimeDecisionStorer].
+    pub async fn get_runtime_decision_storer(&mut self) -> Result<Arc<dyn RuntimeDecisionStorer>> {
+        if self.runtime_decision_storer.is_none() {
+            self.runtime_decision_storer = Some(self.build_runtime_decision_storer().await?);
+        }
+
+        Ok(self.runtime_decision_storer.as_ref().cloned().unwrap())
+    }
+
+    async fn build_certificate_chain_checkpoint_storer(
+        &mut self,
+    ) -> Result<Arc<dyn CertificateChainCheckpointStorer>> {
+        Ok(Arc::new(CertificateChainCheckpointRepository::new(
+            self.get_sqlite_connection().await?,
+        )))
+    }
+
+    /// Get a configured [CertificateChainCheckpointStorer].
+    pub async fn get_certificate_chain_checkpoint_storer(
+        &mut self,
+    ) -> Result<Arc<dyn CertificateChainCheckpointStorer>> {
+        if self.certificate_chain_checkpoint_storer.is_none() {
+            self.certificate_chain_checkpoint_storer =
+                Some(self.build_certificate_chain_checkpoint_storer().await?);
+        }
+
+        Ok(self
+            .certificate_chain_checkpoint_storer
+            .as_ref()
+            .cloned()
+            .unwrap())
+    }
+
+    async fn build_signer_metadata_storer(&mut self) -> Result<Arc<dyn SignerMetadataStorer>> {
+        Ok(Arc::new(SignerMetadataRepository::new(
+            self.get_sqlite_connection().await?,
+        )))
+    }
+
+    /// Get a configured [SignerMetadataStorer].
+    pub async fn get_signer_metadata_storer(&mut self) -> Result<Arc<dyn SignerMetadataStorer>> {
+        if self.signer_metadata_storer.is_none() {
+            self.signer_metadata_storer = Some(self.build_signer_metadata_storer().await?);
+        }
+
+        Ok(self.signer_metadata_storer.as_ref().cloned().unwrap())
+    }
+
+    async fn build_signer_participation_storer(
+        &mut self,
+    ) -> Result<Arc<dyn SignerParticipationStorer>> {
+        Ok(Arc::new(SignerParticipationRepository::new(
             self.get_sqlite_connection().await?,
         )))
     }
 
+    /// Get a configured [SignerParticipationStorer].
+    pub async fn get_signer_participation_storer(
+        &mut self,
+    ) -> Result<Arc<dyn SignerParticipationStorer>> {
+        if self.signer_participation_storer.is_none() {
+            self.signer_participation_storer =
+                Some(self.build_signer_participation_storer().await?);
+        }
+
+        Ok(self.signer_participation_storer.as_ref().cloned().unwrap())
+    }
+
+    async fn build_verification_key_store(&mut self) -> Result<Arc<dyn VerificationKeyStorer>> {
+        let mut store = SignerRegistrationStore::new(self.get_sqlite_connection().await?);
+        if let Some(cipher) = self.configuration.build_store_cipher()? {
+            store = store.with_cipher(cipher);
+        }
+
+        Ok(Arc::new(store))
+    }
+
     /// Get a configured [VerificationKeyStorer].
     pub async fn get_verification_key_store(&mut self) -> Result<Arc<dyn VerificationKeyStorer>> {
         if self.verification_key_store.is_none() {
@@ -564,9 +1002,19 @@ impl DependenciesBuilder {
                     Some(cardano_cli_runner),
                 );
 
-                chain_observer_builder
+                let chain_observer = chain_observer_builder
                     .build()
-                    .with_context(|| "Dependencies Builder can not build chain observer")?
+                    .with_context(|| "Dependencies Builder can not build chain observer")?;
+
+                Arc::new(
+                    CachedChainObserver::new(chain_observer)
+                        .with_epoch_time_to_live(Duration::from_secs(
+                            self.configuration.chain_observer_epoch_cache_ttl,
+                        ))
+                        .with_chain_point_time_to_live(Duration::from_secs(
+                            self.configuration.chain_observer_chain_point_cache_ttl,
+                        )),
+                )
             }
             _ => Arc::new(FakeObserver::default()),
         };
@@ -738,8 +1186,11 @@ impl DependenciesBuilder {
             ExecutionEnvironment::Production => Some(self.get_immutable_cache_provider().await?),
             _ => None,
         };
-        let digester =
+        let mut digester =
             CardanoImmutableDigester::new(immutable_digester_cache, self.get_logger().await?);
+        if let Some(number_of_threads) = self.configuration.immutable_digester_threads {
+            digester = digester.with_thread_pool_size(number_of_threads)?;
+        }
 
         Ok(Arc::new(digester))
     }
@@ -770,11 +1221,14 @@ impl DependenciesBuilder {
                         .into(),
                 };
 
-                Arc::new(CompressedArchiveSnapshotter::new(
-                    self.configuration.db_directory.clone(),
-                    ongoing_snapshot_directory,
-                    algorithm,
-                )?)
+                Arc::new(
+                    CompressedArchiveSnapshotter::new(
+                        self.configuration.db_directory.clone(),
+                        ongoing_snapshot_directory,
+                        algorithm,
+                    )?
+                    .with_separate_ancillary_archive(self.configuration.snapshot_ancillary_archive),
+                )
             }
             _ => Arc::new(DumbSnapshotter::new()),
         };
@@ -844,6 +1298,9 @@ impl DependenciesBuilder {
             self.get_chain_observer().await?,
             self.get_verification_key_store().await?,
             self.get_signer_store().await?,
+            Arc::new(SignerUnregistrationRepository::new(
+                self.get_sqlite_connection().await?,
+            )),
             self.configuration.safe_epoch_retention_limit(),
         );
 
@@ -908,15 +1365,21 @@ impl DependenciesBuilder {
                 message: "Error while building EraChecker".to_string(),
                 error: Some(e.into()),
             })?;
+        let current_era = era_epoch_token.get_current_supported_era().map_err(|e| {
+            DependenciesBuilderError::Initialization {
+                message: "Error while building EraChecker".to_string(),
+                error: Some(e),
+            }
+        })?;
         let era_checker = Arc::new(EraChecker::new(
-            era_epoch_token.get_current_supported_era().map_err(|e| {
-                DependenciesBuilderError::Initialization {
-                    message: "Error while building EraChecker".to_string(),
-                    error: Some(e),
-                }
-            })?,
+            current_era,
             era_epoch_token.get_current_epoch(),
         ));
+        era_checker.change_era_with_transition(
+            current_era,
+            era_epoch_token.get_current_epoch(),
+            era_epoch_token.get_transition_supported_era(),
+        );
 
         Ok(era_checker)
     }
@@ -991,6 +1454,45 @@ impl DependenciesBuilder {
         Ok(self.event_transmitter.as_ref().cloned().unwrap())
     }
 
+    async fn build_event_reader(&mut self) -> Result<Arc<dyn EventReader>> {
+        let event_reader = Arc::new(EventPersister::new(
+            self.get_event_store_sqlite_connection().await?,
+        ));
+
+        Ok(event_reader)
+    }
+
+    /// [EventReader] service
+    pub async fn get_event_reader(&mut self) -> Result<Arc<dyn EventReader>> {
+        if self.event_reader.is_none() {
+            self.event_reader = Some(self.build_event_reader().await?);
+        }
+
+        Ok(self.event_reader.as_ref().cloned().unwrap())
+    }
+
+    async fn build_log_levels(&mut self) -> Result<ComponentLogLevels> {
+        let log_levels = ComponentLogLevels::new(Level::Info);
+        log_levels.set_component_levels(&self.configuration.list_component_log_levels().map_err(
+            |e| DependenciesBuilderError::Initialization {
+                message: "Could not parse the component log levels".to_string(),
+                error: Some(e),
+            },
+        )?);
+
+        Ok(log_levels)
+    }
+
+    /// [ComponentLogLevels] handle, the one used by the logger of the aggregator should be set
+    /// beforehand, else a handle that is not used by any logger is built.
+    pub async fn get_log_levels(&mut self) -> Result<ComponentLogLevels> {
+        if self.log_levels.is_none() {
+            self.log_levels = Some(self.build_log_levels().await?);
+        }
+
+        Ok(self.log_levels.as_ref().cloned().unwrap())
+    }
+
     async fn build_api_version_provider(&mut self) -> Result<Arc<APIVersionProvider>> {
         let api_version_provider = Arc::new(APIVersionProvider::new(self.get_era_checker().await?));
 
@@ -1054,14 +1556,17 @@ impl DependenciesBuilder {
             &self.configuration.db_directory,
             self.get_logger().await?,
         ));
-        let transactions_importer = Arc::new(CardanoTransactionsImporter::new(
-            self.get_block_scanner().await?,
-            self.get_transaction_store().await?,
-            &self.configuration.db_directory,
-            // Rescan the last immutable when importing transactions, it may have been partially imported
-            Some(1),
-            self.get_logger().await?,
-        ));
+        let transactions_importer = Arc::new(
+            CardanoTransactionsImporter::new(
+                self.get_block_scanner().await?,
+                self.get_transaction_store().await?,
+                &self.configuration.db_directory,
+                // Rescan the last immutable when importing transactions, it may have been partially imported
+                Some(1),
+                self.get_logger().await?,
+            )
+            .with_prover_cache(self.get_prover_cache().await?),
+        );
         let block_range_root_retriever = self.get_transaction_repository().await?;
         let cardano_transactions_builder = Arc::new(CardanoTransactionsSignableBuilder::new(
             transactions_importer,
@@ -1106,12 +1611,35 @@ impl DependenciesBuilder {
             ));
         let cardano_transactions_artifact_builder =
             Arc::new(CardanoTransactionsArtifactBuilder::new());
-        let signed_entity_service = Arc::new(MithrilSignedEntityService::new(
-            signed_entity_storer,
-            mithril_stake_distribution_artifact_builder,
-            cardano_immutable_files_full_artifact_builder,
-            cardano_transactions_artifact_builder,
+        // The lease is renewed during the creation of an artifact, it can not be instantaneous
+        let signed_entity_lease_duration = chrono::Duration::from_std(Duration::from_secs(
+            self.configuration.signed_entity_lease_duration.max(1),
+        ))
+        .map_err(|e| DependenciesBuilderError::Initialization {
+            message: format!(
+                "Could not use configuration setting 'signed_entity_lease_duration' value '{}' as a duration.",
+                self.configuration.signed_entity_lease_duration
+            ),
+            error: Some(e.into()),
+        })?;
+        // The aggregator is identified by its URL, so that once restarted it takes back the
+        // leases it did not release instead of waiting for them to be stale
+        let signed_entity_type_lock = Arc::new(SignedEntityTypeLock::new().with_lease_storer(
+            Arc::new(SignedEntityLeaseRepository::new(
+                self.get_sqlite_connection().await?,
+            )),
+            self.configuration.get_server_url(),
+            signed_entity_lease_duration,
         ));
+        let signed_entity_service = Arc::new(
+            MithrilSignedEntityService::new(
+                signed_entity_storer,
+                mithril_stake_distribution_artifact_builder,
+                cardano_immutable_files_full_artifact_builder,
+                cardano_transactions_artifact_builder,
+            )
+            .with_signed_entity_type_lock(signed_entity_type_lock),
+        );
 
         Ok(signed_entity_service)
     }
@@ -1128,12 +1656,25 @@ impl DependenciesBuilder {
     async fn build_epoch_service(&mut self) -> Result<EpochServiceWrapper> {
         let verification_key_store = self.get_verification_key_store().await?;
         let protocol_parameters_store = self.get_protocol_parameters_store().await?;
+        let era_checker = self.get_era_checker().await?;
 
-        let epoch_service = Arc::new(RwLock::new(MithrilEpochService::new(
-            self.configuration.protocol_parameters.clone(),
-            protocol_parameters_store,
-            verification_key_store,
-        )));
+        let protocol_feature_activations =
+            self.configuration
+                .list_protocol_feature_activations()
+                .map_err(|e| DependenciesBuilderError::Initialization {
+                    message: "Could not parse the protocol feature activations".to_string(),
+                    error: Some(e),
+                })?;
+
+        let epoch_service = Arc::new(RwLock::new(
+            MithrilEpochService::new(
+                self.configuration.protocol_parameters.clone(),
+                protocol_parameters_store,
+                verification_key_store,
+                era_checker,
+            )
+            .with_protocol_feature_activations(protocol_feature_activations),
+        ));
 
         Ok(epoch_service)
     }
@@ -1170,11 +1711,18 @@ impl DependenciesBuilder {
             sqlite_connection: self.get_sqlite_connection().await?,
             sqlite_connection_transaction: self.get_sqlite_connection_cardano_transaction().await?,
             stake_store: self.get_stake_store().await?,
+            artifact_storage: self.get_artifact_storage().await?,
             snapshot_uploader: self.get_snapshot_uploader().await?,
             multi_signer: self.get_multi_signer().await?,
             certificate_pending_store: self.get_certificate_pending_store().await?,
             certificate_repository: self.get_certificate_repository().await?,
             open_message_repository: self.get_open_message_repository().await?,
+            runtime_decision_storer: self.get_runtime_decision_storer().await?,
+            certificate_chain_checkpoint_storer: self
+                .get_certificate_chain_checkpoint_storer()
+                .await?,
+            signer_metadata_storer: self.get_signer_metadata_storer().await?,
+            signer_participation_storer: self.get_signer_participation_storer().await?,
             verification_key_store: self.get_verification_key_store().await?,
             protocol_parameters_store: self.get_protocol_parameters_store().await?,
             chain_observer: self.get_chain_observer().await?,
@@ -1189,6 +1737,8 @@ impl DependenciesBuilder {
             era_checker: self.get_era_checker().await?,
             era_reader: self.get_era_reader().await?,
             event_transmitter: self.get_event_transmitter().await?,
+            event_reader: self.get_event_reader().await?,
+            log_levels: self.get_log_levels().await?,
             api_version_provider: self.get_api_version_provider().await?,
             stake_distribution_service: self.get_stake_distribution_service().await?,
             signer_recorder: self.get_signer_store().await?,
@@ -1203,6 +1753,19 @@ impl DependenciesBuilder {
             block_scanner: self.get_block_scanner().await?,
             transaction_store: self.get_transaction_store().await?,
             prover_service: self.get_prover_service().await?,
+            proof_budget_service: self.get_proof_budget_service().await?,
+            quorum_simulation_service: self.get_quorum_simulation_service().await?,
+            status_service: self.get_status_service().await?,
+            metrics_service: self.get_metrics_service().await?,
+            pruning_service: self.get_pruning_service().await?,
+            stale_snapshot_detector: self.get_stale_snapshot_detector().await?,
+            immutable_file_lag_watchdog: self.get_immutable_file_lag_watchdog().await?,
+            signature_authenticator: self.get_signature_authenticator().await?,
+            file_digest_cache: self.get_file_digest_cache().await?,
+            snapshot_manifest_cache: self.get_snapshot_manifest_cache().await?,
+            runtime_cycle_trigger: self.get_runtime_cycle_trigger().await?,
+            snapshot_upload_schedule: self.get_snapshot_upload_schedule().await?,
+            configuration_reloader: self.get_configuration_reloader().await?,
         };
 
         Ok(dependency_manager)
@@ -1210,9 +1773,34 @@ impl DependenciesBuilder {
 
     /// Create dependencies for the [EventStore] task.
     pub async fn create_event_store(&mut self) -> Result<EventStore> {
-        let event_store = EventStore::new(self.get_event_transmitter_receiver().await?);
+        let event_store = EventStore::new(
+            self.get_event_transmitter_receiver().await?,
+            self.get_event_store_sqlite_connection().await?,
+        );
+        let mut event_sinks: Vec<Arc<dyn EventSink>> = vec![];
+        if let Some(url) = &self.configuration.event_sink_webhook_url {
+            event_sinks.push(Arc::new(WebhookEventSink::new(url).map_err(|e| {
+                DependenciesBuilderError::Initialization {
+                    message: "Could not build the event webhook sink".to_string(),
+                    error: Some(e),
+                }
+            })?));
+        }
+        if let Some(path) = &self.configuration.event_sink_ndjson_file {
+            event_sinks.push(Arc::new(NdjsonFileEventSink::new(path.clone())));
+        }
+        if event_sinks.is_empty() {
+            return Ok(event_store);
+        }
 
-        Ok(event_store)
+        let event_forwarder = EventForwarder::new(
+            event_sinks,
+            self.get_event_store_sqlite_connection().await?,
+            self.configuration.event_sink_max_retries,
+            Duration::from_millis(self.configuration.event_sink_retry_delay),
+        );
+
+        Ok(event_store.with_forwarder(event_forwarder))
     }
 
     /// Create the AggregatorRunner
@@ -1225,6 +1813,7 @@ impl DependenciesBuilder {
                 "Dependencies Builder can not get Cardano network while creating aggregator runner"
             })?,
         );
+        let runtime_cycle_trigger = dependency_container.runtime_cycle_trigger.clone();
         let runtime = AggregatorRuntime::new(
             config,
             None,
@@ -1234,7 +1823,8 @@ impl DependenciesBuilder {
         .map_err(|e| DependenciesBuilderError::Initialization {
             message: "Cannot initialize Aggregator runtime.".to_string(),
             error: Some(e.into()),
-        })?;
+        })?
+        .with_cycle_trigger(runtime_cycle_trigger);
 
         Ok(runtime)
     }
@@ -1262,6 +1852,7 @@ impl DependenciesBuilder {
             genesis_verifier: self.get_genesis_verifier().await?,
             protocol_parameters_store: self.get_protocol_parameters_store().await?,
             verification_key_store: self.get_verification_key_store().await?,
+            era_checker: self.get_era_checker().await?,
         };
 
         Ok(dependencies)
@@ -1312,26 +1903,104 @@ impl DependenciesBuilder {
         let single_signature_repository = Arc::new(SingleSignatureRepository::new(
             self.get_sqlite_connection().await?,
         ));
+        let buffered_single_signature_repository = Arc::new(
+            BufferedSingleSignatureRepository::new(self.get_sqlite_connection().await?),
+        );
         let certificate_repository = self.get_certificate_repository().await?;
+        let certification_unit_of_work = Arc::new(CertificationUnitOfWork::new(
+            self.get_certifier_sqlite_connection().await?,
+        ));
         let certificate_verifier = self.get_certificate_verifier().await?;
         let genesis_verifier = self.get_genesis_verifier().await?;
         let multi_signer = self.get_multi_signer().await?;
         let ticker_service = self.get_ticker_service().await?;
         let epoch_service = self.get_epoch_service().await?;
+        let era_checker = self.get_era_checker().await?;
         let logger = self.get_logger().await?;
+        let certificate_chain_checkpoint_storer =
+            self.get_certificate_chain_checkpoint_storer().await?;
+        let certificate_chain_checkpoint_policy =
+            self.build_certificate_chain_checkpoint_policy()?;
+        let reopening_policy = self.get_open_message_reopening_policy().await?;
+        let certification_grace_periods = self
+            .configuration
+            .list_certification_grace_periods()
+            .map_err(|e| DependenciesBuilderError::Initialization {
+                message: "Could not parse the certification grace periods".to_string(),
+                error: Some(e),
+            })?;
 
-        Ok(Arc::new(MithrilCertifierService::new(
+        let certifier_service = MithrilCertifierService::new(
             cardano_network,
             open_message_repository,
             single_signature_repository,
+            buffered_single_signature_repository,
             certificate_repository,
+            certification_unit_of_work,
             certificate_verifier,
             genesis_verifier,
             multi_signer,
             ticker_service,
             epoch_service,
+            era_checker,
             logger,
-        )))
+        )
+        .with_clock_skew_tolerance(Duration::from_secs(self.configuration.clock_skew_tolerance))
+        .with_certification_grace_periods(certification_grace_periods)
+        .with_certificate_chain_checkpoints(
+            certificate_chain_checkpoint_storer,
+            certificate_chain_checkpoint_policy,
+        )
+        .with_shared_reopening_policy(reopening_policy);
+
+        Ok(Arc::new(certifier_service))
+    }
+
+    fn build_certificate_chain_checkpoint_policy(
+        &self,
+    ) -> Result<CertificateChainCheckpointPolicy> {
+        let signer = match &self
+            .configuration
+            .certificate_chain_checkpoint_secret_key_path
+        {
+            Some(secret_key_path) => Some(Arc::new(
+                GenesisTools::read_genesis_signer(secret_key_path).map_err(|e| {
+                    DependenciesBuilderError::Initialization {
+                        message: "Could not read the certificate chain checkpoint secret key"
+                            .to_string(),
+                        error: Some(e),
+                    }
+                })?,
+            )),
+            None => None,
+        };
+        let trusted_verification_key = match (
+            self.configuration.trust_certificate_chain_checkpoints,
+            &self.configuration.certificate_chain_checkpoint_verification_key,
+        ) {
+            (false, _) => None,
+            (true, Some(verification_key)) => Some(
+                ProtocolGenesisVerificationKey::from_json_hex(verification_key).map_err(|e| {
+                    DependenciesBuilderError::Initialization {
+                        message: format!(
+                            "Could not decode the certificate chain checkpoint verification key: '{verification_key}'"
+                        ),
+                        error: Some(e),
+                    }
+                })?,
+            ),
+            (true, None) => {
+                return Err(DependenciesBuilderError::MissingConfiguration(
+                    "certificate_chain_checkpoint_verification_key".to_string(),
+                ))
+            }
+        };
+
+        Ok(CertificateChainCheckpointPolicy {
+            interval: self.configuration.certificate_chain_checkpoint_interval,
+            signer,
+            trusted_verification_key,
+        })
     }
 
     /// [CertifierService] service
@@ -1345,11 +2014,16 @@ impl DependenciesBuilder {
 
     /// build HTTP message service
     pub async fn build_message_service(&mut self) -> Result<Arc<dyn MessageService>> {
-        let certificate_repository = Arc::new(CertificateRepository::new(
-            self.get_sqlite_connection().await?,
-        ));
-        let signed_entity_storer = self.get_signed_entity_storer().await?;
-        let service = MithrilMessageService::new(certificate_repository, signed_entity_storer);
+        let read_connection = self.get_read_sqlite_connection().await?;
+        let certificate_repository = Arc::new(CertificateRepository::new(read_connection.clone()));
+        let signed_entity_storer = Arc::new(SignedEntityStore::new(read_connection));
+        let mut service = MithrilMessageService::new(certificate_repository, signed_entity_storer);
+        if self.configuration.snapshot_signed_url_expiration.is_some() {
+            service = service.with_snapshot_signed_download_base_url(format!(
+                "{}{SERVER_BASE_PATH}",
+                self.configuration.get_server_url()
+            ));
+        }
 
         Ok(Arc::new(service))
     }
@@ -1367,7 +2041,11 @@ impl DependenciesBuilder {
     pub async fn build_prover_service(&mut self) -> Result<Arc<dyn ProverService>> {
         let transaction_retriever = self.get_transaction_repository().await?;
         let block_range_root_retriever = self.get_transaction_repository().await?;
-        let service = MithrilProverService::new(transaction_retriever, block_range_root_retriever);
+        let service = MithrilProverService::new(
+            transaction_retriever,
+            block_range_root_retriever,
+            self.get_prover_cache().await?,
+        );
 
         Ok(Arc::new(service))
     }
@@ -1381,6 +2059,328 @@ impl DependenciesBuilder {
         Ok(self.prover_service.as_ref().cloned().unwrap())
     }
 
+    async fn build_prover_cache(&mut self) -> Result<Arc<ProverCache>> {
+        let prover_cache = ProverCache::new(
+            self.configuration.prover_cache_capacity,
+            self.get_metrics_service().await?,
+        );
+
+        Ok(Arc::new(prover_cache))
+    }
+
+    /// [ProverCache] shared by the prover service and the transactions importer
+    pub async fn get_prover_cache(&mut self) -> Result<Arc<ProverCache>> {
+        if self.prover_cache.is_none() {
+            self.prover_cache = Some(self.build_prover_cache().await?);
+        }
+
+        Ok(self.prover_cache.as_ref().cloned().unwrap())
+    }
+
+    /// build Proof budget service
+    pub async fn build_proof_budget_service(&mut self) -> Result<Arc<dyn ProofBudgetService>> {
+        let transaction_retriever = self.get_transaction_repository().await?;
+        let service = MithrilProofBudgetService::new(
+            transaction_retriever,
+            self.configuration.proof_max_request_cost,
+            self.configuration.proof_client_budget,
+            Duration::from_secs(self.configuration.proof_client_budget_window),
+        );
+
+        Ok(Arc::new(service))
+    }
+
+    /// [ProofBudgetService] service
+    pub async fn get_proof_budget_service(&mut self) -> Result<Arc<dyn ProofBudgetService>> {
+        if self.proof_budget_service.is_none() {
+            self.proof_budget_service = Some(self.build_proof_budget_service().await?);
+        }
+
+        Ok(self.proof_budget_service.as_ref().cloned().unwrap())
+    }
+
+    /// build Quorum simulation service
+    pub async fn build_quorum_simulation_service(
+        &mut self,
+    ) -> Result<Arc<dyn QuorumSimulationService>> {
+        let service = MithrilQuorumSimulationService::new(self.get_epoch_service().await?);
+
+        Ok(Arc::new(service))
+    }
+
+    /// [QuorumSimulationService] service
+    pub async fn get_quorum_simulation_service(
+        &mut self,
+    ) -> Result<Arc<dyn QuorumSimulationService>> {
+        if self.quorum_simulation_service.is_none() {
+            self.quorum_simulation_service = Some(self.build_quorum_simulation_service().await?);
+        }
+
+        Ok(self.quorum_simulation_service.as_ref().cloned().unwrap())
+    }
+
+    /// build Status service
+    pub async fn build_status_service(&mut self) -> Result<Arc<dyn StatusService>> {
+        let database_version =
+            MithrilStatusService::read_database_version(&*self.get_sqlite_connection().await?)?;
+        let service = MithrilStatusService::new(
+            self.configuration.clone(),
+            self.get_configuration_reloader().await?,
+            self.get_epoch_service().await?,
+            self.get_era_checker().await?,
+            self.get_certificate_repository().await?,
+            database_version,
+        );
+
+        Ok(Arc::new(service))
+    }
+
+    /// [StatusService] service
+    pub async fn get_status_service(&mut self) -> Result<Arc<dyn StatusService>> {
+        if self.status_service.is_none() {
+            self.status_service = Some(self.build_status_service().await?);
+        }
+
+        Ok(self.status_service.as_ref().cloned().unwrap())
+    }
+
+    async fn build_metrics_service(&self) -> Result<Arc<MetricsService>> {
+        let metrics_service =
+            MetricsService::new().map_err(|e| DependenciesBuilderError::Initialization {
+                message: "Could not create the metrics service".to_string(),
+                error: Some(e),
+            })?;
+
+        Ok(Arc::new(metrics_service))
+    }
+
+    /// [MetricsService] service
+    pub async fn get_metrics_service(&mut self) -> Result<Arc<MetricsService>> {
+        if self.metrics_service.is_none() {
+            self.metrics_service = Some(self.build_metrics_service().await?);
+        }
+
+        Ok(self.metrics_service.as_ref().cloned().unwrap())
+    }
+
+    async fn build_query_instrumentation(&mut self) -> Result<Arc<QueryInstrumentation>> {
+        let slow_query_threshold = match self.configuration.database_slow_query_threshold {
+            0 => None,
+            threshold => Some(Duration::from_millis(threshold)),
+        };
+        let query_instrumentation =
+            QueryInstrumentation::new(slow_query_threshold, self.get_logger().await?)
+                .with_observer(self.get_metrics_service().await?);
+
+        Ok(Arc::new(query_instrumentation))
+    }
+
+    /// [QueryInstrumentation] of the SQLite connections of this aggregator, that logs the slow
+    /// queries and exports the duration and fetched rows of the queries to its [MetricsService].
+    pub async fn get_query_instrumentation(&mut self) -> Result<Arc<QueryInstrumentation>> {
+        if self.query_instrumentation.is_none() {
+            self.query_instrumentation = Some(self.build_query_instrumentation().await?);
+        }
+
+        Ok(self.query_instrumentation.as_ref().cloned().unwrap())
+    }
+
+    /// Create [PruningService] service
+    pub async fn build_pruning_service(&mut self) -> Result<Arc<dyn PruningService>> {
+        let retention_policy = RetentionPolicy::new(
+            self.configuration.open_message_retention_epochs,
+            self.configuration.single_signature_retention_epochs,
+        );
+        let single_signature_repository = Arc::new(SingleSignatureRepository::new(
+            self.get_sqlite_connection().await?,
+        ));
+        let service = MithrilPruningService::new(
+            retention_policy,
+            self.get_open_message_repository().await?,
+            single_signature_repository,
+            self.get_metrics_service().await?,
+        );
+
+        Ok(Arc::new(service))
+    }
+
+    /// [PruningService] service
+    pub async fn get_pruning_service(&mut self) -> Result<Arc<dyn PruningService>> {
+        if self.pruning_service.is_none() {
+            self.pruning_service = Some(self.build_pruning_service().await?);
+        }
+
+        Ok(self.pruning_service.as_ref().cloned().unwrap())
+    }
+
+    /// Create [StaleSnapshotDetector] service
+    pub async fn build_stale_snapshot_detector(
+        &mut self,
+    ) -> Result<Arc<dyn StaleSnapshotDetector>> {
+        let service = MithrilStaleSnapshotDetector::new(
+            self.configuration.stale_snapshot_cadence_multiple,
+            self.get_signed_entity_storer().await?,
+        );
+
+        Ok(Arc::new(service))
+    }
+
+    /// [StaleSnapshotDetector] service
+    pub async fn get_stale_snapshot_detector(&mut self) -> Result<Arc<dyn StaleSnapshotDetector>> {
+        if self.stale_snapshot_detector.is_none() {
+            self.stale_snapshot_detector = Some(self.build_stale_snapshot_detector().await?);
+        }
+
+        Ok(self.stale_snapshot_detector.as_ref().cloned().unwrap())
+    }
+
+    /// Create [ImmutableFileLagWatchdog] service
+    pub async fn build_immutable_file_lag_watchdog(
+        &mut self,
+    ) -> Result<Arc<dyn ImmutableFileLagWatchdog>> {
+        let service =
+            MithrilImmutableFileLagWatchdog::new(self.configuration.immutable_file_lag_threshold);
+
+        Ok(Arc::new(service))
+    }
+
+    /// [ImmutableFileLagWatchdog] service
+    pub async fn get_immutable_file_lag_watchdog(
+        &mut self,
+    ) -> Result<Arc<dyn ImmutableFileLagWatchdog>> {
+        if self.immutable_file_lag_watchdog.is_none() {
+            self.immutable_file_lag_watchdog =
+                Some(self.build_immutable_file_lag_watchdog().await?);
+        }
+
+        Ok(self.immutable_file_lag_watchdog.as_ref().cloned().unwrap())
+    }
+
+    /// Create [SignatureAuthenticator] service
+    pub async fn build_signature_authenticator(
+        &mut self,
+    ) -> Result<Arc<dyn SignatureAuthenticator>> {
+        let service = MithrilSignatureAuthenticator::new(
+            self.configuration.signature_registration_authentication,
+            self.get_epoch_service().await?,
+            self.get_chain_observer().await?,
+        );
+
+        Ok(Arc::new(service))
+    }
+
+    /// [SignatureAuthenticator] service
+    pub async fn get_signature_authenticator(&mut self) -> Result<Arc<dyn SignatureAuthenticator>> {
+        if self.signature_authenticator.is_none() {
+            self.signature_authenticator = Some(self.build_signature_authenticator().await?);
+        }
+
+        Ok(self.signature_authenticator.as_ref().cloned().unwrap())
+    }
+
+    /// [FileDigestCache] of the served snapshot archives
+    pub async fn get_file_digest_cache(&mut self) -> Result<Arc<FileDigestCache>> {
+        if self.file_digest_cache.is_none() {
+            self.file_digest_cache = Some(Arc::new(FileDigestCache::new()));
+        }
+
+        Ok(self.file_digest_cache.as_ref().cloned().unwrap())
+    }
+
+    /// [SnapshotManifestCache] of the served snapshot manifests
+    pub async fn get_snapshot_manifest_cache(&mut self) -> Result<Arc<SnapshotManifestCache>> {
+        if self.snapshot_manifest_cache.is_none() {
+            self.snapshot_manifest_cache = Some(Arc::new(SnapshotManifestCache::new(
+                &self
+                    .configuration
+                    .snapshot_directory
+                    .join(MANIFESTS_DIRECTORY),
+            )));
+        }
+
+        Ok(self.snapshot_manifest_cache.as_ref().cloned().unwrap())
+    }
+
+    /// [RuntimeCycleTrigger] shared by the runtime and the admin routes
+    pub async fn get_runtime_cycle_trigger(&mut self) -> Result<Arc<RuntimeCycleTrigger>> {
+        if self.runtime_cycle_trigger.is_none() {
+            self.runtime_cycle_trigger = Some(Arc::new(RuntimeCycleTrigger::new()));
+        }
+
+        Ok(self.runtime_cycle_trigger.as_ref().cloned().unwrap())
+    }
+
+    /// [SharedOpenMessageReopeningPolicy] shared by the certifier and the configuration reloader
+    pub async fn get_open_message_reopening_policy(
+        &mut self,
+    ) -> Result<SharedOpenMessageReopeningPolicy> {
+        if self.open_message_reopening_policy.is_none() {
+            let reopening_policy = ReloadableSettings::from_configuration(&self.configuration)
+                .open_message_reopening_policy();
+            self.open_message_reopening_policy =
+                Some(Arc::new(std::sync::RwLock::new(reopening_policy)));
+        }
+
+        Ok(self
+            .open_message_reopening_policy
+            .as_ref()
+            .cloned()
+            .unwrap())
+    }
+
+    /// [BandwidthLimiter] shared by the snapshot uploads and the configuration reloader
+    pub async fn get_snapshot_upload_bandwidth_limiter(&mut self) -> Result<Arc<BandwidthLimiter>> {
+        if self.snapshot_upload_bandwidth_limiter.is_none() {
+            let bandwidth_limiter = BandwidthLimiter::unlimited();
+            bandwidth_limiter
+                .set_bytes_per_second(self.configuration.snapshot_upload_max_bandwidth);
+            self.snapshot_upload_bandwidth_limiter = Some(Arc::new(bandwidth_limiter));
+        }
+
+        Ok(self
+            .snapshot_upload_bandwidth_limiter
+            .as_ref()
+            .cloned()
+            .unwrap())
+    }
+
+    /// [SharedUploadSchedule] shared by the snapshot uploader, the runtime and the configuration
+    /// reloader, the aggregator does not start if the upload windows are invalid
+    pub async fn get_snapshot_upload_schedule(&mut self) -> Result<SharedUploadSchedule> {
+        if self.snapshot_upload_schedule.is_none() {
+            let upload_schedule = self.configuration.snapshot_upload_schedule().map_err(|e| {
+                DependenciesBuilderError::Initialization {
+                    message: "Invalid snapshot upload windows".to_string(),
+                    error: Some(e),
+                }
+            })?;
+            self.snapshot_upload_schedule = Some(Arc::new(std::sync::RwLock::new(upload_schedule)));
+        }
+
+        Ok(self.snapshot_upload_schedule.as_ref().cloned().unwrap())
+    }
+
+    async fn build_configuration_reloader(&mut self) -> Result<Arc<ConfigurationReloader>> {
+        let configuration_reloader = ConfigurationReloader::new(
+            &self.configuration,
+            self.get_log_levels().await?,
+            self.get_open_message_reopening_policy().await?,
+            self.get_snapshot_upload_bandwidth_limiter().await?,
+            self.get_snapshot_upload_schedule().await?,
+        );
+
+        Ok(Arc::new(configuration_reloader))
+    }
+
+    /// [ConfigurationReloader] service
+    pub async fn get_configuration_reloader(&mut self) -> Result<Arc<ConfigurationReloader>> {
+        if self.configuration_reloader.is_none() {
+            self.configuration_reloader = Some(self.build_configuration_reloader().await?);
+        }
+
+        Ok(self.configuration_reloader.as_ref().cloned().unwrap())
+    }
+
     /// Remove the dependencies builder from memory to release Arc instances.
     pub async fn vanish(self) {
         self.drop_sqlite_connections().await;
@@ -10,6 +10,7 @@ use mithril_common::{
     digesters::{ImmutableDigester, ImmutableFileObserver},
     entities::{Epoch, ProtocolParameters, SignerWithStake, StakeDistribution},
     era::{EraChecker, EraReader},
+    logging::ComponentLogLevels,
     signable_builder::SignableBuilderService,
     test_utils::MithrilFixture,
     TimePointProvider,
@@ -17,21 +18,26 @@ use mithril_common::{
 use mithril_persistence::{sqlite::SqliteConnection, store::StakeStorer};
 
 use crate::{
+    artifact_storage::ArtifactStorage,
     configuration::*,
     database::repository::{
-        CertificateRepository, OpenMessageRepository, SignedEntityStorer, SignerGetter,
-        StakePoolStore,
+        CertificateChainCheckpointStorer, CertificateRepository, OpenMessageRepository,
+        RuntimeDecisionStorer, SignedEntityStorer, SignerGetter, SignerMetadataStorer,
+        SignerParticipationStorer, StakePoolStore,
     },
-    event_store::{EventMessage, TransmitterService},
+    event_store::{EventMessage, EventReader, TransmitterService},
     multi_signer::MultiSigner,
     services::{
-        CertifierService, EpochService, MessageService, ProverService, SignedEntityService,
-        StakeDistributionService, TickerService, TransactionStore,
+        CertifierService, ConfigurationReloader, EpochService, ImmutableFileLagWatchdog,
+        MessageService, ProofBudgetService, ProverService, PruningService, QuorumSimulationService,
+        SignatureAuthenticator, SignedEntityService, StakeDistributionService,
+        StaleSnapshotDetector, StatusService, TickerService, TransactionStore,
     },
     signer_registerer::SignerRecorder,
-    snapshot_uploaders::SnapshotUploader,
-    CertificatePendingStore, ProtocolParametersStorer, SignerRegisterer,
-    SignerRegistrationRoundOpener, Snapshotter, VerificationKeyStorer,
+    snapshot_uploaders::{SharedUploadSchedule, SnapshotUploader},
+    tools::{FileDigestCache, SnapshotManifestCache},
+    CertificatePendingStore, MetricsService, ProtocolParametersStorer, RuntimeCycleTrigger,
+    SignerRegisterer, SignerRegistrationRoundOpener, Snapshotter, VerificationKeyStorer,
 };
 
 /// MultiSignerWrapper wraps a [MultiSigner]
@@ -57,6 +63,9 @@ pub struct DependencyContainer {
     /// It shall be a private dependency.
     pub stake_store: Arc<StakePoolStore>,
 
+    /// Artifact storage service.
+    pub artifact_storage: Arc<dyn ArtifactStorage>,
+
     /// Snapshot uploader service.
     pub snapshot_uploader: Arc<dyn SnapshotUploader>,
 
@@ -72,6 +81,18 @@ pub struct DependencyContainer {
     /// Open message store.
     pub open_message_repository: Arc<OpenMessageRepository>,
 
+    /// Runtime decision store.
+    pub runtime_decision_storer: Arc<dyn RuntimeDecisionStorer>,
+
+    /// Certificate chain checkpoint store.
+    pub certificate_chain_checkpoint_storer: Arc<dyn CertificateChainCheckpointStorer>,
+
+    /// Signer metadata store.
+    pub signer_metadata_storer: Arc<dyn SignerMetadataStorer>,
+
+    /// Signer participation store.
+    pub signer_participation_storer: Arc<dyn SignerParticipationStorer>,
+
     /// Verification key store.
     pub verification_key_store: Arc<dyn VerificationKeyStorer>,
 
@@ -120,6 +141,12 @@ pub struct DependencyContainer {
     /// Event Transmitter Service
     pub event_transmitter: Arc<TransmitterService<EventMessage>>,
 
+    /// Event Reader Service
+    pub event_reader: Arc<dyn EventReader>,
+
+    /// Log levels of the components of the aggregator
+    pub log_levels: ComponentLogLevels,
+
     /// API Version provider
     pub api_version_provider: Arc<APIVersionProvider>,
 
@@ -155,6 +182,45 @@ pub struct DependencyContainer {
 
     /// Prover service
     pub prover_service: Arc<dyn ProverService>,
+
+    /// Proof budget service
+    pub proof_budget_service: Arc<dyn ProofBudgetService>,
+
+    /// Quorum simulation service
+    pub quorum_simulation_service: Arc<dyn QuorumSimulationService>,
+
+    /// Status service
+    pub status_service: Arc<dyn StatusService>,
+
+    /// Metrics service
+    pub metrics_service: Arc<MetricsService>,
+
+    /// Pruning service
+    pub pruning_service: Arc<dyn PruningService>,
+
+    /// Stale snapshot detector
+    pub stale_snapshot_detector: Arc<dyn StaleSnapshotDetector>,
+
+    /// Immutable file lag watchdog
+    pub immutable_file_lag_watchdog: Arc<dyn ImmutableFileLagWatchdog>,
+
+    /// Signature authenticator
+    pub signature_authenticator: Arc<dyn SignatureAuthenticator>,
+
+    /// Digests of the served snapshot archives
+    pub file_digest_cache: Arc<FileDigestCache>,
+
+    /// Objects of the served snapshot manifests
+    pub snapshot_manifest_cache: Arc<SnapshotManifestCache>,
+
+    /// Trigger of the cycles of the runtime
+    pub runtime_cycle_trigger: Arc<RuntimeCycleTrigger>,
+
+    /// Schedule of the snapshot uploads
+    pub snapshot_upload_schedule: SharedUploadSchedule,
+
+    /// Reloader of the settings that can be changed without a restart
+    pub configuration_reloader: Arc<ConfigurationReloader>,
 }
 
 #[doc(hidden)]
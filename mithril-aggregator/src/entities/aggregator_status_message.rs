@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use mithril_common::entities::{Epoch, SignedEntityTypeDiscriminants};
+use mithril_common::era::SupportedEra;
+use mithril_persistence::database::DbVersion;
+
+/// Message structure of the status of the aggregator, for operators dashboards and explorers
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AggregatorStatusMessage {
+    /// Version of the aggregator node
+    pub aggregator_node_version: String,
+
+    /// Current supported era
+    pub supported_era: SupportedEra,
+
+    /// Current epoch
+    pub epoch: Epoch,
+
+    /// Hash of the latest certificate, `None` if no certificate was created yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_certificate_hash: Option<String>,
+
+    /// Signed entity types enabled on the aggregator
+    pub signed_entity_types: BTreeSet<SignedEntityTypeDiscriminants>,
+
+    /// Version of the structure of the database, the latest migration applied
+    pub database_version: DbVersion,
+
+    /// Date and time when the aggregator was started
+    pub started_at: DateTime<Utc>,
+
+    /// Time elapsed since the aggregator was started, in seconds
+    pub uptime_seconds: u64,
+}
@@ -0,0 +1,182 @@
+use chrono::{DateTime, Duration, Utc};
+use mithril_common::entities::{SignedEntityType, SignedEntityTypeDiscriminants};
+use serde::{Deserialize, Serialize};
+
+use crate::database::record::SignedEntityRecord;
+
+/// Message structure of the estimates of the next certification of each signed entity type
+pub type BeaconEstimateListMessage = Vec<BeaconEstimateListItemMessage>;
+
+/// Message structure of the estimate of the next certification of a signed entity type
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BeaconEstimateListItemMessage {
+    /// Signed entity type
+    pub signed_entity_type: SignedEntityTypeDiscriminants,
+
+    /// Date and time of the last certification of the signed entity type, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_certified_at: Option<DateTime<Utc>>,
+
+    /// Average duration, in seconds, between two consecutive beacons of the signed entity type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beacon_interval_seconds: Option<i64>,
+
+    /// Estimated date and time of the next certification of the signed entity type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_expected_at: Option<DateTime<Utc>>,
+}
+
+impl BeaconEstimateListItemMessage {
+    /// Estimate the next certification of a signed entity type from its latest signed entities.
+    ///
+    /// The interval between two beacons is the time elapsed between the oldest and the newest
+    /// given signed entities divided by the number of beacons between them, so beacons skipped
+    /// by the aggregator are accounted for: an immutable file for the Cardano database and
+    /// Cardano transactions, an epoch for the stake distributions.
+    pub fn estimate(
+        signed_entity_type: SignedEntityTypeDiscriminants,
+        signed_entities: &[SignedEntityRecord],
+    ) -> Self {
+        let newest = signed_entities.iter().max_by_key(|s| s.created_at);
+        let oldest = signed_entities.iter().min_by_key(|s| s.created_at);
+        let beacon_interval = match (oldest, newest) {
+            (Some(oldest), Some(newest)) => {
+                let beacons = Self::beacon_position(&newest.signed_entity_type)
+                    .saturating_sub(Self::beacon_position(&oldest.signed_entity_type));
+                let elapsed = newest.created_at - oldest.created_at;
+
+                (beacons > 0 && elapsed > Duration::zero()).then(|| elapsed / beacons as i32)
+            }
+            _ => None,
+        };
+        let last_certified_at = newest.map(|s| s.created_at);
+
+        Self {
+            signed_entity_type,
+            last_certified_at,
+            beacon_interval_seconds: beacon_interval.map(|interval| interval.num_seconds()),
+            next_expected_at: last_certified_at
+                .zip(beacon_interval)
+                .map(|(last_certified_at, interval)| last_certified_at + interval),
+        }
+    }
+
+    fn beacon_position(signed_entity_type: &SignedEntityType) -> u64 {
+        match signed_entity_type {
+            SignedEntityType::MithrilStakeDistribution(epoch)
+            | SignedEntityType::CardanoStakeDistribution(epoch) => **epoch,
+            SignedEntityType::CardanoImmutableFilesFull(beacon)
+            | SignedEntityType::CardanoTransactions(beacon) => beacon.immutable_file_number,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::{CardanoDbBeacon, Epoch};
+
+    use super::*;
+
+    fn signed_entity(signed_entity_type: SignedEntityType, created_at: &str) -> SignedEntityRecord {
+        SignedEntityRecord {
+            signed_entity_id: "signed-entity-id".to_string(),
+            signed_entity_type,
+            certificate_id: "certificate-id".to_string(),
+            artifact: "".to_string(),
+            created_at: DateTime::parse_from_rfc3339(created_at)
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    fn immutable_files_full(immutable_file_number: u64) -> SignedEntityType {
+        SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::new(
+            "devnet",
+            1,
+            immutable_file_number,
+        ))
+    }
+
+    #[test]
+    fn no_estimate_without_signed_entities() {
+        let estimate = BeaconEstimateListItemMessage::estimate(
+            SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+            &[],
+        );
+
+        assert_eq!(
+            BeaconEstimateListItemMessage {
+                signed_entity_type: SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+                last_certified_at: None,
+                beacon_interval_seconds: None,
+                next_expected_at: None,
+            },
+            estimate
+        );
+    }
+
+    #[test]
+    fn no_interval_with_a_single_signed_entity() {
+        let estimate = BeaconEstimateListItemMessage::estimate(
+            SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+            &[signed_entity(
+                immutable_files_full(10),
+                "2024-05-01T10:00:00Z",
+            )],
+        );
+
+        assert!(estimate.last_certified_at.is_some());
+        assert_eq!(None, estimate.beacon_interval_seconds);
+        assert_eq!(None, estimate.next_expected_at);
+    }
+
+    #[test]
+    fn estimate_from_the_immutable_files_cadence() {
+        // 3 immutable files in 3 hours, the immutable file 12 was not certified
+        let estimate = BeaconEstimateListItemMessage::estimate(
+            SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+            &[
+                signed_entity(immutable_files_full(13), "2024-05-01T13:00:00Z"),
+                signed_entity(immutable_files_full(11), "2024-05-01T11:00:00Z"),
+                signed_entity(immutable_files_full(10), "2024-05-01T10:00:00Z"),
+            ],
+        );
+
+        assert_eq!(Some(3600), estimate.beacon_interval_seconds);
+        assert_eq!(
+            Some(
+                DateTime::parse_from_rfc3339("2024-05-01T14:00:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            ),
+            estimate.next_expected_at
+        );
+    }
+
+    #[test]
+    fn estimate_from_the_epochs_cadence() {
+        let estimate = BeaconEstimateListItemMessage::estimate(
+            SignedEntityTypeDiscriminants::MithrilStakeDistribution,
+            &[
+                signed_entity(
+                    SignedEntityType::MithrilStakeDistribution(Epoch(4)),
+                    "2024-05-01T00:00:00Z",
+                ),
+                signed_entity(
+                    SignedEntityType::MithrilStakeDistribution(Epoch(6)),
+                    "2024-05-11T00:00:00Z",
+                ),
+            ],
+        );
+
+        assert_eq!(Some(5 * 24 * 3600), estimate.beacon_interval_seconds);
+        assert_eq!(
+            Some(
+                DateTime::parse_from_rfc3339("2024-05-16T00:00:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            ),
+            estimate.next_expected_at
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use mithril_common::entities::{ProtocolMessage, ProtocolMessagePartKey, ProtocolMessagePartValue};
+use serde::{Deserialize, Serialize};
+
+use crate::database::record::CertificateRecord;
+
+/// Message structure of the protocol message signed by a certificate, in canonical form
+///
+/// The signed message of the certificate is the hex encoded SHA256 hash of the concatenation of
+/// the key and the value of each part, taken in the order of `message_parts`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateProtocolMessage {
+    /// Hash of the certificate
+    pub certificate_hash: String,
+
+    /// Message signed by the certificate
+    pub signed_message: String,
+
+    /// Parts of the protocol message, in the order they are hashed
+    pub message_parts: Vec<CertificateProtocolMessagePart>,
+}
+
+/// Message structure of a part of a protocol message
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateProtocolMessagePart {
+    /// Key of the part
+    pub key: ProtocolMessagePartKey,
+
+    /// Value of the part
+    pub value: ProtocolMessagePartValue,
+}
+
+impl CertificateProtocolMessage {
+    /// Rebuild the protocol message from its parts
+    pub fn protocol_message(&self) -> ProtocolMessage {
+        let mut protocol_message = ProtocolMessage::new();
+        for part in &self.message_parts {
+            protocol_message.set_message_part(part.key, part.value.clone());
+        }
+
+        protocol_message
+    }
+}
+
+impl From<CertificateRecord> for CertificateProtocolMessage {
+    fn from(record: CertificateRecord) -> Self {
+        Self {
+            certificate_hash: record.certificate_id,
+            signed_message: record.message,
+            // The parts are iterated in the same order as the one used to compute the hash
+            message_parts: record
+                .protocol_message
+                .message_parts
+                .into_iter()
+                .map(|(key, value)| CertificateProtocolMessagePart { key, value })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::fake_data;
+
+    use super::*;
+
+    #[test]
+    fn message_parts_are_listed_in_hash_order_and_hash_to_the_signed_message() {
+        let certificate = fake_data::certificate("certificate-hash".to_string());
+        let mut record = CertificateRecord::from(certificate.clone());
+        record.message = certificate.protocol_message.compute_hash();
+
+        let message = CertificateProtocolMessage::from(record);
+
+        assert_eq!(
+            certificate
+                .protocol_message
+                .message_parts
+                .keys()
+                .cloned()
+                .collect::<Vec<_>>(),
+            message
+                .message_parts
+                .iter()
+                .map(|part| part.key)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            message.signed_message,
+            message.protocol_message().compute_hash()
+        );
+    }
+}
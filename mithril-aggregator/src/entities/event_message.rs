@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::event_store::Event;
+
+/// Message structure of the events recorded by the event store
+pub type EventListMessage = Vec<EventListItemMessage>;
+
+/// Message structure of an event recorded by the event store
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventListItemMessage {
+    /// Sequential number of the event
+    pub event_id: i64,
+
+    /// Date and time when the event was recorded
+    pub created_at: DateTime<Utc>,
+
+    /// Source of the event, e.g. `Runtime::create_certificate`
+    pub source: String,
+
+    /// Action of the event, it gives the type of its content
+    pub action: String,
+
+    /// Content of the event, with its headers
+    pub content: Value,
+}
+
+impl From<Event> for EventListItemMessage {
+    fn from(event: Event) -> Self {
+        Self {
+            event_id: event.event_id,
+            created_at: event.created_at,
+            source: event.source,
+            action: event.action,
+            // Events content is stored as JSON but kept as a string if it can't be parsed
+            content: serde_json::from_str(&event.content).unwrap_or(Value::String(event.content)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn event(content: &str) -> Event {
+        Event {
+            event_id: 1,
+            created_at: Utc::now(),
+            source: "Runtime::create_certificate".to_string(),
+            action: "certificate_created".to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn content_is_parsed_as_json_or_kept_as_string() {
+        let message = EventListItemMessage::from(event(
+            r#"{"headers": {"epoch": "5"}, "content": {"certificate_hash": "abc"}}"#,
+        ));
+        assert_eq!(
+            json!({"headers": {"epoch": "5"}, "content": {"certificate_hash": "abc"}}),
+            message.content
+        );
+
+        let message = EventListItemMessage::from(event("not json"));
+        assert_eq!(Value::String("not json".to_string()), message.content);
+    }
+}
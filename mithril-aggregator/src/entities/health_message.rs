@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+use crate::services::{ImmutableFileLagStatus, StaleSnapshotStatus};
+
+/// Health status of the aggregator
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// The aggregator works as expected
+    Ok,
+
+    /// The aggregator is ready but a condition needs the attention of its operator
+    Warning,
+
+    /// The aggregator can not certify new artifacts as its Cardano node does not progress
+    Degraded,
+}
+
+/// Message structure of the readiness of the aggregator
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReadyMessage {
+    /// Health status
+    pub status: HealthStatus,
+
+    /// Human readable descriptions of the conditions that raised a warning
+    pub warnings: Vec<String>,
+
+    /// Freshness of the latest certified snapshot
+    pub stale_snapshot: StaleSnapshotStatus,
+
+    /// Progression of the immutable file number of the Cardano node database
+    pub immutable_file_lag: ImmutableFileLagStatus,
+}
+
+impl HealthReadyMessage {
+    /// Create the readiness message from the freshness of the latest certified snapshot and the
+    /// progression of the immutable file number
+    pub fn new(
+        stale_snapshot: StaleSnapshotStatus,
+        immutable_file_lag: ImmutableFileLagStatus,
+    ) -> Self {
+        let mut warnings = vec![];
+        if stale_snapshot.is_stale {
+            warnings.push(format!(
+                "No snapshot has been certified since {}",
+                stale_snapshot
+                    .last_certified_at
+                    .map(|date| date.to_rfc3339())
+                    .unwrap_or_default()
+            ));
+        }
+        if immutable_file_lag.is_lagging {
+            warnings.push(format!(
+                "The immutable file number of the Cardano node has not progressed since {}",
+                immutable_file_lag
+                    .last_progress_at
+                    .map(|date| date.to_rfc3339())
+                    .unwrap_or_default()
+            ));
+        }
+
+        Self {
+            status: if immutable_file_lag.is_lagging {
+                HealthStatus::Degraded
+            } else if warnings.is_empty() {
+                HealthStatus::Ok
+            } else {
+                HealthStatus::Warning
+            },
+            warnings,
+            stale_snapshot,
+            immutable_file_lag,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+
+    #[test]
+    fn ok_if_the_latest_snapshot_is_not_stale() {
+        let message = HealthReadyMessage::new(
+            StaleSnapshotStatus::default(),
+            ImmutableFileLagStatus::default(),
+        );
+
+        assert_eq!(HealthStatus::Ok, message.status);
+        assert!(message.warnings.is_empty());
+    }
+
+    #[test]
+    fn warning_if_the_latest_snapshot_is_stale() {
+        let last_certified_at = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let message = HealthReadyMessage::new(
+            StaleSnapshotStatus {
+                last_certified_at: Some(last_certified_at),
+                is_stale: true,
+                ..StaleSnapshotStatus::default()
+            },
+            ImmutableFileLagStatus::default(),
+        );
+
+        assert_eq!(HealthStatus::Warning, message.status);
+        assert_eq!(
+            vec!["No snapshot has been certified since 2024-01-01T00:00:00+00:00".to_string()],
+            message.warnings
+        );
+    }
+
+    #[test]
+    fn degraded_if_the_immutable_file_number_does_not_progress() {
+        let last_progress_at = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let message = HealthReadyMessage::new(
+            StaleSnapshotStatus::default(),
+            ImmutableFileLagStatus {
+                last_progress_at: Some(last_progress_at),
+                is_lagging: true,
+                ..ImmutableFileLagStatus::default()
+            },
+        );
+
+        assert_eq!(HealthStatus::Degraded, message.status);
+        assert_eq!(
+            vec![
+                "The immutable file number of the Cardano node has not progressed since 2024-01-01T00:00:00+00:00"
+                    .to_string()
+            ],
+            message.warnings
+        );
+    }
+}
@@ -1,12 +1,43 @@
 //! Entities module
 //!
 //! This module provide domain entities for the services & state machine.
+mod aggregator_status_message;
+mod beacon_estimate_message;
+mod certificate_protocol_message;
+mod event_message;
+mod health_message;
 mod open_message;
+mod open_message_quorum_status_message;
+mod quorum_simulation_message;
+mod runtime_cycle_trigger_message;
+mod runtime_decision_message;
+mod signer_history_message;
 mod signer_registration_message;
 mod signer_ticker_message;
+mod signer_version_message;
+mod stake_distribution_diff_message;
 
+pub use aggregator_status_message::AggregatorStatusMessage;
+pub use beacon_estimate_message::{BeaconEstimateListItemMessage, BeaconEstimateListMessage};
+pub use certificate_protocol_message::{
+    CertificateProtocolMessage, CertificateProtocolMessagePart,
+};
+pub use event_message::{EventListItemMessage, EventListMessage};
+pub use health_message::{HealthReadyMessage, HealthStatus};
 pub use open_message::OpenMessage;
+pub use open_message_quorum_status_message::OpenMessageQuorumStatusMessage;
+pub use quorum_simulation_message::{QuorumSimulationMessage, QuorumSimulationScenarioMessage};
+pub use runtime_cycle_trigger_message::{RuntimeCycleTriggerMessage, RuntimeCycleTriggeredMessage};
+pub use runtime_decision_message::{RuntimeDecisionListItemMessage, RuntimeDecisionListMessage};
+pub use signer_history_message::{SignerHistoryListItemMessage, SignerHistoryMessage};
 pub use signer_registration_message::{
     SignerRegistrationsListItemMessage, SignerRegistrationsMessage,
 };
 pub use signer_ticker_message::{SignerTickerListItemMessage, SignersTickersMessage};
+pub use signer_version_message::{
+    SignerEraListItemMessage, SignerVersionListItemMessage, SignersVersionsMessage,
+    UNKNOWN_SIGNER_NODE_VERSION,
+};
+pub use stake_distribution_diff_message::{
+    StakeChangeListItemMessage, StakeDistributionDiffMessage,
+};
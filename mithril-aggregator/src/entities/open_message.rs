@@ -128,6 +128,7 @@ mod test {
             is_expired: false,
             created_at,
             expires_at: None,
+            reopening_count: 0,
         };
         let expected = OpenMessage {
             epoch: Epoch(1),
@@ -156,6 +157,7 @@ mod test {
             is_expired: false,
             created_at,
             expires_at: None,
+            reopening_count: 0,
             single_signatures: vec![fake_data::single_signatures(vec![1, 4, 5])],
         };
         let expected = OpenMessage {
@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use mithril_common::entities::{Epoch, ProtocolParameters, SignedEntityType};
+
+use crate::database::record::{OpenMessageQuorumStatusRecord, OpenMessageRecord};
+
+/// Message structure of the progress of an open message toward the quorum of the protocol
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OpenMessageQuorumStatusMessage {
+    /// Epoch of the open message
+    pub epoch: Epoch,
+
+    /// Signed entity type of the open message
+    pub signed_entity_type: SignedEntityType,
+
+    /// Has the open message been converted into a certificate
+    pub is_certified: bool,
+
+    /// Has the open message expired
+    pub is_expired: bool,
+
+    /// Date and time before which no certificate is created for the open message, so that more
+    /// single signatures can be included
+    pub certifiable_at: DateTime<Utc>,
+
+    /// Number of registered single signatures
+    pub signatures_count: u64,
+
+    /// Number of lottery indexes won by the registered single signatures
+    pub won_lottery_indexes: u64,
+
+    /// Number of won lottery indexes needed to reach the quorum (`k` protocol parameter)
+    pub quorum: u64,
+
+    /// Percentage of the quorum reached, capped to 100
+    pub quorum_percentage: f64,
+
+    /// Cumulative stake of the signers that registered a single signature
+    pub signed_stake: u64,
+
+    /// Cumulative stake of the signers that can sign the open message
+    pub total_stake: u64,
+}
+
+impl OpenMessageQuorumStatusMessage {
+    /// Compute the quorum status of the given open message from the aggregation of its single
+    /// signatures and the protocol parameters of its signers.
+    ///
+    /// The certification grace period is the minimum duration the open message stays open
+    /// before a certificate can be created for it.
+    pub fn new(
+        open_message: OpenMessageRecord,
+        quorum_status: OpenMessageQuorumStatusRecord,
+        protocol_parameters: &ProtocolParameters,
+        certification_grace_period: Duration,
+    ) -> Self {
+        let quorum = protocol_parameters.k;
+        let quorum_percentage = if quorum == 0 {
+            100.0
+        } else {
+            (quorum_status.won_lottery_indexes as f64 * 100.0 / quorum as f64).min(100.0)
+        };
+        let certifiable_at = chrono::Duration::from_std(certification_grace_period)
+            .ok()
+            .and_then(|grace_period| open_message.created_at.checked_add_signed(grace_period))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+        Self {
+            epoch: open_message.epoch,
+            signed_entity_type: open_message.signed_entity_type,
+            is_certified: open_message.is_certified,
+            is_expired: open_message.is_expired,
+            certifiable_at,
+            signatures_count: quorum_status.signatures_count,
+            won_lottery_indexes: quorum_status.won_lottery_indexes,
+            quorum,
+            quorum_percentage,
+            signed_stake: quorum_status.signed_stake,
+            total_stake: quorum_status.total_stake,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quorum_status(won_lottery_indexes: u64) -> OpenMessageQuorumStatusRecord {
+        OpenMessageQuorumStatusRecord {
+            open_message_id: OpenMessageRecord::dummy().open_message_id,
+            signatures_count: 2,
+            won_lottery_indexes,
+            signed_stake: 10,
+            total_stake: 30,
+        }
+    }
+
+    #[test]
+    fn compute_the_percentage_of_the_quorum() {
+        let message = OpenMessageQuorumStatusMessage::new(
+            OpenMessageRecord::dummy(),
+            quorum_status(3),
+            &ProtocolParameters::new(4, 10, 0.65),
+            Duration::ZERO,
+        );
+
+        assert_eq!(4, message.quorum);
+        assert_eq!(75.0, message.quorum_percentage);
+    }
+
+    #[test]
+    fn cap_the_percentage_of_the_quorum_to_100() {
+        let message = OpenMessageQuorumStatusMessage::new(
+            OpenMessageRecord::dummy(),
+            quorum_status(12),
+            &ProtocolParameters::new(4, 10, 0.65),
+            Duration::ZERO,
+        );
+
+        assert_eq!(100.0, message.quorum_percentage);
+    }
+
+    #[test]
+    fn certifiable_at_is_the_end_of_the_certification_grace_period() {
+        let open_message = OpenMessageRecord::dummy();
+        let message = OpenMessageQuorumStatusMessage::new(
+            open_message.clone(),
+            quorum_status(3),
+            &ProtocolParameters::new(4, 10, 0.65),
+            Duration::from_secs(30),
+        );
+
+        assert_eq!(
+            open_message.created_at + chrono::Duration::seconds(30),
+            message.certifiable_at
+        );
+    }
+}
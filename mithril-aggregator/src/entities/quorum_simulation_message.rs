@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+use mithril_common::entities::{Epoch, ProtocolParameters};
+
+/// Message structure of a simulation of the probability to reach the quorum with given protocol
+/// parameters and the stake distribution of the registered signers
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuorumSimulationMessage {
+    /// Epoch of the registered signers
+    pub epoch: Epoch,
+
+    /// Simulated protocol parameters
+    pub protocol_parameters: ProtocolParameters,
+
+    /// Number of registered signers
+    pub signers_count: u64,
+
+    /// Cumulative stake of the registered signers
+    pub total_stake: u64,
+
+    /// Expected number of lotteries won when all the registered signers sign
+    pub expected_won_lotteries: f64,
+
+    /// Probability to reach the quorum when all the registered signers sign
+    pub quorum_probability: f64,
+
+    /// Probability to reach the quorum targeted by the simulation
+    pub target_probability: f64,
+
+    /// Minimum ratio of the total stake that must sign to reach the quorum with the target
+    /// probability, `None` if it can not be reached even when all the signers sign
+    pub min_participating_stake_ratio: Option<f64>,
+
+    /// Probability to reach the quorum when the signers with the largest stakes do not sign
+    pub scenarios: Vec<QuorumSimulationScenarioMessage>,
+}
+
+/// Message structure of a scenario of a quorum simulation
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuorumSimulationScenarioMessage {
+    /// Number of signers, among the ones with the largest stakes, that do not sign
+    pub absent_largest_signers: u64,
+
+    /// Ratio of the total stake held by the signers that sign
+    pub participating_stake_ratio: f64,
+
+    /// Probability to reach the quorum
+    pub quorum_probability: f64,
+}
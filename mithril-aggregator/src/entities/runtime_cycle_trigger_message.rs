@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+use crate::RuntimeStep;
+
+/// Message structure of a request to trigger an immediate cycle of the runtime
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeCycleTriggerMessage {
+    /// Step to perform, a plain cycle of the runtime if not set
+    #[serde(default)]
+    pub step: RuntimeStep,
+}
+
+/// Message structure of a triggered cycle of the runtime
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeCycleTriggeredMessage {
+    /// Step performed by the triggered cycle
+    pub step: RuntimeStep,
+
+    /// State of the runtime when the cycle was triggered
+    pub state: String,
+}
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use mithril_common::entities::SignedEntityType;
+use serde::{Deserialize, Serialize};
+
+use crate::database::record::RuntimeDecisionRecord;
+
+/// Message structure of the decisions taken by the runtime
+pub type RuntimeDecisionListMessage = Vec<RuntimeDecisionListItemMessage>;
+
+/// Message structure of a decision taken by the runtime
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeDecisionListItemMessage {
+    /// Signed entity type (and its beacon) the decision was taken for
+    pub signed_entity_type: SignedEntityType,
+
+    /// Decision taken by the runtime
+    pub decision: String,
+
+    /// Detailed reason of the decision
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Date and time when the decision was taken for the first time
+    pub first_seen_at: DateTime<Utc>,
+
+    /// Date and time when the decision was taken for the last time
+    pub last_seen_at: DateTime<Utc>,
+}
+
+impl From<RuntimeDecisionRecord> for RuntimeDecisionListItemMessage {
+    fn from(record: RuntimeDecisionRecord) -> Self {
+        Self {
+            signed_entity_type: record.signed_entity_type,
+            decision: record.decision.to_string(),
+            reason: record.reason,
+            first_seen_at: record.created_at,
+            last_seen_at: record.last_seen_at,
+        }
+    }
+}
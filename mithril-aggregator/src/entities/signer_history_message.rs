@@ -0,0 +1,121 @@
+use mithril_common::entities::{Epoch, PartyId};
+use serde::{Deserialize, Serialize};
+
+use crate::database::record::SignerParticipationRecord;
+
+/// Message structure of the participation of a signer at an epoch
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerHistoryListItemMessage {
+    /// Epoch of the participation
+    pub epoch: Epoch,
+
+    /// Whether the signer was registered to sign at this epoch
+    pub registered: bool,
+
+    /// Number of single signatures issued by the signer at this epoch
+    pub signatures_issued: u64,
+}
+
+/// Message structure of the registrations and signatures history of a signer
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignerHistoryMessage {
+    /// Party id of the signer
+    pub party_id: PartyId,
+
+    /// Number of epochs at which the signer was registered to sign
+    pub epochs_registered: u64,
+
+    /// Total number of single signatures issued by the signer
+    pub signatures_issued: u64,
+
+    /// Ratio, between 0 and 1, of the registered epochs at which the signer issued at least one
+    /// single signature
+    pub participation_rate: f64,
+
+    /// Participation of the signer per epoch, most recent first
+    pub epochs: Vec<SignerHistoryListItemMessage>,
+}
+
+impl SignerHistoryMessage {
+    /// Summarize the given participation records of a signer
+    pub fn new(party_id: PartyId, records: Vec<SignerParticipationRecord>) -> Self {
+        let epochs_registered = records.iter().filter(|r| r.registered).count() as u64;
+        let epochs_participated = records
+            .iter()
+            .filter(|r| r.registered && r.signatures_issued > 0)
+            .count() as u64;
+        let participation_rate = if epochs_registered == 0 {
+            0.0
+        } else {
+            epochs_participated as f64 / epochs_registered as f64
+        };
+
+        Self {
+            party_id,
+            epochs_registered,
+            signatures_issued: records.iter().map(|r| r.signatures_issued).sum(),
+            participation_rate,
+            epochs: records
+                .into_iter()
+                .map(|r| SignerHistoryListItemMessage {
+                    epoch: r.epoch,
+                    registered: r.registered,
+                    signatures_issued: r.signatures_issued,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn participation(
+        epoch: u64,
+        registered: bool,
+        signatures_issued: u64,
+    ) -> SignerParticipationRecord {
+        SignerParticipationRecord {
+            signer_id: "signer-1".to_string(),
+            epoch: Epoch(epoch),
+            registered,
+            signatures_issued,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn summarize_signer_without_history() {
+        let message = SignerHistoryMessage::new("signer-1".to_string(), vec![]);
+
+        assert_eq!(0, message.epochs_registered);
+        assert_eq!(0, message.signatures_issued);
+        assert_eq!(0.0, message.participation_rate);
+        assert!(message.epochs.is_empty());
+    }
+
+    #[test]
+    fn summarize_signer_history() {
+        let message = SignerHistoryMessage::new(
+            "signer-1".to_string(),
+            vec![
+                participation(8, true, 0),
+                participation(7, false, 1),
+                participation(6, true, 3),
+                participation(5, true, 0),
+                participation(4, true, 2),
+            ],
+        );
+
+        assert_eq!(4, message.epochs_registered);
+        assert_eq!(6, message.signatures_issued);
+        assert_eq!(0.5, message.participation_rate);
+        assert_eq!(
+            vec![Epoch(8), Epoch(7), Epoch(6), Epoch(5), Epoch(4)],
+            message.epochs.iter().map(|e| e.epoch).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+
+use mithril_common::entities::Epoch;
+use serde::{Deserialize, Serialize};
+
+use crate::database::record::SignerMetadataRecord;
+
+/// Version reported for the signers that did not send their node version
+pub const UNKNOWN_SIGNER_NODE_VERSION: &str = "unknown";
+
+/// Message structure of the number of signers running a node version
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerVersionListItemMessage {
+    /// Version of the signer node software
+    pub version: String,
+
+    /// Number of signers running this version
+    pub signers: usize,
+}
+
+/// Message structure of the number of signers supporting an era
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerEraListItemMessage {
+    /// Era supported by the signers
+    pub era: String,
+
+    /// Number of signers supporting this era
+    pub signers: usize,
+}
+
+/// Message structure of the distribution of the versions of the signers that registered at the
+/// latest registration epoch
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SignersVersionsMessage {
+    /// Registration epoch of the signers, none if no signer registered yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registration_epoch: Option<Epoch>,
+
+    /// Number of signers that registered at the registration epoch
+    pub total_signers: usize,
+
+    /// Number of signers per node version, most used first
+    pub versions: Vec<SignerVersionListItemMessage>,
+
+    /// Number of signers per supported era, most supported first
+    pub eras: Vec<SignerEraListItemMessage>,
+}
+
+impl SignersVersionsMessage {
+    /// Summarize the given signers metadata, they are expected to share the same registration epoch
+    pub fn new(signers_metadata: Vec<SignerMetadataRecord>) -> Self {
+        let mut versions: BTreeMap<String, usize> = BTreeMap::new();
+        let mut eras: BTreeMap<String, usize> = BTreeMap::new();
+        for signer_metadata in &signers_metadata {
+            let version = signer_metadata
+                .node_version
+                .clone()
+                .unwrap_or_else(|| UNKNOWN_SIGNER_NODE_VERSION.to_string());
+            *versions.entry(version).or_default() += 1;
+            for era in &signer_metadata.supported_eras {
+                *eras.entry(era.clone()).or_default() += 1;
+            }
+        }
+
+        let mut versions: Vec<_> = versions
+            .into_iter()
+            .map(|(version, signers)| SignerVersionListItemMessage { version, signers })
+            .collect();
+        versions.sort_by(|a, b| b.signers.cmp(&a.signers));
+        let mut eras: Vec<_> = eras
+            .into_iter()
+            .map(|(era, signers)| SignerEraListItemMessage { era, signers })
+            .collect();
+        eras.sort_by(|a, b| b.signers.cmp(&a.signers));
+
+        Self {
+            registration_epoch: signers_metadata.first().map(|s| s.registration_epoch),
+            total_signers: signers_metadata.len(),
+            versions,
+            eras,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn signer_metadata(
+        node_version: Option<&str>,
+        supported_eras: &[&str],
+    ) -> SignerMetadataRecord {
+        SignerMetadataRecord {
+            signer_id: "signer".to_string(),
+            registration_epoch: Epoch(12),
+            node_version: node_version.map(|v| v.to_string()),
+            supported_eras: supported_eras.iter().map(|e| e.to_string()).collect(),
+            ip_address: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn summarize_no_signers() {
+        assert_eq!(
+            SignersVersionsMessage::default(),
+            SignersVersionsMessage::new(vec![])
+        );
+    }
+
+    #[test]
+    fn summarize_signers_versions_and_eras() {
+        let message = SignersVersionsMessage::new(vec![
+            signer_metadata(Some("0.2.150"), &["thales"]),
+            signer_metadata(Some("0.2.151"), &["thales", "pythagoras"]),
+            signer_metadata(Some("0.2.151"), &["thales", "pythagoras"]),
+            signer_metadata(None, &[]),
+        ]);
+
+        assert_eq!(
+            SignersVersionsMessage {
+                registration_epoch: Some(Epoch(12)),
+                total_signers: 4,
+                versions: vec![
+                    SignerVersionListItemMessage {
+                        version: "0.2.151".to_string(),
+                        signers: 2
+                    },
+                    SignerVersionListItemMessage {
+                        version: "0.2.150".to_string(),
+                        signers: 1
+                    },
+                    SignerVersionListItemMessage {
+                        version: UNKNOWN_SIGNER_NODE_VERSION.to_string(),
+                        signers: 1
+                    },
+                ],
+                eras: vec![
+                    SignerEraListItemMessage {
+                        era: "thales".to_string(),
+                        signers: 3
+                    },
+                    SignerEraListItemMessage {
+                        era: "pythagoras".to_string(),
+                        signers: 2
+                    },
+                ],
+            },
+            message
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use mithril_common::entities::{Epoch, PartyId, Stake, StakeDistribution, StakeDistributionDiff};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::SignerRegistrationsListItemMessage;
+
+/// Message structure of the difference between the signers stakes of two consecutive
+/// registration epochs.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StakeDistributionDiffMessage {
+    /// The registration epoch of the previous signers stakes.
+    pub previous_registered_at: Epoch,
+
+    /// The registration epoch of the current signers stakes.
+    pub registered_at: Epoch,
+
+    /// The signers that registered at the current epoch only
+    pub new_signers: Vec<SignerRegistrationsListItemMessage>,
+
+    /// The signers that registered at the previous epoch only
+    pub departed_signers: Vec<SignerRegistrationsListItemMessage>,
+
+    /// The signers that registered at both epochs with a different stake
+    pub stake_changes: Vec<StakeChangeListItemMessage>,
+}
+
+/// Message structure of the stake change of a signer
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StakeChangeListItemMessage {
+    /// The signer party id
+    pub party_id: PartyId,
+
+    /// The signer stake at the previous registration epoch
+    pub previous_stake: Stake,
+
+    /// The signer stake at the current registration epoch
+    pub current_stake: Stake,
+}
+
+impl StakeDistributionDiffMessage {
+    /// Build a [StakeDistributionDiffMessage] from the difference of the signers stakes
+    /// registered at the two given epochs.
+    pub fn new(
+        previous_registered_at: Epoch,
+        registered_at: Epoch,
+        diff: StakeDistributionDiff,
+    ) -> Self {
+        let to_list_items = |stakes: StakeDistribution| {
+            stakes
+                .into_iter()
+                .map(|(party_id, stake)| SignerRegistrationsListItemMessage { party_id, stake })
+                .collect()
+        };
+
+        Self {
+            previous_registered_at,
+            registered_at,
+            new_signers: to_list_items(diff.new_parties),
+            departed_signers: to_list_items(diff.departed_parties),
+            stake_changes: diff
+                .stake_deltas
+                .into_iter()
+                .map(|delta| StakeChangeListItemMessage {
+                    party_id: delta.party_id,
+                    previous_stake: delta.previous_stake,
+                    current_stake: delta.current_stake,
+                })
+                .collect(),
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use anyhow::anyhow;
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use sqlite::Value;
 
 use std::{collections::HashMap, sync::Arc};
 
@@ -9,6 +11,9 @@ use mithril_persistence::sqlite::{
     WhereCondition,
 };
 
+#[cfg(test)]
+use mockall::automock;
+
 /// Event that is sent from a thread to be persisted.
 #[derive(Debug, Clone)]
 pub struct EventMessage {
@@ -49,6 +54,7 @@ impl EventMessage {
 }
 
 /// Event persisted in the Event Store.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Event {
     /// Sequential number of the event, this is set by the database.
     pub event_id: i64,
@@ -108,40 +114,141 @@ struct EventPersisterProvider<'conn> {
 
 impl<'conn> EventPersisterProvider<'conn> {
     pub fn new(connection: &'conn SqliteConnection) -> Self {
-        let myself = Self { connection };
-        myself.create_table_if_not_exists();
+        Self { connection }
+    }
+}
+
+impl<'conn> Provider<'conn> for EventPersisterProvider<'conn> {
+    type Entity = Event;
 
-        myself
+    fn get_connection(&'conn self) -> &'conn SqliteConnection {
+        self.connection
     }
 
-    fn create_table_if_not_exists(&self) {
-        let sql = r#"
-        create table if not exists event (
-            event_id integer primary key asc autoincrement,
-            created_at text not null,
-            source text not null,
-            action text not null,
-            content text nul null
-        )"#;
+    fn get_definition(&self, data: &str) -> String {
+        let projection = Self::Entity::get_projection().expand(SourceAlias::default());
 
-        self.connection.execute(sql).unwrap();
+        format!(r#"insert into event {data} returning {projection}"#)
     }
 }
 
-impl<'conn> Provider<'conn> for EventPersisterProvider<'conn> {
+/// Filters applied when querying the events, the events matching all the given filters are
+/// returned.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilters {
+    /// Only return the events with this action.
+    pub action: Option<String>,
+
+    /// Only return the events with this source.
+    pub source: Option<String>,
+
+    /// Only return the events created at or after this date.
+    pub since: Option<DateTime<Utc>>,
+
+    /// Maximum number of events to return, most recent first.
+    pub limit: usize,
+}
+
+struct GetEventProvider<'conn> {
+    connection: &'conn SqliteConnection,
+    limit: usize,
+}
+
+impl<'conn> GetEventProvider<'conn> {
+    pub fn new(connection: &'conn SqliteConnection, limit: usize) -> Self {
+        Self { connection, limit }
+    }
+
+    fn get_filters_condition(&self, filters: &EventFilters) -> WhereCondition {
+        let mut condition = WhereCondition::default();
+        if let Some(action) = &filters.action {
+            condition = condition.and_where(WhereCondition::new(
+                "action = ?*",
+                vec![Value::String(action.clone())],
+            ));
+        }
+        if let Some(source) = &filters.source {
+            condition = condition.and_where(WhereCondition::new(
+                "source = ?*",
+                vec![Value::String(source.clone())],
+            ));
+        }
+        if let Some(since) = &filters.since {
+            condition = condition.and_where(WhereCondition::new(
+                "created_at >= ?*",
+                vec![Value::String(since.to_rfc3339())],
+            ));
+        }
+
+        condition
+    }
+}
+
+impl<'conn> Provider<'conn> for GetEventProvider<'conn> {
     type Entity = Event;
 
     fn get_connection(&'conn self) -> &'conn SqliteConnection {
         self.connection
     }
 
-    fn get_definition(&self, data: &str) -> String {
+    fn get_definition(&self, condition: &str) -> String {
         let projection = Self::Entity::get_projection().expand(SourceAlias::default());
 
-        format!(r#"insert into event {data} returning {projection}"#)
+        format!(
+            "select {projection} from event where {condition} order by event_id desc limit {}",
+            self.limit
+        )
+    }
+}
+
+struct GetEventsAfterProvider<'conn> {
+    connection: &'conn SqliteConnection,
+    limit: usize,
+}
+
+impl<'conn> GetEventsAfterProvider<'conn> {
+    pub fn new(connection: &'conn SqliteConnection, limit: usize) -> Self {
+        Self { connection, limit }
+    }
+
+    fn get_after_event_id_condition(&self, event_id: i64, actions: &[String]) -> WhereCondition {
+        WhereCondition::new("event_id > ?*", vec![Value::Integer(event_id)]).and_where(
+            WhereCondition::where_in(
+                "action",
+                actions
+                    .iter()
+                    .map(|action| Value::String(action.clone()))
+                    .collect(),
+            ),
+        )
+    }
+}
+
+impl<'conn> Provider<'conn> for GetEventsAfterProvider<'conn> {
+    type Entity = Event;
+
+    fn get_connection(&'conn self) -> &'conn SqliteConnection {
+        self.connection
+    }
+
+    fn get_definition(&self, condition: &str) -> String {
+        let projection = Self::Entity::get_projection().expand(SourceAlias::default());
+
+        format!(
+            "select {projection} from event where {condition} order by event_id asc limit {}",
+            self.limit
+        )
     }
 }
 
+/// Read access to the events persisted in the Event Store.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait EventReader: Sync + Send {
+    /// Return the events matching the given filters, most recent first.
+    async fn get_events(&self, filters: EventFilters) -> StdResult<Vec<Event>>;
+}
+
 /// The EventPersister is the adapter to persist EventMessage turning them into
 /// Event.
 pub struct EventPersister {
@@ -182,13 +289,46 @@ impl EventPersister {
             "No record from the database after I saved event message {log_message:?}"
         ))
     }
+
+    /// Return the events with one of the given actions created after the given event, oldest
+    /// first.
+    pub fn get_events_after(
+        &self,
+        event_id: i64,
+        actions: &[String],
+        limit: usize,
+    ) -> StdResult<Vec<Event>> {
+        let provider = GetEventsAfterProvider::new(&self.connection, limit);
+        let events = provider.find(provider.get_after_event_id_condition(event_id, actions))?;
+
+        Ok(events.collect())
+    }
+}
+
+#[async_trait]
+impl EventReader for EventPersister {
+    async fn get_events(&self, filters: EventFilters) -> StdResult<Vec<Event>> {
+        let provider = GetEventProvider::new(&self.connection, filters.limit);
+        let events = provider.find(provider.get_filters_condition(&filters))?;
+
+        Ok(events.collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use mithril_common::StdResult;
-    use sqlite::Connection;
+    use mithril_persistence::sqlite::ConnectionBuilder;
+
+    fn event_store_connection() -> Arc<SqliteConnection> {
+        let connection = ConnectionBuilder::open_memory()
+            .with_migrations(crate::event_store::migration::get_migrations())
+            .build()
+            .unwrap();
+
+        Arc::new(connection)
+    }
 
     #[test]
     fn event_projection() {
@@ -202,8 +342,7 @@ mod tests {
 
     #[test]
     fn provider_sql() {
-        let connection = Arc::new(Connection::open_thread_safe(":memory:").unwrap());
-        let persister = EventPersister::new(connection);
+        let persister = EventPersister::new(event_store_connection());
         let message = EventMessage::new("source", "action", "content");
         let (parameters, values) = persister.get_persist_parameters(message).unwrap().expand();
 
@@ -216,11 +355,113 @@ mod tests {
 
     #[test]
     fn can_persist_event() -> StdResult<()> {
-        let connection = Arc::new(Connection::open_thread_safe(":memory:").unwrap());
-        let persister = EventPersister::new(connection);
+        let persister = EventPersister::new(event_store_connection());
         let message = EventMessage::new("source", "action", "content");
 
         let _event = persister.persist(message)?;
         Ok(())
     }
+
+    #[test]
+    fn get_events_condition_sql() {
+        let connection = event_store_connection();
+        let provider = GetEventProvider::new(&connection, 10);
+        let (condition, values) = provider
+            .get_filters_condition(&EventFilters {
+                action: Some("action".to_string()),
+                source: Some("source".to_string()),
+                since: Some(Utc::now()),
+                limit: 10,
+            })
+            .expand();
+
+        assert_eq!(
+            "action = ?1 and source = ?2 and created_at >= ?3".to_string(),
+            condition
+        );
+        assert_eq!(3, values.len());
+    }
+
+    #[tokio::test]
+    async fn get_events_matching_filters_most_recent_first() {
+        let persister = EventPersister::new(event_store_connection());
+        for (source, action) in [
+            ("Runtime", "open_message_created"),
+            ("HTTP", "register_signatures"),
+            ("Runtime", "certificate_created"),
+            ("Runtime", "open_message_created"),
+        ] {
+            persister
+                .persist(EventMessage::new(source, action, r#""content""#))
+                .unwrap();
+        }
+
+        let events = persister
+            .get_events(EventFilters {
+                source: Some("Runtime".to_string()),
+                limit: 10,
+                ..EventFilters::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![4, 3, 1],
+            events.iter().map(|e| e.event_id).collect::<Vec<_>>()
+        );
+
+        let events = persister
+            .get_events(EventFilters {
+                action: Some("open_message_created".to_string()),
+                limit: 1,
+                ..EventFilters::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![4],
+            events.iter().map(|e| e.event_id).collect::<Vec<_>>()
+        );
+
+        let events = persister
+            .get_events(EventFilters {
+                since: Some(Utc::now() + chrono::Duration::try_minutes(1).unwrap()),
+                limit: 10,
+                ..EventFilters::default()
+            })
+            .await
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn get_events_after_an_event_with_the_given_actions_oldest_first() {
+        let persister = EventPersister::new(event_store_connection());
+        for action in [
+            "certificate_created",
+            "register_signatures",
+            "artifact_created",
+            "certificate_created",
+            "certificate_created",
+        ] {
+            persister
+                .persist(EventMessage::new("Runtime", action, r#""content""#))
+                .unwrap();
+        }
+
+        let events = persister
+            .get_events_after(
+                1,
+                &[
+                    "certificate_created".to_string(),
+                    "artifact_created".to_string(),
+                ],
+                2,
+            )
+            .unwrap();
+
+        assert_eq!(
+            vec![3, 4],
+            events.iter().map(|e| e.event_id).collect::<Vec<_>>()
+        );
+    }
 }
@@ -0,0 +1,306 @@
+use anyhow::Context;
+use slog_scope::{debug, warn};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::watch;
+
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::SqliteConnection;
+
+use super::{EventPersister, EventSink, PublishedEvent};
+
+/// Actions of the events that are forwarded to the external sinks.
+pub const FORWARDED_EVENT_ACTIONS: [&str; 5] = [
+    "certificate_created",
+    "artifact_created",
+    "signer_registration_round_opened",
+    "signer_registration_round_closed",
+    "register_signer",
+];
+
+/// Maximum number of events read at once from the Event Store when forwarding them.
+const FORWARDING_BATCH_SIZE: usize = 100;
+
+/// Forward the events persisted in the Event Store to external [sinks][EventSink].
+///
+/// The delivery is at-least-once: the last event delivered to each sink is recorded in the
+/// Event Store database only after the sink acknowledged it. A failing delivery is retried with
+/// an exponential backoff, then the remaining events are forwarded once a new event is persisted
+/// or once the aggregator restarts.
+pub struct EventForwarder {
+    sinks: Vec<Arc<dyn EventSink>>,
+    connection: Arc<SqliteConnection>,
+    actions: Vec<String>,
+    max_retries: u32,
+    retry_delay: Duration,
+}
+
+impl EventForwarder {
+    /// EventForwarder factory
+    pub fn new(
+        sinks: Vec<Arc<dyn EventSink>>,
+        connection: Arc<SqliteConnection>,
+        max_retries: u32,
+        retry_delay: Duration,
+    ) -> Self {
+        Self {
+            sinks,
+            connection,
+            actions: FORWARDED_EVENT_ACTIONS.map(String::from).to_vec(),
+            max_retries,
+            retry_delay,
+        }
+    }
+
+    /// Start the delivery to the sinks that never received an event after the latest event
+    /// already persisted, so the history of the Event Store is not replayed to a new sink.
+    pub fn initialize_cursors(&self) -> StdResult<()> {
+        let latest_event_id = self.get_latest_event_id()?;
+        for sink in &self.sinks {
+            if self.get_cursor(&sink.name())?.is_none() {
+                self.save_cursor(&sink.name(), latest_event_id)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Forward the pending events each time a new event is signaled, until the signaling
+    /// side is dropped.
+    pub async fn run(&self, mut new_event_receiver: watch::Receiver<()>) {
+        loop {
+            self.forward_pending_events().await;
+            if new_event_receiver.changed().await.is_err() {
+                // No more events will be persisted, forward the last ones before stopping
+                self.forward_pending_events().await;
+                break;
+            }
+        }
+    }
+
+    /// Forward to each sink the events persisted since the last event it received.
+    pub async fn forward_pending_events(&self) {
+        for sink in &self.sinks {
+            if let Err(error) = self.forward_pending_events_to_sink(sink.as_ref()).await {
+                warn!(
+                    "Event forwarder: events could not be delivered, they will be retried later";
+                    "sink" => sink.name(), "error" => ?error
+                );
+            }
+        }
+    }
+
+    async fn forward_pending_events_to_sink(&self, sink: &dyn EventSink) -> StdResult<()> {
+        let sink_name = sink.name();
+        let persister = EventPersister::new(self.connection.clone());
+        loop {
+            let cursor = self.get_cursor(&sink_name)?.unwrap_or_default();
+            let events =
+                persister.get_events_after(cursor, &self.actions, FORWARDING_BATCH_SIZE)?;
+            if events.is_empty() {
+                return Ok(());
+            }
+
+            for event in events {
+                self.publish_with_retries(sink, &PublishedEvent::from(&event))
+                    .await?;
+                self.save_cursor(&sink_name, event.event_id)?;
+                debug!(
+                    "Event forwarder: event ID={} delivered", event.event_id;
+                    "sink" => &sink_name
+                );
+            }
+        }
+    }
+
+    async fn publish_with_retries(
+        &self,
+        sink: &dyn EventSink,
+        event: &PublishedEvent,
+    ) -> StdResult<()> {
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+        loop {
+            match sink.publish(event).await {
+                Ok(()) => return Ok(()),
+                Err(error) if attempt < self.max_retries => {
+                    attempt += 1;
+                    debug!(
+                        "Event forwarder: delivery of event ID={} failed, retrying in {delay:?}",
+                        event.event_id;
+                        "sink" => sink.name(), "attempt" => attempt, "error" => ?error
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+                Err(error) => {
+                    return Err(error).with_context(|| {
+                        format!(
+                            "Delivery of event ID={} failed after {attempt} retries",
+                            event.event_id
+                        )
+                    })
+                }
+            }
+        }
+    }
+
+    fn get_latest_event_id(&self) -> StdResult<i64> {
+        let mut statement = self
+            .connection
+            .prepare("select coalesce(max(event_id), 0) from event")?;
+        statement.next()?;
+
+        Ok(statement.read::<i64, _>(0)?)
+    }
+
+    fn get_cursor(&self, sink_name: &str) -> StdResult<Option<i64>> {
+        let mut statement = self
+            .connection
+            .prepare("select last_event_id from event_sink_cursor where sink_name = ?")?;
+        statement.bind((1, sink_name))?;
+        match statement.next()? {
+            sqlite::State::Row => Ok(Some(statement.read::<i64, _>(0)?)),
+            sqlite::State::Done => Ok(None),
+        }
+    }
+
+    fn save_cursor(&self, sink_name: &str, last_event_id: i64) -> StdResult<()> {
+        let mut statement = self.connection.prepare(
+            "insert into event_sink_cursor (sink_name, last_event_id) values (?1, ?2) \
+            on conflict (sink_name) do update set last_event_id = excluded.last_event_id",
+        )?;
+        statement.bind((1, sink_name))?;
+        statement.bind((2, last_event_id))?;
+        statement.next()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use mithril_persistence::sqlite::ConnectionBuilder;
+    use std::sync::Mutex;
+
+    use crate::event_store::{migration, EventMessage, MockEventSink};
+
+    use super::*;
+
+    fn event_store_connection() -> Arc<SqliteConnection> {
+        let connection = ConnectionBuilder::open_memory()
+            .with_migrations(migration::get_migrations())
+            .build()
+            .unwrap();
+
+        Arc::new(connection)
+    }
+
+    fn persist_events(connection: &Arc<SqliteConnection>, actions: &[&str]) {
+        let persister = EventPersister::new(connection.clone());
+        for action in actions {
+            persister
+                .persist(EventMessage::new("Runtime", action, "{}"))
+                .unwrap();
+        }
+    }
+
+    fn recording_sink(
+        name: &str,
+        published_event_ids: Arc<Mutex<Vec<i64>>>,
+        failures_before_success: usize,
+    ) -> MockEventSink {
+        let mut sink = MockEventSink::new();
+        let name = name.to_string();
+        sink.expect_name().returning(move || name.clone());
+        let mut remaining_failures = failures_before_success;
+        sink.expect_publish().returning(move |event| {
+            if remaining_failures > 0 {
+                remaining_failures -= 1;
+                return Err(anyhow!("sink unavailable"));
+            }
+            published_event_ids.lock().unwrap().push(event.event_id);
+            Ok(())
+        });
+
+        sink
+    }
+
+    #[tokio::test]
+    async fn forward_only_the_forwarded_actions_persisted_after_the_initialization() {
+        let connection = event_store_connection();
+        persist_events(&connection, &["certificate_created"]);
+        let published_event_ids = Arc::new(Mutex::new(vec![]));
+        let forwarder = EventForwarder::new(
+            vec![Arc::new(recording_sink(
+                "sink",
+                published_event_ids.clone(),
+                0,
+            ))],
+            connection.clone(),
+            0,
+            Duration::ZERO,
+        );
+
+        forwarder.initialize_cursors().unwrap();
+        persist_events(
+            &connection,
+            &[
+                "certificate_created",
+                "register_signatures",
+                "artifact_created",
+            ],
+        );
+        forwarder.forward_pending_events().await;
+
+        assert_eq!(vec![2, 4], *published_event_ids.lock().unwrap());
+        assert_eq!(Some(4), forwarder.get_cursor("sink").unwrap());
+    }
+
+    #[tokio::test]
+    async fn delivery_is_retried_until_the_sink_accepts_the_event() {
+        let connection = event_store_connection();
+        let published_event_ids = Arc::new(Mutex::new(vec![]));
+        let forwarder = EventForwarder::new(
+            vec![Arc::new(recording_sink(
+                "sink",
+                published_event_ids.clone(),
+                2,
+            ))],
+            connection.clone(),
+            2,
+            Duration::from_millis(1),
+        );
+        forwarder.initialize_cursors().unwrap();
+        persist_events(&connection, &["certificate_created"]);
+
+        forwarder.forward_pending_events().await;
+
+        assert_eq!(vec![1], *published_event_ids.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn undelivered_events_are_forwarded_again_on_the_next_forwarding() {
+        let connection = event_store_connection();
+        let published_event_ids = Arc::new(Mutex::new(vec![]));
+        let forwarder = EventForwarder::new(
+            vec![Arc::new(recording_sink(
+                "sink",
+                published_event_ids.clone(),
+                1,
+            ))],
+            connection.clone(),
+            0,
+            Duration::ZERO,
+        );
+        forwarder.initialize_cursors().unwrap();
+        persist_events(&connection, &["certificate_created", "artifact_created"]);
+
+        forwarder.forward_pending_events().await;
+        assert!(published_event_ids.lock().unwrap().is_empty());
+        assert_eq!(Some(0), forwarder.get_cursor("sink").unwrap());
+
+        forwarder.forward_pending_events().await;
+        assert_eq!(vec![1, 2], *published_event_ids.lock().unwrap());
+    }
+}
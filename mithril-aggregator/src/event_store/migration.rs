@@ -0,0 +1,44 @@
+//! Migration module of the event store database
+//!
+use mithril_persistence::database::SqlMigration;
+
+/// Get all the migrations required by this version of the software.
+/// There shall be one migration per database version. There could be several
+/// statements per migration.
+pub fn get_migrations() -> Vec<SqlMigration> {
+    vec![
+        // Migration 1
+        // Add the `event` table, it may already exist if it was created by a previous version of
+        // the event store.
+        // Add indexes to query the events by action and by date.
+        SqlMigration::new(
+            1,
+            r#"
+create table if not exists event (
+    event_id integer primary key asc autoincrement,
+    created_at text not null,
+    source text not null,
+    action text not null,
+    content text
+);
+create index if not exists event_action_index on event(action);
+create index if not exists event_created_at_index on event(created_at);
+"#,
+        ),
+        // Migration 2
+        // Add the `event_sink_cursor` table to track the last event delivered to each external
+        // event sink.
+        SqlMigration::new_reversible(
+            2,
+            r#"
+create table event_sink_cursor (
+    sink_name text not null primary key,
+    last_event_id integer not null
+);
+"#,
+            r#"
+drop table event_sink_cursor;
+"#,
+        ),
+    ]
+}
@@ -2,9 +2,18 @@
 //! This module proposes tools to send messages between processes and how to
 //! persist them in a separate database.
 mod event;
+mod forwarder;
+pub mod migration;
 mod runner;
+mod sink;
 mod transmitter_service;
 
-pub use event::{Event, EventMessage, EventPersister};
+#[cfg(test)]
+pub use event::MockEventReader;
+pub use event::{Event, EventFilters, EventMessage, EventPersister, EventReader};
+pub use forwarder::{EventForwarder, FORWARDED_EVENT_ACTIONS};
 pub use runner::EventStore;
+#[cfg(test)]
+pub use sink::MockEventSink;
+pub use sink::{EventSink, NdjsonFileEventSink, PublishedEvent, WebhookEventSink};
 pub use transmitter_service::TransmitterService;
@@ -1,36 +1,58 @@
 use anyhow::Context;
 use slog_scope::{debug, info};
-use sqlite::Connection;
-use std::{path::PathBuf, sync::Arc};
-use tokio::sync::mpsc::UnboundedReceiver;
+use std::sync::Arc;
+use tokio::sync::{mpsc::UnboundedReceiver, watch};
 
 use mithril_common::StdResult;
+use mithril_persistence::sqlite::SqliteConnection;
 
-use super::{EventMessage, EventPersister};
+use super::{EventForwarder, EventMessage, EventPersister};
 
 /// EventMessage receiver service.
 pub struct EventStore {
     receiver: UnboundedReceiver<EventMessage>,
+    connection: Arc<SqliteConnection>,
+    forwarder: Option<Arc<EventForwarder>>,
 }
 
 impl EventStore {
-    /// Instanciate the EventMessage receiver service.
-    pub fn new(receiver: UnboundedReceiver<EventMessage>) -> Self {
-        Self { receiver }
+    /// Instanciate the EventMessage receiver service, the events are persisted using the given
+    /// connection to the event store database.
+    pub fn new(
+        receiver: UnboundedReceiver<EventMessage>,
+        connection: Arc<SqliteConnection>,
+    ) -> Self {
+        Self {
+            receiver,
+            connection,
+            forwarder: None,
+        }
+    }
+
+    /// Forward the persisted events to external sinks using the given [EventForwarder].
+    pub fn with_forwarder(mut self, forwarder: EventForwarder) -> Self {
+        self.forwarder = Some(Arc::new(forwarder));
+        self
     }
 
     /// Launch the service. It runs until all the transmitters are gone and all
     /// messages have been processed. This means this service shall be waited
     /// upon completion to ensure all events are properly saved in the database.
-    pub async fn run(&mut self, file: Option<PathBuf>) -> StdResult<()> {
-        let connection = {
-            let connection = match file {
-                Some(path) => Connection::open_thread_safe(path)?,
-                None => Connection::open_thread_safe(":memory:")?,
-            };
-            Arc::new(connection)
+    pub async fn run(&mut self) -> StdResult<()> {
+        let persister = EventPersister::new(self.connection.clone());
+        let (new_event_sender, forwarding_thread) = match &self.forwarder {
+            Some(forwarder) => {
+                forwarder
+                    .initialize_cursors()
+                    .with_context(|| "event forwarder initialization failure")?;
+                let (new_event_sender, new_event_receiver) = watch::channel(());
+                let forwarder = forwarder.clone();
+                let forwarding_thread =
+                    tokio::spawn(async move { forwarder.run(new_event_receiver).await });
+                (Some(new_event_sender), Some(forwarding_thread))
+            }
+            None => (None, None),
         };
-        let persister = EventPersister::new(connection);
         info!("monitoring: starting event loop to log messages.");
         loop {
             if let Some(message) = self.receiver.recv().await {
@@ -39,12 +61,23 @@ impl EventStore {
                     .persist(message)
                     .with_context(|| "event persist failure")?;
                 debug!("event ID={} created", event.event_id);
+                if let Some(new_event_sender) = &new_event_sender {
+                    new_event_sender.send_replace(());
+                }
             } else {
                 info!("No more events to proceed, quitting…");
                 break;
             }
         }
 
+        // Dropping the sender stops the forwarder once the last events are forwarded
+        drop(new_event_sender);
+        if let Some(forwarding_thread) = forwarding_thread {
+            forwarding_thread
+                .await
+                .with_context(|| "event forwarder failure")?;
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,222 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::{path::PathBuf, time::Duration};
+use tokio::io::AsyncWriteExt;
+
+use mithril_common::StdResult;
+
+use super::Event;
+
+#[cfg(test)]
+use mockall::automock;
+
+/// Event as published to the external [sinks][EventSink].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PublishedEvent {
+    /// Sequential number of the event in the Event Store, an event delivered more than once
+    /// keeps the same number.
+    pub event_id: i64,
+
+    /// Date of creation of the event.
+    pub created_at: DateTime<Utc>,
+
+    /// Source of the event.
+    pub source: String,
+
+    /// Action of the event, it represents the type of its content.
+    pub action: String,
+
+    /// JSON content of the event.
+    pub content: serde_json::Value,
+}
+
+impl From<&Event> for PublishedEvent {
+    fn from(event: &Event) -> Self {
+        Self {
+            event_id: event.event_id,
+            created_at: event.created_at,
+            source: event.source.clone(),
+            action: event.action.clone(),
+            content: serde_json::from_str(&event.content)
+                .unwrap_or_else(|_| serde_json::Value::String(event.content.clone())),
+        }
+    }
+}
+
+/// External system to which the events of the Event Store are forwarded.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait EventSink: Sync + Send {
+    /// Name of the sink, used to keep track of the last event delivered to it.
+    fn name(&self) -> String;
+
+    /// Publish an event to the sink, returns once the sink has acknowledged it.
+    async fn publish(&self, event: &PublishedEvent) -> StdResult<()>;
+}
+
+/// [EventSink] that sends each event as JSON in the body of a `POST` request to a webhook.
+pub struct WebhookEventSink {
+    url: String,
+    http_client: reqwest::Client,
+}
+
+impl WebhookEventSink {
+    /// Timeout of a request sent to the webhook.
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// WebhookEventSink factory
+    pub fn new(url: &str) -> StdResult<Self> {
+        let http_client = reqwest::ClientBuilder::new()
+            .timeout(Self::REQUEST_TIMEOUT)
+            .build()
+            .with_context(|| "Building http client for the event webhook failed")?;
+
+        Ok(Self {
+            url: url.to_string(),
+            http_client,
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for WebhookEventSink {
+    fn name(&self) -> String {
+        format!("webhook:{}", self.url)
+    }
+
+    async fn publish(&self, event: &PublishedEvent) -> StdResult<()> {
+        self.http_client
+            .post(&self.url)
+            .json(event)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Event webhook '{}' did not accept the event", self.url))?;
+
+        Ok(())
+    }
+}
+
+/// [EventSink] that appends each event as a JSON line to a file (NDJSON).
+pub struct NdjsonFileEventSink {
+    path: PathBuf,
+}
+
+impl NdjsonFileEventSink {
+    /// NdjsonFileEventSink factory
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait]
+impl EventSink for NdjsonFileEventSink {
+    fn name(&self) -> String {
+        format!("ndjson_file:{}", self.path.display())
+    }
+
+    async fn publish(&self, event: &PublishedEvent) -> StdResult<()> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .with_context(|| format!("Could not open event file '{}'", self.path.display()))?;
+        file.write_all(&line).await?;
+        file.flush().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpmock::MockServer;
+    use mithril_common::test_utils::TempDir;
+
+    use super::*;
+
+    fn event(event_id: i64, content: &str) -> Event {
+        Event {
+            event_id,
+            created_at: DateTime::parse_from_rfc3339("2024-02-01T10:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            source: "Runtime".to_string(),
+            action: "certificate_created".to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn published_event_content_is_parsed_as_json_when_possible() {
+        let published_event = PublishedEvent::from(&event(1, r#"{"content": {"epoch": 12}}"#));
+        assert_eq!(
+            serde_json::json!({"content": {"epoch": 12}}),
+            published_event.content
+        );
+
+        let published_event = PublishedEvent::from(&event(2, "not json"));
+        assert_eq!(
+            serde_json::Value::String("not json".to_string()),
+            published_event.content
+        );
+    }
+
+    #[tokio::test]
+    async fn ndjson_file_sink_appends_one_line_per_event() {
+        let path = TempDir::create("aggregator_event_sink", "ndjson_file_sink").join("events.json");
+        let sink = NdjsonFileEventSink::new(path.clone());
+
+        sink.publish(&PublishedEvent::from(&event(1, r#"{"a": 1}"#)))
+            .await
+            .unwrap();
+        sink.publish(&PublishedEvent::from(&event(2, r#"{"b": 2}"#)))
+            .await
+            .unwrap();
+
+        let lines = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<serde_json::Value>(line).unwrap()["event_id"].clone()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(vec![serde_json::json!(1), serde_json::json!(2)], lines);
+    }
+
+    #[tokio::test]
+    async fn webhook_sink_posts_the_event_as_json() {
+        let server = MockServer::start();
+        let event = PublishedEvent::from(&event(1, r#"{"a": 1}"#));
+        let webhook_mock = server.mock(|when, then| {
+            when.method(httpmock::Method::POST)
+                .path("/events")
+                .json_body(serde_json::to_value(&event).unwrap());
+            then.status(204);
+        });
+        let sink = WebhookEventSink::new(&server.url("/events")).unwrap();
+
+        sink.publish(&event).await.unwrap();
+
+        webhook_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn webhook_sink_fails_if_the_webhook_does_not_accept_the_event() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(httpmock::Method::POST).path("/events");
+            then.status(503);
+        });
+        let sink = WebhookEventSink::new(&server.url("/events")).unwrap();
+
+        sink.publish(&PublishedEvent::from(&event(1, "{}")))
+            .await
+            .expect_err("publish should fail when the webhook answers with an error status");
+    }
+}
@@ -0,0 +1,490 @@
+use std::sync::Arc;
+use warp::Filter;
+
+use crate::http_server::routes::middlewares;
+use crate::DependencyContainer;
+
+pub fn routes(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    log_levels(dependency_manager.clone())
+        .or(update_log_levels(dependency_manager.clone()))
+        .or(trigger_runtime_cycle(dependency_manager))
+}
+
+/// GET /admin/log-levels
+fn log_levels(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("admin" / "log-levels")
+        .and(warp::get())
+        .and(warp::header::optional::<String>("authorization"))
+        .and(middlewares::with_config(dependency_manager.clone()))
+        .and(middlewares::with_log_levels(dependency_manager))
+        .and_then(handlers::log_levels)
+}
+
+/// PUT /admin/log-levels
+fn update_log_levels(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("admin" / "log-levels")
+        .and(warp::put())
+        .and(warp::header::optional::<String>("authorization"))
+        .and(middlewares::with_config(dependency_manager.clone()))
+        .and(warp::body::json())
+        .and(middlewares::with_log_levels(dependency_manager))
+        .and_then(handlers::update_log_levels)
+}
+
+/// POST /admin/runtime/cycle
+fn trigger_runtime_cycle(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("admin" / "runtime" / "cycle")
+        .and(warp::post())
+        .and(warp::header::optional::<String>("authorization"))
+        .and(middlewares::with_config(dependency_manager.clone()))
+        .and(warp::body::json())
+        .and(middlewares::with_runtime_cycle_trigger(dependency_manager))
+        .and_then(handlers::trigger_runtime_cycle)
+}
+
+mod handlers {
+    use mithril_common::entities::ClientError;
+    use mithril_common::logging::{parse_level, ComponentLogLevels};
+    use slog_scope::{debug, info, warn};
+    use std::convert::Infallible;
+    use std::sync::Arc;
+    use warp::http::StatusCode;
+
+    use mithril_common::messages::{LogLevelsMessage, LogLevelsUpdateMessage};
+
+    use crate::entities::{RuntimeCycleTriggerMessage, RuntimeCycleTriggeredMessage};
+    use crate::http_server::routes::reply;
+    use crate::{Configuration, RuntimeCycleTrigger};
+
+    /// Check the bearer token of an admin request, returns the reply to send if it's rejected
+    fn check_authorization(
+        authorization: Option<String>,
+        config: &Configuration,
+    ) -> Option<Box<dyn warp::Reply>> {
+        match reply::check_bearer_token(authorization.as_deref(), config.admin_api_token.as_ref()) {
+            Ok(()) => None,
+            // The admin routes are hidden when they are disabled
+            Err(StatusCode::NOT_FOUND) => Some(reply::empty(StatusCode::NOT_FOUND)),
+            Err(status) => {
+                warn!("admin::unauthorized");
+                Some(reply::empty(status))
+            }
+        }
+    }
+
+    /// Log levels
+    pub async fn log_levels(
+        authorization: Option<String>,
+        config: Configuration,
+        log_levels: ComponentLogLevels,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: admin/log_levels");
+
+        if let Some(rejection) = check_authorization(authorization, &config) {
+            return Ok(rejection);
+        }
+
+        Ok(reply::json(
+            &LogLevelsMessage::from(&log_levels),
+            StatusCode::OK,
+        ))
+    }
+
+    /// Update log levels
+    pub async fn update_log_levels(
+        authorization: Option<String>,
+        config: Configuration,
+        message: LogLevelsUpdateMessage,
+        log_levels: ComponentLogLevels,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: admin/update_log_levels"; "message" => ?message);
+
+        if let Some(rejection) = check_authorization(authorization, &config) {
+            return Ok(rejection);
+        }
+
+        // All the levels are parsed before applying any of them, so an invalid update is not
+        // partially applied.
+        let mut levels = vec![];
+        for (component, level) in message.components {
+            match level.as_deref().map(parse_level).transpose() {
+                Ok(level) => levels.push((component, level)),
+                Err(error) => {
+                    warn!("admin::update_log_levels::bad_request"; "error" => ?error);
+                    return Ok(reply::bad_request(
+                        "invalid_log_level".to_string(),
+                        format!("invalid level for component '{component}': {error}"),
+                    ));
+                }
+            }
+        }
+        for (component, level) in levels {
+            info!("admin: log level updated"; "component" => %component, "level" => ?level);
+            log_levels.set_component_level(component, level);
+        }
+
+        Ok(reply::json(
+            &LogLevelsMessage::from(&log_levels),
+            StatusCode::OK,
+        ))
+    }
+
+    /// Trigger an immediate cycle of the runtime
+    pub async fn trigger_runtime_cycle(
+        authorization: Option<String>,
+        config: Configuration,
+        message: RuntimeCycleTriggerMessage,
+        runtime_cycle_trigger: Arc<RuntimeCycleTrigger>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: admin/trigger_runtime_cycle"; "message" => ?message);
+
+        if let Some(rejection) = check_authorization(authorization, &config) {
+            return Ok(rejection);
+        }
+
+        let state = runtime_cycle_trigger.get_state();
+        match runtime_cycle_trigger.trigger(message.step) {
+            Ok(()) => {
+                info!("admin: runtime cycle triggered"; "step" => ?message.step, "state" => &state);
+                Ok(reply::json(
+                    &RuntimeCycleTriggeredMessage {
+                        step: message.step,
+                        state,
+                    },
+                    StatusCode::ACCEPTED,
+                ))
+            }
+            Err(error) => {
+                warn!("admin::trigger_runtime_cycle::conflict"; "error" => ?error);
+                Ok(reply::json(
+                    &ClientError::new("runtime_cycle_not_triggered".to_string(), error.to_string()),
+                    StatusCode::CONFLICT,
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::logging::{ComponentLogLevels, LogComponent};
+    use mithril_common::test_utils::apispec::APISpec;
+    use serde_json::Value::Null;
+    use slog::Level;
+    use warp::{
+        http::{Method, StatusCode},
+        test::request,
+    };
+
+    use mithril_common::messages::{LogLevelsMessage, LogLevelsUpdateMessage};
+    use mithril_secrets::SecretString;
+
+    use crate::entities::{RuntimeCycleTriggerMessage, RuntimeCycleTriggeredMessage};
+    use crate::{http_server::SERVER_BASE_PATH, initialize_dependencies, RuntimeStep};
+
+    use super::*;
+
+    const ADMIN_API_TOKEN: &str = "admin-secret";
+
+    fn setup_router(
+        dependency_manager: Arc<DependencyContainer>,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let cors = warp::cors()
+            .allow_any_origin()
+            .allow_headers(vec!["content-type", "authorization"])
+            .allow_methods(vec![
+                Method::GET,
+                Method::PUT,
+                Method::POST,
+                Method::OPTIONS,
+            ]);
+
+        warp::any()
+            .and(warp::path(SERVER_BASE_PATH))
+            .and(routes(dependency_manager).with(cors))
+    }
+
+    async fn setup_dependencies(log_levels: ComponentLogLevels) -> Arc<DependencyContainer> {
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.config.admin_api_token = Some(SecretString::new(ADMIN_API_TOKEN));
+        dependency_manager.log_levels = log_levels;
+
+        Arc::new(dependency_manager)
+    }
+
+    #[tokio::test]
+    async fn test_log_levels_get_ok() {
+        let log_levels = ComponentLogLevels::new(Level::Info);
+        log_levels.set_component_level(LogComponent::Prover, Some(Level::Debug));
+
+        let method = Method::GET.as_str();
+        let path = "/admin/log-levels";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header("authorization", format!("Bearer {ADMIN_API_TOKEN}"))
+            .reply(&setup_router(setup_dependencies(log_levels.clone()).await))
+            .await;
+
+        assert_eq!(
+            LogLevelsMessage::from(&log_levels),
+            serde_json::from_slice(response.body()).unwrap()
+        );
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_log_levels_get_without_valid_token() {
+        let method = Method::GET.as_str();
+        let path = "/admin/log-levels";
+        let router = setup_router(setup_dependencies(ComponentLogLevels::new(Level::Info)).await);
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&router)
+            .await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header("authorization", "Bearer not-the-token")
+            .reply(&router)
+            .await;
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::UNAUTHORIZED,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_log_levels_get_when_admin_routes_are_disabled() {
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.config.admin_api_token = None;
+
+        let method = Method::GET.as_str();
+        let path = "/admin/log-levels";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header("authorization", format!("Bearer {ADMIN_API_TOKEN}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::NOT_FOUND,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_log_levels_put_ok() {
+        let log_levels = ComponentLogLevels::new(Level::Info);
+        log_levels.set_component_level(LogComponent::Runtime, Some(Level::Debug));
+        let message = LogLevelsUpdateMessage {
+            components: [
+                (LogComponent::Certifier, Some("trace".to_string())),
+                (LogComponent::Runtime, None),
+            ]
+            .into(),
+        };
+
+        let method = Method::PUT.as_str();
+        let path = "/admin/log-levels";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header("authorization", format!("Bearer {ADMIN_API_TOKEN}"))
+            .json(&message)
+            .reply(&setup_router(setup_dependencies(log_levels.clone()).await))
+            .await;
+
+        assert_eq!(Level::Trace, log_levels.level(LogComponent::Certifier));
+        assert_eq!(Level::Info, log_levels.level(LogComponent::Runtime));
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_log_levels_put_with_invalid_level_does_not_update_any_level() {
+        let log_levels = ComponentLogLevels::new(Level::Info);
+        let message = LogLevelsUpdateMessage {
+            components: [
+                (LogComponent::Certifier, Some("debug".to_string())),
+                (LogComponent::Http, Some("verbose".to_string())),
+            ]
+            .into(),
+        };
+
+        let method = Method::PUT.as_str();
+        let path = "/admin/log-levels";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header("authorization", format!("Bearer {ADMIN_API_TOKEN}"))
+            .json(&message)
+            .reply(&setup_router(setup_dependencies(log_levels.clone()).await))
+            .await;
+
+        assert!(log_levels.component_levels().is_empty());
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::BAD_REQUEST,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_trigger_runtime_cycle_post_ok() {
+        let dependency_manager = setup_dependencies(ComponentLogLevels::new(Level::Info)).await;
+        dependency_manager
+            .runtime_cycle_trigger
+            .set_state("signing".to_string());
+        let message = RuntimeCycleTriggerMessage {
+            step: RuntimeStep::BuildArtifact,
+        };
+
+        let method = Method::POST.as_str();
+        let path = "/admin/runtime/cycle";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header("authorization", format!("Bearer {ADMIN_API_TOKEN}"))
+            .json(&message)
+            .reply(&setup_router(dependency_manager.clone()))
+            .await;
+
+        assert_eq!(
+            RuntimeCycleTriggeredMessage {
+                step: RuntimeStep::BuildArtifact,
+                state: "signing".to_string(),
+            },
+            serde_json::from_slice(response.body()).unwrap()
+        );
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::ACCEPTED,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_trigger_runtime_cycle_post_when_a_cycle_is_already_triggered() {
+        let dependency_manager = setup_dependencies(ComponentLogLevels::new(Level::Info)).await;
+        dependency_manager
+            .runtime_cycle_trigger
+            .trigger(RuntimeStep::Cycle)
+            .unwrap();
+        let message = RuntimeCycleTriggerMessage::default();
+
+        let method = Method::POST.as_str();
+        let path = "/admin/runtime/cycle";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header("authorization", format!("Bearer {ADMIN_API_TOKEN}"))
+            .json(&message)
+            .reply(&setup_router(dependency_manager))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::CONFLICT,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_trigger_runtime_cycle_post_without_valid_token() {
+        let dependency_manager = setup_dependencies(ComponentLogLevels::new(Level::Info)).await;
+        let message = RuntimeCycleTriggerMessage::default();
+
+        let method = Method::POST.as_str();
+        let path = "/admin/runtime/cycle";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header("authorization", "Bearer not-the-token")
+            .json(&message)
+            .reply(&setup_router(dependency_manager.clone()))
+            .await;
+
+        assert_eq!(
+            None,
+            dependency_manager
+                .runtime_cycle_trigger
+                .wait(std::time::Duration::from_millis(1))
+                .await
+        );
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::UNAUTHORIZED,
+        )
+        .unwrap();
+    }
+}
@@ -16,6 +16,7 @@ fn artifact_cardano_transactions(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("artifact" / "cardano-transactions")
         .and(warp::get())
+        .and(middlewares::with_client_api_version())
         .and(middlewares::with_http_message_service(dependency_manager))
         .and_then(handlers::list_artifacts)
 }
@@ -34,6 +35,7 @@ pub mod handlers {
     use crate::http_server::routes::reply;
     use crate::services::MessageService;
 
+    use semver::Version;
     use slog_scope::{debug, warn};
     use std::convert::Infallible;
     use std::sync::Arc;
@@ -43,6 +45,7 @@ pub mod handlers {
 
     /// List Cardano Transactions set artifacts
     pub async fn list_artifacts(
+        client_api_version: Option<Version>,
         http_message_service: Arc<dyn MessageService>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: artifacts");
@@ -51,7 +54,7 @@ pub mod handlers {
             .get_cardano_transaction_list_message(LIST_MAX_ITEMS)
             .await
         {
-            Ok(message) => Ok(reply::json(&message, StatusCode::OK)),
+            Ok(message) => Ok(reply::json_list(message, client_api_version)),
             Err(err) => {
                 warn!("list_artifacts_cardano_transactions"; "error" => ?err);
 
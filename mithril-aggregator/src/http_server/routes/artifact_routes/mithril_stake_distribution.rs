@@ -17,6 +17,8 @@ fn artifact_mithril_stake_distributions(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("artifact" / "mithril-stake-distributions")
         .and(warp::get())
+        .and(middlewares::with_client_api_version())
+        .and(middlewares::with_if_none_match())
         .and(middlewares::with_http_message_service(dependency_manager))
         .and_then(handlers::list_artifacts)
 }
@@ -27,6 +29,7 @@ fn artifact_mithril_stake_distribution_by_id(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("artifact" / "mithril-stake-distribution" / String)
         .and(warp::get())
+        .and(middlewares::with_if_none_match())
         .and(middlewares::with_http_message_service(dependency_manager))
         .and_then(handlers::get_artifact_by_signed_entity_id)
 }
@@ -35,6 +38,7 @@ pub mod handlers {
     use crate::http_server::routes::reply;
     use crate::services::MessageService;
 
+    use semver::Version;
     use slog_scope::{debug, warn};
     use std::convert::Infallible;
     use std::sync::Arc;
@@ -44,6 +48,8 @@ pub mod handlers {
 
     /// List MithrilStakeDistribution artifacts
     pub async fn list_artifacts(
+        client_api_version: Option<Version>,
+        if_none_match: Option<String>,
         http_message_service: Arc<dyn MessageService>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: artifacts");
@@ -52,7 +58,11 @@ pub mod handlers {
             .get_mithril_stake_distribution_list_message(LIST_MAX_ITEMS)
             .await
         {
-            Ok(message) => Ok(reply::json(&message, StatusCode::OK)),
+            Ok(message) => Ok(reply::json_list_with_etag(
+                message,
+                client_api_version,
+                if_none_match,
+            )),
             Err(err) => {
                 warn!("list_artifacts_mithril_stake_distribution"; "error" => ?err);
 
@@ -64,6 +74,7 @@ pub mod handlers {
     /// Get Artifact by signed entity id
     pub async fn get_artifact_by_signed_entity_id(
         signed_entity_id: String,
+        if_none_match: Option<String>,
         http_message_service: Arc<dyn MessageService>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: artifact/{signed_entity_id}");
@@ -72,7 +83,7 @@ pub mod handlers {
             .get_mithril_stake_distribution_message(&signed_entity_id)
             .await
         {
-            Ok(Some(message)) => Ok(reply::json(&message, StatusCode::OK)),
+            Ok(Some(message)) => Ok(reply::json_with_etag(&message, if_none_match)),
             Ok(None) => {
                 warn!("get_mithril_stake_distribution_details::not_found");
                 Ok(reply::empty(StatusCode::NOT_FOUND))
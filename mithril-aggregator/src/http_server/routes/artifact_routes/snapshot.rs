@@ -1,6 +1,7 @@
 use crate::http_server::routes::middlewares;
 use crate::http_server::SERVER_BASE_PATH;
-use crate::DependencyContainer;
+use crate::snapshot_uploaders::{MANIFESTS_DIRECTORY, OBJECTS_DIRECTORY};
+use crate::{DependencyContainer, SnapshotUploaderType};
 use std::sync::Arc;
 use warp::hyper::Uri;
 use warp::Filter;
@@ -13,7 +14,10 @@ pub fn routes(
             dependency_manager.clone(),
         ))
         .or(serve_snapshots_dir(dependency_manager.clone()))
-        .or(snapshot_download(dependency_manager))
+        .or(serve_snapshot_manifests_dir(dependency_manager.clone()))
+        .or(serve_snapshot_objects_dir(dependency_manager.clone()))
+        .or(snapshot_download(dependency_manager.clone()))
+        .or(snapshot_signed_download(dependency_manager))
         .or(artifact_cardano_full_immutable_snapshots_legacy())
         .or(artifact_cardano_full_immutable_snapshot_by_id_legacy())
 }
@@ -24,6 +28,8 @@ fn artifact_cardano_full_immutable_snapshots(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("artifact" / "snapshots")
         .and(warp::get())
+        .and(middlewares::with_client_api_version())
+        .and(middlewares::with_if_none_match())
         .and(middlewares::with_http_message_service(dependency_manager))
         .and_then(handlers::list_artifacts)
 }
@@ -34,32 +40,120 @@ fn artifact_cardano_full_immutable_snapshot_by_id(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("artifact" / "snapshot" / String)
         .and(warp::get())
+        .and(middlewares::with_if_none_match())
         .and(middlewares::with_http_message_service(dependency_manager))
         .and_then(handlers::get_artifact_by_signed_entity_id)
 }
 
-/// GET /artifact/snapshots/{digest}/download
+/// GET /artifact/snapshot/{digest}/download
 fn snapshot_download(
     dependency_manager: Arc<DependencyContainer>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("artifact" / "snapshot" / String / "download")
         .and(warp::get().or(warp::head()).unify())
-        .and(middlewares::with_config(dependency_manager.clone()))
+        .and(middlewares::with_artifact_storage(
+            dependency_manager.clone(),
+        ))
         .and(middlewares::with_signed_entity_service(dependency_manager))
         .and_then(handlers::snapshot_download)
 }
 
+/// GET /artifact/snapshot/{digest}/download/signed
+fn snapshot_signed_download(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("artifact" / "snapshot" / String / "download" / "signed")
+        .and(warp::get().or(warp::head()).unify())
+        .and(middlewares::with_artifact_storage(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_signed_entity_service(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_config(dependency_manager))
+        .and_then(handlers::snapshot_signed_download)
+}
+
+/// GET /snapshot_download/{file_name}
 fn serve_snapshots_dir(
     dependency_manager: Arc<DependencyContainer>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    let config = dependency_manager.config.clone();
+    // The archives stored in a remote artifact storage are downloaded directly from it
+    let local_directory = dependency_manager.artifact_storage.local_directory();
+    let is_stored_locally = local_directory.is_some();
 
     warp::path("snapshot_download")
-        .and(warp::fs::dir(config.snapshot_directory))
-        .and(middlewares::with_signed_entity_service(dependency_manager))
+        .and(warp::any().and_then(move || async move {
+            if is_stored_locally {
+                Ok(())
+            } else {
+                Err(warp::reject::not_found())
+            }
+        }))
+        .untuple_one()
+        .and(warp::fs::dir(local_directory.unwrap_or_default()))
+        .and(middlewares::with_signed_entity_service(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_config(dependency_manager.clone()))
+        .and(middlewares::with_file_digest_cache(dependency_manager))
         .and_then(handlers::ensure_downloaded_file_is_a_snapshot)
 }
 
+/// Reject the requests if the snapshots are not stored by the deduplicating snapshot uploader
+fn with_deduplicated_snapshot_storage(
+    dependency_manager: &DependencyContainer,
+) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    let is_deduplicated =
+        dependency_manager.config.snapshot_uploader_type == SnapshotUploaderType::Deduplicated;
+
+    warp::any()
+        .and_then(move || async move {
+            if is_deduplicated {
+                Ok(())
+            } else {
+                Err(warp::reject::not_found())
+            }
+        })
+        .untuple_one()
+}
+
+/// GET /snapshot_manifest/{digest}.manifest.json
+fn serve_snapshot_manifests_dir(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    let config = dependency_manager.config.clone();
+
+    warp::path("snapshot_manifest")
+        .and(with_deduplicated_snapshot_storage(&dependency_manager))
+        .and(warp::fs::dir(
+            config.snapshot_directory.join(MANIFESTS_DIRECTORY),
+        ))
+        .and(middlewares::with_signed_entity_service(dependency_manager))
+        .and_then(handlers::ensure_downloaded_file_is_a_signed_snapshot_manifest)
+}
+
+/// GET /snapshot_objects/{digest}/{object_hash}
+fn serve_snapshot_objects_dir(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    let config = dependency_manager.config.clone();
+
+    warp::path("snapshot_objects")
+        .and(with_deduplicated_snapshot_storage(&dependency_manager))
+        .and(warp::path::param::<String>())
+        .and(warp::fs::dir(
+            config.snapshot_directory.join(OBJECTS_DIRECTORY),
+        ))
+        .and(middlewares::with_signed_entity_service(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_snapshot_manifest_cache(
+            dependency_manager,
+        ))
+        .and_then(handlers::ensure_downloaded_object_belongs_to_a_signed_snapshot)
+}
+
 /// GET /snapshots
 // TODO: This legacy route should be removed when this code is released with a new distribution
 fn artifact_cardano_full_immutable_snapshots_legacy(
@@ -89,20 +183,28 @@ fn artifact_cardano_full_immutable_snapshot_by_id_legacy(
 }
 
 mod handlers {
+    use crate::artifact_storage::ArtifactStorage;
     use crate::http_server::routes::reply;
-    use crate::http_server::SERVER_BASE_PATH;
     use crate::services::MessageService;
-    use crate::{services::SignedEntityService, Configuration};
+    use crate::services::SignedEntityService;
+    use crate::tools::{FileDigestCache, SnapshotManifestCache};
+    use crate::Configuration;
+    use mithril_common::entities::{Snapshot, SnapshotManifest};
+    use semver::Version;
     use slog_scope::{debug, warn};
     use std::convert::Infallible;
     use std::str::FromStr;
     use std::sync::Arc;
-    use warp::http::{StatusCode, Uri};
+    use std::time::Duration;
+    use warp::http::{HeaderValue, StatusCode, Uri};
+    use warp::Reply;
 
     pub const LIST_MAX_ITEMS: usize = 20;
 
     /// List Snapshot artifacts
     pub async fn list_artifacts(
+        client_api_version: Option<Version>,
+        if_none_match: Option<String>,
         http_message_service: Arc<dyn MessageService>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: artifacts");
@@ -111,7 +213,11 @@ mod handlers {
             .get_snapshot_list_message(LIST_MAX_ITEMS)
             .await
         {
-            Ok(message) => Ok(reply::json(&message, StatusCode::OK)),
+            Ok(message) => Ok(reply::json_list_with_etag(
+                message,
+                client_api_version,
+                if_none_match,
+            )),
             Err(err) => {
                 warn!("list_artifacts_snapshot"; "error" => ?err);
                 Ok(reply::internal_server_error(err))
@@ -122,6 +228,7 @@ mod handlers {
     /// Get Artifact by signed entity id
     pub async fn get_artifact_by_signed_entity_id(
         signed_entity_id: String,
+        if_none_match: Option<String>,
         http_message_service: Arc<dyn MessageService>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: artifact/{signed_entity_id}");
@@ -129,7 +236,7 @@ mod handlers {
             .get_snapshot_message(&signed_entity_id)
             .await
         {
-            Ok(Some(signed_entity)) => Ok(reply::json(&signed_entity, StatusCode::OK)),
+            Ok(Some(signed_entity)) => Ok(reply::json_with_etag(&signed_entity, if_none_match)),
             Ok(None) => {
                 warn!("snapshot_details::not_found");
                 Ok(reply::empty(StatusCode::NOT_FOUND))
@@ -142,9 +249,14 @@ mod handlers {
     }
 
     /// Download a file if and only if it's a snapshot archive
+    ///
+    /// If enabled, the SHA-256 of the archive is added in a `Repr-Digest` header (RFC 9530) when
+    /// it's already computed, otherwise its computation is started in background.
     pub async fn ensure_downloaded_file_is_a_snapshot(
         reply: warp::fs::File,
         signed_entity_service: Arc<dyn SignedEntityService>,
+        config: Configuration,
+        file_digest_cache: Arc<FileDigestCache>,
     ) -> Result<impl warp::Reply, Infallible> {
         let filepath = reply.path().to_path_buf();
         debug!(
@@ -157,14 +269,36 @@ mod handlers {
                 .get_signed_snapshot_by_id(&digest)
                 .await
             {
-                Ok(Some(_)) => Ok(Box::new(warp::reply::with_header(
-                    reply,
-                    "Content-Disposition",
-                    format!(
-                        "attachment; filename=\"{}\"",
-                        filepath.file_name().unwrap().to_str().unwrap()
-                    ),
-                )) as Box<dyn warp::Reply>),
+                Ok(Some(_)) => {
+                    let mut response = warp::reply::with_header(
+                        reply,
+                        "Content-Disposition",
+                        format!(
+                            "attachment; filename=\"{}\"",
+                            filepath.file_name().unwrap().to_str().unwrap()
+                        ),
+                    )
+                    .into_response();
+
+                    if config.snapshot_download_digest_header {
+                        match file_digest_cache.get(&filepath) {
+                            Some(digest) => {
+                                if let Ok(value) =
+                                    HeaderValue::from_str(&format!("sha-256=:{digest}:"))
+                                {
+                                    response.headers_mut().insert("Repr-Digest", value);
+                                }
+                            }
+                            None => {
+                                // The archive is served right away, the header is added to the
+                                // next downloads once the digest is computed
+                                file_digest_cache.compute_in_background(&filepath);
+                            }
+                        }
+                    }
+
+                    Ok(Box::new(response) as Box<dyn warp::Reply>)
+                }
                 _ => Ok(reply::empty(StatusCode::NOT_FOUND)),
             },
             Err(err) => {
@@ -174,10 +308,98 @@ mod handlers {
         }
     }
 
+    /// Download a snapshot manifest if and only if its snapshot is signed
+    pub async fn ensure_downloaded_file_is_a_signed_snapshot_manifest(
+        reply: warp::fs::File,
+        signed_entity_service: Arc<dyn SignedEntityService>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let filepath = reply.path().to_path_buf();
+        debug!(
+            "⇄ HTTP SERVER: ensure_downloaded_file_is_a_signed_snapshot_manifest / file: `{}`",
+            filepath.display()
+        );
+
+        let manifest_suffix = format!(".{}", SnapshotManifest::FILE_EXTENSION);
+        let digest = match filepath
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .and_then(|file_name| file_name.strip_suffix(&manifest_suffix))
+        {
+            Some(digest) => digest,
+            None => return Ok(reply::empty(StatusCode::NOT_FOUND)),
+        };
+
+        match signed_entity_service
+            .get_signed_snapshot_by_id(digest)
+            .await
+        {
+            Ok(Some(_)) => Ok(Box::new(reply) as Box<dyn warp::Reply>),
+            Ok(None) => Ok(reply::empty(StatusCode::NOT_FOUND)),
+            Err(err) => {
+                warn!("ensure_downloaded_file_is_a_signed_snapshot_manifest::error"; "error" => ?err);
+                Ok(reply::internal_server_error(err))
+            }
+        }
+    }
+
+    /// Download an object if and only if it's a file of the given signed snapshot, or its
+    /// ancillary archive
+    pub async fn ensure_downloaded_object_belongs_to_a_signed_snapshot(
+        digest: String,
+        reply: warp::fs::File,
+        signed_entity_service: Arc<dyn SignedEntityService>,
+        snapshot_manifest_cache: Arc<SnapshotManifestCache>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let object_hash = reply
+            .path()
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .unwrap_or_default()
+            .to_string();
+        debug!(
+            "⇄ HTTP SERVER: ensure_downloaded_object_belongs_to_a_signed_snapshot / digest: `{digest}`, object: `{object_hash}`"
+        );
+
+        let signed_entity = match signed_entity_service
+            .get_signed_snapshot_by_id(&digest)
+            .await
+        {
+            Ok(Some(signed_entity)) => signed_entity,
+            Ok(None) => return Ok(reply::empty(StatusCode::NOT_FOUND)),
+            Err(err) => {
+                warn!("ensure_downloaded_object_belongs_to_a_signed_snapshot::error"; "error" => ?err);
+                return Ok(reply::internal_server_error(err));
+            }
+        };
+
+        let ancillary_object_suffix = format!("/snapshot_objects/{digest}/{object_hash}");
+        let is_ancillary_archive = signed_entity
+            .artifact
+            .ancillary_locations
+            .unwrap_or_default()
+            .iter()
+            .any(|location| location.ends_with(&ancillary_object_suffix));
+        if is_ancillary_archive {
+            return Ok(Box::new(reply) as Box<dyn warp::Reply>);
+        }
+
+        match snapshot_manifest_cache
+            .is_object_of_snapshot(&digest, &object_hash)
+            .await
+        {
+            Ok(true) => Ok(Box::new(reply) as Box<dyn warp::Reply>),
+            Ok(false) => Ok(reply::empty(StatusCode::NOT_FOUND)),
+            Err(err) => {
+                warn!("ensure_downloaded_object_belongs_to_a_signed_snapshot::manifest_error"; "error" => ?err);
+                Ok(reply::empty(StatusCode::NOT_FOUND))
+            }
+        }
+    }
+
     /// Snapshot download
     pub async fn snapshot_download(
         digest: String,
-        config: Configuration,
+        artifact_storage: Arc<dyn ArtifactStorage>,
         signed_entity_service: Arc<dyn SignedEntityService>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: snapshot_download/{}", digest);
@@ -187,22 +409,8 @@ mod handlers {
             .await
         {
             Ok(Some(signed_entity)) => {
-                let snapshot = signed_entity.artifact;
-                let filename = format!(
-                    "{}-e{}-i{}.{}.{}",
-                    snapshot.beacon.network,
-                    snapshot.beacon.epoch,
-                    snapshot.beacon.immutable_file_number,
-                    snapshot.digest,
-                    snapshot.compression_algorithm.tar_file_extension()
-                );
-                let snapshot_uri = format!(
-                    "{}{}/snapshot_download/{}",
-                    config.get_server_url(),
-                    SERVER_BASE_PATH,
-                    filename
-                );
-                let snapshot_uri = Uri::from_str(&snapshot_uri).unwrap();
+                let filename = snapshot_archive_file_name(&signed_entity.artifact);
+                let snapshot_uri = Uri::from_str(&artifact_storage.location(&filename)).unwrap();
 
                 Ok(Box::new(warp::redirect::found(snapshot_uri)) as Box<dyn warp::Reply>)
             }
@@ -216,11 +424,78 @@ mod handlers {
             }
         }
     }
+
+    /// Snapshot download with a time-limited signed URL
+    ///
+    /// Redirect to the regular location of the snapshot archive if signed URLs are disabled or
+    /// not supported by the artifact storage.
+    pub async fn snapshot_signed_download(
+        digest: String,
+        artifact_storage: Arc<dyn ArtifactStorage>,
+        signed_entity_service: Arc<dyn SignedEntityService>,
+        config: Configuration,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: snapshot_signed_download/{}", digest);
+
+        match signed_entity_service
+            .get_signed_snapshot_by_id(&digest)
+            .await
+        {
+            Ok(Some(signed_entity)) => {
+                let filename = snapshot_archive_file_name(&signed_entity.artifact);
+                let signed_location = match config.snapshot_signed_url_expiration {
+                    Some(expiration) => match artifact_storage
+                        .signed_location(&filename, Duration::from_secs(expiration))
+                        .await
+                    {
+                        Ok(signed_location) => signed_location,
+                        Err(err) => {
+                            warn!("snapshot_signed_download::signing_error"; "error" => ?err);
+                            return Ok(reply::internal_server_error(err));
+                        }
+                    },
+                    None => None,
+                };
+                let location =
+                    signed_location.unwrap_or_else(|| artifact_storage.location(&filename));
+                let snapshot_uri = Uri::from_str(&location).unwrap();
+
+                // A signed URL expires, the redirection must not be cached
+                Ok(Box::new(warp::reply::with_header(
+                    warp::redirect::found(snapshot_uri),
+                    "Cache-Control",
+                    "no-store",
+                )) as Box<dyn warp::Reply>)
+            }
+            Ok(None) => {
+                warn!("snapshot_signed_download::not_found");
+                Ok(reply::empty(StatusCode::NOT_FOUND))
+            }
+            Err(err) => {
+                warn!("snapshot_signed_download::error"; "error" => ?err);
+                Ok(reply::internal_server_error(err))
+            }
+        }
+    }
+
+    /// Name of the archive of a snapshot in the artifact storage
+    fn snapshot_archive_file_name(snapshot: &Snapshot) -> String {
+        format!(
+            "{}-e{}-i{}.{}.{}",
+            snapshot.beacon.network,
+            snapshot.beacon.epoch,
+            snapshot.beacon.immutable_file_number,
+            snapshot.digest,
+            snapshot.compression_algorithm.tar_file_extension()
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::artifact_storage::{LocalArtifactStorage, MockArtifactStorage};
     use crate::http_server::routes::artifact_routes::test_utils::*;
+    use crate::tools::SnapshotManifestCache;
     use crate::{
         http_server::SERVER_BASE_PATH,
         initialize_dependencies,
@@ -228,14 +503,18 @@ mod tests {
         services::{MockMessageService, MockSignedEntityService},
     };
     use mithril_common::{
-        entities::{CardanoDbBeacon, SignedEntityType, Snapshot},
+        entities::{
+            CardanoDbBeacon, SignedEntityType, Snapshot, SnapshotManifest, SnapshotManifestEntry,
+        },
         messages::ToMessageAdapter,
-        test_utils::{apispec::APISpec, fake_data},
+        test_utils::{apispec::APISpec, fake_data, TempDir},
     };
     use mithril_persistence::sqlite::HydrationError;
     use serde_json::Value::Null;
+    use std::time::Duration;
     use warp::{
         http::{Method, StatusCode},
+        hyper::body::Bytes,
         test::request,
     };
 
@@ -520,4 +799,390 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_snapshot_signed_download_returns_302_found_to_the_signed_location() {
+        let signed_entity = create_signed_entity(
+            SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::default()),
+            fake_data::snapshots(1)[0].clone(),
+        );
+        let mut mock_signed_entity_service = MockSignedEntityService::new();
+        mock_signed_entity_service
+            .expect_get_signed_snapshot_by_id()
+            .return_once(|_| Ok(Some(signed_entity)))
+            .once();
+        let mut mock_artifact_storage = MockArtifactStorage::new();
+        mock_artifact_storage
+            .expect_signed_location()
+            .withf(|_, expires_in| *expires_in == Duration::from_secs(600))
+            .return_once(|file_name, _| {
+                Ok(Some(format!(
+                    "https://bucket.host/{file_name}?X-Amz-Signature=abc"
+                )))
+            })
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signed_entity_service = Arc::new(mock_signed_entity_service);
+        dependency_manager.artifact_storage = Arc::new(mock_artifact_storage);
+        dependency_manager.config.snapshot_signed_url_expiration = Some(600);
+
+        let method = Method::GET.as_str();
+        let path = "/artifact/snapshot/{digest}/download/signed";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(response.status(), StatusCode::FOUND);
+        let location = response.headers()["location"].to_str().unwrap();
+        assert!(
+            location.starts_with("https://bucket.host/")
+                && location.ends_with("?X-Amz-Signature=abc"),
+            "Expected a signed location, got {location}",
+        );
+        assert_eq!("no-store", response.headers()["cache-control"]);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_signed_download_returns_302_found_to_the_location_if_signed_urls_are_disabled(
+    ) {
+        let network = "devnet";
+        let signed_entity = create_signed_entity(
+            SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::default()),
+            Snapshot {
+                beacon: CardanoDbBeacon::new(network, 1, 10),
+                ..fake_data::snapshots(1)[0].clone()
+            },
+        );
+        let mut mock_signed_entity_service = MockSignedEntityService::new();
+        mock_signed_entity_service
+            .expect_get_signed_snapshot_by_id()
+            .return_once(|_| Ok(Some(signed_entity)))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signed_entity_service = Arc::new(mock_signed_entity_service);
+        dependency_manager.config.snapshot_signed_url_expiration = None;
+
+        let method = Method::GET.as_str();
+        let path = "/artifact/snapshot/{digest}/download/signed";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(response.status(), StatusCode::FOUND);
+        let location = response.headers()["location"].to_str().unwrap();
+        assert!(
+            location.contains(&format!("/{SERVER_BASE_PATH}/snapshot_download/{network}")),
+            "Expected value '/{SERVER_BASE_PATH}/snapshot_download/{network}' not found in {location}",
+        );
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_signed_download_returns_404_not_found_when_no_snapshot() {
+        let mut mock_signed_entity_service = MockSignedEntityService::new();
+        mock_signed_entity_service
+            .expect_get_signed_snapshot_by_id()
+            .return_once(|_| Ok(None))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signed_entity_service = Arc::new(mock_signed_entity_service);
+
+        let method = Method::GET.as_str();
+        let path = "/artifact/snapshot/{digest}/download/signed";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/gzip",
+            &Null,
+            &response,
+            &StatusCode::NOT_FOUND,
+        )
+        .unwrap();
+    }
+
+    const LOCAL_ARCHIVE_FILE_NAME: &str = "devnet-e1-i10.0123abcd.tar.gz";
+
+    async fn setup_local_snapshot_archive(
+        test_name: &str,
+        digest_header: bool,
+    ) -> Arc<DependencyContainer> {
+        let local_directory = TempDir::create("snapshot_routes", test_name);
+        std::fs::write(local_directory.join(LOCAL_ARCHIVE_FILE_NAME), "content").unwrap();
+        let signed_entity = create_signed_entity(
+            SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::default()),
+            fake_data::snapshots(1)[0].clone(),
+        );
+        let mut mock_signed_entity_service = MockSignedEntityService::new();
+        mock_signed_entity_service
+            .expect_get_signed_snapshot_by_id()
+            .returning(move |_| Ok(Some(signed_entity.clone())));
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signed_entity_service = Arc::new(mock_signed_entity_service);
+        dependency_manager.artifact_storage = Arc::new(LocalArtifactStorage::new(
+            &local_directory,
+            "http://0.0.0.0:8080/aggregator/snapshot_download".to_string(),
+        ));
+        dependency_manager.config.snapshot_download_digest_header = digest_header;
+
+        Arc::new(dependency_manager)
+    }
+
+    async fn request_local_snapshot_archive(
+        dependency_manager: Arc<DependencyContainer>,
+    ) -> warp::http::Response<Bytes> {
+        request()
+            .method(Method::GET.as_str())
+            .path(&format!(
+                "/{SERVER_BASE_PATH}/snapshot_download/{LOCAL_ARCHIVE_FILE_NAME}"
+            ))
+            .reply(&setup_router(dependency_manager))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_local_archive_download_stream_the_file_without_digest_header() {
+        let dependency_manager = setup_local_snapshot_archive(
+            "test_snapshot_local_archive_download_stream_the_file_without_digest_header",
+            false,
+        )
+        .await;
+
+        let response = request_local_snapshot_archive(dependency_manager).await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(b"content", &response.body()[..]);
+        assert!(response.headers().get("Repr-Digest").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_local_archive_download_serve_the_file_before_its_digest_is_computed() {
+        let dependency_manager = setup_local_snapshot_archive(
+            "test_snapshot_local_archive_download_serve_the_file_before_its_digest_is_computed",
+            true,
+        )
+        .await;
+
+        let response = request_local_snapshot_archive(dependency_manager.clone()).await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(b"content", &response.body()[..]);
+        assert!(response.headers().get("Repr-Digest").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_local_archive_download_with_digest_header_once_computed() {
+        let dependency_manager = setup_local_snapshot_archive(
+            "test_snapshot_local_archive_download_with_digest_header_once_computed",
+            true,
+        )
+        .await;
+        let archive_path = dependency_manager
+            .artifact_storage
+            .local_directory()
+            .unwrap()
+            .join(LOCAL_ARCHIVE_FILE_NAME);
+        if let Some(computation) = dependency_manager
+            .file_digest_cache
+            .compute_in_background(&archive_path)
+        {
+            computation.await.unwrap();
+        }
+
+        let response = request_local_snapshot_archive(dependency_manager).await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(
+            "sha-256=:7XACtDnprIRfIjV9giusFERzD722AW0+yUMil7nsn3M=:",
+            response.headers()["Repr-Digest"]
+        );
+    }
+
+    const DEDUPLICATED_SNAPSHOT_DIGEST: &str = "0123abcd";
+
+    async fn setup_deduplicated_snapshot_storage(
+        test_name: &str,
+        snapshot_uploader_type: SnapshotUploaderType,
+    ) -> Arc<DependencyContainer> {
+        let snapshot_directory = TempDir::create("snapshot_routes", test_name);
+        let manifests_directory = snapshot_directory.join(MANIFESTS_DIRECTORY);
+        let objects_directory = snapshot_directory.join(OBJECTS_DIRECTORY);
+        std::fs::create_dir_all(&manifests_directory).unwrap();
+        std::fs::create_dir_all(&objects_directory).unwrap();
+        for digest in [DEDUPLICATED_SNAPSHOT_DIGEST, "4567efab"] {
+            let manifest = SnapshotManifest::new(
+                digest.to_string(),
+                format!("http://0.0.0.0:8080/{SERVER_BASE_PATH}/snapshot_objects/{digest}"),
+                vec![SnapshotManifestEntry {
+                    path: "immutable/00001.chunk".to_string(),
+                    object_hash: format!("object-of-{digest}"),
+                    size: 7,
+                }],
+            );
+            std::fs::write(
+                manifests_directory.join(SnapshotManifest::file_name(digest)),
+                serde_json::to_vec(&manifest).unwrap(),
+            )
+            .unwrap();
+        }
+        for object_hash in [
+            format!("object-of-{DEDUPLICATED_SNAPSHOT_DIGEST}"),
+            "object-of-4567efab".to_string(),
+            "ancillary-object".to_string(),
+        ] {
+            std::fs::write(objects_directory.join(object_hash), "content").unwrap();
+        }
+
+        let snapshot = Snapshot {
+            digest: DEDUPLICATED_SNAPSHOT_DIGEST.to_string(),
+            ..fake_data::snapshots(1)[0].clone()
+        }
+        .with_ancillary_archive(
+            7,
+            vec![format!(
+                "http://0.0.0.0:8080/{SERVER_BASE_PATH}/snapshot_objects/{DEDUPLICATED_SNAPSHOT_DIGEST}/ancillary-object"
+            )],
+        );
+        let signed_entity = create_signed_entity(
+            SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::default()),
+            snapshot,
+        );
+        let mut mock_signed_entity_service = MockSignedEntityService::new();
+        mock_signed_entity_service
+            .expect_get_signed_snapshot_by_id()
+            .returning(move |digest| {
+                Ok((digest == DEDUPLICATED_SNAPSHOT_DIGEST).then(|| signed_entity.clone()))
+            });
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signed_entity_service = Arc::new(mock_signed_entity_service);
+        dependency_manager.snapshot_manifest_cache =
+            Arc::new(SnapshotManifestCache::new(&manifests_directory));
+        dependency_manager.config.snapshot_directory = snapshot_directory;
+        dependency_manager.config.snapshot_uploader_type = snapshot_uploader_type;
+
+        Arc::new(dependency_manager)
+    }
+
+    async fn request_path(
+        dependency_manager: Arc<DependencyContainer>,
+        path: &str,
+    ) -> warp::http::Response<Bytes> {
+        request()
+            .method(Method::GET.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/{path}"))
+            .reply(&setup_router(dependency_manager))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_manifest_and_objects_are_not_served_without_deduplicated_snapshot_uploader(
+    ) {
+        let dependency_manager = setup_deduplicated_snapshot_storage(
+            "test_snapshot_manifest_and_objects_are_not_served_without_deduplicated_snapshot_uploader",
+            SnapshotUploaderType::Local,
+        )
+        .await;
+
+        for path in [
+            format!("snapshot_manifest/{DEDUPLICATED_SNAPSHOT_DIGEST}.manifest.json"),
+            format!(
+                "snapshot_objects/{DEDUPLICATED_SNAPSHOT_DIGEST}/object-of-{DEDUPLICATED_SNAPSHOT_DIGEST}"
+            ),
+        ] {
+            let response = request_path(dependency_manager.clone(), &path).await;
+
+            assert_eq!(StatusCode::NOT_FOUND, response.status(), "path: {path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_manifest_of_a_signed_snapshot_is_served() {
+        let dependency_manager = setup_deduplicated_snapshot_storage(
+            "test_snapshot_manifest_of_a_signed_snapshot_is_served",
+            SnapshotUploaderType::Deduplicated,
+        )
+        .await;
+
+        let response = request_path(
+            dependency_manager,
+            &format!("snapshot_manifest/{DEDUPLICATED_SNAPSHOT_DIGEST}.manifest.json"),
+        )
+        .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        let manifest: SnapshotManifest = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(DEDUPLICATED_SNAPSHOT_DIGEST, manifest.digest);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_manifest_of_a_not_signed_snapshot_is_not_served() {
+        let dependency_manager = setup_deduplicated_snapshot_storage(
+            "test_snapshot_manifest_of_a_not_signed_snapshot_is_not_served",
+            SnapshotUploaderType::Deduplicated,
+        )
+        .await;
+
+        let response = request_path(
+            dependency_manager,
+            "snapshot_manifest/4567efab.manifest.json",
+        )
+        .await;
+
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_objects_of_a_signed_snapshot_are_served() {
+        let dependency_manager = setup_deduplicated_snapshot_storage(
+            "test_snapshot_objects_of_a_signed_snapshot_are_served",
+            SnapshotUploaderType::Deduplicated,
+        )
+        .await;
+
+        for object_hash in [
+            format!("object-of-{DEDUPLICATED_SNAPSHOT_DIGEST}"),
+            "ancillary-object".to_string(),
+        ] {
+            let response = request_path(
+                dependency_manager.clone(),
+                &format!("snapshot_objects/{DEDUPLICATED_SNAPSHOT_DIGEST}/{object_hash}"),
+            )
+            .await;
+
+            assert_eq!(StatusCode::OK, response.status(), "object: {object_hash}");
+            assert_eq!(b"content", &response.body()[..]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_objects_not_referenced_by_a_signed_snapshot_are_not_served() {
+        let dependency_manager = setup_deduplicated_snapshot_storage(
+            "test_snapshot_objects_not_referenced_by_a_signed_snapshot_are_not_served",
+            SnapshotUploaderType::Deduplicated,
+        )
+        .await;
+
+        for path in [
+            // Object of another snapshot requested under the digest of the signed snapshot
+            format!("snapshot_objects/{DEDUPLICATED_SNAPSHOT_DIGEST}/object-of-4567efab"),
+            // Object of a snapshot that is not signed
+            "snapshot_objects/4567efab/object-of-4567efab".to_string(),
+        ] {
+            let response = request_path(dependency_manager.clone(), &path).await;
+
+            assert_eq!(StatusCode::NOT_FOUND, response.status(), "path: {path}");
+        }
+    }
 }
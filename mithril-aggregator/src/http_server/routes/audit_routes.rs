@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use warp::Filter;
+
+use crate::http_server::routes::middlewares;
+use crate::DependencyContainer;
+
+/// Default period, in hours, covered by the runtime decisions audit when no `since` is given
+const DEFAULT_RUNTIME_DECISIONS_PERIOD_IN_HOURS: i64 = 24;
+
+#[derive(Deserialize, Serialize, Debug)]
+struct RuntimeDecisionsQueryParams {
+    since: Option<String>,
+}
+
+pub fn routes(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    audit_runtime_decisions(dependency_manager)
+}
+
+/// GET /audit/runtime-decisions
+fn audit_runtime_decisions(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("audit" / "runtime-decisions")
+        .and(warp::get())
+        .and(warp::query::<RuntimeDecisionsQueryParams>())
+        .and(middlewares::with_runtime_decision_storer(
+            dependency_manager,
+        ))
+        .and_then(handlers::runtime_decisions)
+}
+
+mod handlers {
+    use chrono::{DateTime, Duration, Utc};
+    use slog_scope::{debug, warn};
+    use std::{convert::Infallible, sync::Arc};
+    use warp::http::StatusCode;
+
+    use crate::database::repository::RuntimeDecisionStorer;
+    use crate::entities::{RuntimeDecisionListItemMessage, RuntimeDecisionListMessage};
+    use crate::http_server::routes::reply;
+    use crate::unwrap_to_internal_server_error;
+
+    use super::{RuntimeDecisionsQueryParams, DEFAULT_RUNTIME_DECISIONS_PERIOD_IN_HOURS};
+
+    /// Runtime decisions
+    pub async fn runtime_decisions(
+        query_parameters: RuntimeDecisionsQueryParams,
+        runtime_decision_storer: Arc<dyn RuntimeDecisionStorer>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: runtime_decisions"; "since" => ?query_parameters.since);
+
+        let since = match &query_parameters.since {
+            Some(since) => match DateTime::parse_from_rfc3339(since) {
+                Ok(since) => since.with_timezone(&Utc),
+                Err(error) => {
+                    warn!("runtime_decisions::bad_request"; "since" => since, "error" => ?error);
+                    return Ok(reply::bad_request(
+                        "invalid_since_parameter".to_string(),
+                        format!("'since' must be a RFC 3339 date, got '{since}': {error}"),
+                    ));
+                }
+            },
+            None => {
+                Utc::now() - Duration::try_hours(DEFAULT_RUNTIME_DECISIONS_PERIOD_IN_HOURS).unwrap()
+            }
+        };
+
+        let decisions = unwrap_to_internal_server_error!(
+            runtime_decision_storer.get_decisions_since(since).await,
+            "runtime_decisions::error"
+        );
+        let message: RuntimeDecisionListMessage = decisions
+            .into_iter()
+            .map(RuntimeDecisionListItemMessage::from)
+            .collect();
+
+        Ok(reply::json(&message, StatusCode::OK))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use chrono::Utc;
+    use mithril_common::entities::{Epoch, SignedEntityType};
+    use mithril_common::test_utils::apispec::APISpec;
+    use serde_json::Value::Null;
+    use warp::{
+        http::{Method, StatusCode},
+        test::request,
+    };
+
+    use crate::database::record::{RuntimeDecision, RuntimeDecisionRecord};
+    use crate::database::repository::MockRuntimeDecisionStorer;
+    use crate::{http_server::SERVER_BASE_PATH, initialize_dependencies};
+
+    use super::*;
+
+    fn setup_router(
+        dependency_manager: Arc<DependencyContainer>,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let cors = warp::cors()
+            .allow_any_origin()
+            .allow_headers(vec!["content-type"])
+            .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+
+        warp::any()
+            .and(warp::path(SERVER_BASE_PATH))
+            .and(routes(dependency_manager).with(cors))
+    }
+
+    #[tokio::test]
+    async fn test_runtime_decisions_get_ok() {
+        let mut mock_runtime_decision_storer = MockRuntimeDecisionStorer::new();
+        mock_runtime_decision_storer
+            .expect_get_decisions_since()
+            .return_once(|_| {
+                Ok(vec![RuntimeDecisionRecord {
+                    runtime_decision_id: 1,
+                    signed_entity_type: SignedEntityType::MithrilStakeDistribution(Epoch(5)),
+                    decision: RuntimeDecision::ProtocolMessageUnavailable,
+                    reason: Some("Missing stake distribution".to_string()),
+                    created_at: Utc::now(),
+                    last_seen_at: Utc::now(),
+                }])
+            })
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.runtime_decision_storer = Arc::new(mock_runtime_decision_storer);
+
+        let method = Method::GET.as_str();
+        let path = "/audit/runtime-decisions";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{path}?since=2024-01-19T13:43:05.618857482Z"
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_runtime_decisions_get_with_invalid_since_parameter() {
+        let mut mock_runtime_decision_storer = MockRuntimeDecisionStorer::new();
+        mock_runtime_decision_storer
+            .expect_get_decisions_since()
+            .never();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.runtime_decision_storer = Arc::new(mock_runtime_decision_storer);
+
+        let method = Method::GET.as_str();
+        let path = "/audit/runtime-decisions";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}?since=yesterday"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::BAD_REQUEST,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_runtime_decisions_get_ko() {
+        let mut mock_runtime_decision_storer = MockRuntimeDecisionStorer::new();
+        mock_runtime_decision_storer
+            .expect_get_decisions_since()
+            .return_once(|_| Err(anyhow!("an error")))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.runtime_decision_storer = Arc::new(mock_runtime_decision_storer);
+
+        let method = Method::GET.as_str();
+        let path = "/audit/runtime-decisions";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .unwrap();
+    }
+}
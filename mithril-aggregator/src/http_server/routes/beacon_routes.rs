@@ -0,0 +1,191 @@
+use std::sync::Arc;
+use warp::Filter;
+
+use crate::http_server::routes::middlewares;
+use crate::DependencyContainer;
+
+pub fn routes(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    beacons_next_estimates(dependency_manager)
+}
+
+/// GET /beacons/next-estimates
+fn beacons_next_estimates(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("beacons" / "next-estimates")
+        .and(warp::get())
+        .and(middlewares::with_config(dependency_manager.clone()))
+        .and(middlewares::with_signed_entity_storer(dependency_manager))
+        .and_then(handlers::next_estimates)
+}
+
+mod handlers {
+    use slog_scope::{debug, warn};
+    use std::{convert::Infallible, sync::Arc};
+    use warp::http::StatusCode;
+
+    use crate::database::repository::SignedEntityStorer;
+    use crate::entities::{BeaconEstimateListItemMessage, BeaconEstimateListMessage};
+    use crate::http_server::routes::reply;
+    use crate::{unwrap_to_internal_server_error, Configuration};
+
+    /// Number of the latest signed entities of each type used to estimate the next certification
+    pub const ESTIMATE_SAMPLE_SIZE: usize = 10;
+
+    /// Next certifications estimates
+    pub async fn next_estimates(
+        config: Configuration,
+        signed_entity_storer: Arc<dyn SignedEntityStorer>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: beacons_next_estimates");
+
+        let signed_entity_types = unwrap_to_internal_server_error!(
+            config.list_allowed_signed_entity_types_discriminants(),
+            "beacons_next_estimates::error"
+        );
+        let mut message = BeaconEstimateListMessage::new();
+        for signed_entity_type in signed_entity_types {
+            let signed_entities = unwrap_to_internal_server_error!(
+                signed_entity_storer
+                    .get_last_signed_entities_by_type(&signed_entity_type, ESTIMATE_SAMPLE_SIZE)
+                    .await,
+                "beacons_next_estimates::error"
+            );
+            message.push(BeaconEstimateListItemMessage::estimate(
+                signed_entity_type,
+                &signed_entities,
+            ));
+        }
+
+        Ok(reply::json(&message, StatusCode::OK))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use chrono::{Duration, Utc};
+    use mithril_common::entities::{
+        CardanoDbBeacon, SignedEntityType, SignedEntityTypeDiscriminants,
+    };
+    use mithril_common::test_utils::apispec::APISpec;
+    use serde_json::Value::Null;
+    use warp::{
+        http::{Method, StatusCode},
+        test::request,
+    };
+
+    use crate::database::record::SignedEntityRecord;
+    use crate::database::repository::MockSignedEntityStorer;
+    use crate::entities::BeaconEstimateListMessage;
+    use crate::{http_server::SERVER_BASE_PATH, initialize_dependencies};
+
+    use super::*;
+
+    fn setup_router(
+        dependency_manager: Arc<DependencyContainer>,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let cors = warp::cors()
+            .allow_any_origin()
+            .allow_headers(vec!["content-type"])
+            .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+
+        warp::any()
+            .and(warp::path(SERVER_BASE_PATH))
+            .and(routes(dependency_manager).with(cors))
+    }
+
+    #[tokio::test]
+    async fn test_beacons_next_estimates_get_ok() {
+        let now = Utc::now();
+        let mut mock_signed_entity_storer = MockSignedEntityStorer::new();
+        mock_signed_entity_storer
+            .expect_get_last_signed_entities_by_type()
+            .returning(move |signed_entity_type, _| match signed_entity_type {
+                SignedEntityTypeDiscriminants::CardanoImmutableFilesFull => Ok(vec![
+                    SignedEntityRecord {
+                        signed_entity_id: "signed-entity-2".to_string(),
+                        signed_entity_type: SignedEntityType::CardanoImmutableFilesFull(
+                            CardanoDbBeacon::new("devnet", 1, 2),
+                        ),
+                        certificate_id: "certificate-2".to_string(),
+                        artifact: "".to_string(),
+                        created_at: now,
+                    },
+                    SignedEntityRecord {
+                        signed_entity_id: "signed-entity-1".to_string(),
+                        signed_entity_type: SignedEntityType::CardanoImmutableFilesFull(
+                            CardanoDbBeacon::new("devnet", 1, 1),
+                        ),
+                        certificate_id: "certificate-1".to_string(),
+                        artifact: "".to_string(),
+                        created_at: now - Duration::try_hours(2).unwrap(),
+                    },
+                ]),
+                _ => Ok(vec![]),
+            });
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signed_entity_storer = Arc::new(mock_signed_entity_storer);
+
+        let method = Method::GET.as_str();
+        let path = "/beacons/next-estimates";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+
+        let message: BeaconEstimateListMessage = serde_json::from_slice(response.body()).unwrap();
+        let estimate = message
+            .iter()
+            .find(|e| {
+                e.signed_entity_type == SignedEntityTypeDiscriminants::CardanoImmutableFilesFull
+            })
+            .expect("an estimate should be returned for the Cardano immutable files full");
+        assert_eq!(Some(2 * 3600), estimate.beacon_interval_seconds);
+    }
+
+    #[tokio::test]
+    async fn test_beacons_next_estimates_get_ko() {
+        let mut mock_signed_entity_storer = MockSignedEntityStorer::new();
+        mock_signed_entity_storer
+            .expect_get_last_signed_entities_by_type()
+            .returning(|_, _| Err(anyhow!("an error")));
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signed_entity_storer = Arc::new(mock_signed_entity_storer);
+
+        let method = Method::GET.as_str();
+        let path = "/beacons/next-estimates";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .unwrap();
+    }
+}
@@ -1,13 +1,36 @@
 use crate::http_server::routes::middlewares;
 use crate::DependencyContainer;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use warp::Filter;
 
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct CertificatesQueryParams {
+    epoch_from: Option<String>,
+    epoch_to: Option<String>,
+    signed_entity_type: Option<String>,
+    format: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct CertificateQueryParams {
+    format: Option<String>,
+    with_verification_path: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct FormatQueryParams {
+    format: Option<String>,
+}
+
 pub fn routes(
     dependency_manager: Arc<DependencyContainer>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     certificate_pending(dependency_manager.clone())
         .or(certificate_certificates(dependency_manager.clone()))
+        .or(certificate_protocol_message(dependency_manager.clone()))
+        .or(certificate_chain_checkpoints(dependency_manager.clone()))
+        .or(genesis_key_rotations(dependency_manager.clone()))
         .or(certificate_certificate_hash(dependency_manager))
 }
 
@@ -17,6 +40,7 @@ fn certificate_pending(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("certificate-pending")
         .and(warp::get())
+        .and(middlewares::with_client_api_version())
         .and(middlewares::with_config(dependency_manager.clone()))
         .and(middlewares::with_time_point_provider(
             dependency_manager.clone(),
@@ -33,7 +57,13 @@ fn certificate_certificates(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("certificates")
         .and(warp::get())
-        .and(middlewares::with_http_message_service(dependency_manager))
+        .and(warp::query::<CertificatesQueryParams>())
+        .and(middlewares::with_client_api_version())
+        .and(middlewares::with_if_none_match())
+        .and(middlewares::with_http_message_service(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_certificate_repository(dependency_manager))
         .and_then(handlers::certificate_certificates)
 }
 
@@ -43,26 +73,86 @@ fn certificate_certificate_hash(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("certificate" / String)
         .and(warp::get())
-        .and(middlewares::with_http_message_service(dependency_manager))
+        .and(warp::query::<CertificateQueryParams>())
+        .and(middlewares::with_if_none_match())
+        .and(middlewares::with_http_message_service(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_certificate_repository(dependency_manager))
         .and_then(handlers::certificate_certificate_hash)
 }
 
+/// GET /certificate/{certificate_hash}/protocol-message
+fn certificate_protocol_message(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("certificate" / String / "protocol-message")
+        .and(warp::get())
+        .and(middlewares::with_http_message_service(dependency_manager))
+        .and_then(handlers::certificate_protocol_message)
+}
+
+/// GET /certificate-chain-checkpoints
+fn certificate_chain_checkpoints(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("certificate-chain-checkpoints")
+        .and(warp::get())
+        .and(middlewares::with_certificate_chain_checkpoint_storer(
+            dependency_manager,
+        ))
+        .and_then(handlers::certificate_chain_checkpoints)
+}
+
+/// GET /genesis-key-rotations
+fn genesis_key_rotations(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("genesis-key-rotations")
+        .and(warp::get())
+        .and(warp::query::<FormatQueryParams>())
+        .and(middlewares::with_certificate_repository(dependency_manager))
+        .and_then(handlers::genesis_key_rotations)
+}
+
 mod handlers {
     use crate::{
-        http_server::routes::reply, services::MessageService, unwrap_to_internal_server_error,
-        CertificatePendingStore, Configuration, ToCertificatePendingMessageAdapter,
+        database::repository::{
+            CertificateChainCheckpointStorer, CertificateListFilters, CertificateRepository,
+        },
+        http_server::routes::reply,
+        services::MessageService,
+        unwrap_to_internal_server_error, CertificatePendingStore, Configuration,
+        ToCertificatePendingMessageAdapter,
     };
 
+    use mithril_common::certificate_chain::{
+        certificates_to_cbor_bytes, CertificateVerificationPath,
+    };
+    use mithril_common::entities::{Certificate, Epoch, SignedEntityTypeDiscriminants};
+    use mithril_common::messages::{
+        CertificateChainCheckpointListItemMessage, CertificateChainCheckpointListMessage,
+        CertificateListMessage,
+    };
+    use mithril_common::StdResult;
     use mithril_common::TimePointProvider;
+    use semver::Version;
     use slog_scope::{debug, warn};
     use std::convert::Infallible;
+    use std::str::FromStr;
     use std::sync::Arc;
     use warp::http::StatusCode;
 
+    use super::{CertificateQueryParams, CertificatesQueryParams, FormatQueryParams};
+
     pub const LIST_MAX_ITEMS: usize = 20;
 
+    /// Maximum number of certificates of a verification path returned at once
+    pub const VERIFICATION_PATH_MAX_LENGTH: usize = 100;
+
     /// Certificate Pending
     pub async fn certificate_pending(
+        client_api_version: Option<Version>,
         config: Configuration,
         time_point_provider: Arc<dyn TimePointProvider>,
         certificate_pending_store: Arc<CertificatePendingStore>,
@@ -77,12 +167,13 @@ mod handlers {
         );
 
         match certificate_pending_store.get().await {
-            Ok(Some(certificate_pending)) => Ok(reply::json(
+            Ok(Some(certificate_pending)) => Ok(reply::json_for_api_version(
                 &ToCertificatePendingMessageAdapter::adapt(
                     certificate_pending,
                     network,
                     time_point.immutable_file_number,
                 ),
+                client_api_version,
                 StatusCode::OK,
             )),
             Ok(None) => Ok(reply::empty(StatusCode::NO_CONTENT)),
@@ -93,17 +184,94 @@ mod handlers {
         }
     }
 
+    fn parse_epoch_parameter(name: &str, value: &Option<String>) -> Result<Option<Epoch>, String> {
+        value
+            .as_ref()
+            .map(|epoch| {
+                epoch
+                    .parse::<u64>()
+                    .map(Epoch)
+                    .map_err(|_| format!("'{name}' must be an epoch number, got '{epoch}'"))
+            })
+            .transpose()
+    }
+
+    /// Check if the certificates must be returned with their CBOR encoding
+    fn parse_format_parameter(format: Option<&str>) -> Result<bool, String> {
+        match format {
+            None | Some("json") => Ok(false),
+            Some("cbor") => Ok(true),
+            Some(format) => Err(format!("Unsupported certificate format: '{format}'")),
+        }
+    }
+
+    fn parse_certificates_query_parameters(
+        query_parameters: &CertificatesQueryParams,
+    ) -> Result<(CertificateListFilters, bool), String> {
+        let signed_entity_type = query_parameters
+            .signed_entity_type
+            .as_ref()
+            .map(|discriminant| {
+                SignedEntityTypeDiscriminants::from_str(discriminant)
+                    .map_err(|_| format!("Unknown signed entity type: '{discriminant}'"))
+            })
+            .transpose()?;
+
+        let filters = CertificateListFilters {
+            epoch_from: parse_epoch_parameter("epoch_from", &query_parameters.epoch_from)?,
+            epoch_to: parse_epoch_parameter("epoch_to", &query_parameters.epoch_to)?,
+            signed_entity_type,
+        };
+
+        Ok((
+            filters,
+            parse_format_parameter(query_parameters.format.as_deref())?,
+        ))
+    }
+
     /// List all Certificates
     pub async fn certificate_certificates(
+        query_parameters: CertificatesQueryParams,
+        client_api_version: Option<Version>,
+        if_none_match: Option<String>,
         http_message_service: Arc<dyn MessageService>,
+        certificate_repository: Arc<CertificateRepository>,
     ) -> Result<impl warp::Reply, Infallible> {
-        debug!("⇄ HTTP SERVER: certificate_certificates",);
+        debug!("⇄ HTTP SERVER: certificate_certificates"; "query_parameters" => ?query_parameters);
+
+        let (filters, is_cbor) = match parse_certificates_query_parameters(&query_parameters) {
+            Ok(parameters) => parameters,
+            Err(message) => {
+                warn!("certificate_certificates::bad_request"; "error" => &message);
+                return Ok(reply::bad_request(
+                    "invalid_query_parameter".to_string(),
+                    message,
+                ));
+            }
+        };
+        if is_cbor {
+            let certificates: Vec<Certificate> = unwrap_to_internal_server_error!(
+                certificate_repository
+                    .get_latest_certificates_with_filters(&filters, LIST_MAX_ITEMS)
+                    .await,
+                "certificate_certificates::error"
+            );
+            let bytes = unwrap_to_internal_server_error!(
+                certificates_to_cbor_bytes(&certificates),
+                "certificate_certificates::cbor_error"
+            );
+            return Ok(reply::cbor(bytes, StatusCode::OK));
+        }
 
         match http_message_service
-            .get_certificate_list_message(LIST_MAX_ITEMS)
+            .get_certificate_list_message(&filters, LIST_MAX_ITEMS)
             .await
         {
-            Ok(certificates) => Ok(reply::json(&certificates, StatusCode::OK)),
+            Ok(certificates) => Ok(reply::json_list_with_etag(
+                certificates,
+                client_api_version,
+                if_none_match,
+            )),
             Err(err) => {
                 warn!("certificate_certificates::error"; "error" => ?err);
                 Ok(reply::internal_server_error(err))
@@ -111,21 +279,97 @@ mod handlers {
         }
     }
 
+    /// Check if the certificate must be returned with its CBOR encoding
+    fn parse_certificate_query_parameters(
+        query_parameters: &CertificateQueryParams,
+    ) -> Result<bool, String> {
+        let is_cbor = parse_format_parameter(query_parameters.format.as_deref())?;
+        if !is_cbor && query_parameters.with_verification_path.unwrap_or(false) {
+            return Err(
+                "'with_verification_path' is only supported with the 'cbor' format".to_string(),
+            );
+        }
+
+        Ok(is_cbor)
+    }
+
+    /// CBOR encoding of a certificate, followed by its verification path if requested
+    ///
+    /// The verification path is capped to [VERIFICATION_PATH_MAX_LENGTH] certificates, if it does
+    /// not end with the genesis certificate it goes on with the verification path of the previous
+    /// hash of its last certificate.
+    async fn encode_certificate_cbor(
+        certificate_repository: &CertificateRepository,
+        certificate_hash: &str,
+        with_verification_path: bool,
+    ) -> StdResult<Option<Vec<u8>>> {
+        let Some(certificate) = certificate_repository
+            .get_certificate::<Certificate>(certificate_hash)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let bytes = if with_verification_path {
+            CertificateVerificationPath::retrieve(
+                certificate_repository,
+                certificate_hash,
+                VERIFICATION_PATH_MAX_LENGTH,
+            )
+            .await?
+            .to_cbor_bytes()?
+        } else {
+            certificate.to_cbor_bytes()?
+        };
+
+        Ok(Some(bytes))
+    }
+
     /// Certificate by certificate hash
     pub async fn certificate_certificate_hash(
         certificate_hash: String,
+        query_parameters: CertificateQueryParams,
+        if_none_match: Option<String>,
         http_message_service: Arc<dyn MessageService>,
+        certificate_repository: Arc<CertificateRepository>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!(
             "⇄ HTTP SERVER: certificate_certificate_hash/{}",
-            certificate_hash
+            certificate_hash; "query_parameters" => ?query_parameters
         );
 
+        let is_cbor = match parse_certificate_query_parameters(&query_parameters) {
+            Ok(is_cbor) => is_cbor,
+            Err(message) => {
+                warn!("certificate_certificate_hash::bad_request"; "error" => &message);
+                return Ok(reply::bad_request(
+                    "invalid_query_parameter".to_string(),
+                    message,
+                ));
+            }
+        };
+        if is_cbor {
+            return match encode_certificate_cbor(
+                &certificate_repository,
+                &certificate_hash,
+                query_parameters.with_verification_path.unwrap_or(false),
+            )
+            .await
+            {
+                Ok(Some(bytes)) => Ok(reply::cbor(bytes, StatusCode::OK)),
+                Ok(None) => Ok(reply::empty(StatusCode::NOT_FOUND)),
+                Err(err) => {
+                    warn!("certificate_certificate_hash::cbor_error"; "error" => ?err);
+                    Ok(reply::internal_server_error(err))
+                }
+            };
+        }
+
         match http_message_service
             .get_certificate_message(&certificate_hash)
             .await
         {
-            Ok(Some(certificate)) => Ok(reply::json(&certificate, StatusCode::OK)),
+            Ok(Some(certificate)) => Ok(reply::json_with_etag(&certificate, if_none_match)),
             Ok(None) => Ok(reply::empty(StatusCode::NOT_FOUND)),
             Err(err) => {
                 warn!("certificate_certificate_hash::error"; "error" => ?err);
@@ -133,16 +377,109 @@ mod handlers {
             }
         }
     }
+
+    /// Protocol message signed by a certificate
+    pub async fn certificate_protocol_message(
+        certificate_hash: String,
+        http_message_service: Arc<dyn MessageService>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!(
+            "⇄ HTTP SERVER: certificate_protocol_message/{}",
+            certificate_hash
+        );
+
+        match http_message_service
+            .get_certificate_protocol_message(&certificate_hash)
+            .await
+        {
+            Ok(Some(protocol_message)) => Ok(reply::json(&protocol_message, StatusCode::OK)),
+            Ok(None) => Ok(reply::empty(StatusCode::NOT_FOUND)),
+            Err(err) => {
+                warn!("certificate_protocol_message::error"; "error" => ?err);
+                Ok(reply::internal_server_error(err))
+            }
+        }
+    }
+
+    /// Checkpoints of the certificate chain
+    pub async fn certificate_chain_checkpoints(
+        certificate_chain_checkpoint_storer: Arc<dyn CertificateChainCheckpointStorer>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: certificate_chain_checkpoints");
+
+        let checkpoints = unwrap_to_internal_server_error!(
+            certificate_chain_checkpoint_storer.get_checkpoints().await,
+            "certificate_chain_checkpoints::error"
+        );
+        let message: CertificateChainCheckpointListMessage = checkpoints
+            .into_iter()
+            .map(CertificateChainCheckpointListItemMessage::from)
+            .collect();
+
+        Ok(reply::json(&message, StatusCode::OK))
+    }
+
+    /// Genesis key rotation certificates
+    pub async fn genesis_key_rotations(
+        query_parameters: FormatQueryParams,
+        certificate_repository: Arc<CertificateRepository>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: genesis_key_rotations"; "query_parameters" => ?query_parameters);
+
+        let is_cbor = match parse_format_parameter(query_parameters.format.as_deref()) {
+            Ok(is_cbor) => is_cbor,
+            Err(message) => {
+                warn!("genesis_key_rotations::bad_request"; "error" => &message);
+                return Ok(reply::bad_request(
+                    "invalid_query_parameter".to_string(),
+                    message,
+                ));
+            }
+        };
+        if is_cbor {
+            let certificates: Vec<Certificate> = unwrap_to_internal_server_error!(
+                certificate_repository
+                    .get_latest_genesis_key_rotation_certificates(LIST_MAX_ITEMS)
+                    .await,
+                "genesis_key_rotations::error"
+            );
+            let bytes = unwrap_to_internal_server_error!(
+                certificates_to_cbor_bytes(&certificates),
+                "genesis_key_rotations::cbor_error"
+            );
+            return Ok(reply::cbor(bytes, StatusCode::OK));
+        }
+
+        let message: CertificateListMessage = unwrap_to_internal_server_error!(
+            certificate_repository
+                .get_latest_genesis_key_rotation_certificates(LIST_MAX_ITEMS)
+                .await,
+            "genesis_key_rotations::error"
+        );
+
+        Ok(reply::json(&message, StatusCode::OK))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use anyhow::anyhow;
     use mithril_common::{
-        entities::CertificatePending,
+        certificate_chain::{
+            certificates_from_cbor_bytes, CertificateGenesisProducer, CertificateVerificationPath,
+        },
+        crypto_helper::{tests_setup::setup_certificate_chain, ProtocolGenesisSigner},
+        entities::{Certificate, CertificatePending, Epoch, SignedEntityTypeDiscriminants},
+        messages::{
+            CertificateListItemMessage, CertificateListMessage, PaginatedMessage,
+            PAGINATED_MESSAGE_MIN_API_VERSION,
+        },
         test_utils::{apispec::APISpec, fake_data},
+        MITHRIL_API_VERSION_HEADER,
     };
     use mithril_persistence::store::adapter::DumbStoreAdapter;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
     use serde_json::Value::Null;
     use warp::{
         http::{Method, StatusCode},
@@ -150,7 +487,13 @@ mod tests {
     };
 
     use crate::{
-        http_server::SERVER_BASE_PATH, initialize_dependencies, services::MockMessageService,
+        database::{
+            record::CertificateChainCheckpointRecord,
+            repository::{CertificateListFilters, MockCertificateChainCheckpointStorer},
+        },
+        http_server::{routes::reply, SERVER_BASE_PATH},
+        initialize_dependencies,
+        services::MockMessageService,
         CertificatePendingStore,
     };
 
@@ -288,13 +631,175 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_certificate_certificates_get_ok_paginated_if_client_api_version_supports_it() {
+        let dependency_manager = initialize_dependencies().await;
+        dependency_manager
+            .certificate_repository
+            .create_certificate(fake_data::genesis_certificate("{certificate_hash}"))
+            .await
+            .expect("certificate store save should have succeeded");
+        let router = setup_router(Arc::new(dependency_manager));
+
+        let method = Method::GET.as_str();
+        let path = "/certificates";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header(MITHRIL_API_VERSION_HEADER, "0.1.24")
+            .reply(&router)
+            .await;
+        let message: CertificateListMessage = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(1, message.len());
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header(
+                MITHRIL_API_VERSION_HEADER,
+                PAGINATED_MESSAGE_MIN_API_VERSION.to_string(),
+            )
+            .reply(&router)
+            .await;
+        let message: PaginatedMessage<CertificateListItemMessage> =
+            serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(1, message.items.len());
+        assert_eq!(1, message.total);
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_certificate_certificates_get_ok_with_filters() {
+        let mut dependency_manager = initialize_dependencies().await;
+        let mut message_service = MockMessageService::new();
+        message_service
+            .expect_get_certificate_list_message()
+            .withf(|filters, _| {
+                filters
+                    == &CertificateListFilters {
+                        epoch_from: Some(Epoch(3)),
+                        epoch_to: Some(Epoch(5)),
+                        signed_entity_type: Some(
+                            SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+                        ),
+                    }
+            })
+            .return_once(|_, _| Ok(vec![]))
+            .once();
+        dependency_manager.message_service = Arc::new(message_service);
+
+        let method = Method::GET.as_str();
+        let path = "/certificates";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{path}?epoch_from=3&epoch_to=5&signed_entity_type=CardanoImmutableFilesFull"
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_certificate_certificates_get_ok_with_the_canonical_name_of_the_signed_entity_type(
+    ) {
+        let mut dependency_manager = initialize_dependencies().await;
+        let mut message_service = MockMessageService::new();
+        message_service
+            .expect_get_certificate_list_message()
+            .withf(|filters, _| {
+                filters
+                    == &CertificateListFilters {
+                        epoch_from: Some(Epoch(3)),
+                        epoch_to: Some(Epoch(5)),
+                        signed_entity_type: Some(
+                            SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+                        ),
+                    }
+            })
+            .return_once(|_, _| Ok(vec![]))
+            .once();
+        dependency_manager.message_service = Arc::new(message_service);
+
+        let method = Method::GET.as_str();
+        let path = "/certificates";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{path}?epoch_from=3&epoch_to=5&signed_entity_type=cardano-immutable-files-full"
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_certificate_certificates_get_ko_400_with_invalid_filters() {
+        for query in ["epoch_from=three", "signed_entity_type=Unknown"] {
+            let dependency_manager = initialize_dependencies().await;
+
+            let method = Method::GET.as_str();
+            let path = "/certificates";
+
+            let response = request()
+                .method(method)
+                .path(&format!("/{SERVER_BASE_PATH}{path}?{query}"))
+                .reply(&setup_router(Arc::new(dependency_manager)))
+                .await;
+
+            APISpec::verify_conformity(
+                APISpec::get_all_spec_files(),
+                method,
+                path,
+                "application/json",
+                &Null,
+                &response,
+                &StatusCode::BAD_REQUEST,
+            )
+            .unwrap();
+        }
+    }
+
     #[tokio::test]
     async fn test_certificate_when_error_retrieving_certificates_returns_ko_500() {
         let mut dependency_manager = initialize_dependencies().await;
         let mut message_service = MockMessageService::new();
         message_service
             .expect_get_certificate_list_message()
-            .returning(|_| Err(anyhow!("an error")));
+            .returning(|_, _| Err(anyhow!("an error")));
         dependency_manager.message_service = Arc::new(message_service);
 
         let method = Method::GET.as_str();
@@ -348,6 +853,170 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_certificate_certificate_hash_get_not_modified_304() {
+        let dependency_manager = initialize_dependencies().await;
+        dependency_manager
+            .certificate_repository
+            .create_certificate(fake_data::genesis_certificate("{certificate_hash}"))
+            .await
+            .expect("certificate store save should have succeeded");
+        let router = setup_router(Arc::new(dependency_manager));
+
+        let method = Method::GET.as_str();
+        let path = "/certificate/{certificate_hash}";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&router)
+            .await;
+        let etag = response
+            .headers()
+            .get(reply::ETAG_HEADER)
+            .expect("response should have an etag header")
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header(reply::IF_NONE_MATCH_HEADER, &etag)
+            .reply(&router)
+            .await;
+
+        assert_eq!(
+            Some(etag.as_str()),
+            response
+                .headers()
+                .get(reply::ETAG_HEADER)
+                .and_then(|value| value.to_str().ok())
+        );
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::NOT_MODIFIED,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_certificate_certificate_hash_get_cbor_ok() {
+        let dependency_manager = initialize_dependencies().await;
+        let genesis_certificate = Certificate {
+            signed_message: "01".repeat(32),
+            ..fake_data::genesis_certificate(&"aa".repeat(32))
+        };
+        let certificate = Certificate {
+            previous_hash: genesis_certificate.hash.clone(),
+            signed_message: "01".repeat(32),
+            ..fake_data::certificate("bb".repeat(32))
+        };
+        dependency_manager
+            .certificate_repository
+            .create_many_certificates(vec![genesis_certificate.clone(), certificate.clone()])
+            .await
+            .unwrap();
+        let router = setup_router(Arc::new(dependency_manager));
+
+        let response = request()
+            .method(Method::GET.as_str())
+            .path(&format!(
+                "/{SERVER_BASE_PATH}/certificate/{}?format=cbor",
+                certificate.hash
+            ))
+            .reply(&router)
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!("application/cbor", response.headers()["content-type"]);
+        let decoded = Certificate::from_cbor_bytes(response.body()).unwrap();
+        assert_eq!(certificate, decoded);
+
+        let response = request()
+            .method(Method::GET.as_str())
+            .path(&format!(
+                "/{SERVER_BASE_PATH}/certificate/{}?format=cbor&with_verification_path=true",
+                certificate.hash
+            ))
+            .reply(&router)
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        let decoded = CertificateVerificationPath::from_cbor_bytes(response.body()).unwrap();
+        assert_eq!(vec![certificate, genesis_certificate], decoded.certificates);
+        assert!(decoded.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_certificate_certificates_get_cbor_ok() {
+        let (certificates, _) = setup_certificate_chain(5, 2);
+        let dependency_manager = initialize_dependencies().await;
+        dependency_manager
+            .certificate_repository
+            .create_many_certificates(certificates.iter().rev().cloned().collect())
+            .await
+            .unwrap();
+        let expected_certificates: Vec<Certificate> = dependency_manager
+            .certificate_repository
+            .get_latest_certificates_with_filters(
+                &CertificateListFilters::default(),
+                handlers::LIST_MAX_ITEMS,
+            )
+            .await
+            .unwrap();
+
+        let response = request()
+            .method(Method::GET.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/certificates?format=cbor"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!("application/cbor", response.headers()["content-type"]);
+        assert_eq!(
+            expected_certificates,
+            certificates_from_cbor_bytes(response.body()).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_certificate_list_routes_get_ko_400_with_invalid_format() {
+        let dependency_manager = initialize_dependencies().await;
+        let router = setup_router(Arc::new(dependency_manager));
+
+        for path in ["/certificates", "/genesis-key-rotations"] {
+            let response = request()
+                .method(Method::GET.as_str())
+                .path(&format!("/{SERVER_BASE_PATH}{path}?format=xml"))
+                .reply(&router)
+                .await;
+
+            assert_eq!(StatusCode::BAD_REQUEST, response.status(), "path: {path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_certificate_certificate_hash_get_ko_400_with_invalid_format() {
+        let dependency_manager = initialize_dependencies().await;
+        let router = setup_router(Arc::new(dependency_manager));
+
+        for query in ["format=xml", "with_verification_path=true"] {
+            let response = request()
+                .method(Method::GET.as_str())
+                .path(&format!("/{SERVER_BASE_PATH}/certificate/whatever?{query}"))
+                .reply(&router)
+                .await;
+
+            assert_eq!(StatusCode::BAD_REQUEST, response.status(), "query: {query}");
+        }
+    }
+
     #[tokio::test]
     async fn test_certificate_certificate_hash_get_ok_404() {
         let dependency_manager = initialize_dependencies().await;
@@ -405,4 +1074,255 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_certificate_protocol_message_get_ok() {
+        let dependency_manager = initialize_dependencies().await;
+        dependency_manager
+            .certificate_repository
+            .create_certificate(fake_data::genesis_certificate("{certificate_hash}"))
+            .await
+            .expect("certificate store save should have succeeded");
+
+        let method = Method::GET.as_str();
+        let path = "/certificate/{certificate_hash}/protocol-message";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_certificate_protocol_message_get_ok_404() {
+        let dependency_manager = initialize_dependencies().await;
+
+        let method = Method::GET.as_str();
+        let path = "/certificate/{certificate_hash}/protocol-message";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::NOT_FOUND,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_certificate_when_error_on_retrieving_protocol_message_returns_ko_500() {
+        let mut dependency_manager = initialize_dependencies().await;
+        let mut message_service = MockMessageService::new();
+        message_service
+            .expect_get_certificate_protocol_message()
+            .returning(|_| Err(anyhow!("an error")));
+        dependency_manager.message_service = Arc::new(message_service);
+
+        let method = Method::GET.as_str();
+        let path = "/certificate/{certificate_hash}/protocol-message";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{}",
+                path.replace("{certificate_hash}", "whatever")
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_certificate_chain_checkpoints_get_ok() {
+        let mut checkpoint_storer = MockCertificateChainCheckpointStorer::new();
+        checkpoint_storer
+            .expect_get_checkpoints()
+            .return_once(|| {
+                Ok(vec![CertificateChainCheckpointRecord {
+                    epoch: Epoch(10),
+                    certificate_hash: "certificate-hash-10".to_string(),
+                    signature: "7369676e6174757265".to_string(),
+                    created_at: chrono::Utc::now(),
+                }])
+            })
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certificate_chain_checkpoint_storer = Arc::new(checkpoint_storer);
+
+        let method = Method::GET.as_str();
+        let path = "/certificate-chain-checkpoints";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_certificate_chain_checkpoints_get_ko() {
+        let mut checkpoint_storer = MockCertificateChainCheckpointStorer::new();
+        checkpoint_storer
+            .expect_get_checkpoints()
+            .return_once(|| Err(anyhow!("an error")))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certificate_chain_checkpoint_storer = Arc::new(checkpoint_storer);
+
+        let method = Method::GET.as_str();
+        let path = "/certificate-chain-checkpoints";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_genesis_key_rotations_get_ok() {
+        let (certificates, _) = setup_certificate_chain(5, 2);
+        let dependency_manager = initialize_dependencies().await;
+        dependency_manager
+            .certificate_repository
+            .create_many_certificates(certificates.iter().rev().cloned().collect())
+            .await
+            .unwrap();
+        let rotation_certificate =
+            CertificateGenesisProducer::create_genesis_key_rotation_certificate(
+                &certificates[0],
+                &ProtocolGenesisSigner::create_deterministic_genesis_signer(),
+                &ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed(
+                    [1u8; 32],
+                )),
+            )
+            .unwrap();
+        dependency_manager
+            .certificate_repository
+            .create_certificate(rotation_certificate.clone())
+            .await
+            .unwrap();
+
+        let method = Method::GET.as_str();
+        let path = "/genesis-key-rotations";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        let message: CertificateListMessage = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(
+            vec![rotation_certificate.hash],
+            message.into_iter().map(|c| c.hash).collect::<Vec<_>>()
+        );
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_genesis_key_rotations_get_cbor_ok() {
+        let (certificates, _) = setup_certificate_chain(5, 2);
+        let dependency_manager = initialize_dependencies().await;
+        dependency_manager
+            .certificate_repository
+            .create_many_certificates(certificates.iter().rev().cloned().collect())
+            .await
+            .unwrap();
+        let rotation_certificate =
+            CertificateGenesisProducer::create_genesis_key_rotation_certificate(
+                &certificates[0],
+                &ProtocolGenesisSigner::create_deterministic_genesis_signer(),
+                &ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed(
+                    [1u8; 32],
+                )),
+            )
+            .unwrap();
+        dependency_manager
+            .certificate_repository
+            .create_certificate(rotation_certificate.clone())
+            .await
+            .unwrap();
+
+        let response = request()
+            .method(Method::GET.as_str())
+            .path(&format!(
+                "/{SERVER_BASE_PATH}/genesis-key-rotations?format=cbor"
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!("application/cbor", response.headers()["content-type"]);
+        assert_eq!(
+            vec![rotation_certificate.hash],
+            certificates_from_cbor_bytes(response.body())
+                .unwrap()
+                .into_iter()
+                .map(|c| c.hash)
+                .collect::<Vec<_>>()
+        );
+    }
 }
@@ -15,6 +15,7 @@ fn epoch_settings(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("epoch-settings")
         .and(warp::get())
+        .and(middlewares::with_client_api_version())
         .and(middlewares::with_epoch_service(dependency_manager))
         .and_then(handlers::epoch_settings)
 }
@@ -24,13 +25,17 @@ mod handlers {
     use crate::http_server::routes::reply;
     use crate::ToEpochSettingsMessageAdapter;
     use mithril_common::entities::EpochSettings;
-    use mithril_common::messages::ToMessageAdapter;
+    use mithril_common::messages::{EpochSettingsMessage, ToMessageAdapter};
+    use semver::Version;
     use slog_scope::{debug, warn};
     use std::convert::Infallible;
     use warp::http::StatusCode;
 
     /// Epoch Settings
+    ///
+    /// The upcoming signers are only computed for the clients whose API version supports them.
     pub async fn epoch_settings(
+        client_api_version: Option<Version>,
         epoch_service: EpochServiceWrapper,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: epoch_settings");
@@ -42,10 +47,26 @@ mod handlers {
             epoch_service.upcoming_protocol_parameters(),
         ) {
             (Ok(epoch), Ok(protocol_parameters), Ok(next_protocol_parameters)) => {
+                let upcoming_signers = if client_api_version.is_some_and(|version| {
+                    EpochSettingsMessage::is_upcoming_signers_supported_by(&version)
+                }) {
+                    epoch_service
+                        .upcoming_signers_with_stake()
+                        .await
+                        .ok()
+                        .map(|signers| signers.into_iter().map(Into::into).collect())
+                } else {
+                    None
+                };
                 let epoch_settings = EpochSettings {
                     epoch,
                     protocol_parameters: protocol_parameters.clone(),
                     next_protocol_parameters: next_protocol_parameters.clone(),
+                    protocol_feature_activations: epoch_service
+                        .protocol_feature_activations()
+                        .clone(),
+                    registration_nonce: epoch_service.current_registration_nonce().ok(),
+                    upcoming_signers,
                 };
                 let epoch_settings_message = ToEpochSettingsMessageAdapter::adapt(epoch_settings);
                 Ok(reply::json(&epoch_settings_message, StatusCode::OK))
@@ -61,8 +82,13 @@ mod handlers {
 #[cfg(test)]
 mod tests {
     use mithril_common::{
-        entities::Epoch,
+        entities::{
+            Epoch, ProtocolFeature, ProtocolFeatureActivations, SignerWithStake,
+            StakeDistributionParty,
+        },
+        messages::{EpochSettingsMessage, UPCOMING_SIGNERS_MIN_API_VERSION},
         test_utils::{apispec::APISpec, MithrilFixtureBuilder},
+        MITHRIL_API_VERSION_HEADER,
     };
     use serde_json::Value::Null;
     use tokio::sync::RwLock;
@@ -71,7 +97,7 @@ mod tests {
 
     use crate::http_server::SERVER_BASE_PATH;
     use crate::initialize_dependencies;
-    use crate::services::FakeEpochService;
+    use crate::services::{EpochService, FakeEpochService};
 
     use super::*;
 
@@ -115,6 +141,106 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_epoch_settings_get_ok_with_protocol_feature_activations() {
+        let mut dependency_manager = initialize_dependencies().await;
+        let fixture = MithrilFixtureBuilder::default().with_signers(5).build();
+        let protocol_feature_activations = ProtocolFeatureActivations::new()
+            .with_activation(ProtocolFeature::BatchedSignedEntityTypes, Epoch(12));
+        let epoch_service = FakeEpochService::from_fixture(Epoch(5), &fixture)
+            .with_protocol_feature_activations(protocol_feature_activations.clone());
+        dependency_manager.epoch_service = Arc::new(RwLock::new(epoch_service));
+
+        let response = request()
+            .method(Method::GET.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/epoch-settings"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        let message: EpochSettingsMessage = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(
+            protocol_feature_activations,
+            message.protocol_feature_activations
+        );
+    }
+
+    #[tokio::test]
+    async fn test_epoch_settings_get_ok_with_registration_nonce() {
+        let mut dependency_manager = initialize_dependencies().await;
+        let fixture = MithrilFixtureBuilder::default().with_signers(5).build();
+        let epoch_service = FakeEpochService::from_fixture(Epoch(5), &fixture);
+        let registration_nonce = epoch_service.current_registration_nonce().unwrap();
+        dependency_manager.epoch_service = Arc::new(RwLock::new(epoch_service));
+
+        let response = request()
+            .method(Method::GET.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/epoch-settings"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        let message: EpochSettingsMessage = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(Some(registration_nonce), message.registration_nonce);
+    }
+
+    async fn request_epoch_settings_with_upcoming_signers(
+        client_api_version: Option<&str>,
+    ) -> (Vec<SignerWithStake>, EpochSettingsMessage) {
+        let mut dependency_manager = initialize_dependencies().await;
+        let fixture = MithrilFixtureBuilder::default().with_signers(5).build();
+        let upcoming_signers = fixture.signers_with_stake()[0..2].to_vec();
+        let epoch_service = FakeEpochService::from_fixture(Epoch(5), &fixture)
+            .with_upcoming_signers(&upcoming_signers);
+        dependency_manager.epoch_service = Arc::new(RwLock::new(epoch_service));
+
+        let mut request = request()
+            .method(Method::GET.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/epoch-settings"));
+        if let Some(client_api_version) = client_api_version {
+            request = request.header(MITHRIL_API_VERSION_HEADER, client_api_version);
+        }
+        let response = request
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        let message: EpochSettingsMessage = serde_json::from_slice(response.body()).unwrap();
+
+        (upcoming_signers, message)
+    }
+
+    #[tokio::test]
+    async fn test_epoch_settings_get_ok_with_upcoming_signers() {
+        let (upcoming_signers, message) = request_epoch_settings_with_upcoming_signers(Some(
+            &UPCOMING_SIGNERS_MIN_API_VERSION.to_string(),
+        ))
+        .await;
+
+        assert_eq!(
+            Some(
+                upcoming_signers
+                    .into_iter()
+                    .map(StakeDistributionParty::from)
+                    .collect()
+            ),
+            message.upcoming_signers
+        );
+    }
+
+    #[tokio::test]
+    async fn test_epoch_settings_get_ok_without_upcoming_signers_for_older_clients() {
+        for client_api_version in [None, Some("0.1.72")] {
+            let (_, message) =
+                request_epoch_settings_with_upcoming_signers(client_api_version).await;
+
+            assert_eq!(
+                None, message.upcoming_signers,
+                "client API version: {client_api_version:?}"
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_epoch_settings_get_ko_500() {
         let method = Method::GET.as_str();
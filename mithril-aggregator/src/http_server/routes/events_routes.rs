@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use warp::Filter;
+
+use crate::http_server::routes::middlewares;
+use crate::DependencyContainer;
+
+/// Number of events returned when no `limit` is given
+const DEFAULT_EVENTS_LIMIT: usize = 100;
+
+/// Maximum number of events that can be returned at once
+const MAX_EVENTS_LIMIT: usize = 1000;
+
+#[derive(Deserialize, Serialize, Debug)]
+struct EventsQueryParams {
+    action: Option<String>,
+    source: Option<String>,
+    since: Option<String>,
+    limit: Option<usize>,
+}
+
+pub fn routes(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    events(dependency_manager)
+}
+
+/// GET /events
+fn events(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("events")
+        .and(warp::get())
+        .and(warp::query::<EventsQueryParams>())
+        .and(middlewares::with_event_reader(dependency_manager))
+        .and_then(handlers::events)
+}
+
+mod handlers {
+    use chrono::{DateTime, Utc};
+    use slog_scope::{debug, warn};
+    use std::{convert::Infallible, sync::Arc};
+    use warp::http::StatusCode;
+
+    use crate::entities::{EventListItemMessage, EventListMessage};
+    use crate::event_store::{EventFilters, EventReader};
+    use crate::http_server::routes::reply;
+    use crate::unwrap_to_internal_server_error;
+
+    use super::{EventsQueryParams, DEFAULT_EVENTS_LIMIT, MAX_EVENTS_LIMIT};
+
+    /// Events
+    pub async fn events(
+        query_parameters: EventsQueryParams,
+        event_reader: Arc<dyn EventReader>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: events"; "query_parameters" => ?query_parameters);
+
+        let since = match &query_parameters.since {
+            Some(since) => match DateTime::parse_from_rfc3339(since) {
+                Ok(since) => Some(since.with_timezone(&Utc)),
+                Err(error) => {
+                    warn!("events::bad_request"; "since" => since, "error" => ?error);
+                    return Ok(reply::bad_request(
+                        "invalid_since_parameter".to_string(),
+                        format!("'since' must be a RFC 3339 date, got '{since}': {error}"),
+                    ));
+                }
+            },
+            None => None,
+        };
+        let filters = EventFilters {
+            action: query_parameters.action,
+            source: query_parameters.source,
+            since,
+            limit: query_parameters
+                .limit
+                .unwrap_or(DEFAULT_EVENTS_LIMIT)
+                .min(MAX_EVENTS_LIMIT),
+        };
+
+        let events = unwrap_to_internal_server_error!(
+            event_reader.get_events(filters).await,
+            "events::error"
+        );
+        let message: EventListMessage =
+            events.into_iter().map(EventListItemMessage::from).collect();
+
+        Ok(reply::json(&message, StatusCode::OK))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use chrono::Utc;
+    use mithril_common::test_utils::apispec::APISpec;
+    use serde_json::Value::Null;
+    use warp::{
+        http::{Method, StatusCode},
+        test::request,
+    };
+
+    use crate::event_store::{Event, EventFilters, MockEventReader};
+    use crate::{http_server::SERVER_BASE_PATH, initialize_dependencies};
+
+    use super::*;
+
+    fn setup_router(
+        dependency_manager: Arc<DependencyContainer>,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let cors = warp::cors()
+            .allow_any_origin()
+            .allow_headers(vec!["content-type"])
+            .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+
+        warp::any()
+            .and(warp::path(SERVER_BASE_PATH))
+            .and(routes(dependency_manager).with(cors))
+    }
+
+    #[tokio::test]
+    async fn test_events_get_ok() {
+        let mut mock_event_reader = MockEventReader::new();
+        mock_event_reader
+            .expect_get_events()
+            .withf(|filters| {
+                filters
+                    == &EventFilters {
+                        action: Some("certificate_created".to_string()),
+                        source: None,
+                        since: None,
+                        limit: MAX_EVENTS_LIMIT,
+                    }
+            })
+            .return_once(|_| {
+                Ok(vec![Event {
+                    event_id: 1,
+                    created_at: Utc::now(),
+                    source: "Runtime::create_certificate".to_string(),
+                    action: "certificate_created".to_string(),
+                    content:
+                        r#"{"headers": {"epoch": "5"}, "content": {"certificate_hash": "abc"}}"#
+                            .to_string(),
+                }])
+            })
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.event_reader = Arc::new(mock_event_reader);
+
+        let method = Method::GET.as_str();
+        let path = "/events";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{path}?action=certificate_created&limit=5000"
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_events_get_with_invalid_since_parameter() {
+        let mut mock_event_reader = MockEventReader::new();
+        mock_event_reader.expect_get_events().never();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.event_reader = Arc::new(mock_event_reader);
+
+        let method = Method::GET.as_str();
+        let path = "/events";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}?since=yesterday"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::BAD_REQUEST,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_events_get_ko() {
+        let mut mock_event_reader = MockEventReader::new();
+        mock_event_reader
+            .expect_get_events()
+            .return_once(|_| Err(anyhow!("an error")))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.event_reader = Arc::new(mock_event_reader);
+
+        let method = Method::GET.as_str();
+        let path = "/events";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .unwrap();
+    }
+}
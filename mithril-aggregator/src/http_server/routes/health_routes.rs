@@ -0,0 +1,206 @@
+use std::sync::Arc;
+use warp::Filter;
+
+use crate::http_server::routes::middlewares;
+use crate::DependencyContainer;
+
+pub fn routes(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    health_ready(dependency_manager)
+}
+
+/// GET /health/ready
+fn health_ready(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("health" / "ready")
+        .and(warp::get())
+        .and(middlewares::with_stale_snapshot_detector(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_immutable_file_lag_watchdog(
+            dependency_manager,
+        ))
+        .and_then(handlers::ready)
+}
+
+mod handlers {
+    use chrono::Utc;
+    use slog_scope::{debug, warn};
+    use std::{convert::Infallible, sync::Arc};
+    use warp::http::StatusCode;
+
+    use crate::entities::HealthReadyMessage;
+    use crate::http_server::routes::reply;
+    use crate::services::{ImmutableFileLagWatchdog, StaleSnapshotDetector};
+    use crate::unwrap_to_internal_server_error;
+
+    /// Readiness of the aggregator
+    pub async fn ready(
+        stale_snapshot_detector: Arc<dyn StaleSnapshotDetector>,
+        immutable_file_lag_watchdog: Arc<dyn ImmutableFileLagWatchdog>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: health_ready");
+
+        let now = Utc::now();
+        let stale_snapshot = unwrap_to_internal_server_error!(
+            stale_snapshot_detector.get_status(now).await,
+            "health_ready::error"
+        );
+        let immutable_file_lag = immutable_file_lag_watchdog.get_status(now).await;
+
+        Ok(reply::json(
+            &HealthReadyMessage::new(stale_snapshot, immutable_file_lag),
+            StatusCode::OK,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use chrono::{Duration, Utc};
+    use mithril_common::test_utils::apispec::APISpec;
+    use serde_json::Value::Null;
+    use warp::{
+        http::{Method, StatusCode},
+        test::request,
+    };
+
+    use crate::entities::{HealthReadyMessage, HealthStatus};
+    use crate::services::{
+        ImmutableFileLagStatus, MockImmutableFileLagWatchdog, MockStaleSnapshotDetector,
+        StaleSnapshotStatus,
+    };
+    use crate::{http_server::SERVER_BASE_PATH, initialize_dependencies};
+
+    use super::*;
+
+    fn setup_router(
+        dependency_manager: Arc<DependencyContainer>,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let cors = warp::cors()
+            .allow_any_origin()
+            .allow_headers(vec!["content-type"])
+            .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+
+        warp::any()
+            .and(warp::path(SERVER_BASE_PATH))
+            .and(routes(dependency_manager).with(cors))
+    }
+
+    #[tokio::test]
+    async fn test_health_ready_get_ok() {
+        let mut mock_stale_snapshot_detector = MockStaleSnapshotDetector::new();
+        mock_stale_snapshot_detector
+            .expect_get_status()
+            .returning(|now| {
+                Ok(StaleSnapshotStatus {
+                    last_certified_at: Some(now - Duration::try_hours(4).unwrap()),
+                    expected_interval_seconds: Some(3600),
+                    stale_after: Some(now - Duration::try_hours(1).unwrap()),
+                    is_stale: true,
+                })
+            });
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.stale_snapshot_detector = Arc::new(mock_stale_snapshot_detector);
+
+        let method = Method::GET.as_str();
+        let path = "/health/ready";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+
+        let message: HealthReadyMessage = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(HealthStatus::Warning, message.status);
+        assert!(message.stale_snapshot.last_certified_at.unwrap() < Utc::now());
+    }
+
+    #[tokio::test]
+    async fn test_health_ready_get_degraded_if_the_immutable_file_number_is_lagging() {
+        let mut mock_immutable_file_lag_watchdog = MockImmutableFileLagWatchdog::new();
+        mock_immutable_file_lag_watchdog
+            .expect_get_status()
+            .returning(|now| ImmutableFileLagStatus {
+                last_immutable_file_number: Some(10),
+                last_progress_at: Some(now - Duration::try_hours(13).unwrap()),
+                lag_seconds: 13 * 3600,
+                threshold_seconds: 12 * 3600,
+                is_lagging: true,
+            });
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.immutable_file_lag_watchdog = Arc::new(mock_immutable_file_lag_watchdog);
+
+        let method = Method::GET.as_str();
+        let path = "/health/ready";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+
+        let message: HealthReadyMessage = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(HealthStatus::Degraded, message.status);
+        assert_eq!(
+            Some(10),
+            message.immutable_file_lag.last_immutable_file_number
+        );
+    }
+
+    #[tokio::test]
+    async fn test_health_ready_get_ko() {
+        let mut mock_stale_snapshot_detector = MockStaleSnapshotDetector::new();
+        mock_stale_snapshot_detector
+            .expect_get_status()
+            .returning(|_| Err(anyhow!("an error")));
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.stale_snapshot_detector = Arc::new(mock_stale_snapshot_detector);
+
+        let method = Method::GET.as_str();
+        let path = "/health/ready";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .unwrap();
+    }
+}
@@ -1,17 +1,38 @@
 use crate::{
-    database::repository::SignerGetter,
+    artifact_storage::ArtifactStorage,
+    database::repository::{
+        CertificateChainCheckpointStorer, CertificateRepository, OpenMessageRepository,
+        RuntimeDecisionStorer, SignedEntityStorer, SignerGetter, SignerMetadataStorer,
+        SignerParticipationStorer,
+    },
     dependency_injection::EpochServiceWrapper,
-    event_store::{EventMessage, TransmitterService},
+    event_store::{EventMessage, EventReader, TransmitterService},
+    http_server::routes::reply,
     services::{
-        CertifierService, MessageService, ProverService, SignedEntityService, TickerService,
+        CertifierService, ImmutableFileLagWatchdog, MessageService, ProofBudgetService,
+        ProverService, QuorumSimulationService, SignatureAuthenticator,
+        SignatureAuthenticatorError, SignedEntityService, StaleSnapshotDetector, StatusService,
+        TickerService,
     },
-    CertificatePendingStore, Configuration, DependencyContainer, SignerRegisterer,
-    VerificationKeyStorer,
+    tools::{FileDigestCache, SnapshotManifestCache},
+    CertificatePendingStore, Configuration, DependencyContainer, ProtocolParametersStorer,
+    RuntimeCycleTrigger, SignerRegisterer, VerificationKeyStorer,
 };
 
-use mithril_common::{api_version::APIVersionProvider, TimePointProvider};
+use anyhow::anyhow;
+use mithril_common::{
+    api_version::APIVersionProvider,
+    entities::{HexEncodedSingleSignature, PartyId, SingleSignatures},
+    logging::ComponentLogLevels,
+    StdError, TimePointProvider, MITHRIL_API_VERSION_HEADER, MITHRIL_SIGNATURE_ORIGIN_HEADER,
+};
+use semver::Version;
+use serde::de::DeserializeOwned;
+use slog_scope::{debug, warn};
 use std::convert::Infallible;
 use std::sync::Arc;
+use warp::hyper::body::Bytes;
+use warp::reject::Reject;
 use warp::Filter;
 
 /// With certificate pending store
@@ -35,11 +56,22 @@ pub fn with_signer_getter(
     warp::any().map(move || dependency_manager.signer_getter.clone())
 }
 
-/// With config middleware
+/// With artifact storage middleware
+pub fn with_artifact_storage(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn ArtifactStorage>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.artifact_storage.clone())
+}
+
+/// With config middleware, including the settings reloaded since the start of the aggregator
 pub fn with_config(
     dependency_manager: Arc<DependencyContainer>,
 ) -> impl Filter<Extract = (Configuration,), Error = Infallible> + Clone {
-    warp::any().map(move || dependency_manager.config.clone())
+    warp::any().map(move || {
+        dependency_manager
+            .configuration_reloader
+            .reloaded_configuration(&dependency_manager.config)
+    })
 }
 
 /// With Event transmitter middleware
@@ -49,6 +81,20 @@ pub fn with_event_transmitter(
     warp::any().map(move || dependency_manager.event_transmitter.clone())
 }
 
+/// With event reader middleware
+pub fn with_event_reader(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn EventReader>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.event_reader.clone())
+}
+
+/// With log levels middleware
+pub fn with_log_levels(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (ComponentLogLevels,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.log_levels.clone())
+}
+
 /// With time point provider middleware
 pub fn with_time_point_provider(
     dependency_manager: Arc<DependencyContainer>,
@@ -84,6 +130,13 @@ pub fn with_signed_entity_service(
     warp::any().map(move || dependency_manager.signed_entity_service.clone())
 }
 
+/// With signed entity storer
+pub fn with_signed_entity_storer(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn SignedEntityStorer>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.signed_entity_storer.clone())
+}
+
 /// With verification key store
 pub fn with_verification_key_store(
     dependency_manager: Arc<DependencyContainer>,
@@ -98,6 +151,19 @@ pub fn with_api_version_provider(
     warp::any().map(move || dependency_manager.api_version_provider.clone())
 }
 
+/// With the API version sent by the client, `None` if it's missing or can't be parsed
+pub fn with_client_api_version(
+) -> impl Filter<Extract = (Option<Version>,), Error = warp::Rejection> + Clone {
+    warp::header::optional::<String>(MITHRIL_API_VERSION_HEADER)
+        .map(|version: Option<String>| version.and_then(|v| Version::parse(&v).ok()))
+}
+
+/// With the entity tags sent by the client in the `If-None-Match` header, if any
+pub fn with_if_none_match(
+) -> impl Filter<Extract = (Option<String>,), Error = warp::Rejection> + Clone {
+    warp::header::optional::<String>(reply::IF_NONE_MATCH_HEADER)
+}
+
 /// With Message service
 pub fn with_http_message_service(
     dependency_manager: Arc<DependencyContainer>,
@@ -105,9 +171,245 @@ pub fn with_http_message_service(
     warp::any().map(move || dependency_manager.message_service.clone())
 }
 
+/// With Quorum simulation service
+pub fn with_quorum_simulation_service(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn QuorumSimulationService>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.quorum_simulation_service.clone())
+}
+
+/// With Status service
+pub fn with_status_service(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn StatusService>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.status_service.clone())
+}
+
 /// With Prover service
 pub fn with_prover_service(
     dependency_manager: Arc<DependencyContainer>,
 ) -> impl Filter<Extract = (Arc<dyn ProverService>,), Error = Infallible> + Clone {
     warp::any().map(move || dependency_manager.prover_service.clone())
 }
+
+/// With Proof budget service
+pub fn with_proof_budget_service(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn ProofBudgetService>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.proof_budget_service.clone())
+}
+
+/// With runtime decision storer
+pub fn with_runtime_decision_storer(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn RuntimeDecisionStorer>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.runtime_decision_storer.clone())
+}
+
+/// With certificate chain checkpoint storer
+pub fn with_certificate_chain_checkpoint_storer(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn CertificateChainCheckpointStorer>,), Error = Infallible> + Clone
+{
+    warp::any().map(move || {
+        dependency_manager
+            .certificate_chain_checkpoint_storer
+            .clone()
+    })
+}
+
+/// With stale snapshot detector
+pub fn with_stale_snapshot_detector(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn StaleSnapshotDetector>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.stale_snapshot_detector.clone())
+}
+
+/// With immutable file lag watchdog
+pub fn with_immutable_file_lag_watchdog(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn ImmutableFileLagWatchdog>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.immutable_file_lag_watchdog.clone())
+}
+
+/// With file digest cache
+pub fn with_file_digest_cache(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<FileDigestCache>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.file_digest_cache.clone())
+}
+
+/// With snapshot manifest cache
+pub fn with_snapshot_manifest_cache(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<SnapshotManifestCache>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.snapshot_manifest_cache.clone())
+}
+
+/// With runtime cycle trigger middleware
+pub fn with_runtime_cycle_trigger(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<RuntimeCycleTrigger>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.runtime_cycle_trigger.clone())
+}
+
+/// With signer metadata storer
+pub fn with_signer_metadata_storer(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn SignerMetadataStorer>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.signer_metadata_storer.clone())
+}
+
+/// With signature authenticator
+pub fn with_signature_authenticator(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn SignatureAuthenticator>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.signature_authenticator.clone())
+}
+
+/// Message sent by the signers, which origin is authenticated by
+/// [with_signer_authenticated_body]
+pub trait SignerOriginatedMessage {
+    /// Maximum length of the body of the requests sending the message
+    const MAX_CONTENT_LENGTH: u64;
+
+    /// Maximum number of single signatures held by the message, the messages holding more
+    /// single signatures are rejected before being authenticated
+    const MAX_SINGLE_SIGNATURES: usize;
+
+    /// Single signatures held by the message with the party ids of their signers, the origin of
+    /// the single signatures of each signer is authenticated with the signature it sends in the
+    /// `mithril-signature-origin` header
+    fn origin_single_signatures(&self) -> Vec<(PartyId, HexEncodedSingleSignature)>;
+
+    /// Single signatures held by the message, which must be authenticated with the signature of
+    /// the registration nonce sent along each of them
+    ///
+    /// The single signatures that can not be decoded are left out, they are rejected by the
+    /// handlers of the message.
+    fn single_signatures(&self) -> Vec<SingleSignatures>;
+}
+
+/// Rejection of a request sent by a signer which body can not be decoded or which origin can not
+/// be authenticated
+#[derive(Debug)]
+pub enum SignerOriginRejection {
+    /// The body of the request can not be decoded
+    InvalidBody(StdError),
+
+    /// The request holds more single signatures than allowed
+    TooManySingleSignatures(StdError),
+
+    /// The request is not sent with the signatures needed to authenticate it
+    Unauthenticated(StdError),
+
+    /// The signatures sent with the request do not authenticate it
+    Forbidden(StdError),
+
+    /// The authentication of the origin of the request failed
+    Error(StdError),
+}
+
+impl SignerOriginRejection {
+    fn from_authentication_error(err: StdError) -> Self {
+        match err.downcast_ref::<SignatureAuthenticatorError>() {
+            Some(
+                SignatureAuthenticatorError::MissingNonceSignature(_)
+                | SignatureAuthenticatorError::MissingOriginSignature(_),
+            ) => {
+                debug!("signer_origin::unauthenticated"; "error" => ?err);
+                Self::Unauthenticated(err)
+            }
+            Some(_) => {
+                debug!("signer_origin::forbidden"; "error" => ?err);
+                Self::Forbidden(err)
+            }
+            None => {
+                warn!("signer_origin::authentication_error"; "error" => ?err);
+                Self::Error(err)
+            }
+        }
+    }
+}
+
+impl Reject for SignerOriginRejection {}
+
+/// With the JSON body of a request sent by signers, rejected with a [SignerOriginRejection] if
+/// its origin can not be authenticated with the signatures of the `mithril-signature-origin` header
+/// or if one of its single signatures is not sent with a valid signature of the registration nonce
+///
+/// The size of the body and its number of single signatures are checked before authenticating
+/// it, so that an oversized request does not trigger any signature verification.
+pub fn with_signer_authenticated_body<T>(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (T,), Error = warp::Rejection> + Clone
+where
+    T: DeserializeOwned + SignerOriginatedMessage + Send + 'static,
+{
+    warp::header::optional::<String>(MITHRIL_SIGNATURE_ORIGIN_HEADER)
+        .and(warp::body::content_length_limit(T::MAX_CONTENT_LENGTH))
+        .and(warp::body::bytes())
+        .and(with_signature_authenticator(dependency_manager))
+        .and_then(
+            |origin_signatures: Option<String>,
+             body: Bytes,
+             signature_authenticator: Arc<dyn SignatureAuthenticator>| async move {
+                let message: T = serde_json::from_slice(&body).map_err(|err| {
+                    warp::reject::custom(SignerOriginRejection::InvalidBody(anyhow!(err)))
+                })?;
+                let origin_single_signatures = message.origin_single_signatures();
+                if origin_single_signatures.len() > T::MAX_SINGLE_SIGNATURES {
+                    return Err(warp::reject::custom(
+                        SignerOriginRejection::TooManySingleSignatures(anyhow!(
+                            "the request holds {} signatures but it can not hold more than {}",
+                            origin_single_signatures.len(),
+                            T::MAX_SINGLE_SIGNATURES
+                        )),
+                    ));
+                }
+
+                signature_authenticator
+                    .authenticate_origin(&origin_single_signatures, origin_signatures.as_deref())
+                    .await
+                    .map_err(|err| {
+                        warp::reject::custom(SignerOriginRejection::from_authentication_error(err))
+                    })?;
+                signature_authenticator
+                    .authenticate(&message.single_signatures())
+                    .await
+                    .map_err(|err| {
+                        warp::reject::custom(SignerOriginRejection::from_authentication_error(err))
+                    })?;
+
+                Ok::<T, warp::Rejection>(message)
+            },
+        )
+}
+
+/// With signer participation storer
+pub fn with_signer_participation_storer(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn SignerParticipationStorer>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.signer_participation_storer.clone())
+}
+
+/// With open message repository
+pub fn with_open_message_repository(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<OpenMessageRepository>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.open_message_repository.clone())
+}
+
+/// With protocol parameters store
+pub fn with_protocol_parameters_store(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<dyn ProtocolParametersStorer>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.protocol_parameters_store.clone())
+}
+
+/// With certificate repository
+pub fn with_certificate_repository(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (Arc<CertificateRepository>,), Error = Infallible> + Clone {
+    warp::any().map(move || dependency_manager.certificate_repository.clone())
+}
@@ -1,14 +1,23 @@
+mod admin_routes;
 mod artifact_routes;
+mod audit_routes;
+mod beacon_routes;
 mod certificate_routes;
 mod epoch_routes;
+mod events_routes;
+mod health_routes;
 mod middlewares;
+mod open_message_routes;
+mod openapi_routes;
 mod proof_routes;
+mod protocol_parameters_routes;
 pub(crate) mod reply;
 mod root_routes;
 pub mod router;
 mod signatures_routes;
 mod signer_routes;
 mod statistics_routes;
+mod status_routes;
 
 /// Match the given result and do an early return with an internal server error (500)
 /// if it was an Error. Else return the unwrapped value.
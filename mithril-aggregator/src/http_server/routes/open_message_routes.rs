@@ -0,0 +1,333 @@
+use std::sync::Arc;
+use warp::Filter;
+
+use crate::http_server::routes::middlewares;
+use crate::DependencyContainer;
+
+pub fn routes(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    open_message_status(dependency_manager)
+}
+
+/// GET /open-message/{discriminant}/status
+fn open_message_status(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("open-message" / String / "status")
+        .and(warp::get())
+        .and(middlewares::with_open_message_repository(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_protocol_parameters_store(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_config(dependency_manager))
+        .and_then(handlers::open_message_status)
+}
+
+mod handlers {
+    use anyhow::anyhow;
+    use slog_scope::{debug, warn};
+    use std::{convert::Infallible, str::FromStr, sync::Arc};
+    use warp::http::StatusCode;
+
+    use mithril_common::entities::SignedEntityTypeDiscriminants;
+
+    use crate::database::repository::OpenMessageRepository;
+    use crate::entities::OpenMessageQuorumStatusMessage;
+    use crate::http_server::routes::reply;
+    use crate::{unwrap_to_internal_server_error, Configuration, ProtocolParametersStorer};
+
+    /// Quorum status of the latest open message of a signed entity type
+    pub async fn open_message_status(
+        discriminant: String,
+        open_message_repository: Arc<OpenMessageRepository>,
+        protocol_parameters_store: Arc<dyn ProtocolParametersStorer>,
+        config: Configuration,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: open_message_status/{discriminant}");
+
+        let signed_entity_type_discriminant =
+            match SignedEntityTypeDiscriminants::from_str(&discriminant) {
+                Ok(signed_entity_type_discriminant) => signed_entity_type_discriminant,
+                Err(err) => {
+                    warn!("open_message_status::invalid_discriminant"; "error" => ?err);
+                    return Ok(reply::bad_request(
+                        "invalid_discriminant".to_string(),
+                        format!("Unknown signed entity type: '{discriminant}'"),
+                    ));
+                }
+            };
+
+        let open_message = match unwrap_to_internal_server_error!(
+            open_message_repository
+                .get_latest_open_message_by_discriminant(&signed_entity_type_discriminant)
+                .await,
+            "open_message_status::error"
+        ) {
+            Some(open_message) => open_message,
+            None => return Ok(reply::empty(StatusCode::NOT_FOUND)),
+        };
+        let quorum_status = match unwrap_to_internal_server_error!(
+            open_message_repository
+                .get_open_message_quorum_status(&open_message.open_message_id)
+                .await,
+            "open_message_status::error"
+        ) {
+            Some(quorum_status) => quorum_status,
+            None => {
+                return Ok(reply::internal_server_error(anyhow!(
+                    "No quorum status computed for open message '{}'",
+                    open_message.open_message_id
+                )))
+            }
+        };
+        let signer_retrieval_epoch = unwrap_to_internal_server_error!(
+            open_message.epoch.offset_to_signer_retrieval_epoch(),
+            "open_message_status::error"
+        );
+        let protocol_parameters = match unwrap_to_internal_server_error!(
+            protocol_parameters_store
+                .get_protocol_parameters(signer_retrieval_epoch)
+                .await,
+            "open_message_status::error"
+        ) {
+            Some(protocol_parameters) => protocol_parameters,
+            None => {
+                return Ok(reply::internal_server_error(anyhow!(
+                    "No protocol parameters found for epoch {signer_retrieval_epoch}"
+                )))
+            }
+        };
+
+        let certification_grace_period = unwrap_to_internal_server_error!(
+            config.list_certification_grace_periods(),
+            "open_message_status::error"
+        )
+        .get(&signed_entity_type_discriminant)
+        .copied()
+        .unwrap_or_default();
+
+        Ok(reply::json(
+            &OpenMessageQuorumStatusMessage::new(
+                open_message,
+                quorum_status,
+                &protocol_parameters,
+                certification_grace_period,
+            ),
+            StatusCode::OK,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value::Null;
+    use warp::{
+        http::{Method, StatusCode},
+        test::request,
+    };
+
+    use mithril_common::entities::{Epoch, ProtocolMessage, SignedEntityType};
+    use mithril_common::test_utils::{apispec::APISpec, fake_data};
+
+    use crate::entities::OpenMessageQuorumStatusMessage;
+    use crate::{http_server::SERVER_BASE_PATH, initialize_dependencies};
+
+    use super::*;
+
+    fn setup_router(
+        dependency_manager: Arc<DependencyContainer>,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let cors = warp::cors()
+            .allow_any_origin()
+            .allow_headers(vec!["content-type"])
+            .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+
+        warp::any()
+            .and(warp::path(SERVER_BASE_PATH))
+            .and(routes(dependency_manager).with(cors))
+    }
+
+    #[tokio::test]
+    async fn test_open_message_status_get_ok() {
+        let dependency_manager = initialize_dependencies().await;
+        let epoch = Epoch(5);
+        dependency_manager
+            .protocol_parameters_store
+            .save_protocol_parameters(
+                epoch.offset_to_signer_retrieval_epoch().unwrap(),
+                fake_data::protocol_parameters(),
+            )
+            .await
+            .unwrap();
+        dependency_manager
+            .open_message_repository
+            .create_open_message(
+                epoch,
+                &SignedEntityType::MithrilStakeDistribution(epoch),
+                &ProtocolMessage::new(),
+            )
+            .await
+            .unwrap();
+
+        let method = Method::GET.as_str();
+        let path = "/open-message/{discriminant}/status";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{}",
+                path.replace("{discriminant}", "MithrilStakeDistribution")
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+
+        let message: OpenMessageQuorumStatusMessage =
+            serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(
+            SignedEntityType::MithrilStakeDistribution(epoch),
+            message.signed_entity_type
+        );
+        assert_eq!(fake_data::protocol_parameters().k, message.quorum);
+        assert_eq!(0.0, message.quorum_percentage);
+    }
+
+    #[tokio::test]
+    async fn test_open_message_status_get_ok_with_the_end_of_the_certification_grace_period() {
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.config.certification_grace_periods =
+            Some("MithrilStakeDistribution=30".to_string());
+        let epoch = Epoch(5);
+        dependency_manager
+            .protocol_parameters_store
+            .save_protocol_parameters(
+                epoch.offset_to_signer_retrieval_epoch().unwrap(),
+                fake_data::protocol_parameters(),
+            )
+            .await
+            .unwrap();
+        let open_message = dependency_manager
+            .open_message_repository
+            .create_open_message(
+                epoch,
+                &SignedEntityType::MithrilStakeDistribution(epoch),
+                &ProtocolMessage::new(),
+            )
+            .await
+            .unwrap();
+
+        let response = request()
+            .method(Method::GET.as_str())
+            .path(&format!(
+                "/{SERVER_BASE_PATH}/open-message/MithrilStakeDistribution/status"
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        let message: OpenMessageQuorumStatusMessage =
+            serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(
+            open_message.created_at + chrono::Duration::seconds(30),
+            message.certifiable_at
+        );
+    }
+
+    #[tokio::test]
+    async fn test_open_message_status_get_ko_404_without_open_message() {
+        let dependency_manager = initialize_dependencies().await;
+
+        let method = Method::GET.as_str();
+        let path = "/open-message/{discriminant}/status";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{}",
+                path.replace("{discriminant}", "CardanoTransactions")
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::NOT_FOUND,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_open_message_status_get_ko_404_without_open_message_with_the_canonical_name_of_the_discriminant(
+    ) {
+        let dependency_manager = initialize_dependencies().await;
+
+        let method = Method::GET.as_str();
+        let path = "/open-message/{discriminant}/status";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{}",
+                path.replace("{discriminant}", "cardano-transactions")
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::NOT_FOUND,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_open_message_status_get_ko_400_with_unknown_discriminant() {
+        let dependency_manager = initialize_dependencies().await;
+
+        let method = Method::GET.as_str();
+        let path = "/open-message/{discriminant}/status";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{}",
+                path.replace("{discriminant}", "Unknown")
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::BAD_REQUEST,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,239 @@
+use std::sync::Arc;
+use warp::Filter;
+
+use crate::http_server::routes::middlewares;
+use crate::DependencyContainer;
+
+pub fn routes(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    openapi_spec(dependency_manager.clone()).or(openapi_explorer(dependency_manager))
+}
+
+/// GET /openapi.json
+fn openapi_spec(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("openapi.json")
+        .and(warp::get())
+        .and(middlewares::with_api_version_provider(dependency_manager))
+        .and_then(handlers::openapi_spec)
+}
+
+/// GET /openapi
+fn openapi_explorer(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("openapi")
+        .and(warp::get())
+        .and(middlewares::with_config(dependency_manager))
+        .and_then(handlers::openapi_explorer)
+}
+
+mod handlers {
+    use mithril_common::api_version::APIVersionProvider;
+    use slog_scope::{debug, warn};
+    use std::{convert::Infallible, sync::Arc};
+    use warp::http::StatusCode;
+
+    use crate::http_server::routes::reply;
+    use crate::{unwrap_to_internal_server_error, Configuration};
+
+    /// Page of the Open API explorer.
+    ///
+    /// The page is self-contained: its style and script are inlined so that no third-party
+    /// asset is loaded by the browsers of the aggregator users.
+    const OPENAPI_EXPLORER_PAGE: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>Mithril Aggregator API</title>
+  <style>
+    body { font-family: sans-serif; margin: 2em auto; max-width: 960px; color: #222; }
+    details { border: 1px solid #ccc; border-radius: 4px; margin: 0.5em 0; padding: 0.5em; }
+    summary { cursor: pointer; }
+    .method { display: inline-block; min-width: 4em; font-weight: bold; text-transform: uppercase; }
+    pre { background: #f6f6f6; overflow: auto; padding: 0.5em; }
+  </style>
+</head>
+<body>
+  <h1 id="title">Mithril Aggregator API</h1>
+  <p id="description"></p>
+  <div id="operations"></div>
+  <script>
+    const element = (tag, text, className) => {
+      const node = document.createElement(tag);
+      if (text !== undefined) node.textContent = text;
+      if (className !== undefined) node.className = className;
+      return node;
+    };
+
+    fetch("openapi.json")
+      .then((response) => response.json())
+      .then((spec) => {
+        document.getElementById("title").textContent = `${spec.info.title} ${spec.info.version}`;
+        document.getElementById("description").textContent = spec.info.description || "";
+        const operations = document.getElementById("operations");
+        for (const [path, methods] of Object.entries(spec.paths || {})) {
+          for (const [method, operation] of Object.entries(methods)) {
+            const details = element("details");
+            const summary = element("summary");
+            summary.append(element("span", method, "method"), element("code", path));
+            summary.append(` ${operation.summary || ""}`);
+            details.append(summary, element("p", operation.description || ""));
+            details.append(element("pre", JSON.stringify(operation, null, 2)));
+            operations.append(details);
+          }
+        }
+        const schemas = element("details");
+        schemas.append(element("summary", "Schemas"));
+        schemas.append(element("pre", JSON.stringify(spec.components || {}, null, 2)));
+        operations.append(schemas);
+      })
+      .catch((error) => {
+        document.getElementById("description").textContent = `Could not load the specification: ${error}`;
+      });
+  </script>
+</body>
+</html>
+"##;
+
+    /// Open API specification of the running API version
+    pub async fn openapi_spec(
+        api_version_provider: Arc<APIVersionProvider>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: openapi_spec");
+
+        let open_api_spec = unwrap_to_internal_server_error!(
+            api_version_provider.get_current_open_api_spec(),
+            "openapi_spec::error"
+        );
+        let open_api_spec = unwrap_to_internal_server_error!(
+            serde_yaml::from_str::<serde_json::Value>(open_api_spec),
+            "openapi_spec::error"
+        );
+
+        Ok(reply::json(&open_api_spec, StatusCode::OK))
+    }
+
+    /// Open API explorer
+    pub async fn openapi_explorer(
+        config: Configuration,
+    ) -> Result<Box<dyn warp::Reply>, Infallible> {
+        debug!("⇄ HTTP SERVER: openapi_explorer");
+
+        // The explorer route is hidden when it is disabled
+        if !config.enable_openapi_explorer {
+            return Ok(reply::empty(StatusCode::NOT_FOUND));
+        }
+
+        Ok(Box::new(warp::reply::html(OPENAPI_EXPLORER_PAGE)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::apispec::APISpec;
+    use serde_json::Value::Null;
+    use warp::{
+        http::{Method, StatusCode},
+        test::request,
+    };
+
+    use crate::{http_server::SERVER_BASE_PATH, initialize_dependencies};
+
+    use super::*;
+
+    fn setup_router(
+        dependency_manager: Arc<DependencyContainer>,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let cors = warp::cors()
+            .allow_any_origin()
+            .allow_headers(vec!["content-type"])
+            .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+
+        warp::any()
+            .and(warp::path(SERVER_BASE_PATH))
+            .and(routes(dependency_manager).with(cors))
+    }
+
+    #[tokio::test]
+    async fn test_openapi_spec_get_ok() {
+        let dependency_manager = Arc::new(initialize_dependencies().await);
+
+        let method = Method::GET.as_str();
+        let path = "/openapi.json";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(dependency_manager.clone()))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+
+        let open_api: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(
+            dependency_manager
+                .api_version_provider
+                .compute_current_version()
+                .unwrap()
+                .to_string(),
+            open_api["info"]["version"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_openapi_explorer_get_ok_when_enabled() {
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.config.enable_openapi_explorer = true;
+
+        let response = request()
+            .method(Method::GET.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/openapi"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert!(String::from_utf8_lossy(response.body()).contains("openapi.json"));
+    }
+
+    #[tokio::test]
+    async fn test_openapi_explorer_does_not_load_third_party_assets() {
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.config.enable_openapi_explorer = true;
+
+        let response = request()
+            .method(Method::GET.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/openapi"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        let page = String::from_utf8_lossy(response.body());
+        assert!(!page.contains("http://") && !page.contains("https://"));
+        assert!(!page.contains(" src="));
+    }
+
+    #[tokio::test]
+    async fn test_openapi_explorer_is_hidden_when_disabled() {
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.config.enable_openapi_explorer = false;
+
+        let response = request()
+            .method(Method::GET.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/openapi"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    }
+}
@@ -29,35 +29,65 @@ fn proof_cardano_transaction(
     warp::path!("proof" / "cardano-transaction")
         .and(warp::get())
         .and(warp::query::<CardanoTransactionProofQueryParams>())
+        .and(warp::addr::remote())
+        .and(warp::header::headers_cloned())
+        .and(middlewares::with_config(dependency_manager.clone()))
         .and(middlewares::with_signed_entity_service(
             dependency_manager.clone(),
         ))
+        .and(middlewares::with_proof_budget_service(
+            dependency_manager.clone(),
+        ))
         .and(middlewares::with_prover_service(dependency_manager))
         .and_then(handlers::proof_cardano_transaction)
 }
 
 mod handlers {
     use mithril_common::{
-        entities::{CardanoTransactionsSnapshot, SignedEntity},
+        entities::{CardanoTransactionsSnapshot, ClientError, SignedEntity},
         messages::CardanoTransactionsProofsMessage,
         StdResult,
     };
     use slog_scope::{debug, warn};
-    use std::{convert::Infallible, sync::Arc};
-    use warp::http::StatusCode;
+    use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+    use warp::http::{HeaderMap, StatusCode};
 
     use crate::{
         http_server::routes::reply,
         message_adapters::ToCardanoTransactionsProofsMessageAdapter,
-        services::{ProverService, SignedEntityService},
-        unwrap_to_internal_server_error,
+        services::{ProofBudgetError, ProofBudgetService, ProverService, SignedEntityService},
+        unwrap_to_internal_server_error, Configuration,
     };
 
     use super::CardanoTransactionProofQueryParams;
 
+    /// Identify the client of a request by its IP address, read from the header set by the
+    /// trusted reverse proxy if one is configured.
+    ///
+    /// The last address of the header is used since it's the one added by the trusted proxy, the
+    /// previous ones are given by the client and can be forged.
+    pub fn client_ip(
+        client_address: Option<SocketAddr>,
+        headers: &HeaderMap,
+        client_ip_header: Option<&str>,
+    ) -> String {
+        client_ip_header
+            .and_then(|header| headers.get_all(header).iter().last())
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit(',').next())
+            .map(|address| address.trim().to_string())
+            .filter(|address| !address.is_empty())
+            .or_else(|| client_address.map(|address| address.ip().to_string()))
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
     pub async fn proof_cardano_transaction(
         transaction_parameters: CardanoTransactionProofQueryParams,
+        client_address: Option<SocketAddr>,
+        headers: HeaderMap,
+        config: Configuration,
         signed_entity_service: Arc<dyn SignedEntityService>,
+        proof_budget_service: Arc<dyn ProofBudgetService>,
         prover_service: Arc<dyn ProverService>,
     ) -> Result<impl warp::Reply, Infallible> {
         let transaction_hashes = transaction_parameters
@@ -70,6 +100,41 @@ mod handlers {
             transaction_parameters.transaction_hashes
         );
 
+        // The budget is checked first so that an over budget request never reaches the prover
+        let client = client_ip(
+            client_address,
+            &headers,
+            config.proof_client_ip_header.as_deref(),
+        );
+        if let Err(error) = proof_budget_service
+            .charge_request(&client, &transaction_hashes)
+            .await
+        {
+            return match error.downcast_ref::<ProofBudgetError>() {
+                Some(budget_error @ ProofBudgetError::RequestOverBudget { .. }) => {
+                    debug!("proof_cardano_transaction::request_over_budget"; "client" => &client, "error" => ?budget_error);
+                    Ok(reply::bad_request(
+                        "proof_request_over_budget".to_string(),
+                        budget_error.to_string(),
+                    ))
+                }
+                Some(budget_error @ ProofBudgetError::ClientOverBudget { .. }) => {
+                    debug!("proof_cardano_transaction::client_over_budget"; "client" => &client, "error" => ?budget_error);
+                    Ok(reply::json(
+                        &ClientError::new(
+                            "proof_client_over_budget".to_string(),
+                            budget_error.to_string(),
+                        ),
+                        StatusCode::TOO_MANY_REQUESTS,
+                    ))
+                }
+                None => {
+                    warn!("proof_cardano_transaction::budget_error"; "error" => ?error);
+                    Ok(reply::internal_server_error(error))
+                }
+            };
+        }
+
         match unwrap_to_internal_server_error!(
             signed_entity_service
                 .get_last_cardano_transaction_snapshot()
@@ -130,7 +195,7 @@ mod tests {
         test::request,
     };
 
-    use crate::services::MockSignedEntityService;
+    use crate::services::{MockProofBudgetService, MockSignedEntityService, ProofBudgetError};
     use crate::{
         dependency_injection::DependenciesBuilder, http_server::SERVER_BASE_PATH,
         services::MockProverService, Configuration,
@@ -254,6 +319,77 @@ mod tests {
         .unwrap();
     }
 
+    async fn test_proof_request_over_budget(
+        budget_error: ProofBudgetError,
+        expected_status_code: StatusCode,
+    ) {
+        let config = Configuration::new_sample();
+        let mut builder = DependenciesBuilder::new(config);
+        let mut dependency_manager = builder.build_dependency_container().await.unwrap();
+        let mut mock_proof_budget_service = MockProofBudgetService::new();
+        mock_proof_budget_service
+            .expect_charge_request()
+            .return_once(|_, _| Err(budget_error.into()))
+            .once();
+        dependency_manager.proof_budget_service = Arc::new(mock_proof_budget_service);
+        let mut mock_prover_service = MockProverService::new();
+        mock_prover_service
+            .expect_compute_transactions_proofs()
+            .never();
+        dependency_manager.prover_service = Arc::new(mock_prover_service);
+
+        let method = Method::GET.as_str();
+        let path = "/proof/cardano-transaction";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{path}?transaction_hashes=tx-123,tx-456"
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &expected_status_code,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn proof_cardano_transaction_request_over_budget() {
+        test_proof_request_over_budget(
+            ProofBudgetError::RequestOverBudget {
+                cost: 200,
+                max_cost: 100,
+                transactions: 2,
+                block_ranges: 100,
+                max_transactions_per_request: 1,
+            },
+            StatusCode::BAD_REQUEST,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn proof_cardano_transaction_client_over_budget() {
+        test_proof_request_over_budget(
+            ProofBudgetError::ClientOverBudget {
+                cost: 20,
+                remaining_budget: 10,
+                retry_after: std::time::Duration::from_secs(30),
+                max_transactions_per_request: 1,
+            },
+            StatusCode::TOO_MANY_REQUESTS,
+        )
+        .await;
+    }
+
     #[tokio::test]
     async fn proof_cardano_transaction_ko() {
         let config = Configuration::new_sample();
@@ -287,4 +423,25 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn client_ip_is_read_from_the_trusted_proxy_header_if_configured() {
+        let address = Some("10.0.0.1:443".parse().unwrap());
+        let mut headers = warp::http::HeaderMap::new();
+        headers.insert("x-forwarded-for", "1.1.1.1, 2.2.2.2".parse().unwrap());
+
+        assert_eq!("10.0.0.1", handlers::client_ip(address, &headers, None));
+        assert_eq!(
+            "2.2.2.2",
+            handlers::client_ip(address, &headers, Some("X-Forwarded-For"))
+        );
+        assert_eq!(
+            "10.0.0.1",
+            handlers::client_ip(address, &headers, Some("X-Real-IP"))
+        );
+        assert_eq!(
+            "unknown",
+            handlers::client_ip(None, &warp::http::HeaderMap::new(), None)
+        );
+    }
 }
@@ -0,0 +1,194 @@
+use std::sync::Arc;
+use warp::Filter;
+
+use crate::http_server::routes::middlewares;
+use crate::services::QuorumSimulationParameters;
+use crate::DependencyContainer;
+
+pub fn routes(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    protocol_parameters_simulation(dependency_manager)
+}
+
+/// GET /protocol-parameters/simulation
+fn protocol_parameters_simulation(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("protocol-parameters" / "simulation")
+        .and(warp::get())
+        .and(warp::query::<QuorumSimulationParameters>())
+        .and(middlewares::with_quorum_simulation_service(
+            dependency_manager,
+        ))
+        .and_then(handlers::protocol_parameters_simulation)
+}
+
+mod handlers {
+    use slog_scope::{debug, warn};
+    use std::{convert::Infallible, sync::Arc};
+    use warp::http::StatusCode;
+
+    use crate::http_server::routes::reply;
+    use crate::services::{
+        QuorumSimulationError, QuorumSimulationParameters, QuorumSimulationService,
+    };
+
+    /// Simulation of the probability to reach the quorum with the registered signers
+    pub async fn protocol_parameters_simulation(
+        parameters: QuorumSimulationParameters,
+        quorum_simulation_service: Arc<dyn QuorumSimulationService>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: protocol_parameters_simulation"; "parameters" => ?parameters);
+
+        match quorum_simulation_service.simulate(parameters).await {
+            Ok(simulation) => Ok(reply::json(&simulation, StatusCode::OK)),
+            Err(err) => match err.downcast_ref::<QuorumSimulationError>() {
+                Some(QuorumSimulationError::InvalidParameters(_)) => {
+                    debug!("protocol_parameters_simulation::invalid_parameters"; "error" => ?err);
+                    Ok(reply::bad_request(
+                        "invalid_simulation_parameters".to_string(),
+                        err.to_string(),
+                    ))
+                }
+                None => {
+                    warn!("protocol_parameters_simulation::error"; "error" => ?err);
+                    Ok(reply::internal_server_error(err))
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use serde_json::Value::Null;
+    use warp::{
+        http::{Method, StatusCode},
+        test::request,
+    };
+
+    use mithril_common::entities::Epoch;
+    use mithril_common::test_utils::{apispec::APISpec, MithrilFixtureBuilder};
+    use tokio::sync::RwLock;
+
+    use crate::services::{
+        FakeEpochService, MithrilQuorumSimulationService, MockQuorumSimulationService,
+        QuorumSimulationError,
+    };
+    use crate::{http_server::SERVER_BASE_PATH, initialize_dependencies};
+
+    use super::*;
+
+    fn setup_router(
+        dependency_manager: Arc<DependencyContainer>,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let cors = warp::cors()
+            .allow_any_origin()
+            .allow_headers(vec!["content-type"])
+            .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+
+        warp::any()
+            .and(warp::path(SERVER_BASE_PATH))
+            .and(routes(dependency_manager).with(cors))
+    }
+
+    #[tokio::test]
+    async fn test_protocol_parameters_simulation_get_ok() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(3).build();
+        let epoch_service = FakeEpochService::from_fixture(Epoch(4), &fixture);
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.quorum_simulation_service = Arc::new(
+            MithrilQuorumSimulationService::new(Arc::new(RwLock::new(epoch_service))),
+        );
+
+        let method = Method::GET.as_str();
+        let path = "/protocol-parameters/simulation";
+
+        let response = request()
+            .method(method)
+            .path(&format!(
+                "/{SERVER_BASE_PATH}{path}?k=5&m=100&phi_f=0.65&target_probability=0.9"
+            ))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_protocol_parameters_simulation_get_ko_400_with_invalid_parameters() {
+        let mut mock_quorum_simulation_service = MockQuorumSimulationService::new();
+        mock_quorum_simulation_service
+            .expect_simulate()
+            .return_once(|_| {
+                Err(
+                    QuorumSimulationError::InvalidParameters("k must be lower than m".to_string())
+                        .into(),
+                )
+            });
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.quorum_simulation_service = Arc::new(mock_quorum_simulation_service);
+
+        let method = Method::GET.as_str();
+        let path = "/protocol-parameters/simulation";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}?k=101&m=100"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::BAD_REQUEST,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_protocol_parameters_simulation_get_ko_500() {
+        let mut mock_quorum_simulation_service = MockQuorumSimulationService::new();
+        mock_quorum_simulation_service
+            .expect_simulate()
+            .return_once(|_| Err(anyhow!("an error")));
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.quorum_simulation_service = Arc::new(mock_quorum_simulation_service);
+
+        let method = Method::GET.as_str();
+        let path = "/protocol-parameters/simulation";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .unwrap();
+    }
+}
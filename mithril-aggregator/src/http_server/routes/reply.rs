@@ -1,7 +1,39 @@
 use mithril_common::entities::{ClientError, InternalServerError};
+use mithril_common::messages::{DeprecatedFieldsAdapter, PaginatedMessage};
+use mithril_secrets::SecretString;
+use semver::Version;
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use warp::http::StatusCode;
 
+/// Name of the header holding the entity tag of a response
+pub const ETAG_HEADER: &str = "etag";
+
+/// Name of the header holding the entity tags known by the client for a conditional request
+pub const IF_NONE_MATCH_HEADER: &str = "if-none-match";
+
+/// Check the bearer `Authorization` header of a request to a route protected by the given token,
+/// returns the status of the reply to send if the request is rejected.
+///
+/// A protected route is hidden (`404 Not Found`) if no token is configured. The token is compared
+/// in constant time so it can't be guessed from the response time.
+pub fn check_bearer_token(
+    authorization: Option<&str>,
+    token: Option<&SecretString>,
+) -> Result<(), StatusCode> {
+    match token {
+        None => Err(StatusCode::NOT_FOUND),
+        Some(token)
+            if authorization
+                .and_then(|authorization| authorization.strip_prefix("Bearer "))
+                .is_some_and(|received_token| token.matches(received_token)) =>
+        {
+            Ok(())
+        }
+        Some(_) => Err(StatusCode::UNAUTHORIZED),
+    }
+}
+
 pub fn json<T>(value: &T, status_code: StatusCode) -> Box<dyn warp::Reply>
 where
     T: Serialize,
@@ -12,6 +44,105 @@ where
     ))
 }
 
+/// Reply a message in the form expected by a client using the given API version, the deprecated
+/// fields of the message are kept if the client API version is unknown
+pub fn json_for_api_version<T>(
+    value: &T,
+    client_api_version: Option<Version>,
+    status_code: StatusCode,
+) -> Box<dyn warp::Reply>
+where
+    T: DeprecatedFieldsAdapter,
+{
+    let json_value = match client_api_version {
+        Some(version) => value.to_json_value_for_api_version(&version),
+        None => serde_json::to_value(value).map_err(|err| err.into()),
+    };
+
+    match json_value {
+        Ok(json_value) => json(&json_value, status_code),
+        Err(err) => internal_server_error(err),
+    }
+}
+
+/// Reply a list of items, wrapped in a [PaginatedMessage] if the client API version supports it
+pub fn json_list<T>(items: Vec<T>, client_api_version: Option<Version>) -> Box<dyn warp::Reply>
+where
+    T: Serialize,
+{
+    match client_api_version {
+        Some(version) if PaginatedMessage::<T>::is_supported_by(&version) => {
+            json(&PaginatedMessage::single_page(items), StatusCode::OK)
+        }
+        _ => json(&items, StatusCode::OK),
+    }
+}
+
+/// Reply the given value with an `ETag` header computed from its content, or an empty
+/// `304 Not Modified` response if the `If-None-Match` header sent by the client matches it.
+pub fn json_with_etag<T>(value: &T, if_none_match: Option<String>) -> Box<dyn warp::Reply>
+where
+    T: Serialize,
+{
+    let body = match serde_json::to_vec(value) {
+        Ok(body) => body,
+        Err(err) => return internal_server_error(err.to_string()),
+    };
+    let etag = compute_etag(&body);
+
+    if if_none_match.is_some_and(|if_none_match| etag_matches(&if_none_match, &etag)) {
+        Box::new(warp::reply::with_header(
+            empty(StatusCode::NOT_MODIFIED),
+            ETAG_HEADER,
+            etag,
+        ))
+    } else {
+        Box::new(warp::reply::with_header(
+            warp::reply::with_header(body, "content-type", "application/json"),
+            ETAG_HEADER,
+            etag,
+        ))
+    }
+}
+
+/// Same as [json_list] but with the conditional request support of [json_with_etag]
+pub fn json_list_with_etag<T>(
+    items: Vec<T>,
+    client_api_version: Option<Version>,
+    if_none_match: Option<String>,
+) -> Box<dyn warp::Reply>
+where
+    T: Serialize,
+{
+    match client_api_version {
+        Some(version) if PaginatedMessage::<T>::is_supported_by(&version) => {
+            json_with_etag(&PaginatedMessage::single_page(items), if_none_match)
+        }
+        _ => json_with_etag(&items, if_none_match),
+    }
+}
+
+/// Strong entity tag of a response body
+fn compute_etag(body: &[u8]) -> String {
+    format!("\"{}\"", hex::encode(Sha256::digest(body)))
+}
+
+/// Check if the given entity tag is one of the entity tags of an `If-None-Match` header
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(|tag| tag.trim().trim_start_matches("W/"))
+        .any(|tag| tag == "*" || tag == etag)
+}
+
+/// Reply the given CBOR encoded bytes
+pub fn cbor(bytes: Vec<u8>, status_code: StatusCode) -> Box<dyn warp::Reply> {
+    Box::new(warp::reply::with_status(
+        warp::reply::with_header(bytes, "content-type", "application/cbor"),
+        status_code,
+    ))
+}
+
 pub fn empty(status_code: StatusCode) -> Box<dyn warp::Reply> {
     Box::new(warp::reply::with_status(warp::reply::reply(), status_code))
 }
@@ -20,6 +151,14 @@ pub fn bad_request(label: String, message: String) -> Box<dyn warp::Reply> {
     json(&ClientError::new(label, message), StatusCode::BAD_REQUEST)
 }
 
+pub fn unauthorized(label: String, message: String) -> Box<dyn warp::Reply> {
+    json(&ClientError::new(label, message), StatusCode::UNAUTHORIZED)
+}
+
+pub fn forbidden(label: String, message: String) -> Box<dyn warp::Reply> {
+    json(&ClientError::new(label, message), StatusCode::FORBIDDEN)
+}
+
 pub fn internal_server_error<T: Into<InternalServerError>>(message: T) -> Box<dyn warp::Reply> {
     json(&message.into(), StatusCode::INTERNAL_SERVER_ERROR)
 }
@@ -27,3 +166,97 @@ pub fn internal_server_error<T: Into<InternalServerError>>(message: T) -> Box<dy
 pub fn service_unavailable<T: Into<InternalServerError>>(message: T) -> Box<dyn warp::Reply> {
     json(&message.into(), StatusCode::SERVICE_UNAVAILABLE)
 }
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::messages::{
+        CertificatePendingMessage, SIGNER_PARTY_ID_REMOVAL_API_VERSION,
+    };
+    use warp::http::header::{CONTENT_TYPE, ETAG};
+    use warp::hyper::body::to_bytes;
+    use warp::Reply;
+
+    use super::*;
+
+    async fn reply_json_value(reply: Box<dyn warp::Reply>) -> serde_json::Value {
+        let body = to_bytes(reply.into_response().into_body()).await.unwrap();
+
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn json_for_api_version_removes_the_deprecated_fields_for_newer_clients() {
+        let message = CertificatePendingMessage::dummy();
+
+        let json_value = reply_json_value(json_for_api_version(
+            &message,
+            Some(SIGNER_PARTY_ID_REMOVAL_API_VERSION),
+            StatusCode::OK,
+        ))
+        .await;
+
+        assert!(json_value["signers"][0].get("party_id").is_none());
+    }
+
+    #[tokio::test]
+    async fn json_for_api_version_keeps_the_deprecated_fields_for_older_or_unknown_clients() {
+        let message = CertificatePendingMessage::dummy();
+
+        for client_api_version in [None, Some(Version::new(0, 1, 0))] {
+            let json_value = reply_json_value(json_for_api_version(
+                &message,
+                client_api_version.clone(),
+                StatusCode::OK,
+            ))
+            .await;
+
+            assert_eq!(
+                serde_json::to_value(&message).unwrap(),
+                json_value,
+                "client api version: {client_api_version:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn json_with_etag_reply_the_value_with_its_etag() {
+        let response = json_with_etag(&vec!["a", "b"], None).into_response();
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!("application/json", response.headers()[CONTENT_TYPE]);
+        assert_eq!(
+            compute_etag(br#"["a","b"]"#),
+            response.headers()[ETAG].to_str().unwrap()
+        );
+    }
+
+    #[test]
+    fn json_with_etag_reply_not_modified_if_the_etag_match() {
+        let etag = compute_etag(br#"["a","b"]"#);
+
+        for if_none_match in [
+            etag.clone(),
+            format!("W/{etag}"),
+            format!("\"other\", {etag}"),
+            "*".to_string(),
+        ] {
+            let response =
+                json_with_etag(&vec!["a", "b"], Some(if_none_match.clone())).into_response();
+
+            assert_eq!(
+                StatusCode::NOT_MODIFIED,
+                response.status(),
+                "If-None-Match '{if_none_match}' should match"
+            );
+            assert_eq!(etag, response.headers()[ETAG].to_str().unwrap());
+        }
+    }
+
+    #[test]
+    fn json_with_etag_reply_the_value_if_the_etag_does_not_match() {
+        let response =
+            json_with_etag(&vec!["a", "b"], Some("\"other\"".to_string())).into_response();
+
+        assert_eq!(StatusCode::OK, response.status());
+    }
+}
@@ -1,21 +1,25 @@
 use crate::http_server::routes::{
-    artifact_routes, certificate_routes, epoch_routes, root_routes, signatures_routes,
-    signer_routes, statistics_routes,
+    admin_routes, artifact_routes, audit_routes, beacon_routes, certificate_routes, epoch_routes,
+    events_routes, health_routes, openapi_routes, root_routes, signatures_routes, signer_routes,
+    statistics_routes, status_routes,
 };
 use crate::http_server::SERVER_BASE_PATH;
-use crate::DependencyContainer;
+use crate::{Configuration, DependencyContainer};
 
 use mithril_common::api_version::APIVersionProvider;
 use mithril_common::MITHRIL_API_VERSION_HEADER;
 
 use slog_scope::warn;
+use std::str::FromStr;
 use std::sync::Arc;
+use warp::filters::BoxedFilter;
 use warp::http::Method;
 use warp::http::StatusCode;
 use warp::reject::Reject;
 use warp::{Filter, Rejection, Reply};
 
-use super::{middlewares, proof_routes};
+use super::middlewares::SignerOriginRejection;
+use super::{middlewares, open_message_routes, proof_routes, protocol_parameters_routes, reply};
 
 #[derive(Debug)]
 pub struct VersionMismatchError;
@@ -31,12 +35,12 @@ impl Reject for VersionParseError {}
 pub fn routes(
     dependency_manager: Arc<DependencyContainer>,
 ) -> impl Filter<Extract = (impl Reply,), Error = warp::Rejection> + Clone {
-    let cors = warp::cors()
-        .allow_any_origin()
-        .allow_headers(vec!["content-type", MITHRIL_API_VERSION_HEADER])
-        .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+    let cors = build_cors(&dependency_manager.config);
 
     warp::any()
+        .and(path_prefix(
+            dependency_manager.config.server_path_prefix.clone(),
+        ))
         .and(header_must_be(
             dependency_manager.api_version_provider.clone(),
         ))
@@ -55,8 +59,19 @@ pub fn routes(
                 .or(proof_routes::routes(dependency_manager.clone()))
                 .or(signer_routes::routes(dependency_manager.clone()))
                 .or(signatures_routes::routes(dependency_manager.clone()))
+                .or(open_message_routes::routes(dependency_manager.clone()))
                 .or(epoch_routes::routes(dependency_manager.clone()))
+                .or(protocol_parameters_routes::routes(
+                    dependency_manager.clone(),
+                ))
                 .or(statistics_routes::routes(dependency_manager.clone()))
+                .or(audit_routes::routes(dependency_manager.clone()))
+                .or(events_routes::routes(dependency_manager.clone()))
+                .or(admin_routes::routes(dependency_manager.clone()))
+                .or(beacon_routes::routes(dependency_manager.clone()))
+                .or(health_routes::routes(dependency_manager.clone()))
+                .or(status_routes::routes(dependency_manager.clone()))
+                .or(openapi_routes::routes(dependency_manager.clone()))
                 .or(root_routes::routes(dependency_manager.clone()))
                 .with(cors),
         )
@@ -74,6 +89,73 @@ pub fn routes(
         })
 }
 
+/// Routes of an aggregator hosting several networks.
+///
+/// The routes of each hosted network are served under the path prefix of its configuration,
+/// the routes of the main network are served when none of them match.
+pub fn with_hosted_networks_routes<F, R>(
+    main_routes: F,
+    hosted_networks_routes: Vec<F>,
+) -> BoxedFilter<(Box<dyn Reply>,)>
+where
+    F: Filter<Extract = (R,), Error = Rejection> + Clone + Send + Sync + 'static,
+    R: Reply + 'static,
+{
+    fn boxed<F, R>(routes: F) -> BoxedFilter<(Box<dyn Reply>,)>
+    where
+        F: Filter<Extract = (R,), Error = Rejection> + Clone + Send + Sync + 'static,
+        R: Reply + 'static,
+    {
+        routes
+            .map(|reply| Box::new(reply) as Box<dyn Reply>)
+            .boxed()
+    }
+
+    hosted_networks_routes
+        .into_iter()
+        .rev()
+        .fold(boxed(main_routes), |combined_routes, routes| {
+            boxed(routes).or(combined_routes).unify().boxed()
+        })
+}
+
+/// Path prefix of the routes, if any
+fn path_prefix(prefix: Option<String>) -> BoxedFilter<()> {
+    match prefix {
+        Some(prefix) => warp::path(prefix).boxed(),
+        None => warp::any().boxed(),
+    }
+}
+
+/// Build the CORS policy from the configuration
+fn build_cors(config: &Configuration) -> warp::cors::Builder {
+    let cors = warp::cors()
+        .allow_headers(vec![
+            "content-type",
+            MITHRIL_API_VERSION_HEADER,
+            reply::IF_NONE_MATCH_HEADER,
+        ])
+        .expose_headers(vec![reply::ETAG_HEADER])
+        .allow_methods(
+            config
+                .list_cors_allowed_methods()
+                .unwrap_or_else(|_| {
+                    vec!["GET".to_string(), "POST".to_string(), "OPTIONS".to_string()]
+                })
+                .iter()
+                .filter_map(|method| Method::from_str(method).ok()),
+        );
+
+    // The origins are validated at startup, an invalid configuration must not allow any origin
+    match config
+        .list_cors_allowed_origins()
+        .unwrap_or_else(|_| Some(vec![]))
+    {
+        Some(origins) => cors.allow_origins(origins.iter().map(|origin| origin.as_str())),
+        None => cors.allow_any_origin(),
+    }
+}
+
 /// API Version verification
 fn header_must_be(
     api_version_provider: Arc<APIVersionProvider>,
@@ -105,9 +187,25 @@ fn header_must_be(
         .untuple_one()
 }
 
-pub async fn handle_custom(reject: Rejection) -> Result<impl Reply, Rejection> {
+pub async fn handle_custom(reject: Rejection) -> Result<Box<dyn Reply>, Rejection> {
     if reject.find::<VersionMismatchError>().is_some() {
-        Ok(StatusCode::PRECONDITION_FAILED)
+        Ok(reply::empty(StatusCode::PRECONDITION_FAILED))
+    } else if let Some(rejection) = reject.find::<SignerOriginRejection>() {
+        Ok(match rejection {
+            SignerOriginRejection::InvalidBody(err) => {
+                reply::bad_request("invalid_body".to_string(), err.to_string())
+            }
+            SignerOriginRejection::TooManySingleSignatures(err) => {
+                reply::bad_request("signatures_batch_too_large".to_string(), err.to_string())
+            }
+            SignerOriginRejection::Unauthenticated(err) => {
+                reply::unauthorized("missing_signer_authentication".to_string(), err.to_string())
+            }
+            SignerOriginRejection::Forbidden(err) => {
+                reply::forbidden("invalid_signer_authentication".to_string(), err.to_string())
+            }
+            SignerOriginRejection::Error(err) => reply::internal_server_error(err.to_string()),
+        })
     } else {
         Err(reject)
     }
@@ -123,8 +221,52 @@ mod tests {
         era::{EraChecker, SupportedEra},
     };
 
+    use crate::initialize_dependencies;
+
     use super::*;
 
+    #[tokio::test]
+    async fn test_cors_only_allow_configured_origins() {
+        let config = Configuration {
+            cors_allowed_origins: Some("https://allowed.com".to_string()),
+            ..Configuration::new_sample()
+        };
+        let filters = warp::any().map(warp::reply).with(build_cors(&config));
+
+        let response = warp::test::request()
+            .method("OPTIONS")
+            .header("origin", "https://not-allowed.com")
+            .header("access-control-request-method", "GET")
+            .reply(&filters)
+            .await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+        let response = warp::test::request()
+            .method("OPTIONS")
+            .header("origin", "https://allowed.com")
+            .header("access-control-request-method", "GET")
+            .reply(&filters)
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_cors_only_allow_configured_methods() {
+        let config = Configuration {
+            cors_allowed_methods: Some("GET".to_string()),
+            ..Configuration::new_sample()
+        };
+        let filters = warp::any().map(warp::reply).with(build_cors(&config));
+
+        let response = warp::test::request()
+            .method("OPTIONS")
+            .header("origin", "https://whatever.com")
+            .header("access-control-request-method", "POST")
+            .reply(&filters)
+            .await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    }
+
     #[tokio::test]
     async fn test_no_version() {
         let era_checker = EraChecker::new(SupportedEra::dummy(), Epoch(1));
@@ -185,4 +327,43 @@ mod tests {
             .await
             .expect(r#"request with the good version "0.1.2" should not be rejected"#);
     }
+
+    #[tokio::test]
+    async fn test_routes_are_served_under_the_configured_path_prefix() {
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.config.server_path_prefix = Some("preprod".to_string());
+        let filters = routes(Arc::new(dependency_manager));
+
+        let response = warp::test::request()
+            .path("/preprod/aggregator/")
+            .reply(&filters)
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let response = warp::test::request()
+            .path("/aggregator/")
+            .reply(&filters)
+            .await;
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_hosted_networks_routes_are_served_under_their_network_prefix() {
+        let main_dependency_manager = initialize_dependencies().await;
+        let mut preprod_dependency_manager = initialize_dependencies().await;
+        preprod_dependency_manager.config.server_path_prefix = Some("preprod".to_string());
+        let filters = with_hosted_networks_routes(
+            routes(Arc::new(main_dependency_manager)),
+            vec![routes(Arc::new(preprod_dependency_manager))],
+        );
+
+        for (path, expected_status) in [
+            ("/aggregator/", StatusCode::OK),
+            ("/preprod/aggregator/", StatusCode::OK),
+            ("/preview/aggregator/", StatusCode::NOT_FOUND),
+        ] {
+            let response = warp::test::request().path(path).reply(&filters).await;
+            assert_eq!(expected_status, response.status(), "path: {path}");
+        }
+    }
 }
@@ -1,12 +1,19 @@
-use crate::http_server::routes::middlewares;
+use crate::http_server::routes::middlewares::{self, SignerOriginatedMessage};
+use crate::message_adapters::FromRegisterSingleSignatureAdapter;
 use crate::DependencyContainer;
+use mithril_common::entities::{HexEncodedSingleSignature, PartyId, SingleSignatures};
+use mithril_common::messages::{
+    RegisterSignatureMessage, RegisterSignaturesBatchMessage, TryFromMessageAdapter,
+    REGISTER_SIGNATURES_BATCH_MAX_SIZE,
+};
 use std::sync::Arc;
 use warp::Filter;
 
 pub fn routes(
     dependency_manager: Arc<DependencyContainer>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
-    register_signatures(dependency_manager)
+    register_signatures(dependency_manager.clone())
+        .or(register_signatures_batch(dependency_manager))
 }
 
 /// POST /register-signatures
@@ -15,106 +22,327 @@ fn register_signatures(
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     warp::path!("register-signatures")
         .and(warp::post())
-        .and(warp::body::json())
+        .and(middlewares::with_signer_authenticated_body::<
+            RegisterSignatureMessage,
+        >(dependency_manager.clone()))
         .and(middlewares::with_certifier_service(
             dependency_manager.clone(),
         ))
-        .and(middlewares::with_ticker_service(dependency_manager))
+        .and(middlewares::with_ticker_service(dependency_manager.clone()))
+        .and(middlewares::with_event_transmitter(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_signer_participation_storer(
+            dependency_manager,
+        ))
         .and_then(handlers::register_signatures)
 }
 
+/// POST /register-signatures/batch
+fn register_signatures_batch(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("register-signatures" / "batch")
+        .and(warp::post())
+        .and(middlewares::with_signer_authenticated_body::<
+            RegisterSignaturesBatchMessage,
+        >(dependency_manager.clone()))
+        .and(middlewares::with_certifier_service(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_ticker_service(dependency_manager.clone()))
+        .and(middlewares::with_event_transmitter(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_signer_participation_storer(
+            dependency_manager,
+        ))
+        .and_then(handlers::register_signatures_batch)
+}
+
+/// Maximum length of the body of a request registering a single signature
+const REGISTER_SIGNATURE_MAX_CONTENT_LENGTH: u64 = 64 * 1024;
+
+impl SignerOriginatedMessage for RegisterSignatureMessage {
+    const MAX_CONTENT_LENGTH: u64 = REGISTER_SIGNATURE_MAX_CONTENT_LENGTH;
+    const MAX_SINGLE_SIGNATURES: usize = 1;
+
+    fn origin_single_signatures(&self) -> Vec<(PartyId, HexEncodedSingleSignature)> {
+        vec![(self.party_id.clone(), self.signature.clone())]
+    }
+
+    fn single_signatures(&self) -> Vec<SingleSignatures> {
+        FromRegisterSingleSignatureAdapter::try_adapt(self.clone())
+            .into_iter()
+            .collect()
+    }
+}
+
+impl SignerOriginatedMessage for RegisterSignaturesBatchMessage {
+    const MAX_CONTENT_LENGTH: u64 =
+        REGISTER_SIGNATURES_BATCH_MAX_SIZE as u64 * REGISTER_SIGNATURE_MAX_CONTENT_LENGTH;
+    const MAX_SINGLE_SIGNATURES: usize = REGISTER_SIGNATURES_BATCH_MAX_SIZE;
+
+    fn origin_single_signatures(&self) -> Vec<(PartyId, HexEncodedSingleSignature)> {
+        self.signatures
+            .iter()
+            .flat_map(|signature| signature.origin_single_signatures())
+            .collect()
+    }
+
+    fn single_signatures(&self) -> Vec<SingleSignatures> {
+        self.signatures
+            .iter()
+            .filter_map(|signature| {
+                FromRegisterSingleSignatureAdapter::try_adapt(signature.clone()).ok()
+            })
+            .collect()
+    }
+}
+
 mod handlers {
     use mithril_common::{
         entities::SignedEntityType,
-        messages::{RegisterSignatureMessage, TryFromMessageAdapter},
+        messages::{
+            RegisterSignatureMessage, RegisterSignatureResultMessage, RegisterSignatureStatus,
+            RegisterSignaturesBatchMessage, RegisterSignaturesBatchResultMessage,
+            TryFromMessageAdapter,
+        },
+        StdError,
     };
 
+    use serde_json::json;
     use slog_scope::{debug, trace, warn};
     use std::convert::Infallible;
     use std::sync::Arc;
     use warp::http::StatusCode;
 
     use crate::{
+        database::repository::SignerParticipationStorer,
+        event_store::{EventMessage, TransmitterService},
         http_server::routes::reply,
         message_adapters::FromRegisterSingleSignatureAdapter,
         services::{CertifierService, CertifierServiceError, TickerService},
     };
 
+    /// Reasons why a single signature could not be registered
+    enum RegistrationFailure {
+        Invalid(StdError),
+        UnacceptedEra(StdError),
+        NotFound,
+        AlreadyCertified,
+        Error(StdError),
+    }
+
+    /// Register a single signature, returns the signed entity type it was registered for
+    async fn register_single_signature(
+        message: RegisterSignatureMessage,
+        certifier_service: &dyn CertifierService,
+        ticker_service: &dyn TickerService,
+        event_transmitter: &TransmitterService<EventMessage>,
+        signer_participation_storer: &dyn SignerParticipationStorer,
+    ) -> Result<SignedEntityType, RegistrationFailure> {
+        let signed_entity_type = match message.signed_entity_type.clone() {
+            Some(signed_entity_type) => signed_entity_type,
+            None => ticker_service
+                .get_current_immutable_beacon()
+                .await
+                .map(SignedEntityType::CardanoImmutableFilesFull)
+                .map_err(|err| {
+                    warn!("register_signatures::cant_retrieve_signed_entity_type"; "error" => ?err);
+                    RegistrationFailure::Error(err)
+                })?,
+        };
+
+        let signatures = FromRegisterSingleSignatureAdapter::try_adapt(message).map_err(|err| {
+            warn!("register_signatures::payload decoding error"; "error" => ?err);
+            RegistrationFailure::Invalid(err)
+        })?;
+
+        certifier_service
+            .register_single_signature(&signed_entity_type, &signatures)
+            .await
+            .map_err(|err| match err.downcast_ref::<CertifierServiceError>() {
+                Some(CertifierServiceError::AlreadyCertified(signed_entity_type)) => {
+                    debug!("register_signatures::open_message_already_certified"; "signed_entity_type" => ?signed_entity_type);
+                    RegistrationFailure::AlreadyCertified
+                }
+                Some(CertifierServiceError::NotFound(signed_entity_type)) => {
+                    debug!("register_signatures::not_found"; "signed_entity_type" => ?signed_entity_type);
+                    RegistrationFailure::NotFound
+                }
+                Some(CertifierServiceError::UnacceptedEra { era, .. }) => {
+                    debug!("register_signatures::unaccepted_era"; "era" => ?era);
+                    RegistrationFailure::UnacceptedEra(err)
+                }
+                Some(_) | None => {
+                    warn!("register_signatures::error"; "error" => ?err);
+                    RegistrationFailure::Error(err)
+                }
+            })?;
+
+        let _ = event_transmitter.send_event_message(
+            "HTTP::register_signatures",
+            "register_signatures",
+            &json!({
+                "signed_entity_type": signed_entity_type,
+                "party_id": signatures.party_id,
+                "won_indexes": signatures.won_indexes,
+            }),
+            vec![],
+        );
+
+        // Failing to record the participation of a signer must not fail its signature
+        if let Err(err) = signer_participation_storer
+            .record_signature(&signatures.party_id, signed_entity_type.get_epoch())
+            .await
+        {
+            warn!("register_signatures::failed_to_record_signer_participation"; "error" => ?err);
+        }
+
+        Ok(signed_entity_type)
+    }
+
     /// Register Signatures
     pub async fn register_signatures(
         message: RegisterSignatureMessage,
         certifier_service: Arc<dyn CertifierService>,
         ticker_service: Arc<dyn TickerService>,
+        event_transmitter: Arc<TransmitterService<EventMessage>>,
+        signer_participation_storer: Arc<dyn SignerParticipationStorer>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!("⇄ HTTP SERVER: register_signatures/{:?}", message);
         trace!("⇄ HTTP SERVER: register_signatures"; "complete_message" => #?message );
 
-        let signed_entity_type = match message.signed_entity_type.clone() {
-            Some(signed_entity_type) => Ok(signed_entity_type),
-            None => ticker_service
-                .get_current_immutable_beacon()
-                .await
-                .map(SignedEntityType::CardanoImmutableFilesFull),
-        };
+        match register_single_signature(
+            message,
+            certifier_service.as_ref(),
+            ticker_service.as_ref(),
+            event_transmitter.as_ref(),
+            signer_participation_storer.as_ref(),
+        )
+        .await
+        {
+            Ok(_) => Ok(reply::empty(StatusCode::CREATED)),
+            Err(RegistrationFailure::Invalid(err)) => Ok(reply::bad_request(
+                "Could not decode signature payload".to_string(),
+                err.to_string(),
+            )),
+            Err(RegistrationFailure::UnacceptedEra(err)) => Ok(reply::bad_request(
+                "Signature produced under an unaccepted era".to_string(),
+                err.to_string(),
+            )),
+            Err(RegistrationFailure::NotFound) => Ok(reply::empty(StatusCode::NOT_FOUND)),
+            Err(RegistrationFailure::AlreadyCertified) => Ok(reply::empty(StatusCode::GONE)),
+            Err(RegistrationFailure::Error(err)) => Ok(reply::internal_server_error(err)),
+        }
+    }
 
-        match signed_entity_type {
-            Ok(signed_entity_type) => {
-                let signatures = match FromRegisterSingleSignatureAdapter::try_adapt(message) {
-                    Ok(signature) => signature,
-                    Err(err) => {
-                        warn!("register_signatures::payload decoding error"; "error" => ?err);
-
-                        return Ok(reply::bad_request(
-                            "Could not decode signature payload".to_string(),
-                            err.to_string(),
-                        ));
-                    }
-                };
-
-                match certifier_service
-                    .register_single_signature(&signed_entity_type, &signatures)
-                    .await
-                {
-                    Err(err) => match err.downcast_ref::<CertifierServiceError>() {
-                        Some(CertifierServiceError::AlreadyCertified(signed_entity_type)) => {
-                            debug!("register_signatures::open_message_already_certified"; "signed_entity_type" => ?signed_entity_type);
-                            Ok(reply::empty(StatusCode::GONE))
-                        }
-                        Some(CertifierServiceError::NotFound(signed_entity_type)) => {
-                            debug!("register_signatures::not_found"; "signed_entity_type" => ?signed_entity_type);
-                            Ok(reply::empty(StatusCode::NOT_FOUND))
-                        }
-                        Some(_) | None => {
-                            warn!("register_signatures::error"; "error" => ?err);
-                            Ok(reply::internal_server_error(err))
-                        }
-                    },
-                    Ok(()) => Ok(reply::empty(StatusCode::CREATED)),
-                }
-            }
-            Err(err) => {
-                warn!("register_signatures::cant_retrieve_signed_entity_type"; "error" => ?err);
-                Ok(reply::internal_server_error(err))
-            }
+    /// Register a batch of Signatures
+    pub async fn register_signatures_batch(
+        message: RegisterSignaturesBatchMessage,
+        certifier_service: Arc<dyn CertifierService>,
+        ticker_service: Arc<dyn TickerService>,
+        event_transmitter: Arc<TransmitterService<EventMessage>>,
+        signer_participation_storer: Arc<dyn SignerParticipationStorer>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!(
+            "⇄ HTTP SERVER: register_signatures_batch/{} signatures",
+            message.signatures.len()
+        );
+        trace!("⇄ HTTP SERVER: register_signatures_batch"; "complete_message" => #?message );
+
+        if message.signatures.is_empty() {
+            return Ok(reply::bad_request(
+                "empty_signatures_batch".to_string(),
+                "the batch must contain at least one signature".to_string(),
+            ));
         }
+
+        // Each signature is registered independently, the failure of one of them does not
+        // prevent the registration of the others.
+        let mut results = vec![];
+        for signature in message.signatures {
+            let party_id = signature.party_id.clone();
+            let requested_signed_entity_type = signature.signed_entity_type.clone();
+            let (signed_entity_type, status, error) = match register_single_signature(
+                signature,
+                certifier_service.as_ref(),
+                ticker_service.as_ref(),
+                event_transmitter.as_ref(),
+                signer_participation_storer.as_ref(),
+            )
+            .await
+            {
+                Ok(signed_entity_type) => (
+                    Some(signed_entity_type),
+                    RegisterSignatureStatus::Registered,
+                    None,
+                ),
+                Err(RegistrationFailure::Invalid(err))
+                | Err(RegistrationFailure::UnacceptedEra(err)) => (
+                    requested_signed_entity_type,
+                    RegisterSignatureStatus::Invalid,
+                    Some(format!("{err:#}")),
+                ),
+                Err(RegistrationFailure::NotFound) => (
+                    requested_signed_entity_type,
+                    RegisterSignatureStatus::NotFound,
+                    None,
+                ),
+                Err(RegistrationFailure::AlreadyCertified) => (
+                    requested_signed_entity_type,
+                    RegisterSignatureStatus::AlreadyCertified,
+                    None,
+                ),
+                Err(RegistrationFailure::Error(err)) => (
+                    requested_signed_entity_type,
+                    RegisterSignatureStatus::Error,
+                    Some(format!("{err:#}")),
+                ),
+            };
+            results.push(RegisterSignatureResultMessage {
+                signed_entity_type,
+                party_id,
+                status,
+                error,
+            });
+        }
+
+        Ok(reply::json(
+            &RegisterSignaturesBatchResultMessage { results },
+            StatusCode::OK,
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use anyhow::anyhow;
+    use mockall::predicate::eq;
     use warp::http::{Method, StatusCode};
     use warp::test::request;
 
     use mithril_common::{
-        entities::SignedEntityType, messages::RegisterSignatureMessage,
+        entities::SignedEntityType,
+        era::SupportedEra,
+        messages::{
+            RegisterSignatureMessage, RegisterSignatureStatus, RegisterSignaturesBatchMessage,
+            RegisterSignaturesBatchResultMessage, REGISTER_SIGNATURES_BATCH_MAX_SIZE,
+        },
         test_utils::apispec::APISpec,
+        MITHRIL_SIGNATURE_ORIGIN_HEADER,
     };
 
     use crate::{
-        http_server::SERVER_BASE_PATH,
+        database::repository::MockSignerParticipationStorer,
+        http_server::{routes::router, SERVER_BASE_PATH},
         initialize_dependencies,
-        services::{CertifierServiceError, MockCertifierService},
+        services::{
+            CertifierServiceError, MithrilSignatureAuthenticator, MockCertifierService,
+            MockSignatureAuthenticator, SignatureAuthenticatorError,
+        },
+        tools::mocks::MockChainObserver,
     };
 
     use super::*;
@@ -130,6 +358,7 @@ mod tests {
         warp::any()
             .and(warp::path(SERVER_BASE_PATH))
             .and(routes(dependency_manager).with(cors))
+            .recover(router::handle_custom)
     }
 
     #[tokio::test]
@@ -165,6 +394,61 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_register_signatures_post_record_signer_participation() {
+        let message = RegisterSignatureMessage::dummy();
+        let signed_entity_type = message.signed_entity_type.clone().unwrap();
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_register_single_signature()
+            .return_once(move |_, _| Ok(()));
+        let mut mock_signer_participation_storer = MockSignerParticipationStorer::new();
+        mock_signer_participation_storer
+            .expect_record_signature()
+            .with(
+                eq(message.party_id.clone()),
+                eq(signed_entity_type.get_epoch()),
+            )
+            .return_once(|_, _| Ok(()))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certifier_service = Arc::new(mock_certifier_service);
+        dependency_manager.signer_participation_storer = Arc::new(mock_signer_participation_storer);
+
+        let response = request()
+            .method(Method::POST.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/register-signatures"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::CREATED, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_register_signatures_post_ok_even_if_signer_participation_recording_fails() {
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_register_single_signature()
+            .return_once(move |_, _| Ok(()));
+        let mut mock_signer_participation_storer = MockSignerParticipationStorer::new();
+        mock_signer_participation_storer
+            .expect_record_signature()
+            .return_once(|_, _| Err(anyhow!("an error")));
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certifier_service = Arc::new(mock_certifier_service);
+        dependency_manager.signer_participation_storer = Arc::new(mock_signer_participation_storer);
+
+        let response = request()
+            .method(Method::POST.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/register-signatures"))
+            .json(&RegisterSignatureMessage::dummy())
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::CREATED, response.status());
+    }
+
     #[tokio::test]
     async fn test_register_signatures_post_ko_400() {
         let mut mock_certifier_service = MockCertifierService::new();
@@ -199,6 +483,250 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_register_signatures_post_ko_400_when_era_is_not_accepted() {
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_register_single_signature()
+            .return_once(move |_, _| {
+                Err(CertifierServiceError::UnacceptedEra {
+                    era: SupportedEra::Pythagoras,
+                    accepted_eras: vec![SupportedEra::Thales],
+                }
+                .into())
+            });
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certifier_service = Arc::new(mock_certifier_service);
+
+        let message = RegisterSignatureMessage {
+            era: Some(SupportedEra::Pythagoras),
+            ..RegisterSignatureMessage::dummy()
+        };
+
+        let method = Method::POST.as_str();
+        let path = "/register-signatures";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::BAD_REQUEST,
+        )
+        .unwrap();
+    }
+
+    async fn register_signature_with_authentication_error(
+        authentication_error: fn(PartyId) -> SignatureAuthenticatorError,
+        expected_status_code: StatusCode,
+    ) {
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_register_single_signature()
+            .never();
+        let mut mock_signature_authenticator = MockSignatureAuthenticator::new();
+        mock_signature_authenticator
+            .expect_authenticate_origin()
+            .return_once(|_, _| Ok(()));
+        mock_signature_authenticator
+            .expect_authenticate()
+            .return_once(move |signatures| {
+                Err(authentication_error(signatures[0].party_id.clone()).into())
+            });
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certifier_service = Arc::new(mock_certifier_service);
+        dependency_manager.signature_authenticator = Arc::new(mock_signature_authenticator);
+
+        let message = RegisterSignatureMessage::dummy();
+
+        let method = Method::POST.as_str();
+        let path = "/register-signatures";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &expected_status_code,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_register_signatures_post_ko_401_when_signature_is_sent_without_nonce_signature() {
+        register_signature_with_authentication_error(
+            SignatureAuthenticatorError::MissingNonceSignature,
+            StatusCode::UNAUTHORIZED,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_register_signatures_post_ko_403_when_nonce_signature_is_invalid() {
+        register_signature_with_authentication_error(
+            SignatureAuthenticatorError::InvalidNonceSignature,
+            StatusCode::FORBIDDEN,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_register_signatures_batch_post_ko_403_when_a_nonce_signature_is_invalid() {
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_register_single_signature()
+            .never();
+        let mut mock_signature_authenticator = MockSignatureAuthenticator::new();
+        mock_signature_authenticator
+            .expect_authenticate_origin()
+            .return_once(|_, _| Ok(()));
+        mock_signature_authenticator
+            .expect_authenticate()
+            .returning(|signatures| {
+                Err(SignatureAuthenticatorError::InvalidNonceSignature(
+                    signatures[0].party_id.clone(),
+                )
+                .into())
+            });
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certifier_service = Arc::new(mock_certifier_service);
+        dependency_manager.signature_authenticator = Arc::new(mock_signature_authenticator);
+
+        let message = RegisterSignaturesBatchMessage {
+            signatures: vec![RegisterSignatureMessage::dummy()],
+        };
+
+        let method = Method::POST.as_str();
+        let path = "/register-signatures/batch";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::FORBIDDEN,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_register_signatures_post_ko_403_when_origin_is_not_authenticated() {
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_register_single_signature()
+            .never();
+        let mut mock_signature_authenticator = MockSignatureAuthenticator::new();
+        mock_signature_authenticator
+            .expect_authenticate_origin()
+            .return_once(|single_signatures, _| {
+                Err(SignatureAuthenticatorError::InvalidOriginSignature(
+                    single_signatures[0].0.clone(),
+                )
+                .into())
+            });
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certifier_service = Arc::new(mock_certifier_service);
+        dependency_manager.signature_authenticator = Arc::new(mock_signature_authenticator);
+
+        let message = RegisterSignatureMessage::dummy();
+
+        let method = Method::POST.as_str();
+        let path = "/register-signatures";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header(MITHRIL_SIGNATURE_ORIGIN_HEADER, "invalid")
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::FORBIDDEN,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_register_signatures_batch_post_ko_401_without_the_origin_signature_of_a_signer() {
+        let mut mock_signature_authenticator = MockSignatureAuthenticator::new();
+        mock_signature_authenticator
+            .expect_authenticate_origin()
+            .withf(|single_signatures, _| single_signatures.len() == 2)
+            .return_once(|single_signatures, _| {
+                Err(SignatureAuthenticatorError::MissingOriginSignature(
+                    single_signatures[1].0.clone(),
+                )
+                .into())
+            });
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signature_authenticator = Arc::new(mock_signature_authenticator);
+
+        let message = RegisterSignaturesBatchMessage {
+            signatures: vec![
+                RegisterSignatureMessage::dummy(),
+                RegisterSignatureMessage {
+                    party_id: "another-party-id".to_string(),
+                    ..RegisterSignatureMessage::dummy()
+                },
+            ],
+        };
+
+        let method = Method::POST.as_str();
+        let path = "/register-signatures/batch";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::UNAUTHORIZED,
+        )
+        .unwrap();
+    }
+
     #[tokio::test]
     async fn test_register_signatures_post_ko_404() {
         let signed_entity_type = SignedEntityType::dummy();
@@ -301,4 +829,195 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_register_signatures_batch_post_ok_with_the_status_of_each_signature() {
+        let mut mock_certifier_service = MockCertifierService::new();
+        let mut sequence = mockall::Sequence::new();
+        mock_certifier_service
+            .expect_register_single_signature()
+            .once()
+            .in_sequence(&mut sequence)
+            .return_once(move |_, _| Ok(()));
+        mock_certifier_service
+            .expect_register_single_signature()
+            .once()
+            .in_sequence(&mut sequence)
+            .return_once(move |signed_entity_type, _| {
+                Err(CertifierServiceError::AlreadyCertified(signed_entity_type.clone()).into())
+            });
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certifier_service = Arc::new(mock_certifier_service);
+
+        let mut invalid_signature = RegisterSignatureMessage::dummy();
+        invalid_signature.signature = "invalid-signature".to_string();
+        let message = RegisterSignaturesBatchMessage {
+            signatures: vec![
+                RegisterSignatureMessage::dummy(),
+                invalid_signature,
+                RegisterSignatureMessage::dummy(),
+            ],
+        };
+
+        let method = Method::POST.as_str();
+        let path = "/register-signatures/batch";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        let result: RegisterSignaturesBatchResultMessage =
+            serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(
+            vec![
+                RegisterSignatureStatus::Registered,
+                RegisterSignatureStatus::Invalid,
+                RegisterSignatureStatus::AlreadyCertified,
+            ],
+            result.results.iter().map(|r| r.status).collect::<Vec<_>>()
+        );
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_register_signatures_batch_post_ko_400_when_empty() {
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_register_single_signature()
+            .never();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certifier_service = Arc::new(mock_certifier_service);
+
+        let message = RegisterSignaturesBatchMessage { signatures: vec![] };
+
+        let method = Method::POST.as_str();
+        let path = "/register-signatures/batch";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::BAD_REQUEST,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_register_signatures_batch_post_ko_400_when_too_large() {
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_register_single_signature()
+            .never();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certifier_service = Arc::new(mock_certifier_service);
+
+        let message = RegisterSignaturesBatchMessage {
+            signatures: vec![
+                RegisterSignatureMessage::dummy();
+                REGISTER_SIGNATURES_BATCH_MAX_SIZE + 1
+            ],
+        };
+
+        let method = Method::POST.as_str();
+        let path = "/register-signatures/batch";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::BAD_REQUEST,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_register_signatures_batch_post_ko_400_when_too_large_without_authenticating_it() {
+        let mut chain_observer = MockChainObserver::new();
+        chain_observer.expect_get_current_kes_period().never();
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_register_single_signature()
+            .never();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.certifier_service = Arc::new(mock_certifier_service);
+        dependency_manager.signature_authenticator = Arc::new(MithrilSignatureAuthenticator::new(
+            true,
+            dependency_manager.epoch_service.clone(),
+            Arc::new(chain_observer),
+        ));
+
+        let message = RegisterSignaturesBatchMessage {
+            signatures: vec![
+                RegisterSignatureMessage::dummy();
+                REGISTER_SIGNATURES_BATCH_MAX_SIZE + 1
+            ],
+        };
+
+        let response = request()
+            .method(Method::POST.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/register-signatures/batch"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_register_signatures_post_ko_413_when_body_is_too_large() {
+        let mut chain_observer = MockChainObserver::new();
+        chain_observer.expect_get_current_kes_period().never();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signature_authenticator = Arc::new(MithrilSignatureAuthenticator::new(
+            true,
+            dependency_manager.epoch_service.clone(),
+            Arc::new(chain_observer),
+        ));
+
+        let message = RegisterSignatureMessage {
+            signature: "a".repeat(REGISTER_SIGNATURE_MAX_CONTENT_LENGTH as usize),
+            ..RegisterSignatureMessage::dummy()
+        };
+
+        let response = request()
+            .method(Method::POST.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/register-signatures"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, response.status());
+    }
 }
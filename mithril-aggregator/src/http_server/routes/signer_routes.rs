@@ -7,12 +7,20 @@ use crate::DependencyContainer;
 
 const MITHRIL_SIGNER_VERSION_HEADER: &str = "signer-node-version";
 
+/// Maximum size, in bytes, of a signer diagnostic payload
+const SIGNER_DIAGNOSTIC_MAX_CONTENT_LENGTH: u64 = 64 * 1024;
+
 pub fn routes(
     dependency_manager: Arc<DependencyContainer>,
 ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
     register_signer(dependency_manager.clone())
+        .or(unregister_signer(dependency_manager.clone()))
         .or(registered_signers(dependency_manager.clone()))
-        .or(signers_tickers(dependency_manager))
+        .or(signers_stake_distribution_diff(dependency_manager.clone()))
+        .or(signers_tickers(dependency_manager.clone()))
+        .or(signers_versions(dependency_manager.clone()))
+        .or(signer_diagnostics(dependency_manager.clone()))
+        .or(signer_history(dependency_manager))
 }
 
 /// POST /register-signer
@@ -24,6 +32,7 @@ fn register_signer(
         .and(warp::header::optional::<String>(
             MITHRIL_SIGNER_VERSION_HEADER,
         ))
+        .and(warp::addr::remote())
         .and(warp::body::json())
         .and(middlewares::with_signer_registerer(
             dependency_manager.clone(),
@@ -31,10 +40,33 @@ fn register_signer(
         .and(middlewares::with_event_transmitter(
             dependency_manager.clone(),
         ))
-        .and(middlewares::with_time_point_provider(dependency_manager))
+        .and(middlewares::with_time_point_provider(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_signer_metadata_storer(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_signer_participation_storer(
+            dependency_manager,
+        ))
         .and_then(handlers::register_signer)
 }
 
+/// DELETE /register-signer
+fn unregister_signer(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("register-signer")
+        .and(warp::delete())
+        .and(warp::body::json())
+        .and(middlewares::with_signer_registerer(
+            dependency_manager.clone(),
+        ))
+        .and(middlewares::with_epoch_service(dependency_manager.clone()))
+        .and(middlewares::with_event_transmitter(dependency_manager))
+        .and_then(handlers::unregister_signer)
+}
+
 /// Get /signers/tickers
 fn signers_tickers(
     dependency_manager: Arc<DependencyContainer>,
@@ -46,6 +78,32 @@ fn signers_tickers(
         .and_then(handlers::signers_tickers)
 }
 
+/// Get /signers/versions
+fn signers_versions(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("signers" / "versions")
+        .and(warp::get())
+        .and(middlewares::with_signer_metadata_storer(dependency_manager))
+        .and_then(handlers::signers_versions)
+}
+
+/// POST /signers/diagnostics
+fn signer_diagnostics(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("signers" / "diagnostics")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(
+            SIGNER_DIAGNOSTIC_MAX_CONTENT_LENGTH,
+        ))
+        .and(warp::header::optional::<String>("authorization"))
+        .and(middlewares::with_config(dependency_manager.clone()))
+        .and(warp::body::json())
+        .and(middlewares::with_event_transmitter(dependency_manager))
+        .and_then(handlers::signer_diagnostics)
+}
+
 /// Get /signers/registered/:epoch
 fn registered_signers(
     dependency_manager: Arc<DependencyContainer>,
@@ -56,31 +114,71 @@ fn registered_signers(
         .and_then(handlers::registered_signers)
 }
 
+/// Get /signers/stake-distribution-diff/:epoch
+fn signers_stake_distribution_diff(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("signers" / "stake-distribution-diff" / String)
+        .and(warp::get())
+        .and(middlewares::with_verification_key_store(dependency_manager))
+        .and_then(handlers::signers_stake_distribution_diff)
+}
+
+/// Get /signers/:party_id/history
+fn signer_history(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("signers" / String / "history")
+        .and(warp::get())
+        .and(middlewares::with_signer_participation_storer(
+            dependency_manager,
+        ))
+        .and_then(handlers::signer_history)
+}
+
 mod handlers {
-    use crate::database::repository::SignerGetter;
+    use crate::database::record::SignerMetadataRecord;
+    use crate::database::repository::{
+        SignerGetter, SignerMetadataStorer, SignerParticipationStorer,
+    };
     use crate::entities::{
-        SignerRegistrationsMessage, SignerTickerListItemMessage, SignersTickersMessage,
+        SignerHistoryMessage, SignerRegistrationsMessage, SignerTickerListItemMessage,
+        SignersTickersMessage, SignersVersionsMessage, StakeDistributionDiffMessage,
     };
     use crate::event_store::{EventMessage, TransmitterService};
     use crate::{
         http_server::routes::reply, Configuration, SignerRegisterer, SignerRegistrationError,
     };
-    use crate::{FromRegisterSignerAdapter, VerificationKeyStorer};
-    use mithril_common::entities::Epoch;
-    use mithril_common::messages::{RegisterSignerMessage, TryFromMessageAdapter};
+    use crate::{
+        FromRegisterSignerAdapter, FromRegisterSignerNextEraAdapter, VerificationKeyStorer,
+    };
+    use chrono::Utc;
+    use mithril_common::crypto_helper::ProtocolSignerVerificationKeySignature;
+    use mithril_common::entities::{Epoch, PartyId, StakeDistributionDiff};
+    use mithril_common::messages::{
+        RegisterSignerMessage, SignerDiagnosticMessage, TryFromMessageAdapter,
+        UnregisterSignerMessage,
+    };
     use mithril_common::TimePointProvider;
     use slog_scope::{debug, trace, warn};
     use std::convert::Infallible;
+    use std::net::SocketAddr;
     use std::sync::Arc;
     use warp::http::StatusCode;
 
+    use super::MITHRIL_SIGNER_VERSION_HEADER;
+
     /// Register Signer
+    #[allow(clippy::too_many_arguments)]
     pub async fn register_signer(
         signer_node_version: Option<String>,
+        remote_address: Option<SocketAddr>,
         register_signer_message: RegisterSignerMessage,
         signer_registerer: Arc<dyn SignerRegisterer>,
         event_transmitter: Arc<TransmitterService<EventMessage>>,
         time_point_provider: Arc<dyn TimePointProvider>,
+        signer_metadata_storer: Arc<dyn SignerMetadataStorer>,
+        signer_participation_storer: Arc<dyn SignerParticipationStorer>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!(
             "⇄ HTTP SERVER: register_signer/{:?}",
@@ -103,6 +201,33 @@ mod handlers {
             },
         };
 
+        let node_metadata = register_signer_message
+            .node_metadata
+            .clone()
+            .unwrap_or_default();
+        let signer_metadata = SignerMetadataRecord {
+            signer_id: register_signer_message.party_id.clone(),
+            registration_epoch,
+            // The version sent in the message prevails over the one sent in the headers
+            node_version: node_metadata
+                .node_version
+                .or_else(|| signer_node_version.clone()),
+            supported_eras: node_metadata.supported_eras,
+            ip_address: remote_address.map(|address| address.ip().to_string()),
+            updated_at: Utc::now(),
+        };
+
+        let next_era_signer =
+            match FromRegisterSignerNextEraAdapter::try_adapt(register_signer_message.clone()) {
+                Ok(next_era_signer) => next_era_signer,
+                Err(err) => {
+                    warn!("register_signer::next era payload decoding error"; "error" => ?err);
+                    return Ok(reply::bad_request(
+                        "Could not decode signer next era payload".to_string(),
+                        err.to_string(),
+                    ));
+                }
+            };
         let signer = match FromRegisterSignerAdapter::try_adapt(register_signer_message) {
             Ok(signer) => signer,
             Err(err) => {
@@ -130,10 +255,25 @@ mod handlers {
             headers.push(("epoch", epoch_str.as_str()));
         }
 
-        match signer_registerer
+        let mut registration_result = signer_registerer
             .register_signer(registration_epoch, &signer)
-            .await
-        {
+            .await;
+        // The verification key of the next era is registered once the one of the current era is
+        if let Some((next_era, next_era_signer)) = next_era_signer {
+            if matches!(
+                registration_result,
+                Ok(_) | Err(SignerRegistrationError::ExistingSigner(_))
+            ) {
+                if let Err(err) = signer_registerer
+                    .register_next_era_signer(registration_epoch, next_era, &next_era_signer)
+                    .await
+                {
+                    registration_result = Err(err);
+                }
+            }
+        }
+
+        match registration_result {
             Ok(signer_with_stake) => {
                 let _ = event_transmitter.send_event_message(
                     "HTTP::signer_register",
@@ -141,11 +281,23 @@ mod handlers {
                     &signer_with_stake,
                     headers,
                 );
+                record_signer_registration(
+                    signer_metadata_storer,
+                    signer_participation_storer,
+                    signer_metadata,
+                )
+                .await;
 
                 Ok(reply::empty(StatusCode::CREATED))
             }
             Err(SignerRegistrationError::ExistingSigner(signer_with_stake)) => {
                 debug!("register_signer::already_registered");
+                record_signer_registration(
+                    signer_metadata_storer,
+                    signer_participation_storer,
+                    signer_metadata,
+                )
+                .await;
                 let _ = event_transmitter.send_event_message(
                     "HTTP::signer_register",
                     "register_signer",
@@ -154,6 +306,13 @@ mod handlers {
                 );
                 Ok(reply::empty(StatusCode::CREATED))
             }
+            Err(err @ SignerRegistrationError::UnregisteredSigner(_)) => {
+                warn!("register_signer::unregistered_signer"; "error" => ?err);
+                Ok(reply::forbidden(
+                    "unregistered_signer".to_string(),
+                    err.to_string(),
+                ))
+            }
             Err(SignerRegistrationError::FailedSignerRegistration(err)) => {
                 warn!("register_signer::failed_signer_registration"; "error" => ?err);
                 Ok(reply::bad_request(
@@ -174,6 +333,115 @@ mod handlers {
         }
     }
 
+    /// Unregister Signer
+    pub async fn unregister_signer(
+        unregister_signer_message: UnregisterSignerMessage,
+        signer_registerer: Arc<dyn SignerRegisterer>,
+        epoch_service: EpochServiceWrapper,
+        event_transmitter: Arc<TransmitterService<EventMessage>>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!(
+            "⇄ HTTP SERVER: unregister_signer/{:?}",
+            unregister_signer_message
+        );
+
+        let signature: ProtocolSignerVerificationKeySignature =
+            match unregister_signer_message.signature.clone().try_into() {
+                Ok(signature) => signature,
+                Err(err) => {
+                    warn!("unregister_signer::payload decoding error"; "error" => ?err);
+                    return Ok(reply::unauthorized(
+                        "Could not decode the signature of the signer unregistration".to_string(),
+                        err.to_string(),
+                    ));
+                }
+            };
+        let registration_nonce = match epoch_service.read().await.current_registration_nonce() {
+            Ok(registration_nonce) => registration_nonce,
+            Err(err) => {
+                warn!("unregister_signer::registration_nonce_unavailable"; "error" => ?err);
+                return Ok(reply::service_unavailable(err));
+            }
+        };
+
+        match signer_registerer
+            .unregister_signer(
+                unregister_signer_message.epoch,
+                &unregister_signer_message.party_id,
+                &registration_nonce,
+                &signature,
+            )
+            .await
+        {
+            Ok(signer_with_stake) => {
+                let _ = event_transmitter.send_event_message(
+                    "HTTP::signer_unregister",
+                    "unregister_signer",
+                    &signer_with_stake,
+                    vec![(
+                        "epoch",
+                        unregister_signer_message.epoch.to_string().as_str(),
+                    )],
+                );
+
+                Ok(reply::empty(StatusCode::OK))
+            }
+            Err(SignerRegistrationError::UnknownSigner(party_id)) => {
+                debug!("unregister_signer::unknown_signer"; "party_id" => &party_id);
+                Ok(reply::empty(StatusCode::NOT_FOUND))
+            }
+            Err(err @ SignerRegistrationError::InvalidUnregistrationSignature(_)) => {
+                warn!("unregister_signer::invalid_signature"; "error" => ?err);
+                Ok(reply::forbidden(
+                    "failed_signer_unregistration".to_string(),
+                    err.to_string(),
+                ))
+            }
+            Err(err @ SignerRegistrationError::RegistrationRoundUnexpectedEpoch { .. }) => {
+                warn!("unregister_signer::failed_signer_unregistration"; "error" => ?err);
+                Ok(reply::bad_request(
+                    "failed_signer_unregistration".to_string(),
+                    err.to_string(),
+                ))
+            }
+            Err(SignerRegistrationError::RegistrationRoundNotYetOpened) => {
+                warn!("unregister_signer::registration_round_not_yed_opened");
+                Ok(reply::service_unavailable(
+                    SignerRegistrationError::RegistrationRoundNotYetOpened.to_string(),
+                ))
+            }
+            Err(err) => {
+                warn!("unregister_signer::error"; "error" => ?err);
+                Ok(reply::internal_server_error(err.to_string()))
+            }
+        }
+    }
+
+    /// Failing to record the metadata or the participation of a signer must not fail its
+    /// registration
+    async fn record_signer_registration(
+        signer_metadata_storer: Arc<dyn SignerMetadataStorer>,
+        signer_participation_storer: Arc<dyn SignerParticipationStorer>,
+        signer_metadata: SignerMetadataRecord,
+    ) {
+        // The signer registered at the registration epoch will sign at the signing epoch
+        let signing_epoch = signer_metadata
+            .registration_epoch
+            .offset_to_signer_signing_offset();
+        if let Err(err) = signer_participation_storer
+            .record_registration(&signer_metadata.signer_id, signing_epoch)
+            .await
+        {
+            warn!("register_signer::failed_to_record_signer_participation"; "error" => ?err);
+        }
+        if let Err(err) = signer_metadata_storer
+            .record_signer_metadata(signer_metadata)
+            .await
+        {
+            warn!("register_signer::failed_to_record_signer_metadata"; "error" => ?err);
+        }
+    }
+
     /// Get Registered Signers for a given epoch
     pub async fn registered_signers(
         registered_at: String,
@@ -213,6 +481,61 @@ mod handlers {
         }
     }
 
+    /// Get the difference between the signers stakes registered at a given epoch and at the
+    /// epoch before
+    pub async fn signers_stake_distribution_diff(
+        registered_at: String,
+        verification_key_store: Arc<dyn VerificationKeyStorer>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!(
+            "⇄ HTTP SERVER: signers/stake-distribution-diff/{:?}",
+            registered_at
+        );
+
+        let (previous_registered_at, registered_at) = match registered_at
+            .parse::<u64>()
+            .map_err(anyhow::Error::from)
+            .and_then(|epoch| Ok((Epoch(epoch).previous()?, Epoch(epoch))))
+        {
+            Ok(epochs) => epochs,
+            Err(err) => {
+                warn!("signers_stake_distribution_diff::invalid_epoch"; "error" => ?err);
+                return Ok(reply::bad_request(
+                    "invalid_epoch".to_string(),
+                    err.to_string(),
+                ));
+            }
+        };
+
+        // The signers of the previous epoch may not be available anymore (ie: pruned), in which
+        // case all the current signers are reported as new.
+        let signers = verification_key_store
+            .get_signers(registered_at.offset_to_recording_epoch())
+            .await;
+        let previous_signers = verification_key_store
+            .get_signers(previous_registered_at.offset_to_recording_epoch())
+            .await;
+        match (signers, previous_signers) {
+            (Ok(Some(signers)), Ok(previous_signers)) => {
+                let diff = StakeDistributionDiff::from_signers(
+                    &previous_signers.unwrap_or_default(),
+                    &signers,
+                );
+                let message =
+                    StakeDistributionDiffMessage::new(previous_registered_at, registered_at, diff);
+                Ok(reply::json(&message, StatusCode::OK))
+            }
+            (Ok(None), Ok(_)) => {
+                warn!("signers_stake_distribution_diff::not_found");
+                Ok(reply::empty(StatusCode::NOT_FOUND))
+            }
+            (Err(err), _) | (_, Err(err)) => {
+                warn!("signers_stake_distribution_diff::error"; "error" => ?err);
+                Ok(reply::internal_server_error(err))
+            }
+        }
+    }
+
     pub async fn signers_tickers(
         configuration: Configuration,
         signer_getter: Arc<dyn SignerGetter>,
@@ -241,6 +564,94 @@ mod handlers {
             }
         }
     }
+
+    /// Record the diagnostic submitted by a signer, it can be queried with the events of the
+    /// `HTTP::signer_diagnostics` source
+    pub async fn signer_diagnostics(
+        authorization: Option<String>,
+        config: Configuration,
+        signer_diagnostic_message: SignerDiagnosticMessage,
+        event_transmitter: Arc<TransmitterService<EventMessage>>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!(
+            "⇄ HTTP SERVER: signers/diagnostics";
+            "signer_id" => &signer_diagnostic_message.signer_id,
+            "consecutive_failures" => signer_diagnostic_message.consecutive_failures
+        );
+
+        if let Err(status) = reply::check_bearer_token(
+            authorization.as_deref(),
+            config.signer_diagnostics_token.as_ref(),
+        ) {
+            warn!("signer_diagnostics::rejected"; "status" => %status);
+            return Ok(reply::empty(status));
+        }
+
+        let headers = vec![
+            ("signer_id", signer_diagnostic_message.signer_id.as_str()),
+            (
+                MITHRIL_SIGNER_VERSION_HEADER,
+                signer_diagnostic_message.signer_node_version.as_str(),
+            ),
+        ];
+
+        match event_transmitter.send_event_message(
+            "HTTP::signer_diagnostics",
+            "signer_diagnostic_submitted",
+            &signer_diagnostic_message,
+            headers,
+        ) {
+            Err(err) => {
+                warn!("signer_diagnostics::error"; "error" => ?err);
+                Ok(reply::internal_server_error(err))
+            }
+            Ok(_) => Ok(reply::empty(StatusCode::CREATED)),
+        }
+    }
+
+    /// Get the registrations and signatures history of a signer
+    pub async fn signer_history(
+        party_id: PartyId,
+        signer_participation_storer: Arc<dyn SignerParticipationStorer>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: signers/{party_id}/history");
+
+        match signer_participation_storer
+            .get_signer_history(&party_id)
+            .await
+        {
+            Ok(records) if records.is_empty() => {
+                warn!("signer_history::not_found"; "party_id" => &party_id);
+                Ok(reply::empty(StatusCode::NOT_FOUND))
+            }
+            Ok(records) => Ok(reply::json(
+                &SignerHistoryMessage::new(party_id, records),
+                StatusCode::OK,
+            )),
+            Err(err) => {
+                warn!("signer_history::error"; "error" => ?err);
+                Ok(reply::internal_server_error(err))
+            }
+        }
+    }
+
+    /// Get the distribution of the versions of the signers
+    pub async fn signers_versions(
+        signer_metadata_storer: Arc<dyn SignerMetadataStorer>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: signers/versions");
+
+        match signer_metadata_storer.get_latest_signers_metadata().await {
+            Ok(signers_metadata) => Ok(reply::json(
+                &SignersVersionsMessage::new(signers_metadata),
+                StatusCode::OK,
+            )),
+            Err(err) => {
+                warn!("signers_versions::error"; "error" => ?err);
+                Ok(reply::internal_server_error(err))
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -248,6 +659,7 @@ mod tests {
     use anyhow::anyhow;
     use mockall::predicate::eq;
     use serde_json::Value::Null;
+    use tokio::sync::RwLock;
     use warp::{
         http::{Method, StatusCode},
         test::request,
@@ -256,18 +668,31 @@ mod tests {
     use mithril_common::entities::Epoch;
     use mithril_common::{
         crypto_helper::ProtocolRegistrationError,
-        messages::RegisterSignerMessage,
-        test_utils::{apispec::APISpec, fake_data},
+        era::SupportedEra,
+        messages::{
+            NextEraRegistrationMessagePart, RegisterSignerMessage, SignerDiagnosticMessage,
+            UnregisterSignerMessage,
+        },
+        test_utils::{apispec::APISpec, fake_data, MithrilFixtureBuilder},
     };
     use mithril_persistence::store::adapter::AdapterError;
+    use mithril_secrets::SecretString;
 
     use crate::{
-        database::{record::SignerRecord, repository::MockSignerGetter},
+        database::{
+            record::{SignerMetadataRecord, SignerParticipationRecord, SignerRecord},
+            repository::{
+                MockSignerGetter, MockSignerMetadataStorer, MockSignerParticipationStorer,
+            },
+        },
+        dependency_injection::DependenciesBuilder,
+        entities::StakeDistributionDiffMessage,
         http_server::SERVER_BASE_PATH,
         initialize_dependencies,
+        services::{EpochService, FakeEpochService},
         signer_registerer::MockSignerRegisterer,
         store::MockVerificationKeyStorer,
-        SignerRegistrationError,
+        Configuration, SignerRegistrationError,
     };
 
     use super::*;
@@ -278,13 +703,34 @@ mod tests {
         let cors = warp::cors()
             .allow_any_origin()
             .allow_headers(vec!["content-type"])
-            .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+            .allow_methods(vec![
+                Method::GET,
+                Method::POST,
+                Method::DELETE,
+                Method::OPTIONS,
+            ]);
 
         warp::any()
             .and(warp::path(SERVER_BASE_PATH))
             .and(routes(dependency_manager).with(cors))
     }
 
+    fn epoch_service_with_registration_nonce() -> FakeEpochService {
+        FakeEpochService::from_fixture(Epoch(1), &MithrilFixtureBuilder::default().build())
+    }
+
+    /// Dependencies whose epoch service publishes a registration nonce
+    async fn dependencies_with_registration_nonce(
+        signer_registerer: MockSignerRegisterer,
+    ) -> DependencyContainer {
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.epoch_service =
+            Arc::new(RwLock::new(epoch_service_with_registration_nonce()));
+        dependency_manager.signer_registerer = Arc::new(signer_registerer);
+
+        dependency_manager
+    }
+
     #[tokio::test]
     async fn test_register_signer_post_ok() {
         let signer_with_stake = fake_data::signers_with_stakes(1).pop().unwrap();
@@ -322,6 +768,58 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_register_signer_post_ok_with_next_era_registration() {
+        let signer_with_stake = fake_data::signers_with_stakes(1).pop().unwrap();
+        let mut mock_signer_registerer = MockSignerRegisterer::new();
+        mock_signer_registerer
+            .expect_register_signer()
+            .return_once(|_, _| Ok(signer_with_stake));
+        mock_signer_registerer
+            .expect_register_next_era_signer()
+            .withf(|_, era, _| *era == SupportedEra::dummy())
+            .return_once(|_, _, _| Ok(()))
+            .once();
+        mock_signer_registerer
+            .expect_get_current_round()
+            .return_once(|| None);
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signer_registerer = Arc::new(mock_signer_registerer);
+
+        let register_signer_message = RegisterSignerMessage::dummy();
+        let signer = RegisterSignerMessage {
+            next_era_registration: Some(NextEraRegistrationMessagePart {
+                era: SupportedEra::dummy().to_string(),
+                verification_key: register_signer_message.verification_key.clone(),
+                verification_key_signature: register_signer_message
+                    .verification_key_signature
+                    .clone(),
+            }),
+            ..register_signer_message
+        };
+
+        let method = Method::POST.as_str();
+        let path = "/register-signer";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&signer)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &signer,
+            &response,
+            &StatusCode::CREATED,
+        )
+        .unwrap();
+    }
+
     #[tokio::test]
     async fn test_register_signer_post_ok_existing() {
         let signer_with_stake = fake_data::signers_with_stakes(1).pop().unwrap();
@@ -478,39 +976,246 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_registered_signers_get_offset_given_epoch_to_registration_epoch() {
-        let asked_epoch = Epoch(1);
-        let expected_retrieval_epoch = asked_epoch.offset_to_recording_epoch();
-        let mut mock_verification_key_store = MockVerificationKeyStorer::new();
-        mock_verification_key_store
-            .expect_get_signers()
-            .with(eq(expected_retrieval_epoch))
-            .return_once(|_| Ok(Some(fake_data::signers_with_stakes(3))))
-            .once();
+    async fn test_register_signer_post_ko_403_for_an_unregistered_signer() {
+        let mut mock_signer_registerer = MockSignerRegisterer::new();
+        mock_signer_registerer
+            .expect_register_signer()
+            .return_once(|_, signer| {
+                Err(SignerRegistrationError::UnregisteredSigner(
+                    signer.party_id.to_owned(),
+                ))
+            });
         let mut dependency_manager = initialize_dependencies().await;
-        dependency_manager.verification_key_store = Arc::new(mock_verification_key_store);
+        dependency_manager.signer_registerer = Arc::new(mock_signer_registerer);
 
-        let method = Method::GET.as_str();
-        let base_path = "/signers/registered";
+        let signer: RegisterSignerMessage = RegisterSignerMessage::dummy();
+        let method = Method::POST.as_str();
+        let path = "/register-signer";
 
         let response = request()
             .method(method)
-            .path(&format!("/{SERVER_BASE_PATH}{base_path}/{}", asked_epoch))
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&signer)
             .reply(&setup_router(Arc::new(dependency_manager)))
             .await;
 
-        assert!(
-            response.status().is_success(),
-            "expected the response to succeed, was: {response:#?}"
-        );
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &signer,
+            &response,
+            &StatusCode::FORBIDDEN,
+        )
+        .unwrap();
     }
 
     #[tokio::test]
-    async fn test_registered_signers_get_ok() {
-        let mut mock_verification_key_store = MockVerificationKeyStorer::new();
-        mock_verification_key_store
-            .expect_get_signers()
-            .return_once(|_| Ok(Some(fake_data::signers_with_stakes(3))))
+    async fn test_unregister_signer_delete_ok() {
+        let signer_with_stake = fake_data::signers_with_stakes(1).pop().unwrap();
+        let message = UnregisterSignerMessage::dummy();
+        let expected_registration_nonce = epoch_service_with_registration_nonce()
+            .current_registration_nonce()
+            .unwrap();
+        let mut mock_signer_registerer = MockSignerRegisterer::new();
+        mock_signer_registerer
+            .expect_unregister_signer()
+            .withf(move |epoch, party_id, registration_nonce, _| {
+                *epoch == Epoch(1)
+                    && party_id == "pool1m8crhnqj5k2kyszf5j2scshupystyxc887zdfrpzh6ty6eun4fx"
+                    && registration_nonce == expected_registration_nonce
+            })
+            .return_once(|_, _, _, _| Ok(signer_with_stake));
+        let dependency_manager = dependencies_with_registration_nonce(mock_signer_registerer).await;
+
+        let method = Method::DELETE.as_str();
+        let path = "/register-signer";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unregister_signer_delete_ko_404_for_an_unknown_signer() {
+        let message = UnregisterSignerMessage::dummy();
+        let mut mock_signer_registerer = MockSignerRegisterer::new();
+        mock_signer_registerer
+            .expect_unregister_signer()
+            .return_once(|_, party_id, _, _| {
+                Err(SignerRegistrationError::UnknownSigner(party_id.to_owned()))
+            });
+        let dependency_manager = dependencies_with_registration_nonce(mock_signer_registerer).await;
+
+        let method = Method::DELETE.as_str();
+        let path = "/register-signer";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::NOT_FOUND,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unregister_signer_delete_ko_403_for_an_invalid_signature() {
+        let message = UnregisterSignerMessage::dummy();
+        let mut mock_signer_registerer = MockSignerRegisterer::new();
+        mock_signer_registerer
+            .expect_unregister_signer()
+            .return_once(|_, party_id, _, _| {
+                Err(SignerRegistrationError::InvalidUnregistrationSignature(
+                    party_id.to_owned(),
+                ))
+            });
+        let dependency_manager = dependencies_with_registration_nonce(mock_signer_registerer).await;
+
+        let method = Method::DELETE.as_str();
+        let path = "/register-signer";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::FORBIDDEN,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unregister_signer_delete_ko_401_for_an_undecodable_signature() {
+        let message = UnregisterSignerMessage {
+            signature: "not-a-signature".to_string(),
+            ..UnregisterSignerMessage::dummy()
+        };
+        let mut mock_signer_registerer = MockSignerRegisterer::new();
+        mock_signer_registerer.expect_unregister_signer().never();
+        let dependency_manager = dependencies_with_registration_nonce(mock_signer_registerer).await;
+
+        let method = Method::DELETE.as_str();
+        let path = "/register-signer";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::UNAUTHORIZED,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unregister_signer_delete_ko_503_when_no_registration_round_is_opened() {
+        let message = UnregisterSignerMessage::dummy();
+        let mut mock_signer_registerer = MockSignerRegisterer::new();
+        mock_signer_registerer
+            .expect_unregister_signer()
+            .return_once(|_, _, _, _| Err(SignerRegistrationError::RegistrationRoundNotYetOpened));
+        let dependency_manager = dependencies_with_registration_nonce(mock_signer_registerer).await;
+
+        let method = Method::DELETE.as_str();
+        let path = "/register-signer";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::SERVICE_UNAVAILABLE,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_registered_signers_get_offset_given_epoch_to_registration_epoch() {
+        let asked_epoch = Epoch(1);
+        let expected_retrieval_epoch = asked_epoch.offset_to_recording_epoch();
+        let mut mock_verification_key_store = MockVerificationKeyStorer::new();
+        mock_verification_key_store
+            .expect_get_signers()
+            .with(eq(expected_retrieval_epoch))
+            .return_once(|_| Ok(Some(fake_data::signers_with_stakes(3))))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.verification_key_store = Arc::new(mock_verification_key_store);
+
+        let method = Method::GET.as_str();
+        let base_path = "/signers/registered";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{base_path}/{}", asked_epoch))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert!(
+            response.status().is_success(),
+            "expected the response to succeed, was: {response:#?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_registered_signers_get_ok() {
+        let mut mock_verification_key_store = MockVerificationKeyStorer::new();
+        mock_verification_key_store
+            .expect_get_signers()
+            .return_once(|_| Ok(Some(fake_data::signers_with_stakes(3))))
             .once();
         let mut dependency_manager = initialize_dependencies().await;
         dependency_manager.verification_key_store = Arc::new(mock_verification_key_store);
@@ -597,6 +1302,106 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_signers_stake_distribution_diff_get_ok() {
+        let signers = fake_data::signers_with_stakes(3);
+        let previous_signers = signers[1..].to_vec();
+        let asked_epoch = Epoch(5);
+        let mut mock_verification_key_store = MockVerificationKeyStorer::new();
+        mock_verification_key_store
+            .expect_get_signers()
+            .with(eq(asked_epoch.offset_to_recording_epoch()))
+            .return_once(|_| Ok(Some(signers)))
+            .once();
+        mock_verification_key_store
+            .expect_get_signers()
+            .with(eq((asked_epoch - 1).offset_to_recording_epoch()))
+            .return_once(|_| Ok(Some(previous_signers)))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.verification_key_store = Arc::new(mock_verification_key_store);
+
+        let base_path = "/signers/stake-distribution-diff";
+        let method = Method::GET.as_str();
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{base_path}/{asked_epoch}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            &format!("{base_path}/{{epoch}}"),
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+        let message: StakeDistributionDiffMessage =
+            serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(1, message.new_signers.len());
+        assert!(message.departed_signers.is_empty());
+        assert!(message.stake_changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_signers_stake_distribution_diff_returns_404_not_found_when_no_registration() {
+        let mut mock_verification_key_store = MockVerificationKeyStorer::new();
+        mock_verification_key_store
+            .expect_get_signers()
+            .returning(|_| Ok(None));
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.verification_key_store = Arc::new(mock_verification_key_store);
+
+        let base_path = "/signers/stake-distribution-diff";
+        let method = Method::GET.as_str();
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{base_path}/3"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            &format!("{base_path}/{{epoch}}"),
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::NOT_FOUND,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_signers_stake_distribution_diff_get_ko_400_for_epoch_zero() {
+        let dependency_manager = initialize_dependencies().await;
+
+        let base_path = "/signers/stake-distribution-diff";
+        let method = Method::GET.as_str();
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{base_path}/0"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            &format!("{base_path}/{{epoch}}"),
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::BAD_REQUEST,
+        )
+        .unwrap();
+    }
+
     #[tokio::test]
     async fn test_signers_tickers_get_ok() {
         let mut mock_signer_getter = MockSignerGetter::new();
@@ -675,4 +1480,410 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_register_signer_post_record_signer_metadata() {
+        let signer_with_stake = fake_data::signers_with_stakes(1).pop().unwrap();
+        let mut mock_signer_registerer = MockSignerRegisterer::new();
+        mock_signer_registerer
+            .expect_register_signer()
+            .return_once(|_, _| Ok(signer_with_stake));
+        let mut mock_signer_metadata_storer = MockSignerMetadataStorer::new();
+        mock_signer_metadata_storer
+            .expect_record_signer_metadata()
+            .withf(|metadata: &SignerMetadataRecord| {
+                metadata.registration_epoch == Epoch(1)
+                    && metadata.node_version == Some("0.2.150".to_string())
+                    && metadata.supported_eras == vec!["thales".to_string()]
+            })
+            .return_once(|_| Ok(()))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signer_registerer = Arc::new(mock_signer_registerer);
+        dependency_manager.signer_metadata_storer = Arc::new(mock_signer_metadata_storer);
+
+        let signer = RegisterSignerMessage {
+            epoch: Some(Epoch(1)),
+            ..RegisterSignerMessage::dummy()
+        };
+
+        let response = request()
+            .method(Method::POST.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/register-signer"))
+            .header("signer-node-version", "0.1.0")
+            .json(&signer)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::CREATED, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_register_signer_post_record_header_version_without_node_metadata() {
+        let signer_with_stake = fake_data::signers_with_stakes(1).pop().unwrap();
+        let mut mock_signer_registerer = MockSignerRegisterer::new();
+        mock_signer_registerer
+            .expect_register_signer()
+            .return_once(|_, _| Ok(signer_with_stake));
+        let mut mock_signer_metadata_storer = MockSignerMetadataStorer::new();
+        mock_signer_metadata_storer
+            .expect_record_signer_metadata()
+            .withf(|metadata: &SignerMetadataRecord| {
+                metadata.node_version == Some("0.1.0".to_string())
+                    && metadata.supported_eras.is_empty()
+            })
+            .return_once(|_| Ok(()))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signer_registerer = Arc::new(mock_signer_registerer);
+        dependency_manager.signer_metadata_storer = Arc::new(mock_signer_metadata_storer);
+
+        let signer = RegisterSignerMessage {
+            node_metadata: None,
+            ..RegisterSignerMessage::dummy()
+        };
+
+        let response = request()
+            .method(Method::POST.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/register-signer"))
+            .header("signer-node-version", "0.1.0")
+            .json(&signer)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::CREATED, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_register_signer_post_ok_even_if_signer_metadata_recording_fails() {
+        let signer_with_stake = fake_data::signers_with_stakes(1).pop().unwrap();
+        let mut mock_signer_registerer = MockSignerRegisterer::new();
+        mock_signer_registerer
+            .expect_register_signer()
+            .return_once(|_, _| Ok(signer_with_stake));
+        let mut mock_signer_metadata_storer = MockSignerMetadataStorer::new();
+        mock_signer_metadata_storer
+            .expect_record_signer_metadata()
+            .return_once(|_| Err(anyhow!("an error")));
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signer_registerer = Arc::new(mock_signer_registerer);
+        dependency_manager.signer_metadata_storer = Arc::new(mock_signer_metadata_storer);
+
+        let response = request()
+            .method(Method::POST.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/register-signer"))
+            .json(&RegisterSignerMessage::dummy())
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::CREATED, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_register_signer_post_record_signer_participation_at_signing_epoch() {
+        let signer_with_stake = fake_data::signers_with_stakes(1).pop().unwrap();
+        let mut mock_signer_registerer = MockSignerRegisterer::new();
+        mock_signer_registerer
+            .expect_register_signer()
+            .return_once(|_, _| Ok(signer_with_stake));
+        let mut mock_signer_participation_storer = MockSignerParticipationStorer::new();
+        mock_signer_participation_storer
+            .expect_record_registration()
+            .with(eq(RegisterSignerMessage::dummy().party_id), eq(Epoch(3)))
+            .return_once(|_, _| Ok(()))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signer_registerer = Arc::new(mock_signer_registerer);
+        dependency_manager.signer_participation_storer = Arc::new(mock_signer_participation_storer);
+
+        let signer = RegisterSignerMessage {
+            epoch: Some(Epoch(1)),
+            ..RegisterSignerMessage::dummy()
+        };
+
+        let response = request()
+            .method(Method::POST.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/register-signer"))
+            .json(&signer)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::CREATED, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_signer_history_get_ok() {
+        let mut mock_signer_participation_storer = MockSignerParticipationStorer::new();
+        mock_signer_participation_storer
+            .expect_get_signer_history()
+            .with(eq("pool1".to_string()))
+            .return_once(|_| {
+                Ok(vec![SignerParticipationRecord {
+                    signer_id: "pool1".to_string(),
+                    epoch: Epoch(12),
+                    registered: true,
+                    signatures_issued: 3,
+                    updated_at: Default::default(),
+                }])
+            })
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signer_participation_storer = Arc::new(mock_signer_participation_storer);
+
+        let method = Method::GET.as_str();
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}/signers/pool1/history"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            "/signers/{party_id}/history",
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_signer_history_returns_404_not_found_when_signer_never_seen() {
+        let mut mock_signer_participation_storer = MockSignerParticipationStorer::new();
+        mock_signer_participation_storer
+            .expect_get_signer_history()
+            .return_once(|_| Ok(vec![]))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signer_participation_storer = Arc::new(mock_signer_participation_storer);
+
+        let method = Method::GET.as_str();
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}/signers/pool1/history"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            "/signers/{party_id}/history",
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::NOT_FOUND,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_signer_history_get_ko() {
+        let mut mock_signer_participation_storer = MockSignerParticipationStorer::new();
+        mock_signer_participation_storer
+            .expect_get_signer_history()
+            .return_once(|_| Err(anyhow!("an error")))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signer_participation_storer = Arc::new(mock_signer_participation_storer);
+
+        let method = Method::GET.as_str();
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}/signers/pool1/history"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            "/signers/{party_id}/history",
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_signers_versions_get_ok() {
+        let mut mock_signer_metadata_storer = MockSignerMetadataStorer::new();
+        mock_signer_metadata_storer
+            .expect_get_latest_signers_metadata()
+            .return_once(|| {
+                Ok(vec![SignerMetadataRecord {
+                    signer_id: "pool1".to_string(),
+                    registration_epoch: Epoch(12),
+                    node_version: Some("0.2.150".to_string()),
+                    supported_eras: vec!["thales".to_string()],
+                    ip_address: None,
+                    updated_at: Default::default(),
+                }])
+            })
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signer_metadata_storer = Arc::new(mock_signer_metadata_storer);
+
+        let method = Method::GET.as_str();
+        let path = "/signers/versions";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_signers_versions_get_ko() {
+        let mut mock_signer_metadata_storer = MockSignerMetadataStorer::new();
+        mock_signer_metadata_storer
+            .expect_get_latest_signers_metadata()
+            .return_once(|| Err(anyhow!("an error")))
+            .once();
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.signer_metadata_storer = Arc::new(mock_signer_metadata_storer);
+
+        let method = Method::GET.as_str();
+        let path = "/signers/versions";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .unwrap();
+    }
+
+    const SIGNER_DIAGNOSTICS_TOKEN: &str = "diagnostics-token";
+
+    #[tokio::test]
+    async fn test_signer_diagnostics_post_ok() {
+        let config = Configuration {
+            signer_diagnostics_token: Some(SecretString::new(SIGNER_DIAGNOSTICS_TOKEN)),
+            ..Configuration::new_sample()
+        };
+        let mut builder = DependenciesBuilder::new(config);
+        let mut rx = builder.get_event_transmitter_receiver().await.unwrap();
+        let dependency_manager = builder.build_dependency_container().await.unwrap();
+        let message = SignerDiagnosticMessage::dummy();
+
+        let method = Method::POST.as_str();
+        let path = "/signers/diagnostics";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .header(
+                "authorization",
+                format!("Bearer {SIGNER_DIAGNOSTICS_TOKEN}"),
+            )
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        let result = APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &message,
+            &response,
+            &StatusCode::CREATED,
+        );
+
+        let event = rx.try_recv().unwrap();
+        assert_eq!("HTTP::signer_diagnostics", event.source);
+        assert_eq!("signer_diagnostic_submitted", event.action);
+        assert_eq!(
+            Some(&message.signer_id),
+            event.headers.get("signer_id"),
+            "the anonymized signer id should be recorded in the event headers"
+        );
+        result.unwrap();
+    }
+
+    async fn post_signer_diagnostic(
+        configured_token: Option<&str>,
+        authorization: Option<&str>,
+    ) -> warp::http::Response<warp::hyper::body::Bytes> {
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.config.signer_diagnostics_token =
+            configured_token.map(SecretString::new);
+        let mut request = request()
+            .method(Method::POST.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/signers/diagnostics"));
+        if let Some(authorization) = authorization {
+            request = request.header("authorization", authorization);
+        }
+
+        request
+            .json(&SignerDiagnosticMessage::dummy())
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_signer_diagnostics_post_ko_404_when_no_token_is_configured() {
+        let response = post_signer_diagnostic(None, None).await;
+
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_signer_diagnostics_post_ko_401_without_the_configured_token() {
+        let response = post_signer_diagnostic(Some(SIGNER_DIAGNOSTICS_TOKEN), None).await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+        let response =
+            post_signer_diagnostic(Some(SIGNER_DIAGNOSTICS_TOKEN), Some("Bearer another-token"))
+                .await;
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_signer_diagnostics_post_ko_413_when_payload_is_too_large() {
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.config.signer_diagnostics_token =
+            Some(SecretString::new(SIGNER_DIAGNOSTICS_TOKEN));
+        let message = SignerDiagnosticMessage {
+            current_state: "a".repeat(SIGNER_DIAGNOSTIC_MAX_CONTENT_LENGTH as usize),
+            ..SignerDiagnosticMessage::dummy()
+        };
+
+        let response = request()
+            .method(Method::POST.as_str())
+            .path(&format!("/{SERVER_BASE_PATH}/signers/diagnostics"))
+            .json(&message)
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, response.status());
+    }
 }
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+use warp::Filter;
+
+use crate::http_server::routes::middlewares;
+use crate::DependencyContainer;
+
+pub fn routes(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    status(dependency_manager)
+}
+
+/// GET /status
+fn status(
+    dependency_manager: Arc<DependencyContainer>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("status")
+        .and(warp::get())
+        .and(middlewares::with_status_service(dependency_manager))
+        .and_then(handlers::status)
+}
+
+mod handlers {
+    use slog_scope::{debug, warn};
+    use std::{convert::Infallible, sync::Arc};
+    use warp::http::StatusCode;
+
+    use crate::http_server::routes::reply;
+    use crate::services::StatusService;
+    use crate::unwrap_to_internal_server_error;
+
+    /// Build, runtime and system information of the aggregator
+    pub async fn status(
+        status_service: Arc<dyn StatusService>,
+    ) -> Result<impl warp::Reply, Infallible> {
+        debug!("⇄ HTTP SERVER: status");
+
+        let status =
+            unwrap_to_internal_server_error!(status_service.get_status().await, "status::error");
+
+        Ok(reply::json(&status, StatusCode::OK))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use chrono::Utc;
+    use serde_json::Value::Null;
+    use std::collections::BTreeSet;
+    use warp::{
+        http::{Method, StatusCode},
+        test::request,
+    };
+
+    use mithril_common::entities::{Epoch, SignedEntityTypeDiscriminants};
+    use mithril_common::era::SupportedEra;
+    use mithril_common::test_utils::apispec::APISpec;
+
+    use crate::entities::AggregatorStatusMessage;
+    use crate::services::MockStatusService;
+    use crate::{http_server::SERVER_BASE_PATH, initialize_dependencies};
+
+    use super::*;
+
+    fn setup_router(
+        dependency_manager: Arc<DependencyContainer>,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let cors = warp::cors()
+            .allow_any_origin()
+            .allow_headers(vec!["content-type"])
+            .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+
+        warp::any()
+            .and(warp::path(SERVER_BASE_PATH))
+            .and(routes(dependency_manager).with(cors))
+    }
+
+    fn dummy_status(latest_certificate_hash: Option<String>) -> AggregatorStatusMessage {
+        AggregatorStatusMessage {
+            aggregator_node_version: "0.5.80".to_string(),
+            supported_era: SupportedEra::dummy(),
+            epoch: Epoch(329),
+            latest_certificate_hash,
+            signed_entity_types: BTreeSet::from([
+                SignedEntityTypeDiscriminants::MithrilStakeDistribution,
+                SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+            ]),
+            database_version: 32,
+            started_at: Utc::now(),
+            uptime_seconds: 86400,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_get_ok() {
+        let mut mock_status_service = MockStatusService::new();
+        mock_status_service
+            .expect_get_status()
+            .return_once(|| Ok(dummy_status(Some("certificate-hash".to_string()))));
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.status_service = Arc::new(mock_status_service);
+
+        let method = Method::GET.as_str();
+        let path = "/status";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_status_get_ok_without_certificate() {
+        let mut mock_status_service = MockStatusService::new();
+        mock_status_service
+            .expect_get_status()
+            .return_once(|| Ok(dummy_status(None)));
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.status_service = Arc::new(mock_status_service);
+
+        let method = Method::GET.as_str();
+        let path = "/status";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_status_get_ko_500() {
+        let mut mock_status_service = MockStatusService::new();
+        mock_status_service
+            .expect_get_status()
+            .return_once(|| Err(anyhow!("an error")));
+        let mut dependency_manager = initialize_dependencies().await;
+        dependency_manager.status_service = Arc::new(mock_status_service);
+
+        let method = Method::GET.as_str();
+        let path = "/status";
+
+        let response = request()
+            .method(method)
+            .path(&format!("/{SERVER_BASE_PATH}{path}"))
+            .reply(&setup_router(Arc::new(dependency_manager)))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_all_spec_files(),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::INTERNAL_SERVER_ERROR,
+        )
+        .unwrap();
+    }
+}
@@ -12,6 +12,7 @@
 //! You can find more information on how it works reading the [documentation website](https://mithril.network/doc/mithril/mithril-network/aggregator).
 
 mod artifact_builder;
+pub mod artifact_storage;
 mod commands;
 mod configuration;
 pub mod database;
@@ -20,6 +21,7 @@ pub mod entities;
 pub mod event_store;
 mod http_server;
 mod message_adapters;
+pub mod metrics;
 mod multi_signer;
 mod runtime;
 pub mod services;
@@ -38,17 +40,22 @@ pub use crate::multi_signer::{MultiSigner, MultiSignerImpl};
 pub use commands::{CommandType, MainOpts};
 pub use dependency_injection::DependencyContainer;
 pub use message_adapters::{
-    FromRegisterSignerAdapter, ToCertificatePendingMessageAdapter, ToEpochSettingsMessageAdapter,
+    FromRegisterSignerAdapter, FromRegisterSignerNextEraAdapter,
+    ToCertificatePendingMessageAdapter, ToEpochSettingsMessageAdapter,
 };
+pub use metrics::{MetricsServer, MetricsService};
 pub use runtime::{
-    AggregatorConfig, AggregatorRunner, AggregatorRunnerTrait, AggregatorRuntime, RuntimeError,
+    AggregatorConfig, AggregatorRunner, AggregatorRunnerTrait, AggregatorRuntime,
+    RuntimeCycleTrigger, RuntimeError, RuntimeStep, RuntimeTriggerError,
 };
 pub use signer_registerer::{
     MithrilSignerRegisterer, SignerRecorder, SignerRegisterer, SignerRegistrationError,
     SignerRegistrationRound, SignerRegistrationRoundOpener,
 };
 pub use snapshot_uploaders::{
-    DumbSnapshotUploader, LocalSnapshotUploader, RemoteSnapshotUploader, SnapshotUploader,
+    DeduplicatingSnapshotUploader, DumbSnapshotUploader, LocalSnapshotUploader,
+    MeteredSnapshotUploader, RemoteSnapshotUploader, ScheduledSnapshotUploader,
+    SharedUploadSchedule, SnapshotUploader, UploadSchedule, UploadWindow,
 };
 pub use snapshotter::{
     CompressedArchiveSnapshotter, DumbSnapshotter, SnapshotError, Snapshotter,
@@ -58,7 +65,9 @@ pub use store::{
     CertificatePendingStore, ProtocolParametersStorer, VerificationKeyStore, VerificationKeyStorer,
 };
 pub use tools::{
-    CExplorerSignerRetriever, SignersImporter, SignersImporterPersister, SignersImporterRetriever,
+    AggregatorCertificatesRetriever, CExplorerSignerRetriever, CertificatesImportReport,
+    CertificatesImporter, CertificatesImporterRetriever, SignersImporter, SignersImporterPersister,
+    SignersImporterRetriever,
 };
 
 #[cfg(test)]
@@ -1,37 +1,61 @@
 #![doc = include_str!("../README.md")]
 
-use clap::Parser;
 use mithril_aggregator::{CommandType, MainOpts};
+use mithril_common::logging::{ComponentLevelFilter, ComponentLogLevels};
 use mithril_common::StdResult;
-use slog::{Drain, Fuse, Level, Logger};
+use mithril_secrets::SecretsEnvironmentResolver;
+use mithril_service_manager::{notifier_from_environment, ServiceNotifier};
+use slog::{Drain, Fuse, Logger};
 use slog_async::Async;
 use std::sync::Arc;
 
-fn build_io_logger<W: std::io::Write + Send + 'static>(log_level: Level, io: W) -> Fuse<Async> {
+fn build_io_logger<W: std::io::Write + Send + 'static>(
+    log_levels: ComponentLogLevels,
+    io: W,
+) -> Fuse<Async> {
     let drain = slog_bunyan::new(io).set_pretty(false).build().fuse();
-    let drain = slog::LevelFilter::new(drain, log_level).fuse();
+    let drain = ComponentLevelFilter::new(drain, log_levels).fuse();
 
     slog_async::Async::new(drain).build().fuse()
 }
 
-/// Build a logger from args.
-pub fn build_logger(args: &MainOpts) -> Logger {
+/// Build a logger from args, the level of each component is read from the given log levels.
+pub fn build_logger(args: &MainOpts, log_levels: ComponentLogLevels) -> Logger {
     let drain = match args.command.command_type() {
-        CommandType::Server => build_io_logger(args.log_level(), std::io::stdout()),
-        CommandType::CommandLine => build_io_logger(args.log_level(), std::io::stderr()),
+        CommandType::Server => build_io_logger(log_levels, std::io::stdout()),
+        CommandType::CommandLine => build_io_logger(log_levels, std::io::stderr()),
     };
 
     Logger::root(Arc::new(drain), slog::o!())
 }
 
-#[tokio::main]
-async fn main() -> StdResult<()> {
-    // Load args
-    let args = MainOpts::parse();
-    let _guard = slog_scope::set_global_logger(build_logger(&args));
+fn main() -> StdResult<()> {
+    #[cfg(all(windows, feature = "windows_service"))]
+    if mithril_service_manager::windows::run_if_started_as_service("mithril-aggregator", run)? {
+        return Ok(());
+    }
 
-    #[cfg(feature = "bundle_openssl")]
-    openssl_probe::init_ssl_cert_env_vars();
+    run(notifier_from_environment())
+}
 
-    args.execute().await
+/// Run the aggregator, its state is reported to the service manager through the given notifier.
+fn run(service_notifier: Arc<dyn ServiceNotifier>) -> StdResult<()> {
+    tokio::runtime::Runtime::new()?.block_on(async {
+        // Resolve the secrets given through files or secret managers before reading the settings
+        let environment = SecretsEnvironmentResolver::new()
+            .with_enabled_resolvers()
+            .resolve_environment()
+            .await?;
+
+        // Load args
+        let args: MainOpts = environment.parse_arguments();
+        let log_levels = ComponentLogLevels::new(args.log_level());
+        let _guard = slog_scope::set_global_logger(build_logger(&args, log_levels.clone()));
+
+        #[cfg(feature = "bundle_openssl")]
+        openssl_probe::init_ssl_cert_env_vars();
+
+        args.execute(&environment, log_levels, service_notifier)
+            .await
+    })
 }
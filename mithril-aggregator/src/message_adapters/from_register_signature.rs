@@ -22,6 +22,14 @@ impl TryFromMessageAdapter<RegisterSignatureMessage, SingleSignatures>
                     "'FromRegisterSingleSignatureAdapter' can not convert the single signature"
                 })?,
             won_indexes: register_single_signature_message.won_indexes,
+            era: register_single_signature_message.era,
+            nonce_signature: register_single_signature_message
+                .nonce_signature
+                .map(|nonce_signature| nonce_signature.try_into())
+                .transpose()
+                .with_context(|| {
+                    "'FromRegisterSingleSignatureAdapter' can not convert the nonce signature"
+                })?,
         };
 
         Ok(signatures)
@@ -39,4 +47,15 @@ mod tests {
 
         assert_eq!("party_id".to_string(), signatures.party_id);
     }
+
+    #[test]
+    fn test_message_with_invalid_nonce_signature() {
+        let message = RegisterSignatureMessage {
+            nonce_signature: Some("invalid".to_string()),
+            ..RegisterSignatureMessage::dummy()
+        };
+
+        FromRegisterSingleSignatureAdapter::try_adapt(message)
+            .expect_err("An invalid nonce signature should fail the conversion");
+    }
 }
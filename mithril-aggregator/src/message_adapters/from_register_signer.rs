@@ -1,6 +1,9 @@
 use anyhow::Context;
+use std::str::FromStr;
+
 use mithril_common::{
     entities::Signer,
+    era::SupportedEra,
     messages::{RegisterSignerMessage, TryFromMessageAdapter},
     StdResult,
 };
@@ -40,8 +43,44 @@ impl TryFromMessageAdapter<RegisterSignerMessage, Signer> for FromRegisterSigner
     }
 }
 
+/// Adapter to convert the next era registration of a [RegisterSignerMessage] to the [Signer]
+/// registered with the verification key of the next era, if any.
+pub struct FromRegisterSignerNextEraAdapter;
+
+impl TryFromMessageAdapter<RegisterSignerMessage, Option<(SupportedEra, Signer)>>
+    for FromRegisterSignerNextEraAdapter
+{
+    /// Method to trigger the conversion.
+    fn try_adapt(
+        register_signer_message: RegisterSignerMessage,
+    ) -> StdResult<Option<(SupportedEra, Signer)>> {
+        let Some(next_era_registration) = register_signer_message.next_era_registration.clone()
+        else {
+            return Ok(None);
+        };
+        let era = SupportedEra::from_str(&next_era_registration.era).with_context(|| {
+            format!(
+                "'FromRegisterSignerNextEraAdapter' can not convert the era '{}'",
+                next_era_registration.era
+            )
+        })?;
+        let signer = FromRegisterSignerAdapter::try_adapt(RegisterSignerMessage {
+            verification_key: next_era_registration.verification_key,
+            verification_key_signature: next_era_registration.verification_key_signature,
+            next_era_registration: None,
+            ..register_signer_message
+        })?;
+
+        Ok(Some((era, signer)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use mithril_common::crypto_helper::ProtocolSignerVerificationKey;
+    use mithril_common::messages::NextEraRegistrationMessagePart;
+    use mithril_common::test_utils::fake_keys;
+
     use super::*;
 
     #[test]
@@ -56,4 +95,61 @@ mod tests {
 
         assert_eq!("one".to_string(), signer.party_id);
     }
+
+    #[test]
+    fn adapt_the_next_era_registration() {
+        let register_signer_message = RegisterSignerMessage {
+            party_id: "one".to_string(),
+            next_era_registration: Some(NextEraRegistrationMessagePart {
+                era: SupportedEra::dummy().to_string(),
+                verification_key: fake_keys::signer_verification_key()[1].to_string(),
+                verification_key_signature: None,
+            }),
+            ..RegisterSignerMessage::dummy()
+        };
+
+        let (era, signer) =
+            FromRegisterSignerNextEraAdapter::try_adapt(register_signer_message.clone())
+                .expect("Converting the next era registration should not fail")
+                .expect("The next era registration should be converted");
+
+        assert_eq!(SupportedEra::dummy(), era);
+        assert_eq!("one".to_string(), signer.party_id);
+        assert_eq!(
+            ProtocolSignerVerificationKey::try_from(fake_keys::signer_verification_key()[1])
+                .unwrap(),
+            signer.verification_key
+        );
+        assert_eq!(None, signer.verification_key_signature);
+        assert_eq!(
+            FromRegisterSignerAdapter::try_adapt(register_signer_message)
+                .unwrap()
+                .operational_certificate,
+            signer.operational_certificate
+        );
+    }
+
+    #[test]
+    fn adapt_a_message_without_next_era_registration() {
+        let next_era_signer = FromRegisterSignerNextEraAdapter::try_adapt(RegisterSignerMessage {
+            next_era_registration: None,
+            ..RegisterSignerMessage::dummy()
+        })
+        .unwrap();
+
+        assert_eq!(None, next_era_signer);
+    }
+
+    #[test]
+    fn adapt_a_next_era_registration_of_an_unknown_era_fails() {
+        FromRegisterSignerNextEraAdapter::try_adapt(RegisterSignerMessage {
+            next_era_registration: Some(NextEraRegistrationMessagePart {
+                era: "unknown".to_string(),
+                verification_key: fake_keys::signer_verification_key()[1].to_string(),
+                verification_key_signature: None,
+            }),
+            ..RegisterSignerMessage::dummy()
+        })
+        .expect_err("Converting the registration of an unknown era should fail");
+    }
 }
@@ -11,7 +11,7 @@ mod to_snapshot_list_message;
 mod to_snapshot_message;
 
 pub use from_register_signature::FromRegisterSingleSignatureAdapter;
-pub use from_register_signer::FromRegisterSignerAdapter;
+pub use from_register_signer::{FromRegisterSignerAdapter, FromRegisterSignerNextEraAdapter};
 #[cfg(test)]
 pub use to_cardano_transaction_list_message::ToCardanoTransactionListMessageAdapter;
 #[cfg(test)]
@@ -30,6 +30,8 @@ impl ToCertificatePendingMessageAdapter {
             next_protocol_parameters: certificate_pending.next_protocol_parameters,
             signers: Self::adapt_signers(certificate_pending.signers),
             next_signers: Self::adapt_signers(certificate_pending.next_signers),
+            batched_signed_entity_types: certificate_pending.batched_signed_entity_types,
+            initiated_at: certificate_pending.initiated_at,
         }
     }
 }
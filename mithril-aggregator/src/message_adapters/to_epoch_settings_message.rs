@@ -11,6 +11,9 @@ impl ToMessageAdapter<EpochSettings, EpochSettingsMessage> for ToEpochSettingsMe
             epoch: epoch_settings.epoch,
             protocol_parameters: epoch_settings.protocol_parameters,
             next_protocol_parameters: epoch_settings.next_protocol_parameters,
+            protocol_feature_activations: epoch_settings.protocol_feature_activations,
+            registration_nonce: epoch_settings.registration_nonce,
+            upcoming_signers: epoch_settings.upcoming_signers,
         }
     }
 }
@@ -17,6 +17,10 @@ impl ToMessageAdapter<SignedEntity<Snapshot>, SnapshotMessage> for ToSnapshotMes
             locations: signed_entity.artifact.locations,
             compression_algorithm: Some(signed_entity.artifact.compression_algorithm),
             cardano_node_version: Some(signed_entity.artifact.cardano_node_version),
+            ancillary_size: signed_entity.artifact.ancillary_size,
+            ancillary_locations: signed_entity.artifact.ancillary_locations,
+            signed_locations: None,
+            archive_hash: signed_entity.artifact.archive_hash,
         }
     }
 }
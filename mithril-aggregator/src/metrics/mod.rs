@@ -0,0 +1,63 @@
+//! metrics module.
+//! This module contains the aggregator metrics service and metrics server.
+
+mod server;
+mod service;
+
+pub use server::MetricsServer;
+pub use service::MetricsService;
+
+/// 'pruned_open_messages_since_startup' metric name
+pub const PRUNED_OPEN_MESSAGES_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_pruned_open_messages_since_startup";
+/// 'pruned_open_messages_since_startup' metric help
+pub const PRUNED_OPEN_MESSAGES_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of open messages removed by the retention policy since startup on a Mithril aggregator node";
+
+/// 'pruned_single_signatures_since_startup' metric name
+pub const PRUNED_SINGLE_SIGNATURES_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_pruned_single_signatures_since_startup";
+/// 'pruned_single_signatures_since_startup' metric help
+pub const PRUNED_SINGLE_SIGNATURES_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of single signatures removed by the retention policy since startup on a Mithril aggregator node";
+
+/// 'proof_cache_hits_since_startup' metric name
+pub const PROOF_CACHE_HITS_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_proof_cache_hits_since_startup";
+/// 'proof_cache_hits_since_startup' metric help
+pub const PROOF_CACHE_HITS_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of Cardano transactions proof requests served from the prover cache since startup on a Mithril aggregator node";
+
+/// 'proof_cache_misses_since_startup' metric name
+pub const PROOF_CACHE_MISSES_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_proof_cache_misses_since_startup";
+/// 'proof_cache_misses_since_startup' metric help
+pub const PROOF_CACHE_MISSES_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of Cardano transactions proof requests not found in the prover cache since startup on a Mithril aggregator node";
+
+/// 'snapshot_uploaded_bytes_since_startup' metric name
+pub const SNAPSHOT_UPLOADED_BYTES_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_snapshot_uploaded_bytes_since_startup";
+/// 'snapshot_uploaded_bytes_since_startup' metric help
+pub const SNAPSHOT_UPLOADED_BYTES_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of bytes of the snapshot archives uploaded since startup on a Mithril aggregator node";
+
+/// 'snapshot_upload_milliseconds_since_startup' metric name
+pub const SNAPSHOT_UPLOAD_MILLISECONDS_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_aggregator_snapshot_upload_milliseconds_since_startup";
+/// 'snapshot_upload_milliseconds_since_startup' metric help
+pub const SNAPSHOT_UPLOAD_MILLISECONDS_SINCE_STARTUP_METRIC_HELP: &str =
+    "Time spent uploading the snapshot archives since startup on a Mithril aggregator node, in milliseconds";
+
+/// 'database_query_duration_seconds' metric name
+pub const DATABASE_QUERY_DURATION_SECONDS_METRIC_NAME: &str =
+    "mithril_aggregator_database_query_duration_seconds";
+/// 'database_query_duration_seconds' metric help
+pub const DATABASE_QUERY_DURATION_SECONDS_METRIC_HELP: &str =
+    "Duration of the database queries performed by a Mithril aggregator node, in seconds, by query type";
+
+/// 'database_query_rows' metric name
+pub const DATABASE_QUERY_ROWS_METRIC_NAME: &str = "mithril_aggregator_database_query_rows";
+/// 'database_query_rows' metric help
+pub const DATABASE_QUERY_ROWS_METRIC_HELP: &str =
+    "Number of rows fetched by the database queries performed by a Mithril aggregator node, by query type";
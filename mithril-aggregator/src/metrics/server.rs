@@ -0,0 +1,96 @@
+use anyhow::Context;
+use std::net::IpAddr;
+use std::sync::Arc;
+use tokio::sync::oneshot::Receiver;
+use warp::{http::StatusCode, Filter};
+
+use mithril_common::StdResult;
+use slog_scope::{error, info, warn};
+
+use crate::MetricsService;
+
+/// The MetricsServer is responsible for exposing the metrics of the aggregator.
+pub struct MetricsServer {
+    server_port: u16,
+    server_ip: String,
+    metrics_service: Arc<MetricsService>,
+}
+
+impl MetricsServer {
+    /// Create a new MetricsServer instance.
+    pub fn new(server_ip: &str, server_port: u16, metrics_service: Arc<MetricsService>) -> Self {
+        Self {
+            server_port,
+            server_ip: server_ip.to_string(),
+            metrics_service,
+        }
+    }
+
+    /// Metrics server endpoint.
+    pub fn endpoint(&self) -> String {
+        format!("http://{}:{}", self.server_ip, self.server_port)
+    }
+
+    /// GET /metrics
+    fn routes(
+        metrics_service: Arc<MetricsService>,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        warp::path!("metrics").and(warp::get()).map(move || {
+            match metrics_service.export_metrics() {
+                Ok(metrics) => warp::reply::with_status(metrics, StatusCode::OK),
+                Err(err) => {
+                    error!("MetricsServer: can not export metrics"; "error" => ?err);
+                    warp::reply::with_status(
+                        format!("Error: {err:?}"),
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    )
+                }
+            }
+        })
+    }
+
+    /// Serve the metrics on a HTTP server.
+    pub async fn start(&self, shutdown_rx: Receiver<()>) -> StdResult<()> {
+        info!(
+            "MetricsServer: starting HTTP server for metrics on port {}",
+            self.server_port
+        );
+        let server_address = (
+            self.server_ip
+                .parse::<IpAddr>()
+                .with_context(|| format!("Invalid metrics server IP: '{}'", self.server_ip))?,
+            self.server_port,
+        );
+        let (_, server) = warp::serve(Self::routes(self.metrics_service.clone()))
+            .try_bind_with_graceful_shutdown(server_address, async {
+                shutdown_rx.await.ok();
+                warn!("MetricsServer: shutting down HTTP server after receiving signal");
+            })
+            .with_context(|| format!("MetricsServer can not bind to '{}'", self.endpoint()))?;
+        server.await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::metrics::PRUNED_OPEN_MESSAGES_SINCE_STARTUP_METRIC_NAME;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_metrics_route_export_the_metrics() {
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+
+        let response = warp::test::request()
+            .method("GET")
+            .path("/metrics")
+            .reply(&MetricsServer::routes(metrics_service))
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert!(String::from_utf8_lossy(response.body())
+            .contains(PRUNED_OPEN_MESSAGES_SINCE_STARTUP_METRIC_NAME));
+    }
+}
@@ -0,0 +1,363 @@
+use mithril_common::StdResult;
+use mithril_persistence::sqlite::{QueryObserver, QueryStatistics};
+use prometheus::{
+    exponential_buckets, Counter, Encoder, HistogramOpts, HistogramVec, Opts, Registry, TextEncoder,
+};
+use slog_scope::debug;
+
+use super::{
+    DATABASE_QUERY_DURATION_SECONDS_METRIC_HELP, DATABASE_QUERY_DURATION_SECONDS_METRIC_NAME,
+    DATABASE_QUERY_ROWS_METRIC_HELP, DATABASE_QUERY_ROWS_METRIC_NAME,
+    PROOF_CACHE_HITS_SINCE_STARTUP_METRIC_HELP, PROOF_CACHE_HITS_SINCE_STARTUP_METRIC_NAME,
+    PROOF_CACHE_MISSES_SINCE_STARTUP_METRIC_HELP, PROOF_CACHE_MISSES_SINCE_STARTUP_METRIC_NAME,
+    PRUNED_OPEN_MESSAGES_SINCE_STARTUP_METRIC_HELP, PRUNED_OPEN_MESSAGES_SINCE_STARTUP_METRIC_NAME,
+    PRUNED_SINGLE_SIGNATURES_SINCE_STARTUP_METRIC_HELP,
+    PRUNED_SINGLE_SIGNATURES_SINCE_STARTUP_METRIC_NAME,
+    SNAPSHOT_UPLOADED_BYTES_SINCE_STARTUP_METRIC_HELP,
+    SNAPSHOT_UPLOADED_BYTES_SINCE_STARTUP_METRIC_NAME,
+    SNAPSHOT_UPLOAD_MILLISECONDS_SINCE_STARTUP_METRIC_HELP,
+    SNAPSHOT_UPLOAD_MILLISECONDS_SINCE_STARTUP_METRIC_NAME,
+};
+
+/// Type alias for a metric name.
+pub type MetricName = str;
+
+/// Type alias for a counter value.
+type CounterValue = u64;
+
+/// Metrics service which is responsible for recording and exposing metrics.
+pub struct MetricsService {
+    registry: Registry,
+    pruned_open_messages_since_startup_counter: Box<Counter>,
+    pruned_single_signatures_since_startup_counter: Box<Counter>,
+    proof_cache_hits_since_startup_counter: Box<Counter>,
+    proof_cache_misses_since_startup_counter: Box<Counter>,
+    snapshot_uploaded_bytes_since_startup_counter: Box<Counter>,
+    snapshot_upload_milliseconds_since_startup_counter: Box<Counter>,
+    database_query_duration_seconds_histogram: Box<HistogramVec>,
+    database_query_rows_histogram: Box<HistogramVec>,
+}
+
+impl MetricsService {
+    /// Create a new `MetricsService` instance.
+    pub fn new() -> StdResult<Self> {
+        let registry = Registry::new();
+
+        // Retention policies metrics
+        let pruned_open_messages_since_startup_counter = Box::new(Self::create_metric_counter(
+            PRUNED_OPEN_MESSAGES_SINCE_STARTUP_METRIC_NAME,
+            PRUNED_OPEN_MESSAGES_SINCE_STARTUP_METRIC_HELP,
+        )?);
+        registry.register(pruned_open_messages_since_startup_counter.clone())?;
+
+        let pruned_single_signatures_since_startup_counter = Box::new(Self::create_metric_counter(
+            PRUNED_SINGLE_SIGNATURES_SINCE_STARTUP_METRIC_NAME,
+            PRUNED_SINGLE_SIGNATURES_SINCE_STARTUP_METRIC_HELP,
+        )?);
+        registry.register(pruned_single_signatures_since_startup_counter.clone())?;
+
+        // Prover cache metrics
+        let proof_cache_hits_since_startup_counter = Box::new(Self::create_metric_counter(
+            PROOF_CACHE_HITS_SINCE_STARTUP_METRIC_NAME,
+            PROOF_CACHE_HITS_SINCE_STARTUP_METRIC_HELP,
+        )?);
+        registry.register(proof_cache_hits_since_startup_counter.clone())?;
+
+        let proof_cache_misses_since_startup_counter = Box::new(Self::create_metric_counter(
+            PROOF_CACHE_MISSES_SINCE_STARTUP_METRIC_NAME,
+            PROOF_CACHE_MISSES_SINCE_STARTUP_METRIC_HELP,
+        )?);
+        registry.register(proof_cache_misses_since_startup_counter.clone())?;
+
+        // Snapshot upload metrics
+        let snapshot_uploaded_bytes_since_startup_counter = Box::new(Self::create_metric_counter(
+            SNAPSHOT_UPLOADED_BYTES_SINCE_STARTUP_METRIC_NAME,
+            SNAPSHOT_UPLOADED_BYTES_SINCE_STARTUP_METRIC_HELP,
+        )?);
+        registry.register(snapshot_uploaded_bytes_since_startup_counter.clone())?;
+
+        let snapshot_upload_milliseconds_since_startup_counter =
+            Box::new(Self::create_metric_counter(
+                SNAPSHOT_UPLOAD_MILLISECONDS_SINCE_STARTUP_METRIC_NAME,
+                SNAPSHOT_UPLOAD_MILLISECONDS_SINCE_STARTUP_METRIC_HELP,
+            )?);
+        registry.register(snapshot_upload_milliseconds_since_startup_counter.clone())?;
+
+        // Database metrics
+        let database_query_duration_seconds_histogram =
+            Box::new(Self::create_metric_histogram_vec(
+                DATABASE_QUERY_DURATION_SECONDS_METRIC_NAME,
+                DATABASE_QUERY_DURATION_SECONDS_METRIC_HELP,
+                exponential_buckets(0.0005, 4.0, 9)?,
+            )?);
+        registry.register(database_query_duration_seconds_histogram.clone())?;
+
+        let database_query_rows_histogram = Box::new(Self::create_metric_histogram_vec(
+            DATABASE_QUERY_ROWS_METRIC_NAME,
+            DATABASE_QUERY_ROWS_METRIC_HELP,
+            exponential_buckets(1.0, 10.0, 7)?,
+        )?);
+        registry.register(database_query_rows_histogram.clone())?;
+
+        Ok(Self {
+            registry,
+            pruned_open_messages_since_startup_counter,
+            pruned_single_signatures_since_startup_counter,
+            proof_cache_hits_since_startup_counter,
+            proof_cache_misses_since_startup_counter,
+            snapshot_uploaded_bytes_since_startup_counter,
+            snapshot_upload_milliseconds_since_startup_counter,
+            database_query_duration_seconds_histogram,
+            database_query_rows_histogram,
+        })
+    }
+
+    fn create_metric_counter(name: &MetricName, help: &str) -> StdResult<Counter> {
+        let counter_opts = Opts::new(name, help);
+        let counter = Counter::with_opts(counter_opts)?;
+
+        Ok(counter)
+    }
+
+    fn create_metric_histogram_vec(
+        name: &MetricName,
+        help: &str,
+        buckets: Vec<f64>,
+    ) -> StdResult<HistogramVec> {
+        let histogram_opts = HistogramOpts::new(name, help).buckets(buckets);
+        let histogram = HistogramVec::new(histogram_opts, &["query_type"])?;
+
+        Ok(histogram)
+    }
+
+    /// Export the metrics as a string with the Open Metrics standard format.
+    /// These metrics can be exposed on a HTTP server.
+    pub fn export_metrics(&self) -> StdResult<String> {
+        let mut buffer = vec![];
+        let encoder = TextEncoder::new();
+        let metric_families = self.registry.gather();
+        encoder.encode(&metric_families, &mut buffer)?;
+
+        Ok(String::from_utf8(buffer)?)
+    }
+
+    /// Increase the `pruned_open_messages_since_startup` counter by the given value.
+    pub fn pruned_open_messages_since_startup_counter_increase(&self, value: CounterValue) {
+        debug!(
+            "MetricsService: increasing 'pruned_open_messages_since_startup' counter by {value}"
+        );
+        self.pruned_open_messages_since_startup_counter
+            .inc_by(value as f64);
+    }
+
+    /// Get the `pruned_open_messages_since_startup` counter.
+    pub fn pruned_open_messages_since_startup_counter_get(&self) -> CounterValue {
+        self.pruned_open_messages_since_startup_counter
+            .get()
+            .round() as CounterValue
+    }
+
+    /// Increase the `pruned_single_signatures_since_startup` counter by the given value.
+    pub fn pruned_single_signatures_since_startup_counter_increase(&self, value: CounterValue) {
+        debug!(
+            "MetricsService: increasing 'pruned_single_signatures_since_startup' counter by {value}"
+        );
+        self.pruned_single_signatures_since_startup_counter
+            .inc_by(value as f64);
+    }
+
+    /// Get the `pruned_single_signatures_since_startup` counter.
+    pub fn pruned_single_signatures_since_startup_counter_get(&self) -> CounterValue {
+        self.pruned_single_signatures_since_startup_counter
+            .get()
+            .round() as CounterValue
+    }
+
+    /// Increase the `proof_cache_hits_since_startup` counter by the given value.
+    pub fn proof_cache_hits_since_startup_counter_increase(&self, value: CounterValue) {
+        debug!("MetricsService: increasing 'proof_cache_hits_since_startup' counter by {value}");
+        self.proof_cache_hits_since_startup_counter
+            .inc_by(value as f64);
+    }
+
+    /// Get the `proof_cache_hits_since_startup` counter.
+    pub fn proof_cache_hits_since_startup_counter_get(&self) -> CounterValue {
+        self.proof_cache_hits_since_startup_counter.get().round() as CounterValue
+    }
+
+    /// Increase the `proof_cache_misses_since_startup` counter by the given value.
+    pub fn proof_cache_misses_since_startup_counter_increase(&self, value: CounterValue) {
+        debug!("MetricsService: increasing 'proof_cache_misses_since_startup' counter by {value}");
+        self.proof_cache_misses_since_startup_counter
+            .inc_by(value as f64);
+    }
+
+    /// Get the `proof_cache_misses_since_startup` counter.
+    pub fn proof_cache_misses_since_startup_counter_get(&self) -> CounterValue {
+        self.proof_cache_misses_since_startup_counter.get().round() as CounterValue
+    }
+
+    /// Increase the `snapshot_uploaded_bytes_since_startup` counter by the given value.
+    pub fn snapshot_uploaded_bytes_since_startup_counter_increase(&self, value: CounterValue) {
+        debug!(
+            "MetricsService: increasing 'snapshot_uploaded_bytes_since_startup' counter by {value}"
+        );
+        self.snapshot_uploaded_bytes_since_startup_counter
+            .inc_by(value as f64);
+    }
+
+    /// Get the `snapshot_uploaded_bytes_since_startup` counter.
+    pub fn snapshot_uploaded_bytes_since_startup_counter_get(&self) -> CounterValue {
+        self.snapshot_uploaded_bytes_since_startup_counter
+            .get()
+            .round() as CounterValue
+    }
+
+    /// Increase the `snapshot_upload_milliseconds_since_startup` counter by the given value.
+    pub fn snapshot_upload_milliseconds_since_startup_counter_increase(&self, value: CounterValue) {
+        debug!(
+            "MetricsService: increasing 'snapshot_upload_milliseconds_since_startup' counter by {value}"
+        );
+        self.snapshot_upload_milliseconds_since_startup_counter
+            .inc_by(value as f64);
+    }
+
+    /// Get the `snapshot_upload_milliseconds_since_startup` counter.
+    pub fn snapshot_upload_milliseconds_since_startup_counter_get(&self) -> CounterValue {
+        self.snapshot_upload_milliseconds_since_startup_counter
+            .get()
+            .round() as CounterValue
+    }
+}
+
+impl QueryObserver for MetricsService {
+    fn on_query_done(&self, statistics: &QueryStatistics) {
+        let query_type = [statistics.query_type.as_str()];
+        self.database_query_duration_seconds_histogram
+            .with_label_values(&query_type)
+            .observe(statistics.duration.as_secs_f64());
+        self.database_query_rows_histogram
+            .with_label_values(&query_type)
+            .observe(statistics.rows as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus_parse::Value;
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn parse_metrics(raw_metrics: &str) -> StdResult<BTreeMap<String, Value>> {
+        Ok(
+            prometheus_parse::Scrape::parse(raw_metrics.lines().map(|s| Ok(s.to_owned())))?
+                .samples
+                .into_iter()
+                .map(|s| (s.metric, s.value))
+                .collect::<BTreeMap<_, _>>(),
+        )
+    }
+
+    #[test]
+    fn test_export_metrics() {
+        let metrics_service = MetricsService::new().unwrap();
+        let exported_metrics = metrics_service.export_metrics().unwrap();
+
+        let parsed_metrics = parse_metrics(&exported_metrics).unwrap();
+
+        let parsed_metrics_expected = BTreeMap::from([
+            (
+                PRUNED_OPEN_MESSAGES_SINCE_STARTUP_METRIC_NAME.to_string(),
+                Value::Counter(0.0),
+            ),
+            (
+                PRUNED_SINGLE_SIGNATURES_SINCE_STARTUP_METRIC_NAME.to_string(),
+                Value::Counter(0.0),
+            ),
+            (
+                PROOF_CACHE_HITS_SINCE_STARTUP_METRIC_NAME.to_string(),
+                Value::Counter(0.0),
+            ),
+            (
+                PROOF_CACHE_MISSES_SINCE_STARTUP_METRIC_NAME.to_string(),
+                Value::Counter(0.0),
+            ),
+            (
+                SNAPSHOT_UPLOADED_BYTES_SINCE_STARTUP_METRIC_NAME.to_string(),
+                Value::Counter(0.0),
+            ),
+            (
+                SNAPSHOT_UPLOAD_MILLISECONDS_SINCE_STARTUP_METRIC_NAME.to_string(),
+                Value::Counter(0.0),
+            ),
+        ]);
+        assert_eq!(parsed_metrics_expected, parsed_metrics);
+    }
+
+    #[test]
+    fn test_pruned_open_messages_since_startup_counter_increase() {
+        let metrics_service = MetricsService::new().unwrap();
+        assert_eq!(
+            0,
+            metrics_service.pruned_open_messages_since_startup_counter_get(),
+        );
+
+        metrics_service.pruned_open_messages_since_startup_counter_increase(3);
+        metrics_service.pruned_open_messages_since_startup_counter_increase(2);
+        assert_eq!(
+            5,
+            metrics_service.pruned_open_messages_since_startup_counter_get(),
+        );
+    }
+
+    #[test]
+    fn test_pruned_single_signatures_since_startup_counter_increase() {
+        let metrics_service = MetricsService::new().unwrap();
+        assert_eq!(
+            0,
+            metrics_service.pruned_single_signatures_since_startup_counter_get(),
+        );
+
+        metrics_service.pruned_single_signatures_since_startup_counter_increase(42);
+        assert_eq!(
+            42,
+            metrics_service.pruned_single_signatures_since_startup_counter_get(),
+        );
+    }
+
+    #[test]
+    fn test_database_query_statistics_are_exported_as_histograms() {
+        let metrics_service = MetricsService::new().unwrap();
+
+        metrics_service.on_query_done(&QueryStatistics {
+            query_type: "GetCertificateRecordProvider".to_string(),
+            duration: std::time::Duration::from_millis(3),
+            rows: 12,
+        });
+        let exported_metrics = metrics_service.export_metrics().unwrap();
+
+        assert!(exported_metrics.contains(&format!(
+            "{DATABASE_QUERY_DURATION_SECONDS_METRIC_NAME}_count{{query_type=\"GetCertificateRecordProvider\"}} 1"
+        )));
+        assert!(exported_metrics.contains(&format!(
+            "{DATABASE_QUERY_ROWS_METRIC_NAME}_sum{{query_type=\"GetCertificateRecordProvider\"}} 12"
+        )));
+    }
+
+    #[test]
+    fn test_proof_cache_hits_and_misses_since_startup_counters_increase() {
+        let metrics_service = MetricsService::new().unwrap();
+
+        metrics_service.proof_cache_hits_since_startup_counter_increase(2);
+        metrics_service.proof_cache_misses_since_startup_counter_increase(1);
+        assert_eq!(
+            2,
+            metrics_service.proof_cache_hits_since_startup_counter_get(),
+        );
+        assert_eq!(
+            1,
+            metrics_service.proof_cache_misses_since_startup_counter_get(),
+        );
+    }
+}
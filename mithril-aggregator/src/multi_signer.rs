@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
+use rayon::{ThreadPool, ThreadPoolBuilder};
 use slog_scope::{debug, warn};
 
 use mithril_common::{
@@ -18,9 +19,11 @@ use mockall::automock;
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait MultiSigner: Sync + Send {
-    /// Verify a single signature
+    /// Verify a single signature of the given signed entity type, with the signature scheme of
+    /// the era of its epoch
     async fn verify_single_signature(
         &self,
+        signed_entity_type: &entities::SignedEntityType,
         message: &entities::ProtocolMessage,
         signatures: &entities::SingleSignatures,
     ) -> StdResult<()>;
@@ -35,13 +38,32 @@ pub trait MultiSigner: Sync + Send {
 /// MultiSignerImpl is an implementation of the MultiSigner
 pub struct MultiSignerImpl {
     epoch_service: EpochServiceWrapper,
+    thread_pool: Option<ThreadPool>,
 }
 
 impl MultiSignerImpl {
     /// MultiSignerImpl factory
     pub fn new(epoch_service: EpochServiceWrapper) -> Self {
         debug!("New MultiSignerImpl created");
-        Self { epoch_service }
+        Self {
+            epoch_service,
+            thread_pool: None,
+        }
+    }
+
+    /// Set the number of threads used to verify the single signatures when creating a
+    /// multi-signature, if not set the global rayon thread pool is used.
+    pub fn with_thread_pool_size(mut self, number_of_threads: usize) -> StdResult<Self> {
+        let thread_pool = ThreadPoolBuilder::new()
+            .num_threads(number_of_threads)
+            .thread_name(|index| format!("multi-signer-{index}"))
+            .build()
+            .with_context(|| {
+                format!("Multi Signer can not build a thread pool of {number_of_threads} threads")
+            })?;
+        self.thread_pool = Some(thread_pool);
+
+        Ok(self)
     }
 }
 
@@ -50,6 +72,7 @@ impl MultiSigner for MultiSignerImpl {
     /// Verify a single signature
     async fn verify_single_signature(
         &self,
+        signed_entity_type: &entities::SignedEntityType,
         message: &entities::ProtocolMessage,
         single_signature: &entities::SingleSignatures,
     ) -> StdResult<()> {
@@ -59,9 +82,11 @@ impl MultiSigner for MultiSignerImpl {
         );
 
         let epoch_service = self.epoch_service.read().await;
-        let protocol_multi_signer = epoch_service.protocol_multi_signer().with_context(|| {
-            "Multi Signer could not get protocol multi-signer from epoch service"
-        })?;
+        let protocol_multi_signer = epoch_service
+            .protocol_multi_signer(signed_entity_type.get_epoch())
+            .with_context(|| {
+                "Multi Signer could not get protocol multi-signer from epoch service"
+            })?;
 
         protocol_multi_signer
             .verify_single_signature(message, single_signature)
@@ -78,14 +103,24 @@ impl MultiSigner for MultiSignerImpl {
         debug!("MultiSigner:create_multi_signature({open_message:?})");
 
         let epoch_service = self.epoch_service.read().await;
-        let protocol_multi_signer = epoch_service.protocol_multi_signer().with_context(|| {
-            "Multi Signer could not get protocol multi-signer from epoch service"
-        })?;
-
-        match protocol_multi_signer.aggregate_single_signatures(
-            &open_message.single_signatures,
-            &open_message.protocol_message,
-        ) {
+        let protocol_multi_signer = epoch_service
+            .protocol_multi_signer(open_message.signed_entity_type.get_epoch())
+            .with_context(|| {
+                "Multi Signer could not get protocol multi-signer from epoch service"
+            })?;
+
+        let aggregate = || {
+            protocol_multi_signer.aggregate_single_signatures(
+                &open_message.single_signatures,
+                &open_message.protocol_message,
+            )
+        };
+        let aggregation_result = match &self.thread_pool {
+            Some(thread_pool) => thread_pool.install(aggregate),
+            None => aggregate(),
+        };
+
+        match aggregation_result {
             Ok(multi_signature) => Ok(Some(multi_signature)),
             Err(ProtocolAggregationError::NotEnoughSignatures(actual, expected)) => {
                 warn!("Could not compute multi-signature: Not enough signatures. Got only {} out of {}.", actual, expected);
@@ -163,9 +198,13 @@ mod tests {
             }
         }
 
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon {
+            epoch,
+            ..fake_data::beacon()
+        });
         for signature in &signatures {
             multi_signer
-                .verify_single_signature(&message, signature)
+                .verify_single_signature(&signed_entity_type, &message, signature)
                 .await
                 .expect("single signature should be valid");
         }
@@ -181,10 +220,7 @@ mod tests {
 
         let mut open_message = OpenMessage {
             epoch,
-            signed_entity_type: SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon {
-                epoch,
-                ..fake_data::beacon()
-            }),
+            signed_entity_type,
             protocol_message: message.clone(),
             is_certified: false,
             single_signatures: Vec::new(),
@@ -210,6 +246,36 @@ mod tests {
         // Add the remaining signatures to reach the quorum: multi-signer should create a multi-signature
         open_message.single_signatures.append(&mut signatures);
 
+        assert!(
+            multi_signer
+                .create_multi_signature(&open_message)
+                .await
+                .expect("create multi signature should not fail")
+                .is_some(),
+            "no multi-signature were computed"
+        );
+    }
+    #[tokio::test]
+    async fn test_multi_signer_multi_signature_with_dedicated_thread_pool_ok() {
+        let epoch = Epoch(5);
+        let fixture = MithrilFixtureBuilder::default().with_signers(5).build();
+        let multi_signer = MultiSignerImpl::new(Arc::new(RwLock::new(
+            FakeEpochService::from_fixture(epoch, &fixture),
+        )))
+        .with_thread_pool_size(2)
+        .unwrap();
+
+        let message = setup_message();
+        let signatures = fixture.sign_all(&message);
+
+        let open_message = OpenMessage {
+            epoch,
+            signed_entity_type: SignedEntityType::MithrilStakeDistribution(epoch),
+            protocol_message: message.clone(),
+            single_signatures: signatures,
+            ..OpenMessage::dummy()
+        };
+
         assert!(
             multi_signer
                 .create_multi_signature(&open_message)
@@ -0,0 +1,572 @@
+use anyhow::anyhow;
+use slog_scope::{debug, info, warn};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use mithril_common::entities::{Certificate, SignedEntityType, SignedEntityTypeDiscriminants};
+use mithril_common::StdResult;
+
+use crate::runtime::AggregatorRunnerTrait;
+use crate::services::SignedEntityServiceError;
+
+/// Delay before retrying the build of an artifact already being created elsewhere
+const ARTIFACT_CREATION_IN_PROGRESS_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Maximum number of retries of the build of an artifact already being created elsewhere, they
+/// span the default duration of the lease on the creation of an artifact
+const ARTIFACT_CREATION_IN_PROGRESS_MAX_RETRIES: u32 = 60;
+
+/// Maximum number of builds, pending or failed, kept for a signed entity type, the oldest ones
+/// are dropped beyond it
+const MAX_ARTIFACT_BUILDS_PER_SIGNED_ENTITY_TYPE: usize = 16;
+
+/// Build of the artifact of a certified signed entity type
+#[derive(Debug, Clone)]
+struct ArtifactBuild {
+    signed_entity_type: SignedEntityType,
+    certificate: Certificate,
+}
+
+/// Builds of the artifacts of a signed entity type
+#[derive(Default)]
+struct ArtifactBuildQueue {
+    /// builds waiting to be started, in the order of their certificates
+    pending: VecDeque<ArtifactBuild>,
+
+    /// builds that failed, waiting to be retried
+    failed: Vec<ArtifactBuild>,
+
+    /// whether a task is building the pending artifacts
+    is_building: bool,
+
+    /// task building the pending artifacts, if not already awaited
+    worker: Option<JoinHandle<()>>,
+}
+
+impl ArtifactBuildQueue {
+    fn len(&self) -> usize {
+        self.pending.len() + self.failed.len()
+    }
+
+    /// Check if a build of the given signed entity type supersedes its previous builds: only
+    /// the artifact of the latest Cardano database snapshot is worth building.
+    fn is_superseding(discriminant: SignedEntityTypeDiscriminants) -> bool {
+        discriminant == SignedEntityTypeDiscriminants::CardanoImmutableFilesFull
+    }
+
+    /// Drop the builds superseded by a newer build of the given signed entity type.
+    fn drop_superseded_builds(&mut self, discriminant: SignedEntityTypeDiscriminants) {
+        if !Self::is_superseding(discriminant) {
+            return;
+        }
+        for build in self.pending.drain(..).chain(self.failed.drain(..)) {
+            info!(
+                "ARTIFACT BUILD POOL: dropping the build of a superseded artifact";
+                "signed_entity_type" => ?build.signed_entity_type
+            );
+        }
+    }
+
+    /// Keep a failed build to be retried, unless a newer build supersedes it.
+    fn push_failed_build(
+        &mut self,
+        discriminant: SignedEntityTypeDiscriminants,
+        build: ArtifactBuild,
+    ) {
+        if Self::is_superseding(discriminant) && !self.pending.is_empty() {
+            info!(
+                "ARTIFACT BUILD POOL: dropping the failed build of a superseded artifact";
+                "signed_entity_type" => ?build.signed_entity_type
+            );
+        } else {
+            self.failed.push(build);
+        }
+    }
+
+    /// Drop the oldest builds beyond the maximum number of builds kept for a signed entity type.
+    fn drop_oldest_builds(&mut self) {
+        while self.len() >= MAX_ARTIFACT_BUILDS_PER_SIGNED_ENTITY_TYPE {
+            let dropped_build = if self.failed.is_empty() {
+                self.pending.pop_front()
+            } else {
+                Some(self.failed.remove(0))
+            };
+            if let Some(build) = dropped_build {
+                warn!(
+                    "ARTIFACT BUILD POOL: too many artifact builds, dropping the oldest one";
+                    "signed_entity_type" => ?build.signed_entity_type,
+                    "certificate_hash" => &build.certificate.hash
+                );
+            }
+        }
+    }
+}
+
+type ArtifactBuildQueues = Arc<Mutex<BTreeMap<SignedEntityTypeDiscriminants, ArtifactBuildQueue>>>;
+
+/// The ArtifactBuildPool builds the artifacts of the certified signed entity types in the
+/// background, each signed entity type progressing in its own task.
+///
+/// The artifacts of a signed entity type are built in the order of their certificates, while a
+/// slow artifact build (ie: a Cardano database snapshot) never delays the certification nor the
+/// artifacts of the other signed entity types.
+///
+/// The failed builds are kept to be retried with [retry_failed_builds][Self::retry_failed_builds],
+/// and the builds left when the aggregator stops are awaited with [drain][Self::drain].
+pub struct ArtifactBuildPool {
+    runner: Arc<dyn AggregatorRunnerTrait>,
+    queues: ArtifactBuildQueues,
+    retry_delay: Duration,
+}
+
+impl ArtifactBuildPool {
+    /// ArtifactBuildPool factory
+    pub fn new(runner: Arc<dyn AggregatorRunnerTrait>) -> Self {
+        Self {
+            runner,
+            queues: Arc::new(Mutex::new(BTreeMap::new())),
+            retry_delay: ARTIFACT_CREATION_IN_PROGRESS_RETRY_DELAY,
+        }
+    }
+
+    #[cfg(test)]
+    fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Build the artifact, the build is retried while the artifact is being created by another
+    /// task or aggregator process
+    async fn build(
+        runner: &dyn AggregatorRunnerTrait,
+        signed_entity_type: &SignedEntityType,
+        certificate: &Certificate,
+        retry_delay: Duration,
+    ) -> StdResult<()> {
+        let mut remaining_retries = ARTIFACT_CREATION_IN_PROGRESS_MAX_RETRIES;
+        loop {
+            match runner
+                .create_artifact(signed_entity_type, certificate)
+                .await
+            {
+                Err(error)
+                    if remaining_retries > 0
+                        && matches!(
+                            error.downcast_ref::<SignedEntityServiceError>(),
+                            Some(SignedEntityServiceError::ArtifactCreationInProgress(_))
+                        ) =>
+                {
+                    debug!(
+                        "ARTIFACT BUILD POOL: artifact already being created, retrying later";
+                        "signed_entity_type" => ?signed_entity_type
+                    );
+                    remaining_retries -= 1;
+                    tokio::time::sleep(retry_delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Build the pending artifacts of a signed entity type until there are none left, the
+    /// failed builds are kept to be retried.
+    async fn build_pending_artifacts(
+        runner: Arc<dyn AggregatorRunnerTrait>,
+        queues: ArtifactBuildQueues,
+        discriminant: SignedEntityTypeDiscriminants,
+        retry_delay: Duration,
+    ) {
+        loop {
+            let build = {
+                let mut queues = queues.lock().unwrap();
+                let queue = queues.entry(discriminant).or_default();
+                match queue.pending.pop_front() {
+                    Some(build) => build,
+                    None => {
+                        queue.is_building = false;
+                        return;
+                    }
+                }
+            };
+
+            debug!("ARTIFACT BUILD POOL: building artifact"; "signed_entity_type" => ?build.signed_entity_type);
+            if let Err(error) = Self::build(
+                runner.as_ref(),
+                &build.signed_entity_type,
+                &build.certificate,
+                retry_delay,
+            )
+            .await
+            {
+                warn!(
+                    "ARTIFACT BUILD POOL: failed to build artifact, it will be retried";
+                    "signed_entity_type" => ?build.signed_entity_type,
+                    "certificate_hash" => &build.certificate.hash,
+                    "error" => ?error
+                );
+                queues
+                    .lock()
+                    .unwrap()
+                    .entry(discriminant)
+                    .or_default()
+                    .push_failed_build(discriminant, build);
+            }
+        }
+    }
+
+    /// Add builds to the queue of their signed entity type, and start a task building them if
+    /// there is none.
+    fn enqueue(&self, discriminant: SignedEntityTypeDiscriminants, builds: Vec<ArtifactBuild>) {
+        let mut queues = self.queues.lock().unwrap();
+        let queue = queues.entry(discriminant).or_default();
+        for build in builds {
+            queue.drop_superseded_builds(discriminant);
+            queue.drop_oldest_builds();
+            queue.pending.push_back(build);
+        }
+
+        if !queue.is_building && !queue.pending.is_empty() {
+            queue.is_building = true;
+            queue.worker = Some(tokio::spawn(Self::build_pending_artifacts(
+                self.runner.clone(),
+                self.queues.clone(),
+                discriminant,
+                self.retry_delay,
+            )));
+        }
+    }
+
+    /// Schedule the build of the artifact of a signed entity type, it starts once the builds
+    /// already scheduled for the same signed entity type are done.
+    ///
+    /// The pending builds of a superseded Cardano database snapshot are dropped.
+    pub fn schedule(&self, signed_entity_type: SignedEntityType, certificate: Certificate) {
+        let discriminant = SignedEntityTypeDiscriminants::from(&signed_entity_type);
+
+        self.enqueue(
+            discriminant,
+            vec![ArtifactBuild {
+                signed_entity_type,
+                certificate,
+            }],
+        );
+    }
+
+    /// Schedule again the builds that failed.
+    pub fn retry_failed_builds(&self) {
+        let failed_builds: Vec<_> = self
+            .queues
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .filter(|(_, queue)| !queue.failed.is_empty())
+            .map(|(discriminant, queue)| (*discriminant, std::mem::take(&mut queue.failed)))
+            .collect();
+
+        for (discriminant, builds) in failed_builds {
+            info!("ARTIFACT BUILD POOL: retrying the failed artifact builds"; "signed_entity_type" => ?discriminant, "builds" => builds.len());
+            self.enqueue(discriminant, builds);
+        }
+    }
+
+    /// Wait for all the scheduled builds to be done, fails if a build failed.
+    pub async fn wait_for_builds(&self) -> StdResult<()> {
+        loop {
+            let workers: Vec<_> = self
+                .queues
+                .lock()
+                .unwrap()
+                .values_mut()
+                .filter_map(|queue| queue.worker.take())
+                .collect();
+            if workers.is_empty() {
+                break;
+            }
+            for worker in workers {
+                worker
+                    .await
+                    .map_err(|e| anyhow!(e).context("artifact build task panicked"))?;
+            }
+        }
+
+        let failed_signed_entity_types: Vec<_> = self
+            .queues
+            .lock()
+            .unwrap()
+            .values()
+            .flat_map(|queue| &queue.failed)
+            .map(|build| build.signed_entity_type.clone())
+            .collect();
+        if failed_signed_entity_types.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "failed to build the artifacts of: {failed_signed_entity_types:?}"
+            ))
+        }
+    }
+
+    /// Wait, at most for the given duration, for the scheduled builds to be done before the
+    /// aggregator stops.
+    pub async fn drain(&self, timeout: Duration) {
+        info!("ARTIFACT BUILD POOL: waiting for the scheduled artifact builds to be done");
+        match tokio::time::timeout(timeout, self.wait_for_builds()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => {
+                warn!("ARTIFACT BUILD POOL: some artifacts could not be built"; "error" => ?error);
+            }
+            Err(_) => {
+                let remaining_builds: usize =
+                    self.queues.lock().unwrap().values().map(|q| q.len()).sum();
+                warn!(
+                    "ARTIFACT BUILD POOL: the artifact builds were not done in time";
+                    "timeout" => ?timeout, "remaining_builds" => remaining_builds
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use mithril_common::entities::{CardanoDbBeacon, Epoch};
+    use mithril_common::test_utils::fake_data;
+
+    use crate::runtime::runner::MockAggregatorRunner;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn build_the_artifacts_of_a_signed_entity_type_in_order() {
+        let built_artifacts = Arc::new(Mutex::new(vec![]));
+        let mut runner = MockAggregatorRunner::new();
+        let artifacts = built_artifacts.clone();
+        runner
+            .expect_create_artifact()
+            .times(3)
+            .returning(move |signed_entity_type, _| {
+                artifacts.lock().unwrap().push(signed_entity_type.clone());
+                Ok(())
+            });
+        let pool = ArtifactBuildPool::new(Arc::new(runner));
+
+        let signed_entity_types: Vec<_> = (1..=3)
+            .map(|epoch| SignedEntityType::MithrilStakeDistribution(Epoch(epoch)))
+            .collect();
+        for signed_entity_type in &signed_entity_types {
+            pool.schedule(
+                signed_entity_type.clone(),
+                fake_data::certificate("certificate_hash".to_string()),
+            );
+        }
+        pool.wait_for_builds().await.unwrap();
+
+        assert_eq!(signed_entity_types, *built_artifacts.lock().unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn a_slow_artifact_build_does_not_delay_the_other_signed_entity_types() {
+        let snapshot_signed_entity_type =
+            SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::default());
+        let stake_distribution_signed_entity_type =
+            SignedEntityType::MithrilStakeDistribution(Epoch(1));
+        let (stake_distribution_built_tx, stake_distribution_built_rx) = mpsc::channel();
+        let mut runner = MockAggregatorRunner::new();
+        runner
+            .expect_create_artifact()
+            .with(
+                predicate::eq(snapshot_signed_entity_type.clone()),
+                predicate::always(),
+            )
+            .return_once(move |_, _| {
+                stake_distribution_built_rx
+                    .recv_timeout(Duration::from_secs(5))
+                    .map_err(|_| anyhow!("the stake distribution artifact was not built first"))
+            });
+        runner
+            .expect_create_artifact()
+            .with(
+                predicate::eq(stake_distribution_signed_entity_type.clone()),
+                predicate::always(),
+            )
+            .return_once(move |_, _| {
+                stake_distribution_built_tx.send(()).unwrap();
+                Ok(())
+            });
+        let pool = ArtifactBuildPool::new(Arc::new(runner));
+
+        pool.schedule(
+            snapshot_signed_entity_type,
+            fake_data::certificate("snapshot_certificate_hash".to_string()),
+        );
+        pool.schedule(
+            stake_distribution_signed_entity_type,
+            fake_data::certificate("stake_distribution_certificate_hash".to_string()),
+        );
+
+        pool.wait_for_builds().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn retry_the_build_of_an_artifact_already_being_created() {
+        let signed_entity_type = SignedEntityType::MithrilStakeDistribution(Epoch(1));
+        let mut runner = MockAggregatorRunner::new();
+        let in_progress_signed_entity_type = signed_entity_type.clone();
+        runner
+            .expect_create_artifact()
+            .once()
+            .return_once(move |_, _| {
+                Err(SignedEntityServiceError::ArtifactCreationInProgress(
+                    in_progress_signed_entity_type,
+                )
+                .into())
+            });
+        runner
+            .expect_create_artifact()
+            .once()
+            .returning(|_, _| Ok(()));
+        let pool =
+            ArtifactBuildPool::new(Arc::new(runner)).with_retry_delay(Duration::from_millis(1));
+
+        pool.schedule(
+            signed_entity_type,
+            fake_data::certificate("certificate_hash".to_string()),
+        );
+
+        pool.wait_for_builds().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn wait_for_builds_fails_if_a_build_failed() {
+        let mut runner = MockAggregatorRunner::new();
+        runner
+            .expect_create_artifact()
+            .once()
+            .returning(|_, _| Err(anyhow!("artifact build error")));
+        let pool = ArtifactBuildPool::new(Arc::new(runner));
+
+        pool.schedule(
+            SignedEntityType::MithrilStakeDistribution(Epoch(1)),
+            fake_data::certificate("certificate_hash".to_string()),
+        );
+
+        pool.wait_for_builds()
+            .await
+            .expect_err("waiting for a failed build should fail");
+    }
+
+    fn recording_runner(
+        built_artifacts: Arc<Mutex<Vec<SignedEntityType>>>,
+    ) -> MockAggregatorRunner {
+        let mut runner = MockAggregatorRunner::new();
+        runner
+            .expect_create_artifact()
+            .returning(move |signed_entity_type, _| {
+                built_artifacts
+                    .lock()
+                    .unwrap()
+                    .push(signed_entity_type.clone());
+                Ok(())
+            });
+
+        runner
+    }
+
+    #[tokio::test]
+    async fn retry_the_failed_builds() {
+        let signed_entity_type = SignedEntityType::MithrilStakeDistribution(Epoch(1));
+        let mut runner = MockAggregatorRunner::new();
+        runner
+            .expect_create_artifact()
+            .once()
+            .returning(|_, _| Err(anyhow!("artifact build error")));
+        runner
+            .expect_create_artifact()
+            .once()
+            .returning(|_, _| Ok(()));
+        let pool = ArtifactBuildPool::new(Arc::new(runner));
+
+        pool.schedule(
+            signed_entity_type,
+            fake_data::certificate("certificate_hash".to_string()),
+        );
+        pool.wait_for_builds()
+            .await
+            .expect_err("waiting for a failed build should fail");
+
+        pool.retry_failed_builds();
+        pool.wait_for_builds().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn only_build_the_latest_of_the_pending_cardano_database_snapshots() {
+        let built_artifacts = Arc::new(Mutex::new(vec![]));
+        let pool = ArtifactBuildPool::new(Arc::new(recording_runner(built_artifacts.clone())));
+        let signed_entity_types: Vec<_> = (1..=3)
+            .map(|immutable_file_number| {
+                SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::new(
+                    "devnet".to_string(),
+                    1,
+                    immutable_file_number,
+                ))
+            })
+            .collect();
+
+        // The builds are scheduled before the task building them is started
+        for signed_entity_type in &signed_entity_types {
+            pool.schedule(
+                signed_entity_type.clone(),
+                fake_data::certificate("certificate_hash".to_string()),
+            );
+        }
+        pool.wait_for_builds().await.unwrap();
+
+        assert_eq!(
+            vec![signed_entity_types[2].clone()],
+            *built_artifacts.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn drop_the_oldest_builds_beyond_the_maximum_kept_for_a_signed_entity_type() {
+        let built_artifacts = Arc::new(Mutex::new(vec![]));
+        let pool = ArtifactBuildPool::new(Arc::new(recording_runner(built_artifacts.clone())));
+        let signed_entity_types: Vec<_> = (1..=MAX_ARTIFACT_BUILDS_PER_SIGNED_ENTITY_TYPE as u64
+            + 2)
+            .map(|epoch| SignedEntityType::MithrilStakeDistribution(Epoch(epoch)))
+            .collect();
+
+        for signed_entity_type in &signed_entity_types {
+            pool.schedule(
+                signed_entity_type.clone(),
+                fake_data::certificate("certificate_hash".to_string()),
+            );
+        }
+        pool.wait_for_builds().await.unwrap();
+
+        assert_eq!(
+            signed_entity_types[2..].to_vec(),
+            *built_artifacts.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn drain_waits_for_the_scheduled_builds() {
+        let built_artifacts = Arc::new(Mutex::new(vec![]));
+        let pool = ArtifactBuildPool::new(Arc::new(recording_runner(built_artifacts.clone())));
+
+        pool.schedule(
+            SignedEntityType::MithrilStakeDistribution(Epoch(1)),
+            fake_data::certificate("certificate_hash".to_string()),
+        );
+        pool.drain(Duration::from_secs(5)).await;
+
+        assert_eq!(1, built_artifacts.lock().unwrap().len());
+    }
+}
@@ -1,7 +1,11 @@
+mod artifact_build_pool;
 mod error;
 mod runner;
 mod state_machine;
+mod trigger;
 
+pub use artifact_build_pool::ArtifactBuildPool;
 pub use error::RuntimeError;
 pub use runner::{AggregatorConfig, AggregatorRunner, AggregatorRunnerTrait};
 pub use state_machine::*;
+pub use trigger::*;
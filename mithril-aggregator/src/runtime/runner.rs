@@ -1,16 +1,20 @@
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
-use slog_scope::{debug, warn};
-use std::sync::Arc;
+use chrono::Utc;
+use serde_json::json;
+use slog_scope::{debug, info, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError};
 use std::time::Duration;
 
 use mithril_common::entities::{
-    Certificate, CertificatePending, Epoch, ProtocolMessage, ProtocolMessagePartKey,
-    SignedEntityType, Signer, TimePoint,
+    Certificate, CertificatePending, Epoch, ProtocolFeature, ProtocolMessage, ProtocolMessagePart,
+    SignedCertificateMetadata, SignedEntityType, SignedEntityTypeDiscriminants, Signer, TimePoint,
 };
 use mithril_common::{CardanoNetwork, StdResult};
 use mithril_persistence::store::StakeStorer;
 
+use crate::database::record::RuntimeDecision;
 use crate::entities::OpenMessage;
 use crate::DependencyContainer;
 
@@ -53,6 +57,11 @@ pub trait AggregatorRunnerTrait: Sync + Send {
         current_time_point: &TimePoint,
     ) -> StdResult<Option<OpenMessage>>;
 
+    /// Raise an alert event when the latest snapshot becomes stale and, if configured, force a
+    /// new certification round for the snapshot of the given time point by deleting its open
+    /// message if it is not certified.
+    async fn check_stale_snapshot(&self, time_point: &TimePoint) -> StdResult<()>;
+
     /// Check if a certificate chain is valid.
     async fn is_certificate_chain_valid(&self, time_point: &TimePoint) -> StdResult<()>;
 
@@ -130,12 +139,180 @@ pub trait AggregatorRunnerTrait: Sync + Send {
 /// holds services and configuration.
 pub struct AggregatorRunner {
     dependencies: Arc<DependencyContainer>,
+    is_snapshot_stale: AtomicBool,
+    is_immutable_file_lagging: AtomicBool,
 }
 
 impl AggregatorRunner {
     /// Create a new instance of the Aggregator Runner.
     pub fn new(dependencies: Arc<DependencyContainer>) -> Self {
-        Self { dependencies }
+        Self {
+            dependencies,
+            is_snapshot_stale: AtomicBool::new(false),
+            is_immutable_file_lagging: AtomicBool::new(false),
+        }
+    }
+
+    /// Record the immutable file number of the given time point and raise an alert event when the
+    /// immutable file number stops progressing for longer than the configured threshold, or when
+    /// it progresses again.
+    async fn check_immutable_file_lag(&self, time_point: &TimePoint) {
+        let now = Utc::now();
+        let watchdog = &self.dependencies.immutable_file_lag_watchdog;
+        watchdog
+            .record_immutable_file_number(time_point.immutable_file_number, now)
+            .await;
+        let status = watchdog.get_status(now).await;
+        let was_lagging = self
+            .is_immutable_file_lagging
+            .swap(status.is_lagging, Ordering::Relaxed);
+        let action = match (was_lagging, status.is_lagging) {
+            (false, true) => {
+                warn!("RUNNER: the immutable file number of the Cardano node is lagging"; "status" => ?status);
+                "immutable_file_lag_detected"
+            }
+            (true, false) => {
+                info!("RUNNER: the immutable file number of the Cardano node progresses again"; "status" => ?status);
+                "immutable_file_lag_recovered"
+            }
+            _ => return,
+        };
+
+        let _ = self.dependencies.event_transmitter.send_event_message(
+            "Runtime::check_immutable_file_lag",
+            action,
+            &status,
+            vec![("epoch", time_point.epoch.to_string().as_str())],
+        );
+    }
+
+    /// Batch in the protocol message of the given signed entity type the protocol messages of
+    /// the other signed entity types that wait for a new certificate round, if the
+    /// [batched signed entity types][ProtocolFeature::BatchedSignedEntityTypes] feature is active.
+    ///
+    /// A signed entity type is skipped if its protocol message can not be computed or conflicts
+    /// with the parts already set: it will be certified in its own round.
+    async fn batch_protocol_messages(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        protocol_message: &mut ProtocolMessage,
+        other_signed_entity_types: &[SignedEntityType],
+    ) -> StdResult<()> {
+        {
+            let epoch_service = self.dependencies.epoch_service.read().await;
+            if !epoch_service.protocol_feature_activations().is_active(
+                ProtocolFeature::BatchedSignedEntityTypes,
+                epoch_service.epoch_of_current_data()?,
+            ) {
+                return Ok(());
+            }
+        }
+
+        let mut batched = vec![];
+        let mut merged_message = protocol_message.clone();
+        for other_signed_entity_type in other_signed_entity_types {
+            if self
+                .get_current_open_message_for_signed_entity_type(other_signed_entity_type)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            let other_protocol_message = match self
+                .compute_protocol_message(other_signed_entity_type)
+                .await
+            {
+                Ok(other_protocol_message) => other_protocol_message,
+                Err(error) => {
+                    debug!("RUNNER: can not batch signed entity type, its protocol message is unavailable"; "signed_entity_type" => ?other_signed_entity_type, "error" => ?error);
+                    continue;
+                }
+            };
+            let batch = [(other_signed_entity_type.clone(), other_protocol_message)];
+            if let Err(error) = merged_message.batch(&batch) {
+                debug!("RUNNER: can not batch signed entity type"; "signed_entity_type" => ?other_signed_entity_type, "error" => ?error);
+                continue;
+            }
+            batched.extend(batch);
+        }
+
+        protocol_message.batch(&batched)?;
+        for (batched_signed_entity_type, _) in batched {
+            self.record_runtime_decision(
+                &batched_signed_entity_type,
+                RuntimeDecision::BatchedInOpenMessage,
+                Some(signed_entity_type.to_string()),
+            )
+            .await;
+        }
+
+        Ok(())
+    }
+
+    /// Set in the given protocol message the metadata of the certificate that will certify it,
+    /// if the [signed certificate metadata][ProtocolFeature::SignedCertificateMetadata] feature
+    /// is active or mandatory in the era of the signed entity type.
+    async fn sign_certificate_metadata(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        protocol_message: &mut ProtocolMessage,
+    ) -> StdResult<()> {
+        let epoch_service = self.dependencies.epoch_service.read().await;
+        let signed_era = self
+            .dependencies
+            .era_checker
+            .era_of_epoch(signed_entity_type.get_epoch());
+        if !epoch_service
+            .protocol_feature_activations()
+            .is_active_in_era(
+                ProtocolFeature::SignedCertificateMetadata,
+                epoch_service.epoch_of_current_data()?,
+                signed_era,
+            )
+        {
+            return Ok(());
+        }
+
+        protocol_message.set_part(ProtocolMessagePart::SignedCertificateMetadata(
+            SignedCertificateMetadata {
+                network: self.dependencies.config.get_network()?.to_string(),
+                protocol_parameters: epoch_service.current_protocol_parameters()?.clone(),
+                initiated_at: Utc::now(),
+            },
+        ))
+    }
+
+    /// Check if the upload of the snapshot of the given signed entity type is deferred because
+    /// it's outside of the snapshot upload windows
+    fn is_snapshot_upload_deferred(&self, signed_entity_type: &SignedEntityType) -> bool {
+        matches!(
+            signed_entity_type,
+            SignedEntityType::CardanoImmutableFilesFull(_)
+        ) && !self
+            .dependencies
+            .snapshot_upload_schedule
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_open_at(Utc::now())
+    }
+
+    /// Record a decision of the runtime for audit purposes.
+    ///
+    /// A failure to record the decision is logged but must not prevent the runtime to proceed.
+    async fn record_runtime_decision(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        decision: RuntimeDecision,
+        reason: Option<String>,
+    ) {
+        if let Err(error) = self
+            .dependencies
+            .runtime_decision_storer
+            .record_decision(signed_entity_type, decision, reason)
+            .await
+        {
+            warn!("RUNNER: could not record runtime decision"; "signed_entity_type" => ?signed_entity_type, "decision" => %decision, "error" => ?error);
+        }
     }
 }
 
@@ -150,6 +327,7 @@ impl AggregatorRunnerTrait for AggregatorRunner {
             .time_point_provider
             .get_current_time_point()
             .await?;
+        self.check_immutable_file_lag(&time_point).await;
 
         Ok(time_point)
     }
@@ -175,30 +353,99 @@ impl AggregatorRunnerTrait for AggregatorRunner {
         current_time_point: &TimePoint,
     ) -> StdResult<Option<OpenMessage>> {
         debug!("RUNNER: get_current_non_certified_open_message"; "time_point" => #?current_time_point);
-        let signed_entity_types = self
+        // The allowed signed entity types can be reloaded at runtime
+        let config = self
             .dependencies
-            .config
+            .configuration_reloader
+            .reloaded_configuration(&self.dependencies.config);
+
+        for disabled_signed_entity_type in config
+            .list_disabled_signed_entity_types(current_time_point)
+            .with_context(|| {
+                "AggregatorRunner can not create the list of disabled signed entity types"
+            })?
+        {
+            self.record_runtime_decision(
+                &disabled_signed_entity_type,
+                RuntimeDecision::SignedEntityTypeDisabled,
+                None,
+            )
+            .await;
+        }
+        let signed_entity_types = config
             .list_allowed_signed_entity_types(current_time_point)
             .with_context(|| {
                 "AggregatorRunner can not create the list of allowed signed entity types"
             })?;
-        for signed_entity_type in signed_entity_types {
-            let current_open_message = self.get_current_open_message_for_signed_entity_type(&signed_entity_type)
+        for (index, signed_entity_type) in signed_entity_types.iter().enumerate() {
+            let current_open_message = self.get_current_open_message_for_signed_entity_type(signed_entity_type)
                 .await
                 .with_context(|| format!("AggregatorRunner can not get current open message for signed entity type: '{}'", &signed_entity_type))?;
             match current_open_message {
                 None => {
-                    let protocol_message = self.compute_protocol_message(&signed_entity_type).await.with_context(|| format!("AggregatorRunner can not compute protocol message for signed_entity_type: '{signed_entity_type}'"))?;
-                    let open_message_new = self.create_open_message(&signed_entity_type, &protocol_message)
+                    let mut protocol_message = match self
+                        .compute_protocol_message(signed_entity_type)
+                        .await
+                    {
+                        Ok(protocol_message) => protocol_message,
+                        Err(error) => {
+                            self.record_runtime_decision(
+                                signed_entity_type,
+                                RuntimeDecision::ProtocolMessageUnavailable,
+                                Some(format!("{error:?}")),
+                            )
+                            .await;
+                            return Err(error.context(format!("AggregatorRunner can not compute protocol message for signed_entity_type: '{signed_entity_type}'")));
+                        }
+                    };
+                    self.batch_protocol_messages(
+                        signed_entity_type,
+                        &mut protocol_message,
+                        &signed_entity_types[index + 1..],
+                    )
+                    .await
+                    .with_context(|| format!("AggregatorRunner can not batch protocol messages for signed_entity_type: '{signed_entity_type}'"))?;
+                    self.sign_certificate_metadata(signed_entity_type, &mut protocol_message)
+                        .await
+                        .with_context(|| format!("AggregatorRunner can not sign the certificate metadata for signed_entity_type: '{signed_entity_type}'"))?;
+                    let open_message_new = self.create_open_message(signed_entity_type, &protocol_message)
                         .await
                         .with_context(|| format!("AggregatorRunner can not create open message for signed_entity_type: '{signed_entity_type}'"))?;
+                    self.record_runtime_decision(
+                        signed_entity_type,
+                        RuntimeDecision::OpenMessageCreated,
+                        None,
+                    )
+                    .await;
+                    if self.is_snapshot_upload_deferred(signed_entity_type) {
+                        self.record_runtime_decision(
+                            signed_entity_type,
+                            RuntimeDecision::OutsideSnapshotUploadWindow,
+                            Some("The snapshot is certified but its upload is deferred to the next upload window".to_string()),
+                        )
+                        .await;
+                    }
 
                     return Ok(Some(open_message_new));
                 }
                 Some(open_message) => {
                     if !open_message.is_certified && !open_message.is_expired {
+                        self.record_runtime_decision(
+                            signed_entity_type,
+                            RuntimeDecision::OpenMessageInProgress,
+                            None,
+                        )
+                        .await;
                         return Ok(Some(open_message));
                     }
+
+                    let decision = if open_message.is_certified {
+                        RuntimeDecision::BeaconAlreadyCertified
+                    } else {
+                        RuntimeDecision::OpenMessageExpired
+                    };
+                    self.record_runtime_decision(signed_entity_type, decision, None)
+                        .await;
                 }
             }
         }
@@ -206,6 +453,59 @@ impl AggregatorRunnerTrait for AggregatorRunner {
         Ok(None)
     }
 
+    async fn check_stale_snapshot(&self, time_point: &TimePoint) -> StdResult<()> {
+        debug!("RUNNER: check_stale_snapshot"; "time_point" => #?time_point);
+        let status = self
+            .dependencies
+            .stale_snapshot_detector
+            .get_status(Utc::now())
+            .await
+            .with_context(|| "AggregatorRunner can not check if the latest snapshot is stale")?;
+        let was_stale = self
+            .is_snapshot_stale
+            .swap(status.is_stale, Ordering::Relaxed);
+        if !status.is_stale || was_stale {
+            return Ok(());
+        }
+
+        warn!("RUNNER: the latest snapshot is stale"; "status" => ?status);
+        let _ = self.dependencies.event_transmitter.send_event_message(
+            "Runtime::check_stale_snapshot",
+            "stale_snapshot_detected",
+            &status,
+            vec![("epoch", time_point.epoch.to_string().as_str())],
+        );
+
+        if !self.dependencies.config.stale_snapshot_force_certification {
+            return Ok(());
+        }
+        let signed_entity_type = SignedEntityType::from_time_point(
+            &SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+            &self.dependencies.config.network,
+            time_point,
+        );
+        let certifier_service = &self.dependencies.certifier_service;
+        match certifier_service
+            .get_open_message(&signed_entity_type)
+            .await
+            .with_context(|| format!("CertifierService can not get open message for signed_entity_type: '{signed_entity_type}'"))?
+        {
+            Some(open_message) if open_message.is_certified => {
+                info!("RUNNER: the snapshot of the current time point is already certified, no new certification round is forced"; "signed_entity_type" => ?signed_entity_type);
+            }
+            Some(_) => {
+                certifier_service
+                    .delete_open_message(&signed_entity_type)
+                    .await
+                    .with_context(|| format!("CertifierService can not delete open message for signed_entity_type: '{signed_entity_type}'"))?;
+                info!("RUNNER: forced a new certification round"; "signed_entity_type" => ?signed_entity_type);
+            }
+            None => {}
+        }
+
+        Ok(())
+    }
+
     async fn is_certificate_chain_valid(&self, time_point: &TimePoint) -> StdResult<()> {
         debug!("RUNNER: is_certificate_chain_valid");
         self.dependencies
@@ -236,10 +536,24 @@ impl AggregatorRunnerTrait for AggregatorRunner {
             .await?
             .unwrap_or_default();
 
+        let total_stake: u64 = stakes.values().sum();
+        let stakes_count = stakes.len();
         self.dependencies
             .signer_registration_round_opener
             .open_registration_round(registration_epoch, stakes)
-            .await
+            .await?;
+        let _ = self.dependencies.event_transmitter.send_event_message(
+            "Runtime::open_signer_registration_round",
+            "signer_registration_round_opened",
+            &json!({
+                "registration_epoch": registration_epoch,
+                "stake_distribution_size": stakes_count,
+                "total_stake": total_stake,
+            }),
+            vec![("epoch", new_time_point.epoch.to_string().as_str())],
+        );
+
+        Ok(())
     }
 
     async fn close_signer_registration_round(&self) -> StdResult<()> {
@@ -248,7 +562,15 @@ impl AggregatorRunnerTrait for AggregatorRunner {
         self.dependencies
             .signer_registration_round_opener
             .close_registration_round()
-            .await
+            .await?;
+        let _ = self.dependencies.event_transmitter.send_event_message(
+            "Runtime::close_signer_registration_round",
+            "signer_registration_round_closed",
+            &json!({}),
+            vec![],
+        );
+
+        Ok(())
     }
 
     async fn update_protocol_parameters(&self) -> StdResult<()> {
@@ -273,13 +595,13 @@ impl AggregatorRunnerTrait for AggregatorRunner {
             .with_context(|| format!("Runner can not compute protocol message for signed entity type: '{signed_entity_type}'"))?;
 
         let epoch_service = self.dependencies.epoch_service.read().await;
-        protocol_message.set_message_part(
-            ProtocolMessagePartKey::NextAggregateVerificationKey,
+        protocol_message.set_part(ProtocolMessagePart::NextAggregateVerificationKey(
             epoch_service
                 .next_aggregate_verification_key()?
                 .to_json_hex()
                 .with_context(|| "convert next avk to json hex failure")?,
-        );
+        ))?;
+        protocol_message.validate_for(signed_entity_type)?;
 
         Ok(protocol_message)
     }
@@ -371,7 +693,8 @@ impl AggregatorRunnerTrait for AggregatorRunner {
     ) -> StdResult<Option<Certificate>> {
         debug!("RUNNER: create_certificate");
 
-        self.dependencies
+        let certificate = self
+            .dependencies
             .certifier_service
             .create_certificate(signed_entity_type)
             .await
@@ -379,7 +702,21 @@ impl AggregatorRunnerTrait for AggregatorRunner {
                 format!(
                     "CertifierService can not create certificate for signed_entity_type: '{signed_entity_type}'"
                 )
-            })
+            })?;
+
+        if let Some(certificate) = &certificate {
+            let _ = self.dependencies.event_transmitter.send_event_message(
+                "Runtime::create_certificate",
+                "certificate_created",
+                &json!({
+                    "signed_entity_type": signed_entity_type,
+                    "certificate_hash": certificate.hash,
+                }),
+                vec![("epoch", certificate.epoch.to_string().as_str())],
+            );
+        }
+
+        Ok(certificate)
     }
 
     async fn create_artifact(
@@ -398,6 +735,15 @@ impl AggregatorRunnerTrait for AggregatorRunner {
                     certificate.hash
                 )
             })?;
+        let _ = self.dependencies.event_transmitter.send_event_message(
+            "Runtime::create_artifact",
+            "artifact_created",
+            &json!({
+                "signed_entity_type": signed_entity_type,
+                "certificate_hash": certificate.hash,
+            }),
+            vec![("epoch", certificate.epoch.to_string().as_str())],
+        );
 
         Ok(())
     }
@@ -418,18 +764,37 @@ impl AggregatorRunnerTrait for AggregatorRunner {
         let current_era = token
             .get_current_supported_era()
             .with_context(|| "EraEpochToken can not get current supported era")?;
-        self.dependencies
-            .era_checker
-            .change_era(current_era, token.get_current_epoch());
+        let transition_era = token.get_transition_supported_era();
+        self.dependencies.era_checker.change_era_with_transition(
+            current_era,
+            token.get_current_epoch(),
+            transition_era,
+        );
         debug!(
             "Current Era is {} (Epoch {}).",
             current_era,
             token.get_current_epoch()
         );
+        if let Some(transition_era) = transition_era {
+            info!("Era transition epoch: single signatures of the previous Era '{transition_era}' are still accepted (Epoch {}).", token.get_current_epoch());
+        }
 
-        if token.get_next_supported_era().is_err() {
-            let era_name = &token.get_next_era_marker().unwrap().name;
-            warn!("Upcoming Era '{era_name}' is not supported by this version of the software. Please update!");
+        match (
+            token.get_next_supported_era(),
+            token.get_next_era_marker().and_then(|marker| marker.epoch),
+        ) {
+            (Err(_), _) => {
+                let era_name = &token.get_next_era_marker().unwrap().name;
+                warn!("Upcoming Era '{era_name}' is not supported by this version of the software. Please update!");
+            }
+            // The signers registered ahead of the switch sign the epochs of the upcoming era
+            // with the verification keys they created with its signature scheme.
+            (Ok(Some(next_era)), Some(activation_epoch)) => {
+                self.dependencies
+                    .era_checker
+                    .set_upcoming_era(next_era, activation_epoch);
+            }
+            _ => {}
         }
 
         Ok(())
@@ -452,6 +817,12 @@ impl AggregatorRunnerTrait for AggregatorRunner {
             .inform_epoch(epoch)
             .await?;
 
+        self.dependencies
+            .pruning_service
+            .prune(epoch)
+            .await
+            .with_context(|| format!("PruningService can not prune data at epoch: '{epoch}'"))?;
+
         self.dependencies
             .epoch_service
             .write()
@@ -467,32 +838,53 @@ impl AggregatorRunnerTrait for AggregatorRunner {
         signed_entity_type: &SignedEntityType,
         protocol_message: &ProtocolMessage,
     ) -> StdResult<OpenMessage> {
-        self.dependencies
+        let open_message = self
+            .dependencies
             .certifier_service
             .create_open_message(signed_entity_type, protocol_message)
-            .await
+            .await?;
+        let _ = self.dependencies.event_transmitter.send_event_message(
+            "Runtime::create_open_message",
+            "open_message_created",
+            &json!({
+                "signed_entity_type": signed_entity_type,
+                "protocol_message": protocol_message,
+            }),
+            vec![("epoch", open_message.epoch.to_string().as_str())],
+        );
+
+        Ok(open_message)
     }
 }
 
 #[cfg(test)]
 pub mod tests {
-    use crate::services::FakeEpochService;
+    use crate::services::{FakeEpochService, MithrilEpochService};
     use crate::{
+        database::{record::RuntimeDecision, repository::SignerUnregistrationRepository},
         entities::OpenMessage,
+        event_store::{EventMessage, TransmitterService},
         initialize_dependencies,
         runtime::{AggregatorRunner, AggregatorRunnerTrait},
-        services::{MithrilStakeDistributionService, MockCertifierService},
-        DependencyContainer, MithrilSignerRegisterer, SignerRegistrationRound,
+        services::{
+            ImmutableFileLagStatus, MithrilStakeDistributionService, MockCertifierService,
+            MockImmutableFileLagWatchdog, MockPruningService, MockStaleSnapshotDetector,
+            PruningReport, StaleSnapshotStatus,
+        },
+        Configuration, DependencyContainer, MithrilSignerRegisterer, SignerRegistrationRound,
+        UploadSchedule,
     };
+    use anyhow::anyhow;
     use async_trait::async_trait;
     use chrono::{DateTime, Utc};
     use mithril_common::{
         chain_observer::FakeObserver,
         digesters::DumbImmutableFileObserver,
         entities::{
-            CertificatePending, ProtocolMessage, SignedEntityType, Signer, StakeDistribution,
-            TimePoint,
+            CertificatePending, ChainPoint, Epoch, ProtocolFeature, ProtocolFeatureActivations,
+            ProtocolMessage, SignedEntityType, Signer, StakeDistribution, TimePoint,
         },
+        era::{adapters::EraReaderDummyAdapter, EraMarker, EraReader, SupportedEra},
         signable_builder::SignableBuilderService,
         test_utils::{fake_data, MithrilFixtureBuilder},
         StdResult, TimePointProviderImpl,
@@ -500,6 +892,7 @@ pub mod tests {
     use mithril_persistence::store::StakeStorer;
     use mockall::predicate::eq;
     use mockall::{mock, Sequence};
+    use std::str::FromStr;
     use std::sync::Arc;
     use tokio::sync::RwLock;
 
@@ -538,6 +931,21 @@ pub mod tests {
         AggregatorRunner::new(Arc::new(deps))
     }
 
+    /// Protocol message with all the parts computed by the signable builders, so that it is
+    /// valid for every signed entity type once the next aggregate verification key is set
+    fn fake_signable_protocol_message() -> ProtocolMessage {
+        let mut protocol_message = ProtocolMessage::new();
+        for part in [
+            ProtocolMessagePart::SnapshotDigest("digest".to_string()),
+            ProtocolMessagePart::CardanoTransactionsMerkleRoot("mk-root".to_string()),
+            ProtocolMessagePart::LatestImmutableFileNumber(1),
+        ] {
+            protocol_message.set_part(part).unwrap();
+        }
+
+        protocol_message
+    }
+
     async fn build_runner(mock_certifier_service: MockCertifierService) -> AggregatorRunner {
         let mut deps = initialize_dependencies().await;
         deps.certifier_service = Arc::new(mock_certifier_service);
@@ -545,7 +953,7 @@ pub mod tests {
         let mut mock_signable_builder_service = MockSignableBuilderServiceImpl::new();
         mock_signable_builder_service
             .expect_compute_protocol_message()
-            .return_once(|_| Ok(ProtocolMessage::default()));
+            .return_once(|_| Ok(fake_signable_protocol_message()));
         deps.signable_builder_service = Arc::new(mock_signable_builder_service);
 
         let runner = build_runner_with_fixture_data(deps).await;
@@ -607,7 +1015,7 @@ pub mod tests {
 
     #[tokio::test]
     async fn test_get_time_point_from_chain() {
-        let expected = TimePoint::new(2, 17);
+        let expected = TimePoint::new(2, 17, ChainPoint::dummy());
         let mut dependencies = initialize_dependencies().await;
         let immutable_file_observer = Arc::new(DumbImmutableFileObserver::default());
         immutable_file_observer
@@ -670,6 +1078,9 @@ pub mod tests {
             deps.chain_observer.clone(),
             deps.verification_key_store.clone(),
             deps.signer_recorder.clone(),
+            Arc::new(SignerUnregistrationRepository::new(
+                deps.sqlite_connection.clone(),
+            )),
             None,
         ));
         deps.signer_registration_round_opener = signer_registration_round_opener.clone();
@@ -710,6 +1121,9 @@ pub mod tests {
             deps.chain_observer.clone(),
             deps.verification_key_store.clone(),
             deps.signer_recorder.clone(),
+            Arc::new(SignerUnregistrationRepository::new(
+                deps.sqlite_connection.clone(),
+            )),
             None,
         ));
         deps.signer_registration_round_opener = signer_registration_round_opener.clone();
@@ -865,6 +1279,25 @@ pub mod tests {
         assert_eq!(time_point.epoch, era_checker.current_epoch());
     }
 
+    #[tokio::test]
+    async fn update_era_checker_records_the_upcoming_era_and_its_activation_epoch() {
+        let mut deps = initialize_dependencies().await;
+        deps.era_reader = Arc::new(EraReader::new(Arc::new(
+            EraReaderDummyAdapter::from_markers(vec![
+                EraMarker::new(&SupportedEra::Thales.to_string(), Some(Epoch(1))),
+                EraMarker::new(&SupportedEra::Pythagoras.to_string(), Some(Epoch(7))),
+            ]),
+        )));
+        let era_checker = deps.era_checker.clone();
+        let runner = AggregatorRunner::new(Arc::new(deps));
+
+        runner.update_era_checker(Epoch(5)).await.unwrap();
+
+        assert_eq!(SupportedEra::Thales, era_checker.current_era());
+        assert_eq!(SupportedEra::Thales, era_checker.era_of_epoch(Epoch(6)));
+        assert_eq!(SupportedEra::Pythagoras, era_checker.era_of_epoch(Epoch(7)));
+    }
+
     #[tokio::test]
     async fn test_inform_new_epoch() {
         let mut mock_certifier_service = MockCertifierService::new();
@@ -880,8 +1313,15 @@ pub mod tests {
             .await
             .unwrap()
             .unwrap();
+        let mut mock_pruning_service = MockPruningService::new();
+        mock_pruning_service
+            .expect_prune()
+            .with(eq(current_epoch))
+            .returning(|_| Ok(PruningReport::default()))
+            .times(1);
 
         deps.certifier_service = Arc::new(mock_certifier_service);
+        deps.pruning_service = Arc::new(mock_pruning_service);
         deps.epoch_service = Arc::new(RwLock::new(FakeEpochService::from_fixture(
             current_epoch,
             &MithrilFixtureBuilder::default().build(),
@@ -1075,6 +1515,365 @@ pub mod tests {
         assert!(open_message_returned.is_none());
     }
 
+    #[tokio::test]
+    async fn test_get_current_non_certified_open_message_record_why_no_open_message_was_returned() {
+        let certified_and_not_expired = create_open_message(IsCertified::Yes, IsExpired::No);
+        let not_certified_and_expired = create_open_message(IsCertified::No, IsExpired::Yes);
+
+        let runner = {
+            let mut mock_certifier_service = MockCertifierService::new();
+            init_certifier_service_mock(
+                &mut mock_certifier_service,
+                vec![certified_and_not_expired, not_certified_and_expired],
+            );
+
+            mock_certifier_service.expect_create_open_message().never();
+            build_runner(mock_certifier_service).await
+        };
+
+        runner
+            .get_current_non_certified_open_message(&TimePoint::dummy())
+            .await
+            .unwrap();
+
+        let decisions: Vec<RuntimeDecision> = runner
+            .dependencies
+            .runtime_decision_storer
+            .get_decisions_since(Utc::now() - chrono::Duration::try_minutes(1).unwrap())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|record| record.decision)
+            .collect();
+        assert!(decisions.contains(&RuntimeDecision::BeaconAlreadyCertified));
+        assert!(decisions.contains(&RuntimeDecision::OpenMessageExpired));
+    }
+
+    fn closed_upload_windows() -> String {
+        let closed_window_start = Utc::now() + chrono::Duration::try_hours(2).unwrap();
+        let closed_window_end = closed_window_start + chrono::Duration::try_hours(1).unwrap();
+
+        format!(
+            "{}-{}",
+            closed_window_start.format("%H:%M"),
+            closed_window_end.format("%H:%M")
+        )
+    }
+
+    fn certifier_service_creating_the_snapshot_open_message() -> MockCertifierService {
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_get_open_message()
+            .with(eq(SignedEntityType::MithrilStakeDistribution(
+                TimePoint::dummy().epoch,
+            )))
+            .returning(|_| Ok(Some(create_open_message(IsCertified::Yes, IsExpired::No))));
+        mock_certifier_service
+            .expect_get_open_message()
+            .with(eq(SignedEntityType::CardanoImmutableFilesFull(
+                fake_data::beacon(),
+            )))
+            .times(1)
+            .return_once(|_| Ok(None));
+        mock_certifier_service
+            .expect_get_open_message()
+            .returning(|_| Ok(Some(create_open_message(IsCertified::No, IsExpired::No))));
+        mock_certifier_service
+            .expect_create_open_message()
+            .times(1)
+            .return_once(|_, _| Ok(create_open_message(IsCertified::No, IsExpired::No)));
+        mock_certifier_service
+            .expect_inform_epoch()
+            .returning(|_| Ok(()));
+        mock_certifier_service
+            .expect_mark_open_message_if_expired()
+            .returning(|_| Ok(None));
+
+        mock_certifier_service
+    }
+
+    async fn recorded_outside_snapshot_upload_window_decisions(runner: &AggregatorRunner) -> usize {
+        runner
+            .dependencies
+            .runtime_decision_storer
+            .get_decisions_since(Utc::now() - chrono::Duration::try_minutes(1).unwrap())
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|record| record.decision == RuntimeDecision::OutsideSnapshotUploadWindow)
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_get_current_non_certified_open_message_certify_the_snapshot_outside_of_the_upload_windows(
+    ) {
+        let mut deps = initialize_dependencies().await;
+        *deps.snapshot_upload_schedule.write().unwrap() =
+            UploadSchedule::from_str(&closed_upload_windows()).unwrap();
+        deps.certifier_service = Arc::new(certifier_service_creating_the_snapshot_open_message());
+        let runner = build_runner_with_fixture_data(deps).await;
+
+        let open_message_returned = runner
+            .get_current_non_certified_open_message(&TimePoint::dummy())
+            .await
+            .unwrap();
+        runner
+            .get_current_non_certified_open_message(&TimePoint::dummy())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            Some(create_open_message(IsCertified::No, IsExpired::No)),
+            open_message_returned
+        );
+        assert_eq!(
+            1,
+            recorded_outside_snapshot_upload_window_decisions(&runner).await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_current_non_certified_open_message_use_the_reloaded_upload_windows() {
+        let mut deps = initialize_dependencies().await;
+        deps.configuration_reloader
+            .reload(&Configuration {
+                snapshot_upload_windows: Some(closed_upload_windows()),
+                ..deps.config.clone()
+            })
+            .unwrap();
+        deps.certifier_service = Arc::new(certifier_service_creating_the_snapshot_open_message());
+        let runner = build_runner_with_fixture_data(deps).await;
+
+        runner
+            .get_current_non_certified_open_message(&TimePoint::dummy())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            1,
+            recorded_outside_snapshot_upload_window_decisions(&runner).await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_current_non_certified_open_message_record_no_upload_decision_inside_the_upload_windows(
+    ) {
+        let mut deps = initialize_dependencies().await;
+        deps.certifier_service = Arc::new(certifier_service_creating_the_snapshot_open_message());
+        let runner = build_runner_with_fixture_data(deps).await;
+
+        runner
+            .get_current_non_certified_open_message(&TimePoint::dummy())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            0,
+            recorded_outside_snapshot_upload_window_decisions(&runner).await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_current_non_certified_open_message_record_protocol_message_computation_failure(
+    ) {
+        let mut deps = initialize_dependencies().await;
+        let mut mock_certifier_service = MockCertifierService::new();
+        init_certifier_service_mock(&mut mock_certifier_service, vec![]);
+        mock_certifier_service.expect_create_open_message().never();
+        deps.certifier_service = Arc::new(mock_certifier_service);
+        let mut mock_signable_builder_service = MockSignableBuilderServiceImpl::new();
+        mock_signable_builder_service
+            .expect_compute_protocol_message()
+            .return_once(|_| Err(anyhow!("Missing stake distribution")));
+        deps.signable_builder_service = Arc::new(mock_signable_builder_service);
+        let runner = build_runner_with_fixture_data(deps).await;
+
+        runner
+            .get_current_non_certified_open_message(&TimePoint::dummy())
+            .await
+            .expect_err("get_current_non_certified_open_message should fail");
+
+        let decisions = runner
+            .dependencies
+            .runtime_decision_storer
+            .get_decisions_since(Utc::now() - chrono::Duration::try_minutes(1).unwrap())
+            .await
+            .unwrap();
+        let unavailable_decision = decisions
+            .iter()
+            .find(|record| record.decision == RuntimeDecision::ProtocolMessageUnavailable)
+            .expect("A 'protocol message unavailable' decision should have been recorded");
+        assert!(unavailable_decision
+            .reason
+            .as_ref()
+            .unwrap()
+            .contains("Missing stake distribution"));
+    }
+
+    #[tokio::test]
+    async fn test_get_current_non_certified_open_message_batch_the_signed_entity_types_waiting_for_a_round_when_feature_is_active(
+    ) {
+        let mut deps = initialize_dependencies().await;
+        deps.epoch_service = Arc::new(RwLock::new(
+            MithrilEpochService::new(
+                deps.config.protocol_parameters.clone(),
+                deps.protocol_parameters_store.clone(),
+                deps.verification_key_store.clone(),
+                deps.era_checker.clone(),
+            )
+            .with_protocol_feature_activations(
+                ProtocolFeatureActivations::new()
+                    .with_activation(ProtocolFeature::BatchedSignedEntityTypes, Epoch(0)),
+            ),
+        ));
+        let allowed_signed_entity_types = deps
+            .config
+            .list_allowed_signed_entity_types(&TimePoint::dummy())
+            .unwrap();
+        let expected_batched_signed_entity_types = allowed_signed_entity_types[1..].to_vec();
+        let open_message_signed_entity_type = allowed_signed_entity_types[0].clone();
+        let mut mock_certifier_service = MockCertifierService::new();
+        init_certifier_service_mock(&mut mock_certifier_service, vec![]);
+        mock_certifier_service
+            .expect_create_open_message()
+            .withf(move |signed_entity_type, protocol_message| {
+                signed_entity_type == &allowed_signed_entity_types[0]
+                    && protocol_message.get_batched_signed_entity_types().unwrap()
+                        == expected_batched_signed_entity_types
+            })
+            .return_once(|_, _| Ok(OpenMessage::dummy()))
+            .times(1);
+        deps.certifier_service = Arc::new(mock_certifier_service);
+        let mut mock_signable_builder_service = MockSignableBuilderServiceImpl::new();
+        mock_signable_builder_service
+            .expect_compute_protocol_message()
+            .returning(|_| Ok(fake_signable_protocol_message()));
+        deps.signable_builder_service = Arc::new(mock_signable_builder_service);
+        let runner = build_runner_with_fixture_data(deps).await;
+        let current_epoch = runner
+            .dependencies
+            .ticker_service
+            .get_current_epoch()
+            .await
+            .unwrap();
+        runner.inform_new_epoch(current_epoch).await.unwrap();
+        runner.precompute_epoch_data().await.unwrap();
+
+        runner
+            .get_current_non_certified_open_message(&TimePoint::dummy())
+            .await
+            .unwrap();
+
+        let decisions = runner
+            .dependencies
+            .runtime_decision_storer
+            .get_decisions_since(Utc::now() - chrono::Duration::try_minutes(1).unwrap())
+            .await
+            .unwrap();
+        let batched_decisions = decisions
+            .iter()
+            .filter(|record| record.decision == RuntimeDecision::BatchedInOpenMessage)
+            .collect::<Vec<_>>();
+        assert!(!batched_decisions.is_empty());
+        for record in batched_decisions {
+            assert_eq!(
+                Some(open_message_signed_entity_type.clone()),
+                record.batched_in().unwrap()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_current_non_certified_open_message_sign_the_certificate_metadata_when_feature_is_active(
+    ) {
+        let mut deps = initialize_dependencies().await;
+        deps.epoch_service = Arc::new(RwLock::new(
+            MithrilEpochService::new(
+                deps.config.protocol_parameters.clone(),
+                deps.protocol_parameters_store.clone(),
+                deps.verification_key_store.clone(),
+                deps.era_checker.clone(),
+            )
+            .with_protocol_feature_activations(
+                ProtocolFeatureActivations::new()
+                    .with_activation(ProtocolFeature::SignedCertificateMetadata, Epoch(0)),
+            ),
+        ));
+        let expected_network = deps.config.get_network().unwrap().to_string();
+        let mut mock_certifier_service = MockCertifierService::new();
+        init_certifier_service_mock(&mut mock_certifier_service, vec![]);
+        mock_certifier_service
+            .expect_create_open_message()
+            .withf(move |_, protocol_message| {
+                protocol_message
+                    .get_signed_certificate_metadata()
+                    .unwrap()
+                    .is_some_and(|metadata| metadata.network == expected_network)
+            })
+            .return_once(|_, _| Ok(OpenMessage::dummy()))
+            .times(1);
+        deps.certifier_service = Arc::new(mock_certifier_service);
+        let mut mock_signable_builder_service = MockSignableBuilderServiceImpl::new();
+        mock_signable_builder_service
+            .expect_compute_protocol_message()
+            .returning(|_| Ok(fake_signable_protocol_message()));
+        deps.signable_builder_service = Arc::new(mock_signable_builder_service);
+        let runner = build_runner_with_fixture_data(deps).await;
+        let current_epoch = runner
+            .dependencies
+            .ticker_service
+            .get_current_epoch()
+            .await
+            .unwrap();
+        runner.inform_new_epoch(current_epoch).await.unwrap();
+        runner.precompute_epoch_data().await.unwrap();
+
+        runner
+            .get_current_non_certified_open_message(&TimePoint::dummy())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_current_non_certified_open_message_sign_the_certificate_metadata_in_the_era_where_it_is_mandatory(
+    ) {
+        let mut deps = initialize_dependencies().await;
+        deps.era_checker
+            .change_era(SupportedEra::Pythagoras, Epoch(0));
+        let mut mock_certifier_service = MockCertifierService::new();
+        init_certifier_service_mock(&mut mock_certifier_service, vec![]);
+        mock_certifier_service
+            .expect_create_open_message()
+            .withf(|_, protocol_message| {
+                protocol_message
+                    .get_signed_certificate_metadata()
+                    .unwrap()
+                    .is_some()
+            })
+            .return_once(|_, _| Ok(OpenMessage::dummy()))
+            .times(1);
+        deps.certifier_service = Arc::new(mock_certifier_service);
+        let mut mock_signable_builder_service = MockSignableBuilderServiceImpl::new();
+        mock_signable_builder_service
+            .expect_compute_protocol_message()
+            .returning(|_| Ok(fake_signable_protocol_message()));
+        deps.signable_builder_service = Arc::new(mock_signable_builder_service);
+        let runner = build_runner_with_fixture_data(deps).await;
+        let current_epoch = runner
+            .dependencies
+            .ticker_service
+            .get_current_epoch()
+            .await
+            .unwrap();
+        runner.inform_new_epoch(current_epoch).await.unwrap();
+        runner.precompute_epoch_data().await.unwrap();
+
+        runner
+            .get_current_non_certified_open_message(&TimePoint::dummy())
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn test_get_current_non_certified_open_message_should_return_first_not_certified_and_not_expired_open_message(
     ) {
@@ -1142,4 +1941,140 @@ pub mod tests {
             .await
             .unwrap();
     }
+
+    fn stale_snapshot_detector(is_stale: bool) -> MockStaleSnapshotDetector {
+        let mut stale_snapshot_detector = MockStaleSnapshotDetector::new();
+        stale_snapshot_detector
+            .expect_get_status()
+            .returning(move |_| {
+                Ok(StaleSnapshotStatus {
+                    is_stale,
+                    ..StaleSnapshotStatus::default()
+                })
+            });
+
+        stale_snapshot_detector
+    }
+
+    #[tokio::test]
+    async fn test_check_stale_snapshot_force_a_new_certification_round_once_when_enabled() {
+        let time_point = TimePoint::dummy();
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_get_open_message()
+            .returning(|_| {
+                Ok(Some(OpenMessage {
+                    is_certified: false,
+                    ..OpenMessage::dummy()
+                }))
+            })
+            .times(1);
+        mock_certifier_service
+            .expect_delete_open_message()
+            .with(eq(SignedEntityType::CardanoImmutableFilesFull(
+                fake_data::beacon(),
+            )))
+            .returning(|_| Ok(Some(OpenMessage::dummy())))
+            .times(1);
+
+        let mut deps = initialize_dependencies().await;
+        deps.config.network = fake_data::beacon().network;
+        deps.config.stale_snapshot_force_certification = true;
+        deps.certifier_service = Arc::new(mock_certifier_service);
+        deps.stale_snapshot_detector = Arc::new(stale_snapshot_detector(true));
+        let runner = AggregatorRunner::new(Arc::new(deps));
+
+        runner.check_stale_snapshot(&time_point).await.unwrap();
+        runner.check_stale_snapshot(&time_point).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_stale_snapshot_does_not_delete_a_certified_open_message() {
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service
+            .expect_get_open_message()
+            .returning(|_| {
+                Ok(Some(OpenMessage {
+                    is_certified: true,
+                    ..OpenMessage::dummy()
+                }))
+            })
+            .times(1);
+        mock_certifier_service.expect_delete_open_message().never();
+
+        let mut deps = initialize_dependencies().await;
+        deps.config.stale_snapshot_force_certification = true;
+        deps.certifier_service = Arc::new(mock_certifier_service);
+        deps.stale_snapshot_detector = Arc::new(stale_snapshot_detector(true));
+        let runner = AggregatorRunner::new(Arc::new(deps));
+
+        runner
+            .check_stale_snapshot(&TimePoint::dummy())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_stale_snapshot_does_not_force_a_new_certification_round_when_disabled() {
+        let mut mock_certifier_service = MockCertifierService::new();
+        mock_certifier_service.expect_delete_open_message().never();
+
+        let mut deps = initialize_dependencies().await;
+        deps.config.stale_snapshot_force_certification = false;
+        deps.certifier_service = Arc::new(mock_certifier_service);
+        deps.stale_snapshot_detector = Arc::new(stale_snapshot_detector(true));
+        let runner = AggregatorRunner::new(Arc::new(deps));
+
+        runner
+            .check_stale_snapshot(&TimePoint::dummy())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_immutable_file_lag_raise_an_alert_event_once_when_lagging_and_on_recovery()
+    {
+        let time_point = TimePoint::dummy();
+        let mut mock_immutable_file_lag_watchdog = MockImmutableFileLagWatchdog::new();
+        mock_immutable_file_lag_watchdog
+            .expect_record_immutable_file_number()
+            .with(
+                eq(time_point.immutable_file_number),
+                mockall::predicate::always(),
+            )
+            .returning(|_, _| ())
+            .times(3);
+        let mut sequence = Sequence::new();
+        for is_lagging in [true, true, false] {
+            mock_immutable_file_lag_watchdog
+                .expect_get_status()
+                .returning(move |_| ImmutableFileLagStatus {
+                    is_lagging,
+                    ..ImmutableFileLagStatus::default()
+                })
+                .times(1)
+                .in_sequence(&mut sequence);
+        }
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<EventMessage>();
+
+        let mut deps = initialize_dependencies().await;
+        deps.immutable_file_lag_watchdog = Arc::new(mock_immutable_file_lag_watchdog);
+        deps.event_transmitter = Arc::new(TransmitterService::new(tx));
+        let runner = AggregatorRunner::new(Arc::new(deps));
+
+        for _ in 0..3 {
+            runner.check_immutable_file_lag(&time_point).await;
+        }
+
+        let actions: Vec<String> = std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|message| message.action)
+            .collect();
+        assert_eq!(
+            vec![
+                "immutable_file_lag_detected".to_string(),
+                "immutable_file_lag_recovered".to_string()
+            ],
+            actions
+        );
+    }
 }
@@ -1,15 +1,15 @@
 use crate::{
     entities::OpenMessage,
-    runtime::{AggregatorRunnerTrait, RuntimeError},
+    runtime::{AggregatorRunnerTrait, ArtifactBuildPool, RuntimeCycleTrigger, RuntimeError},
     AggregatorConfig,
 };
 
 use anyhow::Context;
 use mithril_common::entities::{SignedEntityType, TimePoint};
+use mithril_common::StdResult;
 use slog_scope::{crit, info, trace, warn};
 use std::fmt::Display;
 use std::sync::Arc;
-use tokio::time::sleep;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct IdleState {
@@ -65,6 +65,12 @@ pub struct AggregatorRuntime {
 
     /// specific runner for this state machine
     runner: Arc<dyn AggregatorRunnerTrait>,
+
+    /// trigger of the cycles on demand
+    cycle_trigger: Arc<RuntimeCycleTrigger>,
+
+    /// builds the artifacts of the certified signed entity types in the background
+    artifact_build_pool: Arc<ArtifactBuildPool>,
 }
 
 impl AggregatorRuntime {
@@ -89,10 +95,18 @@ impl AggregatorRuntime {
         Ok(Self {
             config: aggregator_config,
             state,
+            artifact_build_pool: Arc::new(ArtifactBuildPool::new(runner.clone())),
             runner,
+            cycle_trigger: Arc::new(RuntimeCycleTrigger::new()),
         })
     }
 
+    /// Set the trigger of the cycles on demand, shared with the admin routes.
+    pub fn with_cycle_trigger(mut self, cycle_trigger: Arc<RuntimeCycleTrigger>) -> Self {
+        self.cycle_trigger = cycle_trigger;
+        self
+    }
+
     /// Return the actual state of the state machine.
     pub fn get_state(&self) -> String {
         match self.state {
@@ -102,6 +116,17 @@ impl AggregatorRuntime {
         }
     }
 
+    /// Return the pool building the artifacts in the background, to drain it when the
+    /// aggregator stops.
+    pub fn artifact_build_pool(&self) -> Arc<ArtifactBuildPool> {
+        self.artifact_build_pool.clone()
+    }
+
+    /// Wait for the artifacts builds scheduled in the background to be done.
+    pub async fn wait_for_artifact_builds(&self) -> StdResult<()> {
+        self.artifact_build_pool.wait_for_builds().await
+    }
+
     /// Launches an infinite loop ticking the state machine.
     pub async fn run(&mut self) -> Result<(), RuntimeError> {
         info!("STATE MACHINE: launching");
@@ -149,11 +174,14 @@ impl AggregatorRuntime {
                 }
             }
 
+            self.cycle_trigger.set_state(self.get_state());
             info!(
                 "… Cycle finished, Sleeping for {} ms",
                 self.config.interval.as_millis()
             );
-            sleep(self.config.interval).await;
+            if let Some(step) = self.cycle_trigger.wait(self.config.interval).await {
+                info!("STATE MACHINE: cycle triggered on demand"; "step" => ?step);
+            }
         }
     }
 
@@ -161,6 +189,7 @@ impl AggregatorRuntime {
     pub async fn cycle(&mut self) -> Result<(), RuntimeError> {
         info!("================================================================================");
         info!("STATE MACHINE: new cycle: {}", self.state);
+        self.artifact_build_pool.retry_failed_builds();
 
         match self.state.clone() {
             AggregatorState::Idle(state) => {
@@ -188,6 +217,14 @@ impl AggregatorRuntime {
                         "AggregatorRuntime in the state READY can not get current time point from chain"
                     })?;
 
+                if state.current_time_point.epoch == last_time_point.epoch {
+                    // READY > READY: a stale snapshot can force a new certification round, it
+                    // must be done before looking for the open message to certify
+                    if let Err(error) = self.runner.check_stale_snapshot(&last_time_point).await {
+                        warn!("STATE MACHINE: could not check if the latest snapshot is stale"; "error" => ?error);
+                    }
+                }
+
                 if state.current_time_point.epoch < last_time_point.epoch {
                     // transition READY > IDLE
                     info!("→ Epoch has changed, transitioning to IDLE"; "last_time_point" => ?last_time_point);
@@ -329,14 +366,18 @@ impl AggregatorRuntime {
                     .to_string(),
                 nested_error: Some(e),
             })?;
-        self.runner
-            .create_artifact(&state.open_message.signed_entity_type, &certificate)
-            .await
-            .map_err(|e| RuntimeError::ReInit {
-                message: "transiting SIGNING → READY: failed to create artifact. Retrying…"
-                    .to_string(),
-                nested_error: Some(e),
-            })?;
+        let batched_signed_entity_types = state
+            .open_message
+            .protocol_message
+            .get_batched_signed_entity_types()?;
+        // The artifacts are built in the background so the certification of the other signed
+        // entity types does not wait for them
+        for signed_entity_type in std::iter::once(state.open_message.signed_entity_type)
+            .chain(batched_signed_entity_types)
+        {
+            self.artifact_build_pool
+                .schedule(signed_entity_type, certificate.clone());
+        }
 
         Ok(ReadyState {
             current_time_point: state.current_time_point,
@@ -386,7 +427,18 @@ impl AggregatorRuntime {
                 new_time_point.clone(),
                 &open_message.signed_entity_type,
             )
-            .await?;
+            .await?
+            .with_batched_signed_entity_types(
+                open_message
+                    .protocol_message
+                    .get_batched_signed_entity_types()?,
+            )
+            .with_initiated_at(
+                open_message
+                    .protocol_message
+                    .get_signed_certificate_metadata()?
+                    .map(|metadata| metadata.initiated_at),
+            );
         self.runner
             .save_pending_certificate(certificate_pending.clone())
             .await?;
@@ -406,7 +458,7 @@ mod tests {
     use mockall::predicate;
     use std::time::Duration;
 
-    use mithril_common::entities::{Epoch, SignedEntityType};
+    use mithril_common::entities::{Epoch, ProtocolMessage, SignedEntityType};
     use mithril_common::test_utils::fake_data;
 
     use super::super::runner::MockAggregatorRunner;
@@ -573,6 +625,10 @@ mod tests {
             .expect_get_time_point_from_chain()
             .once()
             .returning(move || Ok(next_time_point.clone()));
+        runner
+            .expect_check_stale_snapshot()
+            .once()
+            .returning(|_| Ok(()));
         runner
             .expect_get_current_non_certified_open_message()
             .once()
@@ -602,6 +658,10 @@ mod tests {
             .expect_get_time_point_from_chain()
             .once()
             .returning(|| Ok(TimePoint::dummy()));
+        runner
+            .expect_check_stale_snapshot()
+            .once()
+            .returning(|_| Ok(()));
         runner
             .expect_get_current_non_certified_open_message()
             .once()
@@ -705,7 +765,7 @@ mod tests {
         let mut runner = MockAggregatorRunner::new();
         runner
             .expect_get_time_point_from_chain()
-            .once()
+            .times(2)
             .returning(|| Ok(TimePoint::dummy()));
         runner
             .expect_get_current_open_message_for_signed_entity_type()
@@ -722,22 +782,35 @@ mod tests {
             .expect_create_artifact()
             .once()
             .returning(|_, _| Err(anyhow!("whatever")));
+        runner
+            .expect_create_artifact()
+            .once()
+            .returning(|_, _| Ok(()));
+        runner
+            .expect_check_stale_snapshot()
+            .once()
+            .returning(|_| Ok(()));
+        runner
+            .expect_get_current_non_certified_open_message()
+            .once()
+            .returning(|_| Ok(None));
         let state = SigningState {
             current_time_point: TimePoint::dummy(),
             open_message: OpenMessage::dummy(),
         };
         let mut runtime = init_runtime(Some(AggregatorState::Signing(state)), runner).await;
-        let err = runtime
-            .cycle()
-            .await
-            .expect_err("cycle should have returned an error");
+        runtime.cycle().await.unwrap();
 
-        match err {
-            RuntimeError::ReInit { .. } => (),
-            _ => panic!("ReInit error expected, got {err:?}."),
-        };
+        // The certification does not wait for the artifact
+        assert_eq!("ready".to_string(), runtime.get_state());
+        runtime
+            .wait_for_artifact_builds()
+            .await
+            .expect_err("the artifact build should have failed");
 
-        assert_eq!("signing".to_string(), runtime.get_state());
+        // The failed artifact build is retried at the next cycle
+        runtime.cycle().await.unwrap();
+        runtime.wait_for_artifact_builds().await.unwrap();
     }
 
     #[tokio::test]
@@ -769,6 +842,65 @@ mod tests {
         };
         let mut runtime = init_runtime(Some(AggregatorState::Signing(state)), runner).await;
         runtime.cycle().await.unwrap();
+        runtime.wait_for_artifact_builds().await.unwrap();
+
+        assert_eq!("ready".to_string(), runtime.get_state());
+    }
+
+    #[tokio::test]
+    async fn signing_certificate_is_created_with_batched_signed_entity_types() {
+        let open_message = {
+            let mut open_message = OpenMessage::dummy();
+            open_message
+                .protocol_message
+                .batch(&[(
+                    SignedEntityType::MithrilStakeDistribution(open_message.epoch),
+                    ProtocolMessage::new(),
+                )])
+                .unwrap();
+            open_message
+        };
+        let signed_entity_type = open_message.signed_entity_type.clone();
+        let batched_signed_entity_type =
+            SignedEntityType::MithrilStakeDistribution(open_message.epoch);
+        let mut runner = MockAggregatorRunner::new();
+        runner
+            .expect_get_time_point_from_chain()
+            .once()
+            .returning(|| Ok(TimePoint::dummy()));
+        let current_open_message = open_message.clone();
+        runner
+            .expect_get_current_open_message_for_signed_entity_type()
+            .once()
+            .returning(move |_| Ok(Some(current_open_message.clone())));
+        runner
+            .expect_create_certificate()
+            .return_once(move |_| Ok(Some(fake_data::certificate("whatever".to_string()))));
+        runner
+            .expect_drop_pending_certificate()
+            .once()
+            .returning(|| Ok(Some(fake_data::certificate_pending())));
+        runner
+            .expect_create_artifact()
+            .with(predicate::eq(signed_entity_type), predicate::always())
+            .once()
+            .returning(|_, _| Ok(()));
+        runner
+            .expect_create_artifact()
+            .with(
+                predicate::eq(batched_signed_entity_type),
+                predicate::always(),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let state = SigningState {
+            current_time_point: TimePoint::dummy(),
+            open_message,
+        };
+        let mut runtime = init_runtime(Some(AggregatorState::Signing(state)), runner).await;
+        runtime.cycle().await.unwrap();
+        runtime.wait_for_artifact_builds().await.unwrap();
 
         assert_eq!("ready".to_string(), runtime.get_state());
     }
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::Notify;
+
+/// Step of the state machine that can be triggered on demand
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuntimeStep {
+    /// A cycle of the state machine, whatever its state
+    #[default]
+    Cycle,
+
+    /// The cycle of the `ready` state, that creates the open message of the next signed entity
+    /// type to certify
+    CreateOpenMessage,
+
+    /// The cycle of the `signing` state, that builds the certificate and the artifact of the
+    /// current open message once it's signed
+    BuildArtifact,
+}
+
+impl RuntimeStep {
+    /// Check if the step is performed by a cycle in the given state of the state machine
+    pub fn is_performed_in(&self, state: &str) -> bool {
+        match self {
+            RuntimeStep::Cycle => true,
+            RuntimeStep::CreateOpenMessage => state == "ready",
+            RuntimeStep::BuildArtifact => state == "signing",
+        }
+    }
+}
+
+/// Error returned when a cycle of the state machine can not be triggered
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum RuntimeTriggerError {
+    /// A cycle is already triggered and has not started yet
+    #[error("A runtime cycle is already triggered for step '{0:?}'")]
+    AlreadyTriggered(RuntimeStep),
+
+    /// The step is not performed in the current state of the state machine
+    #[error("The step '{step:?}' can not be performed in the state '{state}' of the runtime")]
+    UnexpectedState {
+        /// Requested step
+        step: RuntimeStep,
+        /// Current state of the state machine
+        state: String,
+    },
+}
+
+/// Trigger an immediate cycle of the state machine instead of waiting for the end of its run
+/// interval.
+///
+/// The cycle is run by the loop of the state machine itself, so it never runs concurrently with
+/// another cycle, and a single trigger can be pending at a time.
+pub struct RuntimeCycleTrigger {
+    notify: Notify,
+    pending_step: Mutex<Option<RuntimeStep>>,
+    state: RwLock<String>,
+}
+
+impl RuntimeCycleTrigger {
+    /// RuntimeCycleTrigger factory
+    pub fn new() -> Self {
+        Self {
+            notify: Notify::new(),
+            pending_step: Mutex::new(None),
+            state: RwLock::new("idle".to_string()),
+        }
+    }
+
+    /// Request an immediate cycle to perform the given step
+    pub fn trigger(&self, step: RuntimeStep) -> Result<(), RuntimeTriggerError> {
+        let mut pending_step = self.pending_step.lock().unwrap();
+        if let Some(pending_step) = *pending_step {
+            return Err(RuntimeTriggerError::AlreadyTriggered(pending_step));
+        }
+        let state = self.get_state();
+        if !step.is_performed_in(&state) {
+            return Err(RuntimeTriggerError::UnexpectedState { step, state });
+        }
+        *pending_step = Some(step);
+        self.notify.notify_one();
+
+        Ok(())
+    }
+
+    /// Current state of the state machine, as reported after its latest cycle
+    pub fn get_state(&self) -> String {
+        self.state.read().unwrap().clone()
+    }
+
+    /// Report the state of the state machine after a cycle
+    pub fn set_state(&self, state: String) {
+        *self.state.write().unwrap() = state;
+    }
+
+    /// Wait for the given interval or until a cycle is triggered, returns the triggered step if any
+    pub async fn wait(&self, interval: Duration) -> Option<RuntimeStep> {
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = self.notify.notified() => {}
+        }
+
+        self.pending_step.lock().unwrap().take()
+    }
+}
+
+impl Default for RuntimeCycleTrigger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn a_triggered_cycle_stops_the_wait_for_the_run_interval() {
+        let trigger = RuntimeCycleTrigger::new();
+        trigger.trigger(RuntimeStep::Cycle).unwrap();
+
+        let step = tokio::time::timeout(
+            Duration::from_secs(5),
+            trigger.wait(Duration::from_secs(3600)),
+        )
+        .await
+        .expect("the wait should be stopped by the trigger");
+
+        assert_eq!(Some(RuntimeStep::Cycle), step);
+        assert_eq!(None, trigger.wait(Duration::from_millis(1)).await);
+    }
+
+    #[test]
+    fn a_single_cycle_can_be_pending() {
+        let trigger = RuntimeCycleTrigger::new();
+        trigger.trigger(RuntimeStep::Cycle).unwrap();
+
+        assert_eq!(
+            Err(RuntimeTriggerError::AlreadyTriggered(RuntimeStep::Cycle)),
+            trigger.trigger(RuntimeStep::Cycle)
+        );
+    }
+
+    #[test]
+    fn a_step_can_only_be_triggered_in_the_state_that_performs_it() {
+        let trigger = RuntimeCycleTrigger::new();
+        trigger.set_state("ready".to_string());
+
+        assert_eq!(
+            Err(RuntimeTriggerError::UnexpectedState {
+                step: RuntimeStep::BuildArtifact,
+                state: "ready".to_string()
+            }),
+            trigger.trigger(RuntimeStep::BuildArtifact)
+        );
+        trigger.trigger(RuntimeStep::CreateOpenMessage).unwrap();
+    }
+}
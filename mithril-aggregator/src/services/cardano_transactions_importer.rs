@@ -12,6 +12,8 @@ use mithril_common::entities::{BlockNumber, BlockRange, CardanoTransaction, Immu
 use mithril_common::signable_builder::TransactionsImporter;
 use mithril_common::StdResult;
 
+use crate::services::ProverCache;
+
 /// Cardano transactions store
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
@@ -47,6 +49,7 @@ pub struct CardanoTransactionsImporter {
     logger: Logger,
     rescan_offset: Option<usize>,
     dirpath: PathBuf,
+    prover_cache: Option<Arc<ProverCache>>,
 }
 
 impl CardanoTransactionsImporter {
@@ -68,9 +71,17 @@ impl CardanoTransactionsImporter {
             logger,
             rescan_offset,
             dirpath: dirpath.to_owned(),
+            prover_cache: None,
         }
     }
 
+    /// Invalidate the given prover cache each time transactions are imported, since the proofs
+    /// it holds may not include them.
+    pub fn with_prover_cache(mut self, prover_cache: Arc<ProverCache>) -> Self {
+        self.prover_cache = Some(prover_cache);
+        self
+    }
+
     async fn import_transactions(&self, up_to_beacon: ImmutableFileNumber) -> StdResult<()> {
         let from = self.get_starting_beacon().await?;
         self.parse_and_store_transactions_not_imported_yet(from, up_to_beacon)
@@ -123,7 +134,7 @@ impl CardanoTransactionsImporter {
             .transaction_store
             .get_block_interval_without_block_range_root()
             .await?
-            .map(|range| BlockRange::all_block_ranges_in(BlockRange::start(range.start)..range.end))
+            .map(BlockRange::all_block_ranges_in_aligned)
         {
             // Everything is already computed
             None => return Ok(()),
@@ -170,7 +181,13 @@ impl CardanoTransactionsImporter {
 impl TransactionsImporter for CardanoTransactionsImporter {
     async fn import(&self, up_to_beacon: ImmutableFileNumber) -> StdResult<()> {
         self.import_transactions(up_to_beacon).await?;
-        self.import_block_ranges().await
+        self.import_block_ranges().await?;
+
+        if let Some(prover_cache) = &self.prover_cache {
+            prover_cache.invalidate();
+        }
+
+        Ok(())
     }
 }
 
@@ -182,10 +199,13 @@ mod tests {
         BlockStreamer, DumbBlockScanner, DumbBlockStreamer, ScannedBlock,
     };
     use mithril_common::crypto_helper::MKTree;
-    use mithril_common::entities::{BlockNumber, BlockRangesSequence};
+    use mithril_common::entities::{
+        BlockNumber, BlockRangesSequence, CardanoDbBeacon, CardanoTransactionsSetProof,
+    };
 
     use crate::database::repository::CardanoTransactionRepository;
     use crate::database::test_helper::cardano_tx_db_connection;
+    use crate::services::ProverCacheKey;
 
     use super::*;
 
@@ -633,6 +653,40 @@ mod tests {
         assert_eq!(cold_imported_transactions, warm_imported_transactions);
     }
 
+    #[tokio::test]
+    async fn importing_invalidates_the_prover_cache() {
+        let blocks = vec![ScannedBlock::new(
+            "block_hash-1",
+            10,
+            15,
+            11,
+            vec!["tx_hash-1", "tx_hash-2"],
+        )];
+        let connection = Arc::new(cardano_tx_db_connection().unwrap());
+        let prover_cache = Arc::new(ProverCache::new(
+            10,
+            Arc::new(crate::MetricsService::new().unwrap()),
+        ));
+        let cache_key =
+            ProverCacheKey::new(&CardanoDbBeacon::default(), &["tx_hash-1".to_string()]);
+        prover_cache.insert(
+            cache_key.clone(),
+            vec![CardanoTransactionsSetProof::dummy()],
+        );
+        let importer = CardanoTransactionsImporter::new_for_test(
+            Arc::new(DumbBlockScanner::new(blocks)),
+            Arc::new(CardanoTransactionRepository::new(connection)),
+        )
+        .with_prover_cache(prover_cache.clone());
+
+        importer
+            .import(12)
+            .await
+            .expect("Transactions Importer should succeed");
+
+        assert_eq!(None, prover_cache.get(&cache_key));
+    }
+
     #[tokio::test]
     async fn change_parsed_lower_bound_when_rescan_limit_is_set() {
         fn importer_with_offset(
@@ -5,28 +5,36 @@
 //! single signatures and deal with the multi_signer for aggregate signature
 //! creation.
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use chrono::Utc;
 use mithril_common::{
     certificate_chain::CertificateVerifier,
-    crypto_helper::{ProtocolGenesisVerifier, PROTOCOL_VERSION},
+    crypto_helper::{
+        ProtocolGenesisSigner, ProtocolGenesisVerificationKey, ProtocolGenesisVerifier,
+        PROTOCOL_VERSION,
+    },
     entities::{
         Certificate, CertificateMetadata, CertificateSignature, Epoch, ProtocolMessage,
-        SignedEntityType, SingleSignatures, StakeDistributionParty,
+        SignedEntityType, SignedEntityTypeDiscriminants, SingleSignatures, StakeDistributionParty,
     },
+    era::{EraChecker, SupportedEra},
+    messages::CertificateChainCheckpointListItemMessage,
     CardanoNetwork, StdResult,
 };
 use slog::Logger;
 use slog_scope::{debug, error, info, trace, warn};
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 use tokio::sync::RwLock;
 
 use crate::{
-    database::record::{OpenMessageRecord, OpenMessageWithSingleSignaturesRecord},
+    database::record::OpenMessageWithSingleSignaturesRecord,
     database::repository::{
-        CertificateRepository, OpenMessageRepository, SingleSignatureRepository,
+        BufferedSingleSignatureRepository, CertificateChainCheckpointStorer, CertificateRepository,
+        CertificationUnitOfWork, OpenMessageRepository, SingleSignatureRepository,
     },
     entities::OpenMessage,
     services::TickerService,
@@ -74,6 +82,17 @@ pub enum CertifierServiceError {
     /// Could not verify certificate chain because could not find last certificate.
     #[error("No certificate found.")]
     CouldNotFindLastCertificate,
+
+    /// The single signature was produced under an era that is not accepted, neither the
+    /// current era nor the previous era during the transition epoch of an era switch.
+    #[error("Single signature produced under era '{era}' is not accepted, accepted eras: {accepted_eras:?}.")]
+    UnacceptedEra {
+        /// Era of the single signature
+        era: SupportedEra,
+
+        /// Eras accepted by the certifier
+        accepted_eras: Vec<SupportedEra>,
+    },
 }
 
 /// ## CertifierService
@@ -89,8 +108,9 @@ pub trait CertifierService: Sync + Send {
     async fn inform_epoch(&self, epoch: Epoch) -> StdResult<()>;
 
     /// Add a new single signature for the open message at the given beacon. If
-    /// the open message does not exist or the open message has been certified
-    /// since then, an error is returned.
+    /// the open message has been certified since then, an error is returned. If
+    /// the open message does not exist yet, the single signature is buffered and
+    /// registered when the open message is created.
     async fn register_single_signature(
         &self,
         signed_entity_type: &SignedEntityType,
@@ -100,7 +120,8 @@ pub trait CertifierService: Sync + Send {
     /// Create an open message at the given beacon. If the open message does not
     /// exist or exists at an older beacon, the older open messages are cleared
     /// along with their associated single signatures and the new open message
-    /// is created. If the message already exists, an error is returned.
+    /// is created with the valid single signatures buffered for its signed
+    /// entity type. If the message already exists, an error is returned.
     async fn create_open_message(
         &self,
         signed_entity_type: &SignedEntityType,
@@ -120,6 +141,14 @@ pub trait CertifierService: Sync + Send {
         signed_entity_type: &SignedEntityType,
     ) -> StdResult<Option<OpenMessage>>;
 
+    /// Delete the open message at the given Beacon, along with its single
+    /// signatures, so a new certification round can start for it. The deleted
+    /// open message is returned if it existed.
+    async fn delete_open_message(
+        &self,
+        signed_entity_type: &SignedEntityType,
+    ) -> StdResult<Option<OpenMessage>>;
+
     /// Create a certificate if possible. If the pointed open message does
     /// not exist or has been already certified, an error is raised. If a multi
     /// signature is created then the flag `is_certified` of the open
@@ -145,18 +174,79 @@ pub trait CertifierService: Sync + Send {
     async fn verify_certificate_chain(&self, epoch: Epoch) -> StdResult<()>;
 }
 
+/// Policy applied to the open messages that expire before being certified.
+///
+/// An expired open message is re-opened with an extended expiration date, keeping the single
+/// signatures already registered, until it has been re-opened `max_retries` times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenMessageReopeningPolicy {
+    /// Maximum number of times an open message can be re-opened
+    pub max_retries: u32,
+
+    /// Duration added to the current time to compute the new expiration date of a re-opened
+    /// open message
+    pub expiration_extension: Duration,
+}
+
+/// [OpenMessageReopeningPolicy] shared between the [MithrilCertifierService] and the
+/// [ConfigurationReloader][crate::services::ConfigurationReloader] that updates it when the
+/// configuration is reloaded, open messages are never re-opened if `None`.
+pub type SharedOpenMessageReopeningPolicy =
+    Arc<std::sync::RwLock<Option<OpenMessageReopeningPolicy>>>;
+
+impl OpenMessageReopeningPolicy {
+    /// Can an open message that was already re-opened the given number of times be re-opened
+    pub fn allows_reopening(&self, reopening_count: u32) -> bool {
+        reopening_count < self.max_retries
+    }
+}
+
+/// Policy of the checkpoints of the certificate chain.
+///
+/// A checkpoint records a certificate whose chain has been verified, it is produced and signed
+/// every `interval` epochs. When the checkpoints are trusted, the verification of the certificate
+/// chain stops at the first checkpointed certificate whose checkpoint signature is valid instead
+/// of going down to the genesis certificate.
+#[derive(Debug, Clone, Default)]
+pub struct CertificateChainCheckpointPolicy {
+    /// Number of epochs between two checkpoints, no checkpoint is produced if `0`
+    pub interval: u64,
+
+    /// Signer of the produced checkpoints, no checkpoint is produced if not set
+    pub signer: Option<Arc<ProtocolGenesisSigner>>,
+
+    /// Verification key of the signature of the trusted checkpoints, the checkpoints are not
+    /// trusted when verifying the certificate chain if not set
+    pub trusted_verification_key: Option<ProtocolGenesisVerificationKey>,
+}
+
+impl CertificateChainCheckpointPolicy {
+    /// Is a checkpoint due for a certificate of the given epoch
+    pub fn is_checkpoint_due(&self, epoch: Epoch) -> bool {
+        self.signer.is_some() && self.interval > 0 && *epoch % self.interval == 0
+    }
+}
+
 /// Mithril CertifierService implementation
 pub struct MithrilCertifierService {
     network: CardanoNetwork,
     open_message_repository: Arc<OpenMessageRepository>,
     single_signature_repository: Arc<SingleSignatureRepository>,
+    buffered_single_signature_repository: Arc<BufferedSingleSignatureRepository>,
     certificate_repository: Arc<CertificateRepository>,
+    certification_unit_of_work: Arc<CertificationUnitOfWork>,
     certificate_verifier: Arc<dyn CertificateVerifier>,
     genesis_verifier: Arc<ProtocolGenesisVerifier>,
     multi_signer: Arc<RwLock<dyn MultiSigner>>,
     // todo: should be removed after removing immutable file number from the certificate metadata
     ticker_service: Arc<dyn TickerService>,
     epoch_service: EpochServiceWrapper,
+    era_checker: Arc<EraChecker>,
+    clock_skew_tolerance: Duration,
+    certification_grace_periods: BTreeMap<SignedEntityTypeDiscriminants, Duration>,
+    reopening_policy: SharedOpenMessageReopeningPolicy,
+    checkpoint_storer: Option<Arc<dyn CertificateChainCheckpointStorer>>,
+    checkpoint_policy: CertificateChainCheckpointPolicy,
     _logger: Logger,
 }
 
@@ -167,28 +257,154 @@ impl MithrilCertifierService {
         network: CardanoNetwork,
         open_message_repository: Arc<OpenMessageRepository>,
         single_signature_repository: Arc<SingleSignatureRepository>,
+        buffered_single_signature_repository: Arc<BufferedSingleSignatureRepository>,
         certificate_repository: Arc<CertificateRepository>,
+        certification_unit_of_work: Arc<CertificationUnitOfWork>,
         certificate_verifier: Arc<dyn CertificateVerifier>,
         genesis_verifier: Arc<ProtocolGenesisVerifier>,
         multi_signer: Arc<RwLock<dyn MultiSigner>>,
         ticker_service: Arc<dyn TickerService>,
         epoch_service: EpochServiceWrapper,
+        era_checker: Arc<EraChecker>,
         logger: Logger,
     ) -> Self {
         Self {
             network,
             open_message_repository,
             single_signature_repository,
+            buffered_single_signature_repository,
             certificate_repository,
+            certification_unit_of_work,
             multi_signer,
             certificate_verifier,
             genesis_verifier,
             ticker_service,
             epoch_service,
+            era_checker,
+            clock_skew_tolerance: Duration::ZERO,
+            certification_grace_periods: BTreeMap::new(),
+            reopening_policy: Arc::new(std::sync::RwLock::new(None)),
+            checkpoint_storer: None,
+            checkpoint_policy: CertificateChainCheckpointPolicy::default(),
             _logger: logger,
         }
     }
 
+    /// Set the tolerance to the clock skew between the aggregator and the signers when
+    /// checking the expiration of the open messages.
+    pub fn with_clock_skew_tolerance(mut self, clock_skew_tolerance: Duration) -> Self {
+        self.clock_skew_tolerance = clock_skew_tolerance;
+        self
+    }
+
+    /// Set the minimum duration, per signed entity type, that an open message stays open before
+    /// a certificate can be created for it.
+    pub fn with_certification_grace_periods(
+        mut self,
+        certification_grace_periods: BTreeMap<SignedEntityTypeDiscriminants, Duration>,
+    ) -> Self {
+        self.certification_grace_periods = certification_grace_periods;
+        self
+    }
+
+    /// Set the policy used to re-open the open messages that expire before being certified, by
+    /// default they are never re-opened.
+    pub fn with_reopening_policy(mut self, reopening_policy: OpenMessageReopeningPolicy) -> Self {
+        self.reopening_policy = Arc::new(std::sync::RwLock::new(Some(reopening_policy)));
+        self
+    }
+
+    /// Set the policy used to re-open the open messages that expire before being certified from
+    /// a shared policy that can be updated while the service is running.
+    pub fn with_shared_reopening_policy(
+        mut self,
+        reopening_policy: SharedOpenMessageReopeningPolicy,
+    ) -> Self {
+        self.reopening_policy = reopening_policy;
+        self
+    }
+
+    /// Record the checkpoints of the certificate chain in the given storer with the given policy,
+    /// by default no checkpoint is recorded nor trusted.
+    pub fn with_certificate_chain_checkpoints(
+        mut self,
+        checkpoint_storer: Arc<dyn CertificateChainCheckpointStorer>,
+        checkpoint_policy: CertificateChainCheckpointPolicy,
+    ) -> Self {
+        self.checkpoint_storer = Some(checkpoint_storer);
+        self.checkpoint_policy = checkpoint_policy;
+        self
+    }
+
+    /// Get the hashes of the checkpointed certificates whose checkpoint signature is valid, the
+    /// checkpoints with an invalid signature are ignored.
+    async fn get_trusted_checkpoints(&self) -> StdResult<Vec<String>> {
+        let (Some(checkpoint_storer), Some(verification_key)) = (
+            &self.checkpoint_storer,
+            &self.checkpoint_policy.trusted_verification_key,
+        ) else {
+            return Ok(vec![]);
+        };
+
+        let checkpoints = checkpoint_storer
+            .get_checkpoints()
+            .await
+            .with_context(|| "Certifier can not get the certificate chain checkpoints")?;
+        let mut trusted_checkpoints = vec![];
+        for checkpoint in checkpoints {
+            let checkpoint = CertificateChainCheckpointListItemMessage::from(checkpoint);
+            match checkpoint.verify_signature(verification_key) {
+                Ok(()) => trusted_checkpoints.push(checkpoint.certificate_hash),
+                Err(e) => warn!(
+                    "CertifierService::get_trusted_checkpoints: ignoring a certificate chain checkpoint with an invalid signature";
+                    "epoch" => ?checkpoint.epoch, "certificate_hash" => &checkpoint.certificate_hash, "error" => ?e
+                ),
+            }
+        }
+
+        Ok(trusted_checkpoints)
+    }
+
+    async fn record_checkpoint_if_due(&self, certificate: &Certificate) -> StdResult<()> {
+        let (Some(checkpoint_storer), Some(checkpoint_signer)) =
+            (&self.checkpoint_storer, &self.checkpoint_policy.signer)
+        else {
+            return Ok(());
+        };
+        if !self.checkpoint_policy.is_checkpoint_due(certificate.epoch)
+            || checkpoint_storer
+                .get_checkpoint(certificate.epoch)
+                .await?
+                .is_some()
+        {
+            return Ok(());
+        }
+
+        info!(
+            "CertifierService::record_checkpoint_if_due: recording a certificate chain checkpoint";
+            "epoch" => ?certificate.epoch, "certificate_hash" => &certificate.hash
+        );
+        let signature = checkpoint_signer.sign(
+            &CertificateChainCheckpointListItemMessage::compute_signed_message(
+                certificate.epoch,
+                &certificate.hash,
+            ),
+        );
+        checkpoint_storer
+            .record_checkpoint(
+                certificate.epoch,
+                &certificate.hash,
+                signature.to_bytes_hex(),
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Certifier can not record the certificate chain checkpoint of epoch '{}'",
+                    certificate.epoch
+                )
+            })
+    }
+
     async fn get_open_message_record(
         &self,
         signed_entity_type: &SignedEntityType,
@@ -211,14 +427,9 @@ impl MithrilCertifierService {
 impl CertifierService for MithrilCertifierService {
     async fn inform_epoch(&self, epoch: Epoch) -> StdResult<()> {
         debug!("CertifierService::inform_epoch(epoch: {epoch:?})");
-        let nb = self
-            .open_message_repository
-            .clean_epoch(epoch)
-            .await
-            .with_context(|| {
-                format!("Certifier can not clean open messages from epoch '{epoch}'")
-            })?;
-        info!("MithrilCertifierService: Informed of a new Epoch: {epoch:?}. Cleaned {nb} open messages along with their single signatures.");
+        // The open messages of the previous epochs are pruned by the `PruningService`
+        // according to the retention policies.
+        info!("MithrilCertifierService: Informed of a new Epoch: {epoch:?}.");
 
         Ok(())
     }
@@ -231,13 +442,33 @@ impl CertifierService for MithrilCertifierService {
         debug!("CertifierService::register_single_signature(signed_entity_type: {signed_entity_type:?}, single_signatures: {signature:?}");
         trace!("CertifierService::register_single_signature"; "complete_single_signatures" => #?signature);
 
-        let open_message = self
+        // A single signature that does not advertise its era was produced under the current era.
+        if let Some(era) = signature.era {
+            if !self.era_checker.is_era_accepted(era) {
+                warn!("CertifierService::register_single_signature: single signature for {signed_entity_type:?} was produced under era '{era}' which is not accepted.");
+
+                return Err(CertifierServiceError::UnacceptedEra {
+                    era,
+                    accepted_eras: self.era_checker.accepted_eras(),
+                }
+                .into());
+            }
+        }
+
+        let Some(open_message) = self
             .get_open_message_record(signed_entity_type)
             .await.with_context(|| format!("CertifierService can not get open message record for signed_entity_type: '{signed_entity_type}'"))?
-            .ok_or_else(|| {
-                warn!("CertifierService::register_single_signature: OpenMessage not found for type {signed_entity_type:?}.");
-                CertifierServiceError::NotFound(signed_entity_type.clone())
-            })?;
+        else {
+            // The single signature can only be verified against the protocol message of the
+            // open message, it is verified when the open message is created.
+            self.buffered_single_signature_repository
+                .buffer_single_signature(signed_entity_type.into(), signature)
+                .await
+                .with_context(|| format!("Certifier can not buffer the single signature of party '{}' for signed entity type: '{signed_entity_type}'", signature.party_id))?;
+            info!("CertifierService::register_single_signature: buffered pool '{}' single signature for {signed_entity_type:?}, its open message does not exist yet.", signature.party_id);
+
+            return Ok(());
+        };
 
         if open_message.is_certified {
             warn!("CertifierService::register_single_signature: open message {signed_entity_type:?} is already certified, cannot register single signature.");
@@ -253,7 +484,11 @@ impl CertifierService for MithrilCertifierService {
 
         let multi_signer = self.multi_signer.read().await;
         multi_signer
-            .verify_single_signature(&open_message.protocol_message, signature)
+            .verify_single_signature(
+                &open_message.signed_entity_type,
+                &open_message.protocol_message,
+                signature,
+            )
             .await?;
 
         let single_signature = self
@@ -272,14 +507,40 @@ impl CertifierService for MithrilCertifierService {
         protocol_message: &ProtocolMessage,
     ) -> StdResult<OpenMessage> {
         debug!("CertifierService::create_open_message(signed_entity_type: {signed_entity_type:?}, protocol_message: {protocol_message:?})");
+        let buffered_signatures = self
+            .buffered_single_signature_repository
+            .get_buffered_single_signatures(signed_entity_type.into())
+            .await
+            .with_context(|| format!("Certifier can not get the single signatures buffered for signed entity type: '{signed_entity_type}'"))?;
+        let buffered_party_ids = buffered_signatures
+            .iter()
+            .map(|signature| signature.party_id.clone())
+            .collect::<Vec<_>>();
+        let mut valid_signatures = vec![];
+        {
+            let multi_signer = self.multi_signer.read().await;
+            for signature in buffered_signatures {
+                match multi_signer
+                    .verify_single_signature(signed_entity_type, protocol_message, &signature)
+                    .await
+                {
+                    Ok(()) => valid_signatures.push(signature),
+                    Err(error) => {
+                        warn!("CertifierService::create_open_message: discarding the invalid single signature buffered by pool '{}' for {signed_entity_type:?}", signature.party_id; "error" => ?error);
+                    }
+                }
+            }
+        }
+
         let open_message = self
-            .open_message_repository
-            .create_open_message(
+            .certification_unit_of_work
+            .create_open_message_with_buffered_signatures(
                 signed_entity_type.get_epoch(),
                 signed_entity_type,
                 protocol_message,
+                &valid_signatures,
+                &buffered_party_ids,
             )
-            .await
             .with_context(|| {
                 format!(
                     "Certifier can not create open message from protocol_message: '{:?}, epoch: '{}''",
@@ -287,6 +548,12 @@ impl CertifierService for MithrilCertifierService {
                     signed_entity_type.get_epoch()
                 )
             })?;
+        if !valid_signatures.is_empty() {
+            info!(
+                "CertifierService::create_open_message: registered {} buffered single signatures for {signed_entity_type:?}",
+                valid_signatures.len()
+            );
+        }
         info!("CertifierService::create_open_message: created open message for {signed_entity_type:?}");
         debug!(
             "CertifierService::create_open_message: created open message ID='{}'",
@@ -318,20 +585,66 @@ impl CertifierService for MithrilCertifierService {
     ) -> StdResult<Option<OpenMessage>> {
         debug!("CertifierService::mark_open_message_if_expired");
 
-        let mut open_message_record = self
+        let Some(mut open_message_record) = self
             .open_message_repository
-            .get_expired_open_message(signed_entity_type)
+            .get_expired_open_message(signed_entity_type, self.clock_skew_tolerance)
             .await
-            .with_context(|| "Certifier can not get expired open messages")?;
-        if let Some(open_message_record) = open_message_record.as_mut() {
-            open_message_record.is_expired = true;
-            self.open_message_repository
-                .update_open_message(open_message_record)
-                .await
-                .with_context(|| "Certifier can not update open message to mark it as expired")?;
+            .with_context(|| "Certifier can not get expired open messages")?
+        else {
+            return Ok(None);
+        };
+
+        let reopening_policy = *self
+            .reopening_policy
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        match reopening_policy {
+            Some(policy) if policy.allows_reopening(open_message_record.reopening_count) => {
+                let expiration_extension = chrono::Duration::from_std(policy.expiration_extension)
+                    .with_context(|| "Invalid open message reopening expiration extension")?;
+                open_message_record.expires_at = Some(Utc::now() + expiration_extension);
+                open_message_record.reopening_count += 1;
+                self.open_message_repository
+                    .update_open_message(&open_message_record)
+                    .await
+                    .with_context(|| "Certifier can not update open message to re-open it")?;
+                info!(
+                    "CertifierService::mark_open_message_if_expired: re-opened expired open message {signed_entity_type:?} ({}/{})",
+                    open_message_record.reopening_count, policy.max_retries
+                );
+
+                Ok(None)
+            }
+            _ => {
+                open_message_record.is_expired = true;
+                self.open_message_repository
+                    .update_open_message(&open_message_record)
+                    .await
+                    .with_context(|| {
+                        "Certifier can not update open message to mark it as expired"
+                    })?;
+
+                Ok(Some(open_message_record.into()))
+            }
         }
+    }
 
-        Ok(open_message_record.map(|record| record.into()))
+    async fn delete_open_message(
+        &self,
+        signed_entity_type: &SignedEntityType,
+    ) -> StdResult<Option<OpenMessage>> {
+        debug!("CertifierService::delete_open_message(signed_entity_type: {signed_entity_type:?})");
+
+        let open_message = self
+            .open_message_repository
+            .delete_open_message(signed_entity_type)
+            .await
+            .with_context(|| format!("Certifier can not delete open message for signed entity type: '{signed_entity_type}'"))?;
+        if open_message.is_some() {
+            info!("CertifierService::delete_open_message: deleted open message for {signed_entity_type:?}");
+        }
+
+        Ok(open_message.map(|record| record.into()))
     }
 
     async fn create_certificate(
@@ -340,13 +653,24 @@ impl CertifierService for MithrilCertifierService {
     ) -> StdResult<Option<Certificate>> {
         debug!("CertifierService::create_certificate(signed_entity_type: {signed_entity_type:?})");
         let open_message_record = self
-            .get_open_message_record(signed_entity_type)
-            .await?
+            .open_message_repository
+            .get_open_message(signed_entity_type)
+            .await
+            .with_context(|| format!("Certifier can not get open message for signed entity type: '{signed_entity_type}'"))?
             .ok_or_else(|| {
                 warn!("CertifierService::create_certificate: OpenMessage not found for type {signed_entity_type:?}.");
                 CertifierServiceError::NotFound(signed_entity_type.clone())
             })?;
-        let open_message: OpenMessage = open_message_record.clone().into();
+        // The single signatures to aggregate are fetched in bulk, by pages, with an indexed query.
+        let single_signatures = self
+            .single_signature_repository
+            .get_all_single_signatures_for_open_message(&open_message_record.open_message_id)
+            .await
+            .with_context(|| format!("Certifier can not get the single signatures of the open message for signed entity type: '{signed_entity_type}'"))?;
+        let open_message = OpenMessage {
+            single_signatures,
+            ..open_message_record.clone().into()
+        };
 
         if open_message.is_certified {
             warn!("CertifierService::create_certificate: open message {signed_entity_type:?} is already certified, cannot create certificate.");
@@ -360,6 +684,22 @@ impl CertifierService for MithrilCertifierService {
             return Err(CertifierServiceError::Expired(signed_entity_type.clone()).into());
         }
 
+        if let Some(grace_period) = self
+            .certification_grace_periods
+            .get(&signed_entity_type.into())
+        {
+            let certifiable_at = chrono::Duration::from_std(*grace_period)
+                .ok()
+                .and_then(|grace_period| open_message.created_at.checked_add_signed(grace_period))
+                .ok_or_else(|| {
+                    anyhow!("Invalid certification grace period for {signed_entity_type:?}: {grace_period:?}")
+                })?;
+            if Utc::now() < certifiable_at {
+                debug!("CertifierService::create_certificate: open message {signed_entity_type:?} can not be certified before {certifiable_at}");
+                return Ok(None);
+            }
+        }
+
         let multi_signer = self.multi_signer.read().await;
         let multi_signature = match multi_signer.create_multi_signature(&open_message).await? {
             None => {
@@ -382,7 +722,15 @@ impl CertifierService for MithrilCertifierService {
             .collect::<Vec<_>>();
 
         let protocol_version = PROTOCOL_VERSION.to_string();
-        let initiated_at = open_message.created_at;
+        // The initiation date and time signed with the protocol message prevails, the open
+        // message being created just after it was computed.
+        let initiated_at = match open_message
+            .protocol_message
+            .get_signed_certificate_metadata()?
+        {
+            Some(signed_metadata) => signed_metadata.initiated_at,
+            None => open_message.created_at,
+        };
         let sealed_at = Utc::now();
         let immutable_file_number = self
             .ticker_service
@@ -390,15 +738,23 @@ impl CertifierService for MithrilCertifierService {
             .await
             .with_context(|| "Could not retrieve current beacon to create certificate")?
             .immutable_file_number;
-        let metadata = CertificateMetadata::new(
-            self.network.to_string(),
-            immutable_file_number,
-            protocol_version,
-            epoch_service.current_protocol_parameters()?.clone(),
-            initiated_at,
-            sealed_at,
-            StakeDistributionParty::from_signers(signers),
-        );
+        let metadata = CertificateMetadata {
+            // The multi-signature was issued with the signature scheme of the era of the signed
+            // epoch, which is the era the certificate is verified with.
+            era: Some(
+                self.era_checker
+                    .era_of_epoch(signed_entity_type.get_epoch()),
+            ),
+            ..CertificateMetadata::new(
+                self.network.to_string(),
+                immutable_file_number,
+                protocol_version,
+                epoch_service.current_protocol_parameters()?.clone(),
+                initiated_at,
+                sealed_at,
+                StakeDistributionParty::from_signers(signers),
+            )
+        };
         let parent_certificate_hash = self
             .certificate_repository
             .get_master_certificate_for_epoch::<Certificate>(open_message.epoch)
@@ -431,21 +787,17 @@ impl CertifierService for MithrilCertifierService {
                 )
             })?;
 
+        // The certificate is stored and the open messages are certified all at once, or not at
+        // all if any of those writes fails. The signed entity types batched in the protocol
+        // message are certified by the same certificate: their open messages are created already
+        // certified so that no other certificate round is started for their beacons.
+        let batched_signed_entity_types = open_message
+            .protocol_message
+            .get_batched_signed_entity_types()?;
         let certificate = self
-            .certificate_repository
-            .create_certificate(certificate)
-            .await
-            .with_context(|| {format!(
-                "Certifier can not create certificate for signed entity type: '{signed_entity_type}'")
-            })?;
-
-        let mut open_message_certified: OpenMessageRecord = open_message_record.into();
-        open_message_certified.is_certified = true;
-        self.open_message_repository
-            .update_open_message(&open_message_certified)
-            .await
-            .with_context(|| format!("Certifier can not update open message for signed entity type: '{signed_entity_type}'"))
-            ?;
+            .certification_unit_of_work
+            .store_certificate(certificate, &open_message_record, &batched_signed_entity_types)
+            .with_context(|| format!("Certifier can not store the certificate of signed entity type: '{signed_entity_type}'"))?;
 
         Ok(Some(certificate))
     }
@@ -476,13 +828,16 @@ impl CertifierService for MithrilCertifierService {
                 .into());
             }
 
+            let trusted_checkpoints = self.get_trusted_checkpoints().await?;
             self.certificate_verifier
-                .verify_certificate_chain(
+                .verify_certificate_chain_to_checkpoints(
                     certificate.to_owned(),
                     &self.genesis_verifier.to_verification_key(),
+                    &trusted_checkpoints,
                 )
                 .await
                 .with_context(|| "CertificateVerifier can not verify certificate chain")?;
+            self.record_checkpoint_if_due(certificate).await?;
 
             Ok(())
         } else {
@@ -494,12 +849,20 @@ impl CertifierService for MithrilCertifierService {
 #[cfg(test)]
 mod tests {
     use crate::{
-        dependency_injection::DependenciesBuilder, multi_signer::MockMultiSigner,
-        services::FakeEpochService, Configuration,
+        database::{
+            record::CertificateChainCheckpointRecord,
+            repository::MockCertificateChainCheckpointStorer,
+        },
+        dependency_injection::DependenciesBuilder,
+        multi_signer::MockMultiSigner,
+        services::FakeEpochService,
+        Configuration,
     };
     use chrono::{DateTime, Days};
     use mithril_common::{
-        entities::{CardanoDbBeacon, ProtocolMessagePartKey},
+        entities::{
+            CardanoDbBeacon, ProtocolMessagePart, ProtocolMessagePartKey, SignedCertificateMetadata,
+        },
         test_utils::{fake_data, MithrilFixture, MithrilFixtureBuilder},
     };
 
@@ -514,24 +877,36 @@ mod tests {
             let open_message_repository = Arc::new(OpenMessageRepository::new(connection.clone()));
             let single_signature_repository =
                 Arc::new(SingleSignatureRepository::new(connection.clone()));
+            let buffered_single_signature_repository =
+                Arc::new(BufferedSingleSignatureRepository::new(connection.clone()));
             let certificate_repository = Arc::new(CertificateRepository::new(connection));
+            let certification_unit_of_work = Arc::new(CertificationUnitOfWork::new(
+                dependency_builder
+                    .get_certifier_sqlite_connection()
+                    .await
+                    .unwrap(),
+            ));
             let certificate_verifier = dependency_builder.get_certificate_verifier().await.unwrap();
             let genesis_verifier = dependency_builder.get_genesis_verifier().await.unwrap();
             let multi_signer = dependency_builder.get_multi_signer().await.unwrap();
             let ticker_service = dependency_builder.get_ticker_service().await.unwrap();
             let epoch_service = dependency_builder.get_epoch_service().await.unwrap();
+            let era_checker = dependency_builder.get_era_checker().await.unwrap();
             let logger = dependency_builder.get_logger().await.unwrap();
 
             Self::new(
                 network,
                 open_message_repository,
                 single_signature_repository,
+                buffered_single_signature_repository,
                 certificate_repository,
+                certification_unit_of_work,
                 certificate_verifier,
                 genesis_verifier,
                 multi_signer,
                 ticker_service,
                 epoch_service,
+                era_checker,
                 logger,
             )
         }
@@ -579,7 +954,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn should_clean_epoch_when_inform_epoch() {
+    async fn should_not_clean_open_messages_when_inform_epoch() {
         let beacon = CardanoDbBeacon::new("devnet".to_string(), 1, 1);
         let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(beacon.clone());
         let protocol_message = ProtocolMessage::new();
@@ -596,7 +971,33 @@ mod tests {
             .get_open_message(&signed_entity_type)
             .await
             .unwrap();
-        assert!(open_message.is_none());
+        assert!(open_message.is_some());
+    }
+
+    #[tokio::test]
+    async fn should_delete_open_message_with_its_single_signatures() {
+        let beacon = CardanoDbBeacon::new("devnet".to_string(), 1, 1);
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(beacon.clone());
+        let protocol_message = ProtocolMessage::new();
+        let epochs_with_signers = (1..=3).map(Epoch).collect::<Vec<_>>();
+        let fixture = MithrilFixtureBuilder::default().with_signers(1).build();
+        let certifier_service = setup_certifier_service(&fixture, &epochs_with_signers, None).await;
+        certifier_service
+            .create_open_message(&signed_entity_type, &protocol_message)
+            .await
+            .unwrap();
+
+        let deleted_open_message = certifier_service
+            .delete_open_message(&signed_entity_type)
+            .await
+            .unwrap();
+
+        assert!(deleted_open_message.is_some());
+        assert!(certifier_service
+            .get_open_message(&signed_entity_type)
+            .await
+            .unwrap()
+            .is_none());
     }
 
     #[tokio::test]
@@ -631,6 +1032,35 @@ mod tests {
         assert!(open_message.unwrap().is_expired);
     }
 
+    #[tokio::test]
+    async fn should_not_mark_open_message_expired_within_clock_skew_tolerance() {
+        let beacon = CardanoDbBeacon::new("devnet".to_string(), 3, 1);
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(beacon.clone());
+        let protocol_message = ProtocolMessage::new();
+        let epochs_with_signers = (1..=5).map(Epoch).collect::<Vec<_>>();
+        let fixture = MithrilFixtureBuilder::default().with_signers(1).build();
+        let certifier_service = setup_certifier_service(&fixture, &epochs_with_signers, None)
+            .await
+            .with_clock_skew_tolerance(Duration::from_secs(60));
+        let mut open_message = certifier_service
+            .open_message_repository
+            .create_open_message(beacon.epoch, &signed_entity_type, &protocol_message)
+            .await
+            .unwrap();
+        open_message.expires_at = Some(Utc::now() - chrono::Duration::try_seconds(10).unwrap());
+        certifier_service
+            .open_message_repository
+            .update_open_message(&open_message)
+            .await
+            .unwrap();
+
+        let open_message = certifier_service
+            .mark_open_message_if_expired(&signed_entity_type)
+            .await
+            .expect("mark_open_message_if_expired should not fail");
+        assert!(open_message.is_none());
+    }
+
     #[tokio::test]
     async fn should_not_mark_open_message_expired_when_does_not_expire() {
         let beacon = CardanoDbBeacon::new("devnet".to_string(), 3, 1);
@@ -685,6 +1115,171 @@ mod tests {
         assert!(open_message.is_none());
     }
 
+    #[tokio::test]
+    async fn should_reopen_expired_open_message_preserving_its_single_signatures_when_policy_allows_it(
+    ) {
+        let beacon = CardanoDbBeacon::new("devnet".to_string(), 3, 1);
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(beacon.clone());
+        let protocol_message = ProtocolMessage::new();
+        let epochs_with_signers = (1..=3).map(Epoch).collect::<Vec<_>>();
+        let fixture = MithrilFixtureBuilder::default().with_signers(3).build();
+        let certifier_service =
+            setup_certifier_service(&fixture, &epochs_with_signers, Some(beacon.epoch))
+                .await
+                .with_reopening_policy(OpenMessageReopeningPolicy {
+                    max_retries: 1,
+                    expiration_extension: Duration::from_secs(3600),
+                });
+        certifier_service
+            .create_open_message(&signed_entity_type, &protocol_message)
+            .await
+            .unwrap();
+        let signature = fixture
+            .signers_fixture()
+            .iter()
+            .find_map(|signer_fixture| signer_fixture.sign(&protocol_message))
+            .unwrap();
+        certifier_service
+            .register_single_signature(&signed_entity_type, &signature)
+            .await
+            .unwrap();
+        let expire_open_message = || async {
+            let mut open_message = certifier_service
+                .open_message_repository
+                .get_open_message(&signed_entity_type)
+                .await
+                .unwrap()
+                .unwrap();
+            open_message.expires_at = Some(Utc::now() - chrono::Duration::try_seconds(10).unwrap());
+            certifier_service
+                .open_message_repository
+                .update_open_message(&open_message)
+                .await
+                .unwrap();
+        };
+
+        expire_open_message().await;
+        let expired_open_message = certifier_service
+            .mark_open_message_if_expired(&signed_entity_type)
+            .await
+            .unwrap();
+        assert!(expired_open_message.is_none());
+
+        let open_message = certifier_service
+            .get_open_message(&signed_entity_type)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!open_message.is_expired);
+        assert!(open_message.expires_at.unwrap() > Utc::now());
+        assert_eq!(
+            vec![signature.party_id],
+            open_message
+                .single_signatures
+                .into_iter()
+                .map(|signature| signature.party_id)
+                .collect::<Vec<_>>()
+        );
+
+        expire_open_message().await;
+        let expired_open_message = certifier_service
+            .mark_open_message_if_expired(&signed_entity_type)
+            .await
+            .unwrap();
+        assert!(
+            expired_open_message.unwrap().is_expired,
+            "The open message should be marked as expired once the policy max retries is reached"
+        );
+    }
+
+    #[test]
+    fn checkpoint_policy_produces_a_checkpoint_every_interval_epochs() {
+        let policy = CertificateChainCheckpointPolicy {
+            interval: 10,
+            signer: Some(Arc::new(
+                ProtocolGenesisSigner::create_deterministic_genesis_signer(),
+            )),
+            trusted_verification_key: None,
+        };
+
+        assert!(policy.is_checkpoint_due(Epoch(10)));
+        assert!(policy.is_checkpoint_due(Epoch(20)));
+        assert!(!policy.is_checkpoint_due(Epoch(15)));
+
+        let disabled_policy = CertificateChainCheckpointPolicy {
+            interval: 0,
+            ..policy.clone()
+        };
+        assert!(!disabled_policy.is_checkpoint_due(Epoch(10)));
+
+        let policy_without_signer = CertificateChainCheckpointPolicy {
+            signer: None,
+            ..policy
+        };
+        assert!(!policy_without_signer.is_checkpoint_due(Epoch(10)));
+    }
+
+    #[tokio::test]
+    async fn trusted_checkpoints_are_only_the_checkpoints_with_a_valid_signature() {
+        let checkpoint_signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let signed_checkpoint =
+            |epoch: Epoch, certificate_hash: &str| CertificateChainCheckpointRecord {
+                epoch,
+                certificate_hash: certificate_hash.to_string(),
+                signature: checkpoint_signer
+                    .sign(
+                        &CertificateChainCheckpointListItemMessage::compute_signed_message(
+                            epoch,
+                            certificate_hash,
+                        ),
+                    )
+                    .to_bytes_hex(),
+                created_at: Utc::now(),
+            };
+        let checkpoints = vec![
+            signed_checkpoint(Epoch(20), "certificate-20"),
+            CertificateChainCheckpointRecord {
+                certificate_hash: "forged-certificate-10".to_string(),
+                ..signed_checkpoint(Epoch(10), "certificate-10")
+            },
+        ];
+        let mut checkpoint_storer = MockCertificateChainCheckpointStorer::new();
+        checkpoint_storer
+            .expect_get_checkpoints()
+            .return_once(move || Ok(checkpoints));
+        let fixture = MithrilFixtureBuilder::default().with_signers(1).build();
+        let certifier_service = setup_certifier_service(&fixture, &[], None)
+            .await
+            .with_certificate_chain_checkpoints(
+                Arc::new(checkpoint_storer),
+                CertificateChainCheckpointPolicy {
+                    interval: 10,
+                    signer: None,
+                    trusted_verification_key: Some(
+                        checkpoint_signer
+                            .create_genesis_verifier()
+                            .to_verification_key(),
+                    ),
+                },
+            );
+
+        let trusted_checkpoints = certifier_service.get_trusted_checkpoints().await.unwrap();
+
+        assert_eq!(vec!["certificate-20".to_string()], trusted_checkpoints);
+    }
+
+    #[test]
+    fn reopening_policy_allows_reopening_until_max_retries() {
+        let policy = OpenMessageReopeningPolicy {
+            max_retries: 2,
+            expiration_extension: Duration::from_secs(60),
+        };
+
+        assert!(policy.allows_reopening(0));
+        assert!(policy.allows_reopening(1));
+        assert!(!policy.allows_reopening(2));
+    }
+
     #[tokio::test]
     async fn should_register_valid_single_signature() {
         let beacon = CardanoDbBeacon::new("devnet".to_string(), 3, 1);
@@ -750,6 +1345,82 @@ mod tests {
             .expect_err("register_single_signature should fail");
     }
 
+    #[tokio::test]
+    async fn should_register_single_signature_buffered_before_its_open_message_was_created() {
+        let beacon = CardanoDbBeacon::new("devnet".to_string(), 3, 1);
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(beacon.clone());
+        let protocol_message = ProtocolMessage::new();
+        let epochs_with_signers = (1..=3).map(Epoch).collect::<Vec<_>>();
+        let fixture = MithrilFixtureBuilder::default().with_signers(1).build();
+        let certifier_service =
+            setup_certifier_service(&fixture, &epochs_with_signers, Some(beacon.epoch)).await;
+        let signature = fixture.signers_fixture()[0]
+            .sign(&protocol_message)
+            .unwrap();
+
+        certifier_service
+            .register_single_signature(&signed_entity_type, &signature)
+            .await
+            .expect("A single signature received before its open message should be buffered");
+        certifier_service
+            .create_open_message(&signed_entity_type, &protocol_message)
+            .await
+            .unwrap();
+
+        let open_message = certifier_service
+            .get_open_message(&signed_entity_type)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(vec![signature], open_message.single_signatures);
+        assert!(certifier_service
+            .buffered_single_signature_repository
+            .get_buffered_single_signatures((&signed_entity_type).into())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_discard_invalid_buffered_single_signature_when_creating_its_open_message() {
+        let beacon = CardanoDbBeacon::new("devnet".to_string(), 3, 1);
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(beacon.clone());
+        let mut protocol_message = ProtocolMessage::new();
+        let epochs_with_signers = (1..=3).map(Epoch).collect::<Vec<_>>();
+        let fixture = MithrilFixtureBuilder::default().with_signers(1).build();
+        let certifier_service =
+            setup_certifier_service(&fixture, &epochs_with_signers, Some(beacon.epoch)).await;
+        let signature = fixture.signers_fixture()[0]
+            .sign(&protocol_message)
+            .unwrap();
+
+        certifier_service
+            .register_single_signature(&signed_entity_type, &signature)
+            .await
+            .unwrap();
+        protocol_message.set_message_part(
+            ProtocolMessagePartKey::SnapshotDigest,
+            "snapshot-digest-123".to_string(),
+        );
+        certifier_service
+            .create_open_message(&signed_entity_type, &protocol_message)
+            .await
+            .unwrap();
+
+        let open_message = certifier_service
+            .get_open_message(&signed_entity_type)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(open_message.single_signatures.is_empty());
+        assert!(certifier_service
+            .buffered_single_signature_repository
+            .get_buffered_single_signatures((&signed_entity_type).into())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn should_not_register_single_signature_for_certified_open_message() {
         let beacon = CardanoDbBeacon::new("devnet".to_string(), 3, 1);
@@ -814,6 +1485,106 @@ mod tests {
             .expect_err("register_single_signature should fail");
     }
 
+    #[tokio::test]
+    async fn should_not_register_single_signature_of_an_unaccepted_era() {
+        let beacon = CardanoDbBeacon::new("devnet".to_string(), 3, 1);
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(beacon.clone());
+        let protocol_message = ProtocolMessage::new();
+        let epochs_with_signers = (1..=3).map(Epoch).collect::<Vec<_>>();
+        let fixture = MithrilFixtureBuilder::default().with_signers(1).build();
+        let certifier_service =
+            setup_certifier_service(&fixture, &epochs_with_signers, Some(beacon.epoch)).await;
+        certifier_service
+            .era_checker
+            .change_era(SupportedEra::Thales, beacon.epoch);
+
+        certifier_service
+            .create_open_message(&signed_entity_type, &protocol_message)
+            .await
+            .unwrap();
+
+        let signature = fixture.signers_fixture()[0]
+            .sign(&protocol_message)
+            .unwrap()
+            .with_era(SupportedEra::Pythagoras);
+        let error = certifier_service
+            .register_single_signature(&signed_entity_type, &signature)
+            .await
+            .expect_err("register_single_signature should fail");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<CertifierServiceError>(),
+                Some(CertifierServiceError::UnacceptedEra { .. })
+            ),
+            "Expected an UnacceptedEra error, got: {error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn should_create_certificate_with_single_signatures_of_both_eras_during_era_transition() {
+        let network = fake_data::network();
+        let beacon = CardanoDbBeacon::new(network.to_string(), 3, 1);
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(beacon.clone());
+        let protocol_message = ProtocolMessage::new();
+        let epochs_with_signers = (1..=3).map(Epoch).collect::<Vec<_>>();
+        let fixture = MithrilFixtureBuilder::default().with_signers(3).build();
+        let certifier_service = setup_certifier_service_with_network(
+            network,
+            &fixture,
+            &epochs_with_signers,
+            Some(beacon.epoch),
+        )
+        .await;
+        certifier_service.era_checker.change_era_with_transition(
+            SupportedEra::Pythagoras,
+            beacon.epoch,
+            Some(SupportedEra::Thales),
+        );
+
+        certifier_service
+            .create_open_message(&signed_entity_type, &protocol_message)
+            .await
+            .unwrap();
+        let genesis_certificate =
+            fixture.create_genesis_certificate(network.to_string(), beacon.epoch - 1, 1);
+        certifier_service
+            .certificate_repository
+            .create_certificate(genesis_certificate)
+            .await
+            .unwrap();
+
+        let eras = [SupportedEra::Thales, SupportedEra::Pythagoras];
+        for (index, signer_fixture) in fixture.signers_fixture().iter().enumerate() {
+            if let Some(signature) = signer_fixture.sign(&protocol_message) {
+                certifier_service
+                    .register_single_signature(
+                        &signed_entity_type,
+                        &signature.with_era(eras[index % eras.len()]),
+                    )
+                    .await
+                    .expect("register_single_signature should not fail");
+            }
+        }
+
+        let certificate_created = certifier_service
+            .create_certificate(&signed_entity_type)
+            .await
+            .unwrap()
+            .expect("a certificate should have been created");
+        assert_eq!(
+            Some(SupportedEra::Pythagoras),
+            certificate_created.metadata.era
+        );
+
+        let certificate_retrieved = certifier_service
+            .get_certificate_by_hash(&certificate_created.hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(certificate_created, certificate_retrieved);
+    }
+
     #[tokio::test]
     async fn should_create_certificate_when_multi_signature_produced() {
         let network = fake_data::network();
@@ -890,6 +1661,120 @@ mod tests {
         assert!(!latest_certificates.is_empty());
     }
 
+    #[tokio::test]
+    async fn should_certify_batched_signed_entity_types_when_certificate_created() {
+        let network = fake_data::network();
+        let beacon = CardanoDbBeacon::new(network.to_string(), 3, 1);
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(beacon.clone());
+        let batched_signed_entity_type = SignedEntityType::MithrilStakeDistribution(beacon.epoch);
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message
+            .batch(&[(batched_signed_entity_type.clone(), ProtocolMessage::new())])
+            .unwrap();
+        let epochs_with_signers = (1..=3).map(Epoch).collect::<Vec<_>>();
+        let fixture = MithrilFixtureBuilder::default().with_signers(3).build();
+        let certifier_service = setup_certifier_service_with_network(
+            network,
+            &fixture,
+            &epochs_with_signers,
+            Some(beacon.epoch),
+        )
+        .await;
+        certifier_service
+            .create_open_message(&signed_entity_type, &protocol_message)
+            .await
+            .unwrap();
+        let genesis_certificate =
+            fixture.create_genesis_certificate(network.to_string(), beacon.epoch - 1, 1);
+        certifier_service
+            .certificate_repository
+            .create_certificate(genesis_certificate)
+            .await
+            .unwrap();
+        for signer_fixture in fixture.signers_fixture() {
+            if let Some(signature) = signer_fixture.sign(&protocol_message) {
+                certifier_service
+                    .register_single_signature(&signed_entity_type, &signature)
+                    .await
+                    .expect("register_single_signature should not fail");
+            }
+        }
+
+        let certificate = certifier_service
+            .create_certificate(&signed_entity_type)
+            .await
+            .unwrap()
+            .expect("a certificate should have been created");
+
+        assert_eq!(protocol_message, certificate.protocol_message);
+        let batched_open_message = certifier_service
+            .get_open_message(&batched_signed_entity_type)
+            .await
+            .unwrap()
+            .expect("an open message should exist for the batched signed entity type");
+        assert!(batched_open_message.is_certified);
+        assert_eq!(protocol_message, batched_open_message.protocol_message);
+    }
+
+    #[tokio::test]
+    async fn should_create_certificate_with_the_metadata_signed_in_the_protocol_message() {
+        let network = fake_data::network();
+        let beacon = CardanoDbBeacon::new(network.to_string(), 3, 1);
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(beacon.clone());
+        let fixture = MithrilFixtureBuilder::default().with_signers(3).build();
+        let signed_metadata = SignedCertificateMetadata {
+            network: network.to_string(),
+            protocol_parameters: fixture.protocol_parameters(),
+            initiated_at: DateTime::parse_from_rfc3339("2024-02-12T13:11:47.123456789Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        };
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message
+            .set_part(ProtocolMessagePart::SignedCertificateMetadata(
+                signed_metadata.clone(),
+            ))
+            .unwrap();
+        let epochs_with_signers = (1..=3).map(Epoch).collect::<Vec<_>>();
+        let certifier_service = setup_certifier_service_with_network(
+            network,
+            &fixture,
+            &epochs_with_signers,
+            Some(beacon.epoch),
+        )
+        .await;
+        certifier_service
+            .create_open_message(&signed_entity_type, &protocol_message)
+            .await
+            .unwrap();
+        let genesis_certificate =
+            fixture.create_genesis_certificate(network.to_string(), beacon.epoch - 1, 1);
+        certifier_service
+            .certificate_repository
+            .create_certificate(genesis_certificate)
+            .await
+            .unwrap();
+        for signer_fixture in fixture.signers_fixture() {
+            if let Some(signature) = signer_fixture.sign(&protocol_message) {
+                certifier_service
+                    .register_single_signature(&signed_entity_type, &signature)
+                    .await
+                    .expect("register_single_signature should not fail");
+            }
+        }
+
+        let certificate = certifier_service
+            .create_certificate(&signed_entity_type)
+            .await
+            .unwrap()
+            .expect("a certificate should have been created");
+
+        assert_eq!(
+            signed_metadata,
+            SignedCertificateMetadata::from(&certificate.metadata)
+        );
+    }
+
     #[tokio::test]
     async fn should_not_create_certificate_for_open_message_not_created() {
         let beacon = CardanoDbBeacon::new("devnet".to_string(), 1, 1);
@@ -948,6 +1833,60 @@ mod tests {
         assert!(create_certificate_result.is_none());
     }
 
+    #[tokio::test]
+    async fn should_not_create_certificate_before_the_end_of_the_certification_grace_period() {
+        let mut mock_multi_signer = MockMultiSigner::new();
+        mock_multi_signer.expect_create_multi_signature().never();
+        let beacon = CardanoDbBeacon::new("devnet".to_string(), 1, 1);
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(beacon.clone());
+        let protocol_message = ProtocolMessage::new();
+        let epochs_with_signers = (1..=5).map(Epoch).collect::<Vec<_>>();
+        let fixture = MithrilFixtureBuilder::default().with_signers(5).build();
+        let mut certifier_service = setup_certifier_service(&fixture, &epochs_with_signers, None)
+            .await
+            .with_certification_grace_periods(BTreeMap::from([(
+                SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+                Duration::from_secs(60),
+            )]));
+        certifier_service.multi_signer = Arc::new(RwLock::new(mock_multi_signer));
+        certifier_service
+            .create_open_message(&signed_entity_type, &protocol_message)
+            .await
+            .unwrap();
+        let create_certificate_result = certifier_service
+            .create_certificate(&signed_entity_type)
+            .await
+            .unwrap();
+        assert!(create_certificate_result.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_fail_to_create_certificate_with_an_out_of_range_certification_grace_period() {
+        let mut mock_multi_signer = MockMultiSigner::new();
+        mock_multi_signer.expect_create_multi_signature().never();
+        let beacon = CardanoDbBeacon::new("devnet".to_string(), 1, 1);
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(beacon.clone());
+        let protocol_message = ProtocolMessage::new();
+        let epochs_with_signers = (1..=5).map(Epoch).collect::<Vec<_>>();
+        let fixture = MithrilFixtureBuilder::default().with_signers(5).build();
+        let mut certifier_service = setup_certifier_service(&fixture, &epochs_with_signers, None)
+            .await
+            .with_certification_grace_periods(BTreeMap::from([(
+                SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+                Duration::from_secs(u64::MAX),
+            )]));
+        certifier_service.multi_signer = Arc::new(RwLock::new(mock_multi_signer));
+        certifier_service
+            .create_open_message(&signed_entity_type, &protocol_message)
+            .await
+            .unwrap();
+
+        certifier_service
+            .create_certificate(&signed_entity_type)
+            .await
+            .expect_err("An out of range certification grace period should fail without panicking");
+    }
+
     #[tokio::test]
     async fn test_epoch_gap_certificate_chain() {
         let builder = MithrilFixtureBuilder::default();
@@ -0,0 +1,402 @@
+use anyhow::Context;
+use mithril_common::logging::ComponentLogLevels;
+use mithril_common::StdResult;
+use slog_scope::{info, warn};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+use crate::services::{OpenMessageReopeningPolicy, SharedOpenMessageReopeningPolicy};
+use crate::snapshot_uploaders::SharedUploadSchedule;
+use crate::tools::BandwidthLimiter;
+use crate::Configuration;
+
+/// The subset of the [Configuration] settings that can be changed while the aggregator is
+/// running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReloadableSettings {
+    /// [Configuration::component_log_levels]
+    pub component_log_levels: Option<String>,
+
+    /// [Configuration::signed_entity_types]
+    pub signed_entity_types: Option<String>,
+
+    /// [Configuration::open_message_reopening_max_retries]
+    pub open_message_reopening_max_retries: u32,
+
+    /// [Configuration::open_message_reopening_extension]
+    pub open_message_reopening_extension: u64,
+
+    /// [Configuration::snapshot_upload_max_bandwidth]
+    pub snapshot_upload_max_bandwidth: Option<u64>,
+
+    /// [Configuration::snapshot_upload_windows]
+    pub snapshot_upload_windows: Option<String>,
+}
+
+impl ReloadableSettings {
+    /// Extract the reloadable settings of the given configuration
+    pub fn from_configuration(configuration: &Configuration) -> Self {
+        Self {
+            component_log_levels: configuration.component_log_levels.clone(),
+            signed_entity_types: configuration.signed_entity_types.clone(),
+            open_message_reopening_max_retries: configuration.open_message_reopening_max_retries,
+            open_message_reopening_extension: configuration.open_message_reopening_extension,
+            snapshot_upload_max_bandwidth: configuration.snapshot_upload_max_bandwidth,
+            snapshot_upload_windows: configuration.snapshot_upload_windows.clone(),
+        }
+    }
+
+    /// Override the reloadable settings of the given configuration
+    pub fn apply_to(&self, configuration: &mut Configuration) {
+        configuration.component_log_levels = self.component_log_levels.clone();
+        configuration.signed_entity_types = self.signed_entity_types.clone();
+        configuration.open_message_reopening_max_retries = self.open_message_reopening_max_retries;
+        configuration.open_message_reopening_extension = self.open_message_reopening_extension;
+        configuration.snapshot_upload_max_bandwidth = self.snapshot_upload_max_bandwidth;
+        configuration.snapshot_upload_windows = self.snapshot_upload_windows.clone();
+    }
+
+    /// Policy used to re-open the expired open messages, `None` if they are never re-opened
+    pub fn open_message_reopening_policy(&self) -> Option<OpenMessageReopeningPolicy> {
+        (self.open_message_reopening_max_retries > 0).then(|| OpenMessageReopeningPolicy {
+            max_retries: self.open_message_reopening_max_retries,
+            expiration_extension: Duration::from_secs(self.open_message_reopening_extension),
+        })
+    }
+
+    /// Names of the settings that differ from the given ones
+    pub fn changed_settings(&self, other: &Self) -> Vec<&'static str> {
+        [
+            (
+                "component_log_levels",
+                self.component_log_levels != other.component_log_levels,
+            ),
+            (
+                "signed_entity_types",
+                self.signed_entity_types != other.signed_entity_types,
+            ),
+            (
+                "open_message_reopening_max_retries",
+                self.open_message_reopening_max_retries != other.open_message_reopening_max_retries,
+            ),
+            (
+                "open_message_reopening_extension",
+                self.open_message_reopening_extension != other.open_message_reopening_extension,
+            ),
+            (
+                "snapshot_upload_max_bandwidth",
+                self.snapshot_upload_max_bandwidth != other.snapshot_upload_max_bandwidth,
+            ),
+            (
+                "snapshot_upload_windows",
+                self.snapshot_upload_windows != other.snapshot_upload_windows,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, is_changed)| is_changed.then_some(name))
+        .collect()
+    }
+}
+
+/// Reload the [reloadable settings][ReloadableSettings] of the configuration and propagate them
+/// to the runtime-configurable services of the aggregator.
+///
+/// The other settings of a reloaded configuration are ignored, changing them still requires a
+/// restart.
+pub struct ConfigurationReloader {
+    initial_settings: ReloadableSettings,
+    reloaded_settings: RwLock<Option<ReloadableSettings>>,
+    log_levels: ComponentLogLevels,
+    open_message_reopening_policy: SharedOpenMessageReopeningPolicy,
+    snapshot_upload_bandwidth_limiter: Arc<BandwidthLimiter>,
+    snapshot_upload_schedule: SharedUploadSchedule,
+}
+
+impl ConfigurationReloader {
+    /// ConfigurationReloader factory, the given configuration is the one the services were
+    /// built with
+    pub fn new(
+        configuration: &Configuration,
+        log_levels: ComponentLogLevels,
+        open_message_reopening_policy: SharedOpenMessageReopeningPolicy,
+        snapshot_upload_bandwidth_limiter: Arc<BandwidthLimiter>,
+        snapshot_upload_schedule: SharedUploadSchedule,
+    ) -> Self {
+        Self {
+            initial_settings: ReloadableSettings::from_configuration(configuration),
+            reloaded_settings: RwLock::new(None),
+            log_levels,
+            open_message_reopening_policy,
+            snapshot_upload_bandwidth_limiter,
+            snapshot_upload_schedule,
+        }
+    }
+
+    /// Settings currently in use
+    pub fn current_settings(&self) -> ReloadableSettings {
+        self.reloaded_settings
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+            .unwrap_or_else(|| self.initial_settings.clone())
+    }
+
+    /// Copy of the given configuration with the reloaded settings, if any
+    pub fn reloaded_configuration(&self, configuration: &Configuration) -> Configuration {
+        let mut configuration = configuration.clone();
+        if let Some(settings) = self
+            .reloaded_settings
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+        {
+            settings.apply_to(&mut configuration);
+        }
+
+        configuration
+    }
+
+    /// Reload the settings from the given configuration, returns the names of the changed
+    /// settings.
+    ///
+    /// Nothing is changed if one of the reloaded settings is invalid.
+    pub fn reload(&self, configuration: &Configuration) -> StdResult<Vec<&'static str>> {
+        let component_log_levels = configuration.list_component_log_levels()?;
+        configuration.list_allowed_signed_entity_types_discriminants()?;
+        let snapshot_upload_schedule = configuration.snapshot_upload_schedule()?;
+
+        let settings = ReloadableSettings::from_configuration(configuration);
+        let current_settings = self.current_settings();
+        let changed_settings = settings.changed_settings(&current_settings);
+        if changed_settings.is_empty() {
+            return Ok(changed_settings);
+        }
+
+        if settings.component_log_levels != current_settings.component_log_levels {
+            for component in self.log_levels.component_levels().keys() {
+                self.log_levels.set_component_level(*component, None);
+            }
+            self.log_levels.set_component_levels(&component_log_levels);
+        }
+        *self
+            .open_message_reopening_policy
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = settings.open_message_reopening_policy();
+        self.snapshot_upload_bandwidth_limiter
+            .set_bytes_per_second(settings.snapshot_upload_max_bandwidth);
+        *self
+            .snapshot_upload_schedule
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = snapshot_upload_schedule;
+        *self
+            .reloaded_settings
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(settings);
+
+        Ok(changed_settings)
+    }
+
+    /// Reload the settings from the configuration returned by `load_configuration` on `SIGHUP`
+    /// and, if set, at the given interval.
+    ///
+    /// Never returns: the reload failures are logged and the current settings are kept.
+    pub async fn watch<F>(&self, load_configuration: F, reload_interval: Option<Duration>)
+    where
+        F: Fn() -> StdResult<Configuration> + Send,
+    {
+        let mut hangup_signal = Self::hangup_signal();
+        loop {
+            tokio::select! {
+                _ = Self::wait_hangup(&mut hangup_signal) => {
+                    info!("ConfigurationReloader: SIGHUP received, reloading the configuration");
+                }
+                _ = Self::wait_interval(reload_interval) => {}
+            }
+
+            match load_configuration()
+                .with_context(|| "Could not load the configuration")
+                .and_then(|configuration| self.reload(&configuration))
+            {
+                Ok(changed_settings) if changed_settings.is_empty() => {}
+                Ok(changed_settings) => {
+                    info!("ConfigurationReloader: reloaded settings"; "settings" => ?changed_settings);
+                }
+                Err(error) => {
+                    warn!("ConfigurationReloader: could not reload the configuration, the current settings are kept"; "error" => ?error);
+                }
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    fn hangup_signal() -> Option<tokio::signal::unix::Signal> {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        signal(SignalKind::hangup())
+            .inspect_err(|error| {
+                warn!("ConfigurationReloader: could not listen to SIGHUP"; "error" => ?error);
+            })
+            .ok()
+    }
+
+    #[cfg(not(unix))]
+    fn hangup_signal() -> Option<()> {
+        None
+    }
+
+    #[cfg(unix)]
+    async fn wait_hangup(hangup_signal: &mut Option<tokio::signal::unix::Signal>) {
+        match hangup_signal {
+            Some(signal) => {
+                signal.recv().await;
+            }
+            None => std::future::pending().await,
+        }
+    }
+
+    #[cfg(not(unix))]
+    async fn wait_hangup(_hangup_signal: &mut Option<()>) {
+        std::future::pending().await
+    }
+
+    async fn wait_interval(reload_interval: Option<Duration>) {
+        match reload_interval {
+            Some(interval) => tokio::time::sleep(interval).await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use mithril_common::logging::LogComponent;
+    use slog::Level;
+
+    use crate::snapshot_uploaders::UploadSchedule;
+
+    use super::*;
+
+    fn reloader(configuration: &Configuration) -> ConfigurationReloader {
+        ConfigurationReloader::new(
+            configuration,
+            ComponentLogLevels::new(Level::Info),
+            Arc::new(RwLock::new(None)),
+            Arc::new(BandwidthLimiter::unlimited()),
+            Arc::new(RwLock::new(UploadSchedule::default())),
+        )
+    }
+
+    #[test]
+    fn reloaded_configuration_is_unchanged_until_a_reload() {
+        let configuration = Configuration::new_sample();
+        let reloader = reloader(&configuration);
+
+        assert_eq!(
+            ReloadableSettings::from_configuration(&configuration),
+            ReloadableSettings::from_configuration(
+                &reloader.reloaded_configuration(&configuration)
+            )
+        );
+        assert_eq!(Vec::<&str>::new(), reloader.reload(&configuration).unwrap());
+    }
+
+    #[test]
+    fn reload_propagates_the_changed_settings_to_the_services() {
+        let configuration = Configuration::new_sample();
+        let reloader = reloader(&configuration);
+        let new_configuration = Configuration {
+            component_log_levels: Some("prover=debug".to_string()),
+            signed_entity_types: Some("CardanoTransactions".to_string()),
+            open_message_reopening_max_retries: 2,
+            open_message_reopening_extension: 60,
+            snapshot_upload_max_bandwidth: Some(1000),
+            snapshot_upload_windows: Some("02:00-04:00".to_string()),
+            ..configuration.clone()
+        };
+
+        let changed_settings = reloader.reload(&new_configuration).unwrap();
+
+        assert_eq!(
+            vec![
+                "component_log_levels",
+                "signed_entity_types",
+                "open_message_reopening_max_retries",
+                "open_message_reopening_extension",
+                "snapshot_upload_max_bandwidth",
+                "snapshot_upload_windows",
+            ],
+            changed_settings
+        );
+        assert_eq!(
+            Level::Debug,
+            reloader.log_levels.level(LogComponent::Prover)
+        );
+        assert_eq!(
+            Some(OpenMessageReopeningPolicy {
+                max_retries: 2,
+                expiration_extension: Duration::from_secs(60),
+            }),
+            *reloader.open_message_reopening_policy.read().unwrap()
+        );
+        assert_eq!(
+            Some(1000),
+            reloader
+                .snapshot_upload_bandwidth_limiter
+                .bytes_per_second()
+        );
+        let upload_schedule = reloader.snapshot_upload_schedule.read().unwrap();
+        assert!(!upload_schedule.is_open_at(
+            DateTime::parse_from_rfc3339("2024-05-02T12:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc)
+        ));
+        assert_eq!(
+            Some("CardanoTransactions".to_string()),
+            reloader
+                .reloaded_configuration(&configuration)
+                .signed_entity_types
+        );
+    }
+
+    #[test]
+    fn reload_resets_the_log_levels_of_the_components_removed_from_the_configuration() {
+        let configuration = Configuration {
+            component_log_levels: Some("prover=debug".to_string()),
+            ..Configuration::new_sample()
+        };
+        let reloader = reloader(&configuration);
+        reloader
+            .log_levels
+            .set_component_levels(&configuration.list_component_log_levels().unwrap());
+
+        reloader
+            .reload(&Configuration {
+                component_log_levels: None,
+                ..configuration
+            })
+            .unwrap();
+
+        assert_eq!(Level::Info, reloader.log_levels.level(LogComponent::Prover));
+    }
+
+    #[test]
+    fn invalid_settings_are_not_reloaded() {
+        let configuration = Configuration::new_sample();
+        let reloader = reloader(&configuration);
+
+        reloader
+            .reload(&Configuration {
+                component_log_levels: Some("prover=debug".to_string()),
+                snapshot_upload_windows: Some("invalid".to_string()),
+                ..configuration.clone()
+            })
+            .expect_err("an invalid upload window should fail the reload");
+
+        assert_eq!(
+            ReloadableSettings::from_configuration(&configuration),
+            reloader.current_settings()
+        );
+        assert_eq!(Level::Info, reloader.log_levels.level(LogComponent::Prover));
+    }
+}
@@ -1,12 +1,20 @@
 use anyhow::Context;
 use async_trait::async_trait;
-use slog_scope::debug;
+use slog_scope::{debug, info};
+use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
 
-use mithril_common::crypto_helper::ProtocolAggregateVerificationKey;
-use mithril_common::entities::{Epoch, ProtocolParameters, SignerWithStake};
-use mithril_common::protocol::{MultiSigner as ProtocolMultiSigner, SignerBuilder};
+use mithril_common::crypto_helper::{compute_registration_nonce, ProtocolAggregateVerificationKey};
+use mithril_common::entities::{
+    Epoch, ProtocolFeatureActivations, ProtocolParameters, SignerWithStake, StakeDistributionDiff,
+};
+use mithril_common::era::{EraChecker, SupportedEra};
+#[cfg(test)]
+use mithril_common::protocol::SignerBuilder;
+use mithril_common::protocol::{
+    EraSignatureSchemes, MultiSigner as ProtocolMultiSigner, ProtocolSignatureScheme,
+};
 use mithril_common::StdResult;
 
 use crate::{ProtocolParametersStorer, VerificationKeyStorer};
@@ -64,14 +72,29 @@ pub trait EpochService: Sync + Send {
     /// Get next aggregate verification key for next epoch
     fn next_aggregate_verification_key(&self) -> StdResult<&ProtocolAggregateVerificationKey>;
 
+    /// Get the nonce that the signers must sign to authenticate their single signatures in the
+    /// current epoch
+    fn current_registration_nonce(&self) -> StdResult<String>;
+
     /// Get signers with stake for the current epoch
     fn current_signers_with_stake(&self) -> StdResult<&Vec<SignerWithStake>>;
 
     /// Get signers with stake for the next epoch
     fn next_signers_with_stake(&self) -> StdResult<&Vec<SignerWithStake>>;
 
-    /// Get the [protocol multi signer][ProtocolMultiSigner] for the current epoch
-    fn protocol_multi_signer(&self) -> StdResult<&ProtocolMultiSigner>;
+    /// Get the signers registered so far for the epoch after the next one, with the stake they
+    /// had when they registered.
+    ///
+    /// Since the registrations are still open for this epoch the result is only a projection and
+    /// is read from the store on each call.
+    async fn upcoming_signers_with_stake(&self) -> StdResult<Vec<SignerWithStake>>;
+
+    /// Get the [protocol multi signer][ProtocolMultiSigner] of the current epoch built with the
+    /// signature scheme of the era of the given signed epoch
+    fn protocol_multi_signer(&self, signed_epoch: Epoch) -> StdResult<&ProtocolMultiSigner>;
+
+    /// Get the activation epochs of the protocol features
+    fn protocol_feature_activations(&self) -> &ProtocolFeatureActivations;
 }
 
 struct EpochData {
@@ -86,17 +109,34 @@ struct EpochData {
 struct ComputedEpochData {
     aggregate_verification_key: ProtocolAggregateVerificationKey,
     next_aggregate_verification_key: ProtocolAggregateVerificationKey,
-    protocol_multi_signer: ProtocolMultiSigner,
+    /// Multi signers of the current epoch for each accepted era
+    protocol_multi_signers: HashMap<SupportedEra, ProtocolMultiSigner>,
+}
+
+/// The registration nonce of an epoch is seeded with its aggregate verification key, which is only
+/// known once the signers of the epoch are.
+fn compute_epoch_registration_nonce(
+    epoch: Epoch,
+    aggregate_verification_key: &ProtocolAggregateVerificationKey,
+) -> StdResult<String> {
+    let seed = aggregate_verification_key
+        .to_json_hex()
+        .with_context(|| "Epoch service failed to encode the aggregate verification key")?;
+
+    Ok(compute_registration_nonce(epoch, seed.as_bytes()))
 }
 
 /// Implementation of the [epoch service][EpochService].
 pub struct MithrilEpochService {
     /// Protocol parameters that will be inserted when inform_epoch is called
     future_protocol_parameters: ProtocolParameters,
+    protocol_feature_activations: ProtocolFeatureActivations,
     epoch_data: Option<EpochData>,
     computed_epoch_data: Option<ComputedEpochData>,
     protocol_parameters_store: Arc<dyn ProtocolParametersStorer>,
     verification_key_store: Arc<dyn VerificationKeyStorer>,
+    era_checker: Arc<EraChecker>,
+    signature_schemes: EraSignatureSchemes,
 }
 
 impl MithrilEpochService {
@@ -105,23 +145,54 @@ impl MithrilEpochService {
         future_protocol_parameters: ProtocolParameters,
         protocol_parameters_store: Arc<dyn ProtocolParametersStorer>,
         verification_key_store: Arc<dyn VerificationKeyStorer>,
+        era_checker: Arc<EraChecker>,
     ) -> Self {
         Self {
             future_protocol_parameters,
+            protocol_feature_activations: ProtocolFeatureActivations::new(),
             epoch_data: None,
             computed_epoch_data: None,
             protocol_parameters_store,
             verification_key_store,
+            era_checker,
+            signature_schemes: EraSignatureSchemes::default(),
         }
     }
 
+    /// Set the activation epochs of the protocol features
+    pub fn with_protocol_feature_activations(
+        mut self,
+        protocol_feature_activations: ProtocolFeatureActivations,
+    ) -> Self {
+        self.protocol_feature_activations = protocol_feature_activations;
+        self
+    }
+
+    /// Set the signature schemes used by each era
+    pub fn with_signature_schemes(mut self, signature_schemes: EraSignatureSchemes) -> Self {
+        self.signature_schemes = signature_schemes;
+        self
+    }
+
+    fn signature_scheme(&self, era: SupportedEra) -> StdResult<Arc<dyn ProtocolSignatureScheme>> {
+        self.signature_schemes.get(era).with_context(|| {
+            format!("Epoch service failed to get the signature scheme of era '{era}'")
+        })
+    }
+
+    /// Get the signers recorded at the given retrieval epoch, with the verification keys they
+    /// registered for the era of the epoch they sign.
     async fn get_signers_with_stake_at_epoch(
         &self,
         signer_retrieval_epoch: Epoch,
+        signing_epoch: Epoch,
     ) -> StdResult<Vec<SignerWithStake>> {
         let signers = self
             .verification_key_store
-            .get_signers(signer_retrieval_epoch)
+            .get_signers_of_era(
+                signer_retrieval_epoch,
+                self.era_checker.era_of_epoch(signing_epoch),
+            )
             .await?
             .unwrap_or_default();
 
@@ -162,6 +233,29 @@ impl MithrilEpochService {
             .map(|_| ())
     }
 
+    fn log_signers_stake_changes(
+        epoch: Epoch,
+        current_signers: &[SignerWithStake],
+        next_signers: &[SignerWithStake],
+    ) {
+        let diff = StakeDistributionDiff::from_signers(current_signers, next_signers);
+
+        info!(
+            "EpochService: signers stake changes for the next epoch";
+            "epoch" => ?epoch,
+            "new_signers" => diff.new_parties.len(),
+            "departed_signers" => diff.departed_parties.len(),
+            "stake_changes" => diff.stake_deltas.len(),
+            "total_stake_delta" => diff.total_stake_delta().to_string(),
+        );
+        debug!(
+            "EpochService: signers stake changes details";
+            "new_signers" => ?diff.new_parties,
+            "departed_signers" => ?diff.departed_parties,
+            "stake_changes" => ?diff.stake_deltas,
+        );
+    }
+
     fn unwrap_data(&self) -> Result<&EpochData, EpochServiceError> {
         self.epoch_data
             .as_ref()
@@ -202,11 +296,12 @@ impl EpochService for MithrilEpochService {
             .await?;
 
         let current_signers = self
-            .get_signers_with_stake_at_epoch(signer_retrieval_epoch)
+            .get_signers_with_stake_at_epoch(signer_retrieval_epoch, epoch)
             .await?;
         let next_signers = self
-            .get_signers_with_stake_at_epoch(next_signer_retrieval_epoch)
+            .get_signers_with_stake_at_epoch(next_signer_retrieval_epoch, epoch.next())
             .await?;
+        Self::log_signers_stake_changes(epoch, &current_signers, &next_signers);
 
         self.epoch_data = Some(EpochData {
             epoch,
@@ -238,20 +333,33 @@ impl EpochService for MithrilEpochService {
             "can't precompute epoch data if inform_epoch has not been called first"
         })?;
 
-        let protocol_multi_signer = SignerBuilder::new(&data.signers, &data.protocol_parameters)
-            .with_context(|| "Epoch service failed to build protocol multi signer")?
-            .build_multi_signer();
+        // During the transition epoch of an era switch the signed entities of the previous epochs
+        // are still signed with the signature scheme of the previous era.
+        let mut protocol_multi_signers = HashMap::new();
+        for era in self.era_checker.accepted_eras() {
+            let protocol_multi_signer = self
+                .signature_scheme(era)?
+                .build_multi_signer(&data.signers, &data.protocol_parameters)
+                .with_context(|| {
+                    format!("Epoch service failed to build protocol multi signer of era '{era}'")
+                })?;
+            protocol_multi_signers.insert(era, protocol_multi_signer);
+        }
 
-        let next_protocol_multi_signer =
-            SignerBuilder::new(&data.next_signers, &data.next_protocol_parameters)
-                .with_context(|| "Epoch service failed to build next protocol multi signer")?
-                .build_multi_signer();
+        // The signers of the next epoch sign with the signature scheme of its era, which differs
+        // from the current one ahead of an era switch.
+        let current_era = self.era_checker.current_era();
+        let next_protocol_multi_signer = self
+            .signature_scheme(self.era_checker.era_of_epoch(data.epoch.next()))?
+            .build_multi_signer(&data.next_signers, &data.next_protocol_parameters)
+            .with_context(|| "Epoch service failed to build next protocol multi signer")?;
 
         self.computed_epoch_data = Some(ComputedEpochData {
-            aggregate_verification_key: protocol_multi_signer.compute_aggregate_verification_key(),
+            aggregate_verification_key: protocol_multi_signers[&current_era]
+                .compute_aggregate_verification_key(),
             next_aggregate_verification_key: next_protocol_multi_signer
                 .compute_aggregate_verification_key(),
-            protocol_multi_signer,
+            protocol_multi_signers,
         });
 
         Ok(())
@@ -281,6 +389,13 @@ impl EpochService for MithrilEpochService {
         Ok(&self.unwrap_computed_data()?.next_aggregate_verification_key)
     }
 
+    fn current_registration_nonce(&self) -> StdResult<String> {
+        compute_epoch_registration_nonce(
+            self.epoch_of_current_data()?,
+            self.current_aggregate_verification_key()?,
+        )
+    }
+
     fn current_signers_with_stake(&self) -> StdResult<&Vec<SignerWithStake>> {
         Ok(&self.unwrap_data()?.signers)
     }
@@ -289,8 +404,33 @@ impl EpochService for MithrilEpochService {
         Ok(&self.unwrap_data()?.next_signers)
     }
 
-    fn protocol_multi_signer(&self) -> StdResult<&ProtocolMultiSigner> {
-        Ok(&self.unwrap_computed_data()?.protocol_multi_signer)
+    async fn upcoming_signers_with_stake(&self) -> StdResult<Vec<SignerWithStake>> {
+        let epoch = self.unwrap_data()?.epoch;
+        let upcoming_signer_retrieval_epoch = epoch.offset_to_recording_epoch();
+
+        self.get_signers_with_stake_at_epoch(upcoming_signer_retrieval_epoch, epoch.next().next())
+            .await
+            .with_context(|| "Epoch service failed to obtain the upcoming signers")
+    }
+
+    fn protocol_multi_signer(&self, signed_epoch: Epoch) -> StdResult<&ProtocolMultiSigner> {
+        let epoch = self.unwrap_data()?.epoch;
+        let era = self.era_checker.era_of_epoch(signed_epoch);
+
+        self.unwrap_computed_data()?
+            .protocol_multi_signers
+            .get(&era)
+            .ok_or_else(|| {
+                EpochServiceError::UnavailableData(
+                    epoch,
+                    format!("protocol multi signer of era '{era}'"),
+                )
+                .into()
+            })
+    }
+
+    fn protocol_feature_activations(&self) -> &ProtocolFeatureActivations {
+        &self.protocol_feature_activations
     }
 }
 
@@ -298,6 +438,8 @@ impl EpochService for MithrilEpochService {
 pub struct FakeEpochService {
     epoch_data: Option<EpochData>,
     computed_epoch_data: Option<ComputedEpochData>,
+    upcoming_signers: Vec<SignerWithStake>,
+    protocol_feature_activations: ProtocolFeatureActivations,
     inform_epoch_error: bool,
     update_protocol_parameters_error: bool,
     precompute_epoch_data_error: bool,
@@ -338,8 +480,14 @@ impl FakeEpochService {
                     .compute_aggregate_verification_key(),
                 next_aggregate_verification_key: next_protocol_multi_signer
                     .compute_aggregate_verification_key(),
-                protocol_multi_signer,
+                // The fake epoch service uses the same multi signer for all the eras
+                protocol_multi_signers: HashMap::from([(
+                    SupportedEra::dummy(),
+                    protocol_multi_signer,
+                )]),
             }),
+            upcoming_signers: vec![],
+            protocol_feature_activations: ProtocolFeatureActivations::new(),
             inform_epoch_error: false,
             update_protocol_parameters_error: false,
             precompute_epoch_data_error: false,
@@ -366,12 +514,27 @@ impl FakeEpochService {
         Self {
             epoch_data: None,
             computed_epoch_data: None,
+            upcoming_signers: vec![],
+            protocol_feature_activations: ProtocolFeatureActivations::new(),
             inform_epoch_error: false,
             update_protocol_parameters_error: false,
             precompute_epoch_data_error: false,
         }
     }
 
+    pub fn with_protocol_feature_activations(
+        mut self,
+        protocol_feature_activations: ProtocolFeatureActivations,
+    ) -> Self {
+        self.protocol_feature_activations = protocol_feature_activations;
+        self
+    }
+
+    pub fn with_upcoming_signers(mut self, upcoming_signers: &[SignerWithStake]) -> Self {
+        self.upcoming_signers = upcoming_signers.to_vec();
+        self
+    }
+
     pub fn toggle_errors(
         &mut self,
         inform_epoch: bool,
@@ -446,6 +609,13 @@ impl EpochService for FakeEpochService {
         Ok(&self.unwrap_computed_data()?.next_aggregate_verification_key)
     }
 
+    fn current_registration_nonce(&self) -> StdResult<String> {
+        compute_epoch_registration_nonce(
+            self.epoch_of_current_data()?,
+            self.current_aggregate_verification_key()?,
+        )
+    }
+
     fn current_signers_with_stake(&self) -> StdResult<&Vec<SignerWithStake>> {
         Ok(&self.unwrap_data()?.signers)
     }
@@ -454,17 +624,31 @@ impl EpochService for FakeEpochService {
         Ok(&self.unwrap_data()?.next_signers)
     }
 
-    fn protocol_multi_signer(&self) -> StdResult<&ProtocolMultiSigner> {
-        Ok(&self.unwrap_computed_data()?.protocol_multi_signer)
+    async fn upcoming_signers_with_stake(&self) -> StdResult<Vec<SignerWithStake>> {
+        self.unwrap_data()?;
+        Ok(self.upcoming_signers.clone())
+    }
+
+    fn protocol_multi_signer(&self, _signed_epoch: Epoch) -> StdResult<&ProtocolMultiSigner> {
+        self.unwrap_computed_data()?
+            .protocol_multi_signers
+            .values()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("No protocol multi signer in the fake epoch service"))
+    }
+
+    fn protocol_feature_activations(&self) -> &ProtocolFeatureActivations {
+        &self.protocol_feature_activations
     }
 }
 
 #[cfg(test)]
 mod tests {
     use mithril_common::entities::PartyId;
+    use mithril_common::protocol::StmSignatureScheme;
     use mithril_common::test_utils::{fake_data, MithrilFixture, MithrilFixtureBuilder};
     use mithril_persistence::store::adapter::MemoryAdapter;
-    use std::collections::{BTreeSet, HashMap};
+    use std::collections::BTreeSet;
 
     use crate::services::epoch_service::tests::ServiceBuilderParameters::WithFutureProtocolParameters;
     use crate::store::FakeProtocolParametersStorer;
@@ -486,6 +670,7 @@ mod tests {
     struct ExpectedComputedEpochData {
         aggregate_verification_key: ProtocolAggregateVerificationKey,
         next_aggregate_verification_key: ProtocolAggregateVerificationKey,
+        registration_nonce: String,
     }
 
     impl ExpectedEpochData {
@@ -514,6 +699,7 @@ mod tests {
             Ok(Self {
                 aggregate_verification_key: service.current_aggregate_verification_key()?.clone(),
                 next_aggregate_verification_key: service.next_aggregate_verification_key()?.clone(),
+                registration_nonce: service.current_registration_nonce()?,
             })
         }
     }
@@ -532,6 +718,7 @@ mod tests {
         DifferentFixtureForSecondEpoch(MithrilFixture),
         UpcomingProtocolParameters(ProtocolParameters),
         WithFutureProtocolParameters(ProtocolParameters),
+        UpcomingSigners(Vec<SignerWithStake>),
     }
 
     /// By default will copy data from the given fixture for all epochs, can be fined tuned
@@ -546,6 +733,7 @@ mod tests {
         let mut next_epoch_fixture = current_epoch_fixture;
         let mut upcoming_protocol_parameters = current_epoch_fixture.protocol_parameters();
         let mut future_protocol_parameters = current_epoch_fixture.protocol_parameters();
+        let mut upcoming_signers = vec![];
 
         for params in additional_params {
             match params {
@@ -556,6 +744,9 @@ mod tests {
                     upcoming_protocol_parameters = params.clone()
                 }
                 WithFutureProtocolParameters(params) => future_protocol_parameters = params.clone(),
+                ServiceBuilderParameters::UpcomingSigners(signers) => {
+                    upcoming_signers = signers.clone()
+                }
             }
         }
 
@@ -583,6 +774,10 @@ mod tests {
                     next_signer_retrieval_epoch,
                     map_signers_for_vkey_store(&next_epoch_fixture.signers_with_stake()),
                 ),
+                (
+                    next_signer_retrieval_epoch.next(),
+                    map_signers_for_vkey_store(&upcoming_signers),
+                ),
             ]))
             .unwrap(),
         ));
@@ -591,6 +786,7 @@ mod tests {
             future_protocol_parameters,
             Arc::new(protocol_parameters_store),
             Arc::new(vkey_store),
+            Arc::new(EraChecker::new(SupportedEra::dummy(), epoch)),
         )
     }
 
@@ -646,6 +842,93 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn next_signers_are_the_signers_with_the_keys_registered_for_the_era_of_the_next_epoch() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(3).build();
+        let signers = fixture.signers_with_stake();
+        let epoch = Epoch(5);
+        let mut service = build_service(epoch, &fixture, &[]).await;
+        service.era_checker = Arc::new(EraChecker::new(SupportedEra::Thales, epoch));
+        service
+            .era_checker
+            .set_upcoming_era(SupportedEra::Pythagoras, epoch.next());
+        // The signers register the verification key of another signer for the next era
+        let next_era_signers: Vec<SignerWithStake> = signers
+            .iter()
+            .zip(signers.iter().cycle().skip(1))
+            .map(|(signer, other_signer)| SignerWithStake {
+                verification_key: other_signer.verification_key.clone(),
+                verification_key_signature: other_signer.verification_key_signature.clone(),
+                ..signer.clone()
+            })
+            .collect();
+        for next_era_signer in &next_era_signers {
+            service
+                .verification_key_store
+                .save_next_era_verification_key(
+                    epoch.offset_to_next_signer_retrieval_epoch(),
+                    SupportedEra::Pythagoras,
+                    next_era_signer.clone().into(),
+                )
+                .await
+                .unwrap();
+        }
+
+        service.inform_epoch(epoch).await.unwrap();
+        service.precompute_epoch_data().await.unwrap();
+
+        let data = ExpectedEpochData::from_service(&service).await.unwrap();
+        assert_eq!(BTreeSet::from_iter(signers), data.signers);
+        assert_eq!(
+            BTreeSet::from_iter(next_era_signers.clone()),
+            data.next_signers
+        );
+        assert_eq!(
+            &SignerBuilder::new(&next_era_signers, &fixture.protocol_parameters())
+                .unwrap()
+                .build_multi_signer()
+                .compute_aggregate_verification_key(),
+            service.next_aggregate_verification_key().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn upcoming_signers_are_the_signers_registered_for_the_epoch_after_the_next_one() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(3).build();
+        let upcoming_signers = MithrilFixtureBuilder::default()
+            .with_signers(2)
+            .build()
+            .signers_with_stake();
+        let epoch = Epoch(5);
+        let mut service = build_service(
+            epoch,
+            &fixture,
+            &[ServiceBuilderParameters::UpcomingSigners(
+                upcoming_signers.clone(),
+            )],
+        )
+        .await;
+
+        service
+            .upcoming_signers_with_stake()
+            .await
+            .expect_err("upcoming_signers_with_stake should fail before inform_epoch");
+
+        service
+            .inform_epoch(epoch)
+            .await
+            .expect("inform_epoch should not fail");
+        let signers = service
+            .upcoming_signers_with_stake()
+            .await
+            .expect("upcoming_signers_with_stake should not fail");
+
+        assert_eq!(
+            upcoming_signers.into_iter().collect::<BTreeSet<_>>(),
+            signers.into_iter().collect::<BTreeSet<_>>()
+        );
+    }
+
     #[tokio::test]
     async fn compute_data_with_data_from_inform_epoch() {
         let current_epoch_fixture = MithrilFixtureBuilder::default().with_signers(3).build();
@@ -682,10 +965,80 @@ mod tests {
             ExpectedComputedEpochData {
                 aggregate_verification_key: current_epoch_fixture.compute_avk(),
                 next_aggregate_verification_key: next_epoch_fixture.compute_avk(),
+                registration_nonce: compute_registration_nonce(
+                    epoch,
+                    current_epoch_fixture
+                        .compute_avk()
+                        .to_json_hex()
+                        .unwrap()
+                        .as_bytes()
+                ),
             }
         );
     }
 
+    #[tokio::test]
+    async fn precompute_epoch_data_fails_without_signature_scheme_for_the_current_era() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(3).build();
+        let epoch = Epoch(5);
+        let mut service = build_service(epoch, &fixture, &[])
+            .await
+            .with_signature_schemes(EraSignatureSchemes::empty());
+
+        service
+            .inform_epoch(epoch)
+            .await
+            .expect("inform_epoch should not fail");
+        service
+            .precompute_epoch_data()
+            .await
+            .expect_err("precompute_epoch_data should fail without a signature scheme");
+    }
+
+    #[tokio::test]
+    async fn precompute_epoch_data_builds_a_multi_signer_for_each_accepted_era() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(3).build();
+        let epoch = Epoch(5);
+        let stm_scheme: Arc<dyn ProtocolSignatureScheme> = Arc::new(StmSignatureScheme);
+        let mut service = build_service(epoch, &fixture, &[])
+            .await
+            .with_signature_schemes(
+                EraSignatureSchemes::empty()
+                    .with_scheme(SupportedEra::Pythagoras, stm_scheme.clone()),
+            );
+        service.era_checker.change_era_with_transition(
+            SupportedEra::Pythagoras,
+            epoch,
+            Some(SupportedEra::Thales),
+        );
+        service.inform_epoch(epoch).await.unwrap();
+
+        service.precompute_epoch_data().await.expect_err(
+            "precompute_epoch_data should fail without a scheme for the transition era",
+        );
+
+        service.signature_schemes = service
+            .signature_schemes
+            .clone()
+            .with_scheme(SupportedEra::Thales, stm_scheme);
+        service.precompute_epoch_data().await.unwrap();
+
+        let protocol_multi_signers = &service
+            .computed_epoch_data
+            .as_ref()
+            .unwrap()
+            .protocol_multi_signers;
+        assert_eq!(2, protocol_multi_signers.len());
+        assert!(protocol_multi_signers.contains_key(&SupportedEra::Thales));
+        assert!(protocol_multi_signers.contains_key(&SupportedEra::Pythagoras));
+        service
+            .protocol_multi_signer(Epoch(4))
+            .expect("the multi signer of the transition era should be available");
+        service
+            .protocol_multi_signer(epoch)
+            .expect("the multi signer of the current era should be available");
+    }
+
     #[tokio::test]
     async fn inform_epoch_reset_computed_data() {
         let fixture = MithrilFixtureBuilder::default().with_signers(3).build();
@@ -695,12 +1048,15 @@ mod tests {
         service.computed_epoch_data = Some(ComputedEpochData {
             aggregate_verification_key: avk.clone(),
             next_aggregate_verification_key: avk.clone(),
-            protocol_multi_signer: SignerBuilder::new(
-                &fixture.signers_with_stake(),
-                &fixture.protocol_parameters(),
-            )
-            .unwrap()
-            .build_multi_signer(),
+            protocol_multi_signers: HashMap::from([(
+                SupportedEra::dummy(),
+                SignerBuilder::new(
+                    &fixture.signers_with_stake(),
+                    &fixture.protocol_parameters(),
+                )
+                .unwrap()
+                .build_multi_signer(),
+            )]),
         });
 
         service
@@ -789,9 +1145,13 @@ mod tests {
                 "next_aggregate_verification_key",
                 service.next_aggregate_verification_key().err(),
             ),
+            (
+                "current_registration_nonce",
+                service.current_registration_nonce().err(),
+            ),
             (
                 "protocol_multi_signer",
-                service.protocol_multi_signer().err(),
+                service.protocol_multi_signer(Epoch(4)).err(),
             ),
         ] {
             let error =
@@ -827,9 +1187,13 @@ mod tests {
                 "next_aggregate_verification_key",
                 service.next_aggregate_verification_key().err(),
             ),
+            (
+                "current_registration_nonce",
+                service.current_registration_nonce().err(),
+            ),
             (
                 "protocol_multi_signer",
-                service.protocol_multi_signer().err(),
+                service.protocol_multi_signer(Epoch(4)).err(),
             ),
         ] {
             let error =
@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use mithril_common::entities::ImmutableFileNumber;
+
+/// Progression of the immutable file number of the Cardano node database read by the aggregator
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImmutableFileLagStatus {
+    /// Latest immutable file number read from the Cardano node database, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_immutable_file_number: Option<ImmutableFileNumber>,
+
+    /// Date and time at which the latest immutable file number was first read, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_progress_at: Option<DateTime<Utc>>,
+
+    /// Duration, in seconds, since the immutable file number last progressed
+    pub lag_seconds: i64,
+
+    /// Duration, in seconds, without progression after which the Cardano node is considered
+    /// lagging
+    pub threshold_seconds: u64,
+
+    /// True if the immutable file number has not progressed for more than `threshold_seconds`
+    pub is_lagging: bool,
+}
+
+/// The ImmutableFileLagWatchdog checks that the immutable file number of the Cardano node database
+/// keeps progressing, a Cardano node that stopped syncing prevents the certification of new
+/// artifacts.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ImmutableFileLagWatchdog: Sync + Send {
+    /// Record the immutable file number read from the Cardano node database at the given date.
+    async fn record_immutable_file_number(
+        &self,
+        immutable_file_number: ImmutableFileNumber,
+        now: DateTime<Utc>,
+    );
+
+    /// Compute the progression of the immutable file number at the given date.
+    async fn get_status(&self, now: DateTime<Utc>) -> ImmutableFileLagStatus;
+}
+
+/// Mithril aggregator implementation of the [ImmutableFileLagWatchdog]
+///
+/// The progression is tracked in memory from the start of the aggregator: the Cardano node is
+/// lagging when the immutable file number read has not changed for more than the threshold.
+pub struct MithrilImmutableFileLagWatchdog {
+    threshold_seconds: u64,
+    last_progress: RwLock<Option<(ImmutableFileNumber, DateTime<Utc>)>>,
+}
+
+impl MithrilImmutableFileLagWatchdog {
+    /// MithrilImmutableFileLagWatchdog factory
+    pub fn new(threshold_seconds: u64) -> Self {
+        Self {
+            threshold_seconds,
+            last_progress: RwLock::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl ImmutableFileLagWatchdog for MithrilImmutableFileLagWatchdog {
+    async fn record_immutable_file_number(
+        &self,
+        immutable_file_number: ImmutableFileNumber,
+        now: DateTime<Utc>,
+    ) {
+        let mut last_progress = self.last_progress.write().await;
+        if last_progress.map_or(true, |(number, _)| number != immutable_file_number) {
+            *last_progress = Some((immutable_file_number, now));
+        }
+    }
+
+    async fn get_status(&self, now: DateTime<Utc>) -> ImmutableFileLagStatus {
+        let last_progress = *self.last_progress.read().await;
+        let lag_seconds = last_progress
+            .map(|(_, progress_at)| (now - progress_at).num_seconds().max(0))
+            .unwrap_or_default();
+
+        ImmutableFileLagStatus {
+            last_immutable_file_number: last_progress.map(|(number, _)| number),
+            last_progress_at: last_progress.map(|(_, progress_at)| progress_at),
+            lag_seconds,
+            threshold_seconds: self.threshold_seconds,
+            is_lagging: last_progress.is_some() && lag_seconds > self.threshold_seconds as i64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(date)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[tokio::test]
+    async fn not_lagging_without_recorded_immutable_file_number() {
+        let watchdog = MithrilImmutableFileLagWatchdog::new(3600);
+
+        let status = watchdog.get_status(date("2024-01-01T12:00:00Z")).await;
+
+        assert_eq!(
+            ImmutableFileLagStatus {
+                threshold_seconds: 3600,
+                ..ImmutableFileLagStatus::default()
+            },
+            status
+        );
+    }
+
+    #[tokio::test]
+    async fn lagging_when_the_immutable_file_number_does_not_progress_beyond_the_threshold() {
+        let watchdog = MithrilImmutableFileLagWatchdog::new(3600);
+        watchdog
+            .record_immutable_file_number(10, date("2024-01-01T00:00:00Z"))
+            .await;
+        watchdog
+            .record_immutable_file_number(10, date("2024-01-01T00:30:00Z"))
+            .await;
+
+        let status = watchdog.get_status(date("2024-01-01T00:59:00Z")).await;
+        assert!(!status.is_lagging);
+
+        let status = watchdog.get_status(date("2024-01-01T01:01:00Z")).await;
+        assert_eq!(
+            ImmutableFileLagStatus {
+                last_immutable_file_number: Some(10),
+                last_progress_at: Some(date("2024-01-01T00:00:00Z")),
+                lag_seconds: 3660,
+                threshold_seconds: 3600,
+                is_lagging: true,
+            },
+            status
+        );
+    }
+
+    #[tokio::test]
+    async fn not_lagging_anymore_once_the_immutable_file_number_progresses() {
+        let watchdog = MithrilImmutableFileLagWatchdog::new(3600);
+        watchdog
+            .record_immutable_file_number(10, date("2024-01-01T00:00:00Z"))
+            .await;
+        watchdog
+            .record_immutable_file_number(11, date("2024-01-01T02:00:00Z"))
+            .await;
+
+        let status = watchdog.get_status(date("2024-01-01T02:10:00Z")).await;
+
+        assert_eq!(Some(11), status.last_immutable_file_number);
+        assert_eq!(600, status.lag_seconds);
+        assert!(!status.is_lagging);
+    }
+}
@@ -15,7 +15,10 @@ use mithril_common::{
     StdResult,
 };
 
-use crate::database::repository::{CertificateRepository, SignedEntityStorer};
+use crate::database::repository::{
+    CertificateListFilters, CertificateRepository, SignedEntityStorer,
+};
+use crate::entities::CertificateProtocolMessage;
 
 #[cfg(test)]
 use mockall::automock;
@@ -37,9 +40,18 @@ pub trait MessageService: Sync + Send {
         certificate_hash: &str,
     ) -> StdResult<Option<CertificateMessage>>;
 
-    /// Return the message representation of the last N certificates
-    async fn get_certificate_list_message(&self, limit: usize)
-        -> StdResult<CertificateListMessage>;
+    /// Return the protocol message signed by a certificate, in canonical form, if it exists.
+    async fn get_certificate_protocol_message(
+        &self,
+        certificate_hash: &str,
+    ) -> StdResult<Option<CertificateProtocolMessage>>;
+
+    /// Return the message representation of the last N certificates matching the given filters
+    async fn get_certificate_list_message(
+        &self,
+        filters: &CertificateListFilters,
+        limit: usize,
+    ) -> StdResult<CertificateListMessage>;
 
     /// Return the information regarding the given snapshot
     async fn get_snapshot_message(
@@ -80,6 +92,7 @@ pub trait MessageService: Sync + Send {
 pub struct MithrilMessageService {
     certificate_repository: Arc<CertificateRepository>,
     signed_entity_storer: Arc<dyn SignedEntityStorer>,
+    snapshot_signed_download_base_url: Option<String>,
 }
 
 impl MithrilMessageService {
@@ -91,8 +104,16 @@ impl MithrilMessageService {
         Self {
             certificate_repository,
             signed_entity_storer,
+            snapshot_signed_download_base_url: None,
         }
     }
+
+    /// List the signed download route of the snapshots, under the given base URL of the
+    /// aggregator API, in the `signed_locations` of the snapshot messages
+    pub fn with_snapshot_signed_download_base_url(mut self, base_url: String) -> Self {
+        self.snapshot_signed_download_base_url = Some(base_url);
+        self
+    }
 }
 
 #[async_trait]
@@ -106,12 +127,22 @@ impl MessageService for MithrilMessageService {
             .await
     }
 
+    async fn get_certificate_protocol_message(
+        &self,
+        certificate_hash: &str,
+    ) -> StdResult<Option<CertificateProtocolMessage>> {
+        self.certificate_repository
+            .get_certificate(certificate_hash)
+            .await
+    }
+
     async fn get_certificate_list_message(
         &self,
+        filters: &CertificateListFilters,
         limit: usize,
     ) -> StdResult<CertificateListMessage> {
         self.certificate_repository
-            .get_latest_certificates(limit)
+            .get_latest_certificates_with_filters(filters, limit)
             .await
     }
 
@@ -124,7 +155,21 @@ impl MessageService for MithrilMessageService {
             .get_signed_entity(signed_entity_id)
             .await?;
 
-        signed_entity.map(|s| s.try_into()).transpose()
+        let message: Option<SnapshotMessage> = signed_entity.map(|s| s.try_into()).transpose()?;
+
+        Ok(message.map(|message| SnapshotMessage {
+            signed_locations: self
+                .snapshot_signed_download_base_url
+                .as_ref()
+                .map(|base_url| {
+                    vec![format!(
+                        "{}/artifact/snapshot/{}/download/signed",
+                        base_url.trim_end_matches('/'),
+                        message.digest
+                    )]
+                }),
+            ..message
+        }))
     }
 
     async fn get_snapshot_list_message(&self, limit: usize) -> StdResult<SnapshotListMessage> {
@@ -209,6 +254,8 @@ mod tests {
     };
     use crate::Configuration;
 
+    use super::{MessageService, MithrilMessageService};
+
     #[tokio::test]
     async fn get_no_certificate() {
         // setup
@@ -248,6 +295,50 @@ mod tests {
         assert_eq!(genesis_certificate.hash, certificate_message.hash);
     }
 
+    #[tokio::test]
+    async fn get_certificate_protocol_message() {
+        let configuration = Configuration::new_sample();
+        let mut dep_builder = DependenciesBuilder::new(configuration);
+        let repository = dep_builder.get_certificate_repository().await.unwrap();
+        let service = dep_builder.get_message_service().await.unwrap();
+        let fixture = MithrilFixtureBuilder::default().with_signers(3).build();
+        let genesis_certificate = fixture.create_genesis_certificate("whatever", Epoch(2), 1);
+        repository
+            .create_certificate(genesis_certificate.clone())
+            .await
+            .unwrap();
+
+        let protocol_message = service
+            .get_certificate_protocol_message(&genesis_certificate.hash)
+            .await
+            .unwrap()
+            .expect("There should be a certificate protocol message.");
+
+        assert_eq!(genesis_certificate.hash, protocol_message.certificate_hash);
+        assert_eq!(
+            genesis_certificate.protocol_message,
+            protocol_message.protocol_message()
+        );
+        assert_eq!(
+            genesis_certificate.signed_message,
+            protocol_message.protocol_message().compute_hash()
+        );
+    }
+
+    #[tokio::test]
+    async fn get_no_certificate_protocol_message() {
+        let configuration = Configuration::new_sample();
+        let mut dep_builder = DependenciesBuilder::new(configuration);
+        let service = dep_builder.get_message_service().await.unwrap();
+
+        let protocol_message = service
+            .get_certificate_protocol_message("whatever")
+            .await
+            .unwrap();
+
+        assert!(protocol_message.is_none());
+    }
+
     #[tokio::test]
     async fn get_last_certificates() {
         let configuration = Configuration::new_sample();
@@ -267,10 +358,27 @@ mod tests {
             .unwrap();
 
         // test
-        let certificate_messages = service.get_certificate_list_message(5).await.unwrap();
+        let certificate_messages = service
+            .get_certificate_list_message(&CertificateListFilters::default(), 5)
+            .await
+            .unwrap();
 
         assert_eq!(2, certificate_messages.len());
         assert_eq!(last_certificate_hash, certificate_messages[0].hash);
+
+        let certificate_messages = service
+            .get_certificate_list_message(
+                &CertificateListFilters {
+                    epoch_to: Some(Epoch(2)),
+                    ..CertificateListFilters::default()
+                },
+                5,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(1, certificate_messages.len());
+        assert_eq!(certificates[0].hash, certificate_messages[0].hash);
     }
 
     #[tokio::test]
@@ -314,6 +422,44 @@ mod tests {
         assert_eq!(message, response);
     }
 
+    #[tokio::test]
+    async fn get_snapshot_with_signed_download_location() {
+        let entity = SignedEntity::<Snapshot>::dummy();
+        let digest = entity.artifact.digest.clone();
+        let record = SignedEntityRecord {
+            signed_entity_id: entity.signed_entity_id.clone(),
+            signed_entity_type: entity.signed_entity_type.clone(),
+            certificate_id: entity.certificate_id.clone(),
+            artifact: serde_json::to_string(&entity.artifact).unwrap(),
+            created_at: entity.created_at,
+        };
+        let mut storer = MockSignedEntityStorer::new();
+        storer
+            .expect_get_signed_entity()
+            .return_once(|_| Ok(Some(record)))
+            .once();
+        let configuration = Configuration::new_sample();
+        let mut dep_builder = DependenciesBuilder::new(configuration);
+        let service = MithrilMessageService::new(
+            dep_builder.get_certificate_repository().await.unwrap(),
+            Arc::new(storer),
+        )
+        .with_snapshot_signed_download_base_url("https://aggregator/api/".to_string());
+
+        let response = service
+            .get_snapshot_message("whatever")
+            .await
+            .unwrap()
+            .expect("A SnapshotMessage was expected.");
+
+        assert_eq!(
+            Some(vec![format!(
+                "https://aggregator/api/artifact/snapshot/{digest}/download/signed"
+            )]),
+            response.signed_locations
+        );
+    }
+
     #[tokio::test]
     async fn get_snapshot_list_message() {
         let entity = SignedEntity::<Snapshot>::dummy();
@@ -6,23 +6,50 @@
 //! * StakeEntity: fetches Cardano stake distribution information
 //! * Certifier: registers signers and create certificates once ready
 //! * SignedEntity: provides information about signed entities.
+//! * Pruning: enforces the retention policies of the database.
+//! * StaleSnapshot: detects when the snapshots are not certified at their expected cadence.
+//! * ImmutableFileLag: detects when the Cardano node database stops progressing.
+//! * SignatureAuthenticator: authenticates the single signatures sent by the signers.
+//! * ConfigurationReloader: reloads the settings that can be changed without a restart.
+//! * QuorumSimulation: simulates the probability to reach the quorum with given protocol parameters.
+//! * Status: assembles the build, runtime and system information of the aggregator.
 //!
 //! Each service is defined by a public API (a trait) that is used in the controllers (runtimes).
 
 mod cardano_transactions_importer;
 mod certifier;
+mod configuration_reloader;
 mod epoch_service;
+mod immutable_file_lag;
 mod message;
+mod proof_budget;
 mod prover;
+mod prover_cache;
+mod pruning;
+mod quorum_simulation;
+mod signature_authenticator;
 mod signed_entity;
+mod signed_entity_lock;
 mod stake_distribution;
+mod stale_snapshot;
+mod status;
 mod ticker;
 
 pub use cardano_transactions_importer::*;
 pub use certifier::*;
+pub use configuration_reloader::*;
 pub use epoch_service::*;
+pub use immutable_file_lag::*;
 pub use message::*;
+pub use proof_budget::*;
 pub use prover::*;
+pub use prover_cache::*;
+pub use pruning::*;
+pub use quorum_simulation::*;
+pub use signature_authenticator::*;
 pub use signed_entity::*;
+pub use signed_entity_lock::*;
 pub use stake_distribution::*;
+pub use stale_snapshot::*;
+pub use status::*;
 pub use ticker::*;
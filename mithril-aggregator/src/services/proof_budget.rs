@@ -0,0 +1,411 @@
+use async_trait::async_trait;
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use mithril_common::{
+    entities::{BlockRange, TransactionHash},
+    StdResult,
+};
+
+use crate::services::TransactionsRetriever;
+
+/// Cost of a Cardano transactions proof request
+///
+/// The work of the prover grows with the number of requested transactions and with the number of
+/// block ranges that contain them (a Merkle tree is computed for each of these block ranges), so
+/// the cost of a request is the product of both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofRequestCost {
+    /// Number of requested transactions
+    pub transactions: u64,
+
+    /// Number of block ranges that contain the requested transactions
+    pub block_ranges: u64,
+}
+
+impl ProofRequestCost {
+    /// Value of the cost, a request is never free even if none of its transactions are found
+    pub fn value(&self) -> u64 {
+        self.transactions.saturating_mul(self.block_ranges.max(1))
+    }
+
+    /// Maximum number of transactions per request that guarantees a cost lower than the given
+    /// maximum cost when this request is split.
+    pub fn max_transactions_per_request(&self, max_cost: u64) -> u64 {
+        (max_cost / self.block_ranges.max(1)).max(1)
+    }
+}
+
+/// Errors returned when a proof request exceeds a budget
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ProofBudgetError {
+    /// The cost of the request exceeds the maximum cost of a single request.
+    #[error("The proof request costs {cost} ({transactions} transactions in {block_ranges} block ranges) but a request can not cost more than {max_cost}: split it into requests of at most {max_transactions_per_request} transactions.")]
+    RequestOverBudget {
+        /// Cost of the request
+        cost: u64,
+        /// Maximum cost of a request
+        max_cost: u64,
+        /// Number of requested transactions
+        transactions: u64,
+        /// Number of block ranges that contain the requested transactions
+        block_ranges: u64,
+        /// Number of transactions per request that fits in the budget
+        max_transactions_per_request: u64,
+    },
+
+    /// The cost of the request exceeds the remaining budget of the client.
+    #[error("The proof request costs {cost} but only {remaining_budget} remains in the client budget: retry in {} seconds, or split it into requests of at most {max_transactions_per_request} transactions.", .retry_after.as_secs())]
+    ClientOverBudget {
+        /// Cost of the request
+        cost: u64,
+        /// Remaining budget of the client in the current window
+        remaining_budget: u64,
+        /// Time before the budget of the client is replenished
+        retry_after: Duration,
+        /// Number of transactions per request that fits in the remaining budget
+        max_transactions_per_request: u64,
+    },
+}
+
+/// Budgeting of the Cardano transactions proof requests, used to prevent that cheap requests
+/// make the aggregator do a large amount of work.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ProofBudgetService: Sync + Send {
+    /// Compute the cost of a proof request and charge it to the budget of the client.
+    ///
+    /// Fails with a [ProofBudgetError] if the request exceeds the maximum cost of a request or
+    /// the remaining budget of the client, in which case nothing is charged.
+    async fn charge_request(
+        &self,
+        client: &str,
+        transaction_hashes: &[TransactionHash],
+    ) -> StdResult<ProofRequestCost>;
+}
+
+struct ClientBudget {
+    window_start: Instant,
+    spent: u64,
+}
+
+/// Mithril proof budget service
+pub struct MithrilProofBudgetService {
+    transaction_retriever: Arc<dyn TransactionsRetriever>,
+    max_request_cost: u64,
+    client_budget: u64,
+    budget_window: Duration,
+    clients_budgets: Mutex<HashMap<String, ClientBudget>>,
+}
+
+impl MithrilProofBudgetService {
+    /// Create a new proof budget service, each client can spend `client_budget` in every
+    /// `budget_window`.
+    pub fn new(
+        transaction_retriever: Arc<dyn TransactionsRetriever>,
+        max_request_cost: u64,
+        client_budget: u64,
+        budget_window: Duration,
+    ) -> Self {
+        Self {
+            transaction_retriever,
+            max_request_cost,
+            client_budget,
+            budget_window,
+            clients_budgets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn compute_cost(
+        &self,
+        transaction_hashes: &[TransactionHash],
+    ) -> StdResult<ProofRequestCost> {
+        let transactions = self
+            .transaction_retriever
+            .get_by_hashes(transaction_hashes.to_vec())
+            .await?;
+        let block_ranges = transactions
+            .iter()
+            .map(|t| BlockRange::from_block_number(t.block_number))
+            .collect::<BTreeSet<_>>();
+
+        Ok(ProofRequestCost {
+            transactions: transaction_hashes.len() as u64,
+            block_ranges: block_ranges.len() as u64,
+        })
+    }
+
+    fn check_request_cost(&self, cost: &ProofRequestCost) -> Result<(), ProofBudgetError> {
+        if cost.value() > self.max_request_cost {
+            return Err(ProofBudgetError::RequestOverBudget {
+                cost: cost.value(),
+                max_cost: self.max_request_cost,
+                transactions: cost.transactions,
+                block_ranges: cost.block_ranges,
+                max_transactions_per_request: cost
+                    .max_transactions_per_request(self.max_request_cost),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check the cost against the remaining budget of the client, and charge it if `charge` is
+    /// set and it fits.
+    async fn check_client_budget(
+        &self,
+        client: &str,
+        cost: &ProofRequestCost,
+        charge: bool,
+    ) -> Result<(), ProofBudgetError> {
+        let now = Instant::now();
+        let mut clients_budgets = self.clients_budgets.lock().await;
+        // Forget the clients whose budget window is over, their budget is replenished anyway
+        clients_budgets
+            .retain(|_, budget| now.duration_since(budget.window_start) < self.budget_window);
+        let client_budget = clients_budgets
+            .entry(client.to_string())
+            .or_insert(ClientBudget {
+                window_start: now,
+                spent: 0,
+            });
+        let remaining_budget = self.client_budget.saturating_sub(client_budget.spent);
+        if cost.value() > remaining_budget {
+            return Err(ProofBudgetError::ClientOverBudget {
+                cost: cost.value(),
+                remaining_budget,
+                retry_after: self
+                    .budget_window
+                    .saturating_sub(now.duration_since(client_budget.window_start)),
+                max_transactions_per_request: cost.max_transactions_per_request(remaining_budget),
+            });
+        }
+        if charge {
+            client_budget.spent += cost.value();
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ProofBudgetService for MithrilProofBudgetService {
+    async fn charge_request(
+        &self,
+        client: &str,
+        transaction_hashes: &[TransactionHash],
+    ) -> StdResult<ProofRequestCost> {
+        // A request costs at least its number of transactions: the caps are checked with this
+        // lower bound first so that an over budget request never reaches the database.
+        let minimum_cost = ProofRequestCost {
+            transactions: transaction_hashes.len() as u64,
+            block_ranges: 1,
+        };
+        self.check_request_cost(&minimum_cost)?;
+        self.check_client_budget(client, &minimum_cost, false)
+            .await?;
+
+        let cost = self.compute_cost(transaction_hashes).await?;
+        self.check_request_cost(&cost)?;
+        self.check_client_budget(client, &cost, true).await?;
+
+        Ok(cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::CardanoTransaction;
+
+    use crate::services::MockTransactionsRetriever;
+
+    use super::*;
+
+    fn transaction_hashes(number: usize) -> Vec<TransactionHash> {
+        (0..number).map(|i| format!("tx-{i}")).collect()
+    }
+
+    /// Build a budget service where the requested transactions are spread in the given number of
+    /// block ranges
+    fn build_service(
+        block_ranges: u64,
+        max_request_cost: u64,
+        client_budget: u64,
+        budget_window: Duration,
+    ) -> MithrilProofBudgetService {
+        let mut transaction_retriever = MockTransactionsRetriever::new();
+        transaction_retriever
+            .expect_get_by_hashes()
+            .returning(move |hashes| {
+                Ok(hashes
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, hash)| {
+                        let block_number = (i as u64 % block_ranges) * BlockRange::LENGTH;
+                        CardanoTransaction::new(hash, block_number, 1, "block_hash", 1)
+                    })
+                    .collect())
+            });
+
+        MithrilProofBudgetService::new(
+            Arc::new(transaction_retriever),
+            max_request_cost,
+            client_budget,
+            budget_window,
+        )
+    }
+
+    #[test]
+    fn cost_is_the_product_of_transactions_and_block_ranges() {
+        let cost = ProofRequestCost {
+            transactions: 20,
+            block_ranges: 5,
+        };
+
+        assert_eq!(100, cost.value());
+        assert_eq!(8, cost.max_transactions_per_request(40));
+        assert_eq!(
+            20,
+            ProofRequestCost {
+                transactions: 20,
+                block_ranges: 0,
+            }
+            .value()
+        );
+    }
+
+    #[tokio::test]
+    async fn charge_request_within_budgets() {
+        let service = build_service(2, 100, 1000, Duration::from_secs(60));
+
+        let cost = service
+            .charge_request("client", &transaction_hashes(10))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ProofRequestCost {
+                transactions: 10,
+                block_ranges: 2,
+            },
+            cost
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_request_costing_more_than_the_maximum_request_cost() {
+        let service = build_service(5, 100, 1000, Duration::from_secs(60));
+
+        let error = service
+            .charge_request("client", &transaction_hashes(30))
+            .await
+            .expect_err("the request should be over budget");
+
+        assert_eq!(
+            Some(&ProofBudgetError::RequestOverBudget {
+                cost: 150,
+                max_cost: 100,
+                transactions: 30,
+                block_ranges: 5,
+                max_transactions_per_request: 20,
+            }),
+            error.downcast_ref::<ProofBudgetError>()
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_request_exceeding_the_remaining_budget_of_the_client() {
+        let service = build_service(1, 100, 150, Duration::from_secs(60));
+        service
+            .charge_request("client", &transaction_hashes(100))
+            .await
+            .unwrap();
+
+        let error = service
+            .charge_request("client", &transaction_hashes(60))
+            .await
+            .expect_err("the client should be over budget");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<ProofBudgetError>(),
+                Some(ProofBudgetError::ClientOverBudget {
+                    cost: 60,
+                    remaining_budget: 50,
+                    max_transactions_per_request: 50,
+                    ..
+                })
+            ),
+            "Expected 'ClientOverBudget' error but got '{error:?}'"
+        );
+        service
+            .charge_request("another_client", &transaction_hashes(60))
+            .await
+            .expect("the budget of each client should be independent");
+        service
+            .charge_request("client", &transaction_hashes(50))
+            .await
+            .expect("a request within the remaining budget should be accepted");
+    }
+
+    #[tokio::test]
+    async fn reject_request_with_more_transactions_than_the_caps_without_reading_the_database() {
+        let mut transaction_retriever = MockTransactionsRetriever::new();
+        transaction_retriever.expect_get_by_hashes().never();
+        let service = MithrilProofBudgetService::new(
+            Arc::new(transaction_retriever),
+            100,
+            50,
+            Duration::from_secs(60),
+        );
+
+        let error = service
+            .charge_request("client", &transaction_hashes(101))
+            .await
+            .expect_err("the request should be over budget");
+        assert!(
+            matches!(
+                error.downcast_ref::<ProofBudgetError>(),
+                Some(ProofBudgetError::RequestOverBudget { .. })
+            ),
+            "Expected 'RequestOverBudget' error but got '{error:?}'"
+        );
+
+        let error = service
+            .charge_request("client", &transaction_hashes(51))
+            .await
+            .expect_err("the client should be over budget");
+        assert!(
+            matches!(
+                error.downcast_ref::<ProofBudgetError>(),
+                Some(ProofBudgetError::ClientOverBudget { .. })
+            ),
+            "Expected 'ClientOverBudget' error but got '{error:?}'"
+        );
+    }
+
+    #[tokio::test]
+    async fn client_budget_is_replenished_after_the_budget_window() {
+        let service = build_service(1, 100, 100, Duration::from_millis(50));
+        service
+            .charge_request("client", &transaction_hashes(100))
+            .await
+            .unwrap();
+        service
+            .charge_request("client", &transaction_hashes(1))
+            .await
+            .expect_err("the client budget should be exhausted");
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        service
+            .charge_request("client", &transaction_hashes(100))
+            .await
+            .expect("the client budget should be replenished");
+    }
+}
@@ -15,6 +15,8 @@ use mithril_common::{
     StdResult,
 };
 
+use crate::services::{ProverCache, ProverCacheKey};
+
 /// Prover service is the cryptographic engine in charge of producing cryptographic proofs for transactions
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
@@ -48,9 +50,13 @@ pub trait TransactionsRetriever: Sync + Send {
 }
 
 /// Mithril prover
+///
+/// The computed proofs are kept in a [ProverCache] keyed by the beacon and the set of the proven
+/// transactions.
 pub struct MithrilProverService {
     transaction_retriever: Arc<dyn TransactionsRetriever>,
     block_range_root_retriever: Arc<dyn BlockRangeRootRetriever>,
+    cache: Arc<ProverCache>,
 }
 
 impl MithrilProverService {
@@ -58,10 +64,12 @@ impl MithrilProverService {
     pub fn new(
         transaction_retriever: Arc<dyn TransactionsRetriever>,
         block_range_root_retriever: Arc<dyn BlockRangeRootRetriever>,
+        cache: Arc<ProverCache>,
     ) -> Self {
         Self {
             transaction_retriever,
             block_range_root_retriever,
+            cache,
         }
     }
 
@@ -109,6 +117,11 @@ impl ProverService for MithrilProverService {
         up_to: &CardanoDbBeacon,
         transaction_hashes: &[TransactionHash],
     ) -> StdResult<Vec<CardanoTransactionsSetProof>> {
+        let cache_key = ProverCacheKey::new(up_to, transaction_hashes);
+        if let Some(proofs) = self.cache.get(&cache_key) {
+            return Ok(proofs);
+        }
+
         // 1 - Compute the set of block ranges with transactions to prove
         let block_ranges_transactions = self.get_block_ranges(transaction_hashes).await?;
         let block_range_transactions = self
@@ -134,20 +147,23 @@ impl ProverService for MithrilProverService {
         }
 
         // 5 - Compute the proof for all transactions
-        if let Ok(mk_proof) = mk_map.compute_proof(transaction_hashes) {
+        let proofs = if let Ok(mk_proof) = mk_map.compute_proof(transaction_hashes) {
             let transaction_hashes_certified: Vec<TransactionHash> = transaction_hashes
                 .iter()
                 .filter(|hash| mk_proof.contains(&hash.as_str().into()).is_ok())
                 .cloned()
                 .collect();
 
-            Ok(vec![CardanoTransactionsSetProof::new(
+            vec![CardanoTransactionsSetProof::new(
                 transaction_hashes_certified,
                 mk_proof,
-            )])
+            )]
         } else {
-            Ok(vec![])
-        }
+            vec![]
+        };
+        self.cache.insert(cache_key, proofs.clone());
+
+        Ok(proofs)
     }
 }
 
@@ -161,6 +177,8 @@ mod tests {
     use mockall::mock;
     use mockall::predicate::eq;
 
+    use crate::MetricsService;
+
     use super::*;
 
     mock! {
@@ -192,10 +210,12 @@ mod tests {
             let block_range_length = BlockRange::LENGTH as usize;
             let max_transaction_per_block_number =
                 max(1, total_transactions_per_block_range / block_range_length);
+            let block_ranges = BlockRange::all_block_ranges_in(
+                0..(total_block_ranges as u64 * BlockRange::LENGTH),
+            );
             let mut transactions = vec![];
 
-            for i in 0..total_block_ranges {
-                let block_range = BlockRange::from_block_number((i * block_range_length) as u64);
+            for (i, block_range) in block_ranges.enumerate() {
                 for j in 0..total_transactions_per_block_range {
                     let transaction_index = i * total_transactions_per_block_range + j;
                     let block_number =
@@ -336,6 +356,25 @@ mod tests {
         transaction_retriever_mock_config: F,
         block_range_root_retriever_mock_config: G,
     ) -> MithrilProverService
+    where
+        F: FnOnce(&mut MockTransactionsRetriever),
+        G: FnOnce(&mut MockBlockRangeRootRetrieverImpl),
+    {
+        build_prover_with_cache(
+            transaction_retriever_mock_config,
+            block_range_root_retriever_mock_config,
+            Arc::new(ProverCache::new(
+                0,
+                Arc::new(MetricsService::new().unwrap()),
+            )),
+        )
+    }
+
+    fn build_prover_with_cache<F, G>(
+        transaction_retriever_mock_config: F,
+        block_range_root_retriever_mock_config: G,
+        cache: Arc<ProverCache>,
+    ) -> MithrilProverService
     where
         F: FnOnce(&mut MockTransactionsRetriever),
         G: FnOnce(&mut MockBlockRangeRootRetrieverImpl),
@@ -348,6 +387,7 @@ mod tests {
         MithrilProverService::new(
             Arc::new(transaction_retriever),
             Arc::new(block_range_root_retriever),
+            cache,
         )
     }
 
@@ -579,4 +619,67 @@ mod tests {
             .await
             .expect_err("Should have failed because of block range root retriever failure");
     }
+
+    #[tokio::test]
+    async fn compute_proof_once_until_the_cache_is_invalidated() {
+        let total_block_ranges = 5;
+        let total_transactions_per_block_range = 3;
+        let transactions = test_data::generate_transactions(
+            total_block_ranges,
+            total_transactions_per_block_range,
+        );
+        let transactions_to_prove =
+            test_data::filter_transactions_for_indices(&[1, 2, 4], &transactions);
+        let test_data = test_data::build_test_data(&transactions_to_prove, &transactions);
+        let cache = Arc::new(ProverCache::new(
+            10,
+            Arc::new(MetricsService::new().unwrap()),
+        ));
+        let prover = build_prover_with_cache(
+            |retriever_mock| {
+                let transactions_to_prove = transactions_to_prove.clone();
+                retriever_mock
+                    .expect_get_by_hashes()
+                    .returning(move |_| Ok(transactions_to_prove.clone()))
+                    .times(2);
+
+                let all_transactions_in_block_ranges_to_prove =
+                    test_data.all_transactions_in_block_ranges_to_prove.clone();
+                retriever_mock
+                    .expect_get_by_block_ranges()
+                    .returning(move |_| Ok(all_transactions_in_block_ranges_to_prove.clone()))
+                    .times(2);
+            },
+            |block_range_root_retriever_mock| {
+                let block_ranges_map = test_data.block_ranges_map.clone();
+                block_range_root_retriever_mock
+                    .expect_compute_merkle_map_from_block_range_roots()
+                    .returning(move |_| {
+                        Ok(test_data::compute_mk_map_from_block_ranges_map(
+                            block_ranges_map.clone(),
+                        ))
+                    })
+                    .times(2);
+            },
+            cache.clone(),
+        );
+        let mut reversed_transaction_hashes = test_data.transaction_hashes_to_prove.clone();
+        reversed_transaction_hashes.reverse();
+
+        let transactions_set_proof = prover
+            .compute_transactions_proofs(&test_data.beacon, &test_data.transaction_hashes_to_prove)
+            .await
+            .unwrap();
+        let cached_transactions_set_proof = prover
+            .compute_transactions_proofs(&test_data.beacon, &reversed_transaction_hashes)
+            .await
+            .unwrap();
+        assert_eq!(transactions_set_proof, cached_transactions_set_proof);
+
+        cache.invalidate();
+        prover
+            .compute_transactions_proofs(&test_data.beacon, &test_data.transaction_hashes_to_prove)
+            .await
+            .unwrap();
+    }
 }
@@ -0,0 +1,193 @@
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+use slog_scope::debug;
+use std::{collections::BTreeSet, num::NonZeroUsize, sync::Arc, sync::Mutex};
+
+use mithril_common::entities::{CardanoDbBeacon, CardanoTransactionsSetProof, TransactionHash};
+
+use crate::MetricsService;
+
+/// Key of the [ProverCache]: the beacon of the proofs and the digest of the sorted set of the
+/// proven transaction hashes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProverCacheKey {
+    beacon: CardanoDbBeacon,
+    transaction_hashes_digest: String,
+}
+
+impl ProverCacheKey {
+    /// Create the key of the proofs of the given transactions at the given beacon, the order
+    /// and the duplicates of the transaction hashes are not significant.
+    pub fn new(beacon: &CardanoDbBeacon, transaction_hashes: &[TransactionHash]) -> Self {
+        let mut hasher = Sha256::new();
+        for hash in transaction_hashes.iter().collect::<BTreeSet<_>>() {
+            hasher.update(hash.as_bytes());
+            hasher.update([0]);
+        }
+
+        Self {
+            beacon: beacon.clone(),
+            transaction_hashes_digest: hex::encode(hasher.finalize()),
+        }
+    }
+}
+
+/// LRU cache of the proofs computed by the [MithrilProverService][crate::services::MithrilProverService].
+///
+/// The cached proofs are only valid for the transactions known when they were computed: the
+/// cache must be [invalidated][Self::invalidate] when new transactions are imported.
+pub struct ProverCache {
+    entries: Option<Mutex<LruCache<ProverCacheKey, Vec<CardanoTransactionsSetProof>>>>,
+    metrics_service: Arc<MetricsService>,
+}
+
+impl ProverCache {
+    /// Create a cache holding the proofs of at most `capacity` requests, a zero capacity
+    /// disables the cache.
+    pub fn new(capacity: usize, metrics_service: Arc<MetricsService>) -> Self {
+        Self {
+            entries: NonZeroUsize::new(capacity)
+                .map(|capacity| Mutex::new(LruCache::new(capacity))),
+            metrics_service,
+        }
+    }
+
+    /// Get the cached proofs for the given key, if any.
+    pub fn get(&self, key: &ProverCacheKey) -> Option<Vec<CardanoTransactionsSetProof>> {
+        let entries = self.entries.as_ref()?;
+        let proofs = entries.lock().unwrap().get(key).cloned();
+        match proofs {
+            Some(_) => self
+                .metrics_service
+                .proof_cache_hits_since_startup_counter_increase(1),
+            None => self
+                .metrics_service
+                .proof_cache_misses_since_startup_counter_increase(1),
+        }
+
+        proofs
+    }
+
+    /// Cache the proofs computed for the given key, evicting the least recently used ones if the
+    /// cache is full.
+    pub fn insert(&self, key: ProverCacheKey, proofs: Vec<CardanoTransactionsSetProof>) {
+        if let Some(entries) = &self.entries {
+            entries.lock().unwrap().put(key, proofs);
+        }
+    }
+
+    /// Remove all the cached proofs.
+    pub fn invalidate(&self) {
+        if let Some(entries) = &self.entries {
+            debug!("ProverCache: invalidating the cached proofs");
+            entries.lock().unwrap().clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn beacon(immutable_file_number: u64) -> CardanoDbBeacon {
+        CardanoDbBeacon::new("devnet", 1, immutable_file_number)
+    }
+
+    fn hashes(hashes: &[&str]) -> Vec<TransactionHash> {
+        hashes.iter().map(|hash| hash.to_string()).collect()
+    }
+
+    fn proofs(transaction_hashes: &[TransactionHash]) -> Vec<CardanoTransactionsSetProof> {
+        let leaves: Vec<_> = transaction_hashes
+            .iter()
+            .map(|hash| (1, hash.to_owned()))
+            .collect();
+
+        vec![CardanoTransactionsSetProof::from_leaves(&leaves).unwrap()]
+    }
+
+    #[test]
+    fn key_does_not_depend_on_the_order_and_duplicates_of_the_hashes() {
+        assert_eq!(
+            ProverCacheKey::new(&beacon(1), &hashes(&["tx-1", "tx-2"])),
+            ProverCacheKey::new(&beacon(1), &hashes(&["tx-2", "tx-1", "tx-2"]))
+        );
+        assert_ne!(
+            ProverCacheKey::new(&beacon(1), &hashes(&["tx-1", "tx-2"])),
+            ProverCacheKey::new(&beacon(2), &hashes(&["tx-1", "tx-2"]))
+        );
+        assert_ne!(
+            ProverCacheKey::new(&beacon(1), &hashes(&["tx-1", "tx-2"])),
+            ProverCacheKey::new(&beacon(1), &hashes(&["tx-1tx-2"]))
+        );
+    }
+
+    #[test]
+    fn get_cached_proofs_and_record_hits_and_misses() {
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let cache = ProverCache::new(2, metrics_service.clone());
+        let transaction_hashes = hashes(&["tx-1", "tx-2"]);
+        let key = ProverCacheKey::new(&beacon(1), &transaction_hashes);
+
+        assert_eq!(None, cache.get(&key));
+        cache.insert(key.clone(), proofs(&transaction_hashes));
+
+        assert_eq!(Some(proofs(&transaction_hashes)), cache.get(&key));
+        assert_eq!(
+            1,
+            metrics_service.proof_cache_hits_since_startup_counter_get()
+        );
+        assert_eq!(
+            1,
+            metrics_service.proof_cache_misses_since_startup_counter_get()
+        );
+    }
+
+    #[test]
+    fn evict_the_least_recently_used_proofs() {
+        let cache = ProverCache::new(2, Arc::new(MetricsService::new().unwrap()));
+        let transaction_hashes = hashes(&["tx-1"]);
+        let keys: Vec<_> = (1..=3)
+            .map(|immutable_file_number| {
+                ProverCacheKey::new(&beacon(immutable_file_number), &transaction_hashes)
+            })
+            .collect();
+
+        cache.insert(keys[0].clone(), proofs(&transaction_hashes));
+        cache.insert(keys[1].clone(), proofs(&transaction_hashes));
+        cache.get(&keys[0]);
+        cache.insert(keys[2].clone(), proofs(&transaction_hashes));
+
+        assert!(cache.get(&keys[0]).is_some());
+        assert!(cache.get(&keys[1]).is_none());
+        assert!(cache.get(&keys[2]).is_some());
+    }
+
+    #[test]
+    fn invalidate_remove_all_cached_proofs() {
+        let cache = ProverCache::new(2, Arc::new(MetricsService::new().unwrap()));
+        let transaction_hashes = hashes(&["tx-1"]);
+        let key = ProverCacheKey::new(&beacon(1), &transaction_hashes);
+        cache.insert(key.clone(), proofs(&transaction_hashes));
+
+        cache.invalidate();
+
+        assert_eq!(None, cache.get(&key));
+    }
+
+    #[test]
+    fn zero_capacity_disables_the_cache() {
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let cache = ProverCache::new(0, metrics_service.clone());
+        let transaction_hashes = hashes(&["tx-1"]);
+        let key = ProverCacheKey::new(&beacon(1), &transaction_hashes);
+
+        cache.insert(key.clone(), proofs(&transaction_hashes));
+
+        assert_eq!(None, cache.get(&key));
+        assert_eq!(
+            0,
+            metrics_service.proof_cache_misses_since_startup_counter_get()
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use slog_scope::{debug, info};
+use std::sync::Arc;
+
+use mithril_common::entities::Epoch;
+use mithril_common::StdResult;
+
+use crate::database::repository::{OpenMessageRepository, SingleSignatureRepository};
+use crate::MetricsService;
+
+/// Retention policies of the data that are pruned at each epoch transition.
+///
+/// The retentions are given in number of epochs, including the current one, for which the data
+/// are kept. The certificates are always kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Number of epochs for which the open messages are kept
+    pub open_message_epochs: u64,
+
+    /// Number of epochs for which the single signatures are kept
+    pub single_signature_epochs: u64,
+}
+
+impl RetentionPolicy {
+    /// Create a retention policy, the current epoch is always kept and the single signatures are
+    /// never kept longer than their open messages since they are deleted along with them.
+    pub fn new(open_message_epochs: u64, single_signature_epochs: Option<u64>) -> Self {
+        let open_message_epochs = open_message_epochs.max(1);
+        let single_signature_epochs = single_signature_epochs
+            .unwrap_or(open_message_epochs)
+            .clamp(1, open_message_epochs);
+
+        Self {
+            open_message_epochs,
+            single_signature_epochs,
+        }
+    }
+
+    /// First epoch of the open messages kept at the given current epoch.
+    pub fn open_message_threshold(&self, current_epoch: Epoch) -> Epoch {
+        current_epoch - (self.open_message_epochs - 1)
+    }
+
+    /// First epoch of the open messages which single signatures are kept at the given current epoch.
+    pub fn single_signature_threshold(&self, current_epoch: Epoch) -> Epoch {
+        current_epoch - (self.single_signature_epochs - 1)
+    }
+}
+
+/// Number of rows reclaimed by a pruning
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruningReport {
+    /// Number of open messages removed
+    pub open_messages: usize,
+
+    /// Number of single signatures removed
+    pub single_signatures: usize,
+}
+
+/// The PruningService enforces the retention policies of the aggregator database.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PruningService: Sync + Send {
+    /// Prune the data that are out of the retention policies at the given current epoch.
+    async fn prune(&self, current_epoch: Epoch) -> StdResult<PruningReport>;
+}
+
+/// Mithril aggregator implementation of the [PruningService]
+pub struct MithrilPruningService {
+    retention_policy: RetentionPolicy,
+    open_message_repository: Arc<OpenMessageRepository>,
+    single_signature_repository: Arc<SingleSignatureRepository>,
+    metrics_service: Arc<MetricsService>,
+}
+
+impl MithrilPruningService {
+    /// MithrilPruningService factory
+    pub fn new(
+        retention_policy: RetentionPolicy,
+        open_message_repository: Arc<OpenMessageRepository>,
+        single_signature_repository: Arc<SingleSignatureRepository>,
+        metrics_service: Arc<MetricsService>,
+    ) -> Self {
+        Self {
+            retention_policy,
+            open_message_repository,
+            single_signature_repository,
+            metrics_service,
+        }
+    }
+}
+
+#[async_trait]
+impl PruningService for MithrilPruningService {
+    async fn prune(&self, current_epoch: Epoch) -> StdResult<PruningReport> {
+        debug!("PruningService::prune(current_epoch: {current_epoch:?})"; "retention_policy" => ?self.retention_policy);
+
+        // The single signatures are pruned first so that the ones removed along with their open
+        // messages are counted.
+        let single_signatures_threshold = self
+            .retention_policy
+            .single_signature_threshold(current_epoch);
+        let single_signatures = self
+            .single_signature_repository
+            .prune(single_signatures_threshold)
+            .await
+            .with_context(|| {
+                format!("Pruning service can not prune single signatures older than epoch '{single_signatures_threshold}'")
+            })?;
+
+        let open_messages_threshold = self.retention_policy.open_message_threshold(current_epoch);
+        let open_messages = self
+            .open_message_repository
+            .clean_epoch(open_messages_threshold)
+            .await
+            .with_context(|| {
+                format!("Pruning service can not prune open messages older than epoch '{open_messages_threshold}'")
+            })?;
+
+        self.metrics_service
+            .pruned_single_signatures_since_startup_counter_increase(single_signatures as u64);
+        self.metrics_service
+            .pruned_open_messages_since_startup_counter_increase(open_messages as u64);
+        info!(
+            "PruningService: pruned {open_messages} open messages and {single_signatures} single signatures at epoch {current_epoch}"
+        );
+
+        Ok(PruningReport {
+            open_messages,
+            single_signatures,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::{ProtocolMessage, SignedEntityType};
+    use mithril_persistence::sqlite::SqliteConnection;
+
+    use crate::database::record::SingleSignatureRecord;
+    use crate::database::test_helper::{
+        insert_epoch_settings, insert_single_signatures_in_db, main_db_connection,
+        setup_single_signature_records,
+    };
+
+    use super::*;
+
+    async fn insert_open_message_with_signatures(
+        connection: &SqliteConnection,
+        repository: &OpenMessageRepository,
+        epoch: Epoch,
+        total_signer: u64,
+    ) {
+        let open_message = repository
+            .create_open_message(
+                epoch,
+                &SignedEntityType::MithrilStakeDistribution(epoch),
+                &ProtocolMessage::new(),
+            )
+            .await
+            .unwrap();
+        let single_signature_records = setup_single_signature_records(1, 1, total_signer)
+            .into_iter()
+            .map(|record| SingleSignatureRecord {
+                open_message_id: open_message.open_message_id,
+                ..record
+            })
+            .collect();
+        insert_single_signatures_in_db(connection, single_signature_records).unwrap();
+    }
+
+    async fn setup_pruning_service(
+        retention_policy: RetentionPolicy,
+        epochs: &[u64],
+    ) -> (
+        MithrilPruningService,
+        Arc<OpenMessageRepository>,
+        Arc<MetricsService>,
+    ) {
+        let connection = main_db_connection().unwrap();
+        insert_epoch_settings(&connection, epochs).unwrap();
+        let connection = Arc::new(connection);
+        let open_message_repository = Arc::new(OpenMessageRepository::new(connection.clone()));
+        for epoch in epochs {
+            insert_open_message_with_signatures(
+                &connection,
+                &open_message_repository,
+                Epoch(*epoch),
+                2,
+            )
+            .await;
+        }
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let pruning_service = MithrilPruningService::new(
+            retention_policy,
+            open_message_repository.clone(),
+            Arc::new(SingleSignatureRepository::new(connection)),
+            metrics_service.clone(),
+        );
+
+        (pruning_service, open_message_repository, metrics_service)
+    }
+
+    #[test]
+    fn retention_policy_keeps_at_least_the_current_epoch() {
+        assert_eq!(
+            RetentionPolicy {
+                open_message_epochs: 1,
+                single_signature_epochs: 1
+            },
+            RetentionPolicy::new(0, Some(0))
+        );
+    }
+
+    #[test]
+    fn retention_policy_does_not_keep_single_signatures_longer_than_open_messages() {
+        assert_eq!(
+            RetentionPolicy {
+                open_message_epochs: 3,
+                single_signature_epochs: 3
+            },
+            RetentionPolicy::new(3, None)
+        );
+        assert_eq!(
+            RetentionPolicy {
+                open_message_epochs: 3,
+                single_signature_epochs: 2
+            },
+            RetentionPolicy::new(3, Some(2))
+        );
+        assert_eq!(
+            RetentionPolicy {
+                open_message_epochs: 3,
+                single_signature_epochs: 3
+            },
+            RetentionPolicy::new(3, Some(5))
+        );
+    }
+
+    #[test]
+    fn retention_policy_thresholds() {
+        let policy = RetentionPolicy::new(3, Some(2));
+
+        assert_eq!(Epoch(8), policy.open_message_threshold(Epoch(10)));
+        assert_eq!(Epoch(9), policy.single_signature_threshold(Epoch(10)));
+        assert_eq!(Epoch(0), policy.open_message_threshold(Epoch(1)));
+    }
+
+    #[tokio::test]
+    async fn prune_open_messages_and_single_signatures_out_of_the_retention_policy() {
+        let (pruning_service, open_message_repository, metrics_service) =
+            setup_pruning_service(RetentionPolicy::new(3, Some(2)), &[1, 2, 3, 4, 5]).await;
+
+        let report = pruning_service.prune(Epoch(5)).await.unwrap();
+
+        assert_eq!(
+            PruningReport {
+                open_messages: 2,
+                single_signatures: 6,
+            },
+            report
+        );
+        for (epoch, expected_signatures) in [(3, Some(0)), (4, Some(2)), (5, Some(2)), (2, None)] {
+            let open_message = open_message_repository
+                .get_open_message_with_single_signatures(
+                    &SignedEntityType::MithrilStakeDistribution(Epoch(epoch)),
+                )
+                .await
+                .unwrap();
+            assert_eq!(
+                expected_signatures,
+                open_message.map(|message| message.single_signatures.len()),
+                "Unexpected single signatures for the open message of epoch {epoch}"
+            );
+        }
+        assert_eq!(
+            2,
+            metrics_service.pruned_open_messages_since_startup_counter_get()
+        );
+        assert_eq!(
+            6,
+            metrics_service.pruned_single_signatures_since_startup_counter_get()
+        );
+    }
+
+    #[tokio::test]
+    async fn prune_keep_only_the_current_epoch_with_the_default_retention_policy() {
+        let (pruning_service, open_message_repository, _) =
+            setup_pruning_service(RetentionPolicy::new(1, None), &[1, 2]).await;
+
+        let report = pruning_service.prune(Epoch(2)).await.unwrap();
+
+        assert_eq!(
+            PruningReport {
+                open_messages: 1,
+                single_signatures: 2,
+            },
+            report
+        );
+        assert!(open_message_repository
+            .get_open_message(&SignedEntityType::MithrilStakeDistribution(Epoch(1)))
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
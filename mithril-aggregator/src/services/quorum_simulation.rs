@@ -0,0 +1,378 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use mithril_common::entities::{Epoch, ProtocolParameters, Stake};
+use mithril_common::StdResult;
+
+use crate::dependency_injection::EpochServiceWrapper;
+use crate::entities::{QuorumSimulationMessage, QuorumSimulationScenarioMessage};
+
+/// Probability to reach the quorum targeted by a simulation when none is given
+pub const DEFAULT_QUORUM_SIMULATION_TARGET_PROBABILITY: f64 = 0.99;
+
+/// Maximum number of lotteries of a simulation, the computation of the quorum probability is
+/// linear in their number
+pub const MAX_QUORUM_SIMULATION_LOTTERIES: u64 = 100_000;
+
+/// Maximum number of signers with the largest stakes removed by the scenarios of a simulation
+const MAX_ABSENT_LARGEST_SIGNERS: usize = 5;
+
+/// Number of iterations of the search of the minimum participating stake ratio, enough to
+/// compute it with a precision far below a lovelace
+const MIN_STAKE_RATIO_SEARCH_ITERATIONS: usize = 64;
+
+/// Parameters of a quorum simulation, the protocol parameters of the current epoch are used for
+/// the ones that are not given.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuorumSimulationParameters {
+    /// Quorum parameter
+    pub k: Option<u64>,
+
+    /// Security parameter (number of lotteries)
+    pub m: Option<u64>,
+
+    /// f in phi(w) = 1 - (1 - f)^w
+    pub phi_f: Option<f64>,
+
+    /// Probability to reach the quorum targeted by the simulation
+    pub target_probability: Option<f64>,
+}
+
+/// Errors dedicated to the QuorumSimulationService.
+#[derive(Debug, Error, PartialEq)]
+pub enum QuorumSimulationError {
+    /// The parameters of the simulation are not valid.
+    #[error("Invalid quorum simulation parameters: {0}")]
+    InvalidParameters(String),
+}
+
+/// The QuorumSimulationService simulates the probability to reach the quorum of the protocol
+/// with the stake distribution of the registered signers, to help tuning the protocol parameters.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait QuorumSimulationService: Sync + Send {
+    /// Simulate the quorum with the signers registered for the current epoch, fails with a
+    /// [QuorumSimulationError] if the parameters are not valid.
+    async fn simulate(
+        &self,
+        parameters: QuorumSimulationParameters,
+    ) -> StdResult<QuorumSimulationMessage>;
+}
+
+/// Mithril aggregator implementation of the [QuorumSimulationService]
+pub struct MithrilQuorumSimulationService {
+    epoch_service: EpochServiceWrapper,
+}
+
+impl MithrilQuorumSimulationService {
+    /// MithrilQuorumSimulationService factory
+    pub fn new(epoch_service: EpochServiceWrapper) -> Self {
+        Self { epoch_service }
+    }
+
+    fn check_parameters(
+        protocol_parameters: &ProtocolParameters,
+        target_probability: f64,
+    ) -> Result<(), QuorumSimulationError> {
+        if protocol_parameters.k == 0 || protocol_parameters.k > protocol_parameters.m {
+            return Err(QuorumSimulationError::InvalidParameters(format!(
+                "k must be greater than 0 and lower than or equal to m (k={}, m={})",
+                protocol_parameters.k, protocol_parameters.m
+            )));
+        }
+        if protocol_parameters.m > MAX_QUORUM_SIMULATION_LOTTERIES {
+            return Err(QuorumSimulationError::InvalidParameters(format!(
+                "m must be lower than or equal to {MAX_QUORUM_SIMULATION_LOTTERIES} (m={})",
+                protocol_parameters.m
+            )));
+        }
+        let is_valid_phi_f = protocol_parameters.phi_f > 0.0 && protocol_parameters.phi_f <= 1.0;
+        if !is_valid_phi_f {
+            return Err(QuorumSimulationError::InvalidParameters(format!(
+                "phi_f must be in ]0, 1] (phi_f={})",
+                protocol_parameters.phi_f
+            )));
+        }
+        let is_valid_target_probability = target_probability > 0.0 && target_probability < 1.0;
+        if !is_valid_target_probability {
+            return Err(QuorumSimulationError::InvalidParameters(format!(
+                "target_probability must be in ]0, 1[ (target_probability={target_probability})"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Minimum participating stake ratio to reach the quorum with the target probability, the
+    /// quorum probability grows with the participating stake so it's found by bisection.
+    fn compute_min_participating_stake_ratio(
+        protocol_parameters: &ProtocolParameters,
+        target_probability: f64,
+    ) -> Option<f64> {
+        if protocol_parameters.quorum_probability(1.0) < target_probability {
+            return None;
+        }
+
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..MIN_STAKE_RATIO_SEARCH_ITERATIONS {
+            let middle = (low + high) / 2.0;
+            if protocol_parameters.quorum_probability(middle) >= target_probability {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+
+        Some(high)
+    }
+
+    /// Compute the simulation for the given stakes of the signers, sorted in descending order.
+    fn compute_simulation(
+        epoch: Epoch,
+        protocol_parameters: ProtocolParameters,
+        target_probability: f64,
+        stakes: Vec<Stake>,
+    ) -> QuorumSimulationMessage {
+        let total_stake: u64 = stakes.iter().sum();
+        let stake_ratio = |stake: u64| {
+            if total_stake == 0 {
+                0.0
+            } else {
+                stake as f64 / total_stake as f64
+            }
+        };
+
+        let scenarios = (0..=MAX_ABSENT_LARGEST_SIGNERS.min(stakes.len().saturating_sub(1)))
+            .map(|absent_largest_signers| {
+                let participating_stake_ratio =
+                    stake_ratio(stakes[absent_largest_signers..].iter().sum());
+
+                QuorumSimulationScenarioMessage {
+                    absent_largest_signers: absent_largest_signers as u64,
+                    participating_stake_ratio,
+                    quorum_probability: protocol_parameters
+                        .quorum_probability(participating_stake_ratio),
+                }
+            })
+            .collect();
+        let all_signers_stake_ratio = stake_ratio(total_stake);
+
+        QuorumSimulationMessage {
+            epoch,
+            signers_count: stakes.len() as u64,
+            total_stake,
+            expected_won_lotteries: protocol_parameters.m as f64
+                * protocol_parameters.phi(all_signers_stake_ratio),
+            quorum_probability: protocol_parameters.quorum_probability(all_signers_stake_ratio),
+            target_probability,
+            min_participating_stake_ratio: Self::compute_min_participating_stake_ratio(
+                &protocol_parameters,
+                target_probability,
+            ),
+            scenarios,
+            protocol_parameters,
+        }
+    }
+}
+
+#[async_trait]
+impl QuorumSimulationService for MithrilQuorumSimulationService {
+    async fn simulate(
+        &self,
+        parameters: QuorumSimulationParameters,
+    ) -> StdResult<QuorumSimulationMessage> {
+        let (epoch, protocol_parameters, target_probability, mut stakes) = {
+            let epoch_service = self.epoch_service.read().await;
+            let epoch = epoch_service
+                .epoch_of_current_data()
+                .with_context(|| "QuorumSimulationService can not get the current epoch")?;
+            let current_protocol_parameters = epoch_service
+                .current_protocol_parameters()
+                .with_context(|| "QuorumSimulationService can not get the protocol parameters")?;
+            let protocol_parameters = ProtocolParameters::new(
+                parameters.k.unwrap_or(current_protocol_parameters.k),
+                parameters.m.unwrap_or(current_protocol_parameters.m),
+                parameters
+                    .phi_f
+                    .unwrap_or(current_protocol_parameters.phi_f),
+            );
+            let target_probability = parameters
+                .target_probability
+                .unwrap_or(DEFAULT_QUORUM_SIMULATION_TARGET_PROBABILITY);
+            Self::check_parameters(&protocol_parameters, target_probability)?;
+
+            let stakes: Vec<Stake> = epoch_service
+                .current_signers_with_stake()
+                .with_context(|| "QuorumSimulationService can not get the current signers")?
+                .iter()
+                .map(|signer| signer.stake)
+                .collect();
+
+            (epoch, protocol_parameters, target_probability, stakes)
+        };
+        stakes.sort_unstable_by(|a, b| b.cmp(a));
+
+        // spawn a separate thread to not block the runtime with the CPU bound computation
+        tokio::task::spawn_blocking(move || {
+            Self::compute_simulation(epoch, protocol_parameters, target_probability, stakes)
+        })
+        .await
+        .with_context(|| "QuorumSimulationService can not compute the simulation")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    use mithril_common::entities::{Epoch, StakeDistribution};
+    use mithril_common::test_utils::{MithrilFixtureBuilder, StakeDistributionGenerationMethod};
+
+    use crate::services::FakeEpochService;
+
+    use super::*;
+
+    fn build_service(
+        protocol_parameters: ProtocolParameters,
+        stakes: &[u64],
+    ) -> MithrilQuorumSimulationService {
+        let fixture = MithrilFixtureBuilder::default()
+            .with_protocol_parameters(protocol_parameters)
+            .with_stake_distribution(StakeDistributionGenerationMethod::Custom(
+                StakeDistribution::from_iter(
+                    stakes
+                        .iter()
+                        .enumerate()
+                        .map(|(i, stake)| (format!("pool{i}"), *stake)),
+                ),
+            ))
+            .build();
+        let epoch_service = FakeEpochService::from_fixture(Epoch(4), &fixture);
+
+        MithrilQuorumSimulationService::new(Arc::new(RwLock::new(epoch_service)))
+    }
+
+    #[tokio::test]
+    async fn simulate_with_the_current_protocol_parameters() {
+        let protocol_parameters = ProtocolParameters::new(5, 100, 0.65);
+        let service = build_service(protocol_parameters.clone(), &[10, 40, 20, 30]);
+
+        let simulation = service
+            .simulate(QuorumSimulationParameters::default())
+            .await
+            .unwrap();
+
+        assert_eq!(protocol_parameters, simulation.protocol_parameters);
+        assert_eq!(4, simulation.signers_count);
+        assert_eq!(100, simulation.total_stake);
+        assert_eq!(65.0, simulation.expected_won_lotteries.round());
+        assert_eq!(
+            protocol_parameters.quorum_probability(1.0),
+            simulation.quorum_probability
+        );
+        assert_eq!(
+            DEFAULT_QUORUM_SIMULATION_TARGET_PROBABILITY,
+            simulation.target_probability
+        );
+        assert_eq!(
+            vec![(0, 1.0), (1, 0.6), (2, 0.3), (3, 0.1)],
+            simulation
+                .scenarios
+                .iter()
+                .map(|scenario| (
+                    scenario.absent_largest_signers,
+                    (scenario.participating_stake_ratio * 100.0).round() / 100.0
+                ))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn simulate_with_the_given_protocol_parameters() {
+        let service = build_service(ProtocolParameters::new(5, 100, 0.65), &[10, 40, 20, 30]);
+
+        let simulation = service
+            .simulate(QuorumSimulationParameters {
+                k: Some(50),
+                phi_f: Some(0.2),
+                target_probability: Some(0.5),
+                ..QuorumSimulationParameters::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            ProtocolParameters::new(50, 100, 0.2),
+            simulation.protocol_parameters
+        );
+        assert_eq!(0.5, simulation.target_probability);
+        assert!(simulation.quorum_probability < 0.5);
+        assert_eq!(None, simulation.min_participating_stake_ratio);
+    }
+
+    #[tokio::test]
+    async fn the_min_participating_stake_ratio_reaches_the_target_probability() {
+        let service = build_service(ProtocolParameters::new(20, 100, 0.65), &[10, 40, 20, 30]);
+
+        let simulation = service
+            .simulate(QuorumSimulationParameters::default())
+            .await
+            .unwrap();
+        let min_participating_stake_ratio = simulation.min_participating_stake_ratio.unwrap();
+
+        assert!(min_participating_stake_ratio < 1.0);
+        assert!(
+            simulation
+                .protocol_parameters
+                .quorum_probability(min_participating_stake_ratio)
+                >= simulation.target_probability
+        );
+        assert!(
+            simulation
+                .protocol_parameters
+                .quorum_probability(min_participating_stake_ratio * 0.99)
+                < simulation.target_probability
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_invalid_parameters() {
+        let service = build_service(ProtocolParameters::new(5, 100, 0.65), &[10, 40]);
+
+        for parameters in [
+            QuorumSimulationParameters {
+                k: Some(101),
+                ..QuorumSimulationParameters::default()
+            },
+            QuorumSimulationParameters {
+                k: Some(5),
+                m: Some(MAX_QUORUM_SIMULATION_LOTTERIES + 1),
+                ..QuorumSimulationParameters::default()
+            },
+            QuorumSimulationParameters {
+                phi_f: Some(1.5),
+                ..QuorumSimulationParameters::default()
+            },
+            QuorumSimulationParameters {
+                target_probability: Some(1.0),
+                ..QuorumSimulationParameters::default()
+            },
+        ] {
+            let error = service
+                .simulate(parameters.clone())
+                .await
+                .expect_err("Simulation should fail with invalid parameters");
+
+            assert!(
+                matches!(
+                    error.downcast_ref::<QuorumSimulationError>(),
+                    Some(QuorumSimulationError::InvalidParameters(_))
+                ),
+                "Unexpected error for {parameters:?}: {error:?}"
+            );
+        }
+    }
+}
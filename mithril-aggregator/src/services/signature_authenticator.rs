@@ -0,0 +1,721 @@
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use thiserror::Error;
+
+use mithril_common::chain_observer::ChainObserver;
+use mithril_common::crypto_helper::{
+    compute_operational_certificate_kes_period, compute_registration_nonce_message,
+    compute_request_origin_message, verify_registration_nonce_signature,
+    verify_request_origin_signature, KESPeriod, OpCert, ProtocolSignerMessageSignature,
+};
+use mithril_common::entities::{HexEncodedSingleSignature, PartyId, SingleSignatures};
+use mithril_common::messages::RequestOriginSignaturesMessage;
+use mithril_common::StdResult;
+
+use crate::dependency_injection::EpochServiceWrapper;
+
+/// Maximum duration, in seconds, between the date and time at which a signer signed the origin of
+/// a request and its authentication, in both directions to tolerate the skew between the clocks
+/// of the signer and of the aggregator
+const REQUEST_ORIGIN_SIGNATURE_VALIDITY_IN_SECONDS: i64 = 5 * 60;
+
+/// Errors dedicated to the SignatureAuthenticator.
+#[derive(Debug, Error)]
+pub enum SignatureAuthenticatorError {
+    /// The signer is not registered for the current epoch.
+    #[error("Signer '{0}' is not registered for the current epoch")]
+    UnknownSigner(PartyId),
+
+    /// The signer registered without an operational certificate, its KES key is not known.
+    #[error("Signer '{0}' did not register with an operational certificate")]
+    NoOperationalCertificate(PartyId),
+
+    /// The single signature is not sent along a signature of the registration nonce.
+    #[error("Signature of signer '{0}' is not sent with a signature of the registration nonce")]
+    MissingNonceSignature(PartyId),
+
+    /// The signature of the registration nonce does not match the signer KES key.
+    #[error("Invalid signature of the registration nonce for signer '{0}'")]
+    InvalidNonceSignature(PartyId),
+
+    /// The request is not sent along a signature of its origin by the signer.
+    #[error("Request of signer '{0}' is not sent with a signature of its origin")]
+    MissingOriginSignature(PartyId),
+
+    /// The signature of the origin of the request does not match the verification key registered
+    /// by the signer.
+    #[error("Invalid signature of the request origin for signer '{0}'")]
+    InvalidOriginSignature(PartyId),
+
+    /// The signature of the origin of the request was not made recently enough, it may be
+    /// replayed.
+    #[error("Signature of the request origin for signer '{0}' is expired")]
+    ExpiredOriginSignature(PartyId),
+}
+
+/// The SignatureAuthenticator checks that the single signatures are sent by the signers they
+/// claim to originate from, before they are registered by the certifier.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait SignatureAuthenticator: Sync + Send {
+    /// Authenticate the given single signatures, sent in a single request, fails with a
+    /// [SignatureAuthenticatorError] if one of them can not be authenticated.
+    async fn authenticate(&self, signatures: &[SingleSignatures]) -> StdResult<()>;
+
+    /// Authenticate the origin of a request sending the given single signatures of their
+    /// signers, with the [RequestOriginSignaturesMessage] header value sent along it, fails with a
+    /// [SignatureAuthenticatorError] if the origin of the single signatures of one of the signers
+    /// can not be authenticated.
+    async fn authenticate_origin(
+        &self,
+        single_signatures: &[(PartyId, HexEncodedSingleSignature)],
+        origin_signatures: Option<&str>,
+    ) -> StdResult<()>;
+}
+
+/// Mithril aggregator implementation of the [SignatureAuthenticator]
+///
+/// The single signatures must come with a signature of the registration nonce of the current
+/// epoch made with the KES secret key, evolved to the current KES period, of the operational
+/// certificate the signer registered with.
+///
+/// The requests sending them must come with a signature by each of their signers of its single
+/// signatures, made recently with the signing key of the verification key it registered for the
+/// current epoch.
+pub struct MithrilSignatureAuthenticator {
+    enabled: bool,
+    epoch_service: EpochServiceWrapper,
+    chain_observer: Arc<dyn ChainObserver>,
+}
+
+impl MithrilSignatureAuthenticator {
+    /// MithrilSignatureAuthenticator factory, when disabled all the signatures are accepted.
+    pub fn new(
+        enabled: bool,
+        epoch_service: EpochServiceWrapper,
+        chain_observer: Arc<dyn ChainObserver>,
+    ) -> Self {
+        Self {
+            enabled,
+            epoch_service,
+            chain_observer,
+        }
+    }
+
+    /// Get the current KES period of the chain, read with the given operational certificate.
+    async fn get_current_kes_period(
+        &self,
+        operational_certificate: &OpCert,
+    ) -> StdResult<KESPeriod> {
+        self.chain_observer
+            .get_current_kes_period(operational_certificate)
+            .await
+            .with_context(|| "SignatureAuthenticator can not get the current KES period")?
+            .ok_or_else(|| anyhow!("SignatureAuthenticator could not get the current KES period"))
+    }
+}
+
+#[async_trait]
+impl SignatureAuthenticator for MithrilSignatureAuthenticator {
+    async fn authenticate(&self, signatures: &[SingleSignatures]) -> StdResult<()> {
+        if !self.enabled || signatures.is_empty() {
+            return Ok(());
+        }
+
+        let epoch_service = self.epoch_service.read().await;
+        let current_signers = epoch_service
+            .current_signers_with_stake()
+            .with_context(|| "SignatureAuthenticator can not get the current signers")?;
+        let registration_nonce = epoch_service
+            .current_registration_nonce()
+            .with_context(|| "SignatureAuthenticator can not get the current registration nonce")?;
+        let mut signers_operational_certificates = Vec::with_capacity(signatures.len());
+        for signatures in signatures {
+            let party_id = &signatures.party_id;
+            let signer = current_signers
+                .iter()
+                .find(|signer| &signer.party_id == party_id)
+                .ok_or_else(|| SignatureAuthenticatorError::UnknownSigner(party_id.clone()))?;
+            let operational_certificate =
+                signer.operational_certificate.as_ref().ok_or_else(|| {
+                    SignatureAuthenticatorError::NoOperationalCertificate(party_id.clone())
+                })?;
+            signers_operational_certificates.push((**operational_certificate).clone());
+        }
+        drop(epoch_service);
+
+        // The current KES period of the chain is read once for all the single signatures
+        let mut current_kes_period = None;
+        for (signatures, operational_certificate) in
+            signatures.iter().zip(&signers_operational_certificates)
+        {
+            let party_id = &signatures.party_id;
+            let nonce_signature = signatures.nonce_signature.as_ref().ok_or_else(|| {
+                SignatureAuthenticatorError::MissingNonceSignature(party_id.clone())
+            })?;
+            let current_kes_period = match current_kes_period {
+                Some(kes_period) => kes_period,
+                None => {
+                    let kes_period = self.get_current_kes_period(operational_certificate).await?;
+                    current_kes_period = Some(kes_period);
+                    kes_period
+                }
+            };
+
+            let message = compute_registration_nonce_message(
+                &registration_nonce,
+                party_id,
+                &signatures.signature.to_bytes(),
+            );
+            compute_operational_certificate_kes_period(current_kes_period, operational_certificate)
+                .ok_or_else(|| anyhow!("The operational certificate is not valid yet"))
+                .and_then(|kes_period| {
+                    verify_registration_nonce_signature(
+                        nonce_signature,
+                        operational_certificate,
+                        kes_period,
+                        &message,
+                    )
+                })
+                .map_err(|_| {
+                    SignatureAuthenticatorError::InvalidNonceSignature(party_id.clone())
+                })?;
+        }
+
+        Ok(())
+    }
+
+    async fn authenticate_origin(
+        &self,
+        single_signatures: &[(PartyId, HexEncodedSingleSignature)],
+        origin_signatures: Option<&str>,
+    ) -> StdResult<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let mut signers_single_signatures: BTreeMap<&PartyId, Vec<HexEncodedSingleSignature>> =
+            BTreeMap::new();
+        for (party_id, single_signature) in single_signatures {
+            signers_single_signatures
+                .entry(party_id)
+                .or_default()
+                .push(single_signature.to_owned());
+        }
+        let Some(first_party_id) = signers_single_signatures.keys().next() else {
+            return Ok(());
+        };
+        let origin_signatures = origin_signatures
+            .ok_or_else(|| {
+                SignatureAuthenticatorError::MissingOriginSignature((*first_party_id).clone())
+            })
+            .and_then(|header_value| {
+                RequestOriginSignaturesMessage::from_header_value(header_value).map_err(|_| {
+                    SignatureAuthenticatorError::InvalidOriginSignature((*first_party_id).clone())
+                })
+            })?;
+        let epoch_service = self.epoch_service.read().await;
+        let current_signers = epoch_service
+            .current_signers_with_stake()
+            .with_context(|| "SignatureAuthenticator can not get the current signers")?;
+        let now = Utc::now();
+
+        for (party_id, single_signatures) in signers_single_signatures {
+            let signer = current_signers
+                .iter()
+                .find(|signer| &signer.party_id == party_id)
+                .ok_or_else(|| SignatureAuthenticatorError::UnknownSigner(party_id.clone()))?;
+            let Some(origin_signature) = origin_signatures.get_signature_of(party_id) else {
+                return Err(
+                    SignatureAuthenticatorError::MissingOriginSignature(party_id.clone()).into(),
+                );
+            };
+            if (now - origin_signature.signed_at).num_seconds().abs()
+                > REQUEST_ORIGIN_SIGNATURE_VALIDITY_IN_SECONDS
+            {
+                return Err(
+                    SignatureAuthenticatorError::ExpiredOriginSignature(party_id.clone()).into(),
+                );
+            }
+
+            let message = compute_request_origin_message(
+                party_id,
+                origin_signature.signed_at,
+                &single_signatures,
+            );
+            ProtocolSignerMessageSignature::from_json_hex(&origin_signature.signature)
+                .and_then(|signature| {
+                    verify_request_origin_signature(&signature, &signer.verification_key, &message)
+                })
+                .map_err(|_| {
+                    SignatureAuthenticatorError::InvalidOriginSignature(party_id.clone())
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration};
+    use tokio::sync::RwLock;
+
+    use mithril_common::crypto_helper::sign_registration_nonce_message;
+    use mithril_common::entities::Epoch;
+    use mithril_common::messages::RequestOriginSignatureMessage;
+    use mithril_common::test_utils::{
+        fake_keys, MithrilFixture, MithrilFixtureBuilder, SignerFixture,
+    };
+
+    use crate::services::{EpochService, FakeEpochService};
+    use crate::tools::mocks::MockChainObserver;
+
+    use super::*;
+
+    /// Current KES period of the chain, the KES keys of the fixtures start at KES period 0
+    const CURRENT_KES_PERIOD: KESPeriod = 2;
+
+    fn build_authenticator(
+        enabled: bool,
+        fixture: &MithrilFixture,
+    ) -> (MithrilSignatureAuthenticator, String) {
+        let epoch_service = FakeEpochService::from_fixture(Epoch(4), fixture);
+        let registration_nonce = epoch_service.current_registration_nonce().unwrap();
+        let mut chain_observer = MockChainObserver::new();
+        chain_observer
+            .expect_get_current_kes_period()
+            .returning(|_| Ok(Some(CURRENT_KES_PERIOD)));
+
+        (
+            MithrilSignatureAuthenticator::new(
+                enabled,
+                Arc::new(RwLock::new(epoch_service)),
+                Arc::new(chain_observer),
+            ),
+            registration_nonce,
+        )
+    }
+
+    fn sign(signer: &SignerFixture) -> SingleSignatures {
+        SingleSignatures::new(
+            signer.party_id(),
+            fake_keys::single_signature()[0].try_into().unwrap(),
+            vec![1, 3],
+        )
+    }
+
+    fn sign_nonce_at_kes_period(
+        signer: &SignerFixture,
+        registration_nonce: &str,
+        kes_period: KESPeriod,
+    ) -> SingleSignatures {
+        let signatures = sign(signer);
+        let message = compute_registration_nonce_message(
+            registration_nonce,
+            &signatures.party_id,
+            &signatures.signature.to_bytes(),
+        );
+        let nonce_signature = sign_registration_nonce_message(
+            signer.kes_secret_key_path().unwrap(),
+            kes_period,
+            &message,
+        )
+        .unwrap();
+
+        signatures.with_nonce_signature(nonce_signature)
+    }
+
+    fn sign_nonce(signer: &SignerFixture, registration_nonce: &str) -> SingleSignatures {
+        sign_nonce_at_kes_period(signer, registration_nonce, CURRENT_KES_PERIOD)
+    }
+
+    fn sign_origin_at(
+        signer: &SignerFixture,
+        signed_at: DateTime<Utc>,
+        single_signatures: &[&str],
+    ) -> RequestOriginSignatureMessage {
+        let single_signatures: Vec<HexEncodedSingleSignature> = single_signatures
+            .iter()
+            .map(|single_signature| single_signature.to_string())
+            .collect();
+        let message =
+            compute_request_origin_message(&signer.party_id(), signed_at, &single_signatures);
+
+        RequestOriginSignatureMessage {
+            party_id: signer.party_id(),
+            signed_at,
+            signature: signer
+                .protocol_initializer
+                .sign_message(&message)
+                .to_json_hex()
+                .unwrap(),
+        }
+    }
+
+    fn sign_origin(
+        signer: &SignerFixture,
+        single_signatures: &[&str],
+    ) -> RequestOriginSignatureMessage {
+        sign_origin_at(signer, Utc::now(), single_signatures)
+    }
+
+    fn origin_header(signatures: Vec<RequestOriginSignatureMessage>) -> String {
+        RequestOriginSignaturesMessage { signatures }
+            .to_header_value()
+            .unwrap()
+    }
+
+    fn single_signatures_of(
+        signer: &SignerFixture,
+        single_signatures: &[&str],
+    ) -> Vec<(PartyId, HexEncodedSingleSignature)> {
+        single_signatures
+            .iter()
+            .map(|single_signature| (signer.party_id(), single_signature.to_string()))
+            .collect()
+    }
+
+    fn assert_authentication_error(
+        result: StdResult<()>,
+        expected: fn(&SignatureAuthenticatorError) -> bool,
+    ) {
+        let error = result.expect_err("Authentication should fail");
+        match error.downcast_ref::<SignatureAuthenticatorError>() {
+            Some(authentication_error) if expected(authentication_error) => (),
+            _ => panic!("Unexpected authentication error: {error:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn accept_any_signature_when_disabled() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, _) = build_authenticator(false, &fixture);
+
+        authenticator
+            .authenticate(&[sign(&fixture.signers_fixture()[0])])
+            .await
+            .expect("Authentication should not fail when disabled");
+    }
+
+    #[tokio::test]
+    async fn accept_signature_with_a_valid_nonce_signature() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, registration_nonce) = build_authenticator(true, &fixture);
+
+        authenticator
+            .authenticate(&[sign_nonce(
+                &fixture.signers_fixture()[0],
+                &registration_nonce,
+            )])
+            .await
+            .expect("Authentication should not fail");
+    }
+
+    #[tokio::test]
+    async fn read_the_current_kes_period_once_for_all_the_signatures_of_a_request() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let epoch_service = FakeEpochService::from_fixture(Epoch(4), &fixture);
+        let registration_nonce = epoch_service.current_registration_nonce().unwrap();
+        let mut chain_observer = MockChainObserver::new();
+        chain_observer
+            .expect_get_current_kes_period()
+            .returning(|_| Ok(Some(CURRENT_KES_PERIOD)))
+            .once();
+        let authenticator = MithrilSignatureAuthenticator::new(
+            true,
+            Arc::new(RwLock::new(epoch_service)),
+            Arc::new(chain_observer),
+        );
+        let signers = fixture.signers_fixture();
+
+        authenticator
+            .authenticate(&[
+                sign_nonce(&signers[0], &registration_nonce),
+                sign_nonce(&signers[1], &registration_nonce),
+                sign_nonce(&signers[0], &registration_nonce),
+            ])
+            .await
+            .expect("Authentication should not fail");
+    }
+
+    #[tokio::test]
+    async fn reject_signature_without_nonce_signature() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, _) = build_authenticator(true, &fixture);
+
+        assert_authentication_error(
+            authenticator
+                .authenticate(&[sign(&fixture.signers_fixture()[0])])
+                .await,
+            |error| matches!(error, SignatureAuthenticatorError::MissingNonceSignature(_)),
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_signature_with_the_nonce_signed_by_another_signer() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, registration_nonce) = build_authenticator(true, &fixture);
+        let signers = fixture.signers_fixture();
+        let other_signer_signatures = sign_nonce(&signers[1], &registration_nonce);
+        let signatures = SingleSignatures {
+            nonce_signature: other_signer_signatures.nonce_signature,
+            ..sign(&signers[0])
+        };
+
+        assert_authentication_error(authenticator.authenticate(&[signatures]).await, |error| {
+            matches!(error, SignatureAuthenticatorError::InvalidNonceSignature(_))
+        });
+    }
+
+    #[tokio::test]
+    async fn reject_signature_with_a_nonce_signature_of_another_epoch() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, _) = build_authenticator(true, &fixture);
+
+        assert_authentication_error(
+            authenticator
+                .authenticate(&[sign_nonce(&fixture.signers_fixture()[0], "another-nonce")])
+                .await,
+            |error| matches!(error, SignatureAuthenticatorError::InvalidNonceSignature(_)),
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_signature_with_a_nonce_signature_made_at_another_kes_period() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, registration_nonce) = build_authenticator(true, &fixture);
+
+        for kes_period in [CURRENT_KES_PERIOD - 1, CURRENT_KES_PERIOD + 1] {
+            assert_authentication_error(
+                authenticator
+                    .authenticate(&[sign_nonce_at_kes_period(
+                        &fixture.signers_fixture()[0],
+                        &registration_nonce,
+                        kes_period,
+                    )])
+                    .await,
+                |error| matches!(error, SignatureAuthenticatorError::InvalidNonceSignature(_)),
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn reject_signature_of_an_unknown_signer() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, registration_nonce) = build_authenticator(true, &fixture);
+        let signatures = SingleSignatures {
+            party_id: "unknown-party-id".to_string(),
+            ..sign_nonce(&fixture.signers_fixture()[0], &registration_nonce)
+        };
+
+        assert_authentication_error(authenticator.authenticate(&[signatures]).await, |error| {
+            matches!(error, SignatureAuthenticatorError::UnknownSigner(_))
+        });
+    }
+
+    #[tokio::test]
+    async fn reject_signature_of_a_signer_without_operational_certificate() {
+        let fixture = MithrilFixtureBuilder::default()
+            .with_signers(2)
+            .disable_signers_certification()
+            .build();
+        let (authenticator, _) = build_authenticator(true, &fixture);
+
+        assert_authentication_error(
+            authenticator
+                .authenticate(&[sign(&fixture.signers_fixture()[0])])
+                .await,
+            |error| {
+                matches!(
+                    error,
+                    SignatureAuthenticatorError::NoOperationalCertificate(_)
+                )
+            },
+        );
+    }
+
+    #[tokio::test]
+    async fn accept_request_with_a_valid_origin_signature() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, _) = build_authenticator(true, &fixture);
+        let signer = &fixture.signers_fixture()[0];
+
+        authenticator
+            .authenticate_origin(
+                &single_signatures_of(signer, &["signature-1", "signature-2"]),
+                Some(&origin_header(vec![sign_origin(
+                    signer,
+                    &["signature-1", "signature-2"],
+                )])),
+            )
+            .await
+            .expect("Authentication should not fail");
+    }
+
+    #[tokio::test]
+    async fn accept_request_holding_the_signatures_of_several_signers() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, _) = build_authenticator(true, &fixture);
+        let signers = fixture.signers_fixture();
+        let single_signatures = [
+            single_signatures_of(&signers[0], &["signature-1"]),
+            single_signatures_of(&signers[1], &["signature-2"]),
+            single_signatures_of(&signers[0], &["signature-3"]),
+        ]
+        .concat();
+
+        authenticator
+            .authenticate_origin(
+                &single_signatures,
+                Some(&origin_header(vec![
+                    sign_origin(&signers[1], &["signature-2"]),
+                    sign_origin(&signers[0], &["signature-1", "signature-3"]),
+                ])),
+            )
+            .await
+            .expect("Authentication should not fail");
+    }
+
+    #[tokio::test]
+    async fn accept_request_without_origin_signature_when_disabled() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, _) = build_authenticator(false, &fixture);
+
+        authenticator
+            .authenticate_origin(
+                &single_signatures_of(&fixture.signers_fixture()[0], &["signature"]),
+                None,
+            )
+            .await
+            .expect("Authentication should not fail when disabled");
+    }
+
+    #[tokio::test]
+    async fn reject_request_without_the_origin_signature_of_one_of_its_signers() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, _) = build_authenticator(true, &fixture);
+        let signers = fixture.signers_fixture();
+        let single_signatures = [
+            single_signatures_of(&signers[0], &["signature-1"]),
+            single_signatures_of(&signers[1], &["signature-2"]),
+        ]
+        .concat();
+
+        for origin_header in [
+            None,
+            Some(origin_header(vec![sign_origin(
+                &signers[0],
+                &["signature-1"],
+            )])),
+        ] {
+            assert_authentication_error(
+                authenticator
+                    .authenticate_origin(&single_signatures, origin_header.as_deref())
+                    .await,
+                |error| {
+                    matches!(
+                        error,
+                        SignatureAuthenticatorError::MissingOriginSignature(_)
+                    )
+                },
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn reject_request_with_the_origin_signature_of_other_single_signatures_or_signer() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, _) = build_authenticator(true, &fixture);
+        let signers = fixture.signers_fixture();
+        let signed_at = Utc::now();
+
+        for origin_header in [
+            origin_header(vec![sign_origin(&signers[0], &["another signature"])]),
+            origin_header(vec![RequestOriginSignatureMessage {
+                party_id: signers[0].party_id(),
+                ..sign_origin(&signers[1], &["signature"])
+            }]),
+            origin_header(vec![RequestOriginSignatureMessage {
+                signed_at: signed_at + Duration::try_seconds(1).unwrap(),
+                ..sign_origin_at(&signers[0], signed_at, &["signature"])
+            }]),
+            origin_header(vec![RequestOriginSignatureMessage {
+                signature: "invalid".to_string(),
+                ..sign_origin(&signers[0], &["signature"])
+            }]),
+            "invalid".to_string(),
+        ] {
+            assert_authentication_error(
+                authenticator
+                    .authenticate_origin(
+                        &single_signatures_of(&signers[0], &["signature"]),
+                        Some(&origin_header),
+                    )
+                    .await,
+                |error| {
+                    matches!(
+                        error,
+                        SignatureAuthenticatorError::InvalidOriginSignature(_)
+                    )
+                },
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn reject_request_with_an_origin_signature_made_too_long_ago_or_ahead() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, _) = build_authenticator(true, &fixture);
+        let signer = &fixture.signers_fixture()[0];
+        let validity = Duration::try_seconds(REQUEST_ORIGIN_SIGNATURE_VALIDITY_IN_SECONDS).unwrap();
+        let one_minute = Duration::try_minutes(1).unwrap();
+
+        for signed_at in [
+            Utc::now() - validity - one_minute,
+            Utc::now() + validity + one_minute,
+        ] {
+            assert_authentication_error(
+                authenticator
+                    .authenticate_origin(
+                        &single_signatures_of(signer, &["signature"]),
+                        Some(&origin_header(vec![sign_origin_at(
+                            signer,
+                            signed_at,
+                            &["signature"],
+                        )])),
+                    )
+                    .await,
+                |error| {
+                    matches!(
+                        error,
+                        SignatureAuthenticatorError::ExpiredOriginSignature(_)
+                    )
+                },
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn reject_request_holding_the_signatures_of_an_unknown_signer() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (authenticator, _) = build_authenticator(true, &fixture);
+        let origin_signature = RequestOriginSignatureMessage {
+            party_id: "unknown-party-id".to_string(),
+            ..sign_origin(&fixture.signers_fixture()[0], &["signature"])
+        };
+
+        assert_authentication_error(
+            authenticator
+                .authenticate_origin(
+                    &[("unknown-party-id".to_string(), "signature".to_string())],
+                    Some(&origin_header(vec![origin_signature])),
+                )
+                .await,
+            |error| matches!(error, SignatureAuthenticatorError::UnknownSigner(_)),
+        );
+    }
+}
@@ -7,6 +7,7 @@ use async_trait::async_trait;
 use chrono::Utc;
 use slog_scope::info;
 use std::sync::Arc;
+use thiserror::Error;
 
 use mithril_common::{
     entities::{
@@ -20,11 +21,21 @@ use mithril_common::{
 use crate::{
     artifact_builder::ArtifactBuilder,
     database::{record::SignedEntityRecord, repository::SignedEntityStorer},
+    services::SignedEntityTypeLock,
 };
 
 #[cfg(test)]
 use mockall::automock;
 
+/// Errors dedicated to the SignedEntityService.
+#[derive(Debug, Error)]
+pub enum SignedEntityServiceError {
+    /// The artifact is being created by another task or aggregator process, its creation can be
+    /// retried once it's done.
+    #[error("The artifact of the signed entity type {0:?} is already being created.")]
+    ArtifactCreationInProgress(SignedEntityType),
+}
+
 /// ArtifactBuilder Service trait
 #[cfg_attr(test, automock)]
 #[async_trait]
@@ -76,6 +87,7 @@ pub struct MithrilSignedEntityService {
         Arc<dyn ArtifactBuilder<CardanoDbBeacon, Snapshot>>,
     cardano_transactions_artifact_builder:
         Arc<dyn ArtifactBuilder<CardanoDbBeacon, CardanoTransactionsSnapshot>>,
+    signed_entity_type_lock: Arc<SignedEntityTypeLock>,
 }
 
 impl MithrilSignedEntityService {
@@ -97,9 +109,57 @@ impl MithrilSignedEntityService {
             mithril_stake_distribution_artifact_builder,
             cardano_immutable_files_full_artifact_builder,
             cardano_transactions_artifact_builder,
+            signed_entity_type_lock: Arc::new(SignedEntityTypeLock::default()),
         }
     }
 
+    /// Set the lock that prevents concurrent creations of the artifact of a signed entity type
+    pub fn with_signed_entity_type_lock(
+        mut self,
+        signed_entity_type_lock: Arc<SignedEntityTypeLock>,
+    ) -> Self {
+        self.signed_entity_type_lock = signed_entity_type_lock;
+        self
+    }
+
+    async fn compute_and_store_artifact(
+        &self,
+        signed_entity_type: SignedEntityType,
+        certificate: &Certificate,
+    ) -> StdResult<()> {
+        let mut remaining_retries = 2;
+        let artifact = loop {
+            remaining_retries -= 1;
+
+            match self
+                .compute_artifact(signed_entity_type.clone(), certificate)
+                .await
+            {
+                Err(error) if remaining_retries == 0 => break Err(error),
+                Err(_error) => (),
+                Ok(artifact) => break Ok(artifact),
+            };
+        }?;
+
+        let signed_entity = SignedEntityRecord {
+            signed_entity_id: artifact.get_id(),
+            signed_entity_type: signed_entity_type.clone(),
+            certificate_id: certificate.hash.clone(),
+            artifact: serde_json::to_string(&artifact)?,
+            created_at: Utc::now(),
+        };
+
+        self.signed_entity_storer
+            .store_signed_entity(&signed_entity)
+            .await
+            .with_context(|| {
+                format!(
+                    "Signed Entity Service can not store signed entity with type: '{signed_entity_type}'"
+                )
+            })?;
+        Ok(())
+    }
+
     /// Compute artifact from signed entity type
     async fn compute_artifact(
         &self,
@@ -171,37 +231,27 @@ impl SignedEntityService for MithrilSignedEntityService {
             "certificate_hash" => &certificate.hash
         );
 
-        let mut remaining_retries = 2;
-        let artifact = loop {
-            remaining_retries -= 1;
-
-            match self
-                .compute_artifact(signed_entity_type.clone(), certificate)
-                .await
-            {
-                Err(error) if remaining_retries == 0 => break Err(error),
-                Err(_error) => (),
-                Ok(artifact) => break Ok(artifact),
-            };
-        }?;
+        if !self
+            .signed_entity_type_lock
+            .try_lock(&signed_entity_type)
+            .await?
+        {
+            return Err(
+                SignedEntityServiceError::ArtifactCreationInProgress(signed_entity_type).into(),
+            );
+        }
 
-        let signed_entity = SignedEntityRecord {
-            signed_entity_id: artifact.get_id(),
-            signed_entity_type: signed_entity_type.clone(),
-            certificate_id: certificate.hash.clone(),
-            artifact: serde_json::to_string(&artifact)?,
-            created_at: Utc::now(),
+        // The lease is renewed while the artifact is created, the creation is abandoned if
+        // another aggregator took it over
+        let result = tokio::select! {
+            result = self.compute_and_store_artifact(signed_entity_type.clone(), certificate) => result,
+            Err(error) = self.signed_entity_type_lock.keep_lease(&signed_entity_type) => Err(error),
         };
+        self.signed_entity_type_lock
+            .release(&signed_entity_type)
+            .await;
 
-        self.signed_entity_storer
-            .store_signed_entity(&signed_entity)
-            .await
-            .with_context(|| {
-                format!(
-                    "Signed Entity Service can not store signed entity with type: '{signed_entity_type}'"
-                )
-            })?;
-        Ok(())
+        result
     }
 
     async fn get_last_signed_snapshots(
@@ -404,6 +454,45 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn should_not_create_the_artifact_of_a_signed_entity_type_already_being_created() {
+        let mut mock_container = MockDependencyInjector::new();
+        mock_container
+            .mock_mithril_stake_distribution_artifact_builder
+            .expect_compute_artifact()
+            .never();
+        mock_container
+            .mock_signed_entity_storer
+            .expect_store_signed_entity()
+            .never();
+        let signed_entity_type = SignedEntityType::MithrilStakeDistribution(Epoch(1));
+        let signed_entity_type_lock = Arc::new(SignedEntityTypeLock::default());
+        signed_entity_type_lock
+            .try_lock(&signed_entity_type)
+            .await
+            .unwrap();
+        let artifact_builder_service = mock_container
+            .build_artifact_builder_service()
+            .with_signed_entity_type_lock(signed_entity_type_lock.clone());
+
+        let error = artifact_builder_service
+            .create_artifact(
+                signed_entity_type.clone(),
+                &fake_data::certificate("hash".to_string()),
+            )
+            .await
+            .expect_err("the creation of an artifact already being created should fail");
+
+        assert!(matches!(
+            error.downcast_ref::<SignedEntityServiceError>(),
+            Some(SignedEntityServiceError::ArtifactCreationInProgress(_))
+        ));
+        assert!(!signed_entity_type_lock
+            .try_lock(&signed_entity_type)
+            .await
+            .unwrap());
+    }
+
     #[tokio::test]
     async fn build_snapshot_artifact_when_given_cardano_immutable_files_full_entity_type() {
         let mut mock_container = MockDependencyInjector::new();
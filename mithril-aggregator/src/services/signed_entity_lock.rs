@@ -0,0 +1,221 @@
+use anyhow::anyhow;
+use chrono::{Duration, Utc};
+use slog_scope::warn;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use mithril_common::{entities::SignedEntityType, StdResult};
+
+use crate::database::repository::SignedEntityLeaseStorer;
+
+/// Lock that ensures that a single creation of the artifact of a signed entity type (and its
+/// beacon) is in flight at a time.
+///
+/// The lock is held in memory for the concurrent creations of this process and, if a
+/// [lease storer][SignedEntityLeaseStorer] is given, as a lease persisted in the database for the
+/// concurrent aggregator processes sharing it (i.e. after a restart race). The lease of a process
+/// that did not release it expires after the lease duration, unless it is taken back by the same
+/// holder (i.e. the same aggregator once restarted).
+pub struct SignedEntityTypeLock {
+    locked_entities: Mutex<Vec<SignedEntityType>>,
+    lease_storer: Option<Arc<dyn SignedEntityLeaseStorer>>,
+    holder: String,
+    lease_duration: Duration,
+}
+
+impl SignedEntityTypeLock {
+    /// Create a lock held in memory only
+    pub fn new() -> Self {
+        Self {
+            locked_entities: Mutex::new(Vec::new()),
+            lease_storer: None,
+            holder: uuid::Uuid::new_v4().to_string(),
+            lease_duration: Duration::zero(),
+        }
+    }
+
+    /// Persist the lock as a lease held by the given holder in the given storer, the lease is
+    /// stale after the given duration unless it is renewed.
+    ///
+    /// The holder must identify the aggregator across its restarts.
+    pub fn with_lease_storer(
+        mut self,
+        lease_storer: Arc<dyn SignedEntityLeaseStorer>,
+        holder: String,
+        lease_duration: Duration,
+    ) -> Self {
+        self.lease_storer = Some(lease_storer);
+        self.holder = holder;
+        self.lease_duration = lease_duration;
+        self
+    }
+
+    /// Try to lock the given signed entity type, returns `false` if it is already locked
+    pub async fn try_lock(&self, signed_entity_type: &SignedEntityType) -> StdResult<bool> {
+        let mut locked_entities = self.locked_entities.lock().await;
+        if locked_entities.contains(signed_entity_type) {
+            return Ok(false);
+        }
+        if let Some(lease_storer) = &self.lease_storer {
+            let expires_at = Utc::now() + self.lease_duration;
+            if !lease_storer
+                .try_acquire_lease(signed_entity_type, &self.holder, expires_at)
+                .await?
+            {
+                return Ok(false);
+            }
+        }
+        locked_entities.push(signed_entity_type.clone());
+
+        Ok(true)
+    }
+
+    /// Renew the lease of the given locked signed entity type until the returned future is
+    /// dropped, the lease is renewed three times per lease duration.
+    ///
+    /// Only returns if the lease was taken over by another holder, the failures to renew it are
+    /// logged and retried.
+    pub async fn keep_lease(&self, signed_entity_type: &SignedEntityType) -> StdResult<()> {
+        let Some(lease_storer) = &self.lease_storer else {
+            return std::future::pending().await;
+        };
+        let renewal_interval = (self.lease_duration / 3).to_std()?;
+
+        loop {
+            tokio::time::sleep(renewal_interval).await;
+            match lease_storer
+                .try_acquire_lease(
+                    signed_entity_type,
+                    &self.holder,
+                    Utc::now() + self.lease_duration,
+                )
+                .await
+            {
+                Ok(true) => {}
+                Ok(false) => {
+                    return Err(anyhow!(
+                        "The lease of the signed entity type '{signed_entity_type}' was taken over by another holder"
+                    ));
+                }
+                Err(error) => {
+                    warn!(
+                        "SignedEntityTypeLock: the lease could not be renewed";
+                        "signed_entity_type" => ?signed_entity_type, "error" => ?error
+                    );
+                }
+            }
+        }
+    }
+
+    /// Release the lock of the given signed entity type
+    pub async fn release(&self, signed_entity_type: &SignedEntityType) {
+        let mut locked_entities = self.locked_entities.lock().await;
+        if let Some(lease_storer) = &self.lease_storer {
+            // A lease that could not be released expires after the lease duration
+            if let Err(error) = lease_storer
+                .release_lease(signed_entity_type, &self.holder)
+                .await
+            {
+                warn!(
+                    "SignedEntityTypeLock: the lease could not be released";
+                    "signed_entity_type" => ?signed_entity_type, "error" => ?error
+                );
+            }
+        }
+        locked_entities.retain(|locked_entity| locked_entity != signed_entity_type);
+    }
+}
+
+impl Default for SignedEntityTypeLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::Epoch;
+
+    use crate::database::repository::MockSignedEntityLeaseStorer;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn signed_entity_type_can_be_locked_again_only_once_released() {
+        let lock = SignedEntityTypeLock::new();
+        let signed_entity_type = SignedEntityType::MithrilStakeDistribution(Epoch(5));
+
+        assert!(lock.try_lock(&signed_entity_type).await.unwrap());
+        assert!(!lock.try_lock(&signed_entity_type).await.unwrap());
+        assert!(lock
+            .try_lock(&SignedEntityType::MithrilStakeDistribution(Epoch(6)))
+            .await
+            .unwrap());
+
+        lock.release(&signed_entity_type).await;
+        assert!(lock.try_lock(&signed_entity_type).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn signed_entity_type_is_not_locked_if_its_lease_is_held_by_another_process() {
+        let mut lease_storer = MockSignedEntityLeaseStorer::new();
+        lease_storer
+            .expect_try_acquire_lease()
+            .returning(|_, _, _| Ok(false))
+            .once();
+        let lock = SignedEntityTypeLock::new().with_lease_storer(
+            Arc::new(lease_storer),
+            "holder".to_string(),
+            Duration::try_hours(1).unwrap(),
+        );
+
+        assert!(!lock
+            .try_lock(&SignedEntityType::MithrilStakeDistribution(Epoch(5)))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn keep_lease_renews_the_lease_of_its_holder() {
+        let mut lease_storer = MockSignedEntityLeaseStorer::new();
+        lease_storer
+            .expect_try_acquire_lease()
+            .withf(|_, holder, _| holder == "holder")
+            .returning(|_, _, _| Ok(true))
+            .times(2..);
+        let lock = SignedEntityTypeLock::new().with_lease_storer(
+            Arc::new(lease_storer),
+            "holder".to_string(),
+            Duration::try_milliseconds(30).unwrap(),
+        );
+
+        tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            lock.keep_lease(&SignedEntityType::MithrilStakeDistribution(Epoch(5))),
+        )
+        .await
+        .expect_err("keep_lease should not return while the lease is renewed");
+    }
+
+    #[tokio::test]
+    async fn keep_lease_fails_once_the_lease_is_taken_over() {
+        let mut lease_storer = MockSignedEntityLeaseStorer::new();
+        lease_storer
+            .expect_try_acquire_lease()
+            .returning(|_, _, _| Ok(false))
+            .once();
+        let lock = SignedEntityTypeLock::new().with_lease_storer(
+            Arc::new(lease_storer),
+            "holder".to_string(),
+            Duration::try_milliseconds(30).unwrap(),
+        );
+
+        tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            lock.keep_lease(&SignedEntityType::MithrilStakeDistribution(Epoch(5))),
+        )
+        .await
+        .expect("keep_lease should return once the lease is taken over")
+        .expect_err("keep_lease should fail once the lease is taken over");
+    }
+}
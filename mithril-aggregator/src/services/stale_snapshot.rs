@@ -0,0 +1,186 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use mithril_common::entities::SignedEntityTypeDiscriminants;
+use mithril_common::StdResult;
+
+use crate::database::repository::SignedEntityStorer;
+use crate::entities::BeaconEstimateListItemMessage;
+
+/// Number of the latest snapshots used to compute the expected cadence of the certifications
+const CADENCE_SAMPLE_SIZE: usize = 10;
+
+/// Freshness of the latest certified snapshot of the Cardano database
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaleSnapshotStatus {
+    /// Date and time of the latest certified snapshot, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_certified_at: Option<DateTime<Utc>>,
+
+    /// Expected duration, in seconds, between two certified snapshots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_interval_seconds: Option<i64>,
+
+    /// Date and time after which the latest certified snapshot is considered stale
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale_after: Option<DateTime<Utc>>,
+
+    /// True if no snapshot has been certified since `stale_after`
+    pub is_stale: bool,
+}
+
+/// The StaleSnapshotDetector checks that the Cardano database snapshots are certified at their
+/// expected cadence.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait StaleSnapshotDetector: Sync + Send {
+    /// Compute the freshness of the latest certified snapshot at the given date.
+    async fn get_status(&self, now: DateTime<Utc>) -> StdResult<StaleSnapshotStatus>;
+}
+
+/// Mithril aggregator implementation of the [StaleSnapshotDetector]
+///
+/// The expected cadence is estimated from the latest certified snapshots, the latest snapshot is
+/// stale when no snapshot has been certified within `cadence_multiple` times this cadence.
+pub struct MithrilStaleSnapshotDetector {
+    cadence_multiple: u64,
+    signed_entity_storer: Arc<dyn SignedEntityStorer>,
+}
+
+impl MithrilStaleSnapshotDetector {
+    /// MithrilStaleSnapshotDetector factory
+    pub fn new(cadence_multiple: u64, signed_entity_storer: Arc<dyn SignedEntityStorer>) -> Self {
+        Self {
+            cadence_multiple: cadence_multiple.max(1),
+            signed_entity_storer,
+        }
+    }
+}
+
+#[async_trait]
+impl StaleSnapshotDetector for MithrilStaleSnapshotDetector {
+    async fn get_status(&self, now: DateTime<Utc>) -> StdResult<StaleSnapshotStatus> {
+        let signed_entity_type = SignedEntityTypeDiscriminants::CardanoImmutableFilesFull;
+        let snapshots = self
+            .signed_entity_storer
+            .get_last_signed_entities_by_type(&signed_entity_type, CADENCE_SAMPLE_SIZE)
+            .await
+            .with_context(|| "StaleSnapshotDetector can not get the latest snapshots")?;
+        let estimate = BeaconEstimateListItemMessage::estimate(signed_entity_type, &snapshots);
+        let stale_after = estimate
+            .last_certified_at
+            .zip(estimate.beacon_interval_seconds)
+            .map(|(last_certified_at, interval)| {
+                last_certified_at
+                    + chrono::Duration::seconds(interval * self.cadence_multiple as i64)
+            });
+
+        Ok(StaleSnapshotStatus {
+            last_certified_at: estimate.last_certified_at,
+            expected_interval_seconds: estimate.beacon_interval_seconds,
+            stale_after,
+            is_stale: stale_after.is_some_and(|stale_after| now > stale_after),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::{CardanoDbBeacon, SignedEntityType};
+
+    use crate::database::record::SignedEntityRecord;
+    use crate::database::repository::MockSignedEntityStorer;
+
+    use super::*;
+
+    fn date(date: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(date)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn snapshot(immutable_file_number: u64, created_at: &str) -> SignedEntityRecord {
+        SignedEntityRecord {
+            signed_entity_id: format!("snapshot-{immutable_file_number}"),
+            signed_entity_type: SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::new(
+                "devnet",
+                1,
+                immutable_file_number,
+            )),
+            certificate_id: "certificate-id".to_string(),
+            artifact: "".to_string(),
+            created_at: date(created_at),
+        }
+    }
+
+    fn build_detector(
+        cadence_multiple: u64,
+        snapshots: Vec<SignedEntityRecord>,
+    ) -> MithrilStaleSnapshotDetector {
+        let mut signed_entity_storer = MockSignedEntityStorer::new();
+        signed_entity_storer
+            .expect_get_last_signed_entities_by_type()
+            .returning(move |_, _| Ok(snapshots.clone()));
+
+        MithrilStaleSnapshotDetector::new(cadence_multiple, Arc::new(signed_entity_storer))
+    }
+
+    #[tokio::test]
+    async fn not_stale_without_certified_snapshots() {
+        let detector = build_detector(3, vec![]);
+
+        let status = detector
+            .get_status(date("2024-01-01T12:00:00Z"))
+            .await
+            .unwrap();
+
+        assert_eq!(StaleSnapshotStatus::default(), status);
+    }
+
+    #[tokio::test]
+    async fn not_stale_within_the_multiple_of_the_cadence() {
+        let detector = build_detector(
+            3,
+            vec![
+                snapshot(2, "2024-01-01T01:00:00Z"),
+                snapshot(1, "2024-01-01T00:00:00Z"),
+            ],
+        );
+
+        let status = detector
+            .get_status(date("2024-01-01T04:00:00Z"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            StaleSnapshotStatus {
+                last_certified_at: Some(date("2024-01-01T01:00:00Z")),
+                expected_interval_seconds: Some(3600),
+                stale_after: Some(date("2024-01-01T04:00:00Z")),
+                is_stale: false,
+            },
+            status
+        );
+    }
+
+    #[tokio::test]
+    async fn stale_after_the_multiple_of_the_cadence() {
+        let detector = build_detector(
+            3,
+            vec![
+                snapshot(2, "2024-01-01T01:00:00Z"),
+                snapshot(1, "2024-01-01T00:00:00Z"),
+            ],
+        );
+
+        let status = detector
+            .get_status(date("2024-01-01T04:00:01Z"))
+            .await
+            .unwrap();
+
+        assert!(status.is_stale);
+    }
+}
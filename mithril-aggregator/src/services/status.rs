@@ -0,0 +1,180 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use mithril_common::entities::Certificate;
+use mithril_common::era::EraChecker;
+use mithril_common::StdResult;
+use mithril_persistence::database::{ApplicationNodeType, DatabaseVersionProvider, DbVersion};
+use mithril_persistence::sqlite::SqliteConnection;
+
+use crate::database::repository::CertificateRepository;
+use crate::dependency_injection::EpochServiceWrapper;
+use crate::entities::AggregatorStatusMessage;
+use crate::services::ConfigurationReloader;
+use crate::Configuration;
+
+/// The StatusService assembles the build, runtime and system information of the aggregator.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait StatusService: Sync + Send {
+    /// Compute the current status of the aggregator
+    async fn get_status(&self) -> StdResult<AggregatorStatusMessage>;
+}
+
+/// Mithril aggregator implementation of the [StatusService]
+///
+/// The status is served to unauthenticated clients: the current epoch is read from the cache of
+/// the epoch service instead of the chain, and the database version, that only changes when the
+/// aggregator starts, is read once.
+pub struct MithrilStatusService {
+    config: Configuration,
+    configuration_reloader: Arc<ConfigurationReloader>,
+    epoch_service: EpochServiceWrapper,
+    era_checker: Arc<EraChecker>,
+    certificate_repository: Arc<CertificateRepository>,
+    database_version: DbVersion,
+    started_at: DateTime<Utc>,
+}
+
+impl MithrilStatusService {
+    /// MithrilStatusService factory, the aggregator is considered started when the service is
+    /// created.
+    pub fn new(
+        config: Configuration,
+        configuration_reloader: Arc<ConfigurationReloader>,
+        epoch_service: EpochServiceWrapper,
+        era_checker: Arc<EraChecker>,
+        certificate_repository: Arc<CertificateRepository>,
+        database_version: DbVersion,
+    ) -> Self {
+        Self {
+            config,
+            configuration_reloader,
+            epoch_service,
+            era_checker,
+            certificate_repository,
+            database_version,
+            started_at: Utc::now(),
+        }
+    }
+
+    /// Read the version of the structure of the database, the latest migration applied.
+    pub fn read_database_version(sqlite_connection: &SqliteConnection) -> StdResult<DbVersion> {
+        let database_version_provider = DatabaseVersionProvider::new(sqlite_connection);
+        // The 'db_version' table does not exist before the first migration
+        if !database_version_provider.table_exists()? {
+            return Ok(0);
+        }
+
+        Ok(database_version_provider
+            .get_application_version(&ApplicationNodeType::Aggregator)
+            .with_context(|| "StatusService can not get the database version")?
+            .map(|database_version| database_version.version)
+            .unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl StatusService for MithrilStatusService {
+    async fn get_status(&self) -> StdResult<AggregatorStatusMessage> {
+        let epoch = self
+            .epoch_service
+            .read()
+            .await
+            .epoch_of_current_data()
+            .with_context(|| "StatusService can not get the current epoch")?;
+        let latest_certificate_hash = self
+            .certificate_repository
+            .get_latest_certificates::<Certificate>(1)
+            .await
+            .with_context(|| "StatusService can not get the latest certificate")?
+            .first()
+            .map(|certificate| certificate.hash.clone());
+        // The allowed signed entity types can be reloaded at runtime
+        let signed_entity_types = self
+            .configuration_reloader
+            .reloaded_configuration(&self.config)
+            .list_allowed_signed_entity_types_discriminants()
+            .with_context(|| "StatusService can not list the allowed signed entity types")?;
+        let now = Utc::now();
+
+        Ok(AggregatorStatusMessage {
+            aggregator_node_version: env!("CARGO_PKG_VERSION").to_string(),
+            supported_era: self.era_checker.current_era(),
+            epoch,
+            latest_certificate_hash,
+            signed_entity_types,
+            database_version: self.database_version,
+            started_at: self.started_at,
+            uptime_seconds: (now - self.started_at).num_seconds().max(0) as u64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::RwLock;
+
+    use mithril_common::crypto_helper::tests_setup::setup_certificate_chain;
+    use mithril_common::entities::Epoch;
+    use mithril_common::test_utils::MithrilFixtureBuilder;
+
+    use crate::database::test_helper::insert_certificate_records;
+    use crate::dependency_injection::DependenciesBuilder;
+    use crate::services::FakeEpochService;
+
+    use super::*;
+
+    async fn build_service(deps: &mut DependenciesBuilder, epoch: Epoch) -> MithrilStatusService {
+        let fixture = MithrilFixtureBuilder::default().with_signers(1).build();
+        let connection = deps.get_sqlite_connection().await.unwrap();
+
+        MithrilStatusService::new(
+            deps.configuration.clone(),
+            deps.get_configuration_reloader().await.unwrap(),
+            Arc::new(RwLock::new(FakeEpochService::from_fixture(epoch, &fixture))),
+            deps.get_era_checker().await.unwrap(),
+            deps.get_certificate_repository().await.unwrap(),
+            MithrilStatusService::read_database_version(&connection).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn get_status_assembles_the_information_of_the_services() {
+        let (certificates, _) = setup_certificate_chain(3, 1);
+        let mut deps = DependenciesBuilder::new(Configuration::new_sample());
+        let connection = deps.get_sqlite_connection().await.unwrap();
+        // The certificates are stored from the genesis to the latest certificate
+        insert_certificate_records(&connection, certificates.iter().rev().cloned().collect());
+        let service = build_service(&mut deps, Epoch(7)).await;
+
+        let status = service.get_status().await.unwrap();
+
+        assert_eq!(env!("CARGO_PKG_VERSION"), status.aggregator_node_version);
+        assert_eq!(Epoch(7), status.epoch);
+        assert_eq!(
+            Some(certificates[0].hash.clone()),
+            status.latest_certificate_hash
+        );
+        assert_eq!(
+            deps.configuration
+                .list_allowed_signed_entity_types_discriminants()
+                .unwrap(),
+            status.signed_entity_types
+        );
+        assert!(status.database_version > 0);
+        assert!(status.started_at <= Utc::now());
+    }
+
+    #[tokio::test]
+    async fn get_status_without_certificate() {
+        let mut deps = DependenciesBuilder::new(Configuration::new_sample());
+        let service = build_service(&mut deps, Epoch(7)).await;
+
+        let status = service.get_status().await.unwrap();
+
+        assert_eq!(None, status.latest_certificate_hash);
+    }
+}
@@ -10,7 +10,7 @@ use async_trait::async_trait;
 use mithril_common::{
     chain_observer::ChainObserver,
     digesters::ImmutableFileObserver,
-    entities::{CardanoDbBeacon, Epoch},
+    entities::{CardanoDbBeacon, ChainPoint, Epoch},
     CardanoNetwork, StdResult,
 };
 use thiserror::Error;
@@ -19,6 +19,9 @@ use thiserror::Error;
 enum MithrilTickerError {
     #[error("No Epoch information was returned by the ChainObserver.")]
     NoEpoch,
+
+    #[error("No ChainPoint information was returned by the ChainObserver.")]
+    NoChainPoint,
 }
 
 /// Service trait with consistent business oriented API.
@@ -29,6 +32,9 @@ pub trait TickerService: Send + Sync {
 
     /// Return the current Beacon used for CardanoImmutableFileDigest message type.
     async fn get_current_immutable_beacon(&self) -> StdResult<CardanoDbBeacon>;
+
+    /// Return the current ChainPoint (slot, block number and hash) as read from the chain.
+    async fn get_current_chain_point(&self) -> StdResult<ChainPoint>;
 }
 
 /// ## MithrilTickerService
@@ -87,6 +93,16 @@ impl TickerService for MithrilTickerService {
             immutable_file_number,
         ))
     }
+
+    async fn get_current_chain_point(&self) -> StdResult<ChainPoint> {
+        let chain_point = self
+            .chain_observer
+            .get_current_chain_point()
+            .await?
+            .ok_or(MithrilTickerError::NoChainPoint)?;
+
+        Ok(chain_point)
+    }
 }
 
 #[cfg(test)]
@@ -101,7 +117,11 @@ mod tests {
         chain_observer
             .expect_get_current_epoch()
             .returning(|| Ok(Some(Epoch(10))))
-            .times(1);
+            .times(0..=1);
+        chain_observer
+            .expect_get_current_chain_point()
+            .returning(|| Ok(Some(ChainPoint::dummy())))
+            .times(0..=1);
         let immutable_observer = DumbImmutableFileObserver::new();
         immutable_observer.shall_return(Some(99)).await;
         let network = CardanoNetwork::DevNet(42);
@@ -136,6 +156,14 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn get_chain_point() {
+        let ticker_service = get_ticker().await;
+        let chain_point = ticker_service.get_current_chain_point().await.unwrap();
+
+        assert_eq!(ChainPoint::dummy(), chain_point);
+    }
+
     #[tokio::test]
     async fn no_beacon_error() {
         let mut chain_observer = MockChainObserver::new();
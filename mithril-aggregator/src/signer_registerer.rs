@@ -6,11 +6,17 @@ use tokio::sync::RwLock;
 
 use mithril_common::{
     chain_observer::ChainObserver,
-    crypto_helper::{KESPeriod, ProtocolKeyRegistration},
-    entities::{Epoch, Signer, SignerWithStake, StakeDistribution},
+    crypto_helper::{
+        compute_operational_certificate_kes_period, compute_signer_unregistration_message,
+        verify_registration_nonce_signature, KESPeriod, ProtocolKeyRegistration,
+        ProtocolSignerVerificationKeySignature,
+    },
+    entities::{Epoch, PartyId, Signer, SignerWithStake, StakeDistribution},
+    era::SupportedEra,
     StdError, StdResult,
 };
 
+use crate::database::repository::SignerUnregistrationStorer;
 use crate::VerificationKeyStorer;
 
 use mithril_common::chain_observer::ChainObserverError;
@@ -52,6 +58,18 @@ pub enum SignerRegistrationError {
     /// Signer recorder failed.
     #[error("signer recorder failed: '{0}'")]
     FailedSignerRecorder(String),
+
+    /// Signer to unregister is not registered.
+    #[error("signer '{0}' is not registered")]
+    UnknownSigner(PartyId),
+
+    /// Signature of the signer unregistration can not be verified.
+    #[error("invalid signature of the unregistration of signer '{0}'")]
+    InvalidUnregistrationSignature(PartyId),
+
+    /// Signer is unregistered from the epoch of the registration round.
+    #[error("signer '{0}' is unregistered from the epoch of the registration round")]
+    UnregisteredSigner(PartyId),
 }
 
 /// Represents the information needed to handle a signer registration round
@@ -84,6 +102,29 @@ pub trait SignerRegisterer: Sync + Send {
         signer: &Signer,
     ) -> Result<SignerWithStake, SignerRegistrationError>;
 
+    /// Register the verification key created by a signer with the signature scheme of the given
+    /// era, ahead of the switch to this era, along with the verification key of its registration
+    /// for the current era.
+    async fn register_next_era_signer(
+        &self,
+        epoch: Epoch,
+        era: SupportedEra,
+        signer: &Signer,
+    ) -> Result<(), SignerRegistrationError>;
+
+    /// Unregister a signer from the given epoch, the unregistration must be signed with the KES
+    /// secret key of the operational certificate the signer registered with, along with the
+    /// given registration nonce of the current epoch.
+    ///
+    /// The signer can not be registered again at this epoch.
+    async fn unregister_signer(
+        &self,
+        epoch: Epoch,
+        party_id: &PartyId,
+        registration_nonce: &str,
+        signature: &ProtocolSignerVerificationKeySignature,
+    ) -> Result<SignerWithStake, SignerRegistrationError>;
+
     /// Get current open round if exists
     async fn get_current_round(&self) -> Option<SignerRegistrationRound>;
 }
@@ -125,6 +166,9 @@ pub struct MithrilSignerRegisterer {
     /// Signer recorder
     signer_recorder: Arc<dyn SignerRecorder>,
 
+    /// Tombstones of the unregistered signers
+    signer_unregistration_store: Arc<dyn SignerUnregistrationStorer>,
+
     /// Number of epochs before previous records will be deleted at the next registration round
     /// opening
     verification_key_epoch_retention_limit: Option<u64>,
@@ -136,6 +180,7 @@ impl MithrilSignerRegisterer {
         chain_observer: Arc<dyn ChainObserver>,
         verification_key_store: Arc<dyn VerificationKeyStorer>,
         signer_recorder: Arc<dyn SignerRecorder>,
+        signer_unregistration_store: Arc<dyn SignerUnregistrationStorer>,
         verification_key_epoch_retention_limit: Option<u64>,
     ) -> Self {
         Self {
@@ -143,6 +188,7 @@ impl MithrilSignerRegisterer {
             chain_observer,
             verification_key_store,
             signer_recorder,
+            signer_unregistration_store,
             verification_key_epoch_retention_limit,
         }
     }
@@ -151,6 +197,69 @@ impl MithrilSignerRegisterer {
     pub async fn get_current_round(&self) -> Option<SignerRegistrationRound> {
         self.current_round.read().await.as_ref().cloned()
     }
+
+    fn check_registration_round(
+        registration_round: Option<&SignerRegistrationRound>,
+        epoch: Epoch,
+    ) -> Result<&SignerRegistrationRound, SignerRegistrationError> {
+        let registration_round =
+            registration_round.ok_or(SignerRegistrationError::RegistrationRoundNotYetOpened)?;
+        if registration_round.epoch != epoch {
+            return Err(SignerRegistrationError::RegistrationRoundUnexpectedEpoch {
+                current_round_epoch: registration_round.epoch,
+                received_epoch: epoch,
+            });
+        }
+
+        Ok(registration_round)
+    }
+
+    /// Verify the verification key of the signer and the signature of its operational
+    /// certificate, returns the party id of the signer.
+    async fn verify_signer_key_registration(
+        &self,
+        registration_round: &SignerRegistrationRound,
+        signer: &Signer,
+    ) -> Result<PartyId, SignerRegistrationError> {
+        let mut key_registration = ProtocolKeyRegistration::init(
+            &registration_round
+                .stake_distribution
+                .iter()
+                .map(|(k, v)| (k.to_owned(), *v))
+                .collect::<Vec<_>>(),
+        );
+        let party_id_register = match signer.party_id.as_str() {
+            "" => None,
+            party_id => Some(party_id.to_string()),
+        };
+        let kes_period = match &signer.operational_certificate {
+            Some(operational_certificate) => Some(
+                self.chain_observer
+                    .get_current_kes_period(operational_certificate)
+                    .await?
+                    .unwrap_or_default()
+                    - operational_certificate.start_kes_period as KESPeriod,
+            ),
+            None => None,
+        };
+        let party_id_save = key_registration
+            .register(
+                party_id_register.clone(),
+                signer.operational_certificate.clone(),
+                signer.verification_key_signature,
+                kes_period,
+                signer.verification_key,
+            )
+            .with_context(|| {
+                format!(
+                    "KeyRegwrapper can not register signer with party_id: '{:?}'",
+                    party_id_register
+                )
+            })
+            .map_err(|e| SignerRegistrationError::FailedSignerRegistration(anyhow!(e)))?;
+
+        Ok(party_id_save)
+    }
 }
 
 #[async_trait]
@@ -177,6 +286,16 @@ impl SignerRegistrationRoundOpener for MithrilSignerRegisterer {
                     )
                 })
                 .map_err(|e| SignerRegistrationError::StoreError(anyhow!(e)))?;
+            self.signer_unregistration_store
+                .prune_signer_unregistrations(registration_epoch - retention_limit)
+                .await
+                .with_context(|| {
+                    format!(
+                        "SignerUnregistrationStorer can not prune signer unregistrations below epoch: '{}'",
+                        registration_epoch - retention_limit
+                    )
+                })
+                .map_err(|e| SignerRegistrationError::StoreError(anyhow!(e)))?;
         }
 
         Ok(())
@@ -198,52 +317,24 @@ impl SignerRegisterer for MithrilSignerRegisterer {
         signer: &Signer,
     ) -> Result<SignerWithStake, SignerRegistrationError> {
         let registration_round = self.current_round.read().await;
-        let registration_round = registration_round
-            .as_ref()
-            .ok_or(SignerRegistrationError::RegistrationRoundNotYetOpened)?;
-        if registration_round.epoch != epoch {
-            return Err(SignerRegistrationError::RegistrationRoundUnexpectedEpoch {
-                current_round_epoch: registration_round.epoch,
-                received_epoch: epoch,
-            });
-        }
-
-        let mut key_registration = ProtocolKeyRegistration::init(
-            &registration_round
-                .stake_distribution
-                .iter()
-                .map(|(k, v)| (k.to_owned(), *v))
-                .collect::<Vec<_>>(),
-        );
-        let party_id_register = match signer.party_id.as_str() {
-            "" => None,
-            party_id => Some(party_id.to_string()),
-        };
-        let kes_period = match &signer.operational_certificate {
-            Some(operational_certificate) => Some(
-                self.chain_observer
-                    .get_current_kes_period(operational_certificate)
-                    .await?
-                    .unwrap_or_default()
-                    - operational_certificate.start_kes_period as KESPeriod,
-            ),
-            None => None,
-        };
-        let party_id_save = key_registration
-            .register(
-                party_id_register.clone(),
-                signer.operational_certificate.clone(),
-                signer.verification_key_signature,
-                kes_period,
-                signer.verification_key,
-            )
+        let registration_round =
+            Self::check_registration_round(registration_round.as_ref(), epoch)?;
+        let party_id_save = self
+            .verify_signer_key_registration(registration_round, signer)
+            .await?;
+        if self
+            .signer_unregistration_store
+            .is_signer_unregistered(epoch, &party_id_save)
+            .await
             .with_context(|| {
                 format!(
-                    "KeyRegwrapper can not register signer with party_id: '{:?}'",
-                    party_id_register
+                    "SignerUnregistrationStorer can not check the unregistration of party_id: '{party_id_save}' for epoch: '{epoch}'"
                 )
             })
-            .map_err(|e| SignerRegistrationError::FailedSignerRegistration(anyhow!(e)))?;
+            .map_err(SignerRegistrationError::StoreError)?
+        {
+            return Err(SignerRegistrationError::UnregisteredSigner(party_id_save));
+        }
         let mut signer_save = SignerWithStake::from_signer(
             signer.to_owned(),
             *registration_round
@@ -278,6 +369,125 @@ impl SignerRegisterer for MithrilSignerRegisterer {
         }
     }
 
+    async fn register_next_era_signer(
+        &self,
+        epoch: Epoch,
+        era: SupportedEra,
+        signer: &Signer,
+    ) -> Result<(), SignerRegistrationError> {
+        let registration_round = self.current_round.read().await;
+        let registration_round =
+            Self::check_registration_round(registration_round.as_ref(), epoch)?;
+        let party_id_save = self
+            .verify_signer_key_registration(registration_round, signer)
+            .await?;
+        let is_registered = self
+            .verification_key_store
+            .get_verification_keys(epoch)
+            .await
+            .with_context(|| {
+                format!("VerificationKeyStorer can not get verification keys for epoch: '{epoch}'")
+            })
+            .map_err(SignerRegistrationError::StoreError)?
+            .is_some_and(|signers| signers.contains_key(&party_id_save));
+        if !is_registered {
+            return Err(SignerRegistrationError::UnknownSigner(party_id_save));
+        }
+
+        self.verification_key_store
+            .save_next_era_verification_key(
+                epoch,
+                era,
+                Signer {
+                    party_id: party_id_save.clone(),
+                    ..signer.to_owned()
+                },
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "VerificationKeyStorer can not save the {era} era verification key for party_id: '{party_id_save}' for epoch: '{epoch}'"
+                )
+            })
+            .map_err(SignerRegistrationError::StoreError)
+    }
+
+    async fn unregister_signer(
+        &self,
+        epoch: Epoch,
+        party_id: &PartyId,
+        registration_nonce: &str,
+        signature: &ProtocolSignerVerificationKeySignature,
+    ) -> Result<SignerWithStake, SignerRegistrationError> {
+        let registration_round = self.current_round.read().await;
+        let registration_round = registration_round
+            .as_ref()
+            .ok_or(SignerRegistrationError::RegistrationRoundNotYetOpened)?;
+        if registration_round.epoch != epoch {
+            return Err(SignerRegistrationError::RegistrationRoundUnexpectedEpoch {
+                current_round_epoch: registration_round.epoch,
+                received_epoch: epoch,
+            });
+        }
+
+        let signer = self
+            .verification_key_store
+            .get_signers(epoch)
+            .await
+            .with_context(|| {
+                format!("VerificationKeyStorer can not get the signers for epoch: '{epoch}'")
+            })
+            .map_err(SignerRegistrationError::StoreError)?
+            .unwrap_or_default()
+            .into_iter()
+            .find(|signer| &signer.party_id == party_id)
+            .ok_or_else(|| SignerRegistrationError::UnknownSigner(party_id.clone()))?;
+        let operational_certificate = signer.operational_certificate.as_ref().ok_or_else(|| {
+            SignerRegistrationError::InvalidUnregistrationSignature(party_id.clone())
+        })?;
+        let current_kes_period = self
+            .chain_observer
+            .get_current_kes_period(operational_certificate)
+            .await?
+            .unwrap_or_default();
+        compute_operational_certificate_kes_period(current_kes_period, operational_certificate)
+            .ok_or_else(|| anyhow!("The operational certificate is not valid yet"))
+            .and_then(|kes_period| {
+                verify_registration_nonce_signature(
+                    signature,
+                    operational_certificate,
+                    kes_period,
+                    &compute_signer_unregistration_message(epoch, registration_nonce, party_id),
+                )
+            })
+            .map_err(|_| {
+                SignerRegistrationError::InvalidUnregistrationSignature(party_id.clone())
+            })?;
+
+        // The tombstone is recorded first so that the signer can not be registered again at
+        // this epoch, even by replaying its previous registration.
+        self.signer_unregistration_store
+            .record_signer_unregistration(epoch, party_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "SignerUnregistrationStorer can not record the unregistration of party_id: '{party_id}' for epoch: '{epoch}'"
+                )
+            })
+            .map_err(SignerRegistrationError::StoreError)?;
+
+        self.verification_key_store
+            .remove_verification_key(epoch, party_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "VerificationKeyStorer can not remove verification key for party_id: '{party_id}' for epoch: '{epoch}'"
+                )
+            })
+            .map_err(SignerRegistrationError::StoreError)?
+            .ok_or_else(|| SignerRegistrationError::UnknownSigner(party_id.clone()))
+    }
+
     async fn get_current_round(&self) -> Option<SignerRegistrationRound> {
         self.current_round.read().await.as_ref().cloned()
     }
@@ -289,17 +499,94 @@ mod tests {
 
     use mithril_common::{
         chain_observer::FakeObserver,
+        crypto_helper::{compute_signer_unregistration_message, sign_registration_nonce_message},
         entities::{Epoch, PartyId, Signer, SignerWithStake},
-        test_utils::{fake_data, MithrilFixtureBuilder},
+        era::SupportedEra,
+        protocol::{ProtocolSignatureScheme, StmSignatureScheme},
+        test_utils::{fake_data, MithrilFixture, MithrilFixtureBuilder, SignerFixture},
     };
     use mithril_persistence::store::adapter::MemoryAdapter;
 
     use crate::{
-        MithrilSignerRegisterer, SignerRegisterer, SignerRegistrationRoundOpener,
-        VerificationKeyStore, VerificationKeyStorer,
+        database::{
+            repository::{SignerUnregistrationRepository, SignerUnregistrationStorer},
+            test_helper::main_db_connection,
+        },
+        MithrilSignerRegisterer, SignerRegisterer, SignerRegistrationError,
+        SignerRegistrationRoundOpener, VerificationKeyStore, VerificationKeyStorer,
     };
 
-    use super::MockSignerRecorder;
+    use super::{MockSignerRecorder, ProtocolSignerVerificationKeySignature};
+
+    const REGISTRATION_NONCE: &str = "registration-nonce";
+
+    fn signer_unregistration_store() -> Arc<SignerUnregistrationRepository> {
+        Arc::new(SignerUnregistrationRepository::new(Arc::new(
+            main_db_connection().unwrap(),
+        )))
+    }
+
+    async fn signer_registerer_with_registered_signers(
+        registration_epoch: Epoch,
+        fixture: &MithrilFixture,
+    ) -> (MithrilSignerRegisterer, Arc<VerificationKeyStore>) {
+        let verification_key_store = Arc::new(VerificationKeyStore::new(Box::new(
+            MemoryAdapter::<Epoch, HashMap<PartyId, SignerWithStake>>::new(None).unwrap(),
+        )));
+        let mut signer_recorder = MockSignerRecorder::new();
+        signer_recorder
+            .expect_record_signer_registration()
+            .returning(|_| Ok(()));
+        let signer_registerer = MithrilSignerRegisterer::new(
+            Arc::new(FakeObserver::default()),
+            verification_key_store.clone(),
+            Arc::new(signer_recorder),
+            signer_unregistration_store(),
+            None,
+        );
+        signer_registerer
+            .open_registration_round(registration_epoch, fixture.stake_distribution())
+            .await
+            .unwrap();
+        for signer in fixture.signers() {
+            signer_registerer
+                .register_signer(registration_epoch, &signer)
+                .await
+                .unwrap();
+        }
+
+        (signer_registerer, verification_key_store)
+    }
+
+    fn sign_unregistration(
+        signer: &SignerFixture,
+        epoch: Epoch,
+    ) -> ProtocolSignerVerificationKeySignature {
+        sign_registration_nonce_message(
+            signer.kes_secret_key_path().unwrap(),
+            0,
+            &compute_signer_unregistration_message(epoch, REGISTRATION_NONCE, &signer.party_id()),
+        )
+        .unwrap()
+    }
+
+    fn create_next_era_signer(signer: &SignerFixture, fixture: &MithrilFixture) -> Signer {
+        let protocol_initializer = StmSignatureScheme
+            .create_protocol_initializer(
+                signer.signer_with_stake.stake,
+                &fixture.protocol_parameters(),
+                signer.kes_secret_key_path.clone(),
+                Some(0),
+                &mut rand_core::OsRng,
+            )
+            .unwrap();
+
+        Signer {
+            verification_key: protocol_initializer.verification_key().into(),
+            verification_key_signature: protocol_initializer.verification_key_signature(),
+            ..signer.signer_with_stake.clone().into()
+        }
+    }
 
     #[tokio::test]
     async fn can_register_signer_if_registration_round_is_opened_with_operational_certificate() {
@@ -315,6 +602,7 @@ mod tests {
             Arc::new(FakeObserver::default()),
             verification_key_store.clone(),
             Arc::new(signer_recorder),
+            signer_unregistration_store(),
             None,
         );
         let registration_epoch = Epoch(1);
@@ -360,6 +648,7 @@ mod tests {
             Arc::new(FakeObserver::default()),
             verification_key_store.clone(),
             Arc::new(signer_recorder),
+            signer_unregistration_store(),
             None,
         );
         let registration_epoch = Epoch(1);
@@ -404,6 +693,7 @@ mod tests {
             Arc::new(FakeObserver::default()),
             verification_key_store.clone(),
             Arc::new(signer_recorder),
+            signer_unregistration_store(),
             None,
         );
         let registration_epoch = Epoch(1);
@@ -433,10 +723,18 @@ mod tests {
                 .unwrap(),
         )));
         let signer_recorder = MockSignerRecorder::new();
+        let signer_unregistration_store = signer_unregistration_store();
+        for epoch in 1..=5 {
+            signer_unregistration_store
+                .record_signer_unregistration(Epoch(epoch), &"party_id".to_string())
+                .await
+                .unwrap();
+        }
         let signer_registerer = MithrilSignerRegisterer::new(
             Arc::new(FakeObserver::default()),
             verification_key_store.clone(),
             Arc::new(signer_recorder),
+            signer_unregistration_store.clone(),
             Some(2),
         );
         let fixture = MithrilFixtureBuilder::default().with_signers(5).build();
@@ -462,5 +760,277 @@ mod tests {
             verification_keys.is_some(),
             "Verification keys of the previous epoch should not have been pruned"
         );
+
+        for (epoch, is_pruned) in [(3, true), (4, false)] {
+            let is_unregistered = signer_unregistration_store
+                .is_signer_unregistered(Epoch(epoch), &"party_id".to_string())
+                .await
+                .unwrap();
+            assert_eq!(
+                !is_pruned, is_unregistered,
+                "unexpected signer unregistration at epoch {epoch}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn can_unregister_signer_with_a_valid_signature() {
+        let registration_epoch = Epoch(1);
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (signer_registerer, verification_key_store) =
+            signer_registerer_with_registered_signers(registration_epoch, &fixture).await;
+        let signers = fixture.signers_fixture();
+
+        let unregistered_signer = signer_registerer
+            .unregister_signer(
+                registration_epoch,
+                &signers[0].party_id(),
+                REGISTRATION_NONCE,
+                &sign_unregistration(&signers[0], registration_epoch),
+            )
+            .await
+            .expect("signer unregistration should not fail");
+
+        assert_eq!(signers[0].party_id(), unregistered_signer.party_id);
+        let registered_signers = verification_key_store
+            .get_verification_keys(registration_epoch)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            vec![signers[1].party_id()],
+            registered_signers.into_keys().collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn cant_unregister_signer_with_an_invalid_signature() {
+        let registration_epoch = Epoch(1);
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (signer_registerer, verification_key_store) =
+            signer_registerer_with_registered_signers(registration_epoch, &fixture).await;
+        let signers = fixture.signers_fixture();
+
+        for signature in [
+            sign_unregistration(&signers[1], registration_epoch),
+            sign_unregistration(&signers[0], registration_epoch + 1),
+        ] {
+            let error = signer_registerer
+                .unregister_signer(
+                    registration_epoch,
+                    &signers[0].party_id(),
+                    REGISTRATION_NONCE,
+                    &signature,
+                )
+                .await
+                .expect_err("signer unregistration should fail with an invalid signature");
+
+            assert!(
+                matches!(
+                    error,
+                    SignerRegistrationError::InvalidUnregistrationSignature(_)
+                ),
+                "unexpected error: {error:?}"
+            );
+        }
+        assert_eq!(
+            2,
+            verification_key_store
+                .get_signers(registration_epoch)
+                .await
+                .unwrap()
+                .unwrap()
+                .len()
+        );
+    }
+
+    #[tokio::test]
+    async fn cant_unregister_an_unknown_signer_or_for_another_epoch() {
+        let registration_epoch = Epoch(1);
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (signer_registerer, _) =
+            signer_registerer_with_registered_signers(registration_epoch, &fixture).await;
+        let signer = &fixture.signers_fixture()[0];
+
+        let error = signer_registerer
+            .unregister_signer(
+                registration_epoch,
+                &"unknown-party-id".to_string(),
+                REGISTRATION_NONCE,
+                &sign_unregistration(signer, registration_epoch),
+            )
+            .await
+            .expect_err("unregistration of an unknown signer should fail");
+        assert!(
+            matches!(error, SignerRegistrationError::UnknownSigner(_)),
+            "unexpected error: {error:?}"
+        );
+
+        let error = signer_registerer
+            .unregister_signer(
+                registration_epoch + 1,
+                &signer.party_id(),
+                REGISTRATION_NONCE,
+                &sign_unregistration(signer, registration_epoch + 1),
+            )
+            .await
+            .expect_err("unregistration for another epoch than the current round should fail");
+        assert!(
+            matches!(
+                error,
+                SignerRegistrationError::RegistrationRoundUnexpectedEpoch { .. }
+            ),
+            "unexpected error: {error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn cant_unregister_signer_with_the_signature_made_with_another_registration_nonce() {
+        let registration_epoch = Epoch(1);
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (signer_registerer, _) =
+            signer_registerer_with_registered_signers(registration_epoch, &fixture).await;
+        let signer = &fixture.signers_fixture()[0];
+
+        let error = signer_registerer
+            .unregister_signer(
+                registration_epoch,
+                &signer.party_id(),
+                "another-registration-nonce",
+                &sign_unregistration(signer, registration_epoch),
+            )
+            .await
+            .expect_err("signer unregistration should fail with another registration nonce");
+
+        assert!(
+            matches!(
+                error,
+                SignerRegistrationError::InvalidUnregistrationSignature(_)
+            ),
+            "unexpected error: {error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn cant_register_again_an_unregistered_signer_at_the_same_epoch() {
+        let registration_epoch = Epoch(1);
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (signer_registerer, verification_key_store) =
+            signer_registerer_with_registered_signers(registration_epoch, &fixture).await;
+        let signer = &fixture.signers_fixture()[0];
+
+        signer_registerer
+            .unregister_signer(
+                registration_epoch,
+                &signer.party_id(),
+                REGISTRATION_NONCE,
+                &sign_unregistration(signer, registration_epoch),
+            )
+            .await
+            .unwrap();
+        let error = signer_registerer
+            .register_signer(registration_epoch, &fixture.signers()[0])
+            .await
+            .expect_err("registration of an unregistered signer should fail");
+
+        assert!(
+            matches!(error, SignerRegistrationError::UnregisteredSigner(_)),
+            "unexpected error: {error:?}"
+        );
+        assert_eq!(
+            1,
+            verification_key_store
+                .get_signers(registration_epoch)
+                .await
+                .unwrap()
+                .unwrap()
+                .len()
+        );
+    }
+
+    #[tokio::test]
+    async fn can_register_the_next_era_verification_key_of_a_registered_signer() {
+        let registration_epoch = Epoch(1);
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (signer_registerer, verification_key_store) =
+            signer_registerer_with_registered_signers(registration_epoch, &fixture).await;
+        let signer = &fixture.signers_fixture()[0];
+        let next_era_signer = create_next_era_signer(signer, &fixture);
+
+        signer_registerer
+            .register_next_era_signer(registration_epoch, SupportedEra::dummy(), &next_era_signer)
+            .await
+            .expect("next era signer registration should not fail");
+
+        let signers_of_era = verification_key_store
+            .get_signers_of_era(registration_epoch, SupportedEra::dummy())
+            .await
+            .unwrap()
+            .unwrap();
+        let signer_of_era = signers_of_era
+            .iter()
+            .find(|s| s.party_id == signer.party_id())
+            .unwrap();
+        assert_eq!(
+            next_era_signer.verification_key,
+            signer_of_era.verification_key
+        );
+        assert_eq!(signer.signer_with_stake.stake, signer_of_era.stake);
+    }
+
+    #[tokio::test]
+    async fn cant_register_the_next_era_verification_key_of_a_signer_not_registered() {
+        let registration_epoch = Epoch(1);
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (signer_registerer, _) =
+            signer_registerer_with_registered_signers(registration_epoch, &fixture).await;
+        let signer = &fixture.signers_fixture()[0];
+        signer_registerer
+            .unregister_signer(
+                registration_epoch,
+                &signer.party_id(),
+                REGISTRATION_NONCE,
+                &sign_unregistration(signer, registration_epoch),
+            )
+            .await
+            .unwrap();
+
+        let error = signer_registerer
+            .register_next_era_signer(
+                registration_epoch,
+                SupportedEra::dummy(),
+                &create_next_era_signer(signer, &fixture),
+            )
+            .await
+            .expect_err("next era registration of a signer not registered should fail");
+
+        assert!(
+            matches!(error, SignerRegistrationError::UnknownSigner(_)),
+            "unexpected error: {error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn cant_register_a_next_era_verification_key_not_signed_by_the_signer() {
+        let registration_epoch = Epoch(1);
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let (signer_registerer, _) =
+            signer_registerer_with_registered_signers(registration_epoch, &fixture).await;
+        let signers = fixture.signers_fixture();
+        let next_era_signer = Signer {
+            verification_key_signature: create_next_era_signer(&signers[1], &fixture)
+                .verification_key_signature,
+            ..create_next_era_signer(&signers[0], &fixture)
+        };
+
+        let error = signer_registerer
+            .register_next_era_signer(registration_epoch, SupportedEra::dummy(), &next_era_signer)
+            .await
+            .expect_err("next era registration of a key not signed by the signer should fail");
+
+        assert!(
+            matches!(error, SignerRegistrationError::FailedSignerRegistration(_)),
+            "unexpected error: {error:?}"
+        );
     }
 }
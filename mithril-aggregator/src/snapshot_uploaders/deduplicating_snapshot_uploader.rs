@@ -0,0 +1,539 @@
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use flate2::read::GzDecoder;
+use mithril_common::entities::{
+    CompressionAlgorithm, SnapshotManifest, SnapshotManifestEntry, SnapshotObjectHash,
+};
+use mithril_common::StdResult;
+use sha2::{Digest, Sha256};
+use slog_scope::debug;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tar::Archive;
+use uuid::Uuid;
+
+use crate::http_server;
+use crate::snapshot_uploaders::{SnapshotArchiveType, SnapshotLocation, SnapshotUploader};
+use crate::tools::{self, BandwidthLimiter, BlockingThrottledReader};
+
+/// Name of the sub directory where the content addressed objects are stored
+pub const OBJECTS_DIRECTORY: &str = "objects";
+
+/// Name of the sub directory where the snapshot manifests are stored
+pub const MANIFESTS_DIRECTORY: &str = "manifests";
+
+/// Extension of the files being written, renamed to their final name once complete
+const TEMPORARY_FILE_EXTENSION: &str = "tmp";
+
+/// Writer hashing the written bytes the same way than [SnapshotManifest::compute_object_hash]
+struct HashingWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+    written_bytes: u64,
+}
+
+impl<W: Write> HashingWriter<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+            written_bytes: 0,
+        }
+    }
+
+    fn finalize(self) -> (W, SnapshotObjectHash, u64) {
+        (
+            self.inner,
+            hex::encode(self.hasher.finalize()),
+            self.written_bytes,
+        )
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.written_bytes += written as u64;
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// DeduplicatingSnapshotUploader is a snapshot uploader that stores each file of a snapshot
+/// archive as a content addressed object, plus a manifest per snapshot.
+///
+/// Files shared between consecutive snapshots (most of the immutable files) are only stored once.
+#[derive(Clone)]
+pub struct DeduplicatingSnapshotUploader {
+    /// Snapshot server listening IP
+    snapshot_server_url: String,
+
+    /// Target folder where to store the objects and manifests
+    target_location: PathBuf,
+
+    /// Compression algorithm of the archives to upload
+    compression_algorithm: CompressionAlgorithm,
+
+    bandwidth_limiter: Option<Arc<BandwidthLimiter>>,
+}
+
+impl DeduplicatingSnapshotUploader {
+    /// DeduplicatingSnapshotUploader factory
+    pub(crate) fn new(
+        snapshot_server_url: String,
+        target_location: &Path,
+        compression_algorithm: CompressionAlgorithm,
+    ) -> Self {
+        debug!("New DeduplicatingSnapshotUploader created"; "snapshot_server_url" => &snapshot_server_url);
+        Self {
+            snapshot_server_url,
+            target_location: target_location.to_path_buf(),
+            compression_algorithm,
+            bandwidth_limiter: None,
+        }
+    }
+
+    /// Limit the bandwidth used to read the uploaded archives with the given [BandwidthLimiter]
+    pub fn with_bandwidth_limiter(mut self, bandwidth_limiter: Arc<BandwidthLimiter>) -> Self {
+        self.bandwidth_limiter = Some(bandwidth_limiter);
+        self
+    }
+
+    /// The objects are served under the digest of their snapshot so that the aggregator can
+    /// check that they belong to a signed snapshot
+    fn objects_location(&self, digest: &str) -> String {
+        format!(
+            "{}{}/snapshot_objects/{}",
+            self.snapshot_server_url,
+            http_server::SERVER_BASE_PATH,
+            digest
+        )
+    }
+
+    fn manifest_location(&self, digest: &str) -> String {
+        format!(
+            "{}{}/snapshot_manifest/{}",
+            self.snapshot_server_url,
+            http_server::SERVER_BASE_PATH,
+            SnapshotManifest::file_name(digest)
+        )
+    }
+
+    /// Open the given archive file, its reads are paced by the bandwidth limiter if any
+    fn open_archive_file(&self, snapshot_filepath: &Path) -> StdResult<Box<dyn Read>> {
+        let archive_file = File::open(snapshot_filepath)?;
+
+        Ok(match &self.bandwidth_limiter {
+            Some(bandwidth_limiter) => Box::new(BlockingThrottledReader::new(
+                archive_file,
+                bandwidth_limiter.clone(),
+            )),
+            None => Box::new(archive_file),
+        })
+    }
+
+    fn open_archive(&self, snapshot_filepath: &Path) -> StdResult<Archive<Box<dyn Read>>> {
+        let archive_file = self.open_archive_file(snapshot_filepath)?;
+        let archive: Archive<Box<dyn Read>> = match self.compression_algorithm {
+            CompressionAlgorithm::Gzip => Archive::new(Box::new(GzDecoder::new(archive_file))),
+            CompressionAlgorithm::Zstandard => {
+                Archive::new(Box::new(zstd::Decoder::new(archive_file)?))
+            }
+        };
+
+        Ok(archive)
+    }
+
+    /// Path of a temporary file in the given directory, renamed once completely written so that
+    /// a crash never leaves a truncated object or manifest behind.
+    fn temporary_file_path(directory: &Path) -> PathBuf {
+        directory.join(format!(".{}.{TEMPORARY_FILE_EXTENSION}", Uuid::new_v4()))
+    }
+
+    /// Stream the given content to the objects directory, the object is identified by the hash
+    /// of its content and is not written again if it's already stored.
+    fn store_object(
+        objects_dir: &Path,
+        content: &mut impl Read,
+    ) -> StdResult<(SnapshotObjectHash, u64)> {
+        let temporary_path = Self::temporary_file_path(objects_dir);
+        let temporary_file = File::create(&temporary_path).with_context(|| {
+            format!(
+                "Could not create temporary object: '{}'",
+                temporary_path.display()
+            )
+        })?;
+        let mut writer = HashingWriter::new(temporary_file);
+        let result = io::copy(content, &mut writer)
+            .map_err(anyhow::Error::from)
+            .and_then(|_| {
+                let (file, object_hash, size) = writer.finalize();
+                file.sync_all()?;
+                Ok((object_hash, size))
+            });
+        let (object_hash, size) = match result {
+            Ok(stored) => stored,
+            Err(error) => {
+                let _ = fs::remove_file(&temporary_path);
+                return Err(error.context(format!(
+                    "Could not write temporary object: '{}'",
+                    temporary_path.display()
+                )));
+            }
+        };
+
+        let object_path = objects_dir.join(&object_hash);
+        if object_path.exists() {
+            fs::remove_file(&temporary_path)?;
+        } else {
+            fs::rename(&temporary_path, &object_path)
+                .with_context(|| format!("Could not write object: '{}'", object_path.display()))?;
+        }
+
+        Ok((object_hash, size))
+    }
+
+    fn create_objects_directory(&self) -> StdResult<PathBuf> {
+        let objects_dir = self.target_location.join(OBJECTS_DIRECTORY);
+        fs::create_dir_all(&objects_dir).with_context(|| {
+            format!(
+                "Could not create objects directory: '{}'",
+                objects_dir.display()
+            )
+        })?;
+
+        Ok(objects_dir)
+    }
+
+    /// Store each regular file of the archive as an object, skipping the objects already stored.
+    fn store_objects(&self, snapshot_filepath: &Path) -> StdResult<Vec<SnapshotManifestEntry>> {
+        let objects_dir = self.create_objects_directory()?;
+        let mut archive = self.open_archive(snapshot_filepath)?;
+        let mut entries = vec![];
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let path = entry
+                .path()?
+                .to_str()
+                .ok_or_else(|| anyhow!("Archive entry path is not valid UTF-8"))?
+                .to_string();
+            let (object_hash, size) = Self::store_object(&objects_dir, &mut entry)?;
+
+            entries.push(SnapshotManifestEntry {
+                path,
+                object_hash,
+                size,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    fn write_manifest(&self, manifest: &SnapshotManifest) -> StdResult<()> {
+        let manifests_dir = self.target_location.join(MANIFESTS_DIRECTORY);
+        fs::create_dir_all(&manifests_dir).with_context(|| {
+            format!(
+                "Could not create manifests directory: '{}'",
+                manifests_dir.display()
+            )
+        })?;
+        let manifest_path = manifests_dir.join(SnapshotManifest::file_name(&manifest.digest));
+        let temporary_path = Self::temporary_file_path(&manifests_dir);
+        fs::write(&temporary_path, serde_json::to_vec(manifest)?)
+            .and_then(|_| fs::rename(&temporary_path, &manifest_path))
+            .with_context(|| format!("Could not write manifest: '{}'", manifest_path.display()))?;
+
+        Ok(())
+    }
+
+    /// Blocking upload, the archive is read and the objects are written synchronously
+    fn upload_snapshot_blocking(
+        &self,
+        snapshot_filepath: &Path,
+        archive_type: SnapshotArchiveType,
+    ) -> StdResult<SnapshotLocation> {
+        // The ancillary files change at each snapshot, their archive is stored as a single object
+        let digest = tools::extract_digest_from_path(snapshot_filepath)?;
+        if archive_type == SnapshotArchiveType::Ancillary {
+            let objects_dir = self.create_objects_directory()?;
+            let mut archive_file = self.open_archive_file(snapshot_filepath)?;
+            let (object_hash, _) = Self::store_object(&objects_dir, &mut archive_file)
+                .with_context(|| "Ancillary archive storage failure")?;
+
+            return Ok(format!("{}/{object_hash}", self.objects_location(&digest)));
+        }
+
+        let entries = self
+            .store_objects(snapshot_filepath)
+            .with_context(|| "Snapshot objects storage failure")?;
+        let manifest =
+            SnapshotManifest::new(digest.clone(), self.objects_location(&digest), entries);
+        self.write_manifest(&manifest)?;
+
+        Ok(self.manifest_location(&digest))
+    }
+}
+
+#[async_trait]
+impl SnapshotUploader for DeduplicatingSnapshotUploader {
+    async fn upload_snapshot(
+        &self,
+        snapshot_filepath: &Path,
+        archive_type: SnapshotArchiveType,
+    ) -> StdResult<SnapshotLocation> {
+        let uploader = self.clone();
+        let snapshot_filepath = snapshot_filepath.to_path_buf();
+
+        tokio::task::spawn_blocking(move || {
+            uploader.upload_snapshot_blocking(&snapshot_filepath, archive_type)
+        })
+        .await
+        .with_context(|| "Deduplicating snapshot upload task failure")?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use flate2::{write::GzEncoder, Compression};
+    use std::path::{Path, PathBuf};
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn create_fake_archive(dir: &Path, digest: &str, files: &[(&str, &str)]) -> PathBuf {
+        let file_path = dir.join(format!("test.{digest}.tar.gz"));
+        let archive_file = File::create(&file_path).unwrap();
+        let mut tar = tar::Builder::new(GzEncoder::new(archive_file, Compression::default()));
+        for (name, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_cksum();
+            tar.append_data(&mut header, name, content.as_bytes())
+                .unwrap();
+        }
+        tar.into_inner().unwrap().finish().unwrap();
+
+        file_path
+    }
+
+    fn read_manifest(target_dir: &Path, digest: &str) -> SnapshotManifest {
+        let manifest_path = target_dir
+            .join(MANIFESTS_DIRECTORY)
+            .join(SnapshotManifest::file_name(digest));
+        serde_json::from_slice(&fs::read(manifest_path).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn upload_store_objects_and_manifest() {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let digest = "41e27b9ed5a32531b95b2b7ff3c0757591a06a337efaf19a524a998e348028e7";
+        let archive = create_fake_archive(
+            source_dir.path(),
+            digest,
+            &[
+                ("immutable/00001.chunk", "chunk 1"),
+                ("protocolMagicId", "42"),
+            ],
+        );
+        let uploader = DeduplicatingSnapshotUploader::new(
+            "http://test.com:8080/".to_string(),
+            target_dir.path(),
+            CompressionAlgorithm::Gzip,
+        );
+
+        let location = uploader
+            .upload_snapshot(&archive, SnapshotArchiveType::Snapshot)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            format!(
+                "http://test.com:8080/{}/snapshot_manifest/{digest}.manifest.json",
+                http_server::SERVER_BASE_PATH
+            ),
+            location
+        );
+        let manifest = read_manifest(target_dir.path(), digest);
+        assert_eq!(
+            format!(
+                "http://test.com:8080/{}/snapshot_objects/{digest}",
+                http_server::SERVER_BASE_PATH
+            ),
+            manifest.objects_location
+        );
+        assert_eq!(2, manifest.entries.len());
+        for entry in &manifest.entries {
+            let object_content = fs::read(
+                target_dir
+                    .path()
+                    .join(OBJECTS_DIRECTORY)
+                    .join(&entry.object_hash),
+            )
+            .unwrap();
+            assert_eq!(
+                SnapshotManifest::compute_object_hash(&object_content),
+                entry.object_hash
+            );
+            assert_eq!(object_content.len() as u64, entry.size);
+        }
+    }
+
+    #[tokio::test]
+    async fn upload_with_a_bandwidth_limiter_store_all_the_objects() {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let digest = "41e27b9ed5a32531b95b2b7ff3c0757591a06a337efaf19a524a998e348028e7";
+        let archive = create_fake_archive(
+            source_dir.path(),
+            digest,
+            &[
+                ("immutable/00001.chunk", "chunk 1"),
+                ("protocolMagicId", "42"),
+            ],
+        );
+        let uploader = DeduplicatingSnapshotUploader::new(
+            "http://test.com:8080/".to_string(),
+            target_dir.path(),
+            CompressionAlgorithm::Gzip,
+        )
+        .with_bandwidth_limiter(Arc::new(BandwidthLimiter::new(1024 * 1024)));
+
+        uploader
+            .upload_snapshot(&archive, SnapshotArchiveType::Snapshot)
+            .await
+            .unwrap();
+
+        let manifest = read_manifest(target_dir.path(), digest);
+        assert_eq!(2, manifest.entries.len());
+        for entry in &manifest.entries {
+            assert!(target_dir
+                .path()
+                .join(OBJECTS_DIRECTORY)
+                .join(&entry.object_hash)
+                .exists());
+        }
+    }
+
+    #[tokio::test]
+    async fn upload_does_not_leave_temporary_files() {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let archive = create_fake_archive(
+            source_dir.path(),
+            "0123",
+            &[
+                ("immutable/00001.chunk", "chunk 1"),
+                ("immutable/00002.chunk", "chunk 1"),
+            ],
+        );
+        let uploader = DeduplicatingSnapshotUploader::new(
+            "http://test.com:8080/".to_string(),
+            target_dir.path(),
+            CompressionAlgorithm::Gzip,
+        );
+
+        uploader
+            .upload_snapshot(&archive, SnapshotArchiveType::Snapshot)
+            .await
+            .unwrap();
+
+        for directory in [OBJECTS_DIRECTORY, MANIFESTS_DIRECTORY] {
+            let temporary_files = fs::read_dir(target_dir.path().join(directory))
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| {
+                    path.extension()
+                        .is_some_and(|extension| extension == TEMPORARY_FILE_EXTENSION)
+                })
+                .count();
+            assert_eq!(0, temporary_files, "temporary files left in '{directory}'");
+        }
+    }
+
+    #[tokio::test]
+    async fn files_shared_between_snapshots_are_stored_once() {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let uploader = DeduplicatingSnapshotUploader::new(
+            "http://test.com:8080/".to_string(),
+            target_dir.path(),
+            CompressionAlgorithm::Gzip,
+        );
+        let first_archive = create_fake_archive(
+            source_dir.path(),
+            "0123",
+            &[("immutable/00001.chunk", "chunk 1")],
+        );
+        let second_archive = create_fake_archive(
+            source_dir.path(),
+            "4567",
+            &[
+                ("immutable/00001.chunk", "chunk 1"),
+                ("immutable/00002.chunk", "chunk 2"),
+            ],
+        );
+
+        uploader
+            .upload_snapshot(&first_archive, SnapshotArchiveType::Snapshot)
+            .await
+            .unwrap();
+        uploader
+            .upload_snapshot(&second_archive, SnapshotArchiveType::Snapshot)
+            .await
+            .unwrap();
+
+        let stored_objects = fs::read_dir(target_dir.path().join(OBJECTS_DIRECTORY))
+            .unwrap()
+            .count();
+        assert_eq!(2, stored_objects);
+        assert_eq!(2, read_manifest(target_dir.path(), "4567").entries.len());
+    }
+
+    #[tokio::test]
+    async fn upload_ancillary_archive_as_a_single_object_without_manifest() {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let digest = "41e27b9ed5a32531b95b2b7ff3c0757591a06a337efaf19a524a998e348028e7";
+        let archive = create_fake_archive(
+            source_dir.path(),
+            digest,
+            &[
+                ("ledger/00001", "ledger state"),
+                ("volatile/blocks-0.dat", "block"),
+            ],
+        );
+        let uploader = DeduplicatingSnapshotUploader::new(
+            "http://test.com:8080/".to_string(),
+            target_dir.path(),
+            CompressionAlgorithm::Gzip,
+        );
+
+        let location = uploader
+            .upload_snapshot(&archive, SnapshotArchiveType::Ancillary)
+            .await
+            .unwrap();
+
+        let object_hash = location
+            .strip_prefix(&format!(
+                "http://test.com:8080/{}/snapshot_objects/{digest}/",
+                http_server::SERVER_BASE_PATH
+            ))
+            .expect("The location should be the one of an object");
+        assert_eq!(
+            fs::read(&archive).unwrap(),
+            fs::read(target_dir.path().join(OBJECTS_DIRECTORY).join(object_hash)).unwrap()
+        );
+        assert!(!target_dir.path().join(MANIFESTS_DIRECTORY).exists());
+    }
+}
@@ -3,7 +3,7 @@ use async_trait::async_trait;
 use mithril_common::StdResult;
 use std::{path::Path, sync::RwLock};
 
-use super::{SnapshotLocation, SnapshotUploader};
+use super::{SnapshotArchiveType, SnapshotLocation, SnapshotUploader};
 
 /// Dummy uploader for test purposes.
 ///
@@ -41,7 +41,11 @@ impl Default for DumbSnapshotUploader {
 #[async_trait]
 impl SnapshotUploader for DumbSnapshotUploader {
     /// Upload a snapshot
-    async fn upload_snapshot(&self, snapshot_filepath: &Path) -> StdResult<SnapshotLocation> {
+    async fn upload_snapshot(
+        &self,
+        snapshot_filepath: &Path,
+        _archive_type: SnapshotArchiveType,
+    ) -> StdResult<SnapshotLocation> {
         let mut value = self
             .last_uploaded
             .write()
@@ -66,7 +70,7 @@ mod tests {
             .expect("uploader should not fail")
             .is_none());
         let res = uploader
-            .upload_snapshot(Path::new("/tmp/whatever"))
+            .upload_snapshot(Path::new("/tmp/whatever"), SnapshotArchiveType::Snapshot)
             .await
             .expect("uploading with a dumb uploader should not fail");
         assert_eq!(res, "/tmp/whatever".to_string());
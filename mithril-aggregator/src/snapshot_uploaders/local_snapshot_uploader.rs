@@ -1,41 +1,52 @@
-use anyhow::Context;
 use async_trait::async_trait;
 use mithril_common::StdResult;
 use slog_scope::debug;
-use std::path::{Path, PathBuf};
+use std::path::Path;
+use std::sync::Arc;
 
+use crate::artifact_storage::ArtifactStorage;
 use crate::http_server;
-use crate::snapshot_uploaders::{SnapshotLocation, SnapshotUploader};
+use crate::snapshot_uploaders::{SnapshotArchiveType, SnapshotLocation, SnapshotUploader};
 use crate::tools;
 
-/// LocalSnapshotUploader is a snapshot uploader working using local files
+/// LocalSnapshotUploader is a snapshot uploader working using local files, the snapshots are
+/// downloaded through the aggregator HTTP server
 pub struct LocalSnapshotUploader {
     /// Snapshot server listening IP
     snapshot_server_url: String,
 
-    /// Target folder where to store snapshots archive
-    target_location: PathBuf,
+    /// Storage of the snapshots archives
+    artifact_storage: Arc<dyn ArtifactStorage>,
 }
 
 impl LocalSnapshotUploader {
     /// LocalSnapshotUploader factory
-    pub(crate) fn new(snapshot_server_url: String, target_location: &Path) -> Self {
+    pub(crate) fn new(
+        snapshot_server_url: String,
+        artifact_storage: Arc<dyn ArtifactStorage>,
+    ) -> Self {
         debug!("New LocalSnapshotUploader created"; "snapshot_server_url" => &snapshot_server_url);
         Self {
             snapshot_server_url,
-            target_location: target_location.to_path_buf(),
+            artifact_storage,
         }
     }
 }
 
 #[async_trait]
 impl SnapshotUploader for LocalSnapshotUploader {
-    async fn upload_snapshot(&self, snapshot_filepath: &Path) -> StdResult<SnapshotLocation> {
+    async fn upload_snapshot(
+        &self,
+        snapshot_filepath: &Path,
+        archive_type: SnapshotArchiveType,
+    ) -> StdResult<SnapshotLocation> {
         let archive_name = snapshot_filepath.file_name().unwrap().to_str().unwrap();
-        let target_path = &self.target_location.join(archive_name);
-        tokio::fs::copy(snapshot_filepath, target_path)
-            .await
-            .with_context(|| "Snapshot copy failure")?;
+        let stored_location = self.artifact_storage.store(snapshot_filepath).await?;
+
+        // The ancillary archives are not served by the snapshot download route
+        if archive_type == SnapshotArchiveType::Ancillary {
+            return Ok(stored_location);
+        }
 
         let digest = tools::extract_digest_from_path(Path::new(archive_name));
         let location = format!(
@@ -52,11 +63,13 @@ impl SnapshotUploader for LocalSnapshotUploader {
 #[cfg(test)]
 mod tests {
     use super::LocalSnapshotUploader;
+    use crate::artifact_storage::LocalArtifactStorage;
     use crate::http_server;
-    use crate::snapshot_uploaders::SnapshotUploader;
+    use crate::snapshot_uploaders::{SnapshotArchiveType, SnapshotUploader};
     use std::fs::File;
     use std::io::Write;
     use std::path::{Path, PathBuf};
+    use std::sync::Arc;
     use tempfile::tempdir;
 
     fn create_fake_archive(dir: &Path, digest: &str) -> PathBuf {
@@ -84,25 +97,64 @@ mod tests {
             http_server::SERVER_BASE_PATH,
             &digest
         );
-        let uploader = LocalSnapshotUploader::new(url, target_dir.path());
+        let uploader = LocalSnapshotUploader::new(
+            url.clone(),
+            Arc::new(LocalArtifactStorage::new(target_dir.path(), url)),
+        );
 
         let location = uploader
-            .upload_snapshot(&archive)
+            .upload_snapshot(&archive, SnapshotArchiveType::Snapshot)
             .await
             .expect("local upload should not fail");
 
         assert_eq!(expected_location, location);
     }
 
+    #[tokio::test]
+    async fn should_return_the_stored_location_for_an_ancillary_archive() {
+        let source_dir = tempdir().unwrap();
+        let target_dir = tempdir().unwrap();
+        let digest = "41e27b9ed5a32531b95b2b7ff3c0757591a06a337efaf19a524a998e348028e7";
+        let archive = source_dir
+            .path()
+            .join(format!("test.{digest}.ancillary.tar.gz"));
+        File::create(&archive).unwrap();
+        let uploader = LocalSnapshotUploader::new(
+            "http://test.com:8080/".to_string(),
+            Arc::new(LocalArtifactStorage::new(
+                target_dir.path(),
+                "http://test.com:8080/aggregator/snapshot_download".to_string(),
+            )),
+        );
+
+        let location = uploader
+            .upload_snapshot(&archive, SnapshotArchiveType::Ancillary)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            format!(
+                "http://test.com:8080/aggregator/snapshot_download/test.{digest}.ancillary.tar.gz"
+            ),
+            location
+        );
+    }
+
     #[tokio::test]
     async fn should_copy_file_to_target_location() {
         let source_dir = tempdir().unwrap();
         let target_dir = tempdir().unwrap();
         let digest = "41e27b9ed5a32531b95b2b7ff3c0757591a06a337efaf19a524a998e348028e7";
         let archive = create_fake_archive(source_dir.path(), digest);
-        let uploader =
-            LocalSnapshotUploader::new("http://test.com:8080/".to_string(), target_dir.path());
-        uploader.upload_snapshot(&archive).await.unwrap();
+        let url = "http://test.com:8080/".to_string();
+        let uploader = LocalSnapshotUploader::new(
+            url.clone(),
+            Arc::new(LocalArtifactStorage::new(target_dir.path(), url)),
+        );
+        uploader
+            .upload_snapshot(&archive, SnapshotArchiveType::Snapshot)
+            .await
+            .unwrap();
 
         assert!(target_dir
             .path()
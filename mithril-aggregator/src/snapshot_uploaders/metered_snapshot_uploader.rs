@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use mithril_common::StdResult;
+use slog_scope::info;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::snapshot_uploaders::{SnapshotArchiveType, SnapshotLocation, SnapshotUploader};
+use crate::MetricsService;
+
+/// MeteredSnapshotUploader records the size and the duration of the uploads of another
+/// [SnapshotUploader] in the [MetricsService], the upload throughput can be computed from them.
+pub struct MeteredSnapshotUploader {
+    uploader: Arc<dyn SnapshotUploader>,
+    metrics_service: Arc<MetricsService>,
+}
+
+impl MeteredSnapshotUploader {
+    /// MeteredSnapshotUploader factory
+    pub fn new(uploader: Arc<dyn SnapshotUploader>, metrics_service: Arc<MetricsService>) -> Self {
+        Self {
+            uploader,
+            metrics_service,
+        }
+    }
+}
+
+#[async_trait]
+impl SnapshotUploader for MeteredSnapshotUploader {
+    async fn upload_snapshot(
+        &self,
+        snapshot_filepath: &Path,
+        archive_type: SnapshotArchiveType,
+    ) -> StdResult<SnapshotLocation> {
+        let uploaded_bytes = tokio::fs::metadata(snapshot_filepath)
+            .await
+            .map(|metadata| metadata.len())
+            .unwrap_or_default();
+        let started_at = Instant::now();
+        let location = self
+            .uploader
+            .upload_snapshot(snapshot_filepath, archive_type)
+            .await?;
+        let elapsed = started_at.elapsed();
+
+        self.metrics_service
+            .snapshot_uploaded_bytes_since_startup_counter_increase(uploaded_bytes);
+        self.metrics_service
+            .snapshot_upload_milliseconds_since_startup_counter_increase(elapsed.as_millis() as u64);
+        info!(
+            "Snapshot uploaded: {uploaded_bytes} bytes in {elapsed:?} ({:.0} bytes/s)",
+            uploaded_bytes as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+            "location" => &location
+        );
+
+        Ok(location)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use std::fs;
+
+    use mithril_common::test_utils::TempDir;
+
+    use crate::snapshot_uploaders::MockSnapshotUploader;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn upload_snapshot_records_the_uploaded_bytes() {
+        let snapshot_filepath = TempDir::create(
+            "metered_snapshot_uploader",
+            "upload_snapshot_records_the_uploaded_bytes",
+        )
+        .join("snapshot.tar.gz");
+        fs::write(&snapshot_filepath, vec![0; 1024]).unwrap();
+        let mut uploader = MockSnapshotUploader::new();
+        uploader
+            .expect_upload_snapshot()
+            .returning(|_, _| Ok("https://cdn.mithril.network/snapshot.tar.gz".to_string()));
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let metered_uploader =
+            MeteredSnapshotUploader::new(Arc::new(uploader), metrics_service.clone());
+
+        let location = metered_uploader
+            .upload_snapshot(&snapshot_filepath, SnapshotArchiveType::Snapshot)
+            .await
+            .unwrap();
+
+        assert_eq!("https://cdn.mithril.network/snapshot.tar.gz", location);
+        assert_eq!(
+            1024,
+            metrics_service.snapshot_uploaded_bytes_since_startup_counter_get()
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_upload_is_not_recorded() {
+        let mut uploader = MockSnapshotUploader::new();
+        uploader
+            .expect_upload_snapshot()
+            .returning(|_, _| Err(anyhow!("upload failure")));
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let metered_uploader =
+            MeteredSnapshotUploader::new(Arc::new(uploader), metrics_service.clone());
+
+        metered_uploader
+            .upload_snapshot(Path::new("snapshot.tar.gz"), SnapshotArchiveType::Snapshot)
+            .await
+            .expect_err("the upload should fail");
+
+        assert_eq!(
+            0,
+            metrics_service.snapshot_uploaded_bytes_since_startup_counter_get()
+        );
+    }
+}
@@ -1,13 +1,24 @@
+mod deduplicating_snapshot_uploader;
 mod dumb_snapshot_uploader;
 mod local_snapshot_uploader;
+mod metered_snapshot_uploader;
 mod remote_snapshot_uploader;
+mod scheduled_snapshot_uploader;
 mod snapshot_uploader;
+mod upload_schedule;
 
+pub use deduplicating_snapshot_uploader::{
+    DeduplicatingSnapshotUploader, MANIFESTS_DIRECTORY, OBJECTS_DIRECTORY,
+};
 pub use dumb_snapshot_uploader::*;
 pub use local_snapshot_uploader::LocalSnapshotUploader;
+pub use metered_snapshot_uploader::MeteredSnapshotUploader;
 pub use remote_snapshot_uploader::RemoteSnapshotUploader;
+pub use scheduled_snapshot_uploader::ScheduledSnapshotUploader;
+pub use snapshot_uploader::SnapshotArchiveType;
 pub use snapshot_uploader::SnapshotLocation;
 pub use snapshot_uploader::SnapshotUploader;
+pub use upload_schedule::{SharedUploadSchedule, UploadSchedule, UploadWindow};
 
 #[cfg(test)]
 pub use snapshot_uploader::MockSnapshotUploader;
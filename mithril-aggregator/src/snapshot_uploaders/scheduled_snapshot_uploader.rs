@@ -0,0 +1,149 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use mithril_common::StdResult;
+use slog_scope::info;
+use std::path::Path;
+use std::sync::{Arc, PoisonError};
+use std::time::Duration;
+
+use crate::snapshot_uploaders::{
+    SharedUploadSchedule, SnapshotArchiveType, SnapshotLocation, SnapshotUploader,
+};
+
+/// Interval at which a deferred upload checks if the upload schedule is open
+const UPLOAD_SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// ScheduledSnapshotUploader defers the uploads of another [SnapshotUploader] until the
+/// [upload schedule][crate::snapshot_uploaders::UploadSchedule] is open.
+///
+/// The schedule is shared, an upload waiting for a window picks up its reloaded windows.
+pub struct ScheduledSnapshotUploader {
+    uploader: Arc<dyn SnapshotUploader>,
+    upload_schedule: SharedUploadSchedule,
+    check_interval: Duration,
+}
+
+impl ScheduledSnapshotUploader {
+    /// ScheduledSnapshotUploader factory
+    pub fn new(uploader: Arc<dyn SnapshotUploader>, upload_schedule: SharedUploadSchedule) -> Self {
+        Self {
+            uploader,
+            upload_schedule,
+            check_interval: UPLOAD_SCHEDULE_CHECK_INTERVAL,
+        }
+    }
+
+    #[cfg(test)]
+    fn with_check_interval(mut self, check_interval: Duration) -> Self {
+        self.check_interval = check_interval;
+        self
+    }
+
+    fn is_upload_schedule_open(&self) -> bool {
+        self.upload_schedule
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_open_at(Utc::now())
+    }
+
+    async fn wait_upload_schedule_open(&self, snapshot_filepath: &Path) {
+        if self.is_upload_schedule_open() {
+            return;
+        }
+
+        info!(
+            "Outside of the snapshot upload windows, the upload is deferred to the next window";
+            "snapshot_filepath" => snapshot_filepath.display()
+        );
+        while !self.is_upload_schedule_open() {
+            tokio::time::sleep(self.check_interval).await;
+        }
+    }
+}
+
+#[async_trait]
+impl SnapshotUploader for ScheduledSnapshotUploader {
+    async fn upload_snapshot(
+        &self,
+        snapshot_filepath: &Path,
+        archive_type: SnapshotArchiveType,
+    ) -> StdResult<SnapshotLocation> {
+        self.wait_upload_schedule_open(snapshot_filepath).await;
+
+        self.uploader
+            .upload_snapshot(snapshot_filepath, archive_type)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::RwLock;
+
+    use crate::snapshot_uploaders::{MockSnapshotUploader, UploadSchedule};
+
+    use super::*;
+
+    fn uploader_returning_location() -> MockSnapshotUploader {
+        let mut uploader = MockSnapshotUploader::new();
+        uploader
+            .expect_upload_snapshot()
+            .returning(|_, _| Ok("https://cdn.mithril.network/snapshot.tar.gz".to_string()));
+        uploader
+    }
+
+    /// Schedule whose only window ended a few hours ago, closed now and for the next hours
+    fn closed_upload_schedule() -> UploadSchedule {
+        let now = Utc::now().time();
+        let start = now - chrono::Duration::try_hours(4).unwrap();
+        let end = now - chrono::Duration::try_hours(2).unwrap();
+
+        UploadSchedule::from_str(&format!(
+            "{}-{}",
+            start.format("%H:%M"),
+            end.format("%H:%M")
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn upload_snapshot_immediately_when_the_schedule_is_open() {
+        let scheduled_uploader = ScheduledSnapshotUploader::new(
+            Arc::new(uploader_returning_location()),
+            Arc::new(RwLock::new(UploadSchedule::default())),
+        );
+
+        let location = scheduled_uploader
+            .upload_snapshot(Path::new("snapshot.tar.gz"), SnapshotArchiveType::Snapshot)
+            .await
+            .unwrap();
+
+        assert_eq!("https://cdn.mithril.network/snapshot.tar.gz", location);
+    }
+
+    #[tokio::test]
+    async fn upload_snapshot_is_deferred_until_the_schedule_is_open() {
+        let upload_schedule = Arc::new(RwLock::new(closed_upload_schedule()));
+        let scheduled_uploader = ScheduledSnapshotUploader::new(
+            Arc::new(uploader_returning_location()),
+            upload_schedule.clone(),
+        )
+        .with_check_interval(Duration::from_millis(10));
+
+        let upload = scheduled_uploader
+            .upload_snapshot(Path::new("snapshot.tar.gz"), SnapshotArchiveType::Snapshot);
+        tokio::pin!(upload);
+        tokio::time::timeout(Duration::from_millis(50), &mut upload)
+            .await
+            .expect_err("the upload should be deferred while the schedule is closed");
+
+        *upload_schedule.write().unwrap() = UploadSchedule::default();
+        let location = tokio::time::timeout(Duration::from_secs(1), upload)
+            .await
+            .expect("the upload should resume once the schedule is open")
+            .unwrap();
+
+        assert_eq!("https://cdn.mithril.network/snapshot.tar.gz", location);
+    }
+}
@@ -7,10 +7,25 @@ use mockall::automock;
 
 pub type SnapshotLocation = String;
 
+/// Type of the archives uploaded by a [SnapshotUploader]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotArchiveType {
+    /// Archive of the files of a snapshot certified by its digest
+    Snapshot,
+
+    /// Archive of the ancillary files of a snapshot (ledger state snapshots and volatile
+    /// database), not certified by its digest
+    Ancillary,
+}
+
 /// SnapshotUploader represents a snapshot uploader interactor
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait SnapshotUploader: Sync + Send {
-    /// Upload a snapshot
-    async fn upload_snapshot(&self, snapshot_filepath: &Path) -> StdResult<SnapshotLocation>;
+    /// Upload a snapshot archive of the given type
+    async fn upload_snapshot(
+        &self,
+        snapshot_filepath: &Path,
+        archive_type: SnapshotArchiveType,
+    ) -> StdResult<SnapshotLocation>;
 }
@@ -0,0 +1,137 @@
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, NaiveTime, Utc};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use mithril_common::{StdError, StdResult};
+
+/// Daily time window, in UTC, during which the snapshots can be uploaded.
+///
+/// A window whose end is before its start spans midnight (i.e. `22:00-06:00`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadWindow {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl UploadWindow {
+    /// Check if the given time is within the window (its start included, its end excluded)
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            self.start <= time || time < self.end
+        }
+    }
+}
+
+impl FromStr for UploadWindow {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| anyhow!("Invalid upload window '{s}', expected 'HH:MM-HH:MM'"))?;
+        let parse_time = |time: &str| {
+            NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                .with_context(|| format!("Invalid time '{time}' in upload window '{s}'"))
+        };
+        let window = Self {
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+        };
+        if window.start == window.end {
+            return Err(anyhow!(
+                "Invalid upload window '{s}', its start and end must differ"
+            ));
+        }
+
+        Ok(window)
+    }
+}
+
+/// Schedule of the snapshot uploads: the snapshots are uploaded only during its windows, or at
+/// any time if it has no window.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UploadSchedule {
+    windows: Vec<UploadWindow>,
+}
+
+/// [UploadSchedule] shared between the snapshot uploader and the services that can update it
+pub type SharedUploadSchedule = Arc<RwLock<UploadSchedule>>;
+
+impl UploadSchedule {
+    /// Check if the snapshots can be uploaded at the given date
+    pub fn is_open_at(&self, date: DateTime<Utc>) -> bool {
+        self.windows.is_empty()
+            || self
+                .windows
+                .iter()
+                .any(|window| window.contains(date.time()))
+    }
+}
+
+impl FromStr for UploadSchedule {
+    type Err = StdError;
+
+    /// Parse a comma separated list of `HH:MM-HH:MM` windows
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let windows = s
+            .split(',')
+            .map(str::trim)
+            .filter(|window| !window.is_empty())
+            .map(UploadWindow::from_str)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(Self { windows })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date_at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(&format!("2024-05-02T{time}:00Z"))
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn schedule_without_window_is_always_open() {
+        let schedule = UploadSchedule::from_str("").unwrap();
+
+        assert!(schedule.is_open_at(date_at("00:00")));
+        assert!(schedule.is_open_at(date_at("13:37")));
+    }
+
+    #[test]
+    fn schedule_is_open_only_during_its_windows() {
+        let schedule = UploadSchedule::from_str("02:00-04:00, 12:00-13:30").unwrap();
+
+        assert!(!schedule.is_open_at(date_at("01:59")));
+        assert!(schedule.is_open_at(date_at("02:00")));
+        assert!(schedule.is_open_at(date_at("03:59")));
+        assert!(!schedule.is_open_at(date_at("04:00")));
+        assert!(schedule.is_open_at(date_at("13:00")));
+        assert!(!schedule.is_open_at(date_at("13:30")));
+    }
+
+    #[test]
+    fn window_ending_before_its_start_spans_midnight() {
+        let schedule = UploadSchedule::from_str("22:00-06:00").unwrap();
+
+        assert!(schedule.is_open_at(date_at("23:00")));
+        assert!(schedule.is_open_at(date_at("05:59")));
+        assert!(!schedule.is_open_at(date_at("06:00")));
+        assert!(!schedule.is_open_at(date_at("21:59")));
+    }
+
+    #[test]
+    fn invalid_windows_are_rejected() {
+        for windows in ["22:00", "22:00-25:00", "noon-13:00", "12:00-12:00"] {
+            UploadSchedule::from_str(windows)
+                .expect_err(&format!("Upload windows '{windows}' should be rejected"));
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use anyhow::{anyhow, Context};
 use flate2::Compression;
 use flate2::{read::GzDecoder, write::GzEncoder};
+use mithril_common::digesters::ImmutableFileLayout;
 use mithril_common::StdResult;
+use sha2::{Digest, Sha256};
 use slog_scope::{info, warn};
+use std::ffi::OsStr;
 use std::fs::{self, File};
-use std::io::{self, Read, Seek, SeekFrom};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::sync::RwLock;
 use tar::{Archive, Entry, EntryType};
@@ -18,6 +21,41 @@ use crate::ZstandardCompressionParameters;
 pub trait Snapshotter: Sync + Send {
     /// Create a new snapshot with the given archive name.
     fn snapshot(&self, archive_name: &str) -> StdResult<OngoingSnapshot>;
+
+    /// Create a new snapshot of the ancillary files (ledger state snapshots and volatile
+    /// database) with the given archive name.
+    ///
+    /// Returns `None` if the ancillary files are not packaged in a separate archive.
+    fn snapshot_ancillary(&self, archive_name: &str) -> StdResult<Option<OngoingSnapshot>>;
+}
+
+/// Directories of the Cardano database that are not certified by the snapshot digest and that
+/// can be packaged in a separate ancillary archive.
+const ANCILLARY_DIRECTORIES: [&str; 2] = ["ledger", "volatile"];
+
+/// Files of the Cardano database added to an archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveContent {
+    /// The whole database
+    All,
+    /// The whole database except the ancillary files
+    WithoutAncillaryFiles,
+    /// The ancillary files only
+    AncillaryFiles,
+}
+
+impl ArchiveContent {
+    fn includes(&self, file_name: &OsStr) -> bool {
+        let is_ancillary = ANCILLARY_DIRECTORIES
+            .iter()
+            .any(|directory| file_name == OsStr::new(directory));
+
+        match self {
+            Self::All => true,
+            Self::WithoutAncillaryFiles => !is_ancillary,
+            Self::AncillaryFiles => is_ancillary,
+        }
+    }
 }
 
 /// Compression algorithm and parameters of the [CompressedArchiveSnapshotter].
@@ -45,17 +83,31 @@ pub struct CompressedArchiveSnapshotter {
 
     /// Compression algorithm used for the archive
     compression_algorithm: SnapshotterCompressionAlgorithm,
+
+    /// Package the ancillary files in a separate archive
+    separate_ancillary_archive: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OngoingSnapshot {
     filepath: PathBuf,
     filesize: u64,
+    filehash: Option<String>,
 }
 
 impl OngoingSnapshot {
     pub fn new(filepath: PathBuf, filesize: u64) -> Self {
-        Self { filepath, filesize }
+        Self {
+            filepath,
+            filesize,
+            filehash: None,
+        }
+    }
+
+    /// Set the hex encoded SHA-256 of the snapshot file
+    pub fn with_file_hash(mut self, filehash: String) -> Self {
+        self.filehash = Some(filehash);
+        self
     }
 
     pub fn get_file_path(&self) -> &PathBuf {
@@ -65,6 +117,11 @@ impl OngoingSnapshot {
     pub fn get_file_size(&self) -> &u64 {
         &self.filesize
     }
+
+    /// Hex encoded SHA-256 of the snapshot file, if computed by the snapshotter
+    pub fn get_file_hash(&self) -> Option<&String> {
+        self.filehash.as_ref()
+    }
 }
 
 /// Snapshotter error type.
@@ -93,25 +150,32 @@ pub enum SnapshotError {
 
 impl Snapshotter for CompressedArchiveSnapshotter {
     fn snapshot(&self, archive_name: &str) -> StdResult<OngoingSnapshot> {
-        let archive_path = self.ongoing_snapshot_directory.join(archive_name);
-        let filesize = self.create_and_verify_archive(&archive_path).map_err(|err| {
-            if archive_path.exists() {
-                if let Err(remove_error) = std::fs::remove_file(&archive_path) {
-                    warn!(
-                        " > Post snapshotter.snapshot failure, could not remove temporary archive at path: path:{}, err: {}",
-                        archive_path.display(),
-                        remove_error
-                    );
-                }
-            }
+        let content = if self.separate_ancillary_archive {
+            ArchiveContent::WithoutAncillaryFiles
+        } else {
+            ArchiveContent::All
+        };
 
-            err
-        }).with_context(|| format!("CompressedArchiveSnapshotter can not create and verify archive: '{}'", archive_path.display()))?;
+        self.snapshot_content(archive_name, content)
+    }
 
-        Ok(OngoingSnapshot {
-            filepath: archive_path,
-            filesize,
-        })
+    fn snapshot_ancillary(&self, archive_name: &str) -> StdResult<Option<OngoingSnapshot>> {
+        if !self.separate_ancillary_archive {
+            return Ok(None);
+        }
+        if !ANCILLARY_DIRECTORIES
+            .iter()
+            .any(|directory| self.db_directory.join(directory).exists())
+        {
+            warn!(
+                "No ancillary files found in the database directory, skipping the ancillary archive";
+                "db_directory" => %self.db_directory.display()
+            );
+            return Ok(None);
+        }
+
+        self.snapshot_content(archive_name, ArchiveContent::AncillaryFiles)
+            .map(Some)
     }
 }
 
@@ -145,9 +209,56 @@ impl CompressedArchiveSnapshotter {
             db_directory,
             ongoing_snapshot_directory,
             compression_algorithm,
+            separate_ancillary_archive: false,
         })
     }
 
+    /// Package the ancillary files (ledger state snapshots and volatile database) in a separate
+    /// archive instead of the snapshot archive.
+    pub fn with_separate_ancillary_archive(mut self, separate_ancillary_archive: bool) -> Self {
+        self.separate_ancillary_archive = separate_ancillary_archive;
+        self
+    }
+
+    fn snapshot_content(
+        &self,
+        archive_name: &str,
+        content: ArchiveContent,
+    ) -> StdResult<OngoingSnapshot> {
+        let archive_path = self.ongoing_snapshot_directory.join(archive_name);
+        let filesize = self.create_and_verify_archive(&archive_path, content).map_err(|err| {
+            if archive_path.exists() {
+                if let Err(remove_error) = std::fs::remove_file(&archive_path) {
+                    warn!(
+                        " > Post snapshotter.snapshot failure, could not remove temporary archive at path: path:{}, err: {}",
+                        archive_path.display(),
+                        remove_error
+                    );
+                }
+            }
+
+            err
+        }).with_context(|| format!("CompressedArchiveSnapshotter can not create and verify archive: '{}'", archive_path.display()))?;
+
+        let filehash = Self::compute_file_hash(&archive_path).with_context(|| {
+            format!(
+                "CompressedArchiveSnapshotter can not compute the hash of archive: '{}'",
+                archive_path.display()
+            )
+        })?;
+
+        Ok(OngoingSnapshot::new(archive_path, filesize).with_file_hash(filehash))
+    }
+
+    /// Compute the hex encoded SHA-256 of a file
+    fn compute_file_hash(filepath: &Path) -> StdResult<String> {
+        let mut file = File::open(filepath)?;
+        let mut hasher = Sha256::new();
+        io::copy(&mut file, &mut hasher)?;
+
+        Ok(hex::encode(hasher.finalize()))
+    }
+
     fn get_file_size(filepath: &Path) -> StdResult<u64> {
         let res = std::fs::metadata(filepath)
             .map_err(|e| SnapshotError::GeneralError(e.to_string()))?
@@ -155,11 +266,14 @@ impl CompressedArchiveSnapshotter {
         Ok(res)
     }
 
-    fn create_archive(&self, archive_path: &Path) -> StdResult<u64> {
+    fn create_archive(&self, archive_path: &Path, content: ArchiveContent) -> StdResult<u64> {
+        let immutable_file_layout =
+            ImmutableFileLayout::detect(&self.db_directory.join("immutable"));
         info!(
             "compressing {} into {}",
             self.db_directory.display(),
-            archive_path.display()
+            archive_path.display();
+            "immutable_file_layout" => %immutable_file_layout
         );
 
         let tar_file = File::create(archive_path).map_err(SnapshotError::CreateArchiveError)?;
@@ -169,8 +283,7 @@ impl CompressedArchiveSnapshotter {
                 let enc = GzEncoder::new(tar_file, Compression::default());
                 let mut tar = tar::Builder::new(enc);
 
-                tar.append_dir_all(".", &self.db_directory)
-                    .map_err(SnapshotError::CreateArchiveError)
+                self.append_db_directory(&mut tar, content)
                     .with_context(|| {
                         format!(
                             "GzEncoder Builder can not add directory: '{}' to the archive",
@@ -192,8 +305,7 @@ impl CompressedArchiveSnapshotter {
                     .map_err(SnapshotError::CreateArchiveError)?;
                 let mut tar = tar::Builder::new(enc);
 
-                tar.append_dir_all(".", &self.db_directory)
-                    .map_err(SnapshotError::CreateArchiveError)
+                self.append_db_directory(&mut tar, content)
                     .with_context(|| {
                         format!(
                             "ZstandardEncoder Builder can not add directory: '{}' to the archive",
@@ -223,13 +335,46 @@ impl CompressedArchiveSnapshotter {
         Ok(filesize)
     }
 
-    fn create_and_verify_archive(&self, archive_path: &Path) -> StdResult<u64> {
-        let filesize = self.create_archive(archive_path).with_context(|| {
-            format!(
-                "CompressedArchiveSnapshotter can not create archive with path: '{}''",
-                archive_path.display()
-            )
-        })?;
+    fn append_db_directory<W: Write>(
+        &self,
+        tar: &mut tar::Builder<W>,
+        content: ArchiveContent,
+    ) -> Result<(), SnapshotError> {
+        if content == ArchiveContent::All {
+            tar.append_dir_all(".", &self.db_directory)?;
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(&self.db_directory)? {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            if !content.includes(&file_name) {
+                continue;
+            }
+
+            if entry.file_type()?.is_dir() {
+                tar.append_dir_all(&file_name, entry.path())?;
+            } else {
+                tar.append_path_with_name(entry.path(), &file_name)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn create_and_verify_archive(
+        &self,
+        archive_path: &Path,
+        content: ArchiveContent,
+    ) -> StdResult<u64> {
+        let filesize = self
+            .create_archive(archive_path, content)
+            .with_context(|| {
+                format!(
+                    "CompressedArchiveSnapshotter can not create archive with path: '{}''",
+                    archive_path.display()
+                )
+            })?;
         self.verify_archive(archive_path).with_context(|| {
             format!(
                 "CompressedArchiveSnapshotter can not verify archive with path: '{}''",
@@ -341,6 +486,7 @@ impl CompressedArchiveSnapshotter {
 /// Snapshotter that does nothing. It is mainly used for test purposes.
 pub struct DumbSnapshotter {
     last_snapshot: RwLock<Option<OngoingSnapshot>>,
+    separate_ancillary_archive: bool,
 }
 
 impl DumbSnapshotter {
@@ -348,9 +494,16 @@ impl DumbSnapshotter {
     pub fn new() -> Self {
         Self {
             last_snapshot: RwLock::new(None),
+            separate_ancillary_archive: false,
         }
     }
 
+    /// Produce a fake ancillary snapshot too.
+    pub fn with_separate_ancillary_archive(mut self) -> Self {
+        self.separate_ancillary_archive = true;
+        self
+    }
+
     /// Return the last fake snapshot produced.
     pub fn get_last_snapshot(&self) -> StdResult<Option<OngoingSnapshot>> {
         let value = self
@@ -376,14 +529,17 @@ impl Snapshotter for DumbSnapshotter {
             .last_snapshot
             .write()
             .map_err(|e| SnapshotError::UploadFileError(e.to_string()))?;
-        let snapshot = OngoingSnapshot {
-            filepath: Path::new(archive_name).to_path_buf(),
-            filesize: 0,
-        };
+        let snapshot = OngoingSnapshot::new(Path::new(archive_name).to_path_buf(), 0);
         *value = Some(snapshot.clone());
 
         Ok(snapshot)
     }
+
+    fn snapshot_ancillary(&self, archive_name: &str) -> StdResult<Option<OngoingSnapshot>> {
+        Ok(self
+            .separate_ancillary_archive
+            .then(|| OngoingSnapshot::new(Path::new(archive_name).to_path_buf(), 0)))
+    }
 }
 
 #[cfg(test)]
@@ -519,6 +675,7 @@ mod tests {
         snapshotter
             .create_archive(
                 &pending_snapshot_directory.join(Path::new(pending_snapshot_archive_file)),
+                ArchiveContent::All,
             )
             .expect("create_archive should not fail");
         snapshotter
@@ -532,6 +689,61 @@ mod tests {
             .expect("Snapshotter::snapshot should not fail.");
     }
 
+    #[test]
+    fn should_compute_the_hash_of_the_created_archive() {
+        let test_dir = get_test_directory("should_compute_the_hash_of_the_created_archive");
+        let pending_snapshot_directory = test_dir.join("pending_snapshot");
+        let db_directory = test_dir.join("db");
+
+        DummyImmutablesDbBuilder::new(db_directory.as_os_str().to_str().unwrap())
+            .with_immutables(&[1, 2, 3])
+            .append_immutable_trio()
+            .build();
+
+        let snapshotter = CompressedArchiveSnapshotter::new(
+            db_directory,
+            pending_snapshot_directory,
+            SnapshotterCompressionAlgorithm::Gzip,
+        )
+        .unwrap();
+
+        let ongoing_snapshot = snapshotter
+            .snapshot("archive.tar.gz")
+            .expect("Snapshotter::snapshot should not fail.");
+        let archive = fs::read(ongoing_snapshot.get_file_path()).unwrap();
+
+        assert_eq!(
+            Some(&hex::encode(Sha256::digest(archive))),
+            ongoing_snapshot.get_file_hash()
+        );
+    }
+
+    #[test]
+    fn should_create_a_valid_archive_of_immutable_files_with_v2_layout() {
+        let test_dir =
+            get_test_directory("should_create_a_valid_archive_of_immutable_files_with_v2_layout");
+        let pending_snapshot_directory = test_dir.join("pending_snapshot");
+        let pending_snapshot_archive_file = "archive.tar.gz";
+        let db_directory = test_dir.join("db");
+
+        DummyImmutablesDbBuilder::new(db_directory.as_os_str().to_str().unwrap())
+            .with_layout(ImmutableFileLayout::V2)
+            .with_immutables(&[1, 2, 3])
+            .append_immutable_trio()
+            .build();
+
+        let snapshotter = CompressedArchiveSnapshotter::new(
+            db_directory,
+            pending_snapshot_directory,
+            SnapshotterCompressionAlgorithm::Gzip,
+        )
+        .unwrap();
+
+        snapshotter
+            .snapshot(pending_snapshot_archive_file)
+            .expect("Snapshotter::snapshot should not fail.");
+    }
+
     #[test]
     fn should_create_a_valid_archive_with_zstandard_snapshotter() {
         let test_dir =
@@ -557,6 +769,7 @@ mod tests {
         snapshotter
             .create_archive(
                 &pending_snapshot_directory.join(Path::new(pending_snapshot_archive_file)),
+                ArchiveContent::All,
             )
             .expect("create_archive should not fail");
         snapshotter
@@ -569,4 +782,77 @@ mod tests {
             .snapshot(pending_snapshot_archive_file)
             .expect("Snapshotter::snapshot should not fail.");
     }
+
+    fn list_gzip_archive_root_entries(archive_path: &Path) -> Vec<String> {
+        let mut archive = Archive::new(GzDecoder::new(File::open(archive_path).unwrap()));
+        let mut root_entries: Vec<String> = archive
+            .entries()
+            .unwrap()
+            .filter_map(|entry| {
+                let path = entry.unwrap().path().unwrap().into_owned();
+                path.components()
+                    .find(|component| component.as_os_str() != ".")
+                    .map(|component| component.as_os_str().to_string_lossy().to_string())
+            })
+            .collect();
+        root_entries.sort();
+        root_entries.dedup();
+
+        root_entries
+    }
+
+    #[test]
+    fn should_package_ancillary_files_in_a_separate_archive_if_enabled() {
+        let test_dir =
+            get_test_directory("should_package_ancillary_files_in_a_separate_archive_if_enabled");
+        let pending_snapshot_directory = test_dir.join("pending_snapshot");
+        let db_directory = test_dir.join("db");
+
+        DummyImmutablesDbBuilder::new(db_directory.as_os_str().to_str().unwrap())
+            .with_immutables(&[1, 2, 3])
+            .append_immutable_trio()
+            .build();
+        for directory in ANCILLARY_DIRECTORIES {
+            fs::create_dir_all(db_directory.join(directory)).unwrap();
+            File::create(db_directory.join(directory).join("file")).unwrap();
+        }
+
+        let snapshotter = CompressedArchiveSnapshotter::new(
+            db_directory.clone(),
+            pending_snapshot_directory.clone(),
+            SnapshotterCompressionAlgorithm::Gzip,
+        )
+        .unwrap();
+        assert_eq!(
+            None,
+            snapshotter.snapshot_ancillary("ancillary.tar.gz").unwrap()
+        );
+        let snapshot = snapshotter.snapshot("full.tar.gz").unwrap();
+        assert_eq!(
+            vec!["immutable", "ledger", "volatile"],
+            list_gzip_archive_root_entries(snapshot.get_file_path())
+        );
+
+        let snapshotter = CompressedArchiveSnapshotter::new(
+            db_directory,
+            pending_snapshot_directory,
+            SnapshotterCompressionAlgorithm::Gzip,
+        )
+        .unwrap()
+        .with_separate_ancillary_archive(true);
+        let snapshot = snapshotter.snapshot("archive.tar.gz").unwrap();
+        let ancillary_snapshot = snapshotter
+            .snapshot_ancillary("ancillary.tar.gz")
+            .unwrap()
+            .expect("An ancillary archive should have been created");
+
+        assert_eq!(
+            vec!["immutable"],
+            list_gzip_archive_root_entries(snapshot.get_file_path())
+        );
+        assert_eq!(
+            vec!["ledger", "volatile"],
+            list_gzip_archive_root_entries(ancillary_snapshot.get_file_path())
+        );
+    }
 }
@@ -4,6 +4,7 @@ mod verification_key_store;
 
 pub use pending_certificate_store::CertificatePendingStore;
 pub use protocol_parameters_store::ProtocolParametersStorer;
+pub(crate) use verification_key_store::with_era_verification_keys;
 pub use verification_key_store::{VerificationKeyStore, VerificationKeyStorer};
 
 #[cfg(test)]
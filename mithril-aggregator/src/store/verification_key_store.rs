@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use tokio::sync::RwLock;
 
 use mithril_common::entities::{Epoch, PartyId, Signer, SignerWithStake};
+use mithril_common::era::SupportedEra;
 use mithril_persistence::store::adapter::StoreAdapter;
 
 #[cfg(test)]
@@ -36,13 +37,58 @@ pub trait VerificationKeyStorer: Sync + Send {
     /// Returns the list of signers for the given `epoch`.
     async fn get_signers(&self, epoch: Epoch) -> StdResult<Option<Vec<SignerWithStake>>>;
 
+    /// Save the verification key created by the given [Signer] with the signature scheme of the
+    /// given era, registered for the given [Epoch] ahead of the switch to this era.
+    async fn save_next_era_verification_key(
+        &self,
+        epoch: Epoch,
+        era: SupportedEra,
+        signer: Signer,
+    ) -> StdResult<()>;
+
+    /// Returns the list of signers for the given `epoch`, with the verification keys they
+    /// registered for the given `era` ahead of its switch in place of their main ones.
+    async fn get_signers_of_era(
+        &self,
+        epoch: Epoch,
+        era: SupportedEra,
+    ) -> StdResult<Option<Vec<SignerWithStake>>>;
+
+    /// Remove the verification key of the given signer for the given [Epoch], returns the
+    /// removed value if one existed.
+    async fn remove_verification_key(
+        &self,
+        epoch: Epoch,
+        party_id: &PartyId,
+    ) -> StdResult<Option<SignerWithStake>>;
+
     /// Prune all verification keys that are at or below the given epoch.
     async fn prune_verification_keys(&self, max_epoch_to_prune: Epoch) -> StdResult<()>;
 }
 
+/// Substitute the verification keys of the given signers with the ones they registered for
+/// another era, if any.
+pub(crate) fn with_era_verification_keys(
+    signers: Vec<SignerWithStake>,
+    era_signers: &HashMap<PartyId, Signer>,
+) -> Vec<SignerWithStake> {
+    signers
+        .into_iter()
+        .map(|signer| match era_signers.get(&signer.party_id) {
+            Some(era_signer) => SignerWithStake {
+                verification_key: era_signer.verification_key.clone(),
+                verification_key_signature: era_signer.verification_key_signature.clone(),
+                ..signer
+            },
+            None => signer,
+        })
+        .collect()
+}
+
 /// Store for the `VerificationKey`.
 pub struct VerificationKeyStore {
     adapter: RwLock<Adapter>,
+    next_era_signers: RwLock<HashMap<(Epoch, SupportedEra), HashMap<PartyId, Signer>>>,
 }
 
 impl VerificationKeyStore {
@@ -50,6 +96,7 @@ impl VerificationKeyStore {
     pub fn new(adapter: Adapter) -> Self {
         Self {
             adapter: RwLock::new(adapter),
+            next_era_signers: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -102,6 +149,60 @@ impl VerificationKeyStorer for VerificationKeyStore {
         Ok(record.map(|h| h.into_values().collect()))
     }
 
+    async fn save_next_era_verification_key(
+        &self,
+        epoch: Epoch,
+        era: SupportedEra,
+        signer: Signer,
+    ) -> StdResult<()> {
+        self.next_era_signers
+            .write()
+            .await
+            .entry((epoch, era))
+            .or_default()
+            .insert(signer.party_id.to_owned(), signer);
+
+        Ok(())
+    }
+
+    async fn get_signers_of_era(
+        &self,
+        epoch: Epoch,
+        era: SupportedEra,
+    ) -> StdResult<Option<Vec<SignerWithStake>>> {
+        let signers = self.get_signers(epoch).await?;
+        let next_era_signers = self.next_era_signers.read().await;
+
+        Ok(
+            signers.map(|signers| match next_era_signers.get(&(epoch, era)) {
+                Some(era_signers) => with_era_verification_keys(signers, era_signers),
+                None => signers,
+            }),
+        )
+    }
+
+    async fn remove_verification_key(
+        &self,
+        epoch: Epoch,
+        party_id: &PartyId,
+    ) -> StdResult<Option<SignerWithStake>> {
+        let mut adapter = self.adapter.write().await;
+        let Some(mut signers) = adapter.get_record(&epoch).await? else {
+            return Ok(None);
+        };
+        let removed_signer = signers.remove(party_id);
+        if removed_signer.is_some() {
+            adapter.store_record(&epoch, &signers).await?;
+        }
+        for ((era_epoch, _), era_signers) in self.next_era_signers.write().await.iter_mut() {
+            if era_epoch == &epoch {
+                era_signers.remove(party_id);
+            }
+        }
+
+        Ok(removed_signer)
+    }
+
     async fn prune_verification_keys(&self, max_epoch_to_prune: Epoch) -> StdResult<()> {
         let mut adapter = self.adapter.write().await;
 
@@ -117,6 +218,10 @@ impl VerificationKeyStorer for VerificationKeyStore {
             adapter.remove(&epoch).await
                 .with_context(|| format!("Pruning verification keys: could not remove record for epoch '{epoch}' from the database."))?;
         }
+        self.next_era_signers
+            .write()
+            .await
+            .retain(|(epoch, _), _| epoch > &max_epoch_to_prune);
 
         Ok(())
     }
@@ -160,10 +265,20 @@ macro_rules! test_verification_key_storer {
                 test_suite::get_signers_for_existing_epoch(&$store_builder).await;
             }
 
+            #[tokio::test]
+            async fn remove_signer_from_store() {
+                test_suite::remove_signer_from_store(&$store_builder).await;
+            }
+
             #[tokio::test]
             async fn can_prune_keys_from_given_epoch_retention_limit() {
                 test_suite::can_prune_keys_from_given_epoch_retention_limit(&$store_builder).await;
             }
+
+            #[tokio::test]
+            async fn get_signers_of_era_with_their_next_era_keys() {
+                test_suite::get_signers_of_era_with_their_next_era_keys(&$store_builder).await;
+            }
         }
     };
 }
@@ -175,6 +290,7 @@ pub(crate) use test_verification_key_storer;
 #[cfg(test)]
 pub mod test_suite {
     use mithril_common::entities::{Epoch, PartyId, Signer, SignerWithStake};
+    use mithril_common::era::SupportedEra;
     use mithril_common::test_utils::fake_keys;
     use std::collections::{BTreeMap, BTreeSet, HashMap};
     use std::sync::Arc;
@@ -324,6 +440,42 @@ pub mod test_suite {
         assert_eq!(expected_signers, res);
     }
 
+    pub async fn remove_signer_from_store(store_builder: &StoreBuilder) {
+        let signers = build_signers(2, 2);
+        let store = store_builder(signers.clone());
+
+        let removed_signer = store
+            .remove_verification_key(Epoch(1), &"party_id:e1:1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            signers[0].1.get("party_id:e1:1").cloned(),
+            removed_signer,
+            "The removed signer should be returned"
+        );
+
+        let remaining_keys = store
+            .get_verification_keys(Epoch(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            vec!["party_id:e1:2".to_string()],
+            remaining_keys.into_keys().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            2,
+            store.get_signers(Epoch(2)).await.unwrap().unwrap().len(),
+            "The keys of the other epochs should not be removed"
+        );
+
+        let removed_signer = store
+            .remove_verification_key(Epoch(1), &"party_id:e1:1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(None, removed_signer);
+    }
+
     pub async fn can_prune_keys_from_given_epoch_retention_limit(store_builder: &StoreBuilder) {
         let signers = build_signers(6, 2);
         let store = store_builder(signers);
@@ -346,6 +498,61 @@ pub mod test_suite {
             assert_eq!(None, pruned_epoch_keys);
         }
     }
+
+    pub async fn get_signers_of_era_with_their_next_era_keys(store_builder: &StoreBuilder) {
+        let signers = build_signers(2, 2);
+        let store = store_builder(signers.clone());
+        let era = SupportedEra::dummy();
+        let signer = signers[0].1.get("party_id:e1:1").cloned().unwrap();
+        let next_era_signer = Signer {
+            verification_key: fake_keys::signer_verification_key()[2].try_into().unwrap(),
+            ..signer.clone().into()
+        };
+        store
+            .save_next_era_verification_key(Epoch(1), era, next_era_signer.clone())
+            .await
+            .unwrap();
+
+        let signers_of_era = store
+            .get_signers_of_era(Epoch(1), era)
+            .await
+            .unwrap()
+            .map(|x| BTreeSet::from_iter(x.into_iter()));
+        let mut expected_signers = BTreeSet::from_iter(signers[0].1.clone().into_values());
+        expected_signers.remove(&signer);
+        expected_signers.insert(SignerWithStake {
+            verification_key: next_era_signer.verification_key.clone(),
+            ..signer.clone()
+        });
+        assert_eq!(Some(expected_signers), signers_of_era);
+        assert_eq!(
+            Some(BTreeSet::from_iter(signers[0].1.clone().into_values())),
+            store
+                .get_signers(Epoch(1))
+                .await
+                .unwrap()
+                .map(|x| BTreeSet::from_iter(x.into_iter())),
+            "The main verification keys should not be replaced"
+        );
+
+        store
+            .remove_verification_key(Epoch(1), &signer.party_id)
+            .await
+            .unwrap();
+        store
+            .save_verification_key(Epoch(1), signer.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            Some(BTreeSet::from_iter(signers[0].1.clone().into_values())),
+            store
+                .get_signers_of_era(Epoch(1), era)
+                .await
+                .unwrap()
+                .map(|x| BTreeSet::from_iter(x.into_iter())),
+            "The next era verification key of a removed signer should be removed"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,232 @@
+use std::{
+    future::Future,
+    io::{self, Read},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncRead, ReadBuf},
+    time::{sleep_until, Instant, Sleep},
+};
+
+/// Limit the average bandwidth used by the transfers sharing it.
+///
+/// The transfers are paced: once some bytes have been transferred, the next ones are delayed
+/// until the time needed to transfer them at the limited rate has elapsed.
+///
+/// The limit can be changed while transfers are in progress, i.e. when the configuration of the
+/// aggregator is reloaded.
+pub struct BandwidthLimiter {
+    /// Limited rate, `0` if the bandwidth is not limited
+    bytes_per_second: AtomicU64,
+    next_transfer_at: Mutex<Option<Instant>>,
+}
+
+impl BandwidthLimiter {
+    /// BandwidthLimiter factory
+    pub fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second: AtomicU64::new(bytes_per_second.max(1)),
+            next_transfer_at: Mutex::new(None),
+        }
+    }
+
+    /// BandwidthLimiter factory, the transfers are not paced until a limit is set
+    pub fn unlimited() -> Self {
+        Self {
+            bytes_per_second: AtomicU64::new(0),
+            next_transfer_at: Mutex::new(None),
+        }
+    }
+
+    /// Set the limited rate, or remove the limit if `None`
+    pub fn set_bytes_per_second(&self, bytes_per_second: Option<u64>) {
+        self.bytes_per_second.store(
+            bytes_per_second.map(|rate| rate.max(1)).unwrap_or(0),
+            Ordering::Relaxed,
+        );
+    }
+
+    /// Limited rate, `None` if the bandwidth is not limited
+    pub fn bytes_per_second(&self) -> Option<u64> {
+        match self.bytes_per_second.load(Ordering::Relaxed) {
+            0 => None,
+            rate => Some(rate),
+        }
+    }
+
+    /// Account for the transfer of the given number of bytes, returns the instant from which the
+    /// next bytes can be transferred without exceeding the bandwidth limit.
+    pub fn consume(&self, bytes: u64) -> Instant {
+        let now = Instant::now();
+        let Some(bytes_per_second) = self.bytes_per_second() else {
+            return now;
+        };
+        let transfer_duration = Duration::from_secs_f64(bytes as f64 / bytes_per_second as f64);
+        let mut next_transfer_at = self.next_transfer_at.lock().unwrap();
+        let transfer_start = next_transfer_at.filter(|at| *at > now).unwrap_or(now);
+        let transfer_end = transfer_start + transfer_duration;
+        *next_transfer_at = Some(transfer_end);
+
+        transfer_end
+    }
+
+    /// Account for the transfer of the given number of bytes and wait until the next bytes can be
+    /// transferred.
+    pub async fn throttle(&self, bytes: u64) {
+        sleep_until(self.consume(bytes)).await;
+    }
+}
+
+/// [AsyncRead] wrapper whose reads are paced by a [BandwidthLimiter].
+pub struct ThrottledReader<R> {
+    inner: R,
+    limiter: Arc<BandwidthLimiter>,
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl<R: AsyncRead + Unpin> ThrottledReader<R> {
+    /// ThrottledReader factory
+    pub fn new(inner: R, limiter: Arc<BandwidthLimiter>) -> Self {
+        Self {
+            inner,
+            limiter,
+            delay: None,
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for ThrottledReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if let Some(delay) = this.delay.as_mut() {
+            ready!(delay.as_mut().poll(cx));
+            this.delay = None;
+        }
+
+        let filled_before = buf.filled().len();
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        let read_bytes = (buf.filled().len() - filled_before) as u64;
+        if read_bytes > 0 {
+            this.delay = Some(Box::pin(sleep_until(this.limiter.consume(read_bytes))));
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// [Read] wrapper whose reads are paced by a [BandwidthLimiter] by putting the current thread to
+/// sleep, it must only be used outside of the async runtime (i.e. in a blocking task).
+pub struct BlockingThrottledReader<R> {
+    inner: R,
+    limiter: Arc<BandwidthLimiter>,
+}
+
+impl<R: Read> BlockingThrottledReader<R> {
+    /// BlockingThrottledReader factory
+    pub fn new(inner: R, limiter: Arc<BandwidthLimiter>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+impl<R: Read> Read for BlockingThrottledReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read_bytes = self.inner.read(buf)?;
+        if read_bytes > 0 {
+            let next_read_at = self.limiter.consume(read_bytes as u64);
+            std::thread::sleep(next_read_at.saturating_duration_since(Instant::now()));
+        }
+
+        Ok(read_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn throttle_paces_the_transfers_at_the_limited_rate() {
+        let limiter = BandwidthLimiter::new(1000);
+        let started_at = Instant::now();
+
+        for _ in 0..3 {
+            limiter.throttle(100).await;
+        }
+
+        assert!(started_at.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[tokio::test]
+    async fn transfers_sharing_a_limiter_share_its_bandwidth() {
+        let limiter = BandwidthLimiter::new(1000);
+        let started_at = Instant::now();
+
+        let next_transfer_at = limiter.consume(100);
+        let other_next_transfer_at = limiter.consume(100);
+
+        assert!(next_transfer_at >= started_at + Duration::from_millis(100));
+        assert!(other_next_transfer_at >= next_transfer_at + Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn an_unlimited_limiter_does_not_pace_the_transfers_until_a_limit_is_set() {
+        let limiter = BandwidthLimiter::unlimited();
+        let started_at = Instant::now();
+
+        assert!(limiter.consume(1_000_000) <= Instant::now());
+
+        limiter.set_bytes_per_second(Some(1000));
+        assert_eq!(Some(1000), limiter.bytes_per_second());
+        assert!(limiter.consume(100) >= started_at + Duration::from_millis(100));
+
+        limiter.set_bytes_per_second(None);
+        assert_eq!(None, limiter.bytes_per_second());
+    }
+
+    #[tokio::test]
+    async fn throttled_reader_reads_all_the_content_at_the_limited_rate() {
+        let content = vec![1u8; 300];
+        let mut reader = ThrottledReader::new(&content[..], Arc::new(BandwidthLimiter::new(1000)));
+        let started_at = Instant::now();
+
+        let mut read_content = vec![];
+        let mut chunk = [0u8; 100];
+        loop {
+            let read_bytes = reader.read(&mut chunk).await.unwrap();
+            if read_bytes == 0 {
+                break;
+            }
+            read_content.extend_from_slice(&chunk[..read_bytes]);
+        }
+
+        assert_eq!(content, read_content);
+        // The last read, that detects the end of the content, waits for the third chunk
+        assert!(started_at.elapsed() >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn blocking_throttled_reader_reads_all_the_content_at_the_limited_rate() {
+        let content = vec![1u8; 300];
+        let mut reader =
+            BlockingThrottledReader::new(&content[..], Arc::new(BandwidthLimiter::new(1000)));
+        let started_at = Instant::now();
+
+        let mut read_content = vec![];
+        reader.read_to_end(&mut read_content).unwrap();
+
+        assert_eq!(content, read_content);
+        assert!(started_at.elapsed() >= Duration::from_millis(300));
+    }
+}
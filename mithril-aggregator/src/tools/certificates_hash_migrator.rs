@@ -191,7 +191,8 @@ impl CertificatesHashMigrator {
 #[cfg(test)]
 mod test {
     use mithril_common::entities::{
-        ImmutableFileNumber, SignedEntityType, SignedEntityTypeDiscriminants as Type, TimePoint,
+        ChainPoint, ImmutableFileNumber, SignedEntityType, SignedEntityTypeDiscriminants as Type,
+        TimePoint,
     };
     use mithril_common::test_utils::fake_data;
     use mithril_persistence::sqlite::{ConnectionBuilder, ConnectionOptions, SqliteConnection};
@@ -218,7 +219,7 @@ mod test {
     }
 
     fn time_at(epoch: u64, immutable_file_number: ImmutableFileNumber) -> TimePoint {
-        TimePoint::new(epoch, immutable_file_number)
+        TimePoint::new(epoch, immutable_file_number, ChainPoint::dummy())
     }
 
     fn dummy_genesis(certificate_hash: &str, time_point: TimePoint) -> Certificate {
@@ -0,0 +1,644 @@
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use reqwest::{IntoUrl, Url};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use slog_scope::info;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use mithril_common::certificate_chain::{
+    CertificateRetriever, CertificateRetrieverError, CertificateVerifier,
+    MithrilCertificateVerifier,
+};
+use mithril_common::crypto_helper::ProtocolGenesisVerificationKey;
+use mithril_common::entities::{
+    CardanoTransactionsSnapshot, Certificate, MithrilStakeDistribution, SignedEntityType, Snapshot,
+};
+use mithril_common::messages::{
+    CardanoTransactionSnapshotListMessage, CertificateListMessage, CertificateMessage,
+    MithrilStakeDistributionListMessage, MithrilStakeDistributionMessage,
+    SignerWithStakeMessagePart, SnapshotListMessage,
+};
+use mithril_common::StdResult;
+
+use crate::database::record::SignedEntityRecord;
+use crate::database::repository::{CertificateRepository, SignedEntityStorer};
+
+#[cfg(test)]
+use mockall::automock;
+
+/// Report of a [CertificatesImporter] run
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct CertificatesImportReport {
+    /// Number of certificates added to the database
+    pub imported_certificates: usize,
+
+    /// Number of signed entities added to the database
+    pub imported_signed_entities: usize,
+}
+
+/// Tool that import the certificate chain of another aggregator, and the signed entities
+/// associated to its certificates.
+///
+/// The chain is retrieved from the latest certificate of the remote aggregator down to its
+/// genesis certificate, or down to the first certificate already stored in the database, so
+/// running the import several times only adds the missing certificates.
+pub struct CertificatesImporter {
+    retriever: Arc<dyn CertificatesImporterRetriever>,
+    certificate_repository: Arc<CertificateRepository>,
+    signed_entity_storer: Arc<dyn SignedEntityStorer>,
+    genesis_verification_key: ProtocolGenesisVerificationKey,
+}
+
+impl CertificatesImporter {
+    /// [CertificatesImporter] factory
+    pub fn new(
+        retriever: Arc<dyn CertificatesImporterRetriever>,
+        certificate_repository: Arc<CertificateRepository>,
+        signed_entity_storer: Arc<dyn SignedEntityStorer>,
+        genesis_verification_key: ProtocolGenesisVerificationKey,
+    ) -> Self {
+        Self {
+            retriever,
+            certificate_repository,
+            signed_entity_storer,
+            genesis_verification_key,
+        }
+    }
+
+    /// Import, validate and persist the missing certificates, then the signed entities
+    pub async fn run(&self) -> StdResult<CertificatesImportReport> {
+        info!("🔧 Certificates Importer: starting");
+        let certificates = self
+            .retrieve_missing_certificates()
+            .await
+            .with_context(|| "Failed to retrieve certificates from the remote aggregator")?;
+        self.verify_certificates(&certificates)
+            .await
+            .with_context(|| "Invalid certificate chain retrieved from the remote aggregator")?;
+
+        let imported_certificates = certificates.len();
+        info!(
+            "🔧 Certificates Importer: persisting certificates in the database";
+            "number_of_certificates_to_insert" => imported_certificates
+        );
+        self.certificate_repository
+            .create_many_certificates(certificates.into_iter().rev().collect())
+            .await
+            .with_context(|| "Failed to persist retrieved certificates into the database")?;
+
+        let imported_signed_entities = self
+            .import_signed_entities()
+            .await
+            .with_context(|| "Failed to import signed entities from the remote aggregator")?;
+
+        Ok(CertificatesImportReport {
+            imported_certificates,
+            imported_signed_entities,
+        })
+    }
+
+    /// Walk the remote chain until the genesis certificate or a certificate already stored,
+    /// returns the missing certificates, the most recent first.
+    async fn retrieve_missing_certificates(&self) -> StdResult<Vec<Certificate>> {
+        let mut certificates = vec![];
+        let mut next_hash = self.retriever.get_latest_certificate_hash().await?;
+
+        while let Some(hash) = next_hash {
+            if self.is_certificate_stored(&hash).await? {
+                break;
+            }
+            let certificate = self.retriever.get_certificate(&hash).await?;
+            if certificate.hash != hash {
+                return Err(anyhow!(
+                    "Retrieved certificate hash '{}' does not match requested hash '{hash}'",
+                    certificate.hash
+                ));
+            }
+            next_hash = (!certificate.is_genesis()).then(|| certificate.previous_hash.clone());
+            certificates.push(certificate);
+        }
+
+        Ok(certificates)
+    }
+
+    async fn is_certificate_stored(&self, hash: &str) -> StdResult<bool> {
+        Ok(self
+            .certificate_repository
+            .get_certificate::<Certificate>(hash)
+            .await?
+            .is_some())
+    }
+
+    async fn verify_certificates(&self, certificates: &[Certificate]) -> StdResult<()> {
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(ImportedCertificatesRetriever {
+                imported_certificates: certificates
+                    .iter()
+                    .map(|certificate| (certificate.hash.clone(), certificate.clone()))
+                    .collect(),
+                certificate_repository: self.certificate_repository.clone(),
+            }),
+        );
+
+        for certificate in certificates {
+            verifier
+                .verify_certificate(certificate, &self.genesis_verification_key)
+                .await
+                .with_context(|| format!("Certificate '{}' is invalid", certificate.hash))?;
+        }
+
+        Ok(())
+    }
+
+    async fn import_signed_entities(&self) -> StdResult<usize> {
+        let mut imported_signed_entities = 0;
+
+        for record in self.retriever.get_signed_entities().await? {
+            if self
+                .signed_entity_storer
+                .get_signed_entity(&record.signed_entity_id)
+                .await?
+                .is_some()
+                || !self.is_certificate_stored(&record.certificate_id).await?
+            {
+                continue;
+            }
+            self.signed_entity_storer
+                .store_signed_entity(&record)
+                .await?;
+            imported_signed_entities += 1;
+        }
+
+        Ok(imported_signed_entities)
+    }
+}
+
+/// Trait that define how a [CertificatesImporter] retrieve the data to import.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait CertificatesImporterRetriever: Sync + Send {
+    /// Retrieve the hash of the latest certificate, if any.
+    async fn get_latest_certificate_hash(&self) -> StdResult<Option<String>>;
+
+    /// Retrieve the certificate with the given hash.
+    async fn get_certificate(&self, hash: &str) -> StdResult<Certificate>;
+
+    /// Retrieve the signed entities that can be imported.
+    async fn get_signed_entities(&self) -> StdResult<Vec<SignedEntityRecord>>;
+}
+
+/// A [CertificatesImporterRetriever] fetching data from the HTTP API of an aggregator.
+///
+/// Only the signed entities listed by the artifacts routes of the aggregator can be retrieved.
+pub struct AggregatorCertificatesRetriever {
+    aggregator_endpoint: Url,
+    client: reqwest::Client,
+}
+
+impl AggregatorCertificatesRetriever {
+    /// Create a new [AggregatorCertificatesRetriever] that will fetch data from the given
+    /// aggregator endpoint.
+    pub fn new<T: IntoUrl>(aggregator_endpoint: T, timeout: Option<Duration>) -> StdResult<Self> {
+        let mut aggregator_endpoint = aggregator_endpoint
+            .into_url()
+            .with_context(|| "Given `aggregator_endpoint` is not a valid Url")?;
+        if !aggregator_endpoint.path().ends_with('/') {
+            aggregator_endpoint.set_path(&format!("{}/", aggregator_endpoint.path()));
+        }
+        let client_builder = reqwest::Client::builder();
+        let client = match timeout {
+            None => client_builder,
+            Some(timeout) => client_builder.timeout(timeout),
+        }
+        .build()
+        .with_context(|| "Http Client build failed")?;
+
+        Ok(Self {
+            aggregator_endpoint,
+            client,
+        })
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> StdResult<T> {
+        let url = self
+            .aggregator_endpoint
+            .join(path)
+            .with_context(|| format!("Invalid aggregator route: '{path}'"))?;
+        let response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .with_context(|| format!("Request to '{url}' failed"))?;
+
+        response
+            .error_for_status()
+            .with_context(|| format!("Data fetching from '{url}' failed"))?
+            .json::<T>()
+            .await
+            .with_context(|| format!("Failed to deserialize data retrieved from '{url}'"))
+    }
+
+    async fn get_snapshots(&self) -> StdResult<Vec<SignedEntityRecord>> {
+        let snapshots: SnapshotListMessage = self.get("artifact/snapshots").await?;
+
+        snapshots
+            .into_iter()
+            .map(|message| {
+                let artifact = Snapshot {
+                    digest: message.digest.clone(),
+                    beacon: message.beacon.clone(),
+                    size: message.size,
+                    locations: message.locations,
+                    compression_algorithm: message.compression_algorithm.unwrap_or_default(),
+                    cardano_node_version: message.cardano_node_version.unwrap_or_default(),
+                    ancillary_size: None,
+                    ancillary_locations: None,
+                    archive_hash: None,
+                };
+
+                Ok(SignedEntityRecord {
+                    signed_entity_id: message.digest,
+                    signed_entity_type: SignedEntityType::CardanoImmutableFilesFull(message.beacon),
+                    certificate_id: message.certificate_hash,
+                    artifact: serde_json::to_string(&artifact)?,
+                    created_at: message.created_at,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_mithril_stake_distributions(&self) -> StdResult<Vec<SignedEntityRecord>> {
+        let list: MithrilStakeDistributionListMessage =
+            self.get("artifact/mithril-stake-distributions").await?;
+        let mut records = vec![];
+
+        // The list items do not contain the signers, the details must be fetched
+        for item in list {
+            let message: MithrilStakeDistributionMessage = self
+                .get(&format!(
+                    "artifact/mithril-stake-distribution/{}",
+                    item.hash
+                ))
+                .await?;
+            let artifact = MithrilStakeDistribution {
+                epoch: message.epoch,
+                signers_with_stake: SignerWithStakeMessagePart::try_into_signers(
+                    message.signers_with_stake,
+                )?,
+                hash: message.hash.clone(),
+                protocol_parameters: message.protocol_parameters,
+            };
+
+            records.push(SignedEntityRecord {
+                signed_entity_id: message.hash,
+                signed_entity_type: SignedEntityType::MithrilStakeDistribution(message.epoch),
+                certificate_id: message.certificate_hash,
+                artifact: serde_json::to_string(&artifact)?,
+                created_at: message.created_at,
+            });
+        }
+
+        Ok(records)
+    }
+
+    async fn get_cardano_transactions(&self) -> StdResult<Vec<SignedEntityRecord>> {
+        let list: CardanoTransactionSnapshotListMessage =
+            self.get("artifact/cardano-transactions").await?;
+
+        list.into_iter()
+            .map(|message| {
+                let artifact = CardanoTransactionsSnapshot {
+                    hash: message.hash.clone(),
+                    merkle_root: message.merkle_root,
+                    beacon: message.beacon.clone(),
+                };
+
+                Ok(SignedEntityRecord {
+                    signed_entity_id: message.hash,
+                    signed_entity_type: SignedEntityType::CardanoTransactions(message.beacon),
+                    certificate_id: message.certificate_hash,
+                    artifact: serde_json::to_string(&artifact)?,
+                    created_at: message.created_at,
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl CertificatesImporterRetriever for AggregatorCertificatesRetriever {
+    async fn get_latest_certificate_hash(&self) -> StdResult<Option<String>> {
+        info!(
+            "🔧 Certificates Importer: retrieving latest certificate";
+            "aggregator_endpoint" => &self.aggregator_endpoint.as_str()
+        );
+        let certificates: CertificateListMessage = self.get("certificates").await?;
+
+        Ok(certificates
+            .into_iter()
+            .next()
+            .map(|certificate| certificate.hash))
+    }
+
+    async fn get_certificate(&self, hash: &str) -> StdResult<Certificate> {
+        let message: CertificateMessage = self.get(&format!("certificate/{hash}")).await?;
+
+        message.try_into()
+    }
+
+    async fn get_signed_entities(&self) -> StdResult<Vec<SignedEntityRecord>> {
+        info!(
+            "🔧 Certificates Importer: retrieving signed entities";
+            "aggregator_endpoint" => &self.aggregator_endpoint.as_str()
+        );
+        let mut records = self.get_snapshots().await?;
+        records.extend(self.get_mithril_stake_distributions().await?);
+        records.extend(self.get_cardano_transactions().await?);
+
+        Ok(records)
+    }
+}
+
+/// *Internal type* Retrieve the previous certificates during the verification of the imported
+/// certificates, from the imported certificates first then from the database.
+struct ImportedCertificatesRetriever {
+    imported_certificates: HashMap<String, Certificate>,
+    certificate_repository: Arc<CertificateRepository>,
+}
+
+#[async_trait]
+impl CertificateRetriever for ImportedCertificatesRetriever {
+    async fn get_certificate_details(
+        &self,
+        certificate_hash: &str,
+    ) -> Result<Certificate, CertificateRetrieverError> {
+        match self.imported_certificates.get(certificate_hash) {
+            Some(certificate) => Ok(certificate.clone()),
+            None => {
+                self.certificate_repository
+                    .get_certificate_details(certificate_hash)
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::crypto_helper::tests_setup::setup_certificate_chain;
+    use mithril_common::messages::{
+        CardanoTransactionSnapshotListItemMessage, CertificateListItemMessage,
+        MithrilStakeDistributionListItemMessage, SnapshotListItemMessage,
+    };
+    use mithril_common::test_utils::test_http_server::test_http_server;
+    use warp::Filter;
+
+    use crate::database::repository::SignedEntityStore;
+    use crate::database::test_helper::main_db_connection;
+
+    use super::*;
+
+    struct TestImporter {
+        importer: CertificatesImporter,
+        certificate_repository: Arc<CertificateRepository>,
+        signed_entity_store: Arc<SignedEntityStore>,
+    }
+
+    fn build_importer(
+        retriever: MockCertificatesImporterRetriever,
+        genesis_verification_key: ProtocolGenesisVerificationKey,
+    ) -> TestImporter {
+        let connection = Arc::new(main_db_connection().unwrap());
+        let certificate_repository = Arc::new(CertificateRepository::new(connection.clone()));
+        let signed_entity_store = Arc::new(SignedEntityStore::new(connection));
+
+        TestImporter {
+            importer: CertificatesImporter::new(
+                Arc::new(retriever),
+                certificate_repository.clone(),
+                signed_entity_store.clone(),
+                genesis_verification_key,
+            ),
+            certificate_repository,
+            signed_entity_store,
+        }
+    }
+
+    fn retriever_serving(
+        certificates: &[Certificate],
+        signed_entities: Vec<SignedEntityRecord>,
+    ) -> MockCertificatesImporterRetriever {
+        let latest_hash = certificates.first().map(|c| c.hash.clone());
+        let certificates: HashMap<String, Certificate> = certificates
+            .iter()
+            .map(|c| (c.hash.clone(), c.clone()))
+            .collect();
+        let mut retriever = MockCertificatesImporterRetriever::new();
+        retriever
+            .expect_get_latest_certificate_hash()
+            .returning(move || Ok(latest_hash.clone()));
+        retriever.expect_get_certificate().returning(move |hash| {
+            certificates
+                .get(hash)
+                .cloned()
+                .ok_or(anyhow!("unknown certificate '{hash}'"))
+        });
+        retriever
+            .expect_get_signed_entities()
+            .returning(move || Ok(signed_entities.clone()));
+
+        retriever
+    }
+
+    #[tokio::test]
+    async fn import_the_whole_chain_down_to_genesis() {
+        let (certificates, genesis_verifier) = setup_certificate_chain(6, 2);
+        let test = build_importer(
+            retriever_serving(&certificates, vec![]),
+            genesis_verifier.to_verification_key(),
+        );
+
+        let report = test.importer.run().await.unwrap();
+
+        assert_eq!(
+            CertificatesImportReport {
+                imported_certificates: certificates.len(),
+                imported_signed_entities: 0,
+            },
+            report
+        );
+        let stored_certificates: Vec<Certificate> = test
+            .certificate_repository
+            .get_latest_certificates(usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(certificates, stored_certificates);
+    }
+
+    #[tokio::test]
+    async fn import_only_the_certificates_that_are_not_already_stored() {
+        let (certificates, genesis_verifier) = setup_certificate_chain(6, 2);
+        let test = build_importer(
+            retriever_serving(&certificates, vec![]),
+            genesis_verifier.to_verification_key(),
+        );
+        test.certificate_repository
+            .create_many_certificates(certificates[3..].iter().rev().cloned().collect())
+            .await
+            .unwrap();
+
+        let report = test.importer.run().await.unwrap();
+        assert_eq!(3, report.imported_certificates);
+
+        let report = test.importer.run().await.unwrap();
+        assert_eq!(CertificatesImportReport::default(), report);
+    }
+
+    #[tokio::test]
+    async fn do_not_persist_anything_if_the_chain_is_invalid() {
+        let (mut certificates, genesis_verifier) = setup_certificate_chain(6, 2);
+        certificates[2].signed_message = "tampered".to_string();
+        let test = build_importer(
+            retriever_serving(&certificates, vec![]),
+            genesis_verifier.to_verification_key(),
+        );
+
+        test.importer
+            .run()
+            .await
+            .expect_err("An invalid chain should not be imported");
+
+        let stored_certificates: Vec<Certificate> = test
+            .certificate_repository
+            .get_latest_certificates(usize::MAX)
+            .await
+            .unwrap();
+        assert!(stored_certificates.is_empty());
+    }
+
+    #[tokio::test]
+    async fn import_once_the_signed_entities_of_the_stored_certificates() {
+        let (certificates, genesis_verifier) = setup_certificate_chain(3, 1);
+        let mut signed_entities = SignedEntityRecord::fake_records(2);
+        signed_entities[0].certificate_id = certificates[0].hash.clone();
+        signed_entities[1].certificate_id = "unknown-certificate".to_string();
+        let test = build_importer(
+            retriever_serving(&certificates, signed_entities.clone()),
+            genesis_verifier.to_verification_key(),
+        );
+
+        let report = test.importer.run().await.unwrap();
+        assert_eq!(1, report.imported_signed_entities);
+
+        let report = test.importer.run().await.unwrap();
+        assert_eq!(0, report.imported_signed_entities);
+
+        let stored_signed_entity = test
+            .signed_entity_store
+            .get_signed_entity(&signed_entities[0].signed_entity_id)
+            .await
+            .unwrap();
+        assert_eq!(Some(signed_entities[0].clone()), stored_signed_entity);
+        let not_stored_signed_entity = test
+            .signed_entity_store
+            .get_signed_entity(&signed_entities[1].signed_entity_id)
+            .await
+            .unwrap();
+        assert_eq!(None, not_stored_signed_entity);
+    }
+
+    #[tokio::test]
+    async fn aggregator_retriever_retrieve_certificates() {
+        let (certificates, _) = setup_certificate_chain(2, 1);
+        let latest_certificate = certificates[0].clone();
+        let latest_message = CertificateMessage::try_from(latest_certificate.clone()).unwrap();
+        let list_message = vec![CertificateListItemMessage {
+            hash: latest_certificate.hash.clone(),
+            ..CertificateListItemMessage::dummy()
+        }];
+        let server = test_http_server(
+            warp::path!("aggregator" / "certificates")
+                .map(move || warp::reply::json(&list_message))
+                .or(warp::path!("aggregator" / "certificate" / String)
+                    .map(move |_hash| warp::reply::json(&latest_message))),
+        );
+
+        let retriever =
+            AggregatorCertificatesRetriever::new(format!("{}/aggregator", server.url()), None)
+                .unwrap();
+
+        assert_eq!(
+            Some(latest_certificate.hash.clone()),
+            retriever.get_latest_certificate_hash().await.unwrap()
+        );
+        assert_eq!(
+            latest_certificate,
+            retriever
+                .get_certificate(&latest_certificate.hash)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn aggregator_retriever_retrieve_signed_entities_of_all_artifacts() {
+        let snapshots = vec![SnapshotListItemMessage::dummy()];
+        let mithril_stake_distributions = vec![MithrilStakeDistributionListItemMessage::dummy()];
+        let mithril_stake_distribution = MithrilStakeDistributionMessage::dummy();
+        let cardano_transactions = vec![CardanoTransactionSnapshotListItemMessage::dummy()];
+        let server = test_http_server(
+            warp::path!("artifact" / "snapshots")
+                .map(move || warp::reply::json(&snapshots))
+                .or(warp::path!("artifact" / "mithril-stake-distributions")
+                    .map(move || warp::reply::json(&mithril_stake_distributions)))
+                .or(
+                    warp::path!("artifact" / "mithril-stake-distribution" / String)
+                        .map(move |_hash| warp::reply::json(&mithril_stake_distribution)),
+                )
+                .or(warp::path!("artifact" / "cardano-transactions")
+                    .map(move || warp::reply::json(&cardano_transactions))),
+        );
+
+        let retriever = AggregatorCertificatesRetriever::new(server.url(), None).unwrap();
+        let records = retriever.get_signed_entities().await.unwrap();
+
+        assert_eq!(
+            vec![
+                SignedEntityType::CardanoImmutableFilesFull(
+                    SnapshotListItemMessage::dummy().beacon
+                ),
+                SignedEntityType::MithrilStakeDistribution(
+                    MithrilStakeDistributionMessage::dummy().epoch
+                ),
+                SignedEntityType::CardanoTransactions(
+                    CardanoTransactionSnapshotListItemMessage::dummy().beacon
+                ),
+            ],
+            records
+                .iter()
+                .map(|record| record.signed_entity_type.clone())
+                .collect::<Vec<_>>()
+        );
+        let snapshot: Snapshot = records[0].clone().into();
+        assert_eq!(SnapshotListItemMessage::dummy().digest, snapshot.digest);
+    }
+
+    #[tokio::test]
+    async fn aggregator_retriever_handle_http_data_fetching_error() {
+        let server = test_http_server(
+            warp::path!("certificates")
+                .map(|| warp::reply::with_status("", warp::http::StatusCode::NOT_FOUND)),
+        );
+
+        let retriever = AggregatorCertificatesRetriever::new(server.url(), None).unwrap();
+        retriever
+            .get_latest_certificate_hash()
+            .await
+            .expect_err("An error should have been raised");
+    }
+}
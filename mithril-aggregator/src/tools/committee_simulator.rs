@@ -0,0 +1,418 @@
+use anyhow::anyhow;
+use mithril_common::{
+    entities::{ProtocolParameters, Stake, StakeDistribution},
+    StdResult,
+};
+use rand_chacha::ChaCha20Rng;
+use rand_core::{RngCore, SeedableRng};
+use serde::Serialize;
+
+/// Parameters of a committee simulation run by the [CommitteeSimulator]
+#[derive(Debug, Clone)]
+pub struct CommitteeSimulationParameters {
+    /// Protocol parameters used for the lotteries
+    pub protocol_parameters: ProtocolParameters,
+
+    /// Number of signing rounds to simulate
+    pub number_of_rounds: u64,
+
+    /// Probability for each signer to send its signature during a round
+    pub participation_rate: f64,
+
+    /// Mean delay before a signature is received by the aggregator (in milliseconds),
+    /// the delays follow an exponential distribution
+    pub mean_signature_delay_ms: f64,
+
+    /// Seed of the random generator, the same seed always yields the same report
+    pub seed: u64,
+}
+
+/// Distribution of the delays needed to reach the quorum (in milliseconds)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CertificateLatencyDistribution {
+    /// Smallest latency
+    pub min: f64,
+
+    /// Average latency
+    pub mean: f64,
+
+    /// Median latency
+    pub p50: f64,
+
+    /// 90th percentile of the latencies
+    pub p90: f64,
+
+    /// 99th percentile of the latencies
+    pub p99: f64,
+
+    /// Largest latency
+    pub max: f64,
+}
+
+/// Result of a committee simulation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommitteeSimulationReport {
+    /// Number of simulated signing rounds
+    pub number_of_rounds: u64,
+
+    /// Number of rounds where the quorum was not reached
+    pub number_of_failed_rounds: u64,
+
+    /// Estimated probability that a round does not reach the quorum
+    pub quorum_failure_probability: f64,
+
+    /// Average number of unique indexes won per round
+    pub mean_won_indexes: f64,
+
+    /// Latency distribution of the rounds that reached the quorum, `None` if all rounds failed
+    pub certificate_latency_ms: Option<CertificateLatencyDistribution>,
+}
+
+/// Tool that simulates signing rounds for a stake distribution to estimate the quorum failure
+/// probability and the certificate latency of a set of protocol parameters.
+///
+/// The lotteries are simulated without any cryptography: a signer with the relative stake `w`
+/// wins each of the `m` lotteries with the probability `phi(w) = 1 - (1 - phi_f)^w`.
+pub struct CommitteeSimulator {
+    stakes: Vec<Stake>,
+}
+
+impl CommitteeSimulator {
+    /// [CommitteeSimulator] factory
+    pub fn new(stake_distribution: &StakeDistribution) -> StdResult<Self> {
+        let stakes: Vec<Stake> = stake_distribution
+            .values()
+            .copied()
+            .filter(|stake| *stake > 0)
+            .collect();
+        if stakes.is_empty() {
+            return Err(anyhow!(
+                "the stake distribution must contain at least one signer with a stake"
+            ));
+        }
+
+        Ok(Self { stakes })
+    }
+
+    /// Run the simulation
+    pub fn simulate(
+        &self,
+        parameters: &CommitteeSimulationParameters,
+    ) -> StdResult<CommitteeSimulationReport> {
+        Self::check_parameters(parameters)?;
+
+        let mut rng = ChaCha20Rng::seed_from_u64(parameters.seed);
+        let lottery_probabilities =
+            self.compute_lottery_probabilities(&parameters.protocol_parameters);
+        let mut latencies = vec![];
+        let mut total_won_indexes = 0;
+
+        for _ in 0..parameters.number_of_rounds {
+            let (won_indexes, latency) =
+                Self::simulate_round(&lottery_probabilities, parameters, &mut rng);
+            total_won_indexes += won_indexes;
+            if let Some(latency) = latency {
+                latencies.push(latency);
+            }
+        }
+
+        let number_of_failed_rounds = parameters.number_of_rounds - latencies.len() as u64;
+
+        Ok(CommitteeSimulationReport {
+            number_of_rounds: parameters.number_of_rounds,
+            number_of_failed_rounds,
+            quorum_failure_probability: number_of_failed_rounds as f64
+                / parameters.number_of_rounds as f64,
+            mean_won_indexes: total_won_indexes as f64 / parameters.number_of_rounds as f64,
+            certificate_latency_ms: Self::compute_latency_distribution(latencies),
+        })
+    }
+
+    fn check_parameters(parameters: &CommitteeSimulationParameters) -> StdResult<()> {
+        let protocol_parameters = &parameters.protocol_parameters;
+        if protocol_parameters.m == 0 || protocol_parameters.k == 0 {
+            return Err(anyhow!(
+                "protocol parameters 'k' and 'm' must be greater than zero"
+            ));
+        }
+        if !(0.0..=1.0).contains(&protocol_parameters.phi_f) {
+            return Err(anyhow!(
+                "protocol parameter 'phi_f' must be between 0 and 1"
+            ));
+        }
+        if parameters.number_of_rounds == 0 {
+            return Err(anyhow!("the number of rounds must be greater than zero"));
+        }
+        if !(0.0..=1.0).contains(&parameters.participation_rate) {
+            return Err(anyhow!("the participation rate must be between 0 and 1"));
+        }
+        if parameters.mean_signature_delay_ms < 0.0 {
+            return Err(anyhow!("the mean signature delay can not be negative"));
+        }
+
+        Ok(())
+    }
+
+    fn compute_lottery_probabilities(&self, protocol_parameters: &ProtocolParameters) -> Vec<f64> {
+        let total_stake = self.stakes.iter().map(|stake| *stake as f64).sum::<f64>();
+
+        self.stakes
+            .iter()
+            .map(|stake| protocol_parameters.phi(*stake as f64 / total_stake))
+            .collect()
+    }
+
+    /// Simulate a round, returns the number of unique indexes won and the delay at which
+    /// the quorum was reached if it was.
+    fn simulate_round(
+        lottery_probabilities: &[f64],
+        parameters: &CommitteeSimulationParameters,
+        rng: &mut ChaCha20Rng,
+    ) -> (u64, Option<f64>) {
+        let m = parameters.protocol_parameters.m;
+        let k = parameters.protocol_parameters.k;
+        let mut signatures: Vec<(f64, Vec<u64>)> = vec![];
+
+        for probability in lottery_probabilities {
+            if uniform_sample(rng) >= parameters.participation_rate {
+                continue;
+            }
+            let indexes = sample_won_indexes(*probability, m, rng);
+            if indexes.is_empty() {
+                continue;
+            }
+            let delay = -parameters.mean_signature_delay_ms * open_uniform_sample(rng).ln();
+            signatures.push((delay, indexes));
+        }
+        signatures.sort_by(|(left, _), (right, _)| left.total_cmp(right));
+
+        let mut is_index_won = vec![false; m as usize];
+        let mut won_indexes = 0;
+        let mut quorum_latency = None;
+        for (delay, indexes) in signatures {
+            for index in indexes {
+                if !is_index_won[index as usize] {
+                    is_index_won[index as usize] = true;
+                    won_indexes += 1;
+                }
+            }
+            if quorum_latency.is_none() && won_indexes >= k {
+                quorum_latency = Some(delay);
+            }
+        }
+
+        (won_indexes, quorum_latency)
+    }
+
+    fn compute_latency_distribution(
+        mut latencies: Vec<f64>,
+    ) -> Option<CertificateLatencyDistribution> {
+        if latencies.is_empty() {
+            return None;
+        }
+        latencies.sort_by(|left, right| left.total_cmp(right));
+        let percentile = |rank: f64| {
+            let index = ((rank * latencies.len() as f64).ceil() as usize).max(1) - 1;
+            latencies[index]
+        };
+
+        Some(CertificateLatencyDistribution {
+            min: latencies[0],
+            mean: latencies.iter().sum::<f64>() / latencies.len() as f64,
+            p50: percentile(0.5),
+            p90: percentile(0.9),
+            p99: percentile(0.99),
+            max: latencies[latencies.len() - 1],
+        })
+    }
+}
+
+/// Uniform sample in `[0, 1)`
+fn uniform_sample(rng: &mut ChaCha20Rng) -> f64 {
+    (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Uniform sample in `(0, 1]`
+fn open_uniform_sample(rng: &mut ChaCha20Rng) -> f64 {
+    1.0 - uniform_sample(rng)
+}
+
+/// Sample the indexes won among `m` lotteries each won with the given probability.
+///
+/// The gaps between two won indexes follow a geometric distribution, this allows to draw only
+/// one random number per won index instead of one per lottery.
+fn sample_won_indexes(probability: f64, m: u64, rng: &mut ChaCha20Rng) -> Vec<u64> {
+    if probability <= 0.0 {
+        return vec![];
+    }
+    if probability >= 1.0 {
+        return (0..m).collect();
+    }
+
+    let log_failure = (1.0 - probability).ln();
+    let mut indexes = vec![];
+    let mut index = 0.0;
+    loop {
+        index += (open_uniform_sample(rng).ln() / log_failure).floor();
+        if index >= m as f64 {
+            break;
+        }
+        indexes.push(index as u64);
+        index += 1.0;
+    }
+
+    indexes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stake_distribution(stakes: &[Stake]) -> StakeDistribution {
+        stakes
+            .iter()
+            .enumerate()
+            .map(|(index, stake)| (format!("pool{index}"), *stake))
+            .collect()
+    }
+
+    fn simulation_parameters(k: u64, m: u64, phi_f: f64) -> CommitteeSimulationParameters {
+        CommitteeSimulationParameters {
+            protocol_parameters: ProtocolParameters::new(k, m, phi_f),
+            number_of_rounds: 200,
+            participation_rate: 1.0,
+            mean_signature_delay_ms: 1000.0,
+            seed: 0,
+        }
+    }
+
+    #[test]
+    fn fails_with_an_empty_or_stakeless_distribution() {
+        CommitteeSimulator::new(&StakeDistribution::new())
+            .expect_err("an empty stake distribution should fail");
+        CommitteeSimulator::new(&stake_distribution(&[0, 0]))
+            .expect_err("a stake distribution without stake should fail");
+    }
+
+    #[test]
+    fn fails_with_invalid_parameters() {
+        let simulator = CommitteeSimulator::new(&stake_distribution(&[10, 20])).unwrap();
+
+        for parameters in [
+            simulation_parameters(0, 100, 0.2),
+            simulation_parameters(5, 100, 1.5),
+            CommitteeSimulationParameters {
+                number_of_rounds: 0,
+                ..simulation_parameters(5, 100, 0.2)
+            },
+            CommitteeSimulationParameters {
+                participation_rate: 1.2,
+                ..simulation_parameters(5, 100, 0.2)
+            },
+        ] {
+            simulator
+                .simulate(&parameters)
+                .expect_err(&format!("simulation should fail with {parameters:?}"));
+        }
+    }
+
+    #[test]
+    fn quorum_is_always_reached_when_every_lottery_is_won() {
+        let simulator = CommitteeSimulator::new(&stake_distribution(&[10, 20, 30])).unwrap();
+
+        let report = simulator
+            .simulate(&simulation_parameters(50, 100, 1.0))
+            .unwrap();
+
+        assert_eq!(0, report.number_of_failed_rounds);
+        assert_eq!(0.0, report.quorum_failure_probability);
+        assert_eq!(100.0, report.mean_won_indexes);
+        assert!(report.certificate_latency_ms.is_some());
+    }
+
+    #[test]
+    fn quorum_is_never_reached_if_it_exceeds_the_number_of_lotteries() {
+        let simulator = CommitteeSimulator::new(&stake_distribution(&[10, 20, 30])).unwrap();
+
+        let report = simulator
+            .simulate(&simulation_parameters(101, 100, 1.0))
+            .unwrap();
+
+        assert_eq!(report.number_of_rounds, report.number_of_failed_rounds);
+        assert_eq!(1.0, report.quorum_failure_probability);
+        assert_eq!(None, report.certificate_latency_ms);
+    }
+
+    #[test]
+    fn quorum_is_never_reached_without_participation() {
+        let simulator = CommitteeSimulator::new(&stake_distribution(&[10, 20, 30])).unwrap();
+
+        let report = simulator
+            .simulate(&CommitteeSimulationParameters {
+                participation_rate: 0.0,
+                ..simulation_parameters(5, 100, 0.9)
+            })
+            .unwrap();
+
+        assert_eq!(1.0, report.quorum_failure_probability);
+        assert_eq!(0.0, report.mean_won_indexes);
+    }
+
+    #[test]
+    fn mean_won_indexes_matches_the_expected_lottery_outcome() {
+        let simulator = CommitteeSimulator::new(&stake_distribution(&[1; 100])).unwrap();
+        let parameters = CommitteeSimulationParameters {
+            number_of_rounds: 1000,
+            ..simulation_parameters(5, 200, 0.2)
+        };
+        // An index is won by at least one of the signers with probability 'phi_f'
+        let expected_mean_won_indexes = 200.0 * 0.2;
+
+        let report = simulator.simulate(&parameters).unwrap();
+
+        assert!(
+            (report.mean_won_indexes - expected_mean_won_indexes).abs() < 1.0,
+            "mean won indexes {} should be close to {expected_mean_won_indexes}",
+            report.mean_won_indexes
+        );
+    }
+
+    #[test]
+    fn simulation_is_deterministic_for_a_given_seed() {
+        let simulator = CommitteeSimulator::new(&stake_distribution(&[10, 20, 30, 40])).unwrap();
+        let parameters = CommitteeSimulationParameters {
+            participation_rate: 0.8,
+            ..simulation_parameters(20, 100, 0.3)
+        };
+
+        let report = simulator.simulate(&parameters).unwrap();
+
+        assert_eq!(report, simulator.simulate(&parameters).unwrap());
+        assert_ne!(
+            report,
+            simulator
+                .simulate(&CommitteeSimulationParameters {
+                    seed: 42,
+                    ..parameters
+                })
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn latency_distribution_is_ordered() {
+        let simulator = CommitteeSimulator::new(&stake_distribution(&[10, 20, 30, 40])).unwrap();
+
+        let latency = simulator
+            .simulate(&simulation_parameters(20, 100, 0.5))
+            .unwrap()
+            .certificate_latency_ms
+            .expect("some rounds should have reached the quorum");
+
+        assert!(latency.min <= latency.p50);
+        assert!(latency.p50 <= latency.p90);
+        assert!(latency.p90 <= latency.p99);
+        assert!(latency.p99 <= latency.max);
+        assert!(latency.min <= latency.mean && latency.mean <= latency.max);
+    }
+}
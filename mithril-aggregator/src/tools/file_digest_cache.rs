@@ -0,0 +1,257 @@
+use anyhow::Context;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+use slog_scope::warn;
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::BufReader,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+use tokio::task::JoinHandle;
+
+use mithril_common::StdResult;
+
+/// Size of the buffer used to read the files when computing their digest
+const READ_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Number of files whose digest is cached, the least recently served ones are evicted first
+const CACHE_CAPACITY: usize = 64;
+
+/// Length and modification date of a file, used to detect that a cached digest is outdated
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileFingerprint {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl FileFingerprint {
+    fn read(path: &Path) -> StdResult<Self> {
+        let metadata = std::fs::metadata(path)
+            .with_context(|| format!("Could not read metadata of file: '{}'", path.display()))?;
+
+        Ok(Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
+
+/// LRU cache of the SHA-256 digests of the files served by the aggregator.
+///
+/// The digests are computed by background tasks, either when a file is stored or the first time
+/// it's requested, so that serving a file never waits for the digest of its whole content. Only
+/// one computation runs at a time for a given file, and a digest is computed again if the length
+/// or the modification date of the file changed.
+pub struct FileDigestCache {
+    entries: Mutex<LruCache<PathBuf, (FileFingerprint, String)>>,
+    computations_in_progress: Mutex<HashSet<PathBuf>>,
+}
+
+impl Default for FileDigestCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileDigestCache {
+    /// FileDigestCache factory
+    pub fn new() -> Self {
+        Self::with_capacity(NonZeroUsize::new(CACHE_CAPACITY).unwrap())
+    }
+
+    /// FileDigestCache factory, caching the digests of at most `capacity` files
+    pub fn with_capacity(capacity: NonZeroUsize) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            computations_in_progress: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Get the cached base64 encoded SHA-256 digest of the given file, if it's up to date.
+    pub fn get(&self, path: &Path) -> Option<String> {
+        let fingerprint = FileFingerprint::read(path).ok()?;
+        match self.entries.lock().unwrap().get(path) {
+            Some((cached_fingerprint, digest)) if cached_fingerprint == &fingerprint => {
+                Some(digest.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Compute the digest of the given file in a background task and cache it.
+    ///
+    /// Nothing is done if the cached digest is up to date or if a computation is already in
+    /// progress for this file, otherwise the handle of the spawned task is returned.
+    pub fn compute_in_background(self: &Arc<Self>, path: &Path) -> Option<JoinHandle<()>> {
+        if self.get(path).is_some()
+            || !self
+                .computations_in_progress
+                .lock()
+                .unwrap()
+                .insert(path.to_path_buf())
+        {
+            return None;
+        }
+
+        let cache = self.clone();
+        let file_path = path.to_path_buf();
+        Some(tokio::task::spawn_blocking(move || {
+            if let Err(error) = cache.compute_and_cache(&file_path) {
+                warn!("FileDigestCache::compute_in_background::error"; "path" => ?file_path, "error" => ?error);
+            }
+            cache
+                .computations_in_progress
+                .lock()
+                .unwrap()
+                .remove(&file_path);
+        }))
+    }
+
+    fn compute_and_cache(&self, path: &Path) -> StdResult<()> {
+        // The fingerprint is read before the content so that a file modified during the
+        // computation is detected as outdated
+        let fingerprint = FileFingerprint::read(path)?;
+        let digest = Self::compute_digest(path)?;
+        self.entries
+            .lock()
+            .unwrap()
+            .put(path.to_path_buf(), (fingerprint, digest));
+
+        Ok(())
+    }
+
+    fn compute_digest(path: &Path) -> StdResult<String> {
+        let file = File::open(path)
+            .with_context(|| format!("Could not open file: '{}'", path.display()))?;
+        let mut reader = BufReader::with_capacity(READ_BUFFER_SIZE, file);
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut reader, &mut hasher)
+            .with_context(|| format!("Could not read file: '{}'", path.display()))?;
+
+        Ok(STANDARD.encode(hasher.finalize()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::TempDir;
+
+    use super::*;
+
+    // Base64 encoded SHA-256 of "content"
+    const CONTENT_DIGEST: &str = "7XACtDnprIRfIjV9giusFERzD722AW0+yUMil7nsn3M=";
+
+    async fn compute(cache: &Arc<FileDigestCache>, path: &Path) {
+        cache
+            .compute_in_background(path)
+            .expect("A computation should have been spawned")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn compute_the_base64_sha256_of_a_file() {
+        let dir = TempDir::create("file_digest_cache", "compute_the_base64_sha256_of_a_file");
+        let path = dir.join("file.tar.gz");
+        std::fs::write(&path, "content").unwrap();
+        let cache = Arc::new(FileDigestCache::new());
+        assert_eq!(None, cache.get(&path));
+
+        compute(&cache, &path).await;
+
+        assert_eq!(Some(CONTENT_DIGEST.to_string()), cache.get(&path));
+    }
+
+    #[tokio::test]
+    async fn do_not_compute_again_an_up_to_date_digest() {
+        let dir = TempDir::create(
+            "file_digest_cache",
+            "do_not_compute_again_an_up_to_date_digest",
+        );
+        let path = dir.join("file.tar.gz");
+        std::fs::write(&path, "content").unwrap();
+        let cache = Arc::new(FileDigestCache::new());
+        compute(&cache, &path).await;
+
+        assert!(cache.compute_in_background(&path).is_none());
+    }
+
+    #[tokio::test]
+    async fn share_a_computation_in_progress_for_the_same_file() {
+        let dir = TempDir::create(
+            "file_digest_cache",
+            "share_a_computation_in_progress_for_the_same_file",
+        );
+        let path = dir.join("file.tar.gz");
+        std::fs::write(&path, "content").unwrap();
+        let cache = Arc::new(FileDigestCache::new());
+
+        let computation = cache.compute_in_background(&path).unwrap();
+        assert!(cache.compute_in_background(&path).is_none());
+        computation.await.unwrap();
+
+        assert_eq!(Some(CONTENT_DIGEST.to_string()), cache.get(&path));
+        assert!(cache.computations_in_progress.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn the_digest_is_outdated_when_the_file_changed() {
+        let dir = TempDir::create(
+            "file_digest_cache",
+            "the_digest_is_outdated_when_the_file_changed",
+        );
+        let path = dir.join("file.tar.gz");
+        std::fs::write(&path, "content").unwrap();
+        let cache = Arc::new(FileDigestCache::new());
+        compute(&cache, &path).await;
+
+        std::fs::write(&path, "another content").unwrap();
+        assert_eq!(None, cache.get(&path));
+
+        compute(&cache, &path).await;
+        let digest = cache.get(&path).unwrap();
+        assert_ne!(CONTENT_DIGEST, digest);
+    }
+
+    #[tokio::test]
+    async fn evict_the_least_recently_used_digest_when_the_cache_is_full() {
+        let dir = TempDir::create(
+            "file_digest_cache",
+            "evict_the_least_recently_used_digest_when_the_cache_is_full",
+        );
+        let cache = Arc::new(FileDigestCache::with_capacity(
+            NonZeroUsize::new(2).unwrap(),
+        ));
+        for file_name in ["1.tar.gz", "2.tar.gz", "3.tar.gz"] {
+            let path = dir.join(file_name);
+            std::fs::write(&path, "content").unwrap();
+            compute(&cache, &path).await;
+        }
+
+        let entries = cache.entries.lock().unwrap();
+        assert_eq!(2, entries.len());
+        assert!(!entries.contains(&dir.join("1.tar.gz")));
+    }
+
+    #[tokio::test]
+    async fn a_failed_computation_does_not_block_the_next_ones() {
+        let dir = TempDir::create(
+            "file_digest_cache",
+            "a_failed_computation_does_not_block_the_next_ones",
+        );
+        let path = dir.join("not_found.tar.gz");
+        let cache = Arc::new(FileDigestCache::new());
+
+        compute(&cache, &path).await;
+        assert_eq!(None, cache.get(&path));
+
+        std::fs::write(&path, "content").unwrap();
+        compute(&cache, &path).await;
+        assert_eq!(Some(CONTENT_DIGEST.to_string()), cache.get(&path));
+    }
+}
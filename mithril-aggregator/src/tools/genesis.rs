@@ -5,9 +5,10 @@ use mithril_common::{
     certificate_chain::{CertificateGenesisProducer, CertificateVerifier},
     crypto_helper::{
         ProtocolAggregateVerificationKey, ProtocolGenesisSignature, ProtocolGenesisSigner,
-        ProtocolGenesisVerifier,
+        ProtocolGenesisVerifier, SigningProvider,
     },
-    entities::{ProtocolParameters, TimePoint},
+    entities::{Certificate, ProtocolParameters, TimePoint},
+    era::{EraChecker, SupportedEra},
     protocol::SignerBuilder,
     CardanoNetwork, StdResult, TimePointProvider,
 };
@@ -36,6 +37,9 @@ pub struct GenesisToolsDependency {
 
     /// Certificate store.
     pub certificate_repository: Arc<CertificateRepository>,
+
+    /// Era checker service.
+    pub era_checker: Arc<EraChecker>,
 }
 
 pub struct GenesisTools {
@@ -46,9 +50,11 @@ pub struct GenesisTools {
     genesis_verifier: Arc<ProtocolGenesisVerifier>,
     certificate_verifier: Arc<dyn CertificateVerifier>,
     certificate_repository: Arc<CertificateRepository>,
+    era_checker: Arc<EraChecker>,
 }
 
 impl GenesisTools {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         protocol_parameters: ProtocolParameters,
         network: CardanoNetwork,
@@ -57,6 +63,7 @@ impl GenesisTools {
         genesis_verifier: Arc<ProtocolGenesisVerifier>,
         certificate_verifier: Arc<dyn CertificateVerifier>,
         certificate_repository: Arc<CertificateRepository>,
+        era_checker: Arc<EraChecker>,
     ) -> Self {
         Self {
             protocol_parameters,
@@ -66,6 +73,7 @@ impl GenesisTools {
             genesis_verifier,
             certificate_verifier,
             certificate_repository,
+            era_checker,
         }
     }
 
@@ -112,6 +120,7 @@ impl GenesisTools {
             genesis_verifier,
             certificate_verifier,
             certificate_repository,
+            dependencies.era_checker,
         ))
     }
 
@@ -149,13 +158,15 @@ impl GenesisTools {
             .await
     }
 
-    /// Sign the genesis certificate
-    pub async fn sign_genesis_certificate(
-        to_sign_payload_path: &Path,
-        target_signed_payload_path: &Path,
-        genesis_secret_key_path: &Path,
-    ) -> StdResult<()> {
-        let mut genesis_secret_key_file = File::open(genesis_secret_key_path).unwrap();
+    /// Read the genesis signer from a file holding a genesis secret key
+    pub fn read_genesis_signer(genesis_secret_key_path: &Path) -> StdResult<ProtocolGenesisSigner> {
+        let mut genesis_secret_key_file =
+            File::open(genesis_secret_key_path).with_context(|| {
+                format!(
+                    "Could not open genesis secret key file: '{}'",
+                    genesis_secret_key_path.display()
+                )
+            })?;
         let mut genesis_secret_key_serialized = String::new();
         genesis_secret_key_file.read_to_string(&mut genesis_secret_key_serialized)?;
 
@@ -163,13 +174,24 @@ impl GenesisTools {
             .trim()
             .try_into()
             .with_context(|| "Genesis secret key decode error")?;
-        let genesis_signer = ProtocolGenesisSigner::from_secret_key(genesis_secret_key);
 
+        Ok(ProtocolGenesisSigner::from_secret_key(genesis_secret_key))
+    }
+
+    /// Sign the genesis certificate
+    pub async fn sign_genesis_certificate(
+        to_sign_payload_path: &Path,
+        target_signed_payload_path: &Path,
+        signing_provider: &dyn SigningProvider,
+    ) -> StdResult<()> {
         let mut to_sign_payload_file = File::open(to_sign_payload_path).unwrap();
         let mut to_sign_payload_buffer = Vec::new();
         to_sign_payload_file.read_to_end(&mut to_sign_payload_buffer)?;
 
-        let genesis_signature = genesis_signer.sign(&to_sign_payload_buffer);
+        let genesis_signature = signing_provider
+            .sign(&to_sign_payload_buffer)
+            .await
+            .with_context(|| "Genesis signing provider can not sign the payload")?;
         let signed_payload = genesis_signature.to_bytes();
 
         let mut target_signed_payload_file = File::create(target_signed_payload_path)?;
@@ -178,6 +200,67 @@ impl GenesisTools {
         Ok(())
     }
 
+    /// Rotate the genesis key
+    ///
+    /// A genesis key rotation certificate, cross-signed with the previous and the next genesis
+    /// keys, is chained to the latest certificate. The previous genesis key must be the one
+    /// configured for the aggregator.
+    ///
+    /// Only available from the Pythagoras era, as the previous versions of the nodes can not
+    /// deserialize the protocol message of the rotation certificate.
+    ///
+    /// The rotation does not recover from a compromise of the previous genesis key: whoever holds
+    /// it can rotate to a key of their own as well. The next genesis verification key must be
+    /// distributed out of band, like the previous one, so that it can be checked by the clients.
+    pub async fn rotate_genesis_key(
+        &self,
+        previous_genesis_signer: &ProtocolGenesisSigner,
+        next_genesis_signer: &ProtocolGenesisSigner,
+    ) -> StdResult<Certificate> {
+        let current_era = self.era_checker.current_era();
+        if current_era < SupportedEra::Pythagoras {
+            return Err(anyhow!(
+                "Genesis tool can not rotate the genesis key in the '{current_era}' era, it is only available from the '{}' era",
+                SupportedEra::Pythagoras
+            ));
+        }
+        let latest_certificate = self
+            .certificate_repository
+            .get_latest_certificates::<Certificate>(1)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                anyhow!("Genesis tool can not rotate the genesis key: no certificate to chain to")
+            })?;
+        let rotation_certificate =
+            CertificateGenesisProducer::create_genesis_key_rotation_certificate(
+                &latest_certificate,
+                previous_genesis_signer,
+                next_genesis_signer,
+            )?;
+        self.certificate_verifier
+            .verify_genesis_certificate(
+                &rotation_certificate,
+                &self.genesis_verifier.to_verification_key(),
+            )
+            .await
+            .with_context(|| {
+                "Genesis tool can not verify the genesis key rotation certificate with the configured genesis verification key"
+            })?;
+        self.certificate_repository
+            .create_certificate(rotation_certificate.clone())
+            .await
+            .with_context(|| {
+                format!(
+                    "Genesis tool can not create genesis key rotation certificate: '{}'",
+                    rotation_certificate.hash
+                )
+            })?;
+
+        Ok(rotation_certificate)
+    }
+
     async fn create_and_save_genesis_certificate(
         &self,
         genesis_signature: ProtocolGenesisSignature,
@@ -215,8 +298,11 @@ mod tests {
     use mithril_common::{
         certificate_chain::MithrilCertificateVerifier,
         crypto_helper::{ProtocolClerk, ProtocolGenesisSigner},
+        entities::Epoch,
         test_utils::{fake_data, MithrilFixtureBuilder, TempDir},
     };
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
     use std::path::PathBuf;
 
     use super::*;
@@ -239,6 +325,18 @@ mod tests {
         Arc<CertificateRepository>,
         Arc<ProtocolGenesisVerifier>,
         Arc<dyn CertificateVerifier>,
+    ) {
+        build_tools_in_era(genesis_signer, SupportedEra::Pythagoras)
+    }
+
+    fn build_tools_in_era(
+        genesis_signer: &ProtocolGenesisSigner,
+        era: SupportedEra,
+    ) -> (
+        GenesisTools,
+        Arc<CertificateRepository>,
+        Arc<ProtocolGenesisVerifier>,
+        Arc<dyn CertificateVerifier>,
     ) {
         let connection = main_db_connection().unwrap();
         let certificate_store = Arc::new(CertificateRepository::new(Arc::new(connection)));
@@ -256,6 +354,7 @@ mod tests {
             genesis_verifier.clone(),
             certificate_verifier.clone(),
             certificate_store.clone(),
+            Arc::new(EraChecker::new(era, Epoch(1))),
         );
 
         (
@@ -282,10 +381,12 @@ mod tests {
         genesis_tools
             .export_payload_to_sign(&payload_path)
             .expect("export_payload_to_sign should not fail");
+        let signing_provider = GenesisTools::read_genesis_signer(&genesis_secret_key_path)
+            .expect("reading the genesis signer should not fail");
         GenesisTools::sign_genesis_certificate(
             &payload_path,
             &signed_payload_path,
-            &genesis_secret_key_path,
+            &signing_provider,
         )
         .await
         .expect("sign_genesis_certificate should not fail");
@@ -332,4 +433,119 @@ mod tests {
                 "verify_genesis_certificate should successfully validate the genesis certificate",
             );
     }
+
+    #[tokio::test]
+    async fn rotate_genesis_key_chains_a_cross_signed_certificate_to_the_latest_certificate() {
+        let genesis_signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let next_genesis_signer =
+            ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed([1u8; 32]));
+        let (genesis_tools, certificate_store, genesis_verifier, certificate_verifier) =
+            build_tools(&genesis_signer);
+        genesis_tools
+            .bootstrap_test_genesis_certificate(
+                ProtocolGenesisSigner::create_deterministic_genesis_signer(),
+            )
+            .await
+            .unwrap();
+        let genesis_certificate = certificate_store
+            .get_latest_certificates::<Certificate>(1)
+            .await
+            .unwrap()
+            .remove(0);
+
+        let rotation_certificate = genesis_tools
+            .rotate_genesis_key(&genesis_signer, &next_genesis_signer)
+            .await
+            .expect("rotating the genesis key should not fail");
+
+        let last_certificates: Vec<Certificate> =
+            certificate_store.get_latest_certificates(10).await.unwrap();
+        assert_eq!(
+            vec![rotation_certificate.clone(), genesis_certificate.clone()],
+            last_certificates
+        );
+        assert_eq!(genesis_certificate.hash, rotation_certificate.previous_hash);
+        for genesis_verification_key in [
+            genesis_verifier.to_verification_key(),
+            next_genesis_signer
+                .create_genesis_verifier()
+                .to_verification_key(),
+        ] {
+            certificate_verifier
+                .verify_certificate(&rotation_certificate, &genesis_verification_key)
+                .await
+                .expect("the rotation certificate should be valid with both genesis keys");
+        }
+    }
+
+    #[tokio::test]
+    async fn rotate_genesis_key_fails_with_a_previous_genesis_key_that_is_not_configured() {
+        let genesis_signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let another_genesis_signer =
+            ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed([2u8; 32]));
+        let (genesis_tools, certificate_store, _, _) = build_tools(&genesis_signer);
+        genesis_tools
+            .bootstrap_test_genesis_certificate(
+                ProtocolGenesisSigner::create_deterministic_genesis_signer(),
+            )
+            .await
+            .unwrap();
+
+        genesis_tools
+            .rotate_genesis_key(
+                &another_genesis_signer,
+                &ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed(
+                    [1u8; 32],
+                )),
+            )
+            .await
+            .expect_err("rotating with a genesis key that is not configured should fail");
+
+        let last_certificates: Vec<Certificate> =
+            certificate_store.get_latest_certificates(10).await.unwrap();
+        assert_eq!(1, last_certificates.len());
+    }
+
+    #[tokio::test]
+    async fn rotate_genesis_key_fails_before_the_pythagoras_era() {
+        let genesis_signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let (genesis_tools, certificate_store, _, _) =
+            build_tools_in_era(&genesis_signer, SupportedEra::Thales);
+        genesis_tools
+            .bootstrap_test_genesis_certificate(
+                ProtocolGenesisSigner::create_deterministic_genesis_signer(),
+            )
+            .await
+            .unwrap();
+
+        genesis_tools
+            .rotate_genesis_key(
+                &genesis_signer,
+                &ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed(
+                    [1u8; 32],
+                )),
+            )
+            .await
+            .expect_err("rotating the genesis key before the Pythagoras era should fail");
+
+        let last_certificates: Vec<Certificate> =
+            certificate_store.get_latest_certificates(10).await.unwrap();
+        assert_eq!(1, last_certificates.len());
+    }
+
+    #[tokio::test]
+    async fn rotate_genesis_key_fails_without_certificate() {
+        let genesis_signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let (genesis_tools, _, _, _) = build_tools(&genesis_signer);
+
+        genesis_tools
+            .rotate_genesis_key(
+                &genesis_signer,
+                &ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed(
+                    [1u8; 32],
+                )),
+            )
+            .await
+            .expect_err("rotating the genesis key without certificate should fail");
+    }
 }
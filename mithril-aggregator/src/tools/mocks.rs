@@ -22,6 +22,11 @@ mock! {
             &self,
         ) -> Result<Option<StakeDistribution>, ChainObserverError>;
 
+        async fn get_stake_distribution(
+            &self,
+            epoch: Epoch,
+        ) -> Result<Option<StakeDistribution>, ChainObserverError>;
+
         async fn get_current_kes_period(
             &self,
             opcert: &OpCert,
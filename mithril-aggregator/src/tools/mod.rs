@@ -1,20 +1,46 @@
+mod bandwidth_limiter;
 mod certificates_hash_migrator;
+mod certificates_importer;
+mod committee_simulator;
 mod digest_helpers;
 mod era;
+mod file_digest_cache;
 mod genesis;
 #[cfg(test)]
 pub mod mocks;
 mod remote_file_uploader;
 mod signer_importer;
+mod signer_registrations;
+mod snapshot_manifest_cache;
+mod stake_distribution_backfiller;
 
+pub use bandwidth_limiter::{BandwidthLimiter, BlockingThrottledReader, ThrottledReader};
 pub use certificates_hash_migrator::CertificatesHashMigrator;
+pub use certificates_importer::{
+    AggregatorCertificatesRetriever, CertificatesImportReport, CertificatesImporter,
+    CertificatesImporterRetriever,
+};
+pub use committee_simulator::{
+    CertificateLatencyDistribution, CommitteeSimulationParameters, CommitteeSimulationReport,
+    CommitteeSimulator,
+};
 pub use digest_helpers::extract_digest_from_path;
 pub use era::EraTools;
+pub use file_digest_cache::FileDigestCache;
 pub use genesis::{GenesisTools, GenesisToolsDependency};
 pub use remote_file_uploader::{GcpFileUploader, RemoteFileUploader};
 pub use signer_importer::{
     CExplorerSignerRetriever, SignersImporter, SignersImporterPersister, SignersImporterRetriever,
 };
+pub use signer_registrations::{
+    EpochSignerRegistrations, SignerRegistrationsExport, SignerRegistrationsTools,
+};
+pub use snapshot_manifest_cache::SnapshotManifestCache;
+pub use stake_distribution_backfiller::{
+    ChainObserverStakeDistributionRetriever, DbSyncStakeDistributionRetriever,
+    JsonFileStakeDistributionRetriever, StakeDistributionBackfillReport,
+    StakeDistributionBackfillRetriever, StakeDistributionBackfiller,
+};
 
 #[cfg(test)]
 pub use remote_file_uploader::MockRemoteFileUploader;
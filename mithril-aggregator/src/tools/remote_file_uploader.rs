@@ -6,9 +6,12 @@ use cloud_storage::{
 };
 use mithril_common::StdResult;
 use slog_scope::info;
-use std::{env, path::Path};
+use std::{env, path::Path, sync::Arc};
+use tokio::io::AsyncRead;
 use tokio_util::{codec::BytesCodec, codec::FramedRead};
 
+use crate::tools::{BandwidthLimiter, ThrottledReader};
+
 #[cfg(test)]
 use mockall::automock;
 
@@ -23,12 +26,22 @@ pub trait RemoteFileUploader: Sync + Send {
 /// GcpFileUploader represents a Google Cloud Platform file uploader interactor
 pub struct GcpFileUploader {
     bucket: String,
+    bandwidth_limiter: Option<Arc<BandwidthLimiter>>,
 }
 
 impl GcpFileUploader {
     /// GcpFileUploader factory
     pub fn new(bucket: String) -> Self {
-        Self { bucket }
+        Self {
+            bucket,
+            bandwidth_limiter: None,
+        }
+    }
+
+    /// Limit the bandwidth used by the uploads with the given [BandwidthLimiter]
+    pub fn with_bandwidth_limiter(mut self, bandwidth_limiter: Arc<BandwidthLimiter>) -> Self {
+        self.bandwidth_limiter = Some(bandwidth_limiter);
+        self
     }
 }
 
@@ -46,7 +59,13 @@ impl RemoteFileUploader for GcpFileUploader {
         info!("uploading {}", filename);
         let client = Client::default();
         let file = tokio::fs::File::open(filepath).await.unwrap();
-        let stream = FramedRead::new(file, BytesCodec::new());
+        let reader: Box<dyn AsyncRead + Send + Sync + Unpin> = match &self.bandwidth_limiter {
+            Some(bandwidth_limiter) => {
+                Box::new(ThrottledReader::new(file, bandwidth_limiter.clone()))
+            }
+            None => Box::new(file),
+        };
+        let stream = FramedRead::new(reader, BytesCodec::new());
         client
             .object()
             .create_streamed(
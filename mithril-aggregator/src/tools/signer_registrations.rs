@@ -0,0 +1,275 @@
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use mithril_common::{
+    crypto_helper::{EraMarkersSigner, EraMarkersVerifier, EraMarkersVerifierSignature},
+    entities::{Epoch, SignerWithStake},
+    StdResult,
+};
+
+use crate::VerificationKeyStorer;
+
+/// Signer registrations recorded by an aggregator for an epoch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochSignerRegistrations {
+    /// Epoch at which the signers are registered
+    pub epoch: Epoch,
+
+    /// Registered signers, with their keys, operational certificates and stakes
+    pub signers: Vec<SignerWithStake>,
+}
+
+/// Signed export of the signer registrations of an aggregator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerRegistrationsExport {
+    /// Current epoch of the aggregator when the registrations were exported
+    pub exported_at_epoch: Epoch,
+
+    /// Registrations for each exported epoch
+    pub registrations: Vec<EpochSignerRegistrations>,
+
+    /// Signature of the exported registrations
+    pub signature: EraMarkersVerifierSignature,
+}
+
+impl SignerRegistrationsExport {
+    fn message_to_bytes(
+        exported_at_epoch: Epoch,
+        registrations: &[EpochSignerRegistrations],
+    ) -> StdResult<Vec<u8>> {
+        serde_json::to_vec(&(exported_at_epoch, registrations))
+            .with_context(|| "signer registrations export could not be serialized")
+    }
+
+    /// Verify the signature of the export
+    pub fn verify_signature(&self, verifier: &EraMarkersVerifier) -> StdResult<()> {
+        verifier
+            .verify(
+                &Self::message_to_bytes(self.exported_at_epoch, &self.registrations)?,
+                &self.signature,
+            )
+            .with_context(|| "signer registrations export could not verify signature")
+    }
+}
+
+/// Tools to export and import the signer registrations of an aggregator, used to migrate them to
+/// another aggregator without losing the registrations of the ongoing epochs.
+pub struct SignerRegistrationsTools {
+    verification_key_store: Arc<dyn VerificationKeyStorer>,
+}
+
+impl SignerRegistrationsTools {
+    /// `SignerRegistrationsTools` factory
+    pub fn new(verification_key_store: Arc<dyn VerificationKeyStorer>) -> Self {
+        Self {
+            verification_key_store,
+        }
+    }
+
+    /// Export and sign the registrations needed to sign at the given current epoch: the ones of
+    /// the signers of the current and next epochs, and the ones recorded for the upcoming epoch.
+    pub async fn export(
+        &self,
+        current_epoch: Epoch,
+        signer: &EraMarkersSigner,
+    ) -> StdResult<SignerRegistrationsExport> {
+        let epochs = [
+            current_epoch.offset_to_signer_retrieval_epoch()?,
+            current_epoch.offset_to_next_signer_retrieval_epoch(),
+            current_epoch.offset_to_recording_epoch(),
+        ];
+
+        let mut registrations = vec![];
+        for epoch in epochs {
+            let mut signers = self
+                .verification_key_store
+                .get_signers(epoch)
+                .await
+                .with_context(|| format!("could not get the signers of epoch {epoch}"))?
+                .unwrap_or_default();
+            if signers.is_empty() {
+                continue;
+            }
+            signers.sort();
+            registrations.push(EpochSignerRegistrations { epoch, signers });
+        }
+
+        if registrations.is_empty() {
+            return Err(anyhow!(
+                "no signer registrations to export at epoch {current_epoch}"
+            ));
+        }
+
+        let signature = signer.sign(&SignerRegistrationsExport::message_to_bytes(
+            current_epoch,
+            &registrations,
+        )?);
+
+        Ok(SignerRegistrationsExport {
+            exported_at_epoch: current_epoch,
+            registrations,
+            signature,
+        })
+    }
+
+    /// Verify the signature of an export and save all its registrations, the registrations that
+    /// already exist for the same signers and epochs are replaced.
+    ///
+    /// Returns the number of imported registrations.
+    pub async fn import(
+        &self,
+        export: SignerRegistrationsExport,
+        verifier: &EraMarkersVerifier,
+    ) -> StdResult<usize> {
+        export.verify_signature(verifier)?;
+
+        let mut imported_registrations = 0;
+        for epoch_registrations in export.registrations {
+            let epoch = epoch_registrations.epoch;
+            for signer in epoch_registrations.signers {
+                let party_id = signer.party_id.clone();
+                self.verification_key_store
+                    .save_verification_key(epoch, signer)
+                    .await
+                    .with_context(|| {
+                        format!("could not import registration of signer '{party_id}' for epoch {epoch}")
+                    })?;
+                imported_registrations += 1;
+            }
+        }
+
+        Ok(imported_registrations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::{entities::PartyId, test_utils::MithrilFixtureBuilder};
+    use mithril_persistence::store::adapter::MemoryAdapter;
+    use std::collections::HashMap;
+
+    use crate::VerificationKeyStore;
+
+    use super::*;
+
+    fn empty_store() -> Arc<VerificationKeyStore> {
+        Arc::new(VerificationKeyStore::new(Box::new(
+            MemoryAdapter::<Epoch, HashMap<PartyId, SignerWithStake>>::new(None).unwrap(),
+        )))
+    }
+
+    async fn store_with_signers(
+        epochs: &[Epoch],
+        signers: &[SignerWithStake],
+    ) -> Arc<VerificationKeyStore> {
+        let store = empty_store();
+        for epoch in epochs {
+            for signer in signers {
+                store
+                    .save_verification_key(*epoch, signer.clone())
+                    .await
+                    .unwrap();
+            }
+        }
+
+        store
+    }
+
+    #[tokio::test]
+    async fn export_then_import_preserves_the_registrations_of_each_epoch() {
+        let signers = MithrilFixtureBuilder::default()
+            .with_signers(3)
+            .build()
+            .signers_with_stake();
+        let source_store = store_with_signers(&[Epoch(4), Epoch(5), Epoch(6)], &signers).await;
+        let target_store = empty_store();
+        let signer = EraMarkersSigner::create_deterministic_signer();
+
+        let export = SignerRegistrationsTools::new(source_store.clone())
+            .export(Epoch(5), &signer)
+            .await
+            .unwrap();
+        let imported = SignerRegistrationsTools::new(target_store.clone())
+            .import(export, &signer.create_verifier())
+            .await
+            .unwrap();
+
+        assert_eq!(9, imported);
+        for epoch in [Epoch(4), Epoch(5), Epoch(6)] {
+            let mut expected_signers = source_store.get_signers(epoch).await.unwrap().unwrap();
+            expected_signers.sort();
+            let mut imported_signers = target_store.get_signers(epoch).await.unwrap().unwrap();
+            imported_signers.sort();
+            // Signers equality only compares their party ids
+            assert_eq!(
+                serde_json::to_value(expected_signers).unwrap(),
+                serde_json::to_value(imported_signers).unwrap(),
+                "epoch {epoch}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn export_skips_epochs_without_registrations() {
+        let signers = MithrilFixtureBuilder::default()
+            .with_signers(2)
+            .build()
+            .signers_with_stake();
+        let store = store_with_signers(&[Epoch(4), Epoch(5)], &signers).await;
+
+        let export = SignerRegistrationsTools::new(store)
+            .export(Epoch(5), &EraMarkersSigner::create_deterministic_signer())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![Epoch(4), Epoch(5)],
+            export
+                .registrations
+                .iter()
+                .map(|r| r.epoch)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn export_fails_without_any_registration() {
+        SignerRegistrationsTools::new(empty_store())
+            .export(Epoch(5), &EraMarkersSigner::create_deterministic_signer())
+            .await
+            .expect_err("export should fail without any registration");
+    }
+
+    #[tokio::test]
+    async fn import_fails_if_the_export_is_tampered_or_signed_with_another_key() {
+        let signers = MithrilFixtureBuilder::default()
+            .with_signers(2)
+            .build()
+            .signers_with_stake();
+        let signer = EraMarkersSigner::create_deterministic_signer();
+        let export = SignerRegistrationsTools::new(store_with_signers(&[Epoch(5)], &signers).await)
+            .export(Epoch(5), &signer)
+            .await
+            .unwrap();
+        let target_store = empty_store();
+        let tools = SignerRegistrationsTools::new(target_store.clone());
+
+        let mut tampered_export = export.clone();
+        tampered_export.registrations[0].signers[0].stake += 1;
+        tools
+            .import(tampered_export, &signer.create_verifier())
+            .await
+            .expect_err("import of a tampered export should fail");
+
+        tools
+            .import(
+                export,
+                &EraMarkersSigner::create_non_deterministic_signer().create_verifier(),
+            )
+            .await
+            .expect_err("import of an export signed with another key should fail");
+
+        assert_eq!(None, target_store.get_signers(Epoch(5)).await.unwrap());
+    }
+}
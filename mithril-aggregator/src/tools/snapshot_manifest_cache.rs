@@ -0,0 +1,165 @@
+use anyhow::Context;
+use lru::LruCache;
+use std::{
+    collections::HashSet,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use mithril_common::{
+    entities::{SnapshotManifest, SnapshotObjectHash},
+    StdResult,
+};
+
+/// Number of manifests whose objects are cached, the least recently used ones are evicted first
+const CACHE_CAPACITY: usize = 8;
+
+/// LRU cache of the objects referenced by the snapshot manifests stored by the aggregator.
+///
+/// A manifest is never modified once written, so it's only read and parsed the first time the
+/// objects of its snapshot are requested instead of once per downloaded object.
+pub struct SnapshotManifestCache {
+    manifests_directory: PathBuf,
+    entries: Mutex<LruCache<String, Arc<HashSet<SnapshotObjectHash>>>>,
+}
+
+impl SnapshotManifestCache {
+    /// SnapshotManifestCache factory
+    pub fn new(manifests_directory: &Path) -> Self {
+        Self::with_capacity(
+            manifests_directory,
+            NonZeroUsize::new(CACHE_CAPACITY).unwrap(),
+        )
+    }
+
+    /// SnapshotManifestCache factory, caching the objects of at most `capacity` manifests
+    pub fn with_capacity(manifests_directory: &Path, capacity: NonZeroUsize) -> Self {
+        Self {
+            manifests_directory: manifests_directory.to_path_buf(),
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Check if the object with the given hash is referenced by the manifest of the snapshot with
+    /// the given digest.
+    pub async fn is_object_of_snapshot(&self, digest: &str, object_hash: &str) -> StdResult<bool> {
+        let object_hashes = self.get_object_hashes(digest).await?;
+
+        Ok(object_hashes.contains(object_hash))
+    }
+
+    async fn get_object_hashes(&self, digest: &str) -> StdResult<Arc<HashSet<SnapshotObjectHash>>> {
+        if let Some(object_hashes) = self.entries.lock().unwrap().get(digest) {
+            return Ok(object_hashes.clone());
+        }
+
+        let manifest_path = self
+            .manifests_directory
+            .join(SnapshotManifest::file_name(digest));
+        let content = tokio::fs::read(&manifest_path)
+            .await
+            .with_context(|| format!("Could not read manifest: '{}'", manifest_path.display()))?;
+        let manifest: SnapshotManifest = serde_json::from_slice(&content)
+            .with_context(|| format!("Could not parse manifest: '{}'", manifest_path.display()))?;
+        let object_hashes = Arc::new(
+            manifest
+                .entries
+                .into_iter()
+                .map(|entry| entry.object_hash)
+                .collect::<HashSet<_>>(),
+        );
+        self.entries
+            .lock()
+            .unwrap()
+            .put(digest.to_string(), object_hashes.clone());
+
+        Ok(object_hashes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::SnapshotManifestEntry;
+    use mithril_common::test_utils::TempDir;
+
+    use super::*;
+
+    fn write_manifest(manifests_directory: &Path, digest: &str, object_hashes: &[&str]) {
+        let manifest = SnapshotManifest::new(
+            digest.to_string(),
+            "http://whatever/snapshot_objects".to_string(),
+            object_hashes
+                .iter()
+                .enumerate()
+                .map(|(index, object_hash)| SnapshotManifestEntry {
+                    path: format!("immutable/{index:05}.chunk"),
+                    object_hash: object_hash.to_string(),
+                    size: 1,
+                })
+                .collect(),
+        );
+        std::fs::write(
+            manifests_directory.join(SnapshotManifest::file_name(digest)),
+            serde_json::to_vec(&manifest).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn only_the_objects_of_the_manifest_belong_to_the_snapshot() {
+        let dir = TempDir::create(
+            "snapshot_manifest_cache",
+            "only_the_objects_of_the_manifest_belong_to_the_snapshot",
+        );
+        write_manifest(&dir, "0123", &["object-1", "object-2"]);
+        write_manifest(&dir, "4567", &["object-3"]);
+        let cache = SnapshotManifestCache::new(&dir);
+
+        assert!(cache
+            .is_object_of_snapshot("0123", "object-1")
+            .await
+            .unwrap());
+        assert!(cache
+            .is_object_of_snapshot("0123", "object-2")
+            .await
+            .unwrap());
+        assert!(!cache
+            .is_object_of_snapshot("0123", "object-3")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn the_manifest_is_read_once() {
+        let dir = TempDir::create("snapshot_manifest_cache", "the_manifest_is_read_once");
+        write_manifest(&dir, "0123", &["object-1"]);
+        let cache = SnapshotManifestCache::new(&dir);
+        cache
+            .is_object_of_snapshot("0123", "object-1")
+            .await
+            .unwrap();
+
+        std::fs::remove_file(dir.join(SnapshotManifest::file_name("0123"))).unwrap();
+
+        assert!(cache
+            .is_object_of_snapshot("0123", "object-1")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn fail_if_the_manifest_does_not_exist() {
+        let dir = TempDir::create(
+            "snapshot_manifest_cache",
+            "fail_if_the_manifest_does_not_exist",
+        );
+        let cache = SnapshotManifestCache::new(&dir);
+
+        cache
+            .is_object_of_snapshot("0123", "object-1")
+            .await
+            .expect_err("Reading a missing manifest should fail");
+        assert!(cache.entries.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,417 @@
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use serde::Serialize;
+use slog_scope::{info, warn};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::process::Command;
+
+use mithril_common::chain_observer::ChainObserver;
+use mithril_common::entities::{Epoch, StakeDistribution};
+use mithril_common::StdResult;
+use mithril_persistence::store::StakeStorer;
+
+#[cfg(test)]
+use mockall::automock;
+
+/// Report of a [StakeDistributionBackfiller] run
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct StakeDistributionBackfillReport {
+    /// Epochs without a stake distribution in the store
+    pub missing_epochs: Vec<Epoch>,
+
+    /// Missing epochs which stake distribution was retrieved and saved in the store
+    pub backfilled_epochs: Vec<Epoch>,
+
+    /// Missing epochs which stake distribution could not be retrieved
+    pub unavailable_epochs: Vec<Epoch>,
+}
+
+/// Tool that detects the epochs without a stake distribution in the stake store, i.e. when the
+/// aggregator was down across an epoch boundary, and repairs the store with the stake
+/// distributions retrieved from a [StakeDistributionBackfillRetriever].
+pub struct StakeDistributionBackfiller {
+    stake_storer: Arc<dyn StakeStorer>,
+    retriever: Arc<dyn StakeDistributionBackfillRetriever>,
+}
+
+impl StakeDistributionBackfiller {
+    /// [StakeDistributionBackfiller] factory
+    pub fn new(
+        stake_storer: Arc<dyn StakeStorer>,
+        retriever: Arc<dyn StakeDistributionBackfillRetriever>,
+    ) -> Self {
+        Self {
+            stake_storer,
+            retriever,
+        }
+    }
+
+    /// List the epochs of the given range (both included) without a stake distribution
+    pub async fn find_missing_epochs(
+        &self,
+        from_epoch: Epoch,
+        to_epoch: Epoch,
+    ) -> StdResult<Vec<Epoch>> {
+        if from_epoch > to_epoch {
+            return Err(anyhow!(
+                "Invalid epoch range: from epoch {from_epoch} is after to epoch {to_epoch}"
+            ));
+        }
+
+        let mut missing_epochs = vec![];
+        for epoch in (*from_epoch..=*to_epoch).map(Epoch) {
+            if self.stake_storer.get_stakes(epoch).await?.is_none() {
+                missing_epochs.push(epoch);
+            }
+        }
+
+        Ok(missing_epochs)
+    }
+
+    /// Retrieve and save the stake distribution of the missing epochs of the given range (both
+    /// included), nothing is saved if `dry_run` is set.
+    pub async fn run(
+        &self,
+        from_epoch: Epoch,
+        to_epoch: Epoch,
+        dry_run: bool,
+    ) -> StdResult<StakeDistributionBackfillReport> {
+        info!("🔧 Stake Distribution Backfiller: starting"; "from_epoch" => ?from_epoch, "to_epoch" => ?to_epoch, "dry_run" => dry_run);
+        let missing_epochs = self.find_missing_epochs(from_epoch, to_epoch).await?;
+        let mut report = StakeDistributionBackfillReport {
+            missing_epochs: missing_epochs.clone(),
+            ..StakeDistributionBackfillReport::default()
+        };
+
+        for epoch in missing_epochs {
+            match self.retriever.retrieve(epoch).await.with_context(|| {
+                format!("Failed to retrieve the stake distribution of epoch {epoch}")
+            })? {
+                Some(stake_distribution) if !stake_distribution.is_empty() => {
+                    if !dry_run {
+                        self.stake_storer
+                            .save_stakes(epoch, stake_distribution)
+                            .await
+                            .with_context(|| {
+                                format!("Failed to save the stake distribution of epoch {epoch}")
+                            })?;
+                    }
+                    report.backfilled_epochs.push(epoch);
+                }
+                _ => {
+                    warn!("🔧 Stake Distribution Backfiller: no stake distribution available"; "epoch" => ?epoch);
+                    report.unavailable_epochs.push(epoch);
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Trait that define how a [StakeDistributionBackfiller] retrieve the historical stake
+/// distributions.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait StakeDistributionBackfillRetriever: Sync + Send {
+    /// Retrieve the stake distribution of the given epoch, if available.
+    async fn retrieve(&self, epoch: Epoch) -> StdResult<Option<StakeDistribution>>;
+}
+
+/// A [StakeDistributionBackfillRetriever] using the chain observer of the aggregator.
+///
+/// The ledger state of a Cardano node only keeps the stake snapshots of the current epoch and of
+/// the two previous epochs, so only these epochs can be retrieved.
+pub struct ChainObserverStakeDistributionRetriever {
+    chain_observer: Arc<dyn ChainObserver>,
+}
+
+impl ChainObserverStakeDistributionRetriever {
+    /// [ChainObserverStakeDistributionRetriever] factory
+    pub fn new(chain_observer: Arc<dyn ChainObserver>) -> Self {
+        Self { chain_observer }
+    }
+}
+
+#[async_trait]
+impl StakeDistributionBackfillRetriever for ChainObserverStakeDistributionRetriever {
+    async fn retrieve(&self, epoch: Epoch) -> StdResult<Option<StakeDistribution>> {
+        Ok(self.chain_observer.get_stake_distribution(epoch).await?)
+    }
+}
+
+/// A [StakeDistributionBackfillRetriever] querying the `epoch_stake` table of a db-sync database
+/// with `psql`.
+///
+/// The `epoch_stake` rows of an epoch hold the stake distribution used for the leader election of
+/// this epoch, which was computed two epochs before: the stake distribution of the epoch `N` is
+/// read from the rows of the epoch `N + 2`, once db-sync has inserted them during the epoch
+/// `N + 1`.
+pub struct DbSyncStakeDistributionRetriever {
+    connection_string: String,
+}
+
+impl DbSyncStakeDistributionRetriever {
+    /// Offset between an epoch and the epoch of its stake distribution in the `epoch_stake` table
+    const EPOCH_STAKE_OFFSET: u64 = 2;
+
+    /// [DbSyncStakeDistributionRetriever] factory
+    pub fn new(connection_string: &str) -> Self {
+        Self {
+            connection_string: connection_string.to_string(),
+        }
+    }
+
+    fn query(epoch: Epoch) -> String {
+        format!(
+            "select pool_hash.view, sum(epoch_stake.amount) \
+            from epoch_stake inner join pool_hash on pool_hash.id = epoch_stake.pool_id \
+            where epoch_stake.epoch_no = {} \
+            group by pool_hash.view",
+            *epoch + Self::EPOCH_STAKE_OFFSET
+        )
+    }
+
+    /// Parse the unaligned `psql` output of the query, one `<pool_id>|<stake>` row per line
+    fn parse_query_output(output: &str) -> StdResult<Option<StakeDistribution>> {
+        let mut stake_distribution = StakeDistribution::new();
+        for line in output.lines().filter(|line| !line.trim().is_empty()) {
+            let (pool_id, stake) = line
+                .split_once('|')
+                .ok_or_else(|| anyhow!("Invalid db-sync epoch stake row: '{line}'"))?;
+            let stake: u64 = stake
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid stake in db-sync epoch stake row: '{line}'"))?;
+            if stake > 0 {
+                stake_distribution.insert(pool_id.trim().to_string(), stake);
+            }
+        }
+
+        Ok((!stake_distribution.is_empty()).then_some(stake_distribution))
+    }
+}
+
+#[async_trait]
+impl StakeDistributionBackfillRetriever for DbSyncStakeDistributionRetriever {
+    async fn retrieve(&self, epoch: Epoch) -> StdResult<Option<StakeDistribution>> {
+        let output = Command::new("psql")
+            .arg(&self.connection_string)
+            .args(["--no-psqlrc", "--no-align", "--tuples-only"])
+            .arg("--command")
+            .arg(Self::query(epoch))
+            .output()
+            .await
+            .with_context(|| "Could not run psql to query the db-sync database")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "psql failed to query the db-sync database: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        Self::parse_query_output(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// A [StakeDistributionBackfillRetriever] reading the stake distributions from JSON files,
+/// i.e. exported from a db-sync database.
+///
+/// The stake distribution of an epoch is read from the `stake-distribution-<epoch>.json` file
+/// of the directory, as a map of party ids to their stake.
+pub struct JsonFileStakeDistributionRetriever {
+    directory: PathBuf,
+}
+
+impl JsonFileStakeDistributionRetriever {
+    /// [JsonFileStakeDistributionRetriever] factory
+    pub fn new(directory: &Path) -> Self {
+        Self {
+            directory: directory.to_path_buf(),
+        }
+    }
+
+    fn file_path(&self, epoch: Epoch) -> PathBuf {
+        self.directory
+            .join(format!("stake-distribution-{epoch}.json"))
+    }
+}
+
+#[async_trait]
+impl StakeDistributionBackfillRetriever for JsonFileStakeDistributionRetriever {
+    async fn retrieve(&self, epoch: Epoch) -> StdResult<Option<StakeDistribution>> {
+        let file_path = self.file_path(epoch);
+        if !file_path.exists() {
+            return Ok(None);
+        }
+        let file = File::open(&file_path).with_context(|| {
+            format!(
+                "Could not open stake distribution file '{}'",
+                file_path.display()
+            )
+        })?;
+        let stake_distribution = serde_json::from_reader(file).with_context(|| {
+            format!(
+                "Could not parse stake distribution file '{}'",
+                file_path.display()
+            )
+        })?;
+
+        Ok(Some(stake_distribution))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::TempDir;
+
+    use crate::database::repository::StakePoolStore;
+    use crate::database::test_helper::main_db_connection;
+    use crate::tools::mocks::MockChainObserver;
+
+    use super::*;
+
+    fn stake_distribution(stake: u64) -> StakeDistribution {
+        StakeDistribution::from([("pool1".to_string(), stake)])
+    }
+
+    async fn stake_store_with_epochs(epochs: &[u64]) -> Arc<StakePoolStore> {
+        let store = Arc::new(StakePoolStore::new(
+            Arc::new(main_db_connection().unwrap()),
+            None,
+        ));
+        for epoch in epochs {
+            store
+                .save_stakes(Epoch(*epoch), stake_distribution(*epoch))
+                .await
+                .unwrap();
+        }
+
+        store
+    }
+
+    #[tokio::test]
+    async fn find_the_epochs_without_stake_distribution() {
+        let backfiller = StakeDistributionBackfiller::new(
+            stake_store_with_epochs(&[1, 2, 5]).await,
+            Arc::new(MockStakeDistributionBackfillRetriever::new()),
+        );
+
+        let missing_epochs = backfiller
+            .find_missing_epochs(Epoch(1), Epoch(6))
+            .await
+            .unwrap();
+
+        assert_eq!(vec![Epoch(3), Epoch(4), Epoch(6)], missing_epochs);
+        backfiller
+            .find_missing_epochs(Epoch(6), Epoch(1))
+            .await
+            .expect_err("an inverted epoch range should be rejected");
+    }
+
+    #[tokio::test]
+    async fn backfill_the_retrievable_missing_epochs() {
+        let stake_store = stake_store_with_epochs(&[1, 4]).await;
+        let mut retriever = MockStakeDistributionBackfillRetriever::new();
+        retriever
+            .expect_retrieve()
+            .returning(|epoch| Ok((epoch == Epoch(2)).then(|| stake_distribution(20))));
+        let backfiller = StakeDistributionBackfiller::new(stake_store.clone(), Arc::new(retriever));
+
+        let report = backfiller.run(Epoch(1), Epoch(4), false).await.unwrap();
+
+        assert_eq!(
+            StakeDistributionBackfillReport {
+                missing_epochs: vec![Epoch(2), Epoch(3)],
+                backfilled_epochs: vec![Epoch(2)],
+                unavailable_epochs: vec![Epoch(3)],
+            },
+            report
+        );
+        assert_eq!(
+            Some(stake_distribution(20)),
+            stake_store.get_stakes(Epoch(2)).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn backfill_does_not_save_anything_on_dry_run() {
+        let stake_store = stake_store_with_epochs(&[1]).await;
+        let mut retriever = MockStakeDistributionBackfillRetriever::new();
+        retriever
+            .expect_retrieve()
+            .returning(|_| Ok(Some(stake_distribution(20))));
+        let backfiller = StakeDistributionBackfiller::new(stake_store.clone(), Arc::new(retriever));
+
+        let report = backfiller.run(Epoch(1), Epoch(2), true).await.unwrap();
+
+        assert_eq!(vec![Epoch(2)], report.backfilled_epochs);
+        assert_eq!(None, stake_store.get_stakes(Epoch(2)).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn chain_observer_retriever_retrieve_the_stake_distribution_of_the_epoch() {
+        let mut chain_observer = MockChainObserver::new();
+        chain_observer
+            .expect_get_stake_distribution()
+            .returning(|epoch| Ok((epoch == Epoch(9)).then(|| stake_distribution(9))));
+        let retriever = ChainObserverStakeDistributionRetriever::new(Arc::new(chain_observer));
+
+        assert_eq!(
+            Some(stake_distribution(9)),
+            retriever.retrieve(Epoch(9)).await.unwrap()
+        );
+        assert_eq!(None, retriever.retrieve(Epoch(8)).await.unwrap());
+    }
+
+    #[test]
+    fn db_sync_retriever_query_the_epoch_stake_of_two_epochs_later() {
+        let query = DbSyncStakeDistributionRetriever::query(Epoch(12));
+
+        assert!(
+            query.contains("where epoch_stake.epoch_no = 14 "),
+            "unexpected query: {query}"
+        );
+    }
+
+    #[test]
+    fn db_sync_retriever_parse_the_query_output() {
+        let output = "pool1abc|1000\npool1def|0\npool1ghi|250\n";
+
+        assert_eq!(
+            Some(StakeDistribution::from([
+                ("pool1abc".to_string(), 1000),
+                ("pool1ghi".to_string(), 250),
+            ])),
+            DbSyncStakeDistributionRetriever::parse_query_output(output).unwrap()
+        );
+        assert_eq!(
+            None,
+            DbSyncStakeDistributionRetriever::parse_query_output("\n").unwrap()
+        );
+        DbSyncStakeDistributionRetriever::parse_query_output("pool1abc|not-a-stake")
+            .expect_err("an invalid stake should be rejected");
+    }
+
+    #[tokio::test]
+    async fn json_file_retriever_read_the_file_of_the_epoch() {
+        let directory = TempDir::create(
+            "stake_distribution_backfiller",
+            "json_file_retriever_read_the_file_of_the_epoch",
+        );
+        std::fs::write(
+            directory.join("stake-distribution-12.json"),
+            r#"{"pool1": 12}"#,
+        )
+        .unwrap();
+        let retriever = JsonFileStakeDistributionRetriever::new(&directory);
+
+        assert_eq!(
+            Some(stake_distribution(12)),
+            retriever.retrieve(Epoch(12)).await.unwrap()
+        );
+        assert_eq!(None, retriever.retrieve(Epoch(13)).await.unwrap());
+    }
+}
@@ -3,7 +3,7 @@ mod test_extensions;
 use mithril_aggregator::Configuration;
 use mithril_common::{
     entities::{
-        CardanoDbBeacon, Epoch, ProtocolParameters, SignedEntityType,
+        CardanoDbBeacon, ChainPoint, Epoch, ProtocolParameters, SignedEntityType,
         SignedEntityTypeDiscriminants, StakeDistributionParty, TimePoint,
     },
     test_utils::MithrilFixtureBuilder,
@@ -22,7 +22,8 @@ async fn create_certificate() {
         data_stores_directory: get_test_dir("create_certificate"),
         ..Configuration::new_sample()
     };
-    let mut tester = RuntimeTester::build(TimePoint::new(1, 1), configuration).await;
+    let mut tester =
+        RuntimeTester::build(TimePoint::new(1, 1, ChainPoint::dummy()), configuration).await;
 
     comment!("create signers & declare stake distribution");
     let fixture = MithrilFixtureBuilder::default()
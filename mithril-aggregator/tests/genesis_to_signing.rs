@@ -2,7 +2,7 @@ mod test_extensions;
 
 use mithril_aggregator::Configuration;
 use mithril_common::{
-    entities::{CardanoDbBeacon, ProtocolParameters, TimePoint},
+    entities::{CardanoDbBeacon, ChainPoint, ProtocolParameters, TimePoint},
     test_utils::MithrilFixtureBuilder,
 };
 use test_extensions::{utilities::get_test_dir, ExpectedCertificate, RuntimeTester};
@@ -19,7 +19,8 @@ async fn genesis_to_signing() {
         data_stores_directory: get_test_dir("genesis_to_signing"),
         ..Configuration::new_sample()
     };
-    let mut tester = RuntimeTester::build(TimePoint::new(1, 1), configuration).await;
+    let mut tester =
+        RuntimeTester::build(TimePoint::new(1, 1, ChainPoint::dummy()), configuration).await;
 
     comment!("Create signers & declare stake distribution");
     let fixture = MithrilFixtureBuilder::default()
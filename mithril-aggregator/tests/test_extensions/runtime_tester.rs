@@ -130,12 +130,16 @@ impl RuntimeTester {
         }
     }
 
-    /// cycle the runtime once
+    /// cycle the runtime once, and wait for the artifacts built in the background
     pub async fn cycle(&mut self) -> StdResult<()> {
         self.runtime
             .cycle()
             .await
-            .with_context(|| "Ticking the state machine should not fail")
+            .with_context(|| "Ticking the state machine should not fail")?;
+        self.runtime
+            .wait_for_artifact_builds()
+            .await
+            .with_context(|| "Building the artifacts should not fail")
     }
 
     /// Check if a message has been sent.
@@ -43,6 +43,13 @@ pub struct CardanoDbDownloadCommand {
     /// Genesis Verification Key to check the certificate chain.
     #[clap(long, env = "GENESIS_VERIFICATION_KEY")]
     genesis_verification_key: Option<String>,
+
+    /// Also download the ancillary files (ledger state snapshots and volatile database) if the
+    /// aggregator provides them, which speeds up the start of the Cardano node.
+    ///
+    /// The ancillary files are not certified by the Mithril multi-signature.
+    #[clap(long)]
+    include_ancillary: bool,
 }
 
 impl CardanoDbDownloadCommand {
@@ -63,11 +70,17 @@ impl CardanoDbDownloadCommand {
         } else {
             ProgressOutputType::Tty
         };
-        let progress_printer = ProgressPrinter::new(progress_output_type, 5);
+        let number_of_steps = if self.include_ancillary { 6 } else { 5 };
+        let progress_file = params
+            .get("progress_file")
+            .map(|path| Arc::new(ProgressFile::new(Path::new(&path), number_of_steps)));
+        let progress_printer = ProgressPrinter::new(progress_output_type, number_of_steps)
+            .with_progress_file(progress_file.clone());
         let client = client_builder(&params)?
-            .add_feedback_receiver(Arc::new(IndicatifFeedbackReceiver::new(
-                progress_output_type,
-            )))
+            .add_feedback_receiver(Arc::new(
+                IndicatifFeedbackReceiver::new(progress_output_type)
+                    .with_progress_file(progress_file.clone()),
+            ))
             .build()?;
 
         let get_list_of_artifact_ids = || async {
@@ -115,11 +128,33 @@ impl CardanoDbDownloadCommand {
             )
         })?;
 
+        // The ancillary files are unpacked before computing the digest so that the certified
+        // files can be checked after any change the ancillary archive could make to them.
+        let mut step_number = 4;
+        if self.include_ancillary {
+            Self::download_and_unpack_ancillary_files(
+                step_number,
+                &progress_printer,
+                &client,
+                &cardano_db_message,
+                &db_dir,
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Can not download and unpack the ancillary files for digest: '{}'",
+                    self.digest
+                )
+            })?;
+            step_number += 1;
+        }
+
         let message =
-            Self::compute_cardano_db_message(4, &progress_printer, &certificate, &db_dir).await?;
+            Self::compute_cardano_db_message(step_number, &progress_printer, &certificate, &db_dir)
+                .await?;
 
         Self::verify_cardano_db_signature(
-            5,
+            step_number + 1,
             &progress_printer,
             &certificate,
             &message,
@@ -128,6 +163,10 @@ impl CardanoDbDownloadCommand {
         )
         .await?;
 
+        if let Some(progress_file) = progress_file {
+            progress_file.finish()?;
+        }
+
         Self::log_download_information(&db_dir, &cardano_db_message, self.json)?;
 
         Ok(())
@@ -208,6 +247,33 @@ impl CardanoDbDownloadCommand {
         Ok(())
     }
 
+    async fn download_and_unpack_ancillary_files(
+        step_number: u16,
+        progress_printer: &ProgressPrinter,
+        client: &Client,
+        cardano_db: &Snapshot,
+        db_dir: &Path,
+    ) -> MithrilResult<()> {
+        if cardano_db.ancillary_locations.is_none() {
+            progress_printer.report_step(
+                step_number,
+                "No ancillary files available for this cardano db, skipping their download",
+            )?;
+            return Ok(());
+        }
+
+        progress_printer.report_step(
+            step_number,
+            "Downloading and unpacking the ancillary files (not certified by Mithril)",
+        )?;
+        client
+            .snapshot()
+            .download_unpack_ancillary(cardano_db, db_dir)
+            .await?;
+
+        Ok(())
+    }
+
     async fn compute_cardano_db_message(
         step_number: u16,
         progress_printer: &ProgressPrinter,
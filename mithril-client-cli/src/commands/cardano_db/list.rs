@@ -1,10 +1,15 @@
+use chrono::{DateTime, Utc};
 use clap::Parser;
 use cli_table::{format::Justify, print_stdout, Cell, Table};
 use config::{builder::DefaultState, ConfigBuilder};
 use std::collections::HashMap;
 
 use crate::{commands::client_builder_with_fallback_genesis_key, configuration::ConfigParameters};
-use mithril_client::MithrilResult;
+use mithril_client::{
+    common::{Epoch, ImmutableFileNumber},
+    list_options::{ListOptions, ListSortOrder},
+    MithrilResult,
+};
 
 /// Clap command to list existing cardano dbs
 #[derive(Parser, Debug, Clone)]
@@ -12,6 +17,22 @@ pub struct CardanoDbListCommand {
     /// Enable JSON output.
     #[clap(long)]
     json: bool,
+
+    /// Only list the cardano dbs of the given epoch.
+    #[clap(long)]
+    epoch: Option<u64>,
+
+    /// Only list the cardano dbs at the given immutable file number.
+    #[clap(long)]
+    immutable_file_number: Option<ImmutableFileNumber>,
+
+    /// Only list the cardano dbs created after the given date (RFC 3339, i.e. `2024-05-02T10:00:00Z`).
+    #[clap(long)]
+    created_after: Option<DateTime<Utc>>,
+
+    /// List the oldest cardano dbs first.
+    #[clap(long)]
+    oldest_first: bool,
 }
 
 impl CardanoDbListCommand {
@@ -20,12 +41,34 @@ impl CardanoDbListCommand {
         self.json
     }
 
+    /// Filter and sort options of the list
+    pub fn list_options(&self) -> ListOptions {
+        let mut options = ListOptions::new();
+        if let Some(epoch) = self.epoch {
+            options = options.with_epoch(Epoch(epoch));
+        }
+        if let Some(immutable_file_number) = self.immutable_file_number {
+            options = options.with_immutable_file_number(immutable_file_number);
+        }
+        if let Some(created_after) = self.created_after {
+            options = options.with_created_after(created_after);
+        }
+        if self.oldest_first {
+            options = options.with_sort_order(ListSortOrder::OldestFirst);
+        }
+
+        options
+    }
+
     /// Main command execution
     pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> MithrilResult<()> {
         let config = config_builder.build()?;
         let params = ConfigParameters::new(config.try_deserialize::<HashMap<String, String>>()?);
         let client = client_builder_with_fallback_genesis_key(&params)?.build()?;
-        let items = client.snapshot().list().await?;
+        let items = client
+            .snapshot()
+            .list_with_options(&self.list_options())
+            .await?;
 
         if self.json {
             println!("{}", serde_json::to_string(&items)?);
@@ -60,3 +103,41 @@ impl CardanoDbListCommand {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn list_options_are_built_from_the_command_arguments() {
+        let command = CardanoDbListCommand::try_parse_from([
+            "list",
+            "--epoch",
+            "450",
+            "--immutable-file-number",
+            "8500",
+            "--created-after",
+            "2024-05-02T10:00:00Z",
+            "--oldest-first",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            ListOptions::new()
+                .with_epoch(Epoch(450))
+                .with_immutable_file_number(8500)
+                .with_created_after(Utc.with_ymd_and_hms(2024, 5, 2, 10, 0, 0).unwrap())
+                .with_sort_order(ListSortOrder::OldestFirst),
+            command.list_options()
+        );
+    }
+
+    #[test]
+    fn list_options_are_the_default_without_arguments() {
+        let command = CardanoDbListCommand::try_parse_from(["list"]).unwrap();
+
+        assert_eq!(ListOptions::new(), command.list_options());
+    }
+}
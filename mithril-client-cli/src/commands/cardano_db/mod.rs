@@ -2,10 +2,12 @@
 mod download;
 mod list;
 mod show;
+mod verify;
 
 pub use download::*;
 pub use list::*;
 pub use show::*;
+pub use verify::*;
 
 use clap::Subcommand;
 use config::{builder::DefaultState, ConfigBuilder};
@@ -21,6 +23,10 @@ pub enum CardanoDbCommands {
     /// Download a Cardano db snapshot and verify its associated certificate
     #[clap(arg_required_else_help = true)]
     Download(CardanoDbDownloadCommand),
+
+    /// Verify a previously downloaded Cardano db archive against its certificate
+    #[clap(arg_required_else_help = true)]
+    Verify(CardanoDbVerifyCommand),
 }
 
 /// Cardano db snapshots
@@ -41,6 +47,7 @@ impl CardanoDbCommands {
         match self {
             Self::Download(cmd) => cmd.execute(config_builder).await,
             Self::Snapshot(cmd) => cmd.execute(config_builder).await,
+            Self::Verify(cmd) => cmd.execute(config_builder).await,
         }
     }
 }
@@ -73,6 +80,10 @@ pub mod deprecated {
         /// Download a Cardano db snapshot and verify its associated certificate
         #[clap(arg_required_else_help = true)]
         Download(CardanoDbDownloadCommand),
+
+        /// Verify a previously downloaded Cardano db archive against its certificate
+        #[clap(arg_required_else_help = true)]
+        Verify(CardanoDbVerifyCommand),
     }
 
     impl SnapshotCommands {
@@ -85,6 +96,7 @@ pub mod deprecated {
                 Self::List(cmd) => cmd.execute(config_builder).await,
                 Self::Download(cmd) => cmd.execute(config_builder).await,
                 Self::Show(cmd) => cmd.execute(config_builder).await,
+                Self::Verify(cmd) => cmd.execute(config_builder).await,
             }
         }
 
@@ -94,6 +106,7 @@ pub mod deprecated {
                 Self::List(cmd) => cmd.is_json_output_enabled(),
                 Self::Download(cmd) => cmd.is_json_output_enabled(),
                 Self::Show(cmd) => cmd.is_json_output_enabled(),
+                Self::Verify(cmd) => cmd.is_json_output_enabled(),
             }
         }
     }
@@ -0,0 +1,320 @@
+use anyhow::{anyhow, Context};
+use chrono::Utc;
+use clap::Parser;
+use config::{builder::DefaultState, ConfigBuilder, Map, Source, Value, ValueKind};
+use slog_scope::{debug, warn};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    commands::client_builder,
+    configuration::ConfigParameters,
+    utils::{CardanoDbDownloadChecker, CardanoDbUtils, ProgressOutputType, ProgressPrinter},
+};
+use mithril_client::{
+    common::ProtocolMessage, Client, MessageBuilder, MithrilCertificate, MithrilResult,
+};
+
+/// Clap command to verify a previously downloaded Cardano db archive against its certificate,
+/// without downloading it again.
+///
+/// The previous certificates of the chain are read from the certificate cache set with the
+/// `--certificate-cache-directory` global option: once synchronized with `certificate sync` the
+/// verification does not need the aggregator.
+#[derive(Parser, Debug, Clone)]
+pub struct CardanoDbVerifyCommand {
+    /// Enable JSON output.
+    #[clap(long)]
+    json: bool,
+
+    /// Path of the Cardano db archive to verify (`tar.gz` or `tar.zst`).
+    archive: PathBuf,
+
+    /// Path of a JSON file holding the certificate of the Cardano db.
+    #[clap(long, required_unless_present = "certificate_hash")]
+    certificate_file: Option<PathBuf>,
+
+    /// Hash of the certificate of the Cardano db.
+    #[clap(long, conflicts_with = "certificate_file")]
+    certificate_hash: Option<String>,
+
+    /// Directory where the archive is unpacked to compute its digest, the Cardano db is kept
+    /// in its `db` subdirectory.
+    ///
+    /// By default a temporary directory is used and removed after the verification.
+    #[clap(long)]
+    unpack_dir: Option<PathBuf>,
+
+    /// Genesis Verification Key to check the certificate chain.
+    #[clap(long, env = "GENESIS_VERIFICATION_KEY")]
+    genesis_verification_key: Option<String>,
+}
+
+impl CardanoDbVerifyCommand {
+    /// Is JSON output enabled
+    pub fn is_json_output_enabled(&self) -> bool {
+        self.json
+    }
+
+    /// Command execution
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> MithrilResult<()> {
+        let config = config_builder.add_source(self.clone()).build()?;
+        let params = ConfigParameters::new(config.try_deserialize::<HashMap<String, String>>()?);
+        let client = client_builder(&params)?.build()?;
+
+        let progress_output_type = if self.json {
+            ProgressOutputType::JsonReporter
+        } else {
+            ProgressOutputType::Tty
+        };
+        let progress_printer = ProgressPrinter::new(progress_output_type, 4);
+
+        let certificate = self
+            .read_certificate_and_verify_chain(1, &progress_printer, &client)
+            .await?;
+
+        let (unpack_dir, is_temporary) = match &self.unpack_dir {
+            Some(unpack_dir) => (unpack_dir.clone(), false),
+            None => (
+                std::env::temp_dir().join("mithril-client").join(format!(
+                    "cardano-db-verify-{}",
+                    Utc::now().timestamp_nanos_opt().unwrap_or_default()
+                )),
+                true,
+            ),
+        };
+        let db_dir = unpack_dir.join("db");
+
+        let result = self
+            .unpack_and_verify(2, &progress_printer, &client, &certificate, &db_dir)
+            .await;
+
+        if is_temporary {
+            if let Err(error) = std::fs::remove_dir_all(&unpack_dir) {
+                warn!(
+                    "Could not remove temporary unpack directory '{}': {error}",
+                    unpack_dir.display()
+                );
+            }
+        }
+        let digest = result?;
+
+        Self::log_verify_information(&digest, &certificate, self.json);
+
+        Ok(())
+    }
+
+    async fn read_certificate_and_verify_chain(
+        &self,
+        step_number: u16,
+        progress_printer: &ProgressPrinter,
+        client: &Client,
+    ) -> MithrilResult<MithrilCertificate> {
+        progress_printer.report_step(
+            step_number,
+            "Reading the certificate and verifying the certificate chain…",
+        )?;
+
+        match (&self.certificate_file, &self.certificate_hash) {
+            (Some(certificate_file), _) => {
+                let certificate = Self::read_certificate_file(certificate_file)?;
+                client
+                    .certificate()
+                    .verify_chain_from_certificate(&certificate)
+                    .await?;
+
+                Ok(certificate)
+            }
+            (None, Some(certificate_hash)) => client
+                .certificate()
+                .verify_chain(certificate_hash)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Can not verify the certificate chain from certificate_hash: '{certificate_hash}'"
+                    )
+                }),
+            (None, None) => Err(anyhow!(
+                "A certificate file or a certificate hash must be given"
+            )),
+        }
+    }
+
+    fn read_certificate_file(certificate_file: &Path) -> MithrilResult<MithrilCertificate> {
+        let content = std::fs::read_to_string(certificate_file).with_context(|| {
+            format!(
+                "Could not read certificate file: '{}'",
+                certificate_file.display()
+            )
+        })?;
+
+        serde_json::from_str(&content).with_context(|| {
+            format!(
+                "Could not parse certificate file: '{}'",
+                certificate_file.display()
+            )
+        })
+    }
+
+    async fn unpack_and_verify(
+        &self,
+        step_number: u16,
+        progress_printer: &ProgressPrinter,
+        client: &Client,
+        certificate: &MithrilCertificate,
+        db_dir: &Path,
+    ) -> MithrilResult<String> {
+        progress_printer.report_step(step_number, "Unpacking the cardano db archive…")?;
+        CardanoDbDownloadChecker::ensure_dir_exist(db_dir)?;
+        if std::fs::read_dir(db_dir)?.next().is_some() {
+            return Err(anyhow!(
+                "Unpack directory '{}' is not empty, please remove its content or use another directory",
+                db_dir.display()
+            ));
+        }
+        client
+            .snapshot()
+            .unpack_archive(&self.archive, db_dir)
+            .await
+            .with_context(|| {
+                format!(
+                    "Can not unpack the cardano db archive: '{}'",
+                    self.archive.display()
+                )
+            })?;
+
+        progress_printer.report_step(step_number + 1, "Computing the cardano db message")?;
+        let message = CardanoDbUtils::wait_spinner(
+            progress_printer,
+            MessageBuilder::new().compute_snapshot_message(certificate, db_dir),
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "Can not compute the cardano db message from the directory: '{}'",
+                db_dir.display()
+            )
+        })?;
+
+        progress_printer.report_step(step_number + 2, "Verifying the cardano db signature…")?;
+        Self::verify_cardano_db_signature(certificate, &message)
+    }
+
+    fn verify_cardano_db_signature(
+        certificate: &MithrilCertificate,
+        message: &ProtocolMessage,
+    ) -> MithrilResult<String> {
+        let digest = message.snapshot_digest().unwrap_or_default().to_string();
+
+        if !certificate.match_message(message) {
+            debug!("Digest verification failed"; "computed_digest" => &digest);
+
+            return Err(anyhow!(
+                "Certificate verification failed (computed cardano db digest = '{digest}')."
+            ));
+        }
+
+        Ok(digest)
+    }
+
+    fn log_verify_information(digest: &str, certificate: &MithrilCertificate, json_output: bool) {
+        if json_output {
+            println!(
+                r#"{{"timestamp": "{}", "digest": "{}", "certificate_hash": "{}"}}"#,
+                Utc::now().to_rfc3339(),
+                digest,
+                certificate.hash
+            );
+        } else {
+            println!(
+                "Cardano db archive with digest '{digest}' has been successfully checked against the Mithril multi-signature contained in the certificate '{}'.",
+                certificate.hash
+            );
+        }
+    }
+}
+
+impl Source for CardanoDbVerifyCommand {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, config::ConfigError> {
+        let mut map = Map::new();
+        let namespace = "clap arguments".to_string();
+
+        if let Some(genesis_verification_key) = self.genesis_verification_key.clone() {
+            map.insert(
+                "genesis_verification_key".to_string(),
+                Value::new(Some(&namespace), ValueKind::from(genesis_verification_key)),
+            );
+        }
+
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_client::common::ProtocolMessagePartKey;
+    use mithril_common::test_utils::TempDir;
+
+    use super::*;
+
+    fn certificate_signing_digest(digest: &str) -> MithrilCertificate {
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message
+            .set_message_part(ProtocolMessagePartKey::SnapshotDigest, digest.to_string());
+
+        MithrilCertificate {
+            protocol_message: protocol_message.clone(),
+            signed_message: protocol_message.compute_hash(),
+            ..MithrilCertificate::dummy()
+        }
+    }
+
+    #[test]
+    fn verify_cardano_db_signature_return_the_digest_if_messages_match() {
+        let certificate = certificate_signing_digest("digest");
+
+        let digest = CardanoDbVerifyCommand::verify_cardano_db_signature(
+            &certificate,
+            &certificate.protocol_message,
+        )
+        .unwrap();
+
+        assert_eq!("digest", digest);
+    }
+
+    #[test]
+    fn verify_cardano_db_signature_fail_if_messages_mismatch() {
+        let certificate = certificate_signing_digest("digest");
+        let mut message = ProtocolMessage::new();
+        message.set_message_part(
+            ProtocolMessagePartKey::SnapshotDigest,
+            "another-digest".to_string(),
+        );
+
+        CardanoDbVerifyCommand::verify_cardano_db_signature(&certificate, &message)
+            .expect_err("verification should fail when the messages mismatch");
+    }
+
+    #[test]
+    fn read_certificate_file_parse_a_certificate_message() {
+        let dir = TempDir::create("client-cli", "read_certificate_file_parse_a_certificate");
+        let certificate_file = dir.join("certificate.json");
+        let certificate = certificate_signing_digest("digest");
+        std::fs::write(
+            &certificate_file,
+            serde_json::to_string(&certificate).unwrap(),
+        )
+        .unwrap();
+
+        let read_certificate =
+            CardanoDbVerifyCommand::read_certificate_file(&certificate_file).unwrap();
+
+        assert_eq!(certificate, read_certificate);
+    }
+}
@@ -2,12 +2,14 @@ use anyhow::{anyhow, Context};
 use clap::Parser;
 use cli_table::{print_stdout, Cell, Table};
 use config::{builder::DefaultState, ConfigBuilder, Map, Source, Value, ValueKind};
+use serde::Serialize;
 use slog_scope::debug;
 use std::{collections::HashMap, sync::Arc};
 
 use mithril_client::{
-    common::TransactionHash, CardanoTransactionsProofs, MessageBuilder, MithrilCertificate,
-    MithrilResult, VerifiedCardanoTransactions, VerifyCardanoTransactionsProofsError,
+    common::{CardanoDbBeacon, SignedEntityType, TransactionHash},
+    CardanoTransactionsProofs, MessageBuilder, MithrilCertificate, MithrilResult,
+    VerifiedCardanoTransactions, VerifyCardanoTransactionsProofsError,
 };
 
 use crate::utils::{IndicatifFeedbackReceiver, ProgressOutputType, ProgressPrinter};
@@ -88,11 +90,12 @@ impl CardanoTransactionsCertifyCommand {
             &verified_transactions,
         )?;
 
-        Self::log_certify_information(
-            &verified_transactions,
-            &cardano_transaction_proof.non_certified_transactions,
-            self.json,
-        )
+        let summary = CertifiedTransactionsSummary::new(
+            &self.transactions_hashes,
+            verified_transactions.certified_transactions(),
+            &certificate,
+        )?;
+        Self::log_certify_information(&summary, self.json)
     }
 
     fn verify_proof_validity(
@@ -135,43 +138,45 @@ Mithril may not have signed those transactions yet, please try again later."
     }
 
     fn log_certify_information(
-        verified_transactions: &VerifiedCardanoTransactions,
-        non_certified_transactions: &[TransactionHash],
+        summary: &CertifiedTransactionsSummary,
         json_output: bool,
     ) -> MithrilResult<()> {
         if json_output {
-            println!(
-                r#"{{"certified_transactions": {}, "non_certified_transactions": {}}}"#,
-                serde_json::to_string(verified_transactions.certified_transactions())?,
-                serde_json::to_string(non_certified_transactions)?,
-            );
+            println!("{}", serde_json::to_string(summary)?);
         } else {
             println!(
                 r###"Cardano transactions proof has been successfully signed in the associated Mithril certificate."###,
             );
 
-            if !non_certified_transactions.is_empty() {
+            if !summary.non_certified_transactions.is_empty() {
                 println!(
                     r###"
 No proof could be computed for some Cardano transactions. Mithril may not have signed those transactions yet, please try again later."###,
                 );
             }
 
-            let result_table = verified_transactions
-                .certified_transactions()
+            println!(
+                r###"
+Certificate hash: {}
+Beacon: epoch {}, immutable file number {} ({})
+"###,
+                summary.certificate_hash,
+                summary.beacon.epoch,
+                summary.beacon.immutable_file_number,
+                summary.beacon.network,
+            );
+
+            let result_table = summary
+                .transactions
                 .iter()
                 .map(|tx| {
                     vec![
-                        tx.cell(),
-                        "✅".cell().justify(cli_table::format::Justify::Center),
+                        tx.hash.as_str().cell(),
+                        if tx.certified { "✅" } else { "❌" }
+                            .cell()
+                            .justify(cli_table::format::Justify::Center),
                     ]
                 })
-                .chain(non_certified_transactions.iter().map(|tx| {
-                    vec![
-                        tx.cell(),
-                        "❌".cell().justify(cli_table::format::Justify::Center),
-                    ]
-                }))
                 .table()
                 .title(vec!["Transaction Hash", "Certified"]);
 
@@ -182,6 +187,69 @@ No proof could be computed for some Cardano transactions. Mithril may not have s
     }
 }
 
+/// Certification status of a transaction
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct TransactionCertificationStatus {
+    hash: TransactionHash,
+    certified: bool,
+}
+
+/// Summary of the certification of the requested transactions, suitable for wallets
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct CertifiedTransactionsSummary {
+    /// Certification status of each requested transaction, in the order of the request
+    transactions: Vec<TransactionCertificationStatus>,
+    certified_transactions: Vec<TransactionHash>,
+    non_certified_transactions: Vec<TransactionHash>,
+    /// Hash of the certificate that signs the certified transactions
+    certificate_hash: String,
+    /// Beacon of the certificate that signs the certified transactions
+    beacon: CardanoDbBeacon,
+}
+
+impl CertifiedTransactionsSummary {
+    fn new(
+        requested_transactions: &[String],
+        certified_transactions: &[TransactionHash],
+        certificate: &MithrilCertificate,
+    ) -> MithrilResult<Self> {
+        let beacon = match &certificate.signed_entity_type {
+            SignedEntityType::CardanoTransactions(beacon) => beacon.clone(),
+            signed_entity_type => {
+                return Err(anyhow!(
+                    "The certificate '{}' does not sign Cardano transactions but '{signed_entity_type}'",
+                    certificate.hash
+                ))
+            }
+        };
+        let mut transactions: Vec<TransactionCertificationStatus> = vec![];
+        for hash in requested_transactions {
+            if transactions.iter().all(|tx| &tx.hash != hash) {
+                transactions.push(TransactionCertificationStatus {
+                    hash: hash.clone(),
+                    certified: certified_transactions.contains(hash),
+                });
+            }
+        }
+
+        Ok(Self {
+            certified_transactions: transactions
+                .iter()
+                .filter(|tx| tx.certified)
+                .map(|tx| tx.hash.clone())
+                .collect(),
+            non_certified_transactions: transactions
+                .iter()
+                .filter(|tx| !tx.certified)
+                .map(|tx| tx.hash.clone())
+                .collect(),
+            transactions,
+            certificate_hash: certificate.hash.clone(),
+            beacon,
+        })
+    }
+}
+
 impl Source for CardanoTransactionsCertifyCommand {
     fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
         Box::new(self.clone())
@@ -201,3 +269,66 @@ impl Source for CardanoTransactionsCertifyCommand {
         Ok(map)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mithril_client::common::Epoch;
+
+    use super::*;
+
+    fn cardano_transactions_certificate(beacon: CardanoDbBeacon) -> MithrilCertificate {
+        MithrilCertificate {
+            signed_entity_type: SignedEntityType::CardanoTransactions(beacon),
+            ..MithrilCertificate::dummy()
+        }
+    }
+
+    #[test]
+    fn summary_gives_the_certification_status_of_each_requested_transaction_in_order() {
+        let beacon = CardanoDbBeacon::new("testnet".to_string(), 8, 120);
+        let certificate = cardano_transactions_certificate(beacon.clone());
+        let requested_transactions = ["tx-3", "tx-1", "tx-2", "tx-1"].map(String::from);
+
+        let summary = CertifiedTransactionsSummary::new(
+            &requested_transactions,
+            &["tx-1".to_string(), "tx-3".to_string()],
+            &certificate,
+        )
+        .unwrap();
+
+        assert_eq!(
+            CertifiedTransactionsSummary {
+                transactions: vec![
+                    TransactionCertificationStatus {
+                        hash: "tx-3".to_string(),
+                        certified: true,
+                    },
+                    TransactionCertificationStatus {
+                        hash: "tx-1".to_string(),
+                        certified: true,
+                    },
+                    TransactionCertificationStatus {
+                        hash: "tx-2".to_string(),
+                        certified: false,
+                    },
+                ],
+                certified_transactions: vec!["tx-3".to_string(), "tx-1".to_string()],
+                non_certified_transactions: vec!["tx-2".to_string()],
+                certificate_hash: certificate.hash.clone(),
+                beacon,
+            },
+            summary
+        );
+    }
+
+    #[test]
+    fn summary_fails_if_the_certificate_does_not_sign_cardano_transactions() {
+        let certificate = MithrilCertificate {
+            signed_entity_type: SignedEntityType::MithrilStakeDistribution(Epoch(8)),
+            ..MithrilCertificate::dummy()
+        };
+
+        CertifiedTransactionsSummary::new(&["tx-1".to_string()], &[], &certificate)
+            .expect_err("A certificate that does not sign Cardano transactions should fail");
+    }
+}
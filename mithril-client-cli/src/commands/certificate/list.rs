@@ -0,0 +1,190 @@
+use clap::Parser;
+use cli_table::{format::Justify, print_stdout, Cell, Table};
+use config::{builder::DefaultState, ConfigBuilder};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::{commands::client_builder_with_fallback_genesis_key, configuration::ConfigParameters};
+use mithril_client::{
+    common::{SignedEntityType, SignedEntityTypeDiscriminants},
+    MithrilCertificateListItem, MithrilResult,
+};
+
+/// Signed entity type filter of the certificate list, given either by the canonical name of
+/// a signed entity type (i.e. `cardano-transactions`) or by a signed entity type with its beacon
+/// (i.e. `cardano-transactions/network=preview/epoch=5/immutable=123`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignedEntityTypeFilter {
+    /// Keep the certificates of any beacon of the signed entity type
+    Discriminant(SignedEntityTypeDiscriminants),
+
+    /// Keep the certificates of this signed entity type and beacon only
+    SignedEntityType(SignedEntityType),
+}
+
+impl SignedEntityTypeFilter {
+    fn matches(&self, signed_entity_type: &SignedEntityType) -> bool {
+        match self {
+            Self::Discriminant(discriminant) => {
+                SignedEntityTypeDiscriminants::from(signed_entity_type) == *discriminant
+            }
+            Self::SignedEntityType(expected) => signed_entity_type == expected,
+        }
+    }
+}
+
+impl FromStr for SignedEntityTypeFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.contains('/') {
+            SignedEntityType::from_str(value).map(Self::SignedEntityType)
+        } else {
+            SignedEntityTypeDiscriminants::from_str(value).map(Self::Discriminant)
+        }
+    }
+}
+
+/// Certificate LIST command
+#[derive(Parser, Debug, Clone)]
+pub struct CertificateListCommand {
+    /// Enable JSON output.
+    #[clap(long)]
+    json: bool,
+
+    /// Only list the certificates of the given signed entity type, given by its canonical name
+    /// (i.e. `cardano-transactions`) or with its beacon
+    /// (i.e. `cardano-transactions/network=preview/epoch=5/immutable=123`).
+    #[clap(long)]
+    signed_entity_type: Option<SignedEntityTypeFilter>,
+}
+
+impl CertificateListCommand {
+    /// Keep the certificates matching the signed entity type filter of the command
+    fn filter(&self, items: Vec<MithrilCertificateListItem>) -> Vec<MithrilCertificateListItem> {
+        match &self.signed_entity_type {
+            Some(filter) => items
+                .into_iter()
+                .filter(|item| filter.matches(&item.signed_entity_type))
+                .collect(),
+            None => items,
+        }
+    }
+
+    /// Main command execution
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> MithrilResult<()> {
+        let config = config_builder.build()?;
+        let params = ConfigParameters::new(config.try_deserialize::<HashMap<String, String>>()?);
+        let client = client_builder_with_fallback_genesis_key(&params)?.build()?;
+        let items = self.filter(client.certificate().list().await?);
+
+        if self.json {
+            println!("{}", serde_json::to_string(&items)?);
+        } else {
+            let items = items
+                .into_iter()
+                .map(|item| {
+                    vec![
+                        format!("{}", item.epoch).cell(),
+                        item.signed_entity_type.to_string().cell(),
+                        item.hash.cell(),
+                        item.metadata.sealed_at.to_string().cell(),
+                    ]
+                })
+                .collect::<Vec<_>>()
+                .table()
+                .title(vec![
+                    "Epoch".cell(),
+                    "Signed Entity Type".cell(),
+                    "Hash".cell(),
+                    "Sealed".cell().justify(Justify::Right),
+                ]);
+            print_stdout(items)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_client::common::{CardanoDbBeacon, Epoch};
+
+    use super::*;
+
+    fn certificate_list_item(signed_entity_type: SignedEntityType) -> MithrilCertificateListItem {
+        MithrilCertificateListItem {
+            signed_entity_type,
+            ..MithrilCertificateListItem::dummy()
+        }
+    }
+
+    #[test]
+    fn parse_the_signed_entity_type_filter_from_a_canonical_name_or_a_beacon() {
+        let command = CertificateListCommand::try_parse_from([
+            "list",
+            "--signed-entity-type",
+            "cardano-transactions",
+        ])
+        .unwrap();
+        assert_eq!(
+            Some(SignedEntityTypeFilter::Discriminant(
+                SignedEntityTypeDiscriminants::CardanoTransactions
+            )),
+            command.signed_entity_type
+        );
+
+        let command = CertificateListCommand::try_parse_from([
+            "list",
+            "--signed-entity-type",
+            "cardano-transactions/network=preview/epoch=5/immutable=123",
+        ])
+        .unwrap();
+        assert_eq!(
+            Some(SignedEntityTypeFilter::SignedEntityType(
+                SignedEntityType::CardanoTransactions(CardanoDbBeacon::new("preview", 5, 123))
+            )),
+            command.signed_entity_type
+        );
+    }
+
+    #[test]
+    fn reject_an_invalid_signed_entity_type_filter() {
+        for invalid in ["unknown", "cardano-transactions/epoch=5"] {
+            CertificateListCommand::try_parse_from(["list", "--signed-entity-type", invalid])
+                .expect_err(&format!("parsing '{invalid}' should fail"));
+        }
+    }
+
+    #[test]
+    fn filter_the_certificates_by_signed_entity_type() {
+        let transactions =
+            SignedEntityType::CardanoTransactions(CardanoDbBeacon::new("preview", 5, 123));
+        let items = vec![
+            certificate_list_item(SignedEntityType::MithrilStakeDistribution(Epoch(5))),
+            certificate_list_item(transactions.clone()),
+            certificate_list_item(SignedEntityType::CardanoTransactions(CardanoDbBeacon::new(
+                "preview", 5, 124,
+            ))),
+        ];
+
+        let command = CertificateListCommand::try_parse_from(["list"]).unwrap();
+        assert_eq!(items, command.filter(items.clone()));
+
+        let command = CertificateListCommand::try_parse_from([
+            "list",
+            "--signed-entity-type",
+            "cardano-transactions",
+        ])
+        .unwrap();
+        assert_eq!(items[1..].to_vec(), command.filter(items.clone()));
+
+        let command = CertificateListCommand::try_parse_from([
+            "list",
+            "--signed-entity-type",
+            &transactions.to_string(),
+        ])
+        .unwrap();
+        assert_eq!(vec![items[1].clone()], command.filter(items));
+    }
+}
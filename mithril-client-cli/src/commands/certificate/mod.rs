@@ -0,0 +1,32 @@
+//! Commands for the Mithril certificates
+mod list;
+mod sync;
+
+pub use list::*;
+pub use sync::*;
+
+use clap::Subcommand;
+use config::{builder::DefaultState, ConfigBuilder};
+use mithril_client::MithrilResult;
+
+/// Mithril certificates management
+#[derive(Subcommand, Debug, Clone)]
+pub enum CertificateCommands {
+    /// List the certificates of the aggregator
+    #[clap(arg_required_else_help = false)]
+    List(CertificateListCommand),
+
+    /// Download the new certificates into the local certificate cache and verify the chain
+    #[clap(arg_required_else_help = false)]
+    Sync(CertificateSyncCommand),
+}
+
+impl CertificateCommands {
+    /// Execute certificate command
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> MithrilResult<()> {
+        match self {
+            Self::List(cmd) => cmd.execute(config_builder).await,
+            Self::Sync(cmd) => cmd.execute(config_builder).await,
+        }
+    }
+}
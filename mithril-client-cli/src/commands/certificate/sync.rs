@@ -0,0 +1,88 @@
+use anyhow::Context;
+use clap::Parser;
+use config::{builder::DefaultState, ConfigBuilder, Map, Source, Value, ValueKind};
+use std::collections::HashMap;
+
+use crate::{commands::client_builder, configuration::ConfigParameters};
+use mithril_client::MithrilResult;
+
+/// Clap command to synchronize the local certificate cache
+///
+/// The cache directory is set with the `--certificate-cache-directory` global option.
+#[derive(Parser, Debug, Clone)]
+pub struct CertificateSyncCommand {
+    /// Enable JSON output.
+    #[clap(long)]
+    json: bool,
+
+    /// Genesis Verification Key to check the certificate chain.
+    #[clap(long, env = "GENESIS_VERIFICATION_KEY")]
+    genesis_verification_key: Option<String>,
+}
+
+impl CertificateSyncCommand {
+    /// Certificate sync command
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> MithrilResult<()> {
+        let config = config_builder.add_source(self.clone()).build()?;
+        let params = ConfigParameters::new(config.try_deserialize::<HashMap<String, String>>()?);
+        params.require("certificate_cache_directory")?;
+        let client = client_builder(&params)?.build()?;
+
+        let report = client
+            .certificate()
+            .sync()
+            .await
+            .with_context(|| "Can not synchronize the certificate cache")?;
+
+        if let Some(latest_certificate_hash) = &report.latest_certificate_hash {
+            client
+                .certificate()
+                .verify_chain(latest_certificate_hash)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Can not verify the certificate chain from certificate_hash: '{latest_certificate_hash}'"
+                    )
+                })?;
+        }
+
+        if self.json {
+            println!(
+                r#"{{"latest_certificate_hash": {}, "downloaded_certificates": {}}}"#,
+                serde_json::to_string(&report.latest_certificate_hash)?,
+                report.downloaded_certificates
+            );
+        } else {
+            println!(
+                "Downloaded {} new certificate(s), latest certificate: {}",
+                report.downloaded_certificates,
+                report
+                    .latest_certificate_hash
+                    .as_deref()
+                    .unwrap_or("none (the aggregator has no certificate)")
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Source for CertificateSyncCommand {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, config::ConfigError> {
+        let mut map = Map::new();
+        let namespace = "clap arguments".to_string();
+
+        if let Some(genesis_verification_key) = self.genesis_verification_key.clone() {
+            map.insert(
+                "genesis_verification_key".to_string(),
+                Value::new(Some(&namespace), ValueKind::from(genesis_verification_key)),
+            );
+        }
+
+        Ok(map)
+    }
+}
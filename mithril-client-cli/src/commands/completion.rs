@@ -0,0 +1,74 @@
+//! Completion command
+//!
+//! Generates the completion scripts of the CLI for the supported shells from the definition of
+//! its commands.
+
+use clap::{Command, Parser};
+use clap_complete::Shell;
+use std::io::Write;
+
+use mithril_client::MithrilResult;
+
+/// Clap command to generate the completion script of the CLI for a shell.
+#[derive(Parser, Debug, Clone)]
+pub struct CompletionCommand {
+    /// Shell for which the completion script is generated.
+    #[clap(value_enum)]
+    shell: Shell,
+}
+
+impl CompletionCommand {
+    /// Command execution, the completion script is printed on the standard output
+    pub fn execute(&self, command: &mut Command) -> MithrilResult<()> {
+        let mut stdout = std::io::stdout();
+        write_completion_script(self.shell, command, &mut stdout);
+        stdout.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Write the completion script of the given command for the given shell
+fn write_completion_script(shell: Shell, command: &mut Command, buffer: &mut dyn Write) {
+    let bin_name = command.get_name().to_string();
+    clap_complete::generate(shell, command, bin_name, buffer);
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Arg;
+
+    use super::*;
+
+    fn test_command() -> Command {
+        Command::new("my-cli").subcommand(
+            Command::new("cardano-db")
+                .about("Cardano db management")
+                .subcommand(
+                    Command::new("download").arg(
+                        Arg::new("download_dir")
+                            .long("download-dir")
+                            .help("Download directory"),
+                    ),
+                ),
+        )
+    }
+
+    fn completion_script(shell: Shell) -> String {
+        let mut buffer = vec![];
+        write_completion_script(shell, &mut test_command(), &mut buffer);
+
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn completion_scripts_complete_the_subcommands_and_their_options() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = completion_script(shell);
+
+            assert!(script.contains("my-cli"), "{shell}: {script}");
+            assert!(script.contains("cardano-db"), "{shell}: {script}");
+            assert!(script.contains("download-dir"), "{shell}: {script}");
+        }
+    }
+}
@@ -7,6 +7,9 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use super::verify::{
+    verify_mithril_stake_distribution_signature, MithrilStakeDistributionVerificationReport,
+};
 use crate::utils::{IndicatifFeedbackReceiver, ProgressOutputType, ProgressPrinter};
 use crate::{commands::client_builder, configuration::ConfigParameters, utils::ExpanderUtils};
 use mithril_client::MessageBuilder;
@@ -113,18 +116,15 @@ impl MithrilStakeDistributionDownloadCommand {
             "Verify that the Mithril stake distribution is signed in the associated certificate",
         )?;
         let message = MessageBuilder::new()
-            .compute_mithril_stake_distribution_message(&mithril_stake_distribution)
+            .compute_mithril_stake_distribution_message_with_certificate(
+                &certificate,
+                &mithril_stake_distribution,
+            )
             .with_context(|| {
                 "Can not compute the message for the given Mithril stake distribution"
             })?;
 
-        if !certificate.match_message(&message) {
-            return Err(anyhow::anyhow!(
-                    "Certificate and message did not match:\ncertificate_message: '{}'\n computed_message: '{}'",
-                    certificate.signed_message,
-                    message.compute_hash()
-                ));
-        }
+        verify_mithril_stake_distribution_signature(&certificate, &message)?;
 
         progress_printer.report_step(4, "Writing fetched Mithril stake distribution to a file")?;
         if !download_dir.is_dir() {
@@ -144,18 +144,22 @@ impl MithrilStakeDistributionDownloadCommand {
             })?,
         )?;
 
+        let report = MithrilStakeDistributionVerificationReport::new(
+            &mithril_stake_distribution,
+            &certificate,
+            &message,
+        );
         if self.json {
-            println!(
-                r#"{{"mithril_stake_distribution_hash": "{}", "filepath": "{}"}}"#,
-                mithril_stake_distribution.hash,
-                filepath.display()
-            );
+            let mut output = serde_json::to_value(&report)?;
+            output["filepath"] = filepath.display().to_string().into();
+            println!("{output}");
         } else {
             println!(
                 "Mithril Stake Distribution '{}' has been verified and saved as '{}'.",
                 mithril_stake_distribution.hash,
                 filepath.display()
             );
+            report.print_details();
         }
 
         Ok(())
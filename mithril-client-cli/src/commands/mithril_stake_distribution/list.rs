@@ -1,10 +1,15 @@
+use chrono::{DateTime, Utc};
 use clap::Parser;
 use cli_table::{format::Justify, print_stdout, Cell, Table};
 use config::{builder::DefaultState, ConfigBuilder};
 use std::collections::HashMap;
 
 use crate::{commands::client_builder_with_fallback_genesis_key, configuration::ConfigParameters};
-use mithril_client::MithrilResult;
+use mithril_client::{
+    common::Epoch,
+    list_options::{ListOptions, ListSortOrder},
+    MithrilResult,
+};
 
 /// Mithril stake distribution LIST command
 #[derive(Parser, Debug, Clone)]
@@ -12,15 +17,46 @@ pub struct MithrilStakeDistributionListCommand {
     /// Enable JSON output.
     #[clap(long)]
     json: bool,
+
+    /// Only list the Mithril stake distributions of the given epoch.
+    #[clap(long)]
+    epoch: Option<u64>,
+
+    /// Only list the Mithril stake distributions created after the given date (RFC 3339, i.e. `2024-05-02T10:00:00Z`).
+    #[clap(long)]
+    created_after: Option<DateTime<Utc>>,
+
+    /// List the oldest Mithril stake distributions first.
+    #[clap(long)]
+    oldest_first: bool,
 }
 
 impl MithrilStakeDistributionListCommand {
+    /// Filter and sort options of the list
+    pub fn list_options(&self) -> ListOptions {
+        let mut options = ListOptions::new();
+        if let Some(epoch) = self.epoch {
+            options = options.with_epoch(Epoch(epoch));
+        }
+        if let Some(created_after) = self.created_after {
+            options = options.with_created_after(created_after);
+        }
+        if self.oldest_first {
+            options = options.with_sort_order(ListSortOrder::OldestFirst);
+        }
+
+        options
+    }
+
     /// Main command execution
     pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> MithrilResult<()> {
         let config = config_builder.build()?;
         let params = ConfigParameters::new(config.try_deserialize::<HashMap<String, String>>()?);
         let client = client_builder_with_fallback_genesis_key(&params)?.build()?;
-        let lines = client.mithril_stake_distribution().list().await?;
+        let lines = client
+            .mithril_stake_distribution()
+            .list_with_options(&self.list_options())
+            .await?;
 
         if self.json {
             println!("{}", serde_json::to_string(&lines)?);
@@ -1,9 +1,11 @@
 //! Commands for the Mithril Stake Distribution artifact
 mod download;
 mod list;
+mod verify;
 
 pub use download::*;
 pub use list::*;
+pub use verify::*;
 
 use clap::Subcommand;
 use config::{builder::DefaultState, ConfigBuilder};
@@ -19,6 +21,10 @@ pub enum MithrilStakeDistributionCommands {
     /// Download and verify the given Mithril Stake Distribution
     #[clap(arg_required_else_help = false)]
     Download(MithrilStakeDistributionDownloadCommand),
+
+    /// Verify a previously downloaded Mithril Stake Distribution against its certificate
+    #[clap(arg_required_else_help = true)]
+    Verify(MithrilStakeDistributionVerifyCommand),
 }
 
 impl MithrilStakeDistributionCommands {
@@ -27,6 +33,7 @@ impl MithrilStakeDistributionCommands {
         match self {
             Self::List(cmd) => cmd.execute(config_builder).await,
             Self::Download(cmd) => cmd.execute(config_builder).await,
+            Self::Verify(cmd) => cmd.execute(config_builder).await,
         }
     }
 }
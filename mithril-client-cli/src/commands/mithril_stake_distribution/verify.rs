@@ -0,0 +1,367 @@
+use anyhow::{anyhow, Context};
+use clap::Parser;
+use config::{builder::DefaultState, ConfigBuilder, Map, Source, Value, ValueKind};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    commands::client_builder,
+    configuration::ConfigParameters,
+    utils::{ProgressOutputType, ProgressPrinter},
+};
+use mithril_client::{
+    common::{Epoch, ProtocolMessage, ProtocolMessagePartKey},
+    Client, MessageBuilder, MithrilCertificate, MithrilResult, MithrilStakeDistribution,
+};
+
+/// Clap command to verify a previously downloaded Mithril Stake Distribution file against its
+/// certificate, without downloading it again.
+///
+/// The previous certificates of the chain are read from the certificate cache set with the
+/// `--certificate-cache-directory` global option: once synchronized with `certificate sync` the
+/// verification does not need the aggregator.
+#[derive(Parser, Debug, Clone)]
+pub struct MithrilStakeDistributionVerifyCommand {
+    /// Enable JSON output.
+    #[clap(long)]
+    json: bool,
+
+    /// Path of the Mithril Stake Distribution file to verify.
+    file: PathBuf,
+
+    /// Path of a JSON file holding the certificate of the Mithril Stake Distribution.
+    #[clap(long)]
+    certificate_file: Option<PathBuf>,
+
+    /// Hash of the certificate of the Mithril Stake Distribution.
+    ///
+    /// By default, the certificate hash of the Mithril Stake Distribution file is used.
+    #[clap(long, conflicts_with = "certificate_file")]
+    certificate_hash: Option<String>,
+
+    /// Genesis Verification Key to check the certificate chain.
+    #[clap(long, env = "GENESIS_VERIFICATION_KEY")]
+    genesis_verification_key: Option<String>,
+}
+
+impl MithrilStakeDistributionVerifyCommand {
+    /// Command execution
+    pub async fn execute(&self, config_builder: ConfigBuilder<DefaultState>) -> MithrilResult<()> {
+        let config = config_builder.add_source(self.clone()).build()?;
+        let params = ConfigParameters::new(config.try_deserialize::<HashMap<String, String>>()?);
+        let client = client_builder(&params)?.build()?;
+
+        let progress_output_type = if self.json {
+            ProgressOutputType::JsonReporter
+        } else {
+            ProgressOutputType::Tty
+        };
+        let progress_printer = ProgressPrinter::new(progress_output_type, 3);
+
+        progress_printer.report_step(1, "Reading the Mithril stake distribution file…")?;
+        let mithril_stake_distribution = Self::read_mithril_stake_distribution_file(&self.file)?;
+
+        let certificate = self
+            .read_certificate_and_verify_chain(
+                2,
+                &progress_printer,
+                &client,
+                &mithril_stake_distribution,
+            )
+            .await?;
+
+        progress_printer.report_step(
+            3,
+            "Verify that the Mithril stake distribution is signed in the associated certificate",
+        )?;
+        let message = MessageBuilder::new()
+            .compute_mithril_stake_distribution_message_with_certificate(
+                &certificate,
+                &mithril_stake_distribution,
+            )
+            .with_context(|| {
+                "Can not compute the message for the given Mithril stake distribution"
+            })?;
+        verify_mithril_stake_distribution_signature(&certificate, &message)?;
+
+        let report = MithrilStakeDistributionVerificationReport::new(
+            &mithril_stake_distribution,
+            &certificate,
+            &message,
+        );
+        if self.json {
+            println!("{}", serde_json::to_string(&report)?);
+        } else {
+            println!(
+                "Mithril Stake Distribution '{}' has been successfully checked against the Mithril multi-signature contained in the certificate '{}'.",
+                report.mithril_stake_distribution_hash, report.certificate_hash
+            );
+            report.print_details();
+        }
+
+        Ok(())
+    }
+
+    async fn read_certificate_and_verify_chain(
+        &self,
+        step_number: u16,
+        progress_printer: &ProgressPrinter,
+        client: &Client,
+        mithril_stake_distribution: &MithrilStakeDistribution,
+    ) -> MithrilResult<MithrilCertificate> {
+        progress_printer.report_step(
+            step_number,
+            "Reading the certificate and verifying the certificate chain…",
+        )?;
+
+        if let Some(certificate_file) = &self.certificate_file {
+            let certificate = Self::read_certificate_file(certificate_file)?;
+            client
+                .certificate()
+                .verify_chain_from_certificate(&certificate)
+                .await?;
+
+            return Ok(certificate);
+        }
+
+        let certificate_hash = self
+            .certificate_hash
+            .as_ref()
+            .unwrap_or(&mithril_stake_distribution.certificate_hash);
+        client
+            .certificate()
+            .verify_chain(certificate_hash)
+            .await
+            .with_context(|| {
+                format!(
+                    "Can not verify the certificate chain from certificate_hash: '{certificate_hash}'"
+                )
+            })
+    }
+
+    fn read_mithril_stake_distribution_file(
+        file: &Path,
+    ) -> MithrilResult<MithrilStakeDistribution> {
+        let content = std::fs::read_to_string(file).with_context(|| {
+            format!(
+                "Could not read Mithril stake distribution file: '{}'",
+                file.display()
+            )
+        })?;
+
+        serde_json::from_str(&content).with_context(|| {
+            format!(
+                "Could not parse Mithril stake distribution file: '{}'",
+                file.display()
+            )
+        })
+    }
+
+    fn read_certificate_file(certificate_file: &Path) -> MithrilResult<MithrilCertificate> {
+        let content = std::fs::read_to_string(certificate_file).with_context(|| {
+            format!(
+                "Could not read certificate file: '{}'",
+                certificate_file.display()
+            )
+        })?;
+
+        serde_json::from_str(&content).with_context(|| {
+            format!(
+                "Could not parse certificate file: '{}'",
+                certificate_file.display()
+            )
+        })
+    }
+}
+
+/// Check that the message computed from a Mithril Stake Distribution is signed by the certificate
+pub(super) fn verify_mithril_stake_distribution_signature(
+    certificate: &MithrilCertificate,
+    message: &ProtocolMessage,
+) -> MithrilResult<()> {
+    if !certificate.match_message(message) {
+        return Err(anyhow!(
+            "Certificate and message did not match:\ncertificate_message: '{}'\n computed_message: '{}'",
+            certificate.signed_message,
+            message.compute_hash()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Report of the verification of a Mithril Stake Distribution
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(super) struct MithrilStakeDistributionVerificationReport {
+    pub mithril_stake_distribution_hash: String,
+    /// Hash of the certificate that signs the Mithril Stake Distribution
+    pub certificate_hash: String,
+    pub epoch: Epoch,
+    /// Aggregate verification key computed from the signers of the Mithril Stake Distribution
+    pub aggregate_verification_key: String,
+    pub signers_count: usize,
+    pub total_stake: u64,
+}
+
+impl MithrilStakeDistributionVerificationReport {
+    pub fn new(
+        mithril_stake_distribution: &MithrilStakeDistribution,
+        certificate: &MithrilCertificate,
+        message: &ProtocolMessage,
+    ) -> Self {
+        Self {
+            mithril_stake_distribution_hash: mithril_stake_distribution.hash.clone(),
+            certificate_hash: certificate.hash.clone(),
+            epoch: mithril_stake_distribution.epoch,
+            aggregate_verification_key: message
+                .get_message_part(&ProtocolMessagePartKey::NextAggregateVerificationKey)
+                .cloned()
+                .unwrap_or_default(),
+            signers_count: mithril_stake_distribution.signers_with_stake.len(),
+            total_stake: mithril_stake_distribution
+                .signers_with_stake
+                .iter()
+                .map(|signer| signer.stake)
+                .sum(),
+        }
+    }
+
+    pub fn print_details(&self) {
+        println!(
+            r###"
+Certificate hash: {}
+Epoch: {}
+Aggregate verification key: {}
+Signers: {}
+Total stake: {}"###,
+            self.certificate_hash,
+            self.epoch,
+            self.aggregate_verification_key,
+            self.signers_count,
+            self.total_stake,
+        );
+    }
+}
+
+impl Source for MithrilStakeDistributionVerifyCommand {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, config::ConfigError> {
+        let mut map = Map::new();
+        let namespace = "clap arguments".to_string();
+
+        if let Some(genesis_verification_key) = self.genesis_verification_key.clone() {
+            map.insert(
+                "genesis_verification_key".to_string(),
+                Value::new(Some(&namespace), ValueKind::from(genesis_verification_key)),
+            );
+        }
+
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_client::MithrilSigner;
+    use mithril_common::test_utils::TempDir;
+
+    use super::*;
+
+    fn certificate_signing_avk(avk: &str) -> MithrilCertificate {
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message.set_message_part(
+            ProtocolMessagePartKey::NextAggregateVerificationKey,
+            avk.to_string(),
+        );
+
+        MithrilCertificate {
+            protocol_message: protocol_message.clone(),
+            signed_message: protocol_message.compute_hash(),
+            ..MithrilCertificate::dummy()
+        }
+    }
+
+    #[test]
+    fn verify_mithril_stake_distribution_signature_succeed_if_messages_match() {
+        let certificate = certificate_signing_avk("avk");
+
+        verify_mithril_stake_distribution_signature(&certificate, &certificate.protocol_message)
+            .unwrap();
+    }
+
+    #[test]
+    fn verify_mithril_stake_distribution_signature_fail_if_messages_mismatch() {
+        let certificate = certificate_signing_avk("avk");
+        let mut message = ProtocolMessage::new();
+        message.set_message_part(
+            ProtocolMessagePartKey::NextAggregateVerificationKey,
+            "another-avk".to_string(),
+        );
+
+        verify_mithril_stake_distribution_signature(&certificate, &message)
+            .expect_err("verification should fail when the messages mismatch");
+    }
+
+    #[test]
+    fn verification_report_sums_the_stakes_of_the_signers() {
+        let mithril_stake_distribution = MithrilStakeDistribution {
+            signers_with_stake: vec![
+                MithrilSigner {
+                    stake: 10,
+                    ..MithrilSigner::dummy()
+                },
+                MithrilSigner {
+                    stake: 32,
+                    ..MithrilSigner::dummy()
+                },
+            ],
+            ..MithrilStakeDistribution::dummy()
+        };
+        let certificate = certificate_signing_avk("avk");
+
+        let report = MithrilStakeDistributionVerificationReport::new(
+            &mithril_stake_distribution,
+            &certificate,
+            &certificate.protocol_message,
+        );
+
+        assert_eq!(
+            MithrilStakeDistributionVerificationReport {
+                mithril_stake_distribution_hash: mithril_stake_distribution.hash.clone(),
+                certificate_hash: certificate.hash.clone(),
+                epoch: mithril_stake_distribution.epoch,
+                aggregate_verification_key: "avk".to_string(),
+                signers_count: 2,
+                total_stake: 42,
+            },
+            report
+        );
+    }
+
+    #[test]
+    fn read_mithril_stake_distribution_file_parse_a_downloaded_file() {
+        let dir = TempDir::create(
+            "client-cli",
+            "read_mithril_stake_distribution_file_parse_a_downloaded_file",
+        );
+        let file = dir.join("mithril_stake_distribution.json");
+        let mithril_stake_distribution = MithrilStakeDistribution::dummy();
+        std::fs::write(
+            &file,
+            serde_json::to_string(&mithril_stake_distribution).unwrap(),
+        )
+        .unwrap();
+
+        let read_mithril_stake_distribution =
+            MithrilStakeDistributionVerifyCommand::read_mithril_stake_distribution_file(&file)
+                .unwrap();
+
+        assert_eq!(mithril_stake_distribution, read_mithril_stake_distribution);
+    }
+}
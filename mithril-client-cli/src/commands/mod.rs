@@ -5,10 +5,13 @@
 
 pub mod cardano_db;
 pub mod cardano_transaction;
+pub mod certificate;
+pub mod completion;
 pub mod mithril_stake_distribution;
 
-use mithril_client::{ClientBuilder, MithrilResult};
+use mithril_client::{certificate_cache::FileCertificateCache, ClientBuilder, MithrilResult};
 use slog_scope::logger;
+use std::sync::Arc;
 
 use crate::configuration::ConfigParameters;
 
@@ -19,7 +22,7 @@ pub(crate) fn client_builder(params: &ConfigParameters) -> MithrilResult<ClientB
     )
     .with_logger(logger());
 
-    Ok(builder)
+    with_certificate_verification_settings(builder, params)
 }
 
 pub(crate) fn client_builder_with_fallback_genesis_key(
@@ -40,5 +43,22 @@ pub(crate) fn client_builder_with_fallback_genesis_key(
     )
     .with_logger(logger());
 
+    with_certificate_verification_settings(builder, params)
+}
+
+fn with_certificate_verification_settings(
+    mut builder: ClientBuilder,
+    params: &ConfigParameters,
+) -> MithrilResult<ClientBuilder> {
+    if let Some(directory) = params.get("certificate_cache_directory") {
+        builder = builder.with_certificate_cache(Arc::new(FileCertificateCache::new(directory)?));
+    }
+    if let Some(checkpoint_verification_key) =
+        params.get("certificate_chain_checkpoint_verification_key")
+    {
+        builder = builder
+            .with_certificate_chain_checkpoint_verification_key(&checkpoint_verification_key);
+    }
+
     Ok(builder)
 }
@@ -17,6 +17,8 @@ use mithril_doc::{Documenter, GenerateDocCommands, StructDoc};
 use mithril_client_cli::commands::{
     cardano_db::{deprecated::SnapshotCommands, CardanoDbCommands},
     cardano_transaction::CardanoTransactionCommands,
+    certificate::CertificateCommands,
+    completion::CompletionCommand,
     mithril_stake_distribution::MithrilStakeDistributionCommands,
 };
 
@@ -81,6 +83,24 @@ pub struct Args {
     /// Enable unstable commands (such as Cardano Transactions)
     #[clap(long)]
     unstable: bool,
+
+    /// Directory of the local certificate cache, read before requesting the aggregator when
+    /// verifying a certificate chain and filled by the `certificate sync` command.
+    #[clap(long, env = "CERTIFICATE_CACHE_DIRECTORY")]
+    #[example = "`./certificates`"]
+    certificate_cache_directory: Option<PathBuf>,
+
+    /// Verification key of the signed checkpoints of the aggregator certificate chain, the
+    /// verification of a certificate chain stops at the first certificate that has a checkpoint
+    /// with a valid signature instead of the genesis certificate.
+    #[clap(long, env = "CERTIFICATE_CHAIN_CHECKPOINT_VERIFICATION_KEY")]
+    certificate_chain_checkpoint_verification_key: Option<String>,
+
+    /// File in which a json snapshot of the progress (phase, percentage, estimated time left) of
+    /// the long running commands is written, to allow external tools to monitor them.
+    #[clap(long, env = "PROGRESS_FILE")]
+    #[example = "`./progress.json`"]
+    progress_file: Option<PathBuf>,
 }
 
 impl Args {
@@ -157,6 +177,38 @@ impl Source for Args {
             );
         }
 
+        if let Some(certificate_cache_directory) = self.certificate_cache_directory.clone() {
+            map.insert(
+                "certificate_cache_directory".to_string(),
+                Value::new(
+                    Some(&namespace),
+                    ValueKind::from(format!("{}", certificate_cache_directory.display())),
+                ),
+            );
+        }
+
+        if let Some(checkpoint_verification_key) =
+            self.certificate_chain_checkpoint_verification_key.clone()
+        {
+            map.insert(
+                "certificate_chain_checkpoint_verification_key".to_string(),
+                Value::new(
+                    Some(&namespace),
+                    ValueKind::from(checkpoint_verification_key),
+                ),
+            );
+        }
+
+        if let Some(progress_file) = self.progress_file.clone() {
+            map.insert(
+                "progress_file".to_string(),
+                Value::new(
+                    Some(&namespace),
+                    ValueKind::from(format!("{}", progress_file.display())),
+                ),
+            );
+        }
+
         Ok(map)
     }
 }
@@ -177,6 +229,12 @@ enum ArtifactCommands {
     #[clap(subcommand, alias("ctx"))]
     CardanoTransaction(CardanoTransactionCommands),
 
+    #[clap(subcommand)]
+    Certificate(CertificateCommands),
+
+    /// Generate the completion script of the CLI for a shell
+    Completion(CompletionCommand),
+
     #[clap(alias("doc"), hide(true))]
     GenerateDoc(GenerateDocCommands),
 }
@@ -212,6 +270,8 @@ impl ArtifactCommands {
                     ctx.execute(config_builder).await
                 }
             }
+            Self::Certificate(cmd) => cmd.execute(config_builder).await,
+            Self::Completion(cmd) => cmd.execute(&mut Args::command()),
             Self::GenerateDoc(cmd) => cmd
                 .execute(&mut Args::command())
                 .map_err(|message| anyhow!(message)),
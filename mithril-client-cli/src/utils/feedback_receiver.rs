@@ -1,9 +1,9 @@
 use async_trait::async_trait;
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressState, ProgressStyle};
-use std::fmt::Write;
+use std::{fmt::Write, sync::Arc};
 use tokio::sync::RwLock;
 
-use super::{DownloadProgressReporter, ProgressOutputType};
+use super::{DownloadProgressReporter, ProgressFile, ProgressOutputType};
 
 use mithril_client::feedback::{FeedbackReceiver, MithrilEvent};
 
@@ -13,6 +13,7 @@ pub struct IndicatifFeedbackReceiver {
     download_progress_reporter: RwLock<Option<DownloadProgressReporter>>,
     certificate_validation_pb: RwLock<Option<ProgressBar>>,
     output_type: ProgressOutputType,
+    progress_file: Option<Arc<ProgressFile>>,
 }
 
 impl IndicatifFeedbackReceiver {
@@ -22,8 +23,15 @@ impl IndicatifFeedbackReceiver {
             download_progress_reporter: RwLock::new(None),
             certificate_validation_pb: RwLock::new(None),
             output_type,
+            progress_file: None,
         }
     }
+
+    /// Also report the download progress to the given [ProgressFile]
+    pub fn with_progress_file(mut self, progress_file: Option<Arc<ProgressFile>>) -> Self {
+        self.progress_file = progress_file;
+        self
+    }
 }
 
 #[async_trait]
@@ -45,8 +53,10 @@ impl FeedbackReceiver for IndicatifFeedbackReceiver {
                     .with_key("eta", |state : &ProgressState, w: &mut dyn Write| write!(w, "{:.1}s", state.eta().as_secs_f64()).unwrap())
                     .progress_chars("#>-"));
                 let mut download_progress_reporter = self.download_progress_reporter.write().await;
-                *download_progress_reporter =
-                    Some(DownloadProgressReporter::new(pb, self.output_type));
+                *download_progress_reporter = Some(
+                    DownloadProgressReporter::new(pb, self.output_type)
+                        .with_progress_file(self.progress_file.clone()),
+                );
             }
             MithrilEvent::SnapshotDownloadProgress {
                 download_id: _,
@@ -5,10 +5,12 @@ mod cardano_db;
 mod cardano_db_download_checker;
 mod expander;
 mod feedback_receiver;
+mod progress_file;
 mod progress_reporter;
 
 pub use cardano_db::*;
 pub use cardano_db_download_checker::*;
 pub use expander::*;
 pub use feedback_receiver::*;
+pub use progress_file::*;
 pub use progress_reporter::*;
@@ -0,0 +1,234 @@
+use anyhow::Context;
+use chrono::Utc;
+use mithril_client::MithrilResult;
+use serde::Serialize;
+use std::{
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Minimal delay between two writes of the download progress in a [ProgressFile]
+const DOWNLOAD_REPORT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Snapshot of the progress of a command, written as json in a [ProgressFile]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProgressSnapshot {
+    /// Date and time of the snapshot
+    pub timestamp: String,
+    /// Description of the current phase of the command
+    pub phase: String,
+    /// Number of the current step
+    pub step_num: u16,
+    /// Total number of steps of the command
+    pub total_steps: u16,
+    /// Overall completion percentage of the command
+    pub percent: f64,
+    /// Estimated number of seconds left for the current download, if any
+    pub eta_seconds: Option<u64>,
+    /// Is the command completed
+    pub completed: bool,
+}
+
+struct ProgressFileState {
+    snapshot: ProgressSnapshot,
+    last_download_report_instant: Option<Instant>,
+}
+
+/// File in which the progress of a command is written as a json [ProgressSnapshot] so it can be
+/// monitored by external tools.
+///
+/// The file is replaced atomically on each update, a reader always gets a complete snapshot.
+pub struct ProgressFile {
+    path: PathBuf,
+    state: Mutex<ProgressFileState>,
+}
+
+impl ProgressFile {
+    /// [ProgressFile] constructor
+    pub fn new(path: &Path, total_steps: u16) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            state: Mutex::new(ProgressFileState {
+                snapshot: ProgressSnapshot {
+                    timestamp: Utc::now().to_rfc3339(),
+                    phase: String::new(),
+                    step_num: 0,
+                    total_steps,
+                    percent: 0.0,
+                    eta_seconds: None,
+                    completed: false,
+                },
+                last_download_report_instant: None,
+            }),
+        }
+    }
+
+    /// Report the start of a step
+    pub fn report_step(&self, step_number: u16, phase: &str) -> MithrilResult<()> {
+        let mut state = self.lock_state()?;
+        state.snapshot.phase = phase.to_string();
+        state.snapshot.step_num = step_number;
+        state.snapshot.percent =
+            Self::compute_percent(step_number, state.snapshot.total_steps, 0.0);
+        state.snapshot.eta_seconds = None;
+        state.last_download_report_instant = None;
+
+        self.write(&mut state.snapshot)
+    }
+
+    /// Report the progress of the download of the current step, the file is not written more
+    /// than once every 500ms.
+    pub fn report_download(
+        &self,
+        downloaded_bytes: u64,
+        total_bytes: u64,
+        eta: Duration,
+    ) -> MithrilResult<()> {
+        let mut state = self.lock_state()?;
+        let is_finished = downloaded_bytes >= total_bytes;
+        let should_report = is_finished
+            || state
+                .last_download_report_instant
+                .map(|instant| instant.elapsed() >= DOWNLOAD_REPORT_INTERVAL)
+                .unwrap_or(true);
+        if !should_report {
+            return Ok(());
+        }
+
+        let fraction = if total_bytes == 0 {
+            1.0
+        } else {
+            (downloaded_bytes as f64 / total_bytes as f64).min(1.0)
+        };
+        state.snapshot.percent = Self::compute_percent(
+            state.snapshot.step_num,
+            state.snapshot.total_steps,
+            fraction,
+        );
+        state.snapshot.eta_seconds = Some(eta.as_secs());
+        state.last_download_report_instant = Some(Instant::now());
+
+        self.write(&mut state.snapshot)
+    }
+
+    /// Report that the command is completed
+    pub fn finish(&self) -> MithrilResult<()> {
+        let mut state = self.lock_state()?;
+        state.snapshot.percent = 100.0;
+        state.snapshot.eta_seconds = None;
+        state.snapshot.completed = true;
+
+        self.write(&mut state.snapshot)
+    }
+
+    fn compute_percent(step_number: u16, total_steps: u16, step_fraction: f64) -> f64 {
+        if total_steps == 0 {
+            return 0.0;
+        }
+        let completed_steps = step_number.saturating_sub(1) as f64 + step_fraction;
+
+        (completed_steps / total_steps as f64 * 100.0).min(100.0)
+    }
+
+    fn lock_state(&self) -> MithrilResult<std::sync::MutexGuard<'_, ProgressFileState>> {
+        self.state
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Could not lock the progress file state: {e}"))
+    }
+
+    fn write(&self, snapshot: &mut ProgressSnapshot) -> MithrilResult<()> {
+        snapshot.timestamp = Utc::now().to_rfc3339();
+        let temporary_path = self.path.with_extension("tmp");
+        std::fs::write(&temporary_path, serde_json::to_vec(snapshot)?).with_context(|| {
+            format!(
+                "Could not write progress file '{}'",
+                temporary_path.display()
+            )
+        })?;
+        std::fs::rename(&temporary_path, &self.path).with_context(|| {
+            format!("Could not replace progress file '{}'", self.path.display())
+        })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::TempDir;
+
+    use super::*;
+
+    fn read_snapshot(path: &Path) -> serde_json::Value {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn write_the_current_step_and_its_percentage() {
+        let path = TempDir::create("client-cli-progress-file", "write_the_current_step")
+            .join("progress.json");
+        let progress_file = ProgressFile::new(&path, 4);
+
+        progress_file.report_step(3, "Downloading").unwrap();
+
+        let snapshot = read_snapshot(&path);
+        assert_eq!(snapshot["phase"], "Downloading");
+        assert_eq!(snapshot["step_num"], 3);
+        assert_eq!(snapshot["total_steps"], 4);
+        assert_eq!(snapshot["percent"], 50.0);
+        assert_eq!(snapshot["eta_seconds"], serde_json::Value::Null);
+        assert_eq!(snapshot["completed"], false);
+    }
+
+    #[test]
+    fn download_progress_is_added_to_the_percentage_of_the_current_step() {
+        let path =
+            TempDir::create("client-cli-progress-file", "download_progress").join("progress.json");
+        let progress_file = ProgressFile::new(&path, 4);
+        progress_file.report_step(3, "Downloading").unwrap();
+
+        progress_file
+            .report_download(50, 100, Duration::from_secs(12))
+            .unwrap();
+
+        let snapshot = read_snapshot(&path);
+        assert_eq!(snapshot["percent"], 62.5);
+        assert_eq!(snapshot["eta_seconds"], 12);
+    }
+
+    #[test]
+    fn download_progress_writes_are_throttled_until_the_download_is_finished() {
+        let path = TempDir::create("client-cli-progress-file", "download_progress_throttled")
+            .join("progress.json");
+        let progress_file = ProgressFile::new(&path, 1);
+        progress_file.report_step(1, "Downloading").unwrap();
+
+        progress_file
+            .report_download(10, 100, Duration::from_secs(9))
+            .unwrap();
+        progress_file
+            .report_download(20, 100, Duration::from_secs(8))
+            .unwrap();
+        assert_eq!(read_snapshot(&path)["percent"], 10.0);
+
+        progress_file
+            .report_download(100, 100, Duration::ZERO)
+            .unwrap();
+        assert_eq!(read_snapshot(&path)["percent"], 100.0);
+    }
+
+    #[test]
+    fn finish_mark_the_progress_as_completed() {
+        let path = TempDir::create("client-cli-progress-file", "finish").join("progress.json");
+        let progress_file = ProgressFile::new(&path, 5);
+        progress_file.report_step(2, "Verifying").unwrap();
+
+        progress_file.finish().unwrap();
+
+        let snapshot = read_snapshot(&path);
+        assert_eq!(snapshot["percent"], 100.0);
+        assert_eq!(snapshot["completed"], true);
+        assert!(!path.with_extension("tmp").exists());
+    }
+}
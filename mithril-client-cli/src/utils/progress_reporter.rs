@@ -2,9 +2,11 @@ use chrono::Utc;
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget};
 use mithril_client::MithrilResult;
 use slog_scope::warn;
+
+use super::ProgressFile;
 use std::{
     ops::Deref,
-    sync::RwLock,
+    sync::{Arc, RwLock},
     time::{Duration, Instant},
 };
 
@@ -34,6 +36,7 @@ pub struct ProgressPrinter {
     multi_progress: MultiProgress,
     output_type: ProgressOutputType,
     number_of_steps: u16,
+    progress_file: Option<Arc<ProgressFile>>,
 }
 
 impl ProgressPrinter {
@@ -43,9 +46,16 @@ impl ProgressPrinter {
             multi_progress: MultiProgress::with_draw_target(output_type.into()),
             output_type,
             number_of_steps,
+            progress_file: None,
         }
     }
 
+    /// Also report the steps to the given [ProgressFile]
+    pub fn with_progress_file(mut self, progress_file: Option<Arc<ProgressFile>>) -> Self {
+        self.progress_file = progress_file;
+        self
+    }
+
     /// Report the current step
     pub fn report_step(&self, step_number: u16, text: &str) -> MithrilResult<()> {
         match self.output_type {
@@ -59,6 +69,9 @@ impl ProgressPrinter {
                 .println(format!("{step_number}/{} - {text}", self.number_of_steps))?,
             ProgressOutputType::Hidden => (),
         };
+        if let Some(progress_file) = &self.progress_file {
+            progress_file.report_step(step_number, text)?;
+        }
 
         Ok(())
     }
@@ -96,6 +109,7 @@ pub struct DownloadProgressReporter {
     progress_bar: ProgressBar,
     output_type: ProgressOutputType,
     last_json_report_instant: RwLock<Option<Instant>>,
+    progress_file: Option<Arc<ProgressFile>>,
 }
 
 impl DownloadProgressReporter {
@@ -105,13 +119,32 @@ impl DownloadProgressReporter {
             progress_bar,
             output_type,
             last_json_report_instant: RwLock::new(None),
+            progress_file: None,
         }
     }
 
+    /// Also report the download progress to the given [ProgressFile]
+    pub fn with_progress_file(mut self, progress_file: Option<Arc<ProgressFile>>) -> Self {
+        self.progress_file = progress_file;
+        self
+    }
+
     /// Report the current progress
     pub fn report(&self, actual_position: u64) {
         self.progress_bar.set_position(actual_position);
 
+        if let Some(progress_file) = &self.progress_file {
+            if let Err(error) = progress_file.report_download(
+                actual_position,
+                self.progress_bar.length().unwrap_or(0),
+                self.progress_bar.eta(),
+            ) {
+                warn!(
+                    "failed to write the download progress to the progress file, error: {error:?}"
+                );
+            }
+        }
+
         if let ProgressOutputType::JsonReporter = self.output_type {
             let should_report = match self.get_remaining_time_since_last_json_report() {
                 Some(remaining_time) => remaining_time > Duration::from_millis(333),
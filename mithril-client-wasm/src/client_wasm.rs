@@ -154,6 +154,28 @@ impl MithrilClient {
         Ok(serde_wasm_bindgen::to_value(&result)?)
     }
 
+    /// Call the client to compute a mithril stake distribution message from the protocol message
+    /// of its certificate
+    #[wasm_bindgen]
+    pub async fn compute_mithril_stake_distribution_message_with_certificate(
+        &self,
+        stake_distribution: JsValue,
+        certificate: JsValue,
+    ) -> WasmResult {
+        let stake_distribution =
+            serde_wasm_bindgen::from_value(stake_distribution).map_err(|err| format!("{err:?}"))?;
+        let certificate =
+            serde_wasm_bindgen::from_value(certificate).map_err(|err| format!("{err:?}"))?;
+        let result = MessageBuilder::new()
+            .compute_mithril_stake_distribution_message_with_certificate(
+                &certificate,
+                &stake_distribution,
+            )
+            .map_err(|err| format!("{err:?}"))?;
+
+        Ok(serde_wasm_bindgen::to_value(&result)?)
+    }
+
     /// Call the client to verify a mithril stake distribution message
     #[wasm_bindgen]
     pub async fn verify_message_match_certificate(
@@ -451,6 +473,32 @@ mod tests {
             .expect("conversion should not fail");
     }
 
+    #[wasm_bindgen_test]
+    async fn compute_mithril_stake_distribution_message_with_certificate_should_return_value_convertible_in_rust_type(
+    ) {
+        let client = get_mithril_client();
+        let msd_js_value = client
+            .get_mithril_stake_distribution(test_data::msd_hashes()[0])
+            .await
+            .unwrap();
+        let msd = serde_wasm_bindgen::from_value::<MithrilStakeDistribution>(msd_js_value.clone())
+            .unwrap();
+        let certificate_js_value = client
+            .get_mithril_certificate(&msd.certificate_hash)
+            .await
+            .unwrap();
+
+        let message_js_value = client
+            .compute_mithril_stake_distribution_message_with_certificate(
+                msd_js_value,
+                certificate_js_value,
+            )
+            .await
+            .expect("compute_mithril_stake_distribution_message_with_certificate should not fail");
+        serde_wasm_bindgen::from_value::<ProtocolMessage>(message_js_value)
+            .expect("conversion should not fail");
+    }
+
     #[wasm_bindgen_test]
     async fn verify_certificate_chain_should_return_value_convertible_in_rust_type() {
         let client = get_mithril_client();
@@ -482,7 +530,10 @@ mod tests {
             .await
             .unwrap();
         let message_js_value = client
-            .compute_mithril_stake_distribution_message(msd_js_value)
+            .compute_mithril_stake_distribution_message_with_certificate(
+                msd_js_value,
+                last_certificate_js_value.clone(),
+            )
             .await
             .unwrap();
 
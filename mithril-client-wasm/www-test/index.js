@@ -104,12 +104,13 @@ await run_test("verify_certificate_chain", test_number, async () => {
 let mithril_stake_distribution_message;
 test_number++;
 await run_test(
-  "compute_mithril_stake_distribution_message",
+  "compute_mithril_stake_distribution_message_with_certificate",
   test_number,
   async () => {
     mithril_stake_distribution_message =
-      await client.compute_mithril_stake_distribution_message(
-        mithril_stake_distribution
+      await client.compute_mithril_stake_distribution_message_with_certificate(
+        mithril_stake_distribution,
+        last_certificate_from_chain
       );
     console.log(
       "mithril_stake_distribution_message",
@@ -6,22 +6,33 @@
 //! [AggregatorRequest] enum.
 //!
 //! An implementation using HTTP is available: [AggregatorHTTPClient].
+//!
+//! The requests sent by the [AggregatorHTTPClient] can be altered by a chain of
+//! [AggregatorRequestMiddleware], i.e. to add authentication or custom headers
+//! ([AggregatorRequestHeaders]).
+//!
+//! Several aggregators can be used with automatic failover using the
+//! [AggregatorFailoverClient][crate::aggregator_failover::AggregatorFailoverClient].
 
 use anyhow::{anyhow, Context};
 use async_recursion::async_recursion;
 use async_trait::async_trait;
-use reqwest::{Response, StatusCode, Url};
+use lru::LruCache;
+use reqwest::header::{HeaderName, HeaderValue, ETAG, IF_NONE_MATCH};
+use reqwest::{Method, RequestBuilder, Response, StatusCode, Url};
 use semver::Version;
 use slog::{debug, Logger};
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 
 #[cfg(test)]
 use mockall::automock;
 
 use mithril_common::MITHRIL_API_VERSION_HEADER;
 
+use crate::list_options::ListOptions;
 use crate::{MithrilError, MithrilResult};
 
 /// Error tied with the Aggregator client
@@ -54,6 +65,8 @@ pub enum AggregatorRequest {
     },
     /// Lists the aggregator [certificates][crate::MithrilCertificate]
     ListCertificates,
+    /// Lists the signed checkpoints of the aggregator certificate chain
+    ListCertificateChainCheckpoints,
     /// Get a specific [Mithril stake distribution][crate::MithrilStakeDistribution] from the aggregator
     GetMithrilStakeDistribution {
         /// Hash of the Mithril stake distribution to retrieve
@@ -61,6 +74,12 @@ pub enum AggregatorRequest {
     },
     /// Lists the aggregator [Mithril stake distribution][crate::MithrilStakeDistribution]
     ListMithrilStakeDistributions,
+    /// Lists the aggregator [Mithril stake distribution][crate::MithrilStakeDistribution] matching
+    /// the given options
+    ListMithrilStakeDistributionsWithOptions {
+        /// Filter and sort options
+        options: ListOptions,
+    },
     /// Get a specific [snapshot][crate::Snapshot] from the aggregator
     GetSnapshot {
         /// Digest of the snapshot to retrieve
@@ -68,6 +87,14 @@ pub enum AggregatorRequest {
     },
     /// Lists the aggregator [snapshots][crate::Snapshot]
     ListSnapshots,
+    /// Lists the aggregator [snapshots][crate::Snapshot] matching the given options
+    ListSnapshotsWithOptions {
+        /// Filter and sort options
+        options: ListOptions,
+    },
+
+    /// Get the readiness of the aggregator, used to probe its health
+    GetHealthReady,
 
     /// Increments the aggregator snapshot download statistics
     IncrementSnapshotStatistic {
@@ -102,16 +129,26 @@ impl AggregatorRequest {
                 format!("certificate/{hash}")
             }
             AggregatorRequest::ListCertificates => "certificates".to_string(),
+            AggregatorRequest::ListCertificateChainCheckpoints => {
+                "certificate-chain-checkpoints".to_string()
+            }
             AggregatorRequest::GetMithrilStakeDistribution { hash } => {
                 format!("artifact/mithril-stake-distribution/{hash}")
             }
             AggregatorRequest::ListMithrilStakeDistributions => {
                 "artifact/mithril-stake-distributions".to_string()
             }
+            AggregatorRequest::ListMithrilStakeDistributionsWithOptions { options } => {
+                Self::route_with_query("artifact/mithril-stake-distributions", options)
+            }
             AggregatorRequest::GetSnapshot { digest } => {
                 format!("artifact/snapshot/{}", digest)
             }
             AggregatorRequest::ListSnapshots => "artifact/snapshots".to_string(),
+            AggregatorRequest::ListSnapshotsWithOptions { options } => {
+                Self::route_with_query("artifact/snapshots", options)
+            }
+            AggregatorRequest::GetHealthReady => "health/ready".to_string(),
             AggregatorRequest::IncrementSnapshotStatistic { snapshot: _ } => {
                 "statistics/snapshot".to_string()
             }
@@ -142,6 +179,13 @@ impl AggregatorRequest {
             _ => None,
         }
     }
+
+    fn route_with_query(route: &str, options: &ListOptions) -> String {
+        match options.to_query_string() {
+            query if query.is_empty() => route.to_string(),
+            query => format!("{route}?{query}"),
+        }
+    }
 }
 
 /// API that defines a client for the Aggregator
@@ -161,11 +205,102 @@ pub trait AggregatorClient: Sync + Send {
     ) -> Result<String, AggregatorClientError>;
 }
 
+/// Context of a request sent to the Aggregator by the [AggregatorHTTPClient].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregatorRequestContext {
+    /// The request sent to the Aggregator
+    pub request: AggregatorRequest,
+
+    /// HTTP method of the request
+    pub method: Method,
+
+    /// Url of the request
+    pub url: Url,
+}
+
+/// Middleware applied by the [AggregatorHTTPClient] to all the requests it sends to the
+/// Aggregator.
+///
+/// The middlewares are applied in the order they were added to the client, each time a request is
+/// sent (a request can be sent more than once if its API version is not supported).
+pub trait AggregatorRequestMiddleware: Sync + Send {
+    /// Alter the request before it is sent, an error aborts the request.
+    fn on_request(
+        &self,
+        context: &AggregatorRequestContext,
+        request_builder: RequestBuilder,
+    ) -> MithrilResult<RequestBuilder>;
+
+    /// Inspect the response received from the Aggregator.
+    fn on_response(&self, _context: &AggregatorRequestContext, _response: &Response) {}
+}
+
+/// [AggregatorRequestMiddleware] adding the same headers to all the requests, i.e. an
+/// authorization token or a user agent.
+pub struct AggregatorRequestHeaders {
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl AggregatorRequestHeaders {
+    /// Constructs a new `AggregatorRequestHeaders`, fails if a header name or value is invalid.
+    pub fn new<K: AsRef<str>, V: AsRef<str>>(headers: &[(K, V)]) -> MithrilResult<Self> {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| {
+                let (name, value) = (name.as_ref(), value.as_ref());
+                let header_name = HeaderName::from_bytes(name.as_bytes())
+                    .with_context(|| format!("Invalid header name: '{name}'"))?;
+                let header_value = HeaderValue::from_str(value)
+                    .with_context(|| format!("Invalid value for header '{name}'"))?;
+
+                Ok((header_name, header_value))
+            })
+            .collect::<MithrilResult<Vec<_>>>()?;
+
+        Ok(Self { headers })
+    }
+}
+
+impl AggregatorRequestMiddleware for AggregatorRequestHeaders {
+    fn on_request(
+        &self,
+        _context: &AggregatorRequestContext,
+        request_builder: RequestBuilder,
+    ) -> MithrilResult<RequestBuilder> {
+        Ok(self
+            .headers
+            .iter()
+            .fold(request_builder, |request_builder, (name, value)| {
+                request_builder.header(name, value)
+            }))
+    }
+}
+
+/// Number of contents cached by the [AggregatorHTTPClient], the least recently used ones are
+/// evicted first
+const CONTENTS_CACHE_CAPACITY: usize = 128;
+
+/// Maximum size, in bytes, of a content cached by the [AggregatorHTTPClient]
+const CONTENTS_CACHE_MAX_CONTENT_SIZE: usize = 1024 * 1024;
+
+/// *Internal type* Content received from the Aggregator along with its entity tag.
+#[derive(Debug, Clone)]
+struct CachedContent {
+    etag: String,
+    content: String,
+}
+
 /// Responsible for HTTP transport and API version check.
+///
+/// The contents received with an entity tag are cached per url so that the next requests are
+/// conditional and the Aggregator can answer them without a body if the content did not change.
+/// The cache is bounded: it keeps the most recently used contents and skips the largest ones.
 pub struct AggregatorHTTPClient {
     http_client: reqwest::Client,
     aggregator_endpoint: Url,
     api_versions: Arc<RwLock<Vec<Version>>>,
+    contents_cache: Mutex<LruCache<Url, CachedContent>>,
+    middlewares: Vec<Arc<dyn AggregatorRequestMiddleware>>,
     logger: Logger,
 }
 
@@ -195,10 +330,46 @@ impl AggregatorHTTPClient {
             http_client,
             aggregator_endpoint,
             api_versions: Arc::new(RwLock::new(api_versions)),
+            contents_cache: Mutex::new(LruCache::new(
+                NonZeroUsize::new(CONTENTS_CACHE_CAPACITY).unwrap(),
+            )),
+            middlewares: vec![],
             logger,
         })
     }
 
+    /// Add a [middleware][AggregatorRequestMiddleware] applied to all the requests sent to the
+    /// Aggregator, after the middlewares already added.
+    pub fn with_middleware(mut self, middleware: Arc<dyn AggregatorRequestMiddleware>) -> Self {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    fn apply_request_middlewares(
+        &self,
+        context: &AggregatorRequestContext,
+        request_builder: RequestBuilder,
+    ) -> Result<RequestBuilder, AggregatorClientError> {
+        self.middlewares
+            .iter()
+            .try_fold(request_builder, |request_builder, middleware| {
+                middleware.on_request(context, request_builder)
+            })
+            .with_context(|| {
+                format!(
+                    "A request middleware failed (method='{}', url='{}')",
+                    context.method, context.url
+                )
+            })
+            .map_err(AggregatorClientError::SubsystemError)
+    }
+
+    fn apply_response_middlewares(&self, context: &AggregatorRequestContext, response: &Response) {
+        for middleware in &self.middlewares {
+            middleware.on_response(context, response);
+        }
+    }
+
     /// Computes the current api version
     async fn compute_current_api_version(&self) -> Option<Version> {
         self.api_versions.read().await.first().cloned()
@@ -226,7 +397,11 @@ impl AggregatorHTTPClient {
     /// Perform a HTTP GET request on the Aggregator and return the given JSON
     #[cfg_attr(target_family = "wasm", async_recursion(?Send))]
     #[cfg_attr(not(target_family = "wasm"), async_recursion)]
-    async fn get(&self, url: Url) -> Result<Response, AggregatorClientError> {
+    async fn get(
+        &self,
+        context: &AggregatorRequestContext,
+    ) -> Result<Response, AggregatorClientError> {
+        let url = &context.url;
         debug!(self.logger, "GET url='{url}'.");
         let request_builder = self.http_client.get(url.clone());
         let current_api_version = self
@@ -240,19 +415,27 @@ impl AggregatorHTTPClient {
         );
         let request_builder =
             request_builder.header(MITHRIL_API_VERSION_HEADER, current_api_version);
+        let request_builder = match self.contents_cache.lock().await.peek(url) {
+            Some(cached_content) => {
+                request_builder.header(IF_NONE_MATCH, cached_content.etag.clone())
+            }
+            None => request_builder,
+        };
+        let request_builder = self.apply_request_middlewares(context, request_builder)?;
         let response = request_builder.send().await.map_err(|e| {
             AggregatorClientError::SubsystemError(anyhow!(e).context(format!(
                 "Cannot perform a GET against the Aggregator HTTP server (url='{url}')"
             )))
         })?;
+        self.apply_response_middlewares(context, &response);
 
         match response.status() {
-            StatusCode::OK => Ok(response),
+            StatusCode::OK | StatusCode::NOT_MODIFIED => Ok(response),
             StatusCode::PRECONDITION_FAILED => {
                 if self.discard_current_api_version().await.is_some()
                     && !self.api_versions.read().await.is_empty()
                 {
-                    return self.get(url).await;
+                    return self.get(context).await;
                 }
 
                 Err(self.handle_api_error(&response).await)
@@ -268,7 +451,12 @@ impl AggregatorHTTPClient {
 
     #[cfg_attr(target_family = "wasm", async_recursion(?Send))]
     #[cfg_attr(not(target_family = "wasm"), async_recursion)]
-    async fn post(&self, url: Url, json: &str) -> Result<Response, AggregatorClientError> {
+    async fn post(
+        &self,
+        context: &AggregatorRequestContext,
+        json: &str,
+    ) -> Result<Response, AggregatorClientError> {
+        let url = &context.url;
         debug!(self.logger, "POST url='{url}' json='{json}'.");
         let request_builder = self.http_client.post(url.to_owned()).body(json.to_owned());
         let current_api_version = self
@@ -282,12 +470,14 @@ impl AggregatorHTTPClient {
         );
         let request_builder =
             request_builder.header(MITHRIL_API_VERSION_HEADER, current_api_version);
+        let request_builder = self.apply_request_middlewares(context, request_builder)?;
 
         let response = request_builder.send().await.map_err(|e| {
             AggregatorClientError::SubsystemError(
                 anyhow!(e).context("Error while POSTing data '{json}' to URL='{url}'."),
             )
         })?;
+        self.apply_response_middlewares(context, &response);
 
         match response.status() {
             StatusCode::OK | StatusCode::CREATED => Ok(response),
@@ -295,7 +485,7 @@ impl AggregatorHTTPClient {
                 if self.discard_current_api_version().await.is_some()
                     && !self.api_versions.read().await.is_empty()
                 {
-                    return self.post(url, json).await;
+                    return self.post(context, json).await;
                 }
 
                 Err(self.handle_api_error(&response).await)
@@ -346,26 +536,74 @@ impl AggregatorClient for AggregatorHTTPClient {
         &self,
         request: AggregatorRequest,
     ) -> Result<String, AggregatorClientError> {
-        let response = self.get(self.get_url_for_route(&request.route())?).await?;
-        let content = format!("{response:?}");
+        let url = self.get_url_for_route(&request.route())?;
+        let response = self
+            .get(&AggregatorRequestContext {
+                request,
+                method: Method::GET,
+                url: url.clone(),
+            })
+            .await?;
 
-        response.text().await.map_err(|e| {
+        if response.status() == StatusCode::NOT_MODIFIED {
+            debug!(
+                self.logger,
+                "Content not modified, using cached content (url='{url}')."
+            );
+            return self
+                .contents_cache
+                .lock()
+                .await
+                .get(&url)
+                .map(|cached_content| cached_content.content.clone())
+                .ok_or_else(|| {
+                    AggregatorClientError::RemoteServerLogical(anyhow!(
+                        "Content of url='{url}' not modified but it is not in cache"
+                    ))
+                });
+        }
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(|etag| etag.to_string());
+        let response_details = format!("{response:?}");
+        let content = response.text().await.map_err(|e| {
             AggregatorClientError::SubsystemError(anyhow!(e).context(format!(
-                "Could not find a JSON body in the response '{content}'."
+                "Could not find a JSON body in the response '{response_details}'."
             )))
-        })
+        })?;
+
+        match etag {
+            Some(etag) if content.len() <= CONTENTS_CACHE_MAX_CONTENT_SIZE => {
+                self.contents_cache.lock().await.put(
+                    url,
+                    CachedContent {
+                        etag,
+                        content: content.clone(),
+                    },
+                );
+            }
+            _ => {
+                self.contents_cache.lock().await.pop(&url);
+            }
+        }
+
+        Ok(content)
     }
 
     async fn post_content(
         &self,
         request: AggregatorRequest,
     ) -> Result<String, AggregatorClientError> {
-        let response = self
-            .post(
-                self.get_url_for_route(&request.route())?,
-                &request.get_body().unwrap_or_default(),
-            )
-            .await?;
+        let body = request.get_body().unwrap_or_default();
+        let context = AggregatorRequestContext {
+            url: self.get_url_for_route(&request.route())?,
+            method: Method::POST,
+            request,
+        };
+        let response = self.post(&context, &body).await?;
 
         response.text().await.map_err(|e| {
             AggregatorClientError::SubsystemError(
@@ -377,6 +615,9 @@ impl AggregatorClient for AggregatorHTTPClient {
 
 #[cfg(test)]
 mod tests {
+    use crate::common::Epoch;
+    use crate::list_options::ListSortOrder;
+
     use super::*;
 
     #[test]
@@ -411,6 +652,11 @@ mod tests {
             .route()
         );
 
+        assert_eq!(
+            "certificate-chain-checkpoints".to_string(),
+            AggregatorRequest::ListCertificateChainCheckpoints.route()
+        );
+
         assert_eq!(
             "artifact/mithril-stake-distribution/abc".to_string(),
             AggregatorRequest::GetMithrilStakeDistribution {
@@ -432,6 +678,14 @@ mod tests {
             AggregatorRequest::ListMithrilStakeDistributions.route()
         );
 
+        assert_eq!(
+            "artifact/mithril-stake-distributions?epoch=5".to_string(),
+            AggregatorRequest::ListMithrilStakeDistributionsWithOptions {
+                options: ListOptions::new().with_epoch(Epoch(5))
+            }
+            .route()
+        );
+
         assert_eq!(
             "artifact/snapshot/abc".to_string(),
             AggregatorRequest::GetSnapshot {
@@ -445,6 +699,29 @@ mod tests {
             AggregatorRequest::ListSnapshots.route()
         );
 
+        assert_eq!(
+            "artifact/snapshots".to_string(),
+            AggregatorRequest::ListSnapshotsWithOptions {
+                options: ListOptions::new()
+            }
+            .route()
+        );
+
+        assert_eq!(
+            "artifact/snapshots?epoch=5&order=asc".to_string(),
+            AggregatorRequest::ListSnapshotsWithOptions {
+                options: ListOptions::new()
+                    .with_epoch(Epoch(5))
+                    .with_sort_order(ListSortOrder::OldestFirst)
+            }
+            .route()
+        );
+
+        assert_eq!(
+            "health/ready".to_string(),
+            AggregatorRequest::GetHealthReady.route()
+        );
+
         assert_eq!(
             "statistics/snapshot".to_string(),
             AggregatorRequest::IncrementSnapshotStatistic {
@@ -482,4 +759,209 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn get_content_use_cached_content_when_not_modified() {
+        let server = httpmock::MockServer::start();
+        let first_request_mock = server.mock(|when, then| {
+            when.path("/certificates").header_missing("if-none-match");
+            then.status(200)
+                .header("etag", "\"etag-value\"")
+                .body("certificates");
+        });
+        let conditional_request_mock = server.mock(|when, then| {
+            when.path("/certificates")
+                .header("if-none-match", "\"etag-value\"");
+            then.status(304).header("etag", "\"etag-value\"");
+        });
+        let client = AggregatorHTTPClient::new(
+            Url::parse(&server.base_url()).unwrap(),
+            vec![Version::new(0, 1, 0)],
+            crate::test_utils::test_logger(),
+        )
+        .unwrap();
+
+        for _ in 0..2 {
+            let content = client
+                .get_content(AggregatorRequest::ListCertificates)
+                .await
+                .unwrap();
+
+            assert_eq!("certificates", content);
+        }
+        first_request_mock.assert_hits(1);
+        conditional_request_mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn get_content_does_not_cache_content_larger_than_the_max_size() {
+        let server = httpmock::MockServer::start();
+        let large_content = "a".repeat(CONTENTS_CACHE_MAX_CONTENT_SIZE + 1);
+        let request_mock = server.mock(|when, then| {
+            when.path("/certificates").header_missing("if-none-match");
+            then.status(200)
+                .header("etag", "\"etag-value\"")
+                .body(&large_content);
+        });
+        let client = AggregatorHTTPClient::new(
+            Url::parse(&server.base_url()).unwrap(),
+            vec![Version::new(0, 1, 0)],
+            crate::test_utils::test_logger(),
+        )
+        .unwrap();
+
+        for _ in 0..2 {
+            client
+                .get_content(AggregatorRequest::ListCertificates)
+                .await
+                .unwrap();
+        }
+        request_mock.assert_hits(2);
+    }
+
+    mod middlewares {
+        use std::sync::Mutex;
+
+        use super::*;
+
+        struct FailingMiddleware;
+
+        impl AggregatorRequestMiddleware for FailingMiddleware {
+            fn on_request(
+                &self,
+                _context: &AggregatorRequestContext,
+                _request_builder: RequestBuilder,
+            ) -> MithrilResult<RequestBuilder> {
+                Err(anyhow!("middleware failure"))
+            }
+        }
+
+        #[derive(Default)]
+        struct RecordingMiddleware {
+            responses: Mutex<Vec<(AggregatorRequestContext, StatusCode)>>,
+        }
+
+        impl AggregatorRequestMiddleware for RecordingMiddleware {
+            fn on_request(
+                &self,
+                _context: &AggregatorRequestContext,
+                request_builder: RequestBuilder,
+            ) -> MithrilResult<RequestBuilder> {
+                Ok(request_builder)
+            }
+
+            fn on_response(&self, context: &AggregatorRequestContext, response: &Response) {
+                self.responses
+                    .lock()
+                    .unwrap()
+                    .push((context.clone(), response.status()));
+            }
+        }
+
+        fn build_client(server: &httpmock::MockServer) -> AggregatorHTTPClient {
+            AggregatorHTTPClient::new(
+                Url::parse(&server.base_url()).unwrap(),
+                vec![Version::new(0, 1, 0)],
+                crate::test_utils::test_logger(),
+            )
+            .unwrap()
+        }
+
+        #[test]
+        fn headers_with_invalid_name_or_value_are_rejected() {
+            AggregatorRequestHeaders::new(&[("invalid name", "value")])
+                .expect_err("A header name with a space should be rejected");
+            AggregatorRequestHeaders::new(&[("name", "invalid\nvalue")])
+                .expect_err("A header value with a new line should be rejected");
+        }
+
+        #[tokio::test]
+        async fn headers_are_added_to_get_and_post_requests() {
+            let server = httpmock::MockServer::start();
+            let get_mock = server.mock(|when, then| {
+                when.method(httpmock::Method::GET)
+                    .path("/certificates")
+                    .header("authorization", "Bearer token")
+                    .header("user-agent", "my-application/1.0");
+                then.status(200).body("certificates");
+            });
+            let post_mock = server.mock(|when, then| {
+                when.method(httpmock::Method::POST)
+                    .path("/statistics/snapshot")
+                    .header("authorization", "Bearer token")
+                    .header("user-agent", "my-application/1.0");
+                then.status(201);
+            });
+            let client = build_client(&server).with_middleware(Arc::new(
+                AggregatorRequestHeaders::new(&[
+                    ("Authorization", "Bearer token"),
+                    ("User-Agent", "my-application/1.0"),
+                ])
+                .unwrap(),
+            ));
+
+            client
+                .get_content(AggregatorRequest::ListCertificates)
+                .await
+                .unwrap();
+            client
+                .post_content(AggregatorRequest::IncrementSnapshotStatistic {
+                    snapshot: "{}".to_string(),
+                })
+                .await
+                .unwrap();
+
+            get_mock.assert();
+            post_mock.assert();
+        }
+
+        #[tokio::test]
+        async fn failing_middleware_aborts_the_request() {
+            let server = httpmock::MockServer::start();
+            let mock = server.mock(|when, then| {
+                when.path("/certificates");
+                then.status(200).body("certificates");
+            });
+            let client = build_client(&server).with_middleware(Arc::new(FailingMiddleware));
+
+            let error = client
+                .get_content(AggregatorRequest::ListCertificates)
+                .await
+                .expect_err("A failing middleware should abort the request");
+
+            assert!(
+                matches!(error, AggregatorClientError::SubsystemError(_)),
+                "Expected a SubsystemError, got: {error:?}"
+            );
+            mock.assert_hits(0);
+        }
+
+        #[tokio::test]
+        async fn middlewares_receive_the_request_context_and_the_response() {
+            let server = httpmock::MockServer::start();
+            server.mock(|when, then| {
+                when.path("/certificates");
+                then.status(200).body("certificates");
+            });
+            let middleware = Arc::new(RecordingMiddleware::default());
+            let client = build_client(&server).with_middleware(middleware.clone());
+
+            client
+                .get_content(AggregatorRequest::ListCertificates)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                vec![(
+                    AggregatorRequestContext {
+                        request: AggregatorRequest::ListCertificates,
+                        method: Method::GET,
+                        url: Url::parse(&server.url("/certificates")).unwrap(),
+                    },
+                    StatusCode::OK
+                )],
+                *middleware.responses.lock().unwrap()
+            );
+        }
+    }
 }
@@ -0,0 +1,871 @@
+//! Automatic failover across several aggregators.
+//!
+//! The [AggregatorFailoverClient] is an [AggregatorClient] that dispatches the requests to a
+//! prioritized list of aggregator endpoints:
+//! - a request is sent to the first available endpoint of the list, if a `GET` request fails with
+//!   a technical error the next endpoint is tried, `POST` requests are not idempotent and are
+//!   never retried,
+//! - an endpoint that failed is set aside, it's probed again with its `health/ready` route once a
+//!   retry delay has elapsed and used again if the probe succeeds,
+//! - before an endpoint is used as a fallback, the client checks that it serves the certificate
+//!   chain of the certificates fetched so far: the fallback must serve the latest certificate
+//!   fetched with a hash that matches its content, since the hash of a certificate covers the
+//!   hash of its previous certificate it chains to the same genesis. If no certificate was
+//!   fetched yet, the certificate chain of the fallback is verified with the genesis verification
+//!   key. The first endpoint of the list is trusted as the aggregator of a client with a single
+//!   aggregator would be.
+//!
+//! Use [ClientBuilder::aggregators][crate::ClientBuilder::aggregators] to build a
+//! [Client][crate::Client] that uses it.
+
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use slog::{debug, info, warn, Logger};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+use mithril_common::crypto_helper::ProtocolGenesisVerificationKey;
+use mithril_common::entities::Certificate;
+
+use crate::aggregator_client::{AggregatorClient, AggregatorClientError, AggregatorRequest};
+use crate::certificate_client::{CertificateVerifier, MithrilCertificateVerifier};
+use crate::feedback::FeedbackSender;
+use crate::{MithrilCertificate, MithrilCertificateListItem, MithrilResult};
+
+/// Default delay before an endpoint that failed is probed again
+pub const DEFAULT_UNHEALTHY_ENDPOINT_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// An aggregator endpoint of an [AggregatorFailoverClient]
+struct FailoverEndpoint {
+    name: String,
+    client: Arc<dyn AggregatorClient>,
+}
+
+#[derive(Default)]
+struct FailoverState {
+    /// Index of the endpoint that served the last successful request
+    current_endpoint: Option<usize>,
+    /// Endpoints that failed, with the time of their last failure
+    unhealthy_endpoints: HashMap<usize, DateTime<Utc>>,
+    /// Hash of the latest certificate fetched, used to check the consistency of the fallbacks
+    reference_certificate_hash: Option<String>,
+    /// Endpoints known to serve a certificate chain consistent with the reference certificate
+    consistent_endpoints: HashSet<usize>,
+}
+
+#[derive(PartialEq)]
+enum RequestMethod {
+    Get,
+    Post,
+}
+
+/// [AggregatorClient] that fails over across a prioritized list of aggregators.
+pub struct AggregatorFailoverClient {
+    endpoints: Vec<FailoverEndpoint>,
+    genesis_verification_key: String,
+    unhealthy_endpoint_retry_delay: Duration,
+    state: RwLock<FailoverState>,
+    logger: Logger,
+}
+
+impl AggregatorFailoverClient {
+    /// Constructs a new `AggregatorFailoverClient`, the endpoints are given as a list of
+    /// `(name, client)` sorted by decreasing priority.
+    ///
+    /// The genesis verification key is used to verify the certificate chain of a fallback
+    /// endpoint when no certificate was fetched yet.
+    pub fn new(
+        endpoints: Vec<(String, Arc<dyn AggregatorClient>)>,
+        genesis_verification_key: &str,
+        logger: Logger,
+    ) -> MithrilResult<Self> {
+        if endpoints.is_empty() {
+            return Err(anyhow!(
+                "An aggregator failover client needs at least one aggregator endpoint"
+            ));
+        }
+        ProtocolGenesisVerificationKey::try_from(genesis_verification_key)
+            .with_context(|| "Invalid genesis verification key")?;
+
+        Ok(Self {
+            endpoints: endpoints
+                .into_iter()
+                .map(|(name, client)| FailoverEndpoint { name, client })
+                .collect(),
+            genesis_verification_key: genesis_verification_key.to_string(),
+            unhealthy_endpoint_retry_delay: DEFAULT_UNHEALTHY_ENDPOINT_RETRY_DELAY,
+            state: RwLock::new(FailoverState::default()),
+            logger,
+        })
+    }
+
+    /// Set the delay after which an endpoint that failed is probed again.
+    pub fn with_unhealthy_endpoint_retry_delay(mut self, delay: Duration) -> Self {
+        self.unhealthy_endpoint_retry_delay = delay;
+        self
+    }
+
+    /// Name of the endpoint that served the last successful request, if any.
+    pub async fn current_endpoint(&self) -> Option<String> {
+        self.state
+            .read()
+            .await
+            .current_endpoint
+            .map(|index| self.endpoints[index].name.clone())
+    }
+
+    /// Errors that are caused by the endpoint itself and not by the request.
+    fn is_failover_error(error: &AggregatorClientError) -> bool {
+        !matches!(error, AggregatorClientError::RemoteServerLogical(_))
+    }
+
+    /// Hash of the certificate of the given response recomputed from its content, `None` if the
+    /// response is not a certificate or if its hash does not match its content.
+    fn compute_certificate_hash(content: &str) -> Option<String> {
+        let message = serde_json::from_str::<MithrilCertificate>(content).ok()?;
+        let certificate = Certificate::try_from(message).ok()?;
+        let hash = certificate.compute_hash();
+
+        (hash == certificate.hash).then_some(hash)
+    }
+
+    async fn mark_unhealthy(&self, index: usize) {
+        self.state
+            .write()
+            .await
+            .unhealthy_endpoints
+            .insert(index, Utc::now());
+    }
+
+    /// Check that the endpoint is not set aside, probing its health if its retry delay has elapsed.
+    async fn is_available(&self, index: usize) -> bool {
+        let unhealthy_since = self
+            .state
+            .read()
+            .await
+            .unhealthy_endpoints
+            .get(&index)
+            .cloned();
+        let Some(unhealthy_since) = unhealthy_since else {
+            return true;
+        };
+        let retry_delay_elapsed = (Utc::now() - unhealthy_since)
+            .to_std()
+            .is_ok_and(|elapsed| elapsed >= self.unhealthy_endpoint_retry_delay);
+        if !retry_delay_elapsed {
+            return false;
+        }
+
+        let endpoint = &self.endpoints[index];
+        match endpoint
+            .client
+            .get_content(AggregatorRequest::GetHealthReady)
+            .await
+        {
+            Ok(_) | Err(AggregatorClientError::RemoteServerLogical(_)) => {
+                info!(self.logger, "Aggregator endpoint is healthy again"; "endpoint" => &endpoint.name);
+                self.state.write().await.unhealthy_endpoints.remove(&index);
+                true
+            }
+            Err(error) => {
+                debug!(self.logger, "Aggregator endpoint is still unhealthy"; "endpoint" => &endpoint.name, "error" => ?error);
+                self.mark_unhealthy(index).await;
+                false
+            }
+        }
+    }
+
+    /// Check that the endpoint serves the certificate chain of the certificates fetched so far.
+    async fn is_consistent(&self, index: usize) -> bool {
+        let reference_certificate_hash = {
+            let state = self.state.read().await;
+            if state.consistent_endpoints.contains(&index) {
+                return true;
+            }
+            state.reference_certificate_hash.clone()
+        };
+
+        let endpoint = &self.endpoints[index];
+        let check_result = match &reference_certificate_hash {
+            Some(hash) => self
+                .check_serves_certificate(endpoint, hash)
+                .await
+                .map(|_| hash.clone()),
+            // The first endpoint is trusted as the aggregator of a single aggregator client
+            None if index == 0 => return true,
+            None => self.verify_latest_certificate_chain(endpoint).await,
+        };
+
+        match check_result {
+            Ok(verified_certificate_hash) => {
+                let mut state = self.state.write().await;
+                state.consistent_endpoints.insert(index);
+                if state.reference_certificate_hash.is_none() {
+                    state.reference_certificate_hash = Some(verified_certificate_hash);
+                }
+                true
+            }
+            Err(error) => {
+                warn!(
+                    self.logger,
+                    "Aggregator endpoint discarded: it does not serve the certificate chain of the client";
+                    "endpoint" => &endpoint.name, "error" => ?error
+                );
+                self.mark_unhealthy(index).await;
+                false
+            }
+        }
+    }
+
+    /// Check that the endpoint serves the certificate with the given hash and a content that
+    /// matches this hash.
+    async fn check_serves_certificate(
+        &self,
+        endpoint: &FailoverEndpoint,
+        certificate_hash: &str,
+    ) -> MithrilResult<()> {
+        let content = endpoint
+            .client
+            .get_content(AggregatorRequest::GetCertificate {
+                hash: certificate_hash.to_string(),
+            })
+            .await?;
+
+        match Self::compute_certificate_hash(&content) {
+            Some(hash) if hash == certificate_hash => Ok(()),
+            _ => Err(anyhow!(
+                "The certificate '{certificate_hash}' served does not match its hash"
+            )),
+        }
+    }
+
+    /// Verify the certificate chain of the latest certificate of the endpoint with the genesis
+    /// verification key, returning the hash of this certificate.
+    async fn verify_latest_certificate_chain(
+        &self,
+        endpoint: &FailoverEndpoint,
+    ) -> MithrilResult<String> {
+        let content = endpoint
+            .client
+            .get_content(AggregatorRequest::ListCertificates)
+            .await?;
+        let latest_certificate_hash =
+            serde_json::from_str::<Vec<MithrilCertificateListItem>>(&content)
+                .with_context(|| "Could not parse the list of certificates")?
+                .first()
+                .map(|certificate| certificate.hash.clone())
+                .ok_or_else(|| anyhow!("The aggregator has no certificate"))?;
+        let content = endpoint
+            .client
+            .get_content(AggregatorRequest::GetCertificate {
+                hash: latest_certificate_hash,
+            })
+            .await?;
+        let latest_certificate = serde_json::from_str::<MithrilCertificate>(&content)
+            .with_context(|| "Could not parse the latest certificate")?;
+
+        let verifier = MithrilCertificateVerifier::new(
+            endpoint.client.clone(),
+            &self.genesis_verification_key,
+            FeedbackSender::new(&[]),
+            self.logger.clone(),
+        )?;
+        verifier
+            .verify_chain(&latest_certificate)
+            .await
+            .with_context(|| "The certificate chain is not valid")?;
+
+        Ok(latest_certificate.hash)
+    }
+
+    async fn record_success(&self, index: usize, request: &AggregatorRequest, content: &str) {
+        let mut state = self.state.write().await;
+        if state.current_endpoint != Some(index) {
+            info!(self.logger, "Using aggregator endpoint"; "endpoint" => &self.endpoints[index].name);
+            state.current_endpoint = Some(index);
+        }
+
+        if let AggregatorRequest::GetCertificate { .. } = request {
+            if let Some(hash) = Self::compute_certificate_hash(content) {
+                state.reference_certificate_hash = Some(hash);
+                state.consistent_endpoints.insert(index);
+            }
+        }
+    }
+
+    async fn send(
+        &self,
+        request: AggregatorRequest,
+        method: RequestMethod,
+    ) -> Result<String, AggregatorClientError> {
+        let mut last_error = None;
+
+        for (index, endpoint) in self.endpoints.iter().enumerate() {
+            if !self.is_available(index).await || !self.is_consistent(index).await {
+                continue;
+            }
+
+            let result = match method {
+                RequestMethod::Get => endpoint.client.get_content(request.clone()).await,
+                RequestMethod::Post => endpoint.client.post_content(request.clone()).await,
+            };
+            match result {
+                Ok(content) => {
+                    self.record_success(index, &request, &content).await;
+                    return Ok(content);
+                }
+                Err(error) if method == RequestMethod::Post => {
+                    // POST requests are not idempotent: they are not sent again to another endpoint
+                    if Self::is_failover_error(&error) {
+                        warn!(
+                            self.logger, "Aggregator endpoint failed";
+                            "endpoint" => &endpoint.name, "error" => ?error
+                        );
+                        self.mark_unhealthy(index).await;
+                    }
+                    return Err(error);
+                }
+                Err(error) if Self::is_failover_error(&error) => {
+                    warn!(
+                        self.logger, "Aggregator endpoint failed, trying the next one";
+                        "endpoint" => &endpoint.name, "error" => ?error
+                    );
+                    self.mark_unhealthy(index).await;
+                    last_error = Some(error);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Err(AggregatorClientError::RemoteServerTechnical(
+            match last_error {
+                Some(error) => anyhow!(error).context("All the aggregator endpoints failed"),
+                None => anyhow!("No healthy aggregator endpoint available"),
+            },
+        ))
+    }
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl AggregatorClient for AggregatorFailoverClient {
+    async fn get_content(
+        &self,
+        request: AggregatorRequest,
+    ) -> Result<String, AggregatorClientError> {
+        self.send(request, RequestMethod::Get).await
+    }
+
+    async fn post_content(
+        &self,
+        request: AggregatorRequest,
+    ) -> Result<String, AggregatorClientError> {
+        self.send(request, RequestMethod::Post).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::eq;
+
+    use mithril_common::crypto_helper::tests_setup::setup_certificate_chain;
+    use mithril_common::crypto_helper::ProtocolGenesisSigner;
+
+    use crate::aggregator_client::MockAggregatorHTTPClient;
+
+    use super::*;
+
+    fn technical_error() -> AggregatorClientError {
+        AggregatorClientError::RemoteServerTechnical(anyhow!("connection refused"))
+    }
+
+    fn get_certificate(hash: &str) -> AggregatorRequest {
+        AggregatorRequest::GetCertificate {
+            hash: hash.to_string(),
+        }
+    }
+
+    fn certificate_content(certificate: &Certificate) -> String {
+        serde_json::to_string(&MithrilCertificate::try_from(certificate.clone()).unwrap()).unwrap()
+    }
+
+    /// Build a certificate chain and the genesis verification key of its genesis certificate
+    fn build_certificate_chain() -> (Vec<Certificate>, String) {
+        let (chain, genesis_verifier) = setup_certificate_chain(3, 1);
+        let genesis_verification_key = genesis_verifier.to_verification_key().try_into().unwrap();
+
+        (chain, genesis_verification_key)
+    }
+
+    /// Make the given client serve the given certificate chain
+    fn serve_certificate_chain(client: &mut MockAggregatorHTTPClient, chain: &[Certificate]) {
+        let certificates_list = serde_json::to_string(&vec![MithrilCertificateListItem {
+            hash: chain[0].hash.clone(),
+            ..MithrilCertificateListItem::dummy()
+        }])
+        .unwrap();
+        client
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListCertificates))
+            .returning(move |_| Ok(certificates_list.clone()));
+        for certificate in chain {
+            let content = certificate_content(certificate);
+            client
+                .expect_get_content()
+                .with(eq(get_certificate(&certificate.hash)))
+                .returning(move |_| Ok(content.clone()));
+        }
+    }
+
+    fn build_failover_client(
+        clients: Vec<MockAggregatorHTTPClient>,
+        genesis_verification_key: &str,
+    ) -> AggregatorFailoverClient {
+        let endpoints = clients
+            .into_iter()
+            .enumerate()
+            .map(|(index, client)| {
+                (
+                    format!("aggregator-{index}"),
+                    Arc::new(client) as Arc<dyn AggregatorClient>,
+                )
+            })
+            .collect();
+
+        AggregatorFailoverClient::new(
+            endpoints,
+            genesis_verification_key,
+            crate::test_utils::test_logger(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn building_without_endpoint_fails() {
+        let (_, genesis_verification_key) = build_certificate_chain();
+
+        AggregatorFailoverClient::new(
+            vec![],
+            &genesis_verification_key,
+            crate::test_utils::test_logger(),
+        )
+        .expect_err("Building a failover client without endpoint should fail");
+    }
+
+    #[test]
+    fn building_with_an_invalid_genesis_verification_key_fails() {
+        AggregatorFailoverClient::new(
+            vec![(
+                "aggregator-0".to_string(),
+                Arc::new(MockAggregatorHTTPClient::new()),
+            )],
+            "invalid",
+            crate::test_utils::test_logger(),
+        )
+        .expect_err("Building a failover client with an invalid genesis key should fail");
+    }
+
+    #[tokio::test]
+    async fn requests_are_sent_to_the_first_endpoint_when_it_is_healthy() {
+        let (_, genesis_verification_key) = build_certificate_chain();
+        let mut primary = MockAggregatorHTTPClient::new();
+        primary
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListSnapshots))
+            .times(2)
+            .returning(|_| Ok("snapshots".to_string()));
+        let fallback = MockAggregatorHTTPClient::new();
+        let client = build_failover_client(vec![primary, fallback], &genesis_verification_key);
+
+        for _ in 0..2 {
+            let content = client
+                .get_content(AggregatorRequest::ListSnapshots)
+                .await
+                .unwrap();
+
+            assert_eq!("snapshots", content);
+        }
+        assert_eq!(
+            Some("aggregator-0".to_string()),
+            client.current_endpoint().await
+        );
+    }
+
+    #[tokio::test]
+    async fn requests_fail_over_to_the_next_endpoint_on_technical_error() {
+        let (chain, genesis_verification_key) = build_certificate_chain();
+        let mut primary = MockAggregatorHTTPClient::new();
+        primary
+            .expect_get_content()
+            .times(1)
+            .returning(|_| Err(technical_error()));
+        primary
+            .expect_post_content()
+            .never()
+            .returning(|_| Ok(String::new()));
+        let mut fallback = MockAggregatorHTTPClient::new();
+        fallback
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListSnapshots))
+            .times(1)
+            .returning(|_| Ok("snapshots".to_string()));
+        serve_certificate_chain(&mut fallback, &chain);
+        fallback
+            .expect_post_content()
+            .times(1)
+            .returning(|_| Ok("statistics".to_string()));
+        let client = build_failover_client(vec![primary, fallback], &genesis_verification_key);
+
+        let content = client
+            .get_content(AggregatorRequest::ListSnapshots)
+            .await
+            .unwrap();
+        assert_eq!("snapshots", content);
+        assert_eq!(
+            Some("aggregator-1".to_string()),
+            client.current_endpoint().await
+        );
+
+        // The primary endpoint is set aside until its retry delay has elapsed
+        let content = client
+            .post_content(AggregatorRequest::IncrementSnapshotStatistic {
+                snapshot: "snapshot".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!("statistics", content);
+    }
+
+    #[tokio::test]
+    async fn post_requests_are_not_sent_again_to_the_next_endpoint() {
+        let (_, genesis_verification_key) = build_certificate_chain();
+        let mut primary = MockAggregatorHTTPClient::new();
+        primary
+            .expect_post_content()
+            .times(1)
+            .returning(|_| Err(technical_error()));
+        let mut fallback = MockAggregatorHTTPClient::new();
+        fallback.expect_get_content().never();
+        fallback.expect_post_content().never();
+        let client = build_failover_client(vec![primary, fallback], &genesis_verification_key);
+
+        let error = client
+            .post_content(AggregatorRequest::IncrementSnapshotStatistic {
+                snapshot: "snapshot".to_string(),
+            })
+            .await
+            .expect_err("A failed POST request should not be sent to the next endpoint");
+
+        assert!(
+            matches!(error, AggregatorClientError::RemoteServerTechnical(_)),
+            "unexpected error: {error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn logical_errors_are_returned_without_failing_over() {
+        let (_, genesis_verification_key) = build_certificate_chain();
+        let mut primary = MockAggregatorHTTPClient::new();
+        primary.expect_get_content().times(1).returning(|_| {
+            Err(AggregatorClientError::RemoteServerLogical(anyhow!(
+                "not found"
+            )))
+        });
+        let mut fallback = MockAggregatorHTTPClient::new();
+        fallback.expect_get_content().never();
+        let client = build_failover_client(vec![primary, fallback], &genesis_verification_key);
+
+        let error = client
+            .get_content(get_certificate("unknown"))
+            .await
+            .expect_err("A logical error should be returned");
+
+        assert!(
+            matches!(error, AggregatorClientError::RemoteServerLogical(_)),
+            "unexpected error: {error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_when_all_the_endpoints_fail() {
+        let (_, genesis_verification_key) = build_certificate_chain();
+        let mut primary = MockAggregatorHTTPClient::new();
+        primary
+            .expect_get_content()
+            .times(1)
+            .returning(|_| Err(technical_error()));
+        let mut fallback = MockAggregatorHTTPClient::new();
+        fallback
+            .expect_get_content()
+            .times(1)
+            .returning(|_| Err(technical_error()));
+        let client = build_failover_client(vec![primary, fallback], &genesis_verification_key);
+
+        let error = client
+            .get_content(AggregatorRequest::ListSnapshots)
+            .await
+            .expect_err("The request should fail when all the endpoints fail");
+        assert!(
+            matches!(error, AggregatorClientError::RemoteServerTechnical(_)),
+            "unexpected error: {error:?}"
+        );
+
+        // Both endpoints are set aside until their retry delay has elapsed
+        client
+            .get_content(AggregatorRequest::ListSnapshots)
+            .await
+            .expect_err("No endpoint should be available");
+    }
+
+    #[tokio::test]
+    async fn unhealthy_endpoint_is_used_again_once_its_health_probe_succeeds() {
+        let (chain, genesis_verification_key) = build_certificate_chain();
+        let mut primary = MockAggregatorHTTPClient::new();
+        primary
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListSnapshots))
+            .times(1)
+            .returning(|_| Err(technical_error()));
+        primary
+            .expect_get_content()
+            .with(eq(AggregatorRequest::GetHealthReady))
+            .times(1)
+            .returning(|_| Ok("ready".to_string()));
+        primary
+            .expect_get_content()
+            .with(eq(get_certificate(&chain[0].hash)))
+            .times(1)
+            .returning({
+                let content = certificate_content(&chain[0]);
+                move |_| Ok(content.clone())
+            });
+        primary
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListMithrilStakeDistributions))
+            .times(1)
+            .returning(|_| Ok("stake distributions from primary".to_string()));
+        let mut fallback = MockAggregatorHTTPClient::new();
+        fallback
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListSnapshots))
+            .times(1)
+            .returning(|_| Ok("snapshots from fallback".to_string()));
+        serve_certificate_chain(&mut fallback, &chain);
+        let client = build_failover_client(vec![primary, fallback], &genesis_verification_key)
+            .with_unhealthy_endpoint_retry_delay(Duration::ZERO);
+
+        let content = client
+            .get_content(AggregatorRequest::ListSnapshots)
+            .await
+            .unwrap();
+        assert_eq!("snapshots from fallback", content);
+
+        let content = client
+            .get_content(AggregatorRequest::ListMithrilStakeDistributions)
+            .await
+            .unwrap();
+        assert_eq!("stake distributions from primary", content);
+        assert_eq!(
+            Some("aggregator-0".to_string()),
+            client.current_endpoint().await
+        );
+    }
+
+    #[tokio::test]
+    async fn fallback_serving_the_latest_fetched_certificate_is_used() {
+        let (chain, genesis_verification_key) = build_certificate_chain();
+        let mut primary = MockAggregatorHTTPClient::new();
+        primary
+            .expect_get_content()
+            .with(eq(get_certificate(&chain[0].hash)))
+            .times(1)
+            .returning({
+                let content = certificate_content(&chain[0]);
+                move |_| Ok(content.clone())
+            });
+        primary
+            .expect_get_content()
+            .with(eq(get_certificate(&chain[1].hash)))
+            .times(1)
+            .returning(|_| Err(technical_error()));
+        let mut fallback = MockAggregatorHTTPClient::new();
+        fallback
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListCertificates))
+            .never();
+        for certificate in &chain[0..2] {
+            let content = certificate_content(certificate);
+            fallback
+                .expect_get_content()
+                .with(eq(get_certificate(&certificate.hash)))
+                .times(1)
+                .returning(move |_| Ok(content.clone()));
+        }
+        let client = build_failover_client(vec![primary, fallback], &genesis_verification_key);
+
+        client
+            .get_content(get_certificate(&chain[0].hash))
+            .await
+            .unwrap();
+        let content = client
+            .get_content(get_certificate(&chain[1].hash))
+            .await
+            .unwrap();
+
+        assert_eq!(certificate_content(&chain[1]), content);
+    }
+
+    #[tokio::test]
+    async fn fallback_not_serving_the_latest_fetched_certificate_is_discarded() {
+        let (chain, genesis_verification_key) = build_certificate_chain();
+        let mut primary = MockAggregatorHTTPClient::new();
+        primary
+            .expect_get_content()
+            .with(eq(get_certificate(&chain[0].hash)))
+            .times(1)
+            .returning({
+                let content = certificate_content(&chain[0]);
+                move |_| Ok(content.clone())
+            });
+        primary
+            .expect_get_content()
+            .with(eq(get_certificate(&chain[1].hash)))
+            .times(1)
+            .returning(|_| Err(technical_error()));
+        let mut fallback = MockAggregatorHTTPClient::new();
+        fallback
+            .expect_get_content()
+            .with(eq(get_certificate(&chain[0].hash)))
+            .times(1)
+            .returning(|_| {
+                Err(AggregatorClientError::RemoteServerLogical(anyhow!(
+                    "not found"
+                )))
+            });
+        fallback
+            .expect_get_content()
+            .with(eq(get_certificate(&chain[1].hash)))
+            .never();
+        let client = build_failover_client(vec![primary, fallback], &genesis_verification_key);
+
+        client
+            .get_content(get_certificate(&chain[0].hash))
+            .await
+            .unwrap();
+        client
+            .get_content(get_certificate(&chain[1].hash))
+            .await
+            .expect_err("A fallback chaining to another genesis should not be used");
+    }
+
+    #[tokio::test]
+    async fn fallback_serving_a_certificate_whose_content_does_not_match_its_hash_is_discarded() {
+        let (chain, genesis_verification_key) = build_certificate_chain();
+        let mut primary = MockAggregatorHTTPClient::new();
+        primary
+            .expect_get_content()
+            .with(eq(get_certificate(&chain[0].hash)))
+            .times(1)
+            .returning({
+                let content = certificate_content(&chain[0]);
+                move |_| Ok(content.clone())
+            });
+        primary
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListSnapshots))
+            .times(1)
+            .returning(|_| Err(technical_error()));
+        let mut fallback = MockAggregatorHTTPClient::new();
+        fallback
+            .expect_get_content()
+            .with(eq(get_certificate(&chain[0].hash)))
+            .times(1)
+            .returning({
+                // A certificate of another chain that echoes the requested hash
+                let mut forged_certificate = chain[1].clone();
+                forged_certificate.hash = chain[0].hash.clone();
+                let content = certificate_content(&forged_certificate);
+                move |_| Ok(content.clone())
+            });
+        fallback
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListSnapshots))
+            .never();
+        let client = build_failover_client(vec![primary, fallback], &genesis_verification_key);
+
+        client
+            .get_content(get_certificate(&chain[0].hash))
+            .await
+            .unwrap();
+        client
+            .get_content(AggregatorRequest::ListSnapshots)
+            .await
+            .expect_err("A fallback chaining to another genesis should not be used");
+    }
+
+    #[tokio::test]
+    async fn fallback_is_used_before_any_certificate_is_fetched_if_its_certificate_chain_is_valid()
+    {
+        let (chain, genesis_verification_key) = build_certificate_chain();
+        let mut primary = MockAggregatorHTTPClient::new();
+        primary
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListSnapshots))
+            .times(1)
+            .returning(|_| Err(technical_error()));
+        let mut fallback = MockAggregatorHTTPClient::new();
+        fallback
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListSnapshots))
+            .times(1)
+            .returning(|_| Ok("snapshots".to_string()));
+        serve_certificate_chain(&mut fallback, &chain);
+        let client = build_failover_client(vec![primary, fallback], &genesis_verification_key);
+
+        let content = client
+            .get_content(AggregatorRequest::ListSnapshots)
+            .await
+            .unwrap();
+
+        assert_eq!("snapshots", content);
+        assert_eq!(
+            Some(chain[0].hash.clone()),
+            client.state.read().await.reference_certificate_hash
+        );
+    }
+
+    #[tokio::test]
+    async fn fallback_is_discarded_before_any_certificate_is_fetched_if_its_certificate_chain_is_not_valid(
+    ) {
+        let (chain, _) = build_certificate_chain();
+        let other_genesis_verification_key: String =
+            ProtocolGenesisSigner::create_non_deterministic_genesis_signer()
+                .create_genesis_verifier()
+                .to_verification_key()
+                .try_into()
+                .unwrap();
+        let mut primary = MockAggregatorHTTPClient::new();
+        primary
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListSnapshots))
+            .times(1)
+            .returning(|_| Err(technical_error()));
+        let mut fallback = MockAggregatorHTTPClient::new();
+        fallback
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListSnapshots))
+            .never();
+        serve_certificate_chain(&mut fallback, &chain);
+        let client =
+            build_failover_client(vec![primary, fallback], &other_genesis_verification_key);
+
+        client
+            .get_content(AggregatorRequest::ListSnapshots)
+            .await
+            .expect_err("A fallback chaining to another genesis should not be used");
+        assert_eq!(None, client.state.read().await.reference_certificate_hash);
+    }
+}
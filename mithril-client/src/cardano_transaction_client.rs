@@ -80,6 +80,7 @@ use crate::{
     MithrilResult,
 };
 use anyhow::Context;
+use mithril_common::messages::PaginatedMessage;
 use std::sync::Arc;
 
 /// HTTP client for CardanoTransactionsAPI from the Aggregator
@@ -124,8 +125,12 @@ impl CardanoTransactionClient {
             .get_content(AggregatorRequest::ListCardanoTransactionSnapshots)
             .await
             .with_context(|| "CardanoTransactionClient Client can not get the artifact list")?;
-        let items = serde_json::from_str::<Vec<CardanoTransactionSnapshotListItem>>(&response)
-            .with_context(|| "CardanoTransactionClient Client can not deserialize artifact list")?;
+        let items =
+            PaginatedMessage::<CardanoTransactionSnapshotListItem>::from_list_json(&response)
+                .with_context(|| {
+                    "CardanoTransactionClient Client can not deserialize artifact list"
+                })?
+                .items;
 
         Ok(items)
     }
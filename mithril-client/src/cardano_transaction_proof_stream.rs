@@ -0,0 +1,366 @@
+//! A verifier of Cardano transactions proofs that works on streams, for large result sets.
+//!
+//! In order to do so it defines a [CardanoTransactionsProofsStreamVerifier] which exposes the following features:
+//!  - [verify_stream][CardanoTransactionsProofsStreamVerifier::verify_stream]: verify incrementally each
+//! [set proof][CardanoTransactionsSetProof] of a stream against the Merkle root signed by a single certificate,
+//! yielding a [result][CardanoTransactionsSetProofVerification] per set proof.
+//!
+//!  - [verify_batch][CardanoTransactionsProofsStreamVerifier::verify_batch]: verify a batch of
+//! [set proofs][CardanoTransactionsSetProof] at once, the parts shared by several set proofs being verified
+//! only once, yielding a [result][CardanoTransactionsSetProofVerification] per set proof.
+//!
+//! When verifying a stream only one set proof is processed at a time, so the memory used does not grow
+//! with the number of verified transactions.
+//!
+//!  **Important:** The certificate given to the verifier must have been verified beforehand, for example with
+//! [verify_chain][crate::certificate_client::CertificateClient::verify_chain].
+//!
+//! # Verify a stream of Cardano transactions proofs
+//!
+//! ```no_run
+//! # async fn run() -> mithril_client::MithrilResult<()> {
+//! use futures::StreamExt;
+//! use mithril_client::{cardano_transaction_proof_stream::CardanoTransactionsProofsStreamVerifier, ClientBuilder};
+//!
+//! let client = ClientBuilder::aggregator("YOUR_AGGREGATOR_ENDPOINT", "YOUR_GENESIS_VERIFICATION_KEY").build()?;
+//!
+//! // 1 - Verify the certificate chain of the certificate that signs the proofs
+//! let certificate = client.certificate().verify_chain("CERTIFICATE_HASH").await?;
+//!
+//! // 2 - Verify each set proof of a stream against this certificate
+//! let cardano_transaction_proof = client.cardano_transaction().get_proofs(&["tx-1", "tx-2"]).await?;
+//! let verifier = CardanoTransactionsProofsStreamVerifier::new(&certificate)?;
+//! let mut verifications = verifier.verify_stream(futures::stream::iter(cardano_transaction_proof.certified_transactions));
+//!
+//! while let Some(verification) = verifications.next().await {
+//!     match verification.result {
+//!         Ok(()) => println!("Certified transactions : {:?}", verification.transactions_hashes),
+//!         Err(error) => println!("Invalid proof for transactions {:?}: {error}", verification.transactions_hashes),
+//!     }
+//! }
+//! #    Ok(())
+//! # }
+//! ```
+
+use anyhow::anyhow;
+use futures::{Stream, StreamExt};
+
+use crate::common::TransactionHash;
+use crate::{
+    CardanoTransactionsSetProof, MithrilCertificate, MithrilResult,
+    VerifyCardanoTransactionsProofsError,
+};
+use mithril_common::entities::CardanoTransactionsSetProof as CardanoTransactionsSetProofEntity;
+
+/// Result of the verification of one [set proof][CardanoTransactionsSetProof] of a stream.
+#[derive(Debug)]
+pub struct CardanoTransactionsSetProofVerification {
+    /// Hashes of the transactions covered by the set proof
+    pub transactions_hashes: Vec<TransactionHash>,
+
+    /// Outcome of the verification, the transactions are certified if it's `Ok`
+    pub result: Result<(), VerifyCardanoTransactionsProofsError>,
+}
+
+impl CardanoTransactionsSetProofVerification {
+    /// Check if the transactions of the set proof are certified
+    pub fn is_certified(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Verifier of streams of Cardano transactions set proofs against a single certificate
+pub struct CardanoTransactionsProofsStreamVerifier {
+    merkle_root: String,
+}
+
+impl CardanoTransactionsProofsStreamVerifier {
+    /// Constructs a new `CardanoTransactionsProofsStreamVerifier` for the given certificate.
+    ///
+    /// Fails if the certificate does not sign a Cardano transactions Merkle root.
+    pub fn new(certificate: &MithrilCertificate) -> MithrilResult<Self> {
+        let merkle_root = certificate
+            .protocol_message
+            .cardano_transactions_merkle_root()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Certificate '{}' does not sign a Cardano transactions Merkle root",
+                    certificate.hash
+                )
+            })?;
+
+        Ok(Self {
+            merkle_root: merkle_root.to_string(),
+        })
+    }
+
+    /// Verify a single set proof against the certificate Merkle root.
+    pub fn verify(
+        &self,
+        set_proof: CardanoTransactionsSetProof,
+    ) -> CardanoTransactionsSetProofVerification {
+        let transactions_hashes = set_proof.transactions_hashes.clone();
+        let result = self.verify_set_proof(set_proof);
+
+        CardanoTransactionsSetProofVerification {
+            transactions_hashes,
+            result,
+        }
+    }
+
+    /// Verify lazily each set proof of the given stream, yielding a result per set proof in the
+    /// same order.
+    pub fn verify_stream<'a, S>(
+        &'a self,
+        set_proofs: S,
+    ) -> impl Stream<Item = CardanoTransactionsSetProofVerification> + 'a
+    where
+        S: Stream<Item = CardanoTransactionsSetProof> + 'a,
+    {
+        set_proofs.map(|set_proof| self.verify(set_proof))
+    }
+
+    /// Verify a batch of set proofs against the certificate Merkle root, yielding a result per
+    /// set proof in the same order.
+    ///
+    /// Faster than verifying the set proofs one by one as the parts shared by several set
+    /// proofs, ie: the proofs of the block ranges they have in common, are only verified once.
+    pub fn verify_batch(
+        &self,
+        set_proofs: Vec<CardanoTransactionsSetProof>,
+    ) -> Vec<CardanoTransactionsSetProofVerification> {
+        let mut malformed_errors = vec![];
+        let mut set_proofs_entities = vec![];
+        for set_proof in set_proofs {
+            let transactions_hashes = set_proof.transactions_hashes.clone();
+            match CardanoTransactionsSetProofEntity::try_from(set_proof) {
+                Ok(set_proof) => {
+                    malformed_errors.push((transactions_hashes, None));
+                    set_proofs_entities.push(set_proof);
+                }
+                Err(error) => malformed_errors.push((transactions_hashes, Some(error))),
+            }
+        }
+        let batch_verifications =
+            CardanoTransactionsSetProofEntity::verify_batch(&set_proofs_entities);
+        let mut set_proofs_verifications = set_proofs_entities.iter().zip(batch_verifications);
+
+        malformed_errors
+            .into_iter()
+            .map(|(transactions_hashes, malformed_error)| {
+                let result = match malformed_error {
+                    Some(error) => Err(VerifyCardanoTransactionsProofsError::MalformedData(error)),
+                    None => {
+                        let (set_proof, verification) = set_proofs_verifications
+                            .next()
+                            .expect("a verification is computed for each well formed set proof");
+                        self.check_set_proof_verification(set_proof, verification)
+                    }
+                };
+
+                CardanoTransactionsSetProofVerification {
+                    transactions_hashes,
+                    result,
+                }
+            })
+            .collect()
+    }
+
+    fn verify_set_proof(
+        &self,
+        set_proof: CardanoTransactionsSetProof,
+    ) -> Result<(), VerifyCardanoTransactionsProofsError> {
+        let set_proof: CardanoTransactionsSetProofEntity = set_proof
+            .try_into()
+            .map_err(VerifyCardanoTransactionsProofsError::MalformedData)?;
+        let verification = set_proof.verify();
+
+        self.check_set_proof_verification(&set_proof, verification)
+    }
+
+    fn check_set_proof_verification(
+        &self,
+        set_proof: &CardanoTransactionsSetProofEntity,
+        verification: MithrilResult<()>,
+    ) -> Result<(), VerifyCardanoTransactionsProofsError> {
+        verification.map_err(|e| VerifyCardanoTransactionsProofsError::InvalidSetProof {
+            transactions_hashes: set_proof.transactions_hashes().to_vec(),
+            source: e,
+        })?;
+
+        if set_proof.merkle_root() != self.merkle_root {
+            return Err(VerifyCardanoTransactionsProofsError::NonMatchingMerkleRoot);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::crypto_helper::MKProof;
+
+    use crate::common::ProtocolMessagePartKey;
+
+    use super::*;
+
+    fn certificate_signing_merkle_root(merkle_root: &str) -> MithrilCertificate {
+        let mut certificate = MithrilCertificate::dummy();
+        certificate.protocol_message.set_message_part(
+            ProtocolMessagePartKey::CardanoTransactionsMerkleRoot,
+            merkle_root.to_string(),
+        );
+
+        certificate
+    }
+
+    fn set_proof_from_leaves(leaves: &[&str]) -> CardanoTransactionsSetProofEntity {
+        CardanoTransactionsSetProofEntity::new(
+            leaves.iter().map(|leaf| leaf.to_string()).collect(),
+            MKProof::from_leaves(leaves).unwrap(),
+        )
+    }
+
+    #[test]
+    fn new_fails_if_certificate_does_not_sign_a_merkle_root() {
+        let mut certificate = MithrilCertificate::dummy();
+        certificate.protocol_message = Default::default();
+
+        CardanoTransactionsProofsStreamVerifier::new(&certificate)
+            .expect_err("A certificate without Merkle root should be rejected");
+    }
+
+    #[tokio::test]
+    async fn verify_stream_yields_a_result_per_set_proof_in_order() {
+        let set_proof = CardanoTransactionsSetProofEntity::dummy();
+        let verifier = CardanoTransactionsProofsStreamVerifier::new(
+            &certificate_signing_merkle_root(&set_proof.merkle_root()),
+        )
+        .unwrap();
+        let set_proofs: Vec<CardanoTransactionsSetProof> = vec![
+            set_proof.clone().try_into().unwrap(),
+            CardanoTransactionsSetProof {
+                transactions_hashes: vec!["tx-malformed".to_string()],
+                proof: "invalid".to_string(),
+            },
+            set_proof.clone().try_into().unwrap(),
+        ];
+
+        let verifications = verifier
+            .verify_stream(futures::stream::iter(set_proofs))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(3, verifications.len());
+        assert!(verifications[0].is_certified());
+        assert_eq!(
+            set_proof.transactions_hashes(),
+            verifications[0].transactions_hashes
+        );
+        assert!(
+            matches!(
+                verifications[1].result,
+                Err(VerifyCardanoTransactionsProofsError::MalformedData(_))
+            ),
+            "Expected 'MalformedData' error but got '{:?}'",
+            verifications[1].result
+        );
+        assert_eq!(
+            vec!["tx-malformed".to_string()],
+            verifications[1].transactions_hashes
+        );
+        assert!(verifications[2].is_certified());
+    }
+
+    #[test]
+    fn verify_fails_if_set_proof_merkle_root_is_not_signed_by_the_certificate() {
+        let verifier =
+            CardanoTransactionsProofsStreamVerifier::new(&certificate_signing_merkle_root(
+                &set_proof_from_leaves(&["tx-1", "tx-2"]).merkle_root(),
+            ))
+            .unwrap();
+
+        let verification =
+            verifier.verify(set_proof_from_leaves(&["tx-3", "tx-4"]).try_into().unwrap());
+
+        assert!(
+            matches!(
+                verification.result,
+                Err(VerifyCardanoTransactionsProofsError::NonMatchingMerkleRoot)
+            ),
+            "Expected 'NonMatchingMerkleRoot' error but got '{:?}'",
+            verification.result
+        );
+    }
+
+    #[test]
+    fn verify_fails_with_an_invalid_set_proof() {
+        let verifier = CardanoTransactionsProofsStreamVerifier::new(
+            &certificate_signing_merkle_root("whatever"),
+        )
+        .unwrap();
+        let set_proof = CardanoTransactionsSetProofEntity::new(
+            vec!["invalid1".to_string()],
+            MKProof::from_leaves(&["invalid2"]).unwrap(),
+        );
+
+        let verification = verifier.verify(set_proof.try_into().unwrap());
+
+        assert!(
+            matches!(
+                verification.result,
+                Err(VerifyCardanoTransactionsProofsError::InvalidSetProof { .. })
+            ),
+            "Expected 'InvalidSetProof' error but got '{:?}'",
+            verification.result
+        );
+        assert_eq!(
+            vec!["invalid1".to_string()],
+            verification.transactions_hashes
+        );
+    }
+
+    #[test]
+    fn verify_batch_yields_a_result_per_set_proof_in_order() {
+        let set_proof = CardanoTransactionsSetProofEntity::dummy();
+        let verifier = CardanoTransactionsProofsStreamVerifier::new(
+            &certificate_signing_merkle_root(&set_proof.merkle_root()),
+        )
+        .unwrap();
+        let set_proofs: Vec<CardanoTransactionsSetProof> = vec![
+            set_proof.clone().try_into().unwrap(),
+            CardanoTransactionsSetProof {
+                transactions_hashes: vec!["tx-malformed".to_string()],
+                proof: "invalid".to_string(),
+            },
+            set_proof_from_leaves(&["tx-3", "tx-4"]).try_into().unwrap(),
+            set_proof.clone().try_into().unwrap(),
+        ];
+
+        let verifications = verifier.verify_batch(set_proofs);
+
+        assert_eq!(4, verifications.len());
+        assert!(verifications[0].is_certified());
+        assert_eq!(
+            set_proof.transactions_hashes(),
+            verifications[0].transactions_hashes
+        );
+        assert!(
+            matches!(
+                verifications[1].result,
+                Err(VerifyCardanoTransactionsProofsError::MalformedData(_))
+            ),
+            "Expected 'MalformedData' error but got '{:?}'",
+            verifications[1].result
+        );
+        assert!(
+            matches!(
+                verifications[2].result,
+                Err(VerifyCardanoTransactionsProofsError::NonMatchingMerkleRoot)
+            ),
+            "Expected 'NonMatchingMerkleRoot' error but got '{:?}'",
+            verifications[2].result
+        );
+        assert!(verifications[3].is_certified());
+    }
+}
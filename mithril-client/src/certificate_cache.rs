@@ -0,0 +1,223 @@
+//! Local stores of certificates, used to avoid downloading again the certificate chain on each
+//! verification.
+//!
+//! A [CertificateCache] can be given to the [ClientBuilder][crate::ClientBuilder], the certificates
+//! are then read from it before falling back to the aggregator, and
+//! [sync][crate::certificate_client::CertificateClient::sync] downloads into it the certificates
+//! issued since the last synchronization.
+//!
+//! **Important:** Certificates read from a cache are verified exactly like the ones downloaded from
+//! the aggregator, a tampered cache makes the chain validation fail.
+//!
+//! # Synchronize a local certificate cache
+//!
+//! ```no_run
+//! # #[cfg(feature = "fs")]
+//! # async fn run() -> mithril_client::MithrilResult<()> {
+//! use mithril_client::{certificate_cache::FileCertificateCache, ClientBuilder};
+//! use std::sync::Arc;
+//!
+//! let client = ClientBuilder::aggregator("YOUR_AGGREGATOR_ENDPOINT", "YOUR_GENESIS_VERIFICATION_KEY")
+//!     .with_certificate_cache(Arc::new(FileCertificateCache::new("./certificates")?))
+//!     .build()?;
+//! let report = client.certificate().sync().await?;
+//!
+//! println!("Downloaded {} new certificates", report.downloaded_certificates);
+//! #    Ok(())
+//! # }
+//! ```
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+use crate::{MithrilCertificate, MithrilResult};
+
+/// API that defines a local store of certificates.
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+pub trait CertificateCache: Sync + Send {
+    /// Get the certificate with the given hash if it's stored.
+    async fn get(&self, certificate_hash: &str) -> MithrilResult<Option<MithrilCertificate>>;
+
+    /// Store the given certificate, replacing any certificate with the same hash.
+    async fn store(&self, certificate: &MithrilCertificate) -> MithrilResult<()>;
+}
+
+/// A [CertificateCache] that keeps the certificates in memory.
+#[derive(Default)]
+pub struct MemoryCertificateCache {
+    certificates: RwLock<HashMap<String, MithrilCertificate>>,
+}
+
+impl MemoryCertificateCache {
+    /// Constructs a new empty `MemoryCertificateCache`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl CertificateCache for MemoryCertificateCache {
+    async fn get(&self, certificate_hash: &str) -> MithrilResult<Option<MithrilCertificate>> {
+        Ok(self
+            .certificates
+            .read()
+            .await
+            .get(certificate_hash)
+            .cloned())
+    }
+
+    async fn store(&self, certificate: &MithrilCertificate) -> MithrilResult<()> {
+        self.certificates
+            .write()
+            .await
+            .insert(certificate.hash.clone(), certificate.clone());
+
+        Ok(())
+    }
+}
+
+cfg_fs! {
+    use anyhow::{anyhow, Context};
+    use std::path::{Path, PathBuf};
+
+    /// A [CertificateCache] that stores each certificate as a JSON file in a directory.
+    pub struct FileCertificateCache {
+        directory: PathBuf,
+    }
+
+    impl FileCertificateCache {
+        /// Constructs a new `FileCertificateCache`, the directory is created if it does not exist.
+        pub fn new<P: AsRef<Path>>(directory: P) -> MithrilResult<Self> {
+            let directory = directory.as_ref().to_path_buf();
+            std::fs::create_dir_all(&directory).with_context(|| {
+                format!(
+                    "Can not create certificate cache directory: '{}'",
+                    directory.display()
+                )
+            })?;
+
+            Ok(Self { directory })
+        }
+
+        fn certificate_path(&self, certificate_hash: &str) -> MithrilResult<PathBuf> {
+            // The hash is used as a file name: only hexadecimal ones are accepted to prevent
+            // reading or writing outside of the cache directory.
+            if certificate_hash.is_empty()
+                || !certificate_hash.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                return Err(anyhow!(
+                    "Invalid certificate hash, it must be hexadecimal: '{certificate_hash}'"
+                ));
+            }
+
+            Ok(self.directory.join(format!("{certificate_hash}.json")))
+        }
+    }
+
+    #[cfg_attr(target_family = "wasm", async_trait(?Send))]
+    #[cfg_attr(not(target_family = "wasm"), async_trait)]
+    impl CertificateCache for FileCertificateCache {
+        async fn get(&self, certificate_hash: &str) -> MithrilResult<Option<MithrilCertificate>> {
+            let path = self.certificate_path(certificate_hash)?;
+            if !path.exists() {
+                return Ok(None);
+            }
+
+            let content = std::fs::read_to_string(&path).with_context(|| {
+                format!("Can not read cached certificate: '{}'", path.display())
+            })?;
+            let certificate = serde_json::from_str(&content).with_context(|| {
+                format!("Can not deserialize cached certificate: '{}'", path.display())
+            })?;
+
+            Ok(Some(certificate))
+        }
+
+        async fn store(&self, certificate: &MithrilCertificate) -> MithrilResult<()> {
+            let path = self.certificate_path(&certificate.hash)?;
+            // Write in a temporary file first so an interrupted write never leaves a truncated
+            // certificate in the cache.
+            let temporary_path = path.with_extension("json.tmp");
+            std::fs::write(&temporary_path, serde_json::to_string(certificate)?).with_context(
+                || format!("Can not write cached certificate: '{}'", temporary_path.display()),
+            )?;
+            std::fs::rename(&temporary_path, &path).with_context(|| {
+                format!("Can not write cached certificate: '{}'", path.display())
+            })?;
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn certificate(hash: &str) -> MithrilCertificate {
+        MithrilCertificate {
+            hash: hash.to_string(),
+            ..MithrilCertificate::dummy()
+        }
+    }
+
+    #[tokio::test]
+    async fn memory_cache_store_and_get_certificates() {
+        let cache = MemoryCertificateCache::new();
+
+        assert_eq!(None, cache.get("abc123").await.unwrap());
+
+        cache.store(&certificate("abc123")).await.unwrap();
+
+        assert_eq!(
+            Some(certificate("abc123")),
+            cache.get("abc123").await.unwrap()
+        );
+        assert_eq!(None, cache.get("def456").await.unwrap());
+    }
+
+    #[cfg(feature = "fs")]
+    mod file_cache {
+        use mithril_common::test_utils::TempDir;
+
+        use super::*;
+
+        #[tokio::test]
+        async fn store_and_get_certificates() {
+            let directory = TempDir::create("client-certificate-cache", "store_and_get");
+            let cache = FileCertificateCache::new(&directory).unwrap();
+
+            assert_eq!(None, cache.get("abc123").await.unwrap());
+
+            cache.store(&certificate("abc123")).await.unwrap();
+
+            assert!(directory.join("abc123.json").exists());
+            assert_eq!(
+                Some(certificate("abc123")),
+                FileCertificateCache::new(&directory)
+                    .unwrap()
+                    .get("abc123")
+                    .await
+                    .unwrap()
+            );
+        }
+
+        #[tokio::test]
+        async fn reject_non_hexadecimal_hashes() {
+            let directory = TempDir::create("client-certificate-cache", "reject_non_hexadecimal");
+            let cache = FileCertificateCache::new(&directory).unwrap();
+
+            cache
+                .get("../abc123")
+                .await
+                .expect_err("A non hexadecimal hash should be rejected");
+            cache
+                .store(&certificate("../abc123"))
+                .await
+                .expect_err("A non hexadecimal hash should be rejected");
+        }
+    }
+}
@@ -4,6 +4,8 @@
 //!  - [get][CertificateClient::get]: get a certificate data from its hash
 //!  - [list][CertificateClient::list]: get the list of available certificates
 //!  - [verify_chain][CertificateClient::verify_chain]: verify a certificate chain
+//!  - [sync][CertificateClient::sync]: download the new certificates into a local
+//! [certificate cache][crate::certificate_cache]
 //!
 //! # Get a certificate
 //!
@@ -54,14 +56,57 @@
 //! #    Ok(())
 //! # }
 //! ```
+//!
+//! # Clock sanity checks
+//!
+//! While validating a chain, the timestamps and epochs of the certificates are checked against
+//! the local clock and against the previous certificate of the chain: a certificate sealed in the
+//! future or older than its predecessor could indicate a malicious or broken aggregator.
+//!
+//! By default anomalies are only logged as warnings, use the
+//! [strict mode][CertificateClockCheckMode::Strict] to make the validation fail instead.
+//!
+//! ```no_run
+//! # async fn run() -> mithril_client::MithrilResult<()> {
+//! use mithril_client::{certificate_client::CertificateClockCheckMode, ClientBuilder};
+//!
+//! let client = ClientBuilder::aggregator("YOUR_AGGREGATOR_ENDPOINT", "YOUR_GENESIS_VERIFICATION_KEY")
+//!     .with_certificate_clock_check_mode(CertificateClockCheckMode::Strict)
+//!     .build()?;
+//! let certificate = client.certificate().verify_chain("CERTIFICATE_HASH").await?;
+//! #    Ok(())
+//! # }
+//! ```
+//!
+//! # Certificate chain checkpoints
+//!
+//! An aggregator can sign checkpoints of its certificate chain: each checkpoint records a
+//! certificate whose chain was verified by the aggregator. When the verification key of these
+//! checkpoints is set, the validation of a chain stops at the first certificate that has a
+//! checkpoint with a valid signature instead of going down to the genesis certificate.
+//!
+//! ```no_run
+//! # async fn run() -> mithril_client::MithrilResult<()> {
+//! use mithril_client::ClientBuilder;
+//!
+//! let client = ClientBuilder::aggregator("YOUR_AGGREGATOR_ENDPOINT", "YOUR_GENESIS_VERIFICATION_KEY")
+//!     .with_certificate_chain_checkpoint_verification_key("YOUR_CHECKPOINT_VERIFICATION_KEY")
+//!     .build()?;
+//! let certificate = client.certificate().verify_chain("CERTIFICATE_HASH").await?;
+//! #    Ok(())
+//! # }
+//! ```
 
 use std::sync::Arc;
 
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
-use slog::{crit, debug, Logger};
+use chrono::{DateTime, Duration, Utc};
+use slog::{crit, debug, warn, Logger};
+use thiserror::Error;
 
 use crate::aggregator_client::{AggregatorClient, AggregatorClientError, AggregatorRequest};
+use crate::certificate_cache::CertificateCache;
 use crate::feedback::{FeedbackSender, MithrilEvent};
 use crate::{MithrilCertificate, MithrilCertificateListItem, MithrilResult};
 use mithril_common::crypto_helper::ProtocolGenesisVerificationKey;
@@ -71,8 +116,8 @@ use mithril_common::{
         CertificateVerifier as CommonCertificateVerifier,
         MithrilCertificateVerifier as CommonMithrilCertificateVerifier,
     },
-    entities::Certificate,
-    messages::CertificateMessage,
+    entities::{Certificate, Epoch},
+    messages::{CertificateChainCheckpointListMessage, CertificateMessage, PaginatedMessage},
 };
 
 #[cfg(test)]
@@ -94,6 +139,16 @@ pub trait CertificateVerifier: Sync + Send {
     async fn verify_chain(&self, certificate: &MithrilCertificate) -> MithrilResult<()>;
 }
 
+/// Report of a [certificate cache synchronization][CertificateClient::sync].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateSyncReport {
+    /// Hash of the latest certificate of the aggregator, `None` if it has no certificate
+    pub latest_certificate_hash: Option<String>,
+
+    /// Number of certificates downloaded and stored in the cache
+    pub downloaded_certificates: usize,
+}
+
 impl CertificateClient {
     /// Constructs a new `CertificateClient`.
     pub fn new(
@@ -103,6 +158,7 @@ impl CertificateClient {
     ) -> Self {
         let retriever = Arc::new(InternalCertificateRetriever {
             aggregator_client: aggregator_client.clone(),
+            cache: None,
             logger,
         });
 
@@ -113,6 +169,13 @@ impl CertificateClient {
         }
     }
 
+    /// Set the [CertificateCache] read before requesting the aggregator and filled
+    /// by [sync][Self::sync].
+    pub fn with_certificate_cache(mut self, cache: Arc<dyn CertificateCache>) -> Self {
+        self.retriever = Arc::new(self.retriever.with_cache(cache));
+        self
+    }
+
     /// Fetch a list of certificates
     pub async fn list(&self) -> MithrilResult<Vec<MithrilCertificateListItem>> {
         let response = self
@@ -120,8 +183,9 @@ impl CertificateClient {
             .get_content(AggregatorRequest::ListCertificates)
             .await
             .with_context(|| "CertificateClient can not get the certificate list")?;
-        let items = serde_json::from_str::<Vec<MithrilCertificateListItem>>(&response)
-            .with_context(|| "CertificateClient can not deserialize certificate list")?;
+        let items = PaginatedMessage::<MithrilCertificateListItem>::from_list_json(&response)
+            .with_context(|| "CertificateClient can not deserialize certificate list")?
+            .items;
 
         Ok(items)
     }
@@ -149,6 +213,85 @@ impl CertificateClient {
 
         Ok(certificate)
     }
+
+    /// Validate the chain starting with the given certificate, for example a certificate read
+    /// from a file.
+    ///
+    /// The previous certificates of the chain are read from the
+    /// [certificate cache][Self::with_certificate_cache] before falling back to the aggregator:
+    /// no request is sent if the cache is synchronized.
+    pub async fn verify_chain_from_certificate(
+        &self,
+        certificate: &MithrilCertificate,
+    ) -> MithrilResult<()> {
+        self.verifier
+            .verify_chain(certificate)
+            .await
+            .with_context(|| {
+                format!(
+                    "Certicate chain of certificate '{}' is invalid",
+                    certificate.hash
+                )
+            })
+    }
+
+    /// Download into the [certificate cache][Self::with_certificate_cache] the certificates
+    /// issued since the last synchronization.
+    ///
+    /// The chain is walked back from the latest certificate of the aggregator until a certificate
+    /// already in the cache, or the genesis certificate, is found. The new certificates are
+    /// stored from the oldest to the latest so an interrupted synchronization never leaves a gap
+    /// in the cached chain.
+    ///
+    /// The downloaded certificates are not verified, use [verify_chain][Self::verify_chain] to
+    /// validate them.
+    pub async fn sync(&self) -> MithrilResult<CertificateSyncReport> {
+        let cache = self.retriever.cache.as_ref().ok_or(anyhow!(
+            "No certificate cache set: can not synchronize the certificates"
+        ))?;
+        let latest_certificate_hash = match self.list().await?.into_iter().next() {
+            Some(latest_certificate) => latest_certificate.hash,
+            None => {
+                return Ok(CertificateSyncReport {
+                    latest_certificate_hash: None,
+                    downloaded_certificates: 0,
+                })
+            }
+        };
+
+        let mut new_certificates = vec![];
+        let mut next_hash = Some(latest_certificate_hash.clone());
+        while let Some(certificate_hash) = next_hash {
+            if cache.get(&certificate_hash).await?.is_some() {
+                break;
+            }
+            let certificate = self
+                .retriever
+                .get_from_aggregator(&certificate_hash)
+                .await?
+                .ok_or(anyhow!(
+                    "No certificate exist for hash '{certificate_hash}'"
+                ))?;
+            next_hash = (certificate.genesis_signature.is_empty()
+                && !certificate.previous_hash.is_empty())
+            .then(|| certificate.previous_hash.clone());
+            new_certificates.push(certificate);
+        }
+
+        for certificate in new_certificates.iter().rev() {
+            cache.store(certificate).await.with_context(|| {
+                format!(
+                    "CertificateClient can not store certificate '{}' in the cache",
+                    certificate.hash
+                )
+            })?;
+        }
+
+        Ok(CertificateSyncReport {
+            latest_certificate_hash: Some(latest_certificate_hash),
+            downloaded_certificates: new_certificates.len(),
+        })
+    }
 }
 
 /// Internal type to implement the [InternalCertificateRetriever] trait and avoid a circular
@@ -156,11 +299,38 @@ impl CertificateClient {
 /// a [CertificateRetriever] as a dependency.
 struct InternalCertificateRetriever {
     aggregator_client: Arc<dyn AggregatorClient>,
+    cache: Option<Arc<dyn CertificateCache>>,
     logger: Logger,
 }
 
 impl InternalCertificateRetriever {
+    fn with_cache(&self, cache: Arc<dyn CertificateCache>) -> Self {
+        Self {
+            aggregator_client: self.aggregator_client.clone(),
+            cache: Some(cache),
+            logger: self.logger.clone(),
+        }
+    }
+
     async fn get(&self, certificate_hash: &str) -> MithrilResult<Option<MithrilCertificate>> {
+        if let Some(cache) = &self.cache {
+            match cache.get(certificate_hash).await {
+                Ok(Some(certificate)) => return Ok(Some(certificate)),
+                Ok(None) => {}
+                Err(e) => warn!(
+                    self.logger,
+                    "Could not read certificate '{certificate_hash}' from the cache: {e:?}"
+                ),
+            }
+        }
+
+        self.get_from_aggregator(certificate_hash).await
+    }
+
+    async fn get_from_aggregator(
+        &self,
+        certificate_hash: &str,
+    ) -> MithrilResult<Option<MithrilCertificate>> {
         let response = self
             .aggregator_client
             .get_content(AggregatorRequest::GetCertificate {
@@ -188,12 +358,174 @@ impl InternalCertificateRetriever {
     }
 }
 
+/// Policy applied when a certificate timestamp or epoch is not plausible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CertificateClockCheckMode {
+    /// Do not check the certificates timestamps and epochs.
+    Disabled,
+    /// Log a warning for each anomaly found.
+    #[default]
+    Warn,
+    /// Fail the certificate chain validation if an anomaly is found.
+    Strict,
+}
+
+/// Anomaly found when checking a certificate against the local clock or its previous certificate.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CertificateClockAnomaly {
+    /// The certificate is sealed after the local time.
+    #[error(
+        "Certificate '{hash}' is sealed in the future: sealed_at='{sealed_at}', local time='{now}'"
+    )]
+    SealedInFuture {
+        /// Hash of the certificate
+        hash: String,
+        /// Seal time of the certificate
+        sealed_at: DateTime<Utc>,
+        /// Local time
+        now: DateTime<Utc>,
+    },
+
+    /// The certificate is sealed before it was initiated.
+    #[error("Certificate '{hash}' is sealed before being initiated: initiated_at='{initiated_at}', sealed_at='{sealed_at}'")]
+    SealedBeforeInitiated {
+        /// Hash of the certificate
+        hash: String,
+        /// Initiation time of the certificate
+        initiated_at: DateTime<Utc>,
+        /// Seal time of the certificate
+        sealed_at: DateTime<Utc>,
+    },
+
+    /// The certificate epoch is lower than the epoch of its previous certificate.
+    #[error("Certificate '{hash}' epoch '{epoch}' is lower than its previous certificate '{previous_hash}' epoch '{previous_epoch}'")]
+    EpochNotMonotonic {
+        /// Hash of the certificate
+        hash: String,
+        /// Epoch of the certificate
+        epoch: Epoch,
+        /// Hash of the previous certificate
+        previous_hash: String,
+        /// Epoch of the previous certificate
+        previous_epoch: Epoch,
+    },
+
+    /// The certificate is sealed before its previous certificate.
+    #[error("Certificate '{hash}' is sealed before its previous certificate '{previous_hash}': sealed_at='{sealed_at}', previous sealed_at='{previous_sealed_at}'")]
+    SealedBeforePrevious {
+        /// Hash of the certificate
+        hash: String,
+        /// Seal time of the certificate
+        sealed_at: DateTime<Utc>,
+        /// Hash of the previous certificate
+        previous_hash: String,
+        /// Seal time of the previous certificate
+        previous_sealed_at: DateTime<Utc>,
+    },
+}
+
+/// Check that the certificates timestamps and epochs are plausible.
+pub struct CertificateClockChecker {
+    mode: CertificateClockCheckMode,
+    tolerance: Duration,
+    logger: Logger,
+}
+
+impl CertificateClockChecker {
+    /// Default tolerance on the local clock drift, in seconds.
+    pub const DEFAULT_TOLERANCE_IN_SECONDS: i64 = 300;
+
+    /// Constructs a new `CertificateClockChecker`.
+    pub fn new(mode: CertificateClockCheckMode, tolerance: Duration, logger: Logger) -> Self {
+        Self {
+            mode,
+            tolerance,
+            logger,
+        }
+    }
+
+    /// List the anomalies of the given certificate compared to the local time and its previous
+    /// certificate.
+    pub fn list_anomalies(
+        &self,
+        certificate: &Certificate,
+        previous_certificate: Option<&Certificate>,
+        now: DateTime<Utc>,
+    ) -> Vec<CertificateClockAnomaly> {
+        let mut anomalies = vec![];
+        let metadata = &certificate.metadata;
+
+        if metadata.sealed_at > now + self.tolerance {
+            anomalies.push(CertificateClockAnomaly::SealedInFuture {
+                hash: certificate.hash.clone(),
+                sealed_at: metadata.sealed_at,
+                now,
+            });
+        }
+        if metadata.sealed_at < metadata.initiated_at {
+            anomalies.push(CertificateClockAnomaly::SealedBeforeInitiated {
+                hash: certificate.hash.clone(),
+                initiated_at: metadata.initiated_at,
+                sealed_at: metadata.sealed_at,
+            });
+        }
+        if let Some(previous) = previous_certificate {
+            if certificate.epoch < previous.epoch {
+                anomalies.push(CertificateClockAnomaly::EpochNotMonotonic {
+                    hash: certificate.hash.clone(),
+                    epoch: certificate.epoch,
+                    previous_hash: previous.hash.clone(),
+                    previous_epoch: previous.epoch,
+                });
+            }
+            if metadata.sealed_at + self.tolerance < previous.metadata.sealed_at {
+                anomalies.push(CertificateClockAnomaly::SealedBeforePrevious {
+                    hash: certificate.hash.clone(),
+                    sealed_at: metadata.sealed_at,
+                    previous_hash: previous.hash.clone(),
+                    previous_sealed_at: previous.metadata.sealed_at,
+                });
+            }
+        }
+
+        anomalies
+    }
+
+    /// Check the given certificate, logging the anomalies found or failing if the
+    /// [strict mode][CertificateClockCheckMode::Strict] is enabled.
+    pub fn check(
+        &self,
+        certificate: &Certificate,
+        previous_certificate: Option<&Certificate>,
+        now: DateTime<Utc>,
+    ) -> MithrilResult<()> {
+        if self.mode == CertificateClockCheckMode::Disabled {
+            return Ok(());
+        }
+
+        let anomalies = self.list_anomalies(certificate, previous_certificate, now);
+        match (self.mode, anomalies.first()) {
+            (_, None) => Ok(()),
+            (CertificateClockCheckMode::Strict, Some(anomaly)) => Err(anyhow!(anomaly.clone())),
+            _ => {
+                for anomaly in anomalies {
+                    warn!(self.logger, "Certificate clock sanity check: {anomaly}");
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Implementation of a [CertificateVerifier] that can send feedbacks using
 /// the [feedback][crate::feedback] mechanism.
 pub struct MithrilCertificateVerifier {
+    retriever: Arc<InternalCertificateRetriever>,
     internal_verifier: Arc<dyn CommonCertificateVerifier>,
     genesis_verification_key: ProtocolGenesisVerificationKey,
+    checkpoint_verification_key: Option<ProtocolGenesisVerificationKey>,
     feedback_sender: FeedbackSender,
+    clock_checker: CertificateClockChecker,
 }
 
 impl MithrilCertificateVerifier {
@@ -206,22 +538,110 @@ impl MithrilCertificateVerifier {
     ) -> MithrilResult<MithrilCertificateVerifier> {
         let retriever = Arc::new(InternalCertificateRetriever {
             aggregator_client: aggregator_client.clone(),
+            cache: None,
             logger: logger.clone(),
         });
         let internal_verifier = Arc::new(CommonMithrilCertificateVerifier::new(
-            logger,
+            logger.clone(),
             retriever.clone(),
         ));
         let genesis_verification_key =
             ProtocolGenesisVerificationKey::try_from(genesis_verification_key)
                 .with_context(|| "Invalid genesis verification key")?;
+        let clock_checker = CertificateClockChecker::new(
+            CertificateClockCheckMode::default(),
+            Duration::try_seconds(CertificateClockChecker::DEFAULT_TOLERANCE_IN_SECONDS).unwrap(),
+            logger,
+        );
 
         Ok(Self {
+            retriever,
             internal_verifier,
             genesis_verification_key,
+            checkpoint_verification_key: None,
             feedback_sender,
+            clock_checker,
         })
     }
+
+    /// Trust the checkpoints of the certificate chain signed with the secret key matching the
+    /// given verification key: the validation of a chain stops at the first certificate that has
+    /// a checkpoint with a valid signature.
+    pub fn with_checkpoint_verification_key(
+        mut self,
+        checkpoint_verification_key: &str,
+    ) -> MithrilResult<Self> {
+        self.checkpoint_verification_key = Some(
+            ProtocolGenesisVerificationKey::try_from(checkpoint_verification_key)
+                .with_context(|| "Invalid certificate chain checkpoint verification key")?,
+        );
+
+        Ok(self)
+    }
+
+    /// Set the [policy][CertificateClockCheckMode] applied when a certificate timestamp or
+    /// epoch is not plausible.
+    pub fn with_clock_check_mode(mut self, mode: CertificateClockCheckMode) -> Self {
+        self.clock_checker.mode = mode;
+        self
+    }
+
+    /// Set the [CertificateCache] read before requesting the aggregator when retrieving
+    /// the previous certificates of the chain.
+    pub fn with_certificate_cache(mut self, cache: Arc<dyn CertificateCache>) -> Self {
+        self.retriever = Arc::new(self.retriever.with_cache(cache));
+        self.internal_verifier = Arc::new(CommonMithrilCertificateVerifier::new(
+            self.retriever.logger.clone(),
+            self.retriever.clone(),
+        ));
+        self
+    }
+}
+
+impl MithrilCertificateVerifier {
+    /// Get the hashes of the checkpointed certificates whose checkpoint signature is valid.
+    ///
+    /// The validation of the chain does not depend on the checkpoints: if they can not be
+    /// retrieved the chain is validated down to the genesis certificate.
+    async fn get_trusted_checkpoints(&self) -> Vec<String> {
+        let Some(checkpoint_verification_key) = &self.checkpoint_verification_key else {
+            return vec![];
+        };
+        let logger = &self.retriever.logger;
+
+        let checkpoints = match self
+            .retriever
+            .aggregator_client
+            .get_content(AggregatorRequest::ListCertificateChainCheckpoints)
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|response| {
+                serde_json::from_str::<CertificateChainCheckpointListMessage>(&response)
+                    .map_err(anyhow::Error::from)
+            }) {
+            Ok(checkpoints) => checkpoints,
+            Err(e) => {
+                warn!(
+                    logger,
+                    "Could not get the certificate chain checkpoints, the chain is validated down to the genesis certificate: {e:?}"
+                );
+                return vec![];
+            }
+        };
+
+        checkpoints
+            .into_iter()
+            .filter_map(|checkpoint| {
+                match checkpoint.verify_signature(checkpoint_verification_key) {
+                    Ok(()) => Some(checkpoint.certificate_hash),
+                    Err(e) => {
+                        warn!(logger, "Ignoring a certificate chain checkpoint: {e:?}");
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
 }
 
 #[cfg_attr(target_family = "wasm", async_trait(?Send))]
@@ -238,12 +658,29 @@ impl CertificateVerifier for MithrilCertificateVerifier {
             })
             .await;
 
-        let mut current_certificate = certificate.clone().try_into()?;
+        let trusted_checkpoints = self.get_trusted_checkpoints().await;
+        let mut current_certificate: Certificate = certificate.clone().try_into()?;
         loop {
+            if trusted_checkpoints.contains(&current_certificate.hash)
+                && current_certificate.hash == current_certificate.compute_hash()
+            {
+                debug!(
+                    self.retriever.logger,
+                    "Certificate '{}' has a trusted checkpoint, its chain is valid",
+                    current_certificate.hash
+                );
+                break;
+            }
+
             let previous_or_none = self
                 .internal_verifier
                 .verify_certificate(&current_certificate, &self.genesis_verification_key)
                 .await?;
+            self.clock_checker.check(
+                &current_certificate,
+                previous_or_none.as_ref(),
+                Utc::now(),
+            )?;
 
             self.feedback_sender
                 .send_event(MithrilEvent::CertificateValidated {
@@ -334,6 +771,23 @@ mod tests {
         assert_eq!(expected, items);
     }
 
+    #[tokio::test]
+    async fn get_certificate_list_from_a_paginated_message() {
+        let expected = vec![MithrilCertificateListItem {
+            hash: "cert-hash-123".to_string(),
+            ..MithrilCertificateListItem::dummy()
+        }];
+        let message = PaginatedMessage::single_page(expected.clone());
+        let mut aggregator_client = MockAggregatorHTTPClient::new();
+        aggregator_client
+            .expect_get_content()
+            .return_once(move |_| Ok(serde_json::to_string(&message).unwrap()));
+        let certificate_client = build_client(Arc::new(aggregator_client), None);
+        let items = certificate_client.list().await.unwrap();
+
+        assert_eq!(expected, items);
+    }
+
     #[tokio::test]
     async fn get_certificate_empty_list() {
         let mut aggregator_client = MockAggregatorHTTPClient::new();
@@ -476,6 +930,137 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    mod clock_checker {
+        use super::*;
+
+        fn build_certificate(
+            hash: &str,
+            epoch: u64,
+            initiated_at: DateTime<Utc>,
+            sealed_at: DateTime<Utc>,
+        ) -> Certificate {
+            let mut certificate = fake_data::certificate(hash.to_string());
+            certificate.epoch = Epoch(epoch);
+            certificate.metadata.initiated_at = initiated_at;
+            certificate.metadata.sealed_at = sealed_at;
+            certificate
+        }
+
+        fn build_checker(mode: CertificateClockCheckMode) -> CertificateClockChecker {
+            CertificateClockChecker::new(
+                mode,
+                Duration::try_seconds(60).unwrap(),
+                test_utils::test_logger(),
+            )
+        }
+
+        #[test]
+        fn no_anomaly_for_plausible_certificates() {
+            let now = Utc::now();
+            let previous = build_certificate(
+                "previous",
+                1,
+                now - Duration::try_hours(2).unwrap(),
+                now - Duration::try_hours(1).unwrap(),
+            );
+            let certificate =
+                build_certificate("current", 2, now - Duration::try_minutes(10).unwrap(), now);
+
+            let anomalies = build_checker(CertificateClockCheckMode::Strict).list_anomalies(
+                &certificate,
+                Some(&previous),
+                now,
+            );
+
+            assert_eq!(Vec::<CertificateClockAnomaly>::new(), anomalies);
+        }
+
+        #[test]
+        fn detect_certificate_sealed_in_the_future_beyond_tolerance() {
+            let now = Utc::now();
+            let checker = build_checker(CertificateClockCheckMode::Strict);
+
+            let within_tolerance =
+                build_certificate("hash", 1, now, now + Duration::try_seconds(30).unwrap());
+            assert!(checker
+                .list_anomalies(&within_tolerance, None, now)
+                .is_empty());
+
+            let beyond_tolerance =
+                build_certificate("hash", 1, now, now + Duration::try_hours(1).unwrap());
+            assert_eq!(
+                vec![CertificateClockAnomaly::SealedInFuture {
+                    hash: "hash".to_string(),
+                    sealed_at: beyond_tolerance.metadata.sealed_at,
+                    now,
+                }],
+                checker.list_anomalies(&beyond_tolerance, None, now)
+            );
+        }
+
+        #[test]
+        fn detect_certificate_sealed_before_being_initiated() {
+            let now = Utc::now();
+            let certificate =
+                build_certificate("hash", 1, now, now - Duration::try_minutes(1).unwrap());
+
+            let anomalies = build_checker(CertificateClockCheckMode::Strict).list_anomalies(
+                &certificate,
+                None,
+                now,
+            );
+
+            assert!(matches!(
+                anomalies.as_slice(),
+                [CertificateClockAnomaly::SealedBeforeInitiated { .. }]
+            ));
+        }
+
+        #[test]
+        fn detect_non_monotonic_epoch_and_timestamps() {
+            let now = Utc::now();
+            let previous =
+                build_certificate("previous", 5, now - Duration::try_minutes(10).unwrap(), now);
+            let certificate = build_certificate(
+                "current",
+                4,
+                now - Duration::try_hours(2).unwrap(),
+                now - Duration::try_hours(1).unwrap(),
+            );
+
+            let anomalies = build_checker(CertificateClockCheckMode::Strict).list_anomalies(
+                &certificate,
+                Some(&previous),
+                now,
+            );
+
+            assert!(matches!(
+                anomalies.as_slice(),
+                [
+                    CertificateClockAnomaly::EpochNotMonotonic { .. },
+                    CertificateClockAnomaly::SealedBeforePrevious { .. }
+                ]
+            ));
+        }
+
+        #[test]
+        fn check_fails_only_in_strict_mode() {
+            let now = Utc::now();
+            let certificate =
+                build_certificate("hash", 1, now, now + Duration::try_days(1).unwrap());
+
+            build_checker(CertificateClockCheckMode::Disabled)
+                .check(&certificate, None, now)
+                .unwrap();
+            build_checker(CertificateClockCheckMode::Warn)
+                .check(&certificate, None, now)
+                .unwrap();
+            build_checker(CertificateClockCheckMode::Strict)
+                .check(&certificate, None, now)
+                .expect_err("Strict mode should fail when an anomaly is found");
+        }
+    }
+
     #[tokio::test]
     async fn verify_chain_return_certificate_with_given_hash() {
         let (chain, verifier) = setup_certificate_chain(3, 1);
@@ -516,4 +1101,303 @@ mod tests {
 
         assert_eq!(certificate.hash, last_certificate_hash);
     }
+
+    #[tokio::test]
+    async fn verify_chain_from_certificate_does_not_request_the_aggregator() {
+        let certificate = MithrilCertificate::dummy();
+        let mut verifier = MockCertificateVerifier::new();
+        verifier
+            .expect_verify_chain()
+            .with(eq(certificate.clone()))
+            .returning(|_| Ok(()))
+            .once();
+        let certificate_client = build_client(
+            Arc::new(MockAggregatorHTTPClient::new()),
+            Some(Arc::new(verifier)),
+        );
+
+        certificate_client
+            .verify_chain_from_certificate(&certificate)
+            .await
+            .expect("Chain validation should succeed");
+    }
+
+    mod sync {
+        use crate::certificate_cache::MemoryCertificateCache;
+
+        use super::*;
+
+        fn aggregator_client_serving_chain(
+            chain: &[Certificate],
+            latest_certificate_hash: Option<String>,
+        ) -> MockAggregatorHTTPClient {
+            let mut aggregator_client = MockAggregatorHTTPClient::new();
+            let list = latest_certificate_hash
+                .into_iter()
+                .map(|hash| MithrilCertificateListItem {
+                    hash,
+                    ..MithrilCertificateListItem::dummy()
+                })
+                .collect::<Vec<_>>();
+            aggregator_client
+                .expect_get_content()
+                .with(eq(AggregatorRequest::ListCertificates))
+                .returning(move |_| Ok(serde_json::to_string(&list).unwrap()));
+
+            for certificate in chain.iter().cloned() {
+                let hash = certificate.hash.clone();
+                let message = serde_json::to_string(
+                    &TryInto::<CertificateMessage>::try_into(certificate).unwrap(),
+                )
+                .unwrap();
+                aggregator_client
+                    .expect_get_content()
+                    .with(eq(AggregatorRequest::GetCertificate { hash }))
+                    .returning(move |_| Ok(message.to_owned()));
+            }
+
+            aggregator_client
+        }
+
+        fn build_client_with_cache(
+            aggregator_client: MockAggregatorHTTPClient,
+            cache: Arc<dyn CertificateCache>,
+        ) -> CertificateClient {
+            build_client(Arc::new(aggregator_client), None).with_certificate_cache(cache)
+        }
+
+        #[tokio::test]
+        async fn sync_fails_without_certificate_cache() {
+            let certificate_client =
+                build_client(Arc::new(aggregator_client_serving_chain(&[], None)), None);
+
+            certificate_client
+                .sync()
+                .await
+                .expect_err("Sync should fail without a certificate cache");
+        }
+
+        #[tokio::test]
+        async fn sync_without_certificate_on_the_aggregator_download_nothing() {
+            let certificate_client = build_client_with_cache(
+                aggregator_client_serving_chain(&[], None),
+                Arc::new(MemoryCertificateCache::new()),
+            );
+
+            let report = certificate_client.sync().await.unwrap();
+
+            assert_eq!(
+                CertificateSyncReport {
+                    latest_certificate_hash: None,
+                    downloaded_certificates: 0,
+                },
+                report
+            );
+        }
+
+        #[tokio::test]
+        async fn sync_download_the_whole_chain_then_only_the_new_certificates() {
+            let (chain, _) = setup_certificate_chain(5, 1);
+            let cache = Arc::new(MemoryCertificateCache::new());
+
+            let report = build_client_with_cache(
+                aggregator_client_serving_chain(&chain[1..], Some(chain[1].hash.clone())),
+                cache.clone(),
+            )
+            .sync()
+            .await
+            .unwrap();
+
+            assert_eq!(
+                CertificateSyncReport {
+                    latest_certificate_hash: Some(chain[1].hash.clone()),
+                    downloaded_certificates: chain.len() - 1,
+                },
+                report
+            );
+            for certificate in &chain[1..] {
+                assert!(cache.get(&certificate.hash).await.unwrap().is_some());
+            }
+
+            // Only the new latest certificate is requested, the others are already cached
+            let report = build_client_with_cache(
+                aggregator_client_serving_chain(&chain[..1], Some(chain[0].hash.clone())),
+                cache.clone(),
+            )
+            .sync()
+            .await
+            .unwrap();
+
+            assert_eq!(
+                CertificateSyncReport {
+                    latest_certificate_hash: Some(chain[0].hash.clone()),
+                    downloaded_certificates: 1,
+                },
+                report
+            );
+            assert!(cache.get(&chain[0].hash).await.unwrap().is_some());
+        }
+
+        #[tokio::test]
+        async fn verify_chain_prefers_the_certificate_cache_over_the_aggregator() {
+            let (chain, verifier) = setup_certificate_chain(3, 1);
+            let verification_key: String = verifier.to_verification_key().try_into().unwrap();
+            let cache = Arc::new(MemoryCertificateCache::new());
+            for certificate in chain.iter().cloned() {
+                cache.store(&certificate.try_into().unwrap()).await.unwrap();
+            }
+
+            // The aggregator does not serve any certificate: all of them must be read from the cache
+            let aggregator_client = Arc::new(aggregator_client_serving_chain(&[], None));
+            let certificate_client = build_client(
+                aggregator_client.clone(),
+                Some(Arc::new(
+                    MithrilCertificateVerifier::new(
+                        aggregator_client,
+                        &verification_key,
+                        FeedbackSender::new(&[]),
+                        test_utils::test_logger(),
+                    )
+                    .unwrap()
+                    .with_certificate_cache(cache.clone()),
+                )),
+            )
+            .with_certificate_cache(cache);
+
+            let certificate = certificate_client
+                .verify_chain(&chain[0].hash)
+                .await
+                .expect("Chain validation should succeed");
+
+            assert_eq!(chain[0].hash, certificate.hash);
+        }
+    }
+
+    mod checkpoints {
+        use mithril_common::crypto_helper::ProtocolGenesisSigner;
+        use mithril_common::messages::CertificateChainCheckpointListItemMessage;
+
+        use super::*;
+
+        fn signed_checkpoint(
+            signer: &ProtocolGenesisSigner,
+            certificate: &Certificate,
+        ) -> CertificateChainCheckpointListItemMessage {
+            let signature = signer.sign(
+                &CertificateChainCheckpointListItemMessage::compute_signed_message(
+                    certificate.epoch,
+                    &certificate.hash,
+                ),
+            );
+
+            CertificateChainCheckpointListItemMessage {
+                epoch: certificate.epoch,
+                certificate_hash: certificate.hash.clone(),
+                signature: signature.to_bytes_hex(),
+                created_at: Utc::now(),
+            }
+        }
+
+        /// Aggregator serving the given checkpoints and, once each, the given certificates
+        fn aggregator_client_serving_chain_and_checkpoints(
+            chain: &[Certificate],
+            checkpoints: CertificateChainCheckpointListMessage,
+        ) -> MockAggregatorHTTPClient {
+            let mut aggregator_client = MockAggregatorHTTPClient::new();
+            aggregator_client
+                .expect_get_content()
+                .with(eq(AggregatorRequest::ListCertificateChainCheckpoints))
+                .returning(move |_| Ok(serde_json::to_string(&checkpoints).unwrap()));
+            for certificate in chain.iter().cloned() {
+                let hash = certificate.hash.clone();
+                let message = serde_json::to_string(
+                    &TryInto::<CertificateMessage>::try_into(certificate).unwrap(),
+                )
+                .unwrap();
+                aggregator_client
+                    .expect_get_content()
+                    .with(eq(AggregatorRequest::GetCertificate { hash }))
+                    .return_once(move |_| Ok(message))
+                    .once();
+            }
+
+            aggregator_client
+        }
+
+        fn build_client_trusting_checkpoints(
+            aggregator_client: MockAggregatorHTTPClient,
+            genesis_verification_key: &str,
+            checkpoint_signer: &ProtocolGenesisSigner,
+        ) -> CertificateClient {
+            let checkpoint_verification_key: String = checkpoint_signer
+                .create_genesis_verifier()
+                .to_verification_key()
+                .try_into()
+                .unwrap();
+            let aggregator_client = Arc::new(aggregator_client);
+
+            build_client(
+                aggregator_client.clone(),
+                Some(Arc::new(
+                    MithrilCertificateVerifier::new(
+                        aggregator_client,
+                        genesis_verification_key,
+                        FeedbackSender::new(&[]),
+                        test_utils::test_logger(),
+                    )
+                    .unwrap()
+                    .with_checkpoint_verification_key(&checkpoint_verification_key)
+                    .unwrap(),
+                )),
+            )
+        }
+
+        #[tokio::test]
+        async fn verify_chain_stops_at_a_checkpoint_with_a_valid_signature() {
+            let (chain, verifier) = setup_certificate_chain(5, 1);
+            let verification_key: String = verifier.to_verification_key().try_into().unwrap();
+            let checkpoint_signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+            // Only the certificates down to the checkpointed one are served by the aggregator
+            let aggregator_client = aggregator_client_serving_chain_and_checkpoints(
+                &chain[0..=2],
+                vec![signed_checkpoint(&checkpoint_signer, &chain[2])],
+            );
+            let certificate_client = build_client_trusting_checkpoints(
+                aggregator_client,
+                &verification_key,
+                &checkpoint_signer,
+            );
+
+            let certificate = certificate_client
+                .verify_chain(&chain[0].hash)
+                .await
+                .expect("Chain validation should succeed");
+
+            assert_eq!(chain[0].hash, certificate.hash);
+        }
+
+        #[tokio::test]
+        async fn verify_chain_ignores_a_checkpoint_with_an_invalid_signature() {
+            let (chain, verifier) = setup_certificate_chain(5, 1);
+            let verification_key: String = verifier.to_verification_key().try_into().unwrap();
+            let checkpoint_signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+            let forged_checkpoint = CertificateChainCheckpointListItemMessage {
+                epoch: chain[2].epoch + 1,
+                ..signed_checkpoint(&checkpoint_signer, &chain[2])
+            };
+            // The whole chain must be served by the aggregator
+            let aggregator_client =
+                aggregator_client_serving_chain_and_checkpoints(&chain, vec![forged_checkpoint]);
+            let certificate_client = build_client_trusting_checkpoints(
+                aggregator_client,
+                &verification_key,
+                &checkpoint_signer,
+            );
+
+            certificate_client
+                .verify_chain(&chain[0].hash)
+                .await
+                .expect("Chain validation should succeed");
+        }
+    }
 }
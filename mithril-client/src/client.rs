@@ -3,12 +3,19 @@ use mithril_common::api_version::APIVersionProvider;
 use reqwest::Url;
 use slog::{o, Logger};
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::aggregator_client::{AggregatorClient, AggregatorHTTPClient};
+use crate::aggregator_client::{
+    AggregatorClient, AggregatorHTTPClient, AggregatorRequestMiddleware,
+};
+use crate::aggregator_failover::{
+    AggregatorFailoverClient, DEFAULT_UNHEALTHY_ENDPOINT_RETRY_DELAY,
+};
 #[cfg(feature = "unstable")]
 use crate::cardano_transaction_client::CardanoTransactionClient;
+use crate::certificate_cache::CertificateCache;
 use crate::certificate_client::{
-    CertificateClient, CertificateVerifier, MithrilCertificateVerifier,
+    CertificateClient, CertificateClockCheckMode, CertificateVerifier, MithrilCertificateVerifier,
 };
 use crate::feedback::{FeedbackReceiver, FeedbackSender};
 use crate::mithril_stake_distribution_client::MithrilStakeDistributionClient;
@@ -54,29 +61,48 @@ impl Client {
 
 /// Builder than can be used to create a [Client] easily or with custom dependencies.
 pub struct ClientBuilder {
-    aggregator_endpoint: Option<String>,
+    aggregator_endpoints: Vec<String>,
+    unhealthy_aggregator_retry_delay: Duration,
     genesis_verification_key: String,
     aggregator_client: Option<Arc<dyn AggregatorClient>>,
     certificate_verifier: Option<Arc<dyn CertificateVerifier>>,
+    certificate_clock_check_mode: CertificateClockCheckMode,
+    certificate_chain_checkpoint_verification_key: Option<String>,
+    certificate_cache: Option<Arc<dyn CertificateCache>>,
     #[cfg(feature = "fs")]
     snapshot_downloader: Option<Arc<dyn SnapshotDownloader>>,
     logger: Option<Logger>,
     feedback_receivers: Vec<Arc<dyn FeedbackReceiver>>,
+    aggregator_request_middlewares: Vec<Arc<dyn AggregatorRequestMiddleware>>,
 }
 
 impl ClientBuilder {
     /// Constructs a new `ClientBuilder` that fetches data from the aggregator at the given
     /// endpoint and with the given genesis verification key.
     pub fn aggregator(endpoint: &str, genesis_verification_key: &str) -> ClientBuilder {
+        Self::aggregators(&[endpoint], genesis_verification_key)
+    }
+
+    /// Constructs a new `ClientBuilder` that fetches data from the aggregators at the given
+    /// endpoints, sorted by decreasing priority, and with the given genesis verification key.
+    ///
+    /// The requests are sent to the first healthy aggregator and fail over to the next ones on
+    /// error, see [AggregatorFailoverClient] for details.
+    pub fn aggregators(endpoints: &[&str], genesis_verification_key: &str) -> ClientBuilder {
         Self {
-            aggregator_endpoint: Some(endpoint.to_string()),
+            aggregator_endpoints: endpoints.iter().map(|e| e.to_string()).collect(),
+            unhealthy_aggregator_retry_delay: DEFAULT_UNHEALTHY_ENDPOINT_RETRY_DELAY,
             genesis_verification_key: genesis_verification_key.to_string(),
             aggregator_client: None,
             certificate_verifier: None,
+            certificate_clock_check_mode: CertificateClockCheckMode::default(),
+            certificate_chain_checkpoint_verification_key: None,
+            certificate_cache: None,
             #[cfg(feature = "fs")]
             snapshot_downloader: None,
             logger: None,
             feedback_receivers: vec![],
+            aggregator_request_middlewares: vec![],
         }
     }
 
@@ -86,14 +112,19 @@ impl ClientBuilder {
     /// to request data from the aggregator.
     pub fn new(genesis_verification_key: &str) -> ClientBuilder {
         Self {
-            aggregator_endpoint: None,
+            aggregator_endpoints: vec![],
+            unhealthy_aggregator_retry_delay: DEFAULT_UNHEALTHY_ENDPOINT_RETRY_DELAY,
             genesis_verification_key: genesis_verification_key.to_string(),
             aggregator_client: None,
             certificate_verifier: None,
+            certificate_clock_check_mode: CertificateClockCheckMode::default(),
+            certificate_chain_checkpoint_verification_key: None,
+            certificate_cache: None,
             #[cfg(feature = "fs")]
             snapshot_downloader: None,
             logger: None,
             feedback_receivers: vec![],
+            aggregator_request_middlewares: vec![],
         }
     }
 
@@ -110,22 +141,45 @@ impl ClientBuilder {
 
         let aggregator_client = match self.aggregator_client {
             None => {
-                let endpoint = self
-                    .aggregator_endpoint
-                    .ok_or(anyhow!("No aggregator endpoint set: \
-                    You must either provide an aggregator endpoint or your own AggregatorClient implementation"))?;
-                let endpoint_url = Url::parse(&endpoint)
-                    .with_context(|| format!("Invalid aggregator endpoint, it must be a correctly formed url: '{endpoint}'"))?;
-
-                Arc::new(
-                    AggregatorHTTPClient::new(
+                if self.aggregator_endpoints.is_empty() {
+                    return Err(anyhow!("No aggregator endpoint set: \
+                    You must either provide an aggregator endpoint or your own AggregatorClient implementation"));
+                }
+                let api_versions = APIVersionProvider::compute_all_versions_sorted()
+                    .with_context(|| "Could not compute aggregator api versions")?;
+
+                let mut endpoints_clients: Vec<(String, Arc<dyn AggregatorClient>)> = vec![];
+                for endpoint in self.aggregator_endpoints {
+                    let endpoint_url = Url::parse(&endpoint)
+                        .with_context(|| format!("Invalid aggregator endpoint, it must be a correctly formed url: '{endpoint}'"))?;
+                    let aggregator_http_client = AggregatorHTTPClient::new(
                         endpoint_url,
-                        APIVersionProvider::compute_all_versions_sorted()
-                            .with_context(|| "Could not compute aggregator api versions")?,
+                        api_versions.clone(),
                         logger.clone(),
                     )
-                    .with_context(|| "Building aggregator client failed")?,
-                )
+                    .with_context(|| "Building aggregator client failed")?;
+                    let aggregator_http_client = self
+                        .aggregator_request_middlewares
+                        .iter()
+                        .fold(aggregator_http_client, |client, middleware| {
+                            client.with_middleware(middleware.clone())
+                        });
+                    endpoints_clients.push((endpoint, Arc::new(aggregator_http_client)));
+                }
+
+                if endpoints_clients.len() == 1 {
+                    endpoints_clients.remove(0).1
+                } else {
+                    Arc::new(
+                        AggregatorFailoverClient::new(
+                            endpoints_clients,
+                            &self.genesis_verification_key,
+                            logger.clone(),
+                        )
+                        .with_context(|| "Building aggregator failover client failed")?
+                        .with_unhealthy_endpoint_retry_delay(self.unhealthy_aggregator_retry_delay),
+                    )
+                }
             }
             Some(client) => client,
         };
@@ -144,25 +198,41 @@ impl ClientBuilder {
             Arc::new(CardanoTransactionClient::new(aggregator_client.clone()));
 
         let certificate_verifier = match self.certificate_verifier {
-            None => Arc::new(
-                MithrilCertificateVerifier::new(
+            None => {
+                let mut verifier = MithrilCertificateVerifier::new(
                     aggregator_client.clone(),
                     &self.genesis_verification_key,
                     feedback_sender.clone(),
                     logger.clone(),
                 )
-                .with_context(|| "Building certificate verifier failed")?,
-            ),
+                .with_context(|| "Building certificate verifier failed")?
+                .with_clock_check_mode(self.certificate_clock_check_mode);
+                if let Some(checkpoint_verification_key) =
+                    &self.certificate_chain_checkpoint_verification_key
+                {
+                    verifier =
+                        verifier.with_checkpoint_verification_key(checkpoint_verification_key)?;
+                }
+                if let Some(cache) = &self.certificate_cache {
+                    verifier = verifier.with_certificate_cache(cache.clone());
+                }
+                Arc::new(verifier)
+            }
             Some(verifier) => verifier,
         };
-        let certificate_client = Arc::new(CertificateClient::new(
+        let mut certificate_client = CertificateClient::new(
             aggregator_client.clone(),
             certificate_verifier,
             logger.clone(),
-        ));
+        );
+        if let Some(cache) = self.certificate_cache {
+            certificate_client = certificate_client.with_certificate_cache(cache);
+        }
+        let certificate_client = Arc::new(certificate_client);
 
         let mithril_stake_distribution_client = Arc::new(MithrilStakeDistributionClient::new(
             aggregator_client.clone(),
+            certificate_client.clone(),
         ));
         let snapshot_client = Arc::new(SnapshotClient::new(
             aggregator_client,
@@ -192,6 +262,28 @@ impl ClientBuilder {
         self
     }
 
+    /// Add a [middleware][AggregatorRequestMiddleware] applied to all the requests sent to the
+    /// aggregator, i.e. to add authentication or custom headers with
+    /// [AggregatorRequestHeaders][crate::aggregator_client::AggregatorRequestHeaders].
+    ///
+    /// Ignored if a custom [AggregatorClient] is set.
+    pub fn add_aggregator_request_middleware(
+        mut self,
+        middleware: Arc<dyn AggregatorRequestMiddleware>,
+    ) -> ClientBuilder {
+        self.aggregator_request_middlewares.push(middleware);
+        self
+    }
+
+    /// Set the delay after which an aggregator endpoint that failed is probed again when several
+    /// aggregator endpoints are set (default: 30 seconds).
+    ///
+    /// Ignored if a custom [AggregatorClient] is set.
+    pub fn with_unhealthy_aggregator_retry_delay(mut self, delay: Duration) -> ClientBuilder {
+        self.unhealthy_aggregator_retry_delay = delay;
+        self
+    }
+
     /// Set the [CertificateVerifier] that will be used to validate certificates.
     pub fn with_certificate_verifier(
         mut self,
@@ -201,6 +293,42 @@ impl ClientBuilder {
         self
     }
 
+    /// Set the [policy][CertificateClockCheckMode] applied by the default [CertificateVerifier]
+    /// when a certificate timestamp or epoch is not plausible.
+    ///
+    /// Ignored if a custom [CertificateVerifier] is set.
+    pub fn with_certificate_clock_check_mode(
+        mut self,
+        certificate_clock_check_mode: CertificateClockCheckMode,
+    ) -> ClientBuilder {
+        self.certificate_clock_check_mode = certificate_clock_check_mode;
+        self
+    }
+
+    /// Set the verification key of the signed checkpoints of the certificate chain, the default
+    /// [CertificateVerifier] then stops the validation of a chain at the first certificate that
+    /// has a checkpoint with a valid signature instead of the genesis certificate.
+    ///
+    /// Ignored if a custom [CertificateVerifier] is set.
+    pub fn with_certificate_chain_checkpoint_verification_key(
+        mut self,
+        checkpoint_verification_key: &str,
+    ) -> ClientBuilder {
+        self.certificate_chain_checkpoint_verification_key =
+            Some(checkpoint_verification_key.to_string());
+        self
+    }
+
+    /// Set the [CertificateCache] from which the certificates are read before requesting the
+    /// aggregator, see [sync][crate::certificate_client::CertificateClient::sync] to fill it.
+    pub fn with_certificate_cache(
+        mut self,
+        certificate_cache: Arc<dyn CertificateCache>,
+    ) -> ClientBuilder {
+        self.certificate_cache = Some(certificate_cache);
+        self
+    }
+
     cfg_fs! {
     /// Set the [SnapshotDownloader] that will be used to download snapshots.
     pub fn with_snapshot_downloader(
@@ -7,7 +7,8 @@
 //! It handles the different types that can be queried to a Mithril aggregator:
 //!
 //! - [Snapshot][snapshot_client] list, get, download tarball and record statistics.
-//! - [Mithril stake distribution][mithril_stake_distribution_client] list and get.
+//! - [Mithril stake distribution][mithril_stake_distribution_client] list, get and build of a
+//! [verifier set][verifier_set] of aggregate verification keys.
 //! - [Cardano transactions][cardano_transaction_client] list & get snapshot, get proofs
 //! _(available using crate feature_ **unstable**_)_.
 //! - [Certificates][certificate_client] list, get, and chain validation.
@@ -83,18 +84,23 @@ macro_rules! cfg_unstable {
 }
 
 pub mod aggregator_client;
+pub mod aggregator_failover;
 cfg_unstable! {
     pub mod cardano_transaction_client;
+    pub mod cardano_transaction_proof_stream;
 }
+pub mod certificate_cache;
 pub mod certificate_client;
 mod client;
 pub mod feedback;
+pub mod list_options;
 mod message;
 pub mod mithril_stake_distribution_client;
 pub mod snapshot_client;
 cfg_fs! {
     pub mod snapshot_downloader;
 }
+pub mod verifier_set;
 
 mod type_alias;
 mod utils;
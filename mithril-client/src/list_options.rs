@@ -0,0 +1,273 @@
+//! Options to filter and sort the lists of artifacts retrieved from an Aggregator.
+//!
+//! The [ListOptions] are given to the `list_with_options` function of the
+//! [snapshot][crate::snapshot_client::SnapshotClient::list_with_options] and
+//! [Mithril stake distribution][crate::mithril_stake_distribution_client::MithrilStakeDistributionClient::list_with_options]
+//! clients.
+//!
+//! The options are sent to the aggregator as query parameters so it can apply them itself, and
+//! they are always applied again by the client to the retrieved list, as the aggregators that do
+//! not support them return their latest artifacts unfiltered.
+//!
+//! ```no_run
+//! # async fn run() -> mithril_client::MithrilResult<()> {
+//! use mithril_client::{common::Epoch, list_options::{ListOptions, ListSortOrder}, ClientBuilder};
+//!
+//! let client = ClientBuilder::aggregator("YOUR_AGGREGATOR_ENDPOINT", "YOUR_GENESIS_VERIFICATION_KEY").build()?;
+//! let options = ListOptions::new()
+//!     .with_epoch(Epoch(450))
+//!     .with_sort_order(ListSortOrder::OldestFirst);
+//! let snapshots = client.snapshot().list_with_options(&options).await?;
+//!
+//! for snapshot in snapshots {
+//!     println!("Snapshot digest={}, immutable file number={}", snapshot.digest, snapshot.beacon.immutable_file_number);
+//! }
+//! #    Ok(())
+//! # }
+//! ```
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::common::{Epoch, ImmutableFileNumber};
+use crate::{MithrilStakeDistributionListItem, SnapshotListItem};
+
+/// Sort order of the items of a list, by creation date
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListSortOrder {
+    /// Most recent items first (the order of the aggregator lists)
+    #[default]
+    NewestFirst,
+
+    /// Oldest items first
+    OldestFirst,
+}
+
+/// Options to filter and sort the items of a list of artifacts
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListOptions {
+    epoch: Option<Epoch>,
+    immutable_file_number: Option<ImmutableFileNumber>,
+    created_after: Option<DateTime<Utc>>,
+    sort_order: ListSortOrder,
+}
+
+impl ListOptions {
+    /// ListOptions factory, without any filter and with the most recent items first
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only keep the items of the given epoch
+    pub fn with_epoch(mut self, epoch: Epoch) -> Self {
+        self.epoch = Some(epoch);
+        self
+    }
+
+    /// Only keep the items which beacon is at the given immutable file number.
+    ///
+    /// Only applies to the artifacts which beacon has an immutable file number (i.e. snapshots).
+    pub fn with_immutable_file_number(
+        mut self,
+        immutable_file_number: ImmutableFileNumber,
+    ) -> Self {
+        self.immutable_file_number = Some(immutable_file_number);
+        self
+    }
+
+    /// Only keep the items created after the given date
+    pub fn with_created_after(mut self, created_after: DateTime<Utc>) -> Self {
+        self.created_after = Some(created_after);
+        self
+    }
+
+    /// Sort the items in the given order
+    pub fn with_sort_order(mut self, sort_order: ListSortOrder) -> Self {
+        self.sort_order = sort_order;
+        self
+    }
+
+    /// Query string sending the options to the aggregator, empty if the options are the default
+    pub(crate) fn to_query_string(&self) -> String {
+        let mut parameters = vec![];
+        if let Some(epoch) = self.epoch {
+            parameters.push(format!("epoch={epoch}"));
+        }
+        if let Some(immutable_file_number) = self.immutable_file_number {
+            parameters.push(format!("immutable_file_number={immutable_file_number}"));
+        }
+        if let Some(created_after) = self.created_after {
+            parameters.push(format!(
+                "created_after={}",
+                created_after.to_rfc3339_opts(SecondsFormat::Secs, true)
+            ));
+        }
+        if self.sort_order == ListSortOrder::OldestFirst {
+            parameters.push("order=asc".to_string());
+        }
+
+        parameters.join("&")
+    }
+
+    /// Filter and sort the given items
+    pub(crate) fn apply<T: ListedArtifact>(&self, items: Vec<T>) -> Vec<T> {
+        let mut items: Vec<T> = items
+            .into_iter()
+            .filter(|item| self.epoch.map_or(true, |epoch| item.epoch() == epoch))
+            .filter(|item| {
+                self.immutable_file_number
+                    .map_or(true, |number| item.immutable_file_number() == Some(number))
+            })
+            .filter(|item| {
+                self.created_after
+                    .map_or(true, |created_after| item.created_at() > created_after)
+            })
+            .collect();
+        match self.sort_order {
+            ListSortOrder::NewestFirst => items.sort_by(|a, b| b.created_at().cmp(&a.created_at())),
+            ListSortOrder::OldestFirst => items.sort_by_key(|item| item.created_at()),
+        }
+
+        items
+    }
+}
+
+/// An item of a list of artifacts that can be filtered and sorted with [ListOptions]
+pub(crate) trait ListedArtifact {
+    /// Epoch of the artifact
+    fn epoch(&self) -> Epoch;
+
+    /// Immutable file number of the beacon of the artifact, if any
+    fn immutable_file_number(&self) -> Option<ImmutableFileNumber>;
+
+    /// Creation date of the artifact
+    fn created_at(&self) -> DateTime<Utc>;
+}
+
+impl ListedArtifact for SnapshotListItem {
+    fn epoch(&self) -> Epoch {
+        self.beacon.epoch
+    }
+
+    fn immutable_file_number(&self) -> Option<ImmutableFileNumber> {
+        Some(self.beacon.immutable_file_number)
+    }
+
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+impl ListedArtifact for MithrilStakeDistributionListItem {
+    fn epoch(&self) -> Epoch {
+        self.epoch
+    }
+
+    fn immutable_file_number(&self) -> Option<ImmutableFileNumber> {
+        None
+    }
+
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use crate::common::CardanoDbBeacon;
+
+    use super::*;
+
+    fn snapshot(epoch: u64, immutable_file_number: u64, created_at_hour: u32) -> SnapshotListItem {
+        SnapshotListItem {
+            digest: format!("digest-{immutable_file_number}"),
+            beacon: CardanoDbBeacon::new("devnet", epoch, immutable_file_number),
+            created_at: Utc
+                .with_ymd_and_hms(2024, 5, 2, created_at_hour, 0, 0)
+                .unwrap(),
+            ..SnapshotListItem::dummy()
+        }
+    }
+
+    fn digests(items: &[SnapshotListItem]) -> Vec<&str> {
+        items.iter().map(|item| item.digest.as_str()).collect()
+    }
+
+    #[test]
+    fn default_options_keep_all_items_newest_first() {
+        let items = vec![snapshot(1, 10, 1), snapshot(1, 12, 3), snapshot(1, 11, 2)];
+
+        let items = ListOptions::new().apply(items);
+
+        assert_eq!(vec!["digest-12", "digest-11", "digest-10"], digests(&items));
+        assert_eq!("", ListOptions::new().to_query_string());
+    }
+
+    #[test]
+    fn filter_items_by_epoch_beacon_and_creation_date() {
+        let items = vec![
+            snapshot(1, 10, 1),
+            snapshot(2, 11, 2),
+            snapshot(2, 12, 3),
+            snapshot(2, 13, 4),
+        ];
+
+        assert_eq!(
+            vec!["digest-13", "digest-12", "digest-11"],
+            digests(&ListOptions::new().with_epoch(Epoch(2)).apply(items.clone()))
+        );
+        assert_eq!(
+            vec!["digest-12"],
+            digests(
+                &ListOptions::new()
+                    .with_immutable_file_number(12)
+                    .apply(items.clone())
+            )
+        );
+        assert_eq!(
+            vec!["digest-13", "digest-12"],
+            digests(
+                &ListOptions::new()
+                    .with_created_after(Utc.with_ymd_and_hms(2024, 5, 2, 2, 0, 0).unwrap())
+                    .apply(items)
+            )
+        );
+    }
+
+    #[test]
+    fn sort_items_oldest_first() {
+        let items = vec![snapshot(1, 12, 3), snapshot(1, 10, 1), snapshot(1, 11, 2)];
+
+        let items = ListOptions::new()
+            .with_sort_order(ListSortOrder::OldestFirst)
+            .apply(items);
+
+        assert_eq!(vec!["digest-10", "digest-11", "digest-12"], digests(&items));
+    }
+
+    #[test]
+    fn immutable_file_number_filter_excludes_artifacts_without_immutable_file_number() {
+        let items = vec![MithrilStakeDistributionListItem::dummy()];
+
+        let items = ListOptions::new()
+            .with_immutable_file_number(12)
+            .apply(items);
+
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn options_are_sent_as_query_parameters() {
+        let options = ListOptions::new()
+            .with_epoch(Epoch(2))
+            .with_immutable_file_number(12)
+            .with_created_after(Utc.with_ymd_and_hms(2024, 5, 2, 2, 0, 0).unwrap())
+            .with_sort_order(ListSortOrder::OldestFirst);
+
+        assert_eq!(
+            "epoch=2&immutable_file_number=12&created_after=2024-05-02T02:00:00Z&order=asc",
+            options.to_query_string()
+        );
+    }
+}
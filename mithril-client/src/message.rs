@@ -12,11 +12,9 @@ use std::path::Path;
 use std::sync::Arc;
 
 use crate::common::{ProtocolMessage, ProtocolMessagePartKey};
-#[cfg(any(feature = "fs", feature = "unstable"))]
-use crate::MithrilCertificate;
 #[cfg(feature = "unstable")]
 use crate::VerifiedCardanoTransactions;
-use crate::{MithrilResult, MithrilSigner, MithrilStakeDistribution};
+use crate::{MithrilCertificate, MithrilResult, MithrilSigner, MithrilStakeDistribution};
 
 /// A [MessageBuilder] can be used to compute the message of Mithril artifacts.
 pub struct MessageBuilder {
@@ -100,9 +98,39 @@ impl MessageBuilder {
     }
 
     /// Compute message for a Mithril stake distribution.
+    ///
+    /// The message only holds the Mithril stake distribution, it does not match the certificates
+    /// batching other signed entity types, use
+    /// [compute_mithril_stake_distribution_message_with_certificate][Self::compute_mithril_stake_distribution_message_with_certificate]
+    /// to verify them.
     pub fn compute_mithril_stake_distribution_message(
         &self,
         mithril_stake_distribution: &MithrilStakeDistribution,
+    ) -> MithrilResult<ProtocolMessage> {
+        self.compute_mithril_stake_distribution_message_from(
+            ProtocolMessage::new(),
+            mithril_stake_distribution,
+        )
+    }
+
+    /// Compute message for a Mithril stake distribution from the protocol message of its
+    /// certificate, so that the parts of the other signed entity types batched in the same
+    /// certificate are kept.
+    pub fn compute_mithril_stake_distribution_message_with_certificate(
+        &self,
+        certificate: &MithrilCertificate,
+        mithril_stake_distribution: &MithrilStakeDistribution,
+    ) -> MithrilResult<ProtocolMessage> {
+        self.compute_mithril_stake_distribution_message_from(
+            certificate.protocol_message.clone(),
+            mithril_stake_distribution,
+        )
+    }
+
+    fn compute_mithril_stake_distribution_message_from(
+        &self,
+        mut message: ProtocolMessage,
+        mithril_stake_distribution: &MithrilStakeDistribution,
     ) -> MithrilResult<ProtocolMessage> {
         let signers =
             MithrilSigner::try_into_signers(mithril_stake_distribution.signers_with_stake.clone())
@@ -121,7 +149,6 @@ impl MessageBuilder {
                 "Could not compute message: aggregate verification key encoding failed"
             })?;
 
-        let mut message = ProtocolMessage::new();
         message.set_message_part(ProtocolMessagePartKey::NextAggregateVerificationKey, avk);
 
         Ok(message)
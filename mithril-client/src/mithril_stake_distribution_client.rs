@@ -3,6 +3,11 @@
 //! In order to do so it defines a [MithrilStakeDistributionClient] which exposes the following features:
 //!  - [get][MithrilStakeDistributionClient::get]: get a Mithril stake distribution data from its hash
 //!  - [list][MithrilStakeDistributionClient::list]: get the list of available Mithril stake distribution
+//!  - [list_with_options][MithrilStakeDistributionClient::list_with_options]: get the list of
+//! available Mithril stake distribution filtered and sorted with the given
+//! [options][crate::list_options::ListOptions]
+//!  - [download_verifier_set][MithrilStakeDistributionClient::download_verifier_set]: build a
+//! [verifier set][crate::verifier_set] from the latest verified Mithril stake distribution
 //!
 //! # Get a Mithril stake distribution
 //!
@@ -37,23 +42,56 @@
 //! #    Ok(())
 //! # }
 //! ```
+//!
+//! # Build a verifier set
+//!
+//! To download the latest Mithril stake distribution, verify its certificate chain and build the
+//! [verifier set][crate::verifier_set::VerifierSet] of its aggregate verification keys using the
+//! [ClientBuilder][crate::client::ClientBuilder].
+//!
+//! ```no_run
+//! # async fn run() -> mithril_client::MithrilResult<()> {
+//! use mithril_client::ClientBuilder;
+//!
+//! let client = ClientBuilder::aggregator("YOUR_AGGREGATOR_ENDPOINT", "YOUR_GENESIS_VERIFICATION_KEY").build()?;
+//! let verifier_set = client.mithril_stake_distribution().download_verifier_set().await?;
+//!
+//! for entry in verifier_set.entries {
+//!     println!("Epoch={}, aggregate verification key={}", entry.epoch, entry.aggregate_verification_key);
+//! }
+//! #    Ok(())
+//! # }
+//! ```
 
 use std::sync::Arc;
 
 use crate::aggregator_client::{AggregatorClient, AggregatorClientError, AggregatorRequest};
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 
-use crate::{MithrilResult, MithrilStakeDistribution, MithrilStakeDistributionListItem};
+use crate::certificate_client::CertificateClient;
+use crate::list_options::ListOptions;
+use crate::verifier_set::VerifierSet;
+use crate::{
+    MessageBuilder, MithrilResult, MithrilStakeDistribution, MithrilStakeDistributionListItem,
+};
+use mithril_common::messages::PaginatedMessage;
 
 /// HTTP client for MithrilStakeDistribution API from the Aggregator
 pub struct MithrilStakeDistributionClient {
     aggregator_client: Arc<dyn AggregatorClient>,
+    certificate_client: Arc<CertificateClient>,
 }
 
 impl MithrilStakeDistributionClient {
     /// Constructs a new `MithrilStakeDistributionClient`.
-    pub fn new(aggregator_client: Arc<dyn AggregatorClient>) -> Self {
-        Self { aggregator_client }
+    pub fn new(
+        aggregator_client: Arc<dyn AggregatorClient>,
+        certificate_client: Arc<CertificateClient>,
+    ) -> Self {
+        Self {
+            aggregator_client,
+            certificate_client,
+        }
     }
 
     /// Fetch a list of signed MithrilStakeDistribution
@@ -63,12 +101,34 @@ impl MithrilStakeDistributionClient {
             .get_content(AggregatorRequest::ListMithrilStakeDistributions)
             .await
             .with_context(|| "MithrilStakeDistribution Client can not get the artifact list")?;
-        let items = serde_json::from_str::<Vec<MithrilStakeDistributionListItem>>(&response)
-            .with_context(|| "MithrilStakeDistribution Client can not deserialize artifact list")?;
+        let items = PaginatedMessage::<MithrilStakeDistributionListItem>::from_list_json(&response)
+            .with_context(|| "MithrilStakeDistribution Client can not deserialize artifact list")?
+            .items;
 
         Ok(items)
     }
 
+    /// Fetch a list of signed MithrilStakeDistribution filtered and sorted with the given options
+    pub async fn list_with_options(
+        &self,
+        options: &ListOptions,
+    ) -> MithrilResult<Vec<MithrilStakeDistributionListItem>> {
+        let response = self
+            .aggregator_client
+            .get_content(
+                AggregatorRequest::ListMithrilStakeDistributionsWithOptions {
+                    options: options.clone(),
+                },
+            )
+            .await
+            .with_context(|| "MithrilStakeDistribution Client can not get the artifact list")?;
+        let items = PaginatedMessage::<MithrilStakeDistributionListItem>::from_list_json(&response)
+            .with_context(|| "MithrilStakeDistribution Client can not deserialize artifact list")?
+            .items;
+
+        Ok(options.apply(items))
+    }
+
     /// Get the given stake distribution data. If it cannot be found, a None is returned.
     pub async fn get(&self, hash: &str) -> MithrilResult<Option<MithrilStakeDistribution>> {
         match self
@@ -90,19 +150,94 @@ impl MithrilStakeDistributionClient {
             Err(e) => Err(e.into()),
         }
     }
+
+    /// Download the latest Mithril stake distribution, verify its certificate chain and that it
+    /// is signed by its certificate, then build the [VerifierSet] of the aggregate verification
+    /// keys of the epoch of the certificate and of the next epoch.
+    pub async fn download_verifier_set(&self) -> MithrilResult<VerifierSet> {
+        let latest_hash = self
+            .list()
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(anyhow!("No Mithril stake distribution available"))?
+            .hash;
+        let mithril_stake_distribution = self.get(&latest_hash).await?.ok_or(anyhow!(
+            "No Mithril stake distribution exist for hash '{latest_hash}'"
+        ))?;
+
+        let certificate = self
+            .certificate_client
+            .verify_chain(&mithril_stake_distribution.certificate_hash)
+            .await?;
+        let message = MessageBuilder::new()
+            .compute_mithril_stake_distribution_message_with_certificate(&certificate, &mithril_stake_distribution)
+            .with_context(|| {
+                "MithrilStakeDistribution Client can not compute the Mithril stake distribution message"
+            })?;
+        if !certificate.match_message(&message) {
+            return Err(anyhow!(
+                "Mithril stake distribution '{}' is not signed by certificate '{}'",
+                mithril_stake_distribution.hash,
+                certificate.hash
+            ));
+        }
+        let next_aggregate_verification_key = message
+            .next_aggregate_verification_key()
+            .ok_or(anyhow!(
+                "Mithril stake distribution message has no next aggregate verification key"
+            ))?
+            .to_string();
+
+        let mut verifier_set = VerifierSet {
+            mithril_stake_distribution_hash: mithril_stake_distribution.hash,
+            certificate_hash: certificate.hash,
+            entries: vec![],
+        };
+        verifier_set.insert(
+            certificate.epoch,
+            certificate.aggregate_verification_key,
+            certificate.metadata.protocol_parameters,
+        );
+        verifier_set.insert(
+            mithril_stake_distribution.epoch.next(),
+            next_aggregate_verification_key,
+            mithril_stake_distribution.protocol_parameters,
+        );
+
+        Ok(verifier_set)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use chrono::{DateTime, Utc};
+    use mithril_common::entities::SignedEntityType;
     use mithril_common::test_utils::fake_data;
 
+    use mockall::predicate::eq;
+
     use crate::aggregator_client::MockAggregatorHTTPClient;
-    use crate::common::Epoch;
-    use crate::MithrilSigner;
+    use crate::certificate_client::MockCertificateVerifier;
+    use crate::common::{Epoch, ProtocolMessage, ProtocolMessagePart, ProtocolMessagePartKey};
+    use crate::{test_utils, MithrilCertificate, MithrilSigner};
 
     use super::*;
 
+    fn build_client(
+        aggregator_client: MockAggregatorHTTPClient,
+        certificate_verifier: MockCertificateVerifier,
+    ) -> MithrilStakeDistributionClient {
+        let aggregator_client = Arc::new(aggregator_client);
+        let certificate_client = Arc::new(CertificateClient::new(
+            aggregator_client.clone(),
+            Arc::new(certificate_verifier),
+            test_utils::test_logger(),
+        ));
+
+        MithrilStakeDistributionClient::new(aggregator_client, certificate_client)
+    }
+
     fn fake_messages() -> Vec<MithrilStakeDistributionListItem> {
         vec![
             MithrilStakeDistributionListItem {
@@ -131,7 +266,7 @@ mod tests {
         http_client
             .expect_get_content()
             .return_once(move |_| Ok(serde_json::to_string(&message).unwrap()));
-        let client = MithrilStakeDistributionClient::new(Arc::new(http_client));
+        let client = build_client(http_client, MockCertificateVerifier::new());
         let items = client.list().await.unwrap();
 
         assert_eq!(2, items.len());
@@ -139,6 +274,26 @@ mod tests {
         assert_eq!("hash-456".to_string(), items[1].hash);
     }
 
+    #[tokio::test]
+    async fn get_mithril_stake_distribution_list_with_options_sends_and_applies_them() {
+        let message = fake_messages();
+        let options = ListOptions::new().with_epoch(Epoch(2));
+        let mut http_client = MockAggregatorHTTPClient::new();
+        http_client
+            .expect_get_content()
+            .with(eq(
+                AggregatorRequest::ListMithrilStakeDistributionsWithOptions {
+                    options: options.clone(),
+                },
+            ))
+            .return_once(move |_| Ok(serde_json::to_string(&message).unwrap()));
+        let client = build_client(http_client, MockCertificateVerifier::new());
+        let items = client.list_with_options(&options).await.unwrap();
+
+        assert_eq!(1, items.len());
+        assert_eq!("hash-456".to_string(), items[0].hash);
+    }
+
     #[tokio::test]
     async fn get_mithril_stake_distribution() {
         let mut http_client = MockAggregatorHTTPClient::new();
@@ -153,7 +308,7 @@ mod tests {
         http_client
             .expect_get_content()
             .return_once(move |_| Ok(serde_json::to_string(&message).unwrap()));
-        let client = MithrilStakeDistributionClient::new(Arc::new(http_client));
+        let client = build_client(http_client, MockCertificateVerifier::new());
         let stake_distribution_entity = client
             .get("hash")
             .await
@@ -163,4 +318,182 @@ mod tests {
         assert_eq!("hash".to_string(), stake_distribution_entity.hash);
         assert_eq!(2, stake_distribution_entity.signers_with_stake.len(),);
     }
+
+    fn mithril_stake_distribution_and_its_certificate(
+        signed: bool,
+    ) -> (MithrilStakeDistribution, MithrilCertificate) {
+        mithril_stake_distribution_and_its_certificate_with_protocol_message(
+            signed,
+            ProtocolMessage::new(),
+        )
+    }
+
+    fn mithril_stake_distribution_and_its_certificate_with_protocol_message(
+        signed: bool,
+        protocol_message: ProtocolMessage,
+    ) -> (MithrilStakeDistribution, MithrilCertificate) {
+        let mithril_stake_distribution = MithrilStakeDistribution {
+            certificate_hash: "certificate-hash-123".to_string(),
+            epoch: Epoch(4),
+            signers_with_stake: MithrilSigner::from_signers(fake_data::signers_with_stakes(2)),
+            hash: "hash-123".to_string(),
+            created_at: DateTime::<Utc>::default(),
+            protocol_parameters: fake_data::protocol_parameters(),
+        };
+        let mut certificate = MithrilCertificate {
+            hash: "certificate-hash-123".to_string(),
+            epoch: Epoch(4),
+            protocol_message,
+            ..MithrilCertificate::dummy()
+        };
+        let message = MessageBuilder::new()
+            .compute_mithril_stake_distribution_message_with_certificate(
+                &certificate,
+                &mithril_stake_distribution,
+            )
+            .unwrap();
+        certificate.signed_message = if signed {
+            message.compute_hash()
+        } else {
+            "another-message".to_string()
+        };
+        certificate.protocol_message = message;
+
+        (mithril_stake_distribution, certificate)
+    }
+
+    fn mock_aggregator_client(
+        mithril_stake_distribution: MithrilStakeDistribution,
+        certificate: MithrilCertificate,
+    ) -> MockAggregatorHTTPClient {
+        let mut http_client = MockAggregatorHTTPClient::new();
+        let list = fake_messages()
+            .into_iter()
+            .map(|item| MithrilStakeDistributionListItem {
+                hash: mithril_stake_distribution.hash.clone(),
+                ..item
+            })
+            .collect::<Vec<_>>();
+        http_client
+            .expect_get_content()
+            .with(eq(AggregatorRequest::ListMithrilStakeDistributions))
+            .return_once(move |_| Ok(serde_json::to_string(&list).unwrap()));
+        http_client
+            .expect_get_content()
+            .with(eq(AggregatorRequest::GetMithrilStakeDistribution {
+                hash: mithril_stake_distribution.hash.clone(),
+            }))
+            .return_once(move |_| Ok(serde_json::to_string(&mithril_stake_distribution).unwrap()));
+        http_client
+            .expect_get_content()
+            .with(eq(AggregatorRequest::GetCertificate {
+                hash: certificate.hash.clone(),
+            }))
+            .return_once(move |_| Ok(serde_json::to_string(&certificate).unwrap()));
+
+        http_client
+    }
+
+    #[tokio::test]
+    async fn download_verifier_set_of_the_latest_mithril_stake_distribution() {
+        let (mithril_stake_distribution, certificate) =
+            mithril_stake_distribution_and_its_certificate(true);
+        let next_aggregate_verification_key = certificate
+            .protocol_message
+            .next_aggregate_verification_key()
+            .unwrap()
+            .to_string();
+        let mut certificate_verifier = MockCertificateVerifier::new();
+        certificate_verifier
+            .expect_verify_chain()
+            .return_once(|_| Ok(()))
+            .once();
+        let client = build_client(
+            mock_aggregator_client(mithril_stake_distribution, certificate.clone()),
+            certificate_verifier,
+        );
+
+        let verifier_set = client.download_verifier_set().await.unwrap();
+
+        assert_eq!("hash-123", verifier_set.mithril_stake_distribution_hash);
+        assert_eq!(certificate.hash, verifier_set.certificate_hash);
+        assert_eq!(
+            Some(certificate.aggregate_verification_key.as_str()),
+            verifier_set.get_aggregate_verification_key(Epoch(4))
+        );
+        assert_eq!(
+            Some(next_aggregate_verification_key.as_str()),
+            verifier_set.get_aggregate_verification_key(Epoch(5))
+        );
+    }
+
+    #[tokio::test]
+    async fn download_verifier_set_of_a_mithril_stake_distribution_batched_in_its_certificate() {
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message.set_message_part(
+            ProtocolMessagePartKey::SnapshotDigest,
+            "snapshot-digest-123".to_string(),
+        );
+        protocol_message
+            .set_part(ProtocolMessagePart::BatchedSignedEntityTypes(vec![
+                SignedEntityType::CardanoImmutableFilesFull(fake_data::beacon()),
+            ]))
+            .unwrap();
+        let (mithril_stake_distribution, certificate) =
+            mithril_stake_distribution_and_its_certificate_with_protocol_message(
+                true,
+                protocol_message,
+            );
+        let mut certificate_verifier = MockCertificateVerifier::new();
+        certificate_verifier
+            .expect_verify_chain()
+            .return_once(|_| Ok(()));
+        let client = build_client(
+            mock_aggregator_client(mithril_stake_distribution, certificate.clone()),
+            certificate_verifier,
+        );
+
+        let verifier_set = client.download_verifier_set().await.unwrap();
+
+        assert_eq!(certificate.hash, verifier_set.certificate_hash);
+    }
+
+    #[tokio::test]
+    async fn download_verifier_set_fail_if_the_certificate_chain_is_invalid() {
+        let (mithril_stake_distribution, certificate) =
+            mithril_stake_distribution_and_its_certificate(true);
+        let mut certificate_verifier = MockCertificateVerifier::new();
+        certificate_verifier
+            .expect_verify_chain()
+            .return_once(|_| Err(anyhow!("invalid chain")));
+        let client = build_client(
+            mock_aggregator_client(mithril_stake_distribution, certificate),
+            certificate_verifier,
+        );
+
+        client
+            .download_verifier_set()
+            .await
+            .expect_err("An invalid certificate chain should fail");
+    }
+
+    #[tokio::test]
+    async fn download_verifier_set_fail_if_the_stake_distribution_is_not_signed_by_its_certificate()
+    {
+        let (mithril_stake_distribution, certificate) =
+            mithril_stake_distribution_and_its_certificate(false);
+        let mut certificate_verifier = MockCertificateVerifier::new();
+        certificate_verifier
+            .expect_verify_chain()
+            .return_once(|_| Ok(()));
+        let client = build_client(
+            mock_aggregator_client(mithril_stake_distribution, certificate),
+            certificate_verifier,
+        );
+
+        client
+            .download_verifier_set()
+            .await
+            .expect_err("A Mithril stake distribution not signed by its certificate should fail");
+    }
 }
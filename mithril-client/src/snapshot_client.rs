@@ -3,7 +3,12 @@
 //! In order to do so it defines a [SnapshotClient] which exposes the following features:
 //!  - [get][SnapshotClient::get]: get a single snapshot data from its digest
 //!  - [list][SnapshotClient::list]: get the list of available snapshots
+//!  - [list_with_options][SnapshotClient::list_with_options]: get the list of available snapshots
+//! filtered and sorted with the given [options][crate::list_options::ListOptions]
 //!  - [download_unpack][SnapshotClient::download_unpack]: download and unpack the tarball of a snapshot to a directory
+//!  - [download_unpack_ancillary][SnapshotClient::download_unpack_ancillary]: download and unpack the tarball of the ancillary files of a snapshot to a directory
+//!  - [download_unpack_partial][SnapshotClient::download_unpack_partial]: download a range of the immutable files of a snapshot to a directory
+//!  - [unpack_archive][SnapshotClient::unpack_archive]: unpack a previously downloaded tarball of a snapshot to a directory
 //!
 //! # Get a single snapshot
 //!
@@ -64,6 +69,105 @@
 //! # }
 //! ```
 //!
+//! # Download the ancillary files of a snapshot
+//! **Note:** _Available on crate feature_ **fs** _only._
+//!
+//! Some aggregators package the ledger state snapshots and the volatile database in a separate
+//! ancillary archive, which allows a Cardano node to start without replaying the whole chain.
+//!
+//! **Warning:** the ancillary files are not certified by the snapshot digest, they are only as
+//! trustworthy as the aggregator that produced them.
+//!
+//! ```no_run
+//! # #[cfg(feature = "fs")]
+//! # async fn run() -> mithril_client::MithrilResult<()> {
+//! use mithril_client::ClientBuilder;
+//! use std::path::Path;
+//!
+//! let client = ClientBuilder::aggregator("YOUR_AGGREGATOR_ENDPOINT", "YOUR_GENESIS_VERIFICATION_KEY").build()?;
+//! let snapshot = client.snapshot().get("SNAPSHOT_DIGEST").await?.unwrap();
+//!
+//! let target_directory = Path::new("/home/user/download/");
+//! client
+//!    .snapshot()
+//!    .download_unpack(&snapshot, target_directory)
+//!    .await?;
+//! if snapshot.ancillary_locations.is_some() {
+//!     client
+//!        .snapshot()
+//!        .download_unpack_ancillary(&snapshot, target_directory)
+//!        .await?;
+//! }
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+//!
+//! # Download a range of the immutable files of a snapshot
+//! **Note:** _Available on crate feature_ **fs** _only._
+//!
+//! Some aggregators publish a manifest listing each file of their snapshots, which allows to
+//! download only the recent immutable files instead of the full history.
+//!
+//! The manifest is verified against the snapshot digest certified by the certificate of the
+//! snapshot before any file is downloaded, so the certificate chain must be verified first.
+//!
+//! **Warning:** a partial database can not be used to start a Cardano node.
+//!
+//! ```no_run
+//! # #[cfg(feature = "fs")]
+//! # async fn run() -> mithril_client::MithrilResult<()> {
+//! use mithril_client::ClientBuilder;
+//! use std::path::Path;
+//!
+//! let client = ClientBuilder::aggregator("YOUR_AGGREGATOR_ENDPOINT", "YOUR_GENESIS_VERIFICATION_KEY").build()?;
+//! let snapshot = client.snapshot().get("SNAPSHOT_DIGEST").await?.unwrap();
+//!
+//! let certificate = client
+//!     .certificate()
+//!     .verify_chain(&snapshot.certificate_hash)
+//!     .await?;
+//!
+//! let target_directory = Path::new("/home/user/download/");
+//! let last_immutable_file_number = snapshot.beacon.immutable_file_number;
+//! client
+//!    .snapshot()
+//!    .download_unpack_partial(
+//!        &snapshot,
+//!        &certificate,
+//!        target_directory,
+//!        last_immutable_file_number.saturating_sub(100)..=last_immutable_file_number,
+//!    )
+//!    .await?;
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+//!
+//! # Unpack a previously downloaded snapshot
+//! **Note:** _Available on crate feature_ **fs** _only._
+//!
+//! To unpack a snapshot tarball already on the disk, for example to verify it again without downloading it.
+//!
+//! ```no_run
+//! # #[cfg(feature = "fs")]
+//! # async fn run() -> mithril_client::MithrilResult<()> {
+//! use mithril_client::ClientBuilder;
+//! use std::path::Path;
+//!
+//! let client = ClientBuilder::aggregator("YOUR_AGGREGATOR_ENDPOINT", "YOUR_GENESIS_VERIFICATION_KEY").build()?;
+//!
+//! // Note: the directory must already exist, and the user running the binary must have read/write access to it.
+//! let target_directory = Path::new("/home/user/unpack/");
+//! client
+//!    .snapshot()
+//!    .unpack_archive(Path::new("/home/user/download/snapshot.tar.zst"), target_directory)
+//!    .await?;
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+//!
 //! # Add statistics
 //! **Note:** _Available on crate feature_ **fs** _only._
 //!
@@ -100,9 +204,16 @@ use thiserror::Error;
 use crate::aggregator_client::{AggregatorClient, AggregatorClientError, AggregatorRequest};
 #[cfg(feature = "fs")]
 use crate::feedback::FeedbackSender;
+use crate::list_options::ListOptions;
 #[cfg(feature = "fs")]
 use crate::snapshot_downloader::SnapshotDownloader;
 use crate::{MithrilResult, Snapshot, SnapshotListItem};
+use mithril_common::messages::PaginatedMessage;
+
+/// Name of the directory, created in the target directory, where the ancillary archive is
+/// unpacked before its allowed directories are moved to the target directory.
+#[cfg(feature = "fs")]
+const ANCILLARY_STAGING_DIRECTORY: &str = ".ancillary-staging";
 
 /// Error for the Snapshot client
 #[derive(Error, Debug)]
@@ -116,6 +227,35 @@ pub enum SnapshotClientError {
         /// list of locations tried
         locations: String,
     },
+
+    /// The snapshot does not have an ancillary archive
+    #[error(
+        "The snapshot with digest '{digest}' does not have an archive of its ancillary files."
+    )]
+    NoAncillaryArchive {
+        /// given digest
+        digest: String,
+    },
+
+    /// The snapshot is not the one certified by the given certificate
+    #[error("The snapshot with digest '{digest}' is not certified by the certificate '{certificate_hash}'.")]
+    UncertifiedSnapshot {
+        /// given digest
+        digest: String,
+
+        /// hash of the given certificate
+        certificate_hash: String,
+    },
+
+    /// The snapshot does not have a manifest location, required by a partial download
+    #[error("The snapshot with digest '{digest}' does not have a manifest allowing a partial download, locations: {{'{locations}'}}.")]
+    NoManifestLocation {
+        /// given digest
+        digest: String,
+
+        /// list of the snapshot locations
+        locations: String,
+    },
 }
 
 /// Aggregator client for the snapshot artifact
@@ -155,12 +295,32 @@ impl SnapshotClient {
             .get_content(AggregatorRequest::ListSnapshots)
             .await
             .with_context(|| "Snapshot Client can not get the artifact list")?;
-        let items = serde_json::from_str::<Vec<SnapshotListItem>>(&response)
-            .with_context(|| "Snapshot Client can not deserialize artifact list")?;
+        let items = PaginatedMessage::<SnapshotListItem>::from_list_json(&response)
+            .with_context(|| "Snapshot Client can not deserialize artifact list")?
+            .items;
 
         Ok(items)
     }
 
+    /// Return a list of available snapshots filtered and sorted with the given options
+    pub async fn list_with_options(
+        &self,
+        options: &ListOptions,
+    ) -> MithrilResult<Vec<SnapshotListItem>> {
+        let response = self
+            .aggregator_client
+            .get_content(AggregatorRequest::ListSnapshotsWithOptions {
+                options: options.clone(),
+            })
+            .await
+            .with_context(|| "Snapshot Client can not get the artifact list")?;
+        let items = PaginatedMessage::<SnapshotListItem>::from_list_json(&response)
+            .with_context(|| "Snapshot Client can not deserialize artifact list")?
+            .items;
+
+        Ok(options.apply(items))
+    }
+
     /// Get the given snapshot data. If it cannot be found, a None is returned.
     pub async fn get(&self, digest: &str) -> MithrilResult<Option<Snapshot>> {
         match self
@@ -191,26 +351,221 @@ impl SnapshotClient {
             snapshot: &Snapshot,
             target_dir: &std::path::Path,
         ) -> MithrilResult<()> {
+            self.download_unpack_from_locations(
+                &snapshot.digest,
+                &snapshot.locations,
+                snapshot.size,
+                snapshot.compression_algorithm.unwrap_or_default(),
+                snapshot.archive_hash.clone(),
+                target_dir,
+            )
+            .await
+        }
+
+        /// Download and unpack the ancillary files (ledger state snapshots and volatile
+        /// database) of the given snapshot to the given directory
+        ///
+        /// Fails if the snapshot does not have an archive of its ancillary files.
+        ///
+        /// **WARNING**: The ancillary files are not certified by the snapshot digest.
+        /// To prevent them from altering the certified files, the archive is unpacked in a
+        /// staging directory and only its `ledger` and `volatile` directories are moved to the
+        /// given directory: the download fails if the archive contains any other entry.
+        ///
+        /// **NOTE**: The directory should already exist, and the user running the binary
+        /// must have read/write access to it.
+        pub async fn download_unpack_ancillary(
+            &self,
+            snapshot: &Snapshot,
+            target_dir: &std::path::Path,
+        ) -> MithrilResult<()> {
+            let Some(locations) = &snapshot.ancillary_locations else {
+                return Err(SnapshotClientError::NoAncillaryArchive {
+                    digest: snapshot.digest.clone(),
+                }
+                .into());
+            };
+
+            use crate::utils::SnapshotUnpacker;
+
+            let staging_dir = target_dir.join(ANCILLARY_STAGING_DIRECTORY);
+            std::fs::create_dir_all(&staging_dir).with_context(|| {
+                format!(
+                    "Could not create ancillary staging directory '{}'",
+                    staging_dir.display()
+                )
+            })?;
+
+            let result = self
+                .download_unpack_from_locations(
+                    &snapshot.digest,
+                    locations,
+                    snapshot.ancillary_size.unwrap_or_default(),
+                    snapshot.compression_algorithm.unwrap_or_default(),
+                    None,
+                    &staging_dir,
+                )
+                .await
+                .and_then(|()| {
+                    SnapshotUnpacker.move_ancillary_files(&staging_dir, target_dir)
+                });
+            let _ = std::fs::remove_dir_all(&staging_dir);
+
+            result
+        }
+
+        /// Download the immutable files of the given range (both included) of the given
+        /// snapshot to the given directory
+        ///
+        /// The manifest of the snapshot is verified against the snapshot digest signed in the
+        /// given certificate, which must be the verified certificate of the snapshot (see
+        /// [CertificateClient::verify_chain][crate::certificate_client::CertificateClient::verify_chain]).
+        ///
+        /// Fails if the certificate does not certify the snapshot, if the snapshot does not have
+        /// a [manifest][crate::common::SnapshotManifest] location or if none of its immutable
+        /// files is in the range.
+        ///
+        /// **WARNING**: A partial database can not be used to start a Cardano node.
+        ///
+        /// **NOTE**: The directory should already exist, and the user running the binary
+        /// must have read/write access to it.
+        pub async fn download_unpack_partial(
+            &self,
+            snapshot: &Snapshot,
+            certificate: &crate::MithrilCertificate,
+            target_dir: &std::path::Path,
+            immutable_files_range: std::ops::RangeInclusive<crate::common::ImmutableFileNumber>,
+        ) -> MithrilResult<()> {
+            use crate::common::{ProtocolMessagePartKey, SignedEntityType, SnapshotManifest};
             use crate::feedback::MithrilEvent;
 
-            for location in snapshot.locations.as_slice() {
+            let (certified_digest, certified_beacon) = match (
+                &certificate.signed_entity_type,
+                certificate
+                    .protocol_message
+                    .get_message_part(&ProtocolMessagePartKey::SnapshotDigest),
+            ) {
+                (SignedEntityType::CardanoImmutableFilesFull(beacon), Some(digest))
+                    if certificate.hash == snapshot.certificate_hash
+                        && digest == &snapshot.digest =>
+                {
+                    (digest, beacon)
+                }
+                _ => {
+                    return Err(SnapshotClientError::UncertifiedSnapshot {
+                        digest: snapshot.digest.clone(),
+                        certificate_hash: certificate.hash.clone(),
+                    }
+                    .into());
+                }
+            };
+
+            let manifest_locations: Vec<&String> = snapshot
+                .locations
+                .iter()
+                .filter(|location| SnapshotManifest::is_manifest_location(location))
+                .collect();
+            if manifest_locations.is_empty() {
+                return Err(SnapshotClientError::NoManifestLocation {
+                    digest: snapshot.digest.clone(),
+                    locations: snapshot.locations.join(", "),
+                }
+                .into());
+            }
+
+            let mut last_download_error = None;
+            for location in &manifest_locations {
                 if self.snapshot_downloader.probe(location).await.is_ok() {
                     let download_id = MithrilEvent::new_snapshot_download_id();
                     self.feedback_sender
                         .send_event(MithrilEvent::SnapshotDownloadStarted {
                             digest: snapshot.digest.clone(),
                             download_id: download_id.clone(),
-                            size: snapshot.size,
+                            // The size of the files of the range is only known by the manifest
+                            size: 0,
                         })
                         .await;
-                    return match self
+                    match self
+                        .snapshot_downloader
+                        .download_unpack_partial(
+                            location,
+                            target_dir,
+                            certified_digest,
+                            certified_beacon,
+                            immutable_files_range.clone(),
+                            &download_id,
+                        )
+                        .await
+                    {
+                        Ok(()) => {
+                            self.feedback_sender
+                                .send_event(MithrilEvent::SnapshotDownloadCompleted {
+                                    download_id,
+                                })
+                                .await;
+
+                            return Ok(());
+                        }
+                        Err(e) => {
+                            slog::warn!(
+                                self.logger,
+                                "Failed partial download of snapshot from '{location}', trying the next location. Error: {e}."
+                            );
+                            last_download_error = Some(e);
+                        }
+                    }
+                }
+            }
+
+            if let Some(e) = last_download_error {
+                return Err(e.context(format!(
+                    "Partial download of snapshot '{}' failed from all the locations",
+                    snapshot.digest
+                )));
+            }
+
+            Err(SnapshotClientError::NoWorkingLocation {
+                digest: snapshot.digest.clone(),
+                locations: manifest_locations
+                    .iter()
+                    .map(|location| location.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            }
+            .into())
+        }
+
+        async fn download_unpack_from_locations(
+            &self,
+            digest: &str,
+            locations: &[String],
+            size: u64,
+            compression_algorithm: crate::common::CompressionAlgorithm,
+            archive_hash: Option<String>,
+            target_dir: &std::path::Path,
+        ) -> MithrilResult<()> {
+            use crate::feedback::MithrilEvent;
+
+            let mut last_download_error = None;
+            for location in locations {
+                if self.snapshot_downloader.probe(location).await.is_ok() {
+                    let download_id = MithrilEvent::new_snapshot_download_id();
+                    self.feedback_sender
+                        .send_event(MithrilEvent::SnapshotDownloadStarted {
+                            digest: digest.to_string(),
+                            download_id: download_id.clone(),
+                            size,
+                        })
+                        .await;
+                    match self
                         .snapshot_downloader
                         .download_unpack(
                             location,
                             target_dir,
-                            snapshot.compression_algorithm.unwrap_or_default(),
+                            compression_algorithm,
                             &download_id,
-                            snapshot.size,
+                            size,
+                            archive_hash.clone(),
                         )
                         .await
                     {
@@ -218,27 +573,72 @@ impl SnapshotClient {
                             self.feedback_sender
                                 .send_event(MithrilEvent::SnapshotDownloadCompleted { download_id })
                                 .await;
-                            Ok(())
+                            return Ok(());
                         }
                         Err(e) => {
                             slog::warn!(
                                 self.logger,
-                                "Failed downloading snapshot from '{location}' Error: {e}."
+                                "Failed downloading snapshot from '{location}', trying the next location. Error: {e}."
                             );
-                            Err(e)
+                            last_download_error = Some(e);
                         }
-                    };
+                    }
                 }
             }
 
-            let locations = snapshot.locations.join(", ");
+            if let Some(e) = last_download_error {
+                return Err(e.context(format!(
+                    "Download of snapshot '{digest}' failed from all the locations"
+                )));
+            }
 
             Err(SnapshotClientError::NoWorkingLocation {
-                digest: snapshot.digest.clone(),
-                locations,
+                digest: digest.to_string(),
+                locations: locations.join(", "),
             }
             .into())
         }
+
+        /// Unpack the given snapshot archive file to the given directory, the compression
+        /// algorithm is deduced from the file extension (`tar.gz` or `tar.zst`).
+        ///
+        /// **NOTE**: The directory should already exist, and the user running the binary
+        /// must have read/write access to it.
+        pub async fn unpack_archive(
+            &self,
+            archive_path: &std::path::Path,
+            target_dir: &std::path::Path,
+        ) -> MithrilResult<()> {
+            use crate::common::CompressionAlgorithm;
+            use crate::utils::SnapshotUnpacker;
+
+            let file_name = archive_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            let compression_algorithm = CompressionAlgorithm::list()
+                .into_iter()
+                .find(|algorithm| file_name.ends_with(&format!(".{}", algorithm.tar_file_extension())))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Unsupported snapshot archive extension, expected 'tar.gz' or 'tar.zst': '{}'",
+                        archive_path.display()
+                    )
+                })?;
+            let archive_path = archive_path.to_path_buf();
+            let unpack_dir = target_dir.to_path_buf();
+
+            tokio::task::spawn_blocking(move || {
+                SnapshotUnpacker.unpack_archive_file(&archive_path, compression_algorithm, &unpack_dir)
+            })
+            .await
+            .with_context(|| {
+                format!(
+                    "Unpack: panic while unpacking to dir '{}'",
+                    target_dir.display()
+                )
+            })?
+        }
     }
 
     /// Increments the aggregator snapshot download statistics
@@ -258,10 +658,14 @@ impl SnapshotClient {
 mod tests_download {
     use crate::{
         aggregator_client::MockAggregatorHTTPClient,
+        common::{ProtocolMessage, ProtocolMessagePartKey, SignedEntityType},
         feedback::{MithrilEvent, StackFeedbackReceiver},
         snapshot_downloader::MockHttpSnapshotDownloader,
-        test_utils,
+        test_utils, MithrilCertificate,
     };
+    use flate2::{write::GzEncoder, Compression};
+    use mithril_common::test_utils::TempDir;
+    use std::fs::{self, File};
     use std::path::Path;
 
     use super::*;
@@ -272,7 +676,7 @@ mod tests_download {
         snapshot_downloader.expect_probe().returning(|_| Ok(()));
         snapshot_downloader
             .expect_download_unpack()
-            .returning(|_, _, _, _, _| Ok(()));
+            .returning(|_, _, _, _, _, _| Ok(()));
         let feedback_receiver = Arc::new(StackFeedbackReceiver::new());
         let client = SnapshotClient::new(
             Arc::new(MockAggregatorHTTPClient::new()),
@@ -302,4 +706,329 @@ mod tests_download {
 
         assert_eq!(actual, expected);
     }
+
+    #[tokio::test]
+    async fn download_unpack_check_the_archive_against_the_hash_of_the_snapshot() {
+        let mut snapshot_downloader = MockHttpSnapshotDownloader::new();
+        snapshot_downloader.expect_probe().returning(|_| Ok(()));
+        snapshot_downloader
+            .expect_download_unpack()
+            .withf(|_, _, _, _, _, archive_hash| archive_hash.as_deref() == Some("archive-hash"))
+            .returning(|_, _, _, _, _, _| Ok(()))
+            .once();
+        let client = SnapshotClient::new(
+            Arc::new(MockAggregatorHTTPClient::new()),
+            Arc::new(snapshot_downloader),
+            FeedbackSender::new(&[]),
+            test_utils::test_logger(),
+        );
+        let snapshot = Snapshot {
+            archive_hash: Some("archive-hash".to_string()),
+            ..Snapshot::dummy()
+        };
+
+        client
+            .download_unpack(&snapshot, Path::new(""))
+            .await
+            .expect("download should succeed");
+    }
+
+    #[tokio::test]
+    async fn download_unpack_ancillary_use_the_ancillary_locations() {
+        let mut snapshot_downloader = MockHttpSnapshotDownloader::new();
+        snapshot_downloader.expect_probe().returning(|_| Ok(()));
+        snapshot_downloader
+            .expect_download_unpack()
+            .withf(|location, _, _, _, size, archive_hash| {
+                location == "https://host/ancillary.tar.gz"
+                    && *size == 1234
+                    && archive_hash.is_none()
+            })
+            .returning(|_, _, _, _, _, _| Ok(()))
+            .once();
+        let client = SnapshotClient::new(
+            Arc::new(MockAggregatorHTTPClient::new()),
+            Arc::new(snapshot_downloader),
+            FeedbackSender::new(&[]),
+            test_utils::test_logger(),
+        );
+        let snapshot = Snapshot {
+            ancillary_size: Some(1234),
+            ancillary_locations: Some(vec!["https://host/ancillary.tar.gz".to_string()]),
+            ..Snapshot::dummy()
+        };
+
+        let target_dir = TempDir::create(
+            "client-snapshot",
+            "download_unpack_ancillary_use_the_ancillary_locations",
+        );
+
+        client
+            .download_unpack_ancillary(&snapshot, &target_dir)
+            .await
+            .expect("download should succeed");
+    }
+
+    fn client_unpacking_ancillary_entries(entries: &'static [&'static str]) -> SnapshotClient {
+        let mut snapshot_downloader = MockHttpSnapshotDownloader::new();
+        snapshot_downloader.expect_probe().returning(|_| Ok(()));
+        snapshot_downloader.expect_download_unpack().returning(
+            move |_, staging_dir, _, _, _, _| {
+                for entry in entries {
+                    let path = staging_dir.join(entry);
+                    fs::create_dir_all(path.parent().unwrap()).unwrap();
+                    fs::write(path, "ancillary content").unwrap();
+                }
+                Ok(())
+            },
+        );
+
+        SnapshotClient::new(
+            Arc::new(MockAggregatorHTTPClient::new()),
+            Arc::new(snapshot_downloader),
+            FeedbackSender::new(&[]),
+            test_utils::test_logger(),
+        )
+    }
+
+    fn snapshot_with_ancillary_archive() -> Snapshot {
+        Snapshot {
+            ancillary_locations: Some(vec!["https://host/ancillary.tar.gz".to_string()]),
+            ..Snapshot::dummy()
+        }
+    }
+
+    #[tokio::test]
+    async fn download_unpack_ancillary_move_the_ledger_and_volatile_directories() {
+        let target_dir = TempDir::create(
+            "client-snapshot",
+            "download_unpack_ancillary_move_the_ledger_and_volatile_directories",
+        );
+        let client = client_unpacking_ancillary_entries(&["ledger/1234", "volatile/blocks-0.dat"]);
+
+        client
+            .download_unpack_ancillary(&snapshot_with_ancillary_archive(), &target_dir)
+            .await
+            .expect("download should succeed");
+
+        assert!(target_dir.join("ledger").join("1234").is_file());
+        assert!(target_dir.join("volatile").join("blocks-0.dat").is_file());
+        assert!(!target_dir.join(ANCILLARY_STAGING_DIRECTORY).exists());
+    }
+
+    #[tokio::test]
+    async fn download_unpack_ancillary_fail_if_the_archive_contains_another_entry() {
+        let target_dir = TempDir::create(
+            "client-snapshot",
+            "download_unpack_ancillary_fail_if_the_archive_contains_another_entry",
+        );
+        fs::create_dir_all(target_dir.join("immutable")).unwrap();
+        fs::write(
+            target_dir.join("immutable").join("00001.chunk"),
+            "certified",
+        )
+        .unwrap();
+        let client = client_unpacking_ancillary_entries(&["ledger/1234", "immutable/00001.chunk"]);
+
+        client
+            .download_unpack_ancillary(&snapshot_with_ancillary_archive(), &target_dir)
+            .await
+            .expect_err("download should fail with an entry outside of ledger and volatile");
+
+        assert_eq!(
+            "certified",
+            fs::read_to_string(target_dir.join("immutable").join("00001.chunk")).unwrap()
+        );
+        assert!(!target_dir.join("ledger").exists());
+        assert!(!target_dir.join(ANCILLARY_STAGING_DIRECTORY).exists());
+    }
+
+    #[tokio::test]
+    async fn download_unpack_ancillary_fail_if_the_snapshot_has_no_ancillary_archive() {
+        let client = SnapshotClient::new(
+            Arc::new(MockAggregatorHTTPClient::new()),
+            Arc::new(MockHttpSnapshotDownloader::new()),
+            FeedbackSender::new(&[]),
+            test_utils::test_logger(),
+        );
+        let snapshot = Snapshot {
+            ancillary_locations: None,
+            ..Snapshot::dummy()
+        };
+
+        client
+            .download_unpack_ancillary(&snapshot, Path::new(""))
+            .await
+            .expect_err("download should fail without ancillary archive");
+    }
+
+    fn certificate_of(snapshot: &Snapshot) -> MithrilCertificate {
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message.set_message_part(
+            ProtocolMessagePartKey::SnapshotDigest,
+            snapshot.digest.clone(),
+        );
+
+        MithrilCertificate {
+            hash: snapshot.certificate_hash.clone(),
+            signed_entity_type: SignedEntityType::CardanoImmutableFilesFull(
+                snapshot.beacon.clone(),
+            ),
+            protocol_message,
+            ..MithrilCertificate::dummy()
+        }
+    }
+
+    #[tokio::test]
+    async fn download_unpack_partial_use_the_manifest_location() {
+        let snapshot = Snapshot {
+            locations: vec![
+                "https://host/snapshot.tar.gz".to_string(),
+                "https://host/digest.manifest.json".to_string(),
+            ],
+            ..Snapshot::dummy()
+        };
+        let mut snapshot_downloader = MockHttpSnapshotDownloader::new();
+        snapshot_downloader.expect_probe().returning(|_| Ok(()));
+        let (expected_digest, expected_beacon) = (snapshot.digest.clone(), snapshot.beacon.clone());
+        snapshot_downloader
+            .expect_download_unpack_partial()
+            .withf(move |location, _, digest, beacon, range, _| {
+                location == "https://host/digest.manifest.json"
+                    && digest == expected_digest
+                    && beacon == &expected_beacon
+                    && range == &(10..=20)
+            })
+            .returning(|_, _, _, _, _, _| Ok(()))
+            .once();
+        let client = SnapshotClient::new(
+            Arc::new(MockAggregatorHTTPClient::new()),
+            Arc::new(snapshot_downloader),
+            FeedbackSender::new(&[]),
+            test_utils::test_logger(),
+        );
+
+        client
+            .download_unpack_partial(
+                &snapshot,
+                &certificate_of(&snapshot),
+                Path::new(""),
+                10..=20,
+            )
+            .await
+            .expect("partial download should succeed");
+    }
+
+    #[tokio::test]
+    async fn download_unpack_partial_fail_if_the_certificate_does_not_certify_the_snapshot() {
+        let client = SnapshotClient::new(
+            Arc::new(MockAggregatorHTTPClient::new()),
+            Arc::new(MockHttpSnapshotDownloader::new()),
+            FeedbackSender::new(&[]),
+            test_utils::test_logger(),
+        );
+        let snapshot = Snapshot {
+            locations: vec!["https://host/digest.manifest.json".to_string()],
+            ..Snapshot::dummy()
+        };
+        let certificate = certificate_of(&Snapshot {
+            digest: "another-digest".to_string(),
+            ..snapshot.clone()
+        });
+
+        let error = client
+            .download_unpack_partial(&snapshot, &certificate, Path::new(""), 10..=20)
+            .await
+            .expect_err("partial download should fail with a certificate of another snapshot");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<SnapshotClientError>(),
+                Some(SnapshotClientError::UncertifiedSnapshot { .. })
+            ),
+            "unexpected error: {error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn download_unpack_partial_fail_if_the_snapshot_has_no_manifest_location() {
+        let client = SnapshotClient::new(
+            Arc::new(MockAggregatorHTTPClient::new()),
+            Arc::new(MockHttpSnapshotDownloader::new()),
+            FeedbackSender::new(&[]),
+            test_utils::test_logger(),
+        );
+        let snapshot = Snapshot {
+            locations: vec!["https://host/snapshot.tar.gz".to_string()],
+            ..Snapshot::dummy()
+        };
+
+        let error = client
+            .download_unpack_partial(
+                &snapshot,
+                &certificate_of(&snapshot),
+                Path::new(""),
+                10..=20,
+            )
+            .await
+            .expect_err("partial download should fail without manifest location");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<SnapshotClientError>(),
+                Some(SnapshotClientError::NoManifestLocation { .. })
+            ),
+            "unexpected error: {error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn unpack_archive_of_a_gzip_tarball() {
+        let temp_dir = TempDir::create("client-snapshot", "unpack_archive_of_a_gzip_tarball");
+        let archive_path = temp_dir.join("snapshot.tar.gz");
+        {
+            let archive = File::create(&archive_path).unwrap();
+            let mut tar = tar::Builder::new(GzEncoder::new(archive, Compression::default()));
+            let content = b"immutable content";
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_cksum();
+            tar.append_data(&mut header, "immutable/00001.chunk", content.as_slice())
+                .unwrap();
+            tar.into_inner().unwrap().finish().unwrap();
+        }
+        let unpack_dir = temp_dir.join("unpack");
+        fs::create_dir(&unpack_dir).unwrap();
+        let client = SnapshotClient::new(
+            Arc::new(MockAggregatorHTTPClient::new()),
+            Arc::new(MockHttpSnapshotDownloader::new()),
+            FeedbackSender::new(&[]),
+            test_utils::test_logger(),
+        );
+
+        client
+            .unpack_archive(&archive_path, &unpack_dir)
+            .await
+            .expect("unpack should succeed");
+
+        assert_eq!(
+            "immutable content",
+            fs::read_to_string(unpack_dir.join("immutable/00001.chunk")).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn unpack_archive_fail_if_the_extension_is_not_supported() {
+        let client = SnapshotClient::new(
+            Arc::new(MockAggregatorHTTPClient::new()),
+            Arc::new(MockHttpSnapshotDownloader::new()),
+            FeedbackSender::new(&[]),
+            test_utils::test_logger(),
+        );
+
+        client
+            .unpack_archive(Path::new("snapshot.zip"), Path::new(""))
+            .await
+            .expect_err("unpack should fail on an unsupported extension");
+    }
 }
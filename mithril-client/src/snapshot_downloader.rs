@@ -6,18 +6,33 @@
 //! Snapshots locations can be of various kinds, right now we only support HTTP
 //! download (using the [HttpSnapshotDownloader]) but other types may be added in
 //! the future.
+//!
+//! A location can either target a compressed tarball or a
+//! [manifest][crate::common::SnapshotManifest] listing the content addressed objects
+//! the snapshot files must be reassembled from. Only a manifest allows a partial download of
+//! a range of immutable files. The manifest of a partial download is verified against the
+//! certified snapshot digest before any of its files is downloaded.
+//!
+//! The integrity of a tarball is checked while it's downloaded, before it's unpacked: the
+//! download fails as soon as the transferred size exceeds the size of the snapshot, and at its end
+//! if the SHA-256 of the transferred bytes differs from the archive hash of the snapshot artifact.
+//! The expected values come from the snapshot artifact and never from the server hosting the
+//! archive.
 
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use futures::StreamExt;
-use reqwest::{Response, StatusCode};
+use reqwest::{header::HeaderMap, Response, StatusCode};
+use sha2::{Digest, Sha256};
 use slog::{debug, Logger};
+use std::ops::RangeInclusive;
 use std::path::Path;
+use tokio::io::AsyncWriteExt;
 
 #[cfg(test)]
 use mockall::automock;
 
-use crate::common::CompressionAlgorithm;
+use crate::common::{CardanoDbBeacon, CompressionAlgorithm, ImmutableFileNumber, SnapshotManifest};
 use crate::feedback::{FeedbackSender, MithrilEvent};
 use crate::utils::SnapshotUnpacker;
 use crate::MithrilResult;
@@ -27,6 +42,9 @@ use crate::MithrilResult;
 pub trait SnapshotDownloader: Sync + Send {
     /// Download and unpack a snapshot archive on the disk.
     ///
+    /// The archive is only unpacked once its size matches the `snapshot_size` and, if given, its
+    /// SHA-256 matches the hex encoded `expected_archive_hash`.
+    ///
     /// The `download_id` is a unique identifier that allow
     /// [feedback receivers][crate::feedback::FeedbackReceiver] to track concurrent downloads.
     ///
@@ -38,6 +56,25 @@ pub trait SnapshotDownloader: Sync + Send {
         compression_algorithm: CompressionAlgorithm,
         download_id: &str,
         snapshot_size: u64,
+        expected_archive_hash: Option<String>,
+    ) -> MithrilResult<()>;
+
+    /// Download on the disk the immutable files of the given range (both included) of a
+    /// snapshot, the location must target a [manifest][crate::common::SnapshotManifest].
+    ///
+    /// The files are only downloaded if the manifest matches the `certified_digest` of the
+    /// snapshot at the given `beacon`, see [SnapshotManifest::verify_snapshot_digest].
+    ///
+    /// The `download_id` is a unique identifier that allow
+    /// [feedback receivers][crate::feedback::FeedbackReceiver] to track concurrent downloads.
+    async fn download_unpack_partial(
+        &self,
+        location: &str,
+        target_dir: &Path,
+        certified_digest: &str,
+        beacon: &CardanoDbBeacon,
+        immutable_files_range: RangeInclusive<ImmutableFileNumber>,
+        download_id: &str,
     ) -> MithrilResult<()>;
 
     /// Test if the given snapshot location exists.
@@ -78,40 +115,106 @@ impl HttpSnapshotDownloader {
             status_code => Err(anyhow!("Unhandled error {status_code}")),
         }
     }
-}
 
-#[cfg_attr(test, automock)]
-#[async_trait]
-impl SnapshotDownloader for HttpSnapshotDownloader {
-    async fn download_unpack(
+    async fn get_manifest(&self, location: &str) -> MithrilResult<SnapshotManifest> {
+        self.get(location)
+            .await?
+            .json()
+            .await
+            .with_context(|| format!("Could not deserialize manifest (location='{location}')"))
+    }
+
+    /// Reassemble the files of a manifest by downloading each of its objects.
+    async fn download_from_manifest(
         &self,
-        location: &str,
+        manifest: &SnapshotManifest,
         target_dir: &Path,
-        compression_algorithm: CompressionAlgorithm,
         download_id: &str,
-        snapshot_size: u64,
     ) -> MithrilResult<()> {
-        if !target_dir.is_dir() {
-            Err(
-                anyhow!("target path is not a directory or does not exist: `{target_dir:?}`")
-                    .context("Download-Unpack: prerequisite error"),
-            )?;
-        }
+        let size = manifest.total_size();
         let mut downloaded_bytes: u64 = 0;
-        let mut remote_stream = self.get(location).await?.bytes_stream();
-        let (sender, receiver) = flume::bounded(5);
 
-        let dest_dir = target_dir.to_path_buf();
-        let unpack_thread = tokio::task::spawn_blocking(move || -> MithrilResult<()> {
-            let unpacker = SnapshotUnpacker;
-            unpacker.unpack_snapshot(receiver, compression_algorithm, &dest_dir)
-        });
+        // Check all the paths before writing anything to the target directory
+        for entry in &manifest.entries {
+            entry.relative_path()?;
+        }
+
+        for entry in &manifest.entries {
+            let object_location = manifest.object_location(&entry.object_hash);
+            let content = self
+                .get(&object_location)
+                .await?
+                .bytes()
+                .await
+                .with_context(|| {
+                    format!("Download: could not read object (location='{object_location}')")
+                })?;
+
+            let computed_hash = SnapshotManifest::compute_object_hash(&content);
+            if computed_hash != entry.object_hash {
+                return Err(anyhow!(
+                    "Download: object hash mismatch for file '{}', expected '{}', got '{computed_hash}'",
+                    entry.path,
+                    entry.object_hash
+                ));
+            }
+
+            let file_path = target_dir.join(entry.relative_path()?);
+            if let Some(parent) = file_path.parent() {
+                std::fs::create_dir_all(parent).with_context(|| {
+                    format!("Unpack: could not create directory '{}'", parent.display())
+                })?;
+            }
+            std::fs::write(&file_path, &content).with_context(|| {
+                format!("Unpack: could not write file '{}'", file_path.display())
+            })?;
+
+            downloaded_bytes += content.len() as u64;
+            self.feedback_sender
+                .send_event(MithrilEvent::SnapshotDownloadProgress {
+                    download_id: download_id.to_owned(),
+                    downloaded_bytes,
+                    size,
+                })
+                .await
+        }
+
+        Ok(())
+    }
+
+    /// Download an archive to the given path, its size and its hash are checked while its bytes
+    /// are transferred.
+    async fn download_verified_archive(
+        &self,
+        location: &str,
+        archive_path: &Path,
+        download_id: &str,
+        snapshot_size: u64,
+        expected_archive_hash: Option<String>,
+    ) -> MithrilResult<()> {
+        let response = self.get(location).await?;
+        let mut verifier = StreamingArchiveVerifier::new(
+            response.headers(),
+            snapshot_size,
+            expected_archive_hash,
+        )?;
+        let mut remote_stream = response.bytes_stream();
+        let mut archive_file = tokio::fs::File::create(archive_path)
+            .await
+            .with_context(|| {
+                format!(
+                    "Download: could not create archive file '{}'",
+                    archive_path.display()
+                )
+            })?;
+        let mut downloaded_bytes: u64 = 0;
 
         while let Some(item) = remote_stream.next().await {
             let chunk = item.with_context(|| "Download: Could not read from byte stream")?;
+            verifier.update(&chunk)?;
 
-            sender.send_async(chunk.to_vec()).await.with_context(|| {
-                format!("Download: could not write {} bytes to stream.", chunk.len())
+            archive_file.write_all(&chunk).await.with_context(|| {
+                format!("Download: could not write {} bytes to file.", chunk.len())
             })?;
 
             downloaded_bytes += chunk.len() as u64;
@@ -123,21 +226,200 @@ impl SnapshotDownloader for HttpSnapshotDownloader {
                 })
                 .await
         }
+        archive_file
+            .flush()
+            .await
+            .with_context(|| "Download: could not flush the archive file")?;
+
+        verifier.finalize()
+    }
+}
+
+/// Check the integrity of a snapshot archive while its bytes are downloaded.
+///
+/// The expected size and SHA-256 come from the snapshot artifact: a size of `0` means it's
+/// unknown, as does a missing hash for the artifacts created before the hash was published.
+struct StreamingArchiveVerifier {
+    expected_size: Option<u64>,
+    expected_hash: Option<String>,
+    hasher: Sha256,
+    transferred_bytes: u64,
+}
+
+impl StreamingArchiveVerifier {
+    fn new(
+        headers: &HeaderMap,
+        snapshot_size: u64,
+        expected_hash: Option<String>,
+    ) -> MithrilResult<Self> {
+        let expected_size = (snapshot_size > 0).then_some(snapshot_size);
+        let content_length = headers
+            .get(reqwest::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+        if let (Some(expected_size), Some(content_length)) = (expected_size, content_length) {
+            if content_length != expected_size {
+                return Err(anyhow!(
+                    "Download: archive size mismatch, expected {expected_size} bytes, the server announced {content_length} bytes"
+                ));
+            }
+        }
+
+        Ok(Self {
+            expected_size,
+            expected_hash,
+            hasher: Sha256::new(),
+            transferred_bytes: 0,
+        })
+    }
+
+    /// Hash a downloaded chunk, fails if more bytes than expected were transferred
+    fn update(&mut self, chunk: &[u8]) -> MithrilResult<()> {
+        self.hasher.update(chunk);
+        self.transferred_bytes += chunk.len() as u64;
+
+        match self.expected_size {
+            Some(expected_size) if self.transferred_bytes > expected_size => Err(anyhow!(
+                "Download: archive size mismatch, expected {expected_size} bytes, got at least {} bytes",
+                self.transferred_bytes
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the size and the hash of the whole transferred archive
+    fn finalize(self) -> MithrilResult<()> {
+        if let Some(expected_size) = self.expected_size {
+            if self.transferred_bytes != expected_size {
+                return Err(anyhow!(
+                    "Download: archive size mismatch, expected {expected_size} bytes, got {} bytes",
+                    self.transferred_bytes
+                ));
+            }
+        }
+        if let Some(expected_hash) = self.expected_hash {
+            let computed_hash = format!("{:x}", self.hasher.finalize());
+            if !computed_hash.eq_ignore_ascii_case(&expected_hash) {
+                return Err(anyhow!(
+                    "Download: archive hash mismatch, expected '{expected_hash}', got '{computed_hash}'"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
 
-        drop(sender); // Signal EOF
-        unpack_thread
+#[cfg_attr(test, automock)]
+#[async_trait]
+impl SnapshotDownloader for HttpSnapshotDownloader {
+    async fn download_unpack(
+        &self,
+        location: &str,
+        target_dir: &Path,
+        compression_algorithm: CompressionAlgorithm,
+        download_id: &str,
+        snapshot_size: u64,
+        expected_archive_hash: Option<String>,
+    ) -> MithrilResult<()> {
+        if !target_dir.is_dir() {
+            Err(
+                anyhow!("target path is not a directory or does not exist: `{target_dir:?}`")
+                    .context("Download-Unpack: prerequisite error"),
+            )?;
+        }
+        if SnapshotManifest::is_manifest_location(location) {
+            let manifest = self.get_manifest(location).await?;
+            return self
+                .download_from_manifest(&manifest, target_dir, download_id)
+                .await;
+        }
+        // The archive is staged next to the unpacked files and removed once unpacked
+        let archive_path = target_dir.join(format!(
+            ".{download_id}.{}",
+            compression_algorithm.tar_file_extension()
+        ));
+        let result = match self
+            .download_verified_archive(
+                location,
+                &archive_path,
+                download_id,
+                snapshot_size,
+                expected_archive_hash,
+            )
             .await
+        {
+            Ok(()) => {
+                let archive = archive_path.clone();
+                let dest_dir = target_dir.to_path_buf();
+                tokio::task::spawn_blocking(move || {
+                    SnapshotUnpacker.unpack_archive_file(&archive, compression_algorithm, &dest_dir)
+                })
+                .await
+                .with_context(|| {
+                    format!(
+                        "Unpack: panic while unpacking to dir '{}'",
+                        target_dir.display()
+                    )
+                })
+                .and_then(|unpack_result| {
+                    unpack_result.with_context(|| {
+                        format!("Unpack: could not unpack to dir '{}'", target_dir.display())
+                    })
+                })
+            }
+            Err(error) => Err(error),
+        };
+        if archive_path.exists() {
+            if let Err(error) = std::fs::remove_file(&archive_path) {
+                debug!(
+                    self.logger,
+                    "Could not remove the downloaded archive '{}': {error}",
+                    archive_path.display()
+                );
+            }
+        }
+
+        result
+    }
+
+    async fn download_unpack_partial(
+        &self,
+        location: &str,
+        target_dir: &Path,
+        certified_digest: &str,
+        beacon: &CardanoDbBeacon,
+        immutable_files_range: RangeInclusive<ImmutableFileNumber>,
+        download_id: &str,
+    ) -> MithrilResult<()> {
+        if !target_dir.is_dir() {
+            Err(
+                anyhow!("target path is not a directory or does not exist: `{target_dir:?}`")
+                    .context("Download-Unpack: prerequisite error"),
+            )?;
+        }
+        if !SnapshotManifest::is_manifest_location(location) {
+            return Err(anyhow!(
+                "A partial download requires a snapshot manifest location, got: '{location}'"
+            ));
+        }
+        let manifest = self.get_manifest(location).await?;
+        manifest
+            .verify_snapshot_digest(certified_digest, beacon)
             .with_context(|| {
                 format!(
-                    "Unpack: panic while unpacking to dir '{}'",
-                    target_dir.display()
+                    "Download: the snapshot manifest can not be trusted (location='{location}')"
                 )
-            })?
-            .with_context(|| {
-                format!("Unpack: could not unpack to dir '{}'", target_dir.display())
             })?;
+        let manifest = manifest.restrict_to_immutable_files_range(&immutable_files_range);
+        if manifest.entries.is_empty() {
+            return Err(anyhow!(
+                "The snapshot does not contain any immutable file in the range {immutable_files_range:?}"
+            ));
+        }
 
-        Ok(())
+        self.download_from_manifest(&manifest, target_dir, download_id)
+            .await
     }
 
     async fn probe(&self, location: &str) -> MithrilResult<()> {
@@ -155,3 +437,444 @@ impl SnapshotDownloader for HttpSnapshotDownloader {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use httpmock::MockServer;
+    use mithril_common::entities::SnapshotManifestEntry;
+
+    use std::path::Path;
+
+    use crate::test_utils;
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir()
+            .join("mithril_client_snapshot_downloader")
+            .join(name);
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+        std::fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn setup_manifest_server(server: &MockServer, files: &[(&str, &[u8])]) -> String {
+        setup_manifest_server_with_digest(server, files, "digest")
+    }
+
+    fn setup_manifest_server_with_digest(
+        server: &MockServer,
+        files: &[(&str, &[u8])],
+        digest: &str,
+    ) -> String {
+        let entries = files
+            .iter()
+            .map(|(path, content)| {
+                let object_hash = SnapshotManifest::compute_object_hash(content);
+                server.mock(|when, then| {
+                    when.path(format!("/objects/{object_hash}"));
+                    then.status(200).body(*content);
+                });
+                SnapshotManifestEntry {
+                    path: path.to_string(),
+                    object_hash,
+                    size: content.len() as u64,
+                }
+            })
+            .collect();
+        let manifest = SnapshotManifest::new(digest.to_string(), server.url("/objects"), entries);
+        server.mock(|when, then| {
+            when.path("/digest.manifest.json");
+            then.status(200)
+                .body(serde_json::to_string(&manifest).unwrap());
+        });
+
+        server.url("/digest.manifest.json")
+    }
+
+    #[tokio::test]
+    async fn download_unpack_reassemble_snapshot_from_manifest() {
+        let server = MockServer::start();
+        let location = setup_manifest_server(
+            &server,
+            &[
+                ("immutable/00001.chunk", b"chunk 1"),
+                ("protocolMagicId", b"42"),
+            ],
+        );
+        let target_dir = temp_dir("download_unpack_reassemble_snapshot_from_manifest");
+        let downloader =
+            HttpSnapshotDownloader::new(FeedbackSender::new(&[]), test_utils::test_logger())
+                .unwrap();
+
+        downloader
+            .download_unpack(
+                &location,
+                &target_dir,
+                CompressionAlgorithm::default(),
+                "download_id",
+                0,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            b"chunk 1".to_vec(),
+            std::fs::read(target_dir.join("immutable/00001.chunk")).unwrap()
+        );
+        assert_eq!(
+            b"42".to_vec(),
+            std::fs::read(target_dir.join("protocolMagicId")).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn download_unpack_fail_if_an_object_does_not_match_its_hash() {
+        let server = MockServer::start();
+        let manifest = SnapshotManifest::new(
+            "digest".to_string(),
+            server.url("/objects"),
+            vec![SnapshotManifestEntry {
+                path: "protocolMagicId".to_string(),
+                object_hash: "invalid_hash".to_string(),
+                size: 2,
+            }],
+        );
+        server.mock(|when, then| {
+            when.path("/objects/invalid_hash");
+            then.status(200).body("42");
+        });
+        server.mock(|when, then| {
+            when.path("/digest.manifest.json");
+            then.status(200)
+                .body(serde_json::to_string(&manifest).unwrap());
+        });
+        let target_dir = temp_dir("download_unpack_fail_if_an_object_does_not_match_its_hash");
+        let downloader =
+            HttpSnapshotDownloader::new(FeedbackSender::new(&[]), test_utils::test_logger())
+                .unwrap();
+
+        downloader
+            .download_unpack(
+                &server.url("/digest.manifest.json"),
+                &target_dir,
+                CompressionAlgorithm::default(),
+                "download_id",
+                0,
+                None,
+            )
+            .await
+            .expect_err("download_unpack should fail on object hash mismatch");
+    }
+
+    #[tokio::test]
+    async fn download_unpack_fail_if_a_manifest_entry_path_escapes_the_target_directory() {
+        let server = MockServer::start();
+        let location = setup_manifest_server(
+            &server,
+            &[
+                ("protocolMagicId", b"42"),
+                ("../outside_target_dir", b"malicious"),
+            ],
+        );
+        let target_dir =
+            temp_dir("download_unpack_fail_if_a_manifest_entry_path_escapes_the_target_directory");
+        let downloader =
+            HttpSnapshotDownloader::new(FeedbackSender::new(&[]), test_utils::test_logger())
+                .unwrap();
+
+        downloader
+            .download_unpack(
+                &location,
+                &target_dir,
+                CompressionAlgorithm::default(),
+                "download_id",
+                0,
+                None,
+            )
+            .await
+            .expect_err("download_unpack should fail on a path escaping the target directory");
+
+        assert!(!target_dir.join("protocolMagicId").exists());
+        assert!(!target_dir
+            .parent()
+            .unwrap()
+            .join("outside_target_dir")
+            .exists());
+    }
+
+    const PARTIAL_DOWNLOAD_FILES: [(&str, &[u8]); 4] = [
+        ("immutable/00001.chunk", b"chunk 1"),
+        ("immutable/00002.chunk", b"chunk 2"),
+        ("immutable/00003.chunk", b"chunk 3"),
+        ("protocolMagicId", b"42"),
+    ];
+
+    fn partial_download_beacon() -> CardanoDbBeacon {
+        CardanoDbBeacon::new("devnet".to_string(), 4, 3)
+    }
+
+    fn partial_download_certified_digest() -> String {
+        let manifest = SnapshotManifest::new(
+            "digest".to_string(),
+            "objects".to_string(),
+            PARTIAL_DOWNLOAD_FILES
+                .iter()
+                .map(|(path, content)| SnapshotManifestEntry {
+                    path: path.to_string(),
+                    object_hash: SnapshotManifest::compute_object_hash(content),
+                    size: content.len() as u64,
+                })
+                .collect(),
+        );
+
+        manifest
+            .compute_snapshot_digest(&partial_download_beacon())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn download_unpack_partial_only_download_the_immutable_files_of_the_range() {
+        let server = MockServer::start();
+        let certified_digest = partial_download_certified_digest();
+        let location =
+            setup_manifest_server_with_digest(&server, &PARTIAL_DOWNLOAD_FILES, &certified_digest);
+        let target_dir =
+            temp_dir("download_unpack_partial_only_download_the_immutable_files_of_the_range");
+        let downloader =
+            HttpSnapshotDownloader::new(FeedbackSender::new(&[]), test_utils::test_logger())
+                .unwrap();
+
+        downloader
+            .download_unpack_partial(
+                &location,
+                &target_dir,
+                &certified_digest,
+                &partial_download_beacon(),
+                2..=3,
+                "download_id",
+            )
+            .await
+            .unwrap();
+
+        assert!(!target_dir.join("immutable/00001.chunk").exists());
+        assert_eq!(
+            b"chunk 2".to_vec(),
+            std::fs::read(target_dir.join("immutable/00002.chunk")).unwrap()
+        );
+        assert!(target_dir.join("immutable/00003.chunk").exists());
+        assert!(!target_dir.join("protocolMagicId").exists());
+
+        downloader
+            .download_unpack_partial(
+                &location,
+                &target_dir,
+                &certified_digest,
+                &partial_download_beacon(),
+                10..=12,
+                "download_id",
+            )
+            .await
+            .expect_err("download_unpack_partial should fail if no immutable file is in range");
+    }
+
+    #[tokio::test]
+    async fn download_unpack_partial_fails_if_the_manifest_does_not_match_the_certified_digest() {
+        let server = MockServer::start();
+        let certified_digest = partial_download_certified_digest();
+        let tampered_files: [(&str, &[u8]); 4] = [
+            ("immutable/00001.chunk", b"chunk 1"),
+            ("immutable/00002.chunk", b"tampered chunk 2"),
+            ("immutable/00003.chunk", b"chunk 3"),
+            ("protocolMagicId", b"42"),
+        ];
+        let location =
+            setup_manifest_server_with_digest(&server, &tampered_files, &certified_digest);
+        let target_dir = temp_dir(
+            "download_unpack_partial_fails_if_the_manifest_does_not_match_the_certified_digest",
+        );
+        let downloader =
+            HttpSnapshotDownloader::new(FeedbackSender::new(&[]), test_utils::test_logger())
+                .unwrap();
+
+        downloader
+            .download_unpack_partial(
+                &location,
+                &target_dir,
+                &certified_digest,
+                &partial_download_beacon(),
+                2..=3,
+                "download_id",
+            )
+            .await
+            .expect_err("download_unpack_partial should fail with an untrusted manifest");
+
+        assert!(!target_dir.join("immutable/00002.chunk").exists());
+    }
+
+    fn gzip_archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        for (path, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, path, *content).unwrap();
+        }
+
+        tar.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn sha256_hex(content: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(content))
+    }
+
+    async fn download_archive(
+        server: &MockServer,
+        target_dir: &Path,
+        snapshot_size: u64,
+        expected_archive_hash: Option<String>,
+    ) -> MithrilResult<()> {
+        let downloader =
+            HttpSnapshotDownloader::new(FeedbackSender::new(&[]), test_utils::test_logger())
+                .unwrap();
+
+        downloader
+            .download_unpack(
+                &server.url("/snapshot.tar.gz"),
+                target_dir,
+                CompressionAlgorithm::Gzip,
+                "download_id",
+                snapshot_size,
+                expected_archive_hash,
+            )
+            .await
+    }
+
+    fn archive_entries(target_dir: &Path) -> Vec<std::fs::DirEntry> {
+        std::fs::read_dir(target_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn download_unpack_check_the_size_and_the_hash_of_the_archive() {
+        let archive = gzip_archive(&[("protocolMagicId", b"42")]);
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/snapshot.tar.gz");
+            then.status(200).body(&archive);
+        });
+        let target_dir = temp_dir("download_unpack_check_the_size_and_the_hash_of_the_archive");
+
+        download_archive(
+            &server,
+            &target_dir,
+            archive.len() as u64,
+            Some(sha256_hex(&archive)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            b"42".to_vec(),
+            std::fs::read(target_dir.join("protocolMagicId")).unwrap()
+        );
+        assert_eq!(
+            1,
+            archive_entries(&target_dir).len(),
+            "the downloaded archive should be removed once unpacked"
+        );
+    }
+
+    #[tokio::test]
+    async fn download_unpack_fail_without_unpacking_if_the_archive_does_not_match_its_hash() {
+        let archive = gzip_archive(&[("protocolMagicId", b"42")]);
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/snapshot.tar.gz");
+            then.status(200).body(&archive);
+        });
+        let target_dir = temp_dir(
+            "download_unpack_fail_without_unpacking_if_the_archive_does_not_match_its_hash",
+        );
+
+        let error = download_archive(
+            &server,
+            &target_dir,
+            archive.len() as u64,
+            Some(sha256_hex(b"another archive")),
+        )
+        .await
+        .expect_err("download_unpack should fail on archive hash mismatch");
+
+        assert!(
+            error.to_string().contains("archive hash mismatch"),
+            "unexpected error: {error:?}"
+        );
+        assert!(
+            archive_entries(&target_dir).is_empty(),
+            "nothing should be unpacked nor left in the target directory"
+        );
+    }
+
+    #[tokio::test]
+    async fn download_unpack_fail_if_the_archive_does_not_match_the_snapshot_size() {
+        let archive = gzip_archive(&[("protocolMagicId", b"42")]);
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.path("/snapshot.tar.gz");
+            then.status(200).body(&archive);
+        });
+        let target_dir =
+            temp_dir("download_unpack_fail_if_the_archive_does_not_match_the_snapshot_size");
+
+        let error = download_archive(&server, &target_dir, archive.len() as u64 - 1, None)
+            .await
+            .expect_err("download_unpack should fail on archive size mismatch");
+
+        assert!(
+            error.to_string().contains("archive size mismatch"),
+            "unexpected error: {error:?}"
+        );
+    }
+
+    #[test]
+    fn verifier_fail_as_soon_as_more_bytes_than_expected_are_transferred() {
+        let mut verifier = StreamingArchiveVerifier::new(&HeaderMap::new(), 4, None).unwrap();
+
+        verifier.update(b"1234").unwrap();
+        verifier
+            .update(b"5")
+            .expect_err("update should fail once the expected size is exceeded");
+    }
+
+    #[test]
+    fn verifier_only_check_the_size_when_the_snapshot_size_is_known() {
+        let mut verifier = StreamingArchiveVerifier::new(&HeaderMap::new(), 0, None).unwrap();
+
+        verifier.update(b"12345").unwrap();
+        verifier.finalize().unwrap();
+    }
+
+    #[test]
+    fn verifier_compare_the_hash_case_insensitively() {
+        let mut verifier = StreamingArchiveVerifier::new(
+            &HeaderMap::new(),
+            0,
+            Some(sha256_hex(b"archive").to_uppercase()),
+        )
+        .unwrap();
+
+        verifier.update(b"archive").unwrap();
+        verifier.finalize().unwrap();
+    }
+}
@@ -2,9 +2,7 @@
 //! This module contains tools needed mostly for the snapshot download and unpack.
 
 cfg_fs! {
-    mod stream_reader;
     mod unpacker;
 
-    pub use stream_reader::*;
     pub use unpacker::*;
 }
@@ -1,48 +1,121 @@
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use flate2::read::GzDecoder;
-use flume::Receiver;
+use std::fs::{self, File};
+use std::io::Read;
 use std::path::Path;
 use tar::Archive;
 
 use crate::common::CompressionAlgorithm;
-use crate::utils::StreamReader;
 use crate::MithrilResult;
 
+/// Directories allowed in an ancillary archive.
+const ANCILLARY_DIRECTORIES: [&str; 2] = ["ledger", "volatile"];
+
 /// Unpack a downloaded archive in a given directory.
 #[derive(Default)]
 pub struct SnapshotUnpacker;
 
 impl SnapshotUnpacker {
-    /// Unpack the snapshot from the given stream into the given directory.
-    pub fn unpack_snapshot(
+    /// Unpack the snapshot archive file at the given path into the given directory.
+    pub fn unpack_archive_file(
         &self,
-        stream: Receiver<Vec<u8>>,
+        archive_path: &Path,
         compression_algorithm: CompressionAlgorithm,
         unpack_dir: &Path,
     ) -> MithrilResult<()> {
-        let input = StreamReader::new(stream);
+        let input = File::open(archive_path).with_context(|| {
+            format!(
+                "Could not open snapshot archive '{}'",
+                archive_path.display()
+            )
+        })?;
+
+        self.unpack(input, compression_algorithm, unpack_dir)
+            .with_context(|| {
+                format!(
+                    "Could not unpack snapshot archive '{}' to directory '{}'",
+                    archive_path.display(),
+                    unpack_dir.display()
+                )
+            })
+    }
+
+    /// Move the ancillary files unpacked in the given staging directory to the given directory.
+    ///
+    /// Only the `ledger` and `volatile` directories are allowed in an ancillary archive: the move
+    /// fails without touching the target directory if the staging directory contains any other
+    /// entry or a symbolic link.
+    pub fn move_ancillary_files(&self, staging_dir: &Path, target_dir: &Path) -> MithrilResult<()> {
+        let mut ancillary_dirs = vec![];
+        for entry in fs::read_dir(staging_dir)? {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            if !ANCILLARY_DIRECTORIES.contains(&file_name.to_string_lossy().as_ref())
+                || !entry.file_type()?.is_dir()
+            {
+                return Err(anyhow!(
+                    "Unexpected entry '{}' in the ancillary archive, only the '{}' directories are allowed",
+                    file_name.to_string_lossy(),
+                    ANCILLARY_DIRECTORIES.join("' and '")
+                ));
+            }
+            Self::ensure_no_symlink(&entry.path())?;
+            ancillary_dirs.push(file_name);
+        }
+
+        for dir in ancillary_dirs {
+            let target = target_dir.join(&dir);
+            if target.exists() {
+                fs::remove_dir_all(&target).with_context(|| {
+                    format!("Could not remove directory '{}'", target.display())
+                })?;
+            }
+            fs::rename(staging_dir.join(&dir), &target).with_context(|| {
+                format!(
+                    "Could not move ancillary directory to '{}'",
+                    target.display()
+                )
+            })?;
+        }
+
+        Ok(())
+    }
 
+    fn ensure_no_symlink(dir: &Path) -> MithrilResult<()> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_symlink() {
+                return Err(anyhow!(
+                    "Unexpected symbolic link '{}' in the ancillary archive",
+                    entry.path().display()
+                ));
+            }
+            if file_type.is_dir() {
+                Self::ensure_no_symlink(&entry.path())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn unpack<R: Read>(
+        &self,
+        input: R,
+        compression_algorithm: CompressionAlgorithm,
+        unpack_dir: &Path,
+    ) -> MithrilResult<()> {
         match compression_algorithm {
             CompressionAlgorithm::Gzip => {
                 let gzip_decoder = GzDecoder::new(input);
                 let mut snapshot_archive = Archive::new(gzip_decoder);
-                snapshot_archive.unpack(unpack_dir).with_context(|| {
-                    format!(
-                        "Could not unpack from streamed data snapshot to directory '{}'",
-                        unpack_dir.display()
-                    )
-                })?;
+                snapshot_archive.unpack(unpack_dir)?;
             }
             CompressionAlgorithm::Zstandard => {
                 let zstandard_decoder = zstd::Decoder::new(input)
                     .with_context(|| "Unpack failed: Create Zstandard decoder error")?;
                 let mut snapshot_archive = Archive::new(zstandard_decoder);
-                snapshot_archive.unpack(unpack_dir).with_context(|| {
-                    format!(
-                        "Could not unpack from streamed data snapshot to directory '{}'",
-                        unpack_dir.display()
-                    )
-                })?;
+                snapshot_archive.unpack(unpack_dir)?;
             }
         };
 
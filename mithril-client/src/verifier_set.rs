@@ -0,0 +1,342 @@
+//! A set of aggregate verification keys, per epoch, that can verify Mithril certificates without
+//! contacting an aggregator.
+//!
+//! A [VerifierSet] is built from a verified Mithril stake distribution with
+//! [download_verifier_set][crate::mithril_stake_distribution_client::MithrilStakeDistributionClient::download_verifier_set],
+//! it can be serialized to be exported and reloaded by downstream applications.
+//!
+//! **Important:** A [VerifierSet] only checks the hashes of a certificate and its multi-signature
+//! against the aggregate verification key and the protocol parameters of its epoch, it does not
+//! validate the certificate chain.
+//!
+//! # Verify a certificate with a verifier set
+//!
+//! ```no_run
+//! # async fn run() -> mithril_client::MithrilResult<()> {
+//! use mithril_client::ClientBuilder;
+//!
+//! let client = ClientBuilder::aggregator("YOUR_AGGREGATOR_ENDPOINT", "YOUR_GENESIS_VERIFICATION_KEY").build()?;
+//! let verifier_set = client.mithril_stake_distribution().download_verifier_set().await?;
+//! std::fs::write("verifier_set.json", serde_json::to_string(&verifier_set)?)?;
+//!
+//! let certificate = client.certificate().get("CERTIFICATE_HASH").await?.unwrap();
+//! verifier_set.verify_certificate(&certificate)?;
+//! #    Ok(())
+//! # }
+//! ```
+
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+
+use mithril_common::crypto_helper::{ProtocolAggregateVerificationKey, ProtocolMultiSignature};
+use mithril_common::entities::Certificate;
+
+use crate::common::{Epoch, ProtocolParameters};
+use crate::{MithrilCertificate, MithrilResult};
+
+/// Aggregate verification key and protocol parameters used to sign the certificates of an epoch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifierSetEntry {
+    /// Epoch of the certificates signed with the key
+    pub epoch: Epoch,
+
+    /// Aggregate verification key, json hex encoded
+    pub aggregate_verification_key: String,
+
+    /// Protocol parameters of the multi-signatures of the epoch
+    pub protocol_parameters: ProtocolParameters,
+}
+
+/// Aggregate verification keys and protocol parameters per epoch, that can be used to verify
+/// the multi-signature of the certificates of these epochs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VerifierSet {
+    /// Hash of the Mithril stake distribution the verifier set was built from
+    pub mithril_stake_distribution_hash: String,
+
+    /// Hash of the certificate that signed the Mithril stake distribution
+    pub certificate_hash: String,
+
+    /// Aggregate verification keys and protocol parameters, sorted by epoch
+    pub entries: Vec<VerifierSetEntry>,
+}
+
+impl VerifierSet {
+    /// Set the aggregate verification key and the protocol parameters of the given epoch,
+    /// replacing the previous ones if any.
+    pub fn insert(
+        &mut self,
+        epoch: Epoch,
+        aggregate_verification_key: String,
+        protocol_parameters: ProtocolParameters,
+    ) {
+        self.entries.retain(|entry| entry.epoch != epoch);
+        self.entries.push(VerifierSetEntry {
+            epoch,
+            aggregate_verification_key,
+            protocol_parameters,
+        });
+        self.entries.sort_by_key(|entry| entry.epoch);
+    }
+
+    /// Get the entry of the given epoch, if known.
+    pub fn get_entry(&self, epoch: Epoch) -> Option<&VerifierSetEntry> {
+        self.entries.iter().find(|entry| entry.epoch == epoch)
+    }
+
+    /// Get the aggregate verification key of the given epoch, if known.
+    pub fn get_aggregate_verification_key(&self, epoch: Epoch) -> Option<&str> {
+        self.get_entry(epoch)
+            .map(|entry| entry.aggregate_verification_key.as_str())
+    }
+
+    /// Verify that the given certificate is consistent and that its multi-signature was produced
+    /// with the aggregate verification key and the protocol parameters of its epoch.
+    ///
+    /// The hash of the certificate and its signed message are recomputed, and the protocol
+    /// parameters of the certificate metadata are not trusted.
+    ///
+    /// Fails if the epoch of the certificate is not in the verifier set.
+    pub fn verify_certificate(&self, certificate: &MithrilCertificate) -> MithrilResult<()> {
+        let entry = self.get_entry(certificate.epoch).ok_or(anyhow!(
+            "No aggregate verification key known for epoch '{}' of certificate '{}'",
+            certificate.epoch,
+            certificate.hash
+        ))?;
+        Self::verify_certificate_hashes(certificate)?;
+
+        let aggregate_verification_key = entry.aggregate_verification_key.as_str();
+        if aggregate_verification_key != certificate.aggregate_verification_key {
+            return Err(anyhow!(
+                "Certificate '{}' aggregate verification key does not match the one of epoch '{}'",
+                certificate.hash,
+                certificate.epoch
+            ));
+        }
+
+        let aggregate_verification_key =
+            ProtocolAggregateVerificationKey::from_json_hex(aggregate_verification_key)
+                .with_context(|| "Could not decode the aggregate verification key")?;
+        let multi_signature = ProtocolMultiSignature::from_json_hex(&certificate.multi_signature)
+            .with_context(|| {
+            format!(
+                "Could not decode the multi-signature of certificate '{}'",
+                certificate.hash
+            )
+        })?;
+
+        multi_signature
+            .verify(
+                certificate.signed_message.as_bytes(),
+                &aggregate_verification_key,
+                &entry.protocol_parameters.clone().into(),
+            )
+            .map_err(|e| {
+                anyhow!(
+                    "Invalid multi-signature for certificate '{}': {e}",
+                    certificate.hash
+                )
+            })
+    }
+
+    fn verify_certificate_hashes(certificate: &MithrilCertificate) -> MithrilResult<()> {
+        if certificate.signed_message != certificate.protocol_message.compute_hash() {
+            return Err(anyhow!(
+                "Certificate '{}' signed message does not match its protocol message",
+                certificate.hash
+            ));
+        }
+
+        let computed_hash = Certificate::try_from(certificate.clone())
+            .with_context(|| {
+                format!(
+                    "Could not convert the message of certificate '{}'",
+                    certificate.hash
+                )
+            })?
+            .compute_hash();
+        if computed_hash != certificate.hash {
+            return Err(anyhow!(
+                "Certificate '{}' hash does not match its computed hash '{computed_hash}'",
+                certificate.hash
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::crypto_helper::tests_setup::setup_certificate_chain;
+
+    use super::*;
+
+    fn latest_standard_certificate() -> MithrilCertificate {
+        let (chain, _) = setup_certificate_chain(3, 1);
+
+        chain.into_iter().next().unwrap().try_into().unwrap()
+    }
+
+    fn verifier_set_for(certificate: &MithrilCertificate) -> VerifierSet {
+        let mut verifier_set = VerifierSet::default();
+        verifier_set.insert(
+            certificate.epoch,
+            certificate.aggregate_verification_key.clone(),
+            certificate.metadata.protocol_parameters.clone(),
+        );
+
+        verifier_set
+    }
+
+    fn with_recomputed_hash(certificate: MithrilCertificate) -> MithrilCertificate {
+        let hash = Certificate::try_from(certificate.clone())
+            .unwrap()
+            .compute_hash();
+
+        MithrilCertificate {
+            hash,
+            ..certificate
+        }
+    }
+
+    #[test]
+    fn insert_keeps_entries_sorted_and_replace_existing_epoch() {
+        let protocol_parameters = ProtocolParameters::new(5, 100, 0.65);
+        let mut verifier_set = VerifierSet::default();
+        verifier_set.insert(Epoch(3), "avk-3".to_string(), protocol_parameters.clone());
+        verifier_set.insert(Epoch(2), "avk-2".to_string(), protocol_parameters.clone());
+        verifier_set.insert(
+            Epoch(3),
+            "new-avk-3".to_string(),
+            protocol_parameters.clone(),
+        );
+
+        assert_eq!(
+            vec![
+                VerifierSetEntry {
+                    epoch: Epoch(2),
+                    aggregate_verification_key: "avk-2".to_string(),
+                    protocol_parameters: protocol_parameters.clone(),
+                },
+                VerifierSetEntry {
+                    epoch: Epoch(3),
+                    aggregate_verification_key: "new-avk-3".to_string(),
+                    protocol_parameters: protocol_parameters.clone(),
+                },
+            ],
+            verifier_set.entries
+        );
+        assert_eq!(
+            Some("new-avk-3"),
+            verifier_set.get_aggregate_verification_key(Epoch(3))
+        );
+        assert_eq!(None, verifier_set.get_aggregate_verification_key(Epoch(4)));
+    }
+
+    #[test]
+    fn verify_certificate_signed_with_the_key_of_its_epoch() {
+        let certificate = latest_standard_certificate();
+        let verifier_set = verifier_set_for(&certificate);
+
+        verifier_set.verify_certificate(&certificate).unwrap();
+    }
+
+    #[test]
+    fn verify_certificate_fail_if_its_epoch_is_unknown() {
+        let certificate = latest_standard_certificate();
+        let mut verifier_set = VerifierSet::default();
+        verifier_set.insert(
+            certificate.epoch + 1,
+            certificate.aggregate_verification_key.clone(),
+            certificate.metadata.protocol_parameters.clone(),
+        );
+
+        verifier_set
+            .verify_certificate(&certificate)
+            .expect_err("Verifying a certificate of an unknown epoch should fail");
+    }
+
+    #[test]
+    fn verify_certificate_fail_if_the_signed_message_was_tampered() {
+        let certificate = with_recomputed_hash(MithrilCertificate {
+            signed_message: "tampered".to_string(),
+            ..latest_standard_certificate()
+        });
+        let verifier_set = verifier_set_for(&certificate);
+
+        verifier_set
+            .verify_certificate(&certificate)
+            .expect_err("Verifying a tampered certificate should fail");
+    }
+
+    #[test]
+    fn verify_certificate_fail_if_the_protocol_message_does_not_match_the_signed_message() {
+        let mut certificate = latest_standard_certificate();
+        certificate.protocol_message.set_message_part(
+            crate::common::ProtocolMessagePartKey::SnapshotDigest,
+            "tampered-digest".to_string(),
+        );
+        let certificate = with_recomputed_hash(certificate);
+        let verifier_set = verifier_set_for(&certificate);
+
+        verifier_set
+            .verify_certificate(&certificate)
+            .expect_err("Verifying a certificate with a tampered protocol message should fail");
+    }
+
+    #[test]
+    fn verify_certificate_fail_if_its_hash_does_not_match_its_content() {
+        let certificate = MithrilCertificate {
+            hash: "tampered-hash".to_string(),
+            ..latest_standard_certificate()
+        };
+        let verifier_set = verifier_set_for(&certificate);
+
+        verifier_set
+            .verify_certificate(&certificate)
+            .expect_err("Verifying a certificate with a tampered hash should fail");
+    }
+
+    #[test]
+    fn verify_certificate_use_the_protocol_parameters_of_the_verifier_set() {
+        let certificate = latest_standard_certificate();
+        let verifier_set = verifier_set_for(&certificate);
+        let mut tampered_certificate = certificate.clone();
+        tampered_certificate.metadata.protocol_parameters = ProtocolParameters::new(1, 1, 1.0);
+        let tampered_certificate = with_recomputed_hash(tampered_certificate);
+
+        verifier_set
+            .verify_certificate(&tampered_certificate)
+            .unwrap();
+
+        let mut verifier_set_with_other_parameters = VerifierSet::default();
+        verifier_set_with_other_parameters.insert(
+            certificate.epoch,
+            certificate.aggregate_verification_key.clone(),
+            ProtocolParameters::new(1, 1, 1.0),
+        );
+        verifier_set_with_other_parameters
+            .verify_certificate(&certificate)
+            .expect_err("Verifying a certificate with other protocol parameters should fail");
+    }
+
+    #[test]
+    fn verifier_set_can_be_exported_and_reloaded() {
+        let mut verifier_set = VerifierSet {
+            mithril_stake_distribution_hash: "msd-hash".to_string(),
+            certificate_hash: "certificate-hash".to_string(),
+            entries: vec![],
+        };
+        verifier_set.insert(
+            Epoch(5),
+            "avk-5".to_string(),
+            ProtocolParameters::new(5, 100, 0.65),
+        );
+
+        let json = serde_json::to_string(&verifier_set).unwrap();
+        let reloaded: VerifierSet = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(verifier_set, reloaded);
+    }
+}
@@ -91,14 +91,21 @@ impl FakeAggregator {
         ])
         .unwrap();
 
+        let certificate = MithrilCertificate {
+            hash: certificate_hash.to_string(),
+            ..MithrilCertificate::dummy()
+        };
         let message = MessageBuilder::new()
-            .compute_mithril_stake_distribution_message(&mithril_stake_distribution)
+            .compute_mithril_stake_distribution_message_with_certificate(
+                &certificate,
+                &mithril_stake_distribution,
+            )
             .expect("Computing msd message should not fail");
 
         let certificate_json = serde_json::to_string(&MithrilCertificate {
-            hash: certificate_hash.to_string(),
             signed_message: message.compute_hash(),
-            ..MithrilCertificate::dummy()
+            protocol_message: message,
+            ..certificate
         })
         .unwrap();
 
@@ -268,6 +275,7 @@ mod file {
                     .or(routes::statistics::routes(self.calls.clone()));
 
             let snapshot_archive_path = build_fake_zstd_snapshot(immutable_db, work_dir);
+            let snapshot_size = std::fs::metadata(&snapshot_archive_path).unwrap().len();
 
             let routes = routes.or(routes::snapshot::download(
                 self.calls.clone(),
@@ -275,7 +283,7 @@ mod file {
             ));
             let server = test_http_server(routes);
 
-            update_snapshot_location(&server.url(), snapshot_digest, snapshot);
+            update_snapshot_location(&server.url(), snapshot_digest, snapshot_size, snapshot);
 
             server
         }
@@ -309,6 +317,7 @@ mod file {
     fn update_snapshot_location(
         aggregator_url: &str,
         snapshot_digest: &str,
+        snapshot_size: u64,
         snapshot: Arc<RwLock<Snapshot>>,
     ) {
         let snapshot_location =
@@ -316,6 +325,7 @@ mod file {
         let mut snapshot_to_update = snapshot.write().unwrap();
         *snapshot_to_update = Snapshot {
             locations: vec![snapshot_location],
+            size: snapshot_size,
             ..snapshot_to_update.clone()
         };
     }
@@ -13,6 +13,7 @@ use crate::StdResult;
 pub struct APIVersionProvider {
     era_checker: Arc<EraChecker>,
     open_api_versions: HashMap<OpenAPIFileName, Version>,
+    open_api_specs: HashMap<OpenAPIFileName, &'static str>,
 }
 
 impl APIVersionProvider {
@@ -21,6 +22,7 @@ impl APIVersionProvider {
         Self {
             era_checker,
             open_api_versions: get_open_api_versions_mapping(),
+            open_api_specs: get_open_api_specs_mapping(),
         }
     }
 
@@ -41,6 +43,16 @@ impl APIVersionProvider {
         Ok(open_api_version.clone())
     }
 
+    /// Get the current Open API specification, in yaml format
+    pub fn get_current_open_api_spec(&self) -> StdResult<&'static str> {
+        let current_era = self.era_checker.current_era();
+        self.open_api_specs
+            .get(&format!("openapi-{current_era}.yaml"))
+            .or_else(|| self.open_api_specs.get("openapi.yaml"))
+            .copied()
+            .ok_or_else(|| anyhow!("Missing default API specification"))
+    }
+
     /// Compute the current api version requirement
     pub fn compute_current_version_requirement(&self) -> StdResult<VersionReq> {
         let version = &self.compute_current_version()?;
@@ -67,6 +79,14 @@ impl APIVersionProvider {
     ) {
         self.open_api_versions = open_api_versions;
     }
+
+    /// Update open api specifications. Test only
+    pub fn update_open_api_specs(
+        &mut self,
+        open_api_specs: HashMap<OpenAPIFileName, &'static str>,
+    ) {
+        self.open_api_specs = open_api_specs;
+    }
 }
 
 #[cfg(test)]
@@ -120,6 +140,41 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_get_current_open_api_spec_default() {
+        let era_checker = EraChecker::new(SupportedEra::dummy(), Epoch(1));
+        let version_provider = APIVersionProvider::new(Arc::new(era_checker));
+
+        let open_api_spec = version_provider.get_current_open_api_spec().unwrap();
+
+        let open_api: serde_yaml::Value = serde_yaml::from_str(open_api_spec).unwrap();
+        assert_eq!(
+            version_provider
+                .compute_current_version()
+                .unwrap()
+                .to_string(),
+            open_api["info"]["version"].as_str().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_current_open_api_spec_era_specific() {
+        let era_checker = EraChecker::new(SupportedEra::dummy(), Epoch(1));
+        let mut version_provider = APIVersionProvider::new(Arc::new(era_checker));
+        version_provider.update_open_api_specs(HashMap::from([
+            ("openapi.yaml".to_string(), "default spec"),
+            (
+                format!("openapi-{}.yaml", SupportedEra::dummy()),
+                "era specific spec",
+            ),
+        ]));
+
+        assert_eq!(
+            "era specific spec",
+            version_provider.get_current_open_api_spec().unwrap()
+        );
+    }
+
     #[test]
     fn test_compute_current_version_requirement_beta() {
         let era_checker = EraChecker::new(SupportedEra::dummy(), Epoch(1));
@@ -0,0 +1,1247 @@
+//! Canonical CBOR encoding of the [Certificates][Certificate] and of their verification path as
+//! Plutus Data, so they can be referenced by on-chain (i.e. Plutus) scripts.
+//!
+//! The certificates are only encoded with constructors, integers, byte strings, lists and maps,
+//! as `serialiseData` encodes them: the constructors are tagged (`121` to `127` for the indexes up
+//! to 6, `1280` to `1400` for the indexes up to 127), the non empty lists are of indefinite length,
+//! the byte strings longer than 64 bytes are split in chunks of 64 bytes and the integers use their
+//! shortest form.
+//!
+//! The texts are encoded as their UTF-8 bytes, the hex encoded hashes and signatures as their
+//! decoded bytes, the maps are sorted by their encoded keys and the optional values are encoded as
+//! a `Maybe` (`Just` is `Constr 0 [value]`, `Nothing` is `Constr 1 []`):
+//!
+//! ```text
+//! certificate = Constr 0 [
+//!   version: I 1,
+//!   hash: B,
+//!   previous_hash: B,
+//!   epoch: I,
+//!   metadata: Constr 0 [
+//!     network: B,
+//!     immutable_file_number: I,
+//!     protocol_version: B,
+//!     protocol_parameters: Constr 0 [k: I, m: I, phi_f: Constr 0 [numerator: I, denominator: I]],
+//!     initiated_at: I,                        ; nanoseconds since UNIX epoch
+//!     sealed_at: I,                           ; nanoseconds since UNIX epoch
+//!     signers: List [* Constr 0 [party_id: B, stake: I]],
+//!     era: Maybe B,
+//!   ],
+//!   protocol_message: Map [* B => B],
+//!   signed_message: B,
+//!   aggregate_verification_key: Constr 0 [merkle_root: B, nr_leaves: I, total_stake: I],
+//!   signature: Constr 0 [genesis_signature: B]
+//!            / Constr 1 [signed_entity_type, multi_signature: B],
+//! ]
+//!
+//! signed_entity_type = Constr 0 / 1 [epoch: I]
+//!                    / Constr 2 / 3 [network: B, epoch: I, immutable_file_number: I]
+//!
+//! certificates = List [* certificate]
+//! ```
+//!
+//! The `phi_f` protocol parameter is encoded as the irreducible fraction of its shortest decimal
+//! representation.
+
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Utc};
+use mithril_stm::stm::{StmAggrSig, StmAggrVerificationKey};
+use serde_json::json;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::certificate_chain::CertificateRetriever;
+use crate::crypto_helper::{
+    ProtocolAggregateVerificationKey, ProtocolGenesisSignature, ProtocolMultiSignature, D,
+};
+use crate::entities::{
+    CardanoDbBeacon, Certificate, CertificateMetadata, CertificateSignature, Epoch,
+    ProtocolMessage, ProtocolMessagePartKey, ProtocolParameters, SignedEntityType,
+    StakeDistributionParty,
+};
+use crate::era::SupportedEra;
+use crate::StdResult;
+
+/// Version of the CBOR encoding of the certificates, first field of an encoded certificate
+pub const CERTIFICATE_CBOR_ENCODING_VERSION: u64 = 1;
+
+const GENESIS_SIGNATURE_CONSTR: u64 = 0;
+const MULTI_SIGNATURE_CONSTR: u64 = 1;
+
+const MAJOR_TYPE_UNSIGNED: u8 = 0;
+const MAJOR_TYPE_NEGATIVE: u8 = 1;
+const MAJOR_TYPE_BYTES: u8 = 2;
+const MAJOR_TYPE_ARRAY: u8 = 4;
+const MAJOR_TYPE_MAP: u8 = 5;
+const MAJOR_TYPE_TAG: u8 = 6;
+const INDEFINITE_LENGTH: u8 = 31;
+const BREAK: u8 = 0xff;
+
+/// The byte strings longer than this size are encoded in chunks of this size
+const BYTES_CHUNK_SIZE: usize = 64;
+
+/// Maximum nesting of a decoded item
+const MAX_DECODING_DEPTH: usize = 16;
+
+/// Largest integer exactly represented by a float, bound of the terms of the `phi_f` fraction
+const MAX_EXACT_FLOAT_INTEGER: u64 = 1 << f64::MANTISSA_DIGITS;
+
+impl Certificate {
+    /// Encode the certificate with its canonical CBOR encoding
+    pub fn to_cbor_bytes(&self) -> StdResult<Vec<u8>> {
+        Ok(encode_certificate(self)?.to_bytes())
+    }
+
+    /// Decode a certificate from its canonical CBOR encoding
+    pub fn from_cbor_bytes(bytes: &[u8]) -> StdResult<Self> {
+        decode_certificate(PlutusData::from_bytes(bytes)?)
+    }
+}
+
+/// Encode a list of certificates with the canonical CBOR encoding of its certificates
+pub fn certificates_to_cbor_bytes(certificates: &[Certificate]) -> StdResult<Vec<u8>> {
+    let certificates = certificates
+        .iter()
+        .map(encode_certificate)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PlutusData::List(certificates).to_bytes())
+}
+
+/// Decode a list of certificates from the canonical CBOR encoding of its certificates
+pub fn certificates_from_cbor_bytes(bytes: &[u8]) -> StdResult<Vec<Certificate>> {
+    PlutusData::from_bytes(bytes)?
+        .into_list("certificates")?
+        .into_iter()
+        .map(decode_certificate)
+        .collect()
+}
+
+/// Path of certificates to verify to check that a certificate is chained to the genesis
+/// certificate: the certificate itself followed by the certificates it is chained to, following
+/// their previous hash.
+///
+/// A certificate is chained to the first certificate of its epoch, itself chained to the first
+/// certificate of the previous epoch: past the certificate itself, the path only holds one
+/// certificate per epoch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CertificateVerificationPath {
+    /// Certificates of the path, the verified certificate first
+    pub certificates: Vec<Certificate>,
+}
+
+impl CertificateVerificationPath {
+    /// Retrieve the verification path of the certificate with the given hash, with at most
+    /// `max_length` certificates.
+    ///
+    /// If the genesis certificate is not reached within `max_length` certificates, the path is
+    /// not [complete][Self::is_complete]: it goes on with the verification path of the previous
+    /// hash of its last certificate.
+    pub async fn retrieve(
+        certificate_retriever: &dyn CertificateRetriever,
+        certificate_hash: &str,
+        max_length: usize,
+    ) -> StdResult<Self> {
+        let mut certificates = vec![];
+        let mut visited_hashes = HashSet::new();
+        let mut next_hash = certificate_hash.to_string();
+
+        while certificates.len() < max_length {
+            if !visited_hashes.insert(next_hash.clone()) {
+                return Err(anyhow!(
+                    "Certificate chain loop detected at certificate '{next_hash}'"
+                ));
+            }
+            let certificate = certificate_retriever
+                .get_certificate_details(&next_hash)
+                .await
+                .with_context(|| format!("Could not retrieve certificate '{next_hash}'"))?;
+            let is_last = certificate.is_genesis() || certificate.is_chaining_to_itself();
+            next_hash = certificate.previous_hash.clone();
+            certificates.push(certificate);
+
+            if is_last {
+                break;
+            }
+        }
+
+        Ok(Self { certificates })
+    }
+
+    /// Check if the path ends with the genesis certificate
+    pub fn is_complete(&self) -> bool {
+        self.certificates.last().is_some_and(|certificate| {
+            certificate.is_genesis() || certificate.is_chaining_to_itself()
+        })
+    }
+
+    /// Encode the verification path with the canonical CBOR encoding of its certificates
+    pub fn to_cbor_bytes(&self) -> StdResult<Vec<u8>> {
+        certificates_to_cbor_bytes(&self.certificates)
+    }
+
+    /// Decode a verification path from the canonical CBOR encoding of its certificates
+    pub fn from_cbor_bytes(bytes: &[u8]) -> StdResult<Self> {
+        Ok(Self {
+            certificates: certificates_from_cbor_bytes(bytes)?,
+        })
+    }
+}
+
+/// Plutus Data, the values handled by the on-chain scripts
+#[derive(Debug, Clone, PartialEq)]
+enum PlutusData {
+    Constr(u64, Vec<PlutusData>),
+    Map(Vec<(PlutusData, PlutusData)>),
+    List(Vec<PlutusData>),
+    Integer(i128),
+    Bytes(Vec<u8>),
+}
+
+impl PlutusData {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.encode(&mut bytes);
+
+        bytes
+    }
+
+    /// Decode the given bytes, which must be the canonical encoding of their value
+    fn from_bytes(bytes: &[u8]) -> StdResult<Self> {
+        let mut decoder = PlutusDataDecoder { bytes, position: 0 };
+        let value = decoder.decode(0).with_context(|| "CBOR decoding failed")?;
+        if decoder.position != bytes.len() {
+            return Err(anyhow!(
+                "CBOR decoding failed: {} trailing bytes",
+                bytes.len() - decoder.position
+            ));
+        }
+        if value.to_bytes() != bytes {
+            return Err(anyhow!(
+                "CBOR decoding failed: the encoding is not canonical"
+            ));
+        }
+
+        Ok(value)
+    }
+
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::Constr(index @ 0..=6, fields) => {
+                write_head(bytes, MAJOR_TYPE_TAG, 121 + index);
+                encode_list(bytes, fields);
+            }
+            Self::Constr(index @ 7..=127, fields) => {
+                write_head(bytes, MAJOR_TYPE_TAG, 1280 + index - 7);
+                encode_list(bytes, fields);
+            }
+            Self::Constr(index, fields) => {
+                write_head(bytes, MAJOR_TYPE_TAG, 102);
+                write_head(bytes, MAJOR_TYPE_ARRAY, 2);
+                write_head(bytes, MAJOR_TYPE_UNSIGNED, *index);
+                encode_list(bytes, fields);
+            }
+            Self::Map(entries) => {
+                write_head(bytes, MAJOR_TYPE_MAP, entries.len() as u64);
+                for (key, value) in entries {
+                    key.encode(bytes);
+                    value.encode(bytes);
+                }
+            }
+            Self::List(items) => encode_list(bytes, items),
+            Self::Integer(integer) => encode_integer(bytes, *integer),
+            Self::Bytes(value) => encode_bytes(bytes, value),
+        }
+    }
+
+    fn into_constr(self, name: &str) -> StdResult<(u64, Vec<PlutusData>)> {
+        match self {
+            Self::Constr(index, fields) => Ok((index, fields)),
+            _ => Err(anyhow!("Plutus Data '{name}' is not a constructor")),
+        }
+    }
+
+    /// Fields of the constructor, which must have the given index and number of fields
+    fn into_constr_fields(
+        self,
+        name: &'static str,
+        expected_index: u64,
+        expected_length: usize,
+    ) -> StdResult<ConstrFields> {
+        match self.into_constr(name)? {
+            (index, fields) if index == expected_index => {
+                ConstrFields::new(name, fields, expected_length)
+            }
+            (index, _) => Err(anyhow!(
+                "Plutus Data '{name}' is the constructor {index}, expected {expected_index}"
+            )),
+        }
+    }
+
+    fn into_list(self, name: &str) -> StdResult<Vec<PlutusData>> {
+        match self {
+            Self::List(items) => Ok(items),
+            _ => Err(anyhow!("Plutus Data '{name}' is not a list")),
+        }
+    }
+
+    fn into_map(self, name: &str) -> StdResult<Vec<(PlutusData, PlutusData)>> {
+        match self {
+            Self::Map(entries) => Ok(entries),
+            _ => Err(anyhow!("Plutus Data '{name}' is not a map")),
+        }
+    }
+
+    fn into_integer(self, name: &str) -> StdResult<i128> {
+        match self {
+            Self::Integer(integer) => Ok(integer),
+            _ => Err(anyhow!("Plutus Data '{name}' is not an integer")),
+        }
+    }
+
+    fn into_u64(self, name: &str) -> StdResult<u64> {
+        let integer = self.into_integer(name)?;
+        u64::try_from(integer).with_context(|| format!("Plutus Data '{name}' is not an uint"))
+    }
+
+    fn into_i64(self, name: &str) -> StdResult<i64> {
+        let integer = self.into_integer(name)?;
+        i64::try_from(integer).with_context(|| format!("Plutus Data '{name}' is not an int"))
+    }
+
+    fn into_bytes(self, name: &str) -> StdResult<Vec<u8>> {
+        match self {
+            Self::Bytes(value) => Ok(value),
+            _ => Err(anyhow!("Plutus Data '{name}' is not a byte string")),
+        }
+    }
+
+    fn into_text(self, name: &str) -> StdResult<String> {
+        String::from_utf8(self.into_bytes(name)?)
+            .with_context(|| format!("Plutus Data '{name}' is not an UTF-8 text"))
+    }
+}
+
+fn integer<T: Into<i128>>(value: T) -> PlutusData {
+    PlutusData::Integer(value.into())
+}
+
+fn text(value: &str) -> PlutusData {
+    PlutusData::Bytes(value.as_bytes().to_vec())
+}
+
+fn maybe(value: Option<PlutusData>) -> PlutusData {
+    match value {
+        Some(value) => PlutusData::Constr(0, vec![value]),
+        None => PlutusData::Constr(1, vec![]),
+    }
+}
+
+fn write_head(bytes: &mut Vec<u8>, major_type: u8, argument: u64) {
+    let major_type = major_type << 5;
+    match argument {
+        0..=23 => bytes.push(major_type | argument as u8),
+        24..=0xff => bytes.extend([major_type | 24, argument as u8]),
+        0x100..=0xffff => {
+            bytes.push(major_type | 25);
+            bytes.extend((argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            bytes.push(major_type | 26);
+            bytes.extend((argument as u32).to_be_bytes());
+        }
+        _ => {
+            bytes.push(major_type | 27);
+            bytes.extend(argument.to_be_bytes());
+        }
+    }
+}
+
+fn encode_list(bytes: &mut Vec<u8>, items: &[PlutusData]) {
+    if items.is_empty() {
+        write_head(bytes, MAJOR_TYPE_ARRAY, 0);
+        return;
+    }
+
+    bytes.push((MAJOR_TYPE_ARRAY << 5) | INDEFINITE_LENGTH);
+    for item in items {
+        item.encode(bytes);
+    }
+    bytes.push(BREAK);
+}
+
+fn encode_bytes(bytes: &mut Vec<u8>, value: &[u8]) {
+    if value.len() <= BYTES_CHUNK_SIZE {
+        write_head(bytes, MAJOR_TYPE_BYTES, value.len() as u64);
+        bytes.extend_from_slice(value);
+        return;
+    }
+
+    bytes.push((MAJOR_TYPE_BYTES << 5) | INDEFINITE_LENGTH);
+    for chunk in value.chunks(BYTES_CHUNK_SIZE) {
+        write_head(bytes, MAJOR_TYPE_BYTES, chunk.len() as u64);
+        bytes.extend_from_slice(chunk);
+    }
+    bytes.push(BREAK);
+}
+
+fn encode_integer(bytes: &mut Vec<u8>, integer: i128) {
+    if let Ok(value) = u64::try_from(integer) {
+        write_head(bytes, MAJOR_TYPE_UNSIGNED, value);
+    } else if let Ok(value) = u64::try_from(-1 - integer) {
+        write_head(bytes, MAJOR_TYPE_NEGATIVE, value);
+    } else {
+        // Bignum: tag 2 for the positive integers, tag 3 for the negative ones
+        let (tag, magnitude) = if integer >= 0 {
+            (2, integer as u128)
+        } else {
+            (3, (-1 - integer) as u128)
+        };
+        let magnitude = magnitude.to_be_bytes();
+        let first_significant_byte = magnitude.iter().position(|byte| *byte != 0).unwrap_or(0);
+        write_head(bytes, MAJOR_TYPE_TAG, tag);
+        encode_bytes(bytes, &magnitude[first_significant_byte..]);
+    }
+}
+
+struct PlutusDataDecoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> PlutusDataDecoder<'a> {
+    fn read(&mut self, length: usize) -> StdResult<&'a [u8]> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| anyhow!("Unexpected end of input"))?;
+        let read_bytes = &self.bytes[self.position..end];
+        self.position = end;
+
+        Ok(read_bytes)
+    }
+
+    fn read_break(&mut self) -> bool {
+        let is_break = self.bytes.get(self.position) == Some(&BREAK);
+        if is_break {
+            self.position += 1;
+        }
+
+        is_break
+    }
+
+    /// Read the major type and the argument of an item, `None` for an indefinite length
+    fn read_head(&mut self) -> StdResult<(u8, Option<u64>)> {
+        let initial_byte = self.read(1)?[0];
+        let additional_information = initial_byte & 0x1f;
+        let argument = match additional_information {
+            0..=23 => Some(additional_information as u64),
+            24 => Some(self.read(1)?[0] as u64),
+            25 => Some(u16::from_be_bytes(self.read(2)?.try_into()?) as u64),
+            26 => Some(u32::from_be_bytes(self.read(4)?.try_into()?) as u64),
+            27 => Some(u64::from_be_bytes(self.read(8)?.try_into()?)),
+            INDEFINITE_LENGTH => None,
+            _ => {
+                return Err(anyhow!(
+                    "Invalid additional information: {additional_information}"
+                ))
+            }
+        };
+
+        Ok((initial_byte >> 5, argument))
+    }
+
+    fn decode(&mut self, depth: usize) -> StdResult<PlutusData> {
+        if depth > MAX_DECODING_DEPTH {
+            return Err(anyhow!("Items nested deeper than {MAX_DECODING_DEPTH}"));
+        }
+
+        match self.read_head()? {
+            (MAJOR_TYPE_UNSIGNED, Some(value)) => Ok(integer(value)),
+            (MAJOR_TYPE_NEGATIVE, Some(value)) => Ok(PlutusData::Integer(-1 - i128::from(value))),
+            (MAJOR_TYPE_BYTES, length) => Ok(PlutusData::Bytes(self.decode_bytes(length)?)),
+            (MAJOR_TYPE_ARRAY, length) => Ok(PlutusData::List(self.decode_list(length, depth)?)),
+            (MAJOR_TYPE_MAP, Some(length)) => {
+                let mut entries = vec![];
+                for _ in 0..length {
+                    entries.push((self.decode(depth + 1)?, self.decode(depth + 1)?));
+                }
+                Ok(PlutusData::Map(entries))
+            }
+            (MAJOR_TYPE_TAG, Some(tag @ 121..=127)) => {
+                Ok(PlutusData::Constr(tag - 121, self.decode_fields(depth)?))
+            }
+            (MAJOR_TYPE_TAG, Some(tag @ 1280..=1400)) => Ok(PlutusData::Constr(
+                tag - 1280 + 7,
+                self.decode_fields(depth)?,
+            )),
+            (MAJOR_TYPE_TAG, Some(102)) => {
+                let (MAJOR_TYPE_ARRAY, Some(2)) = self.read_head()? else {
+                    return Err(anyhow!("Invalid constructor of tag 102"));
+                };
+                let index = self.decode(depth + 1)?.into_u64("constructor index")?;
+                Ok(PlutusData::Constr(index, self.decode_fields(depth)?))
+            }
+            (MAJOR_TYPE_TAG, Some(tag @ (2 | 3))) => {
+                let (MAJOR_TYPE_BYTES, length) = self.read_head()? else {
+                    return Err(anyhow!("Invalid bignum of tag {tag}"));
+                };
+                let magnitude = self.decode_bytes(length)?;
+                if magnitude.len() > 16 {
+                    return Err(anyhow!("Bignum of {} bytes is too large", magnitude.len()));
+                }
+                let mut buffer = [0u8; 16];
+                buffer[16 - magnitude.len()..].copy_from_slice(&magnitude);
+                let magnitude = i128::try_from(u128::from_be_bytes(buffer))
+                    .with_context(|| "Bignum is too large")?;
+                Ok(PlutusData::Integer(if tag == 2 {
+                    magnitude
+                } else {
+                    -1 - magnitude
+                }))
+            }
+            (major_type, _) => Err(anyhow!("Unsupported item of major type {major_type}")),
+        }
+    }
+
+    fn decode_bytes(&mut self, length: Option<u64>) -> StdResult<Vec<u8>> {
+        match length {
+            Some(length) => Ok(self.read(usize::try_from(length)?)?.to_vec()),
+            None => {
+                let mut value = vec![];
+                while !self.read_break() {
+                    let (MAJOR_TYPE_BYTES, Some(length)) = self.read_head()? else {
+                        return Err(anyhow!("Invalid chunk of byte string"));
+                    };
+                    value.extend_from_slice(self.read(usize::try_from(length)?)?);
+                }
+                Ok(value)
+            }
+        }
+    }
+
+    fn decode_list(&mut self, length: Option<u64>, depth: usize) -> StdResult<Vec<PlutusData>> {
+        let mut items = vec![];
+        match length {
+            Some(length) => {
+                for _ in 0..length {
+                    items.push(self.decode(depth + 1)?);
+                }
+            }
+            None => {
+                while !self.read_break() {
+                    items.push(self.decode(depth + 1)?);
+                }
+            }
+        }
+
+        Ok(items)
+    }
+
+    fn decode_fields(&mut self, depth: usize) -> StdResult<Vec<PlutusData>> {
+        match self.read_head()? {
+            (MAJOR_TYPE_ARRAY, length) => self.decode_list(length, depth),
+            _ => Err(anyhow!("The fields of a constructor must be a list")),
+        }
+    }
+}
+
+/// Fields of a decoded constructor, read in order
+struct ConstrFields {
+    name: &'static str,
+    fields: std::vec::IntoIter<PlutusData>,
+}
+
+impl ConstrFields {
+    fn new(name: &'static str, fields: Vec<PlutusData>, expected_length: usize) -> StdResult<Self> {
+        if fields.len() != expected_length {
+            return Err(anyhow!(
+                "Plutus Data '{name}' has {} fields, expected {expected_length}",
+                fields.len()
+            ));
+        }
+
+        Ok(Self {
+            name,
+            fields: fields.into_iter(),
+        })
+    }
+
+    fn next(&mut self, field: &str) -> StdResult<PlutusData> {
+        self.fields
+            .next()
+            .ok_or_else(|| anyhow!("Plutus Data '{}' has no field '{field}'", self.name))
+    }
+
+    fn next_u64(&mut self, field: &str) -> StdResult<u64> {
+        self.next(field)?.into_u64(field)
+    }
+
+    fn next_i64(&mut self, field: &str) -> StdResult<i64> {
+        self.next(field)?.into_i64(field)
+    }
+
+    fn next_bytes(&mut self, field: &str) -> StdResult<Vec<u8>> {
+        self.next(field)?.into_bytes(field)
+    }
+
+    fn next_text(&mut self, field: &str) -> StdResult<String> {
+        self.next(field)?.into_text(field)
+    }
+
+    fn next_hex(&mut self, field: &str) -> StdResult<String> {
+        Ok(hex::encode(self.next_bytes(field)?))
+    }
+}
+
+fn hex_to_bytes(hex_value: &str, field: &str) -> StdResult<PlutusData> {
+    let bytes = hex::decode(hex_value)
+        .with_context(|| format!("Certificate field '{field}' is not hex encoded"))?;
+
+    Ok(PlutusData::Bytes(bytes))
+}
+
+fn encode_certificate(certificate: &Certificate) -> StdResult<PlutusData> {
+    Ok(PlutusData::Constr(
+        0,
+        vec![
+            integer(CERTIFICATE_CBOR_ENCODING_VERSION),
+            hex_to_bytes(&certificate.hash, "hash")?,
+            hex_to_bytes(&certificate.previous_hash, "previous_hash")?,
+            integer(*certificate.epoch),
+            encode_metadata(&certificate.metadata)?,
+            encode_protocol_message(&certificate.protocol_message),
+            hex_to_bytes(&certificate.signed_message, "signed_message")?,
+            encode_aggregate_verification_key(&certificate.aggregate_verification_key),
+            encode_signature(&certificate.signature),
+        ],
+    ))
+}
+
+fn encode_timestamp(date: &DateTime<Utc>) -> StdResult<PlutusData> {
+    date.timestamp_nanos_opt()
+        .map(integer)
+        .ok_or_else(|| anyhow!("Date '{date}' can not be encoded in nanoseconds"))
+}
+
+fn encode_metadata(metadata: &CertificateMetadata) -> StdResult<PlutusData> {
+    #[allow(deprecated)]
+    let immutable_file_number = metadata.immutable_file_number;
+
+    Ok(PlutusData::Constr(
+        0,
+        vec![
+            text(&metadata.network),
+            integer(immutable_file_number),
+            text(&metadata.protocol_version),
+            encode_protocol_parameters(&metadata.protocol_parameters)?,
+            encode_timestamp(&metadata.initiated_at)?,
+            encode_timestamp(&metadata.sealed_at)?,
+            PlutusData::List(
+                metadata
+                    .signers
+                    .iter()
+                    .map(|party| {
+                        PlutusData::Constr(0, vec![text(&party.party_id), integer(party.stake)])
+                    })
+                    .collect(),
+            ),
+            maybe(metadata.era.map(|era| text(&era.to_string()))),
+        ],
+    ))
+}
+
+fn encode_protocol_parameters(protocol_parameters: &ProtocolParameters) -> StdResult<PlutusData> {
+    Ok(PlutusData::Constr(
+        0,
+        vec![
+            integer(protocol_parameters.k),
+            integer(protocol_parameters.m),
+            encode_fraction(protocol_parameters.phi_f, "phi_f")?,
+        ],
+    ))
+}
+
+fn greatest_common_divisor(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        greatest_common_divisor(b, a % b)
+    }
+}
+
+/// Encode a float as the irreducible fraction of its shortest decimal representation
+fn encode_fraction(value: f64, field: &str) -> StdResult<PlutusData> {
+    if !value.is_finite() || value.is_sign_negative() {
+        return Err(anyhow!(
+            "Field '{field}' must be a finite positive number, got '{value}'"
+        ));
+    }
+    let decimal = value.to_string();
+    let (integer_part, fractional_part) = decimal.split_once('.').unwrap_or((&decimal, ""));
+    let numerator = format!("{integer_part}{fractional_part}")
+        .parse::<u64>()
+        .ok();
+    let denominator = u32::try_from(fractional_part.len())
+        .ok()
+        .and_then(|exponent| 10u64.checked_pow(exponent));
+    let (Some(numerator), Some(denominator)) = (numerator, denominator) else {
+        return Err(anyhow!(
+            "Field '{field}' can not be encoded as a fraction, got '{value}'"
+        ));
+    };
+    let divisor = greatest_common_divisor(numerator, denominator);
+    let (numerator, denominator) = (numerator / divisor, denominator / divisor);
+    if numerator > MAX_EXACT_FLOAT_INTEGER || denominator > MAX_EXACT_FLOAT_INTEGER {
+        return Err(anyhow!(
+            "Field '{field}' has too many significant digits to be encoded as a fraction, got '{value}'"
+        ));
+    }
+
+    Ok(PlutusData::Constr(
+        0,
+        vec![integer(numerator), integer(denominator)],
+    ))
+}
+
+/// Decode a fraction as the float nearest to its value
+fn decode_fraction(value: PlutusData, field: &'static str) -> StdResult<f64> {
+    let mut fraction = value.into_constr_fields(field, 0, 2)?;
+    let numerator = fraction.next_u64("numerator")?;
+    let denominator = fraction.next_u64("denominator")?;
+    if denominator == 0
+        || numerator > MAX_EXACT_FLOAT_INTEGER
+        || denominator > MAX_EXACT_FLOAT_INTEGER
+    {
+        return Err(anyhow!(
+            "Field '{field}' is not a supported fraction: {numerator}/{denominator}"
+        ));
+    }
+
+    // Both terms are exactly represented by floats and the division is correctly rounded
+    Ok(numerator as f64 / denominator as f64)
+}
+
+fn encode_protocol_message(protocol_message: &ProtocolMessage) -> PlutusData {
+    let mut parts: Vec<(PlutusData, PlutusData)> = protocol_message
+        .message_parts
+        .iter()
+        .map(|(key, value)| (text(&key.to_string()), text(value)))
+        .collect();
+    parts.sort_by_cached_key(|(key, _)| key.to_bytes());
+
+    PlutusData::Map(parts)
+}
+
+fn encode_aggregate_verification_key(
+    aggregate_verification_key: &ProtocolAggregateVerificationKey,
+) -> PlutusData {
+    PlutusData::Constr(
+        0,
+        vec![
+            PlutusData::Bytes(aggregate_verification_key.get_merkle_root().to_vec()),
+            integer(aggregate_verification_key.get_nr_leaves() as u64),
+            integer(aggregate_verification_key.get_total_stake()),
+        ],
+    )
+}
+
+fn encode_signature(signature: &CertificateSignature) -> PlutusData {
+    match signature {
+        CertificateSignature::GenesisSignature(signature) => PlutusData::Constr(
+            GENESIS_SIGNATURE_CONSTR,
+            vec![PlutusData::Bytes(signature.to_bytes().to_vec())],
+        ),
+        CertificateSignature::MultiSignature(signed_entity_type, signature) => PlutusData::Constr(
+            MULTI_SIGNATURE_CONSTR,
+            vec![
+                encode_signed_entity_type(signed_entity_type),
+                PlutusData::Bytes(signature.to_bytes()),
+            ],
+        ),
+    }
+}
+
+fn encode_signed_entity_type(signed_entity_type: &SignedEntityType) -> PlutusData {
+    let index = signed_entity_type.index() as u64;
+    match signed_entity_type {
+        SignedEntityType::MithrilStakeDistribution(epoch)
+        | SignedEntityType::CardanoStakeDistribution(epoch) => {
+            PlutusData::Constr(index, vec![integer(**epoch)])
+        }
+        SignedEntityType::CardanoImmutableFilesFull(beacon)
+        | SignedEntityType::CardanoTransactions(beacon) => PlutusData::Constr(
+            index,
+            vec![
+                text(&beacon.network),
+                integer(*beacon.epoch),
+                integer(beacon.immutable_file_number),
+            ],
+        ),
+    }
+}
+
+fn decode_certificate(value: PlutusData) -> StdResult<Certificate> {
+    let mut fields = value.into_constr_fields("certificate", 0, 9)?;
+    let version = fields.next_u64("version")?;
+    if version != CERTIFICATE_CBOR_ENCODING_VERSION {
+        return Err(anyhow!(
+            "Unsupported certificate CBOR encoding version: {version}"
+        ));
+    }
+
+    Ok(Certificate {
+        hash: fields.next_hex("hash")?,
+        previous_hash: fields.next_hex("previous_hash")?,
+        epoch: Epoch(fields.next_u64("epoch")?),
+        metadata: decode_metadata(fields.next("metadata")?)?,
+        protocol_message: decode_protocol_message(fields.next("protocol_message")?)?,
+        signed_message: fields.next_hex("signed_message")?,
+        aggregate_verification_key: decode_aggregate_verification_key(
+            fields.next("aggregate_verification_key")?,
+        )?,
+        signature: decode_signature(fields.next("signature")?)?,
+    })
+}
+
+fn decode_metadata(value: PlutusData) -> StdResult<CertificateMetadata> {
+    let mut fields = value.into_constr_fields("metadata", 0, 8)?;
+    let network = fields.next_text("network")?;
+    let immutable_file_number = fields.next_u64("immutable_file_number")?;
+    let protocol_version = fields.next_text("protocol_version")?;
+    let mut parameters =
+        fields
+            .next("protocol_parameters")?
+            .into_constr_fields("protocol_parameters", 0, 3)?;
+    let protocol_parameters = ProtocolParameters {
+        k: parameters.next_u64("k")?,
+        m: parameters.next_u64("m")?,
+        phi_f: decode_fraction(parameters.next("phi_f")?, "phi_f")?,
+    };
+    let initiated_at = DateTime::from_timestamp_nanos(fields.next_i64("initiated_at")?);
+    let sealed_at = DateTime::from_timestamp_nanos(fields.next_i64("sealed_at")?);
+    let signers = fields
+        .next("signers")?
+        .into_list("signers")?
+        .into_iter()
+        .map(|party| {
+            let mut party = party.into_constr_fields("signer", 0, 2)?;
+            Ok(StakeDistributionParty {
+                party_id: party.next_text("party_id")?,
+                stake: party.next_u64("stake")?,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let era = match fields.next("era")?.into_constr("era")? {
+        (0, era) => {
+            let era = ConstrFields::new("era", era, 1)?.next_text("era")?;
+            Some(SupportedEra::from_str(&era).with_context(|| format!("Unsupported era '{era}'"))?)
+        }
+        (1, nothing) if nothing.is_empty() => None,
+        _ => return Err(anyhow!("Field 'era' of 'metadata' is not a Maybe")),
+    };
+
+    #[allow(deprecated)]
+    let metadata = CertificateMetadata {
+        network,
+        immutable_file_number,
+        protocol_version,
+        protocol_parameters,
+        initiated_at,
+        sealed_at,
+        signers,
+        era,
+    };
+
+    Ok(metadata)
+}
+
+fn decode_protocol_message(value: PlutusData) -> StdResult<ProtocolMessage> {
+    let mut protocol_message = ProtocolMessage::new();
+    for (key, value) in value.into_map("protocol_message")? {
+        let key = key.into_text("protocol message part key")?;
+        let value = value.into_text("protocol message part value")?;
+        let key: ProtocolMessagePartKey = serde_json::from_value(json!(key))
+            .with_context(|| format!("Unknown protocol message part '{key}'"))?;
+        protocol_message.set_message_part(key, value);
+    }
+
+    Ok(protocol_message)
+}
+
+fn decode_aggregate_verification_key(
+    value: PlutusData,
+) -> StdResult<ProtocolAggregateVerificationKey> {
+    let mut fields = value.into_constr_fields("aggregate_verification_key", 0, 3)?;
+    let merkle_root = fields.next_bytes("merkle_root")?;
+    let nr_leaves = usize::try_from(fields.next_u64("nr_leaves")?)?;
+    let total_stake = fields.next_u64("total_stake")?;
+
+    Ok(ProtocolAggregateVerificationKey::new(
+        StmAggrVerificationKey::<D>::from_parts(merkle_root, nr_leaves, total_stake),
+    ))
+}
+
+fn decode_signature(value: PlutusData) -> StdResult<CertificateSignature> {
+    match value.into_constr("signature")? {
+        (GENESIS_SIGNATURE_CONSTR, fields) => {
+            let mut fields = ConstrFields::new("signature", fields, 1)?;
+            let signature = ProtocolGenesisSignature::from_bytes(&fields.next_bytes("signature")?)?;
+            Ok(CertificateSignature::GenesisSignature(signature))
+        }
+        (MULTI_SIGNATURE_CONSTR, fields) => {
+            let mut fields = ConstrFields::new("signature", fields, 2)?;
+            let signed_entity_type = decode_signed_entity_type(fields.next("signed_entity_type")?)?;
+            let signature = StmAggrSig::<D>::from_bytes(&fields.next_bytes("signature")?)
+                .map_err(|e| anyhow!(e))
+                .with_context(|| "Invalid multi-signature")?;
+            Ok(CertificateSignature::MultiSignature(
+                signed_entity_type,
+                ProtocolMultiSignature::new(signature),
+            ))
+        }
+        (index, _) => Err(anyhow!(
+            "Unsupported certificate signature constructor: {index}"
+        )),
+    }
+}
+
+fn decode_signed_entity_type(value: PlutusData) -> StdResult<SignedEntityType> {
+    let beacon = |fields: Vec<PlutusData>| -> StdResult<CardanoDbBeacon> {
+        let mut fields = ConstrFields::new("signed_entity_type", fields, 3)?;
+        Ok(CardanoDbBeacon::new(
+            fields.next_text("network")?,
+            fields.next_u64("epoch")?,
+            fields.next_u64("immutable_file_number")?,
+        ))
+    };
+    let epoch = |fields: Vec<PlutusData>| -> StdResult<Epoch> {
+        Ok(Epoch(
+            ConstrFields::new("signed_entity_type", fields, 1)?.next_u64("epoch")?,
+        ))
+    };
+
+    let signed_entity_type = match value.into_constr("signed_entity_type")? {
+        (0, fields) => SignedEntityType::MithrilStakeDistribution(epoch(fields)?),
+        (1, fields) => SignedEntityType::CardanoStakeDistribution(epoch(fields)?),
+        (2, fields) => SignedEntityType::CardanoImmutableFilesFull(beacon(fields)?),
+        (3, fields) => SignedEntityType::CardanoTransactions(beacon(fields)?),
+        (index, _) => return Err(anyhow!("Unsupported signed entity type: {index}")),
+    };
+
+    Ok(signed_entity_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    use crate::certificate_chain::CertificateRetrieverError;
+    use crate::test_utils::{fake_data, fake_keys};
+
+    use super::*;
+
+    fn golden_certificate() -> Certificate {
+        let date = DateTime::parse_from_rfc3339("2024-02-12T13:11:47.123456789Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message.set_message_part(
+            ProtocolMessagePartKey::NextAggregateVerificationKey,
+            "next-avk".to_string(),
+        );
+        protocol_message
+            .set_message_part(ProtocolMessagePartKey::SnapshotDigest, "digest".to_string());
+        #[allow(deprecated)]
+        let metadata = CertificateMetadata {
+            network: "testnet".to_string(),
+            immutable_file_number: 100,
+            protocol_version: "0.1.0".to_string(),
+            protocol_parameters: ProtocolParameters::new(1000, 100, 0.65),
+            initiated_at: date,
+            sealed_at: date,
+            signers: vec![StakeDistributionParty {
+                party_id: "pool1".to_string(),
+                stake: 1_000_000,
+            }],
+            era: Some(SupportedEra::Pythagoras),
+        };
+
+        Certificate {
+            hash: "aa".repeat(32),
+            previous_hash: String::new(),
+            epoch: Epoch(10),
+            metadata,
+            protocol_message,
+            signed_message: "bb".repeat(32),
+            aggregate_verification_key: fake_keys::aggregate_verification_key()[0]
+                .try_into()
+                .unwrap(),
+            signature: CertificateSignature::GenesisSignature(
+                fake_keys::genesis_signature()[0].try_into().unwrap(),
+            ),
+        }
+    }
+
+    fn read_golden_file(name: &str) -> Vec<u8> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("golden")
+            .join("cbor")
+            .join(name);
+        let hex_bytes = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("golden file '{}' could not be read: {e}", path.display()));
+
+        hex::decode(hex_bytes.trim()).unwrap()
+    }
+
+    fn assert_same_certificate(expected: &Certificate, certificate: &Certificate) {
+        assert_eq!(format!("{expected:#?}"), format!("{certificate:#?}"));
+    }
+
+    #[test]
+    fn certificate_encoding_is_frozen() {
+        let golden_bytes = read_golden_file("certificate_genesis_v1.hex");
+
+        assert_eq!(
+            hex::encode(&golden_bytes),
+            hex::encode(golden_certificate().to_cbor_bytes().unwrap())
+        );
+        assert_same_certificate(
+            &golden_certificate(),
+            &Certificate::from_cbor_bytes(&golden_bytes).unwrap(),
+        );
+    }
+
+    #[test]
+    fn signed_entity_type_encoding_is_frozen() {
+        assert_eq!(
+            "d87a9f05ff",
+            hex::encode(
+                encode_signed_entity_type(&SignedEntityType::CardanoStakeDistribution(Epoch(5)))
+                    .to_bytes()
+            )
+        );
+        assert_eq!(
+            "d87c9f476d61696e6e65740a1864ff",
+            hex::encode(
+                encode_signed_entity_type(&SignedEntityType::CardanoTransactions(
+                    CardanoDbBeacon::new("mainnet", 10, 100)
+                ))
+                .to_bytes()
+            )
+        );
+    }
+
+    #[test]
+    fn plutus_data_encoding_follows_serialise_data() {
+        let cases = [
+            (PlutusData::Constr(0, vec![]), "d87980".to_string()),
+            (
+                PlutusData::Constr(7, vec![integer(1)]),
+                "d905009f01ff".to_string(),
+            ),
+            (PlutusData::Constr(128, vec![]), "d86682188080".to_string()),
+            (integer(-1), "20".to_string()),
+            (integer(u64::MAX), "1bffffffffffffffff".to_string()),
+            (
+                integer(i128::from(u64::MAX) + 1),
+                "c249010000000000000000".to_string(),
+            ),
+            (
+                integer(-i128::from(u64::MAX) - 2),
+                "c349010000000000000000".to_string(),
+            ),
+            (
+                PlutusData::Bytes(vec![0xab; 65]),
+                format!("5f5840{}41abff", "ab".repeat(64)),
+            ),
+        ];
+
+        for (value, expected_hex) in cases {
+            let bytes = value.to_bytes();
+            assert_eq!(expected_hex, hex::encode(&bytes), "value: {value:?}");
+            assert_eq!(value, PlutusData::from_bytes(&bytes).unwrap());
+        }
+    }
+
+    #[test]
+    fn plutus_data_decoding_rejects_a_non_canonical_encoding() {
+        for non_canonical_hex in [
+            // Definite length non empty list
+            "d879810a",
+            // Integer not in its shortest form
+            "d8799f1800ff",
+            // Constructor 0 with the general form
+            "d866820080",
+            // Trailing bytes
+            "d8798000",
+        ] {
+            PlutusData::from_bytes(&hex::decode(non_canonical_hex).unwrap())
+                .expect_err(&format!("'{non_canonical_hex}' should be rejected"));
+        }
+    }
+
+    #[test]
+    fn phi_f_is_encoded_as_an_irreducible_fraction() {
+        for (phi_f, expected_numerator, expected_denominator) in [
+            (0.65, 13, 20),
+            (1.0, 1, 1),
+            (0.0, 0, 1),
+            (0.1234567, 1234567, 10000000),
+        ] {
+            let fraction = encode_fraction(phi_f, "phi_f").unwrap();
+
+            assert_eq!(
+                PlutusData::Constr(
+                    0,
+                    vec![integer(expected_numerator), integer(expected_denominator)]
+                ),
+                fraction
+            );
+            assert_eq!(phi_f, decode_fraction(fraction, "phi_f").unwrap());
+        }
+    }
+
+    #[test]
+    fn phi_f_encoding_fails_if_it_is_not_a_finite_positive_number() {
+        for phi_f in [-0.5, f64::NAN, f64::INFINITY] {
+            encode_fraction(phi_f, "phi_f").expect_err(&format!("'{phi_f}' should be rejected"));
+        }
+    }
+
+    #[test]
+    fn multi_signed_certificate_survives_an_encoding_round_trip() {
+        let certificate = Certificate {
+            hash: "cc".repeat(32),
+            previous_hash: "aa".repeat(32),
+            signed_message: "dd".repeat(32),
+            ..fake_data::certificate("whatever".to_string())
+        };
+
+        let decoded = Certificate::from_cbor_bytes(&certificate.to_cbor_bytes().unwrap()).unwrap();
+
+        assert_same_certificate(&certificate, &decoded);
+    }
+
+    #[test]
+    fn encoding_fails_if_a_hash_is_not_hex_encoded() {
+        let certificate = fake_data::certificate("not-hex".to_string());
+
+        certificate
+            .to_cbor_bytes()
+            .expect_err("Encoding should fail with a non hex hash");
+    }
+
+    #[test]
+    fn decoding_fails_with_an_unsupported_version() {
+        let mut bytes = golden_certificate().to_cbor_bytes().unwrap();
+        // The version is the first field of the certificate constructor (tag and list headers)
+        bytes[3] = 0x02;
+
+        Certificate::from_cbor_bytes(&bytes)
+            .expect_err("Decoding should fail with an unsupported version");
+    }
+
+    struct TestCertificateRetriever {
+        certificates: HashMap<String, Certificate>,
+    }
+
+    #[async_trait]
+    impl CertificateRetriever for TestCertificateRetriever {
+        async fn get_certificate_details(
+            &self,
+            certificate_hash: &str,
+        ) -> Result<Certificate, CertificateRetrieverError> {
+            self.certificates
+                .get(certificate_hash)
+                .cloned()
+                .ok_or_else(|| CertificateRetrieverError(anyhow!("not found")))
+        }
+    }
+
+    #[tokio::test]
+    async fn verification_path_follows_the_previous_hashes_up_to_the_genesis() {
+        let genesis = golden_certificate();
+        let certificate = |hash: &str, previous_hash: &str| Certificate {
+            hash: hash.to_string(),
+            previous_hash: previous_hash.to_string(),
+            signed_message: "dd".repeat(32),
+            ..fake_data::certificate("whatever".to_string())
+        };
+        let first_of_epoch = certificate(&"02".repeat(32), &genesis.hash);
+        let latest = certificate(&"03".repeat(32), &first_of_epoch.hash);
+        let retriever = TestCertificateRetriever {
+            certificates: HashMap::from_iter(
+                [genesis.clone(), first_of_epoch.clone(), latest.clone()]
+                    .into_iter()
+                    .map(|c| (c.hash.clone(), c)),
+            ),
+        };
+
+        let path = CertificateVerificationPath::retrieve(&retriever, &latest.hash, 10)
+            .await
+            .unwrap();
+        assert_eq!(vec![latest, first_of_epoch, genesis], path.certificates);
+        assert!(path.is_complete());
+
+        let decoded =
+            CertificateVerificationPath::from_cbor_bytes(&path.to_cbor_bytes().unwrap()).unwrap();
+        assert_eq!(path, decoded);
+    }
+
+    #[tokio::test]
+    async fn verification_path_fails_on_a_chain_loop() {
+        let certificate = |hash: &str, previous_hash: &str| Certificate {
+            hash: hash.to_string(),
+            previous_hash: previous_hash.to_string(),
+            ..fake_data::certificate("whatever".to_string())
+        };
+        let retriever = TestCertificateRetriever {
+            certificates: HashMap::from([
+                ("01".to_string(), certificate("01", "02")),
+                ("02".to_string(), certificate("02", "01")),
+            ]),
+        };
+
+        CertificateVerificationPath::retrieve(&retriever, "01", 10)
+            .await
+            .expect_err("A chain loop should be detected");
+    }
+
+    #[tokio::test]
+    async fn verification_path_is_capped_to_the_given_length() {
+        let genesis = golden_certificate();
+        let certificate = |hash: &str, previous_hash: &str| Certificate {
+            hash: hash.to_string(),
+            previous_hash: previous_hash.to_string(),
+            ..fake_data::certificate("whatever".to_string())
+        };
+        let first_of_epoch = certificate(&"02".repeat(32), &genesis.hash);
+        let latest = certificate(&"03".repeat(32), &first_of_epoch.hash);
+        let retriever = TestCertificateRetriever {
+            certificates: HashMap::from_iter(
+                [genesis.clone(), first_of_epoch.clone(), latest.clone()]
+                    .into_iter()
+                    .map(|c| (c.hash.clone(), c)),
+            ),
+        };
+
+        let path = CertificateVerificationPath::retrieve(&retriever, &latest.hash, 2)
+            .await
+            .unwrap();
+        assert_eq!(vec![latest, first_of_epoch.clone()], path.certificates);
+        assert!(!path.is_complete());
+
+        let remaining_path =
+            CertificateVerificationPath::retrieve(&retriever, &first_of_epoch.previous_hash, 2)
+                .await
+                .unwrap();
+        assert_eq!(vec![genesis], remaining_path.certificates);
+        assert!(remaining_path.is_complete());
+    }
+}
@@ -2,17 +2,18 @@
 //!
 use std::sync::Arc;
 
+use anyhow::{anyhow, Context};
 use chrono::prelude::*;
 use thiserror::Error;
 
 use crate::{
     crypto_helper::{
         ProtocolAggregateVerificationKey, ProtocolGenesisSignature, ProtocolGenesisSigner,
-        PROTOCOL_VERSION,
+        ProtocolGenesisVerificationKey, PROTOCOL_VERSION,
     },
     entities::{
         Certificate, CertificateMetadata, CertificateSignature, Epoch, ImmutableFileNumber,
-        ProtocolMessage, ProtocolMessagePartKey, ProtocolParameters,
+        ProtocolMessage, ProtocolMessagePart, ProtocolMessagePartKey, ProtocolParameters,
     },
     era_deprecate, StdResult,
 };
@@ -96,4 +97,84 @@ impl CertificateGenesisProducer {
             CertificateSignature::GenesisSignature(genesis_signature),
         ))
     }
+
+    /// Create the protocol message of a genesis key rotation
+    ///
+    /// The next aggregate verification key of the certificate chain is certified along with the
+    /// genesis verification key that replaces the current one, so that the chain can continue
+    /// after the rotation.
+    pub fn create_genesis_key_rotation_protocol_message(
+        next_aggregate_verification_key: &str,
+        next_genesis_verification_key: &ProtocolGenesisVerificationKey,
+    ) -> StdResult<ProtocolMessage> {
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message.set_message_part(
+            ProtocolMessagePartKey::NextAggregateVerificationKey,
+            next_aggregate_verification_key.to_owned(),
+        );
+        protocol_message.set_part(ProtocolMessagePart::NextGenesisVerificationKey(
+            next_genesis_verification_key.to_json_hex()?,
+        ))?;
+
+        Ok(protocol_message)
+    }
+
+    /// Create a genesis key rotation certificate chained to the latest certificate of the chain
+    ///
+    /// The rotation protocol message is cross-signed: it is first signed with the previous
+    /// genesis key, this signature is added to the message which is then signed with the next
+    /// genesis key.
+    pub fn create_genesis_key_rotation_certificate(
+        latest_certificate: &Certificate,
+        previous_genesis_signer: &ProtocolGenesisSigner,
+        next_genesis_signer: &ProtocolGenesisSigner,
+    ) -> StdResult<Certificate> {
+        let next_aggregate_verification_key = latest_certificate
+            .protocol_message
+            .next_aggregate_verification_key()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Latest certificate '{}' has no next aggregate verification key",
+                    latest_certificate.hash
+                )
+            })?;
+        let mut protocol_message = Self::create_genesis_key_rotation_protocol_message(
+            next_aggregate_verification_key,
+            &next_genesis_signer
+                .create_genesis_verifier()
+                .to_verification_key(),
+        )
+        .with_context(|| "Could not create the genesis key rotation protocol message")?;
+        let previous_genesis_key_signature =
+            previous_genesis_signer.sign(protocol_message.compute_hash().as_bytes());
+        protocol_message.set_part(ProtocolMessagePart::PreviousGenesisKeySignature(
+            previous_genesis_key_signature.to_bytes_hex(),
+        ))?;
+        let genesis_signature =
+            next_genesis_signer.sign(protocol_message.compute_hash().as_bytes());
+
+        let initiated_at = Utc::now();
+        #[allow(deprecated)]
+        let metadata = CertificateMetadata {
+            era: latest_certificate.metadata.era,
+            ..CertificateMetadata::new(
+                latest_certificate.metadata.network.clone(),
+                latest_certificate.metadata.immutable_file_number,
+                PROTOCOL_VERSION.to_string(),
+                latest_certificate.metadata.protocol_parameters.clone(),
+                initiated_at,
+                Utc::now(),
+                vec![],
+            )
+        };
+
+        Ok(Certificate::new(
+            latest_certificate.hash.clone(),
+            latest_certificate.epoch,
+            metadata,
+            protocol_message,
+            latest_certificate.aggregate_verification_key.clone(),
+            CertificateSignature::GenesisSignature(genesis_signature),
+        ))
+    }
 }
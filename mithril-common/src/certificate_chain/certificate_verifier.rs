@@ -9,12 +9,15 @@ use thiserror::Error;
 
 use super::CertificateRetriever;
 use crate::crypto_helper::{
-    ProtocolAggregateVerificationKey, ProtocolGenesisError, ProtocolGenesisVerificationKey,
-    ProtocolMultiSignature,
+    ProtocolAggregateVerificationKey, ProtocolGenesisError, ProtocolGenesisSignature,
+    ProtocolGenesisVerificationKey, ProtocolMultiSignature,
 };
 use crate::entities::{
-    Certificate, CertificateSignature, ProtocolMessage, ProtocolMessagePartKey, ProtocolParameters,
+    Certificate, CertificateSignature, ProtocolFeature, ProtocolMessage, ProtocolMessagePartKey,
+    ProtocolParameters, SignedCertificateMetadata,
 };
+use crate::era::SupportedEra;
+use crate::protocol::EraSignatureSchemes;
 use crate::StdResult;
 
 #[cfg(test)]
@@ -55,6 +58,26 @@ pub enum CertificateVerifierError {
     /// certificate that's not a genesis certificate.
     #[error("can't validate genesis certificate: given certificate isn't a genesis certificate")]
     InvalidGenesisCertificateProvided,
+
+    /// Error raised when the protocol message of a [Certificate] isn't the signed message of the
+    /// certificate.
+    #[error("certificate protocol message unmatch error")]
+    CertificateProtocolMessageUnmatch,
+
+    /// Error raised when the metadata signed with the protocol message of a [Certificate] don't
+    /// match the metadata of the certificate.
+    #[error("certificate signed metadata unmatch error")]
+    CertificateSignedMetadataUnmatch,
+
+    /// Error raised when the protocol message of a [Certificate] produced in an era where the
+    /// signed certificate metadata are mandatory doesn't carry them.
+    #[error("certificate missing signed metadata error")]
+    MissingSignedMetadata,
+
+    /// Error raised when a genesis key rotation [Certificate] is verified with the replaced
+    /// genesis verification key but isn't signed with this key.
+    #[error("genesis key rotation certificate missing previous genesis key signature error")]
+    MissingPreviousGenesisKeySignature,
 }
 
 /// CertificateVerifier is the cryptographic engine in charge of verifying multi signatures and
@@ -87,12 +110,33 @@ pub trait CertificateVerifier: Send + Sync {
         certificate: Certificate,
         genesis_verification_key: &ProtocolGenesisVerificationKey,
     ) -> StdResult<()> {
+        self.verify_certificate_chain_to_checkpoints(certificate, genesis_verification_key, &[])
+            .await
+    }
+
+    /// Verify that the Certificate Chain associated to a Certificate is valid, the traversal of
+    /// the chain stops at the first certificate whose hash is one of the given trusted checkpoints
+    /// (i.e. a certificate whose own chain was already verified) or at the genesis certificate.
+    async fn verify_certificate_chain_to_checkpoints(
+        &self,
+        certificate: Certificate,
+        genesis_verification_key: &ProtocolGenesisVerificationKey,
+        trusted_checkpoints: &[String],
+    ) -> StdResult<()> {
+        let is_trusted_checkpoint = |certificate: &Certificate| {
+            trusted_checkpoints.contains(&certificate.hash)
+                && certificate.hash == certificate.compute_hash()
+        };
+
         let mut certificate = certificate;
-        while let Some(previous_certificate) = self
-            .verify_certificate(&certificate, genesis_verification_key)
-            .await?
-        {
-            certificate = previous_certificate;
+        while !is_trusted_checkpoint(&certificate) {
+            match self
+                .verify_certificate(&certificate, genesis_verification_key)
+                .await?
+            {
+                Some(previous_certificate) => certificate = previous_certificate,
+                None => break,
+            }
         }
 
         Ok(())
@@ -115,6 +159,7 @@ pub struct MithrilCertificateVerifier {
     /// The logger where the logs should be written
     logger: Logger,
     certificate_retriever: Arc<dyn CertificateRetriever>,
+    signature_schemes: EraSignatureSchemes,
 }
 
 impl MithrilCertificateVerifier {
@@ -124,12 +169,20 @@ impl MithrilCertificateVerifier {
         Self {
             logger,
             certificate_retriever,
+            signature_schemes: EraSignatureSchemes::default(),
         }
     }
 
-    /// Verify a multi signature
+    /// Set the signature schemes used by each era
+    pub fn with_signature_schemes(mut self, signature_schemes: EraSignatureSchemes) -> Self {
+        self.signature_schemes = signature_schemes;
+        self
+    }
+
+    /// Verify a multi signature with the signature scheme of the given era
     fn verify_multi_signature(
         &self,
+        era: SupportedEra,
         message: &[u8],
         multi_signature: &ProtocolMultiSignature,
         aggregate_verification_key: &ProtocolAggregateVerificationKey,
@@ -137,31 +190,135 @@ impl MithrilCertificateVerifier {
     ) -> Result<(), CertificateVerifierError> {
         debug!(
             self.logger,
-            "Verify multi signature for {:?}",
+            "Verify multi signature for {:?} with the signature scheme of era '{era}'",
             message.encode_hex::<String>()
         );
 
-        multi_signature
+        self.signature_schemes
+            .get(era)
+            .and_then(|signature_scheme| {
+                signature_scheme.verify_multi_signature(
+                    message,
+                    multi_signature,
+                    aggregate_verification_key,
+                    protocol_parameters,
+                )
+            })
+            .map_err(|e| CertificateVerifierError::VerifyMultiSignature(e.to_string()))
+    }
+
+    /// Verify that the protocol message is the signed message of the certificate and that the
+    /// metadata signed with it are the metadata of the certificate.
+    ///
+    /// The signed metadata are mandatory in the eras where the
+    /// [signed certificate metadata][ProtocolFeature::SignedCertificateMetadata] feature is
+    /// mandatory, the certificates of the previous eras produced before the activation of the
+    /// feature are still valid without them.
+    fn verify_signed_metadata(&self, certificate: &Certificate) -> StdResult<()> {
+        if certificate.protocol_message.compute_hash() != certificate.signed_message {
+            return Err(anyhow!(
+                CertificateVerifierError::CertificateProtocolMessageUnmatch
+            ));
+        }
+
+        let Some(signed_metadata) = certificate
+            .protocol_message
+            .get_signed_certificate_metadata()
+            .with_context(|| "Can not read the signed metadata of the certificate")?
+        else {
+            return match certificate.metadata.era {
+                Some(era)
+                    if ProtocolFeature::SignedCertificateMetadata.is_mandatory_in_era(era) =>
+                {
+                    Err(anyhow!(CertificateVerifierError::MissingSignedMetadata))
+                }
+                _ => Ok(()),
+            };
+        };
+
+        if signed_metadata != SignedCertificateMetadata::from(&certificate.metadata) {
+            debug!(
+                self.logger,
+                "Signed metadata {:?} do not match the certificate metadata", signed_metadata
+            );
+            return Err(anyhow!(
+                CertificateVerifierError::CertificateSignedMetadataUnmatch
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Verify a genesis key rotation certificate
+    ///
+    /// The certificate must be signed with the genesis key that it certifies and, if the given
+    /// genesis verification key is the replaced one, cross-signed with the replaced key.
+    fn verify_genesis_key_rotation_certificate(
+        &self,
+        certificate: &Certificate,
+        genesis_signature: &ProtocolGenesisSignature,
+        genesis_verification_key: &ProtocolGenesisVerificationKey,
+    ) -> StdResult<()> {
+        if certificate.protocol_message.compute_hash() != certificate.signed_message {
+            return Err(anyhow!(
+                CertificateVerifierError::CertificateProtocolMessageUnmatch
+            ));
+        }
+
+        let next_genesis_verification_key = ProtocolGenesisVerificationKey::from_json_hex(
+            certificate
+                .protocol_message
+                .next_genesis_verification_key()
+                .unwrap_or_default(),
+        )
+        .with_context(|| "Can not decode the next genesis verification key of the certificate")?;
+        next_genesis_verification_key
+            .verify(certificate.signed_message.as_bytes(), genesis_signature)
+            .with_context(|| {
+                "Certificate verifier failed verifying a genesis key rotation certificate with the next genesis key"
+            })?;
+        if next_genesis_verification_key == *genesis_verification_key {
+            return Ok(());
+        }
+
+        let previous_genesis_key_signature = ProtocolGenesisSignature::from_bytes_hex(
+            certificate
+                .protocol_message
+                .previous_genesis_key_signature()
+                .ok_or(CertificateVerifierError::MissingPreviousGenesisKeySignature)?,
+        )
+        .with_context(|| "Can not decode the previous genesis key signature of the certificate")?;
+        let mut rotation_message = certificate.protocol_message.clone();
+        rotation_message
+            .message_parts
+            .remove(&ProtocolMessagePartKey::PreviousGenesisKeySignature);
+        genesis_verification_key
             .verify(
-                message,
-                aggregate_verification_key,
-                &protocol_parameters.to_owned().into(),
+                rotation_message.compute_hash().as_bytes(),
+                &previous_genesis_key_signature,
             )
-            .map_err(|e| CertificateVerifierError::VerifyMultiSignature(e.to_string()))
+            .with_context(|| {
+                "Certificate verifier failed verifying a genesis key rotation certificate with the previous genesis key"
+            })?;
+
+        Ok(())
     }
 
-    /// Verify Standard certificate
     async fn verify_standard_certificate(
         &self,
         certificate: &Certificate,
         signature: &ProtocolMultiSignature,
     ) -> StdResult<Option<Certificate>> {
+        // The certificates issued before their era was recorded were all issued in the Thales
+        // era.
         self.verify_multi_signature(
+            certificate.metadata.era.unwrap_or(SupportedEra::Thales),
             certificate.signed_message.as_bytes(),
             signature,
             &certificate.aggregate_verification_key,
             &certificate.metadata.protocol_parameters,
         )?;
+        self.verify_signed_metadata(certificate)?;
         let previous_certificate = self
             .certificate_retriever
             .get_certificate_details(&certificate.previous_hash)
@@ -207,7 +364,7 @@ impl MithrilCertificateVerifier {
 
         match previous_certificate
             .protocol_message
-            .get_message_part(&ProtocolMessagePartKey::NextAggregateVerificationKey)
+            .next_aggregate_verification_key()
         {
             Some(next_aggregate_verification_key)
                 if valid_certificate_has_different_epoch_as_previous(
@@ -247,6 +404,14 @@ impl CertificateVerifier for MithrilCertificateVerifier {
             _ => Err(CertificateVerifierError::InvalidGenesisCertificateProvided),
         }?;
 
+        if genesis_certificate.is_genesis_key_rotation() {
+            return self.verify_genesis_key_rotation_certificate(
+                genesis_certificate,
+                genesis_signature,
+                genesis_verification_key,
+            );
+        }
+
         genesis_verification_key
             .verify(
                 genesis_certificate.signed_message.as_bytes(),
@@ -302,14 +467,17 @@ impl CertificateVerifier for MithrilCertificateVerifier {
 mod tests {
     use async_trait::async_trait;
     use mockall::mock;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
     use slog_scope;
 
     use super::CertificateRetriever;
     use super::*;
 
-    use crate::certificate_chain::CertificateRetrieverError;
-    use crate::crypto_helper::{tests_setup::*, ProtocolClerk};
-    use crate::test_utils::MithrilFixtureBuilder;
+    use crate::certificate_chain::{CertificateGenesisProducer, CertificateRetrieverError};
+    use crate::crypto_helper::{tests_setup::*, ProtocolClerk, ProtocolGenesisSigner};
+    use crate::entities::ProtocolMessagePart;
+    use crate::test_utils::{fake_data, MithrilFixtureBuilder};
 
     mock! {
         pub CertificateRetrieverImpl { }
@@ -355,6 +523,7 @@ mod tests {
         assert!(
             verifier
                 .verify_multi_signature(
+                    SupportedEra::dummy(),
                     &message_tampered,
                     &multi_signature,
                     &aggregate_verification_key,
@@ -365,6 +534,7 @@ mod tests {
         );
         verifier
             .verify_multi_signature(
+                SupportedEra::dummy(),
                 &message_hash,
                 &multi_signature,
                 &aggregate_verification_key,
@@ -419,6 +589,32 @@ mod tests {
         verify.expect("unexpected error");
     }
 
+    #[tokio::test]
+    async fn test_verify_certificate_ko_without_signature_scheme_for_the_certificate_era() {
+        let (fake_certificates, genesis_verifier) = setup_certificate_chain(5, 1);
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(MockCertificateRetrieverImpl::new()),
+        )
+        .with_signature_schemes(EraSignatureSchemes::empty());
+
+        let error = verifier
+            .verify_certificate(
+                &fake_certificates[0],
+                &genesis_verifier.to_verification_key(),
+            )
+            .await
+            .expect_err("verify_certificate should fail without a signature scheme");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<CertificateVerifierError>(),
+                Some(CertificateVerifierError::VerifyMultiSignature(_))
+            ),
+            "unexpected error: {error:?}"
+        );
+    }
+
     #[tokio::test]
     async fn test_verify_certificate_ko_certificate_chain_previous_hash_unmatch() {
         let total_certificates = 5;
@@ -522,6 +718,151 @@ mod tests {
         );
     }
 
+    fn certificate_with_signed_metadata(signed_metadata: SignedCertificateMetadata) -> Certificate {
+        let mut certificate = fake_data::certificate("certificate-hash".to_string());
+        certificate
+            .protocol_message
+            .set_part(ProtocolMessagePart::SignedCertificateMetadata(
+                signed_metadata,
+            ))
+            .unwrap();
+        certificate.signed_message = certificate.protocol_message.compute_hash();
+
+        certificate
+    }
+
+    fn certificate_without_signed_metadata(era: Option<SupportedEra>) -> Certificate {
+        let mut certificate = fake_data::certificate("certificate-hash".to_string());
+        certificate.metadata.era = era;
+        certificate.signed_message = certificate.protocol_message.compute_hash();
+
+        certificate
+    }
+
+    #[test]
+    fn test_verify_signed_metadata_ok_without_signed_metadata_before_it_is_mandatory() {
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(MockCertificateRetrieverImpl::new()),
+        );
+
+        for era in [None, Some(SupportedEra::Thales)] {
+            verifier
+                .verify_signed_metadata(&certificate_without_signed_metadata(era))
+                .expect("a certificate without signed metadata should be valid");
+        }
+    }
+
+    #[test]
+    fn test_verify_signed_metadata_ko_without_signed_metadata_once_it_is_mandatory() {
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(MockCertificateRetrieverImpl::new()),
+        );
+
+        let error = verifier
+            .verify_signed_metadata(&certificate_without_signed_metadata(Some(
+                SupportedEra::Pythagoras,
+            )))
+            .expect_err("a certificate without mandatory signed metadata should not be valid");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<CertificateVerifierError>(),
+                Some(CertificateVerifierError::MissingSignedMetadata)
+            ),
+            "unexpected error type: {error:?}"
+        );
+    }
+
+    #[test]
+    fn test_verify_signed_metadata_ko_without_signed_metadata_if_not_signed() {
+        let mut certificate = certificate_without_signed_metadata(None);
+        certificate.signed_message = "another-signed-message".to_string();
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(MockCertificateRetrieverImpl::new()),
+        );
+
+        let error = verifier
+            .verify_signed_metadata(&certificate)
+            .expect_err("a protocol message that is not the signed message should not be valid");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<CertificateVerifierError>(),
+                Some(CertificateVerifierError::CertificateProtocolMessageUnmatch)
+            ),
+            "unexpected error type: {error:?}"
+        );
+    }
+
+    #[test]
+    fn test_verify_signed_metadata_ok_with_metadata_of_the_certificate() {
+        let certificate = fake_data::certificate("certificate-hash".to_string());
+        let certificate = certificate_with_signed_metadata(SignedCertificateMetadata::from(
+            &certificate.metadata,
+        ));
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(MockCertificateRetrieverImpl::new()),
+        );
+
+        verifier
+            .verify_signed_metadata(&certificate)
+            .expect("signed metadata matching the certificate metadata should be valid");
+    }
+
+    #[test]
+    fn test_verify_signed_metadata_ko_with_altered_metadata() {
+        let certificate = fake_data::certificate("certificate-hash".to_string());
+        let mut certificate = certificate_with_signed_metadata(SignedCertificateMetadata::from(
+            &certificate.metadata,
+        ));
+        certificate.metadata.initiated_at += chrono::Duration::seconds(1);
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(MockCertificateRetrieverImpl::new()),
+        );
+
+        let error = verifier
+            .verify_signed_metadata(&certificate)
+            .expect_err("altered metadata should not be valid");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<CertificateVerifierError>(),
+                Some(CertificateVerifierError::CertificateSignedMetadataUnmatch)
+            ),
+            "unexpected error type: {error:?}"
+        );
+    }
+
+    #[test]
+    fn test_verify_signed_metadata_ko_with_protocol_message_not_signed() {
+        let certificate = fake_data::certificate("certificate-hash".to_string());
+        let mut certificate = certificate_with_signed_metadata(SignedCertificateMetadata::from(
+            &certificate.metadata,
+        ));
+        certificate.signed_message = "another-signed-message".to_string();
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(MockCertificateRetrieverImpl::new()),
+        );
+
+        let error = verifier
+            .verify_signed_metadata(&certificate)
+            .expect_err("a protocol message that is not the signed message should not be valid");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<CertificateVerifierError>(),
+                Some(CertificateVerifierError::CertificateProtocolMessageUnmatch)
+            ),
+            "unexpected error type: {error:?}"
+        );
+    }
+
     #[tokio::test]
     async fn test_verify_certificate_chain_ok() {
         let total_certificates = 15;
@@ -549,6 +890,37 @@ mod tests {
         verify.expect("unexpected error");
     }
 
+    #[tokio::test]
+    async fn test_verify_certificate_chain_stops_at_trusted_checkpoint() {
+        let total_certificates = 15;
+        let certificates_per_epoch = 2;
+        let (fake_certificates, genesis_verifier) =
+            setup_certificate_chain(total_certificates, certificates_per_epoch);
+        let index_checkpoint = 4;
+        let trusted_checkpoints = vec![fake_certificates[index_checkpoint].hash.clone()];
+        let mut mock_certificate_retriever = MockCertificateRetrieverImpl::new();
+        let certificate_to_verify = fake_certificates[0].clone();
+        for fake_certificate in fake_certificates.into_iter().skip(1).take(index_checkpoint) {
+            mock_certificate_retriever
+                .expect_get_certificate_details()
+                .returning(move |_| Ok(fake_certificate.clone()))
+                .times(1);
+        }
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(mock_certificate_retriever),
+        );
+
+        verifier
+            .verify_certificate_chain_to_checkpoints(
+                certificate_to_verify,
+                &genesis_verifier.to_verification_key(),
+                &trusted_checkpoints,
+            )
+            .await
+            .expect("unexpected error");
+    }
+
     #[tokio::test]
     async fn test_verify_certificate_chain_ko() {
         let total_certificates = 15;
@@ -592,4 +964,122 @@ mod tests {
             "unexpected error type: {error:?}"
         );
     }
+
+    fn setup_genesis_key_rotation() -> (
+        Certificate,
+        ProtocolGenesisVerificationKey,
+        ProtocolGenesisVerificationKey,
+    ) {
+        let (certificates, previous_genesis_verifier) = setup_certificate_chain(5, 2);
+        let previous_genesis_signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let next_genesis_signer =
+            ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed([1u8; 32]));
+        let rotation_certificate =
+            CertificateGenesisProducer::create_genesis_key_rotation_certificate(
+                &certificates[0],
+                &previous_genesis_signer,
+                &next_genesis_signer,
+            )
+            .unwrap();
+
+        (
+            rotation_certificate,
+            previous_genesis_verifier.to_verification_key(),
+            next_genesis_signer
+                .create_genesis_verifier()
+                .to_verification_key(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_verify_genesis_key_rotation_certificate_ok_with_next_genesis_key() {
+        let (rotation_certificate, _, next_genesis_verification_key) = setup_genesis_key_rotation();
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(MockCertificateRetrieverImpl::new()),
+        );
+
+        let previous_certificate = verifier
+            .verify_certificate(&rotation_certificate, &next_genesis_verification_key)
+            .await
+            .unwrap();
+
+        assert!(rotation_certificate.is_genesis_key_rotation());
+        assert_eq!(None, previous_certificate);
+    }
+
+    #[tokio::test]
+    async fn test_verify_genesis_key_rotation_certificate_ok_with_previous_genesis_key() {
+        let (rotation_certificate, previous_genesis_verification_key, _) =
+            setup_genesis_key_rotation();
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(MockCertificateRetrieverImpl::new()),
+        );
+
+        let previous_certificate = verifier
+            .verify_certificate(&rotation_certificate, &previous_genesis_verification_key)
+            .await
+            .unwrap();
+
+        assert_eq!(None, previous_certificate);
+    }
+
+    #[tokio::test]
+    async fn test_verify_genesis_key_rotation_certificate_ko_with_another_genesis_key() {
+        let (rotation_certificate, _, _) = setup_genesis_key_rotation();
+        let another_genesis_verification_key =
+            ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed([2u8; 32]))
+                .create_genesis_verifier()
+                .to_verification_key();
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(MockCertificateRetrieverImpl::new()),
+        );
+
+        verifier
+            .verify_certificate(&rotation_certificate, &another_genesis_verification_key)
+            .await
+            .expect_err("a rotation not signed with the given genesis key should not be valid");
+    }
+
+    #[tokio::test]
+    async fn test_verify_genesis_key_rotation_certificate_ko_without_previous_genesis_key_signature(
+    ) {
+        let (rotation_certificate, previous_genesis_verification_key, _) =
+            setup_genesis_key_rotation();
+        let mut protocol_message = rotation_certificate.protocol_message.clone();
+        protocol_message
+            .message_parts
+            .remove(&ProtocolMessagePartKey::PreviousGenesisKeySignature);
+        let next_genesis_signer =
+            ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed([1u8; 32]));
+        let rotation_certificate = Certificate::new(
+            rotation_certificate.previous_hash,
+            rotation_certificate.epoch,
+            rotation_certificate.metadata,
+            protocol_message.clone(),
+            rotation_certificate.aggregate_verification_key,
+            CertificateSignature::GenesisSignature(
+                next_genesis_signer.sign(protocol_message.compute_hash().as_bytes()),
+            ),
+        );
+        let verifier = MithrilCertificateVerifier::new(
+            slog_scope::logger(),
+            Arc::new(MockCertificateRetrieverImpl::new()),
+        );
+
+        let error = verifier
+            .verify_certificate(&rotation_certificate, &previous_genesis_verification_key)
+            .await
+            .expect_err("a rotation not signed with the previous genesis key should not be valid");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<CertificateVerifierError>(),
+                Some(CertificateVerifierError::MissingPreviousGenesisKeySignature)
+            ),
+            "unexpected error type: {error:?}"
+        );
+    }
 }
@@ -1,9 +1,14 @@
 //! Tools to retrieve, validate the Certificate Chain created by an aggregator
 
+mod certificate_cbor;
 mod certificate_genesis;
 mod certificate_retriever;
 mod certificate_verifier;
 
+pub use certificate_cbor::{
+    certificates_from_cbor_bytes, certificates_to_cbor_bytes, CertificateVerificationPath,
+    CERTIFICATE_CBOR_ENCODING_VERSION,
+};
 pub use certificate_genesis::CertificateGenesisProducer;
 pub use certificate_retriever::{CertificateRetriever, CertificateRetrieverError};
 pub use certificate_verifier::{
@@ -0,0 +1,302 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use crate::chain_observer::interface::*;
+use crate::chain_observer::{ChainAddress, TxDatum};
+use crate::crypto_helper::{KESPeriod, OpCert};
+use crate::entities::*;
+
+/// Value kept in the cache of a [CachedChainObserver] with the instant it was cached.
+struct CachedValue<T> {
+    value: T,
+    cached_at: Instant,
+}
+
+impl<T: Clone> CachedValue<T> {
+    fn new(value: T) -> Self {
+        Self {
+            value,
+            cached_at: Instant::now(),
+        }
+    }
+
+    /// Return the cached value if it is not older than the given time to live.
+    fn get_fresh(&self, time_to_live: Duration) -> Option<T> {
+        (self.cached_at.elapsed() < time_to_live).then(|| self.value.clone())
+    }
+}
+
+#[derive(Default)]
+struct ChainObserverCache {
+    current_epoch: Option<CachedValue<Option<Epoch>>>,
+    current_chain_point: Option<CachedValue<Option<ChainPoint>>>,
+    stake_distribution: Option<(Epoch, StakeDistribution)>,
+}
+
+/// A [ChainObserver] decorator that caches the results of the wrapped observer.
+///
+/// * the current epoch and chain point are cached for a configurable time to live,
+/// * the stake distribution is cached for the current epoch,
+/// * all the cached values are invalidated when a change of epoch is observed.
+///
+/// The datums and the KES periods are always retrieved from the wrapped observer.
+pub struct CachedChainObserver {
+    observer: Arc<dyn ChainObserver>,
+    epoch_time_to_live: Duration,
+    chain_point_time_to_live: Duration,
+    cache: RwLock<ChainObserverCache>,
+}
+
+impl CachedChainObserver {
+    /// Default time to live of the cached current epoch
+    pub const DEFAULT_EPOCH_TIME_TO_LIVE: Duration = Duration::from_secs(10);
+
+    /// Default time to live of the cached current chain point
+    pub const DEFAULT_CHAIN_POINT_TIME_TO_LIVE: Duration = Duration::from_secs(2);
+
+    /// CachedChainObserver factory
+    pub fn new(observer: Arc<dyn ChainObserver>) -> Self {
+        Self {
+            observer,
+            epoch_time_to_live: Self::DEFAULT_EPOCH_TIME_TO_LIVE,
+            chain_point_time_to_live: Self::DEFAULT_CHAIN_POINT_TIME_TO_LIVE,
+            cache: RwLock::new(ChainObserverCache::default()),
+        }
+    }
+
+    /// Set the time to live of the cached current epoch.
+    ///
+    /// The stake distribution cached for an epoch may be served up to this duration after the
+    /// start of the next epoch.
+    pub fn with_epoch_time_to_live(mut self, time_to_live: Duration) -> Self {
+        self.epoch_time_to_live = time_to_live;
+        self
+    }
+
+    /// Set the time to live of the cached current chain point.
+    pub fn with_chain_point_time_to_live(mut self, time_to_live: Duration) -> Self {
+        self.chain_point_time_to_live = time_to_live;
+        self
+    }
+
+    /// Remove all the cached values.
+    pub async fn invalidate(&self) {
+        *self.cache.write().await = ChainObserverCache::default();
+    }
+}
+
+#[async_trait]
+impl ChainObserver for CachedChainObserver {
+    async fn get_current_datums(
+        &self,
+        address: &ChainAddress,
+    ) -> Result<Vec<TxDatum>, ChainObserverError> {
+        self.observer.get_current_datums(address).await
+    }
+
+    async fn get_current_epoch(&self) -> Result<Option<Epoch>, ChainObserverError> {
+        if let Some(epoch) = self
+            .cache
+            .read()
+            .await
+            .current_epoch
+            .as_ref()
+            .and_then(|cached| cached.get_fresh(self.epoch_time_to_live))
+        {
+            return Ok(epoch);
+        }
+
+        let epoch = self.observer.get_current_epoch().await?;
+        let mut cache = self.cache.write().await;
+        let is_epoch_changed = cache
+            .current_epoch
+            .as_ref()
+            .is_some_and(|cached| cached.value != epoch);
+        if is_epoch_changed {
+            *cache = ChainObserverCache::default();
+        }
+        cache.current_epoch = Some(CachedValue::new(epoch));
+
+        Ok(epoch)
+    }
+
+    async fn get_current_chain_point(&self) -> Result<Option<ChainPoint>, ChainObserverError> {
+        if let Some(chain_point) = self
+            .cache
+            .read()
+            .await
+            .current_chain_point
+            .as_ref()
+            .and_then(|cached| cached.get_fresh(self.chain_point_time_to_live))
+        {
+            return Ok(chain_point);
+        }
+
+        let chain_point = self.observer.get_current_chain_point().await?;
+        self.cache.write().await.current_chain_point = Some(CachedValue::new(chain_point.clone()));
+
+        Ok(chain_point)
+    }
+
+    async fn get_current_stake_distribution(
+        &self,
+    ) -> Result<Option<StakeDistribution>, ChainObserverError> {
+        let Some(epoch) = self.get_current_epoch().await? else {
+            return self.observer.get_current_stake_distribution().await;
+        };
+        if let Some((cached_epoch, stake_distribution)) =
+            &self.cache.read().await.stake_distribution
+        {
+            if *cached_epoch == epoch {
+                return Ok(Some(stake_distribution.clone()));
+            }
+        }
+
+        let stake_distribution = self.observer.get_current_stake_distribution().await?;
+        if let Some(stake_distribution) = &stake_distribution {
+            self.cache.write().await.stake_distribution = Some((epoch, stake_distribution.clone()));
+        }
+
+        Ok(stake_distribution)
+    }
+
+    async fn get_stake_distribution(
+        &self,
+        epoch: Epoch,
+    ) -> Result<Option<StakeDistribution>, ChainObserverError> {
+        self.observer.get_stake_distribution(epoch).await
+    }
+
+    async fn get_current_kes_period(
+        &self,
+        opcert: &OpCert,
+    ) -> Result<Option<KESPeriod>, ChainObserverError> {
+        self.observer.get_current_kes_period(opcert).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::chain_observer::{FakeObserver, MockChainObserver};
+    use crate::test_utils::fake_data;
+
+    use super::*;
+
+    fn stake_distribution(signers_count: usize) -> StakeDistribution {
+        fake_data::signers_with_stakes(signers_count)
+            .into_iter()
+            .map(|signer| (signer.party_id, signer.stake))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn cache_the_stake_distribution_for_the_current_epoch() {
+        let mut observer = MockChainObserver::new();
+        observer
+            .expect_get_current_epoch()
+            .returning(|| Ok(Some(Epoch(4))));
+        observer
+            .expect_get_current_stake_distribution()
+            .returning(|| Ok(Some(stake_distribution(3))))
+            .times(1);
+        let cached_observer = CachedChainObserver::new(Arc::new(observer));
+
+        for _ in 0..3 {
+            let stake_distribution = cached_observer
+                .get_current_stake_distribution()
+                .await
+                .unwrap();
+
+            assert_eq!(Some(3), stake_distribution.map(|s| s.len()));
+        }
+    }
+
+    #[tokio::test]
+    async fn retrieve_the_stake_distribution_again_when_the_epoch_changes() {
+        let observer = Arc::new(FakeObserver::default());
+        observer
+            .set_signers(fake_data::signers_with_stakes(2))
+            .await;
+        let cached_observer =
+            CachedChainObserver::new(observer.clone()).with_epoch_time_to_live(Duration::ZERO);
+
+        let stake_distribution = cached_observer
+            .get_current_stake_distribution()
+            .await
+            .unwrap();
+        assert_eq!(Some(2), stake_distribution.map(|s| s.len()));
+
+        observer
+            .set_signers(fake_data::signers_with_stakes(5))
+            .await;
+        let stake_distribution = cached_observer
+            .get_current_stake_distribution()
+            .await
+            .unwrap();
+        assert_eq!(
+            Some(2),
+            stake_distribution.map(|s| s.len()),
+            "stake distribution should be cached for the current epoch"
+        );
+
+        observer.next_epoch().await;
+        let stake_distribution = cached_observer
+            .get_current_stake_distribution()
+            .await
+            .unwrap();
+        assert_eq!(Some(5), stake_distribution.map(|s| s.len()));
+    }
+
+    #[tokio::test]
+    async fn cache_the_chain_point_for_its_time_to_live() {
+        let observer = Arc::new(FakeObserver::default());
+        let cached_observer = CachedChainObserver::new(observer.clone())
+            .with_chain_point_time_to_live(Duration::from_secs(3600));
+        let chain_point = cached_observer.get_current_chain_point().await.unwrap();
+
+        observer
+            .set_current_chain_point(Some(ChainPoint {
+                block_number: 1000,
+                ..ChainPoint::dummy()
+            }))
+            .await;
+
+        assert_eq!(
+            chain_point,
+            cached_observer.get_current_chain_point().await.unwrap()
+        );
+
+        let cached_observer = CachedChainObserver::new(observer.clone())
+            .with_chain_point_time_to_live(Duration::ZERO);
+        cached_observer.get_current_chain_point().await.unwrap();
+        observer.set_current_chain_point(None).await;
+
+        assert_eq!(
+            None,
+            cached_observer.get_current_chain_point().await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn invalidate_remove_all_the_cached_values() {
+        let observer = Arc::new(FakeObserver::default());
+        let cached_observer = CachedChainObserver::new(observer.clone());
+        cached_observer.get_current_epoch().await.unwrap();
+        cached_observer.get_current_chain_point().await.unwrap();
+
+        observer.next_epoch().await;
+        observer.set_current_chain_point(None).await;
+        cached_observer.invalidate().await;
+
+        assert_eq!(
+            observer.get_current_epoch().await.unwrap(),
+            cached_observer.get_current_epoch().await.unwrap()
+        );
+        assert_eq!(
+            None,
+            cached_observer.get_current_chain_point().await.unwrap()
+        );
+    }
+}
@@ -10,7 +10,7 @@ use std::path::PathBuf;
 use tokio::process::Command;
 
 use crate::chain_observer::interface::{ChainObserver, ChainObserverError};
-use crate::chain_observer::{ChainAddress, TxDatum};
+use crate::chain_observer::{ChainAddress, LedgerStakeSnapshot, TxDatum};
 use crate::crypto_helper::{encode_bech32, KESPeriod, OpCert, SerDeShelleyFileFormat};
 use crate::entities::{ChainPoint, Epoch, StakeDistribution};
 use crate::{CardanoNetwork, StdResult};
@@ -369,6 +369,19 @@ impl CardanoCliChainObserver {
     async fn get_current_stake_distribution_optimized(
         &self,
     ) -> Result<Option<StakeDistribution>, ChainObserverError> {
+        self.get_stake_distribution_from_snapshot(LedgerStakeSnapshot::Mark)
+            .await
+    }
+
+    async fn get_stake_distribution_from_snapshot(
+        &self,
+        snapshot: LedgerStakeSnapshot,
+    ) -> Result<Option<StakeDistribution>, ChainObserverError> {
+        let stake_field = match snapshot {
+            LedgerStakeSnapshot::Mark => "stakeMark",
+            LedgerStakeSnapshot::Set => "stakeSet",
+            LedgerStakeSnapshot::Go => "stakeGo",
+        };
         let output = self
             .cli_runner
             .launch_stake_snapshot_all_pools()
@@ -397,9 +410,9 @@ impl CardanoCliChainObserver {
             )
             .map_err(ChainObserverError::General)?;
             let stakes = v
-                .get("stakeMark")
+                .get(stake_field)
                 .ok_or(ChainObserverError::InvalidContent(anyhow!(
-                    "Missing 'stakeMark' field for {pool_id_bech32}"
+                    "Missing '{stake_field}' field for {pool_id_bech32}"
                 )))?
                 .as_u64()
                 .ok_or(ChainObserverError::InvalidContent(anyhow!(
@@ -486,6 +499,21 @@ impl ChainObserver for CardanoCliChainObserver {
         }
     }
 
+    async fn get_stake_distribution(
+        &self,
+        epoch: Epoch,
+    ) -> Result<Option<StakeDistribution>, ChainObserverError> {
+        let Some(current_epoch) = self.get_current_epoch().await? else {
+            return Ok(None);
+        };
+
+        match LedgerStakeSnapshot::holding_epoch(epoch, current_epoch) {
+            Some(LedgerStakeSnapshot::Mark) => self.get_current_stake_distribution().await,
+            Some(snapshot) => self.get_stake_distribution_from_snapshot(snapshot).await,
+            None => Ok(None),
+        }
+    }
+
     async fn get_current_kes_period(
         &self,
         opcert: &OpCert,
@@ -733,6 +761,36 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_stake_distribution_of_past_epochs() {
+        let observer = CardanoCliChainObserver::new(Box::<TestCliRunner>::default());
+        let current_epoch = observer.get_current_epoch().await.unwrap().unwrap();
+
+        let stake_distribution = observer
+            .get_stake_distribution(current_epoch - 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(4, stake_distribution.len());
+        assert!(stake_distribution.values().all(|stake| stake % 10 == 2));
+
+        let stake_distribution = observer
+            .get_stake_distribution(current_epoch - 2)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(3, stake_distribution.len());
+        assert!(stake_distribution.values().all(|stake| stake % 10 == 0));
+
+        assert_eq!(
+            None,
+            observer
+                .get_stake_distribution(current_epoch - 3)
+                .await
+                .unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn test_get_current_kes_period() {
         let keypair = ColdKeyGenerator::create_deterministic_keypair([0u8; 32]);
@@ -13,14 +13,12 @@ pub struct FakeObserver {
     /// [get_current_stake_distribution]: ChainObserver::get_current_stake_distribution
     pub signers: RwLock<Vec<SignerWithStake>>,
 
-    /// A [TimePoint], used by [get_current_epoch]
+    /// A [TimePoint], used by [get_current_epoch] and [get_current_chain_point]
     ///
     /// [get_current_epoch]: ChainObserver::get_current_epoch
+    /// [get_current_chain_point]: ChainObserver::get_current_chain_point
     pub current_time_point: RwLock<Option<TimePoint>>,
 
-    /// The current chain point
-    pub current_chain_point: RwLock<Option<ChainPoint>>,
-
     /// A list of [TxDatum], used by [get_current_datums]
     ///
     /// [get_current_datums]: ChainObserver::get_current_datums
@@ -33,7 +31,6 @@ impl FakeObserver {
         Self {
             signers: RwLock::new(vec![]),
             current_time_point: RwLock::new(current_time_point),
-            current_chain_point: RwLock::new(None),
             datums: RwLock::new(vec![]),
         }
     }
@@ -58,9 +55,15 @@ impl FakeObserver {
 
     /// Set the chain point that will use to compute the result of
     /// [get_current_chain_point][ChainObserver::get_current_chain_point].
+    ///
+    /// The chain point is stored in the [current_time_point][`FakeObserver::current_time_point`],
+    /// a `None` chain point removes the current time point.
     pub async fn set_current_chain_point(&self, new_current_chain_point: Option<ChainPoint>) {
-        let mut current_chain_point = self.current_chain_point.write().await;
-        *current_chain_point = new_current_chain_point;
+        let mut current_time_point = self.current_time_point.write().await;
+        *current_time_point = new_current_chain_point.map(|chain_point| TimePoint {
+            chain_point,
+            ..current_time_point.clone().unwrap_or_default()
+        });
     }
 
     /// Set the datums that will use to compute the result of
@@ -100,7 +103,12 @@ impl ChainObserver for FakeObserver {
     }
 
     async fn get_current_chain_point(&self) -> Result<Option<ChainPoint>, ChainObserverError> {
-        Ok(self.current_chain_point.read().await.clone())
+        Ok(self
+            .current_time_point
+            .read()
+            .await
+            .as_ref()
+            .map(|time_point| time_point.chain_point.clone()))
     }
 
     async fn get_current_stake_distribution(
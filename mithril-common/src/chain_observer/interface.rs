@@ -44,6 +44,23 @@ pub trait ChainObserver: Sync + Send {
         &self,
     ) -> Result<Option<StakeDistribution>, ChainObserverError>;
 
+    /// Retrieve the stake distribution of the given epoch of the Cardano network
+    ///
+    /// The ledger state of a Cardano node only keeps the stake distributions of the current epoch
+    /// and of the two previous epochs, see [LedgerStakeSnapshot][super::LedgerStakeSnapshot],
+    /// `None` is returned for the other epochs.
+    async fn get_stake_distribution(
+        &self,
+        epoch: Epoch,
+    ) -> Result<Option<StakeDistribution>, ChainObserverError> {
+        match self.get_current_epoch().await? {
+            Some(current_epoch) if current_epoch == epoch => {
+                self.get_current_stake_distribution().await
+            }
+            _ => Ok(None),
+        }
+    }
+
     /// Retrieve the KES period of an operational certificate
     async fn get_current_kes_period(
         &self,
@@ -1,13 +1,16 @@
 //! Tools to request metadata, like the current epoch or the stake distribution, from the Cardano
 
+mod cached_observer;
 mod interface;
 mod model;
 
+pub use cached_observer::CachedChainObserver;
 #[cfg(test)]
 pub use interface::MockChainObserver;
 pub use interface::{ChainObserver, ChainObserverError};
 pub use model::{
-    ChainAddress, TxDatum, TxDatumBuilder, TxDatumError, TxDatumFieldTypeName, TxDatumFieldValue,
+    ChainAddress, LedgerStakeSnapshot, TxDatum, TxDatumBuilder, TxDatumError, TxDatumFieldTypeName,
+    TxDatumFieldValue,
 };
 
 cfg_fs_random! {
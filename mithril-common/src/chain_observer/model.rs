@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use strum::{Display, EnumDiscriminants};
 use thiserror::Error;
 
+use crate::entities::Epoch;
 use crate::{StdError, StdResult};
 
 cfg_fs_random! {
@@ -50,6 +51,33 @@ cfg_fs_random! {
 /// [ChainAddress] represents an on chain address.
 pub type ChainAddress = String;
 
+/// [LedgerStakeSnapshot] represents one of the stake snapshots kept by the ledger state of a
+/// Cardano node, each one holding the stake distribution computed at the start of an epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerStakeSnapshot {
+    /// Snapshot holding the stake distribution of the current epoch
+    Mark,
+
+    /// Snapshot holding the stake distribution of the previous epoch
+    Set,
+
+    /// Snapshot holding the stake distribution of the epoch before the previous one
+    Go,
+}
+
+impl LedgerStakeSnapshot {
+    /// Snapshot holding the stake distribution of the given epoch at the current epoch, if it's
+    /// still kept by the ledger state.
+    pub fn holding_epoch(epoch: Epoch, current_epoch: Epoch) -> Option<Self> {
+        match (*current_epoch).checked_sub(*epoch)? {
+            0 => Some(Self::Mark),
+            1 => Some(Self::Set),
+            2 => Some(Self::Go),
+            _ => None,
+        }
+    }
+}
+
 /// [TxDatum] related errors.
 #[derive(Debug, Error)]
 pub enum TxDatumError {
@@ -272,4 +300,28 @@ mod test {
             .get_nth_field_by_type(&TxDatumFieldTypeName::Int, 100)
             .expect_err("should have returned an error");
     }
+
+    #[test]
+    fn ledger_stake_snapshot_holding_an_epoch() {
+        assert_eq!(
+            Some(LedgerStakeSnapshot::Mark),
+            LedgerStakeSnapshot::holding_epoch(Epoch(10), Epoch(10))
+        );
+        assert_eq!(
+            Some(LedgerStakeSnapshot::Set),
+            LedgerStakeSnapshot::holding_epoch(Epoch(9), Epoch(10))
+        );
+        assert_eq!(
+            Some(LedgerStakeSnapshot::Go),
+            LedgerStakeSnapshot::holding_epoch(Epoch(8), Epoch(10))
+        );
+        assert_eq!(
+            None,
+            LedgerStakeSnapshot::holding_epoch(Epoch(7), Epoch(10))
+        );
+        assert_eq!(
+            None,
+            LedgerStakeSnapshot::holding_epoch(Epoch(11), Epoch(10))
+        );
+    }
 }
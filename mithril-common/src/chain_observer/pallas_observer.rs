@@ -7,8 +7,8 @@ use pallas_network::{
     miniprotocols::{
         localstate::{
             queries_v16::{
-                self, Addr, Addrs, Genesis, PostAlonsoTransactionOutput, StakeSnapshot, Stakes,
-                TransactionOutput, UTxOByAddress,
+                self, Addr, Addrs, ChainBlockNumber, Genesis, PostAlonsoTransactionOutput,
+                StakeSnapshot, Stakes, TransactionOutput, UTxOByAddress,
             },
             Client,
         },
@@ -23,7 +23,7 @@ use std::{
 };
 
 use crate::{
-    chain_observer::{interface::*, ChainAddress, TxDatum},
+    chain_observer::{interface::*, ChainAddress, LedgerStakeSnapshot, TxDatum},
     crypto_helper::{encode_bech32, KESPeriod, OpCert},
     entities::{ChainPoint, Epoch, StakeDistribution},
     CardanoNetwork, StdResult,
@@ -199,6 +199,36 @@ impl PallasChainObserver {
         Ok(state_snapshot)
     }
 
+    /// Fetches the current epoch number and the stake snapshots using the provided `statequery`
+    /// client.
+    async fn do_epoch_and_stake_snapshots_state_query(
+        &self,
+        statequery: &mut Client,
+    ) -> StdResult<(u32, StakeSnapshot)> {
+        statequery
+            .acquire(None)
+            .await
+            .map_err(|err| anyhow!(err))
+            .with_context(|| "PallasChainObserver failed to acquire statequery")?;
+
+        let era = queries_v16::get_current_era(statequery)
+            .await
+            .map_err(|err| anyhow!(err))
+            .with_context(|| "PallasChainObserver failed to get current era")?;
+
+        let epoch = queries_v16::get_block_epoch_number(statequery, era)
+            .await
+            .map_err(|err| anyhow!(err))
+            .with_context(|| "PallasChainObserver failed to get block epoch number")?;
+
+        let state_snapshot = queries_v16::get_stake_snapshots(statequery, era, BTreeSet::new())
+            .await
+            .map_err(|err| anyhow!(err))
+            .with_context(|| "PallasChainObserver failed to get stake snapshot")?;
+
+        Ok((epoch, state_snapshot))
+    }
+
     /// Returns the stake pool hash from the given bytestring.
     fn get_stake_pool_hash(&self, key: &Bytes) -> Result<String, ChainObserverError> {
         let pool_id_bech32 = encode_bech32("pool", key)
@@ -216,20 +246,54 @@ impl PallasChainObserver {
 
         let stake_snapshot = self.do_stake_snapshots_state_query(statequery).await?;
 
+        Ok(Some(self.build_stake_distribution(
+            &stake_snapshot,
+            LedgerStakeSnapshot::Mark,
+        )?))
+    }
+
+    /// Fetches the stake distribution of the given epoch using the provided `statequery` client,
+    /// if it's still kept in the stake snapshots of the ledger state.
+    async fn get_stake_distribution_of_epoch(
+        &self,
+        client: &mut NodeClient,
+        epoch: Epoch,
+    ) -> Result<Option<StakeDistribution>, ChainObserverError> {
+        let statequery = client.statequery();
+
+        let (current_epoch, stake_snapshot) = self
+            .do_epoch_and_stake_snapshots_state_query(statequery)
+            .await?;
+
+        LedgerStakeSnapshot::holding_epoch(epoch, Epoch(current_epoch as u64))
+            .map(|snapshot| self.build_stake_distribution(&stake_snapshot, snapshot))
+            .transpose()
+    }
+
+    /// Builds the stake distribution held by the given snapshot of the stake snapshots.
+    fn build_stake_distribution(
+        &self,
+        stake_snapshot: &StakeSnapshot,
+        snapshot: LedgerStakeSnapshot,
+    ) -> Result<StakeDistribution, ChainObserverError> {
+        let pool_stake = |stakes: &Stakes| match snapshot {
+            LedgerStakeSnapshot::Mark => stakes.snapshot_mark_pool,
+            LedgerStakeSnapshot::Set => stakes.snapshot_set_pool,
+            LedgerStakeSnapshot::Go => stakes.snapshot_go_pool,
+        };
         let mut stake_distribution = StakeDistribution::new();
 
-        let have_stakes_in_two_epochs = |stakes: &Stakes| stakes.snapshot_mark_pool > 0;
         for (key, stakes) in stake_snapshot
             .snapshots
             .stake_snapshots
             .iter()
-            .filter(|(_, stakes)| have_stakes_in_two_epochs(stakes))
+            .filter(|(_, stakes)| pool_stake(stakes) > 0)
         {
             let pool_hash = self.get_stake_pool_hash(key)?;
-            stake_distribution.insert(pool_hash, stakes.snapshot_mark_pool);
+            stake_distribution.insert(pool_hash, pool_stake(stakes));
         }
 
-        Ok(Some(stake_distribution))
+        Ok(stake_distribution)
     }
 
     /// # Calculate Current KES Period
@@ -279,6 +343,35 @@ impl PallasChainObserver {
         Ok(chain_point)
     }
 
+    /// Fetches the current chain block number using the provided `statequery` client.
+    async fn do_get_chain_block_no_state_query(
+        &self,
+        statequery: &mut Client,
+    ) -> StdResult<ChainBlockNumber> {
+        let chain_block_number = queries_v16::get_chain_block_no(statequery)
+            .await
+            .map_err(|err| anyhow!(err))
+            .with_context(|| "PallasChainObserver failed to get chain block number")?;
+
+        Ok(chain_block_number)
+    }
+
+    /// Fetches the current chain point, with its block number, using the provided `statequery` client.
+    async fn get_chain_point(&self, statequery: &mut Client) -> StdResult<ChainPoint> {
+        let point = self.do_get_chain_point_state_query(statequery).await?;
+        let chain_block_number = self.do_get_chain_block_no_state_query(statequery).await?;
+        let (slot_number, block_hash) = match point {
+            Point::Origin => (0, String::new()),
+            Point::Specific(slot_number, hash) => (slot_number, hex::encode(hash)),
+        };
+
+        Ok(ChainPoint {
+            slot_number,
+            block_number: chain_block_number.block_number as u64,
+            block_hash,
+        })
+    }
+
     /// Fetches the current era using the provided `statequery` client.
     async fn do_get_current_era_state_query(&self, statequery: &mut Client) -> StdResult<u16> {
         let era = queries_v16::get_current_era(statequery)
@@ -385,8 +478,15 @@ impl ChainObserver for PallasChainObserver {
     }
 
     async fn get_current_chain_point(&self) -> Result<Option<ChainPoint>, ChainObserverError> {
-        // TODO: Implement get_current_chain_point with pallas
-        todo!("Implement get_current_chain_point")
+        let mut client = self.get_client().await?;
+
+        let chain_point = self.get_chain_point(client.statequery()).await?;
+
+        self.post_process_statequery(&mut client).await?;
+
+        client.abort().await;
+
+        Ok(Some(chain_point))
     }
 
     async fn get_current_datums(
@@ -418,6 +518,23 @@ impl ChainObserver for PallasChainObserver {
         Ok(stake_distribution)
     }
 
+    async fn get_stake_distribution(
+        &self,
+        epoch: Epoch,
+    ) -> Result<Option<StakeDistribution>, ChainObserverError> {
+        let mut client = self.get_client().await?;
+
+        let stake_distribution = self
+            .get_stake_distribution_of_epoch(&mut client, epoch)
+            .await?;
+
+        self.post_process_statequery(&mut client).await?;
+
+        client.abort().await;
+
+        Ok(stake_distribution)
+    }
+
     async fn get_current_kes_period(
         &self,
         _opcert: &OpCert,
@@ -581,6 +698,10 @@ mod tests {
             Request::GetChainPoint => {
                 AnyCbor::from_encode(Point::Specific(52851885, vec![1, 2, 3]))
             }
+            Request::GetChainBlockNo => AnyCbor::from_encode(ChainBlockNumber {
+                slot_timeline: 1,
+                block_number: 2431,
+            }),
             Request::LedgerQuery(LedgerQuery::HardForkQuery(HardForkQuery::GetCurrentEra)) => {
                 AnyCbor::from_encode(4)
             }
@@ -695,6 +816,40 @@ mod tests {
         assert_eq!(expected_stake_distribution, computed_stake_distribution);
     }
 
+    #[tokio::test]
+    async fn get_stake_distribution_of_a_past_epoch() {
+        let socket_path =
+            create_temp_dir("get_stake_distribution_of_a_past_epoch").join("node.socket");
+        let server = setup_server(socket_path.clone(), 3).await;
+        let client = tokio::spawn(async move {
+            let observer =
+                super::PallasChainObserver::new(socket_path.as_path(), CardanoNetwork::TestNet(10));
+            observer.get_stake_distribution(Epoch(7)).await.unwrap()
+        });
+
+        let (_, client_res) = tokio::join!(server, client);
+        let computed_stake_distribution = client_res.unwrap().unwrap();
+
+        let mut expected_stake_distribution = StakeDistribution::new();
+        expected_stake_distribution.insert(
+            "pool1qqqqqdk4zhsjuxxd8jyvwncf5eucfskz0xjjj64fdmlgj735lr9".to_string(),
+            300000000002,
+        );
+        expected_stake_distribution.insert(
+            "pool1qqqqpanw9zc0rzh0yp247nzf2s35uvnsm7aaesfl2nnejaev0uc".to_string(),
+            600000000002,
+        );
+        expected_stake_distribution.insert(
+            "pool1qqqqzyqf8mlm70883zht60n4q6uqxg4a8x266sewv8ad2grkztl".to_string(),
+            1200000000002,
+        );
+
+        assert_eq!(4, computed_stake_distribution.len());
+        assert!(expected_stake_distribution
+            .iter()
+            .all(|(pool, stake)| computed_stake_distribution.get(pool) == Some(stake)));
+    }
+
     #[tokio::test]
     async fn get_current_kes_period() {
         let socket_path = create_temp_dir("get_current_kes_period").join("node.socket");
@@ -769,6 +924,28 @@ mod tests {
         assert_eq!(chain_point, Point::Specific(52851885, vec![1, 2, 3]));
     }
 
+    #[tokio::test]
+    async fn get_current_chain_point() {
+        let socket_path = create_temp_dir("get_current_chain_point").join("node.socket");
+        let server = setup_server(socket_path.clone(), 2).await;
+        let client = tokio::spawn(async move {
+            let observer =
+                PallasChainObserver::new(socket_path.as_path(), CardanoNetwork::TestNet(10));
+            observer.get_current_chain_point().await.unwrap()
+        });
+
+        let (_, client_res) = tokio::join!(server, client);
+        let chain_point = client_res.expect("Client failed");
+        assert_eq!(
+            Some(ChainPoint {
+                slot_number: 52851885,
+                block_number: 2431,
+                block_hash: "010203".to_string(),
+            }),
+            chain_point
+        );
+    }
+
     #[tokio::test]
     async fn get_genesis_config() {
         let socket_path = create_temp_dir("get_genesis_config").join("node.socket");
@@ -7,10 +7,11 @@ use crate::{
     crypto_helper::{
         cardano::SerDeShelleyFileFormat,
         types::{
-            ProtocolParameters, ProtocolPartyId, ProtocolSignerVerificationKey,
-            ProtocolSignerVerificationKeySignature, ProtocolStakeDistribution,
+            ProtocolParameters, ProtocolPartyId, ProtocolSignerMessageSignature,
+            ProtocolSignerVerificationKey, ProtocolSignerVerificationKeySignature,
+            ProtocolStakeDistribution,
         },
-        ProtocolOpCert,
+        OpCert, ProtocolOpCert,
     },
     StdError, StdResult,
 };
@@ -96,6 +97,37 @@ pub enum ProtocolInitializerErrorWrapper {
     KesMismatch(KESPeriod, KESPeriod),
 }
 
+/// Sign a message with the KES secret key stored in the given file, evolved up to the given
+/// KES period.
+pub(crate) fn kes_sign<P: AsRef<Path>>(
+    kes_sk_path: P,
+    kes_period: KESPeriod,
+    message: &[u8],
+) -> StdResult<Sum6KesSig> {
+    let mut kes_sk_bytes = Sum6KesBytes::from_file(kes_sk_path)
+        .map_err(|e| anyhow!(e))
+        .with_context(|| "Can not read KES secret key from file")?;
+    let mut kes_sk = Sum6Kes::try_from(&mut kes_sk_bytes)
+        .map_err(|e| ProtocolInitializerErrorWrapper::ProtocolInitializer(anyhow!(e)))
+        .with_context(|| "Can not use KES secret key")?;
+    let kes_sk_period = kes_sk.get_period();
+    if kes_sk_period > kes_period {
+        return Err(anyhow!(ProtocolInitializerErrorWrapper::KesMismatch(
+            kes_sk_period,
+            kes_period,
+        )));
+    }
+
+    // We need to perform the evolutions
+    for period in kes_sk_period..kes_period {
+        kes_sk
+            .update()
+            .map_err(|_| ProtocolInitializerErrorWrapper::KesUpdate(period))?;
+    }
+
+    Ok(kes_sk.sign(message))
+}
+
 /// Wrapper structure for [MithrilStm:StmInitializer](mithril_stm::stm::StmInitializer).
 /// It now obtains a KES signature over the Mithril key. This allows the signers prove
 /// their correct identity with respect to a Cardano PoolID.
@@ -130,29 +162,11 @@ impl StmInitializerWrapper {
     ) -> StdResult<Self> {
         let stm_initializer = StmInitializer::setup(params, stake, rng);
         let kes_signature = if let Some(kes_sk_path) = kes_sk_path {
-            let mut kes_sk_bytes = Sum6KesBytes::from_file(kes_sk_path)
-                .map_err(|e| anyhow!(e))
-                .with_context(|| "StmInitializerWrapper can not read KES secret key from file")?;
-            let mut kes_sk = Sum6Kes::try_from(&mut kes_sk_bytes)
-                .map_err(|e| ProtocolInitializerErrorWrapper::ProtocolInitializer(anyhow!(e)))
-                .with_context(|| "StmInitializerWrapper can not use KES secret key")?;
-            let kes_sk_period = kes_sk.get_period();
-            let provided_period = kes_period.unwrap_or_default();
-            if kes_sk_period > provided_period {
-                return Err(anyhow!(ProtocolInitializerErrorWrapper::KesMismatch(
-                    kes_sk_period,
-                    provided_period,
-                )));
-            }
-
-            // We need to perform the evolutions
-            for period in kes_sk_period..provided_period {
-                kes_sk
-                    .update()
-                    .map_err(|_| ProtocolInitializerErrorWrapper::KesUpdate(period))?;
-            }
-
-            Some(kes_sk.sign(&stm_initializer.verification_key().to_bytes()))
+            Some(kes_sign(
+                kes_sk_path,
+                kes_period.unwrap_or_default(),
+                &stm_initializer.verification_key().to_bytes(),
+            )?)
         } else {
             println!("WARNING: Non certified signer registration by providing only a Pool Id is decommissionned and must be used for tests only!");
             None
@@ -174,6 +188,34 @@ impl StmInitializerWrapper {
         self.kes_signature.map(|k| k.into())
     }
 
+    /// Sign a message, which is not a message signed by the protocol, with the signing key of
+    /// the initializer: the signature is verified with its verification key.
+    pub fn sign_message(&self, message: &[u8]) -> ProtocolSignerMessageSignature {
+        self.stm_initializer.sign_message(message).into()
+    }
+
+    /// Check that the verification key was signed with the KES secret key certified by the given
+    /// operational certificate, at any of its KES periods.
+    pub fn verify_kes_signature(&self, opcert: &OpCert) -> StdResult<()> {
+        let kes_signature = self
+            .kes_signature
+            .as_ref()
+            .ok_or(ProtocolRegistrationErrorWrapper::KesSignatureMissing)?;
+        let verification_key = self.verification_key().to_bytes();
+
+        if (0..64).any(|kes_period| {
+            kes_signature
+                .verify(kes_period, &opcert.kes_vk, &verification_key)
+                .is_ok()
+        }) {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "The verification key was not signed with the KES key of the operational certificate"
+            ))
+        }
+    }
+
     /// Extract the protocol parameters of the initializer
     pub fn get_protocol_parameters(&self) -> ProtocolParameters {
         self.stm_initializer.params
@@ -401,6 +443,34 @@ mod test {
         assert!(key_registration_2.is_ok())
     }
 
+    #[test]
+    fn verify_kes_signature_with_the_operational_certificate() {
+        let params = StmParameters {
+            m: 5,
+            k: 5,
+            phi_f: 1.0,
+        };
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let (_, operational_certificate_file_1, kes_secret_key_file_1) =
+            create_cryptographic_material(3);
+        let (_, operational_certificate_file_2, _) = create_cryptographic_material(4);
+        let initializer = StmInitializerWrapper::setup(
+            params,
+            Some(kes_secret_key_file_1),
+            Some(2),
+            10,
+            &mut rng,
+        )
+        .unwrap();
+
+        initializer
+            .verify_kes_signature(&OpCert::from_file(operational_certificate_file_1).unwrap())
+            .expect("The KES signature should match the operational certificate");
+        initializer
+            .verify_kes_signature(&OpCert::from_file(operational_certificate_file_2).unwrap())
+            .expect_err("The KES signature should not match another operational certificate");
+    }
+
     #[test]
     fn golden_initializer_deserialization() {
         let string = r#"
@@ -1,10 +1,12 @@
 mod codec;
 mod key_certification;
 mod opcert;
+mod registration_nonce;
 
 pub use codec::*;
 pub use key_certification::*;
 pub use opcert::*;
+pub use registration_nonce::*;
 
 cfg_random! {
     mod cold_key;
@@ -0,0 +1,252 @@
+//! Authentication of the single signatures sent by the signers with a nonce.
+//!
+//! The aggregator publishes a nonce for each epoch in its epoch settings. Along with each single
+//! signature, a signer sends a signature of the nonce, its party id and its single signature made
+//! with its KES secret key, which the aggregator checks with the KES verification key of the
+//! operational certificate the signer registered with.
+//!
+//! The same KES signature authenticates the request of a signer to unregister from the upcoming
+//! epochs, bound to the nonce of the current epoch so that it can not be replayed later.
+
+use anyhow::anyhow;
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use kes_summed_ed25519::traits::KesSig;
+use std::path::Path;
+
+use super::key_certification::kes_sign;
+use crate::crypto_helper::{KESPeriod, OpCert, ProtocolSignerVerificationKeySignature};
+use crate::entities::{Epoch, PartyId};
+use crate::StdResult;
+
+/// Compute the registration nonce of an epoch from a seed that is not known before the start of
+/// the epoch.
+pub fn compute_registration_nonce(epoch: Epoch, seed: &[u8]) -> String {
+    let hash = Blake2b::<U32>::new()
+        .chain_update(b"mithril-registration-nonce")
+        .chain_update(epoch.to_be_bytes())
+        .chain_update(seed)
+        .finalize();
+
+    hex::encode(hash)
+}
+
+/// Compute the message signed with the KES secret key of a signer to authenticate one of its
+/// single signatures.
+pub fn compute_registration_nonce_message(
+    nonce: &str,
+    party_id: &PartyId,
+    single_signature: &[u8],
+) -> Vec<u8> {
+    Blake2b::<U32>::new()
+        .chain_update(nonce.as_bytes())
+        .chain_update(party_id.as_bytes())
+        .chain_update(single_signature)
+        .finalize()
+        .to_vec()
+}
+
+/// Compute the message signed with the KES secret key of a signer to authenticate its request to
+/// unregister from the given epoch, with the registration nonce of the current epoch.
+pub fn compute_signer_unregistration_message(
+    epoch: Epoch,
+    nonce: &str,
+    party_id: &PartyId,
+) -> Vec<u8> {
+    Blake2b::<U32>::new()
+        .chain_update(b"mithril-signer-unregistration")
+        .chain_update(epoch.to_be_bytes())
+        .chain_update(nonce.as_bytes())
+        .chain_update(party_id.as_bytes())
+        .finalize()
+        .to_vec()
+}
+
+/// Sign a registration nonce message with the KES secret key stored in the given file, evolved
+/// up to the given KES period.
+pub fn sign_registration_nonce_message<P: AsRef<Path>>(
+    kes_sk_path: P,
+    kes_period: KESPeriod,
+    message: &[u8],
+) -> StdResult<ProtocolSignerVerificationKeySignature> {
+    Ok(kes_sign(kes_sk_path, kes_period, message)?.into())
+}
+
+/// Compute the KES period a KES secret key must be evolved to, relative to the start KES period
+/// of the given operational certificate, at the given current KES period of the chain.
+///
+/// Returns `None` if the operational certificate starts after the current KES period.
+pub fn compute_operational_certificate_kes_period(
+    current_kes_period: KESPeriod,
+    operational_certificate: &OpCert,
+) -> Option<KESPeriod> {
+    current_kes_period.checked_sub(operational_certificate.start_kes_period as KESPeriod)
+}
+
+/// Verify the signature of a registration nonce message with the KES verification key of the
+/// given operational certificate.
+///
+/// The signature is only valid if it was made at the given KES period: accepting a signature made
+/// at a previous period would allow a KES secret key leaked after its evolution to be used.
+pub fn verify_registration_nonce_signature(
+    signature: &ProtocolSignerVerificationKeySignature,
+    operational_certificate: &OpCert,
+    kes_period: KESPeriod,
+    message: &[u8],
+) -> StdResult<()> {
+    signature
+        .verify(kes_period, &operational_certificate.kes_vk, message)
+        .map_err(|_| {
+            anyhow!(
+                "The registration nonce was not signed at KES period {kes_period} with the KES key of the operational certificate"
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use kes_summed_ed25519::kes::Sum6Kes;
+    use kes_summed_ed25519::traits::KesSk;
+    use std::path::PathBuf;
+
+    use crate::crypto_helper::cardano::ColdKeyGenerator;
+    use crate::crypto_helper::{SerDeShelleyFileFormat, Sum6KesBytes};
+    use crate::test_utils::TempDir;
+
+    use super::*;
+
+    fn create_kes_material(test_name: &str, seed: u8) -> (OpCert, PathBuf) {
+        let temp_dir = TempDir::create("mithril_cardano_registration_nonce", test_name);
+        let keypair = ColdKeyGenerator::create_deterministic_keypair([seed; 32]);
+        let mut dummy_buffer = [0u8; Sum6Kes::SIZE + 4];
+        let mut dummy_seed = [seed; 32];
+        let (kes_secret_key, kes_verification_key) =
+            Sum6Kes::keygen(&mut dummy_buffer, &mut dummy_seed);
+        let mut kes_bytes = Sum6KesBytes([0u8; Sum6Kes::SIZE + 4]);
+        kes_bytes.0.copy_from_slice(&kes_secret_key.clone_sk());
+        let kes_secret_key_file = temp_dir.join(format!("kes{seed}.skey"));
+        kes_bytes.to_file(&kes_secret_key_file).unwrap();
+
+        (
+            OpCert::new(kes_verification_key, 0, 0, keypair),
+            kes_secret_key_file,
+        )
+    }
+
+    #[test]
+    fn registration_nonce_depends_on_the_epoch_and_the_seed() {
+        let nonce = compute_registration_nonce(Epoch(3), b"seed");
+
+        assert_eq!(nonce, compute_registration_nonce(Epoch(3), b"seed"));
+        assert_ne!(nonce, compute_registration_nonce(Epoch(4), b"seed"));
+        assert_ne!(nonce, compute_registration_nonce(Epoch(3), b"another seed"));
+    }
+
+    #[test]
+    fn signer_unregistration_message_depends_on_the_epoch_the_nonce_and_the_party_id() {
+        let message =
+            compute_signer_unregistration_message(Epoch(3), "nonce", &"party_id".to_string());
+
+        assert_eq!(
+            message,
+            compute_signer_unregistration_message(Epoch(3), "nonce", &"party_id".to_string())
+        );
+        assert_ne!(
+            message,
+            compute_signer_unregistration_message(Epoch(4), "nonce", &"party_id".to_string())
+        );
+        assert_ne!(
+            message,
+            compute_signer_unregistration_message(
+                Epoch(3),
+                "another_nonce",
+                &"party_id".to_string()
+            )
+        );
+        assert_ne!(
+            message,
+            compute_signer_unregistration_message(
+                Epoch(3),
+                "nonce",
+                &"another_party_id".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn verify_a_registration_nonce_signature_only_at_the_kes_period_it_was_made() {
+        let (operational_certificate, kes_secret_key_file) = create_kes_material(
+            "verify_a_registration_nonce_signature_only_at_the_kes_period_it_was_made",
+            1,
+        );
+        let message =
+            compute_registration_nonce_message("nonce", &"party_id".to_string(), b"signature");
+        let signature = sign_registration_nonce_message(&kes_secret_key_file, 2, &message).unwrap();
+
+        verify_registration_nonce_signature(&signature, &operational_certificate, 2, &message)
+            .expect("The registration nonce signature should be valid at its KES period");
+        for kes_period in [0, 1, 3] {
+            verify_registration_nonce_signature(
+                &signature,
+                &operational_certificate,
+                kes_period,
+                &message,
+            )
+            .expect_err("The registration nonce signature should be invalid at another KES period");
+        }
+    }
+
+    #[test]
+    fn operational_certificate_kes_period_is_relative_to_its_start_kes_period() {
+        let (operational_certificate, _) = create_kes_material(
+            "operational_certificate_kes_period_is_relative_to_its_start_kes_period",
+            1,
+        );
+        let operational_certificate = OpCert {
+            start_kes_period: 5,
+            ..operational_certificate
+        };
+
+        assert_eq!(
+            Some(3),
+            compute_operational_certificate_kes_period(8, &operational_certificate)
+        );
+        assert_eq!(
+            None,
+            compute_operational_certificate_kes_period(4, &operational_certificate)
+        );
+    }
+
+    #[test]
+    fn verify_fail_with_another_message_or_operational_certificate() {
+        let (operational_certificate, kes_secret_key_file) = create_kes_material(
+            "verify_fail_with_another_message_or_operational_certificate",
+            1,
+        );
+        let (other_operational_certificate, _) = create_kes_material(
+            "verify_fail_with_another_message_or_operational_certificate",
+            2,
+        );
+        let message =
+            compute_registration_nonce_message("nonce", &"party_id".to_string(), b"signature");
+        let signature = sign_registration_nonce_message(&kes_secret_key_file, 0, &message).unwrap();
+
+        verify_registration_nonce_signature(
+            &signature,
+            &operational_certificate,
+            0,
+            &compute_registration_nonce_message(
+                "another nonce",
+                &"party_id".to_string(),
+                b"signature",
+            ),
+        )
+        .expect_err("The signature of another message should be invalid");
+        verify_registration_nonce_signature(
+            &signature,
+            &other_operational_certificate,
+            0,
+            &message,
+        )
+        .expect_err("The signature should be invalid for another operational certificate");
+    }
+}
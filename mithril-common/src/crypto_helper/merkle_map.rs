@@ -3,7 +3,7 @@
 use anyhow::{anyhow, Context};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     hash::Hash,
     rc::Rc,
 };
@@ -15,6 +15,8 @@ use super::{MKProof, MKTree, MKTreeNode};
 /// The trait implemented by the keys of a MKMap
 pub trait MKMapKey: PartialEq + Eq + PartialOrd + Ord + Clone + Hash + Into<MKTreeNode> {}
 
+impl MKMapKey for MKTreeNode {}
+
 /// The trait implemented by the values of a MKMap
 pub trait MKMapValue<K: MKMapKey>: Clone + TryInto<MKTreeNode> {
     /// Get the root of the merkelized map value
@@ -193,11 +195,38 @@ impl<K: MKMapKey, V: MKMapValue<K>> TryFrom<MKMap<K, V>> for MKTreeNode {
     }
 }
 
+/// A merkelized map can be the value of another merkelized map which keys have a different type
+/// (e.g. a map keyed by epochs of maps keyed by block ranges of merkle trees of transactions).
+///
+/// The proofs of the inner map are kept as nested proofs in the proof of the outer map.
+impl<K: MKMapKey, SK: MKMapKey, SV: MKMapValue<SK>> MKMapValue<K> for MKMap<SK, SV> {
+    fn compute_root(&self) -> StdResult<MKTreeNode> {
+        MKMap::compute_root(self)
+    }
+
+    fn contains<T: Into<MKTreeNode> + Clone>(&self, leaf: &T) -> bool {
+        MKMap::contains(self, &leaf.to_owned().into()).is_some()
+    }
+
+    fn compute_proof<T: Into<MKTreeNode> + Clone>(
+        &self,
+        leaves: &[T],
+    ) -> StdResult<Option<MKMapProof<K>>> {
+        let proof = MKMap::compute_proof(self, leaves)
+            .with_context(|| "MKMapValue could not compute sub proof for nested MKMap")?;
+
+        Ok(Some(proof.into_nested()))
+    }
+}
+
 /// A MKMapProof that proves membership of an entry in the merkelized map
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct MKMapProof<K: MKMapKey> {
     master_proof: MKProof,
     sub_proofs: Vec<(K, MKMapProof<K>)>,
+    /// Sub proofs of nested merkelized maps which keys have a different type
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    nested_proofs: Vec<(MKTreeNode, MKMapProof<MKTreeNode>)>,
 }
 
 impl<K: MKMapKey> MKMapProof<K> {
@@ -207,9 +236,40 @@ impl<K: MKMapKey> MKMapProof<K> {
         Self {
             master_proof,
             sub_proofs,
+            nested_proofs: vec![],
         }
     }
 
+    /// Convert the proof of a nested merkelized map into a sub proof of a merkelized map with
+    /// another type of keys, the keys of its sub proofs are kept as merkle tree nodes.
+    fn into_nested<NK: MKMapKey>(self) -> MKMapProof<NK> {
+        let mut nested_proofs = self
+            .sub_proofs
+            .into_iter()
+            .map(|(key, proof)| (key.into(), proof.into_nested()))
+            .collect::<Vec<(MKTreeNode, MKMapProof<MKTreeNode>)>>();
+        nested_proofs.extend(self.nested_proofs);
+
+        MKMapProof {
+            master_proof: self.master_proof,
+            sub_proofs: vec![],
+            nested_proofs,
+        }
+    }
+
+    /// Get the leaves of the master proof that match the sub proofs and the nested proofs
+    fn compute_sub_proofs_leaves(&self) -> Vec<MKTreeNode> {
+        self.sub_proofs
+            .iter()
+            .map(|(k, p)| k.to_owned().into() + p.compute_root().to_owned())
+            .chain(
+                self.nested_proofs
+                    .iter()
+                    .map(|(k, p)| k.to_owned() + p.compute_root().to_owned()),
+            )
+            .collect()
+    }
+
     /// Get the root of the merkelized map proof
     pub fn compute_root(&self) -> MKTreeNode {
         self.master_proof.root().to_owned()
@@ -217,25 +277,16 @@ impl<K: MKMapKey> MKMapProof<K> {
 
     /// Verify the merkelized map proof
     pub fn verify(&self) -> StdResult<()> {
-        for (_key, proof) in &self.sub_proofs {
-            proof
-                .verify()
-                .with_context(|| "MKMapProof could not verify sub proof")?;
-        }
+        MKMapProofBatchVerifier::new().verify(self)
+    }
 
-        self.master_proof
-            .verify()
-            .with_context(|| "MKMapProof could not verify master proof")?;
-        if !self.sub_proofs.is_empty() {
-            self.master_proof
-                .contains(
-                    &self
-                        .sub_proofs
-                        .iter()
-                        .map(|(k, p)| k.to_owned().into() + p.compute_root().to_owned())
-                        .collect::<Vec<_>>(),
-                )
-                .with_context(|| "MKMapProof could not match verified leaves of master proof")?;
+    /// Verify a batch of merkelized map proofs, see [MKMapProofBatchVerifier].
+    pub fn verify_batch(proofs: &[&MKMapProof<K>]) -> StdResult<()> {
+        let mut verifier = MKMapProofBatchVerifier::new();
+        for (index, proof) in proofs.iter().enumerate() {
+            verifier
+                .verify(proof)
+                .with_context(|| format!("MKMapProof could not verify proof at index {index}"))?;
         }
 
         Ok(())
@@ -248,12 +299,82 @@ impl<K: MKMapKey> MKMapProof<K> {
             .sub_proofs
             .iter()
             .any(|(_k, p)| p.contains(leaf).is_ok());
-        (master_proof_contains_leaf || sub_proofs_contain_leaf)
+        let nested_proofs_contain_leaf = self
+            .nested_proofs
+            .iter()
+            .any(|(_k, p)| p.contains(leaf).is_ok());
+        (master_proof_contains_leaf || sub_proofs_contain_leaf || nested_proofs_contain_leaf)
             .then_some(())
             .ok_or(anyhow!("MKMapProof does not contain leaf {:?}", leaf))
     }
 }
 
+/// Verifier of a batch of [MKMapProof] computed from the same merkelized map.
+///
+/// The proofs of a batch often share some of their parts: the master proofs of the proofs that
+/// cover the same entries of the map are identical, as are the sub proofs of the same leaves.
+/// Each distinct part is only verified once for the whole batch.
+pub struct MKMapProofBatchVerifier<'a, K: MKMapKey> {
+    verified_proofs: HashSet<&'a MKMapProof<K>>,
+    verified_master_proofs: HashSet<&'a MKProof>,
+    nested_verifier: Option<Box<MKMapProofBatchVerifier<'a, MKTreeNode>>>,
+}
+
+impl<'a, K: MKMapKey> MKMapProofBatchVerifier<'a, K> {
+    /// MKMapProofBatchVerifier factory
+    pub fn new() -> Self {
+        Self {
+            verified_proofs: HashSet::new(),
+            verified_master_proofs: HashSet::new(),
+            nested_verifier: None,
+        }
+    }
+
+    /// Verify the given merkelized map proof, reusing the verifications of the previous proofs
+    pub fn verify(&mut self, proof: &'a MKMapProof<K>) -> StdResult<()> {
+        if self.verified_proofs.contains(proof) {
+            return Ok(());
+        }
+
+        for (_key, sub_proof) in &proof.sub_proofs {
+            self.verify(sub_proof)
+                .with_context(|| "MKMapProof could not verify sub proof")?;
+        }
+        if !proof.nested_proofs.is_empty() {
+            let nested_verifier = self.nested_verifier.get_or_insert_with(Default::default);
+            for (_key, nested_proof) in &proof.nested_proofs {
+                nested_verifier
+                    .verify(nested_proof)
+                    .with_context(|| "MKMapProof could not verify nested proof")?;
+            }
+        }
+
+        if !self.verified_master_proofs.contains(&proof.master_proof) {
+            proof
+                .master_proof
+                .verify()
+                .with_context(|| "MKMapProof could not verify master proof")?;
+            self.verified_master_proofs.insert(&proof.master_proof);
+        }
+        let sub_proofs_leaves = proof.compute_sub_proofs_leaves();
+        if !sub_proofs_leaves.is_empty() {
+            proof
+                .master_proof
+                .contains(&sub_proofs_leaves)
+                .with_context(|| "MKMapProof could not match verified leaves of master proof")?;
+        }
+        self.verified_proofs.insert(proof);
+
+        Ok(())
+    }
+}
+
+impl<K: MKMapKey> Default for MKMapProofBatchVerifier<'_, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<K: MKMapKey> From<MKProof> for MKMapProof<K> {
     fn from(other: MKProof) -> Self {
         MKMapProof::new(other, BTreeMap::default())
@@ -356,7 +477,7 @@ mod tests {
     use std::collections::BTreeSet;
     use std::ops::Range;
 
-    use crate::entities::BlockRange;
+    use crate::entities::{BlockRange, Epoch};
 
     use super::*;
 
@@ -623,6 +744,52 @@ mod tests {
             .expect_err("MKMap should not compute proof for no leaves");
     }
 
+    #[test]
+    fn test_mk_map_should_verify_batch_of_valid_proofs() {
+        let entries = generate_merkle_trees(10, 3);
+        let mktree_nodes_to_certify = [
+            entries[0].1.leaves()[0].clone(),
+            entries[1].1.leaves()[1].clone(),
+            entries[2].1.leaves()[2].clone(),
+        ];
+        let merkle_tree_node_entries = &entries
+            .into_iter()
+            .map(|(range, mktree)| (range.to_owned(), mktree.into()))
+            .collect::<Vec<(_, MKMapNode<_>)>>();
+        let mk_map_full = MKMap::new(merkle_tree_node_entries.as_slice()).unwrap();
+        let mut mk_map_proofs = mktree_nodes_to_certify
+            .iter()
+            .map(|leaf| mk_map_full.compute_proof(&[leaf.to_owned()]).unwrap())
+            .collect::<Vec<_>>();
+        mk_map_proofs.push(mk_map_full.compute_proof(&mktree_nodes_to_certify).unwrap());
+        mk_map_proofs.push(mk_map_proofs[0].clone());
+
+        MKMapProof::verify_batch(&mk_map_proofs.iter().collect::<Vec<_>>()).unwrap();
+    }
+
+    #[test]
+    fn test_mk_map_should_not_verify_batch_containing_an_invalid_proof() {
+        let entries = generate_merkle_trees(10, 3);
+        let merkle_tree_node_entries = &entries
+            .iter()
+            .map(|(range, mktree)| (range.to_owned(), mktree.to_owned().into()))
+            .collect::<Vec<(_, MKMapNode<_>)>>();
+        let mk_map_full = MKMap::new(merkle_tree_node_entries.as_slice()).unwrap();
+        let mk_map_proof = mk_map_full
+            .compute_proof(&[entries[0].1.leaves()[0].clone()])
+            .unwrap();
+        let other_mk_map_proof = mk_map_full
+            .compute_proof(&[entries[1].1.leaves()[0].clone()])
+            .unwrap();
+        let invalid_mk_map_proof = MKMapProof::new(
+            mk_map_proof.master_proof.clone(),
+            BTreeMap::from_iter(other_mk_map_proof.sub_proofs.clone()),
+        );
+
+        MKMapProof::verify_batch(&[&mk_map_proof, &invalid_mk_map_proof])
+            .expect_err("MKMapProof batch with an invalid proof should not be verified");
+    }
+
     #[test]
     fn test_mk_map_should_compute_and_verify_valid_proof() {
         let entries = generate_merkle_trees(10, 3);
@@ -683,4 +850,79 @@ mod tests {
         let map_proof_root_expected = mk_map_full.compute_root().unwrap();
         assert_eq!(map_proof_root, map_proof_root_expected);
     }
+
+    fn generate_merkle_map_by_epochs(
+        entries: Vec<(BlockRange, MKTree)>,
+        block_ranges_per_epoch: usize,
+    ) -> MKMap<Epoch, MKMap<BlockRange, MKMapNode<BlockRange>>> {
+        let epoch_entries = entries
+            .into_iter()
+            .map(|(range, mktree)| (range, mktree.into()))
+            .collect::<Vec<(_, MKMapNode<_>)>>()
+            .chunks(block_ranges_per_epoch)
+            .enumerate()
+            .map(|(epoch, entries)| (Epoch(epoch as u64), MKMap::new(entries).unwrap()))
+            .collect::<Vec<_>>();
+
+        MKMap::new(epoch_entries.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_mk_map_should_compute_and_verify_valid_proof_with_nested_maps_of_other_key_type() {
+        let entries = generate_merkle_trees(60, 3);
+        let mktree_nodes_to_certify = [
+            entries[0].1.leaves()[0].clone(),
+            entries[1].1.leaves()[2].clone(),
+            entries[7].1.leaves()[1].clone(),
+            entries[19].1.leaves()[0].clone(),
+        ];
+        let mk_map_full = generate_merkle_map_by_epochs(entries, 5);
+
+        let mk_map_proof = mk_map_full.compute_proof(&mktree_nodes_to_certify).unwrap();
+
+        mk_map_proof.verify().unwrap();
+        for leaf in &mktree_nodes_to_certify {
+            mk_map_proof.contains(leaf).unwrap();
+        }
+        let map_proof_root = mk_map_proof.compute_root();
+        let map_proof_root_expected = mk_map_full.compute_root().unwrap();
+        assert_eq!(map_proof_root, map_proof_root_expected);
+    }
+
+    #[test]
+    fn test_mk_map_proof_with_nested_maps_of_other_key_type_should_be_serializable() {
+        let entries = generate_merkle_trees(60, 3);
+        let leaf_to_certify = entries[7].1.leaves()[1].clone();
+        let mk_map_full = generate_merkle_map_by_epochs(entries, 5);
+        let mk_map_proof = mk_map_full
+            .compute_proof(&[leaf_to_certify.clone()])
+            .unwrap();
+
+        let serialized_proof = serde_json::to_string(&mk_map_proof).unwrap();
+        let deserialized_proof: MKMapProof<Epoch> =
+            serde_json::from_str(&serialized_proof).unwrap();
+
+        assert_eq!(mk_map_proof, deserialized_proof);
+        deserialized_proof.verify().unwrap();
+        deserialized_proof.contains(&leaf_to_certify).unwrap();
+    }
+
+    #[test]
+    fn test_mk_map_should_not_verify_proof_with_tampered_nested_proof() {
+        let entries = generate_merkle_trees(60, 3);
+        let mk_map_full = generate_merkle_map_by_epochs(entries.clone(), 5);
+        let mk_map_proof = mk_map_full
+            .compute_proof(&[entries[7].1.leaves()[1].clone()])
+            .unwrap();
+        let other_mk_map_proof = mk_map_full
+            .compute_proof(&[entries[8].1.leaves()[1].clone()])
+            .unwrap();
+        let mut invalid_mk_map_proof = mk_map_proof.clone();
+        invalid_mk_map_proof.sub_proofs[0].1.nested_proofs =
+            other_mk_map_proof.sub_proofs[0].1.nested_proofs.clone();
+
+        invalid_mk_map_proof
+            .verify()
+            .expect_err("MKMapProof with a tampered nested proof should not be verified");
+    }
 }
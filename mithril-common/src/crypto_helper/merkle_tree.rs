@@ -120,7 +120,7 @@ impl Merge for MergeMKTreeNode {
 }
 
 /// A Merkle proof
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct MKProof {
     inner_root: Arc<MKTreeNode>,
     inner_leaves: Vec<(MKTreeLeafPosition, Arc<MKTreeNode>)>,
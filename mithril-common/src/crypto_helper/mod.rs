@@ -7,6 +7,8 @@ mod era;
 mod genesis;
 mod merkle_map;
 mod merkle_tree;
+mod request_origin;
+mod signing_provider;
 mod types;
 
 cfg_test_tools! {
@@ -18,8 +20,11 @@ cfg_random! {
 }
 
 pub use cardano::{
-    KESPeriod, OpCert, ProtocolInitializerErrorWrapper, ProtocolRegistrationErrorWrapper,
-    SerDeShelleyFileFormat, Sum6KesBytes,
+    compute_operational_certificate_kes_period, compute_registration_nonce,
+    compute_registration_nonce_message, compute_signer_unregistration_message,
+    sign_registration_nonce_message, verify_registration_nonce_signature, KESPeriod, OpCert,
+    ProtocolInitializerErrorWrapper, ProtocolRegistrationErrorWrapper, SerDeShelleyFileFormat,
+    Sum6KesBytes,
 };
 pub use codec::*;
 pub use era::{
@@ -27,8 +32,12 @@ pub use era::{
     EraMarkersVerifierSignature, EraMarkersVerifierVerificationKey,
 };
 pub use genesis::{ProtocolGenesisError, ProtocolGenesisSigner, ProtocolGenesisVerifier};
-pub use merkle_map::{MKMap, MKMapKey, MKMapNode, MKMapProof, MKMapValue};
+pub use merkle_map::{MKMap, MKMapKey, MKMapNode, MKMapProof, MKMapProofBatchVerifier, MKMapValue};
 pub use merkle_tree::{MKProof, MKTree, MKTreeNode, MKTreeStore};
+pub use request_origin::{compute_request_origin_message, verify_request_origin_signature};
+#[cfg(feature = "gcp_kms")]
+pub use signing_provider::GcpKmsSigningProvider;
+pub use signing_provider::SigningProvider;
 pub use types::*;
 
 /// The current protocol version
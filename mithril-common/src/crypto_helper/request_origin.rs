@@ -0,0 +1,140 @@
+//! Authentication of the origin of the requests sending single signatures.
+//!
+//! Along with a request, each signer of its single signatures sends a signature of them and of
+//! the date and time of the request, made with the signing key of the verification key it
+//! registered for the current epoch. The aggregator checks it with this verification key, and only
+//! accepts it shortly after it was made so that it can not be replayed later.
+//!
+//! The single signatures of a signer are signed on their own, not the whole request body, so that
+//! the single signatures of several signers can be sent in the same request.
+
+use anyhow::anyhow;
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use chrono::{DateTime, Utc};
+
+use crate::crypto_helper::{ProtocolSignerMessageSignature, ProtocolSignerVerificationKey};
+use crate::entities::{HexEncodedSingleSignature, PartyId};
+use crate::StdResult;
+
+/// Compute the message signed by a signer, at the given date and time, to prove that it is the
+/// origin of a request sending the given single signatures of its own.
+pub fn compute_request_origin_message(
+    party_id: &PartyId,
+    signed_at: DateTime<Utc>,
+    single_signatures: &[HexEncodedSingleSignature],
+) -> Vec<u8> {
+    let mut hasher = Blake2b::<U32>::new()
+        .chain_update(b"mithril-request-origin")
+        .chain_update((party_id.len() as u64).to_be_bytes())
+        .chain_update(party_id.as_bytes())
+        .chain_update(signed_at.timestamp().to_be_bytes())
+        .chain_update(signed_at.timestamp_subsec_nanos().to_be_bytes());
+    for single_signature in single_signatures {
+        hasher.update((single_signature.len() as u64).to_be_bytes());
+        hasher.update(single_signature.as_bytes());
+    }
+
+    hasher.finalize().to_vec()
+}
+
+/// Verify the signature of a request origin message with the verification key registered by the
+/// signer.
+pub fn verify_request_origin_signature(
+    signature: &ProtocolSignerMessageSignature,
+    verification_key: &ProtocolSignerVerificationKey,
+    message: &[u8],
+) -> StdResult<()> {
+    signature
+        .verify(message, &verification_key.vk)
+        .map_err(|_| {
+            anyhow!("The request origin was not signed by the owner of the verification key")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::MithrilFixtureBuilder;
+
+    use super::*;
+
+    fn signed_at() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-08-02T12:35:10.123456789Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn request_origin_message_depends_on_the_party_id_the_date_and_the_single_signatures() {
+        let party_id = "party_id".to_string();
+        let single_signatures = vec!["signature-1".to_string(), "signature-2".to_string()];
+        let message = compute_request_origin_message(&party_id, signed_at(), &single_signatures);
+
+        assert_eq!(
+            message,
+            compute_request_origin_message(&party_id, signed_at(), &single_signatures)
+        );
+        assert_ne!(
+            message,
+            compute_request_origin_message(
+                &"another_party_id".to_string(),
+                signed_at(),
+                &single_signatures
+            )
+        );
+        assert_ne!(
+            message,
+            compute_request_origin_message(
+                &party_id,
+                signed_at() + chrono::Duration::nanoseconds(1),
+                &single_signatures
+            )
+        );
+        assert_ne!(
+            message,
+            compute_request_origin_message(&party_id, signed_at(), &single_signatures[..1])
+        );
+        assert_ne!(
+            message,
+            compute_request_origin_message(
+                &party_id,
+                signed_at(),
+                &["signature-1signature-2".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn verify_a_request_origin_signature_with_the_verification_key_of_the_signer() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let signers = fixture.signers_fixture();
+        let message = compute_request_origin_message(
+            &signers[0].party_id(),
+            signed_at(),
+            &["signature".to_string()],
+        );
+        let signature = signers[0].protocol_initializer.sign_message(&message);
+
+        verify_request_origin_signature(
+            &signature,
+            &signers[0].signer_with_stake.verification_key,
+            &message,
+        )
+        .expect("The request origin signature should be valid");
+        verify_request_origin_signature(
+            &signature,
+            &signers[1].signer_with_stake.verification_key,
+            &message,
+        )
+        .expect_err("The signature should be invalid for another verification key");
+        verify_request_origin_signature(
+            &signature,
+            &signers[0].signer_with_stake.verification_key,
+            &compute_request_origin_message(
+                &signers[0].party_id(),
+                signed_at(),
+                &["another signature".to_string()],
+            ),
+        )
+        .expect_err("The signature of another message should be invalid");
+    }
+}
@@ -0,0 +1,275 @@
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{json, Value};
+
+use crate::crypto_helper::{ProtocolGenesisSignature, ProtocolGenesisVerificationKey};
+use crate::StdResult;
+
+use super::SigningProvider;
+
+/// Environment variable holding an OAuth 2.0 access token used to authenticate to GCP
+const ACCESS_TOKEN_VARIABLE: &str = "GOOGLE_OAUTH_ACCESS_TOKEN";
+
+/// Endpoint of the metadata server providing the access token of the instance service account
+const METADATA_TOKEN_ENDPOINT: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Endpoint of the GCP Cloud KMS API
+const CLOUD_KMS_ENDPOINT: &str = "https://cloudkms.googleapis.com/v1";
+
+/// Algorithm of the Cloud KMS keys that can be used by the provider
+const ED25519_ALGORITHM: &str = "EC_SIGN_ED25519";
+
+/// DER prefix of an Ed25519 `SubjectPublicKeyInfo`, followed by the 32 bytes of the key
+const ED25519_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+/// A [SigningProvider] that signs with an `EC_SIGN_ED25519` key version kept in
+/// [GCP Cloud KMS](https://cloud.google.com/kms).
+///
+/// The key version is given by its resource name:
+/// `projects/<project>/locations/<location>/keyRings/<key_ring>/cryptoKeys/<key>/cryptoKeyVersions/<version>`.
+///
+/// The access token is read from the `GOOGLE_OAUTH_ACCESS_TOKEN` environment variable, or from
+/// the metadata server of the instance if it's not set.
+pub struct GcpKmsSigningProvider {
+    key_version_name: String,
+    client: reqwest::Client,
+}
+
+impl GcpKmsSigningProvider {
+    /// GcpKmsSigningProvider factory
+    pub fn new(key_version_name: &str) -> StdResult<Self> {
+        let segments: Vec<&str> = key_version_name.split('/').collect();
+        let is_key_version_name = segments.len() == 10
+            && segments.iter().step_by(2).eq(&[
+                "projects",
+                "locations",
+                "keyRings",
+                "cryptoKeys",
+                "cryptoKeyVersions",
+            ])
+            && segments.iter().all(|segment| !segment.is_empty());
+        if !is_key_version_name {
+            return Err(anyhow!(
+                "Invalid GCP KMS key version name, expected 'projects/<project>/locations/<location>/keyRings/<key_ring>/cryptoKeys/<key>/cryptoKeyVersions/<version>': '{key_version_name}'"
+            ));
+        }
+
+        Ok(Self {
+            key_version_name: key_version_name.to_string(),
+            client: reqwest::Client::new(),
+        })
+    }
+
+    async fn access_token(&self) -> StdResult<String> {
+        if let Ok(token) = std::env::var(ACCESS_TOKEN_VARIABLE) {
+            return Ok(token);
+        }
+
+        let response = self
+            .client
+            .get(METADATA_TOKEN_ENDPOINT)
+            .header("Metadata-Flavor", "Google")
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| {
+                format!("Could not get a GCP access token: '{ACCESS_TOKEN_VARIABLE}' is not set and the metadata server is not reachable")
+            })?
+            .json::<Value>()
+            .await
+            .with_context(|| "Could not parse the GCP access token from the metadata server")?;
+
+        response["access_token"]
+            .as_str()
+            .map(|token| token.to_string())
+            .ok_or_else(|| anyhow!("No access token in the metadata server response"))
+    }
+
+    fn decode_signature(response: &Value) -> StdResult<ProtocolGenesisSignature> {
+        let signature = response["signature"]
+            .as_str()
+            .ok_or_else(|| anyhow!("No signature in the GCP KMS response"))?;
+        let signature = STANDARD
+            .decode(signature)
+            .with_context(|| "Could not decode the GCP KMS signature")?;
+
+        ProtocolGenesisSignature::from_bytes(&signature)
+    }
+
+    fn decode_public_key(response: &Value) -> StdResult<ProtocolGenesisVerificationKey> {
+        match response["algorithm"].as_str() {
+            Some(ED25519_ALGORITHM) => {}
+            algorithm => {
+                return Err(anyhow!(
+                    "The GCP KMS key algorithm must be '{ED25519_ALGORITHM}', got: '{}'",
+                    algorithm.unwrap_or_default()
+                ))
+            }
+        }
+        let pem = response["pem"]
+            .as_str()
+            .ok_or_else(|| anyhow!("No public key in the GCP KMS response"))?;
+        let der: String = pem
+            .lines()
+            .filter(|line| !line.starts_with("-----"))
+            .collect();
+        let der = STANDARD
+            .decode(der.trim())
+            .with_context(|| "Could not decode the GCP KMS public key")?;
+        let key_bytes: [u8; 32] = der
+            .strip_prefix(&ED25519_SPKI_PREFIX)
+            .and_then(|key| key.try_into().ok())
+            .ok_or_else(|| anyhow!("The GCP KMS public key is not an Ed25519 key"))?;
+        let verification_key = ed25519_dalek::VerifyingKey::from_bytes(&key_bytes)
+            .with_context(|| "Invalid GCP KMS Ed25519 public key")?;
+
+        Ok(verification_key.into())
+    }
+}
+
+#[async_trait]
+impl SigningProvider for GcpKmsSigningProvider {
+    async fn sign(&self, message: &[u8]) -> StdResult<ProtocolGenesisSignature> {
+        let token = self.access_token().await?;
+
+        let response = self
+            .client
+            .post(format!(
+                "{CLOUD_KMS_ENDPOINT}/{}:asymmetricSign",
+                self.key_version_name
+            ))
+            .bearer_auth(token)
+            .json(&json!({ "data": STANDARD.encode(message) }))
+            .send()
+            .await
+            .with_context(|| "Could not reach GCP KMS")?
+            .error_for_status()
+            .with_context(|| {
+                format!(
+                    "Could not sign with the GCP KMS key: '{}'",
+                    self.key_version_name
+                )
+            })?
+            .json::<Value>()
+            .await
+            .with_context(|| "Could not parse the GCP KMS signature response")?;
+
+        Self::decode_signature(&response)
+    }
+
+    async fn verification_key(&self) -> StdResult<ProtocolGenesisVerificationKey> {
+        let token = self.access_token().await?;
+
+        let response = self
+            .client
+            .get(format!(
+                "{CLOUD_KMS_ENDPOINT}/{}/publicKey",
+                self.key_version_name
+            ))
+            .bearer_auth(token)
+            .send()
+            .await
+            .with_context(|| "Could not reach GCP KMS")?
+            .error_for_status()
+            .with_context(|| {
+                format!(
+                    "Could not get the public key of the GCP KMS key: '{}'",
+                    self.key_version_name
+                )
+            })?
+            .json::<Value>()
+            .await
+            .with_context(|| "Could not parse the GCP KMS public key response")?;
+
+        Self::decode_public_key(&response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypto_helper::ProtocolGenesisSigner;
+
+    use super::*;
+
+    fn public_key_response(algorithm: &str, der: &[u8]) -> Value {
+        let encoded = STANDARD.encode(der);
+        json!({
+            "algorithm": algorithm,
+            "pem": format!("-----BEGIN PUBLIC KEY-----\n{encoded}\n-----END PUBLIC KEY-----\n"),
+        })
+    }
+
+    #[test]
+    fn new_rejects_malformed_key_version_names() {
+        GcpKmsSigningProvider::new(
+            "projects/mithril/locations/global/keyRings/genesis/cryptoKeys/genesis/cryptoKeyVersions/1",
+        )
+        .expect("A key version name should be accepted");
+
+        for name in [
+            "genesis",
+            "projects/mithril/locations/global/keyRings/genesis/cryptoKeys/genesis",
+            "projects/mithril/locations/global/keyRings/genesis/cryptoKeys/genesis/cryptoKeyVersions/",
+            "projects/mithril/regions/global/keyRings/genesis/cryptoKeys/genesis/cryptoKeyVersions/1",
+        ] {
+            assert!(
+                GcpKmsSigningProvider::new(name).is_err(),
+                "The key version name '{name}' should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_signature_of_kms_response() {
+        let signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let signature = signer.sign(b"message");
+        let response = json!({ "signature": STANDARD.encode(signature.to_bytes()) });
+
+        assert_eq!(
+            signature,
+            GcpKmsSigningProvider::decode_signature(&response).unwrap()
+        );
+        GcpKmsSigningProvider::decode_signature(&json!({}))
+            .expect_err("A response without signature should be rejected");
+    }
+
+    #[test]
+    fn decode_public_key_of_kms_response() {
+        let signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let verification_key = signer.create_genesis_verifier().to_verification_key();
+        let der = [
+            ED25519_SPKI_PREFIX.as_slice(),
+            verification_key.as_bytes().as_slice(),
+        ]
+        .concat();
+
+        assert_eq!(
+            verification_key,
+            GcpKmsSigningProvider::decode_public_key(&public_key_response(ED25519_ALGORITHM, &der))
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn decode_public_key_rejects_non_ed25519_keys() {
+        let signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let verification_key = signer.create_genesis_verifier().to_verification_key();
+        let der = [
+            ED25519_SPKI_PREFIX.as_slice(),
+            verification_key.as_bytes().as_slice(),
+        ]
+        .concat();
+
+        GcpKmsSigningProvider::decode_public_key(&public_key_response("EC_SIGN_P256_SHA256", &der))
+            .expect_err("A key with another algorithm should be rejected");
+        GcpKmsSigningProvider::decode_public_key(&public_key_response(
+            ED25519_ALGORITHM,
+            &der[1..],
+        ))
+        .expect_err("A public key that is not an Ed25519 SPKI should be rejected");
+    }
+}
@@ -0,0 +1,61 @@
+//! Providers of the signatures made with the signing material held by the aggregator
+//!
+//! The signing key can either be held locally, or kept in an external KMS / HSM that signs the
+//! messages on behalf of the aggregator without ever exposing the key.
+
+#[cfg(feature = "gcp_kms")]
+mod gcp_kms;
+
+use async_trait::async_trait;
+
+use crate::crypto_helper::{
+    ProtocolGenesisSignature, ProtocolGenesisSigner, ProtocolGenesisVerificationKey,
+};
+use crate::StdResult;
+
+#[cfg(feature = "gcp_kms")]
+#[cfg_attr(docsrs, doc(cfg(feature = "gcp_kms")))]
+pub use gcp_kms::GcpKmsSigningProvider;
+
+/// A SigningProvider signs messages with an Ed25519 key
+#[async_trait]
+pub trait SigningProvider: Send + Sync {
+    /// Sign a message and returns a [ProtocolGenesisSignature]
+    async fn sign(&self, message: &[u8]) -> StdResult<ProtocolGenesisSignature>;
+
+    /// Get the verification key of the signatures made by the provider
+    async fn verification_key(&self) -> StdResult<ProtocolGenesisVerificationKey>;
+}
+
+#[async_trait]
+impl SigningProvider for ProtocolGenesisSigner {
+    async fn sign(&self, message: &[u8]) -> StdResult<ProtocolGenesisSignature> {
+        Ok(ProtocolGenesisSigner::sign(self, message))
+    }
+
+    async fn verification_key(&self) -> StdResult<ProtocolGenesisVerificationKey> {
+        Ok(self.create_genesis_verifier().to_verification_key())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypto_helper::ProtocolGenesisVerifier;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn local_signing_provider_signatures_are_verified_with_its_verification_key() {
+        let signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let provider: &dyn SigningProvider = &signer;
+        let message = b"genesis payload";
+
+        let signature = provider.sign(message).await.unwrap();
+        let verification_key = provider.verification_key().await.unwrap();
+
+        assert_eq!(signer.sign(message), signature);
+        ProtocolGenesisVerifier::from_verification_key(verification_key)
+            .verify(message, &signature)
+            .expect("The signature should be verified with the provider verification key");
+    }
+}
@@ -1,7 +1,9 @@
 use anyhow::Context;
 use hex::{FromHex, ToHex};
 use kes_summed_ed25519::kes::Sum6KesSig;
-use mithril_stm::stm::{StmAggrSig, StmAggrVerificationKey, StmSig, StmVerificationKeyPoP};
+use mithril_stm::stm::{
+    StmAggrSig, StmAggrVerificationKey, StmMessageSignature, StmSig, StmVerificationKeyPoP,
+};
 
 use crate::crypto_helper::{MKMapProof, MKProof, OpCert, ProtocolKey, ProtocolKeyCodec, D};
 use crate::entities::BlockRange;
@@ -18,6 +20,10 @@ pub type ProtocolSignerVerificationKeySignature = ProtocolKey<Sum6KesSig>;
 /// Wrapper of [MithrilStm:StmSig](type@StmSig) to add serialization utilities.
 pub type ProtocolSingleSignature = ProtocolKey<StmSig>;
 
+/// Wrapper of [MithrilStm:StmMessageSignature](type@StmMessageSignature) to add serialization
+/// utilities.
+pub type ProtocolSignerMessageSignature = ProtocolKey<StmMessageSignature>;
+
 /// Wrapper of [MithrilStm:StmAggrSig](struct@StmAggrSig) to add serialization utilities.
 pub type ProtocolMultiSignature = ProtocolKey<StmAggrSig<D>>;
 
@@ -85,6 +91,6 @@ impl ProtocolKeyCodec<ed25519_dalek::Signature> for ed25519_dalek::Signature {
 impl_codec_and_type_conversions_for_protocol_key!(
     json_hex_codec => StmVerificationKeyPoP, Sum6KesSig, StmSig, StmAggrSig<D>, OpCert,
         ed25519_dalek::VerifyingKey, ed25519_dalek::SigningKey, StmAggrVerificationKey<D>,
-        MKProof
+        MKProof, StmMessageSignature
 );
 impl_codec_and_type_conversions_for_protocol_key!(no_default_codec => ed25519_dalek::Signature);
@@ -4,11 +4,22 @@ use crate::{
         ImmutableFile,
     },
     entities::{CardanoDbBeacon, HexEncodedDigest, ImmutableFileName},
+    StdResult,
 };
+use anyhow::Context;
 use async_trait::async_trait;
+use rayon::{prelude::*, ThreadPool, ThreadPoolBuilder};
 use sha2::{Digest, Sha256};
 use slog::{debug, info, warn, Logger};
-use std::{collections::BTreeMap, io, path::Path, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    io,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 /// Result of a cache computation, contains the digest and the list of new entries to add
 /// to the [ImmutableFileDigestCacheProvider].
@@ -22,6 +33,9 @@ pub struct CardanoImmutableDigester {
 
     /// The logger where the logs should be written
     logger: Logger,
+
+    /// Thread pool used to hash the immutable files, the global rayon thread pool is used if none
+    thread_pool: Option<Arc<ThreadPool>>,
 }
 
 impl CardanoImmutableDigester {
@@ -33,8 +47,26 @@ impl CardanoImmutableDigester {
         Self {
             cache_provider,
             logger,
+            thread_pool: None,
         }
     }
+
+    /// Set the number of threads used to hash the immutable files, if not set the global rayon
+    /// thread pool is used.
+    pub fn with_thread_pool_size(mut self, number_of_threads: usize) -> StdResult<Self> {
+        let thread_pool = ThreadPoolBuilder::new()
+            .num_threads(number_of_threads)
+            .thread_name(|index| format!("immutable-digester-{index}"))
+            .build()
+            .with_context(|| {
+                format!(
+                    "Immutable digester can not build a thread pool of {number_of_threads} threads"
+                )
+            })?;
+        self.thread_pool = Some(Arc::new(thread_pool));
+
+        Ok(self)
+    }
 }
 
 #[async_trait]
@@ -83,9 +115,14 @@ impl ImmutableDigester for CardanoImmutableDigester {
                 // digest is done in a separate thread because it is blocking the whole task
                 let logger = self.logger.clone();
                 let thread_beacon = beacon.clone();
+                let thread_pool = self.thread_pool.clone();
                 let (hash, new_cache_entries) =
                     tokio::task::spawn_blocking(move || -> CacheComputationResult {
-                        compute_hash(logger, &thread_beacon, cached_values)
+                        match thread_pool {
+                            Some(thread_pool) => thread_pool
+                                .install(|| compute_hash(logger, &thread_beacon, cached_values)),
+                            None => compute_hash(logger, &thread_beacon, cached_values),
+                        }
                     })
                     .await
                     .map_err(|e| ImmutableDigesterError::DigestComputationError(e.into()))??;
@@ -113,35 +150,60 @@ fn compute_hash(
     beacon: &CardanoDbBeacon,
     entries: BTreeMap<ImmutableFile, Option<HexEncodedDigest>>,
 ) -> CacheComputationResult {
-    let mut hasher = Sha256::new();
-    let mut new_cached_entries = Vec::new();
-    let mut progress = Progress {
-        index: 0,
-        total: entries.len(),
-    };
+    let new_cached_entries = hash_uncached_entries(&logger, &entries)?;
+    let mut new_digests = new_cached_entries.iter().map(|(_, digest)| digest);
 
+    // The files are hashed in parallel but their digests are chained in the order of the
+    // immutable files so the final digest stays deterministic.
+    let mut hasher = Sha256::new();
     hasher.update(beacon.compute_hash().as_bytes());
-
-    for (ix, (entry, cache)) in entries.iter().enumerate() {
+    for cache in entries.values() {
         match cache {
             None => {
-                let data = hex::encode(entry.compute_raw_hash::<Sha256>()?);
-                hasher.update(&data);
-                new_cached_entries.push((entry.filename.clone(), data));
+                // The uncached entries have been hashed in the same order
+                hasher.update(new_digests.next().unwrap());
             }
             Some(digest) => {
                 hasher.update(digest);
             }
         };
-
-        if progress.report(ix) {
-            info!(logger, "hashing: {}", &progress);
-        }
     }
 
     Ok((hasher.finalize().into(), new_cached_entries))
 }
 
+/// Hash in parallel, in the current rayon thread pool, the entries without a cached digest.
+///
+/// The digests are returned in the order of the entries.
+fn hash_uncached_entries(
+    logger: &Logger,
+    entries: &BTreeMap<ImmutableFile, Option<HexEncodedDigest>>,
+) -> Result<Vec<(ImmutableFileName, HexEncodedDigest)>, io::Error> {
+    let uncached_entries: Vec<&ImmutableFile> = entries
+        .iter()
+        .filter_map(|(entry, cache)| cache.is_none().then_some(entry))
+        .collect();
+    let total = uncached_entries.len();
+    let hashed = AtomicUsize::new(0);
+
+    uncached_entries
+        .into_par_iter()
+        .map(|entry| -> Result<_, io::Error> {
+            let data = hex::encode(entry.compute_raw_hash::<Sha256>()?);
+
+            let mut progress = Progress {
+                index: hashed.fetch_add(1, Ordering::Relaxed),
+                total,
+            };
+            if progress.report(progress.index) {
+                info!(logger, "hashing: {}", &progress);
+            }
+
+            Ok((entry.filename.clone(), data))
+        })
+        .collect()
+}
+
 struct Progress {
     index: usize,
     total: usize,
@@ -175,7 +237,7 @@ mod tests {
                 MemoryImmutableFileDigestCacheProvider, MockImmutableFileDigestCacheProvider,
             },
             CardanoImmutableDigester, DummyImmutablesDbBuilder, ImmutableDigester,
-            ImmutableDigesterError,
+            ImmutableDigesterError, ImmutableFileLayout,
         },
         entities::{CardanoDbBeacon, ImmutableFileNumber},
         test_utils::TestLogger,
@@ -329,6 +391,30 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn can_compute_digest_of_immutable_files_with_v2_layout() {
+        let immutable_db = db_builder("can_compute_digest_of_immutable_files_with_v2_layout")
+            .with_layout(ImmutableFileLayout::V2)
+            .with_immutables(&[1, 2, 3])
+            .append_immutable_trio()
+            .build();
+        let cache = Arc::new(MemoryImmutableFileDigestCacheProvider::default());
+        let digester = CardanoImmutableDigester::new(Some(cache.clone()), TestLogger::stdout());
+        let beacon = CardanoDbBeacon::new("devnet".to_string(), 1, 3);
+
+        digester
+            .compute_digest(&immutable_db.dir, &beacon)
+            .await
+            .expect("compute_digest must not fail");
+
+        let cached_entries = cache
+            .get(immutable_db.immutables_files.clone())
+            .await
+            .expect("Cache read should not fail");
+        assert_eq!(12, cached_entries.len());
+        assert!(cached_entries.values().all(|digest| digest.is_some()));
+    }
+
     #[tokio::test]
     async fn digests_are_stored_into_cache_provider() {
         let immutable_db = db_builder("digests_are_stored_into_cache_provider")
@@ -440,6 +526,46 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn computed_digest_is_the_same_whatever_the_number_of_threads() {
+        let immutable_db = db_builder("computed_digest_is_the_same_whatever_the_number_of_threads")
+            .with_immutables(&(1..=10).collect::<Vec<ImmutableFileNumber>>())
+            .append_immutable_trio()
+            .build();
+        let logger = TestLogger::stdout();
+        let single_thread_digester = CardanoImmutableDigester::new(None, logger.clone())
+            .with_thread_pool_size(1)
+            .unwrap();
+        let multi_threads_digester = CardanoImmutableDigester::new(
+            Some(Arc::new(MemoryImmutableFileDigestCacheProvider::default())),
+            logger.clone(),
+        )
+        .with_thread_pool_size(4)
+        .unwrap();
+
+        // Fill the cache with the first immutables only so the next computation mixes cached
+        // and uncached digests
+        multi_threads_digester
+            .compute_digest(
+                &immutable_db.dir,
+                &CardanoDbBeacon::new("devnet".to_string(), 1, 5),
+            )
+            .await
+            .expect("compute_digest must not fail");
+
+        let beacon = CardanoDbBeacon::new("devnet".to_string(), 1, 10);
+        let single_thread_digest = single_thread_digester
+            .compute_digest(&immutable_db.dir, &beacon)
+            .await
+            .expect("compute_digest must not fail");
+        let multi_threads_digest = multi_threads_digester
+            .compute_digest(&immutable_db.dir, &beacon)
+            .await
+            .expect("compute_digest must not fail");
+
+        assert_eq!(single_thread_digest, multi_threads_digest);
+    }
+
     #[tokio::test]
     async fn cache_read_failure_dont_block_computation() {
         let immutable_db = db_builder("cache_read_failure_dont_block_computation")
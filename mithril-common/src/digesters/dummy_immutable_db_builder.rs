@@ -1,5 +1,8 @@
 use crate::test_utils::TempDir;
-use crate::{digesters::ImmutableFile, entities::ImmutableFileNumber};
+use crate::{
+    digesters::{ImmutableFile, ImmutableFileLayout},
+    entities::ImmutableFileNumber,
+};
 use std::{
     fs::File,
     io::prelude::Write,
@@ -13,6 +16,7 @@ pub struct DummyImmutablesDbBuilder {
     non_immutables_to_write: Vec<String>,
     append_uncompleted_trio: bool,
     file_size: Option<u64>,
+    layout: ImmutableFileLayout,
 }
 
 /// A dummy cardano immutable db.
@@ -23,13 +27,15 @@ pub struct DummyImmutableDb {
     pub immutables_files: Vec<ImmutableFile>,
     /// Files that doesn't follow the immutable file name scheme in the dummy cardano db.
     pub non_immutables_files: Vec<PathBuf>,
+    /// The [layout][ImmutableFileLayout] of the immutable files in the dummy cardano db.
+    pub layout: ImmutableFileLayout,
 }
 
 impl DummyImmutableDb {
     /// Add an immutable chunk file and its primary & secondary to the dummy DB.
     pub fn add_immutable_file(&mut self) -> ImmutableFileNumber {
         let new_file_number = self.last_immutable_number().unwrap_or(0) + 1;
-        let mut new_files = write_immutable_trio(None, &self.dir, new_file_number);
+        let mut new_files = write_immutable_trio(None, &self.dir, new_file_number, self.layout);
 
         self.immutables_files.append(&mut new_files);
 
@@ -52,9 +58,18 @@ impl DummyImmutablesDbBuilder {
             non_immutables_to_write: vec![],
             append_uncompleted_trio: false,
             file_size: None,
+            layout: ImmutableFileLayout::default(),
         }
     }
 
+    /// Set the [layout][ImmutableFileLayout] of the immutable files written by [build][Self::build].
+    ///
+    /// Note: by default the files follow the [ImmutableFileLayout::V1] layout.
+    pub fn with_layout(&mut self, layout: ImmutableFileLayout) -> &mut Self {
+        self.layout = layout;
+        self
+    }
+
     /// Set the immutables file number that will be used to generate the immutable files, for each
     /// number three files will be generated (a 'chunk', a 'primary' and a 'secondary' file).
     pub fn with_immutables(&mut self, immutables: &[ImmutableFileNumber]) -> &mut Self {
@@ -98,6 +113,7 @@ impl DummyImmutablesDbBuilder {
                     None => 0,
                     Some(last) => last + 1,
                 },
+                self.layout,
             );
         }
 
@@ -109,9 +125,10 @@ impl DummyImmutablesDbBuilder {
             dir: self.dir.clone(),
             immutables_files: immutable_numbers
                 .into_iter()
-                .flat_map(|ifn| write_immutable_trio(self.file_size, &self.dir, ifn))
+                .flat_map(|ifn| write_immutable_trio(self.file_size, &self.dir, ifn, self.layout))
                 .collect::<Vec<_>>(),
             non_immutables_files,
+            layout: self.layout,
         }
     }
 
@@ -124,13 +141,10 @@ fn write_immutable_trio(
     optional_size: Option<u64>,
     dir: &Path,
     immutable: ImmutableFileNumber,
+    layout: ImmutableFileLayout,
 ) -> Vec<ImmutableFile> {
     let mut result = vec![];
-    for filename in [
-        format!("{immutable:05}.chunk"),
-        format!("{immutable:05}.primary"),
-        format!("{immutable:05}.secondary"),
-    ] {
+    for filename in layout.file_names(immutable) {
         let file = write_dummy_file(optional_size, dir, &filename);
         result.push(ImmutableFile {
             number: immutable.to_owned(),
@@ -1,5 +1,6 @@
 use crate::entities::{ImmutableFileName, ImmutableFileNumber};
 
+use crate::digesters::ImmutableFileLayout;
 use crate::digesters::ImmutableFileListingError::MissingImmutableFolder;
 use digest::{Digest, Output};
 use std::{
@@ -12,13 +13,8 @@ use std::{
 use thiserror::Error;
 use walkdir::WalkDir;
 
-const IMMUTABLE_FILE_EXTENSIONS: [&str; 3] = ["chunk", "primary", "secondary"];
-
 fn is_immutable(entry: &walkdir::DirEntry) -> bool {
-    let is_file = entry.file_type().is_file();
-    let extension = entry.path().extension().map(|e| e.to_string_lossy());
-
-    is_file && extension.is_some_and(|e| IMMUTABLE_FILE_EXTENSIONS.contains(&e.as_ref()))
+    entry.file_type().is_file() && ImmutableFileLayout::of_file(entry.path()).is_some()
 }
 
 /// Walk the given path and return the first directory named "immutable" it finds
@@ -83,8 +79,18 @@ pub enum ImmutableFileListingError {
 }
 
 impl ImmutableFile {
-    /// ImmutableFile factory
+    /// ImmutableFile factory, the [layout][ImmutableFileLayout] is deduced from the file name.
     pub fn new(path: PathBuf) -> Result<ImmutableFile, ImmutableFileCreationError> {
+        let layout = ImmutableFileLayout::of_file(&path).unwrap_or_default();
+
+        Self::new_with_layout(path, layout)
+    }
+
+    /// ImmutableFile factory for a file following the given [layout][ImmutableFileLayout].
+    pub fn new_with_layout(
+        path: PathBuf,
+        layout: ImmutableFileLayout,
+    ) -> Result<ImmutableFile, ImmutableFileCreationError> {
         let filename = path
             .file_name()
             .ok_or(ImmutableFileCreationError::FileNameExtraction { path: path.clone() })?
@@ -97,7 +103,7 @@ impl ImmutableFile {
             .ok_or(ImmutableFileCreationError::FileStemExtraction { path: path.clone() })?
             .to_str()
             .ok_or(ImmutableFileCreationError::FileNameExtraction { path: path.clone() })?;
-        let immutable_file_number = filestem.parse::<ImmutableFileNumber>()?;
+        let immutable_file_number = layout.parse_immutable_file_number(filestem)?;
 
         Ok(Self {
             path,
@@ -129,6 +135,9 @@ impl ImmutableFile {
 
     /// List all [`ImmutableFile`] in a given directory.
     ///
+    /// The [layout][ImmutableFileLayout] is resolved per file, so that a directory mixing both
+    /// layouts (i.e. while the cardano-node converts its files) is fully listed.
+    ///
     /// Important Note: It will skip the last chunk / primary / secondary trio since they're not yet
     /// complete.
     pub fn list_completed_in_dir(
@@ -326,6 +335,79 @@ mod tests {
         assert_eq!(expected, immutables_names);
     }
 
+    #[test]
+    fn list_immutable_file_with_v2_layout_should_skip_last_number() {
+        let target_dir =
+            get_test_dir("list_immutable_file_with_v2_layout_should_skip_last_number/immutable");
+        let entries = vec![
+            "immutable_00021.chunk",
+            "immutable_00021.metadata",
+            "immutable_00021.primary",
+            "immutable_00021.secondary",
+            "immutable_00022.chunk",
+            "immutable_00022.metadata",
+            "immutable_00022.primary",
+            "immutable_00022.secondary",
+            "immutable_00023.chunk",
+            "immutable_00023.metadata",
+            "immutable_00023.primary",
+            "immutable_00023.secondary",
+        ];
+        create_fake_files(&target_dir, &entries);
+        let immutables = ImmutableFile::list_completed_in_dir(target_dir.parent().unwrap())
+            .expect("ImmutableFile::list_in_dir Failed");
+        let immutables_names: Vec<String> = extract_filenames(&immutables);
+
+        assert_eq!(immutables.last().unwrap().number, 22);
+        let expected: Vec<&str> = entries.into_iter().rev().skip(4).rev().collect();
+        assert_eq!(expected, immutables_names);
+    }
+
+    #[test]
+    fn list_immutable_file_in_a_directory_mixing_layouts() {
+        let target_dir =
+            get_test_dir("list_immutable_file_in_a_directory_mixing_layouts/immutable");
+        let entries = vec![
+            "00001.chunk",
+            "00001.primary",
+            "00001.secondary",
+            "immutable_00002.chunk",
+            "immutable_00002.metadata",
+            "immutable_00002.primary",
+            "immutable_00002.secondary",
+            "immutable_00003.chunk",
+            "immutable_00003.metadata",
+            "immutable_00003.primary",
+            "immutable_00003.secondary",
+        ];
+        create_fake_files(&target_dir, &entries);
+        let immutables = ImmutableFile::list_completed_in_dir(target_dir.parent().unwrap())
+            .expect("ImmutableFile::list_in_dir Failed");
+        let immutables_names: Vec<String> = extract_filenames(&immutables);
+
+        assert_eq!(
+            vec![
+                "00001.chunk",
+                "00001.primary",
+                "00001.secondary",
+                "immutable_00002.chunk",
+                "immutable_00002.metadata",
+                "immutable_00002.primary",
+                "immutable_00002.secondary",
+            ],
+            immutables_names
+        );
+    }
+
+    #[test]
+    fn new_immutable_file_deduce_layout_from_file_name() {
+        let v1_file = ImmutableFile::new(PathBuf::from("00042.chunk")).unwrap();
+        let v2_file = ImmutableFile::new(PathBuf::from("immutable_00042.chunk")).unwrap();
+
+        assert_eq!(42, v1_file.number);
+        assert_eq!(42, v2_file.number);
+    }
+
     #[test]
     fn list_immutable_file_can_list_incomplete_trio() {
         let target_dir = get_test_dir("list_immutable_file_can_list_incomplete_trio/immutable");
@@ -0,0 +1,240 @@
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+use walkdir::WalkDir;
+
+use crate::digesters::ImmutableFileCreationError;
+use crate::entities::{ImmutableFileName, ImmutableFileNumber};
+
+const V1_IMMUTABLE_FILE_EXTENSIONS: [&str; 3] = ["chunk", "primary", "secondary"];
+
+const V2_IMMUTABLE_FILE_EXTENSIONS: [&str; 4] = ["chunk", "metadata", "primary", "secondary"];
+
+const V2_IMMUTABLE_FILE_PREFIX: &str = "immutable_";
+
+/// Layout of the files in the 'immutable' directory of a Cardano node database.
+///
+/// The layout is detected per directory with [ImmutableFileLayout::detect], allowing Mithril
+/// nodes to keep working while the cardano-node they follow is upgraded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImmutableFileLayout {
+    /// Layout of the current cardano-node releases: a 'chunk', a 'primary' and a 'secondary'
+    /// file for each immutable file number, named after the number (i.e. `00042.chunk`).
+    #[default]
+    V1,
+
+    /// Layout of the upcoming cardano-node releases: the files are prefixed by `immutable_`
+    /// (i.e. `immutable_00042.chunk`) and a 'metadata' file is added for each immutable
+    /// file number.
+    V2,
+}
+
+impl ImmutableFileLayout {
+    /// Detect the layout used by the files of the given 'immutable' directory.
+    ///
+    /// Fallback to [ImmutableFileLayout::V1] if the directory does not contain any file
+    /// following the [ImmutableFileLayout::V2] naming scheme (i.e. an empty directory).
+    ///
+    /// A directory mixing both layouts is reported as [ImmutableFileLayout::V2], use
+    /// [ImmutableFileLayout::of_file] to resolve the layout of each of its files.
+    pub fn detect(immutable_dir: &Path) -> Self {
+        let has_v2_files = WalkDir::new(immutable_dir)
+            .min_depth(1)
+            .max_depth(1)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .any(|entry| Self::V2.is_immutable_file(entry.path()));
+
+        if has_v2_files {
+            Self::V2
+        } else {
+            Self::V1
+        }
+    }
+
+    /// Layout of the given immutable file, `None` if it's not an immutable file in any layout.
+    pub fn of_file(path: &Path) -> Option<Self> {
+        [Self::V2, Self::V1]
+            .into_iter()
+            .find(|layout| layout.is_immutable_file(path))
+    }
+
+    /// Extensions of the files that are part of an immutable file number in this layout.
+    pub fn file_extensions(&self) -> &'static [&'static str] {
+        match self {
+            Self::V1 => &V1_IMMUTABLE_FILE_EXTENSIONS,
+            Self::V2 => &V2_IMMUTABLE_FILE_EXTENSIONS,
+        }
+    }
+
+    /// Check if the given path is an immutable file in this layout.
+    pub fn is_immutable_file(&self, path: &Path) -> bool {
+        let has_immutable_extension = path
+            .extension()
+            .map(|e| e.to_string_lossy())
+            .is_some_and(|e| self.file_extensions().contains(&e.as_ref()));
+        let has_expected_prefix = match self {
+            Self::V1 => true,
+            Self::V2 => path
+                .file_stem()
+                .map(|s| s.to_string_lossy())
+                .is_some_and(|s| s.starts_with(V2_IMMUTABLE_FILE_PREFIX)),
+        };
+
+        has_immutable_extension && has_expected_prefix
+    }
+
+    /// Extract the immutable file number from the stem of an immutable file in this layout.
+    pub fn parse_immutable_file_number(
+        &self,
+        file_stem: &str,
+    ) -> Result<ImmutableFileNumber, ImmutableFileCreationError> {
+        let number = match self {
+            Self::V1 => file_stem,
+            Self::V2 => file_stem
+                .strip_prefix(V2_IMMUTABLE_FILE_PREFIX)
+                .unwrap_or(file_stem),
+        };
+
+        Ok(number.parse::<ImmutableFileNumber>()?)
+    }
+
+    /// Name of the files of the given immutable file number in this layout.
+    pub fn file_names(&self, number: ImmutableFileNumber) -> Vec<ImmutableFileName> {
+        let file_stem = match self {
+            Self::V1 => format!("{number:05}"),
+            Self::V2 => format!("{V2_IMMUTABLE_FILE_PREFIX}{number:05}"),
+        };
+
+        self.file_extensions()
+            .iter()
+            .map(|extension| format!("{file_stem}.{extension}"))
+            .collect()
+    }
+}
+
+impl Display for ImmutableFileLayout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::V1 => write!(f, "v1"),
+            Self::V2 => write!(f, "v2"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::path::PathBuf;
+
+    use crate::test_utils::TempDir;
+
+    use super::*;
+
+    fn create_fake_files(subdir_name: &str, filenames: &[&str]) -> PathBuf {
+        let dir = TempDir::create("immutable_file_layout", subdir_name);
+        for filename in filenames {
+            File::create(dir.join(filename)).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn detect_v1_layout() {
+        let dir = create_fake_files(
+            "detect_v1_layout",
+            &["00001.chunk", "00001.primary", "00001.secondary"],
+        );
+
+        assert_eq!(ImmutableFileLayout::V1, ImmutableFileLayout::detect(&dir));
+    }
+
+    #[test]
+    fn detect_v2_layout() {
+        let dir = create_fake_files(
+            "detect_v2_layout",
+            &[
+                "immutable_00001.chunk",
+                "immutable_00001.primary",
+                "immutable_00001.secondary",
+                "immutable_00001.metadata",
+            ],
+        );
+
+        assert_eq!(ImmutableFileLayout::V2, ImmutableFileLayout::detect(&dir));
+    }
+
+    #[test]
+    fn detect_fallback_to_v1_layout_on_empty_directory() {
+        let dir = create_fake_files("detect_fallback_to_v1_layout_on_empty_directory", &[]);
+
+        assert_eq!(ImmutableFileLayout::V1, ImmutableFileLayout::detect(&dir));
+    }
+
+    #[test]
+    fn of_file_resolve_the_layout_of_each_file() {
+        assert_eq!(
+            Some(ImmutableFileLayout::V1),
+            ImmutableFileLayout::of_file(Path::new("00001.chunk"))
+        );
+        assert_eq!(
+            Some(ImmutableFileLayout::V2),
+            ImmutableFileLayout::of_file(Path::new("immutable_00001.chunk"))
+        );
+        assert_eq!(
+            Some(ImmutableFileLayout::V2),
+            ImmutableFileLayout::of_file(Path::new("immutable_00001.metadata"))
+        );
+        assert_eq!(
+            None,
+            ImmutableFileLayout::of_file(Path::new("00001.metadata"))
+        );
+    }
+
+    #[test]
+    fn is_immutable_file_depends_on_layout() {
+        assert!(ImmutableFileLayout::V1.is_immutable_file(Path::new("00001.chunk")));
+        assert!(!ImmutableFileLayout::V1.is_immutable_file(Path::new("00001.metadata")));
+        assert!(!ImmutableFileLayout::V2.is_immutable_file(Path::new("00001.chunk")));
+        assert!(ImmutableFileLayout::V2.is_immutable_file(Path::new("immutable_00001.chunk")));
+        assert!(ImmutableFileLayout::V2.is_immutable_file(Path::new("immutable_00001.metadata")));
+        assert!(!ImmutableFileLayout::V2.is_immutable_file(Path::new("immutable_00001.md")));
+    }
+
+    #[test]
+    fn parse_immutable_file_number_depends_on_layout() {
+        assert_eq!(
+            42,
+            ImmutableFileLayout::V1
+                .parse_immutable_file_number("00042")
+                .unwrap()
+        );
+        assert_eq!(
+            42,
+            ImmutableFileLayout::V2
+                .parse_immutable_file_number("immutable_00042")
+                .unwrap()
+        );
+        ImmutableFileLayout::V1
+            .parse_immutable_file_number("immutable_00042")
+            .expect_err("V1 layout should not parse V2 file stems");
+    }
+
+    #[test]
+    fn file_names_depends_on_layout() {
+        assert_eq!(
+            vec!["00042.chunk", "00042.primary", "00042.secondary"],
+            ImmutableFileLayout::V1.file_names(42)
+        );
+        assert_eq!(
+            vec![
+                "immutable_00042.chunk",
+                "immutable_00042.metadata",
+                "immutable_00042.primary",
+                "immutable_00042.secondary",
+            ],
+            ImmutableFileLayout::V2.file_names(42)
+        );
+    }
+}
@@ -5,11 +5,13 @@ mod cardano_immutable_digester;
 mod dumb_immutable_observer;
 mod immutable_digester;
 mod immutable_file;
+mod immutable_file_layout;
 mod immutable_file_observer;
 
 pub use cardano_immutable_digester::CardanoImmutableDigester;
 pub use immutable_digester::{ImmutableDigester, ImmutableDigesterError};
 pub use immutable_file::{ImmutableFile, ImmutableFileCreationError, ImmutableFileListingError};
+pub use immutable_file_layout::ImmutableFileLayout;
 pub use immutable_file_observer::{
     DumbImmutableFileObserver, ImmutableFileObserver, ImmutableFileObserverError,
     ImmutableFileSystemObserver,
@@ -63,6 +63,46 @@ impl BlockRange {
         BlockRangesSequence::new(interval)
     }
 
+    /// Get all [BlockRange] strictly contained in the given interval once its start is aligned
+    /// down on the start of its block range, i.e. the block ranges that can be computed from a
+    /// first block number up to an excluded last block number.
+    pub fn all_block_ranges_in_aligned(interval: Range<BlockNumber>) -> BlockRangesSequence {
+        BlockRangesSequence::new(Self::start(interval.start)..interval.end)
+    }
+
+    /// Get all [BlockRange] that are complete once the given block number is included, i.e. the
+    /// block ranges that can be computed up to the block number of a beacon.
+    pub fn all_block_ranges_up_to(number: BlockNumber) -> BlockRangesSequence {
+        BlockRangesSequence::new(0..number.saturating_add(1))
+    }
+
+    /// Check if the given block range is fully contained in this block range
+    pub fn contains_range(&self, other: &BlockRange) -> bool {
+        self.inner_range.start <= other.inner_range.start
+            && other.inner_range.end <= self.inner_range.end
+    }
+
+    /// Check if this block range and the given block range have at least a block number in common
+    pub fn intersects(&self, other: &BlockRange) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Get the block range, aligned on the [BlockRange::LENGTH] boundaries, that covers the block
+    /// numbers common to this block range and the given block range, if any.
+    ///
+    /// The intersection of two aligned block ranges is either one of them or empty.
+    pub fn intersection(&self, other: &BlockRange) -> Option<BlockRange> {
+        let start = self.inner_range.start.max(other.inner_range.start);
+        let end = self.inner_range.end.min(other.inner_range.end);
+
+        (start < end).then(|| {
+            BlockRange::new(
+                Self::start(start),
+                Self::start(end - 1).saturating_add(Self::LENGTH),
+            )
+        })
+    }
+
     /// Create a BlockRange from a block number
     pub fn from_block_number(number: BlockNumber) -> Self {
         // Unwrap is safe as the length is always strictly greater than 0
@@ -209,13 +249,13 @@ impl Iterator for BlockRangesSequence {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.start as usize, Some(self.end as usize))
+        (self.len(), Some(self.len()))
     }
 }
 
 impl ExactSizeIterator for BlockRangesSequence {
     fn len(&self) -> usize {
-        ((self.end - self.start) / BlockRange::LENGTH) as usize
+        (self.end.saturating_sub(self.start) / BlockRange::LENGTH) as usize
     }
 }
 
@@ -357,6 +397,110 @@ mod tests {
         assert!(BlockRange::all_block_ranges_in(15..45).contains(&block_range));
     }
 
+    #[test]
+    fn test_block_range_all_block_ranges_in_aligned() {
+        assert_eq!(
+            BlockRange::all_block_ranges_in_aligned(0..14).into_vec(),
+            vec![]
+        );
+        assert_eq!(
+            BlockRange::all_block_ranges_in_aligned(1..15).into_vec(),
+            vec![BlockRange::new(0, 15)]
+        );
+        assert_eq!(
+            BlockRange::all_block_ranges_in_aligned(17..46).into_vec(),
+            vec![BlockRange::new(15, 30), BlockRange::new(30, 45)]
+        );
+    }
+
+    #[test]
+    fn test_block_range_all_block_ranges_up_to() {
+        assert_eq!(BlockRange::all_block_ranges_up_to(0).into_vec(), vec![]);
+        assert_eq!(BlockRange::all_block_ranges_up_to(13).into_vec(), vec![]);
+        assert_eq!(
+            BlockRange::all_block_ranges_up_to(14).into_vec(),
+            vec![BlockRange::new(0, 15)]
+        );
+        assert_eq!(
+            BlockRange::all_block_ranges_up_to(44).into_vec(),
+            vec![
+                BlockRange::new(0, 15),
+                BlockRange::new(15, 30),
+                BlockRange::new(30, 45)
+            ]
+        );
+        assert_eq!(
+            BlockRange::all_block_ranges_up_to(BlockNumber::MAX).end(),
+            BlockRange::start(BlockNumber::MAX)
+        );
+    }
+
+    #[test]
+    fn test_block_range_contains_range() {
+        let block_range = BlockRange::new(15, 30);
+
+        assert!(block_range.contains_range(&BlockRange::new(15, 30)));
+        assert!(block_range.contains_range(&BlockRange::new(16, 29)));
+        assert!(block_range.contains_range(&BlockRange::new(14, 30)).not());
+        assert!(block_range.contains_range(&BlockRange::new(15, 31)).not());
+    }
+
+    #[test]
+    fn test_block_range_intersection() {
+        let block_range = BlockRange::new(15, 30);
+
+        assert_eq!(
+            block_range.intersection(&BlockRange::new(0, 45)),
+            Some(BlockRange::new(15, 30))
+        );
+        assert_eq!(block_range.intersection(&BlockRange::new(30, 45)), None);
+        assert_eq!(
+            BlockRange::new(0, 45).intersection(&BlockRange::new(15, 60)),
+            Some(BlockRange::new(15, 45))
+        );
+        // The common block numbers of unaligned ranges are covered by aligned block ranges
+        assert_eq!(
+            block_range.intersection(&BlockRange::new(0, 20)),
+            Some(BlockRange::new(15, 30))
+        );
+        assert_eq!(
+            block_range.intersection(&BlockRange::new(16, 17)),
+            Some(BlockRange::new(15, 30))
+        );
+        assert_eq!(
+            BlockRange::new(10, 50).intersection(&BlockRange::new(20, 70)),
+            Some(BlockRange::new(15, 60))
+        );
+        // The end of the ranges is exclusive
+        assert_eq!(block_range.intersection(&BlockRange::new(0, 15)), None);
+        assert_eq!(block_range.intersection(&BlockRange::new(30, 45)), None);
+        assert!(block_range.intersects(&BlockRange::new(29, 45)));
+        assert!(block_range.intersects(&BlockRange::new(30, 45)).not());
+    }
+
+    #[test]
+    fn test_block_ranges_sequence_invariants() {
+        for interval in [0..0, 0..14, 1..15, 14..61, 17..1_000, 1_000..17] {
+            let sequence = BlockRange::all_block_ranges_in(interval.clone());
+            let len = sequence.len();
+            assert_eq!(sequence.size_hint(), (len, Some(len)));
+
+            let block_ranges = sequence.into_vec();
+            assert_eq!(len, block_ranges.len(), "interval: {interval:?}");
+            for block_range in &block_ranges {
+                assert_eq!(
+                    BlockRange::from_block_number(block_range.start),
+                    *block_range
+                );
+                assert!(interval.start <= block_range.start && block_range.end <= interval.end);
+            }
+            for (previous, next) in block_ranges.iter().zip(block_ranges.iter().skip(1)) {
+                assert_eq!(previous.end, next.start);
+                assert!(previous.intersects(next).not());
+            }
+        }
+    }
+
     #[test]
     fn test_block_range_from_number() {
         assert_eq!(BlockRange::from_block_number(0), BlockRange::new(0, 15));
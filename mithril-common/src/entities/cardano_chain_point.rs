@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
 
 /// [Cardano Slot number](https://docs.cardano.org/learn/cardano-node/#slotsandepochs)
 pub type SlotNumber = u64;
@@ -10,7 +12,7 @@ pub type BlockNumber = u64;
 pub type BlockHash = String;
 
 ///The Cardano chain point which is used to identify a specific point in the Cardano chain.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct ChainPoint {
     /// The slot number
     pub slot_number: SlotNumber,
@@ -21,3 +23,63 @@ pub struct ChainPoint {
     /// The hex encoded block hash
     pub block_hash: BlockHash,
 }
+
+impl ChainPoint {
+    cfg_test_tools! {
+        /// Create a dummy ChainPoint
+        pub fn dummy() -> Self {
+            Self {
+                slot_number: 100,
+                block_number: 50,
+                block_hash: "block_hash-50".to_string(),
+            }
+        }
+    }
+}
+
+impl PartialOrd for ChainPoint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ChainPoint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.block_number
+            .cmp(&other.block_number)
+            .then(self.slot_number.cmp(&other.slot_number))
+            .then(self.block_hash.cmp(&other.block_hash))
+    }
+}
+
+impl Display for ChainPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ChainPoint (block_number: {}, slot_number: {}, block_hash: {})",
+            self.block_number, self.slot_number, self.block_hash
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_point_ord_compares_block_numbers_first() {
+        let chain_point1 = ChainPoint {
+            slot_number: 50,
+            block_number: 10,
+            block_hash: "hash-2".to_string(),
+        };
+        let chain_point2 = ChainPoint {
+            slot_number: 40,
+            block_number: 11,
+            block_hash: "hash-1".to_string(),
+        };
+
+        assert_eq!(Ordering::Less, chain_point1.cmp(&chain_point2));
+        assert_eq!(Ordering::Equal, chain_point1.cmp(&chain_point1));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::crypto_helper::{MKMapProof, ProtocolMkProof};
+use crate::crypto_helper::{MKMapProof, MKMapProofBatchVerifier, ProtocolMkProof};
 use crate::entities::TransactionHash;
 use crate::messages::CardanoTransactionsSetProofMessagePart;
 use crate::{StdError, StdResult};
@@ -45,7 +45,27 @@ impl CardanoTransactionsSetProof {
 
     /// Verify that transactions set proof is valid
     pub fn verify(&self) -> StdResult<()> {
-        self.transactions_proof.verify()?;
+        self.verify_with(&mut MKMapProofBatchVerifier::new())
+    }
+
+    /// Verify a batch of transactions set proofs, the parts shared by several proofs of the
+    /// batch being verified only once.
+    ///
+    /// Return the result of the verification of each set proof, in the same order.
+    pub fn verify_batch(set_proofs: &[Self]) -> Vec<StdResult<()>> {
+        let mut verifier = MKMapProofBatchVerifier::new();
+
+        set_proofs
+            .iter()
+            .map(|set_proof| set_proof.verify_with(&mut verifier))
+            .collect()
+    }
+
+    fn verify_with<'a>(
+        &'a self,
+        verifier: &mut MKMapProofBatchVerifier<'a, BlockRange>,
+    ) -> StdResult<()> {
+        verifier.verify(&self.transactions_proof)?;
         for hash in &self.transactions_hashes {
             self.transactions_proof.contains(&hash.to_owned().into())?;
         }
@@ -165,4 +185,23 @@ mod tests {
 
         proof.verify().expect_err("The proof should be invalid");
     }
+
+    #[test]
+    fn verify_batch_return_the_result_of_each_proof_in_order() {
+        let proof = CardanoTransactionsSetProof::dummy();
+        let tampered_proof = CardanoTransactionsSetProof {
+            transactions_hashes: vec!["tx-123".to_string()],
+            ..proof.clone()
+        };
+
+        let results =
+            CardanoTransactionsSetProof::verify_batch(&[proof.clone(), tampered_proof, proof]);
+
+        assert_eq!(3, results.len());
+        assert!(results[0].is_ok());
+        results[1]
+            .as_ref()
+            .expect_err("The tampered proof should be invalid");
+        assert!(results[2].is_ok());
+    }
 }
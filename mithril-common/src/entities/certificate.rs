@@ -115,6 +115,16 @@ impl Certificate {
         matches!(self.signature, CertificateSignature::GenesisSignature(_))
     }
 
+    /// Tell if the certificate is a genesis key rotation certificate, i.e. a genesis certificate
+    /// chained to a previous certificate that certifies a new genesis verification key.
+    pub fn is_genesis_key_rotation(&self) -> bool {
+        self.is_genesis()
+            && self
+                .protocol_message
+                .next_genesis_verification_key()
+                .is_some()
+    }
+
     /// Return true if the certificate is chaining into itself (meaning that its hash and previous
     /// hash are equal).
     pub fn is_chaining_to_itself(&self) -> bool {
@@ -5,6 +5,7 @@ use sha2::{Digest, Sha256};
 use crate::entities::{
     ImmutableFileNumber, ProtocolParameters, ProtocolVersion, SignerWithStake, StakeDistribution,
 };
+use crate::era::SupportedEra;
 use crate::era_deprecate;
 
 use super::{PartyId, Stake};
@@ -44,6 +45,33 @@ impl StakeDistributionParty {
     }
 }
 
+/// Part of the [CertificateMetadata] that is signed with the protocol message of the certificate,
+/// so it can not be altered without invalidating the multi-signature.
+///
+/// The date and time at which the certificate is sealed is not signed as it is only known once the
+/// single signatures are aggregated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedCertificateMetadata {
+    /// Cardano network
+    pub network: String,
+
+    /// Protocol parameters
+    pub protocol_parameters: ProtocolParameters,
+
+    /// Date and time when the certificate was initiated
+    pub initiated_at: DateTime<Utc>,
+}
+
+impl From<&CertificateMetadata> for SignedCertificateMetadata {
+    fn from(metadata: &CertificateMetadata) -> Self {
+        Self {
+            network: metadata.network.clone(),
+            protocol_parameters: metadata.protocol_parameters.clone(),
+            initiated_at: metadata.initiated_at,
+        }
+    }
+}
+
 era_deprecate!("Remove immutable file number as it's here only for message backward-compatibility");
 /// CertificateMetadata represents the metadata associated to a Certificate
 #[derive(Clone, Debug, PartialEq, Default)]
@@ -80,6 +108,11 @@ pub struct CertificateMetadata {
     /// The list of the active signers with their stakes and verification keys
     /// part of METADATA(p,n)
     pub signers: Vec<StakeDistributionParty>,
+
+    /// Era under which the certificate was produced
+    /// part of METADATA(p,n) from the Pythagoras era only, as the previous versions of the
+    /// nodes and of the clients do not know this field and could not verify the hash.
+    pub era: Option<SupportedEra>,
 }
 
 impl CertificateMetadata {
@@ -102,6 +135,7 @@ impl CertificateMetadata {
             initiated_at,
             sealed_at,
             signers,
+            era: None,
         }
     }
 
@@ -137,6 +171,10 @@ impl CertificateMetadata {
             hasher.update(party.compute_hash().as_bytes());
         }
 
+        if let Some(era) = self.era.filter(|era| *era != SupportedEra::Thales) {
+            hasher.update(era.to_string().as_bytes());
+        }
+
         hex::encode(hasher.finalize())
     }
 }
@@ -193,6 +231,25 @@ mod tests {
             .compute_hash(),
         );
 
+        // Thales era shouldn't impact the hash so that the previous certificates can still be verified
+        assert_eq!(
+            hash_expected,
+            CertificateMetadata {
+                era: Some(SupportedEra::Thales),
+                ..metadata.clone()
+            }
+            .compute_hash(),
+        );
+
+        assert_ne!(
+            hash_expected,
+            CertificateMetadata {
+                era: Some(SupportedEra::Pythagoras),
+                ..metadata.clone()
+            }
+            .compute_hash(),
+        );
+
         assert_ne!(
             hash_expected,
             CertificateMetadata {
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::entities::{Epoch, PartyId, ProtocolParameters, SignedEntityType, Signer};
@@ -25,6 +26,15 @@ pub struct CertificatePending {
 
     /// Signers that will be able to sign on the next epoch
     pub next_signers: Vec<Signer>,
+
+    /// Signed entity types batched with the signed entity type in the signed protocol message
+    #[serde(default)]
+    pub batched_signed_entity_types: Vec<SignedEntityType>,
+
+    /// Date and time at which the certificate was initiated, set if the metadata of the
+    /// certificate are signed with the protocol message
+    #[serde(default)]
+    pub initiated_at: Option<DateTime<Utc>>,
 }
 
 impl CertificatePending {
@@ -44,9 +54,26 @@ impl CertificatePending {
             next_protocol_parameters,
             signers,
             next_signers,
+            batched_signed_entity_types: vec![],
+            initiated_at: None,
         }
     }
 
+    /// Set the signed entity types batched with the signed entity type
+    pub fn with_batched_signed_entity_types(
+        mut self,
+        batched_signed_entity_types: Vec<SignedEntityType>,
+    ) -> Self {
+        self.batched_signed_entity_types = batched_signed_entity_types;
+        self
+    }
+
+    /// Set the date and time at which the certificate was initiated
+    pub fn with_initiated_at(mut self, initiated_at: Option<DateTime<Utc>>) -> Self {
+        self.initiated_at = initiated_at;
+        self
+    }
+
     /// get a signer from the certificate pending if it has registered
     pub fn get_signer(&self, party_id: PartyId) -> Option<&Signer> {
         self.signers.iter().find(|s| s.party_id == party_id)
@@ -7,6 +7,7 @@ use std::{
 };
 use thiserror::Error;
 
+use crate::crypto_helper::{MKMapKey, MKTreeNode};
 use crate::signable_builder::Beacon as SignableBeacon;
 
 /// Epoch represents a Cardano epoch
@@ -187,6 +188,14 @@ impl Display for Epoch {
 
 impl SignableBeacon for Epoch {}
 
+impl From<Epoch> for MKTreeNode {
+    fn from(other: Epoch) -> Self {
+        MKTreeNode::new(other.to_string().into_bytes())
+    }
+}
+
+impl MKMapKey for Epoch {}
+
 impl TryInto<i64> for Epoch {
     type Error = TryFromIntError;
 
@@ -1,4 +1,6 @@
-use crate::entities::{Epoch, ProtocolParameters};
+use crate::entities::{
+    Epoch, ProtocolFeatureActivations, ProtocolParameters, StakeDistributionParty,
+};
 
 /// EpochSettings represents the settings of an epoch
 #[derive(Clone, Debug, PartialEq, Default)]
@@ -11,4 +13,13 @@ pub struct EpochSettings {
 
     /// Next Protocol parameters
     pub next_protocol_parameters: ProtocolParameters,
+
+    /// Activation epochs of the protocol features
+    pub protocol_feature_activations: ProtocolFeatureActivations,
+
+    /// Nonce that the signers must sign to authenticate their single signatures, if any
+    pub registration_nonce: Option<String>,
+
+    /// Signers registered so far for the epoch after the next one with their stake, if known
+    pub upcoming_signers: Option<Vec<StakeDistributionParty>>,
 }
@@ -14,6 +14,7 @@ mod epoch;
 mod epoch_settings;
 mod http_server_error;
 mod mithril_stake_distribution;
+mod protocol_feature;
 mod protocol_message;
 mod protocol_parameters;
 mod signed_entity;
@@ -21,6 +22,8 @@ mod signed_entity_type;
 mod signer;
 mod single_signatures;
 mod snapshot;
+mod snapshot_manifest;
+mod stake_distribution_diff;
 mod time_point;
 mod type_alias;
 
@@ -32,18 +35,25 @@ pub use cardano_transaction::{CardanoTransaction, TransactionHash};
 pub use cardano_transactions_set_proof::CardanoTransactionsSetProof;
 pub use cardano_transactions_snapshot::CardanoTransactionsSnapshot;
 pub use certificate::{Certificate, CertificateSignature};
-pub use certificate_metadata::{CertificateMetadata, StakeDistributionParty};
+pub use certificate_metadata::{
+    CertificateMetadata, SignedCertificateMetadata, StakeDistributionParty,
+};
 pub use certificate_pending::CertificatePending;
 pub use epoch::{Epoch, EpochError};
 pub use epoch_settings::EpochSettings;
 pub use http_server_error::{ClientError, InternalServerError};
 pub use mithril_stake_distribution::MithrilStakeDistribution;
-pub use protocol_message::{ProtocolMessage, ProtocolMessagePartKey, ProtocolMessagePartValue};
+pub use protocol_feature::{ProtocolFeature, ProtocolFeatureActivations};
+pub use protocol_message::{
+    ProtocolMessage, ProtocolMessagePart, ProtocolMessagePartKey, ProtocolMessagePartValue,
+};
 pub use protocol_parameters::ProtocolParameters;
 pub use signed_entity::*;
 pub use signed_entity_type::*;
 pub use signer::{Signer, SignerWithStake};
 pub use single_signatures::*;
 pub use snapshot::{CompressionAlgorithm, Snapshot};
+pub use snapshot_manifest::{SnapshotManifest, SnapshotManifestEntry, SnapshotObjectHash};
+pub use stake_distribution_diff::{StakeDelta, StakeDistributionDiff};
 pub use time_point::*;
 pub use type_alias::*;
@@ -0,0 +1,206 @@
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use strum::{Display, EnumIter, EnumString};
+
+use crate::entities::Epoch;
+use crate::era::SupportedEra;
+use crate::StdResult;
+
+/// Changes of the protocol that are visible by the other nodes of the network.
+///
+/// Such a change must be switched on at the same epoch by all the nodes, at its activation
+/// epoch distributed by the aggregator in the [epoch settings][crate::entities::EpochSettings].
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Display,
+    EnumString,
+    EnumIter,
+    Serialize,
+    Deserialize,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolFeature {
+    /// Certification of several signed entity types in a single certificate round, with a
+    /// batched protocol message
+    BatchedSignedEntityTypes,
+
+    /// Signature of the network, the protocol parameters and the initiation date and time of
+    /// the certificates with their protocol message
+    SignedCertificateMetadata,
+}
+
+impl ProtocolFeature {
+    /// Era from which the feature is mandatory, whatever its activation epoch, if any
+    pub fn mandatory_from_era(&self) -> Option<SupportedEra> {
+        match self {
+            Self::BatchedSignedEntityTypes => None,
+            Self::SignedCertificateMetadata => Some(SupportedEra::Pythagoras),
+        }
+    }
+
+    /// Check if the feature is mandatory in the given era
+    pub fn is_mandatory_in_era(&self, era: SupportedEra) -> bool {
+        self.mandatory_from_era()
+            .is_some_and(|mandatory_era| mandatory_era <= era)
+    }
+}
+
+/// Activation epochs of the [protocol features][ProtocolFeature].
+///
+/// A feature without an activation epoch is not active.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ProtocolFeatureActivations(BTreeMap<ProtocolFeature, Epoch>);
+
+impl ProtocolFeatureActivations {
+    /// ProtocolFeatureActivations factory, without any activated feature
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Activate the given feature from the given epoch
+    pub fn with_activation(mut self, feature: ProtocolFeature, epoch: Epoch) -> Self {
+        self.0.insert(feature, epoch);
+        self
+    }
+
+    /// Epoch from which the given feature is active, if any
+    pub fn activation_epoch(&self, feature: ProtocolFeature) -> Option<Epoch> {
+        self.0.get(&feature).copied()
+    }
+
+    /// Check if the given feature is active at the given epoch
+    pub fn is_active(&self, feature: ProtocolFeature, epoch: Epoch) -> bool {
+        self.activation_epoch(feature)
+            .is_some_and(|activation_epoch| activation_epoch <= epoch)
+    }
+
+    /// Check if the given feature is active at the given epoch of the given era, a feature is
+    /// always active in the eras where it is mandatory
+    pub fn is_active_in_era(
+        &self,
+        feature: ProtocolFeature,
+        epoch: Epoch,
+        era: SupportedEra,
+    ) -> bool {
+        feature.is_mandatory_in_era(era) || self.is_active(feature, epoch)
+    }
+
+    /// Check if no feature has an activation epoch
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for ProtocolFeatureActivations {
+    type Err = anyhow::Error;
+
+    /// Parse a comma separated list of `feature=epoch` pairs, ie:
+    /// `batched_signed_entity_types=120`
+    fn from_str(value: &str) -> StdResult<Self> {
+        let mut activations = Self::new();
+        for item in value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+        {
+            let (feature, epoch) = item
+                .split_once('=')
+                .ok_or_else(|| anyhow!("'{item}' is not a 'feature=epoch' pair"))?;
+            let feature = ProtocolFeature::from_str(feature.trim())
+                .with_context(|| format!("unknown protocol feature: '{}'", feature.trim()))?;
+            let epoch = epoch
+                .trim()
+                .parse::<u64>()
+                .with_context(|| format!("invalid activation epoch: '{}'", epoch.trim()))?;
+            activations = activations.with_activation(feature, Epoch(epoch));
+        }
+
+        Ok(activations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feature_is_active_from_its_activation_epoch() {
+        let activations = ProtocolFeatureActivations::new()
+            .with_activation(ProtocolFeature::BatchedSignedEntityTypes, Epoch(10));
+
+        assert!(!activations.is_active(ProtocolFeature::BatchedSignedEntityTypes, Epoch(9)));
+        assert!(activations.is_active(ProtocolFeature::BatchedSignedEntityTypes, Epoch(10)));
+        assert!(activations.is_active(ProtocolFeature::BatchedSignedEntityTypes, Epoch(11)));
+    }
+
+    #[test]
+    fn feature_without_activation_epoch_is_never_active() {
+        let activations = ProtocolFeatureActivations::new();
+
+        assert!(!activations.is_active(ProtocolFeature::BatchedSignedEntityTypes, Epoch(u64::MAX)));
+    }
+
+    #[test]
+    fn feature_is_active_in_the_eras_where_it_is_mandatory() {
+        let activations = ProtocolFeatureActivations::new();
+
+        assert!(!activations.is_active_in_era(
+            ProtocolFeature::SignedCertificateMetadata,
+            Epoch(10),
+            SupportedEra::Thales
+        ));
+        assert!(activations.is_active_in_era(
+            ProtocolFeature::SignedCertificateMetadata,
+            Epoch(10),
+            SupportedEra::Pythagoras
+        ));
+        assert!(!activations.is_active_in_era(
+            ProtocolFeature::BatchedSignedEntityTypes,
+            Epoch(10),
+            SupportedEra::Pythagoras
+        ));
+    }
+
+    #[test]
+    fn parse_activations_list() {
+        assert_eq!(
+            ProtocolFeatureActivations::new()
+                .with_activation(ProtocolFeature::BatchedSignedEntityTypes, Epoch(120)),
+            " batched_signed_entity_types = 120 ".parse().unwrap()
+        );
+        assert_eq!(ProtocolFeatureActivations::new(), "".parse().unwrap());
+        "unknown=12"
+            .parse::<ProtocolFeatureActivations>()
+            .expect_err("an unknown feature should be rejected");
+        "batched_signed_entity_types=twelve"
+            .parse::<ProtocolFeatureActivations>()
+            .expect_err("an invalid epoch should be rejected");
+        "batched_signed_entity_types"
+            .parse::<ProtocolFeatureActivations>()
+            .expect_err("a feature without epoch should be rejected");
+    }
+
+    #[test]
+    fn serialize_activations_as_a_map_of_epochs() {
+        let activations = ProtocolFeatureActivations::new()
+            .with_activation(ProtocolFeature::BatchedSignedEntityTypes, Epoch(120));
+        let json = serde_json::to_string(&activations).unwrap();
+
+        assert_eq!(r#"{"batched_signed_entity_types":120}"#, json);
+        assert_eq!(
+            activations,
+            serde_json::from_str::<ProtocolFeatureActivations>(&json).unwrap()
+        );
+    }
+}
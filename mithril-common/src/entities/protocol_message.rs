@@ -1,7 +1,11 @@
+use anyhow::{anyhow, Context};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::{collections::BTreeMap, fmt::Display};
 
+use crate::entities::{ImmutableFileNumber, SignedCertificateMetadata, SignedEntityType};
+use crate::StdResult;
+
 /// The key of a ProtocolMessage
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ProtocolMessagePartKey {
@@ -22,6 +26,38 @@ pub enum ProtocolMessagePartKey {
     /// The ProtocolMessage part key associated to the latest immutable file number signed
     #[serde(rename = "latest_immutable_file_number")]
     LatestImmutableFileNumber,
+
+    /// The ProtocolMessage part key associated to the signed entity types certified in the same
+    /// certificate round as the signed entity type of the message (JSON list).
+    ///
+    /// Only available from the Pythagoras era, as the previous versions of the nodes can not
+    /// deserialize this key.
+    #[serde(rename = "batched_signed_entity_types")]
+    BatchedSignedEntityTypes,
+
+    /// The ProtocolMessage part key associated to the metadata of the certificate that are
+    /// signed with the message (JSON)
+    ///
+    /// Only available from the Pythagoras era, as the previous versions of the nodes can not
+    /// deserialize this key.
+    #[serde(rename = "signed_certificate_metadata")]
+    SignedCertificateMetadata,
+
+    /// The ProtocolMessage part key associated to the genesis verification key that replaces
+    /// the current one in a genesis key rotation (json hex)
+    ///
+    /// Only available from the Pythagoras era, as the previous versions of the nodes can not
+    /// deserialize this key.
+    #[serde(rename = "next_genesis_verification_key")]
+    NextGenesisVerificationKey,
+
+    /// The ProtocolMessage part key associated to the signature, made with the replaced genesis
+    /// key, of the genesis key rotation message (bytes hex)
+    ///
+    /// Only available from the Pythagoras era, as the previous versions of the nodes can not
+    /// deserialize this key.
+    #[serde(rename = "previous_genesis_key_signature")]
+    PreviousGenesisKeySignature,
 }
 
 impl Display for ProtocolMessagePartKey {
@@ -31,6 +67,31 @@ impl Display for ProtocolMessagePartKey {
             Self::NextAggregateVerificationKey => write!(f, "next_aggregate_verification_key"),
             Self::CardanoTransactionsMerkleRoot => write!(f, "cardano_transactions_merkle_root"),
             Self::LatestImmutableFileNumber => write!(f, "latest_immutable_file_number"),
+            Self::BatchedSignedEntityTypes => write!(f, "batched_signed_entity_types"),
+            Self::SignedCertificateMetadata => write!(f, "signed_certificate_metadata"),
+            Self::NextGenesisVerificationKey => write!(f, "next_genesis_verification_key"),
+            Self::PreviousGenesisKeySignature => write!(f, "previous_genesis_key_signature"),
+        }
+    }
+}
+
+impl ProtocolMessagePartKey {
+    /// Keys of the parts that a protocol message must contain to be signed for the given
+    /// signed entity type
+    pub fn required_for(signed_entity_type: &SignedEntityType) -> Vec<Self> {
+        match signed_entity_type {
+            SignedEntityType::MithrilStakeDistribution(_)
+            | SignedEntityType::CardanoStakeDistribution(_) => {
+                vec![Self::NextAggregateVerificationKey]
+            }
+            SignedEntityType::CardanoImmutableFilesFull(_) => {
+                vec![Self::SnapshotDigest, Self::NextAggregateVerificationKey]
+            }
+            SignedEntityType::CardanoTransactions(_) => vec![
+                Self::CardanoTransactionsMerkleRoot,
+                Self::LatestImmutableFileNumber,
+                Self::NextAggregateVerificationKey,
+            ],
         }
     }
 }
@@ -38,6 +99,119 @@ impl Display for ProtocolMessagePartKey {
 /// The value of a ProtocolMessage
 pub type ProtocolMessagePartValue = String;
 
+/// A strongly typed part of a [ProtocolMessage].
+///
+/// Each part is stored in the protocol message as the [value][ProtocolMessagePartValue]
+/// associated to its [key][ProtocolMessagePartKey], so that the typed parts do not change the
+/// serialization of the protocol messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtocolMessagePart {
+    /// Digest of the snapshot
+    SnapshotDigest(String),
+
+    /// Merkle root of the Cardano transactions
+    CardanoTransactionsMerkleRoot(String),
+
+    /// Next epoch aggregate verification key (json hex encoded)
+    NextAggregateVerificationKey(String),
+
+    /// Latest immutable file number signed
+    LatestImmutableFileNumber(ImmutableFileNumber),
+
+    /// Signed entity types certified in the same certificate round
+    BatchedSignedEntityTypes(Vec<SignedEntityType>),
+
+    /// Metadata of the certificate signed with the message
+    SignedCertificateMetadata(SignedCertificateMetadata),
+
+    /// Genesis verification key replacing the current one (json hex encoded)
+    NextGenesisVerificationKey(String),
+
+    /// Signature of the genesis key rotation message made with the replaced genesis key
+    /// (bytes hex encoded)
+    PreviousGenesisKeySignature(String),
+}
+
+impl ProtocolMessagePart {
+    /// Key associated to this part in a protocol message
+    pub fn key(&self) -> ProtocolMessagePartKey {
+        match self {
+            Self::SnapshotDigest(_) => ProtocolMessagePartKey::SnapshotDigest,
+            Self::CardanoTransactionsMerkleRoot(_) => {
+                ProtocolMessagePartKey::CardanoTransactionsMerkleRoot
+            }
+            Self::NextAggregateVerificationKey(_) => {
+                ProtocolMessagePartKey::NextAggregateVerificationKey
+            }
+            Self::LatestImmutableFileNumber(_) => ProtocolMessagePartKey::LatestImmutableFileNumber,
+            Self::BatchedSignedEntityTypes(_) => ProtocolMessagePartKey::BatchedSignedEntityTypes,
+            Self::SignedCertificateMetadata(_) => ProtocolMessagePartKey::SignedCertificateMetadata,
+            Self::NextGenesisVerificationKey(_) => {
+                ProtocolMessagePartKey::NextGenesisVerificationKey
+            }
+            Self::PreviousGenesisKeySignature(_) => {
+                ProtocolMessagePartKey::PreviousGenesisKeySignature
+            }
+        }
+    }
+
+    /// Value stored for this part in a protocol message
+    pub fn to_value(&self) -> StdResult<ProtocolMessagePartValue> {
+        match self {
+            Self::SnapshotDigest(value)
+            | Self::CardanoTransactionsMerkleRoot(value)
+            | Self::NextAggregateVerificationKey(value)
+            | Self::NextGenesisVerificationKey(value)
+            | Self::PreviousGenesisKeySignature(value) => Ok(value.to_owned()),
+            Self::LatestImmutableFileNumber(immutable_file_number) => {
+                Ok(immutable_file_number.to_string())
+            }
+            Self::BatchedSignedEntityTypes(signed_entity_types) => {
+                serde_json::to_string(signed_entity_types)
+                    .with_context(|| "Could not serialize the batched signed entity types")
+            }
+            Self::SignedCertificateMetadata(metadata) => serde_json::to_string(metadata)
+                .with_context(|| "Could not serialize the signed certificate metadata"),
+        }
+    }
+
+    /// Parse the value stored in a protocol message for the given key
+    pub fn parse(key: ProtocolMessagePartKey, value: &str) -> StdResult<Self> {
+        let part = match key {
+            ProtocolMessagePartKey::SnapshotDigest => Self::SnapshotDigest(value.to_owned()),
+            ProtocolMessagePartKey::CardanoTransactionsMerkleRoot => {
+                Self::CardanoTransactionsMerkleRoot(value.to_owned())
+            }
+            ProtocolMessagePartKey::NextAggregateVerificationKey => {
+                Self::NextAggregateVerificationKey(value.to_owned())
+            }
+            ProtocolMessagePartKey::LatestImmutableFileNumber => {
+                Self::LatestImmutableFileNumber(value.parse().with_context(|| {
+                    format!("Invalid latest immutable file number in protocol message: '{value}'")
+                })?)
+            }
+            ProtocolMessagePartKey::BatchedSignedEntityTypes => {
+                Self::BatchedSignedEntityTypes(serde_json::from_str(value).with_context(|| {
+                    format!("Invalid batched signed entity types in protocol message: '{value}'")
+                })?)
+            }
+            ProtocolMessagePartKey::SignedCertificateMetadata => {
+                Self::SignedCertificateMetadata(serde_json::from_str(value).with_context(|| {
+                    format!("Invalid signed certificate metadata in protocol message: '{value}'")
+                })?)
+            }
+            ProtocolMessagePartKey::NextGenesisVerificationKey => {
+                Self::NextGenesisVerificationKey(value.to_owned())
+            }
+            ProtocolMessagePartKey::PreviousGenesisKeySignature => {
+                Self::PreviousGenesisKeySignature(value.to_owned())
+            }
+        };
+
+        Ok(part)
+    }
+}
+
 /// ProtocolMessage represents a message that is signed (or verified) by the Mithril protocol
 #[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct ProtocolMessage {
@@ -72,6 +246,148 @@ impl ProtocolMessage {
         self.message_parts.get(key)
     }
 
+    /// Set a typed message part, replacing the value previously set for its key
+    pub fn set_part(&mut self, part: ProtocolMessagePart) -> StdResult<()> {
+        self.message_parts.insert(part.key(), part.to_value()?);
+
+        Ok(())
+    }
+
+    /// Get the typed message part associated with a key
+    ///
+    /// Fails if the value set for the key can not be parsed.
+    pub fn get_part(&self, key: ProtocolMessagePartKey) -> StdResult<Option<ProtocolMessagePart>> {
+        self.get_message_part(&key)
+            .map(|value| ProtocolMessagePart::parse(key, value))
+            .transpose()
+    }
+
+    /// Get the snapshot digest part
+    pub fn snapshot_digest(&self) -> Option<&str> {
+        self.get_message_part(&ProtocolMessagePartKey::SnapshotDigest)
+            .map(String::as_str)
+    }
+
+    /// Get the Cardano transactions Merkle root part
+    pub fn cardano_transactions_merkle_root(&self) -> Option<&str> {
+        self.get_message_part(&ProtocolMessagePartKey::CardanoTransactionsMerkleRoot)
+            .map(String::as_str)
+    }
+
+    /// Get the next aggregate verification key part
+    pub fn next_aggregate_verification_key(&self) -> Option<&str> {
+        self.get_message_part(&ProtocolMessagePartKey::NextAggregateVerificationKey)
+            .map(String::as_str)
+    }
+
+    /// Get the next genesis verification key part, only set by genesis key rotations
+    pub fn next_genesis_verification_key(&self) -> Option<&str> {
+        self.get_message_part(&ProtocolMessagePartKey::NextGenesisVerificationKey)
+            .map(String::as_str)
+    }
+
+    /// Get the previous genesis key signature part, only set by genesis key rotations
+    pub fn previous_genesis_key_signature(&self) -> Option<&str> {
+        self.get_message_part(&ProtocolMessagePartKey::PreviousGenesisKeySignature)
+            .map(String::as_str)
+    }
+
+    /// Get the latest immutable file number part
+    pub fn latest_immutable_file_number(&self) -> StdResult<Option<ImmutableFileNumber>> {
+        match self.get_part(ProtocolMessagePartKey::LatestImmutableFileNumber)? {
+            Some(ProtocolMessagePart::LatestImmutableFileNumber(immutable_file_number)) => {
+                Ok(Some(immutable_file_number))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Check that this protocol message contains all the parts required for the given signed
+    /// entity type and that all its parts are valid.
+    pub fn validate_for(&self, signed_entity_type: &SignedEntityType) -> StdResult<()> {
+        let missing_parts = ProtocolMessagePartKey::required_for(signed_entity_type)
+            .into_iter()
+            .filter(|key| !self.message_parts.contains_key(key))
+            .map(|key| key.to_string())
+            .collect::<Vec<_>>();
+        if !missing_parts.is_empty() {
+            return Err(anyhow!(
+                "The protocol message of '{signed_entity_type}' is missing the parts: {}",
+                missing_parts.join(", ")
+            ));
+        }
+        for (key, value) in &self.message_parts {
+            ProtocolMessagePart::parse(*key, value).with_context(|| {
+                format!("The protocol message of '{signed_entity_type}' has an invalid part")
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Check if the parts of the given protocol message can be merged in this protocol message,
+    /// i.e. if the parts set in both messages have the same values.
+    pub fn is_compatible_with(&self, other: &ProtocolMessage) -> bool {
+        other.message_parts.iter().all(|(key, value)| {
+            self.get_message_part(key)
+                .map_or(true, |self_value| self_value == value)
+        })
+    }
+
+    /// Batch the protocol messages of other signed entity types computed at the same beacon
+    /// in this protocol message, so that they are all certified by a single certificate.
+    ///
+    /// The parts of the batched messages are merged in this message and the list of the
+    /// batched signed entity types is set in the
+    /// [BatchedSignedEntityTypes][ProtocolMessagePartKey::BatchedSignedEntityTypes] part.
+    ///
+    /// Fails if the parts of a batched message conflict with the parts already set.
+    pub fn batch(&mut self, batched: &[(SignedEntityType, ProtocolMessage)]) -> StdResult<()> {
+        if batched.is_empty() {
+            return Ok(());
+        }
+
+        let mut message = self.clone();
+        for (signed_entity_type, protocol_message) in batched {
+            if !message.is_compatible_with(protocol_message) {
+                return Err(anyhow!(
+                    "The protocol message of '{signed_entity_type}' can not be batched: its parts conflict with the parts already set"
+                ));
+            }
+            message
+                .message_parts
+                .extend(protocol_message.message_parts.clone());
+        }
+        let signed_entity_types = batched
+            .iter()
+            .map(|(signed_entity_type, _)| signed_entity_type.to_owned())
+            .collect::<Vec<_>>();
+        message.set_part(ProtocolMessagePart::BatchedSignedEntityTypes(
+            signed_entity_types,
+        ))?;
+        *self = message;
+
+        Ok(())
+    }
+
+    /// Get the signed entity types batched in this protocol message
+    pub fn get_batched_signed_entity_types(&self) -> StdResult<Vec<SignedEntityType>> {
+        match self.get_part(ProtocolMessagePartKey::BatchedSignedEntityTypes)? {
+            Some(ProtocolMessagePart::BatchedSignedEntityTypes(signed_entity_types)) => {
+                Ok(signed_entity_types)
+            }
+            _ => Ok(vec![]),
+        }
+    }
+
+    /// Get the metadata of the certificate signed with this protocol message, if any
+    pub fn get_signed_certificate_metadata(&self) -> StdResult<Option<SignedCertificateMetadata>> {
+        match self.get_part(ProtocolMessagePartKey::SignedCertificateMetadata)? {
+            Some(ProtocolMessagePart::SignedCertificateMetadata(metadata)) => Ok(Some(metadata)),
+            _ => Ok(None),
+        }
+    }
+
     /// Computes the hash of the protocol message
     pub fn compute_hash(&self) -> String {
         let mut hasher = Sha256::new();
@@ -85,6 +401,8 @@ impl ProtocolMessage {
 
 #[cfg(test)]
 mod tests {
+    use crate::test_utils::fake_data;
+
     use super::*;
 
     #[test]
@@ -151,6 +469,203 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_protocol_message_compute_hash_include_batched_signed_entity_types() {
+        let protocol_message = build_protocol_message_reference();
+        let hash_expected = protocol_message.compute_hash();
+
+        let mut protocol_message_modified = protocol_message.clone();
+        protocol_message_modified.set_message_part(
+            ProtocolMessagePartKey::BatchedSignedEntityTypes,
+            "[]".to_string(),
+        );
+
+        assert_ne!(hash_expected, protocol_message_modified.compute_hash());
+    }
+
+    #[test]
+    fn test_protocol_message_compute_hash_include_next_genesis_verification_key() {
+        let protocol_message = build_protocol_message_reference();
+        let hash_expected = protocol_message.compute_hash();
+
+        let mut protocol_message_modified = protocol_message.clone();
+        protocol_message_modified.set_message_part(
+            ProtocolMessagePartKey::NextGenesisVerificationKey,
+            "next-genesis-verification-key-456".to_string(),
+        );
+
+        assert_ne!(hash_expected, protocol_message_modified.compute_hash());
+    }
+
+    #[test]
+    fn test_protocol_message_batch_merge_parts_and_list_batched_signed_entity_types() {
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message.set_message_part(
+            ProtocolMessagePartKey::NextAggregateVerificationKey,
+            "next-avk-123".to_string(),
+        );
+        let mut batched_message = protocol_message.clone();
+        batched_message.set_message_part(
+            ProtocolMessagePartKey::SnapshotDigest,
+            "snapshot-digest-123".to_string(),
+        );
+        let batched_signed_entity_type =
+            SignedEntityType::CardanoImmutableFilesFull(fake_data::beacon());
+
+        protocol_message
+            .batch(&[(batched_signed_entity_type.clone(), batched_message)])
+            .unwrap();
+
+        assert_eq!(
+            Some(&"snapshot-digest-123".to_string()),
+            protocol_message.get_message_part(&ProtocolMessagePartKey::SnapshotDigest)
+        );
+        assert_eq!(
+            vec![batched_signed_entity_type],
+            protocol_message.get_batched_signed_entity_types().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_protocol_message_batch_fails_with_conflicting_parts() {
+        let mut protocol_message = build_protocol_message_reference();
+        let mut batched_message = ProtocolMessage::new();
+        batched_message.set_message_part(
+            ProtocolMessagePartKey::LatestImmutableFileNumber,
+            "latest-immutable-file-number-456".to_string(),
+        );
+
+        assert!(!protocol_message.is_compatible_with(&batched_message));
+        protocol_message
+            .batch(&[(
+                SignedEntityType::CardanoTransactions(fake_data::beacon()),
+                batched_message,
+            )])
+            .expect_err("batching conflicting protocol messages should fail");
+        assert_eq!(build_protocol_message_reference(), protocol_message);
+    }
+
+    #[test]
+    fn test_protocol_message_batch_nothing_does_not_set_batched_signed_entity_types() {
+        let mut protocol_message = build_protocol_message_reference();
+
+        protocol_message.batch(&[]).unwrap();
+
+        assert_eq!(build_protocol_message_reference(), protocol_message);
+        assert!(protocol_message
+            .get_batched_signed_entity_types()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_protocol_message_typed_parts_are_stored_as_their_untyped_values() {
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message
+            .set_part(ProtocolMessagePart::LatestImmutableFileNumber(123))
+            .unwrap();
+        protocol_message
+            .set_part(ProtocolMessagePart::SnapshotDigest(
+                "snapshot-digest-123".to_string(),
+            ))
+            .unwrap();
+
+        let mut expected_protocol_message = ProtocolMessage::new();
+        expected_protocol_message.set_message_part(
+            ProtocolMessagePartKey::LatestImmutableFileNumber,
+            "123".to_string(),
+        );
+        expected_protocol_message.set_message_part(
+            ProtocolMessagePartKey::SnapshotDigest,
+            "snapshot-digest-123".to_string(),
+        );
+        assert_eq!(expected_protocol_message, protocol_message);
+        assert_eq!(
+            Some(123),
+            protocol_message.latest_immutable_file_number().unwrap()
+        );
+        assert_eq!(
+            Some(ProtocolMessagePart::SnapshotDigest(
+                "snapshot-digest-123".to_string()
+            )),
+            protocol_message
+                .get_part(ProtocolMessagePartKey::SnapshotDigest)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_protocol_message_signed_certificate_metadata_is_stored_as_json() {
+        let signed_metadata =
+            SignedCertificateMetadata::from(&fake_data::certificate("hash".to_string()).metadata);
+        let mut protocol_message = ProtocolMessage::new();
+        assert_eq!(
+            None,
+            protocol_message.get_signed_certificate_metadata().unwrap()
+        );
+
+        protocol_message
+            .set_part(ProtocolMessagePart::SignedCertificateMetadata(
+                signed_metadata.clone(),
+            ))
+            .unwrap();
+
+        assert_eq!(
+            Some(&serde_json::to_string(&signed_metadata).unwrap()),
+            protocol_message.get_message_part(&ProtocolMessagePartKey::SignedCertificateMetadata)
+        );
+        assert_eq!(
+            Some(signed_metadata),
+            protocol_message.get_signed_certificate_metadata().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_protocol_message_get_part_fails_with_invalid_value() {
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message.set_message_part(
+            ProtocolMessagePartKey::LatestImmutableFileNumber,
+            "not-a-number".to_string(),
+        );
+
+        protocol_message
+            .latest_immutable_file_number()
+            .expect_err("an invalid immutable file number should not be parsed");
+    }
+
+    #[test]
+    fn test_protocol_message_validate_for_signed_entity_type_requires_its_parts() {
+        let signed_entity_type = SignedEntityType::CardanoTransactions(fake_data::beacon());
+        let mut protocol_message = ProtocolMessage::new();
+        protocol_message
+            .set_part(ProtocolMessagePart::CardanoTransactionsMerkleRoot(
+                "ctx-merkle-root-123".to_string(),
+            ))
+            .unwrap();
+        protocol_message
+            .set_part(ProtocolMessagePart::NextAggregateVerificationKey(
+                "next-avk-123".to_string(),
+            ))
+            .unwrap();
+
+        protocol_message
+            .validate_for(&signed_entity_type)
+            .expect_err("a message without the latest immutable file number should be invalid");
+
+        protocol_message.set_message_part(
+            ProtocolMessagePartKey::LatestImmutableFileNumber,
+            "invalid".to_string(),
+        );
+        protocol_message
+            .validate_for(&signed_entity_type)
+            .expect_err("a message with an invalid part should be invalid");
+
+        protocol_message
+            .set_part(ProtocolMessagePart::LatestImmutableFileNumber(123))
+            .unwrap();
+        protocol_message.validate_for(&signed_entity_type).unwrap();
+    }
+
     fn build_protocol_message_reference() -> ProtocolMessage {
         let mut protocol_message = ProtocolMessage::new();
         protocol_message.set_message_part(
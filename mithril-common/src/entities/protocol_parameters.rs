@@ -35,6 +35,55 @@ impl ProtocolParameters {
         hasher.update(self.phi_f_fixed().to_be_bytes());
         hex::encode(hasher.finalize())
     }
+
+    /// Probability for a participant holding the given ratio of the total stake to win one of
+    /// the lotteries, i.e. phi(w) = 1 - (1 - f)^w
+    pub fn phi(&self, stake_ratio: f64) -> f64 {
+        1.0 - (1.0 - self.phi_f).powf(stake_ratio)
+    }
+
+    /// Probability to reach the quorum, i.e. to win at least `k` of the `m` lotteries, when
+    /// the participants holding the given ratio of the total stake sign.
+    ///
+    /// The lotteries won by the participants are independent, so a lottery is won by at least
+    /// one of them with the probability phi of their cumulated stake, and the number of won
+    /// lotteries follows a binomial distribution.
+    pub fn quorum_probability(&self, participating_stake_ratio: f64) -> f64 {
+        if self.k == 0 {
+            return 1.0;
+        }
+        if self.k > self.m {
+            return 0.0;
+        }
+        let p = self.phi(participating_stake_ratio.clamp(0.0, 1.0));
+        if p <= 0.0 {
+            return 0.0;
+        }
+        if p >= 1.0 {
+            return 1.0;
+        }
+
+        // Sum the probabilities of winning i lotteries for i in [k, m], computed in log space
+        // to not underflow with large values of m. The computation is linear in m.
+        let (ln_p, ln_q) = (p.ln(), (-p).ln_1p());
+        let mut ln_mass = self.m as f64 * ln_q;
+        let mut ln_sum = f64::NEG_INFINITY;
+        for i in 0..=self.m {
+            if i >= self.k {
+                let (max, min) = if ln_sum > ln_mass {
+                    (ln_sum, ln_mass)
+                } else {
+                    (ln_mass, ln_sum)
+                };
+                ln_sum = max + (min - max).exp().ln_1p();
+            }
+            if i < self.m {
+                ln_mass += ((self.m - i) as f64 / (i + 1) as f64).ln() + ln_p - ln_q;
+            }
+        }
+
+        ln_sum.exp().min(1.0)
+    }
 }
 
 impl PartialEq<ProtocolParameters> for ProtocolParameters {
@@ -92,4 +141,60 @@ mod tests {
             ProtocolParameters::new(1000, 100, 0.124).compute_hash()
         );
     }
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn phi_of_the_total_stake_is_phi_f() {
+        let protocol_parameters = ProtocolParameters::new(5, 100, 0.65);
+
+        assert_close(0.65, protocol_parameters.phi(1.0));
+        assert_close(0.0, protocol_parameters.phi(0.0));
+        assert_close(1.0 - 0.35_f64.sqrt(), protocol_parameters.phi(0.5));
+    }
+
+    #[test]
+    fn quorum_probability_of_small_parameters() {
+        assert_close(
+            0.5,
+            ProtocolParameters::new(1, 1, 0.5).quorum_probability(1.0),
+        );
+        assert_close(
+            0.75,
+            ProtocolParameters::new(1, 2, 0.5).quorum_probability(1.0),
+        );
+        assert_close(
+            0.25,
+            ProtocolParameters::new(2, 2, 0.5).quorum_probability(1.0),
+        );
+        assert_close(
+            1.0,
+            ProtocolParameters::new(0, 2, 0.5).quorum_probability(0.0),
+        );
+        assert_close(
+            0.0,
+            ProtocolParameters::new(3, 2, 0.5).quorum_probability(1.0),
+        );
+        assert_close(
+            0.0,
+            ProtocolParameters::new(1, 2, 0.5).quorum_probability(0.0),
+        );
+    }
+
+    #[test]
+    fn quorum_probability_of_large_parameters() {
+        let protocol_parameters = ProtocolParameters::new(2422, 20973, 0.2);
+
+        assert!(protocol_parameters.quorum_probability(1.0) > 0.999999);
+        assert!(protocol_parameters.quorum_probability(0.5) < 0.001);
+        assert!(
+            protocol_parameters.quorum_probability(0.6)
+                > protocol_parameters.quorum_probability(0.5)
+        );
+    }
 }
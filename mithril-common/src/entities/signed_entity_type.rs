@@ -1,10 +1,13 @@
 use crate::StdResult;
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
 use digest::Update;
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use std::time::Duration;
-use strum::{AsRefStr, Display, EnumDiscriminants, EnumString};
+use strum::{AsRefStr, EnumDiscriminants};
 
 use super::{CardanoDbBeacon, Epoch, TimePoint};
 
@@ -26,10 +29,15 @@ const ENTITY_TYPE_CARDANO_TRANSACTIONS: usize = 3;
 /// are identified by their discriminant (i.e. index in the enum), thus the
 /// modification of this type should only ever consist of appending new
 /// variants.
+///
+/// Its canonical textual representation, used by the HTTP routes, the CLI arguments and the
+/// runtime decisions stored in database, is the canonical name of its discriminant followed by its
+/// beacon fields, i.e.: `mithril-stake-distribution/epoch=5` or
+/// `cardano-transactions/network=devnet/epoch=5/immutable=123`. The `%`, `/` and `=` characters
+/// of the network are percent-encoded.
 // Important note: The order of the variants is important as it is used for the derived Ord trait.
-#[derive(Display, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, EnumDiscriminants)]
-#[strum(serialize_all = "PascalCase")]
-#[strum_discriminants(derive(EnumString, AsRefStr, Serialize, Deserialize, PartialOrd, Ord))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, EnumDiscriminants)]
+#[strum_discriminants(derive(AsRefStr, Serialize, Deserialize, PartialOrd, Ord))]
 pub enum SignedEntityType {
     /// Mithril stake distribution
     MithrilStakeDistribution(Epoch),
@@ -139,7 +147,156 @@ impl SignedEntityType {
     }
 }
 
+impl Display for SignedEntityType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let discriminant = SignedEntityTypeDiscriminants::from(self);
+        match self {
+            Self::MithrilStakeDistribution(epoch) | Self::CardanoStakeDistribution(epoch) => {
+                write!(f, "{discriminant}/epoch={epoch}")
+            }
+            Self::CardanoImmutableFilesFull(beacon) | Self::CardanoTransactions(beacon) => write!(
+                f,
+                "{discriminant}/network={}/epoch={}/immutable={}",
+                escape_beacon_value(&beacon.network),
+                beacon.epoch,
+                beacon.immutable_file_number
+            ),
+        }
+    }
+}
+
+impl FromStr for SignedEntityType {
+    type Err = anyhow::Error;
+
+    /// Parse the canonical textual representation of a signed entity type, ie:
+    /// `cardano-transactions/network=devnet/epoch=5/immutable=123`.
+    ///
+    /// The beacon fields can be given in any order.
+    fn from_str(value: &str) -> StdResult<Self> {
+        let mut segments = value.trim().split('/');
+        let discriminant =
+            SignedEntityTypeDiscriminants::from_str(segments.next().unwrap_or_default())?;
+        let mut fields = BeaconFields::parse(segments)?;
+
+        let signed_entity_type = match discriminant {
+            SignedEntityTypeDiscriminants::MithrilStakeDistribution => {
+                Self::MithrilStakeDistribution(fields.take_epoch()?)
+            }
+            SignedEntityTypeDiscriminants::CardanoStakeDistribution => {
+                Self::CardanoStakeDistribution(fields.take_epoch()?)
+            }
+            SignedEntityTypeDiscriminants::CardanoImmutableFilesFull => {
+                Self::CardanoImmutableFilesFull(fields.take_cardano_db_beacon()?)
+            }
+            SignedEntityTypeDiscriminants::CardanoTransactions => {
+                Self::CardanoTransactions(fields.take_cardano_db_beacon()?)
+            }
+        };
+        fields.ensure_all_taken(&discriminant)?;
+
+        Ok(signed_entity_type)
+    }
+}
+
+/// Percent-encode the characters of a beacon field value that are reserved by the textual
+/// representation of a [SignedEntityType].
+fn escape_beacon_value(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('/', "%2F")
+        .replace('=', "%3D")
+}
+
+/// Decode a beacon field value encoded by [escape_beacon_value].
+fn unescape_beacon_value(value: &str) -> StdResult<String> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut remaining = value;
+    while let Some(position) = remaining.find('%') {
+        unescaped.push_str(&remaining[..position]);
+        let escaped = remaining
+            .get(position..position + 3)
+            .ok_or_else(|| anyhow!("truncated escape sequence in '{value}'"))?;
+        let character = match escaped.to_ascii_uppercase().as_str() {
+            "%25" => '%',
+            "%2F" => '/',
+            "%3D" => '=',
+            _ => return Err(anyhow!("invalid escape sequence '{escaped}' in '{value}'")),
+        };
+        unescaped.push(character);
+        remaining = &remaining[position + 3..];
+    }
+    unescaped.push_str(remaining);
+
+    Ok(unescaped)
+}
+
+/// `field=value` segments of the textual representation of a [SignedEntityType] beacon
+struct BeaconFields<'a>(BTreeMap<&'a str, String>);
+
+impl<'a> BeaconFields<'a> {
+    fn parse(segments: impl Iterator<Item = &'a str>) -> StdResult<Self> {
+        let mut fields = BTreeMap::new();
+        for segment in segments {
+            let (key, value) = segment
+                .split_once('=')
+                .ok_or_else(|| anyhow!("'{segment}' is not a 'field=value' pair"))?;
+            let value = unescape_beacon_value(value)
+                .with_context(|| format!("invalid beacon field '{key}'"))?;
+            if fields.insert(key, value).is_some() {
+                return Err(anyhow!("duplicate beacon field '{key}'"));
+            }
+        }
+
+        Ok(Self(fields))
+    }
+
+    fn take(&mut self, key: &str) -> StdResult<String> {
+        self.0
+            .remove(key)
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| anyhow!("missing beacon field '{key}'"))
+    }
+
+    fn take_number(&mut self, key: &str) -> StdResult<u64> {
+        let value = self.take(key)?;
+        value
+            .parse::<u64>()
+            .with_context(|| format!("invalid beacon field '{key}': '{value}'"))
+    }
+
+    fn take_epoch(&mut self) -> StdResult<Epoch> {
+        self.take_number("epoch").map(Epoch)
+    }
+
+    fn take_cardano_db_beacon(&mut self) -> StdResult<CardanoDbBeacon> {
+        let network = self.take("network")?;
+        let epoch = self.take_number("epoch")?;
+        let immutable_file_number = self.take_number("immutable")?;
+
+        Ok(CardanoDbBeacon::new(&network, epoch, immutable_file_number))
+    }
+
+    fn ensure_all_taken(&self, discriminant: &SignedEntityTypeDiscriminants) -> StdResult<()> {
+        match self.0.keys().next() {
+            Some(key) => Err(anyhow!(
+                "unexpected beacon field '{key}' for signed entity type '{discriminant}'"
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
 impl SignedEntityTypeDiscriminants {
+    /// Get all the discriminants
+    pub fn all() -> BTreeSet<Self> {
+        BTreeSet::from([
+            Self::MithrilStakeDistribution,
+            Self::CardanoStakeDistribution,
+            Self::CardanoImmutableFilesFull,
+            Self::CardanoTransactions,
+        ])
+    }
+
     /// Get the database value from enum's instance
     pub fn index(&self) -> usize {
         match self {
@@ -160,12 +317,179 @@ impl SignedEntityTypeDiscriminants {
             index => Err(anyhow!("Invalid entity_type_id {index}.")),
         }
     }
+
+    /// Get the canonical name of the discriminant, as used in the HTTP routes, the CLI arguments
+    /// and the textual representation of a [SignedEntityType].
+    pub fn canonical_name(&self) -> &'static str {
+        match self {
+            Self::MithrilStakeDistribution => "mithril-stake-distribution",
+            Self::CardanoStakeDistribution => "cardano-stake-distribution",
+            Self::CardanoImmutableFilesFull => "cardano-immutable-files-full",
+            Self::CardanoTransactions => "cardano-transactions",
+        }
+    }
+}
+
+impl Display for SignedEntityTypeDiscriminants {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.canonical_name())
+    }
+}
+
+impl FromStr for SignedEntityTypeDiscriminants {
+    type Err = anyhow::Error;
+
+    /// Parse a discriminant from its canonical name (`cardano-transactions`) or from its variant
+    /// name (`CardanoTransactions`), the latter being kept for the existing configurations.
+    fn from_str(value: &str) -> StdResult<Self> {
+        Self::all()
+            .into_iter()
+            .find(|discriminant| {
+                value == discriminant.canonical_name() || value == discriminant.as_ref()
+            })
+            .ok_or_else(|| anyhow!("unknown signed entity type: '{value}'"))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::entities::ChainPoint;
+
     use super::*;
 
+    fn all_signed_entity_types() -> Vec<SignedEntityType> {
+        let time_point = TimePoint::new(5, 123, ChainPoint::dummy());
+        SignedEntityTypeDiscriminants::all()
+            .iter()
+            .map(|discriminant| {
+                SignedEntityType::from_time_point(discriminant, "devnet", &time_point)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn display_signed_entity_type_in_its_canonical_form() {
+        assert_eq!(
+            vec![
+                "mithril-stake-distribution/epoch=5",
+                "cardano-stake-distribution/epoch=5",
+                "cardano-immutable-files-full/network=devnet/epoch=5/immutable=123",
+                "cardano-transactions/network=devnet/epoch=5/immutable=123",
+            ],
+            all_signed_entity_types()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn signed_entity_type_display_and_parse_round_trip() {
+        for signed_entity_type in all_signed_entity_types() {
+            let text = signed_entity_type.to_string();
+
+            assert_eq!(
+                signed_entity_type,
+                text.parse::<SignedEntityType>().unwrap(),
+                "round trip failed for '{text}'"
+            );
+        }
+    }
+
+    #[test]
+    fn escape_the_reserved_characters_of_the_network() {
+        let signed_entity_type =
+            SignedEntityType::CardanoTransactions(CardanoDbBeacon::new("net/work=1%", 5, 123));
+        let text = signed_entity_type.to_string();
+
+        assert_eq!(
+            "cardano-transactions/network=net%2Fwork%3D1%25/epoch=5/immutable=123",
+            text
+        );
+        assert_eq!(
+            signed_entity_type,
+            text.parse::<SignedEntityType>().unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_lowercase_escape_sequences() {
+        assert_eq!(
+            SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::new("a/b", 5, 123)),
+            "cardano-immutable-files-full/network=a%2fb/epoch=5/immutable=123"
+                .parse::<SignedEntityType>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_signed_entity_type_with_beacon_fields_in_any_order() {
+        assert_eq!(
+            SignedEntityType::CardanoTransactions(CardanoDbBeacon::new("devnet", 5, 123)),
+            "cardano-transactions/immutable=123/epoch=5/network=devnet"
+                .parse::<SignedEntityType>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_signed_entity_type_with_the_variant_name_as_discriminant() {
+        assert_eq!(
+            SignedEntityType::MithrilStakeDistribution(Epoch(5)),
+            "MithrilStakeDistribution/epoch=5"
+                .parse::<SignedEntityType>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_invalid_signed_entity_type_fails() {
+        for invalid in [
+            "",
+            "unknown/epoch=5",
+            "mithril-stake-distribution",
+            "mithril-stake-distribution/epoch=",
+            "mithril-stake-distribution/epoch=five",
+            "mithril-stake-distribution/epoch5",
+            "mithril-stake-distribution/epoch=5/epoch=6",
+            "mithril-stake-distribution/epoch=5/immutable=123",
+            "cardano-transactions/epoch=5/immutable=123",
+            "cardano-transactions/network=devnet/epoch=5",
+            "cardano-transactions/network=dev%/epoch=5/immutable=123",
+            "cardano-transactions/network=dev%2/epoch=5/immutable=123",
+            "cardano-transactions/network=dev%41/epoch=5/immutable=123",
+        ] {
+            invalid
+                .parse::<SignedEntityType>()
+                .expect_err(&format!("parsing '{invalid}' should fail"));
+        }
+    }
+
+    #[test]
+    fn discriminant_display_and_parse_round_trip() {
+        for discriminant in SignedEntityTypeDiscriminants::all() {
+            assert_eq!(discriminant.canonical_name(), discriminant.to_string());
+            assert_eq!(
+                discriminant,
+                discriminant
+                    .to_string()
+                    .parse::<SignedEntityTypeDiscriminants>()
+                    .unwrap()
+            );
+            assert_eq!(
+                discriminant,
+                discriminant
+                    .as_ref()
+                    .parse::<SignedEntityTypeDiscriminants>()
+                    .unwrap()
+            );
+        }
+
+        "unknown"
+            .parse::<SignedEntityTypeDiscriminants>()
+            .expect_err("parsing an unknown discriminant should fail");
+    }
+
     // Expected ord:
     // MithrilStakeDistribution < CardanoStakeDistribution < CardanoImmutableFilesFull < CardanoTransactions
     #[test]
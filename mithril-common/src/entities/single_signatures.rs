@@ -3,8 +3,9 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Formatter};
 
 use crate::{
-    crypto_helper::ProtocolSingleSignature,
+    crypto_helper::{ProtocolSignerVerificationKeySignature, ProtocolSingleSignature},
     entities::{LotteryIndex, PartyId},
+    era::SupportedEra,
 };
 
 /// SingleSignatures represent single signatures originating from a participant in the network
@@ -20,6 +21,14 @@ pub struct SingleSignatures {
     /// The indexes of the won lotteries that lead to the single signatures
     #[serde(rename = "indexes")]
     pub won_indexes: Vec<LotteryIndex>,
+
+    /// The era under which the signer produced the signature, if advertised
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub era: Option<SupportedEra>,
+
+    /// The KES signature of the registration nonce of the epoch, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce_signature: Option<ProtocolSignerVerificationKeySignature>,
 }
 
 impl SingleSignatures {
@@ -33,9 +42,26 @@ impl SingleSignatures {
             party_id,
             signature,
             won_indexes,
+            era: None,
+            nonce_signature: None,
         }
     }
 
+    /// Set the era under which the signature was produced
+    pub fn with_era(mut self, era: SupportedEra) -> Self {
+        self.era = Some(era);
+        self
+    }
+
+    /// Set the KES signature of the registration nonce that authenticates the signature
+    pub fn with_nonce_signature(
+        mut self,
+        nonce_signature: ProtocolSignerVerificationKeySignature,
+    ) -> Self {
+        self.nonce_signature = Some(nonce_signature);
+        self
+    }
+
     /// Convert this [SingleSignatures] to its corresponding [MithrilStm Signature][StmSig].
     pub fn to_protocol_signature(&self) -> StmSig {
         self.signature.clone().into()
@@ -48,11 +74,16 @@ impl Debug for SingleSignatures {
         let mut debug = f.debug_struct("SingleSignatures");
         debug
             .field("party_id", &self.party_id)
-            .field("won_indexes", &format_args!("{:?}", self.won_indexes));
+            .field("won_indexes", &format_args!("{:?}", self.won_indexes))
+            .field("era", &format_args!("{:?}", self.era));
 
         match is_pretty_printing {
             true => debug
                 .field("signature", &format_args!("{:?}", self.signature))
+                .field(
+                    "nonce_signature",
+                    &format_args!("{:?}", self.nonce_signature),
+                )
                 .finish(),
             false => debug.finish_non_exhaustive(),
         }
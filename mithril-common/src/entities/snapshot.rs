@@ -23,6 +23,21 @@ pub struct Snapshot {
 
     /// Version of the Cardano node used to create snapshot archive.
     pub cardano_node_version: String,
+
+    /// Size of the ancillary files archive in Bytes, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ancillary_size: Option<u64>,
+
+    /// Locations where the archive of the ancillary files (ledger state snapshots and volatile
+    /// database) can be retrieved, if any
+    ///
+    /// The ancillary files are not certified by the snapshot digest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ancillary_locations: Option<Vec<String>>,
+
+    /// Hex encoded SHA-256 of the snapshot archive, if computed when the snapshot was created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_hash: Option<String>,
 }
 
 /// Compression algorithm for the snapshot archive artifacts.
@@ -81,8 +96,24 @@ impl Snapshot {
             locations,
             compression_algorithm,
             cardano_node_version,
+            ancillary_size: None,
+            ancillary_locations: None,
+            archive_hash: None,
         }
     }
+
+    /// Set the hex encoded SHA-256 of the snapshot archive
+    pub fn with_archive_hash(mut self, archive_hash: String) -> Self {
+        self.archive_hash = Some(archive_hash);
+        self
+    }
+
+    /// Set the archive of the ancillary files of the snapshot
+    pub fn with_ancillary_archive(mut self, size: u64, locations: Vec<String>) -> Self {
+        self.ancillary_size = Some(size);
+        self.ancillary_locations = Some(locations);
+        self
+    }
 }
 
 #[typetag::serde]
@@ -0,0 +1,490 @@
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::ops::RangeInclusive;
+use std::path::{Component, Path, PathBuf};
+
+use crate::entities::{CardanoDbBeacon, ImmutableFileNumber};
+use crate::StdResult;
+
+/// Hash of a content addressed object stored by a deduplicating snapshot uploader
+pub type SnapshotObjectHash = String;
+
+/// Extensions of the files of an immutable file number, for each naming scheme of the
+/// 'immutable' directory (see `ImmutableFileLayout` in the `digesters` module).
+const IMMUTABLE_FILE_EXTENSIONS: [&str; 3] = ["chunk", "primary", "secondary"];
+const PREFIXED_IMMUTABLE_FILE_EXTENSIONS: [&str; 4] = ["chunk", "metadata", "primary", "secondary"];
+
+/// A file of a snapshot, stored as a content addressed object
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifestEntry {
+    /// Path of the file, relative to the root of the snapshot
+    pub path: String,
+
+    /// Hash of the content of the file, used as the object identifier
+    pub object_hash: SnapshotObjectHash,
+
+    /// Size of the file in Bytes
+    pub size: u64,
+}
+
+impl SnapshotManifestEntry {
+    /// Path of the file relative to the root of the snapshot, checked so that it can be safely
+    /// joined to the directory where the snapshot is reassembled.
+    ///
+    /// Fails if the path is empty, absolute, or contains a `.` or `..` component.
+    pub fn relative_path(&self) -> StdResult<PathBuf> {
+        let path = Path::new(&self.path);
+        if self.path.is_empty()
+            || !path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(anyhow!(
+                "Invalid snapshot manifest entry path: '{}', it must be a relative path without '.' or '..' components",
+                self.path
+            ));
+        }
+
+        Ok(path.to_path_buf())
+    }
+
+    /// Number of the immutable file this entry is part of, `None` if the entry is not in the
+    /// 'immutable' directory of the snapshot (i.e. the ledger state or the protocol magic id).
+    ///
+    /// Both the `00042.chunk` and the `immutable_00042.chunk` file naming schemes are supported.
+    pub fn immutable_file_number(&self) -> Option<ImmutableFileNumber> {
+        let mut components = self.path.rsplit('/');
+        let file_name = components.next()?;
+        if components.next()? != "immutable" {
+            return None;
+        }
+        let (file_stem, _extension) = file_name.split_once('.')?;
+
+        file_stem
+            .strip_prefix("immutable_")
+            .unwrap_or(file_stem)
+            .parse()
+            .ok()
+    }
+
+    /// Check if the entry is one of the immutable files hashed in the snapshot digest, the
+    /// other files of the 'immutable' directory are not part of it.
+    fn is_digested_immutable_file(&self) -> bool {
+        let path = Path::new(&self.path);
+        let is_prefixed = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("immutable_"));
+        let extensions: &[&str] = if is_prefixed {
+            &PREFIXED_IMMUTABLE_FILE_EXTENSIONS
+        } else {
+            &IMMUTABLE_FILE_EXTENSIONS
+        };
+
+        self.immutable_file_number().is_some()
+            && path
+                .extension()
+                .is_some_and(|extension| extensions.contains(&extension.to_string_lossy().as_ref()))
+    }
+}
+
+/// Manifest describing how to reassemble a snapshot from content addressed objects.
+///
+/// Consecutive snapshots share most of their immutable files: storing each file once as an
+/// object and one manifest per snapshot avoids uploading and storing the same content again.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// Digest of the snapshot described by this manifest
+    pub digest: String,
+
+    /// Base url of the location where the objects can be retrieved
+    pub objects_location: String,
+
+    /// Files of the snapshot
+    pub entries: Vec<SnapshotManifestEntry>,
+}
+
+impl SnapshotManifest {
+    /// Extension of the snapshot manifest files
+    pub const FILE_EXTENSION: &'static str = "manifest.json";
+
+    /// SnapshotManifest factory
+    pub fn new(
+        digest: String,
+        objects_location: String,
+        entries: Vec<SnapshotManifestEntry>,
+    ) -> Self {
+        Self {
+            digest,
+            objects_location,
+            entries,
+        }
+    }
+
+    /// Name of the manifest file of the snapshot with the given digest
+    pub fn file_name(digest: &str) -> String {
+        format!("{digest}.{}", Self::FILE_EXTENSION)
+    }
+
+    /// Check if the given snapshot location targets a manifest instead of an archive
+    pub fn is_manifest_location(location: &str) -> bool {
+        location.ends_with(Self::FILE_EXTENSION)
+    }
+
+    /// Compute the hash identifying the object with the given content
+    pub fn compute_object_hash(content: &[u8]) -> SnapshotObjectHash {
+        hex::encode(Sha256::digest(content))
+    }
+
+    /// Location of the object with the given hash
+    pub fn object_location(&self, object_hash: &str) -> String {
+        format!(
+            "{}/{}",
+            self.objects_location.trim_end_matches('/'),
+            object_hash
+        )
+    }
+
+    /// Total size of the files of the snapshot in Bytes
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().map(|e| e.size).sum()
+    }
+
+    /// Compute the digest of the snapshot at the given beacon from the files of this manifest.
+    ///
+    /// The object hash of an entry being the SHA-256 of its content, the result is the digest the
+    /// `CardanoImmutableDigester` computes from the reassembled Cardano node database, without
+    /// having to download it.
+    ///
+    /// Fails if the manifest does not list the immutable files up to the beacon.
+    pub fn compute_snapshot_digest(&self, beacon: &CardanoDbBeacon) -> StdResult<String> {
+        let mut immutable_entries: Vec<(ImmutableFileNumber, &SnapshotManifestEntry)> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.is_digested_immutable_file())
+            .filter_map(|entry| entry.immutable_file_number().map(|number| (number, entry)))
+            .filter(|(number, _)| *number <= beacon.immutable_file_number)
+            .collect();
+        immutable_entries.sort_by(|(number, entry), (other_number, other_entry)| {
+            number
+                .cmp(other_number)
+                .then(entry.path.cmp(&other_entry.path))
+        });
+
+        match immutable_entries.last() {
+            Some((last_number, _)) if *last_number == beacon.immutable_file_number => {}
+            last_entry => {
+                return Err(anyhow!(
+                    "The snapshot manifest does not list the immutable files up to {}, last immutable file listed: {:?}",
+                    beacon.immutable_file_number,
+                    last_entry.map(|(number, _)| number)
+                ));
+            }
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(beacon.compute_hash().as_bytes());
+        for (_, entry) in immutable_entries {
+            hasher.update(entry.object_hash.as_bytes());
+        }
+
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// Verify that this manifest describes the snapshot with the given certified digest at the
+    /// given beacon.
+    ///
+    /// The [digest][SnapshotManifest::digest] of the manifest is not trusted, the digest of the
+    /// snapshot is recomputed from its files.
+    pub fn verify_snapshot_digest(
+        &self,
+        certified_digest: &str,
+        beacon: &CardanoDbBeacon,
+    ) -> StdResult<()> {
+        let computed_digest = self.compute_snapshot_digest(beacon)?;
+        if self.digest != certified_digest || computed_digest != certified_digest {
+            return Err(anyhow!(
+                "The snapshot manifest does not match the certified digest '{certified_digest}', manifest digest: '{}', computed digest: '{computed_digest}'",
+                self.digest
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Copy of this manifest restricted to the immutable files of the given range (both
+    /// included), the files outside the 'immutable' directory are excluded too.
+    ///
+    /// Warning: the [digest][SnapshotManifest::digest] of the restricted manifest is kept as is,
+    /// it does not match its files anymore.
+    pub fn restrict_to_immutable_files_range(
+        &self,
+        immutable_files_range: &RangeInclusive<ImmutableFileNumber>,
+    ) -> Self {
+        let entries = self
+            .entries
+            .iter()
+            .filter(|entry| {
+                entry
+                    .immutable_file_number()
+                    .is_some_and(|number| immutable_files_range.contains(&number))
+            })
+            .cloned()
+            .collect();
+
+        Self::new(self.digest.clone(), self.objects_location.clone(), entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_manifest_location_only_match_manifest_files() {
+        assert!(SnapshotManifest::is_manifest_location(
+            "http://whatever/snapshot_manifest/abc.manifest.json"
+        ));
+        assert!(!SnapshotManifest::is_manifest_location(
+            "http://whatever/snapshot_download/devnet-e1-i2.abc.tar.zst"
+        ));
+    }
+
+    fn entry(path: &str) -> SnapshotManifestEntry {
+        SnapshotManifestEntry {
+            path: path.to_string(),
+            object_hash: "hash".to_string(),
+            size: 1,
+        }
+    }
+
+    #[test]
+    fn immutable_file_number_is_only_parsed_for_files_of_the_immutable_directory() {
+        assert_eq!(
+            Some(42),
+            entry("immutable/00042.chunk").immutable_file_number()
+        );
+        assert_eq!(
+            Some(42),
+            entry("db/immutable/00042.primary").immutable_file_number()
+        );
+        assert_eq!(
+            Some(42),
+            entry("immutable/immutable_00042.metadata").immutable_file_number()
+        );
+        assert_eq!(None, entry("protocolMagicId").immutable_file_number());
+        assert_eq!(None, entry("ledger/00042").immutable_file_number());
+        assert_eq!(
+            None,
+            entry("immutable/not_a_number.chunk").immutable_file_number()
+        );
+    }
+
+    #[test]
+    fn relative_path_rejects_paths_escaping_the_snapshot_directory() {
+        assert_eq!(
+            PathBuf::from("immutable/00042.chunk"),
+            entry("immutable/00042.chunk").relative_path().unwrap()
+        );
+
+        for invalid_path in [
+            "",
+            "/etc/passwd",
+            "../outside",
+            "immutable/../../outside",
+            "./protocolMagicId",
+        ] {
+            entry(invalid_path)
+                .relative_path()
+                .expect_err(&format!("path '{invalid_path}' should be rejected"));
+        }
+    }
+
+    #[test]
+    fn restrict_to_immutable_files_range_only_keep_the_immutable_files_in_range() {
+        let manifest = SnapshotManifest::new(
+            "digest".to_string(),
+            "http://whatever/objects".to_string(),
+            vec![
+                entry("immutable/00001.chunk"),
+                entry("immutable/00002.chunk"),
+                entry("immutable/00002.primary"),
+                entry("immutable/00003.chunk"),
+                entry("immutable/00004.chunk"),
+                entry("ledger/1234"),
+                entry("protocolMagicId"),
+            ],
+        );
+
+        let restricted_manifest = manifest.restrict_to_immutable_files_range(&(2..=3));
+
+        assert_eq!(
+            vec![
+                entry("immutable/00002.chunk"),
+                entry("immutable/00002.primary"),
+                entry("immutable/00003.chunk"),
+            ],
+            restricted_manifest.entries
+        );
+        assert_eq!(manifest.digest, restricted_manifest.digest);
+    }
+
+    #[test]
+    fn object_location_does_not_duplicate_separator() {
+        let manifest = SnapshotManifest::new(
+            "digest".to_string(),
+            "http://whatever/objects/".to_string(),
+            vec![],
+        );
+
+        assert_eq!(
+            "http://whatever/objects/hash",
+            manifest.object_location("hash")
+        );
+    }
+
+    #[test]
+    fn compute_object_hash_is_deterministic_and_content_dependant() {
+        assert_eq!(
+            SnapshotManifest::compute_object_hash(b"content"),
+            SnapshotManifest::compute_object_hash(b"content")
+        );
+        assert_ne!(
+            SnapshotManifest::compute_object_hash(b"content"),
+            SnapshotManifest::compute_object_hash(b"other content")
+        );
+    }
+
+    fn entry_with_hash(path: &str, object_hash: &str) -> SnapshotManifestEntry {
+        SnapshotManifestEntry {
+            path: path.to_string(),
+            object_hash: object_hash.to_string(),
+            size: 1,
+        }
+    }
+
+    #[test]
+    fn compute_snapshot_digest_only_hash_the_immutable_files_up_to_the_beacon() {
+        let beacon = CardanoDbBeacon::new("devnet".to_string(), 3, 2);
+        let manifest = SnapshotManifest::new(
+            "digest".to_string(),
+            "http://whatever/objects".to_string(),
+            vec![
+                entry_with_hash("immutable/00002.primary", "hash-2-primary"),
+                entry_with_hash("immutable/00001.chunk", "hash-1-chunk"),
+                entry_with_hash("immutable/00002.chunk", "hash-2-chunk"),
+                entry_with_hash("immutable/00003.chunk", "hash-3-chunk"),
+                entry_with_hash("immutable/00002.metadata", "not-an-immutable-file"),
+                entry_with_hash("ledger/1234", "hash-ledger"),
+                entry_with_hash("protocolMagicId", "hash-magic-id"),
+            ],
+        );
+
+        let mut hasher = Sha256::new();
+        hasher.update(beacon.compute_hash().as_bytes());
+        for object_hash in ["hash-1-chunk", "hash-2-chunk", "hash-2-primary"] {
+            hasher.update(object_hash.as_bytes());
+        }
+
+        assert_eq!(
+            hex::encode(hasher.finalize()),
+            manifest.compute_snapshot_digest(&beacon).unwrap()
+        );
+    }
+
+    #[test]
+    fn compute_snapshot_digest_fails_if_the_immutable_files_of_the_beacon_are_missing() {
+        let manifest = SnapshotManifest::new(
+            "digest".to_string(),
+            "http://whatever/objects".to_string(),
+            vec![entry("immutable/00001.chunk"), entry("protocolMagicId")],
+        );
+
+        manifest
+            .compute_snapshot_digest(&CardanoDbBeacon::new("devnet".to_string(), 3, 2))
+            .expect_err(
+                "compute_snapshot_digest should fail if the last immutable file is missing",
+            );
+    }
+
+    #[test]
+    fn verify_snapshot_digest_fails_if_the_files_do_not_match_the_certified_digest() {
+        let beacon = CardanoDbBeacon::new("devnet".to_string(), 3, 1);
+        let manifest = SnapshotManifest::new(
+            "digest".to_string(),
+            "http://whatever/objects".to_string(),
+            vec![entry_with_hash("immutable/00001.chunk", "hash-1-chunk")],
+        );
+        let computed_digest = manifest.compute_snapshot_digest(&beacon).unwrap();
+
+        SnapshotManifest {
+            digest: computed_digest.clone(),
+            ..manifest.clone()
+        }
+        .verify_snapshot_digest(&computed_digest, &beacon)
+        .unwrap();
+
+        manifest
+            .verify_snapshot_digest(&computed_digest, &beacon)
+            .expect_err("the digest of the manifest should match the certified digest");
+        SnapshotManifest {
+            digest: "certified-digest".to_string(),
+            ..manifest
+        }
+        .verify_snapshot_digest("certified-digest", &beacon)
+        .expect_err("the files of the manifest should match the certified digest");
+    }
+
+    #[cfg(feature = "fs")]
+    mod fs_only {
+        use crate::digesters::{
+            CardanoImmutableDigester, DummyImmutablesDbBuilder, ImmutableDigester,
+            ImmutableFileLayout,
+        };
+        use crate::test_utils::TestLogger;
+
+        use super::*;
+
+        #[tokio::test]
+        async fn compute_snapshot_digest_match_the_digest_of_the_immutable_digester() {
+            for layout in [ImmutableFileLayout::V1, ImmutableFileLayout::V2] {
+                let immutable_db = DummyImmutablesDbBuilder::new(&format!(
+                    "snapshot_manifest_compute_snapshot_digest_{layout:?}"
+                ))
+                .with_layout(layout)
+                .with_immutables(&[1, 2, 3])
+                .append_immutable_trio()
+                .with_non_immutables(&["not_an_immutable.txt"])
+                .build();
+                let beacon = CardanoDbBeacon::new("devnet".to_string(), 5, 2);
+                let entries = std::fs::read_dir(&immutable_db.dir)
+                    .unwrap()
+                    .map(|file| {
+                        let file = file.unwrap();
+                        let content = std::fs::read(file.path()).unwrap();
+                        SnapshotManifestEntry {
+                            path: format!("immutable/{}", file.file_name().to_string_lossy()),
+                            object_hash: SnapshotManifest::compute_object_hash(&content),
+                            size: content.len() as u64,
+                        }
+                    })
+                    .collect();
+                let manifest = SnapshotManifest::new(
+                    "digest".to_string(),
+                    "http://whatever/objects".to_string(),
+                    entries,
+                );
+
+                let expected_digest = CardanoImmutableDigester::new(None, TestLogger::stdout())
+                    .compute_digest(&immutable_db.dir, &beacon)
+                    .await
+                    .unwrap();
+
+                assert_eq!(
+                    expected_digest,
+                    manifest.compute_snapshot_digest(&beacon).unwrap()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{PartyId, SignerWithStake, Stake, StakeDistribution};
+
+/// Change of the stake of a party present in two consecutive stake distributions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeDelta {
+    /// Party identifier
+    pub party_id: PartyId,
+
+    /// Stake of the party in the previous stake distribution
+    pub previous_stake: Stake,
+
+    /// Stake of the party in the current stake distribution
+    pub current_stake: Stake,
+}
+
+impl StakeDelta {
+    /// Signed difference between the current and the previous stake
+    pub fn delta(&self) -> i128 {
+        self.current_stake as i128 - self.previous_stake as i128
+    }
+}
+
+/// Difference between two consecutive [stake distributions][StakeDistribution].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakeDistributionDiff {
+    /// Parties only present in the current stake distribution
+    pub new_parties: StakeDistribution,
+
+    /// Parties only present in the previous stake distribution
+    pub departed_parties: StakeDistribution,
+
+    /// Parties present in both stake distributions with a different stake
+    pub stake_deltas: Vec<StakeDelta>,
+}
+
+impl StakeDistributionDiff {
+    /// Compute the difference from the `previous` to the `current` stake distribution
+    pub fn compute(previous: &StakeDistribution, current: &StakeDistribution) -> Self {
+        let mut diff = Self::default();
+
+        for (party_id, stake) in current {
+            match previous.get(party_id) {
+                None => {
+                    diff.new_parties.insert(party_id.clone(), *stake);
+                }
+                Some(previous_stake) if previous_stake != stake => {
+                    diff.stake_deltas.push(StakeDelta {
+                        party_id: party_id.clone(),
+                        previous_stake: *previous_stake,
+                        current_stake: *stake,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        diff.departed_parties = previous
+            .iter()
+            .filter(|(party_id, _)| !current.contains_key(*party_id))
+            .map(|(party_id, stake)| (party_id.clone(), *stake))
+            .collect();
+
+        diff
+    }
+
+    /// Compute the difference from the `previous` to the `current` signers stakes
+    pub fn from_signers(previous: &[SignerWithStake], current: &[SignerWithStake]) -> Self {
+        let to_stake_distribution = |signers: &[SignerWithStake]| -> StakeDistribution {
+            signers
+                .iter()
+                .map(|signer| (signer.party_id.clone(), signer.stake))
+                .collect()
+        };
+
+        Self::compute(
+            &to_stake_distribution(previous),
+            &to_stake_distribution(current),
+        )
+    }
+
+    /// Check if the two stake distributions are identical
+    pub fn is_empty(&self) -> bool {
+        self.new_parties.is_empty()
+            && self.departed_parties.is_empty()
+            && self.stake_deltas.is_empty()
+    }
+
+    /// Signed difference between the total stake of the current and the previous stake
+    /// distributions
+    pub fn total_stake_delta(&self) -> i128 {
+        let new_stake: i128 = self.new_parties.values().map(|stake| *stake as i128).sum();
+        let departed_stake: i128 = self
+            .departed_parties
+            .values()
+            .map(|stake| *stake as i128)
+            .sum();
+        let changed_stake: i128 = self.stake_deltas.iter().map(StakeDelta::delta).sum();
+
+        new_stake - departed_stake + changed_stake
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stake_distribution(stakes: &[(&str, Stake)]) -> StakeDistribution {
+        stakes
+            .iter()
+            .map(|(party_id, stake)| (party_id.to_string(), *stake))
+            .collect()
+    }
+
+    #[test]
+    fn diff_of_identical_stake_distributions_is_empty() {
+        let distribution = stake_distribution(&[("party-1", 10), ("party-2", 20)]);
+
+        let diff = StakeDistributionDiff::compute(&distribution, &distribution);
+
+        assert!(diff.is_empty());
+        assert_eq!(0, diff.total_stake_delta());
+    }
+
+    #[test]
+    fn compute_new_departed_and_changed_parties() {
+        let previous = stake_distribution(&[("party-1", 10), ("party-2", 20), ("party-3", 30)]);
+        let current = stake_distribution(&[("party-1", 10), ("party-2", 25), ("party-4", 40)]);
+
+        let diff = StakeDistributionDiff::compute(&previous, &current);
+
+        assert_eq!(
+            StakeDistributionDiff {
+                new_parties: stake_distribution(&[("party-4", 40)]),
+                departed_parties: stake_distribution(&[("party-3", 30)]),
+                stake_deltas: vec![StakeDelta {
+                    party_id: "party-2".to_string(),
+                    previous_stake: 20,
+                    current_stake: 25,
+                }],
+            },
+            diff
+        );
+        assert_eq!(40 - 30 + 5, diff.total_stake_delta());
+    }
+
+    #[test]
+    fn stake_delta_can_be_negative() {
+        let delta = StakeDelta {
+            party_id: "party-1".to_string(),
+            previous_stake: Stake::MAX,
+            current_stake: 0,
+        };
+
+        assert_eq!(-(Stake::MAX as i128), delta.delta());
+    }
+}
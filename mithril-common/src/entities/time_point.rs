@@ -1,4 +1,4 @@
-use crate::entities::{Epoch, ImmutableFileNumber};
+use crate::entities::{ChainPoint, Epoch, ImmutableFileNumber};
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
@@ -12,21 +12,30 @@ pub struct TimePoint {
 
     /// Number of the last immutable files used for the digest computation
     pub immutable_file_number: ImmutableFileNumber,
+
+    /// Chain point, used by the signed entity types that progress with the blocks or slots
+    #[serde(default)]
+    pub chain_point: ChainPoint,
 }
 
 impl TimePoint {
     /// [TimePoint] factory
-    pub fn new(epoch: u64, immutable_file_number: ImmutableFileNumber) -> TimePoint {
+    pub fn new(
+        epoch: u64,
+        immutable_file_number: ImmutableFileNumber,
+        chain_point: ChainPoint,
+    ) -> TimePoint {
         TimePoint {
             epoch: Epoch(epoch),
             immutable_file_number,
+            chain_point,
         }
     }
 
     cfg_test_tools! {
         /// Create a dummy TimePoint
         pub fn dummy() -> Self {
-            Self::new(10, 100)
+            Self::new(10, 100, ChainPoint::dummy())
         }
     }
 }
@@ -42,6 +51,7 @@ impl Ord for TimePoint {
         self.epoch
             .cmp(&other.epoch)
             .then(self.immutable_file_number.cmp(&other.immutable_file_number))
+            .then(self.chain_point.cmp(&other.chain_point))
     }
 }
 
@@ -49,8 +59,11 @@ impl Display for TimePoint {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "TimePoint (epoch: {}, immutable_file_number: {})",
-            self.epoch, self.immutable_file_number
+            "TimePoint (epoch: {}, immutable_file_number: {}, block_number: {}, slot_number: {})",
+            self.epoch,
+            self.immutable_file_number,
+            self.chain_point.block_number,
+            self.chain_point.slot_number
         )
     }
 }
@@ -65,6 +78,7 @@ mod tests {
         let time_point1 = TimePoint {
             epoch: Epoch(0),
             immutable_file_number: 0,
+            chain_point: ChainPoint::dummy(),
         };
 
         assert_eq!(Ordering::Equal, time_point1.cmp(&time_point1));
@@ -75,10 +89,12 @@ mod tests {
         let time_point1 = TimePoint {
             epoch: Epoch(0),
             immutable_file_number: 0,
+            chain_point: ChainPoint::dummy(),
         };
         let time_point2 = TimePoint {
             epoch: Epoch(0),
             immutable_file_number: 1,
+            chain_point: ChainPoint::dummy(),
         };
 
         assert_eq!(Ordering::Less, time_point1.cmp(&time_point2));
@@ -89,10 +105,12 @@ mod tests {
         let time_point1 = TimePoint {
             epoch: Epoch(0),
             immutable_file_number: 1,
+            chain_point: ChainPoint::dummy(),
         };
         let time_point2 = TimePoint {
             epoch: Epoch(0),
             immutable_file_number: 0,
+            chain_point: ChainPoint::dummy(),
         };
 
         assert_eq!(Ordering::Greater, time_point1.cmp(&time_point2));
@@ -103,10 +121,34 @@ mod tests {
         let time_point1 = TimePoint {
             epoch: Epoch(0),
             immutable_file_number: 99,
+            chain_point: ChainPoint::dummy(),
+        };
+        let time_point2 = TimePoint {
+            epoch: Epoch(1),
+            immutable_file_number: 99,
+            chain_point: ChainPoint::dummy(),
+        };
+
+        assert_eq!(Ordering::Less, time_point1.cmp(&time_point2));
+    }
+
+    #[test]
+    fn time_point_ord_same_epoch_and_immutable_compares_chain_points() {
+        let time_point1 = TimePoint {
+            epoch: Epoch(1),
+            immutable_file_number: 99,
+            chain_point: ChainPoint {
+                block_number: 10,
+                ..ChainPoint::dummy()
+            },
         };
         let time_point2 = TimePoint {
             epoch: Epoch(1),
             immutable_file_number: 99,
+            chain_point: ChainPoint {
+                block_number: 11,
+                ..ChainPoint::dummy()
+            },
         };
 
         assert_eq!(Ordering::Less, time_point1.cmp(&time_point2));
@@ -42,6 +42,12 @@ pub type HexEncodedVerificationKey = HexEncodedKey;
 /// Hex encoded Verification Key Signature
 pub type HexEncodedVerificationKeySignature = HexEncodedKey;
 
+/// Hex encoded Registration Nonce Signature
+pub type HexEncodedRegistrationNonceSignature = HexEncodedKey;
+
+/// Hex encoded Request Origin Signature
+pub type HexEncodedRequestOriginSignature = HexEncodedKey;
+
 /// Hex encoded Operational Certificate
 pub type HexEncodedOpCert = HexEncodedKey;
 
@@ -51,6 +57,9 @@ pub type HexEncodedGenesisSecretKey = HexEncodedKey;
 /// Hex encoded Genesis Verification Key
 pub type HexEncodedGenesisVerificationKey = HexEncodedKey;
 
+/// Hex encoded Genesis Signature
+pub type HexEncodedGenesisSignature = HexEncodedKey;
+
 /// Hex encoded Sha256 Digest
 pub type HexEncodedDigest = HexEncodedKey;
 
@@ -7,9 +7,17 @@ use super::SupportedEra;
 struct SupportedEraStamp {
     era: SupportedEra,
     epoch: Epoch,
+    transition_era: Option<SupportedEra>,
+    upcoming_era: Option<(SupportedEra, Epoch)>,
 }
 
 /// EraChecker allows the verification of the current era
+///
+/// During the transition epoch of an era switch, the previous era is still accepted alongside
+/// the current era.
+///
+/// The era that will be activated next can be recorded with its activation epoch, so that the era
+/// of the upcoming epochs is known ahead of the switch.
 pub struct EraChecker {
     current_era_stamp: RwLock<SupportedEraStamp>,
 }
@@ -18,7 +26,12 @@ impl EraChecker {
     /// Era checker factory
     pub fn new(era: SupportedEra, epoch: Epoch) -> Self {
         Self {
-            current_era_stamp: RwLock::new(SupportedEraStamp { era, epoch }),
+            current_era_stamp: RwLock::new(SupportedEraStamp {
+                era,
+                epoch,
+                transition_era: None,
+                upcoming_era: None,
+            }),
         }
     }
 
@@ -32,11 +45,29 @@ impl EraChecker {
         self.current_era_stamp.read().unwrap().epoch
     }
 
+    /// Retrieve the previous era still accepted during the transition epoch, if any
+    pub fn transition_era(&self) -> Option<SupportedEra> {
+        self.current_era_stamp.read().unwrap().transition_era
+    }
+
     /// Change the current era
     pub fn change_era(&self, new_era: SupportedEra, current_epoch: Epoch) {
+        self.change_era_with_transition(new_era, current_epoch, None);
+    }
+
+    /// Change the current era, still accepting the given previous era during the transition
+    /// epoch
+    pub fn change_era_with_transition(
+        &self,
+        new_era: SupportedEra,
+        current_epoch: Epoch,
+        transition_era: Option<SupportedEra>,
+    ) {
         let new_stamp = SupportedEraStamp {
             era: new_era,
             epoch: current_epoch,
+            transition_era: transition_era.filter(|era| *era != new_era),
+            upcoming_era: None,
         };
         let mut stamp = self.current_era_stamp.write().unwrap();
         *stamp = new_stamp;
@@ -46,6 +77,46 @@ impl EraChecker {
     pub fn is_era_active(&self, era: SupportedEra) -> bool {
         self.current_era() == era
     }
+
+    /// Retrieve the accepted eras: the current era and the transition era, if any
+    pub fn accepted_eras(&self) -> Vec<SupportedEra> {
+        let stamp = self.current_era_stamp.read().unwrap();
+
+        [Some(stamp.era), stamp.transition_era]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Check if an era is accepted, either because it is active or because it is the
+    /// transition era
+    pub fn is_era_accepted(&self, era: SupportedEra) -> bool {
+        self.accepted_eras().contains(&era)
+    }
+
+    /// Record the era that will be activated at the given epoch, until the era is changed.
+    pub fn set_upcoming_era(&self, upcoming_era: SupportedEra, activation_epoch: Epoch) {
+        let mut stamp = self.current_era_stamp.write().unwrap();
+        stamp.upcoming_era = Some((upcoming_era, activation_epoch));
+    }
+
+    /// Retrieve the era of the given epoch.
+    ///
+    /// During the transition epoch the epochs that precede it belong to the transition era,
+    /// otherwise all the epochs belong to the current era since the previous era is not accepted
+    /// anymore, except the epochs from the activation epoch of the upcoming era if one was
+    /// recorded.
+    pub fn era_of_epoch(&self, epoch: Epoch) -> SupportedEra {
+        let stamp = self.current_era_stamp.read().unwrap();
+
+        match (stamp.transition_era, stamp.upcoming_era) {
+            (_, Some((upcoming_era, activation_epoch))) if epoch >= activation_epoch => {
+                upcoming_era
+            }
+            (Some(transition_era), _) if epoch < stamp.epoch => transition_era,
+            _ => stamp.era,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -62,4 +133,60 @@ mod tests {
         assert_eq!(Epoch(2), era_checker.current_epoch());
         assert!(era_checker.is_era_active(expected_era));
     }
+
+    #[test]
+    fn previous_era_is_accepted_during_the_transition_epoch() {
+        let era_checker = EraChecker::new(SupportedEra::Thales, Epoch(1));
+        era_checker.change_era_with_transition(
+            SupportedEra::Pythagoras,
+            Epoch(2),
+            Some(SupportedEra::Thales),
+        );
+
+        assert!(era_checker.is_era_active(SupportedEra::Pythagoras));
+        assert!(!era_checker.is_era_active(SupportedEra::Thales));
+        assert_eq!(
+            vec![SupportedEra::Pythagoras, SupportedEra::Thales],
+            era_checker.accepted_eras()
+        );
+        assert!(era_checker.is_era_accepted(SupportedEra::Thales));
+
+        era_checker.change_era(SupportedEra::Pythagoras, Epoch(3));
+
+        assert_eq!(None, era_checker.transition_era());
+        assert!(!era_checker.is_era_accepted(SupportedEra::Thales));
+    }
+
+    #[test]
+    fn epochs_before_the_transition_epoch_belong_to_the_transition_era() {
+        let era_checker = EraChecker::new(SupportedEra::Thales, Epoch(1));
+        era_checker.change_era_with_transition(
+            SupportedEra::Pythagoras,
+            Epoch(5),
+            Some(SupportedEra::Thales),
+        );
+
+        assert_eq!(SupportedEra::Thales, era_checker.era_of_epoch(Epoch(4)));
+        assert_eq!(SupportedEra::Pythagoras, era_checker.era_of_epoch(Epoch(5)));
+        assert_eq!(SupportedEra::Pythagoras, era_checker.era_of_epoch(Epoch(6)));
+
+        era_checker.change_era(SupportedEra::Pythagoras, Epoch(6));
+
+        assert_eq!(SupportedEra::Pythagoras, era_checker.era_of_epoch(Epoch(4)));
+    }
+
+    #[test]
+    fn epochs_from_the_activation_epoch_of_the_upcoming_era_belong_to_it() {
+        let era_checker = EraChecker::new(SupportedEra::Thales, Epoch(3));
+        era_checker.set_upcoming_era(SupportedEra::Pythagoras, Epoch(5));
+
+        assert_eq!(SupportedEra::Thales, era_checker.current_era());
+        assert_eq!(SupportedEra::Thales, era_checker.era_of_epoch(Epoch(4)));
+        assert_eq!(SupportedEra::Pythagoras, era_checker.era_of_epoch(Epoch(5)));
+        assert_eq!(SupportedEra::Pythagoras, era_checker.era_of_epoch(Epoch(6)));
+
+        era_checker.change_era(SupportedEra::Thales, Epoch(4));
+
+        assert_eq!(SupportedEra::Thales, era_checker.era_of_epoch(Epoch(5)));
+    }
 }
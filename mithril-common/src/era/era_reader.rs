@@ -43,6 +43,7 @@ pub struct EraEpochToken {
     current_epoch: Epoch,
     current_era: EraMarker,
     next_era: Option<EraMarker>,
+    previous_era: Option<EraMarker>,
 }
 
 impl EraEpochToken {
@@ -52,9 +53,16 @@ impl EraEpochToken {
             current_epoch,
             current_era,
             next_era,
+            previous_era: None,
         }
     }
 
+    /// Set the [EraMarker] of the Era that preceded the current Era.
+    pub fn with_previous_era(mut self, previous_era: EraMarker) -> Self {
+        self.previous_era = Some(previous_era);
+        self
+    }
+
     /// Try to cast the current [EraMarker] to a [SupportedEra]. If it fails,
     /// that means the current Era is not supported by this version of the
     /// software.
@@ -91,6 +99,25 @@ impl EraEpochToken {
     pub fn get_next_era_marker(&self) -> Option<&EraMarker> {
         self.next_era.as_ref()
     }
+
+    /// Return the [EraMarker] of the Era that preceded the current Era if any.
+    pub fn get_previous_era_marker(&self) -> Option<&EraMarker> {
+        self.previous_era.as_ref()
+    }
+
+    /// Return the previous Era if the Token has been created at the first epoch of the current
+    /// Era, the transition epoch during which both Eras are accepted.
+    ///
+    /// A previous Era that is not supported by this version of the software is ignored.
+    pub fn get_transition_supported_era(&self) -> Option<SupportedEra> {
+        if self.current_era.epoch != Some(self.current_epoch) {
+            return None;
+        }
+
+        self.previous_era
+            .as_ref()
+            .and_then(|marker| SupportedEra::from_str(&marker.name).ok())
+    }
 }
 
 /// The EraReader is responsible of giving the current Era and the Era to come.
@@ -167,12 +194,21 @@ impl EraReader {
             })?;
 
         let next_era_marker = eras.last().filter(|&marker| marker != current_era_marker);
+        let previous_era_marker = eras
+            .iter()
+            .filter(|&marker| marker.epoch.is_some() && marker.epoch < current_era_marker.epoch)
+            .max_by_key(|&marker| marker.epoch);
 
-        Ok(EraEpochToken::new(
+        let token = EraEpochToken::new(
             current_epoch,
             current_era_marker.to_owned(),
             next_era_marker.cloned(),
-        ))
+        );
+
+        Ok(match previous_era_marker {
+            Some(marker) => token.with_previous_era(marker.to_owned()),
+            None => token,
+        })
     }
 }
 
@@ -215,6 +251,10 @@ mod tests {
                     epoch: Some(Epoch(10))
                 },
                 next_era: None,
+                previous_era: Some(EraMarker {
+                    name: "one".to_string(),
+                    epoch: Some(Epoch(1))
+                }),
             },
             token
         );
@@ -261,6 +301,7 @@ mod tests {
                     name: SupportedEra::dummy().to_string(),
                     epoch: Some(Epoch(10))
                 }),
+                previous_era: None,
             },
             token
         );
@@ -348,4 +389,47 @@ mod tests {
             token.get_current_era_marker()
         );
     }
+
+    #[tokio::test]
+    async fn previous_era_is_a_transition_era_only_at_the_first_epoch_of_the_current_era() {
+        let markers = vec![
+            EraMarker::new(&SupportedEra::Thales.to_string(), Some(Epoch(1))),
+            EraMarker::new(&SupportedEra::Pythagoras.to_string(), Some(Epoch(10))),
+        ];
+        let adapter = DummyAdapter::default();
+        adapter.set_markers(markers);
+        let reader = EraReader::new(Arc::new(adapter));
+
+        let token = reader.read_era_epoch_token(Epoch(10)).await.unwrap();
+        assert_eq!(
+            Some(&EraMarker::new(
+                &SupportedEra::Thales.to_string(),
+                Some(Epoch(1))
+            )),
+            token.get_previous_era_marker()
+        );
+        assert_eq!(
+            Some(SupportedEra::Thales),
+            token.get_transition_supported_era()
+        );
+
+        let token = reader.read_era_epoch_token(Epoch(11)).await.unwrap();
+        assert_eq!(None, token.get_transition_supported_era());
+
+        let token = reader.read_era_epoch_token(Epoch(9)).await.unwrap();
+        assert_eq!(None, token.get_previous_era_marker());
+        assert_eq!(None, token.get_transition_supported_era());
+    }
+
+    #[test]
+    fn unsupported_previous_era_is_not_a_transition_era() {
+        let token = EraEpochToken::new(
+            Epoch(10),
+            EraMarker::new(&SupportedEra::dummy().to_string(), Some(Epoch(10))),
+            None,
+        )
+        .with_previous_era(EraMarker::new("one", Some(Epoch(1))));
+
+        assert_eq!(None, token.get_transition_supported_era());
+    }
 }
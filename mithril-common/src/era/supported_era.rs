@@ -2,14 +2,31 @@ use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
 
 /// The era that the software is running or will run
+///
+/// The eras are declared, and thus ordered, chronologically.
 #[derive(
-    Display, EnumString, EnumIter, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize,
+    Display,
+    EnumString,
+    EnumIter,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
 )]
 #[serde(rename_all = "lowercase")]
 #[strum(serialize_all = "lowercase")]
 pub enum SupportedEra {
     /// Thales era
     Thales,
+
+    /// Pythagoras era
+    Pythagoras,
 }
 
 impl SupportedEra {
@@ -44,4 +61,9 @@ mod tests {
 
         assert_eq!(SupportedEra::dummy(), supported_era);
     }
+
+    #[test]
+    fn eras_are_ordered_chronologically() {
+        assert!(SupportedEra::Thales < SupportedEra::Pythagoras);
+    }
 }
@@ -57,6 +57,7 @@ pub mod crypto_helper;
 pub mod entities;
 #[macro_use]
 pub mod era;
+pub mod logging;
 pub mod messages;
 pub mod protocol;
 pub mod signable_builder;
@@ -86,3 +87,6 @@ pub const MITHRIL_API_VERSION_HEADER: &str = "mithril-api-version";
 
 /// Mithril Signer node version header name
 pub const MITHRIL_SIGNER_VERSION_HEADER: &str = "signer-node-version";
+
+/// Mithril Signer request origin signature header name
+pub const MITHRIL_SIGNATURE_ORIGIN_HEADER: &str = "mithril-signature-origin";
@@ -0,0 +1,313 @@
+//! Per component log levels
+//!
+//! The log level of each component of a node (certifier, prover, http, runtime, chain observer)
+//! can be set independently of the global log level, using a [ComponentLevelFilter] drain.
+//!
+//! The component of a log record is deduced from the path of the module that emitted it, so
+//! loggers do not need to be configured by each component.
+//! The levels are held by a [ComponentLogLevels] handle that can be shared and updated at runtime.
+
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+use slog::{Drain, Level, OwnedKVList, Record};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::{Arc, PoisonError, RwLock};
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
+
+use crate::StdResult;
+
+/// Components of a node that can have their own log level
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Display,
+    EnumString,
+    EnumIter,
+    Serialize,
+    Deserialize,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum LogComponent {
+    /// Creation of open messages, registration of single signatures and certificates creation
+    Certifier,
+
+    /// Computation of the Cardano transactions proofs
+    Prover,
+
+    /// HTTP server and clients
+    Http,
+
+    /// State machine of the node
+    Runtime,
+
+    /// Observation of the Cardano chain
+    ChainObserver,
+}
+
+impl LogComponent {
+    /// Segments of module paths that belong to the component
+    fn module_segments(&self) -> &'static [&'static str] {
+        match self {
+            Self::Certifier => &["certifier"],
+            Self::Prover => &["prover"],
+            Self::Http => &["http_server", "aggregator_client"],
+            Self::Runtime => &["runtime"],
+            Self::ChainObserver => &["chain_observer"],
+        }
+    }
+
+    /// Find the component of the given module path, using the first of its segments that belongs
+    /// to a component.
+    pub fn from_module_path(module_path: &str) -> Option<Self> {
+        module_path.split("::").find_map(|segment| {
+            Self::iter().find(|component| component.module_segments().contains(&segment))
+        })
+    }
+}
+
+#[derive(Debug)]
+struct LogLevelsState {
+    default_level: Level,
+    component_levels: BTreeMap<LogComponent, Level>,
+}
+
+/// Shared handle on the log levels of the components of a node
+///
+/// Clones share the same levels, so an update made through one of them is immediately applied
+/// by all the [ComponentLevelFilter] built with the others.
+#[derive(Debug, Clone)]
+pub struct ComponentLogLevels {
+    state: Arc<RwLock<LogLevelsState>>,
+}
+
+impl ComponentLogLevels {
+    /// `ComponentLogLevels` factory, all the components use the given default level until
+    /// a level is set for them.
+    pub fn new(default_level: Level) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(LogLevelsState {
+                default_level,
+                component_levels: BTreeMap::new(),
+            })),
+        }
+    }
+
+    /// Level used by the logs that do not belong to a component with its own level
+    pub fn default_level(&self) -> Level {
+        self.state
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .default_level
+    }
+
+    /// Levels set for a component, the other components use the default level
+    pub fn component_levels(&self) -> BTreeMap<LogComponent, Level> {
+        self.state
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .component_levels
+            .clone()
+    }
+
+    /// Effective level of a component
+    pub fn level(&self, component: LogComponent) -> Level {
+        let state = self.state.read().unwrap_or_else(PoisonError::into_inner);
+
+        state
+            .component_levels
+            .get(&component)
+            .copied()
+            .unwrap_or(state.default_level)
+    }
+
+    /// Set the level of a component, or reset it to the default level if `None`
+    pub fn set_component_level(&self, component: LogComponent, level: Option<Level>) {
+        let mut state = self.state.write().unwrap_or_else(PoisonError::into_inner);
+
+        match level {
+            Some(level) => state.component_levels.insert(component, level),
+            None => state.component_levels.remove(&component),
+        };
+    }
+
+    /// Set the level of each of the given components
+    pub fn set_component_levels(&self, levels: &BTreeMap<LogComponent, Level>) {
+        for (component, level) in levels {
+            self.set_component_level(*component, Some(*level));
+        }
+    }
+
+    /// Effective level of the logs emitted by the given module
+    pub fn level_for_module(&self, module_path: &str) -> Level {
+        match LogComponent::from_module_path(module_path) {
+            Some(component) => self.level(component),
+            None => self.default_level(),
+        }
+    }
+
+    /// Parse a comma separated list of `component=level`, ie: `certifier=debug,http=warn`
+    pub fn parse_component_levels(value: &str) -> StdResult<BTreeMap<LogComponent, Level>> {
+        let mut levels = BTreeMap::new();
+        for item in value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+        {
+            let (component, level) = item
+                .split_once('=')
+                .ok_or_else(|| anyhow!("'{item}' is not a 'component=level' pair"))?;
+            let component = LogComponent::from_str(component.trim())
+                .with_context(|| format!("unknown log component: '{}'", component.trim()))?;
+            levels.insert(component, parse_level(level.trim())?);
+        }
+
+        Ok(levels)
+    }
+}
+
+/// Parse a log level name, ie: `debug`, case insensitive
+pub fn parse_level(value: &str) -> StdResult<Level> {
+    Level::from_str(value).map_err(|_| anyhow!("unknown log level: '{value}'"))
+}
+
+/// Name of a log level, as accepted by [parse_level]
+pub fn level_name(level: Level) -> String {
+    level.as_str().to_lowercase()
+}
+
+/// [Drain] that filters the records using the level of the component that emitted them
+#[derive(Debug, Clone)]
+pub struct ComponentLevelFilter<D: Drain> {
+    drain: D,
+    levels: ComponentLogLevels,
+}
+
+impl<D: Drain> ComponentLevelFilter<D> {
+    /// `ComponentLevelFilter` factory
+    pub fn new(drain: D, levels: ComponentLogLevels) -> Self {
+        Self { drain, levels }
+    }
+}
+
+impl<D: Drain> Drain for ComponentLevelFilter<D> {
+    type Ok = Option<D::Ok>;
+    type Err = D::Err;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        if record
+            .level()
+            .is_at_least(self.levels.level_for_module(record.module()))
+        {
+            self.drain.log(record, values).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn component_is_found_from_the_first_matching_segment_of_the_module_path() {
+        assert_eq!(
+            Some(LogComponent::Certifier),
+            LogComponent::from_module_path("mithril_aggregator::services::certifier")
+        );
+        assert_eq!(
+            Some(LogComponent::Http),
+            LogComponent::from_module_path("mithril_aggregator::http_server::routes::prover")
+        );
+        assert_eq!(
+            Some(LogComponent::Http),
+            LogComponent::from_module_path("mithril_signer::aggregator_client")
+        );
+        assert_eq!(
+            Some(LogComponent::ChainObserver),
+            LogComponent::from_module_path("mithril_common::chain_observer::cli_observer")
+        );
+        assert_eq!(
+            None,
+            LogComponent::from_module_path("mithril_aggregator::services::signed_entity")
+        );
+    }
+
+    #[test]
+    fn component_level_falls_back_to_the_default_level() {
+        let levels = ComponentLogLevels::new(Level::Warning);
+        levels.set_component_level(LogComponent::Prover, Some(Level::Debug));
+
+        assert_eq!(Level::Debug, levels.level(LogComponent::Prover));
+        assert_eq!(Level::Warning, levels.level(LogComponent::Runtime));
+        assert_eq!(
+            Level::Debug,
+            levels.level_for_module("mithril_aggregator::services::prover")
+        );
+        assert_eq!(
+            Level::Warning,
+            levels.level_for_module("mithril_aggregator::dependency_injection")
+        );
+
+        levels.set_component_level(LogComponent::Prover, None);
+
+        assert_eq!(Level::Warning, levels.level(LogComponent::Prover));
+    }
+
+    #[test]
+    fn levels_updates_are_shared_between_clones() {
+        let levels = ComponentLogLevels::new(Level::Info);
+        let cloned_levels = levels.clone();
+
+        levels.set_component_level(LogComponent::Http, Some(Level::Error));
+
+        assert_eq!(
+            BTreeMap::from([(LogComponent::Http, Level::Error)]),
+            cloned_levels.component_levels()
+        );
+    }
+
+    #[test]
+    fn parse_component_levels_list() {
+        assert_eq!(
+            BTreeMap::from([
+                (LogComponent::Certifier, Level::Debug),
+                (LogComponent::ChainObserver, Level::Warning),
+            ]),
+            ComponentLogLevels::parse_component_levels("certifier=debug, chain_observer=WARN")
+                .unwrap()
+        );
+        assert_eq!(
+            BTreeMap::new(),
+            ComponentLogLevels::parse_component_levels("").unwrap()
+        );
+        ComponentLogLevels::parse_component_levels("unknown=debug")
+            .expect_err("an unknown component should be rejected");
+        ComponentLogLevels::parse_component_levels("http=verbose")
+            .expect_err("an unknown level should be rejected");
+        ComponentLogLevels::parse_component_levels("http")
+            .expect_err("a component without level should be rejected");
+    }
+
+    #[test]
+    fn level_names_can_be_parsed_back() {
+        for level in [
+            Level::Critical,
+            Level::Error,
+            Level::Warning,
+            Level::Info,
+            Level::Debug,
+            Level::Trace,
+        ] {
+            assert_eq!(level, parse_level(&level_name(level)).unwrap());
+        }
+    }
+}
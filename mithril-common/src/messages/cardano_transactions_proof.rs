@@ -131,7 +131,7 @@ impl CardanoTransactionsProofsMessage {
     ///
     /// The following checks will be executed:
     ///
-    /// 1 - Check that each Merkle proof is valid
+    /// 1 - Check that each Merkle proof is valid, the parts shared by several proofs being verified only once
     ///
     /// 2 - Check that all proofs share the same Merkle root
     ///
@@ -142,17 +142,20 @@ impl CardanoTransactionsProofsMessage {
         &self,
     ) -> Result<VerifiedCardanoTransactions, VerifyCardanoTransactionsProofsError> {
         let mut merkle_root = None;
-
-        for certified_transaction in &self.certified_transactions {
-            let certified_transaction: CardanoTransactionsSetProof = certified_transaction
-                .clone()
-                .try_into()
-                .map_err(VerifyCardanoTransactionsProofsError::MalformedData)?;
-            certified_transaction.verify().map_err(|e| {
-                VerifyCardanoTransactionsProofsError::InvalidSetProof {
-                    transactions_hashes: certified_transaction.transactions_hashes().to_vec(),
-                    source: e,
-                }
+        let certified_transactions = self
+            .certified_transactions
+            .iter()
+            .map(|certified_transaction| certified_transaction.clone().try_into())
+            .collect::<Result<Vec<CardanoTransactionsSetProof>, _>>()
+            .map_err(VerifyCardanoTransactionsProofsError::MalformedData)?;
+        let verifications = CardanoTransactionsSetProof::verify_batch(&certified_transactions);
+
+        for (certified_transaction, verification) in
+            certified_transactions.iter().zip(verifications)
+        {
+            verification.map_err(|e| VerifyCardanoTransactionsProofsError::InvalidSetProof {
+                transactions_hashes: certified_transaction.transactions_hashes().to_vec(),
+                source: e,
             })?;
 
             let tx_merkle_root = Some(certified_transaction.merkle_root());
@@ -149,6 +149,7 @@ impl TryFrom<CertificateMessage> for Certificate {
             initiated_at: certificate_message.metadata.initiated_at,
             sealed_at: certificate_message.metadata.sealed_at,
             signers: certificate_message.metadata.signers,
+            era: certificate_message.metadata.era,
         };
 
         let certificate = Certificate {
@@ -203,6 +204,7 @@ impl TryFrom<Certificate> for CertificateMessage {
             initiated_at: certificate.metadata.initiated_at,
             sealed_at: certificate.metadata.sealed_at,
             signers: certificate.metadata.signers,
+            era: certificate.metadata.era,
         };
 
         let (multi_signature, genesis_signature) = match certificate.signature {
@@ -288,6 +290,7 @@ mod tests {
                         stake: 20,
                     },
                 ],
+                era: None,
             },
             protocol_message: protocol_message.clone(),
             signed_message: "signed_message".to_string(),
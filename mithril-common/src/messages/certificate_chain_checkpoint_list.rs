@@ -0,0 +1,154 @@
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::crypto_helper::{ProtocolGenesisSignature, ProtocolGenesisVerificationKey};
+use crate::entities::{Epoch, HexEncodedGenesisSignature};
+use crate::StdResult;
+
+/// Message structure of the checkpoints of the certificate chain
+pub type CertificateChainCheckpointListMessage = Vec<CertificateChainCheckpointListItemMessage>;
+
+/// Message structure of a checkpoint of the certificate chain
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateChainCheckpointListItemMessage {
+    /// Epoch of the checkpoint
+    pub epoch: Epoch,
+
+    /// Hash of the checkpointed certificate, whose chain was verified
+    pub certificate_hash: String,
+
+    /// Signature of the checkpoint computed from its epoch and its certificate hash
+    pub signature: HexEncodedGenesisSignature,
+
+    /// Date and time when the checkpoint was created
+    pub created_at: DateTime<Utc>,
+}
+
+impl CertificateChainCheckpointListItemMessage {
+    /// Compute the message signed by the checkpoint of the given certificate at the given epoch
+    pub fn compute_signed_message(epoch: Epoch, certificate_hash: &str) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(b"certificate_chain_checkpoint");
+        hasher.update(epoch.to_be_bytes());
+        hasher.update(certificate_hash.as_bytes());
+
+        hasher.finalize().to_vec()
+    }
+
+    /// Verify the signature of the checkpoint with the given verification key
+    pub fn verify_signature(
+        &self,
+        verification_key: &ProtocolGenesisVerificationKey,
+    ) -> StdResult<()> {
+        let signature = ProtocolGenesisSignature::from_bytes_hex(&self.signature)
+            .with_context(|| "Can not decode the signature of the certificate chain checkpoint")?;
+
+        verification_key
+            .verify(
+                &Self::compute_signed_message(self.epoch, &self.certificate_hash),
+                &signature,
+            )
+            .with_context(|| {
+                format!(
+                    "Invalid signature of the certificate chain checkpoint of epoch '{}'",
+                    self.epoch
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::rand_core::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use crate::crypto_helper::ProtocolGenesisSigner;
+
+    use super::*;
+
+    fn signed_checkpoint(
+        signer: &ProtocolGenesisSigner,
+        epoch: Epoch,
+        certificate_hash: &str,
+    ) -> CertificateChainCheckpointListItemMessage {
+        let signature = signer.sign(
+            &CertificateChainCheckpointListItemMessage::compute_signed_message(
+                epoch,
+                certificate_hash,
+            ),
+        );
+
+        CertificateChainCheckpointListItemMessage {
+            epoch,
+            certificate_hash: certificate_hash.to_string(),
+            signature: signature.to_bytes_hex(),
+            created_at: DateTime::parse_from_rfc3339("2024-01-19T13:43:05.618857482Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    // Test the retro compatibility with possible future upgrades.
+    #[test]
+    fn test_v1() {
+        let json = r#"[{
+"epoch": 420,
+"certificate_hash": "7905e83ab5d7bc082c1bbc3033bfd19c539078830d19080d1f241c70aa532572",
+"signature": "7369676e6174757265",
+"created_at": "2024-01-19T13:43:05.618857482Z"
+}]"#;
+        let message: CertificateChainCheckpointListMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a CertificateChainCheckpointListMessage instance.",
+        );
+
+        assert_eq!(
+            vec![CertificateChainCheckpointListItemMessage {
+                epoch: Epoch(420),
+                certificate_hash:
+                    "7905e83ab5d7bc082c1bbc3033bfd19c539078830d19080d1f241c70aa532572".to_string(),
+                signature: "7369676e6174757265".to_string(),
+                created_at: DateTime::parse_from_rfc3339("2024-01-19T13:43:05.618857482Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+            }],
+            message
+        );
+    }
+
+    #[test]
+    fn verify_signature_of_a_checkpoint_signed_with_the_matching_key() {
+        let signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let checkpoint = signed_checkpoint(&signer, Epoch(10), "certificate-hash");
+
+        checkpoint
+            .verify_signature(&signer.create_genesis_verifier().to_verification_key())
+            .unwrap();
+    }
+
+    #[test]
+    fn verify_signature_fails_with_another_key() {
+        let signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let other_signer =
+            ProtocolGenesisSigner::create_test_genesis_signer(ChaCha20Rng::from_seed([1u8; 32]));
+        let checkpoint = signed_checkpoint(&signer, Epoch(10), "certificate-hash");
+
+        checkpoint
+            .verify_signature(&other_signer.create_genesis_verifier().to_verification_key())
+            .expect_err("Verification with another key should fail");
+    }
+
+    #[test]
+    fn verify_signature_fails_if_the_certificate_hash_is_altered() {
+        let signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let checkpoint = CertificateChainCheckpointListItemMessage {
+            certificate_hash: "another-certificate-hash".to_string(),
+            ..signed_checkpoint(&signer, Epoch(10), "certificate-hash")
+        };
+
+        checkpoint
+            .verify_signature(&signer.create_genesis_verifier().to_verification_key())
+            .expect_err("Verification of an altered checkpoint should fail");
+    }
+}
@@ -1,7 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::entities::{CardanoDbBeacon, Epoch, ProtocolParameters, SignedEntityType};
-use crate::messages::SignerMessagePart;
+use crate::messages::{DeprecatedFieldsAdapter, NestedMessagePart, SignerMessagePart};
 
 /// Structure to transport [crate::entities::CertificatePending] data.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -30,6 +31,15 @@ pub struct CertificatePendingMessage {
 
     /// Signers that will be able to sign on the next epoch
     pub next_signers: Vec<SignerMessagePart>,
+
+    /// Signed entity types batched with the signed entity type in the signed protocol message
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub batched_signed_entity_types: Vec<SignedEntityType>,
+
+    /// Date and time at which the certificate was initiated, set if the metadata of the
+    /// certificate are signed with the protocol message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initiated_at: Option<DateTime<Utc>>,
 }
 
 impl CertificatePendingMessage {
@@ -55,14 +65,28 @@ impl CertificatePendingMessage {
                 },
                 signers: [SignerMessagePart::dummy()].to_vec(),
                 next_signers: [SignerMessagePart::dummy()].to_vec(),
+                batched_signed_entity_types: vec![],
+            initiated_at: None,
             }
         }
     }
 }
 
+impl DeprecatedFieldsAdapter for CertificatePendingMessage {
+    fn nested_parts() -> Vec<NestedMessagePart> {
+        vec![
+            NestedMessagePart::new::<SignerMessagePart>("signers"),
+            NestedMessagePart::new::<SignerMessagePart>("next_signers"),
+        ]
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::crypto_helper::ProtocolOpCert;
     use crate::entities::Epoch;
+    use crate::messages::SIGNER_PARTY_ID_REMOVAL_API_VERSION;
+    use crate::test_utils::fake_keys;
 
     use super::*;
 
@@ -106,6 +130,8 @@ mod tests {
                     kes_period: None
                 }
             ],
+            batched_signed_entity_types: vec![],
+            initiated_at: None,
         }
     }
 
@@ -152,4 +178,77 @@ mod tests {
 
         assert_eq!(golden_message(), message);
     }
+
+    #[test]
+    fn test_v2_with_batched_signed_entity_types() {
+        let mut json: serde_json::Value = serde_json::to_value(golden_message()).unwrap();
+        json["batched_signed_entity_types"] =
+            serde_json::json!([{ "MithrilStakeDistribution": 86 }]);
+        let message: CertificatePendingMessage = serde_json::from_value(json).unwrap();
+
+        assert_eq!(
+            CertificatePendingMessage {
+                batched_signed_entity_types: vec![SignedEntityType::MithrilStakeDistribution(
+                    Epoch(86)
+                )],
+                ..golden_message()
+            },
+            message
+        );
+    }
+
+    #[test]
+    fn batched_signed_entity_types_are_not_serialized_when_empty() {
+        let json = serde_json::to_value(golden_message()).unwrap();
+
+        assert!(json.get("batched_signed_entity_types").is_none());
+    }
+
+    #[test]
+    fn test_v3_with_initiated_at() {
+        let mut json: serde_json::Value = serde_json::to_value(golden_message()).unwrap();
+        json["initiated_at"] = serde_json::json!("2024-02-12T13:11:47Z");
+        let message: CertificatePendingMessage = serde_json::from_value(json).unwrap();
+
+        assert_eq!(
+            CertificatePendingMessage {
+                initiated_at: Some(
+                    DateTime::parse_from_rfc3339("2024-02-12T13:11:47Z")
+                        .unwrap()
+                        .with_timezone(&Utc)
+                ),
+                ..golden_message()
+            },
+            message
+        );
+    }
+
+    #[test]
+    fn party_id_of_the_signers_is_restored_when_received_without_it() {
+        let operational_certificate = fake_keys::operational_certificate()[0].to_string();
+        let protocol_operational_certificate: ProtocolOpCert =
+            operational_certificate.clone().try_into().unwrap();
+        let signer = SignerMessagePart {
+            party_id: protocol_operational_certificate
+                .compute_protocol_party_id()
+                .unwrap(),
+            operational_certificate: Some(operational_certificate),
+            ..SignerMessagePart::dummy()
+        };
+        let message = CertificatePendingMessage {
+            signers: vec![signer.clone()],
+            next_signers: vec![signer],
+            ..golden_message()
+        };
+
+        let json = message
+            .to_json_value_for_api_version(&SIGNER_PARTY_ID_REMOVAL_API_VERSION)
+            .unwrap();
+        assert!(json["signers"][0].get("party_id").is_none());
+        assert!(json["next_signers"][0].get("party_id").is_none());
+
+        let parsed =
+            CertificatePendingMessage::from_json_value_with_deprecated_fields(json).unwrap();
+        assert_eq!(message, parsed);
+    }
 }
@@ -0,0 +1,339 @@
+//! Tools to remove the deprecated fields of the messages without breaking the nodes that still
+//! rely on them.
+//!
+//! A message declares its [deprecated fields][DeprecatedField] with the API version from which
+//! they are removed, then:
+//! * it is sent to a peer with the form matching the API version of this peer: the deprecated
+//!   fields are only sent to the peers using an older API version,
+//! * it is received from a peer in both forms: the deprecated fields missing from the new form are
+//!   restored from the other fields of the message.
+//!
+//! The message parts nested in a message are adapted along with it.
+
+use anyhow::Context;
+use semver::Version;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use crate::StdResult;
+
+/// A field of a message that is removed from a given API version
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeprecatedField {
+    /// Name of the field in the serialized message
+    pub name: &'static str,
+
+    /// First API version from which the field is not sent anymore
+    pub removed_from_api_version: Version,
+}
+
+impl DeprecatedField {
+    /// DeprecatedField factory
+    pub fn new(name: &'static str, removed_from_api_version: Version) -> Self {
+        Self {
+            name,
+            removed_from_api_version,
+        }
+    }
+
+    /// Check if the field is removed from the messages exchanged with a peer using the given API
+    /// version
+    pub fn is_removed_for(&self, api_version: &Version) -> bool {
+        api_version >= &self.removed_from_api_version
+    }
+}
+
+/// A message part, with deprecated fields, nested in a field of a message.
+///
+/// The field can hold a single part, an optional part or a list of parts.
+#[derive(Clone, Copy)]
+pub struct NestedMessagePart {
+    field: &'static str,
+    adapt_for_api_version: fn(&mut Value, &Version),
+    restore_deprecated_fields: fn(&mut Value) -> StdResult<()>,
+}
+
+impl NestedMessagePart {
+    /// NestedMessagePart factory, for the parts of type `T` held by the given field
+    pub fn new<T: DeprecatedFieldsAdapter>(field: &'static str) -> Self {
+        Self {
+            field,
+            adapt_for_api_version: T::adapt_json_value_for_api_version,
+            restore_deprecated_fields: T::restore_json_value,
+        }
+    }
+
+    fn parts_mut<'a>(&self, fields: &'a mut Map<String, Value>) -> Vec<&'a mut Value> {
+        match fields.get_mut(self.field) {
+            Some(Value::Array(parts)) => parts.iter_mut().collect(),
+            Some(part @ Value::Object(_)) => vec![part],
+            _ => vec![],
+        }
+    }
+}
+
+/// Adapt a message with deprecated fields to the API version of the peer it is exchanged with.
+///
+/// The adaptation is done on the serialized form of the message, so the deprecated fields stay
+/// required when the message is deserialized: a message received without them is only accepted
+/// if they can be restored from its other fields.
+pub trait DeprecatedFieldsAdapter: Serialize + DeserializeOwned {
+    /// The deprecated fields of the message
+    fn deprecated_fields() -> Vec<DeprecatedField> {
+        vec![]
+    }
+
+    /// The message parts, with deprecated fields, nested in the message
+    fn nested_parts() -> Vec<NestedMessagePart> {
+        vec![]
+    }
+
+    /// Restore the deprecated fields of a message received in its new form from its other
+    /// serialized fields
+    fn restore_deprecated_fields(_fields: &mut Map<String, Value>) -> StdResult<()> {
+        Ok(())
+    }
+
+    /// Remove from a serialized message, and from its nested parts, the deprecated fields that
+    /// are not sent to a peer using the given API version
+    fn adapt_json_value_for_api_version(value: &mut Value, api_version: &Version) {
+        if let Value::Object(fields) = value {
+            for field in Self::deprecated_fields() {
+                if field.is_removed_for(api_version) {
+                    fields.remove(field.name);
+                }
+            }
+            for nested_part in Self::nested_parts() {
+                for part in nested_part.parts_mut(fields) {
+                    (nested_part.adapt_for_api_version)(part, api_version);
+                }
+            }
+        }
+    }
+
+    /// Restore in a serialized message, and in its nested parts, the deprecated fields that are
+    /// missing
+    fn restore_json_value(value: &mut Value) -> StdResult<()> {
+        if let Value::Object(fields) = value {
+            for nested_part in Self::nested_parts() {
+                for part in nested_part.parts_mut(fields) {
+                    (nested_part.restore_deprecated_fields)(part).with_context(|| {
+                        format!(
+                            "Can not restore the deprecated fields of the parts of field '{}'",
+                            nested_part.field
+                        )
+                    })?;
+                }
+            }
+
+            let has_missing_deprecated_fields = Self::deprecated_fields()
+                .iter()
+                .any(|field| !fields.contains_key(field.name));
+            if has_missing_deprecated_fields {
+                Self::restore_deprecated_fields(fields).with_context(|| {
+                    format!(
+                        "Can not restore the deprecated fields of message '{}'",
+                        std::any::type_name::<Self>()
+                    )
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Serialize the message in the form expected by a peer using the given API version
+    fn to_json_value_for_api_version(&self, api_version: &Version) -> StdResult<Value> {
+        let mut value = serde_json::to_value(self).with_context(|| {
+            format!(
+                "Can not serialize message '{}'",
+                std::any::type_name::<Self>()
+            )
+        })?;
+        Self::adapt_json_value_for_api_version(&mut value, api_version);
+
+        Ok(value)
+    }
+
+    /// Deserialize a message received in any of its forms, restoring its deprecated fields if
+    /// they are missing
+    fn from_json_value_with_deprecated_fields(mut value: Value) -> StdResult<Self> {
+        Self::restore_json_value(&mut value)?;
+
+        serde_json::from_value(value).with_context(|| {
+            format!(
+                "Can not deserialize message '{}'",
+                std::any::type_name::<Self>()
+            )
+        })
+    }
+}
+
+cfg_test_tools! {
+    /// Generate the serialized forms of a message sent to peers using each of the given API
+    /// versions (test-only).
+    pub fn generate_api_version_fixtures<T: DeprecatedFieldsAdapter>(
+        message: &T,
+        api_versions: &[Version],
+    ) -> StdResult<Vec<(Version, Value)>> {
+        api_versions
+            .iter()
+            .map(|api_version| {
+                Ok((
+                    api_version.clone(),
+                    message.to_json_value_for_api_version(api_version)?,
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    struct TestMessagePart {
+        legacy_name: String,
+        name: String,
+    }
+
+    impl DeprecatedFieldsAdapter for TestMessagePart {
+        fn deprecated_fields() -> Vec<DeprecatedField> {
+            vec![DeprecatedField::new("legacy_name", Version::new(0, 2, 0))]
+        }
+
+        fn restore_deprecated_fields(fields: &mut Map<String, Value>) -> StdResult<()> {
+            let name = fields
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow::anyhow!("missing name"))?
+                .to_uppercase();
+            fields.insert("legacy_name".to_string(), Value::String(name));
+
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    struct TestMessage {
+        part: TestMessagePart,
+        parts: Vec<TestMessagePart>,
+    }
+
+    impl DeprecatedFieldsAdapter for TestMessage {
+        fn nested_parts() -> Vec<NestedMessagePart> {
+            vec![
+                NestedMessagePart::new::<TestMessagePart>("part"),
+                NestedMessagePart::new::<TestMessagePart>("parts"),
+            ]
+        }
+    }
+
+    fn message_part(name: &str) -> TestMessagePart {
+        TestMessagePart {
+            legacy_name: name.to_uppercase(),
+            name: name.to_string(),
+        }
+    }
+
+    fn message() -> TestMessage {
+        TestMessage {
+            part: message_part("name"),
+            parts: vec![message_part("first"), message_part("second")],
+        }
+    }
+
+    #[test]
+    fn deprecated_fields_are_only_sent_to_peers_using_an_older_api_version() {
+        let fixtures = generate_api_version_fixtures(
+            &message_part("name"),
+            &[Version::new(0, 1, 9), Version::new(0, 2, 0)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                (
+                    Version::new(0, 1, 9),
+                    serde_json::json!({"legacy_name": "NAME", "name": "name"})
+                ),
+                (Version::new(0, 2, 0), serde_json::json!({"name": "name"})),
+            ],
+            fixtures
+        );
+    }
+
+    #[test]
+    fn deprecated_fields_of_the_nested_parts_are_only_sent_to_peers_using_an_older_api_version() {
+        let fixtures = generate_api_version_fixtures(
+            &message(),
+            &[Version::new(0, 1, 9), Version::new(0, 2, 0)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                (
+                    Version::new(0, 1, 9),
+                    serde_json::json!({
+                        "part": {"legacy_name": "NAME", "name": "name"},
+                        "parts": [
+                            {"legacy_name": "FIRST", "name": "first"},
+                            {"legacy_name": "SECOND", "name": "second"}
+                        ]
+                    })
+                ),
+                (
+                    Version::new(0, 2, 0),
+                    serde_json::json!({
+                        "part": {"name": "name"},
+                        "parts": [{"name": "first"}, {"name": "second"}]
+                    })
+                ),
+            ],
+            fixtures
+        );
+    }
+
+    #[test]
+    fn messages_received_in_any_form_are_restored() {
+        for (api_version, value) in generate_api_version_fixtures(
+            &message(),
+            &[Version::new(0, 1, 9), Version::new(0, 2, 0)],
+        )
+        .unwrap()
+        {
+            let parsed = TestMessage::from_json_value_with_deprecated_fields(value).unwrap();
+
+            assert_eq!(message(), parsed, "api version {api_version}");
+        }
+    }
+
+    #[test]
+    fn deprecated_fields_sent_by_older_peers_are_kept_as_is() {
+        let parsed = TestMessagePart::from_json_value_with_deprecated_fields(
+            serde_json::json!({"legacy_name": "legacy", "name": "name"}),
+        )
+        .unwrap();
+
+        assert_eq!("legacy", parsed.legacy_name);
+    }
+
+    #[test]
+    fn deprecated_fields_are_required_when_parsed_without_the_adapter() {
+        serde_json::from_value::<TestMessagePart>(serde_json::json!({"name": "name"}))
+            .expect_err("A message without its deprecated fields should not be parsed as is");
+    }
+
+    #[test]
+    fn messages_whose_deprecated_fields_can_not_be_restored_are_rejected() {
+        TestMessage::from_json_value_with_deprecated_fields(serde_json::json!({
+            "part": {"name": "name"},
+            "parts": [{"legacy_name": "FIRST"}]
+        }))
+        .expect_err("A nested part whose deprecated fields can not be restored should fail");
+    }
+}
@@ -1,6 +1,13 @@
-use crate::entities::{Epoch, ProtocolParameters};
+use crate::entities::{
+    Epoch, ProtocolFeatureActivations, ProtocolParameters, StakeDistributionParty,
+};
+use semver::Version;
 use serde::{Deserialize, Serialize};
 
+/// Minimum API version of the clients to which the upcoming signers are sent in the
+/// [EpochSettingsMessage], they are not computed for older clients.
+pub const UPCOMING_SIGNERS_MIN_API_VERSION: Version = Version::new(0, 1, 73);
+
 /// EpochSettings represents the settings of an epoch
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub struct EpochSettingsMessage {
@@ -14,6 +21,19 @@ pub struct EpochSettingsMessage {
     /// Next Protocol parameters
     #[serde(rename = "next_protocol")]
     pub next_protocol_parameters: ProtocolParameters,
+
+    /// Activation epochs of the protocol features
+    #[serde(default, skip_serializing_if = "ProtocolFeatureActivations::is_empty")]
+    pub protocol_feature_activations: ProtocolFeatureActivations,
+
+    /// Nonce that the signers must sign to authenticate their single signatures
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registration_nonce: Option<String>,
+
+    /// Signers registered so far to sign in the epoch after the next one, with the stake they
+    /// had when they registered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upcoming_signers: Option<Vec<StakeDistributionParty>>,
 }
 
 impl EpochSettingsMessage {
@@ -31,12 +51,22 @@ impl EpochSettingsMessage {
                 m: 100,
                 phi_f: 0.65,
             },
+            protocol_feature_activations: ProtocolFeatureActivations::new(),
+            registration_nonce: None,
+            upcoming_signers: None,
         }
     }
+
+    /// Check if a client using the given API version supports the upcoming signers
+    pub fn is_upcoming_signers_supported_by(api_version: &Version) -> bool {
+        api_version >= &UPCOMING_SIGNERS_MIN_API_VERSION
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::entities::ProtocolFeature;
+
     use super::*;
 
     fn golden_message() -> EpochSettingsMessage {
@@ -52,6 +82,9 @@ mod tests {
                 m: 1000,
                 phi_f: 0.65,
             },
+            protocol_feature_activations: ProtocolFeatureActivations::new(),
+            registration_nonce: None,
+            upcoming_signers: None,
         }
     }
 
@@ -69,4 +102,71 @@ mod tests {
 
         assert_eq!(golden_message(), message);
     }
+
+    #[test]
+    fn test_v2() {
+        let json = r#"{
+"epoch": 10,
+"protocol":  { "k": 5, "m": 100, "phi_f": 0.65 },
+"next_protocol":  { "k": 50, "m": 1000, "phi_f": 0.65 },
+"protocol_feature_activations": { "batched_signed_entity_types": 12 }
+}"#;
+        let message: EpochSettingsMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a EpochSettingsMessage instance.",
+        );
+
+        assert_eq!(
+            EpochSettingsMessage {
+                protocol_feature_activations: ProtocolFeatureActivations::new()
+                    .with_activation(ProtocolFeature::BatchedSignedEntityTypes, Epoch(12)),
+                ..golden_message()
+            },
+            message
+        );
+    }
+
+    #[test]
+    fn test_v3() {
+        let json = r#"{
+"epoch": 10,
+"protocol":  { "k": 5, "m": 100, "phi_f": 0.65 },
+"next_protocol":  { "k": 50, "m": 1000, "phi_f": 0.65 },
+"registration_nonce": "registration_nonce"
+}"#;
+        let message: EpochSettingsMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a EpochSettingsMessage instance.",
+        );
+
+        assert_eq!(
+            EpochSettingsMessage {
+                registration_nonce: Some("registration_nonce".to_string()),
+                ..golden_message()
+            },
+            message
+        );
+    }
+
+    #[test]
+    fn test_v4() {
+        let json = r#"{
+"epoch": 10,
+"protocol":  { "k": 5, "m": 100, "phi_f": 0.65 },
+"next_protocol":  { "k": 50, "m": 1000, "phi_f": 0.65 },
+"upcoming_signers": [{ "party_id": "party-1", "stake": 100 }]
+}"#;
+        let message: EpochSettingsMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a EpochSettingsMessage instance.",
+        );
+
+        assert_eq!(
+            EpochSettingsMessage {
+                upcoming_signers: Some(vec![StakeDistributionParty {
+                    party_id: "party-1".to_string(),
+                    stake: 100,
+                }]),
+                ..golden_message()
+            },
+            message
+        );
+    }
 }
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::logging::{level_name, ComponentLogLevels, LogComponent};
+
+/// Message structure of the log levels of a node
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogLevelsMessage {
+    /// Level of the logs that do not belong to a component with its own level
+    pub default_level: String,
+
+    /// Effective level of each component
+    pub components: BTreeMap<LogComponent, String>,
+}
+
+impl From<&ComponentLogLevels> for LogLevelsMessage {
+    fn from(log_levels: &ComponentLogLevels) -> Self {
+        Self {
+            default_level: level_name(log_levels.default_level()),
+            components: log_levels
+                .component_levels()
+                .into_iter()
+                .map(|(component, level)| (component, level_name(level)))
+                .collect(),
+        }
+    }
+}
+
+/// Message structure of an update of the log levels of the components of a node
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogLevelsUpdateMessage {
+    /// New level of each updated component, a `null` level resets the component to the default
+    /// level
+    pub components: BTreeMap<LogComponent, Option<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use slog::Level;
+
+    use super::*;
+
+    #[test]
+    fn only_components_with_their_own_level_are_listed() {
+        let log_levels = ComponentLogLevels::new(Level::Warning);
+        log_levels.set_component_level(LogComponent::Certifier, Some(Level::Debug));
+
+        assert_eq!(
+            LogLevelsMessage {
+                default_level: "warn".to_string(),
+                components: BTreeMap::from([(LogComponent::Certifier, "debug".to_string())]),
+            },
+            LogLevelsMessage::from(&log_levels)
+        );
+    }
+}
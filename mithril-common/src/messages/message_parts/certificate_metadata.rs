@@ -1,4 +1,5 @@
 use crate::entities::{ProtocolParameters, ProtocolVersion, StakeDistributionParty};
+use crate::era::SupportedEra;
 
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
@@ -34,6 +35,10 @@ pub struct CertificateMetadataMessagePart {
     /// The list of the active signers with their stakes and verification keys
     /// part of METADATA(p,n)
     pub signers: Vec<StakeDistributionParty>,
+
+    /// Era under which the certificate was produced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub era: Option<SupportedEra>,
 }
 
 impl CertificateMetadataMessagePart {
@@ -59,6 +64,7 @@ impl CertificateMetadataMessagePart {
                     stake: 20,
                 },
             ],
+            era: None,
         }
     }
 }
@@ -88,6 +94,7 @@ mod tests {
                     stake: 20,
                 },
             ],
+            era: None,
         }
     }
 
@@ -154,4 +161,42 @@ mod tests {
 
         assert_eq!(golden_message(), message);
     }
+
+    // Test the backward compatibility with possible future upgrades.
+    #[test]
+    fn test_v3() {
+        let json = r#"{
+            "network": "testnet",
+            "version": "0.1.0",
+            "parameters": {
+                "k": 1000,
+                "m": 100,
+                "phi_f": 0.123
+            },
+            "initiated_at": "2024-02-12T13:11:47Z",
+            "sealed_at": "2024-02-12T13:12:57Z",
+            "signers": [
+                {
+                    "party_id": "1",
+                    "stake": 10
+                },
+                {
+                    "party_id": "2",
+                    "stake": 20
+                }
+            ],
+            "era": "thales"
+        }"#;
+        let message: CertificateMetadataMessagePart = serde_json::from_str(json).expect(
+            "This JSON is expected to be successfully parsed into a CertificateMetadataMessagePart instance.",
+        );
+
+        assert_eq!(
+            CertificateMetadataMessagePart {
+                era: Some(SupportedEra::Thales),
+                ..golden_message()
+            },
+            message
+        );
+    }
 }
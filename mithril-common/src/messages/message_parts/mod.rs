@@ -1,7 +1,13 @@
 mod cardano_transactions_set_proof;
 mod certificate_metadata;
+mod next_era_registration;
 mod signer;
+mod signer_node_metadata;
 
 pub use cardano_transactions_set_proof::CardanoTransactionsSetProofMessagePart;
 pub use certificate_metadata::CertificateMetadataMessagePart;
-pub use signer::{SignerMessagePart, SignerWithStakeMessagePart};
+pub use next_era_registration::NextEraRegistrationMessagePart;
+pub use signer::{
+    SignerMessagePart, SignerWithStakeMessagePart, SIGNER_PARTY_ID_REMOVAL_API_VERSION,
+};
+pub use signer_node_metadata::SignerNodeMetadataMessagePart;
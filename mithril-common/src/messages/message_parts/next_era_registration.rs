@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{HexEncodedVerificationKey, HexEncodedVerificationKeySignature};
+
+/// Key registered by a signer ahead of an era switch, along with the key of the current era, to
+/// sign once the next era is active
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NextEraRegistrationMessagePart {
+    /// The era the key is registered for
+    pub era: String,
+
+    /// The public key used to authenticate signer signature in the next era
+    pub verification_key: HexEncodedVerificationKey,
+
+    /// The encoded signer 'Mithril verification key' signature (signed by the Cardano node KES
+    /// secret key).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_key_signature: Option<HexEncodedVerificationKeySignature>,
+}
@@ -8,15 +8,54 @@ use crate::{
     },
     StdResult,
 };
-use anyhow::Context;
+use anyhow::{anyhow, Context};
+use semver::Version;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::fmt::{Debug, Formatter};
 
+use crate::messages::{DeprecatedField, DeprecatedFieldsAdapter};
+
+/// First API version from which the `party_id` of the signer message parts is not sent anymore,
+/// as it can be computed from the operational certificate of the signer.
+pub const SIGNER_PARTY_ID_REMOVAL_API_VERSION: Version = Version::new(0, 2, 0);
+
+/// Compute the party id of a signer from its operational certificate
+fn compute_party_id_from_operational_certificate(
+    operational_certificate: Option<&HexEncodedOpCert>,
+) -> StdResult<PartyId> {
+    let operational_certificate: ProtocolOpCert = operational_certificate
+        .ok_or_else(|| {
+            anyhow!("The party id of a signer without operational certificate can not be restored")
+        })?
+        .to_owned()
+        .try_into()
+        .with_context(|| "Error while parsing operational certificate message")?;
+
+    operational_certificate
+        .compute_protocol_party_id()
+        .with_context(|| "Error while computing the party id from the operational certificate")
+}
+
+/// Restore the `party_id` of a serialized signer message part from its operational certificate
+fn restore_party_id(fields: &mut Map<String, Value>) -> StdResult<()> {
+    let operational_certificate = fields
+        .get("operational_certificate")
+        .and_then(Value::as_str)
+        .map(|operational_certificate| operational_certificate.to_string());
+    let party_id = compute_party_id_from_operational_certificate(operational_certificate.as_ref())?;
+    fields.insert("party_id".to_string(), Value::String(party_id));
+
+    Ok(())
+}
+
 /// Signer with Stake Message
 #[derive(Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct SignerWithStakeMessagePart {
     /// The unique identifier of the signer
-    // TODO: Should be removed once the signer certification is fully deployed
+    ///
+    /// Deprecated: not sent anymore to the peers using an API version from
+    /// [SIGNER_PARTY_ID_REMOVAL_API_VERSION], it is then restored from the operational certificate.
     pub party_id: PartyId,
 
     /// The public key used to authenticate signer signature
@@ -102,6 +141,32 @@ impl SignerWithStakeMessagePart {
     }
 }
 
+impl DeprecatedFieldsAdapter for SignerWithStakeMessagePart {
+    fn deprecated_fields() -> Vec<DeprecatedField> {
+        vec![DeprecatedField::new(
+            "party_id",
+            SIGNER_PARTY_ID_REMOVAL_API_VERSION,
+        )]
+    }
+
+    fn restore_deprecated_fields(fields: &mut Map<String, Value>) -> StdResult<()> {
+        restore_party_id(fields)
+    }
+}
+
+impl DeprecatedFieldsAdapter for SignerMessagePart {
+    fn deprecated_fields() -> Vec<DeprecatedField> {
+        vec![DeprecatedField::new(
+            "party_id",
+            SIGNER_PARTY_ID_REMOVAL_API_VERSION,
+        )]
+    }
+
+    fn restore_deprecated_fields(fields: &mut Map<String, Value>) -> StdResult<()> {
+        restore_party_id(fields)
+    }
+}
+
 impl From<SignerWithStake> for SignerWithStakeMessagePart {
     fn from(value: SignerWithStake) -> Self {
         Self {
@@ -150,7 +215,9 @@ impl Debug for SignerMessagePart {
 #[derive(Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct SignerMessagePart {
     /// The unique identifier of the signer
-    // TODO: Should be removed once the signer certification is fully deployed
+    ///
+    /// Deprecated: not sent anymore to the peers using an API version from
+    /// [SIGNER_PARTY_ID_REMOVAL_API_VERSION], it is then restored from the operational certificate.
     pub party_id: PartyId,
 
     /// The public key used to authenticate signer signature
@@ -222,3 +289,63 @@ impl Debug for SignerWithStakeMessagePart {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::messages::generate_api_version_fixtures;
+
+    use super::*;
+
+    fn signer_with_stake() -> SignerWithStakeMessagePart {
+        let operational_certificate = fake_keys::operational_certificate()[0].to_string();
+        SignerWithStakeMessagePart {
+            party_id: compute_party_id_from_operational_certificate(Some(&operational_certificate))
+                .unwrap(),
+            operational_certificate: Some(operational_certificate),
+            ..SignerWithStakeMessagePart::dummy()
+        }
+    }
+
+    #[test]
+    fn party_id_is_only_sent_to_peers_using_an_api_version_before_its_removal() {
+        let fixtures = generate_api_version_fixtures(
+            &signer_with_stake(),
+            &[Version::new(0, 1, 49), SIGNER_PARTY_ID_REMOVAL_API_VERSION],
+        )
+        .unwrap();
+
+        assert!(fixtures[0].1.get("party_id").is_some());
+        assert!(fixtures[1].1.get("party_id").is_none());
+    }
+
+    #[test]
+    fn party_id_is_restored_from_the_operational_certificate_for_every_api_version() {
+        let message = signer_with_stake();
+
+        for (api_version, value) in generate_api_version_fixtures(
+            &message,
+            &[Version::new(0, 1, 49), SIGNER_PARTY_ID_REMOVAL_API_VERSION],
+        )
+        .unwrap()
+        {
+            let parsed =
+                SignerWithStakeMessagePart::from_json_value_with_deprecated_fields(value).unwrap();
+
+            assert_eq!(message, parsed, "api version {api_version}");
+        }
+    }
+
+    #[test]
+    fn party_id_can_not_be_restored_without_operational_certificate() {
+        let message = SignerMessagePart {
+            operational_certificate: None,
+            ..SignerMessagePart::dummy()
+        };
+        let value = message
+            .to_json_value_for_api_version(&SIGNER_PARTY_ID_REMOVAL_API_VERSION)
+            .unwrap();
+
+        SignerMessagePart::from_json_value_with_deprecated_fields(value)
+            .expect_err("Restoring the party id without operational certificate should fail");
+    }
+}
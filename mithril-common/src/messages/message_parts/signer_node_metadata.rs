@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Metadata of a signer node sent along its registration
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SignerNodeMetadataMessagePart {
+    /// Version of the signer node software
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_version: Option<String>,
+
+    /// Eras supported by the signer node
+    #[serde(default)]
+    pub supported_eras: Vec<String>,
+}
+
+impl SignerNodeMetadataMessagePart {
+    cfg_test_tools! {
+        /// Return a dummy test entity (test-only).
+        pub fn dummy() -> Self {
+            Self {
+                node_version: Some("0.2.150".to_string()),
+                supported_eras: vec!["thales".to_string()],
+            }
+        }
+    }
+}
@@ -4,18 +4,26 @@ mod cardano_transaction_snapshot;
 mod cardano_transaction_snapshot_list;
 mod cardano_transactions_proof;
 mod certificate;
+mod certificate_chain_checkpoint_list;
 mod certificate_list;
 mod certificate_pending;
+mod deprecation;
 mod epoch_settings;
 mod interface;
+mod log_levels;
 mod message_parts;
 mod mithril_stake_distribution;
 mod mithril_stake_distribution_list;
+mod paginated;
 mod register_signature;
+mod register_signatures_batch;
 mod register_signer;
+mod request_origin_signatures;
+mod signer_diagnostic;
 mod snapshot;
 mod snapshot_download;
 mod snapshot_list;
+mod unregister_signer;
 
 pub use cardano_transaction_snapshot::CardanoTransactionSnapshotMessage;
 pub use cardano_transaction_snapshot_list::{
@@ -26,19 +34,36 @@ pub use cardano_transactions_proof::{
     VerifyCardanoTransactionsProofsError,
 };
 pub use certificate::CertificateMessage;
+pub use certificate_chain_checkpoint_list::{
+    CertificateChainCheckpointListItemMessage, CertificateChainCheckpointListMessage,
+};
 pub use certificate_list::{
     CertificateListItemMessage, CertificateListItemMessageMetadata, CertificateListMessage,
 };
 pub use certificate_pending::CertificatePendingMessage;
-pub use epoch_settings::EpochSettingsMessage;
+pub use deprecation::*;
+pub use epoch_settings::{EpochSettingsMessage, UPCOMING_SIGNERS_MIN_API_VERSION};
 pub use interface::*;
+pub use log_levels::{LogLevelsMessage, LogLevelsUpdateMessage};
 pub use message_parts::*;
 pub use mithril_stake_distribution::MithrilStakeDistributionMessage;
 pub use mithril_stake_distribution_list::{
     MithrilStakeDistributionListItemMessage, MithrilStakeDistributionListMessage,
 };
+pub use paginated::{PaginatedMessage, PAGINATED_MESSAGE_MIN_API_VERSION};
 pub use register_signature::RegisterSignatureMessage;
+pub use register_signatures_batch::{
+    RegisterSignatureResultMessage, RegisterSignatureStatus, RegisterSignaturesBatchMessage,
+    RegisterSignaturesBatchResultMessage, REGISTER_SIGNATURES_BATCH_MAX_SIZE,
+};
 pub use register_signer::RegisterSignerMessage;
+pub use request_origin_signatures::{
+    RequestOriginSignatureMessage, RequestOriginSignaturesMessage,
+};
+pub use signer_diagnostic::{
+    SignerDiagnosticErrorMessage, SignerDiagnosticMessage, SignerDiagnosticStateMessage,
+};
 pub use snapshot::SnapshotMessage;
 pub use snapshot_download::SnapshotDownloadMessage;
 pub use snapshot_list::{SnapshotListItemMessage, SnapshotListMessage};
+pub use unregister_signer::UnregisterSignerMessage;
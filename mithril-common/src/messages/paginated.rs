@@ -0,0 +1,112 @@
+use semver::Version;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Minimum API version of the clients to which the list messages are sent wrapped in a
+/// [PaginatedMessage], older clients receive the bare list of items.
+pub const PAGINATED_MESSAGE_MIN_API_VERSION: Version = Version::new(0, 1, 25);
+
+/// Message structure of a list of items, with its pagination metadata
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaginatedMessage<T> {
+    /// Items of the page
+    pub items: Vec<T>,
+
+    /// Total number of items of the list
+    pub total: u64,
+
+    /// Cursor to use to get the next page, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Shapes of a list message, depending on the API version of the server that sent it
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ListMessage<T> {
+    Paginated(PaginatedMessage<T>),
+    Bare(Vec<T>),
+}
+
+impl<T> PaginatedMessage<T> {
+    /// `PaginatedMessage` factory
+    pub fn new(items: Vec<T>, total: u64, next_cursor: Option<String>) -> Self {
+        Self {
+            items,
+            total,
+            next_cursor,
+        }
+    }
+
+    /// Create a `PaginatedMessage` holding all the items of a list in a single page
+    pub fn single_page(items: Vec<T>) -> Self {
+        let total = items.len() as u64;
+
+        Self::new(items, total, None)
+    }
+
+    /// Check if a client using the given API version supports the `PaginatedMessage` envelope
+    pub fn is_supported_by(api_version: &Version) -> bool {
+        api_version >= &PAGINATED_MESSAGE_MIN_API_VERSION
+    }
+}
+
+impl<T: DeserializeOwned> PaginatedMessage<T> {
+    /// Deserialize a list message, either wrapped in a `PaginatedMessage` or sent as a bare list
+    /// by servers that predate the pagination.
+    pub fn from_list_json(json: &str) -> serde_json::Result<Self> {
+        let message = match serde_json::from_str::<ListMessage<T>>(json)? {
+            ListMessage::Paginated(message) => message,
+            ListMessage::Bare(items) => Self::single_page(items),
+        };
+
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_page_holds_all_items_without_next_cursor() {
+        let message = PaginatedMessage::single_page(vec!["a", "b", "c"]);
+
+        assert_eq!(PaginatedMessage::new(vec!["a", "b", "c"], 3, None), message);
+    }
+
+    #[test]
+    fn is_supported_from_the_minimum_api_version() {
+        assert!(!PaginatedMessage::<String>::is_supported_by(&Version::new(
+            0, 1, 24
+        )));
+        assert!(PaginatedMessage::<String>::is_supported_by(
+            &PAGINATED_MESSAGE_MIN_API_VERSION
+        ));
+        assert!(PaginatedMessage::<String>::is_supported_by(&Version::new(
+            0, 2, 0
+        )));
+    }
+
+    #[test]
+    fn from_list_json_accepts_paginated_and_bare_lists() {
+        assert_eq!(
+            PaginatedMessage::new(vec![1, 2], 10, Some("cursor".to_string())),
+            PaginatedMessage::<u64>::from_list_json(
+                r#"{"items":[1,2],"total":10,"next_cursor":"cursor"}"#
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            PaginatedMessage::single_page(vec![1, 2]),
+            PaginatedMessage::<u64>::from_list_json("[1,2]").unwrap()
+        );
+    }
+
+    #[test]
+    fn next_cursor_is_not_serialized_if_none() {
+        assert_eq!(
+            r#"{"items":[1,2],"total":2}"#,
+            serde_json::to_string(&PaginatedMessage::single_page(vec![1, 2])).unwrap()
+        );
+    }
+}
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Formatter};
 
-use crate::entities::{HexEncodedSingleSignature, LotteryIndex, PartyId, SignedEntityType};
+use crate::entities::{
+    HexEncodedRegistrationNonceSignature, HexEncodedSingleSignature, LotteryIndex, PartyId,
+    SignedEntityType,
+};
+use crate::era::SupportedEra;
 #[cfg(any(test, feature = "test_tools"))]
 use crate::test_utils::fake_keys;
 
@@ -22,6 +26,14 @@ pub struct RegisterSignatureMessage {
     /// The indexes of the won lotteries that lead to the single signatures
     #[serde(rename = "indexes")]
     pub won_indexes: Vec<LotteryIndex>,
+
+    /// The era under which the signer produced the signature, if advertised
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub era: Option<SupportedEra>,
+
+    /// The KES signature of the registration nonce of the epoch, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce_signature: Option<HexEncodedRegistrationNonceSignature>,
 }
 
 impl RegisterSignatureMessage {
@@ -33,6 +45,8 @@ impl RegisterSignatureMessage {
                 party_id: "party_id".to_string(),
                 signature: fake_keys::single_signature()[0].to_string(),
                 won_indexes: vec![1, 3],
+                era: None,
+                nonce_signature: None,
             }
         }
     }
@@ -48,10 +62,14 @@ impl Debug for RegisterSignatureMessage {
                 &format_args!("{:?}", self.signed_entity_type),
             )
             .field("party_id", &self.party_id)
-            .field("won_indexes", &format_args!("{:?}", self.won_indexes));
+            .field("won_indexes", &format_args!("{:?}", self.won_indexes))
+            .field("era", &format_args!("{:?}", self.era));
 
         match is_pretty_printing {
-            true => debug.field("signature", &self.signature).finish(),
+            true => debug
+                .field("signature", &self.signature)
+                .field("nonce_signature", &self.nonce_signature)
+                .finish(),
             false => debug.finish_non_exhaustive(),
         }
     }
@@ -67,6 +85,8 @@ mod tests {
             party_id: "party_id".to_string(),
             signature: "7b227369676d61223a5b3133302c3137372c31352c3232392c32342c3235312c3234372c3137312c3139362c3231302c3134332c3131332c38362c3138392c39322c35362c3131322c33332c3139332c3231322c35342c3231342c32382c3231362c3232372c3137332c3130302c3132372c3137382c34302c39382c38372c32392c3138312c3235352c3131312c3135372c3232342c3233352c34362c3130302c3136392c3233322c3138392c3235322c38322c3133392c33365d2c22696e6465786573223a5b302c312c332c342c362c382c392c31302c31312c31322c31342c31382c32312c32322c32332c32352c32362c32372c33302c33332c33342c33382c34312c34332c35302c35382c35392c36302c36312c36322c36372c36392c37312c37332c37352c37362c37372c38312c38322c38332c38342c39302c39312c39322c39332c39372c39385d2c227369676e65725f696e646578223a327d".to_string(),
             won_indexes: vec![1, 3],
+            era: None,
+            nonce_signature: None,
         }
     }
 
@@ -84,4 +104,48 @@ mod tests {
 
         assert_eq!(golden_message(), message);
     }
+
+    #[test]
+    fn test_v2() {
+        let json = r#"{
+"party_id": "party_id",
+"signature":  "7b227369676d61223a5b3133302c3137372c31352c3232392c32342c3235312c3234372c3137312c3139362c3231302c3134332c3131332c38362c3138392c39322c35362c3131322c33332c3139332c3231322c35342c3231342c32382c3231362c3232372c3137332c3130302c3132372c3137382c34302c39382c38372c32392c3138312c3235352c3131312c3135372c3232342c3233352c34362c3130302c3136392c3233322c3138392c3235322c38322c3133392c33365d2c22696e6465786573223a5b302c312c332c342c362c382c392c31302c31312c31322c31342c31382c32312c32322c32332c32352c32362c32372c33302c33332c33342c33382c34312c34332c35302c35382c35392c36302c36312c36322c36372c36392c37312c37332c37352c37362c37372c38312c38322c38332c38342c39302c39312c39322c39332c39372c39385d2c227369676e65725f696e646578223a327d",
+"indexes": [1, 3],
+"era": "pythagoras"
+}"#;
+        let message: RegisterSignatureMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a RegisterSignatureMessage instance.",
+        );
+
+        assert_eq!(
+            RegisterSignatureMessage {
+                era: Some(SupportedEra::Pythagoras),
+                ..golden_message()
+            },
+            message
+        );
+    }
+
+    #[test]
+    fn test_v3() {
+        let json = r#"{
+"party_id": "party_id",
+"signature":  "7b227369676d61223a5b3133302c3137372c31352c3232392c32342c3235312c3234372c3137312c3139362c3231302c3134332c3131332c38362c3138392c39322c35362c3131322c33332c3139332c3231322c35342c3231342c32382c3231362c3232372c3137332c3130302c3132372c3137382c34302c39382c38372c32392c3138312c3235352c3131312c3135372c3232342c3233352c34362c3130302c3136392c3233322c3138392c3235322c38322c3133392c33365d2c22696e6465786573223a5b302c312c332c342c362c382c392c31302c31312c31322c31342c31382c32312c32322c32332c32352c32362c32372c33302c33332c33342c33382c34312c34332c35302c35382c35392c36302c36312c36322c36372c36392c37312c37332c37352c37362c37372c38312c38322c38332c38342c39302c39312c39322c39332c39372c39385d2c227369676e65725f696e646578223a327d",
+"indexes": [1, 3],
+"era": "pythagoras",
+"nonce_signature": "nonce_signature"
+}"#;
+        let message: RegisterSignatureMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a RegisterSignatureMessage instance.",
+        );
+
+        assert_eq!(
+            RegisterSignatureMessage {
+                era: Some(SupportedEra::Pythagoras),
+                nonce_signature: Some("nonce_signature".to_string()),
+                ..golden_message()
+            },
+            message
+        );
+    }
 }
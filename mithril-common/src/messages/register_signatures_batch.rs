@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{PartyId, SignedEntityType};
+use crate::messages::RegisterSignatureMessage;
+
+/// Maximum number of single signatures in a [RegisterSignaturesBatchMessage].
+pub const REGISTER_SIGNATURES_BATCH_MAX_SIZE: usize = 100;
+
+/// Message structure to register several single signatures in one call.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RegisterSignaturesBatchMessage {
+    /// The single signatures to register, each with its own signed entity type
+    pub signatures: Vec<RegisterSignatureMessage>,
+}
+
+impl RegisterSignaturesBatchMessage {
+    cfg_test_tools! {
+        /// Return a dummy test entity (test-only).
+        pub fn dummy() -> Self {
+            Self {
+                signatures: vec![RegisterSignatureMessage::dummy()],
+            }
+        }
+    }
+}
+
+/// Outcome of the registration of a single signature of a batch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegisterSignatureStatus {
+    /// The single signature is registered.
+    Registered,
+
+    /// The single signature payload could not be decoded.
+    Invalid,
+
+    /// There is no open message for the signed entity type of the single signature.
+    NotFound,
+
+    /// The open message of the signed entity type of the single signature is already certified.
+    AlreadyCertified,
+
+    /// The single signature could not be registered because of an aggregator error.
+    Error,
+}
+
+/// Registration outcome of one of the single signatures of a batch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisterSignatureResultMessage {
+    /// Signed entity type of the single signature
+    #[serde(rename = "entity_type", skip_serializing_if = "Option::is_none")]
+    pub signed_entity_type: Option<SignedEntityType>,
+
+    /// The unique identifier of the signer
+    pub party_id: PartyId,
+
+    /// Outcome of the registration
+    pub status: RegisterSignatureStatus,
+
+    /// Details of the failure, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Message structure returned after the registration of a batch of single signatures, with the
+/// outcome of each single signature, in the order of the batch.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RegisterSignaturesBatchResultMessage {
+    /// Outcome of the registration of each single signature
+    pub results: Vec<RegisterSignatureResultMessage>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::Epoch;
+
+    use super::*;
+
+    fn golden_message() -> RegisterSignaturesBatchResultMessage {
+        RegisterSignaturesBatchResultMessage {
+            results: vec![
+                RegisterSignatureResultMessage {
+                    signed_entity_type: Some(SignedEntityType::MithrilStakeDistribution(Epoch(7))),
+                    party_id: "party_id".to_string(),
+                    status: RegisterSignatureStatus::Registered,
+                    error: None,
+                },
+                RegisterSignatureResultMessage {
+                    signed_entity_type: None,
+                    party_id: "party_id".to_string(),
+                    status: RegisterSignatureStatus::Invalid,
+                    error: Some("invalid signature".to_string()),
+                },
+            ],
+        }
+    }
+
+    // Test the retro compatibility with possible future upgrades.
+    #[test]
+    fn test_v1() {
+        let json = r#"{
+"results": [
+    {
+        "entity_type": { "MithrilStakeDistribution": 7 },
+        "party_id": "party_id",
+        "status": "registered"
+    },
+    {
+        "party_id": "party_id",
+        "status": "invalid",
+        "error": "invalid signature"
+    }
+]
+}"#;
+        let message: RegisterSignaturesBatchResultMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a RegisterSignaturesBatchResultMessage instance.",
+        );
+
+        assert_eq!(golden_message(), message);
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{HexEncodedRequestOriginSignature, PartyId};
+use crate::StdResult;
+
+/// Message structure of the signatures of the origin of a request sending single signatures,
+/// sent hex encoded in the `mithril-signature-origin` header.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RequestOriginSignaturesMessage {
+    /// The signatures of the origin of the request, one for each signer of its single signatures
+    pub signatures: Vec<RequestOriginSignatureMessage>,
+}
+
+/// Message structure of the signature of the origin of a request by one of the signers of its
+/// single signatures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestOriginSignatureMessage {
+    /// The unique identifier of the signer
+    pub party_id: PartyId,
+
+    /// Date and time at which the signer signed the request
+    pub signed_at: DateTime<Utc>,
+
+    /// The signature of the single signatures of the signer held by the request and of the date
+    /// and time, made with the signing key of the verification key registered by the signer
+    pub signature: HexEncodedRequestOriginSignature,
+}
+
+impl RequestOriginSignaturesMessage {
+    /// Encode the message as the value of the `mithril-signature-origin` header.
+    pub fn to_header_value(&self) -> StdResult<String> {
+        let json = serde_json::to_vec(self)
+            .with_context(|| "Can not serialize the request origin signatures")?;
+
+        Ok(hex::encode(json))
+    }
+
+    /// Decode the message from the value of the `mithril-signature-origin` header.
+    pub fn from_header_value(header_value: &str) -> StdResult<Self> {
+        let json = hex::decode(header_value)
+            .with_context(|| "Can not decode the hex of the request origin signatures")?;
+
+        serde_json::from_slice(&json)
+            .with_context(|| "Can not deserialize the request origin signatures")
+    }
+
+    /// Get the signature of the origin of the request by the given signer, if any.
+    pub fn get_signature_of(&self, party_id: &PartyId) -> Option<&RequestOriginSignatureMessage> {
+        self.signatures
+            .iter()
+            .find(|signature| &signature.party_id == party_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn golden_message() -> RequestOriginSignaturesMessage {
+        RequestOriginSignaturesMessage {
+            signatures: vec![RequestOriginSignatureMessage {
+                party_id: "pool1m8crhnqj5k2kyszf5j2scshupystyxc887zdfrpzh6ty6eun4fx".to_string(),
+                signed_at: DateTime::parse_from_rfc3339("2024-08-02T12:35:10.123456789Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+                signature: "7369676e6174757265".to_string(),
+            }],
+        }
+    }
+
+    // Test the retro compatibility with possible future upgrades.
+    #[test]
+    fn test_v1() {
+        let json = r#"{
+"signatures": [{
+    "party_id": "pool1m8crhnqj5k2kyszf5j2scshupystyxc887zdfrpzh6ty6eun4fx",
+    "signed_at": "2024-08-02T12:35:10.123456789Z",
+    "signature": "7369676e6174757265"
+}]
+}"#;
+        let message: RequestOriginSignaturesMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a RequestOriginSignaturesMessage instance.",
+        );
+
+        assert_eq!(golden_message(), message);
+    }
+
+    #[test]
+    fn encode_and_decode_the_header_value() {
+        let header_value = golden_message().to_header_value().unwrap();
+
+        assert!(header_value.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(
+            golden_message(),
+            RequestOriginSignaturesMessage::from_header_value(&header_value).unwrap()
+        );
+        RequestOriginSignaturesMessage::from_header_value("invalid")
+            .expect_err("An invalid header value should not be decoded");
+    }
+
+    #[test]
+    fn get_the_signature_of_a_signer() {
+        let message = golden_message();
+
+        assert_eq!(
+            Some(&message.signatures[0]),
+            message.get_signature_of(
+                &"pool1m8crhnqj5k2kyszf5j2scshupystyxc887zdfrpzh6ty6eun4fx".to_string()
+            )
+        );
+        assert_eq!(
+            None,
+            message.get_signature_of(&"another-party-id".to_string())
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Message structure of an anonymized diagnostic snapshot submitted by a signer on persistent
+/// failures.
+///
+/// The signer is only identified by a random identifier, so that an operator can match a
+/// diagnostic with the signer of an SPO that shares this identifier.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SignerDiagnosticMessage {
+    /// Anonymized identifier of the signer (random identifier created once by the signer)
+    pub signer_id: String,
+
+    /// Version of the signer node
+    pub signer_node_version: String,
+
+    /// Version of the Mithril API used by the signer
+    pub api_version: String,
+
+    /// Current era of the signer
+    pub era: String,
+
+    /// Current state of the signer state machine
+    pub current_state: String,
+
+    /// Number of consecutive failed cycles of the signer state machine
+    pub consecutive_failures: u64,
+
+    /// Latest transitions of the signer state machine, oldest first
+    pub state_history: Vec<SignerDiagnosticStateMessage>,
+
+    /// Latest errors of the signer state machine, oldest first
+    pub last_errors: Vec<SignerDiagnosticErrorMessage>,
+}
+
+/// A transition of the signer state machine
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerDiagnosticStateMessage {
+    /// State entered by the state machine
+    pub state: String,
+
+    /// Date and time of the transition
+    pub entered_at: DateTime<Utc>,
+}
+
+/// An error of the signer state machine
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerDiagnosticErrorMessage {
+    /// Error message
+    pub message: String,
+
+    /// Whether the error stopped the signer
+    pub is_critical: bool,
+
+    /// Date and time of the error
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl SignerDiagnosticMessage {
+    cfg_test_tools! {
+        /// Return a dummy test entity (test-only).
+        pub fn dummy() -> Self {
+            let date = DateTime::parse_from_rfc3339("2024-06-01T10:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc);
+
+            Self {
+                signer_id: "7e8a2fd3c9f6e1d0b0b2a4c98f1e5a3d9c0b7e6a5f4d3c2b1a0f9e8d7c6b5a4f"
+                    .to_string(),
+                signer_node_version: "0.2.150".to_string(),
+                api_version: "0.1.31".to_string(),
+                era: "thales".to_string(),
+                current_state: "Unregistered - Epoch(72)".to_string(),
+                consecutive_failures: 5,
+                state_history: vec![SignerDiagnosticStateMessage {
+                    state: "Unregistered - Epoch(72)".to_string(),
+                    entered_at: date,
+                }],
+                last_errors: vec![SignerDiagnosticErrorMessage {
+                    message: "could not retrieve epoch settings at epoch Epoch(72)".to_string(),
+                    is_critical: false,
+                    occurred_at: date,
+                }],
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn golden_message() -> SignerDiagnosticMessage {
+        let date = DateTime::parse_from_rfc3339("2024-06-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        SignerDiagnosticMessage {
+            signer_id: "signer-id".to_string(),
+            signer_node_version: "0.2.150".to_string(),
+            api_version: "0.1.31".to_string(),
+            era: "thales".to_string(),
+            current_state: "Init".to_string(),
+            consecutive_failures: 3,
+            state_history: vec![SignerDiagnosticStateMessage {
+                state: "Init".to_string(),
+                entered_at: date,
+            }],
+            last_errors: vec![SignerDiagnosticErrorMessage {
+                message: "an error".to_string(),
+                is_critical: false,
+                occurred_at: date,
+            }],
+        }
+    }
+
+    // Test the retro compatibility with possible future upgrades.
+    #[test]
+    fn test_v1() {
+        let json = r#"{
+"signer_id": "signer-id",
+"signer_node_version": "0.2.150",
+"api_version": "0.1.31",
+"era": "thales",
+"current_state": "Init",
+"consecutive_failures": 3,
+"state_history": [{ "state": "Init", "entered_at": "2024-06-01T10:00:00Z" }],
+"last_errors": [{ "message": "an error", "is_critical": false, "occurred_at": "2024-06-01T10:00:00Z" }]
+}"#;
+        let message: SignerDiagnosticMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a SignerDiagnosticMessage instance.",
+        );
+
+        assert_eq!(golden_message(), message);
+    }
+}
@@ -31,6 +31,25 @@ pub struct SnapshotMessage {
     /// Cardano node version
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cardano_node_version: Option<String>,
+
+    /// Size of the ancillary files archive in Bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ancillary_size: Option<u64>,
+
+    /// Locations where the archive of the ancillary files (ledger state snapshots and volatile
+    /// database) can be retrieved, they are not certified by the snapshot digest
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ancillary_locations: Option<Vec<String>>,
+
+    /// Locations of the aggregator routes redirecting to a time-limited signed URL of the
+    /// snapshot archive, as an alternative to the direct `locations`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_locations: Option<Vec<String>>,
+
+    /// Hex encoded SHA-256 of the snapshot archive, the archives downloaded from the `locations`
+    /// are checked against it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_hash: Option<String>,
 }
 
 impl SnapshotMessage {
@@ -52,6 +71,10 @@ impl SnapshotMessage {
             locations: vec!["https://host/certificate.tar.gz".to_string()],
             compression_algorithm: Some(CompressionAlgorithm::Gzip),
             cardano_node_version: Some("0.0.1".to_string()),
+            ancillary_size: None,
+            ancillary_locations: None,
+            signed_locations: None,
+            archive_hash: None,
         }
     }
 }
@@ -77,6 +100,10 @@ mod tests {
             locations: vec!["https://host/certificate.tar.gz".to_string()],
             compression_algorithm: None,
             cardano_node_version: None,
+            ancillary_size: None,
+            ancillary_locations: None,
+            signed_locations: None,
+            archive_hash: None,
         }
     }
 
@@ -97,6 +124,36 @@ mod tests {
             locations: vec!["https://host/certificate.tar.gz".to_string()],
             compression_algorithm: Some(CompressionAlgorithm::Gzip),
             cardano_node_version: Some("0.0.1".to_string()),
+            ancillary_size: None,
+            ancillary_locations: None,
+            signed_locations: None,
+            archive_hash: None,
+        }
+    }
+
+    fn golden_message_v3() -> SnapshotMessage {
+        SnapshotMessage {
+            ancillary_size: Some(3456789),
+            ancillary_locations: Some(vec!["https://host/ancillary.tar.gz".to_string()]),
+            ..golden_message_v2()
+        }
+    }
+
+    fn golden_message_v4() -> SnapshotMessage {
+        SnapshotMessage {
+            signed_locations: Some(vec![
+                "https://aggregator/artifact/snapshot/0b9f5ad7/download/signed".to_string(),
+            ]),
+            ..golden_message_v3()
+        }
+    }
+
+    fn golden_message_v5() -> SnapshotMessage {
+        SnapshotMessage {
+            archive_hash: Some(
+                "5b2d5dd2e4b2b0a1e8d7e2c0c64cf1ce1aba7e2eb3ff2ae3c0ef9a2d3a5e7f10".to_string(),
+            ),
+            ..golden_message_v4()
         }
     }
 
@@ -148,4 +205,98 @@ mod tests {
 
         assert_eq!(golden_message_v2(), message);
     }
+
+    #[test]
+    fn test_v3() {
+        let json = r#"{
+"digest": "0b9f5ad7f33cc523775c82249294eb8a1541d54f08eb3107cafc5638403ec7c6",
+"beacon": {
+  "network": "preview",
+  "epoch": 86,
+  "immutable_file_number": 1728
+},
+"certificate_hash": "d5daf6c03ace4a9c074e951844075b9b373bafc4e039160e3e2af01823e9abfb",
+"size": 807803196,
+"created_at": "2023-01-19T13:43:05.618857482Z",
+"locations": [
+  "https://host/certificate.tar.gz"
+],
+"compression_algorithm": "gzip",
+"cardano_node_version": "0.0.1",
+"ancillary_size": 3456789,
+"ancillary_locations": [
+  "https://host/ancillary.tar.gz"
+]
+}"#;
+        let message: SnapshotMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a SnapshotMessage instance.",
+        );
+
+        assert_eq!(golden_message_v3(), message);
+    }
+
+    #[test]
+    fn test_v4() {
+        let json = r#"{
+"digest": "0b9f5ad7f33cc523775c82249294eb8a1541d54f08eb3107cafc5638403ec7c6",
+"beacon": {
+  "network": "preview",
+  "epoch": 86,
+  "immutable_file_number": 1728
+},
+"certificate_hash": "d5daf6c03ace4a9c074e951844075b9b373bafc4e039160e3e2af01823e9abfb",
+"size": 807803196,
+"created_at": "2023-01-19T13:43:05.618857482Z",
+"locations": [
+  "https://host/certificate.tar.gz"
+],
+"compression_algorithm": "gzip",
+"cardano_node_version": "0.0.1",
+"ancillary_size": 3456789,
+"ancillary_locations": [
+  "https://host/ancillary.tar.gz"
+],
+"signed_locations": [
+  "https://aggregator/artifact/snapshot/0b9f5ad7/download/signed"
+]
+}"#;
+        let message: SnapshotMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a SnapshotMessage instance.",
+        );
+
+        assert_eq!(golden_message_v4(), message);
+    }
+
+    #[test]
+    fn test_v5() {
+        let json = r#"{
+"digest": "0b9f5ad7f33cc523775c82249294eb8a1541d54f08eb3107cafc5638403ec7c6",
+"beacon": {
+  "network": "preview",
+  "epoch": 86,
+  "immutable_file_number": 1728
+},
+"certificate_hash": "d5daf6c03ace4a9c074e951844075b9b373bafc4e039160e3e2af01823e9abfb",
+"size": 807803196,
+"created_at": "2023-01-19T13:43:05.618857482Z",
+"locations": [
+  "https://host/certificate.tar.gz"
+],
+"compression_algorithm": "gzip",
+"cardano_node_version": "0.0.1",
+"ancillary_size": 3456789,
+"ancillary_locations": [
+  "https://host/ancillary.tar.gz"
+],
+"signed_locations": [
+  "https://aggregator/artifact/snapshot/0b9f5ad7/download/signed"
+],
+"archive_hash": "5b2d5dd2e4b2b0a1e8d7e2c0c64cf1ce1aba7e2eb3ff2ae3c0ef9a2d3a5e7f10"
+}"#;
+        let message: SnapshotMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a SnapshotMessage instance.",
+        );
+
+        assert_eq!(golden_message_v5(), message);
+    }
 }
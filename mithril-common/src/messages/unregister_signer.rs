@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{Epoch, HexEncodedRegistrationNonceSignature, PartyId};
+#[cfg(any(test, feature = "test_tools"))]
+use crate::test_utils::fake_keys;
+
+/// Message structure to unregister a signer from the upcoming epochs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnregisterSignerMessage {
+    /// Epoch from which the signer is unregistered
+    pub epoch: Epoch,
+
+    /// The unique identifier of the signer
+    pub party_id: PartyId,
+
+    /// The KES signature of the unregistration message computed from the epoch, the registration
+    /// nonce of the current epoch and the party id
+    pub signature: HexEncodedRegistrationNonceSignature,
+}
+
+impl UnregisterSignerMessage {
+    cfg_test_tools! {
+        /// Return a dummy test entity (test-only).
+        pub fn dummy() -> Self {
+            Self {
+                epoch: Epoch(1),
+                party_id: "pool1m8crhnqj5k2kyszf5j2scshupystyxc887zdfrpzh6ty6eun4fx".to_string(),
+                signature: fake_keys::signer_verification_key_signature()[0].to_string(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // reference structure to compare with what should be deserialized.
+    fn golden_message_v1() -> UnregisterSignerMessage {
+        UnregisterSignerMessage {
+            epoch: Epoch(123),
+            party_id: "pool1m8crhnqj5k2kyszf5j2scshupystyxc887zdfrpzh6ty6eun4fx".to_string(),
+            signature: "7369676e6174757265".to_string(),
+        }
+    }
+
+    // Test the retro compatibility with possible future upgrades.
+    #[test]
+    fn test_v1() {
+        let json = r#"{
+"epoch": 123,
+"party_id": "pool1m8crhnqj5k2kyszf5j2scshupystyxc887zdfrpzh6ty6eun4fx",
+"signature": "7369676e6174757265"
+}"#;
+        let message: UnregisterSignerMessage = serde_json::from_str(json).expect(
+            "This JSON is expected to be succesfully parsed into a UnregisterSignerMessage instance.",
+        );
+
+        assert_eq!(golden_message_v1(), message);
+    }
+}
@@ -5,9 +5,11 @@
 //! aggregate verification keys.
 
 mod multi_signer;
+mod signature_scheme;
 mod signer_builder;
 mod single_signer;
 
 pub use multi_signer::MultiSigner;
+pub use signature_scheme::{EraSignatureSchemes, ProtocolSignatureScheme, StmSignatureScheme};
 pub use signer_builder::{SignerBuilder, SignerBuilderError};
 pub use single_signer::SingleSigner;
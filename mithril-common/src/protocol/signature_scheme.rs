@@ -0,0 +1,282 @@
+use anyhow::anyhow;
+use rand_core::CryptoRngCore;
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+use crate::{
+    crypto_helper::{
+        KESPeriod, ProtocolAggregateVerificationKey, ProtocolInitializer, ProtocolMultiSignature,
+    },
+    entities::{
+        PartyId, ProtocolMessage, ProtocolParameters, SignerWithStake, SingleSignatures, Stake,
+    },
+    era::SupportedEra,
+    protocol::{MultiSigner, SignerBuilder},
+    StdResult,
+};
+
+/// Cryptographic scheme used to issue, aggregate and verify the signatures of the protocol.
+///
+/// Each [era][SupportedEra] is bound to a scheme: a new version of the signature scheme can be
+/// introduced with a new era while the previous one is still used by the eras that precede it.
+pub trait ProtocolSignatureScheme: Send + Sync {
+    /// Create the [ProtocolInitializer] holding the keys a party registers to sign with the
+    /// scheme, its verification key is signed with the KES secret key if one is given.
+    fn create_protocol_initializer(
+        &self,
+        stake: Stake,
+        protocol_parameters: &ProtocolParameters,
+        kes_secret_key_path: Option<PathBuf>,
+        kes_period: Option<KESPeriod>,
+        rng: &mut dyn CryptoRngCore,
+    ) -> StdResult<ProtocolInitializer>;
+
+    /// Build the [MultiSigner] of the given registered signers
+    fn build_multi_signer(
+        &self,
+        signers_with_stake: &[SignerWithStake],
+        protocol_parameters: &ProtocolParameters,
+    ) -> StdResult<MultiSigner>;
+
+    /// Compute the aggregate verification key of the given registered signers
+    fn compute_aggregate_verification_key(
+        &self,
+        signers_with_stake: &[SignerWithStake],
+        protocol_parameters: &ProtocolParameters,
+    ) -> StdResult<ProtocolAggregateVerificationKey>;
+
+    /// Issue the single signature of a party for the given message, with the
+    /// [ProtocolInitializer] created when it registered: the protocol parameters are the ones of
+    /// the initializer.
+    ///
+    /// If the party won no lottery `None` is returned.
+    fn sign(
+        &self,
+        message: &ProtocolMessage,
+        party_id: PartyId,
+        signers_with_stake: &[SignerWithStake],
+        protocol_initializer: ProtocolInitializer,
+    ) -> StdResult<Option<SingleSignatures>>;
+
+    /// Verify a multi-signature of the given message against an aggregate verification key
+    fn verify_multi_signature(
+        &self,
+        message: &[u8],
+        multi_signature: &ProtocolMultiSignature,
+        aggregate_verification_key: &ProtocolAggregateVerificationKey,
+        protocol_parameters: &ProtocolParameters,
+    ) -> StdResult<()>;
+}
+
+/// Signature scheme based on the current version of Mithril STM
+#[derive(Debug, Default)]
+pub struct StmSignatureScheme;
+
+impl ProtocolSignatureScheme for StmSignatureScheme {
+    fn create_protocol_initializer(
+        &self,
+        stake: Stake,
+        protocol_parameters: &ProtocolParameters,
+        kes_secret_key_path: Option<PathBuf>,
+        kes_period: Option<KESPeriod>,
+        mut rng: &mut dyn CryptoRngCore,
+    ) -> StdResult<ProtocolInitializer> {
+        ProtocolInitializer::setup(
+            protocol_parameters.to_owned().into(),
+            kes_secret_key_path,
+            kes_period,
+            stake,
+            &mut rng,
+        )
+    }
+
+    fn build_multi_signer(
+        &self,
+        signers_with_stake: &[SignerWithStake],
+        protocol_parameters: &ProtocolParameters,
+    ) -> StdResult<MultiSigner> {
+        Ok(SignerBuilder::new(signers_with_stake, protocol_parameters)?.build_multi_signer())
+    }
+
+    fn compute_aggregate_verification_key(
+        &self,
+        signers_with_stake: &[SignerWithStake],
+        protocol_parameters: &ProtocolParameters,
+    ) -> StdResult<ProtocolAggregateVerificationKey> {
+        Ok(SignerBuilder::new(signers_with_stake, protocol_parameters)?
+            .compute_aggregate_verification_key())
+    }
+
+    fn sign(
+        &self,
+        message: &ProtocolMessage,
+        party_id: PartyId,
+        signers_with_stake: &[SignerWithStake],
+        protocol_initializer: ProtocolInitializer,
+    ) -> StdResult<Option<SingleSignatures>> {
+        let protocol_parameters = protocol_initializer.get_protocol_parameters().into();
+
+        SignerBuilder::new(signers_with_stake, &protocol_parameters)?
+            .restore_signer_from_initializer(party_id, protocol_initializer)?
+            .sign(message)
+    }
+
+    fn verify_multi_signature(
+        &self,
+        message: &[u8],
+        multi_signature: &ProtocolMultiSignature,
+        aggregate_verification_key: &ProtocolAggregateVerificationKey,
+        protocol_parameters: &ProtocolParameters,
+    ) -> StdResult<()> {
+        multi_signature
+            .verify(
+                message,
+                aggregate_verification_key,
+                &protocol_parameters.to_owned().into(),
+            )
+            .map_err(|e| anyhow!(e))
+    }
+}
+
+/// Registry of the [signature schemes][ProtocolSignatureScheme] used by each era
+#[derive(Clone)]
+pub struct EraSignatureSchemes {
+    schemes: HashMap<SupportedEra, Arc<dyn ProtocolSignatureScheme>>,
+}
+
+impl EraSignatureSchemes {
+    /// Create a registry without any scheme
+    pub fn empty() -> Self {
+        Self {
+            schemes: HashMap::new(),
+        }
+    }
+
+    /// Bind the given scheme to an era, replacing the scheme previously bound to it
+    pub fn with_scheme(
+        mut self,
+        era: SupportedEra,
+        scheme: Arc<dyn ProtocolSignatureScheme>,
+    ) -> Self {
+        self.schemes.insert(era, scheme);
+        self
+    }
+
+    /// Get the scheme bound to the given era
+    pub fn get(&self, era: SupportedEra) -> StdResult<Arc<dyn ProtocolSignatureScheme>> {
+        self.schemes
+            .get(&era)
+            .cloned()
+            .ok_or_else(|| anyhow!("No signature scheme is registered for era '{era}'"))
+    }
+}
+
+impl Default for EraSignatureSchemes {
+    /// All the supported eras use the [StmSignatureScheme]
+    fn default() -> Self {
+        let stm_scheme: Arc<dyn ProtocolSignatureScheme> = Arc::new(StmSignatureScheme);
+
+        SupportedEra::eras()
+            .into_iter()
+            .fold(Self::empty(), |schemes, era| {
+                schemes.with_scheme(era, stm_scheme.clone())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_core::SeedableRng;
+
+    use crate::{entities::ProtocolMessage, test_utils::MithrilFixtureBuilder};
+
+    use super::*;
+
+    #[test]
+    fn default_registry_binds_a_scheme_to_every_supported_era() {
+        let schemes = EraSignatureSchemes::default();
+
+        for era in SupportedEra::eras() {
+            schemes
+                .get(era)
+                .expect("a signature scheme should be registered for every supported era");
+        }
+    }
+
+    #[test]
+    fn get_fails_for_an_era_without_scheme() {
+        let schemes = EraSignatureSchemes::empty();
+
+        schemes
+            .get(SupportedEra::dummy())
+            .expect_err("getting the scheme of an era without scheme should fail");
+    }
+
+    #[test]
+    fn stm_scheme_creates_protocol_initializers_with_the_given_parameters() {
+        let protocol_parameters = ProtocolParameters::new(5, 100, 0.65);
+        let mut rng = rand_chacha::ChaCha20Rng::from_seed([0u8; 32]);
+
+        let protocol_initializer = StmSignatureScheme
+            .create_protocol_initializer(150, &protocol_parameters, None, None, &mut rng)
+            .unwrap();
+
+        let initializer_protocol_parameters: ProtocolParameters =
+            protocol_initializer.get_protocol_parameters().into();
+        assert_eq!(150, protocol_initializer.get_stake());
+        assert_eq!(protocol_parameters, initializer_protocol_parameters);
+        assert!(protocol_initializer.verification_key_signature().is_none());
+    }
+
+    #[test]
+    fn stm_scheme_signatures_are_aggregated_by_its_multi_signer_and_verified() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(3).build();
+        let signers_with_stake = fixture.signers_with_stake();
+        let scheme = StmSignatureScheme;
+        let message = ProtocolMessage::default();
+
+        let single_signatures = fixture
+            .signers_fixture()
+            .into_iter()
+            .filter_map(|signer| {
+                scheme
+                    .sign(
+                        &message,
+                        signer.party_id(),
+                        &signers_with_stake,
+                        signer.protocol_initializer,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let multi_signer = scheme
+            .build_multi_signer(&signers_with_stake, &fixture.protocol_parameters())
+            .unwrap();
+
+        let multi_signature = multi_signer
+            .aggregate_single_signatures(&single_signatures, &message)
+            .expect("signatures issued with the scheme should be aggregated");
+        let aggregate_verification_key = scheme
+            .compute_aggregate_verification_key(&signers_with_stake, &fixture.protocol_parameters())
+            .unwrap();
+        assert_eq!(
+            multi_signer.compute_aggregate_verification_key(),
+            aggregate_verification_key
+        );
+        scheme
+            .verify_multi_signature(
+                message.compute_hash().as_bytes(),
+                &multi_signature,
+                &aggregate_verification_key,
+                &fixture.protocol_parameters(),
+            )
+            .expect("the multi-signature should be verified by the scheme");
+        scheme
+            .verify_multi_signature(
+                b"another message",
+                &multi_signature,
+                &aggregate_verification_key,
+                &fixture.protocol_parameters(),
+            )
+            .expect_err("the multi-signature of another message should not be verified");
+    }
+}
@@ -5,7 +5,7 @@ use std::{
 
 use crate::{
     digesters::ImmutableDigester,
-    entities::{CardanoDbBeacon, ProtocolMessage, ProtocolMessagePartKey},
+    entities::{CardanoDbBeacon, ProtocolMessage, ProtocolMessagePart},
     signable_builder::SignableBuilder,
     StdResult,
 };
@@ -54,7 +54,7 @@ impl SignableBuilder<CardanoDbBeacon> for CardanoImmutableFilesFullSignableBuild
             })?;
         info!(self.logger, "SignableBuilder: digest = '{digest}'.");
         let mut protocol_message = ProtocolMessage::new();
-        protocol_message.set_message_part(ProtocolMessagePartKey::SnapshotDigest, digest);
+        protocol_message.set_part(ProtocolMessagePart::SnapshotDigest(digest))?;
 
         Ok(protocol_message)
     }
@@ -98,11 +98,6 @@ mod tests {
             .await
             .unwrap();
 
-        assert_eq!(
-            &"immutable 0".to_string(),
-            protocol_message
-                .get_message_part(&ProtocolMessagePartKey::SnapshotDigest)
-                .unwrap()
-        );
+        assert_eq!(Some("immutable 0"), protocol_message.snapshot_digest());
     }
 }
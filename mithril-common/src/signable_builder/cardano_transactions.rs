@@ -6,7 +6,7 @@ use slog::{debug, Logger};
 
 use crate::{
     crypto_helper::{MKMap, MKMapNode, MKTreeNode},
-    entities::{BlockRange, CardanoDbBeacon, ProtocolMessage, ProtocolMessagePartKey},
+    entities::{BlockRange, CardanoDbBeacon, ProtocolMessage, ProtocolMessagePart},
     signable_builder::SignableBuilder,
     StdResult,
 };
@@ -93,14 +93,12 @@ impl SignableBuilder<CardanoDbBeacon> for CardanoTransactionsSignableBuilder {
             .compute_root()?;
 
         let mut protocol_message = ProtocolMessage::new();
-        protocol_message.set_message_part(
-            ProtocolMessagePartKey::CardanoTransactionsMerkleRoot,
+        protocol_message.set_part(ProtocolMessagePart::CardanoTransactionsMerkleRoot(
             mk_root.to_hex(),
-        );
-        protocol_message.set_message_part(
-            ProtocolMessagePartKey::LatestImmutableFileNumber,
-            beacon.immutable_file_number.to_string(),
-        );
+        ))?;
+        protocol_message.set_part(ProtocolMessagePart::LatestImmutableFileNumber(
+            beacon.immutable_file_number,
+        ))?;
 
         Ok(protocol_message)
     }
@@ -109,7 +107,10 @@ impl SignableBuilder<CardanoDbBeacon> for CardanoTransactionsSignableBuilder {
 #[cfg(test)]
 mod tests {
 
-    use crate::{entities::CardanoTransaction, test_utils::TestLogger};
+    use crate::{
+        entities::{CardanoTransaction, ProtocolMessagePartKey},
+        test_utils::TestLogger,
+    };
 
     use super::*;
 
@@ -0,0 +1,405 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use std::{
+    cmp::min,
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
+
+use crate::{
+    certificate_chain::{
+        CertificateGenesisProducer, CertificateRetriever, CertificateRetrieverError,
+    },
+    crypto_helper::{
+        ProtocolAggregateVerificationKey, ProtocolClerk, ProtocolGenesisSigner,
+        ProtocolGenesisVerifier,
+    },
+    entities::{
+        Certificate, CertificateMetadata, CertificateSignature, Epoch, ProtocolMessagePartKey,
+        ProtocolParameters, SignedEntityType,
+    },
+    test_utils::{fake_data, MithrilFixture, MithrilFixtureBuilder, SignerFixture},
+};
+
+/// A fully valid chain of certificates, rooted on a genesis certificate.
+///
+/// It can be used as a [CertificateRetriever] to verify the chain.
+pub struct CertificateChainFixture {
+    /// The certificates of the chain, from the latest to the genesis certificate
+    pub certificates_chained: Vec<Certificate>,
+
+    /// The verifier of the genesis certificate of the chain
+    pub genesis_verifier: ProtocolGenesisVerifier,
+}
+
+impl CertificateChainFixture {
+    /// Latest certificate of the chain
+    pub fn latest_certificate(&self) -> &Certificate {
+        self.certificates_chained
+            .first()
+            .expect("a certificate chain should not be empty")
+    }
+
+    /// Genesis certificate of the chain
+    pub fn genesis_certificate(&self) -> &Certificate {
+        self.certificates_chained
+            .last()
+            .expect("a certificate chain should not be empty")
+    }
+}
+
+#[cfg_attr(target_family = "wasm", async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait)]
+impl CertificateRetriever for CertificateChainFixture {
+    async fn get_certificate_details(
+        &self,
+        certificate_hash: &str,
+    ) -> Result<Certificate, CertificateRetrieverError> {
+        self.certificates_chained
+            .iter()
+            .find(|certificate| certificate.hash == certificate_hash)
+            .cloned()
+            .ok_or_else(|| {
+                CertificateRetrieverError(anyhow!(
+                    "certificate '{certificate_hash}' not found in the chain"
+                ))
+            })
+    }
+}
+
+/// A builder of deterministic certificate chains: a genesis certificate followed by certificates
+/// multi-signed by the signers of their epoch, that can be verified by a
+/// [CertificateVerifier][crate::certificate_chain::CertificateVerifier].
+pub struct CertificateChainBuilder {
+    total_certificates: u64,
+    certificates_per_epoch: u64,
+    protocol_parameters: ProtocolParameters,
+    total_signers_per_epoch: Box<dyn Fn(Epoch) -> usize>,
+    signers_churn_per_epoch: u8,
+}
+
+impl Default for CertificateChainBuilder {
+    fn default() -> Self {
+        Self {
+            total_certificates: 5,
+            certificates_per_epoch: 1,
+            protocol_parameters: fake_data::protocol_parameters(),
+            total_signers_per_epoch: Box::new(|epoch| min(2 + *epoch as usize, 5)),
+            signers_churn_per_epoch: 0,
+        }
+    }
+}
+
+impl CertificateChainBuilder {
+    /// CertificateChainBuilder factory
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the total number of certificates of the chain, including the genesis certificate.
+    pub fn with_total_certificates(mut self, total_certificates: u64) -> Self {
+        self.total_certificates = total_certificates;
+        self
+    }
+
+    /// Set the number of certificates per epoch.
+    pub fn with_certificates_per_epoch(mut self, certificates_per_epoch: u64) -> Self {
+        self.certificates_per_epoch = certificates_per_epoch;
+        self
+    }
+
+    /// Set the protocol parameters used to sign the certificates.
+    pub fn with_protocol_parameters(mut self, protocol_parameters: ProtocolParameters) -> Self {
+        self.protocol_parameters = protocol_parameters;
+        self
+    }
+
+    /// Set the number of signers of each epoch.
+    pub fn with_total_signers_per_epoch<F>(mut self, total_signers_per_epoch: F) -> Self
+    where
+        F: Fn(Epoch) -> usize + 'static,
+    {
+        self.total_signers_per_epoch = Box::new(total_signers_per_epoch);
+        self
+    }
+
+    /// Set the number of signers that leave the signers set at each epoch, replaced by new
+    /// signers.
+    pub fn with_signers_churn_per_epoch(mut self, signers_churn_per_epoch: u8) -> Self {
+        self.signers_churn_per_epoch = signers_churn_per_epoch;
+        self
+    }
+
+    /// Build the certificate chain.
+    pub fn build(self) -> CertificateChainFixture {
+        let genesis_signer = ProtocolGenesisSigner::create_deterministic_genesis_signer();
+        let genesis_verifier = genesis_signer.create_genesis_verifier();
+        let genesis_producer = CertificateGenesisProducer::new(Some(Arc::new(genesis_signer)));
+        let epochs = self.compute_epochs();
+        // The fixture of the epoch following the last certificate is needed to compute its next
+        // aggregate verification key
+        let fixture_per_epoch = epochs
+            .iter()
+            .copied()
+            .chain(epochs.last().map(|epoch| *epoch + 1))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|epoch| (epoch, self.build_fixture_for_epoch(epoch)))
+            .collect::<HashMap<_, _>>();
+
+        let certificates = epochs
+            .into_iter()
+            .enumerate()
+            .map(|(index, epoch)| {
+                let fixture = fixture_per_epoch.get(&epoch).unwrap();
+                let next_fixture = fixture_per_epoch.get(&(epoch + 1)).unwrap();
+                let next_avk = Self::compute_avk(&next_fixture.signers_fixture());
+
+                match index {
+                    0 => self.build_genesis_certificate(epoch, next_avk, &genesis_producer),
+                    _ => self.build_certificate(index, epoch, fixture, next_avk),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        CertificateChainFixture {
+            certificates_chained: Self::chain_certificates(certificates),
+            genesis_verifier,
+        }
+    }
+
+    fn compute_epochs(&self) -> Vec<Epoch> {
+        (1..=self.total_certificates)
+            .map(|i| match self.certificates_per_epoch {
+                0 => panic!("expected at least 1 certificate per epoch"),
+                1 => Epoch(i),
+                _ => Epoch(i / self.certificates_per_epoch + 1),
+            })
+            .collect()
+    }
+
+    fn build_fixture_for_epoch(&self, epoch: Epoch) -> MithrilFixture {
+        // Shifting the party id seed shifts the generated party ids by the same amount: the first
+        // signers of the previous epoch leave and new signers are appended.
+        let party_id_shift = (*epoch)
+            .checked_mul(u64::from(self.signers_churn_per_epoch))
+            .expect("signers churn per epoch is too high for the number of epochs");
+        let mut party_id_seed = [0u8; 32];
+        party_id_seed[..8].copy_from_slice(&party_id_shift.to_le_bytes());
+
+        MithrilFixtureBuilder::default()
+            .with_protocol_parameters(self.protocol_parameters.clone())
+            .with_signers((self.total_signers_per_epoch)(epoch))
+            .with_party_id_seed(party_id_seed)
+            .build()
+    }
+
+    fn compute_avk(signers: &[SignerFixture]) -> ProtocolAggregateVerificationKey {
+        ProtocolClerk::from_signer(&signers[0].protocol_signer)
+            .compute_avk()
+            .into()
+    }
+
+    fn build_base_certificate(&self, index: usize, epoch: Epoch) -> Certificate {
+        let base_certificate = fake_data::certificate(format!("certificate_hash-{index}"));
+
+        Certificate {
+            epoch,
+            previous_hash: "".to_string(),
+            #[allow(deprecated)]
+            metadata: CertificateMetadata {
+                immutable_file_number: index as u64 * 10,
+                protocol_parameters: self.protocol_parameters.clone(),
+                ..base_certificate.metadata
+            },
+            ..base_certificate
+        }
+    }
+
+    fn build_genesis_certificate(
+        &self,
+        epoch: Epoch,
+        next_avk: ProtocolAggregateVerificationKey,
+        genesis_producer: &CertificateGenesisProducer,
+    ) -> Certificate {
+        let base_certificate = self.build_base_certificate(0, epoch);
+        let beacon = base_certificate.as_cardano_db_beacon();
+        let genesis_protocol_message =
+            CertificateGenesisProducer::create_genesis_protocol_message(&next_avk).unwrap();
+        let genesis_signature = genesis_producer
+            .sign_genesis_protocol_message(genesis_protocol_message)
+            .unwrap();
+        let genesis_certificate = CertificateGenesisProducer::create_genesis_certificate(
+            self.protocol_parameters.clone(),
+            beacon.network,
+            beacon.epoch,
+            beacon.immutable_file_number,
+            next_avk,
+            genesis_signature,
+        )
+        .unwrap();
+
+        // The genesis certificate is sealed now, use the dates of the base certificate instead
+        // to keep the chain deterministic
+        Certificate {
+            #[allow(deprecated)]
+            metadata: CertificateMetadata {
+                initiated_at: base_certificate.metadata.initiated_at,
+                sealed_at: base_certificate.metadata.sealed_at,
+                ..genesis_certificate.metadata
+            },
+            ..genesis_certificate
+        }
+    }
+
+    fn build_certificate(
+        &self,
+        index: usize,
+        epoch: Epoch,
+        fixture: &MithrilFixture,
+        next_avk: ProtocolAggregateVerificationKey,
+    ) -> Certificate {
+        let mut certificate = self.build_base_certificate(index, epoch);
+        certificate.protocol_message.set_message_part(
+            ProtocolMessagePartKey::SnapshotDigest,
+            format!("digest{index}"),
+        );
+        certificate.protocol_message.set_message_part(
+            ProtocolMessagePartKey::NextAggregateVerificationKey,
+            next_avk.to_json_hex().unwrap(),
+        );
+        certificate.signed_message = certificate.protocol_message.compute_hash();
+        certificate.aggregate_verification_key = Self::compute_avk(&fixture.signers_fixture());
+        certificate.metadata.signers = fixture.stake_distribution_parties();
+
+        let signers = fixture.signers_fixture();
+        let single_signatures = signers
+            .iter()
+            .filter_map(|signer| {
+                signer
+                    .protocol_signer
+                    .sign(certificate.signed_message.as_bytes())
+            })
+            .collect::<Vec<_>>();
+        let multi_signature = ProtocolClerk::from_signer(&signers[0].protocol_signer)
+            .aggregate(&single_signatures, certificate.signed_message.as_bytes())
+            .unwrap();
+        certificate.signature = CertificateSignature::MultiSignature(
+            SignedEntityType::CardanoImmutableFilesFull(certificate.as_cardano_db_beacon()),
+            multi_signature.into(),
+        );
+
+        certificate
+    }
+
+    /// Link each certificate to its previous one and compute their hashes, return the
+    /// certificates from the latest to the genesis certificate.
+    fn chain_certificates(certificates: Vec<Certificate>) -> Vec<Certificate> {
+        let mut certificates_chained: Vec<Certificate> = Vec::with_capacity(certificates.len());
+        for mut certificate in certificates {
+            if let Some(previous_certificate) = certificates_chained.last() {
+                certificate.previous_hash = previous_certificate.hash.clone();
+            }
+            certificate.hash = certificate.compute_hash();
+            certificates_chained.push(certificate);
+        }
+        certificates_chained.reverse();
+
+        certificates_chained
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::certificate_chain::{CertificateVerifier, MithrilCertificateVerifier};
+    use crate::entities::PartyId;
+
+    use super::*;
+
+    async fn verify_chain(certificate_chain: CertificateChainFixture) {
+        let latest_certificate = certificate_chain.latest_certificate().clone();
+        let genesis_verification_key = certificate_chain.genesis_verifier.to_verification_key();
+        let verifier =
+            MithrilCertificateVerifier::new(slog_scope::logger(), Arc::new(certificate_chain));
+
+        verifier
+            .verify_certificate_chain(latest_certificate, &genesis_verification_key)
+            .await
+            .expect("the certificate chain should be valid");
+    }
+
+    fn party_ids(certificate: &Certificate) -> BTreeSet<PartyId> {
+        certificate
+            .metadata
+            .signers
+            .iter()
+            .map(|signer| signer.party_id.clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn build_a_valid_certificate_chain() {
+        let certificate_chain = CertificateChainBuilder::new()
+            .with_total_certificates(7)
+            .with_certificates_per_epoch(2)
+            .build();
+
+        assert_eq!(7, certificate_chain.certificates_chained.len());
+        assert!(certificate_chain.genesis_certificate().is_genesis());
+        assert_eq!(Epoch(4), certificate_chain.latest_certificate().epoch);
+        verify_chain(certificate_chain).await;
+    }
+
+    #[tokio::test]
+    async fn build_a_valid_certificate_chain_with_signers_churn() {
+        let certificate_chain = CertificateChainBuilder::new()
+            .with_total_certificates(4)
+            .with_total_signers_per_epoch(|_| 4)
+            .with_signers_churn_per_epoch(1)
+            .build();
+        let certificates = &certificate_chain.certificates_chained;
+
+        assert_eq!(4, party_ids(&certificates[0]).len());
+        assert_eq!(
+            3,
+            party_ids(&certificates[0])
+                .intersection(&party_ids(&certificates[1]))
+                .count()
+        );
+        verify_chain(certificate_chain).await;
+    }
+
+    #[test]
+    fn build_a_deterministic_certificate_chain() {
+        let build_chain = || {
+            CertificateChainBuilder::new()
+                .with_total_certificates(3)
+                .build()
+                .certificates_chained
+        };
+
+        assert_eq!(build_chain(), build_chain());
+    }
+
+    #[test]
+    fn signers_churn_beyond_epoch_255() {
+        let builder = CertificateChainBuilder::new()
+            .with_total_signers_per_epoch(|_| 3)
+            .with_signers_churn_per_epoch(1);
+        let party_ids_of_epoch = |epoch| {
+            builder
+                .build_fixture_for_epoch(epoch)
+                .signers_with_stake()
+                .into_iter()
+                .map(|s| s.party_id)
+                .collect::<BTreeSet<_>>()
+        };
+
+        assert_eq!(
+            2,
+            party_ids_of_epoch(Epoch(255))
+                .intersection(&party_ids_of_epoch(Epoch(256)))
+                .count()
+        );
+    }
+}
@@ -69,6 +69,9 @@ pub fn epoch_settings() -> entities::EpochSettings {
         epoch: beacon.epoch,
         protocol_parameters,
         next_protocol_parameters,
+        protocol_feature_activations: entities::ProtocolFeatureActivations::new(),
+        registration_nonce: None,
+        upcoming_signers: None,
     }
 }
 
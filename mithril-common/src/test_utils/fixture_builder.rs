@@ -151,12 +151,13 @@ impl MithrilFixtureBuilder {
         party_index: usize,
         kes_key_seed: &mut [u8],
     ) -> PartyId {
-        let mut cold_key_seed: Vec<u8> = (party_index)
+        // The party index is added to the party id seed as a little endian integer, so shifting
+        // the seed by n shifts the generated party ids by n
+        let party_id_seed = u64::from_le_bytes(self.party_id_seed[..8].try_into().unwrap());
+        let mut cold_key_seed = (party_index as u64)
+            .wrapping_add(party_id_seed)
             .to_le_bytes()
-            .iter()
-            .zip(self.party_id_seed)
-            .map(|(v1, v2)| v1 + v2)
-            .collect();
+            .to_vec();
         cold_key_seed.resize(32, 0);
         let keypair =
             ColdKeyGenerator::create_deterministic_keypair(cold_key_seed.try_into().unwrap());
@@ -279,4 +280,28 @@ mod tests {
             assert!(!first_party_ids.contains(&party_id));
         }
     }
+
+    #[test]
+    fn shifting_the_party_id_seed_shifts_the_builded_party_ids() {
+        let party_ids = MithrilFixtureBuilder::default()
+            .with_signers(4)
+            .with_party_id_seed([254u8; 32])
+            .build()
+            .signers_with_stake()
+            .into_iter()
+            .map(|s| s.party_id)
+            .collect::<Vec<_>>();
+        let mut shifted_party_id_seed = [254u8; 32];
+        shifted_party_id_seed[0] = 255;
+        let shifted_party_ids = MithrilFixtureBuilder::default()
+            .with_signers(3)
+            .with_party_id_seed(shifted_party_id_seed)
+            .build()
+            .signers_with_stake()
+            .into_iter()
+            .map(|s| s.party_id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(party_ids[1..].to_vec(), shifted_party_ids);
+    }
 }
@@ -4,6 +4,7 @@
 //! * A Open Api Spec tester
 //! * Some precomputed fake data and keys
 //! * A builder of [MithrilFixture] to generate signers alongside a stake distribution
+//! * A builder of valid certificate chains with [CertificateChainBuilder]
 //!
 
 #[cfg(feature = "apispec")]
@@ -13,6 +14,7 @@ pub mod apispec;
 pub mod fake_data;
 pub mod fake_keys;
 
+mod certificate_chain_builder;
 mod fixture_builder;
 mod mithril_fixture;
 
@@ -22,6 +24,7 @@ mod temp_dir;
 #[cfg_attr(docsrs, doc(cfg(feature = "test_http_server")))]
 pub mod test_http_server;
 
+pub use certificate_chain_builder::{CertificateChainBuilder, CertificateChainFixture};
 pub use fixture_builder::{MithrilFixtureBuilder, StakeDistributionGenerationMethod};
 pub use mithril_fixture::{MithrilFixture, SignerFixture};
 pub use temp_dir::*;
@@ -0,0 +1,700 @@
+//! Wire format compatibility of the messages exchanged between the Mithril nodes.
+//!
+//! * Property based tests check that every message survives a serialization round trip and is
+//!   still parsed when it contains fields that are unknown to this version, as the messages sent
+//!   by newer nodes would.
+//! * Golden files, in `tests/golden/messages`, hold messages as sent by previous releases: they
+//!   must still be parsed by this version.
+
+use chrono::{DateTime, Utc};
+use proptest::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+
+use mithril_common::entities::{
+    CardanoDbBeacon, CompressionAlgorithm, Epoch, ProtocolFeature, ProtocolFeatureActivations,
+    ProtocolMessage, ProtocolMessagePartKey, ProtocolParameters, SignedEntityType,
+    StakeDistributionParty,
+};
+use mithril_common::era::SupportedEra;
+use mithril_common::messages::*;
+
+fn epoch() -> impl Strategy<Value = Epoch> {
+    (0..u32::MAX as u64).prop_map(Epoch)
+}
+
+fn hex_string() -> impl Strategy<Value = String> {
+    "([0-9a-f]{2}){0,64}"
+}
+
+fn network() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just("mainnet".to_string()),
+        Just("preprod".to_string()),
+        Just("devnet".to_string()),
+    ]
+}
+
+fn date() -> impl Strategy<Value = DateTime<Utc>> {
+    // Up to the year 2100
+    (0..4_102_444_800i64, 0..1_000_000_000u32)
+        .prop_map(|(seconds, nanoseconds)| DateTime::from_timestamp(seconds, nanoseconds).unwrap())
+}
+
+fn beacon() -> impl Strategy<Value = CardanoDbBeacon> {
+    (network(), epoch(), any::<u64>()).prop_map(|(network, epoch, immutable_file_number)| {
+        CardanoDbBeacon::new(network, *epoch, immutable_file_number)
+    })
+}
+
+fn signed_entity_type() -> impl Strategy<Value = SignedEntityType> {
+    prop_oneof![
+        epoch().prop_map(SignedEntityType::MithrilStakeDistribution),
+        epoch().prop_map(SignedEntityType::CardanoStakeDistribution),
+        beacon().prop_map(SignedEntityType::CardanoImmutableFilesFull),
+        beacon().prop_map(SignedEntityType::CardanoTransactions),
+    ]
+}
+
+fn protocol_parameters() -> impl Strategy<Value = ProtocolParameters> {
+    (1..10_000u64, 1..10_000u64, 0.0..1.0f64)
+        .prop_map(|(k, m, phi_f)| ProtocolParameters::new(k, m, phi_f))
+}
+
+fn protocol_message() -> impl Strategy<Value = ProtocolMessage> {
+    (
+        proptest::option::of(hex_string()),
+        proptest::option::of(hex_string()),
+        hex_string(),
+    )
+        .prop_map(|(snapshot_digest, merkle_root, next_avk)| {
+            let mut protocol_message = ProtocolMessage::new();
+            if let Some(digest) = snapshot_digest {
+                protocol_message.set_message_part(ProtocolMessagePartKey::SnapshotDigest, digest);
+            }
+            if let Some(merkle_root) = merkle_root {
+                protocol_message.set_message_part(
+                    ProtocolMessagePartKey::CardanoTransactionsMerkleRoot,
+                    merkle_root,
+                );
+            }
+            protocol_message.set_message_part(
+                ProtocolMessagePartKey::NextAggregateVerificationKey,
+                next_avk,
+            );
+            protocol_message
+        })
+}
+
+fn party_id() -> impl Strategy<Value = String> {
+    "pool1[0-9a-z]{10,50}"
+}
+
+fn signer_message_part() -> impl Strategy<Value = SignerMessagePart> {
+    (
+        party_id(),
+        hex_string(),
+        proptest::option::of(hex_string()),
+        proptest::option::of(hex_string()),
+        proptest::option::of(any::<u32>()),
+    )
+        .prop_map(
+            |(
+                party_id,
+                verification_key,
+                verification_key_signature,
+                operational_certificate,
+                kes_period,
+            )| SignerMessagePart {
+                party_id,
+                verification_key,
+                verification_key_signature,
+                operational_certificate,
+                kes_period,
+            },
+        )
+}
+
+fn signer_with_stake_message_part() -> impl Strategy<Value = SignerWithStakeMessagePart> {
+    (signer_message_part(), any::<u64>()).prop_map(|(signer, stake)| SignerWithStakeMessagePart {
+        party_id: signer.party_id,
+        verification_key: signer.verification_key,
+        verification_key_signature: signer.verification_key_signature,
+        operational_certificate: signer.operational_certificate,
+        kes_period: signer.kes_period,
+        stake,
+    })
+}
+
+fn supported_era() -> impl Strategy<Value = SupportedEra> {
+    prop::sample::select(SupportedEra::eras())
+}
+
+fn certificate_metadata() -> impl Strategy<Value = CertificateMetadataMessagePart> {
+    (
+        network(),
+        protocol_parameters(),
+        date(),
+        date(),
+        prop::collection::vec((party_id(), any::<u64>()), 0..5),
+        proptest::option::of(supported_era()),
+    )
+        .prop_map(
+            |(network, protocol_parameters, initiated_at, sealed_at, signers, era)| {
+                CertificateMetadataMessagePart {
+                    network,
+                    protocol_version: "0.1.0".to_string(),
+                    protocol_parameters,
+                    initiated_at,
+                    sealed_at,
+                    signers: signers
+                        .into_iter()
+                        .map(|(party_id, stake)| StakeDistributionParty { party_id, stake })
+                        .collect(),
+                    era,
+                }
+            },
+        )
+}
+
+fn register_signature() -> impl Strategy<Value = RegisterSignatureMessage> {
+    (
+        proptest::option::of(signed_entity_type()),
+        party_id(),
+        hex_string(),
+        prop::collection::vec(any::<u64>(), 0..10),
+        proptest::option::of(supported_era()),
+        proptest::option::of(hex_string()),
+    )
+        .prop_map(
+            |(signed_entity_type, party_id, signature, won_indexes, era, nonce_signature)| {
+                RegisterSignatureMessage {
+                    signed_entity_type,
+                    party_id,
+                    signature,
+                    won_indexes,
+                    era,
+                    nonce_signature,
+                }
+            },
+        )
+}
+
+fn register_signature_status() -> impl Strategy<Value = RegisterSignatureStatus> {
+    prop_oneof![
+        Just(RegisterSignatureStatus::Registered),
+        Just(RegisterSignatureStatus::Invalid),
+        Just(RegisterSignatureStatus::NotFound),
+        Just(RegisterSignatureStatus::AlreadyCertified),
+        Just(RegisterSignatureStatus::Error),
+    ]
+}
+
+fn compression_algorithm() -> impl Strategy<Value = CompressionAlgorithm> {
+    prop_oneof![
+        Just(CompressionAlgorithm::Gzip),
+        Just(CompressionAlgorithm::Zstandard),
+    ]
+}
+
+fn locations() -> impl Strategy<Value = Vec<String>> {
+    prop::collection::vec("https://[a-z]{1,20}\\.org/[a-z0-9]{1,20}", 0..3)
+}
+
+/// Name of the fields added to the messages to simulate newer versions, prefixed so they can't
+/// collide with an existing field.
+fn unknown_field() -> impl Strategy<Value = (String, Value)> {
+    (
+        "[a-z_]{1,20}",
+        prop_oneof![
+            any::<u64>().prop_map(Value::from),
+            ".*".prop_map(Value::from),
+            Just(Value::Null),
+            Just(serde_json::json!({ "nested": [1, 2, 3] })),
+        ],
+    )
+        .prop_map(|(name, value)| (format!("unknown_{name}"), value))
+}
+
+/// Check that a message is unchanged after a serialization round trip, and that it is still
+/// parsed if fields unknown to this version are added to its JSON objects.
+fn check_message<T>(message: &T, unknown_field: &(String, Value)) -> Result<(), TestCaseError>
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let json = serde_json::to_value(message).unwrap();
+    let parsed_message: T = serde_json::from_value(json.clone())
+        .map_err(|e| TestCaseError::fail(format!("round trip parsing failed: {e}")))?;
+    prop_assert_eq!(message, &parsed_message);
+
+    let mut json_with_unknown_field = json;
+    add_field(&mut json_with_unknown_field, unknown_field);
+    let parsed_message: T = serde_json::from_value(json_with_unknown_field)
+        .map_err(|e| TestCaseError::fail(format!("parsing with an unknown field failed: {e}")))?;
+    prop_assert_eq!(message, &parsed_message);
+
+    Ok(())
+}
+
+/// Add a field to a JSON object, or to each of the objects of a JSON array
+fn add_field(json: &mut Value, (name, value): &(String, Value)) {
+    match json {
+        Value::Object(object) => {
+            object.insert(name.clone(), value.clone());
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| add_field(item, &(name.clone(), value.clone()))),
+        _ => {}
+    }
+}
+
+proptest! {
+    #[test]
+    fn cardano_transaction_snapshot_message(
+        (merkle_root, beacon, hash, certificate_hash, created_at) in
+            (hex_string(), beacon(), hex_string(), hex_string(), date()),
+        unknown_field in unknown_field(),
+    ) {
+        check_message(&CardanoTransactionSnapshotMessage {
+            merkle_root, beacon, hash, certificate_hash, created_at,
+        }, &unknown_field)?;
+    }
+
+    #[test]
+    fn cardano_transaction_snapshot_list_message(
+        items in prop::collection::vec(
+            (hex_string(), beacon(), hex_string(), hex_string(), date()), 0..5),
+        unknown_field in unknown_field(),
+    ) {
+        let message: CardanoTransactionSnapshotListMessage = items
+            .into_iter()
+            .map(|(merkle_root, beacon, hash, certificate_hash, created_at)| {
+                CardanoTransactionSnapshotListItemMessage {
+                    merkle_root, beacon, hash, certificate_hash, created_at,
+                }
+            })
+            .collect();
+        check_message(&message, &unknown_field)?;
+    }
+
+    #[test]
+    fn cardano_transactions_proofs_message(
+        certificate_hash in hex_string(),
+        certified_transactions in prop::collection::vec(
+            (prop::collection::vec(hex_string(), 1..5), hex_string()), 0..5),
+        non_certified_transactions in prop::collection::vec(hex_string(), 0..5),
+        latest_immutable_file_number in any::<u64>(),
+        unknown_field in unknown_field(),
+    ) {
+        check_message(&CardanoTransactionsProofsMessage::new(
+            &certificate_hash,
+            certified_transactions
+                .into_iter()
+                .map(|(transactions_hashes, proof)| CardanoTransactionsSetProofMessagePart {
+                    transactions_hashes,
+                    proof,
+                })
+                .collect(),
+            non_certified_transactions,
+            latest_immutable_file_number,
+        ), &unknown_field)?;
+    }
+
+    #[test]
+    fn certificate_message(
+        (hash, previous_hash, signed_entity_type, beacon) in
+            (hex_string(), hex_string(), signed_entity_type(), beacon()),
+        (metadata, protocol_message) in (certificate_metadata(), protocol_message()),
+        (signed_message, aggregate_verification_key, multi_signature, genesis_signature) in
+            (hex_string(), hex_string(), hex_string(), hex_string()),
+        unknown_field in unknown_field(),
+    ) {
+        #[allow(deprecated)]
+        let message = CertificateMessage {
+            hash,
+            previous_hash,
+            epoch: signed_entity_type.get_epoch(),
+            signed_entity_type,
+            beacon,
+            metadata,
+            protocol_message,
+            signed_message,
+            aggregate_verification_key,
+            multi_signature,
+            genesis_signature,
+        };
+        check_message(&message, &unknown_field)?;
+    }
+
+    #[test]
+    fn certificate_list_message(
+        (hash, previous_hash, signed_entity_type, beacon) in
+            (hex_string(), hex_string(), signed_entity_type(), beacon()),
+        (metadata, protocol_message) in (certificate_metadata(), protocol_message()),
+        (signed_message, aggregate_verification_key) in (hex_string(), hex_string()),
+        unknown_field in unknown_field(),
+    ) {
+        #[allow(deprecated)]
+        let message: CertificateListMessage = vec![CertificateListItemMessage {
+            hash,
+            previous_hash,
+            epoch: signed_entity_type.get_epoch(),
+            signed_entity_type,
+            beacon,
+            metadata: CertificateListItemMessageMetadata {
+                network: metadata.network,
+                protocol_version: metadata.protocol_version,
+                protocol_parameters: metadata.protocol_parameters,
+                initiated_at: metadata.initiated_at,
+                sealed_at: metadata.sealed_at,
+                total_signers: metadata.signers.len(),
+            },
+            protocol_message,
+            signed_message,
+            aggregate_verification_key,
+        }];
+        check_message(&message, &unknown_field)?;
+    }
+
+    #[test]
+    fn certificate_pending_message(
+        (beacon, signed_entity_type) in (beacon(), signed_entity_type()),
+        (protocol_parameters, next_protocol_parameters) in
+            (protocol_parameters(), protocol_parameters()),
+        signers in prop::collection::vec(signer_message_part(), 0..5),
+        next_signers in prop::collection::vec(signer_message_part(), 0..5),
+        batched_signed_entity_types in prop::collection::vec(signed_entity_type(), 0..3),
+        initiated_at in prop::option::of(date()),
+        unknown_field in unknown_field(),
+    ) {
+        #[allow(deprecated)]
+        let message = CertificatePendingMessage {
+            epoch: beacon.epoch,
+            beacon,
+            signed_entity_type,
+            protocol_parameters,
+            next_protocol_parameters,
+            signers,
+            next_signers,
+            batched_signed_entity_types,
+            initiated_at,
+        };
+        check_message(&message, &unknown_field)?;
+    }
+
+    #[test]
+    fn epoch_settings_message(
+        epoch in epoch(),
+        protocol_parameters in protocol_parameters(),
+        next_protocol_parameters in protocol_parameters(),
+        batched_signed_entity_types_activation in prop::option::of(epoch()),
+        registration_nonce in prop::option::of(hex_string()),
+        upcoming_signers in prop::option::of(prop::collection::vec((party_id(), any::<u64>()), 0..5)),
+        unknown_field in unknown_field(),
+    ) {
+        let protocol_feature_activations = match batched_signed_entity_types_activation {
+            Some(epoch) => ProtocolFeatureActivations::new()
+                .with_activation(ProtocolFeature::BatchedSignedEntityTypes, epoch),
+            None => ProtocolFeatureActivations::new(),
+        };
+        check_message(&EpochSettingsMessage {
+            epoch,
+            protocol_parameters,
+            next_protocol_parameters,
+            protocol_feature_activations,
+            registration_nonce,
+            upcoming_signers: upcoming_signers.map(|signers| {
+                signers
+                    .into_iter()
+                    .map(|(party_id, stake)| StakeDistributionParty { party_id, stake })
+                    .collect()
+            }),
+        }, &unknown_field)?;
+    }
+
+    #[test]
+    fn mithril_stake_distribution_message(
+        (epoch, hash, certificate_hash, created_at) in
+            (epoch(), hex_string(), hex_string(), date()),
+        signers_with_stake in prop::collection::vec(signer_with_stake_message_part(), 0..5),
+        protocol_parameters in protocol_parameters(),
+        unknown_field in unknown_field(),
+    ) {
+        check_message(&MithrilStakeDistributionMessage {
+            epoch,
+            signers_with_stake,
+            hash,
+            certificate_hash,
+            created_at,
+            protocol_parameters,
+        }, &unknown_field)?;
+    }
+
+    #[test]
+    fn mithril_stake_distribution_list_message(
+        items in prop::collection::vec((epoch(), hex_string(), hex_string(), date()), 0..5),
+        unknown_field in unknown_field(),
+    ) {
+        let message: MithrilStakeDistributionListMessage = items
+            .into_iter()
+            .map(|(epoch, hash, certificate_hash, created_at)| {
+                MithrilStakeDistributionListItemMessage {
+                    epoch, hash, certificate_hash, created_at,
+                }
+            })
+            .collect();
+        check_message(&message, &unknown_field)?;
+    }
+
+    #[test]
+    fn paginated_message(
+        items in prop::collection::vec((epoch(), hex_string(), hex_string(), date()), 0..5),
+        total in any::<u64>(),
+        next_cursor in proptest::option::of("[a-z0-9]{1,20}"),
+        unknown_field in unknown_field(),
+    ) {
+        let items = items
+            .into_iter()
+            .map(|(epoch, hash, certificate_hash, created_at)| {
+                MithrilStakeDistributionListItemMessage {
+                    epoch, hash, certificate_hash, created_at,
+                }
+            })
+            .collect();
+        check_message(&PaginatedMessage::new(items, total, next_cursor), &unknown_field)?;
+    }
+
+    #[test]
+    fn register_signature_message(
+        message in register_signature(),
+        unknown_field in unknown_field(),
+    ) {
+        check_message(&message, &unknown_field)?;
+    }
+
+    #[test]
+    fn register_signatures_batch_message(
+        signatures in prop::collection::vec(register_signature(), 0..5),
+        unknown_field in unknown_field(),
+    ) {
+        check_message(&RegisterSignaturesBatchMessage { signatures }, &unknown_field)?;
+    }
+
+    #[test]
+    fn register_signatures_batch_result_message(
+        results in prop::collection::vec(
+            (
+                proptest::option::of(signed_entity_type()),
+                party_id(),
+                register_signature_status(),
+                proptest::option::of(".*"),
+            ),
+            0..5,
+        ),
+        unknown_field in unknown_field(),
+    ) {
+        let results = results
+            .into_iter()
+            .map(|(signed_entity_type, party_id, status, error)| RegisterSignatureResultMessage {
+                signed_entity_type,
+                party_id,
+                status,
+                error,
+            })
+            .collect();
+        check_message(&RegisterSignaturesBatchResultMessage { results }, &unknown_field)?;
+    }
+
+    #[test]
+    fn request_origin_signatures_message(
+        signatures in prop::collection::vec((party_id(), date(), hex_string()), 0..5),
+        unknown_field in unknown_field(),
+    ) {
+        let signatures = signatures
+            .into_iter()
+            .map(|(party_id, signed_at, signature)| RequestOriginSignatureMessage {
+                party_id,
+                signed_at,
+                signature,
+            })
+            .collect();
+        check_message(&RequestOriginSignaturesMessage { signatures }, &unknown_field)?;
+    }
+
+    #[test]
+    fn register_signer_message(
+        epoch in proptest::option::of(epoch()),
+        signer in signer_message_part(),
+        node_metadata in proptest::option::of((
+            proptest::option::of("[0-9]{1,2}\\.[0-9]{1,2}\\.[0-9]{1,3}"),
+            prop::collection::vec("[a-z]{1,10}", 0..3),
+        )),
+        next_era_registration in proptest::option::of(("[a-z]{1,10}", signer_message_part())),
+        unknown_field in unknown_field(),
+    ) {
+        check_message(&RegisterSignerMessage {
+            epoch,
+            party_id: signer.party_id,
+            verification_key: signer.verification_key,
+            verification_key_signature: signer.verification_key_signature,
+            operational_certificate: signer.operational_certificate,
+            kes_period: signer.kes_period,
+            node_metadata: node_metadata.map(|(node_version, supported_eras)| {
+                SignerNodeMetadataMessagePart {
+                    node_version,
+                    supported_eras,
+                }
+            }),
+            next_era_registration: next_era_registration.map(|(era, next_era_signer)| {
+                NextEraRegistrationMessagePart {
+                    era,
+                    verification_key: next_era_signer.verification_key,
+                    verification_key_signature: next_era_signer.verification_key_signature,
+                }
+            }),
+        }, &unknown_field)?;
+    }
+
+    #[test]
+    fn snapshot_message(
+        (digest, beacon, certificate_hash, size, created_at) in
+            (hex_string(), beacon(), hex_string(), any::<u64>(), date()),
+        locations in locations(),
+        compression_algorithm in proptest::option::of(compression_algorithm()),
+        cardano_node_version in proptest::option::of("[0-9]\\.[0-9]\\.[0-9]"),
+        (ancillary_size, ancillary_locations) in
+            (proptest::option::of(any::<u64>()), proptest::option::of(locations())),
+        signed_locations in proptest::option::of(locations()),
+        archive_hash in proptest::option::of(hex_string()),
+        unknown_field in unknown_field(),
+    ) {
+        check_message(&SnapshotMessage {
+            digest,
+            beacon,
+            certificate_hash,
+            size,
+            created_at,
+            locations,
+            compression_algorithm,
+            cardano_node_version,
+            ancillary_size,
+            ancillary_locations,
+            signed_locations,
+            archive_hash,
+        }, &unknown_field)?;
+    }
+
+    #[test]
+    fn snapshot_list_message(
+        (digest, beacon, certificate_hash, size, created_at) in
+            (hex_string(), beacon(), hex_string(), any::<u64>(), date()),
+        locations in locations(),
+        compression_algorithm in proptest::option::of(compression_algorithm()),
+        cardano_node_version in proptest::option::of("[0-9]\\.[0-9]\\.[0-9]"),
+        unknown_field in unknown_field(),
+    ) {
+        let message: SnapshotListMessage = vec![SnapshotListItemMessage {
+            digest,
+            beacon,
+            certificate_hash,
+            size,
+            created_at,
+            locations,
+            compression_algorithm,
+            cardano_node_version,
+        }];
+        check_message(&message, &unknown_field)?;
+    }
+
+    #[test]
+    fn snapshot_download_message(
+        (digest, beacon, size) in (hex_string(), beacon(), any::<u64>()),
+        locations in locations(),
+        compression_algorithm in compression_algorithm(),
+        cardano_node_version in "[0-9]\\.[0-9]\\.[0-9]",
+        unknown_field in unknown_field(),
+    ) {
+        check_message(&SnapshotDownloadMessage {
+            digest,
+            beacon,
+            size,
+            locations,
+            compression_algorithm,
+            cardano_node_version,
+        }, &unknown_field)?;
+    }
+}
+
+fn golden_file_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join("messages")
+        .join(name)
+}
+
+/// Parse a golden file, then check that the parsed message survives a serialization round trip
+fn check_golden_file<T>(name: &str)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let path = golden_file_path(name);
+    let json = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("golden file '{}' could not be read: {e}", path.display()));
+    let message: T = serde_json::from_str(&json).unwrap_or_else(|e| {
+        panic!(
+            "golden file '{name}' could not be parsed as a '{}': {e}",
+            std::any::type_name::<T>()
+        )
+    });
+    let parsed_message: T = serde_json::from_value(serde_json::to_value(&message).unwrap())
+        .unwrap_or_else(|e| panic!("golden file '{name}' round trip parsing failed: {e}"));
+
+    assert_eq!(message, parsed_message, "golden file '{name}'");
+}
+
+#[test]
+fn golden_files_of_previous_releases_are_parsed() {
+    check_golden_file::<CardanoTransactionSnapshotMessage>("cardano_transaction_snapshot_v1.json");
+    check_golden_file::<CardanoTransactionSnapshotListMessage>(
+        "cardano_transaction_snapshot_list_v1.json",
+    );
+    check_golden_file::<CertificateMessage>("certificate_v1.json");
+    check_golden_file::<CertificateListMessage>("certificate_list_v1.json");
+    check_golden_file::<CertificatePendingMessage>("certificate_pending_v1.json");
+    check_golden_file::<CertificatePendingMessage>("certificate_pending_v2.json");
+    check_golden_file::<EpochSettingsMessage>("epoch_settings_v1.json");
+    check_golden_file::<EpochSettingsMessage>("epoch_settings_v2.json");
+    check_golden_file::<MithrilStakeDistributionMessage>("mithril_stake_distribution_v1.json");
+    check_golden_file::<MithrilStakeDistributionListMessage>(
+        "mithril_stake_distribution_list_v1.json",
+    );
+    check_golden_file::<RegisterSignatureMessage>("register_signature_v1.json");
+    check_golden_file::<RegisterSignaturesBatchResultMessage>(
+        "register_signatures_batch_result_v1.json",
+    );
+    check_golden_file::<RegisterSignerMessage>("register_signer_v1.json");
+    check_golden_file::<RegisterSignerMessage>("register_signer_v2.json");
+    check_golden_file::<SnapshotMessage>("snapshot_v1.json");
+    check_golden_file::<SnapshotMessage>("snapshot_v2.json");
+    check_golden_file::<SnapshotDownloadMessage>("snapshot_download_v1.json");
+    check_golden_file::<SnapshotListMessage>("snapshot_list_v1.json");
+    check_golden_file::<SnapshotListMessage>("snapshot_list_v2.json");
+    check_golden_file::<SnapshotListMessage>("snapshot_list_v3.json");
+}
+
+#[test]
+fn all_golden_files_are_checked() {
+    let source = include_str!("messages_compatibility.rs");
+    let directory = golden_file_path("");
+    for entry in std::fs::read_dir(&directory).unwrap() {
+        let file_name = entry.unwrap().file_name().to_string_lossy().to_string();
+        assert!(
+            source.contains(&format!("\"{file_name}\"")),
+            "golden file '{file_name}' is not checked by 'golden_files_of_previous_releases_are_parsed'"
+        );
+    }
+}
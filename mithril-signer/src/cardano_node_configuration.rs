@@ -0,0 +1,300 @@
+use anyhow::Context;
+use config::{ConfigError, Map, Source, Value, ValueKind};
+use serde::Deserialize;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use mithril_common::StdResult;
+
+/// Settings of the signer derived from a standard cardano-node configuration file, so that an
+/// SPO can configure the signer by pointing at the configuration of its node.
+///
+/// The relative paths of the cardano-node configuration are resolved from the directory of the
+/// configuration file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CardanoNodeConfiguration {
+    /// Path of the socket of the Cardano node (`SocketPath`)
+    pub cardano_node_socket_path: Option<PathBuf>,
+
+    /// Directory of the database of the Cardano node (`DatabasePath`)
+    pub db_directory: Option<PathBuf>,
+
+    /// File path to the KES secret key of the pool (`ShelleyKesKey`)
+    pub kes_secret_key_path: Option<PathBuf>,
+
+    /// File path to the operational certificate of the pool (`ShelleyOperationalCertificate`)
+    pub operational_certificate_path: Option<PathBuf>,
+
+    /// Cardano network, deduced from `RequiresNetworkMagic`
+    pub network: Option<String>,
+
+    /// Cardano network magic, read from the Shelley genesis file (`ShelleyGenesisFile`)
+    pub network_magic: Option<u64>,
+}
+
+/// *Internal type* Map the keys of a cardano-node configuration file used by the signer.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CardanoNodeConfigurationFile {
+    socket_path: Option<PathBuf>,
+    database_path: Option<PathBuf>,
+    shelley_kes_key: Option<PathBuf>,
+    shelley_operational_certificate: Option<PathBuf>,
+    shelley_genesis_file: Option<PathBuf>,
+    requires_network_magic: Option<String>,
+}
+
+/// *Internal type* Map the keys of a Shelley genesis file used by the signer.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShelleyGenesisFile {
+    network_magic: u64,
+}
+
+impl CardanoNodeConfiguration {
+    /// Name of the KES secret key file looked up next to the cardano-node configuration file
+    /// when its path is not in the configuration.
+    pub const DEFAULT_KES_SECRET_KEY_FILE: &'static str = "kes.skey";
+
+    /// Name of the operational certificate file looked up next to the cardano-node
+    /// configuration file when its path is not in the configuration.
+    pub const DEFAULT_OPERATIONAL_CERTIFICATE_FILE: &'static str = "opcert.cert";
+
+    /// Read the signer settings from the given cardano-node configuration file (json format)
+    pub fn from_file(node_configuration_path: &Path) -> StdResult<Self> {
+        let node_configuration: CardanoNodeConfigurationFile =
+            serde_json::from_reader(File::open(node_configuration_path).with_context(|| {
+                format!(
+                    "Could not open cardano-node configuration file '{}'",
+                    node_configuration_path.display()
+                )
+            })?)
+            .with_context(|| {
+                format!(
+                    "Could not parse cardano-node configuration file '{}'",
+                    node_configuration_path.display()
+                )
+            })?;
+        let base_directory = node_configuration_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let resolve = |path: Option<PathBuf>| path.map(|path| base_directory.join(path));
+        let default_file = |file_name: &str| {
+            let path = base_directory.join(file_name);
+            path.is_file().then_some(path)
+        };
+
+        let (network, network_magic) = match node_configuration.requires_network_magic.as_deref() {
+            Some("RequiresNoMagic") => (Some("mainnet".to_string()), None),
+            _ => match resolve(node_configuration.shelley_genesis_file) {
+                Some(genesis_path) => (
+                    Some("private".to_string()),
+                    Some(Self::read_network_magic(&genesis_path)?),
+                ),
+                None => (None, None),
+            },
+        };
+
+        Ok(Self {
+            cardano_node_socket_path: resolve(node_configuration.socket_path),
+            db_directory: resolve(node_configuration.database_path),
+            kes_secret_key_path: resolve(node_configuration.shelley_kes_key)
+                .or_else(|| default_file(Self::DEFAULT_KES_SECRET_KEY_FILE)),
+            operational_certificate_path: resolve(
+                node_configuration.shelley_operational_certificate,
+            )
+            .or_else(|| default_file(Self::DEFAULT_OPERATIONAL_CERTIFICATE_FILE)),
+            network,
+            network_magic,
+        })
+    }
+
+    fn read_network_magic(genesis_path: &Path) -> StdResult<u64> {
+        let genesis: ShelleyGenesisFile =
+            serde_json::from_reader(File::open(genesis_path).with_context(|| {
+                format!(
+                    "Could not open Shelley genesis file '{}'",
+                    genesis_path.display()
+                )
+            })?)
+            .with_context(|| {
+                format!(
+                    "Could not read the network magic from Shelley genesis file '{}'",
+                    genesis_path.display()
+                )
+            })?;
+
+        Ok(genesis.network_magic)
+    }
+}
+
+impl Source for CardanoNodeConfiguration {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let mut result = Map::new();
+        let namespace = "cardano node configuration".to_string();
+        let myself = self.clone();
+        let mut insert_path = |key: &str, path: Option<PathBuf>| {
+            if let Some(path) = path {
+                result.insert(
+                    key.to_string(),
+                    Value::new(
+                        Some(&namespace),
+                        ValueKind::from(path.to_string_lossy().to_string()),
+                    ),
+                );
+            }
+        };
+
+        insert_path("cardano_node_socket_path", myself.cardano_node_socket_path);
+        insert_path("db_directory", myself.db_directory);
+        insert_path("kes_secret_key_path", myself.kes_secret_key_path);
+        insert_path(
+            "operational_certificate_path",
+            myself.operational_certificate_path,
+        );
+        if let Some(network) = myself.network {
+            result.insert(
+                "network".to_string(),
+                Value::new(Some(&namespace), ValueKind::from(network)),
+            );
+        }
+        if let Some(network_magic) = myself.network_magic {
+            result.insert(
+                "network_magic".to_string(),
+                Value::new(Some(&namespace), ValueKind::from(network_magic)),
+            );
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::TempDir;
+    use std::fs;
+
+    use super::*;
+
+    fn write_node_configuration(dir: &Path, configuration: &str) -> PathBuf {
+        let path = dir.join("config.json");
+        fs::write(&path, configuration).unwrap();
+        path
+    }
+
+    #[test]
+    fn read_settings_from_node_configuration_resolving_relative_paths() {
+        let dir = TempDir::create("cardano_node_configuration", "read_settings");
+        fs::write(dir.join("shelley-genesis.json"), r#"{"networkMagic": 2}"#).unwrap();
+        let path = write_node_configuration(
+            &dir,
+            r#"{
+                "RequiresNetworkMagic": "RequiresMagic",
+                "ShelleyGenesisFile": "shelley-genesis.json",
+                "SocketPath": "ipc/node.socket",
+                "DatabasePath": "/cardano/db",
+                "ShelleyKesKey": "keys/kes.skey",
+                "ShelleyOperationalCertificate": "keys/node.cert",
+                "TraceBlockFetchClient": false
+            }"#,
+        );
+
+        let configuration = CardanoNodeConfiguration::from_file(&path).unwrap();
+
+        assert_eq!(
+            CardanoNodeConfiguration {
+                cardano_node_socket_path: Some(dir.join("ipc/node.socket")),
+                db_directory: Some(PathBuf::from("/cardano/db")),
+                kes_secret_key_path: Some(dir.join("keys/kes.skey")),
+                operational_certificate_path: Some(dir.join("keys/node.cert")),
+                network: Some("private".to_string()),
+                network_magic: Some(2),
+            },
+            configuration
+        );
+    }
+
+    #[test]
+    fn mainnet_node_configuration_does_not_require_network_magic() {
+        let dir = TempDir::create("cardano_node_configuration", "mainnet");
+        let path = write_node_configuration(
+            &dir,
+            r#"{
+                "RequiresNetworkMagic": "RequiresNoMagic",
+                "ShelleyGenesisFile": "not-read-shelley-genesis.json"
+            }"#,
+        );
+
+        let configuration = CardanoNodeConfiguration::from_file(&path).unwrap();
+
+        assert_eq!(Some("mainnet".to_string()), configuration.network);
+        assert_eq!(None, configuration.network_magic);
+    }
+
+    #[test]
+    fn derive_kes_and_operational_certificate_paths_from_node_configuration_directory() {
+        let dir = TempDir::create("cardano_node_configuration", "derive_keys_paths");
+        let path = write_node_configuration(&dir, "{}");
+
+        let configuration = CardanoNodeConfiguration::from_file(&path).unwrap();
+        assert_eq!(None, configuration.kes_secret_key_path);
+        assert_eq!(None, configuration.operational_certificate_path);
+
+        fs::write(
+            dir.join(CardanoNodeConfiguration::DEFAULT_KES_SECRET_KEY_FILE),
+            "",
+        )
+        .unwrap();
+        fs::write(
+            dir.join(CardanoNodeConfiguration::DEFAULT_OPERATIONAL_CERTIFICATE_FILE),
+            "",
+        )
+        .unwrap();
+
+        let configuration = CardanoNodeConfiguration::from_file(&path).unwrap();
+        assert_eq!(
+            Some(dir.join(CardanoNodeConfiguration::DEFAULT_KES_SECRET_KEY_FILE)),
+            configuration.kes_secret_key_path
+        );
+        assert_eq!(
+            Some(dir.join(CardanoNodeConfiguration::DEFAULT_OPERATIONAL_CERTIFICATE_FILE)),
+            configuration.operational_certificate_path
+        );
+    }
+
+    #[test]
+    fn fail_if_the_shelley_genesis_file_is_missing() {
+        let dir = TempDir::create("cardano_node_configuration", "missing_genesis");
+        let path = write_node_configuration(
+            &dir,
+            r#"{"ShelleyGenesisFile": "missing-shelley-genesis.json"}"#,
+        );
+
+        CardanoNodeConfiguration::from_file(&path)
+            .expect_err("A missing Shelley genesis file should fail");
+    }
+
+    #[test]
+    fn collect_only_the_derived_settings() {
+        let configuration = CardanoNodeConfiguration {
+            cardano_node_socket_path: Some(PathBuf::from("/ipc/node.socket")),
+            network: Some("private".to_string()),
+            network_magic: Some(2),
+            ..CardanoNodeConfiguration::default()
+        };
+
+        let settings = configuration.collect().unwrap();
+        let mut keys = settings.keys().map(String::as_str).collect::<Vec<_>>();
+        keys.sort();
+
+        assert_eq!(
+            vec!["cardano_node_socket_path", "network", "network_magic"],
+            keys
+        );
+    }
+}
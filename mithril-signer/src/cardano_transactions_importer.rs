@@ -123,7 +123,7 @@ impl CardanoTransactionsImporter {
             .transaction_store
             .get_block_interval_without_block_range_root()
             .await?
-            .map(|range| BlockRange::all_block_ranges_in(BlockRange::start(range.start)..range.end))
+            .map(BlockRange::all_block_ranges_in_aligned)
         {
             // Everything is already computed
             None => return Ok(()),
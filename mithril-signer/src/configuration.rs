@@ -2,18 +2,24 @@ use anyhow::Context;
 use config::{ConfigError, Map, Source, Value, ValueKind};
 use mithril_doc::{Documenter, DocumenterDefault, StructDoc};
 use serde::{Deserialize, Serialize};
-use std::{path::PathBuf, sync::Arc};
+use slog::Level;
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
 
 use mithril_common::{
     chain_observer::ChainObserver,
     crypto_helper::tests_setup,
-    entities::PartyId,
+    entities::{PartyId, SignedEntityTypeDiscriminants},
     era::{
         adapters::{EraReaderAdapterBuilder, EraReaderAdapterType},
         EraReaderAdapter,
     },
+    logging::{ComponentLogLevels, LogComponent},
     CardanoNetwork, StdResult,
 };
+use mithril_persistence::store::StoreCipher;
+use mithril_secrets::SecretString;
+
+use crate::{SignedEntityTypeTickers, TickerSource};
 
 /// Client configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Documenter)]
@@ -43,6 +49,11 @@ pub struct Configuration {
     /// Relay endpoint
     pub relay_endpoint: Option<String>,
 
+    /// Value of the `Proxy-Authorization` header sent to the relay, if it requires the signer
+    /// to authenticate.
+    #[example = "`Basic bWl0aHJpbDpzZWNyZXQ=`"]
+    pub relay_authorization: Option<SecretString>,
+
     /// Party Id
     // TODO: Field should be removed once the signer certification is fully deployed
     #[example = "`pool1pxaqe80sqpde7902er5kf6v0c7y0sv6d5g676766v2h829fvs3x`"]
@@ -95,6 +106,78 @@ pub struct Configuration {
     ///
     /// Will be ignored on (pre)production networks.
     pub allow_unparsable_block: bool,
+
+    /// Number of threads used to hash the immutable files when computing the digest of a snapshot.
+    ///
+    /// If not set, the number of logical CPUs is used.
+    pub immutable_digester_threads: Option<usize>,
+
+    /// Log level of some components of the signer, overriding the global log level.
+    ///
+    /// Available components: `http`, `runtime` and `chain_observer`.
+    #[example = "`runtime=debug,http=warn`"]
+    pub component_log_levels: Option<String>,
+
+    /// Token required as a bearer `Authorization` header to use the admin routes served by the
+    /// metrics server of the signer.
+    ///
+    /// The admin routes are disabled if not set.
+    pub admin_api_token: Option<SecretString>,
+
+    /// Enable the submission of anonymized diagnostics to the aggregator on persistent failures.
+    pub enable_diagnostics_submission: bool,
+
+    /// Number of consecutive failed cycles of the state machine after which a diagnostic is
+    /// submitted, if the submission is enabled.
+    pub diagnostics_failure_threshold: u64,
+
+    /// Token sent as a bearer `Authorization` header with the diagnostics, required by the
+    /// aggregator to accept them.
+    pub diagnostics_submission_token: Option<SecretString>,
+
+    /// Path of the configuration file of the Cardano node (json format).
+    ///
+    /// If set, the socket path, the database directory, the KES secret key and operational
+    /// certificate paths and the network are derived from it when they are not set explicitly.
+    #[example = "`/cardano/config/config.json`"]
+    pub cardano_node_config_path: Option<PathBuf>,
+
+    /// Skew between the clocks of the signer and of the aggregator above which a warning is
+    /// logged (in seconds), the skew is computed from the `Date` header of the aggregator
+    /// responses.
+    pub clock_skew_warning_threshold: u64,
+
+    /// Source of the beacon that ticks the signing rounds of some signed entity types, overriding
+    /// their default source.
+    ///
+    /// Available sources: `immutable_file_number` (default) and `block_number` (default for the
+    /// Cardano transactions).
+    #[example = "`CardanoTransactions=block_number,CardanoImmutableFilesFull=immutable_file_number`"]
+    pub ticker_sources: Option<String>,
+
+    /// Disable the preflight checks run before the digest computations: readability of the
+    /// Cardano node database directory, free disk space and available memory.
+    pub disable_preflight_checks: bool,
+
+    /// Minimum free disk space of the data stores directory required to compute a digest (in MiB).
+    ///
+    /// If not set, the free disk space is not checked.
+    #[example = "`2048`"]
+    pub preflight_min_free_disk_space: Option<u64>,
+
+    /// Minimum available memory of the host required to compute a digest (in MiB), only checked
+    /// on Linux.
+    ///
+    /// If not set, the available memory is not checked.
+    #[example = "`1024`"]
+    pub preflight_min_available_memory: Option<u64>,
+
+    /// Hex encoded 32 bytes key used to encrypt the protocol initializers at rest.
+    ///
+    /// If not set, the protocol initializers are stored in clear. The protocol initializers stored
+    /// in clear can not be read once the key is set.
+    #[example = "`openssl rand -hex 32` output"]
+    pub store_encryption_key: Option<SecretString>,
 }
 
 impl Configuration {
@@ -105,6 +188,7 @@ impl Configuration {
         Self {
             aggregator_endpoint: "http://0.0.0.0:8000".to_string(),
             relay_endpoint: None,
+            relay_authorization: None,
             cardano_cli_path: PathBuf::new(),
             cardano_node_socket_path: PathBuf::new(),
             db_directory: PathBuf::new(),
@@ -126,6 +210,19 @@ impl Configuration {
             metrics_server_ip: "0.0.0.0".to_string(),
             metrics_server_port: 9090,
             allow_unparsable_block: false,
+            immutable_digester_threads: None,
+            component_log_levels: None,
+            admin_api_token: None,
+            enable_diagnostics_submission: false,
+            diagnostics_failure_threshold: 5,
+            diagnostics_submission_token: None,
+            cardano_node_config_path: None,
+            clock_skew_warning_threshold: 5,
+            ticker_sources: None,
+            disable_preflight_checks: true,
+            preflight_min_free_disk_space: None,
+            preflight_min_available_memory: None,
+            store_encryption_key: None,
         }
     }
 
@@ -139,6 +236,28 @@ impl Configuration {
         })
     }
 
+    /// Parse the [component log levels][Configuration::component_log_levels].
+    pub fn list_component_log_levels(&self) -> StdResult<BTreeMap<LogComponent, Level>> {
+        match &self.component_log_levels {
+            Some(component_log_levels) => {
+                ComponentLogLevels::parse_component_levels(component_log_levels)
+                    .with_context(|| "invalid 'component_log_levels' configuration")
+            }
+            None => Ok(BTreeMap::new()),
+        }
+    }
+
+    /// Parse the [ticker sources][Configuration::ticker_sources].
+    pub fn list_ticker_sources(
+        &self,
+    ) -> StdResult<BTreeMap<SignedEntityTypeDiscriminants, TickerSource>> {
+        match &self.ticker_sources {
+            Some(ticker_sources) => SignedEntityTypeTickers::parse_sources(ticker_sources)
+                .with_context(|| "invalid 'ticker_sources' configuration"),
+            None => Ok(BTreeMap::new()),
+        }
+    }
+
     /// Create the SQL store directory if not exist and return the path of the
     /// SQLite3 file.
     pub fn get_sqlite_file(&self, sqlite_file_name: &str) -> StdResult<PathBuf> {
@@ -156,6 +275,19 @@ impl Configuration {
         Ok(self.data_stores_directory.join(sqlite_file_name))
     }
 
+    /// Build the [StoreCipher] from the [store encryption key][Configuration::store_encryption_key]
+    /// if it's set.
+    pub fn build_store_cipher(&self) -> StdResult<Option<Arc<StoreCipher>>> {
+        self.store_encryption_key
+            .as_ref()
+            .map(|key| {
+                StoreCipher::from_hex_key(key.expose())
+                    .map(Arc::new)
+                    .with_context(|| "invalid 'store_encryption_key' configuration")
+            })
+            .transpose()
+    }
+
     /// Create era reader adapter from configuration settings.
     pub fn build_era_reader_adapter(
         &self,
@@ -186,6 +318,12 @@ pub struct DefaultConfiguration {
 
     /// Metrics HTTP server listening port.
     pub metrics_server_port: u16,
+
+    /// Number of consecutive failed cycles after which a diagnostic is submitted.
+    pub diagnostics_failure_threshold: u64,
+
+    /// Clock skew warning threshold (in seconds).
+    pub clock_skew_warning_threshold: u64,
 }
 
 impl Default for DefaultConfiguration {
@@ -194,6 +332,8 @@ impl Default for DefaultConfiguration {
             era_reader_adapter_type: "bootstrap".to_string(),
             metrics_server_ip: "0.0.0.0".to_string(),
             metrics_server_port: 9090,
+            diagnostics_failure_threshold: 5,
+            clock_skew_warning_threshold: 5,
         }
     }
 }
@@ -229,6 +369,22 @@ impl Source for DefaultConfiguration {
             ),
         );
 
+        result.insert(
+            "diagnostics_failure_threshold".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.diagnostics_failure_threshold),
+            ),
+        );
+
+        result.insert(
+            "clock_skew_warning_threshold".to_string(),
+            Value::new(
+                Some(&namespace),
+                ValueKind::from(myself.clock_skew_warning_threshold),
+            ),
+        );
+
         Ok(result)
     }
 }
@@ -9,14 +9,13 @@ pub mod repository;
 
 #[cfg(test)]
 pub mod test_utils {
-    use sqlite::ConnectionThreadSafe;
 
     use mithril_common::StdResult;
-    use mithril_persistence::sqlite::{ConnectionBuilder, ConnectionOptions};
+    use mithril_persistence::sqlite::{ConnectionBuilder, ConnectionOptions, SqliteConnection};
 
     use super::*;
 
-    pub fn cardano_tx_db_connection() -> StdResult<ConnectionThreadSafe> {
+    pub fn cardano_tx_db_connection() -> StdResult<SqliteConnection> {
         let connection = ConnectionBuilder::open_memory()
             .with_options(&[ConnectionOptions::ForceDisableForeignKeys])
             .with_migrations(cardano_transaction_migration::get_migrations())
@@ -0,0 +1,625 @@
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use std::{collections::BTreeMap, fmt::Display, path::Path, sync::Arc};
+use tokio::sync::RwLock;
+
+use mithril_common::{
+    crypto_helper::{OpCert, SerDeShelleyFileFormat, Sum6KesBytes},
+    entities::{
+        CardanoDbBeacon, CertificatePending, Epoch, EpochSettings, ProtocolMessagePartKey,
+        ProtocolParameters, SignedEntityType, Signer, SignerWithStake, SingleSignatures, Stake,
+        TimePoint,
+    },
+    era::SupportedEra,
+    messages::{RegisterSignatureStatus, SignerDiagnosticMessage},
+    protocol::{ProtocolSignatureScheme, SignerBuilder, StmSignatureScheme},
+    StdResult,
+};
+use mithril_persistence::store::StakeStorer;
+
+use crate::{
+    AggregatorClient, AggregatorClientError, Configuration, ProductionServiceBuilder, Runner,
+    ServiceBuilder, SignerRunner, SignerServices,
+};
+
+/// Maximum number of KES periods during which an operational certificate is valid, as set in the
+/// Shelley genesis of the Cardano networks.
+const MAX_KES_EVOLUTIONS: u64 = 62;
+
+/// Stake used in the simulation for a pool that is not yet in the stake distribution.
+const SIMULATED_STAKE: Stake = 1;
+
+/// Status of a check of a [ReadinessReport]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadinessCheckStatus {
+    /// The check passed
+    Passed,
+
+    /// The check passed but the setup should be reviewed
+    Warning,
+
+    /// The check failed, the signer is not ready
+    Failed,
+}
+
+/// Outcome of a check of the setup of the signer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadinessCheck {
+    /// Name of the check
+    pub name: String,
+
+    /// Status of the check
+    pub status: ReadinessCheckStatus,
+
+    /// Details of the outcome
+    pub detail: String,
+}
+
+/// Report of a [dry run][DryRunSimulator] of the signer
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReadinessReport {
+    /// Checks done during the dry run, in order
+    pub checks: Vec<ReadinessCheck>,
+}
+
+impl ReadinessReport {
+    /// Check if the signer is ready, i.e. if none of the checks failed
+    pub fn is_ready(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| check.status != ReadinessCheckStatus::Failed)
+    }
+
+    fn push<N: Into<String>, D: Into<String>>(
+        &mut self,
+        name: N,
+        status: ReadinessCheckStatus,
+        detail: D,
+    ) {
+        self.checks.push(ReadinessCheck {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        });
+    }
+
+    fn passed<N: Into<String>, D: Into<String>>(&mut self, name: N, detail: D) {
+        self.push(name, ReadinessCheckStatus::Passed, detail);
+    }
+
+    fn warning<N: Into<String>, D: Into<String>>(&mut self, name: N, detail: D) {
+        self.push(name, ReadinessCheckStatus::Warning, detail);
+    }
+
+    fn failed<N: Into<String>>(&mut self, name: N, error: anyhow::Error) {
+        self.push(name, ReadinessCheckStatus::Failed, format!("{error:?}"));
+    }
+}
+
+impl Display for ReadinessReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Mithril signer readiness report")?;
+        for check in &self.checks {
+            let status = match check.status {
+                ReadinessCheckStatus::Passed => "OK",
+                ReadinessCheckStatus::Warning => "WARNING",
+                ReadinessCheckStatus::Failed => "FAILED",
+            };
+            writeln!(f, "  [{status}] {}: {}", check.name, check.detail)?;
+        }
+        if self.is_ready() {
+            write!(f, "The signer is ready to sign")
+        } else {
+            write!(f, "The signer is NOT ready to sign")
+        }
+    }
+}
+
+/// Aggregator embedded in the signer for the dry runs.
+///
+/// It serves fixed epoch settings and records the signers and signatures it receives, the
+/// registrations are verified like a real aggregator does (i.e. the verification key signature
+/// is checked against the operational certificate).
+pub struct DryRunAggregatorClient {
+    epoch_settings: EpochSettings,
+    registered_signers: RwLock<BTreeMap<Epoch, Vec<Signer>>>,
+    registered_signatures: RwLock<Vec<(SignedEntityType, SingleSignatures)>>,
+}
+
+impl DryRunAggregatorClient {
+    /// DryRunAggregatorClient factory
+    pub fn new(epoch: Epoch, protocol_parameters: ProtocolParameters) -> Self {
+        Self {
+            epoch_settings: EpochSettings {
+                epoch,
+                protocol_parameters: protocol_parameters.clone(),
+                next_protocol_parameters: protocol_parameters,
+                protocol_feature_activations: Default::default(),
+                registration_nonce: None,
+                upcoming_signers: None,
+            },
+            registered_signers: RwLock::new(BTreeMap::new()),
+            registered_signatures: RwLock::new(vec![]),
+        }
+    }
+
+    /// Get the signers registered for the given epoch
+    pub async fn get_registered_signers(&self, epoch: Epoch) -> Vec<Signer> {
+        self.registered_signers
+            .read()
+            .await
+            .get(&epoch)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Get the single signatures registered so far
+    pub async fn get_registered_signatures(&self) -> Vec<(SignedEntityType, SingleSignatures)> {
+        self.registered_signatures.read().await.clone()
+    }
+}
+
+#[async_trait]
+impl AggregatorClient for DryRunAggregatorClient {
+    async fn retrieve_epoch_settings(
+        &self,
+    ) -> Result<Option<EpochSettings>, AggregatorClientError> {
+        Ok(Some(self.epoch_settings.clone()))
+    }
+
+    async fn retrieve_pending_certificate(
+        &self,
+    ) -> Result<Option<CertificatePending>, AggregatorClientError> {
+        Ok(None)
+    }
+
+    async fn register_signer(
+        &self,
+        epoch: Epoch,
+        signer: &Signer,
+    ) -> Result<(), AggregatorClientError> {
+        // The stake does not matter to check the registration of a single signer
+        SignerBuilder::new(
+            &[SignerWithStake::from_signer(
+                signer.clone(),
+                SIMULATED_STAKE,
+            )],
+            &self.epoch_settings.protocol_parameters,
+        )
+        .map_err(AggregatorClientError::RemoteServerLogical)?;
+
+        let mut registered_signers = self.registered_signers.write().await;
+        let epoch_signers = registered_signers.entry(epoch).or_default();
+        epoch_signers.retain(|registered_signer| registered_signer.party_id != signer.party_id);
+        epoch_signers.push(signer.clone());
+
+        Ok(())
+    }
+
+    async fn register_signer_for_next_era(
+        &self,
+        epoch: Epoch,
+        signer: &Signer,
+        _next_era: SupportedEra,
+        next_era_signer: &Signer,
+    ) -> Result<(), AggregatorClientError> {
+        SignerBuilder::new(
+            &[SignerWithStake::from_signer(
+                next_era_signer.clone(),
+                SIMULATED_STAKE,
+            )],
+            &self.epoch_settings.protocol_parameters,
+        )
+        .map_err(AggregatorClientError::RemoteServerLogical)?;
+
+        self.register_signer(epoch, signer).await
+    }
+
+    async fn register_signatures(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        signatures: &SingleSignatures,
+    ) -> Result<(), AggregatorClientError> {
+        self.registered_signatures
+            .write()
+            .await
+            .push((signed_entity_type.clone(), signatures.clone()));
+
+        Ok(())
+    }
+
+    async fn register_signatures_batch(
+        &self,
+        signatures: &[(SignedEntityType, SingleSignatures)],
+    ) -> Result<Vec<RegisterSignatureStatus>, AggregatorClientError> {
+        self.registered_signatures
+            .write()
+            .await
+            .extend_from_slice(signatures);
+
+        Ok(vec![RegisterSignatureStatus::Registered; signatures.len()])
+    }
+
+    async fn submit_diagnostic(
+        &self,
+        _diagnostic: &SignerDiagnosticMessage,
+    ) -> Result<(), AggregatorClientError> {
+        Ok(())
+    }
+}
+
+/// Simulate the signing cycle of the signer against a [DryRunAggregatorClient] to validate its
+/// setup without interacting with a real aggregator.
+///
+/// The Cardano node and the immutable files are the real ones, but the stores of the simulation
+/// are kept in a `dry-run` subdirectory of the data stores directory, so the stores of the
+/// signer are left untouched.
+pub struct DryRunSimulator {
+    config: Configuration,
+    protocol_parameters: ProtocolParameters,
+}
+
+impl DryRunSimulator {
+    /// DryRunSimulator factory
+    pub fn new(config: &Configuration) -> Self {
+        let mut config = config.clone();
+        config.data_stores_directory = config.data_stores_directory.join("dry-run");
+
+        Self {
+            config,
+            // Protocol parameters of the Mithril mainnet network
+            protocol_parameters: ProtocolParameters::new(2422, 20973, 0.20),
+        }
+    }
+
+    /// Run the simulation and report the readiness of the signer.
+    ///
+    /// The simulation stops at the first failed step, as the following ones depend on it.
+    pub async fn run(&self) -> ReadinessReport {
+        let mut report = ReadinessReport::default();
+        if let Err(error) = self.check_node_socket() {
+            report.failed("Cardano node socket", error);
+            return report;
+        }
+
+        let mut services = match ProductionServiceBuilder::new(&self.config).build().await {
+            Ok(services) => services,
+            Err(error) => {
+                report.failed("Signer services", error);
+                return report;
+            }
+        };
+        let time_point = match services.time_point_provider.get_current_time_point().await {
+            Ok(time_point) => {
+                report.passed(
+                    "Cardano node socket",
+                    format!(
+                        "node reachable at epoch {}, last immutable file {}",
+                        time_point.epoch, time_point.immutable_file_number
+                    ),
+                );
+                time_point
+            }
+            Err(error) => {
+                report.failed("Cardano node socket", error);
+                return report;
+            }
+        };
+
+        match &self.config.operational_certificate_path {
+            Some(path) => match self.check_operational_certificate(path, &services).await {
+                Ok(detail) => report.passed("Operational certificate", detail),
+                Err(error) => {
+                    report.failed("Operational certificate", error);
+                    return report;
+                }
+            },
+            None => report.warning(
+                "Operational certificate",
+                "not configured, the signer registration is not certified",
+            ),
+        }
+        match &self.config.kes_secret_key_path {
+            Some(path) => match Sum6KesBytes::from_file(path) {
+                Ok(_) => report.passed("KES secret key", format!("loaded from {}", path.display())),
+                Err(error) => {
+                    report.failed(
+                        "KES secret key",
+                        anyhow!(error)
+                            .context(format!("Invalid KES secret key '{}'", path.display())),
+                    );
+                    return report;
+                }
+            },
+            None => report.warning(
+                "KES secret key",
+                "not configured, the verification key is not signed",
+            ),
+        }
+
+        let aggregator = Arc::new(DryRunAggregatorClient::new(
+            time_point.epoch,
+            self.protocol_parameters.clone(),
+        ));
+        services.certificate_handler = aggregator.clone();
+        if let Err(error) = self
+            .simulate(&mut report, services, aggregator, &time_point)
+            .await
+        {
+            report.failed("Simulation", error);
+        }
+
+        report
+    }
+
+    fn check_node_socket(&self) -> StdResult<()> {
+        let socket_path = &self.config.cardano_node_socket_path;
+        let metadata = socket_path.metadata().with_context(|| {
+            format!(
+                "Cardano node socket '{}' is not accessible",
+                socket_path.display()
+            )
+        })?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::FileTypeExt;
+            if !metadata.file_type().is_socket() {
+                return Err(anyhow!(
+                    "'{}' is not a socket, is the Cardano node running?",
+                    socket_path.display()
+                ));
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = metadata;
+
+        Ok(())
+    }
+
+    async fn check_operational_certificate(
+        &self,
+        path: &Path,
+        services: &SignerServices,
+    ) -> StdResult<String> {
+        let opcert = OpCert::from_file(path).map_err(|error| {
+            anyhow!(error).context(format!(
+                "Invalid operational certificate '{}'",
+                path.display()
+            ))
+        })?;
+        opcert
+            .validate()
+            .with_context(|| "The operational certificate is not signed by the pool cold key")?;
+        let pool_id = opcert
+            .compute_protocol_party_id()
+            .with_context(|| "Could not compute the pool id of the operational certificate")?;
+        let current_kes_period = services
+            .chain_observer
+            .get_current_kes_period(&opcert)
+            .await?
+            .ok_or_else(|| anyhow!("The current KES period could not be read from the node"))?;
+        let start_kes_period = opcert.start_kes_period;
+        let end_kes_period = start_kes_period + MAX_KES_EVOLUTIONS;
+        if !(start_kes_period..end_kes_period).contains(&(current_kes_period as u64)) {
+            return Err(anyhow!(
+                "The operational certificate is valid from KES period {start_kes_period} to \
+                {end_kes_period}, but the current KES period is {current_kes_period}"
+            ));
+        }
+
+        Ok(format!(
+            "pool {pool_id}, valid until KES period {end_kes_period} \
+            (current: {current_kes_period})"
+        ))
+    }
+
+    /// Register the signer at the two previous epochs, as if it had been running since, then
+    /// sign the immutable files at the current epoch.
+    async fn simulate(
+        &self,
+        report: &mut ReadinessReport,
+        services: SignerServices,
+        aggregator: Arc<DryRunAggregatorClient>,
+        time_point: &TimePoint,
+    ) -> StdResult<()> {
+        let epoch = time_point.epoch;
+        let registration_epochs = [epoch.offset_by(-2)?, epoch.offset_by(-1)?];
+        let party_id = services.single_signer.get_party_id();
+
+        let stake_distribution = services
+            .chain_observer
+            .get_current_stake_distribution()
+            .await?
+            .unwrap_or_default();
+        let stake = match stake_distribution.get(&party_id) {
+            Some(stake) => {
+                report.passed("Stake", format!("pool {party_id} has a stake of {stake}"));
+                *stake
+            }
+            None => {
+                report.warning(
+                    "Stake",
+                    format!(
+                        "pool {party_id} is not in the stake distribution yet, \
+                        a simulated stake is used"
+                    ),
+                );
+                SIMULATED_STAKE
+            }
+        };
+        for registration_epoch in registration_epochs {
+            services
+                .stake_store
+                .save_stakes(
+                    registration_epoch.offset_to_recording_epoch(),
+                    BTreeMap::from([(party_id.clone(), stake)]),
+                )
+                .await?;
+        }
+
+        let runner = SignerRunner::new(self.config.clone(), services);
+        let protocol_parameters = runner
+            .get_epoch_settings()
+            .await?
+            .ok_or_else(|| anyhow!("No epoch settings served by the embedded aggregator"))?
+            .protocol_parameters;
+        for registration_epoch in registration_epochs {
+            if let Err(error) = runner
+                .register_signer_to_aggregator(registration_epoch, &protocol_parameters)
+                .await
+            {
+                report.failed("Key generation and registration", error);
+                return Ok(());
+            }
+        }
+        report.passed(
+            "Key generation and registration",
+            "verification key generated and accepted by the embedded aggregator",
+        );
+
+        let signers_with_stake = |signers: Vec<Signer>| {
+            signers
+                .into_iter()
+                .map(|signer| SignerWithStake::from_signer(signer, stake))
+                .collect::<Vec<_>>()
+        };
+        let current_signers = signers_with_stake(
+            aggregator
+                .get_registered_signers(registration_epochs[0].offset_to_recording_epoch())
+                .await,
+        );
+        let next_signers = signers_with_stake(
+            aggregator
+                .get_registered_signers(registration_epochs[1].offset_to_recording_epoch())
+                .await,
+        );
+        let signed_entity_type = SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::new(
+            self.config.get_network()?.to_string(),
+            *epoch,
+            time_point.immutable_file_number,
+        ));
+        let message = match runner
+            .compute_message(&signed_entity_type, &next_signers)
+            .await
+        {
+            Ok(message) => {
+                report.passed(
+                    "Digest computation",
+                    format!(
+                        "digest of the immutable files up to {}: {}",
+                        time_point.immutable_file_number,
+                        message
+                            .get_message_part(&ProtocolMessagePartKey::SnapshotDigest)
+                            .cloned()
+                            .unwrap_or_default()
+                    ),
+                );
+                message
+            }
+            Err(error) => {
+                report.failed("Digest computation", error);
+                return Ok(());
+            }
+        };
+
+        let signature = runner
+            .compute_single_signature(epoch, &signed_entity_type, &message, &current_signers)
+            .await
+            .and_then(|signature| {
+                signature.ok_or_else(|| anyhow!("No lottery won for the simulated message"))
+            })
+            .and_then(|signature| {
+                StmSignatureScheme
+                    .build_multi_signer(&current_signers, &protocol_parameters)?
+                    .verify_single_signature(&message, &signature)
+                    .with_context(|| "The single signature is invalid")?;
+                Ok(signature)
+            });
+        match signature {
+            Ok(signature) => {
+                runner
+                    .send_single_signature(&signed_entity_type, Some(signature.clone()), None)
+                    .await?;
+                report.passed(
+                    "Signature production",
+                    format!(
+                        "single signature with {} won lotteries verified",
+                        signature.won_indexes.len()
+                    ),
+                );
+            }
+            Err(error) => report.failed("Signature production", error),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::MithrilFixtureBuilder;
+
+    use super::*;
+
+    #[test]
+    fn report_is_ready_if_no_check_failed() {
+        let mut report = ReadinessReport::default();
+        report.passed("check 1", "ok");
+        report.warning("check 2", "to review");
+        assert!(report.is_ready());
+
+        report.failed("check 3", anyhow!("failure"));
+        assert!(!report.is_ready());
+        assert!(report.to_string().contains("[FAILED] check 3"));
+    }
+
+    #[tokio::test]
+    async fn embedded_aggregator_records_the_registered_signers() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let aggregator = DryRunAggregatorClient::new(Epoch(10), fixture.protocol_parameters());
+
+        for signer in fixture.signers() {
+            aggregator
+                .register_signer(Epoch(11), &signer)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            fixture.signers(),
+            aggregator.get_registered_signers(Epoch(11)).await
+        );
+        assert!(aggregator
+            .get_registered_signers(Epoch(10))
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn embedded_aggregator_refuses_a_signer_with_an_invalid_key_signature() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let aggregator = DryRunAggregatorClient::new(Epoch(10), fixture.protocol_parameters());
+        let mut signer = fixture.signers()[0].clone();
+        signer.verification_key_signature = fixture.signers()[1].verification_key_signature;
+
+        aggregator
+            .register_signer(Epoch(11), &signer)
+            .await
+            .expect_err("a signer with an invalid verification key signature should be refused");
+        assert!(aggregator
+            .get_registered_signers(Epoch(11))
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn dry_run_fails_without_node_socket() {
+        let mut config = Configuration::new_sample(&"pool1".to_string());
+        config.cardano_node_socket_path = "/non/existent/node.socket".into();
+
+        let report = DryRunSimulator::new(&config).run().await;
+
+        assert!(!report.is_ready());
+        assert_eq!("Cardano node socket", report.checks[0].name);
+    }
+}
@@ -7,30 +7,42 @@
 //! for more information on how it works.
 
 mod aggregator_client;
+mod cardano_node_configuration;
 mod cardano_transactions_importer;
 mod configuration;
 pub mod database;
+mod dry_run;
 mod message_adapters;
 pub mod metrics;
+mod protocol_initializer_backup;
 mod protocol_initializer_store;
+mod request_origin_signer;
 mod runtime;
 mod single_signer;
 
 #[cfg(test)]
 pub use aggregator_client::dumb::DumbAggregatorClient;
 pub use aggregator_client::*;
+pub use cardano_node_configuration::CardanoNodeConfiguration;
 pub use cardano_transactions_importer::*;
 pub use configuration::{Configuration, DefaultConfiguration};
+pub use dry_run::*;
 pub use message_adapters::{
     FromEpochSettingsAdapter, FromPendingCertificateMessageAdapter, ToRegisterSignerMessageAdapter,
 };
 pub use metrics::*;
-pub use protocol_initializer_store::{ProtocolInitializerStore, ProtocolInitializerStorer};
+pub use protocol_initializer_backup::{
+    EncryptedProtocolInitializerBackup, ProtocolInitializerBackup,
+};
+pub use protocol_initializer_store::{
+    get_signing_protocol_initializer, ProtocolInitializerStore, ProtocolInitializerStorer,
+};
+pub use request_origin_signer::RequestOriginSigner;
 pub use runtime::*;
 pub use single_signer::*;
 
 /// HTTP request timeout duration in milliseconds
-const HTTP_REQUEST_TIMEOUT_DURATION: u64 = 30000;
+pub const HTTP_REQUEST_TIMEOUT_DURATION: u64 = 30000;
 
 /// SQLite file names
 const SQLITE_FILE: &str = "signer.sqlite3";
@@ -4,21 +4,30 @@ use config::{Map, Value};
 use mithril_doc::{Documenter, DocumenterDefault, StructDoc};
 
 use slog::{o, Drain, Level, Logger};
-use slog_scope::{crit, debug};
+use slog_scope::{crit, debug, warn};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::{
-    signal::unix::{signal, SignalKind},
-    sync::oneshot,
-    task::JoinSet,
+#[cfg(unix)]
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::{sync::oneshot, task::JoinSet};
+
+use mithril_common::api_version::APIVersionProvider;
+use mithril_common::crypto_helper::{
+    compute_operational_certificate_kes_period, compute_signer_unregistration_message,
+    sign_registration_nonce_message, OpCert, SerDeShelleyFileFormat,
 };
-
+use mithril_common::logging::{ComponentLevelFilter, ComponentLogLevels};
 use mithril_common::StdResult;
 use mithril_doc::GenerateDocCommands;
+use mithril_secrets::SecretsEnvironmentResolver;
+use mithril_service_manager::{notifier_from_environment, run_watchdog, ServiceNotifier};
 use mithril_signer::{
-    Configuration, DefaultConfiguration, MetricsServer, ProductionServiceBuilder, ServiceBuilder,
-    SignerRunner, SignerState, StateMachine,
+    AggregatorClient, AggregatorHTTPClient, CardanoNodeConfiguration, Configuration,
+    DefaultConfiguration, DryRunSimulator, EncryptedProtocolInitializerBackup, MetricsServer,
+    ProductionServiceBuilder, ProtocolInitializerBackup, ServiceBuilder, SignedEntityTypeTickers,
+    SignerRunner, SignerState, StateMachine, TransitionsLogger, TransitionsRecorder,
+    HTTP_REQUEST_TIMEOUT_DURATION,
 };
 
 /// CLI args
@@ -81,6 +90,30 @@ pub struct Args {
     /// Will be ignored on (pre)production networks.
     #[clap(long)]
     allow_unparsable_block: bool,
+
+    /// Enable the submission of anonymized diagnostics to the aggregator on persistent failures.
+    #[clap(long, env = "ENABLE_DIAGNOSTICS_SUBMISSION", default_value_t = false)]
+    enable_diagnostics_submission: bool,
+
+    /// Disable the preflight checks (database directory readability, free disk space and
+    /// available memory) run before the digest computations.
+    #[clap(long, env = "DISABLE_PREFLIGHT_CHECKS", default_value_t = false)]
+    disable_preflight_checks: bool,
+
+    /// Path of the configuration file of the Cardano node (json format).
+    ///
+    /// The socket path, the database directory, the KES secret key and operational certificate
+    /// paths and the network are derived from it when they are not set explicitly.
+    #[clap(long, env = "CARDANO_NODE_CONFIG_PATH")]
+    cardano_node_config_path: Option<PathBuf>,
+
+    /// Simulate a signing cycle against an embedded aggregator then print a readiness report.
+    ///
+    /// The KES secret key, the operational certificate and the access to the Cardano node socket
+    /// are checked, and a signature of the immutable files is produced without registering to
+    /// the aggregator. The stores of the signer are left untouched.
+    #[clap(long)]
+    dry_run: bool,
 }
 
 impl Args {
@@ -95,12 +128,12 @@ impl Args {
     }
 }
 
-fn build_logger(min_level: Level) -> Logger {
+fn build_logger(log_levels: ComponentLogLevels) -> Logger {
     let drain = slog_bunyan::new(std::io::stdout())
         .set_pretty(false)
         .build()
         .fuse();
-    let drain = slog::LevelFilter::new(drain, min_level).fuse();
+    let drain = ComponentLevelFilter::new(drain, log_levels).fuse();
     let drain = slog_async::Async::new(drain).build().fuse();
 
     Logger::root(Arc::new(drain), o!())
@@ -110,13 +143,190 @@ fn build_logger(min_level: Level) -> Logger {
 enum SignerCommands {
     #[clap(alias("doc"), hide(true))]
     GenerateDoc(GenerateDocCommands),
+
+    /// Export the protocol initializers of the signer to an encrypted backup file
+    Backup(BackupCommand),
+
+    /// Restore the protocol initializers of the signer from an encrypted backup file
+    Restore(RestoreCommand),
+
+    /// Unregister the signer from the signers of the upcoming epochs
+    Unregister(UnregisterCommand),
+}
+
+/// Export the protocol initializers of the signer to an encrypted backup file
+#[derive(Parser, Debug, Clone)]
+struct BackupCommand {
+    /// Path of the backup file to create
+    #[clap(long)]
+    output: PathBuf,
+
+    /// Passphrase used to encrypt the backup
+    #[clap(long, env = "BACKUP_PASSPHRASE", hide_env_values = true)]
+    passphrase: String,
+}
+
+impl BackupCommand {
+    async fn execute(&self, config: &Configuration) -> StdResult<()> {
+        let store = ProductionServiceBuilder::new(config)
+            .build_protocol_initializer_store()
+            .await?;
+        let backup = ProtocolInitializerBackup::export(store.as_ref()).await?;
+        let encrypted_backup = backup.encrypt(&self.passphrase)?;
+        std::fs::write(&self.output, serde_json::to_vec(&encrypted_backup)?).with_context(
+            || {
+                format!(
+                    "Could not write the backup file '{}'",
+                    self.output.display()
+                )
+            },
+        )?;
+
+        println!(
+            "{} protocol initializer(s) exported to '{}'",
+            backup.protocol_initializers.len(),
+            self.output.display()
+        );
+        Ok(())
+    }
+}
+
+/// Restore the protocol initializers of the signer from an encrypted backup file
+#[derive(Parser, Debug, Clone)]
+struct RestoreCommand {
+    /// Path of the backup file to restore
+    #[clap(long)]
+    input: PathBuf,
+
+    /// Passphrase used to encrypt the backup
+    #[clap(long, env = "BACKUP_PASSPHRASE", hide_env_values = true)]
+    passphrase: String,
+}
+
+impl RestoreCommand {
+    async fn execute(&self, config: &Configuration) -> StdResult<()> {
+        let encrypted_backup: EncryptedProtocolInitializerBackup =
+            serde_json::from_slice(&std::fs::read(&self.input).with_context(|| {
+                format!("Could not read the backup file '{}'", self.input.display())
+            })?)
+            .with_context(|| format!("Invalid backup file '{}'", self.input.display()))?;
+        let backup = encrypted_backup.decrypt(&self.passphrase)?;
+
+        match &config.operational_certificate_path {
+            Some(operational_certificate_path) => {
+                let operational_certificate = OpCert::from_file(operational_certificate_path)
+                    .with_context(|| "Could not decode operational certificate")?;
+                backup.verify_operational_certificate(&operational_certificate)?;
+            }
+            None => {
+                warn!("No operational certificate configured, the restored protocol initializers are not verified");
+            }
+        }
+
+        let store = ProductionServiceBuilder::new(config)
+            .build_protocol_initializer_store()
+            .await?;
+        let count = backup.restore(store.as_ref()).await?;
+
+        println!(
+            "{count} protocol initializer(s) restored from '{}'",
+            self.input.display()
+        );
+        Ok(())
+    }
+}
+
+/// Unregister the signer from the signers of the upcoming epochs
+#[derive(Parser, Debug, Clone)]
+struct UnregisterCommand {}
+
+impl UnregisterCommand {
+    async fn execute(&self, config: &Configuration) -> StdResult<()> {
+        let (Some(kes_secret_key_path), Some(operational_certificate_path)) = (
+            &config.kes_secret_key_path,
+            &config.operational_certificate_path,
+        ) else {
+            return Err(anyhow!(
+                "The KES secret key and the operational certificate are required to unregister the signer"
+            ));
+        };
+        let operational_certificate = OpCert::from_file(operational_certificate_path)
+            .with_context(|| "Could not decode operational certificate")?;
+        let party_id = operational_certificate.compute_protocol_party_id()?;
+
+        let service_builder = ProductionServiceBuilder::new(config);
+        let chain_observer = service_builder.build_chain_observer()?;
+        let era_checker = service_builder
+            .build_era_checker(chain_observer.clone())
+            .await?;
+        let aggregator_client = AggregatorHTTPClient::new(
+            config.aggregator_endpoint.clone(),
+            config.relay_endpoint.clone(),
+            Arc::new(APIVersionProvider::new(era_checker)),
+            Some(Duration::from_millis(HTTP_REQUEST_TIMEOUT_DURATION)),
+        )
+        .with_relay_authorization(config.relay_authorization.clone());
+        let epoch_settings = aggregator_client
+            .retrieve_epoch_settings()
+            .await?
+            .ok_or_else(|| anyhow!("The aggregator did not return its epoch settings"))?;
+        // The signer is unregistered from the epoch of the current registration round
+        let epoch = epoch_settings.epoch.offset_to_recording_epoch();
+        let registration_nonce = epoch_settings
+            .registration_nonce
+            .ok_or_else(|| anyhow!("The aggregator did not publish a registration nonce"))?;
+
+        // The KES secret key must be evolved to the current KES period of the operational
+        // certificate, the only one accepted by the aggregator
+        let current_kes_period = chain_observer
+            .get_current_kes_period(&operational_certificate)
+            .await?
+            .ok_or_else(|| anyhow!("The chain observer did not return the current KES period"))?;
+        let kes_period = compute_operational_certificate_kes_period(
+            current_kes_period,
+            &operational_certificate,
+        )
+        .ok_or_else(|| anyhow!("The operational certificate is not valid yet"))?;
+        let signature = sign_registration_nonce_message(
+            kes_secret_key_path,
+            kes_period,
+            &compute_signer_unregistration_message(epoch, &registration_nonce, &party_id),
+        )
+        .with_context(|| "Could not sign the unregistration")?;
+        aggregator_client
+            .unregister_signer(epoch, &party_id, &signature)
+            .await?;
+
+        println!("Signer '{party_id}' unregistered from epoch {epoch}");
+        Ok(())
+    }
+}
+
+fn main() -> StdResult<()> {
+    #[cfg(all(windows, feature = "windows_service"))]
+    if mithril_service_manager::windows::run_if_started_as_service("mithril-signer", run)? {
+        return Ok(());
+    }
+
+    run(notifier_from_environment())
 }
 
-#[tokio::main]
-async fn main() -> StdResult<()> {
+/// Run the signer, its state is reported to the service manager through the given notifier.
+fn run(service_notifier: Arc<dyn ServiceNotifier>) -> StdResult<()> {
+    tokio::runtime::Runtime::new()?.block_on(run_signer(service_notifier))
+}
+
+async fn run_signer(service_notifier: Arc<dyn ServiceNotifier>) -> StdResult<()> {
+    // Resolve the secrets given through files or secret managers before reading the settings
+    let environment = SecretsEnvironmentResolver::new()
+        .with_enabled_resolvers()
+        .resolve_environment()
+        .await?;
+
     // Load args
-    let args = Args::parse();
-    let _guard = slog_scope::set_global_logger(build_logger(args.log_level()));
+    let args: Args = environment.parse_arguments();
+    let log_levels = ComponentLogLevels::new(args.log_level());
+    let _guard = slog_scope::set_global_logger(build_logger(log_levels.clone()));
 
     if let Some(SignerCommands::GenerateDoc(cmd)) = &args.command {
         let config_infos = vec![
@@ -135,7 +345,7 @@ async fn main() -> StdResult<()> {
     debug!("Starting"; "node_version" => env!("CARGO_PKG_VERSION"));
 
     // Load config
-    let config: Configuration = config::Config::builder()
+    let mut config_builder = config::Config::builder()
         .set_default("disable_digests_cache", args.disable_digests_cache)
         .with_context(|| "configuration error: could not set `disable_digests_cache`")?
         .set_default("reset_digests_cache", args.reset_digests_cache)
@@ -144,7 +354,29 @@ async fn main() -> StdResult<()> {
         .with_context(|| "configuration error: could not set `enable_metrics_server`")?
         .set_default("allow_unparsable_block", args.allow_unparsable_block)
         .with_context(|| "configuration error: could not set `allow_unparsable_block`")?
-        .add_source(DefaultConfiguration::default())
+        .set_default(
+            "enable_diagnostics_submission",
+            args.enable_diagnostics_submission,
+        )
+        .with_context(|| "configuration error: could not set `enable_diagnostics_submission`")?
+        .set_default("disable_preflight_checks", args.disable_preflight_checks)
+        .with_context(|| "configuration error: could not set `disable_preflight_checks`")?
+        .add_source(DefaultConfiguration::default());
+    // The settings derived from the Cardano node configuration can be overridden by the
+    // configuration file and the environment
+    if let Some(cardano_node_config_path) = &args.cardano_node_config_path {
+        config_builder = config_builder
+            .set_default(
+                "cardano_node_config_path",
+                cardano_node_config_path.to_string_lossy().to_string(),
+            )
+            .with_context(|| "configuration error: could not set `cardano_node_config_path`")?
+            .add_source(
+                CardanoNodeConfiguration::from_file(cardano_node_config_path)
+                    .with_context(|| "configuration error: invalid Cardano node configuration")?,
+            );
+    }
+    let config: Configuration = config_builder
         .add_source(
             config::File::with_name(&format!(
                 "{}/{}.json",
@@ -153,11 +385,28 @@ async fn main() -> StdResult<()> {
             ))
             .required(false),
         )
-        .add_source(config::Environment::default())
+        .add_source(config::Environment::default().source(Some(environment.to_map())))
         .build()
         .with_context(|| "configuration build error")?
         .try_deserialize()
         .with_context(|| "configuration deserialize error")?;
+    log_levels.set_component_levels(&config.list_component_log_levels()?);
+
+    match &args.command {
+        Some(SignerCommands::Backup(cmd)) => return cmd.execute(&config).await,
+        Some(SignerCommands::Restore(cmd)) => return cmd.execute(&config).await,
+        Some(SignerCommands::Unregister(cmd)) => return cmd.execute(&config).await,
+        _ => {}
+    }
+
+    if args.dry_run {
+        let report = DryRunSimulator::new(&config).run().await;
+        println!("{report}");
+        if !report.is_ready() {
+            return Err(anyhow!("The signer is not ready to sign"));
+        }
+        return Ok(());
+    }
 
     let services = ProductionServiceBuilder::new(&config)
         .build()
@@ -167,12 +416,20 @@ async fn main() -> StdResult<()> {
     let metrics_service = services.metrics_service.clone();
 
     debug!("Started"; "run_mode" => &args.run_mode, "config" => format!("{config:?}"));
-    let state_machine = StateMachine::new(
+    let transitions_recorder = Arc::new(TransitionsRecorder::default());
+    let mut state_machine = StateMachine::new(
         SignerState::Init,
         Box::new(SignerRunner::new(config.clone(), services)),
         Duration::from_millis(config.run_interval),
         metrics_service.clone(),
-    );
+    )
+    .with_transition_listener(Arc::new(TransitionsLogger))
+    .with_transition_listener(transitions_recorder.clone())
+    .with_tickers(SignedEntityTypeTickers::new().with_sources(&config.list_ticker_sources()?));
+    if config.enable_diagnostics_submission {
+        state_machine =
+            state_machine.with_diagnostics_submission(config.diagnostics_failure_threshold);
+    }
 
     let mut join_set = JoinSet::new();
     join_set.spawn(async move {
@@ -191,6 +448,8 @@ async fn main() -> StdResult<()> {
                 config.metrics_server_port,
                 metrics_service,
             )
+            .with_transitions_recorder(transitions_recorder)
+            .with_log_levels_administration(log_levels, config.admin_api_token.clone())
             .start(metrics_server_shutdown_rx)
             .await
             .map_err(|e| anyhow!(e))
@@ -205,6 +464,7 @@ async fn main() -> StdResult<()> {
             .map(|_| Some("Received Ctrl+C".to_string()))
     });
 
+    #[cfg(unix)]
     join_set.spawn(async move {
         let mut sigterm = signal(SignalKind::terminate()).expect("Failed to create SIGTERM signal");
         sigterm
@@ -214,6 +474,7 @@ async fn main() -> StdResult<()> {
             .map(|_| Some("Received SIGTERM".to_string()))
     });
 
+    #[cfg(unix)]
     join_set.spawn(async move {
         let mut sigterm = signal(SignalKind::quit()).expect("Failed to create SIGQUIT signal");
         sigterm
@@ -223,6 +484,25 @@ async fn main() -> StdResult<()> {
             .map(|_| Some("Received SIGQUIT".to_string()))
     });
 
+    let stop_request_notifier = service_notifier.clone();
+    join_set.spawn(async move {
+        stop_request_notifier.wait_for_stop_request().await;
+        Ok(Some("Stop requested by the service manager".to_string()))
+    });
+
+    // The services are initialized and the state machine is running: the signer is ready, this is
+    // reported to the service manager that runs it, if any.
+    if let Err(error) = service_notifier.notify_ready() {
+        warn!("Failed to notify the service manager that the signer is ready"; "error" => ?error);
+    }
+    let watchdog_notifier = service_notifier.clone();
+    join_set.spawn(async move {
+        run_watchdog(watchdog_notifier)
+            .await
+            .with_context(|| "Service manager watchdog error")
+            .map(|_| None)
+    });
+
     let shutdown_reason = match join_set.join_next().await {
         Some(Err(e)) => {
             crit!("A critical error occurred: {e:?}");
@@ -232,6 +512,10 @@ async fn main() -> StdResult<()> {
         None => None,
     };
 
+    if let Err(error) = service_notifier.notify_stopping() {
+        warn!("Failed to notify the service manager that the signer is stopping"; "error" => ?error);
+    }
+
     metrics_server_shutdown_tx
         .send(())
         .map_err(|e| anyhow!("Metrics server shutdown signal could not be sent: {e:?}"))?;
@@ -13,6 +13,9 @@ impl FromMessageAdapter<EpochSettingsMessage, EpochSettings> for FromEpochSettin
             epoch: message.epoch,
             protocol_parameters: message.protocol_parameters,
             next_protocol_parameters: message.next_protocol_parameters,
+            protocol_feature_activations: message.protocol_feature_activations,
+            registration_nonce: message.registration_nonce,
+            upcoming_signers: message.upcoming_signers,
         }
     }
 }
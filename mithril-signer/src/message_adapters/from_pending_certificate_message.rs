@@ -73,6 +73,8 @@ impl TryFromMessageAdapter<CertificatePendingMessage, CertificatePending>
                     message.next_signers
                 )
             })?,
+            batched_signed_entity_types: message.batched_signed_entity_types,
+            initiated_at: message.initiated_at,
         };
 
         Ok(certificate)
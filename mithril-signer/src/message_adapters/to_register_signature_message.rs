@@ -18,6 +18,14 @@ impl TryToMessageAdapter<(SignedEntityType, SingleSignatures), RegisterSignature
                 "'ToRegisterSignatureMessageAdapter' can not convert the single signature"
             })?,
             won_indexes: single_signature.won_indexes,
+            era: single_signature.era,
+            nonce_signature: single_signature
+                .nonce_signature
+                .map(|nonce_signature| nonce_signature.to_json_hex())
+                .transpose()
+                .with_context(|| {
+                    "'ToRegisterSignatureMessageAdapter' can not convert the nonce signature"
+                })?,
         };
 
         Ok(message)
@@ -1,7 +1,11 @@
 use anyhow::Context;
 use mithril_common::{
     entities::{Epoch, Signer},
-    messages::{RegisterSignerMessage, TryToMessageAdapter},
+    era::SupportedEra,
+    messages::{
+        NextEraRegistrationMessagePart, RegisterSignerMessage, SignerNodeMetadataMessagePart,
+        TryToMessageAdapter,
+    },
     StdResult,
 };
 
@@ -38,12 +42,42 @@ impl TryToMessageAdapter<(Epoch, Signer), RegisterSignerMessage>
                 None => None,
             },
             kes_period: signer.kes_period,
+            node_metadata: Some(SignerNodeMetadataMessagePart {
+                node_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+                supported_eras: SupportedEra::eras()
+                    .into_iter()
+                    .map(|era| era.to_string())
+                    .collect(),
+            }),
+            next_era_registration: None,
         };
 
         Ok(message)
     }
 }
 
+impl TryToMessageAdapter<(Epoch, Signer, SupportedEra, Signer), RegisterSignerMessage>
+    for ToRegisterSignerMessageAdapter
+{
+    /// Method to trigger the conversion, the verification key created by the signer with the
+    /// signature scheme of the next era is sent along with the one of the current era.
+    fn try_adapt(
+        (epoch, signer, next_era, next_era_signer): (Epoch, Signer, SupportedEra, Signer),
+    ) -> StdResult<RegisterSignerMessage> {
+        let message = Self::try_adapt((epoch, signer))?;
+        let next_era_message = Self::try_adapt((epoch, next_era_signer))?;
+
+        Ok(RegisterSignerMessage {
+            next_era_registration: Some(NextEraRegistrationMessagePart {
+                era: next_era.to_string(),
+                verification_key: next_era_message.verification_key,
+                verification_key_signature: next_era_message.verification_key_signature,
+            }),
+            ..message
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mithril_common::test_utils::fake_data;
@@ -59,4 +93,48 @@ mod tests {
 
         assert_eq!("0".to_string(), message.party_id);
     }
+
+    #[test]
+    fn adapt_send_signer_node_metadata() {
+        let message =
+            ToRegisterSignerMessageAdapter::try_adapt((Epoch(1), fake_data::signers(1)[0].clone()))
+                .unwrap();
+
+        let node_metadata = message
+            .node_metadata
+            .expect("The signer node metadata should be sent");
+        assert_eq!(
+            Some(env!("CARGO_PKG_VERSION").to_string()),
+            node_metadata.node_version
+        );
+        assert!(!node_metadata.supported_eras.is_empty());
+    }
+
+    #[test]
+    fn adapt_with_the_registration_of_the_next_era() {
+        let signers = fake_data::signers(2);
+        let message = ToRegisterSignerMessageAdapter::try_adapt((
+            Epoch(1),
+            signers[0].clone(),
+            SupportedEra::dummy(),
+            signers[1].clone(),
+        ))
+        .unwrap();
+        let current_era_message =
+            ToRegisterSignerMessageAdapter::try_adapt((Epoch(1), signers[0].clone())).unwrap();
+        let next_era_message =
+            ToRegisterSignerMessageAdapter::try_adapt((Epoch(1), signers[1].clone())).unwrap();
+
+        assert_eq!(
+            RegisterSignerMessage {
+                next_era_registration: Some(NextEraRegistrationMessagePart {
+                    era: SupportedEra::dummy().to_string(),
+                    verification_key: next_era_message.verification_key,
+                    verification_key_signature: next_era_message.verification_key_signature,
+                }),
+                ..current_era_message
+            },
+            message
+        );
+    }
 }
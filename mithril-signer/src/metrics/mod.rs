@@ -49,6 +49,24 @@ pub const SIGNATURE_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_NAME: &str =
 pub const SIGNATURE_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_HELP: &str =
     "Latest epoch at which signature successfully registered on a Mithril signature node";
 
+/// 'signature_computed_since_startup' metric name
+pub const SIGNATURE_COMPUTED_SINCE_STARTUP_METRIC_NAME: &str =
+    "mithril_signer_signature_computed_since_startup";
+/// 'signature_computed_since_startup' metric help
+pub const SIGNATURE_COMPUTED_SINCE_STARTUP_METRIC_HELP: &str =
+    "Number of single signatures computed since startup on a Mithril signer node";
+
+/// 'digest_computation_duration_seconds' metric name
+pub const DIGEST_COMPUTATION_DURATION_METRIC_NAME: &str =
+    "mithril_signer_digest_computation_duration_seconds";
+/// 'digest_computation_duration_seconds' metric help
+pub const DIGEST_COMPUTATION_DURATION_METRIC_HELP: &str =
+    "Duration (in seconds) of the computations of the digest of a signed entity on a Mithril signer node";
+/// 'digest_computation_duration_seconds' metric buckets (in seconds)
+pub const DIGEST_COMPUTATION_DURATION_METRIC_BUCKETS: [f64; 12] = [
+    0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1200.0, 1800.0,
+];
+
 /// 'runtime_cycle_success_since_startup' metric name
 pub const RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_NAME: &str =
     "mithril_signer_runtime_cycle_success_since_startup";
@@ -62,3 +80,34 @@ pub const RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME: &str =
 /// 'runtime_cycle_total_since_startup' metric help
 pub const RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_HELP: &str =
     "Number of runtime cycles since startup on a Mithril signer node";
+
+/// 'runtime_state_init' metric name
+pub const RUNTIME_STATE_INIT_METRIC_NAME: &str = "mithril_signer_runtime_state_init";
+/// 'runtime_state_init' metric help
+pub const RUNTIME_STATE_INIT_METRIC_HELP: &str =
+    "Set to 1 if the runtime of a Mithril signer node is in the 'Init' state";
+
+/// 'runtime_state_unregistered' metric name
+pub const RUNTIME_STATE_UNREGISTERED_METRIC_NAME: &str =
+    "mithril_signer_runtime_state_unregistered";
+/// 'runtime_state_unregistered' metric help
+pub const RUNTIME_STATE_UNREGISTERED_METRIC_HELP: &str =
+    "Set to 1 if the runtime of a Mithril signer node is in the 'Unregistered' state";
+
+/// 'runtime_state_registered' metric name
+pub const RUNTIME_STATE_REGISTERED_METRIC_NAME: &str = "mithril_signer_runtime_state_registered";
+/// 'runtime_state_registered' metric help
+pub const RUNTIME_STATE_REGISTERED_METRIC_HELP: &str =
+    "Set to 1 if the runtime of a Mithril signer node is in the 'Registered' state";
+
+/// 'runtime_state_signed' metric name
+pub const RUNTIME_STATE_SIGNED_METRIC_NAME: &str = "mithril_signer_runtime_state_signed";
+/// 'runtime_state_signed' metric help
+pub const RUNTIME_STATE_SIGNED_METRIC_HELP: &str =
+    "Set to 1 if the runtime of a Mithril signer node is in the 'Signed' state";
+
+/// 'preflight_checks_success' metric name
+pub const PREFLIGHT_CHECKS_SUCCESS_METRIC_NAME: &str = "mithril_signer_preflight_checks_success";
+/// 'preflight_checks_success' metric help
+pub const PREFLIGHT_CHECKS_SUCCESS_METRIC_HELP: &str =
+    "Set to 1 if the latest preflight checks before a digest computation of a Mithril signer node succeeded";
@@ -3,16 +3,21 @@ use std::sync::Arc;
 use axum::{
     body::Body,
     extract::State,
-    http::{Response, StatusCode},
+    http::{header::AUTHORIZATION, HeaderMap, Response, StatusCode},
     response::IntoResponse,
     routing::get,
-    Router,
+    Json, Router,
 };
+use mithril_common::entities::ClientError;
+use mithril_common::logging::{parse_level, ComponentLogLevels};
+use mithril_common::messages::{LogLevelsMessage, LogLevelsUpdateMessage};
 use mithril_common::StdResult;
+use mithril_secrets::SecretString;
+use slog::Level;
 use slog_scope::{error, info, warn};
 use tokio::sync::oneshot::Receiver;
 
-use crate::MetricsService;
+use crate::{MetricsService, TransitionsRecorder};
 
 /// Metrics server errors
 #[derive(Debug)]
@@ -34,11 +39,96 @@ impl IntoResponse for MetricsServerError {
     }
 }
 
-/// The MetricsServer is responsible for exposing the metrics of the signer.
+#[derive(Clone)]
+struct MetricsServerState {
+    metrics_service: Arc<MetricsService>,
+    transitions_recorder: Arc<TransitionsRecorder>,
+    log_levels: Option<ComponentLogLevels>,
+    admin_api_token: Option<SecretString>,
+}
+
+impl MetricsServerState {
+    /// Check the bearer token of an admin request, returns the response to send if it's rejected
+    fn check_authorization(
+        &self,
+        headers: &HeaderMap,
+    ) -> Result<ComponentLogLevels, Response<Body>> {
+        match (&self.admin_api_token, &self.log_levels) {
+            // The admin routes are hidden when they are disabled
+            (None, _) | (_, None) => Err(StatusCode::NOT_FOUND.into_response()),
+            // The token is compared in constant time so it can't be guessed from the response time
+            (Some(token), Some(log_levels))
+                if headers
+                    .get(AUTHORIZATION)
+                    .and_then(|authorization| authorization.to_str().ok())
+                    .and_then(|authorization| authorization.strip_prefix("Bearer "))
+                    .is_some_and(|received_token| token.matches(received_token)) =>
+            {
+                Ok(log_levels.clone())
+            }
+            (Some(_), Some(_)) => {
+                warn!("MetricsServer: unauthorized admin request");
+                Err(StatusCode::UNAUTHORIZED.into_response())
+            }
+        }
+    }
+}
+
+async fn get_log_levels(
+    State(state): State<MetricsServerState>,
+    headers: HeaderMap,
+) -> Response<Body> {
+    match state.check_authorization(&headers) {
+        Ok(log_levels) => Json(LogLevelsMessage::from(&log_levels)).into_response(),
+        Err(rejection) => rejection,
+    }
+}
+
+async fn update_log_levels(
+    State(state): State<MetricsServerState>,
+    headers: HeaderMap,
+    Json(message): Json<LogLevelsUpdateMessage>,
+) -> Response<Body> {
+    let log_levels = match state.check_authorization(&headers) {
+        Ok(log_levels) => log_levels,
+        Err(rejection) => return rejection,
+    };
+
+    // All the levels are parsed before applying any of them, so an invalid update is not
+    // partially applied.
+    let mut levels: Vec<(_, Option<Level>)> = vec![];
+    for (component, level) in message.components {
+        match level.as_deref().map(parse_level).transpose() {
+            Ok(level) => levels.push((component, level)),
+            Err(error) => {
+                warn!("MetricsServer: invalid log levels update"; "error" => ?error);
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ClientError::new(
+                        "invalid_log_level".to_string(),
+                        format!("invalid level for component '{component}': {error}"),
+                    )),
+                )
+                    .into_response();
+            }
+        }
+    }
+    for (component, level) in levels {
+        info!("MetricsServer: log level updated"; "component" => %component, "level" => ?level);
+        log_levels.set_component_level(component, level);
+    }
+
+    Json(LogLevelsMessage::from(&log_levels)).into_response()
+}
+
+/// The MetricsServer is responsible for exposing the metrics and the runtime status of the signer.
 pub struct MetricsServer {
     server_port: u16,
     server_ip: String,
     metrics_service: Arc<MetricsService>,
+    transitions_recorder: Arc<TransitionsRecorder>,
+    log_levels: Option<ComponentLogLevels>,
+    admin_api_token: Option<SecretString>,
 }
 
 impl MetricsServer {
@@ -48,9 +138,33 @@ impl MetricsServer {
             server_port,
             server_ip: server_ip.to_string(),
             metrics_service,
+            transitions_recorder: Arc::new(TransitionsRecorder::default()),
+            log_levels: None,
+            admin_api_token: None,
         }
     }
 
+    /// Expose the runtime status built from the transitions of the given recorder.
+    pub fn with_transitions_recorder(
+        mut self,
+        transitions_recorder: Arc<TransitionsRecorder>,
+    ) -> Self {
+        self.transitions_recorder = transitions_recorder;
+        self
+    }
+
+    /// Serve the `/admin/log-levels` routes to read and update the given log levels, they
+    /// require the given token as a bearer `Authorization` header and are disabled without it.
+    pub fn with_log_levels_administration(
+        mut self,
+        log_levels: ComponentLogLevels,
+        admin_api_token: Option<SecretString>,
+    ) -> Self {
+        self.log_levels = Some(log_levels);
+        self.admin_api_token = admin_api_token;
+        self
+    }
+
     /// Metrics server endpoint.
     pub fn endpoint(&self) -> String {
         format!("http://{}:{}", self.server_ip, self.server_port)
@@ -65,11 +179,29 @@ impl MetricsServer {
         let app = Router::new()
             .route(
                 "/metrics",
-                get(|State(state): State<Arc<MetricsService>>| async move {
-                    state.export_metrics().map_err(MetricsServerError::Internal)
+                get(|State(state): State<MetricsServerState>| async move {
+                    state
+                        .metrics_service
+                        .export_metrics()
+                        .map_err(MetricsServerError::Internal)
                 }),
             )
-            .with_state(self.metrics_service.clone());
+            .route(
+                "/status",
+                get(|State(state): State<MetricsServerState>| async move {
+                    Json(state.transitions_recorder.status())
+                }),
+            )
+            .route(
+                "/admin/log-levels",
+                get(get_log_levels).put(update_log_levels),
+            )
+            .with_state(MetricsServerState {
+                metrics_service: self.metrics_service.clone(),
+                transitions_recorder: self.transitions_recorder.clone(),
+                log_levels: self.log_levels.clone(),
+                admin_api_token: self.admin_api_token.clone(),
+            });
         let listener =
             tokio::net::TcpListener::bind(format!("{}:{}", self.server_ip, self.server_port))
                 .await?;
@@ -87,10 +219,14 @@ impl MetricsServer {
 #[cfg(test)]
 mod tests {
     use anyhow::anyhow;
+    use mithril_common::entities::Epoch;
+    use mithril_common::logging::LogComponent;
     use reqwest::StatusCode;
     use std::time::Duration;
     use tokio::{sync::oneshot, task::yield_now, time::sleep};
 
+    use crate::{SignerState, StateMachineTransitionListener, StateTransition};
+
     use super::*;
 
     #[tokio::test]
@@ -121,4 +257,163 @@ mod tests {
 
         shutdown_tx.send(()).unwrap();
     }
+
+    #[tokio::test]
+    async fn test_status_route() {
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let transitions_recorder = Arc::new(TransitionsRecorder::default());
+        transitions_recorder.on_transition(&StateTransition::new(
+            &SignerState::Init,
+            &SignerState::Unregistered { epoch: Epoch(4) },
+        ));
+        let metrics_server = Arc::new(
+            MetricsServer::new("0.0.0.0", 9091, metrics_service.clone())
+                .with_transitions_recorder(transitions_recorder),
+        );
+        let metrics_server_endpoint = metrics_server.endpoint();
+
+        let status_test = tokio::spawn(async move {
+            // Yield to make sure the web server starts first.
+            yield_now().await;
+
+            let response = reqwest::get(format!("{metrics_server_endpoint}/status"))
+                .await
+                .unwrap();
+
+            assert_eq!(StatusCode::OK, response.status());
+            let status: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(
+                SignerState::Unregistered { epoch: Epoch(4) }.to_string(),
+                status["current_state"]
+            );
+            assert_eq!("signer started", status["last_transitions"][0]["reason"]);
+        });
+
+        tokio::select!(
+            res =  metrics_server.start(shutdown_rx)  => Err(anyhow!("Metrics server exited with value '{res:?}'")),
+            _res = sleep(Duration::from_secs(1)) => Err(anyhow!("Timeout: The test should have already completed.")),
+            res = status_test => res.map_err(|e| e.into()),
+        )
+        .unwrap();
+
+        shutdown_tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_log_levels_routes() {
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let log_levels = ComponentLogLevels::new(Level::Info);
+        let metrics_server = Arc::new(
+            MetricsServer::new("0.0.0.0", 9092, metrics_service.clone())
+                .with_log_levels_administration(
+                    log_levels.clone(),
+                    Some(SecretString::new("admin-secret")),
+                ),
+        );
+        let metrics_server_endpoint = metrics_server.endpoint();
+
+        let log_levels_test = tokio::spawn(async move {
+            // Yield to make sure the web server starts first.
+            yield_now().await;
+            let client = reqwest::Client::new();
+            let url = format!("{metrics_server_endpoint}/admin/log-levels");
+
+            let response = client.get(&url).send().await.unwrap();
+            assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+            let response = client
+                .put(&url)
+                .bearer_auth("not-the-token")
+                .json(&LogLevelsUpdateMessage {
+                    components: [(LogComponent::Runtime, Some("debug".to_string()))].into(),
+                })
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+            assert_eq!(Level::Info, log_levels.level(LogComponent::Runtime));
+
+            let response = client
+                .put(&url)
+                .bearer_auth("admin-secret")
+                .json(&LogLevelsUpdateMessage {
+                    components: [
+                        (LogComponent::Runtime, Some("debug".to_string())),
+                        (LogComponent::Http, Some("verbose".to_string())),
+                    ]
+                    .into(),
+                })
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(StatusCode::BAD_REQUEST, response.status());
+            assert!(log_levels.component_levels().is_empty());
+
+            let response = client
+                .put(&url)
+                .bearer_auth("admin-secret")
+                .json(&LogLevelsUpdateMessage {
+                    components: [(LogComponent::Runtime, Some("debug".to_string()))].into(),
+                })
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(StatusCode::OK, response.status());
+            assert_eq!(Level::Debug, log_levels.level(LogComponent::Runtime));
+
+            let response = client
+                .get(&url)
+                .bearer_auth("admin-secret")
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(StatusCode::OK, response.status());
+            assert_eq!(
+                LogLevelsMessage::from(&log_levels),
+                response.json::<LogLevelsMessage>().await.unwrap()
+            );
+        });
+
+        tokio::select!(
+            res =  metrics_server.start(shutdown_rx)  => Err(anyhow!("Metrics server exited with value '{res:?}'")),
+            _res = sleep(Duration::from_secs(1)) => Err(anyhow!("Timeout: The test should have already completed.")),
+            res = log_levels_test => res.map_err(|e| e.into()),
+        )
+        .unwrap();
+
+        shutdown_tx.send(()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_log_levels_routes_are_disabled_without_admin_token() {
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let metrics_service = Arc::new(MetricsService::new().unwrap());
+        let metrics_server = Arc::new(
+            MetricsServer::new("0.0.0.0", 9093, metrics_service.clone())
+                .with_log_levels_administration(ComponentLogLevels::new(Level::Info), None),
+        );
+        let metrics_server_endpoint = metrics_server.endpoint();
+
+        let log_levels_test = tokio::spawn(async move {
+            // Yield to make sure the web server starts first.
+            yield_now().await;
+
+            let response = reqwest::get(format!("{metrics_server_endpoint}/admin/log-levels"))
+                .await
+                .unwrap();
+
+            assert_eq!(StatusCode::NOT_FOUND, response.status());
+        });
+
+        tokio::select!(
+            res =  metrics_server.start(shutdown_rx)  => Err(anyhow!("Metrics server exited with value '{res:?}'")),
+            _res = sleep(Duration::from_secs(1)) => Err(anyhow!("Timeout: The test should have already completed.")),
+            res = log_levels_test => res.map_err(|e| e.into()),
+        )
+        .unwrap();
+
+        shutdown_tx.send(()).unwrap();
+    }
 }
@@ -1,11 +1,21 @@
 use mithril_common::{entities::Epoch, StdResult};
-use prometheus::{Counter, Encoder, Gauge, Opts, Registry, TextEncoder};
+use prometheus::{Counter, Encoder, Gauge, Histogram, HistogramOpts, Opts, Registry, TextEncoder};
 use slog_scope::debug;
+use std::time::Duration;
+
+use crate::SignerState;
 
 use super::{
-    RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_HELP,
+    DIGEST_COMPUTATION_DURATION_METRIC_BUCKETS, DIGEST_COMPUTATION_DURATION_METRIC_HELP,
+    DIGEST_COMPUTATION_DURATION_METRIC_NAME, PREFLIGHT_CHECKS_SUCCESS_METRIC_HELP,
+    PREFLIGHT_CHECKS_SUCCESS_METRIC_NAME, RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_HELP,
     RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_NAME, RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_HELP,
-    RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME,
+    RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME, RUNTIME_STATE_INIT_METRIC_HELP,
+    RUNTIME_STATE_INIT_METRIC_NAME, RUNTIME_STATE_REGISTERED_METRIC_HELP,
+    RUNTIME_STATE_REGISTERED_METRIC_NAME, RUNTIME_STATE_SIGNED_METRIC_HELP,
+    RUNTIME_STATE_SIGNED_METRIC_NAME, RUNTIME_STATE_UNREGISTERED_METRIC_HELP,
+    RUNTIME_STATE_UNREGISTERED_METRIC_NAME, SIGNATURE_COMPUTED_SINCE_STARTUP_METRIC_HELP,
+    SIGNATURE_COMPUTED_SINCE_STARTUP_METRIC_NAME,
     SIGNATURE_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_HELP,
     SIGNATURE_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_NAME,
     SIGNATURE_REGISTRATION_SUCCESS_SINCE_STARTUP_METRIC_HELP,
@@ -35,8 +45,15 @@ pub struct MetricsService {
     signature_registration_success_since_startup_counter: Box<Counter>,
     signature_registration_total_since_startup_counter: Box<Counter>,
     signature_registration_success_last_epoch_gauge: Box<Gauge>,
+    signature_computed_since_startup_counter: Box<Counter>,
+    digest_computation_duration_histogram: Box<Histogram>,
     runtime_cycle_success_since_startup_counter: Box<Counter>,
     runtime_cycle_total_since_startup_counter: Box<Counter>,
+    runtime_state_init_gauge: Box<Gauge>,
+    runtime_state_unregistered_gauge: Box<Gauge>,
+    runtime_state_registered_gauge: Box<Gauge>,
+    runtime_state_signed_gauge: Box<Gauge>,
+    preflight_checks_success_gauge: Box<Gauge>,
 }
 
 impl MetricsService {
@@ -86,6 +103,20 @@ impl MetricsService {
         )?);
         registry.register(signature_registration_success_last_epoch_gauge.clone())?;
 
+        // Signature computation metrics
+        let signature_computed_since_startup_counter = Box::new(Self::create_metric_counter(
+            SIGNATURE_COMPUTED_SINCE_STARTUP_METRIC_NAME,
+            SIGNATURE_COMPUTED_SINCE_STARTUP_METRIC_HELP,
+        )?);
+        registry.register(signature_computed_since_startup_counter.clone())?;
+
+        let digest_computation_duration_histogram = Box::new(Self::create_metric_histogram(
+            DIGEST_COMPUTATION_DURATION_METRIC_NAME,
+            DIGEST_COMPUTATION_DURATION_METRIC_HELP,
+            DIGEST_COMPUTATION_DURATION_METRIC_BUCKETS.to_vec(),
+        )?);
+        registry.register(digest_computation_duration_histogram.clone())?;
+
         // Runtime cycle metrics
         let runtime_cycle_success_since_startup_counter = Box::new(Self::create_metric_counter(
             RUNTIME_CYCLE_SUCCESS_SINCE_STARTUP_METRIC_NAME,
@@ -99,6 +130,38 @@ impl MetricsService {
         )?);
         registry.register(runtime_cycle_total_since_startup_counter.clone())?;
 
+        // Runtime state metrics
+        let runtime_state_init_gauge = Box::new(Self::create_metric_gauge(
+            RUNTIME_STATE_INIT_METRIC_NAME,
+            RUNTIME_STATE_INIT_METRIC_HELP,
+        )?);
+        registry.register(runtime_state_init_gauge.clone())?;
+
+        let runtime_state_unregistered_gauge = Box::new(Self::create_metric_gauge(
+            RUNTIME_STATE_UNREGISTERED_METRIC_NAME,
+            RUNTIME_STATE_UNREGISTERED_METRIC_HELP,
+        )?);
+        registry.register(runtime_state_unregistered_gauge.clone())?;
+
+        let runtime_state_registered_gauge = Box::new(Self::create_metric_gauge(
+            RUNTIME_STATE_REGISTERED_METRIC_NAME,
+            RUNTIME_STATE_REGISTERED_METRIC_HELP,
+        )?);
+        registry.register(runtime_state_registered_gauge.clone())?;
+
+        let runtime_state_signed_gauge = Box::new(Self::create_metric_gauge(
+            RUNTIME_STATE_SIGNED_METRIC_NAME,
+            RUNTIME_STATE_SIGNED_METRIC_HELP,
+        )?);
+        registry.register(runtime_state_signed_gauge.clone())?;
+
+        // Preflight checks metrics
+        let preflight_checks_success_gauge = Box::new(Self::create_metric_gauge(
+            PREFLIGHT_CHECKS_SUCCESS_METRIC_NAME,
+            PREFLIGHT_CHECKS_SUCCESS_METRIC_HELP,
+        )?);
+        registry.register(preflight_checks_success_gauge.clone())?;
+
         Ok(Self {
             registry,
             signer_registration_success_since_startup_counter,
@@ -107,8 +170,15 @@ impl MetricsService {
             signature_registration_success_since_startup_counter,
             signature_registration_total_since_startup_counter,
             signature_registration_success_last_epoch_gauge,
+            signature_computed_since_startup_counter,
+            digest_computation_duration_histogram,
             runtime_cycle_success_since_startup_counter,
             runtime_cycle_total_since_startup_counter,
+            runtime_state_init_gauge,
+            runtime_state_unregistered_gauge,
+            runtime_state_registered_gauge,
+            runtime_state_signed_gauge,
+            preflight_checks_success_gauge,
         })
     }
 
@@ -126,6 +196,17 @@ impl MetricsService {
         Ok(gauge)
     }
 
+    fn create_metric_histogram(
+        name: &MetricName,
+        help: &str,
+        buckets: Vec<f64>,
+    ) -> StdResult<Histogram> {
+        let histogram_opts = HistogramOpts::new(name, help).buckets(buckets);
+        let histogram = Histogram::with_opts(histogram_opts)?;
+
+        Ok(histogram)
+    }
+
     /// Export the metrics as a string with the Open Metrics standard format.
     /// These metrics can be exposed on a HTTP server.
     pub fn export_metrics(&self) -> StdResult<String> {
@@ -225,6 +306,38 @@ impl MetricsService {
         )
     }
 
+    /// Increment the `signature_computed_since_startup` counter.
+    pub fn signature_computed_since_startup_counter_increment(&self) {
+        debug!("MetricsService: incrementing 'signature_computed_since_startup' counter");
+        self.signature_computed_since_startup_counter.inc();
+    }
+
+    /// Get the `signature_computed_since_startup` counter.
+    pub fn signature_computed_since_startup_counter_get(&self) -> CounterValue {
+        self.signature_computed_since_startup_counter.get().round() as CounterValue
+    }
+
+    /// Record a duration in the `digest_computation_duration` histogram.
+    pub fn digest_computation_duration_histogram_observe(&self, duration: Duration) {
+        debug!(
+            "MetricsService: observe {}s in 'digest_computation_duration' histogram",
+            duration.as_secs_f64()
+        );
+        self.digest_computation_duration_histogram
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Get the number of durations recorded in the `digest_computation_duration` histogram.
+    pub fn digest_computation_duration_histogram_get_count(&self) -> CounterValue {
+        self.digest_computation_duration_histogram
+            .get_sample_count() as CounterValue
+    }
+
+    /// Get the sum of the durations recorded in the `digest_computation_duration` histogram.
+    pub fn digest_computation_duration_histogram_get_sum(&self) -> Duration {
+        Duration::from_secs_f64(self.digest_computation_duration_histogram.get_sample_sum())
+    }
+
     /// Increment the `runtime_cycle_total_since_startup` counter.
     pub fn runtime_cycle_total_since_startup_counter_increment(&self) {
         debug!("MetricsService: incrementing 'runtime_cycle_total_since_startup' counter");
@@ -248,6 +361,46 @@ impl MetricsService {
             .get()
             .round() as CounterValue
     }
+
+    fn runtime_state_gauge(&self, state: &SignerState) -> &Gauge {
+        match state {
+            SignerState::Init => &self.runtime_state_init_gauge,
+            SignerState::Unregistered { .. } => &self.runtime_state_unregistered_gauge,
+            SignerState::Registered { .. } => &self.runtime_state_registered_gauge,
+            SignerState::Signed { .. } => &self.runtime_state_signed_gauge,
+        }
+    }
+
+    /// Set the `runtime_state` gauge of the given state to 1 and the other ones to 0.
+    pub fn runtime_state_gauges_set(&self, state: &SignerState) {
+        debug!("MetricsService: set 'runtime_state' gauges for state '{state}'");
+        for gauge in [
+            &self.runtime_state_init_gauge,
+            &self.runtime_state_unregistered_gauge,
+            &self.runtime_state_registered_gauge,
+            &self.runtime_state_signed_gauge,
+        ] {
+            gauge.set(0.0);
+        }
+        self.runtime_state_gauge(state).set(1.0);
+    }
+
+    /// Check if the `runtime_state` gauge of the given state is set.
+    pub fn runtime_state_gauge_is_set(&self, state: &SignerState) -> bool {
+        self.runtime_state_gauge(state).get() > 0.0
+    }
+
+    /// Set the `preflight_checks_success` gauge to 1 if the preflight checks succeeded, 0 otherwise.
+    pub fn preflight_checks_success_gauge_set(&self, success: bool) {
+        debug!("MetricsService: set 'preflight_checks_success' gauge value to {success}");
+        self.preflight_checks_success_gauge
+            .set(if success { 1.0 } else { 0.0 });
+    }
+
+    /// Check if the `preflight_checks_success` gauge is set.
+    pub fn preflight_checks_success_gauge_is_set(&self) -> bool {
+        self.preflight_checks_success_gauge.get() > 0.0
+    }
 }
 
 #[cfg(test)]
@@ -272,7 +425,9 @@ mod tests {
         let metrics_service = MetricsService::new().unwrap();
         let exported_metrics = metrics_service.export_metrics().unwrap();
 
-        let parsed_metrics = parse_metrics(&exported_metrics).unwrap();
+        let mut parsed_metrics = parse_metrics(&exported_metrics).unwrap();
+        // The samples of a histogram are checked in the histogram tests
+        parsed_metrics.retain(|name, _| !name.starts_with(DIGEST_COMPUTATION_DURATION_METRIC_NAME));
 
         let parsed_metrics_expected = BTreeMap::from([
             (
@@ -283,6 +438,30 @@ mod tests {
                 RUNTIME_CYCLE_TOTAL_SINCE_STARTUP_METRIC_NAME.to_string(),
                 Value::Counter(0.0),
             ),
+            (
+                PREFLIGHT_CHECKS_SUCCESS_METRIC_NAME.to_string(),
+                Value::Gauge(0.0),
+            ),
+            (
+                RUNTIME_STATE_INIT_METRIC_NAME.to_string(),
+                Value::Gauge(0.0),
+            ),
+            (
+                RUNTIME_STATE_REGISTERED_METRIC_NAME.to_string(),
+                Value::Gauge(0.0),
+            ),
+            (
+                RUNTIME_STATE_SIGNED_METRIC_NAME.to_string(),
+                Value::Gauge(0.0),
+            ),
+            (
+                RUNTIME_STATE_UNREGISTERED_METRIC_NAME.to_string(),
+                Value::Gauge(0.0),
+            ),
+            (
+                SIGNATURE_COMPUTED_SINCE_STARTUP_METRIC_NAME.to_string(),
+                Value::Counter(0.0),
+            ),
             (
                 SIGNATURE_REGISTRATION_SUCCESS_LAST_EPOCH_METRIC_NAME.to_string(),
                 Value::Gauge(0.0),
@@ -401,6 +580,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_signature_computed_since_startup_counter_increment() {
+        let metrics_service = MetricsService::new().unwrap();
+        assert_eq!(
+            0,
+            metrics_service.signature_computed_since_startup_counter_get(),
+        );
+
+        metrics_service.signature_computed_since_startup_counter_increment();
+        assert_eq!(
+            1,
+            metrics_service.signature_computed_since_startup_counter_get(),
+        );
+    }
+
+    #[test]
+    fn test_digest_computation_duration_histogram_observe() {
+        let metrics_service = MetricsService::new().unwrap();
+        assert_eq!(
+            0,
+            metrics_service.digest_computation_duration_histogram_get_count(),
+        );
+
+        metrics_service.digest_computation_duration_histogram_observe(Duration::from_millis(1500));
+        metrics_service.digest_computation_duration_histogram_observe(Duration::from_millis(500));
+        assert_eq!(
+            2,
+            metrics_service.digest_computation_duration_histogram_get_count(),
+        );
+        assert_eq!(
+            Duration::from_secs(2),
+            metrics_service.digest_computation_duration_histogram_get_sum(),
+        );
+    }
+
+    #[test]
+    fn test_digest_computation_duration_histogram_export() {
+        let metrics_service = MetricsService::new().unwrap();
+        metrics_service.digest_computation_duration_histogram_observe(Duration::from_secs(3));
+
+        let exported_metrics = metrics_service.export_metrics().unwrap();
+
+        assert!(exported_metrics.contains(&format!(
+            "# TYPE {DIGEST_COMPUTATION_DURATION_METRIC_NAME} histogram"
+        )));
+        assert!(exported_metrics.contains(&format!(
+            "{DIGEST_COMPUTATION_DURATION_METRIC_NAME}_bucket{{le=\"5\"}} 1"
+        )));
+        assert!(exported_metrics.contains(&format!(
+            "{DIGEST_COMPUTATION_DURATION_METRIC_NAME}_count 1"
+        )));
+    }
+
     #[test]
     fn test_runtime_cycle_success_since_startup_counter_increment() {
         let metrics_service = MetricsService::new().unwrap();
@@ -430,4 +662,16 @@ mod tests {
             metrics_service.runtime_cycle_total_since_startup_counter_get(),
         );
     }
+
+    #[test]
+    fn test_runtime_state_gauges_set() {
+        let metrics_service = MetricsService::new().unwrap();
+        let registered_state = SignerState::Registered { epoch: Epoch(12) };
+        assert!(!metrics_service.runtime_state_gauge_is_set(&registered_state));
+
+        metrics_service.runtime_state_gauges_set(&SignerState::Init);
+        metrics_service.runtime_state_gauges_set(&registered_state);
+        assert!(metrics_service.runtime_state_gauge_is_set(&registered_state));
+        assert!(!metrics_service.runtime_state_gauge_is_set(&SignerState::Init));
+    }
 }
@@ -0,0 +1,277 @@
+//! Export and restore of the protocol initializers of the signer.
+//!
+//! The protocol initializers hold the Mithril signing keys of the signer for the upcoming
+//! epochs: losing them prevents the signer to sign until it registers again. They are exported
+//! to a backup file encrypted with a passphrase, and are checked against the operational
+//! certificate of the signer when they are restored.
+
+use anyhow::{anyhow, Context};
+use argon2::Argon2;
+use rand_core::RngCore;
+use serde::{Deserialize, Serialize};
+
+use mithril_common::crypto_helper::{OpCert, ProtocolInitializer};
+use mithril_common::entities::Epoch;
+use mithril_common::StdResult;
+use mithril_persistence::store::{StoreCipher, STORE_CIPHER_KEY_SIZE};
+
+use crate::ProtocolInitializerStorer;
+
+/// Version of the format of the encrypted backups
+const BACKUP_FORMAT_VERSION: u8 = 1;
+
+/// Size in bytes of the salt of the key derivation
+const SALT_SIZE: usize = 16;
+
+/// Protocol initializers of a signer, per epoch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtocolInitializerBackup {
+    /// Protocol initializers, sorted by epoch
+    pub protocol_initializers: Vec<(Epoch, ProtocolInitializer)>,
+}
+
+impl ProtocolInitializerBackup {
+    /// Export all the protocol initializers of the given store.
+    pub async fn export(store: &dyn ProtocolInitializerStorer) -> StdResult<Self> {
+        let mut protocol_initializers = store
+            .get_last_protocol_initializer(usize::MAX)
+            .await
+            .with_context(|| "Could not read the protocol initializers to export")?;
+        protocol_initializers.sort_by_key(|(epoch, _)| *epoch);
+
+        Ok(Self {
+            protocol_initializers,
+        })
+    }
+
+    /// Check that the verification keys of all the protocol initializers were signed with the
+    /// KES key of the given operational certificate.
+    pub fn verify_operational_certificate(
+        &self,
+        operational_certificate: &OpCert,
+    ) -> StdResult<()> {
+        for (epoch, protocol_initializer) in &self.protocol_initializers {
+            protocol_initializer
+                .verify_kes_signature(operational_certificate)
+                .with_context(|| {
+                    format!("The protocol initializer of epoch '{epoch}' does not match the operational certificate")
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Save all the protocol initializers in the given store, replacing the ones already
+    /// stored for the same epochs.
+    ///
+    /// Return the number of restored protocol initializers.
+    pub async fn restore(self, store: &dyn ProtocolInitializerStorer) -> StdResult<usize> {
+        let count = self.protocol_initializers.len();
+        for (epoch, protocol_initializer) in self.protocol_initializers {
+            store
+                .save_protocol_initializer(epoch, protocol_initializer)
+                .await
+                .with_context(|| {
+                    format!("Could not restore the protocol initializer of epoch '{epoch}'")
+                })?;
+        }
+
+        Ok(count)
+    }
+
+    /// Encrypt the backup with the given passphrase.
+    pub fn encrypt(&self, passphrase: &str) -> StdResult<EncryptedProtocolInitializerBackup> {
+        let mut salt = [0u8; SALT_SIZE];
+        rand_core::OsRng.fill_bytes(&mut salt);
+        let cipher = derive_cipher(passphrase, &salt)?;
+        let plaintext = serde_json::to_string(self)
+            .with_context(|| "Could not serialize the protocol initializers backup")?;
+
+        Ok(EncryptedProtocolInitializerBackup {
+            version: BACKUP_FORMAT_VERSION,
+            salt: hex::encode(salt),
+            ciphertext: cipher
+                .encrypt(&plaintext, &backup_associated_data(BACKUP_FORMAT_VERSION))?,
+        })
+    }
+}
+
+/// Protocol initializers backup encrypted with a passphrase.
+///
+/// The encryption key is derived from the passphrase and a random salt with Argon2id, the backup
+/// is encrypted and authenticated with a [StoreCipher] (ChaCha20-Poly1305).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedProtocolInitializerBackup {
+    /// Version of the backup format
+    pub version: u8,
+
+    /// Salt of the key derivation, hex encoded
+    pub salt: String,
+
+    /// Encrypted backup, as encoded by the [StoreCipher]
+    pub ciphertext: String,
+}
+
+impl EncryptedProtocolInitializerBackup {
+    /// Decrypt the backup with the given passphrase.
+    ///
+    /// Fails if the passphrase is invalid or if the backup was tampered.
+    pub fn decrypt(&self, passphrase: &str) -> StdResult<ProtocolInitializerBackup> {
+        if self.version != BACKUP_FORMAT_VERSION {
+            return Err(anyhow!(
+                "Unsupported backup format version: '{}'",
+                self.version
+            ));
+        }
+        if !StoreCipher::is_encrypted(&self.ciphertext) {
+            return Err(anyhow!(
+                "Invalid backup ciphertext: the backup is not encrypted"
+            ));
+        }
+        let salt = hex::decode(&self.salt).with_context(|| "Invalid backup salt")?;
+
+        let plaintext = derive_cipher(passphrase, &salt)?
+            .decrypt(&self.ciphertext, &backup_associated_data(self.version))
+            .with_context(|| {
+                "Could not decrypt the backup: invalid passphrase or corrupted backup"
+            })?;
+
+        serde_json::from_str(&plaintext)
+            .with_context(|| "Could not deserialize the protocol initializers backup")
+    }
+}
+
+/// Associated data binding the encrypted backup to its format version.
+fn backup_associated_data(version: u8) -> String {
+    format!("protocol_initializer_backup:v{version}")
+}
+
+fn derive_cipher(passphrase: &str, salt: &[u8]) -> StdResult<StoreCipher> {
+    let mut key = [0u8; STORE_CIPHER_KEY_SIZE];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Could not derive the backup encryption key: {e}"))?;
+
+    Ok(StoreCipher::from_key(&key))
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::MithrilFixtureBuilder;
+    use mithril_persistence::store::adapter::MemoryAdapter;
+
+    use crate::ProtocolInitializerStore;
+
+    use super::*;
+
+    fn verification_keys(backup: &ProtocolInitializerBackup) -> Vec<(Epoch, String)> {
+        backup
+            .protocol_initializers
+            .iter()
+            .map(|(epoch, initializer)| {
+                (
+                    *epoch,
+                    serde_json::to_string(&initializer.verification_key()).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn backup_with_signer_initializer(signer_index: usize) -> (ProtocolInitializerBackup, OpCert) {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let signer = &fixture.signers_fixture()[signer_index];
+
+        (
+            ProtocolInitializerBackup {
+                protocol_initializers: vec![
+                    (Epoch(4), signer.protocol_initializer.clone()),
+                    (Epoch(5), signer.protocol_initializer.clone()),
+                ],
+            },
+            (*signer.operational_certificate().unwrap()).clone(),
+        )
+    }
+
+    #[tokio::test]
+    async fn export_then_restore_in_another_store() {
+        let (backup, _) = backup_with_signer_initializer(0);
+        let store = ProtocolInitializerStore::new(
+            Box::new(MemoryAdapter::new(Some(backup.protocol_initializers.clone())).unwrap()),
+            None,
+        );
+        let restored_store = ProtocolInitializerStore::new(
+            Box::new(MemoryAdapter::<Epoch, ProtocolInitializer>::new(None).unwrap()),
+            None,
+        );
+
+        let exported = ProtocolInitializerBackup::export(&store).await.unwrap();
+        let count = exported.clone().restore(&restored_store).await.unwrap();
+
+        assert_eq!(2, count);
+        assert_eq!(verification_keys(&backup), verification_keys(&exported));
+        assert_eq!(
+            verification_keys(&backup),
+            verification_keys(
+                &ProtocolInitializerBackup::export(&restored_store)
+                    .await
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn encrypt_then_decrypt_with_the_same_passphrase() {
+        let (backup, _) = backup_with_signer_initializer(0);
+
+        let encrypted = backup.encrypt("passphrase").unwrap();
+        let decrypted = encrypted.decrypt("passphrase").unwrap();
+
+        assert_eq!(verification_keys(&backup), verification_keys(&decrypted));
+    }
+
+    #[test]
+    fn decrypt_fail_with_another_passphrase_or_a_tampered_backup() {
+        let (backup, _) = backup_with_signer_initializer(0);
+        let encrypted = backup.encrypt("passphrase").unwrap();
+
+        encrypted
+            .decrypt("another passphrase")
+            .expect_err("Decrypting with another passphrase should fail");
+
+        let mut tampered_ciphertext = encrypted.ciphertext.clone();
+        let last_char = if tampered_ciphertext.ends_with('0') {
+            "1"
+        } else {
+            "0"
+        };
+        tampered_ciphertext.replace_range(tampered_ciphertext.len() - 1.., last_char);
+        EncryptedProtocolInitializerBackup {
+            ciphertext: tampered_ciphertext,
+            ..encrypted.clone()
+        }
+        .decrypt("passphrase")
+        .expect_err("Decrypting a tampered backup should fail");
+
+        EncryptedProtocolInitializerBackup {
+            ciphertext: serde_json::to_string(&backup).unwrap(),
+            ..encrypted
+        }
+        .decrypt("passphrase")
+        .expect_err("Decrypting a backup that is not encrypted should fail");
+    }
+
+    #[test]
+    fn verify_operational_certificate_of_the_signer() {
+        let (backup, operational_certificate) = backup_with_signer_initializer(0);
+        let (_, other_operational_certificate) = backup_with_signer_initializer(1);
+
+        backup
+            .verify_operational_certificate(&operational_certificate)
+            .expect("The backup should match the operational certificate of the signer");
+        backup
+            .verify_operational_certificate(&other_operational_certificate)
+            .expect_err(
+                "The backup should not match the operational certificate of another signer",
+            );
+    }
+}
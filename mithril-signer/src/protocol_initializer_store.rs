@@ -1,7 +1,9 @@
 use async_trait::async_trait;
 use tokio::sync::RwLock;
 
-use mithril_common::{crypto_helper::ProtocolInitializer, entities::Epoch, StdResult};
+use mithril_common::{
+    crypto_helper::ProtocolInitializer, entities::Epoch, era::EraChecker, StdResult,
+};
 use mithril_persistence::store::{adapter::StoreAdapter, StorePruner};
 
 type Adapter = Box<dyn StoreAdapter<Key = Epoch, Record = ProtocolInitializer>>;
@@ -97,12 +99,39 @@ impl ProtocolInitializerStorer for ProtocolInitializerStore {
     }
 }
 
+/// Get the protocol initializer of the verification key used to sign at the given epoch.
+///
+/// When the era switches at the given epoch, the key created with the signature scheme of the new
+/// era and registered ahead of the switch is used if any, as the aggregator does, otherwise the
+/// key registered for the epoch is used.
+pub async fn get_signing_protocol_initializer(
+    era_checker: &EraChecker,
+    protocol_initializer_store: &dyn ProtocolInitializerStorer,
+    next_era_protocol_initializer_store: &dyn ProtocolInitializerStorer,
+    signing_epoch: Epoch,
+) -> StdResult<Option<ProtocolInitializer>> {
+    let retrieval_epoch = signing_epoch.offset_to_signer_retrieval_epoch()?;
+    if era_checker.era_of_epoch(signing_epoch) != era_checker.era_of_epoch(retrieval_epoch) {
+        if let Some(protocol_initializer) = next_era_protocol_initializer_store
+            .get_protocol_initializer(retrieval_epoch)
+            .await?
+        {
+            return Ok(Some(protocol_initializer));
+        }
+    }
+
+    protocol_initializer_store
+        .get_protocol_initializer(retrieval_epoch)
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
 
     use super::*;
 
+    use mithril_common::era::SupportedEra;
     use mithril_common::test_utils::fake_data;
     use mithril_persistence::store::adapter::MemoryAdapter;
 
@@ -213,4 +242,74 @@ mod tests {
             .unwrap()
             .is_none());
     }
+
+    fn init_next_era_store(epoch: Epoch, stake_epoch: u64) -> ProtocolInitializerStore {
+        let (_, protocol_initializer) = setup_protocol_initializers(stake_epoch).pop().unwrap();
+        let adapter: MemoryAdapter<Epoch, ProtocolInitializer> =
+            MemoryAdapter::new(Some(vec![(epoch, protocol_initializer)])).unwrap();
+        ProtocolInitializerStore::new(Box::new(adapter), None)
+    }
+
+    async fn get_signing_stake(
+        era_checker: &EraChecker,
+        next_era_store: &ProtocolInitializerStore,
+        signing_epoch: Epoch,
+    ) -> u64 {
+        get_signing_protocol_initializer(
+            era_checker,
+            &init_store(4, None),
+            next_era_store,
+            signing_epoch,
+        )
+        .await
+        .unwrap()
+        .expect("a protocol initializer should be found")
+        .get_stake()
+    }
+
+    #[tokio::test]
+    async fn sign_with_the_key_of_the_next_era_at_the_era_switch() {
+        let era_checker = EraChecker::new(SupportedEra::Pythagoras, Epoch(1));
+        era_checker.change_era_with_transition(
+            SupportedEra::Pythagoras,
+            Epoch(5),
+            Some(SupportedEra::Thales),
+        );
+        let next_era_store = init_next_era_store(Epoch(4), 6);
+
+        assert_eq!(
+            700,
+            get_signing_stake(&era_checker, &next_era_store, Epoch(5)).await
+        );
+        assert_eq!(
+            400,
+            get_signing_stake(&era_checker, &next_era_store, Epoch(4)).await
+        );
+    }
+
+    #[tokio::test]
+    async fn sign_with_the_key_of_the_epoch_without_era_switch() {
+        let era_checker = EraChecker::new(SupportedEra::Thales, Epoch(5));
+        let next_era_store = init_next_era_store(Epoch(4), 6);
+
+        assert_eq!(
+            500,
+            get_signing_stake(&era_checker, &next_era_store, Epoch(5)).await
+        );
+    }
+
+    #[tokio::test]
+    async fn sign_with_the_key_of_the_epoch_at_the_era_switch_without_key_of_the_next_era() {
+        let era_checker = EraChecker::new(SupportedEra::Pythagoras, Epoch(1));
+        era_checker.change_era_with_transition(
+            SupportedEra::Pythagoras,
+            Epoch(5),
+            Some(SupportedEra::Thales),
+        );
+
+        assert_eq!(
+            500,
+            get_signing_stake(&era_checker, &init_store(0, None), Epoch(5)).await
+        );
+    }
 }
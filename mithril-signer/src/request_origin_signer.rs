@@ -0,0 +1,267 @@
+use anyhow::{anyhow, Context};
+use chrono::Utc;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use mithril_common::crypto_helper::{compute_request_origin_message, ProtocolInitializer};
+use mithril_common::entities::{Epoch, HexEncodedSingleSignature, PartyId};
+use mithril_common::era::EraChecker;
+use mithril_common::messages::RequestOriginSignatureMessage;
+use mithril_common::StdResult;
+
+use crate::{get_signing_protocol_initializer, ProtocolInitializerStorer};
+
+/// Sign the single signatures sent to the aggregator, along with the date and time of the
+/// request, with the signing key of the verification key the signer signs with at the current
+/// epoch, so the aggregator can check that the signer is the origin of the request.
+///
+/// The protocol initializer holding the signing key is kept until the next epoch, so that the
+/// store is not read for each request.
+pub struct RequestOriginSigner {
+    party_id: PartyId,
+    era_checker: Arc<EraChecker>,
+    protocol_initializer_store: Arc<dyn ProtocolInitializerStorer>,
+    next_era_protocol_initializer_store: Arc<dyn ProtocolInitializerStorer>,
+    protocol_initializer_cache: RwLock<Option<(Epoch, ProtocolInitializer)>>,
+}
+
+impl RequestOriginSigner {
+    /// [RequestOriginSigner] factory
+    pub fn new(
+        party_id: PartyId,
+        era_checker: Arc<EraChecker>,
+        protocol_initializer_store: Arc<dyn ProtocolInitializerStorer>,
+        next_era_protocol_initializer_store: Arc<dyn ProtocolInitializerStorer>,
+    ) -> Self {
+        Self {
+            party_id,
+            era_checker,
+            protocol_initializer_store,
+            next_era_protocol_initializer_store,
+            protocol_initializer_cache: RwLock::new(None),
+        }
+    }
+
+    /// Sign the given single signatures of the signer, now.
+    pub async fn sign(
+        &self,
+        single_signatures: &[HexEncodedSingleSignature],
+    ) -> StdResult<RequestOriginSignatureMessage> {
+        let protocol_initializer = self.get_current_protocol_initializer().await?;
+        let signed_at = Utc::now();
+        let message = compute_request_origin_message(&self.party_id, signed_at, single_signatures);
+        let signature = protocol_initializer
+            .sign_message(&message)
+            .to_json_hex()
+            .with_context(|| "RequestOriginSigner can not encode the request origin signature")?;
+
+        Ok(RequestOriginSignatureMessage {
+            party_id: self.party_id.clone(),
+            signed_at,
+            signature,
+        })
+    }
+
+    /// Get the protocol initializer of the verification key used to sign at the current epoch,
+    /// from the cache if it was already read from the stores during this epoch.
+    async fn get_current_protocol_initializer(&self) -> StdResult<ProtocolInitializer> {
+        let current_epoch = self.era_checker.current_epoch();
+        if let Some((epoch, protocol_initializer)) = &*self.protocol_initializer_cache.read().await
+        {
+            if *epoch == current_epoch {
+                return Ok(protocol_initializer.clone());
+            }
+        }
+
+        let protocol_initializer = get_signing_protocol_initializer(
+            &self.era_checker,
+            self.protocol_initializer_store.as_ref(),
+            self.next_era_protocol_initializer_store.as_ref(),
+            current_epoch,
+        )
+        .await?
+        .ok_or_else(|| {
+            anyhow!("RequestOriginSigner can not find the protocol initializer to sign at epoch {current_epoch}")
+        })?;
+        *self.protocol_initializer_cache.write().await =
+            Some((current_epoch, protocol_initializer.clone()));
+
+        Ok(protocol_initializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use mithril_common::crypto_helper::{
+        verify_request_origin_signature, ProtocolSignerMessageSignature,
+    };
+    use mithril_common::era::SupportedEra;
+    use mithril_common::test_utils::MithrilFixtureBuilder;
+    use mithril_persistence::store::adapter::MemoryAdapter;
+
+    use crate::ProtocolInitializerStore;
+
+    use super::*;
+
+    /// Protocol initializer store counting the protocol initializers read from it.
+    struct CountingProtocolInitializerStore {
+        store: ProtocolInitializerStore,
+        nb_reads: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ProtocolInitializerStorer for CountingProtocolInitializerStore {
+        async fn save_protocol_initializer(
+            &self,
+            epoch: Epoch,
+            protocol_initializer: ProtocolInitializer,
+        ) -> StdResult<Option<ProtocolInitializer>> {
+            self.store
+                .save_protocol_initializer(epoch, protocol_initializer)
+                .await
+        }
+
+        async fn get_protocol_initializer(
+            &self,
+            epoch: Epoch,
+        ) -> StdResult<Option<ProtocolInitializer>> {
+            self.nb_reads.fetch_add(1, Ordering::SeqCst);
+            self.store.get_protocol_initializer(epoch).await
+        }
+
+        async fn get_last_protocol_initializer(
+            &self,
+            last: usize,
+        ) -> StdResult<Vec<(Epoch, ProtocolInitializer)>> {
+            self.store.get_last_protocol_initializer(last).await
+        }
+    }
+
+    fn counting_store(
+        values: Vec<(Epoch, ProtocolInitializer)>,
+    ) -> Arc<CountingProtocolInitializerStore> {
+        Arc::new(CountingProtocolInitializerStore {
+            store: ProtocolInitializerStore::new(
+                Box::new(MemoryAdapter::new(Some(values)).unwrap()),
+                None,
+            ),
+            nb_reads: AtomicUsize::new(0),
+        })
+    }
+
+    #[tokio::test]
+    async fn sign_the_single_signatures_with_the_registered_signing_key() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let signers = fixture.signers_fixture();
+        let era_checker = Arc::new(EraChecker::new(SupportedEra::dummy(), Epoch(5)));
+        let store = counting_store(vec![(Epoch(4), signers[0].protocol_initializer.clone())]);
+        let request_origin_signer = RequestOriginSigner::new(
+            signers[0].party_id(),
+            era_checker,
+            store,
+            counting_store(vec![]),
+        );
+        let single_signatures = vec!["single-signature".to_string()];
+
+        let origin_signature = request_origin_signer
+            .sign(&single_signatures)
+            .await
+            .unwrap();
+
+        let signature =
+            ProtocolSignerMessageSignature::from_json_hex(&origin_signature.signature).unwrap();
+        let message = compute_request_origin_message(
+            &signers[0].party_id(),
+            origin_signature.signed_at,
+            &single_signatures,
+        );
+        assert_eq!(signers[0].party_id(), origin_signature.party_id);
+        verify_request_origin_signature(
+            &signature,
+            &signers[0].signer_with_stake.verification_key,
+            &message,
+        )
+        .expect("the request origin signature should be valid");
+        verify_request_origin_signature(
+            &signature,
+            &signers[1].signer_with_stake.verification_key,
+            &message,
+        )
+        .expect_err("the request origin signature should be invalid for another signer");
+    }
+
+    #[tokio::test]
+    async fn sign_with_the_signing_key_of_the_next_era_at_the_era_switch() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(2).build();
+        let signers = fixture.signers_fixture();
+        let era_checker = Arc::new(EraChecker::new(SupportedEra::Pythagoras, Epoch(1)));
+        era_checker.change_era_with_transition(
+            SupportedEra::Pythagoras,
+            Epoch(5),
+            Some(SupportedEra::Thales),
+        );
+        let request_origin_signer = RequestOriginSigner::new(
+            signers[0].party_id(),
+            era_checker,
+            counting_store(vec![(Epoch(4), signers[0].protocol_initializer.clone())]),
+            counting_store(vec![(Epoch(4), signers[1].protocol_initializer.clone())]),
+        );
+
+        let origin_signature = request_origin_signer.sign(&[]).await.unwrap();
+
+        let signature =
+            ProtocolSignerMessageSignature::from_json_hex(&origin_signature.signature).unwrap();
+        let message =
+            compute_request_origin_message(&signers[0].party_id(), origin_signature.signed_at, &[]);
+        verify_request_origin_signature(
+            &signature,
+            &signers[1].signer_with_stake.verification_key,
+            &message,
+        )
+        .expect("the request origin signature should be made with the key of the next era");
+    }
+
+    #[tokio::test]
+    async fn read_the_protocol_initializer_once_per_epoch() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(1).build();
+        let signer = &fixture.signers_fixture()[0];
+        let era_checker = Arc::new(EraChecker::new(SupportedEra::dummy(), Epoch(5)));
+        let store = counting_store(vec![
+            (Epoch(4), signer.protocol_initializer.clone()),
+            (Epoch(5), signer.protocol_initializer.clone()),
+        ]);
+        let request_origin_signer = RequestOriginSigner::new(
+            signer.party_id(),
+            era_checker.clone(),
+            store.clone(),
+            counting_store(vec![]),
+        );
+
+        request_origin_signer.sign(&[]).await.unwrap();
+        request_origin_signer.sign(&[]).await.unwrap();
+        assert_eq!(1, store.nb_reads.load(Ordering::SeqCst));
+
+        era_checker.change_era(SupportedEra::dummy(), Epoch(6));
+        request_origin_signer.sign(&[]).await.unwrap();
+        assert_eq!(2, store.nb_reads.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn sign_fails_without_a_protocol_initializer_for_the_current_epoch() {
+        let era_checker = Arc::new(EraChecker::new(SupportedEra::dummy(), Epoch(5)));
+        let request_origin_signer = RequestOriginSigner::new(
+            "party_id".to_string(),
+            era_checker,
+            counting_store(vec![]),
+            counting_store(vec![]),
+        );
+
+        request_origin_signer
+            .sign(&[])
+            .await
+            .expect_err("signing without a protocol initializer should fail");
+    }
+}
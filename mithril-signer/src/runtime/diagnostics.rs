@@ -0,0 +1,181 @@
+use chrono::Utc;
+use std::collections::VecDeque;
+
+use mithril_common::messages::{
+    SignerDiagnosticErrorMessage, SignerDiagnosticMessage, SignerDiagnosticStateMessage,
+};
+
+use super::{RuntimeError, SignerState};
+
+/// Number of the latest transitions of the state machine kept in a diagnostic
+const STATE_HISTORY_SIZE: usize = 10;
+
+/// Number of the latest errors of the state machine kept in a diagnostic
+const LAST_ERRORS_SIZE: usize = 10;
+
+/// Record the latest transitions and errors of the state machine, in order to build a diagnostic
+/// when the signer fails persistently.
+///
+/// A diagnostic is submitted once when the number of consecutive failed cycles reaches the
+/// failure threshold, or when a critical error occurs, and again only after a successful cycle.
+pub struct DiagnosticsRecorder {
+    failure_threshold: u64,
+    consecutive_failures: u64,
+    is_submitted: bool,
+    state_history: VecDeque<SignerDiagnosticStateMessage>,
+    last_errors: VecDeque<SignerDiagnosticErrorMessage>,
+}
+
+impl DiagnosticsRecorder {
+    /// DiagnosticsRecorder factory
+    pub fn new(failure_threshold: u64) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            consecutive_failures: 0,
+            is_submitted: false,
+            state_history: VecDeque::with_capacity(STATE_HISTORY_SIZE),
+            last_errors: VecDeque::with_capacity(LAST_ERRORS_SIZE),
+        }
+    }
+
+    /// Record the state of the state machine at the end of a cycle, only the transitions are kept.
+    pub fn record_state(&mut self, state: &SignerState) {
+        let state = state.to_string();
+        if self.state_history.back().map(|s| &s.state) == Some(&state) {
+            return;
+        }
+        if self.state_history.len() == STATE_HISTORY_SIZE {
+            self.state_history.pop_front();
+        }
+        self.state_history.push_back(SignerDiagnosticStateMessage {
+            state,
+            entered_at: Utc::now(),
+        });
+    }
+
+    /// Record a successful cycle of the state machine.
+    pub fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.is_submitted = false;
+    }
+
+    /// Record a failed cycle of the state machine, return `true` if a diagnostic should be
+    /// submitted.
+    pub fn record_failure(&mut self, error: &RuntimeError) -> bool {
+        self.consecutive_failures += 1;
+        if self.last_errors.len() == LAST_ERRORS_SIZE {
+            self.last_errors.pop_front();
+        }
+        self.last_errors.push_back(SignerDiagnosticErrorMessage {
+            message: error.to_string(),
+            is_critical: error.is_critical(),
+            occurred_at: Utc::now(),
+        });
+
+        !self.is_submitted
+            && (error.is_critical() || self.consecutive_failures >= self.failure_threshold)
+    }
+
+    /// Record that the diagnostic has been submitted.
+    pub fn mark_as_submitted(&mut self) {
+        self.is_submitted = true;
+    }
+
+    /// Build the diagnostic of the given current state, the signer identification, versions
+    /// and era are left to the runner.
+    pub fn build_diagnostic(&self, current_state: &SignerState) -> SignerDiagnosticMessage {
+        SignerDiagnosticMessage {
+            current_state: current_state.to_string(),
+            consecutive_failures: self.consecutive_failures,
+            state_history: self.state_history.iter().cloned().collect(),
+            last_errors: self.last_errors.iter().cloned().collect(),
+            ..SignerDiagnosticMessage::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::Epoch;
+
+    use super::*;
+
+    fn keep_state_error(message: &str) -> RuntimeError {
+        RuntimeError::KeepState {
+            message: message.to_string(),
+            nested_error: None,
+        }
+    }
+
+    #[test]
+    fn submit_once_when_the_failure_threshold_is_reached() {
+        let mut recorder = DiagnosticsRecorder::new(3);
+
+        assert!(!recorder.record_failure(&keep_state_error("error 1")));
+        assert!(!recorder.record_failure(&keep_state_error("error 2")));
+        assert!(recorder.record_failure(&keep_state_error("error 3")));
+        recorder.mark_as_submitted();
+
+        assert!(!recorder.record_failure(&keep_state_error("error 4")));
+    }
+
+    #[test]
+    fn submit_again_after_a_successful_cycle() {
+        let mut recorder = DiagnosticsRecorder::new(2);
+        recorder.record_failure(&keep_state_error("error 1"));
+        recorder.record_failure(&keep_state_error("error 2"));
+        recorder.mark_as_submitted();
+
+        recorder.record_success();
+
+        assert!(!recorder.record_failure(&keep_state_error("error 3")));
+        assert!(recorder.record_failure(&keep_state_error("error 4")));
+    }
+
+    #[test]
+    fn submit_on_critical_error() {
+        let mut recorder = DiagnosticsRecorder::new(10);
+
+        assert!(recorder.record_failure(&RuntimeError::Critical {
+            message: "critical".to_string(),
+            nested_error: None,
+        }));
+    }
+
+    #[test]
+    fn build_diagnostic_with_the_latest_transitions_and_errors() {
+        let mut recorder = DiagnosticsRecorder::new(10);
+        for epoch in 1..=(STATE_HISTORY_SIZE as u64 + 2) {
+            let state = SignerState::Unregistered {
+                epoch: Epoch(epoch),
+            };
+            recorder.record_state(&state);
+            recorder.record_state(&state);
+        }
+        for i in 1..=(LAST_ERRORS_SIZE + 2) {
+            recorder.record_failure(&keep_state_error(&format!("error {i}")));
+        }
+
+        let diagnostic = recorder.build_diagnostic(&SignerState::Init);
+
+        assert_eq!("Init", diagnostic.current_state);
+        assert_eq!(LAST_ERRORS_SIZE as u64 + 2, diagnostic.consecutive_failures);
+        assert_eq!(
+            (3..=(STATE_HISTORY_SIZE as u64 + 2))
+                .map(|epoch| SignerState::Unregistered {
+                    epoch: Epoch(epoch)
+                }
+                .to_string())
+                .collect::<Vec<_>>(),
+            diagnostic
+                .state_history
+                .iter()
+                .map(|s| s.state.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(LAST_ERRORS_SIZE, diagnostic.last_errors.len());
+        assert!(diagnostic.last_errors[LAST_ERRORS_SIZE - 1]
+            .message
+            .contains(&format!("error {}", LAST_ERRORS_SIZE + 2)));
+    }
+}
@@ -1,9 +1,17 @@
+mod diagnostics;
 mod error;
+mod preflight;
 mod runner;
 mod signer_services;
 mod state_machine;
+mod ticker;
+mod transitions;
 
+pub use diagnostics::*;
 pub use error::*;
+pub use preflight::*;
 pub use runner::*;
 pub use signer_services::*;
 pub use state_machine::*;
+pub use ticker::*;
+pub use transitions::*;
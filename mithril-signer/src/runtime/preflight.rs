@@ -0,0 +1,226 @@
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+/// Number of bytes in a mebibyte
+const MEBIBYTE: u64 = 1024 * 1024;
+
+/// Error raised when the signer host does not have the resources needed by a heavy operation
+/// (i.e. the computation of the digest of the Cardano node database).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PreflightCheckError {
+    /// The free disk space of the data stores directory is below the threshold.
+    #[error("Preflight check failed: the free disk space of '{directory}' is {free_mib} MiB, below the required {required_mib} MiB")]
+    NotEnoughDiskSpace {
+        /// Checked directory
+        directory: PathBuf,
+        /// Free disk space (in MiB)
+        free_mib: u64,
+        /// Required free disk space (in MiB)
+        required_mib: u64,
+    },
+
+    /// The free disk space of a directory can not be read.
+    #[error(
+        "Preflight check failed: the free disk space of '{directory}' can not be read: {message}"
+    )]
+    DiskSpaceUnreadable {
+        /// Checked directory
+        directory: PathBuf,
+        /// Reason of the failure
+        message: String,
+    },
+
+    /// The Cardano node database directory is not readable.
+    #[error("Preflight check failed: the Cardano node database directory '{directory}' is not readable: {message}")]
+    DbDirectoryUnreadable {
+        /// Checked directory
+        directory: PathBuf,
+        /// Reason of the failure
+        message: String,
+    },
+
+    /// The available memory of the host is below the threshold.
+    #[error("Preflight check failed: the available memory is {available_mib} MiB, below the required {required_mib} MiB")]
+    NotEnoughMemory {
+        /// Available memory (in MiB)
+        available_mib: u64,
+        /// Required available memory (in MiB)
+        required_mib: u64,
+    },
+}
+
+/// Check that the signer host has the resources needed before a heavy operation:
+/// * the Cardano node database directory is readable,
+/// * the free disk space of the data stores directory is above a threshold, if set,
+/// * the available memory of the host is above a threshold, if set (only on Linux, the check is
+///   skipped on the other platforms).
+pub struct PreflightChecker {
+    db_directory: PathBuf,
+    data_stores_directory: PathBuf,
+    min_free_disk_space_mib: Option<u64>,
+    min_available_memory_mib: Option<u64>,
+}
+
+impl PreflightChecker {
+    /// PreflightChecker factory, without any disk space or memory threshold
+    pub fn new(db_directory: &Path, data_stores_directory: &Path) -> Self {
+        Self {
+            db_directory: db_directory.to_path_buf(),
+            data_stores_directory: data_stores_directory.to_path_buf(),
+            min_free_disk_space_mib: None,
+            min_available_memory_mib: None,
+        }
+    }
+
+    /// Set the minimum free disk space of the data stores directory (in MiB)
+    pub fn with_min_free_disk_space(mut self, min_free_disk_space_mib: Option<u64>) -> Self {
+        self.min_free_disk_space_mib = min_free_disk_space_mib;
+        self
+    }
+
+    /// Set the minimum available memory of the host (in MiB)
+    pub fn with_min_available_memory(mut self, min_available_memory_mib: Option<u64>) -> Self {
+        self.min_available_memory_mib = min_available_memory_mib;
+        self
+    }
+
+    /// Run all the checks, returning the errors of the failed ones
+    pub fn run_checks(&self) -> Vec<PreflightCheckError> {
+        [
+            self.check_db_directory(),
+            self.check_free_disk_space(),
+            self.check_available_memory(),
+        ]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect()
+    }
+
+    fn check_db_directory(&self) -> Result<(), PreflightCheckError> {
+        std::fs::read_dir(&self.db_directory)
+            .map(|_| ())
+            .map_err(|e| PreflightCheckError::DbDirectoryUnreadable {
+                directory: self.db_directory.clone(),
+                message: e.to_string(),
+            })
+    }
+
+    fn check_free_disk_space(&self) -> Result<(), PreflightCheckError> {
+        let Some(required_mib) = self.min_free_disk_space_mib else {
+            return Ok(());
+        };
+        let free_mib = fs2::available_space(&self.data_stores_directory).map_err(|e| {
+            PreflightCheckError::DiskSpaceUnreadable {
+                directory: self.data_stores_directory.clone(),
+                message: e.to_string(),
+            }
+        })? / MEBIBYTE;
+
+        if free_mib < required_mib {
+            return Err(PreflightCheckError::NotEnoughDiskSpace {
+                directory: self.data_stores_directory.clone(),
+                free_mib,
+                required_mib,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_available_memory(&self) -> Result<(), PreflightCheckError> {
+        let Some(required_mib) = self.min_available_memory_mib else {
+            return Ok(());
+        };
+        let Some(available_mib) = read_available_memory_mib() else {
+            return Ok(());
+        };
+
+        if available_mib < required_mib {
+            return Err(PreflightCheckError::NotEnoughMemory {
+                available_mib,
+                required_mib,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Read the available memory of the host (in MiB), `None` if it can not be read
+fn read_available_memory_mib() -> Option<u64> {
+    if cfg!(target_os = "linux") {
+        std::fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|meminfo| parse_available_memory_mib(&meminfo))
+    } else {
+        None
+    }
+}
+
+/// Parse the `MemAvailable` entry of a `/proc/meminfo` content (in MiB)
+fn parse_available_memory_mib(meminfo: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemAvailable:"))
+        .and_then(|value| {
+            value
+                .trim()
+                .trim_end_matches("kB")
+                .trim()
+                .parse::<u64>()
+                .ok()
+        })
+        .map(|available_kib| available_kib / 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::test_utils::TempDir;
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        TempDir::create("preflight", name)
+    }
+
+    #[test]
+    fn checks_succeed_when_the_resources_are_available() {
+        let dir = temp_dir("checks_succeed_when_the_resources_are_available");
+        let checker = PreflightChecker::new(&dir, &dir)
+            .with_min_free_disk_space(Some(0))
+            .with_min_available_memory(Some(0));
+
+        assert_eq!(Vec::<PreflightCheckError>::new(), checker.run_checks());
+    }
+
+    #[test]
+    fn checks_fail_when_the_db_directory_is_unreadable_or_the_disk_space_is_too_low() {
+        let dir = temp_dir("checks_fail_when_the_db_directory_is_unreadable");
+        let checker = PreflightChecker::new(&dir.join("missing"), &dir)
+            .with_min_free_disk_space(Some(u64::MAX));
+
+        let errors = checker.run_checks();
+
+        assert_eq!(2, errors.len(), "unexpected errors: {errors:?}");
+        assert!(matches!(
+            errors[0],
+            PreflightCheckError::DbDirectoryUnreadable { .. }
+        ));
+        assert!(matches!(
+            errors[1],
+            PreflightCheckError::NotEnoughDiskSpace {
+                required_mib: u64::MAX,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parse_available_memory_from_meminfo() {
+        let meminfo = "MemTotal:       16318480 kB\nMemFree:         1234567 kB\nMemAvailable:    2097152 kB\n";
+
+        assert_eq!(Some(2048), parse_available_memory_mib(meminfo));
+        assert_eq!(None, parse_available_memory_mib("MemTotal: 16318480 kB"));
+    }
+}
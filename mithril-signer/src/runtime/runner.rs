@@ -1,23 +1,47 @@
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use rand_core::RngCore;
 use slog_scope::{debug, info, trace, warn};
+use std::time::Instant;
 use thiserror::Error;
 
 #[cfg(test)]
 use mockall::automock;
 
-use mithril_common::crypto_helper::{KESPeriod, OpCert, ProtocolOpCert, SerDeShelleyFileFormat};
+use mithril_common::crypto_helper::{
+    compute_operational_certificate_kes_period, compute_registration_nonce_message,
+    sign_registration_nonce_message, KESPeriod, OpCert, ProtocolInitializer, ProtocolOpCert,
+    ProtocolSignerVerificationKeySignature, SerDeShelleyFileFormat,
+};
 use mithril_common::entities::{
-    CertificatePending, Epoch, EpochSettings, PartyId, ProtocolMessage, ProtocolMessagePartKey,
-    ProtocolParameters, SignedEntityType, Signer, SignerWithStake, SingleSignatures, TimePoint,
+    CertificatePending, Epoch, EpochSettings, PartyId, ProtocolMessage, ProtocolMessagePart,
+    ProtocolParameters, SignedCertificateMetadata, SignedEntityType, Signer, SignerWithStake,
+    SingleSignatures, TimePoint,
 };
+use mithril_common::era::SupportedEra;
+use mithril_common::messages::{SignerDiagnosticErrorMessage, SignerDiagnosticMessage};
 use mithril_common::StdResult;
 use mithril_persistence::store::StakeStorer;
 
-use crate::{Configuration, MithrilProtocolInitializerBuilder};
+use crate::{
+    get_signing_protocol_initializer, Configuration, PreflightCheckError, PreflightChecker,
+};
 
 use super::signer_services::SignerServices;
 
+/// Name of the file, in the data stores directory, holding the identifier of the signer in the
+/// submitted diagnostics
+const DIAGNOSTICS_SIGNER_ID_FILE_NAME: &str = "diagnostics_signer_id";
+
+/// Maximum advance, on the clock of the signer, of the initiation date and time of a
+/// certificate supplied by the aggregator
+const CERTIFICATE_INITIATED_AT_MAX_ADVANCE_IN_SECONDS: i64 = 5 * 60;
+
+/// Maximum age of the initiation date and time of a certificate supplied by the aggregator: the
+/// duration of an epoch on the mainnet, as a certificate can be signed until the end of its epoch
+const CERTIFICATE_INITIATED_AT_MAX_AGE_IN_SECONDS: i64 = 5 * 24 * 60 * 60;
+
 /// This trait is mainly intended for mocking.
 #[async_trait]
 pub trait Runner: Send + Sync {
@@ -31,6 +55,8 @@ pub trait Runner: Send + Sync {
     async fn get_current_time_point(&self) -> StdResult<TimePoint>;
 
     /// Register the signer verification key to the aggregator.
+    ///
+    /// The key registered ahead of an era switch is only used to sign once the era is active.
     async fn register_signer_to_aggregator(
         &self,
         epoch: Epoch,
@@ -58,23 +84,44 @@ pub trait Runner: Send + Sync {
         next_signers: &[SignerWithStake],
     ) -> StdResult<ProtocolMessage>;
 
-    /// Create the single signature.
+    /// Read the era of the given epoch from the EraChecker.
+    fn get_era_of_epoch(&self, epoch: Epoch) -> SupportedEra;
+
+    /// Compute the metadata of the certificate that are signed with the protocol message.
+    ///
+    /// The initiation date and time of the certificate, supplied by the aggregator, must be close
+    /// to the current date and time.
+    async fn compute_signed_certificate_metadata(
+        &self,
+        protocol_parameters: &ProtocolParameters,
+        initiated_at: DateTime<Utc>,
+    ) -> StdResult<SignedCertificateMetadata>;
+
+    /// Create the single signature of the message of the given signed entity type.
     async fn compute_single_signature(
         &self,
         epoch: Epoch,
+        signed_entity_type: &SignedEntityType,
         message: &ProtocolMessage,
         signers: &[SignerWithStake],
     ) -> StdResult<Option<SingleSignatures>>;
 
-    /// Send the single signature to the aggregator in order to be aggregated.
+    /// Send the single signature to the aggregator in order to be aggregated, authenticated with
+    /// a signature of the registration nonce of the epoch if any.
     async fn send_single_signature(
         &self,
         signed_entity_type: &SignedEntityType,
         maybe_signature: Option<SingleSignatures>,
+        registration_nonce: Option<String>,
     ) -> StdResult<()>;
 
-    /// Read the current era and update the EraChecker.
+    /// Read the current era and update the EraChecker, the keys registered ahead of the switch
+    /// to the current era are then used to sign.
     async fn update_era_checker(&self, epoch: Epoch) -> StdResult<()>;
+
+    /// Anonymize the given diagnostic, complete it with the versions and the era of the signer
+    /// and submit it to the aggregator.
+    async fn submit_diagnostic(&self, diagnostic: SignerDiagnosticMessage) -> StdResult<()>;
 }
 
 /// This type represents the errors thrown from the Runner.
@@ -92,6 +139,13 @@ pub enum RunnerError {
     /// Parse file error
     #[error("File parse failed: {0}.")]
     FileParse(String),
+    /// The preflight checks run before a heavy operation failed.
+    #[error("Preflight checks failed: [{}].", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    PreflightChecksFailed(Vec<PreflightCheckError>),
+    /// The initiation date and time of a certificate supplied by the aggregator is too far from
+    /// the current date and time.
+    #[error("Certificate initiation date and time '{0}' too far from the current date and time.")]
+    InvalidCertificateInitiatedAt(DateTime<Utc>),
 }
 
 /// Controller methods for the Signer's state machine.
@@ -105,6 +159,139 @@ impl SignerRunner {
     pub fn new(config: Configuration, services: SignerServices) -> Self {
         Self { services, config }
     }
+
+    /// Random identifier of the signer in the submitted diagnostics, created once and kept in the
+    /// data stores directory so that the SPO can share it with the aggregator operators.
+    fn get_or_create_diagnostics_signer_id(&self) -> StdResult<String> {
+        let path = self
+            .config
+            .data_stores_directory
+            .join(DIAGNOSTICS_SIGNER_ID_FILE_NAME);
+        if path.exists() {
+            let signer_id = std::fs::read_to_string(&path).with_context(|| {
+                format!(
+                    "Could not read diagnostics signer id file: '{}'",
+                    path.display()
+                )
+            })?;
+
+            return Ok(signer_id.trim().to_string());
+        }
+
+        let mut bytes = [0u8; 16];
+        rand_core::OsRng.fill_bytes(&mut bytes);
+        let signer_id = hex::encode(bytes);
+        std::fs::write(&path, &signer_id).with_context(|| {
+            format!(
+                "Could not write diagnostics signer id file: '{}'",
+                path.display()
+            )
+        })?;
+        info!("RUNNER: created the identifier of the signer in the diagnostics"; "signer_id" => &signer_id);
+
+        Ok(signer_id)
+    }
+
+    /// Return the upcoming era and its activation epoch if the era switches before the keys
+    /// registered at the given epoch are used to sign.
+    ///
+    /// An upcoming era that is not supported by this version of the software is ignored.
+    async fn get_upcoming_era_switch(
+        &self,
+        epoch: Epoch,
+    ) -> StdResult<Option<(SupportedEra, Epoch)>> {
+        let era_token = self
+            .services
+            .era_reader
+            .read_era_epoch_token(epoch)
+            .await
+            .map_err(Box::new)?;
+        let (Ok(Some(next_era)), Some(switch_epoch)) = (
+            era_token.get_next_supported_era(),
+            era_token
+                .get_next_era_marker()
+                .and_then(|marker| marker.epoch),
+        ) else {
+            return Ok(None);
+        };
+
+        if epoch < switch_epoch && switch_epoch <= epoch.offset_to_signer_signing_offset() {
+            Ok(Some((next_era, switch_epoch)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Compute the current KES period of the given operational certificate.
+    async fn compute_kes_period(&self, operational_certificate: &OpCert) -> StdResult<KESPeriod> {
+        let current_kes_period = self
+            .services
+            .chain_observer
+            .get_current_kes_period(operational_certificate)
+            .await?
+            .unwrap_or_default();
+
+        compute_operational_certificate_kes_period(current_kes_period, operational_certificate)
+            .ok_or_else(|| anyhow!("The operational certificate is not valid yet"))
+    }
+
+    /// Check that the host has the resources needed to compute the digest of the Cardano node
+    /// database, the result is exposed by the `preflight_checks_success` metric.
+    fn run_preflight_checks(&self) -> StdResult<()> {
+        if self.config.disable_preflight_checks {
+            return Ok(());
+        }
+        debug!("RUNNER: run_preflight_checks");
+
+        let errors = PreflightChecker::new(
+            &self.config.db_directory,
+            &self.config.data_stores_directory,
+        )
+        .with_min_free_disk_space(self.config.preflight_min_free_disk_space)
+        .with_min_available_memory(self.config.preflight_min_available_memory)
+        .run_checks();
+        self.services
+            .metrics_service
+            .preflight_checks_success_gauge_set(errors.is_empty());
+
+        if !errors.is_empty() {
+            for error in &errors {
+                warn!("RUNNER: {error}");
+            }
+            return Err(RunnerError::PreflightChecksFailed(errors).into());
+        }
+
+        Ok(())
+    }
+
+    /// Sign the registration nonce along the given single signatures with the KES secret key,
+    /// returns `None` if the signer has no KES secret key.
+    async fn sign_registration_nonce(
+        &self,
+        registration_nonce: &str,
+        single_signatures: &SingleSignatures,
+    ) -> StdResult<Option<ProtocolSignerVerificationKeySignature>> {
+        let (Some(kes_secret_key_path), Some(operational_certificate_path)) = (
+            &self.config.kes_secret_key_path,
+            &self.config.operational_certificate_path,
+        ) else {
+            return Ok(None);
+        };
+        let operational_certificate = OpCert::from_file(operational_certificate_path)
+            .map_err(|_| RunnerError::FileParse("operational_certificate_path".to_string()))
+            .with_context(|| "sign_registration_nonce can not decode OpCert from file")?;
+        let kes_period = self.compute_kes_period(&operational_certificate).await?;
+        let message = compute_registration_nonce_message(
+            registration_nonce,
+            &single_signatures.party_id,
+            &single_signatures.signature.to_bytes(),
+        );
+        let nonce_signature =
+            sign_registration_nonce_message(kes_secret_key_path, kes_period, &message)
+                .with_context(|| "sign_registration_nonce can not sign the registration nonce")?;
+
+        Ok(Some(nonce_signature))
+    }
 }
 
 #[cfg_attr(test, automock)]
@@ -133,16 +320,11 @@ impl Runner for SignerRunner {
     async fn get_current_time_point(&self) -> StdResult<TimePoint> {
         debug!("RUNNER: get_current_time_point");
 
-        let time_point = self
-            .services
+        self.services
             .time_point_provider
             .get_current_time_point()
             .await
-            .with_context(|| "Runner can not get current time point")?;
-        Ok(TimePoint::new(
-            *time_point.epoch,
-            time_point.immutable_file_number,
-        ))
+            .with_context(|| "Runner can not get current time point")
     }
 
     async fn register_signer_to_aggregator(
@@ -182,33 +364,73 @@ impl Runner for SignerRunner {
         };
 
         let kes_period = match operational_certificate {
-            Some(operational_certificate) => Some(
-                self.services
-                    .chain_observer
-                    .get_current_kes_period(&operational_certificate)
-                    .await?
-                    .unwrap_or_default()
-                    - operational_certificate.start_kes_period as KESPeriod,
-            ),
+            Some(operational_certificate) => {
+                Some(self.compute_kes_period(&operational_certificate).await?)
+            }
             None => None,
         };
-        let protocol_initializer = MithrilProtocolInitializerBuilder::build(
-            stake,
-            protocol_parameters,
-            self.config.kes_secret_key_path.clone(),
-            kes_period,
-        )?;
-        let signer = Signer::new(
-            self.services.single_signer.get_party_id(),
-            protocol_initializer.verification_key().into(),
-            protocol_initializer.verification_key_signature(),
-            protocol_operational_certificate,
-            kes_period,
-        );
-        self.services
-            .certificate_handler
-            .register_signer(epoch_offset_to_recording_epoch, &signer)
-            .await?;
+
+        // The keys registered now sign at the signing offset epoch: they are created with the
+        // signature scheme of the next era if it is active by then. If the era switches at this
+        // very epoch, a key of the current era is registered as well in case the switch is
+        // postponed.
+        let signing_epoch = epoch.offset_to_signer_signing_offset();
+        let (era, next_era) = match self.get_upcoming_era_switch(epoch).await? {
+            Some((next_era, switch_epoch)) if switch_epoch < signing_epoch => (next_era, None),
+            Some((next_era, _)) => (self.services.era_checker.current_era(), Some(next_era)),
+            None => (self.services.era_checker.current_era(), None),
+        };
+        let create_signer = |era: SupportedEra| -> StdResult<(ProtocolInitializer, Signer)> {
+            let protocol_initializer = self.services.single_signer.create_protocol_initializer(
+                era,
+                *stake,
+                protocol_parameters,
+                self.config.kes_secret_key_path.clone(),
+                kes_period,
+            )?;
+            let signer = Signer::new(
+                self.services.single_signer.get_party_id(),
+                protocol_initializer.verification_key().into(),
+                protocol_initializer.verification_key_signature(),
+                protocol_operational_certificate.clone(),
+                kes_period,
+            );
+
+            Ok((protocol_initializer, signer))
+        };
+
+        let (protocol_initializer, signer) = create_signer(era)?;
+        match next_era {
+            Some(next_era) => {
+                let (next_era_protocol_initializer, next_era_signer) = create_signer(next_era)?;
+                self.services
+                    .certificate_handler
+                    .register_signer_for_next_era(
+                        epoch_offset_to_recording_epoch,
+                        &signer,
+                        next_era,
+                        &next_era_signer,
+                    )
+                    .await?;
+                info!(
+                    "RUNNER: registered the key of the upcoming era '{next_era}' activated at epoch {signing_epoch}";
+                    "recording_epoch" => ?epoch_offset_to_recording_epoch
+                );
+                self.services
+                    .next_era_protocol_initializer_store
+                    .save_protocol_initializer(
+                        epoch_offset_to_recording_epoch,
+                        next_era_protocol_initializer,
+                    )
+                    .await?;
+            }
+            None => {
+                self.services
+                    .certificate_handler
+                    .register_signer(epoch_offset_to_recording_epoch, &signer)
+                    .await?;
+            }
+        }
         self.services
             .protocol_initializer_store
             .save_protocol_initializer(epoch_offset_to_recording_epoch, protocol_initializer)
@@ -254,13 +476,7 @@ impl Runner for SignerRunner {
             debug!(" > got a Signer from pending certificate");
 
             if let Some(protocol_initializer) = self
-                .services
-                .protocol_initializer_store
-                .get_protocol_initializer(
-                    pending_certificate
-                        .epoch
-                        .offset_to_signer_retrieval_epoch()?,
-                )
+                .get_signing_protocol_initializer(pending_certificate.epoch)
                 .await?
             {
                 debug!(
@@ -330,14 +546,19 @@ impl Runner for SignerRunner {
         next_signers: &[SignerWithStake],
     ) -> StdResult<ProtocolMessage> {
         debug!("RUNNER: compute_message");
+        self.run_preflight_checks()?;
 
         // 1 compute the signed entity type part of the message
+        let digest_computation_start = Instant::now();
         let mut message = self
             .services
             .signable_builder_service
             .compute_protocol_message(signed_entity_type.to_owned())
             .await
             .with_context(|| format!("Runner can not compute protocol message for signed entity type: '{signed_entity_type}'"))?;
+        self.services
+            .metrics_service
+            .digest_computation_duration_histogram_observe(digest_computation_start.elapsed());
 
         // 2 set the next signers keys and stakes in the message
         let epoch = signed_entity_type.get_epoch();
@@ -356,33 +577,74 @@ impl Runner for SignerRunner {
         let avk = self
             .services
             .single_signer
-            .compute_aggregate_verification_key(next_signers, &next_protocol_initializer)?
+            .compute_aggregate_verification_key(
+                epoch.next(),
+                next_signers,
+                &next_protocol_initializer,
+            )?
             .ok_or_else(|| RunnerError::NoValueError("next_signers avk".to_string()))?;
-        message.set_message_part(ProtocolMessagePartKey::NextAggregateVerificationKey, avk);
+        message.set_part(ProtocolMessagePart::NextAggregateVerificationKey(avk))?;
+        message.validate_for(signed_entity_type)?;
 
         Ok(message)
     }
 
+    fn get_era_of_epoch(&self, epoch: Epoch) -> SupportedEra {
+        self.services.era_checker.era_of_epoch(epoch)
+    }
+
+    async fn get_signing_protocol_initializer(
+        &self,
+        epoch: Epoch,
+    ) -> StdResult<Option<ProtocolInitializer>> {
+        get_signing_protocol_initializer(
+            &self.services.era_checker,
+            self.services.protocol_initializer_store.as_ref(),
+            self.services.next_era_protocol_initializer_store.as_ref(),
+            epoch,
+        )
+        .await
+    }
+
+    async fn compute_signed_certificate_metadata(
+        &self,
+        protocol_parameters: &ProtocolParameters,
+        initiated_at: DateTime<Utc>,
+    ) -> StdResult<SignedCertificateMetadata> {
+        debug!("RUNNER: compute_signed_certificate_metadata");
+
+        let now = Utc::now();
+        let max_advance =
+            Duration::try_seconds(CERTIFICATE_INITIATED_AT_MAX_ADVANCE_IN_SECONDS).unwrap();
+        let max_age = Duration::try_seconds(CERTIFICATE_INITIATED_AT_MAX_AGE_IN_SECONDS).unwrap();
+        if initiated_at > now + max_advance || initiated_at < now - max_age {
+            return Err(RunnerError::InvalidCertificateInitiatedAt(initiated_at).into());
+        }
+
+        Ok(SignedCertificateMetadata {
+            network: self.config.get_network()?.to_string(),
+            protocol_parameters: protocol_parameters.clone(),
+            initiated_at,
+        })
+    }
+
     async fn compute_single_signature(
         &self,
         epoch: Epoch,
+        signed_entity_type: &SignedEntityType,
         message: &ProtocolMessage,
         signers: &[SignerWithStake],
     ) -> StdResult<Option<SingleSignatures>> {
         debug!("RUNNER: compute_single_signature");
 
-        let signer_retrieval_epoch = epoch.offset_to_signer_retrieval_epoch()?;
         let protocol_initializer = self
-            .services
-            .protocol_initializer_store
-            .get_protocol_initializer(signer_retrieval_epoch)
+            .get_signing_protocol_initializer(epoch)
             .await?
             .ok_or_else(|| {
-                RunnerError::NoValueError(format!(
-                    "protocol_initializer at epoch {signer_retrieval_epoch}"
-                ))
+                RunnerError::NoValueError(format!("protocol_initializer to sign at epoch {epoch}"))
             })?;
         let signature = self.services.single_signer.compute_single_signatures(
+            signed_entity_type.get_epoch(),
             message,
             signers,
             &protocol_initializer,
@@ -395,6 +657,11 @@ impl Runner for SignerRunner {
                 "NO single signature was computed."
             }
         );
+        if signature.is_some() {
+            self.services
+                .metrics_service
+                .signature_computed_since_startup_counter_increment();
+        }
 
         Ok(signature)
     }
@@ -403,11 +670,20 @@ impl Runner for SignerRunner {
         &self,
         signed_entity_type: &SignedEntityType,
         maybe_signature: Option<SingleSignatures>,
+        registration_nonce: Option<String>,
     ) -> StdResult<()> {
         debug!("RUNNER: send_single_signature");
 
-        if let Some(single_signatures) = maybe_signature {
+        if let Some(mut single_signatures) = maybe_signature {
             debug!(" > there is a single signature to send");
+            if let Some(registration_nonce) = registration_nonce {
+                if let Some(nonce_signature) = self
+                    .sign_registration_nonce(&registration_nonce, &single_signatures)
+                    .await?
+                {
+                    single_signatures = single_signatures.with_nonce_signature(nonce_signature);
+                }
+            }
 
             self.services
                 .certificate_handler
@@ -432,22 +708,80 @@ impl Runner for SignerRunner {
             .await
             .map_err(Box::new)?;
         let current_era = era_token.get_current_supported_era()?;
-        self.services
-            .era_checker
-            .change_era(current_era, era_token.get_current_epoch());
+        self.services.era_checker.change_era_with_transition(
+            current_era,
+            era_token.get_current_epoch(),
+            era_token.get_transition_supported_era(),
+        );
         debug!(
             "Current Era is {} (Epoch {}).",
             current_era,
             era_token.get_current_epoch()
         );
 
-        if era_token.get_next_supported_era().is_err() {
-            let era_name = &era_token.get_next_era_marker().unwrap().name;
-            warn!("Upcoming Era '{era_name}' is not supported by this version of the software. Please update!");
+        match (
+            era_token.get_next_supported_era(),
+            era_token
+                .get_next_era_marker()
+                .and_then(|marker| marker.epoch),
+        ) {
+            (Err(_), _) => {
+                let era_name = &era_token.get_next_era_marker().unwrap().name;
+                warn!("Upcoming Era '{era_name}' is not supported by this version of the software. Please update!");
+            }
+            (Ok(Some(next_era)), Some(activation_epoch)) => {
+                self.services
+                    .era_checker
+                    .set_upcoming_era(next_era, activation_epoch);
+            }
+            _ => {}
         }
 
         Ok(())
     }
+
+    async fn submit_diagnostic(&self, diagnostic: SignerDiagnosticMessage) -> StdResult<()> {
+        debug!("RUNNER: submit_diagnostic");
+
+        // The party id is replaced by a random identifier so that the diagnostic can not be linked
+        // to a pool, a hash of the party id could be reversed from the public list of the pools
+        let party_id = self.services.single_signer.get_party_id();
+        let signer_id = self.get_or_create_diagnostics_signer_id()?;
+        let anonymize = |text: String| {
+            if party_id.is_empty() {
+                text
+            } else {
+                text.replace(&party_id, &signer_id)
+            }
+        };
+        let diagnostic = SignerDiagnosticMessage {
+            signer_node_version: env!("CARGO_PKG_VERSION").to_string(),
+            api_version: self
+                .services
+                .api_version_provider
+                .compute_current_version()?
+                .to_string(),
+            era: self.services.era_checker.current_era().to_string(),
+            current_state: anonymize(diagnostic.current_state),
+            last_errors: diagnostic
+                .last_errors
+                .into_iter()
+                .map(|error| SignerDiagnosticErrorMessage {
+                    message: anonymize(error.message),
+                    ..error
+                })
+                .collect(),
+            signer_id,
+            ..diagnostic
+        };
+
+        self.services
+            .certificate_handler
+            .submit_diagnostic(&diagnostic)
+            .await?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -456,24 +790,30 @@ mod tests {
         api_version::APIVersionProvider,
         cardano_block_scanner::DumbBlockScanner,
         chain_observer::{ChainObserver, FakeObserver},
-        crypto_helper::{MKMap, MKMapNode, MKTreeNode, ProtocolInitializer},
+        crypto_helper::{
+            verify_registration_nonce_signature, MKMap, MKMapNode, MKTreeNode, ProtocolInitializer,
+        },
         digesters::{DumbImmutableDigester, DumbImmutableFileObserver},
-        entities::{BlockRange, CardanoDbBeacon, Epoch, ImmutableFileNumber, StakeDistribution},
+        entities::{
+            BlockRange, CardanoDbBeacon, Epoch, ImmutableFileNumber, ProtocolMessagePartKey,
+            StakeDistribution,
+        },
         era::{
-            adapters::{EraReaderAdapterType, EraReaderBootstrapAdapter},
-            EraChecker, EraReader,
+            adapters::{EraReaderAdapterType, EraReaderBootstrapAdapter, EraReaderDummyAdapter},
+            EraChecker, EraMarker, EraReader,
         },
         signable_builder::{
             BlockRangeRootRetriever, CardanoImmutableFilesFullSignableBuilder,
             CardanoTransactionsSignableBuilder, MithrilSignableBuilderService,
             MithrilStakeDistributionSignableBuilder,
         },
-        test_utils::{fake_data, MithrilFixtureBuilder},
+        test_utils::{fake_data, MithrilFixtureBuilder, TempDir},
         TimePointProvider, TimePointProviderImpl,
     };
     use mithril_persistence::store::adapter::{DumbStoreAdapter, MemoryAdapter};
     use mithril_persistence::store::{StakeStore, StakeStorer};
     use mockall::mock;
+    use sha2::{Digest, Sha256};
     use std::{
         path::{Path, PathBuf},
         sync::Arc,
@@ -481,8 +821,8 @@ mod tests {
 
     use crate::{
         metrics::MetricsService, AggregatorClient, CardanoTransactionsImporter,
-        DumbAggregatorClient, MithrilSingleSigner, MockAggregatorClient, MockTransactionStore,
-        ProtocolInitializerStore, SingleSigner,
+        DumbAggregatorClient, MithrilProtocolInitializerBuilder, MithrilSingleSigner,
+        MockAggregatorClient, MockTransactionStore, ProtocolInitializerStore, SingleSigner,
     };
 
     use super::*;
@@ -579,12 +919,16 @@ mod tests {
             certificate_handler: Arc::new(DumbAggregatorClient::default()),
             chain_observer,
             digester,
-            single_signer: Arc::new(MithrilSingleSigner::new(party_id)),
+            single_signer: Arc::new(MithrilSingleSigner::new(party_id, era_checker.clone())),
             time_point_provider,
             protocol_initializer_store: Arc::new(ProtocolInitializerStore::new(
                 Box::new(adapter),
                 None,
             )),
+            next_era_protocol_initializer_store: Arc::new(ProtocolInitializerStore::new(
+                Box::new(MemoryAdapter::new(None).unwrap()),
+                None,
+            )),
             era_checker,
             era_reader,
             api_version_provider,
@@ -601,6 +945,7 @@ mod tests {
         let config = Configuration {
             aggregator_endpoint: "http://0.0.0.0:3000".to_string(),
             relay_endpoint: None,
+            relay_authorization: None,
             cardano_cli_path: PathBuf::new(),
             cardano_node_socket_path: PathBuf::new(),
             db_directory: PathBuf::new(),
@@ -620,6 +965,19 @@ mod tests {
             metrics_server_ip: "0.0.0.0".to_string(),
             metrics_server_port: 9090,
             allow_unparsable_block: false,
+            immutable_digester_threads: None,
+            component_log_levels: None,
+            admin_api_token: None,
+            enable_diagnostics_submission: false,
+            diagnostics_failure_threshold: 5,
+            diagnostics_submission_token: None,
+            cardano_node_config_path: None,
+            clock_skew_warning_threshold: 5,
+            ticker_sources: None,
+            disable_preflight_checks: true,
+            preflight_min_free_disk_space: None,
+            preflight_min_available_memory: None,
+            store_encryption_key: None,
         };
 
         SignerRunner::new(
@@ -738,6 +1096,186 @@ mod tests {
         );
     }
 
+    fn era_reader_switching_at(switch_epoch: Epoch) -> Arc<EraReader> {
+        Arc::new(EraReader::new(Arc::new(
+            EraReaderDummyAdapter::from_markers(vec![
+                EraMarker::new(&SupportedEra::Thales.to_string(), Some(Epoch(0))),
+                EraMarker::new(&SupportedEra::Pythagoras.to_string(), Some(switch_epoch)),
+            ]),
+        )))
+    }
+
+    async fn init_services_registering_at(
+        switch_epoch_offset: fn(Epoch) -> Epoch,
+    ) -> (SignerServices, Arc<DumbAggregatorClient>, Epoch) {
+        let mut services = init_services().await;
+        let certificate_handler = Arc::new(DumbAggregatorClient::default());
+        services.certificate_handler = certificate_handler.clone();
+        let epoch = services
+            .time_point_provider
+            .get_current_time_point()
+            .await
+            .unwrap()
+            .epoch;
+        services.era_reader = era_reader_switching_at(switch_epoch_offset(epoch));
+        let stakes = services
+            .chain_observer
+            .get_current_stake_distribution()
+            .await
+            .unwrap()
+            .unwrap();
+        services
+            .stake_store
+            .save_stakes(epoch.offset_to_recording_epoch(), stakes)
+            .await
+            .unwrap();
+
+        (services, certificate_handler, epoch)
+    }
+
+    #[tokio::test]
+    async fn register_signer_to_aggregator_ahead_of_an_era_switch_registers_the_keys_of_both_eras()
+    {
+        let (services, certificate_handler, epoch) =
+            init_services_registering_at(|epoch| epoch.offset_to_signer_signing_offset()).await;
+        let protocol_initializer_store = services.protocol_initializer_store.clone();
+        let next_era_protocol_initializer_store =
+            services.next_era_protocol_initializer_store.clone();
+        let recording_epoch = epoch.offset_to_recording_epoch();
+        let runner = init_runner(Some(services), None).await;
+
+        runner
+            .register_signer_to_aggregator(epoch, &fake_data::protocol_parameters())
+            .await
+            .unwrap();
+
+        let protocol_initializer = protocol_initializer_store
+            .get_protocol_initializer(recording_epoch)
+            .await
+            .unwrap()
+            .expect("the key of the current era should be kept");
+        let next_era_protocol_initializer = next_era_protocol_initializer_store
+            .get_protocol_initializer(recording_epoch)
+            .await
+            .unwrap()
+            .expect("the key of the next era should be kept");
+        let signer = certificate_handler
+            .get_last_registered_signer()
+            .await
+            .expect("the key of the current era should be registered");
+        let (next_era, next_era_signer) = certificate_handler
+            .get_last_registered_next_era_signer()
+            .await
+            .expect("the key of the next era should be registered");
+        assert_eq!(SupportedEra::Pythagoras, next_era);
+        assert_eq!(
+            signer.verification_key,
+            protocol_initializer.verification_key().into()
+        );
+        assert_eq!(
+            next_era_signer.verification_key,
+            next_era_protocol_initializer.verification_key().into()
+        );
+        assert_ne!(signer.verification_key, next_era_signer.verification_key);
+    }
+
+    #[tokio::test]
+    async fn register_signer_to_aggregator_the_epoch_before_an_era_switch_registers_a_single_key() {
+        let (services, certificate_handler, epoch) =
+            init_services_registering_at(|epoch| epoch.next()).await;
+        let protocol_initializer_store = services.protocol_initializer_store.clone();
+        let next_era_protocol_initializer_store =
+            services.next_era_protocol_initializer_store.clone();
+        let recording_epoch = epoch.offset_to_recording_epoch();
+        let runner = init_runner(Some(services), None).await;
+
+        runner
+            .register_signer_to_aggregator(epoch, &fake_data::protocol_parameters())
+            .await
+            .unwrap();
+
+        assert!(protocol_initializer_store
+            .get_protocol_initializer(recording_epoch)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(next_era_protocol_initializer_store
+            .get_protocol_initializer(recording_epoch)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(certificate_handler
+            .get_last_registered_signer()
+            .await
+            .is_some());
+        assert!(certificate_handler
+            .get_last_registered_next_era_signer()
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn update_era_checker_records_the_transition_and_the_upcoming_era() {
+        let mut services = init_services().await;
+        let era_checker = services.era_checker.clone();
+        services.era_reader = era_reader_switching_at(Epoch(10));
+        let runner = init_runner(Some(services), None).await;
+
+        runner.update_era_checker(Epoch(9)).await.unwrap();
+        assert_eq!(SupportedEra::Thales, era_checker.current_era());
+        assert_eq!(
+            SupportedEra::Pythagoras,
+            era_checker.era_of_epoch(Epoch(10))
+        );
+
+        runner.update_era_checker(Epoch(10)).await.unwrap();
+        assert_eq!(SupportedEra::Pythagoras, era_checker.current_era());
+        assert_eq!(Some(SupportedEra::Thales), era_checker.transition_era());
+        assert_eq!(SupportedEra::Thales, era_checker.era_of_epoch(Epoch(9)));
+    }
+
+    #[tokio::test]
+    async fn can_i_sign_at_the_era_switch_with_the_key_of_the_next_era() {
+        let mut pending_certificate = fake_data::certificate_pending();
+        let epoch = pending_certificate.epoch;
+        let signer = &mut pending_certificate.signers[0];
+        let mut services = init_services().await;
+        let protocol_initializer_store = services.protocol_initializer_store.clone();
+        let next_era_protocol_initializer_store =
+            services.next_era_protocol_initializer_store.clone();
+        services.era_reader = era_reader_switching_at(epoch);
+        services.single_signer = Arc::new(MithrilSingleSigner::new(
+            signer.party_id.to_owned(),
+            services.era_checker.clone(),
+        ));
+        let runner = init_runner(Some(services), None).await;
+        runner.update_era_checker(epoch).await.unwrap();
+
+        let retrieval_epoch = epoch.offset_to_signer_retrieval_epoch().unwrap();
+        let create_protocol_initializer = || {
+            MithrilProtocolInitializerBuilder::build(
+                &100,
+                &fake_data::protocol_parameters(),
+                None,
+                None,
+            )
+            .unwrap()
+        };
+        let protocol_initializer = create_protocol_initializer();
+        let next_era_protocol_initializer = create_protocol_initializer();
+        signer.verification_key = next_era_protocol_initializer.verification_key().into();
+        protocol_initializer_store
+            .save_protocol_initializer(retrieval_epoch, protocol_initializer)
+            .await
+            .unwrap();
+        next_era_protocol_initializer_store
+            .save_protocol_initializer(retrieval_epoch, next_era_protocol_initializer)
+            .await
+            .unwrap();
+
+        assert!(runner.can_i_sign(&pending_certificate).await.unwrap());
+    }
+
     #[tokio::test]
     async fn test_can_i_sign() {
         let mut pending_certificate = fake_data::certificate_pending();
@@ -745,7 +1283,10 @@ mod tests {
         let signer = &mut pending_certificate.signers[0];
         let mut services = init_services().await;
         let protocol_initializer_store = services.protocol_initializer_store.clone();
-        services.single_signer = Arc::new(MithrilSingleSigner::new(signer.party_id.to_owned()));
+        services.single_signer = Arc::new(MithrilSingleSigner::new(
+            signer.party_id.to_owned(),
+            services.era_checker.clone(),
+        ));
         let runner = init_runner(Some(services), None).await;
 
         let protocol_initializer = MithrilProtocolInitializerBuilder::build(
@@ -818,6 +1359,7 @@ mod tests {
         let protocol_initializer = fixture.signers_fixture()[0].protocol_initializer.clone();
         let single_signer = Arc::new(MithrilSingleSigner::new(
             signer_with_stake.party_id.to_owned(),
+            services.era_checker.clone(),
         ));
         services.single_signer = single_signer.clone();
         services
@@ -839,7 +1381,11 @@ mod tests {
         );
         let avk = services
             .single_signer
-            .compute_aggregate_verification_key(&next_signers, &protocol_initializer)
+            .compute_aggregate_verification_key(
+                current_time_point.epoch.next(),
+                &next_signers,
+                &protocol_initializer,
+            )
             .expect("compute_aggregate_verification_key should not fail")
             .expect("an avk should have been computed");
         expected.set_message_part(ProtocolMessagePartKey::NextAggregateVerificationKey, avk);
@@ -853,6 +1399,70 @@ mod tests {
         assert_eq!(expected, message);
     }
 
+    #[tokio::test]
+    async fn compute_message_fails_if_the_preflight_checks_fail() {
+        let services = init_services().await;
+        let metrics_service = services.metrics_service.clone();
+        let config = Configuration {
+            db_directory: PathBuf::from("/unreadable/cardano/db"),
+            disable_preflight_checks: false,
+            ..Configuration::new_sample(&"1".to_string())
+        };
+
+        let runner = init_runner(Some(services), Some(config)).await;
+        let error = runner
+            .compute_message(&SignedEntityType::MithrilStakeDistribution(Epoch(1)), &[])
+            .await
+            .expect_err("compute_message should fail");
+
+        assert!(
+            matches!(
+                error.downcast_ref::<RunnerError>(),
+                Some(RunnerError::PreflightChecksFailed(errors))
+                    if matches!(errors[..], [PreflightCheckError::DbDirectoryUnreadable { .. }])
+            ),
+            "unexpected error: {error:?}"
+        );
+        assert!(!metrics_service.preflight_checks_success_gauge_is_set());
+    }
+
+    #[tokio::test]
+    async fn compute_signed_certificate_metadata_with_a_recent_initiated_at() {
+        let config = Configuration::new_sample(&"1".to_string());
+        let runner = init_runner(None, Some(config)).await;
+        let initiated_at = Utc::now() - Duration::try_hours(1).unwrap();
+
+        let signed_metadata = runner
+            .compute_signed_certificate_metadata(&fake_data::protocol_parameters(), initiated_at)
+            .await
+            .expect("compute_signed_certificate_metadata should not fail");
+
+        assert_eq!(initiated_at, signed_metadata.initiated_at);
+    }
+
+    #[tokio::test]
+    async fn compute_signed_certificate_metadata_fails_with_an_initiated_at_far_from_now() {
+        let runner = init_runner(None, None).await;
+
+        for initiated_at in [
+            Utc::now() + Duration::try_hours(1).unwrap(),
+            Utc::now() - Duration::try_days(6).unwrap(),
+        ] {
+            let error = runner
+                .compute_signed_certificate_metadata(
+                    &fake_data::protocol_parameters(),
+                    initiated_at,
+                )
+                .await
+                .expect_err("compute_signed_certificate_metadata should fail");
+
+            assert_eq!(
+                Some(&RunnerError::InvalidCertificateInitiatedAt(initiated_at)),
+                error.downcast_ref::<RunnerError>()
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_compute_single_signature() {
         let mut services = init_services().await;
@@ -866,6 +1476,7 @@ mod tests {
         let protocol_initializer = fixture.signers_fixture()[0].protocol_initializer.clone();
         let single_signer = Arc::new(MithrilSingleSigner::new(
             signer_with_stake.party_id.to_string(),
+            services.era_checker.clone(),
         ));
         services.single_signer = single_signer.clone();
         services
@@ -891,33 +1502,105 @@ mod tests {
             "an avk".to_string(),
         );
 
+        let signed_entity_type =
+            SignedEntityType::MithrilStakeDistribution(current_time_point.epoch);
+
         let expected = single_signer
-            .compute_single_signatures(&message, &signers, &protocol_initializer)
+            .compute_single_signatures(
+                signed_entity_type.get_epoch(),
+                &message,
+                &signers,
+                &protocol_initializer,
+            )
             .expect("compute_single_signatures should not fail");
 
+        let metrics_service = services.metrics_service.clone();
         let runner = init_runner(Some(services), None).await;
         let single_signature = runner
-            .compute_single_signature(current_time_point.epoch, &message, &signers)
+            .compute_single_signature(
+                current_time_point.epoch,
+                &signed_entity_type,
+                &message,
+                &signers,
+            )
             .await
             .expect("compute_message should not fail");
         assert_eq!(expected, single_signature);
+        assert_eq!(
+            u32::from(expected.is_some()),
+            metrics_service.signature_computed_since_startup_counter_get()
+        );
     }
 
     #[tokio::test]
     async fn test_send_single_signature() {
         let mut services = init_services().await;
         let mut certificate_handler = MockAggregatorClient::new();
+        let single_signature =
+            fake_data::single_signatures(vec![2, 5, 12]).with_era(SupportedEra::dummy());
+        let expected_signature = single_signature.clone();
         certificate_handler
             .expect_register_signatures()
+            .withf(move |_, signature| signature == &expected_signature)
             .once()
             .returning(|_, _| Ok(()));
         services.certificate_handler = Arc::new(certificate_handler);
         let runner = init_runner(Some(services), None).await;
 
+        runner
+            .send_single_signature(&SignedEntityType::dummy(), Some(single_signature), None)
+            .await
+            .expect("send_single_signature should not fail");
+    }
+
+    #[tokio::test]
+    async fn test_send_single_signature_with_registration_nonce_signature() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(1).build();
+        let signer = &fixture.signers_fixture()[0];
+        let operational_certificate_path = TempDir::create(
+            "signer_runner",
+            "test_send_single_signature_with_registration_nonce_signature",
+        )
+        .join("opcert.cert");
+        OpCert::from(signer.operational_certificate().unwrap())
+            .to_file(&operational_certificate_path)
+            .unwrap();
+        let signer_with_stake = signer.signer_with_stake.clone();
+        let mut services = init_services().await;
+        let mut certificate_handler = MockAggregatorClient::new();
+        certificate_handler
+            .expect_register_signatures()
+            .withf(move |_, signatures| {
+                let message = compute_registration_nonce_message(
+                    "registration-nonce",
+                    &signatures.party_id,
+                    &signatures.signature.to_bytes(),
+                );
+                signatures
+                    .nonce_signature
+                    .as_ref()
+                    .is_some_and(|nonce_signature| {
+                        verify_registration_nonce_signature(
+                            nonce_signature,
+                            signer_with_stake.operational_certificate.as_ref().unwrap(),
+                            0,
+                            &message,
+                        )
+                        .is_ok()
+                    })
+            })
+            .once()
+            .returning(|_, _| Ok(()));
+        services.certificate_handler = Arc::new(certificate_handler);
+        let mut runner = init_runner(Some(services), None).await;
+        runner.config.kes_secret_key_path = signer.kes_secret_key_path().map(Path::to_path_buf);
+        runner.config.operational_certificate_path = Some(operational_certificate_path);
+
         runner
             .send_single_signature(
                 &SignedEntityType::dummy(),
                 Some(fake_data::single_signatures(vec![2, 5, 12])),
+                Some("registration-nonce".to_string()),
             )
             .await
             .expect("send_single_signature should not fail");
@@ -937,4 +1620,62 @@ mod tests {
 
         assert_eq!(time_point.epoch, era_checker.current_epoch());
     }
+
+    #[tokio::test]
+    async fn test_submit_diagnostic_anonymize_the_party_id() {
+        let data_stores_directory = TempDir::create(
+            "signer_runner",
+            "test_submit_diagnostic_anonymize_the_party_id",
+        );
+        let mut services = init_services().await;
+        let party_id = services.single_signer.get_party_id();
+        let submitted_signer_ids = Arc::new(std::sync::Mutex::new(vec![]));
+        let mut certificate_handler = MockAggregatorClient::new();
+        let expected_party_id = party_id.clone();
+        let submitted = submitted_signer_ids.clone();
+        certificate_handler
+            .expect_submit_diagnostic()
+            .withf(move |diagnostic| {
+                diagnostic.signer_id != hex::encode(Sha256::digest(expected_party_id.as_bytes()))
+                    && diagnostic.signer_node_version == env!("CARGO_PKG_VERSION")
+                    && !diagnostic.last_errors[0]
+                        .message
+                        .contains(&expected_party_id)
+            })
+            .times(2)
+            .returning(move |diagnostic| {
+                submitted.lock().unwrap().push(diagnostic.signer_id.clone());
+                Ok(())
+            });
+        services.certificate_handler = Arc::new(certificate_handler);
+        let mut runner = init_runner(Some(services), None).await;
+        runner.config.data_stores_directory = data_stores_directory.clone();
+
+        for _ in 0..2 {
+            runner
+                .submit_diagnostic(SignerDiagnosticMessage {
+                    last_errors: vec![SignerDiagnosticErrorMessage {
+                        message: format!(
+                            "No stake associated with this signer, party_id: {party_id}."
+                        ),
+                        is_critical: false,
+                        occurred_at: chrono::Utc::now(),
+                    }],
+                    ..SignerDiagnosticMessage::dummy()
+                })
+                .await
+                .unwrap();
+        }
+
+        let submitted_signer_ids = submitted_signer_ids.lock().unwrap();
+        assert_eq!(
+            submitted_signer_ids[0], submitted_signer_ids[1],
+            "the random signer id should be kept between the diagnostics"
+        );
+        assert_eq!(
+            submitted_signer_ids[0],
+            std::fs::read_to_string(data_stores_directory.join(DIAGNOSTICS_SIGNER_ID_FILE_NAME))
+                .unwrap()
+        );
+    }
 }
@@ -6,12 +6,13 @@ use mithril_common::{
     api_version::APIVersionProvider,
     cardano_block_scanner::CardanoBlockScanner,
     chain_observer::{CardanoCliRunner, ChainObserver, ChainObserverBuilder, ChainObserverType},
-    crypto_helper::{OpCert, ProtocolPartyId, SerDeShelleyFileFormat},
+    crypto_helper::{OpCert, ProtocolInitializer, ProtocolPartyId, SerDeShelleyFileFormat},
     digesters::{
         cache::{ImmutableFileDigestCacheProvider, JsonImmutableFileDigestCacheProviderBuilder},
         CardanoImmutableDigester, ImmutableDigester, ImmutableFileObserver,
         ImmutableFileSystemObserver,
     },
+    entities::Epoch,
     era::{EraChecker, EraReader},
     signable_builder::{
         CardanoImmutableFilesFullSignableBuilder, CardanoTransactionsSignableBuilder,
@@ -23,14 +24,17 @@ use mithril_common::{
 use mithril_persistence::{
     database::{ApplicationNodeType, SqlMigration},
     sqlite::{ConnectionBuilder, SqliteConnection},
-    store::{adapter::SQLiteAdapter, StakeStore},
+    store::{
+        adapter::{EncryptedStoreAdapter, SQLiteAdapter, StoreAdapter},
+        StakeStore,
+    },
 };
 
 use crate::{
     aggregator_client::AggregatorClient, database::repository::CardanoTransactionRepository,
     metrics::MetricsService, single_signer::SingleSigner, AggregatorHTTPClient,
     CardanoTransactionsImporter, Configuration, MithrilSingleSigner, ProtocolInitializerStore,
-    ProtocolInitializerStorer, HTTP_REQUEST_TIMEOUT_DURATION, SQLITE_FILE,
+    ProtocolInitializerStorer, RequestOriginSigner, HTTP_REQUEST_TIMEOUT_DURATION, SQLITE_FILE,
     SQLITE_FILE_CARDANO_TRANSACTION,
 };
 
@@ -41,6 +45,7 @@ type DigesterService = Arc<dyn ImmutableDigester>;
 type SingleSignerService = Arc<dyn SingleSigner>;
 type TimePointProviderService = Arc<dyn TimePointProvider>;
 type ProtocolInitializerStoreService = Arc<dyn ProtocolInitializerStorer>;
+type ProtocolInitializerAdapter = Box<dyn StoreAdapter<Key = Epoch, Record = ProtocolInitializer>>;
 
 /// The ServiceBuilder is intended to manage Services instance creation.
 /// The goal of this is to put all this code out of the way of business code.
@@ -141,6 +146,21 @@ impl<'a> ProductionServiceBuilder<'a> {
         }
     }
 
+    /// Build the signer of the origin of the requests sent to the aggregator.
+    fn build_request_origin_signer(
+        &self,
+        era_checker: Arc<EraChecker>,
+        protocol_initializer_store: Arc<dyn ProtocolInitializerStorer>,
+        next_era_protocol_initializer_store: Arc<dyn ProtocolInitializerStorer>,
+    ) -> StdResult<Arc<RequestOriginSigner>> {
+        Ok(Arc::new(RequestOriginSigner::new(
+            self.compute_protocol_party_id()?,
+            era_checker,
+            protocol_initializer_store,
+            next_era_protocol_initializer_store,
+        )))
+    }
+
     async fn build_digester_cache_provider(
         &self,
     ) -> StdResult<Option<Arc<dyn ImmutableFileDigestCacheProvider>>> {
@@ -176,12 +196,8 @@ impl<'a> ProductionServiceBuilder<'a> {
 
         Ok(Arc::new(connection))
     }
-}
 
-#[async_trait]
-impl<'a> ServiceBuilder for ProductionServiceBuilder<'a> {
-    /// Build a Services for the Production environment.
-    async fn build(&self) -> StdResult<SignerServices> {
+    fn create_data_stores_directory(&self) -> StdResult<()> {
         if !self.config.data_stores_directory.exists() {
             fs::create_dir_all(self.config.data_stores_directory.clone()).with_context(|| {
                 format!(
@@ -191,6 +207,96 @@ impl<'a> ServiceBuilder for ProductionServiceBuilder<'a> {
             })?;
         }
 
+        Ok(())
+    }
+
+    /// Build the adapter of a protocol initializer store, encrypting its records if a store
+    /// encryption key is configured.
+    fn build_protocol_initializer_adapter(
+        &self,
+        table_name: &str,
+        sqlite_connection: Arc<SqliteConnection>,
+    ) -> StdResult<ProtocolInitializerAdapter> {
+        let adapter: ProtocolInitializerAdapter = match self.config.build_store_cipher()? {
+            Some(cipher) => Box::new(EncryptedStoreAdapter::new(
+                Box::new(SQLiteAdapter::new(table_name, sqlite_connection)?),
+                cipher,
+                table_name,
+            )),
+            None => Box::new(SQLiteAdapter::new(table_name, sqlite_connection)?),
+        };
+
+        Ok(adapter)
+    }
+
+    fn build_protocol_initializer_store_with_connection(
+        &self,
+        sqlite_connection: Arc<SqliteConnection>,
+    ) -> StdResult<ProtocolInitializerStoreService> {
+        Ok(Arc::new(ProtocolInitializerStore::new(
+            self.build_protocol_initializer_adapter("protocol_initializer", sqlite_connection)?,
+            self.config.store_retention_limit,
+        )))
+    }
+
+    fn build_next_era_protocol_initializer_store_with_connection(
+        &self,
+        sqlite_connection: Arc<SqliteConnection>,
+    ) -> StdResult<ProtocolInitializerStoreService> {
+        Ok(Arc::new(ProtocolInitializerStore::new(
+            self.build_protocol_initializer_adapter(
+                "next_era_protocol_initializer",
+                sqlite_connection,
+            )?,
+            self.config.store_retention_limit,
+        )))
+    }
+
+    /// Build the protocol initializer store alone, used to backup and restore the protocol
+    /// initializers.
+    pub async fn build_protocol_initializer_store(
+        &self,
+    ) -> StdResult<ProtocolInitializerStoreService> {
+        self.create_data_stores_directory()?;
+        let sqlite_connection = self
+            .build_sqlite_connection(SQLITE_FILE, crate::database::migration::get_migrations())
+            .await?;
+
+        self.build_protocol_initializer_store_with_connection(sqlite_connection)
+    }
+
+    /// Build the chain observer alone, used to unregister the signer.
+    pub fn build_chain_observer(&self) -> StdResult<ChainObserverService> {
+        let builder = self.chain_observer_builder;
+        builder(self.config)
+    }
+
+    /// Build an [EraChecker] set to the era of the current epoch read by the era reader, used to
+    /// unregister the signer.
+    pub async fn build_era_checker(
+        &self,
+        chain_observer: ChainObserverService,
+    ) -> StdResult<Arc<EraChecker>> {
+        let current_epoch = chain_observer
+            .get_current_epoch()
+            .await?
+            .ok_or_else(|| anyhow!("The chain observer did not return the current epoch"))?;
+        let era_reader = EraReader::new(self.config.build_era_reader_adapter(chain_observer)?);
+        let era_epoch_token = era_reader.read_era_epoch_token(current_epoch).await?;
+
+        Ok(Arc::new(EraChecker::new(
+            era_epoch_token.get_current_supported_era()?,
+            era_epoch_token.get_current_epoch(),
+        )))
+    }
+}
+
+#[async_trait]
+impl<'a> ServiceBuilder for ProductionServiceBuilder<'a> {
+    /// Build a Services for the Production environment.
+    async fn build(&self) -> StdResult<SignerServices> {
+        self.create_data_stores_directory()?;
+
         let sqlite_connection = self
             .build_sqlite_connection(SQLITE_FILE, crate::database::migration::get_migrations())
             .await?;
@@ -201,18 +307,18 @@ impl<'a> ServiceBuilder for ProductionServiceBuilder<'a> {
             )
             .await?;
 
-        let protocol_initializer_store = Arc::new(ProtocolInitializerStore::new(
-            Box::new(SQLiteAdapter::new(
-                "protocol_initializer",
-                sqlite_connection.clone(),
-            )?),
-            self.config.store_retention_limit,
-        ));
-        let single_signer = Arc::new(MithrilSingleSigner::new(self.compute_protocol_party_id()?));
-        let digester = Arc::new(CardanoImmutableDigester::new(
+        let protocol_initializer_store =
+            self.build_protocol_initializer_store_with_connection(sqlite_connection.clone())?;
+        let next_era_protocol_initializer_store = self
+            .build_next_era_protocol_initializer_store_with_connection(sqlite_connection.clone())?;
+        let mut digester = CardanoImmutableDigester::new(
             self.build_digester_cache_provider().await?,
             slog_scope::logger(),
-        ));
+        );
+        if let Some(number_of_threads) = self.config.immutable_digester_threads {
+            digester = digester.with_thread_pool_size(number_of_threads)?;
+        }
+        let digester = Arc::new(digester);
         let stake_store = Arc::new(StakeStore::new(
             Box::new(SQLiteAdapter::new("stake", sqlite_connection)?),
             self.config.store_retention_limit,
@@ -241,13 +347,30 @@ impl<'a> ServiceBuilder for ProductionServiceBuilder<'a> {
             era_epoch_token.get_current_epoch(),
         ));
 
-        let api_version_provider = Arc::new(APIVersionProvider::new(era_checker.clone()));
-        let certificate_handler = Arc::new(AggregatorHTTPClient::new(
-            self.config.aggregator_endpoint.clone(),
-            self.config.relay_endpoint.clone(),
-            api_version_provider.clone(),
-            Some(Duration::from_millis(HTTP_REQUEST_TIMEOUT_DURATION)),
+        let single_signer = Arc::new(MithrilSingleSigner::new(
+            self.compute_protocol_party_id()?,
+            era_checker.clone(),
         ));
+        let api_version_provider = Arc::new(APIVersionProvider::new(era_checker.clone()));
+        let request_origin_signer = self.build_request_origin_signer(
+            era_checker.clone(),
+            protocol_initializer_store.clone(),
+            next_era_protocol_initializer_store.clone(),
+        )?;
+        let certificate_handler = Arc::new(
+            AggregatorHTTPClient::new(
+                self.config.aggregator_endpoint.clone(),
+                self.config.relay_endpoint.clone(),
+                api_version_provider.clone(),
+                Some(Duration::from_millis(HTTP_REQUEST_TIMEOUT_DURATION)),
+            )
+            .with_relay_authorization(self.config.relay_authorization.clone())
+            .with_diagnostics_submission_token(self.config.diagnostics_submission_token.clone())
+            .with_clock_skew_warning_threshold(Some(Duration::from_secs(
+                self.config.clock_skew_warning_threshold,
+            )))
+            .with_request_origin_signer(Some(request_origin_signer)),
+        );
 
         let cardano_immutable_snapshot_builder =
             Arc::new(CardanoImmutableFilesFullSignableBuilder::new(
@@ -295,6 +418,7 @@ impl<'a> ServiceBuilder for ProductionServiceBuilder<'a> {
             single_signer,
             stake_store,
             protocol_initializer_store,
+            next_era_protocol_initializer_store,
             era_checker,
             era_reader,
             api_version_provider,
@@ -329,6 +453,9 @@ pub struct SignerServices {
     /// ProtocolInitializer store
     pub protocol_initializer_store: ProtocolInitializerStoreService,
 
+    /// ProtocolInitializer store of the keys registered ahead of an era switch
+    pub next_era_protocol_initializer_store: ProtocolInitializerStoreService,
+
     /// Era checker service
     pub era_checker: Arc<EraChecker>,
 
@@ -350,7 +477,7 @@ mod tests {
     use mithril_common::{
         chain_observer::FakeObserver,
         digesters::DumbImmutableFileObserver,
-        entities::{Epoch, TimePoint},
+        entities::{ChainPoint, Epoch, TimePoint},
         era::adapters::EraReaderAdapterType,
         test_utils::TempDir,
     };
@@ -373,6 +500,7 @@ mod tests {
             network: "preview".to_string(),
             aggregator_endpoint: "".to_string(),
             relay_endpoint: None,
+            relay_authorization: None,
             party_id: Some("party-123456".to_string()),
             run_interval: 1000,
             db_directory: PathBuf::new(),
@@ -388,6 +516,19 @@ mod tests {
             metrics_server_ip: "0.0.0.0".to_string(),
             metrics_server_port: 9090,
             allow_unparsable_block: false,
+            immutable_digester_threads: None,
+            component_log_levels: None,
+            admin_api_token: None,
+            enable_diagnostics_submission: false,
+            diagnostics_failure_threshold: 5,
+            diagnostics_submission_token: None,
+            cardano_node_config_path: None,
+            clock_skew_warning_threshold: 5,
+            ticker_sources: None,
+            disable_preflight_checks: true,
+            preflight_min_free_disk_space: None,
+            preflight_min_available_memory: None,
+            store_encryption_key: None,
         };
 
         assert!(!stores_dir.exists());
@@ -396,6 +537,7 @@ mod tests {
                 Ok(Arc::new(FakeObserver::new(Some(TimePoint {
                     epoch: Epoch(1),
                     immutable_file_number: 1,
+                    chain_point: ChainPoint::dummy(),
                 }))))
             };
         let immutable_file_observer_builder: fn(
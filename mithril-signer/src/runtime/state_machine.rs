@@ -1,17 +1,22 @@
-use slog_scope::{crit, debug, error, info};
-use std::{fmt::Display, ops::Deref, sync::Arc, time::Duration};
+use slog_scope::{crit, debug, error, info, warn};
+use std::{collections::BTreeMap, fmt::Display, ops::Deref, sync::Arc, time::Duration};
 use tokio::{sync::Mutex, time::sleep};
 
 use mithril_common::{
     crypto_helper::ProtocolInitializerError,
     entities::{
-        CertificatePending, Epoch, EpochSettings, SignedEntityType, SignerWithStake, TimePoint,
+        CertificatePending, Epoch, EpochSettings, ProtocolFeature, ProtocolFeatureActivations,
+        ProtocolMessagePart, SignedEntityType, SignedEntityTypeDiscriminants, SignerWithStake,
+        TimePoint,
     },
 };
 
 use crate::MetricsService;
 
-use super::{Runner, RuntimeError};
+use super::{
+    DiagnosticsRecorder, Runner, RuntimeError, SignedEntityTypeTickers,
+    StateMachineTransitionListener, StateTransition,
+};
 
 /// Different possible states of the state machine.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -91,6 +96,12 @@ pub struct StateMachine {
     runner: Box<dyn Runner>,
     state_sleep: Duration,
     metrics_service: Arc<MetricsService>,
+    diagnostics_recorder: Option<Mutex<DiagnosticsRecorder>>,
+    protocol_feature_activations: Mutex<ProtocolFeatureActivations>,
+    registration_nonce: Mutex<Option<String>>,
+    transition_listeners: Vec<Arc<dyn StateMachineTransitionListener>>,
+    tickers: SignedEntityTypeTickers,
+    signed_time_points: Mutex<BTreeMap<SignedEntityTypeDiscriminants, TimePoint>>,
 }
 
 impl StateMachine {
@@ -101,14 +112,47 @@ impl StateMachine {
         state_sleep: Duration,
         metrics_service: Arc<MetricsService>,
     ) -> Self {
+        metrics_service.runtime_state_gauges_set(&starting_state);
+
         Self {
             state: Mutex::new(starting_state),
             runner,
             state_sleep,
             metrics_service,
+            diagnostics_recorder: None,
+            protocol_feature_activations: Mutex::new(ProtocolFeatureActivations::new()),
+            registration_nonce: Mutex::new(None),
+            transition_listeners: vec![],
+            tickers: SignedEntityTypeTickers::new(),
+            signed_time_points: Mutex::new(BTreeMap::new()),
         }
     }
 
+    /// Submit a diagnostic to the aggregator when the number of consecutive failed cycles reaches
+    /// the given threshold or when a critical error occurs.
+    pub fn with_diagnostics_submission(mut self, failure_threshold: u64) -> Self {
+        self.diagnostics_recorder = Some(Mutex::new(DiagnosticsRecorder::new(failure_threshold)));
+        self
+    }
+
+    /// Notify the given listener of each transition of the state machine.
+    pub fn with_transition_listener(
+        mut self,
+        listener: Arc<dyn StateMachineTransitionListener>,
+    ) -> Self {
+        self.transition_listeners.push(listener);
+        self
+    }
+
+    /// Tick the signing rounds of the signed entity types with the given tickers.
+    ///
+    /// Once it has signed, the state machine only signs a new pending certificate when the beacon
+    /// of the ticker of its signed entity type has advanced since this type was last signed.
+    pub fn with_tickers(mut self, tickers: SignedEntityTypeTickers) -> Self {
+        self.tickers = tickers;
+        self
+    }
+
     /// Return the current state of the state machine.
     pub async fn get_state(&self) -> SignerState {
         self.state.lock().await.to_owned()
@@ -119,7 +163,10 @@ impl StateMachine {
         info!("STATE MACHINE: launching");
 
         loop {
-            if let Err(e) = self.cycle().await {
+            let cycle_result = self.cycle().await;
+            self.record_diagnostics(&cycle_result).await;
+
+            if let Err(e) = cycle_result {
                 if e.is_critical() {
                     crit!("{e}");
 
@@ -137,9 +184,40 @@ impl StateMachine {
         }
     }
 
+    /// Record the outcome of a cycle and submit a diagnostic if the signer fails persistently.
+    ///
+    /// Failing to submit a diagnostic must not stop the state machine.
+    async fn record_diagnostics(&self, cycle_result: &Result<(), RuntimeError>) {
+        let Some(diagnostics_recorder) = &self.diagnostics_recorder else {
+            return;
+        };
+        let state = self.get_state().await;
+        let mut diagnostics_recorder = diagnostics_recorder.lock().await;
+        diagnostics_recorder.record_state(&state);
+
+        match cycle_result {
+            Ok(()) => diagnostics_recorder.record_success(),
+            Err(error) => {
+                if diagnostics_recorder.record_failure(error) {
+                    let diagnostic = diagnostics_recorder.build_diagnostic(&state);
+                    match self.runner.submit_diagnostic(diagnostic).await {
+                        Ok(()) => {
+                            info!("STATE MACHINE: diagnostic submitted to the aggregator");
+                            diagnostics_recorder.mark_as_submitted();
+                        }
+                        Err(e) => {
+                            warn!("STATE MACHINE: could not submit diagnostic"; "error" => ?e)
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     /// Perform a cycle of the state machine.
     pub async fn cycle(&self) -> Result<(), RuntimeError> {
         let mut state = self.state.lock().await;
+        let previous_state = state.clone();
         info!("================================================================================");
         info!("STATE MACHINE: new cycle: {}", *state);
 
@@ -184,10 +262,13 @@ impl StateMachine {
                 }
             }
             SignerState::Registered { epoch } => {
-                if let Some(new_epoch) = self.has_epoch_changed(*epoch).await? {
+                let current_time_point = self
+                    .get_current_time_point("checking if epoch has changed")
+                    .await?;
+                if current_time_point.epoch > *epoch {
                     info!("→ Epoch has changed, transiting to UNREGISTERED");
                     *state = self
-                        .transition_from_registered_to_unregistered(new_epoch)
+                        .transition_from_registered_to_unregistered(current_time_point.epoch)
                         .await?;
                 } else if let Some(pending_certificate) =
                     self.runner.get_pending_certificate().await.map_err(|e| {
@@ -213,7 +294,10 @@ impl StateMachine {
                     {
                         info!(" → we can sign this certificate, transiting to SIGNED");
                         *state = self
-                            .transition_from_registered_to_signed(&pending_certificate)
+                            .transition_from_registered_to_signed(
+                                &pending_certificate,
+                                current_time_point,
+                            )
                             .await?;
                     } else {
                         info!(" ⋅ cannot sign this pending certificate, waiting…");
@@ -226,10 +310,13 @@ impl StateMachine {
                 epoch,
                 signed_entity_type,
             } => {
-                if let Some(new_epoch) = self.has_epoch_changed(*epoch).await? {
+                let current_time_point = self
+                    .get_current_time_point("checking if epoch has changed")
+                    .await?;
+                if current_time_point.epoch > *epoch {
                     info!(" → new Epoch detected, transiting to UNREGISTERED");
                     *state = self
-                        .transition_from_signed_to_unregistered(new_epoch)
+                        .transition_from_signed_to_unregistered(current_time_point.epoch)
                         .await?;
                 } else if let Some(pending_certificate) =
                     self.runner.get_pending_certificate().await.map_err(|e| {
@@ -245,6 +332,14 @@ impl StateMachine {
                     );
                     if pending_certificate.signed_entity_type == *signed_entity_type {
                         info!(" ⋅ pending certificate has not changed, waiting…");
+                    } else if !self
+                        .has_beacon_advanced(
+                            &pending_certificate.signed_entity_type,
+                            &current_time_point,
+                        )
+                        .await
+                    {
+                        info!(" ⋅ the beacon of the pending signed entity type has not advanced since its last signature, waiting…");
                     } else {
                         info!(" → new pending certificate detected, transiting to REGISTERED");
                         *state = self.transition_from_signed_to_registered(*epoch).await?;
@@ -255,12 +350,24 @@ impl StateMachine {
             }
         };
 
+        if *state != previous_state {
+            self.notify_transition(&previous_state, &state);
+        }
+
         self.metrics_service
             .runtime_cycle_success_since_startup_counter_increment();
 
         Ok(())
     }
 
+    fn notify_transition(&self, from: &SignerState, to: &SignerState) {
+        self.metrics_service.runtime_state_gauges_set(to);
+        let transition = StateTransition::new(from, to);
+        for listener in &self.transition_listeners {
+            listener.on_transition(&transition);
+        }
+    }
+
     /// Return the new epoch if the epoch is different than the given one.
     async fn has_epoch_changed(&self, epoch: Epoch) -> Result<Option<Epoch>, RuntimeError> {
         let current_time_point = self
@@ -274,6 +381,27 @@ impl StateMachine {
         }
     }
 
+    /// Check if the beacon of the given signed entity type has advanced since the last signature
+    /// of this signed entity type.
+    async fn has_beacon_advanced(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        current_time_point: &TimePoint,
+    ) -> bool {
+        match self
+            .signed_time_points
+            .lock()
+            .await
+            .get(&SignedEntityTypeDiscriminants::from(signed_entity_type))
+        {
+            Some(signed_time_point) => {
+                self.tickers
+                    .has_advanced(signed_entity_type, signed_time_point, current_time_point)
+            }
+            None => true,
+        }
+    }
+
     async fn transition_from_unregistered_to_unregistered(
         &self,
         new_epoch: Epoch,
@@ -354,6 +482,10 @@ impl StateMachine {
             }
         })?;
 
+        *self.protocol_feature_activations.lock().await =
+            epoch_settings.protocol_feature_activations.clone();
+        *self.registration_nonce.lock().await = epoch_settings.registration_nonce.clone();
+
         self.metrics_service
             .signer_registration_success_since_startup_counter_increment();
         self.metrics_service
@@ -366,6 +498,7 @@ impl StateMachine {
     async fn transition_from_registered_to_signed(
         &self,
         pending_certificate: &CertificatePending,
+        current_time_point: TimePoint,
     ) -> Result<SignerState, RuntimeError> {
         let current_epoch = pending_certificate.epoch;
         let (retrieval_epoch, next_retrieval_epoch) = (
@@ -400,7 +533,7 @@ impl StateMachine {
                 nested_error: Some(e)
             })?;
 
-        let message = self
+        let mut message = self
             .runner
             .compute_message(&pending_certificate.signed_entity_type, &next_signers)
             .await
@@ -408,15 +541,86 @@ impl StateMachine {
                 message: format!("Could not compute message during 'registered → signed' phase (current epoch {current_epoch:?})"),
                 nested_error: Some(e)
             })?;
+        if !pending_certificate.batched_signed_entity_types.is_empty()
+            && !self
+                .protocol_feature_activations
+                .lock()
+                .await
+                .is_active(ProtocolFeature::BatchedSignedEntityTypes, current_epoch)
+        {
+            return Err(RuntimeError::KeepState {
+                message: format!("Could not sign a pending certificate with batched signed entity types during 'registered → signed' phase, the feature is not active (current epoch {current_epoch:?})"),
+                nested_error: None,
+            });
+        }
+        let mut batched_messages = vec![];
+        for batched_signed_entity_type in &pending_certificate.batched_signed_entity_types {
+            let batched_message = self
+                .runner
+                .compute_message(batched_signed_entity_type, &next_signers)
+                .await
+                .map_err(|e| RuntimeError::KeepState {
+                    message: format!("Could not compute message of batched signed entity type '{batched_signed_entity_type}' during 'registered → signed' phase (current epoch {current_epoch:?})"),
+                    nested_error: Some(e)
+                })?;
+            batched_messages.push((batched_signed_entity_type.to_owned(), batched_message));
+        }
+        message
+            .batch(&batched_messages)
+            .map_err(|e| RuntimeError::KeepState {
+                message: format!("Could not batch messages during 'registered → signed' phase (current epoch {current_epoch:?})"),
+                nested_error: Some(e)
+            })?;
+        let is_signed_metadata_active = self
+            .protocol_feature_activations
+            .lock()
+            .await
+            .is_active_in_era(
+                ProtocolFeature::SignedCertificateMetadata,
+                current_epoch,
+                self.runner
+                    .get_era_of_epoch(pending_certificate.signed_entity_type.get_epoch()),
+            );
+        if let Some(initiated_at) = pending_certificate.initiated_at {
+            if !is_signed_metadata_active {
+                return Err(RuntimeError::KeepState {
+                    message: format!("Could not sign a pending certificate with signed metadata during 'registered → signed' phase, the feature is not active (current epoch {current_epoch:?})"),
+                    nested_error: None,
+                });
+            }
+            let signed_metadata = self
+                .runner
+                .compute_signed_certificate_metadata(
+                    &pending_certificate.protocol_parameters,
+                    initiated_at,
+                )
+                .await
+                .map_err(|e| RuntimeError::KeepState {
+                    message: format!("Could not compute the signed certificate metadata during 'registered → signed' phase (current epoch {current_epoch:?})"),
+                    nested_error: Some(e)
+                })?;
+            message
+                .set_part(ProtocolMessagePart::SignedCertificateMetadata(signed_metadata))
+                .map_err(|e| RuntimeError::KeepState {
+                    message: format!("Could not set the signed certificate metadata in the message during 'registered → signed' phase (current epoch {current_epoch:?})"),
+                    nested_error: Some(e)
+                })?;
+        } else if is_signed_metadata_active {
+            return Err(RuntimeError::KeepState {
+                message: format!("Could not sign a pending certificate without signed metadata during 'registered → signed' phase, the feature is active (current epoch {current_epoch:?})"),
+                nested_error: None,
+            });
+        }
         let single_signatures = self
             .runner
-            .compute_single_signature(current_epoch, &message, &signers)
+            .compute_single_signature(current_epoch, &pending_certificate.signed_entity_type, &message, &signers)
             .await
             .map_err(|e| RuntimeError::KeepState {
                 message: format!("Could not compute single signature during 'registered → signed' phase (current epoch {current_epoch:?})"),
                 nested_error: Some(e)
             })?;
-        self.runner.send_single_signature(&pending_certificate.signed_entity_type, single_signatures).await
+        let registration_nonce = self.registration_nonce.lock().await.clone();
+        self.runner.send_single_signature(&pending_certificate.signed_entity_type, single_signatures, registration_nonce).await
             .map_err(|e| RuntimeError::KeepState {
                 message: format!("Could not send single signature during 'registered → signed' phase (current epoch {current_epoch:?})"),
                 nested_error: Some(e)
@@ -426,6 +630,10 @@ impl StateMachine {
             .signature_registration_success_since_startup_counter_increment();
         self.metrics_service
             .signature_registration_success_last_epoch_gauge_set(current_epoch);
+        self.signed_time_points.lock().await.insert(
+            SignedEntityTypeDiscriminants::from(&pending_certificate.signed_entity_type),
+            current_time_point,
+        );
 
         Ok(SignerState::Signed {
             epoch: current_epoch,
@@ -463,13 +671,20 @@ impl StateMachine {
 
 #[cfg(test)]
 mod tests {
+    use chrono::Utc;
+    use mockall::predicate;
+
     use mithril_common::{
-        entities::{CardanoDbBeacon, Epoch, ProtocolMessage},
+        entities::{
+            CardanoDbBeacon, ChainPoint, Epoch, ProtocolMessage, SignedCertificateMetadata,
+        },
+        era::SupportedEra,
         test_utils::fake_data,
     };
 
     use super::*;
     use crate::runtime::runner::MockSignerRunner;
+    use crate::runtime::TransitionsRecorder;
 
     fn init_state_machine(init_state: SignerState, runner: MockSignerRunner) -> StateMachine {
         let metrics_service = Arc::new(MetricsService::new().unwrap());
@@ -478,6 +693,12 @@ mod tests {
             runner: Box::new(runner),
             state_sleep: Duration::from_millis(100),
             metrics_service,
+            diagnostics_recorder: None,
+            protocol_feature_activations: Mutex::new(ProtocolFeatureActivations::new()),
+            registration_nonce: Mutex::new(None),
+            transition_listeners: vec![],
+            tickers: SignedEntityTypeTickers::new(),
+            signed_time_points: Mutex::new(BTreeMap::new()),
         }
     }
 
@@ -518,6 +739,9 @@ mod tests {
             epoch: Epoch(3),
             protocol_parameters: fake_data::protocol_parameters(),
             next_protocol_parameters: fake_data::protocol_parameters(),
+            protocol_feature_activations: ProtocolFeatureActivations::new(),
+            registration_nonce: None,
+            upcoming_signers: None,
         };
         let known_epoch = Epoch(4);
         runner
@@ -546,10 +770,12 @@ mod tests {
     #[tokio::test]
     async fn unregistered_to_registered() {
         let mut runner = MockSignerRunner::new();
-        runner
-            .expect_get_epoch_settings()
-            .once()
-            .returning(|| Ok(Some(fake_data::epoch_settings())));
+        runner.expect_get_epoch_settings().once().returning(|| {
+            Ok(Some(EpochSettings {
+                registration_nonce: Some("registration-nonce".to_string()),
+                ..fake_data::epoch_settings()
+            }))
+        });
         runner
             .expect_get_current_time_point()
             .times(2)
@@ -582,6 +808,82 @@ mod tests {
                 state_machine.get_state().await
             );
         }
+        assert_eq!(
+            Some("registration-nonce".to_string()),
+            *state_machine.registration_nonce.lock().await
+        );
+    }
+
+    #[tokio::test]
+    async fn transition_is_notified_to_the_listeners() {
+        let mut runner = MockSignerRunner::new();
+        runner
+            .expect_get_current_time_point()
+            .once()
+            .returning(|| Ok(TimePoint::dummy()));
+        runner
+            .expect_update_era_checker()
+            .once()
+            .returning(|_e: Epoch| Ok(()));
+        let transitions_recorder = Arc::new(TransitionsRecorder::default());
+        let state_machine = init_state_machine(SignerState::Init, runner)
+            .with_transition_listener(transitions_recorder.clone());
+
+        state_machine
+            .cycle()
+            .await
+            .expect("Cycling the state machine should not fail");
+
+        let transitions = transitions_recorder.last_transitions();
+        assert_eq!(1, transitions.len());
+        assert_eq!(SignerState::Init.to_string(), transitions[0].from);
+        assert_eq!(
+            SignerState::Unregistered {
+                epoch: TimePoint::dummy().epoch
+            }
+            .to_string(),
+            transitions[0].to
+        );
+        assert_eq!("signer started", transitions[0].reason);
+        assert!(state_machine.metrics_service.runtime_state_gauge_is_set(
+            &SignerState::Unregistered {
+                epoch: TimePoint::dummy().epoch
+            }
+        ));
+        assert!(!state_machine
+            .metrics_service
+            .runtime_state_gauge_is_set(&SignerState::Init));
+    }
+
+    #[tokio::test]
+    async fn no_transition_is_notified_when_the_state_is_kept() {
+        let mut runner = MockSignerRunner::new();
+        runner
+            .expect_get_epoch_settings()
+            .once()
+            .returning(|| Ok(None));
+        runner
+            .expect_get_current_time_point()
+            .once()
+            .returning(|| Ok(TimePoint::dummy()));
+        let transitions_recorder = Arc::new(TransitionsRecorder::default());
+        let state_machine = init_state_machine(
+            SignerState::Unregistered {
+                epoch: TimePoint::dummy().epoch,
+            },
+            runner,
+        )
+        .with_transition_listener(transitions_recorder.clone());
+
+        state_machine
+            .cycle()
+            .await
+            .expect("Cycling the state machine should not fail");
+
+        assert_eq!(
+            Vec::<StateTransition>::new(),
+            transitions_recorder.last_transitions()
+        );
     }
 
     #[tokio::test]
@@ -615,6 +917,7 @@ mod tests {
         let time_point = TimePoint {
             immutable_file_number: 99,
             epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
         };
         let state = SignerState::Registered {
             epoch: time_point.epoch,
@@ -654,6 +957,7 @@ mod tests {
         let time_point = TimePoint {
             immutable_file_number: 99,
             epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
         };
         let state = SignerState::Registered {
             epoch: time_point.epoch,
@@ -674,6 +978,9 @@ mod tests {
             .once()
             .returning(move || Ok(Some(certificate_pending.clone())));
         runner.expect_can_i_sign().once().returning(|_| Ok(true));
+        runner
+            .expect_get_era_of_epoch()
+            .returning(|_| SupportedEra::Thales);
         runner
             .expect_associate_signers_with_stake()
             .times(2)
@@ -681,17 +988,21 @@ mod tests {
         runner
             .expect_compute_single_signature()
             .once()
-            .returning(|_, _, _| Ok(Some(fake_data::single_signatures(vec![1, 5, 23]))));
+            .returning(|_, _, _, _| Ok(Some(fake_data::single_signatures(vec![1, 5, 23]))));
         runner
             .expect_compute_message()
             .once()
             .returning(|_, _| Ok(ProtocolMessage::new()));
         runner
             .expect_send_single_signature()
+            .withf(|_, _, registration_nonce| {
+                registration_nonce == &Some("registration-nonce".to_string())
+            })
             .once()
-            .returning(|_, _| Ok(()));
+            .returning(|_, _, _| Ok(()));
 
         let state_machine = init_state_machine(state, runner);
+        *state_machine.registration_nonce.lock().await = Some("registration-nonce".to_string());
         state_machine
             .cycle()
             .await
@@ -708,11 +1019,306 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn registered_to_signed_with_batched_signed_entity_types() {
+        let time_point = TimePoint {
+            immutable_file_number: 99,
+            epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
+        };
+        let state = SignerState::Registered {
+            epoch: time_point.epoch,
+        };
+        let batched_signed_entity_type = SignedEntityType::MithrilStakeDistribution(Epoch(9));
+        let certificate_pending = CertificatePending {
+            epoch: time_point.epoch,
+            ..fake_data::certificate_pending()
+        }
+        .with_batched_signed_entity_types(vec![batched_signed_entity_type.clone()]);
+        let mut runner = MockSignerRunner::new();
+        runner
+            .expect_get_current_time_point()
+            .once()
+            .returning(move || Ok(time_point.to_owned()));
+        runner
+            .expect_get_pending_certificate()
+            .once()
+            .returning(move || Ok(Some(certificate_pending.clone())));
+        runner.expect_can_i_sign().once().returning(|_| Ok(true));
+        runner
+            .expect_get_era_of_epoch()
+            .returning(|_| SupportedEra::Thales);
+        runner
+            .expect_associate_signers_with_stake()
+            .times(2)
+            .returning(|_, _| Ok(fake_data::signers_with_stakes(4)));
+        runner
+            .expect_compute_message()
+            .times(2)
+            .returning(|_, _| Ok(ProtocolMessage::new()));
+        runner
+            .expect_compute_single_signature()
+            .withf(move |_, _, message, _| {
+                message.get_batched_signed_entity_types().unwrap()
+                    == vec![batched_signed_entity_type.clone()]
+            })
+            .once()
+            .returning(|_, _, _, _| Ok(Some(fake_data::single_signatures(vec![1, 5, 23]))));
+        runner
+            .expect_send_single_signature()
+            .once()
+            .returning(|_, _, _| Ok(()));
+
+        let state_machine = init_state_machine(state, runner);
+        *state_machine.protocol_feature_activations.lock().await =
+            ProtocolFeatureActivations::new()
+                .with_activation(ProtocolFeature::BatchedSignedEntityTypes, Epoch(9));
+        state_machine
+            .cycle()
+            .await
+            .expect("Cycling the state machine should not fail");
+
+        assert!(matches!(
+            state_machine.get_state().await,
+            SignerState::Signed { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn registered_to_signed_with_batched_signed_entity_types_refused_if_feature_is_not_active(
+    ) {
+        let time_point = TimePoint {
+            immutable_file_number: 99,
+            epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
+        };
+        let state = SignerState::Registered {
+            epoch: time_point.epoch,
+        };
+        let certificate_pending = CertificatePending {
+            epoch: time_point.epoch,
+            ..fake_data::certificate_pending()
+        }
+        .with_batched_signed_entity_types(vec![
+            SignedEntityType::MithrilStakeDistribution(Epoch(9)),
+        ]);
+        let mut runner = MockSignerRunner::new();
+        runner
+            .expect_get_current_time_point()
+            .once()
+            .returning(move || Ok(time_point.to_owned()));
+        runner
+            .expect_get_pending_certificate()
+            .once()
+            .returning(move || Ok(Some(certificate_pending.clone())));
+        runner.expect_can_i_sign().once().returning(|_| Ok(true));
+        runner
+            .expect_associate_signers_with_stake()
+            .times(2)
+            .returning(|_, _| Ok(fake_data::signers_with_stakes(4)));
+        runner
+            .expect_compute_message()
+            .once()
+            .returning(|_, _| Ok(ProtocolMessage::new()));
+        runner.expect_compute_single_signature().never();
+        runner.expect_send_single_signature().never();
+
+        let state_machine = init_state_machine(state.clone(), runner);
+        *state_machine.protocol_feature_activations.lock().await =
+            ProtocolFeatureActivations::new()
+                .with_activation(ProtocolFeature::BatchedSignedEntityTypes, Epoch(10));
+        state_machine
+            .cycle()
+            .await
+            .expect_err("Cycling the state machine should fail");
+
+        assert_eq!(state, state_machine.get_state().await);
+    }
+
+    #[tokio::test]
+    async fn registered_to_signed_with_signed_certificate_metadata() {
+        let time_point = TimePoint {
+            immutable_file_number: 99,
+            epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
+        };
+        let state = SignerState::Registered {
+            epoch: time_point.epoch,
+        };
+        let initiated_at = Utc::now();
+        let certificate_pending = CertificatePending {
+            epoch: time_point.epoch,
+            ..fake_data::certificate_pending()
+        }
+        .with_initiated_at(Some(initiated_at));
+        let signed_metadata = SignedCertificateMetadata {
+            network: "devnet".to_string(),
+            protocol_parameters: certificate_pending.protocol_parameters.clone(),
+            initiated_at,
+        };
+        let mut runner = MockSignerRunner::new();
+        runner
+            .expect_get_current_time_point()
+            .once()
+            .returning(move || Ok(time_point.to_owned()));
+        runner
+            .expect_get_pending_certificate()
+            .once()
+            .returning(move || Ok(Some(certificate_pending.clone())));
+        runner.expect_can_i_sign().once().returning(|_| Ok(true));
+        runner
+            .expect_get_era_of_epoch()
+            .returning(|_| SupportedEra::Thales);
+        runner
+            .expect_associate_signers_with_stake()
+            .times(2)
+            .returning(|_, _| Ok(fake_data::signers_with_stakes(4)));
+        runner
+            .expect_compute_message()
+            .once()
+            .returning(|_, _| Ok(ProtocolMessage::new()));
+        let computed_signed_metadata = signed_metadata.clone();
+        runner
+            .expect_compute_signed_certificate_metadata()
+            .with(
+                predicate::eq(signed_metadata.protocol_parameters.clone()),
+                predicate::eq(initiated_at),
+            )
+            .once()
+            .returning(move |_, _| Ok(computed_signed_metadata.clone()));
+        runner
+            .expect_compute_single_signature()
+            .withf(move |_, _, message, _| {
+                message.get_signed_certificate_metadata().unwrap() == Some(signed_metadata.clone())
+            })
+            .once()
+            .returning(|_, _, _, _| Ok(Some(fake_data::single_signatures(vec![1, 5, 23]))));
+        runner
+            .expect_send_single_signature()
+            .once()
+            .returning(|_, _, _| Ok(()));
+
+        let state_machine = init_state_machine(state, runner);
+        *state_machine.protocol_feature_activations.lock().await =
+            ProtocolFeatureActivations::new()
+                .with_activation(ProtocolFeature::SignedCertificateMetadata, Epoch(9));
+        state_machine
+            .cycle()
+            .await
+            .expect("Cycling the state machine should not fail");
+
+        assert!(matches!(
+            state_machine.get_state().await,
+            SignerState::Signed { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn registered_to_signed_with_signed_certificate_metadata_refused_if_feature_is_not_active(
+    ) {
+        let time_point = TimePoint {
+            immutable_file_number: 99,
+            epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
+        };
+        let state = SignerState::Registered {
+            epoch: time_point.epoch,
+        };
+        let certificate_pending = CertificatePending {
+            epoch: time_point.epoch,
+            ..fake_data::certificate_pending()
+        }
+        .with_initiated_at(Some(Utc::now()));
+        let mut runner = MockSignerRunner::new();
+        runner
+            .expect_get_current_time_point()
+            .once()
+            .returning(move || Ok(time_point.to_owned()));
+        runner
+            .expect_get_pending_certificate()
+            .once()
+            .returning(move || Ok(Some(certificate_pending.clone())));
+        runner.expect_can_i_sign().once().returning(|_| Ok(true));
+        runner
+            .expect_get_era_of_epoch()
+            .returning(|_| SupportedEra::Thales);
+        runner
+            .expect_associate_signers_with_stake()
+            .times(2)
+            .returning(|_, _| Ok(fake_data::signers_with_stakes(4)));
+        runner
+            .expect_compute_message()
+            .once()
+            .returning(|_, _| Ok(ProtocolMessage::new()));
+        runner.expect_compute_signed_certificate_metadata().never();
+        runner.expect_compute_single_signature().never();
+        runner.expect_send_single_signature().never();
+
+        let state_machine = init_state_machine(state.clone(), runner);
+        state_machine
+            .cycle()
+            .await
+            .expect_err("Cycling the state machine should fail");
+
+        assert_eq!(state, state_machine.get_state().await);
+    }
+
+    #[tokio::test]
+    async fn registered_to_signed_without_signed_certificate_metadata_refused_in_the_era_where_it_is_mandatory(
+    ) {
+        let time_point = TimePoint {
+            immutable_file_number: 99,
+            epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
+        };
+        let state = SignerState::Registered {
+            epoch: time_point.epoch,
+        };
+        let certificate_pending = CertificatePending {
+            epoch: time_point.epoch,
+            ..fake_data::certificate_pending()
+        }
+        .with_initiated_at(None);
+        let mut runner = MockSignerRunner::new();
+        runner
+            .expect_get_current_time_point()
+            .once()
+            .returning(move || Ok(time_point.to_owned()));
+        runner
+            .expect_get_pending_certificate()
+            .once()
+            .returning(move || Ok(Some(certificate_pending.clone())));
+        runner.expect_can_i_sign().once().returning(|_| Ok(true));
+        runner
+            .expect_get_era_of_epoch()
+            .returning(|_| SupportedEra::Pythagoras);
+        runner
+            .expect_associate_signers_with_stake()
+            .times(2)
+            .returning(|_, _| Ok(fake_data::signers_with_stakes(4)));
+        runner
+            .expect_compute_message()
+            .once()
+            .returning(|_, _| Ok(ProtocolMessage::new()));
+        runner.expect_compute_signed_certificate_metadata().never();
+        runner.expect_compute_single_signature().never();
+        runner.expect_send_single_signature().never();
+
+        let state_machine = init_state_machine(state.clone(), runner);
+        state_machine
+            .cycle()
+            .await
+            .expect_err("Cycling the state machine should fail");
+
+        assert_eq!(state, state_machine.get_state().await);
+    }
+
     #[tokio::test]
     async fn signed_to_registered() {
         let time_point = TimePoint {
             immutable_file_number: 99,
             epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
         };
         let time_point_clone = time_point.clone();
         let beacon = CardanoDbBeacon::new(
@@ -760,6 +1366,7 @@ mod tests {
         let time_point = TimePoint {
             immutable_file_number: 99,
             epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
         };
         let new_time_point = TimePoint {
             epoch: Epoch(10),
@@ -797,6 +1404,7 @@ mod tests {
         let time_point = TimePoint {
             immutable_file_number: 99,
             epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
         };
         let time_point_clone = time_point.clone();
         let state = SignerState::Signed {
@@ -829,11 +1437,122 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn signed_to_signed_when_the_beacon_of_the_pending_signed_entity_type_has_not_advanced() {
+        let signed_time_point = TimePoint {
+            immutable_file_number: 99,
+            epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
+        };
+        // Only the block number has advanced, the snapshots are ticked by the immutable file number
+        let current_time_point = TimePoint {
+            chain_point: ChainPoint {
+                block_number: signed_time_point.chain_point.block_number + 1,
+                ..ChainPoint::dummy()
+            },
+            ..signed_time_point.clone()
+        };
+        let state = SignerState::Signed {
+            epoch: signed_time_point.epoch,
+            signed_entity_type: SignedEntityType::dummy(),
+        };
+
+        let mut runner = MockSignerRunner::new();
+        runner
+            .expect_get_current_time_point()
+            .once()
+            .returning(move || Ok(current_time_point.to_owned()));
+        runner
+            .expect_get_pending_certificate()
+            .once()
+            .returning(|| {
+                Ok(Some(CertificatePending {
+                    signed_entity_type: SignedEntityType::CardanoImmutableFilesFull(
+                        CardanoDbBeacon::default(),
+                    ),
+                    ..fake_data::certificate_pending()
+                }))
+            });
+
+        let state_machine = init_state_machine(state, runner);
+        state_machine.signed_time_points.lock().await.insert(
+            SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+            signed_time_point.clone(),
+        );
+        state_machine
+            .cycle()
+            .await
+            .expect("Cycling the state machine should not fail");
+
+        assert_eq!(
+            SignerState::Signed {
+                epoch: signed_time_point.epoch,
+                signed_entity_type: SignedEntityType::dummy(),
+            },
+            state_machine.get_state().await
+        );
+    }
+
+    #[tokio::test]
+    async fn signed_to_registered_when_the_block_number_advanced_since_signature() {
+        let signed_time_point = TimePoint {
+            immutable_file_number: 99,
+            epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
+        };
+        let current_time_point = TimePoint {
+            chain_point: ChainPoint {
+                block_number: signed_time_point.chain_point.block_number + 1,
+                ..ChainPoint::dummy()
+            },
+            ..signed_time_point.clone()
+        };
+        let state = SignerState::Signed {
+            epoch: signed_time_point.epoch,
+            signed_entity_type: SignedEntityType::dummy(),
+        };
+
+        let mut runner = MockSignerRunner::new();
+        runner
+            .expect_get_current_time_point()
+            .once()
+            .returning(move || Ok(current_time_point.to_owned()));
+        runner
+            .expect_get_pending_certificate()
+            .once()
+            .returning(|| {
+                Ok(Some(CertificatePending {
+                    signed_entity_type: SignedEntityType::CardanoTransactions(
+                        CardanoDbBeacon::default(),
+                    ),
+                    ..fake_data::certificate_pending()
+                }))
+            });
+
+        let state_machine = init_state_machine(state, runner);
+        state_machine.signed_time_points.lock().await.insert(
+            SignedEntityTypeDiscriminants::CardanoTransactions,
+            signed_time_point.clone(),
+        );
+        state_machine
+            .cycle()
+            .await
+            .expect("Cycling the state machine should not fail");
+
+        assert_eq!(
+            SignerState::Registered {
+                epoch: signed_time_point.epoch
+            },
+            state_machine.get_state().await
+        );
+    }
+
     #[tokio::test]
     async fn signed_to_signed_unsigned_pending_certificate() {
         let time_point = TimePoint {
             immutable_file_number: 99,
             epoch: Epoch(9),
+            chain_point: ChainPoint::dummy(),
         };
         let time_point_clone = time_point.clone();
         let state = SignerState::Signed {
@@ -870,4 +1589,28 @@ mod tests {
             state_machine.get_state().await
         );
     }
+
+    #[tokio::test]
+    async fn submit_diagnostic_once_when_the_failure_threshold_is_reached() {
+        let mut runner = MockSignerRunner::new();
+        runner
+            .expect_get_current_time_point()
+            .times(3)
+            .returning(|| Err(anyhow::anyhow!("cardano node unreachable")));
+        runner
+            .expect_submit_diagnostic()
+            .withf(|diagnostic| {
+                diagnostic.consecutive_failures == 2 && diagnostic.last_errors.len() == 2
+            })
+            .once()
+            .returning(|_| Ok(()));
+        let state_machine =
+            init_state_machine(SignerState::Init, runner).with_diagnostics_submission(2);
+
+        for _ in 0..3 {
+            let cycle_result = state_machine.cycle().await;
+            assert!(cycle_result.is_err());
+            state_machine.record_diagnostics(&cycle_result).await;
+        }
+    }
 }
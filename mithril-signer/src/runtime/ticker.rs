@@ -0,0 +1,283 @@
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+
+use mithril_common::{
+    entities::{SignedEntityType, SignedEntityTypeDiscriminants, TimePoint},
+    StdError, StdResult,
+};
+
+/// Source of the beacon that ticks the signing rounds of a signed entity type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TickerSource {
+    /// Last immutable file number of the Cardano node database
+    ImmutableFileNumber,
+
+    /// Block number of the tip of the Cardano chain
+    BlockNumber,
+}
+
+impl TickerSource {
+    /// Build the [Ticker] that reads this source
+    pub fn build_ticker(&self) -> Arc<dyn Ticker> {
+        match self {
+            Self::ImmutableFileNumber => Arc::new(ImmutableFileNumberTicker),
+            Self::BlockNumber => Arc::new(BlockNumberTicker),
+        }
+    }
+}
+
+impl FromStr for TickerSource {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "immutable_file_number" => Ok(Self::ImmutableFileNumber),
+            "block_number" => Ok(Self::BlockNumber),
+            source => Err(anyhow!(
+                "Unknown ticker source '{source}', expected 'immutable_file_number' or 'block_number'"
+            )),
+        }
+    }
+}
+
+/// A ticker reads the beacon of the signing rounds of a signed entity type from a time point.
+///
+/// The beacon of a ticker only ever increases, a new signing round can start each time it advances.
+pub trait Ticker: Sync + Send {
+    /// Source of the ticker beacon
+    fn source(&self) -> TickerSource;
+
+    /// Read the beacon of the ticker from the given time point
+    fn tick(&self, time_point: &TimePoint) -> u64;
+}
+
+/// [Ticker] keyed off the immutable file number of the Cardano node database.
+pub struct ImmutableFileNumberTicker;
+
+impl Ticker for ImmutableFileNumberTicker {
+    fn source(&self) -> TickerSource {
+        TickerSource::ImmutableFileNumber
+    }
+
+    fn tick(&self, time_point: &TimePoint) -> u64 {
+        time_point.immutable_file_number
+    }
+}
+
+/// [Ticker] keyed off the block number of the tip of the Cardano chain.
+pub struct BlockNumberTicker;
+
+impl Ticker for BlockNumberTicker {
+    fn source(&self) -> TickerSource {
+        TickerSource::BlockNumber
+    }
+
+    fn tick(&self, time_point: &TimePoint) -> u64 {
+        time_point.chain_point.block_number
+    }
+}
+
+/// The [tickers][Ticker] of the signed entity types.
+///
+/// The Cardano transactions are ticked by the block number, the other signed entity types by the
+/// immutable file number, unless another source is selected for them.
+#[derive(Clone)]
+pub struct SignedEntityTypeTickers {
+    tickers: BTreeMap<SignedEntityTypeDiscriminants, Arc<dyn Ticker>>,
+}
+
+impl SignedEntityTypeTickers {
+    /// Create the tickers with the default source of each signed entity type
+    pub fn new() -> Self {
+        let tickers = SignedEntityTypeDiscriminants::all()
+            .into_iter()
+            .map(|discriminant| {
+                let source = match discriminant {
+                    SignedEntityTypeDiscriminants::CardanoTransactions => TickerSource::BlockNumber,
+                    _ => TickerSource::ImmutableFileNumber,
+                };
+                (discriminant, source.build_ticker())
+            })
+            .collect();
+
+        Self { tickers }
+    }
+
+    /// Select the ticker of a signed entity type
+    pub fn with_ticker(
+        mut self,
+        discriminant: SignedEntityTypeDiscriminants,
+        ticker: Arc<dyn Ticker>,
+    ) -> Self {
+        self.tickers.insert(discriminant, ticker);
+        self
+    }
+
+    /// Select the sources of the tickers of some signed entity types, the other signed entity
+    /// types keep their default source
+    pub fn with_sources(
+        self,
+        sources: &BTreeMap<SignedEntityTypeDiscriminants, TickerSource>,
+    ) -> Self {
+        sources
+            .iter()
+            .fold(self, |tickers, (discriminant, source)| {
+                tickers.with_ticker(*discriminant, source.build_ticker())
+            })
+    }
+
+    /// Get the ticker of the given signed entity type
+    pub fn get_ticker(&self, signed_entity_type: &SignedEntityType) -> Arc<dyn Ticker> {
+        let discriminant = SignedEntityTypeDiscriminants::from(signed_entity_type);
+        self.tickers
+            .get(&discriminant)
+            .cloned()
+            .unwrap_or_else(|| TickerSource::ImmutableFileNumber.build_ticker())
+    }
+
+    /// Check if the beacon of the ticker of the given signed entity type has advanced between
+    /// the given time points
+    pub fn has_advanced(
+        &self,
+        signed_entity_type: &SignedEntityType,
+        from: &TimePoint,
+        to: &TimePoint,
+    ) -> bool {
+        let ticker = self.get_ticker(signed_entity_type);
+
+        ticker.tick(to) > ticker.tick(from)
+    }
+
+    /// Parse a list of ticker sources by signed entity type (i.e.
+    /// `CardanoTransactions=block_number,CardanoImmutableFilesFull=immutable_file_number`)
+    pub fn parse_sources(
+        sources: &str,
+    ) -> StdResult<BTreeMap<SignedEntityTypeDiscriminants, TickerSource>> {
+        sources
+            .split(',')
+            .map(str::trim)
+            .filter(|source| !source.is_empty())
+            .map(|source| {
+                let (discriminant, ticker_source) = source.split_once('=').ok_or_else(|| {
+                    anyhow!("Invalid ticker source '{source}', expected 'SignedEntityType=source'")
+                })?;
+                let discriminant = SignedEntityTypeDiscriminants::from_str(discriminant.trim())
+                    .with_context(|| format!("Unknown signed entity type '{discriminant}'"))?;
+
+                Ok((discriminant, TickerSource::from_str(ticker_source)?))
+            })
+            .collect()
+    }
+}
+
+impl Default for SignedEntityTypeTickers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::{CardanoDbBeacon, ChainPoint, Epoch};
+
+    use super::*;
+
+    fn time_point(immutable_file_number: u64, block_number: u64) -> TimePoint {
+        TimePoint::new(
+            1,
+            immutable_file_number,
+            ChainPoint {
+                slot_number: 10,
+                block_number,
+                block_hash: "block_hash".to_string(),
+            },
+        )
+    }
+
+    #[test]
+    fn cardano_transactions_are_ticked_by_block_number_by_default() {
+        let tickers = SignedEntityTypeTickers::new();
+
+        assert_eq!(
+            TickerSource::BlockNumber,
+            tickers
+                .get_ticker(&SignedEntityType::CardanoTransactions(
+                    CardanoDbBeacon::default()
+                ))
+                .source()
+        );
+        assert_eq!(
+            TickerSource::ImmutableFileNumber,
+            tickers
+                .get_ticker(&SignedEntityType::CardanoImmutableFilesFull(
+                    CardanoDbBeacon::default()
+                ))
+                .source()
+        );
+        assert_eq!(
+            TickerSource::ImmutableFileNumber,
+            tickers
+                .get_ticker(&SignedEntityType::MithrilStakeDistribution(Epoch(1)))
+                .source()
+        );
+    }
+
+    #[test]
+    fn only_the_ticker_of_the_signed_entity_type_is_checked() {
+        let tickers = SignedEntityTypeTickers::new();
+        let transactions = SignedEntityType::CardanoTransactions(CardanoDbBeacon::default());
+        let snapshot = SignedEntityType::CardanoImmutableFilesFull(CardanoDbBeacon::default());
+
+        assert!(!tickers.has_advanced(&transactions, &time_point(5, 100), &time_point(5, 100)));
+        assert!(tickers.has_advanced(&transactions, &time_point(5, 100), &time_point(5, 101)));
+        assert!(!tickers.has_advanced(&transactions, &time_point(5, 100), &time_point(6, 100)));
+        assert!(!tickers.has_advanced(&snapshot, &time_point(5, 100), &time_point(5, 101)));
+        assert!(tickers.has_advanced(&snapshot, &time_point(5, 100), &time_point(6, 100)));
+    }
+
+    #[test]
+    fn block_number_advancing_is_ignored_if_the_signed_entity_type_is_not_ticked_by_it() {
+        let tickers = SignedEntityTypeTickers::new().with_sources(&BTreeMap::from([(
+            SignedEntityTypeDiscriminants::CardanoTransactions,
+            TickerSource::ImmutableFileNumber,
+        )]));
+        let transactions = SignedEntityType::CardanoTransactions(CardanoDbBeacon::default());
+
+        assert!(!tickers.has_advanced(&transactions, &time_point(5, 100), &time_point(5, 101)));
+        assert!(tickers.has_advanced(&transactions, &time_point(5, 100), &time_point(6, 100)));
+    }
+
+    #[test]
+    fn parse_ticker_sources() {
+        let sources = SignedEntityTypeTickers::parse_sources(
+            "CardanoTransactions=immutable_file_number, CardanoImmutableFilesFull=block_number",
+        )
+        .unwrap();
+
+        assert_eq!(
+            BTreeMap::from([
+                (
+                    SignedEntityTypeDiscriminants::CardanoImmutableFilesFull,
+                    TickerSource::BlockNumber
+                ),
+                (
+                    SignedEntityTypeDiscriminants::CardanoTransactions,
+                    TickerSource::ImmutableFileNumber
+                ),
+            ]),
+            sources
+        );
+
+        for invalid_sources in [
+            "CardanoTransactions",
+            "Unknown=block_number",
+            "CardanoTransactions=slot",
+        ] {
+            SignedEntityTypeTickers::parse_sources(invalid_sources).expect_err(&format!(
+                "Ticker sources '{invalid_sources}' should be rejected"
+            ));
+        }
+    }
+}
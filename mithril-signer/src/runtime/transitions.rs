@@ -0,0 +1,212 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use slog_scope::info;
+use std::collections::VecDeque;
+use std::sync::RwLock;
+
+use super::SignerState;
+
+/// Default number of the latest transitions of the state machine kept by a [TransitionsRecorder]
+pub const DEFAULT_TRANSITIONS_HISTORY_SIZE: usize = 20;
+
+/// A transition of the state machine from a state to another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StateTransition {
+    /// State before the transition
+    pub from: String,
+
+    /// State after the transition
+    pub to: String,
+
+    /// Reason of the transition
+    pub reason: String,
+
+    /// Date and time at which the transition occurred
+    pub transited_at: DateTime<Utc>,
+}
+
+impl StateTransition {
+    /// StateTransition factory, the reason is deduced from the kinds of the states
+    pub fn new(from: &SignerState, to: &SignerState) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            reason: Self::reason(from, to).to_string(),
+            transited_at: Utc::now(),
+        }
+    }
+
+    fn reason(from: &SignerState, to: &SignerState) -> &'static str {
+        match (from, to) {
+            (SignerState::Init, _) => "signer started",
+            (_, SignerState::Unregistered { .. }) => "epoch changed",
+            (SignerState::Unregistered { .. }, SignerState::Registered { .. }) => {
+                "signer registered to the aggregator"
+            }
+            (SignerState::Registered { .. }, SignerState::Signed { .. }) => {
+                "pending certificate signed"
+            }
+            (SignerState::Signed { .. }, SignerState::Registered { .. }) => {
+                "new pending certificate detected"
+            }
+            _ => "unexpected transition",
+        }
+    }
+}
+
+/// Listener notified of each transition of the [state machine][super::StateMachine].
+///
+/// A listener is called while the state machine is locked, it must not block.
+pub trait StateMachineTransitionListener: Sync + Send {
+    /// Called after the state machine transited from a state to another
+    fn on_transition(&self, transition: &StateTransition);
+}
+
+/// Log each transition of the state machine with structured fields.
+#[derive(Debug, Default)]
+pub struct TransitionsLogger;
+
+impl StateMachineTransitionListener for TransitionsLogger {
+    fn on_transition(&self, transition: &StateTransition) {
+        info!(
+            "STATE MACHINE: transition";
+            "from" => &transition.from,
+            "to" => &transition.to,
+            "reason" => &transition.reason,
+        );
+    }
+}
+
+/// Status of the signer runtime, built from the recorded transitions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SignerRuntimeStatus {
+    /// Current state of the state machine
+    pub current_state: String,
+
+    /// Latest transitions of the state machine, the most recent last
+    pub last_transitions: Vec<StateTransition>,
+}
+
+/// Record the latest transitions of the state machine.
+pub struct TransitionsRecorder {
+    history_size: usize,
+    transitions: RwLock<VecDeque<StateTransition>>,
+}
+
+impl TransitionsRecorder {
+    /// TransitionsRecorder factory, keeping at most `history_size` transitions
+    pub fn new(history_size: usize) -> Self {
+        let history_size = history_size.max(1);
+
+        Self {
+            history_size,
+            transitions: RwLock::new(VecDeque::with_capacity(history_size)),
+        }
+    }
+
+    /// Get the latest transitions, the most recent last
+    pub fn last_transitions(&self) -> Vec<StateTransition> {
+        self.transitions.read().unwrap().iter().cloned().collect()
+    }
+
+    /// Build the status of the signer runtime
+    pub fn status(&self) -> SignerRuntimeStatus {
+        let last_transitions = self.last_transitions();
+
+        SignerRuntimeStatus {
+            current_state: last_transitions
+                .last()
+                .map(|transition| transition.to.clone())
+                .unwrap_or_else(|| SignerState::Init.to_string()),
+            last_transitions,
+        }
+    }
+}
+
+impl Default for TransitionsRecorder {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRANSITIONS_HISTORY_SIZE)
+    }
+}
+
+impl StateMachineTransitionListener for TransitionsRecorder {
+    fn on_transition(&self, transition: &StateTransition) {
+        let mut transitions = self.transitions.write().unwrap();
+        if transitions.len() == self.history_size {
+            transitions.pop_front();
+        }
+        transitions.push_back(transition.to_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::entities::Epoch;
+
+    use super::*;
+
+    #[test]
+    fn transition_reason_is_deduced_from_the_states() {
+        let unregistered = SignerState::Unregistered { epoch: Epoch(2) };
+        let registered = SignerState::Registered { epoch: Epoch(2) };
+
+        assert_eq!(
+            "signer started",
+            StateTransition::new(&SignerState::Init, &unregistered).reason
+        );
+        assert_eq!(
+            "signer registered to the aggregator",
+            StateTransition::new(&unregistered, &registered).reason
+        );
+        assert_eq!(
+            "epoch changed",
+            StateTransition::new(&registered, &SignerState::Unregistered { epoch: Epoch(3) })
+                .reason
+        );
+    }
+
+    #[test]
+    fn status_without_transition_is_init() {
+        let recorder = TransitionsRecorder::default();
+
+        assert_eq!(
+            SignerRuntimeStatus {
+                current_state: SignerState::Init.to_string(),
+                last_transitions: vec![],
+            },
+            recorder.status()
+        );
+    }
+
+    #[test]
+    fn record_only_the_latest_transitions() {
+        let recorder = TransitionsRecorder::new(2);
+        for epoch in 1..=3 {
+            recorder.on_transition(&StateTransition::new(
+                &SignerState::Unregistered {
+                    epoch: Epoch(epoch - 1),
+                },
+                &SignerState::Unregistered {
+                    epoch: Epoch(epoch),
+                },
+            ));
+        }
+
+        let status = recorder.status();
+        assert_eq!(
+            SignerState::Unregistered { epoch: Epoch(3) }.to_string(),
+            status.current_state
+        );
+        assert_eq!(
+            vec![
+                SignerState::Unregistered { epoch: Epoch(2) }.to_string(),
+                SignerState::Unregistered { epoch: Epoch(3) }.to_string(),
+            ],
+            status
+                .last_transitions
+                .into_iter()
+                .map(|transition| transition.to)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,14 +1,15 @@
 use anyhow::{anyhow, Context};
 use hex::ToHex;
 use slog_scope::{info, trace, warn};
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 use thiserror::Error;
 
 use mithril_common::crypto_helper::{KESPeriod, ProtocolInitializer};
 use mithril_common::entities::{
-    PartyId, ProtocolMessage, ProtocolParameters, SignerWithStake, SingleSignatures, Stake,
+    Epoch, PartyId, ProtocolMessage, ProtocolParameters, SignerWithStake, SingleSignatures, Stake,
 };
-use mithril_common::protocol::SignerBuilder;
+use mithril_common::era::{EraChecker, SupportedEra};
+use mithril_common::protocol::{EraSignatureSchemes, ProtocolSignatureScheme};
 use mithril_common::{StdError, StdResult};
 
 #[cfg(test)]
@@ -41,21 +42,35 @@ impl MithrilProtocolInitializerBuilder {
 /// The SingleSigner is the structure responsible of issuing SingleSignatures.
 #[cfg_attr(test, automock)]
 pub trait SingleSigner: Sync + Send {
-    /// Computes single signatures
+    /// Computes single signatures of a message signed at the given epoch
     fn compute_single_signatures(
         &self,
+        signed_epoch: Epoch,
         protocol_message: &ProtocolMessage,
         signers_with_stake: &[SignerWithStake],
         protocol_initializer: &ProtocolInitializer,
     ) -> StdResult<Option<SingleSignatures>>;
 
-    /// Compute aggregate verification key from stake distribution
+    /// Compute aggregate verification key from stake distribution of the signers of the given
+    /// epoch
     fn compute_aggregate_verification_key(
         &self,
+        signing_epoch: Epoch,
         signers_with_stake: &[SignerWithStake],
         protocol_initializer: &ProtocolInitializer,
     ) -> StdResult<Option<String>>;
 
+    /// Create the protocol initializer of a new verification key with the signature scheme of
+    /// the given era
+    fn create_protocol_initializer(
+        &self,
+        era: SupportedEra,
+        stake: Stake,
+        protocol_parameters: &ProtocolParameters,
+        kes_secret_key_path: Option<PathBuf>,
+        kes_period: Option<KESPeriod>,
+    ) -> StdResult<ProtocolInitializer>;
+
     /// Get party id
     fn get_party_id(&self) -> PartyId;
 }
@@ -77,48 +92,65 @@ pub enum SingleSignerError {
 }
 
 /// Implementation of the SingleSigner.
+///
+/// The signatures are issued and the aggregate verification keys are computed with the signature
+/// scheme of the era of the epoch they are made for.
 pub struct MithrilSingleSigner {
     party_id: PartyId,
+    era_checker: Arc<EraChecker>,
+    signature_schemes: EraSignatureSchemes,
 }
 
 impl MithrilSingleSigner {
     /// Create a new instance of the MithrilSingleSigner.
-    pub fn new(party_id: PartyId) -> Self {
-        Self { party_id }
+    pub fn new(party_id: PartyId, era_checker: Arc<EraChecker>) -> Self {
+        Self {
+            party_id,
+            era_checker,
+            signature_schemes: EraSignatureSchemes::default(),
+        }
+    }
+
+    /// Set the signature schemes used by each era
+    pub fn with_signature_schemes(mut self, signature_schemes: EraSignatureSchemes) -> Self {
+        self.signature_schemes = signature_schemes;
+        self
+    }
+
+    fn signature_scheme(&self, era: SupportedEra) -> StdResult<Arc<dyn ProtocolSignatureScheme>> {
+        self.signature_schemes.get(era)
     }
 }
 
 impl SingleSigner for MithrilSingleSigner {
     fn compute_single_signatures(
         &self,
+        signed_epoch: Epoch,
         protocol_message: &ProtocolMessage,
         signers_with_stake: &[SignerWithStake],
         protocol_initializer: &ProtocolInitializer,
     ) -> StdResult<Option<SingleSignatures>> {
-        let builder = SignerBuilder::new(
-            signers_with_stake,
-            &protocol_initializer.get_protocol_parameters().into(),
-        )
-        .with_context(|| "Mithril Single Signer can not build signer")
-        .map_err(|e| SingleSignerError::ProtocolSignerCreationFailure(anyhow!(e)))?;
-        info!("Signing protocol message"; "protocol_message" =>  #?protocol_message, "signed message" => protocol_message.compute_hash().encode_hex::<String>());
-        let signatures = builder
-            .restore_signer_from_initializer(self.party_id.clone(), protocol_initializer.clone())
-            .with_context(|| {
-                format!(
-                    "Mithril Single Signer can not restore signer with party_id: '{}'",
-                    self.party_id.clone()
-                )
-            })
-            .map_err(|e| SingleSignerError::ProtocolSignerCreationFailure(anyhow!(e)))?
-            .sign(protocol_message)
+        let era = self.era_checker.era_of_epoch(signed_epoch);
+        let signature_scheme = self
+            .signature_scheme(era)
+            .with_context(|| "Mithril Single Signer can not build signer")
+            .map_err(|e| SingleSignerError::ProtocolSignerCreationFailure(anyhow!(e)))?;
+        info!("Signing protocol message"; "protocol_message" =>  #?protocol_message, "signed message" => protocol_message.compute_hash().encode_hex::<String>(), "era" => %era);
+        let signatures = signature_scheme
+            .sign(
+                protocol_message,
+                self.party_id.clone(),
+                signers_with_stake,
+                protocol_initializer.clone(),
+            )
             .with_context(|| {
                 format!(
-                    "Mithril Single Signer can not sign protocol_message: '{:?}'",
-                    protocol_message
+                    "Mithril Single Signer with party_id: '{}' can not sign protocol_message: '{:?}'",
+                    self.party_id, protocol_message
                 )
             })
-            .map_err(SingleSignerError::SignatureFailed)?;
+            .map_err(SingleSignerError::SignatureFailed)?
+            .map(|signature| signature.with_era(era));
 
         match &signatures {
             Some(signature) => {
@@ -139,18 +171,20 @@ impl SingleSigner for MithrilSingleSigner {
     /// Compute aggregate verification key from stake distribution
     fn compute_aggregate_verification_key(
         &self,
+        signing_epoch: Epoch,
         signers_with_stake: &[SignerWithStake],
         protocol_initializer: &ProtocolInitializer,
     ) -> StdResult<Option<String>> {
-        let signer_builder = SignerBuilder::new(
-            signers_with_stake,
-            &protocol_initializer.get_protocol_parameters().into(),
-        )
-        .with_context(|| "Mithril Single Signer can not compute aggregate verification key")
-        .map_err(SingleSignerError::AggregateVerificationKeyComputationFailed)?;
-
-        let encoded_avk = signer_builder
-            .compute_aggregate_verification_key()
+        let encoded_avk = self
+            .signature_scheme(self.era_checker.era_of_epoch(signing_epoch))
+            .and_then(|signature_scheme| {
+                signature_scheme.compute_aggregate_verification_key(
+                    signers_with_stake,
+                    &protocol_initializer.get_protocol_parameters().into(),
+                )
+            })
+            .with_context(|| "Mithril Single Signer can not compute aggregate verification key")
+            .map_err(SingleSignerError::AggregateVerificationKeyComputationFailed)?
             .to_json_hex()
             .with_context(|| {
                 "Mithril Single Signer can not serialize aggregate verification key"
@@ -159,6 +193,36 @@ impl SingleSigner for MithrilSingleSigner {
         Ok(Some(encoded_avk))
     }
 
+    fn create_protocol_initializer(
+        &self,
+        era: SupportedEra,
+        stake: Stake,
+        protocol_parameters: &ProtocolParameters,
+        kes_secret_key_path: Option<PathBuf>,
+        kes_period: Option<KESPeriod>,
+    ) -> StdResult<ProtocolInitializer> {
+        let mut rng = rand_core::OsRng;
+        let protocol_initializer = self
+            .signature_scheme(era)
+            .and_then(|signature_scheme| {
+                signature_scheme.create_protocol_initializer(
+                    stake,
+                    protocol_parameters,
+                    kes_secret_key_path,
+                    kes_period,
+                    &mut rng,
+                )
+            })
+            .with_context(|| {
+                format!(
+                    "Mithril Single Signer can not create a protocol initializer for era '{era}'"
+                )
+            })
+            .map_err(SingleSignerError::ProtocolSignerCreationFailure)?;
+
+        Ok(protocol_initializer)
+    }
+
     /// Get party id
     fn get_party_id(&self) -> PartyId {
         self.party_id.clone()
@@ -171,16 +235,20 @@ mod tests {
 
     use mithril_common::{
         crypto_helper::ProtocolClerk, entities::ProtocolMessagePartKey,
-        test_utils::MithrilFixtureBuilder,
+        protocol::StmSignatureScheme, test_utils::MithrilFixtureBuilder,
     };
 
+    fn era_checker() -> Arc<EraChecker> {
+        Arc::new(EraChecker::new(SupportedEra::dummy(), Epoch(1)))
+    }
+
     #[test]
     fn compute_single_signature_success() {
         let snapshot_digest = "digest".to_string();
         let fixture = MithrilFixtureBuilder::default().with_signers(5).build();
         let signers_with_stake = fixture.signers_with_stake();
         let current_signer = &fixture.signers_fixture()[0];
-        let single_signer = MithrilSingleSigner::new(current_signer.party_id());
+        let single_signer = MithrilSingleSigner::new(current_signer.party_id(), era_checker());
         let clerk = ProtocolClerk::from_signer(&current_signer.protocol_signer);
         let avk = clerk.compute_avk();
         let mut protocol_message = ProtocolMessage::new();
@@ -189,6 +257,7 @@ mod tests {
 
         let sign_result = single_signer
             .compute_single_signatures(
+                Epoch(1),
                 &protocol_message,
                 &signers_with_stake,
                 &current_signer.protocol_initializer,
@@ -209,6 +278,37 @@ mod tests {
                 .is_ok(),
             "produced single signature should be valid"
         );
+        assert_eq!(Some(SupportedEra::dummy()), sign_result.era);
+    }
+
+    #[test]
+    fn compute_single_signature_of_an_epoch_before_the_transition_epoch_uses_the_transition_era() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(5).build();
+        let current_signer = &fixture.signers_fixture()[0];
+        let era_checker = EraChecker::new(SupportedEra::dummy(), Epoch(1));
+        era_checker.change_era_with_transition(
+            SupportedEra::Pythagoras,
+            Epoch(10),
+            Some(SupportedEra::Thales),
+        );
+        let single_signer =
+            MithrilSingleSigner::new(current_signer.party_id(), Arc::new(era_checker))
+                .with_signature_schemes(
+                    EraSignatureSchemes::empty()
+                        .with_scheme(SupportedEra::Thales, Arc::new(StmSignatureScheme)),
+                );
+
+        let signature = single_signer
+            .compute_single_signatures(
+                Epoch(9),
+                &ProtocolMessage::new(),
+                &fixture.signers_with_stake(),
+                &current_signer.protocol_initializer,
+            )
+            .expect("single signer should sign with the signature scheme of the transition era")
+            .expect("single signer should produce a signature here");
+
+        assert_eq!(Some(SupportedEra::Thales), signature.era);
     }
 
     #[test]
@@ -216,15 +316,66 @@ mod tests {
         let fixture = MithrilFixtureBuilder::default().with_signers(5).build();
         let signers_with_stake = fixture.signers_with_stake();
         let current_signer = &fixture.signers_fixture()[0];
-        let single_signer =
-            MithrilSingleSigner::new(current_signer.signer_with_stake.party_id.to_owned());
+        let single_signer = MithrilSingleSigner::new(
+            current_signer.signer_with_stake.party_id.to_owned(),
+            era_checker(),
+        );
 
         single_signer
             .compute_aggregate_verification_key(
+                Epoch(1),
                 &signers_with_stake,
                 &current_signer.protocol_initializer,
             )
             .expect("compute aggregate verification signature should not fail")
             .expect("aggregate verification signature should not be empty");
     }
+
+    #[test]
+    fn create_protocol_initializer_with_the_signature_scheme_of_the_era() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(1).build();
+        let single_signer = MithrilSingleSigner::new("party_id".to_string(), era_checker())
+            .with_signature_schemes(
+                EraSignatureSchemes::empty()
+                    .with_scheme(SupportedEra::Pythagoras, Arc::new(StmSignatureScheme)),
+            );
+
+        let protocol_initializer = single_signer
+            .create_protocol_initializer(
+                SupportedEra::Pythagoras,
+                100,
+                &fixture.protocol_parameters(),
+                None,
+                None,
+            )
+            .expect("the protocol initializer of the era should be created");
+        assert_eq!(100, protocol_initializer.get_stake());
+
+        single_signer
+            .create_protocol_initializer(
+                SupportedEra::Thales,
+                100,
+                &fixture.protocol_parameters(),
+                None,
+                None,
+            )
+            .expect_err("the protocol initializer of an era without signature scheme should fail");
+    }
+
+    #[test]
+    fn compute_single_signature_fails_without_signature_scheme_for_the_current_era() {
+        let fixture = MithrilFixtureBuilder::default().with_signers(5).build();
+        let current_signer = &fixture.signers_fixture()[0];
+        let single_signer = MithrilSingleSigner::new(current_signer.party_id(), era_checker())
+            .with_signature_schemes(EraSignatureSchemes::empty());
+
+        single_signer
+            .compute_single_signatures(
+                Epoch(1),
+                &ProtocolMessage::new(),
+                &fixture.signers_with_stake(),
+                &current_signer.protocol_initializer,
+            )
+            .expect_err("single signer should fail without a signature scheme");
+    }
 }
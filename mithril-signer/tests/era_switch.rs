@@ -50,3 +50,45 @@ async fn era_fail_at_startup() {
         .cycle_unregistered().await
         .expect_err("The state machine must fail because of unsupported Era.");
 }
+
+#[rustfmt::skip]
+#[tokio::test]
+async fn era_switch_rekeys_the_signer() {
+    let protocol_parameters = tests_setup::setup_protocol_parameters();
+    let fixture = MithrilFixtureBuilder::default().with_signers(10).with_protocol_parameters(protocol_parameters.into()).build();
+    let signers_with_stake = fixture.signers_with_stake();
+    let mut tester = StateMachineTester::init(&signers_with_stake)
+        .await.expect("state machine tester init should not fail");
+
+    tester
+        .comment("TEST: the keys registered ahead of a supported era switch are used once the era is active.")
+        .set_era_markers(vec![
+            EraMarker::new(&SupportedEra::Thales.to_string(), Some(Epoch(0))),
+            EraMarker::new(&SupportedEra::Pythagoras.to_string(), Some(Epoch(4))),
+            ])
+        .is_init().await.unwrap()
+        .cycle_unregistered().await.unwrap()
+        .check_era_checker_last_updated_at(Epoch(1)).await.unwrap()
+        .increase_epoch(2).await.unwrap()
+        .cycle_unregistered().await.unwrap()
+        .aggregator_send_epoch_settings().await
+        .cycle_registered().await.unwrap()
+        .comment("The keys registered at epoch 2 sign at epoch 4: a key of the next era is registered along with the key of the current era")
+        .check_protocol_initializer(Epoch(3)).await.unwrap()
+        .check_next_era_protocol_initializer(Epoch(3)).await.unwrap()
+        .check_aggregator_next_era_signers(Epoch(3), 1).await.unwrap()
+        .increase_epoch(3).await.unwrap()
+        .cycle_unregistered().await.unwrap()
+        .aggregator_send_epoch_settings().await
+        .comment("The key registered at epoch 3 signs at epoch 4, it is created with the signature scheme of the next era only")
+        .cycle_registered().await.unwrap()
+        .check_protocol_initializer(Epoch(4)).await.unwrap()
+        .check_aggregator_next_era_signers(Epoch(4), 0).await.unwrap()
+        .increase_epoch(4).await.unwrap()
+        .comment("Reaching the era switch epoch, the signer signs with the key of the next era registered at epoch 2")
+        .cycle_unregistered().await.unwrap()
+        .check_era_checker_last_updated_at(Epoch(4)).await.unwrap()
+        .cycle_registered().await.unwrap()
+        .check_protocol_initializer(Epoch(5)).await.unwrap()
+        .cycle_signed().await.unwrap();
+}
@@ -7,6 +7,8 @@ use mithril_common::{
         CardanoDbBeacon, CertificatePending, Epoch, EpochSettings, SignedEntityType, Signer,
         SingleSignatures, TimePoint,
     },
+    era::SupportedEra,
+    messages::{RegisterSignatureStatus, SignerDiagnosticMessage},
     test_utils::fake_data,
     CardanoNetwork, TimePointProvider, TimePointProviderImpl,
 };
@@ -16,6 +18,7 @@ use tokio::sync::RwLock;
 pub struct FakeAggregator {
     network: CardanoNetwork,
     registered_signers: RwLock<HashMap<Epoch, Vec<Signer>>>,
+    registered_next_era_signers: RwLock<HashMap<Epoch, Vec<Signer>>>,
     time_point_provider: Arc<TimePointProviderImpl>,
     withhold_epoch_settings: RwLock<bool>,
 }
@@ -26,6 +29,7 @@ impl FakeAggregator {
             network,
             withhold_epoch_settings: RwLock::new(true),
             registered_signers: RwLock::new(HashMap::new()),
+            registered_next_era_signers: RwLock::new(HashMap::new()),
             time_point_provider,
         }
     }
@@ -36,6 +40,32 @@ impl FakeAggregator {
         store.get(epoch).cloned()
     }
 
+    pub async fn get_registered_next_era_signers(&self, epoch: &Epoch) -> Option<Vec<Signer>> {
+        let store = self.registered_next_era_signers.read().await;
+
+        store.get(epoch).cloned()
+    }
+
+    /// The signers registered at the given epoch, with the keys they registered for the next era
+    /// if any since the era switches as planned.
+    async fn get_signers_with_era_keys(&self, epoch: &Epoch) -> Vec<Signer> {
+        let next_era_store = self.registered_next_era_signers.read().await;
+        let next_era_signers = next_era_store.get(epoch).cloned().unwrap_or_default();
+
+        self.get_registered_signers(epoch)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|signer| {
+                next_era_signers
+                    .iter()
+                    .find(|next_era_signer| next_era_signer.party_id == signer.party_id)
+                    .cloned()
+                    .unwrap_or(signer)
+            })
+            .collect()
+    }
+
     pub async fn release_epoch_settings(&self) {
         let mut settings = self.withhold_epoch_settings.write().await;
         *settings = false;
@@ -82,22 +112,23 @@ impl AggregatorClient for FakeAggregator {
             *time_point.epoch,
             time_point.immutable_file_number,
         );
-        let mut certificate_pending = CertificatePending {
+        drop(store);
+        let certificate_pending = CertificatePending {
             epoch: time_point.epoch,
             signed_entity_type: SignedEntityType::CardanoImmutableFilesFull(beacon),
+            signers: self
+                .get_signers_with_era_keys(
+                    &time_point.epoch.offset_to_signer_retrieval_epoch().unwrap(),
+                )
+                .await,
+            next_signers: self
+                .get_signers_with_era_keys(
+                    &time_point.epoch.offset_to_next_signer_retrieval_epoch(),
+                )
+                .await,
             ..fake_data::certificate_pending()
         };
 
-        let store = self.registered_signers.read().await;
-        certificate_pending.signers = store
-            .get(&time_point.epoch.offset_to_signer_retrieval_epoch().unwrap())
-            .cloned()
-            .unwrap_or_default();
-        certificate_pending.next_signers = store
-            .get(&time_point.epoch.offset_to_next_signer_retrieval_epoch())
-            .cloned()
-            .unwrap_or_default();
-
         Ok(Some(certificate_pending))
     }
 
@@ -115,6 +146,23 @@ impl AggregatorClient for FakeAggregator {
         Ok(())
     }
 
+    /// Registers signer with the aggregator, along with the key of the next era
+    async fn register_signer_for_next_era(
+        &self,
+        epoch: Epoch,
+        signer: &Signer,
+        _next_era: SupportedEra,
+        next_era_signer: &Signer,
+    ) -> Result<(), AggregatorClientError> {
+        self.register_signer(epoch, signer).await?;
+        let mut store = self.registered_next_era_signers.write().await;
+        let mut signers = store.get(&epoch).cloned().unwrap_or_default();
+        signers.push(next_era_signer.clone());
+        let _ = store.insert(epoch, signers);
+
+        Ok(())
+    }
+
     /// Registers single signatures with the aggregator
     async fn register_signatures(
         &self,
@@ -123,13 +171,30 @@ impl AggregatorClient for FakeAggregator {
     ) -> Result<(), AggregatorClientError> {
         Ok(())
     }
+
+    /// Registers several single signatures with the aggregator
+    async fn register_signatures_batch(
+        &self,
+        signatures: &[(SignedEntityType, SingleSignatures)],
+    ) -> Result<Vec<RegisterSignatureStatus>, AggregatorClientError> {
+        Ok(vec![RegisterSignatureStatus::Registered; signatures.len()])
+    }
+
+    /// Submits a diagnostic to the aggregator
+    async fn submit_diagnostic(
+        &self,
+        _diagnostic: &SignerDiagnosticMessage,
+    ) -> Result<(), AggregatorClientError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use mithril_common::{
         chain_observer::ChainObserver, chain_observer::FakeObserver,
-        digesters::DumbImmutableFileObserver, test_utils::fake_data, CardanoNetwork,
+        digesters::DumbImmutableFileObserver, entities::ChainPoint, test_utils::fake_data,
+        CardanoNetwork,
     };
 
     use super::*;
@@ -140,6 +205,7 @@ mod tests {
         let chain_observer = Arc::new(FakeObserver::new(Some(TimePoint {
             epoch: Epoch(1),
             immutable_file_number: 1,
+            chain_point: ChainPoint::dummy(),
         })));
         let time_point_provider = Arc::new(TimePointProviderImpl::new(
             chain_observer.clone(),
@@ -10,7 +10,7 @@ use mithril_common::{
     cardano_block_scanner::DumbBlockScanner,
     chain_observer::{ChainObserver, FakeObserver},
     digesters::{DumbImmutableDigester, DumbImmutableFileObserver, ImmutableFileObserver},
-    entities::{Epoch, SignerWithStake, TimePoint},
+    entities::{ChainPoint, Epoch, SignerWithStake, TimePoint},
     era::{adapters::EraReaderDummyAdapter, EraChecker, EraMarker, EraReader, SupportedEra},
     signable_builder::{
         CardanoImmutableFilesFullSignableBuilder, CardanoTransactionsSignableBuilder,
@@ -53,6 +53,7 @@ pub struct StateMachineTester {
     chain_observer: Arc<FakeObserver>,
     certificate_handler: Arc<FakeAggregator>,
     protocol_initializer_store: Arc<ProtocolInitializerStore>,
+    next_era_protocol_initializer_store: Arc<ProtocolInitializerStore>,
     stake_store: Arc<StakeStore>,
     era_checker: Arc<EraChecker>,
     era_reader_adapter: Arc<EraReaderDummyAdapter>,
@@ -97,6 +98,7 @@ impl StateMachineTester {
         let chain_observer = Arc::new(FakeObserver::new(Some(TimePoint {
             epoch: Epoch(1),
             immutable_file_number: 1,
+            chain_point: ChainPoint::dummy(),
         })));
         let time_point_provider = Arc::new(TimePointProviderImpl::new(
             chain_observer.clone(),
@@ -111,8 +113,9 @@ impl StateMachineTester {
             Box::new(MemoryAdapter::new(None).unwrap()),
             config.store_retention_limit,
         ));
-        let single_signer = Arc::new(MithrilSingleSigner::new(
-            config.party_id.to_owned().unwrap_or_default(),
+        let next_era_protocol_initializer_store = Arc::new(ProtocolInitializerStore::new(
+            Box::new(MemoryAdapter::new(None).unwrap()),
+            config.store_retention_limit,
         ));
         let stake_store = Arc::new(StakeStore::new(
             Box::new(MemoryAdapter::new(None).unwrap()),
@@ -140,6 +143,10 @@ impl StateMachineTester {
             era_epoch_token.get_current_epoch(),
         ));
 
+        let single_signer = Arc::new(MithrilSingleSigner::new(
+            config.party_id.to_owned().unwrap_or_default(),
+            era_checker.clone(),
+        ));
         let api_version_provider = Arc::new(APIVersionProvider::new(era_checker.clone()));
 
         let cardano_immutable_snapshot_builder =
@@ -181,6 +188,7 @@ impl StateMachineTester {
             chain_observer: chain_observer.clone(),
             digester: digester.clone(),
             protocol_initializer_store: protocol_initializer_store.clone(),
+            next_era_protocol_initializer_store: next_era_protocol_initializer_store.clone(),
             single_signer: single_signer.clone(),
             stake_store: stake_store.clone(),
             era_checker: era_checker.clone(),
@@ -209,6 +217,7 @@ impl StateMachineTester {
             chain_observer,
             certificate_handler,
             protocol_initializer_store,
+            next_era_protocol_initializer_store,
             stake_store,
             era_checker,
             era_reader_adapter,
@@ -310,6 +319,40 @@ impl StateMachineTester {
             ))
     }
 
+    /// check there is a protocol initializer registered ahead of an era switch for the given Epoch
+    pub async fn check_next_era_protocol_initializer(&mut self, epoch: Epoch) -> Result<&mut Self> {
+        let maybe_protocol_initializer = self
+            .next_era_protocol_initializer_store
+            .get_protocol_initializer(epoch)
+            .await
+            .map_err(TestError::SubsystemError)?;
+
+        self.assert(
+            maybe_protocol_initializer.is_some(),
+            format!("there should be a next era protocol intializer in store for Epoch {epoch}"),
+        )
+    }
+
+    /// check the number of signers registered to the aggregator with a key of the next era for
+    /// the given Epoch
+    pub async fn check_aggregator_next_era_signers(
+        &mut self,
+        epoch: Epoch,
+        expected: usize,
+    ) -> Result<&mut Self> {
+        let nb_next_era_signers = self
+            .certificate_handler
+            .get_registered_next_era_signers(&epoch)
+            .await
+            .unwrap_or_default()
+            .len();
+
+        self.assert(
+            expected == nb_next_era_signers,
+            format!("there should be {expected} signers registered with a key of the next era for Epoch {epoch}, got {nb_next_era_signers}"),
+        )
+    }
+
     /// check there is a stake for the given Epoch
     pub async fn check_stake_store(&mut self, epoch: Epoch) -> Result<&mut Self> {
         let maybe_stakes = self
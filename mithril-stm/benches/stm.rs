@@ -147,6 +147,65 @@ fn batch_benches<H>(
     }
 }
 
+fn aggregation_thread_pool_benches<H>(
+    c: &mut Criterion,
+    array_threads: &[usize],
+    nr_parties: usize,
+    params: StmParameters,
+    hashing_alg: &str,
+) where
+    H: Clone + Debug + Digest + Send + Sync + FixedOutput + Default,
+{
+    let mut group = c.benchmark_group(format!("STM/{hashing_alg}"));
+    let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+    let mut msg = [0u8; 16];
+    rng.fill_bytes(&mut msg);
+
+    let param_string = format!(
+        "k: {}, m: {}, nr_parties: {}",
+        params.k, params.m, nr_parties
+    );
+
+    let stakes = (0..nr_parties)
+        .map(|_| 1 + (rng.next_u64() % 9999))
+        .collect::<Vec<_>>();
+
+    let mut initializers: Vec<StmInitializer> = Vec::with_capacity(nr_parties);
+    for stake in stakes {
+        initializers.push(StmInitializer::setup(params, stake, &mut rng));
+    }
+    let mut key_reg = KeyReg::init();
+    for p in initializers.iter() {
+        key_reg.register(p.stake, p.verification_key()).unwrap();
+    }
+
+    let closed_reg = key_reg.close();
+
+    let signers = initializers
+        .into_par_iter()
+        .map(|p| p.new_signer(closed_reg.clone()).unwrap())
+        .collect::<Vec<StmSigner<H>>>();
+
+    let sigs = signers
+        .par_iter()
+        .filter_map(|p| p.sign(&msg))
+        .collect::<Vec<_>>();
+
+    let clerk = StmClerk::from_signer(&signers[0]);
+
+    for &nr_threads in array_threads {
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(nr_threads)
+            .build()
+            .unwrap();
+        let threads_string = format!("{param_string}/threads: {nr_threads}");
+
+        group.bench_function(BenchmarkId::new("Aggregation", threads_string), |b| {
+            b.iter(|| thread_pool.install(|| clerk.aggregate(&sigs, &msg)))
+        });
+    }
+}
+
 fn core_verifier_benches<H>(c: &mut Criterion, nr_parties: usize, params: StmParameters)
 where
     H: Clone + Debug + Digest + Send + Sync + FixedOutput + Default,
@@ -275,6 +334,20 @@ fn core_verifier_benches_blake_2000(c: &mut Criterion) {
     );
 }
 
+fn aggregation_thread_pool_benches_blake_2000(c: &mut Criterion) {
+    aggregation_thread_pool_benches::<Blake2b<U32>>(
+        c,
+        &[1, 2, 4, 8],
+        2000,
+        StmParameters {
+            m: 1523,
+            k: 250,
+            phi_f: 0.2,
+        },
+        "Blake2b",
+    );
+}
+
 criterion_group!(name = benches;
                  config = Criterion::default().nresamples(1000);
                  targets =
@@ -284,5 +357,6 @@ criterion_group!(name = benches;
     stm_benches_blake_2000,
     batch_stm_benches_blake_300,
     batch_stm_benches_blake_2000,
+    aggregation_thread_pool_benches_blake_2000,
 );
 criterion_main!(benches);
@@ -272,6 +272,19 @@ impl<D: Clone + Digest + FixedOutput> MerkleTreeCommitment<D> {
 }
 
 impl<D: Clone + Digest> MerkleTreeCommitmentBatchCompat<D> {
+    pub(crate) fn new(root: Vec<u8>, nr_leaves: usize) -> Self {
+        Self {
+            root,
+            nr_leaves,
+            hasher: PhantomData,
+        }
+    }
+
+    /// Get the number of leaves of the committed tree.
+    pub(crate) fn get_nr_leaves(&self) -> usize {
+        self.nr_leaves
+    }
+
     /// Serializes the Merkle Tree commitment together with a message in a single vector of bytes.
     /// Outputs `msg || self` as a vector of bytes.
     // todo: Do we need to concat msg to whole commitment (nr_leaves and root) or just the root?
@@ -116,6 +116,7 @@ use crate::merkle_tree::{BatchPath, MTLeaf, MerkleTreeCommitmentBatchCompat};
 use crate::multi_sig::{Signature, SigningKey, VerificationKey, VerificationKeyPoP};
 use blake2::digest::{Digest, FixedOutput};
 use rand_core::{CryptoRng, RngCore};
+use rayon::prelude::*;
 use serde::ser::SerializeTuple;
 use serde::{Deserialize, Serialize, Serializer};
 use std::cmp::Ordering;
@@ -136,6 +137,9 @@ pub type StmVerificationKeyPoP = VerificationKeyPoP;
 /// Wrapper of the MultiSignature Verification key
 pub type StmVerificationKey = VerificationKey;
 
+/// Wrapper of the MultiSignature signature of a message signed outside of the STM protocol
+pub type StmMessageSignature = Signature;
+
 /// Used to set protocol parameters.
 // todo: this is the criteria to consider parameters valid:
 // Let A = max assumed adversarial stake
@@ -225,6 +229,32 @@ impl<D: Digest + Clone + FixedOutput> PartialEq for StmAggrVerificationKey<D> {
 
 impl<D: Digest + Clone + FixedOutput> Eq for StmAggrVerificationKey<D> {}
 
+impl<D: Digest + Clone + FixedOutput> StmAggrVerificationKey<D> {
+    /// Build an aggregate key from the root and the number of leaves of its merkle tree
+    /// commitment, and from the total stake of the system.
+    pub fn from_parts(merkle_root: Vec<u8>, nr_leaves: usize, total_stake: Stake) -> Self {
+        Self {
+            mt_commitment: MerkleTreeCommitmentBatchCompat::new(merkle_root, nr_leaves),
+            total_stake,
+        }
+    }
+
+    /// Get the root of the merkle tree commitment.
+    pub fn get_merkle_root(&self) -> &[u8] {
+        &self.mt_commitment.root
+    }
+
+    /// Get the number of leaves of the merkle tree commitment.
+    pub fn get_nr_leaves(&self) -> usize {
+        self.mt_commitment.get_nr_leaves()
+    }
+
+    /// Get the total stake of the system.
+    pub fn get_total_stake(&self) -> Stake {
+        self.total_stake
+    }
+}
+
 /// Signature with its registered party.
 #[derive(Debug, Clone, Hash, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
 pub struct StmSigRegParty {
@@ -321,6 +351,14 @@ impl StmInitializer {
         self.pk
     }
 
+    /// Sign a message with the signing key, outside of the STM protocol.
+    ///
+    /// The signature is verified with the verification key of the initializer. It is not an STM
+    /// signature: the message must not be a message signed by the protocol.
+    pub fn sign_message(&self, msg: &[u8]) -> StmMessageSignature {
+        self.sk.sign(msg)
+    }
+
     /// Build the `avk` for the given list of parties.
     ///
     /// Note that if this StmInitializer was modified *between* the last call to `register`,
@@ -955,6 +993,8 @@ impl CoreVerifier {
     /// In case of conflict (having several signatures for the same index)
     /// it selects the smallest signature (i.e. takes the signature with the smallest scalar).
     /// The function selects at least `self.k` indexes.
+    ///
+    /// The signatures are verified in parallel using the current rayon thread pool.
     ///  # Error
     /// If there is no sufficient signatures, then the function fails.
     // todo: We need to agree on a criteria to dedup (by default we use a BTreeMap that guarantees keys order)
@@ -968,20 +1008,24 @@ impl CoreVerifier {
         let mut sig_by_index: BTreeMap<Index, &StmSigRegParty> = BTreeMap::new();
         let mut removal_idx_by_vk: HashMap<&StmSigRegParty, Vec<Index>> = HashMap::new();
 
-        for sig_reg in sigs.iter() {
-            if sig_reg
-                .sig
-                .verify_core(
-                    params,
-                    &sig_reg.reg_party.0,
-                    &sig_reg.reg_party.1,
-                    msg,
-                    total_stake,
-                )
-                .is_err()
-            {
-                continue;
-            }
+        // Order of the valid signatures is preserved so the deduplication stays deterministic.
+        let valid_sigs: Vec<&StmSigRegParty> = sigs
+            .par_iter()
+            .filter(|sig_reg| {
+                sig_reg
+                    .sig
+                    .verify_core(
+                        params,
+                        &sig_reg.reg_party.0,
+                        &sig_reg.reg_party.1,
+                        msg,
+                        total_stake,
+                    )
+                    .is_ok()
+            })
+            .collect();
+
+        for sig_reg in valid_sigs {
             for index in sig_reg.sig.indexes.iter() {
                 let mut insert_this_sig = false;
                 if let Some(&previous_sig) = sig_by_index.get(index) {
@@ -1200,6 +1244,51 @@ mod tests {
         sigs
     }
 
+    #[test]
+    fn test_avk_rebuilt_from_its_parts() {
+        let params = StmParameters {
+            m: 10,
+            k: 5,
+            phi_f: 0.2,
+        };
+        let ps = setup_parties(params, vec![1, 2, 3]);
+        let avk = StmClerk::from_signer(&ps[0]).compute_avk();
+
+        let rebuilt = StmAggrVerificationKey::<D>::from_parts(
+            avk.get_merkle_root().to_vec(),
+            avk.get_nr_leaves(),
+            avk.get_total_stake(),
+        );
+
+        assert_eq!(3, avk.get_nr_leaves());
+        assert_eq!(6, avk.get_total_stake());
+        assert_eq!(avk, rebuilt);
+    }
+
+    #[test]
+    fn test_message_signed_by_the_initializer() {
+        let params = StmParameters {
+            m: 10,
+            k: 5,
+            phi_f: 0.2,
+        };
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let initializer = StmInitializer::setup(params, 1, &mut rng);
+        let other_initializer = StmInitializer::setup(params, 1, &mut rng);
+
+        let signature = initializer.sign_message(b"message");
+
+        assert!(signature
+            .verify(b"message", &initializer.verification_key().vk)
+            .is_ok());
+        assert!(signature
+            .verify(b"another message", &initializer.verification_key().vk)
+            .is_err());
+        assert!(signature
+            .verify(b"message", &other_initializer.verification_key().vk)
+            .is_err());
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(50))]
 
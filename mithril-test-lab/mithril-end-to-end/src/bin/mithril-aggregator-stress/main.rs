@@ -0,0 +1,247 @@
+use anyhow::{anyhow, Context};
+use clap::Parser;
+use reqwest::StatusCode;
+use slog::Level;
+use slog_scope::{info, warn};
+use std::{sync::Arc, time::Duration};
+
+use mithril_common::{
+    entities::{ProtocolMessage, ProtocolMessagePartKey, SingleSignatures},
+    messages::{CertificatePendingMessage, EpochSettingsMessage, RegisterSignerMessage},
+    test_utils::MithrilFixture,
+    StdResult,
+};
+
+use mithril_end_to_end::stress_test::{load_report, payload_builder};
+
+/// Load generator simulating signers that register and send their single signatures to a running
+/// aggregator, reporting the latencies and the statuses of the responses.
+///
+/// The signers are generated by the tool: their registrations and signatures are accepted only
+/// if the aggregator stake distribution is the one of the generated signers, otherwise the
+/// rejected requests are reported as failures.
+#[derive(Debug, Parser)]
+#[command(author, version, about, long_about = None)]
+struct StressOpts {
+    /// Endpoint of the aggregator under load
+    #[arg(long, env = "AGGREGATOR_ENDPOINT")]
+    aggregator_endpoint: String,
+
+    /// Number of simulated signers
+    #[arg(long, default_value = "100")]
+    num_signers: usize,
+
+    /// Maximum number of signer registrations sent per second
+    #[arg(long, default_value = "50")]
+    registrations_per_second: f64,
+
+    /// Maximum number of single signatures sent per second
+    #[arg(long, default_value = "50")]
+    signatures_per_second: f64,
+
+    /// Number of rounds of registrations and signatures
+    #[arg(long, default_value = "1")]
+    rounds: usize,
+
+    /// Delay between two rounds (in seconds)
+    #[arg(long, default_value = "10")]
+    round_interval: u64,
+
+    /// Maximum ratio of requests without an expected response before the run fails
+    #[arg(long, default_value = "0")]
+    max_error_rate: f64,
+
+    /// Log level
+    #[arg(short = 'v', action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+impl StressOpts {
+    fn log_level(&self) -> Level {
+        match self.verbose {
+            0 => Level::Error,
+            1 => Level::Warning,
+            2 => Level::Info,
+            3 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+
+    fn check(&self) -> StdResult<()> {
+        if self.registrations_per_second <= 0.0 || self.signatures_per_second <= 0.0 {
+            return Err(anyhow!("The rates of requests must be strictly positive"));
+        }
+        if !(0.0..=1.0).contains(&self.max_error_rate) {
+            return Err(anyhow!("The maximum error rate must be between 0 and 1"));
+        }
+
+        Ok(())
+    }
+}
+
+fn init_logger(opts: &StressOpts) -> slog_scope::GlobalLoggerGuard {
+    use slog::Drain;
+
+    let decorator = slog_term::TermDecorator::new().build();
+    let drain = slog_term::FullFormat::new(decorator).build().fuse();
+    let drain = slog_async::Async::new(drain).build().fuse();
+    let drain = slog::LevelFilter::new(drain, opts.log_level()).fuse();
+
+    slog_scope::set_global_logger(slog::Logger::root(Arc::new(drain), slog::o!()))
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(
+    http_client: &reqwest::Client,
+    url: &str,
+) -> StdResult<Option<T>> {
+    let response = http_client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("Could not reach '{url}'"))?;
+
+    match response.status() {
+        StatusCode::OK => {
+            Ok(Some(response.json::<T>().await.with_context(|| {
+                format!("Invalid response body from '{url}'")
+            })?))
+        }
+        StatusCode::NO_CONTENT => Ok(None),
+        status => Err(anyhow!(
+            "Unexpected status {status} from '{url}': {}",
+            response.text().await.unwrap_or_default()
+        )),
+    }
+}
+
+/// Sign the message of the pending certificate as the simulated signers would compute it if
+/// the aggregator stake distribution is the one of the generated signers.
+async fn compute_signatures(
+    signers_fixture: &MithrilFixture,
+    certificate_pending: &CertificatePendingMessage,
+) -> StdResult<Vec<SingleSignatures>> {
+    let signers_fixture = signers_fixture.clone();
+    let signed_entity_type = certificate_pending.signed_entity_type.clone();
+    let signatures = tokio::task::spawn_blocking(move || {
+        let mut message = ProtocolMessage::new();
+        message.set_message_part(
+            ProtocolMessagePartKey::NextAggregateVerificationKey,
+            signers_fixture.compute_and_encode_avk(),
+        );
+        info!("Signing the message of {signed_entity_type:?}");
+
+        signers_fixture.sign_all(&message)
+    })
+    .await?;
+
+    Ok(signatures)
+}
+
+#[tokio::main(flavor = "multi_thread")]
+async fn main() -> StdResult<()> {
+    let opts = StressOpts::parse();
+    opts.check()?;
+    let _logger_guard = init_logger(&opts);
+    info!(">> Starting aggregator stress test with options: {opts:?}");
+
+    let http_client = reqwest::Client::new();
+    let endpoint = opts.aggregator_endpoint.trim_end_matches('/');
+    let epoch_settings =
+        get_json::<EpochSettingsMessage>(&http_client, &format!("{endpoint}/epoch-settings"))
+            .await?
+            .ok_or_else(|| anyhow!("The aggregator did not return its epoch settings"))?;
+    info!(">> Aggregator is at epoch {}", epoch_settings.epoch);
+
+    info!(">> Generating {} signers", opts.num_signers);
+    let signers_fixture = payload_builder::generate_signer_data(
+        opts.num_signers,
+        epoch_settings.next_protocol_parameters,
+    );
+    let mut reports = vec![];
+
+    for round in 1..=opts.rounds {
+        info!(
+            ">> Round {round}/{}: sending the signer registrations",
+            opts.rounds
+        );
+        // Without epoch the signers are registered to the currently opened registration round.
+        let register_messages = payload_builder::generate_register_signer_message(
+            &signers_fixture.signers(),
+            epoch_settings.epoch,
+        )
+        .into_iter()
+        .map(|message| RegisterSignerMessage {
+            epoch: None,
+            ..message
+        })
+        .collect();
+        reports.push(
+            load_report::post_at_rate(
+                "register-signer",
+                &http_client,
+                &format!("{endpoint}/register-signer"),
+                register_messages,
+                opts.registrations_per_second,
+                &[StatusCode::CREATED],
+            )
+            .await,
+        );
+
+        match get_json::<CertificatePendingMessage>(
+            &http_client,
+            &format!("{endpoint}/certificate-pending"),
+        )
+        .await?
+        {
+            Some(certificate_pending) => {
+                info!(
+                    ">> Round {round}/{}: sending the single signatures",
+                    opts.rounds
+                );
+                let signatures = compute_signatures(&signers_fixture, &certificate_pending).await?;
+                let register_messages = payload_builder::generate_register_signature_message(
+                    &signatures,
+                    certificate_pending.signed_entity_type,
+                );
+                reports.push(
+                    load_report::post_at_rate(
+                        "register-signatures",
+                        &http_client,
+                        &format!("{endpoint}/register-signatures"),
+                        register_messages,
+                        opts.signatures_per_second,
+                        // The certificate may be created before all the signatures are sent.
+                        &[StatusCode::CREATED, StatusCode::ACCEPTED, StatusCode::GONE],
+                    )
+                    .await,
+                );
+            }
+            None => warn!("No pending certificate, no single signatures sent in round {round}"),
+        }
+
+        if round < opts.rounds {
+            tokio::time::sleep(Duration::from_secs(opts.round_interval)).await;
+        }
+    }
+
+    info!(">> Display the report:");
+    load_report::print_reports(&reports);
+
+    let requests = reports.iter().map(|r| r.outcomes.len()).sum::<usize>();
+    let failures = reports.iter().map(|r| r.failures()).sum::<usize>();
+    let error_rate = if requests == 0 {
+        0.0
+    } else {
+        failures as f64 / requests as f64
+    };
+    if error_rate > opts.max_error_rate {
+        return Err(anyhow!(
+            "{failures} of the {requests} requests did not get an expected response, error rate {error_rate:.3} is above the maximum {}",
+            opts.max_error_rate
+        ));
+    }
+
+    info!(">> Stress test completed successfully");
+
+    Ok(())
+}
@@ -0,0 +1,300 @@
+use indicatif::{ProgressBar, ProgressDrawTarget};
+use reqwest::StatusCode;
+use serde::Serialize;
+use slog_scope::{debug, warn};
+use std::{collections::BTreeMap, fmt::Display, time::Duration};
+use tokio::{task::JoinSet, time::Instant};
+
+/// Outcome of a single request sent to the aggregator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestOutcome {
+    /// Time elapsed between the sending of the request and the reception of the response
+    pub latency: Duration,
+    /// HTTP status of the response, `None` if no response was received
+    pub status: Option<StatusCode>,
+    /// Is the status of the response one of the expected statuses
+    pub is_expected: bool,
+}
+
+/// Statistics computed on the latencies of a set of requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyStatistics {
+    pub min: Duration,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl LatencyStatistics {
+    /// Compute the statistics of the given latencies, `None` if there is no latency
+    pub fn compute(latencies: &[Duration]) -> Option<Self> {
+        if latencies.is_empty() {
+            return None;
+        }
+        let mut latencies = latencies.to_vec();
+        latencies.sort();
+        // Nearest-rank percentile
+        let percentile = |percent: usize| {
+            let rank = (percent * latencies.len()).div_ceil(100).max(1);
+            latencies[rank - 1]
+        };
+
+        Some(Self {
+            min: latencies[0],
+            mean: latencies.iter().sum::<Duration>() / latencies.len() as u32,
+            p50: percentile(50),
+            p95: percentile(95),
+            p99: percentile(99),
+            max: latencies[latencies.len() - 1],
+        })
+    }
+}
+
+/// Report of the requests sent to the aggregator for an operation
+#[derive(Debug, Clone)]
+pub struct OperationReport {
+    pub operation: String,
+    pub outcomes: Vec<RequestOutcome>,
+    pub elapsed: Duration,
+}
+
+impl OperationReport {
+    /// Number of requests that got an expected response
+    pub fn successes(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.is_expected).count()
+    }
+
+    /// Number of requests that did not get an expected response
+    pub fn failures(&self) -> usize {
+        self.outcomes.len() - self.successes()
+    }
+
+    /// Ratio of the requests that did not get an expected response
+    pub fn error_rate(&self) -> f64 {
+        if self.outcomes.is_empty() {
+            0.0
+        } else {
+            self.failures() as f64 / self.outcomes.len() as f64
+        }
+    }
+
+    /// Number of responses received for each HTTP status, `None` for the requests that did not
+    /// get a response
+    pub fn status_counts(&self) -> BTreeMap<Option<u16>, usize> {
+        let mut counts = BTreeMap::new();
+        for outcome in &self.outcomes {
+            *counts
+                .entry(outcome.status.map(|status| status.as_u16()))
+                .or_insert(0) += 1;
+        }
+
+        counts
+    }
+
+    /// Latency statistics of the requests
+    pub fn latency_statistics(&self) -> Option<LatencyStatistics> {
+        let latencies = self
+            .outcomes
+            .iter()
+            .map(|outcome| outcome.latency)
+            .collect::<Vec<_>>();
+
+        LatencyStatistics::compute(&latencies)
+    }
+
+    /// Number of requests per second actually sent
+    pub fn throughput(&self) -> f64 {
+        if self.elapsed.is_zero() {
+            0.0
+        } else {
+            self.outcomes.len() as f64 / self.elapsed.as_secs_f64()
+        }
+    }
+}
+
+impl Display for OperationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let statuses = self
+            .status_counts()
+            .into_iter()
+            .map(|(status, count)| match status {
+                Some(status) => format!("{status}={count}"),
+                None => format!("no_response={count}"),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{:.1}\t{}",
+            self.operation,
+            self.outcomes.len(),
+            self.successes(),
+            self.failures(),
+            self.throughput(),
+            statuses
+        )?;
+        match self.latency_statistics() {
+            Some(statistics) => write!(
+                f,
+                "\t{}\t{}\t{}\t{}\t{}\t{}",
+                statistics.min.as_millis(),
+                statistics.mean.as_millis(),
+                statistics.p50.as_millis(),
+                statistics.p95.as_millis(),
+                statistics.p99.as_millis(),
+                statistics.max.as_millis()
+            ),
+            None => write!(f, "\t-\t-\t-\t-\t-\t-"),
+        }
+    }
+}
+
+/// Print the given reports as a tab separated table
+pub fn print_reports(reports: &[OperationReport]) {
+    println!("operation\trequests\tsuccesses\tfailures\treq/s\tstatuses\tmin/ms\tmean/ms\tp50/ms\tp95/ms\tp99/ms\tmax/ms");
+    for report in reports {
+        println!("{report}");
+    }
+}
+
+/// Post the given payloads to the given url, starting at most `requests_per_second` requests
+/// each second, and record the outcome of each request.
+///
+/// The responses whose status is not in the `expected_statuses` are counted as failures.
+pub async fn post_at_rate<T: Serialize + Send + 'static>(
+    operation: &str,
+    http_client: &reqwest::Client,
+    url: &str,
+    payloads: Vec<T>,
+    requests_per_second: f64,
+    expected_statuses: &[StatusCode],
+) -> OperationReport {
+    let progress_bar =
+        ProgressBar::with_draw_target(Some(payloads.len() as u64), ProgressDrawTarget::stdout());
+    let mut ticker = tokio::time::interval(Duration::from_secs_f64(1.0 / requests_per_second));
+    let mut join_set = JoinSet::new();
+    let started_at = Instant::now();
+
+    for payload in payloads {
+        ticker.tick().await;
+        let http_request = http_client.post(url).json(&payload);
+        let expected_statuses = expected_statuses.to_vec();
+        join_set.spawn(async move {
+            let sent_at = Instant::now();
+            let response = http_request.send().await;
+            let latency = sent_at.elapsed();
+
+            match response {
+                Ok(response) => {
+                    let status = response.status();
+                    let is_expected = expected_statuses.contains(&status);
+                    if !is_expected {
+                        debug!(
+                            "Unexpected response status {status}";
+                            "body" => response.text().await.unwrap_or_default()
+                        );
+                    }
+
+                    RequestOutcome {
+                        latency,
+                        status: Some(status),
+                        is_expected,
+                    }
+                }
+                Err(error) => {
+                    debug!("Request failed: {error:?}");
+                    RequestOutcome {
+                        latency,
+                        status: None,
+                        is_expected: false,
+                    }
+                }
+            }
+        });
+    }
+
+    let mut outcomes = vec![];
+    while let Some(outcome) = join_set.join_next().await {
+        progress_bar.inc(1);
+        match outcome {
+            Ok(outcome) => outcomes.push(outcome),
+            Err(error) => warn!("Tokio task join failed: {error:?}"),
+        }
+    }
+    progress_bar.finish();
+
+    OperationReport {
+        operation: operation.to_string(),
+        outcomes,
+        elapsed: started_at.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(latency_ms: u64, status: u16, is_expected: bool) -> RequestOutcome {
+        RequestOutcome {
+            latency: Duration::from_millis(latency_ms),
+            status: Some(StatusCode::from_u16(status).unwrap()),
+            is_expected,
+        }
+    }
+
+    #[test]
+    fn latency_statistics_of_no_latency() {
+        assert_eq!(None, LatencyStatistics::compute(&[]));
+    }
+
+    #[test]
+    fn latency_statistics_use_nearest_rank_percentiles() {
+        let latencies = (1..=100)
+            .rev()
+            .map(Duration::from_millis)
+            .collect::<Vec<_>>();
+
+        let statistics = LatencyStatistics::compute(&latencies).unwrap();
+
+        assert_eq!(
+            LatencyStatistics {
+                min: Duration::from_millis(1),
+                mean: Duration::from_micros(50_500),
+                p50: Duration::from_millis(50),
+                p95: Duration::from_millis(95),
+                p99: Duration::from_millis(99),
+                max: Duration::from_millis(100),
+            },
+            statistics
+        );
+    }
+
+    #[test]
+    fn operation_report_counts_successes_failures_and_statuses() {
+        let report = OperationReport {
+            operation: "register".to_string(),
+            outcomes: vec![
+                outcome(10, 201, true),
+                outcome(20, 201, true),
+                outcome(30, 400, false),
+                RequestOutcome {
+                    latency: Duration::from_millis(40),
+                    status: None,
+                    is_expected: false,
+                },
+            ],
+            elapsed: Duration::from_secs(2),
+        };
+
+        assert_eq!(2, report.successes());
+        assert_eq!(2, report.failures());
+        assert_eq!(0.5, report.error_rate());
+        assert_eq!(2.0, report.throughput());
+        assert_eq!(
+            BTreeMap::from([(None, 1), (Some(201), 2), (Some(400), 1)]),
+            report.status_counts()
+        );
+    }
+}
@@ -5,5 +5,6 @@ pub mod entities;
 pub mod fake_chain;
 pub mod fake_client;
 pub mod fake_signer;
+pub mod load_report;
 pub mod payload_builder;
 pub mod wait;
@@ -42,6 +42,8 @@ pub fn generate_register_signer_message(
                 .operational_certificate
                 .map(|o| o.to_json_hex().unwrap()),
             kes_period: signer.kes_period,
+            node_metadata: None,
+            next_era_registration: None,
         })
         .collect::<Vec<_>>()
 }
@@ -58,6 +60,11 @@ pub fn generate_register_signature_message(
             party_id: s.party_id.clone(),
             signature: s.signature.clone().to_json_hex().unwrap(),
             won_indexes: s.won_indexes.clone(),
+            era: s.era,
+            nonce_signature: s
+                .nonce_signature
+                .as_ref()
+                .map(|nonce_signature| nonce_signature.to_json_hex().unwrap()),
         })
         .collect::<Vec<_>>()
 }